@@ -0,0 +1,34 @@
+//! Instanced debug boxes.
+//!
+//! The mesh is a unit wire cube with corners at ±1. Each instance carries a
+//! box center, its half extents and a color; the cube is scaled and moved
+//! into place in world space and drawn in that flat color.
+
+#import bevy_pbr::view_transformations::position_world_to_clip
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    // Locations 1-2 are left to the mesh pipeline's normal and UV attributes
+    @location(3) center: vec4<f32>,
+    @location(4) half_extents: vec4<f32>,
+    @location(5) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    let world_position = vertex.position * vertex.half_extents.xyz + vertex.center.xyz;
+    var out: VertexOutput;
+    out.clip_position = position_world_to_clip(world_position);
+    out.color = vertex.color;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
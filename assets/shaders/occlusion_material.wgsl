@@ -9,11 +9,18 @@
 //! - RegionBased: Hide voxels within detected interior region bounds
 //! - Hybrid: Use region detection when inside, shader-based when outside
 //!
+//! While an interior region is active, RegionBased and Hybrid also cut away
+//! geometry between the camera and the player (the interior cutaway).
+//!
+//! It also marks voxel edges, as the map's `edges` section asks: dark lines
+//! along them, or bevels that tilt the lighting normal towards them.
+//!
 //! Uses pbr_input_from_standard_material for proper PBR lighting with shadows.
 
 #import bevy_pbr::{
     pbr_fragment::pbr_input_from_standard_material,
     pbr_functions::alpha_discard,
+    pbr_types::PbrInput,
 }
 
 #ifdef PREPASS_PIPELINE
@@ -44,7 +51,8 @@ struct OcclusionUniforms {
     mode: u32,
     _padding3: u32,
     _padding4: u32,
-    // Interior region bounds (xyz = bounds, region_max.w = is_active)
+    // Interior region bounds (xyz = bounds, region_max.w = is_active,
+    // region_min.w = interior cutaway enabled)
     region_min: vec4<f32>,
     region_max: vec4<f32>,
 }
@@ -52,6 +60,20 @@ struct OcclusionUniforms {
 @group(#{MATERIAL_BIND_GROUP}) @binding(100)
 var<uniform> occlusion: OcclusionUniforms;
 
+// Edge rendering from the map (binding 101)
+struct EdgeUniforms {
+    // 0 = None, 1 = Lines, 2 = Bevel
+    style: u32,
+    // Line darkness or bevel strength (0.0 to 1.0)
+    strength: f32,
+    // Width of the edge band as a fraction of a voxel
+    width: f32,
+    _padding: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(101)
+var<uniform> edges: EdgeUniforms;
+
 // Bayer matrix for 4x4 ordered dithering
 // This creates a screen-door transparency effect without alpha blending
 const BAYER_MATRIX: array<f32, 16> = array<f32, 16>(
@@ -150,6 +172,46 @@ fn dither_check(screen_pos: vec2<f32>, alpha: f32) -> bool {
     return alpha > threshold;
 }
 
+// Geometry at or below this distance under the player's center is never cut
+// away, so the floor the player stands on stays visible.
+const CUTAWAY_FLOOR_CLEARANCE: f32 = 0.25;
+
+// Alpha for the interior cutaway: geometry near the camera-player segment
+// (between the two) fades out while the player is indoors.
+fn calculate_cutaway_alpha(world_pos: vec3<f32>) -> f32 {
+    if occlusion.region_max.w < 0.5 || occlusion.region_min.w < 0.5 {
+        return 1.0;
+    }
+    if world_pos.y <= occlusion.player_position.y - CUTAWAY_FLOOR_CLEARANCE {
+        return 1.0;
+    }
+
+    let segment = occlusion.player_position - occlusion.camera_position;
+    let length_sq = dot(segment, segment);
+    if length_sq < 0.0001 {
+        return 1.0;
+    }
+
+    // Only geometry strictly between the camera and the player
+    let t = dot(world_pos - occlusion.camera_position, segment) / length_sq;
+    if t <= 0.0 || t >= 1.0 {
+        return 1.0;
+    }
+
+    let distance = length(world_pos - (occlusion.camera_position + segment * t));
+    if distance >= occlusion.occlusion_radius {
+        return 1.0;
+    }
+
+    let edge_softness = 0.5;
+    let soft_distance = smoothstep(
+        occlusion.occlusion_radius - edge_softness,
+        occlusion.occlusion_radius,
+        distance
+    );
+    return mix(occlusion.min_alpha, 1.0, soft_distance);
+}
+
 // Check if fragment is inside the interior region bounds
 // Uses a small inset to avoid z-fighting at boundaries
 fn in_interior_region(world_pos: vec3<f32>) -> bool {
@@ -169,6 +231,53 @@ fn in_interior_region(world_pos: vec3<f32>) -> bool {
     // Note: No upper Y check - we want to hide everything above ceiling_y
 }
 
+// Position of a fragment within its voxel, from -1 to 1 on each axis the face
+// lies along, and 0 on the axis the face looks along. Voxels are centered on
+// whole coordinates, so their edges are at ±1.
+fn voxel_face_offset(world_pos: vec3<f32>, normal: vec3<f32>) -> vec3<f32> {
+    let offset = (world_pos - round(world_pos)) * 2.0;
+    let n = abs(normal);
+    if n.x >= n.y && n.x >= n.z {
+        return vec3<f32>(0.0, offset.y, offset.z);
+    }
+    if n.y >= n.z {
+        return vec3<f32>(offset.x, 0.0, offset.z);
+    }
+    return vec3<f32>(offset.x, offset.y, 0.0);
+}
+
+// Darken or bevel the fragment near voxel edges
+fn apply_edges(pbr_input: ptr<function, PbrInput>, world_pos: vec3<f32>) {
+    if edges.style == 0u {
+        return;
+    }
+    let offset = voxel_face_offset(world_pos, (*pbr_input).world_normal);
+    // Where the edge band starts, in offset units
+    let inner = 1.0 - 2.0 * edges.width;
+
+    if edges.style == 1u {
+        // Lines: a dark band, antialiased over a pixel
+        let edge = max(max(abs(offset.x), abs(offset.y)), abs(offset.z));
+        let aa = max(fwidth(edge), 0.0001);
+        let line = smoothstep(inner - aa, inner + aa, edge);
+        let shade = 1.0 - edges.strength * line;
+        (*pbr_input).material.base_color = vec4<f32>(
+            (*pbr_input).material.base_color.rgb * shade,
+            (*pbr_input).material.base_color.a,
+        );
+    } else {
+        // Bevel: tilt the normal outwards across the band, and shade it a little
+        let band = smoothstep(vec3<f32>(inner), vec3<f32>(1.0), abs(offset));
+        let tilt = sign(offset) * band;
+        (*pbr_input).N = normalize((*pbr_input).N + tilt * edges.strength * 2.0);
+        let shade = 1.0 - 0.3 * edges.strength * max(max(band.x, band.y), band.z);
+        (*pbr_input).material.base_color = vec4<f32>(
+            (*pbr_input).material.base_color.rgb * shade,
+            (*pbr_input).material.base_color.a,
+        );
+    }
+}
+
 @fragment
 fn fragment(
     in: VertexOutput,
@@ -186,11 +295,19 @@ fn fragment(
     
     // Generate PbrInput from StandardMaterial bindings (includes all shadow data)
     var pbr_input = pbr_input_from_standard_material(in, is_front);
+    apply_edges(&pbr_input, world_pos);
     
     // Apply shader-based occlusion ONLY for mode 1 (ShaderBased)
-    // Mode 3 (Hybrid) fallback is temporarily disabled
+    // Mode 3 (Hybrid) fallback is temporarily disabled; modes 2 and 3 use the
+    // interior cutaway instead
+    var occlusion_alpha = 1.0;
     if occlusion.mode == 1u {
-        let occlusion_alpha = calculate_occlusion_alpha(world_pos);
+        occlusion_alpha = calculate_occlusion_alpha(world_pos);
+    } else if occlusion.mode == 2u || occlusion.mode == 3u {
+        occlusion_alpha = calculate_cutaway_alpha(world_pos);
+    }
+
+    if occlusion.mode != 0u {
         let final_alpha = pbr_input.material.base_color.a * occlusion_alpha;
         
         if final_alpha < 0.01 {
@@ -47,6 +47,31 @@ var<uniform> occlusion: OcclusionUniforms;
 
 const XZ_MARGIN_FACTOR: f32 = 2.0;
 
+// Must match CUTAWAY_FLOOR_CLEARANCE in occlusion_material.wgsl
+const CUTAWAY_FLOOR_CLEARANCE: f32 = 0.25;
+
+// Interior cutaway zone: near the camera-player segment, between the two,
+// above the player's floor (region_min.w = cutaway enabled).
+fn in_cutaway_zone(world_pos: vec3<f32>) -> bool {
+    if occlusion.region_max.w < 0.5 || occlusion.region_min.w < 0.5 {
+        return false;
+    }
+    if world_pos.y <= occlusion.player_position.y - CUTAWAY_FLOOR_CLEARANCE {
+        return false;
+    }
+    let segment = occlusion.player_position - occlusion.camera_position;
+    let length_sq = dot(segment, segment);
+    if length_sq < 0.0001 {
+        return false;
+    }
+    let t = dot(world_pos - occlusion.camera_position, segment) / length_sq;
+    if t <= 0.0 || t >= 1.0 {
+        return false;
+    }
+    let distance = length(world_pos - (occlusion.camera_position + segment * t));
+    return distance < occlusion.occlusion_radius;
+}
+
 fn in_interior_region(world_pos: vec3<f32>) -> bool {
     if occlusion.region_max.w < 0.5 {
         return false;
@@ -82,7 +107,7 @@ fn fragment(in: VertexOutput) {
     if !is_shadow_pass {
         // Region-based discard — hide interior regions entirely.
         if occlusion.mode == 2u || occlusion.mode == 3u {
-            if in_interior_region(world_pos) {
+            if in_interior_region(world_pos) || in_cutaway_zone(world_pos) {
                 discard;
             }
         }
@@ -0,0 +1,30 @@
+//! Map file parsing, binary decoding and validation on generated stress maps.
+
+use adrakestory::systems::game::map::format::MapData;
+use adrakestory::systems::game::map::loader::binary::{decode_binary, encode_binary};
+use adrakestory::systems::game::map::validation::validate_map;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn map_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_parsing");
+    group.sample_size(10);
+    for size in [32, 96] {
+        let map = MapData::stress_map(size, 1);
+        let ron_text = ron::ser::to_string_pretty(&map, ron::ser::PrettyConfig::default()).unwrap();
+        let binary = encode_binary(&map).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("ron", size), &ron_text, |b, text| {
+            b.iter(|| ron::from_str::<MapData>(black_box(text)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("binary", size), &binary, |b, bytes| {
+            b.iter(|| decode_binary(black_box(bytes)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("validate", size), &map, |b, map| {
+            b.iter(|| validate_map(black_box(map)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, map_parsing);
+criterion_main!(benches);
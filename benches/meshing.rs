@@ -0,0 +1,93 @@
+//! Greedy meshing throughput on synthetic chunk patterns.
+//!
+//! Each pattern is a 64³ sub-voxel region (8³ voxels) run through
+//! the same occupancy → face culling → greedy merge pipeline as the chunk
+//! spawner. Compare before/after numbers when changing the mesher.
+
+use adrakestory::systems::game::map::spawner::{
+    ChunkMeshBuilder, Face, GreedyMesher, OccupancyGrid, VoxelMaterialPalette,
+};
+use bevy::prelude::Color;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Sub-voxels along one edge of the meshed region.
+const REGION: i32 = 64;
+
+const FACES: [Face; 6] = [
+    Face::PosX,
+    Face::NegX,
+    Face::PosY,
+    Face::NegY,
+    Face::PosZ,
+    Face::NegZ,
+];
+
+/// Fully solid region: best case, every face merges.
+fn solid() -> Vec<(i32, i32, i32)> {
+    let n = REGION;
+    (0..n)
+        .flat_map(|x| (0..n).flat_map(move |y| (0..n).map(move |z| (x, y, z))))
+        .collect()
+}
+
+/// 3D checkerboard of a 32³ block: worst case, nothing merges.
+fn checkerboard() -> Vec<(i32, i32, i32)> {
+    let n = 32;
+    (0..n)
+        .flat_map(|x| (0..n).flat_map(move |y| (0..n).map(move |z| (x, y, z))))
+        .filter(|(x, y, z)| (x + y + z) % 2 == 0)
+        .collect()
+}
+
+/// Rolling heightmap terrain: the typical outdoor case.
+fn terrain() -> Vec<(i32, i32, i32)> {
+    let n = REGION;
+    (0..n)
+        .flat_map(|x| (0..n).map(move |z| (x, z)))
+        .flat_map(|(x, z)| {
+            let height = 24.0 + (x as f32 * 0.2).sin() * 8.0 + (z as f32 * 0.14).cos() * 8.0;
+            (0..height as i32).map(move |y| (x, y, z))
+        })
+        .collect()
+}
+
+/// Mesh sub-voxels (global sub-voxel coordinates) the way the chunk spawner does.
+fn mesh(sub_voxels: &[(i32, i32, i32)]) -> usize {
+    let mut occupancy = OccupancyGrid::new();
+    for &(x, y, z) in sub_voxels {
+        occupancy.insert(0, 0, 0, x, y, z);
+    }
+
+    let mut mesher = GreedyMesher::default();
+    for &(x, y, z) in sub_voxels {
+        let color_index = VoxelMaterialPalette::get_material_index(0, 0, 0, x, y, z) % 4;
+        let color = Color::srgb(0.2 * color_index as f32, 0.5, 0.3);
+        for face in FACES {
+            if face == Face::PosY || !occupancy.has_neighbor(0, 0, 0, x, y, z, face) {
+                mesher.add_face(x, y, z, face, color_index, color);
+            }
+        }
+    }
+
+    let mut builder = ChunkMeshBuilder::default();
+    mesher.build_into(&mut builder);
+    builder.quad_count()
+}
+
+fn greedy_meshing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("greedy_meshing");
+    group.sample_size(10);
+    for (name, sub_voxels) in [
+        ("solid", solid()),
+        ("checkerboard", checkerboard()),
+        ("terrain", terrain()),
+    ] {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &sub_voxels, |b, s| {
+            b.iter(|| mesh(black_box(s)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, greedy_meshing);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! OccupancyGrid insertion and neighbor query cost.
+
+use adrakestory::systems::game::map::spawner::{Face, OccupancyGrid};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Sub-voxels along one edge of the benchmark region.
+const REGION: i32 = 64;
+
+const FACES: [Face; 6] = [
+    Face::PosX,
+    Face::NegX,
+    Face::PosY,
+    Face::NegY,
+    Face::PosZ,
+    Face::NegZ,
+];
+
+/// Half-filled region (lower half solid), so queries hit both present and missing cells.
+fn positions() -> Vec<(i32, i32, i32)> {
+    (0..REGION)
+        .flat_map(|x| (0..REGION / 2).flat_map(move |y| (0..REGION).map(move |z| (x, y, z))))
+        .collect()
+}
+
+fn occupancy(c: &mut Criterion) {
+    let positions = positions();
+    let mut grid = OccupancyGrid::new();
+    for &(x, y, z) in &positions {
+        grid.insert(0, 0, 0, x, y, z);
+    }
+
+    let mut group = c.benchmark_group("occupancy");
+    group.sample_size(20);
+    group.bench_function("insert", |b| {
+        b.iter(|| {
+            let mut grid = OccupancyGrid::new();
+            for &(x, y, z) in black_box(&positions) {
+                grid.insert(0, 0, 0, x, y, z);
+            }
+            grid
+        })
+    });
+    group.bench_function("neighbor_queries", |b| {
+        b.iter(|| {
+            let mut hidden = 0usize;
+            for &(x, y, z) in &positions {
+                for face in FACES {
+                    hidden += grid.has_neighbor(0, 0, 0, x, y, z, black_box(face)) as usize;
+                }
+            }
+            hidden
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, occupancy);
+criterion_main!(benches);
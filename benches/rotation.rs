@@ -0,0 +1,67 @@
+//! Orientation matrix math and rotated pattern geometry.
+
+use adrakestory::systems::game::map::format::{
+    apply_orientation_matrix, axis_angle_to_matrix, multiply_matrices, OrientationMatrix,
+    SubVoxelPattern, IDENTITY,
+};
+use adrakestory::systems::game::map::geometry::RotationAxis;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// All 24 distinct orientations, built by composing single-axis rotations.
+fn all_orientations() -> Vec<OrientationMatrix> {
+    let mut orientations = vec![IDENTITY];
+    let mut frontier = vec![IDENTITY];
+    while let Some(current) = frontier.pop() {
+        for axis in [RotationAxis::X, RotationAxis::Y, RotationAxis::Z] {
+            let next = multiply_matrices(&axis_angle_to_matrix(axis, 1), &current);
+            if !orientations.contains(&next) {
+                orientations.push(next);
+                frontier.push(next);
+            }
+        }
+    }
+    orientations
+}
+
+fn rotation(c: &mut Criterion) {
+    let orientations = all_orientations();
+    assert_eq!(orientations.len(), 24);
+
+    let mut group = c.benchmark_group("rotation");
+    group.bench_function("axis_angle_to_matrix", |b| {
+        b.iter(|| {
+            for axis in [RotationAxis::X, RotationAxis::Y, RotationAxis::Z] {
+                for angle in 0..4 {
+                    black_box(axis_angle_to_matrix(black_box(axis), black_box(angle)));
+                }
+            }
+        })
+    });
+    group.bench_function("multiply_matrices", |b| {
+        b.iter(|| {
+            orientations
+                .iter()
+                .fold(IDENTITY, |acc, m| multiply_matrices(black_box(m), &acc))
+        })
+    });
+    for (name, pattern) in [
+        ("staircase", SubVoxelPattern::Staircase),
+        ("full", SubVoxelPattern::Full),
+    ] {
+        let geometry = pattern.geometry();
+        group.bench_function(format!("apply_orientation/{name}"), |b| {
+            b.iter(|| {
+                for matrix in &orientations {
+                    black_box(apply_orientation_matrix(
+                        geometry.clone(),
+                        black_box(matrix),
+                    ));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, rotation);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+//! Write a synthetic stress map to disk.
+//!
+//! ```text
+//! cargo run --release --example stress_map -- [size] [seed] [output]
+//! ```
+//!
+//! Defaults to a 128×128 map with seed 1 written to
+//! `assets/maps/stress_128.ron`. Load it with `cargo run --release -- --map <output>`.
+
+use adrakestory::systems::game::map::format::MapData;
+use adrakestory::systems::game::map::loader::MapLoader;
+use std::path::PathBuf;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let size: i32 = args
+        .next()
+        .map_or(128, |s| s.parse().expect("size must be an integer"));
+    let seed: u64 = args
+        .next()
+        .map_or(1, |s| s.parse().expect("seed must be an integer"));
+    let output = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("assets/maps/stress_{size}.ron")));
+
+    let map = MapData::stress_map(size, seed);
+    MapLoader::save_to_file(&map, &output).expect("failed to write map");
+    println!(
+        "Wrote {} voxels ({size}×{size}, seed {seed}) to {}",
+        map.world.voxels.len(),
+        output.display()
+    );
+}
@@ -0,0 +1,237 @@
+//! Command-line modes that run without opening the editor window.
+//!
+//! ```text
+//! map_editor validate <map>...
+//! map_editor convert <input> <output>
+//! map_editor stats <map>
+//! map_editor optimize <input> [output]
+//! map_editor diff <base> <other>      (also: --diff <base> <other>)
+//! ```
+//!
+//! Without a command the editor window opens; `--record-input` and
+//! `--play-input` record or replay its input (see
+//! `adrakestory::editor::input_recording`).
+//!
+//! Maps are read as RON or binary, plain or compressed (detected by header),
+//! and written as binary when the output ends in `.bin`, RON otherwise; a
+//! further `.gz` or `.zst` compresses the output (`forest.ron.zst`). Exit
+//! codes: 0 success, 1 invalid map or maps differ, 2 usage or I/O error.
+
+use adrakestory::editor::diff::diff_maps;
+use adrakestory::editor::input_recording::{InputRecording, InputRecordingPlugin};
+use adrakestory::editor::map_tools::{optimize_map, MapStats};
+use adrakestory::systems::game::map::format::MapData;
+use adrakestory::systems::game::map::loader::MapLoader;
+use adrakestory::systems::game::map::validation::validate_map;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage:
+  map_editor                              Open the editor
+  map_editor validate <map>...            Check maps, exit 1 if any is invalid
+  map_editor convert <input> <output>     Convert between RON and binary (.bin),
+                                          compressed with .gz or .zst
+  map_editor stats <map>                  Print map contents summary
+  map_editor optimize <input> [output]    Remove duplicate voxels and unused data
+  map_editor diff <base> <other>          Print differences, exit 1 if any
+
+Editor options:
+  --record-input <file>                   Record keyboard, mouse and gamepad
+                                          input to <file> until exit
+  --play-input <file>                     Play back input recorded to <file>
+  --exit-after-playback                   Quit once the played back input ends";
+
+/// Exit code for an invalid map or differing maps.
+const EXIT_FAILURE: u8 = 1;
+/// Exit code for bad arguments or unreadable/unwritable files.
+const EXIT_ERROR: u8 = 2;
+
+/// Run the command named in `args`, if any.
+///
+/// Returns `None` when no command was given (only flags, if anything), so the
+/// editor window should open.
+pub fn run(args: &[String]) -> Option<ExitCode> {
+    let args: Vec<&str> = args.iter().skip(1).map(String::as_str).collect();
+
+    // `--diff` predates the subcommands and may appear anywhere
+    if let Some(index) = args.iter().position(|a| *a == "--diff") {
+        return Some(diff(&args[index + 1..]));
+    }
+
+    let (command, rest) = args.split_first()?;
+    let code = match *command {
+        "validate" => validate(rest),
+        "convert" => convert(rest),
+        "stats" => stats(rest),
+        "optimize" => optimize(rest),
+        "diff" => diff(rest),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        flag if flag.starts_with('-') => return None,
+        unknown => {
+            eprintln!("Unknown command '{unknown}'");
+            usage_error()
+        }
+    };
+    Some(code)
+}
+
+/// Set up input recording and playback from the editor's flags.
+///
+/// Returns the exit code when a recording to play back cannot be read.
+pub fn input_recording(args: &[String]) -> Result<InputRecordingPlugin, ExitCode> {
+    let mut plugin = InputRecordingPlugin::default();
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record-input" => match args.next() {
+                Some(path) => plugin.record = Some(PathBuf::from(path)),
+                None => eprintln!("Warning: --record-input requires a path argument"),
+            },
+            "--play-input" => match args.next() {
+                Some(path) => match InputRecording::load(path.as_ref()) {
+                    Ok(recording) => plugin.playback = Some(recording),
+                    Err(e) => {
+                        eprintln!("Failed to load input recording {path}: {e}");
+                        return Err(ExitCode::from(EXIT_ERROR));
+                    }
+                },
+                None => eprintln!("Warning: --play-input requires a path argument"),
+            },
+            "--exit-after-playback" => plugin.exit_when_done = true,
+            _ => {}
+        }
+    }
+    Ok(plugin)
+}
+
+fn usage_error() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(EXIT_ERROR)
+}
+
+fn validate(paths: &[&str]) -> ExitCode {
+    if paths.is_empty() {
+        return usage_error();
+    }
+
+    // An unreadable file outranks an invalid map
+    let mut code = 0;
+    for path in paths {
+        // Read without validating so parse errors and validation errors are
+        // reported separately
+        match MapLoader::read_unvalidated(path) {
+            Ok(map) => match validate_map(&map) {
+                Ok(()) => println!("ok      {path}"),
+                Err(e) => {
+                    println!("invalid {path}: {e}");
+                    code = code.max(EXIT_FAILURE);
+                }
+            },
+            Err(e) => {
+                eprintln!("error   {path}: {e}");
+                code = EXIT_ERROR;
+            }
+        }
+    }
+    ExitCode::from(code)
+}
+
+fn convert(args: &[&str]) -> ExitCode {
+    let [input, output] = args else {
+        return usage_error();
+    };
+    let Some(map) = load(input) else {
+        return ExitCode::from(EXIT_ERROR);
+    };
+    save(&map, output)
+}
+
+fn stats(args: &[&str]) -> ExitCode {
+    let [path] = args else {
+        return usage_error();
+    };
+    let Some(map) = load(path) else {
+        return ExitCode::from(EXIT_ERROR);
+    };
+    println!("{}", MapStats::from_map(&map));
+    ExitCode::SUCCESS
+}
+
+fn optimize(args: &[&str]) -> ExitCode {
+    let (input, output) = match args {
+        [input] => (*input, *input),
+        [input, output] => (*input, *output),
+        _ => return usage_error(),
+    };
+    // Duplicate voxels fail validation, so read without it and validate the result
+    let mut map = match MapLoader::read_unvalidated(input) {
+        Ok(map) => map,
+        Err(e) => {
+            eprintln!("Failed to load {input}: {e}");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    let report = optimize_map(&mut map);
+    println!("{report}");
+    if let Err(e) = validate_map(&map) {
+        eprintln!("{input} is still invalid after optimizing, not saved: {e}");
+        return ExitCode::from(EXIT_FAILURE);
+    }
+    if report.is_empty() && input == output {
+        return ExitCode::SUCCESS;
+    }
+    save(&map, output)
+}
+
+fn diff(args: &[&str]) -> ExitCode {
+    let [base_path, other_path, ..] = args else {
+        return usage_error();
+    };
+    let (Some(base), Some(other)) = (load(base_path), load(other_path)) else {
+        return ExitCode::from(EXIT_ERROR);
+    };
+
+    let diff = diff_maps(&base, &other);
+    println!("--- {base_path}\n+++ {other_path}");
+    println!("{}", diff.summary());
+    for change in &diff.voxels {
+        println!("{change}");
+    }
+    for change in &diff.entities {
+        println!("{change}");
+    }
+
+    if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_FAILURE)
+    }
+}
+
+/// Load and validate a map, printing the error on failure.
+fn load(path: &str) -> Option<MapData> {
+    MapLoader::load_simple(path)
+        .map_err(|e| eprintln!("Failed to load {path}: {e}"))
+        .ok()
+}
+
+/// Save `map` to `path` in the format its name asks for (see
+/// `MapLoader::encode_for_path`).
+fn save(map: &MapData, path: &str) -> ExitCode {
+    let result = MapLoader::save_to_file(map, path);
+    match result {
+        Ok(()) => {
+            println!("Wrote {path}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to write {path}: {e}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
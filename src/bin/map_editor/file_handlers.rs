@@ -3,7 +3,9 @@
 use adrakestory::editor::recent_files::{OpenRecentFileEvent, RecentFiles};
 use adrakestory::editor::state;
 use adrakestory::editor::ui::dialogs::MapDataChangedEvent;
+use adrakestory::editor::validation::set_load_diagnostics;
 use adrakestory::editor::EditorState;
+use adrakestory::systems::game::map::loader::MapLoader;
 use bevy::prelude::*;
 
 /// System to handle opening a recent file
@@ -18,33 +20,28 @@ pub fn handle_open_recent_file(
         info!("Opening recent file: {:?}", event.path);
 
         // Try to load the map
-        match std::fs::read_to_string(&event.path) {
-            Ok(contents) => {
-                match ron::from_str::<adrakestory::systems::game::map::format::MapData>(&contents) {
-                    Ok(map_data) => {
-                        info!("Successfully loaded map from: {:?}", event.path);
-                        editor_state.current_map = map_data;
-                        editor_state.file_path = Some(event.path.clone());
-                        editor_state.clear_modified();
-                        editor_state.mark_needs_render();
-                        editor_state.clear_selections();
+        match MapLoader::read_tolerant(&event.path) {
+            Ok((map_data, diagnostics)) => {
+                info!("Successfully loaded map from: {:?}", event.path);
+                editor_state.current_map = map_data;
+                editor_state.file_path = Some(event.path.clone());
+                editor_state.clear_modified();
+                editor_state.mark_needs_render();
+                editor_state.clear_selections();
+                for diagnostic in &diagnostics {
+                    warn!("{:?}: {}", event.path, diagnostic);
+                }
+                set_load_diagnostics(&mut editor_state, diagnostics);
 
-                        // Update recent files (moves to front)
-                        recent_files.add(event.path.clone());
+                // Update recent files (moves to front)
+                recent_files.add(event.path.clone());
 
-                        // Send event to trigger lighting update
-                        map_changed_events.write(MapDataChangedEvent);
-                    }
-                    Err(e) => {
-                        error!("Failed to parse map file: {}", e);
-                        ui_state.error_message = format!("Failed to parse map file:\n{}", e);
-                        ui_state.error_dialog_open = true;
-                    }
-                }
+                // Send event to trigger lighting update
+                map_changed_events.write(MapDataChangedEvent);
             }
             Err(e) => {
-                error!("Failed to read file: {}", e);
-                ui_state.error_message = format!("Failed to read file:\n{}", e);
+                error!("Failed to load map: {}", e);
+                ui_state.error_message = format!("Failed to load map:\n{}", e);
                 ui_state.error_dialog_open = true;
             }
         }
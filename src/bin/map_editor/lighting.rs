@@ -1,7 +1,10 @@
-//! Lighting system for map editor.
+//! Lighting, weather and edge preview systems for map editor.
 
+use adrakestory::editor::renderer::EditorChunkMaterial;
 use adrakestory::editor::ui::dialogs::MapDataChangedEvent;
 use adrakestory::editor::EditorState;
+use adrakestory::systems::game::occlusion::{EdgeUniforms, OcclusionMaterial};
+use adrakestory::systems::game::weather::{wet_tint, Weather};
 use bevy::light::CascadeShadowConfigBuilder;
 use bevy::prelude::*;
 
@@ -67,3 +70,49 @@ pub fn update_lighting_on_map_change(
         );
     }
 }
+
+/// System to preview the map's weather in the viewport whenever it changes
+pub fn sync_weather_preview(editor_state: Res<EditorState>, mut weather: ResMut<Weather>) {
+    let map_weather = &editor_state.current_map.weather;
+    if weather.base != *map_weather {
+        *weather = Weather::settled(map_weather.clone());
+    }
+}
+
+/// System to darken the editor's chunk material with the previewed wetness
+pub fn update_wetness_preview(
+    weather: Res<Weather>,
+    chunk_material: Option<Res<EditorChunkMaterial>>,
+    mut materials: ResMut<Assets<OcclusionMaterial>>,
+) {
+    let Some(chunk_material) = chunk_material else {
+        return;
+    };
+    if !weather.is_changed() && !chunk_material.is_added() {
+        return;
+    }
+    if let Some(material) = materials.get_mut(&chunk_material.0) {
+        material.base.base_color = wet_tint(weather.current.wetness);
+    }
+}
+
+/// System to preview the map's edge style in the viewport whenever it changes
+pub fn sync_edge_preview(
+    editor_state: Res<EditorState>,
+    chunk_material: Option<Res<EditorChunkMaterial>>,
+    mut materials: ResMut<Assets<OcclusionMaterial>>,
+) {
+    let Some(chunk_material) = chunk_material else {
+        return;
+    };
+    let edges = EdgeUniforms::from_data(&editor_state.current_map.edges);
+    let unchanged = materials
+        .get(&chunk_material.0)
+        .is_none_or(|material| material.extension.edge_uniforms == edges);
+    if unchanged {
+        return;
+    }
+    if let Some(material) = materials.get_mut(&chunk_material.0) {
+        material.extension.edge_uniforms = edges;
+    }
+}
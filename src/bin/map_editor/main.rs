@@ -2,24 +2,77 @@
 //!
 //! A standalone GUI application for creating and editing map files.
 
+mod cli;
 mod file_handlers;
 mod lighting;
 mod setup;
 mod status_bar;
 mod ui_system;
 
+use adrakestory::diagnostics::crash_report;
+use adrakestory::editor::autotile::{
+    autotile_new_edits, load_autotile_rules, render_autotile_window, update_autotile_preview,
+    AutotileSettings,
+};
+use adrakestory::editor::bookmarks::{handle_bookmark_events, BookmarkEvent};
+use adrakestory::editor::cinematics::{
+    draw_camera_sequence_path, render_camera_timeline, CameraTimeline,
+};
+use adrakestory::editor::clipboard::{
+    handle_copy_selection, handle_paste_clipboard, CopySelectionEvent, EditorClipboard,
+    PasteClipboardEvent,
+};
+use adrakestory::editor::collab::{
+    draw_collaborator_cursors, render_collab_window, run_collab_session, CollabState,
+};
+use adrakestory::editor::complexity::{draw_chunk_complexity, render_complexity_window};
+use adrakestory::editor::controller::{
+    handle_radial_menu, inject_gamepad_ui_navigation, render_radial_menu,
+    render_ui_navigation_hint, GamepadUiNavigation, RadialMenu,
+};
+use adrakestory::editor::documents::{handle_document_events, DocumentEvent, EditorDocuments};
+use adrakestory::editor::extensions::{
+    run_extension_commands, EditorExtensions, ExtensionCommandEvent,
+};
+use adrakestory::editor::framing::{
+    animate_camera_framing, frame_selection_on_key, handle_frame_selection, CameraFraming,
+    FrameSelectionEvent,
+};
+use adrakestory::editor::generator::{
+    draw_generator_preview, render_generator_window, StructureGenerator,
+};
+use adrakestory::editor::go_to::{handle_go_to, render_go_to_dialog, GoToDialog, GoToEvent};
+use adrakestory::editor::guides::{draw_construction_guides, render_guides_window, GuideSettings};
+use adrakestory::editor::jump_preview::draw_jump_preview;
+use adrakestory::editor::locks::{render_locks_window, update_locked_area_tint, LocksWindow};
 use adrakestory::editor::play::{
     handle_play_map, handle_stop_game, poll_game_process, PlayMapEvent, PlayTestState,
     StopGameEvent,
 };
+use adrakestory::editor::playtest_heatmap::{
+    draw_playtest_heatmap, load_playtest_log, PlaytestHeatmap,
+};
+use adrakestory::editor::prefabs::{
+    draw_prefab_preview, handle_prefab_dialogs, handle_prefab_placement, PrefabFiles,
+};
+use adrakestory::editor::preferences::{update_render_mode, EditorPreferences};
 use adrakestory::editor::recent_files::{OpenRecentFileEvent, RecentFiles};
+use adrakestory::editor::recovery::{render_recovery_dialog, track_unsaved_map, PendingRecovery};
+use adrakestory::editor::selection_boxes::update_selection_boxes;
+use adrakestory::editor::session::{track_map_session, EditorSessions};
 use adrakestory::editor::shortcuts::{handle_global_shortcuts, handle_redo, handle_undo};
+use adrakestory::editor::templates::{
+    render_new_map_dialog, render_save_template_dialog, MapTemplates,
+};
 use adrakestory::editor::tools::ActiveTransform;
 use adrakestory::editor::tools::DragSelectState;
 use adrakestory::editor::tools::VoxelDragState;
 use adrakestory::editor::tools::VoxelRemoveDragState;
 use adrakestory::editor::ui::dialogs::AppExitEvent;
-use adrakestory::editor::{camera, cursor, file_io, grid, renderer, state, tools, ui};
+use adrakestory::editor::validation::{open_validation_on_duplicates, render_validation_window};
+use adrakestory::editor::{
+    billboards, camera, cursor, file_io, grid, renderer, state, tools, ui, viewports,
+};
 use adrakestory::editor::{
     handle_keyboard_cursor_movement, handle_keyboard_selection, handle_play_shortcuts,
     handle_tool_switching, toggle_keyboard_edit_mode,
@@ -28,13 +81,37 @@ use adrakestory::editor::{
     CursorState, EditorHistory, EditorState, KeyboardEditMode, MapRenderState, RedoEvent,
     RenderMapEvent, UndoEvent,
 };
-use adrakestory::editor::{FileSavedEvent, SaveFileDialogReceiver, SaveMapAsEvent, SaveMapEvent};
+use adrakestory::editor::{
+    FileSavedEvent, MapSaveTask, SaveFileDialogReceiver, SaveMapAsEvent, SaveMapEvent,
+};
+use adrakestory::systems::game::debug_boxes::DebugBoxesPlugin;
+use adrakestory::systems::game::occlusion::OcclusionMaterial;
+use adrakestory::systems::game::resources::PhysicsConfig;
+use adrakestory::systems::game::weather::{self, Weather, WeatherDrops};
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::prelude::*;
 use bevy::window::WindowResolution;
-use bevy_egui::EguiPlugin;
+use bevy_egui::{EguiPlugin, EguiPreUpdateSet};
 use grid::InfiniteGridConfig;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(code) = cli::run(&args) {
+        return code;
+    }
+    let input_recording = match cli::input_recording(&args) {
+        Ok(plugin) => plugin,
+        Err(code) => return code,
+    };
+
+    // Write a crash report, and any unsaved map, to crashes/ on a panic
+    crash_report::install("map_editor");
+    let last_crash = crash_report::take_pending("map_editor");
+
+    // Redraw only on input unless continuous rendering is preferred
+    let preferences = EditorPreferences::load();
 
-fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -58,16 +135,32 @@ fn main() {
                 ..default()
             }
         })
+        // FPS readout in the status bar
+        .add_plugins(FrameTimeDiagnosticsPlugin::default())
+        // Chunk material shared with the game, for its voxel edges
+        .add_plugins(MaterialPlugin::<OcclusionMaterial>::default())
+        // --record-input / --play-input
+        .add_plugins(input_recording)
+        // Instanced boxes for selected entities and collision bounds
+        .add_plugins(DebugBoxesPlugin)
         .init_resource::<EditorState>()
         .init_resource::<CursorState>()
+        .init_resource::<cursor::VoxelIndex>()
         .init_resource::<EditorHistory>()
         .init_resource::<state::EditorUIState>()
         .init_resource::<state::ToolMemory>()
         .init_resource::<camera::CameraInputState>()
         .init_resource::<camera::GamepadCameraState>()
+        .init_resource::<GamepadUiNavigation>()
+        .init_resource::<RadialMenu>()
+        .init_resource::<viewports::ViewportLayout>()
         .init_resource::<ui::dialogs::FileDialogReceiver>()
+        .init_resource::<ui::dialogs::MapDiffReview>()
         .init_resource::<SaveFileDialogReceiver>()
+        .init_resource::<MapSaveTask>()
         .init_resource::<MapRenderState>()
+        .init_resource::<renderer::HazardBadges>()
+        .init_resource::<renderer::EditorTypeMaterials>()
         .init_resource::<InfiniteGridConfig>()
         .init_resource::<ActiveTransform>()
         .init_resource::<KeyboardEditMode>()
@@ -76,18 +169,58 @@ fn main() {
         .init_resource::<VoxelRemoveDragState>()
         .init_resource::<ui::OutlinerState>()
         .init_resource::<PlayTestState>()
+        .init_resource::<EditorDocuments>()
+        .init_resource::<CollabState>()
+        .init_resource::<EditorClipboard>()
+        .init_resource::<PrefabFiles>()
+        .init_resource::<StructureGenerator>()
+        .init_resource::<PlaytestHeatmap>()
+        .init_resource::<EditorExtensions>()
+        .init_resource::<GoToDialog>()
+        .init_resource::<CameraFraming>()
+        .init_resource::<GuideSettings>()
+        .init_resource::<LocksWindow>()
+        .init_resource::<MapTemplates>()
+        .init_resource::<CameraTimeline>()
+        .init_resource::<tools::PaintStroke>()
+        .init_resource::<AutotileSettings>()
+        .init_resource::<tools::SculptStroke>()
+        .init_resource::<tools::SplineToolState>()
+        .init_resource::<tools::StaircaseToolState>()
+        // Game physics, for the jump arc preview
+        .init_resource::<PhysicsConfig>()
+        // Weather preview (precipitation, fog and wetness)
+        .init_resource::<Weather>()
+        .init_resource::<WeatherDrops>()
         .insert_resource(RecentFiles::load()) // Load recent files from disk
+        .insert_resource(EditorSessions::load()) // Per-map camera, tool and grid settings
+        .insert_resource(PendingRecovery {
+            notice: last_crash,
+            error: None,
+        })
+        .insert_resource(preferences.winit_settings())
+        .insert_resource(preferences)
         .add_message::<ui::dialogs::FileSelectedEvent>()
         .add_message::<SaveMapEvent>()
         .add_message::<SaveMapAsEvent>()
         .add_message::<FileSavedEvent>()
         .add_message::<RenderMapEvent>()
+        .add_message::<renderer::RenderChunksEvent>()
         .add_message::<ui::dialogs::MapDataChangedEvent>()
         .add_message::<OpenRecentFileEvent>()
         .add_message::<PlayMapEvent>()
         .add_message::<StopGameEvent>()
         .add_message::<UndoEvent>()
         .add_message::<RedoEvent>()
+        .add_message::<DocumentEvent>()
+        .add_message::<BookmarkEvent>()
+        .add_message::<ExtensionCommandEvent>()
+        .add_message::<CopySelectionEvent>()
+        .add_message::<PasteClipboardEvent>()
+        .add_message::<GoToEvent>()
+        .add_message::<FrameSelectionEvent>()
+        .add_message::<tools::GenerateSplineEvent>()
+        .add_message::<tools::BuildStaircaseEvent>()
         .add_message::<tools::UpdateSelectionHighlights>()
         // New unified input event
         .add_message::<tools::EditorInputEvent>()
@@ -102,17 +235,45 @@ fn main() {
         .add_message::<tools::SetRotationAxis>()
         .add_message::<AppExitEvent>()
         .add_systems(Startup, setup::setup_editor)
+        .add_systems(Startup, load_autotile_rules)
         .add_systems(Update, setup::setup_egui_fonts)
         .add_systems(Update, lighting::update_lighting_on_map_change)
+        .add_systems(
+            Update,
+            (
+                lighting::sync_weather_preview,
+                weather::update_precipitation,
+                weather::update_weather_fog,
+                lighting::update_wetness_preview,
+            )
+                .chain(),
+        )
+        .add_systems(Update, lighting::sync_edge_preview)
         .add_systems(Update, ui_system::render_ui)
+        .add_systems(Update, update_render_mode.after(ui_system::render_ui))
         .add_systems(Update, ui::dialogs::check_file_dialog_result)
         .add_systems(Update, ui::dialogs::handle_file_selected)
         .add_systems(Update, ui::dialogs::handle_window_close_request)
-        .add_systems(Update, ui::dialogs::handle_app_exit)
-        .add_systems(Update, file_io::handle_save_map)
+        .add_systems(Update, ui::dialogs::handle_file_drop)
+        .add_systems(
+            Update,
+            ui::dialogs::handle_app_exit.after(file_io::handle_save_map),
+        )
+        .add_systems(Update, file_io::handle_save_map.after(ui_system::render_ui))
         .add_systems(Update, file_io::handle_save_map_as)
         .add_systems(Update, file_io::check_save_dialog_result)
-        .add_systems(Update, file_io::handle_file_saved)
+        .add_systems(
+            Update,
+            (file_io::poll_map_save, file_io::handle_file_saved).chain(),
+        )
+        .add_systems(
+            Update,
+            track_map_session
+                .after(file_io::handle_file_saved)
+                .after(ui_system::render_ui)
+                .after(ui::dialogs::handle_window_close_request)
+                .before(ui::dialogs::handle_app_exit),
+        )
         // Play/test systems
         .add_systems(Update, handle_play_map)
         .add_systems(Update, handle_stop_game)
@@ -126,6 +287,20 @@ fn main() {
         .add_systems(Update, handle_global_shortcuts.after(ui_system::render_ui))
         .add_systems(Update, handle_undo.after(handle_global_shortcuts))
         .add_systems(Update, handle_redo.after(handle_global_shortcuts))
+        .add_systems(
+            Update,
+            (handle_copy_selection, handle_paste_clipboard).after(handle_global_shortcuts),
+        )
+        .add_systems(
+            Update,
+            handle_document_events.after(handle_global_shortcuts),
+        )
+        .add_systems(
+            Update,
+            handle_bookmark_events
+                .after(handle_global_shortcuts)
+                .before(track_map_session),
+        )
         // Keyboard handling systems - must run after render_ui for correct egui state
         .add_systems(
             Update,
@@ -141,11 +316,48 @@ fn main() {
         )
         .add_systems(Update, renderer::detect_map_changes)
         .add_systems(Update, renderer::render_map_system)
+        .add_systems(Update, renderer::draw_hazard_badges)
+        .add_systems(Update, renderer::draw_door_footprints)
+        .add_systems(Update, renderer::draw_teleporter_links)
+        .add_systems(Update, renderer::draw_goal_radii)
+        .add_systems(Update, draw_camera_sequence_path)
+        .add_systems(Update, update_selection_boxes)
+        .add_systems(
+            Update,
+            draw_jump_preview.after(cursor::update_cursor_position),
+        )
         .add_systems(Update, renderer::render_entities_system)
+        .add_systems(Update, billboards::face_camera_billboards)
         .add_systems(
             Update,
             camera::handle_camera_input.after(ui_system::render_ui),
         )
+        // Quad view: lay out the viewport cameras once the panels are placed,
+        // before anything raycasts through them
+        .add_systems(
+            Update,
+            viewports::update_viewports
+                .after(ui_system::render_ui)
+                .before(camera::handle_camera_input)
+                .before(cursor::update_cursor_position),
+        )
+        .add_systems(
+            Update,
+            (
+                viewports::focus_ortho_views_on_open,
+                viewports::handle_ortho_view_input,
+                viewports::update_ortho_cameras,
+            )
+                .chain()
+                .after(viewports::update_viewports),
+        )
+        .add_systems(
+            Update,
+            camera::handle_camera_gestures
+                .after(ui_system::render_ui)
+                .after(viewports::update_viewports)
+                .before(camera::update_editor_camera),
+        )
         .add_systems(Update, camera::update_editor_camera)
         .add_systems(
             Update,
@@ -166,6 +378,10 @@ fn main() {
             Update,
             tools::handle_voxel_drag_placement.after(tools::handle_voxel_placement),
         )
+        .add_systems(
+            Update,
+            tools::handle_placement_rotation_keys.after(ui_system::render_ui),
+        )
         .add_systems(
             Update,
             tools::handle_voxel_removal.after(ui_system::render_ui),
@@ -194,10 +410,208 @@ fn main() {
         // Keep rendering systems
         .add_systems(Update, tools::render_selection_highlights)
         .add_systems(Update, tools::render_transform_preview)
+        .add_systems(Update, tools::render_placement_preview)
         // Entity name labels overlay - must run after render_ui so the egui context is ready
         .add_systems(
             Update,
             ui::render_entity_name_labels.after(ui_system::render_ui),
         )
+        // Sculpt and paint brush strokes and outlines
+        .add_systems(
+            Update,
+            (
+                tools::handle_sculpt_brush.after(cursor::update_cursor_position),
+                tools::draw_sculpt_brush,
+                tools::handle_paint_brush.after(cursor::update_cursor_position),
+                tools::draw_paint_brush,
+            )
+                .after(ui_system::render_ui),
+        )
+        // Spline tool: control point editing, voxel generation and preview
+        .add_systems(
+            Update,
+            (
+                tools::handle_spline_input.after(cursor::update_cursor_position),
+                tools::handle_generate_spline,
+                tools::draw_spline_preview,
+            )
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // Staircase tool: end points, building and preview
+        .add_systems(
+            Update,
+            (
+                tools::handle_staircase_input.after(cursor::update_cursor_position),
+                tools::handle_build_staircase,
+                tools::draw_staircase_preview,
+            )
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // New Map dialog with its templates, and Save as Template
+        .add_systems(
+            Update,
+            (render_new_map_dialog, render_save_template_dialog)
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // Locked Areas window and the tint over locked voxels
+        .add_systems(
+            Update,
+            (render_locks_window, update_locked_area_tint)
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // Construction guides window and their viewport overlay
+        .add_systems(
+            Update,
+            (render_guides_window, draw_construction_guides)
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // Go To dialog (Ctrl+G) and the camera jump it triggers
+        .add_systems(
+            Update,
+            (render_go_to_dialog, handle_go_to)
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // F frames the selection; the camera flight yields to any other move
+        .add_systems(
+            Update,
+            (
+                frame_selection_on_key,
+                handle_frame_selection,
+                animate_camera_framing,
+            )
+                .chain()
+                .after(ui_system::render_ui)
+                .after(camera::handle_camera_input)
+                .after(camera::handle_camera_gestures)
+                .before(viewports::update_ortho_cameras)
+                .before(camera::update_editor_camera),
+        )
+        // Camera sequence timeline; its preview overrides camera input
+        .add_systems(
+            Update,
+            render_camera_timeline
+                .after(ui_system::render_ui)
+                .after(camera::handle_camera_input)
+                .before(camera::update_editor_camera),
+        )
+        // Commands registered by editor plugins
+        .add_systems(Update, run_extension_commands.after(ui_system::render_ui))
+        // Prefab export/import and placement of imported prefabs
+        .add_systems(
+            Update,
+            (
+                handle_prefab_dialogs,
+                handle_prefab_placement.after(cursor::update_cursor_position),
+                draw_prefab_preview,
+            )
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // Structure generator window and its preview at the cursor
+        .add_systems(
+            Update,
+            (
+                render_generator_window.after(cursor::update_cursor_position),
+                draw_generator_preview,
+            )
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // Chunk complexity overlay and heaviest-chunk list
+        .add_systems(
+            Update,
+            (
+                draw_chunk_complexity,
+                render_complexity_window
+                    .after(ui_system::render_ui)
+                    .before(handle_go_to),
+            ),
+        )
+        // Map validation panel with duplicate voxel cleanup
+        .add_systems(
+            Update,
+            (
+                open_validation_on_duplicates,
+                render_validation_window
+                    .after(ui_system::render_ui)
+                    .before(handle_go_to),
+            )
+                .chain(),
+        )
+        // Autotile rules panel, preview and runs after each edit
+        .add_systems(
+            Update,
+            (
+                render_autotile_window.after(ui_system::render_ui),
+                autotile_new_edits,
+                update_autotile_preview,
+            )
+                .chain(),
+        )
+        // Playtest log heatmap overlay
+        .add_systems(
+            Update,
+            (
+                load_playtest_log.after(ui_system::render_ui),
+                draw_playtest_heatmap,
+            ),
+        )
+        // Compare-with-file review window
+        .add_systems(
+            Update,
+            ui::dialogs::render_map_diff_review.after(ui_system::render_ui),
+        )
+        // Crash reports: the unsaved map and GPU, and last run's crash notice
+        .add_systems(
+            Update,
+            (
+                track_unsaved_map,
+                crash_report::track_render_adapter,
+                render_recovery_dialog.after(ui_system::render_ui),
+            ),
+        )
+        // Collaboration session: window, edit exchange and remote cursors
+        .add_systems(
+            Update,
+            (
+                render_collab_window,
+                run_collab_session
+                    .after(handle_undo)
+                    .after(handle_redo)
+                    .after(cursor::update_cursor_position),
+                draw_collaborator_cursors,
+            )
+                .chain()
+                .after(ui_system::render_ui),
+        )
+        // Gamepad navigation of the egui panels (Select toggles it)
+        .add_systems(
+            PreUpdate,
+            inject_gamepad_ui_navigation
+                .after(EguiPreUpdateSet::ProcessInput)
+                .before(EguiPreUpdateSet::BeginPass),
+        )
+        .add_systems(
+            Update,
+            render_ui_navigation_hint.after(ui_system::render_ui),
+        )
+        // Controller radial menus for tools and patterns (hold RB / LB)
+        .add_systems(
+            Update,
+            (
+                handle_radial_menu.before(camera::handle_camera_input),
+                render_radial_menu
+                    .after(handle_radial_menu)
+                    .after(ui_system::render_ui),
+            ),
+        )
         .run();
+
+    ExitCode::SUCCESS
 }
@@ -1,12 +1,16 @@
 //! Editor setup and initialization.
 
+use adrakestory::editor::cursor::VoxelIndex;
 use adrakestory::editor::ui::dialogs::MapDataChangedEvent;
 use adrakestory::editor::ui::FIRA_MONO_FAMILY;
+use adrakestory::editor::viewports::{self, ViewportCamera, ViewportKind};
 use adrakestory::editor::{camera, grid, EditorState};
+use adrakestory::systems::game::weather::WeatherCamera;
 use bevy::light::CascadeShadowConfigBuilder;
+use bevy::post_process::bloom::Bloom;
 use bevy::prelude::*;
 use bevy::text::DEFAULT_FONT_DATA;
-use bevy_egui::EguiContexts;
+use bevy_egui::{EguiContexts, EguiGlobalSettings, PrimaryEguiContext};
 use grid::InfiniteGridConfig;
 
 /// Loads Bevy's embedded FiraMono font into egui under the [`FIRA_MONO_FAMILY`] named family.
@@ -44,10 +48,24 @@ pub fn setup_editor(
     mut materials: ResMut<Assets<StandardMaterial>>,
     grid_config: Res<InfiniteGridConfig>,
     editor_state: Res<EditorState>,
+    mut egui_settings: ResMut<EguiGlobalSettings>,
     mut map_changed_events: MessageWriter<MapDataChangedEvent>,
 ) {
     info!("Starting Map Editor");
 
+    // egui draws through its own camera on top of every viewport, so the UI
+    // keeps the whole window when the 3D view is split
+    egui_settings.auto_create_primary_context = false;
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: ViewportKind::ORTHOGRAPHIC.len() as isize + 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        PrimaryEguiContext,
+    ));
+
     // Spawn 3D camera for viewport
     let camera_pos = Vec3::new(10.0, 10.0, 10.0);
     commands.spawn((
@@ -55,7 +73,14 @@ pub fn setup_editor(
         Transform::from_xyz(camera_pos.x, camera_pos.y, camera_pos.z)
             .looking_at(Vec3::new(2.0, 0.0, 2.0), Vec3::Y),
         camera::EditorCamera::new(),
+        ViewportCamera {
+            kind: ViewportKind::Perspective,
+        },
+        WeatherCamera,
+        // Glowing voxels bleed light as they do in game
+        Bloom::NATURAL,
     ));
+    viewports::spawn_ortho_cameras(&mut commands);
 
     // Get lighting configuration from the current map
     let lighting = &editor_state.current_map.lighting;
@@ -119,6 +144,7 @@ pub fn setup_editor(
 
     // Spawn cursor indicator
     grid::spawn_cursor_indicator(&mut commands, &mut meshes, &mut materials);
+    commands.insert_resource(VoxelIndex::from_map(&editor_state.current_map));
 
     // Send event to trigger initial lighting setup
     map_changed_events.write(MapDataChangedEvent);
@@ -1,10 +1,18 @@
 //! Status bar rendering.
 
+use adrakestory::editor::locks::is_locked;
 use adrakestory::editor::tools::{ActiveTransform, TransformMode};
-use adrakestory::editor::{state, CursorState, EditorHistory, EditorState, KeyboardEditMode};
+use adrakestory::editor::{
+    state, BoundsEnforcement, CursorState, EditorHistory, EditorState, KeyboardEditMode,
+    MapSaveTask,
+};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::Vec3;
 use bevy_egui::egui;
+use std::time::Duration;
 
 /// Render the status bar at the bottom
+#[allow(clippy::too_many_arguments)]
 pub fn render_status_bar(
     ctx: &egui::Context,
     editor_state: &EditorState,
@@ -12,6 +20,8 @@ pub fn render_status_bar(
     history: &EditorHistory,
     keyboard_mode: &KeyboardEditMode,
     active_transform: &ActiveTransform,
+    diagnostics: &DiagnosticsStore,
+    save_task: &MapSaveTask,
 ) {
     let response = egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
         ui.horizontal(|ui| {
@@ -61,17 +71,46 @@ pub fn render_status_bar(
 
             // === Section 3: Cursor Position ===
             if let Some(grid_pos) = cursor_state.grid_pos {
+                let face = cursor_state
+                    .hit_face_normal
+                    .map(|normal| format!(" {}", face_label(normal)))
+                    .unwrap_or_default();
                 ui.label(format!(
-                    "Cursor: ({}, {}, {})",
-                    grid_pos.0, grid_pos.1, grid_pos.2
+                    "Cursor: ({}, {}, {}){}",
+                    grid_pos.0, grid_pos.1, grid_pos.2, face
                 ));
             } else {
                 ui.label("Cursor: --");
             }
 
+            // Flag placements that would land outside the world bounds
+            let placing = matches!(
+                editor_state.active_tool,
+                state::EditorTool::VoxelPlace { .. }
+            );
+            if let Some(pos) = cursor_state.placement_grid_pos.filter(|_| placing) {
+                if editor_state.bounds_enforcement != BoundsEnforcement::Off
+                    && !editor_state.current_map.world.contains(pos)
+                {
+                    ui.colored_label(egui::Color32::from_rgb(255, 200, 80), "⚠ Out of bounds");
+                }
+            }
+
+            // Warn when the active tool would edit a locked voxel
+            if locked_edit_target(editor_state, cursor_state)
+                .is_some_and(|pos| is_locked(&editor_state.locked_areas(), pos))
+            {
+                ui.colored_label(egui::Color32::from_rgb(255, 200, 80), "🔒 Locked area");
+            }
+
             ui.separator();
 
             // === Section 4: Map Statistics ===
+            let world = &editor_state.current_map.world;
+            ui.label(format!(
+                "Map: {}×{}×{}",
+                world.width, world.height, world.depth
+            ));
             ui.label(format!(
                 "Voxels: {}",
                 editor_state.current_map.world.voxels.len()
@@ -98,14 +137,32 @@ pub fn render_status_bar(
             }
 
             // === Section 7: Modified Indicator ===
-            if editor_state.is_modified {
+            if let Some((path, started)) = save_task.current() {
+                ui.add(egui::Spinner::new());
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default();
+                ui.label(format!("Saving {}… {}s", name, started.elapsed().as_secs()));
+            } else if editor_state.is_modified {
                 ui.colored_label(egui::Color32::from_rgb(255, 200, 100), "● Modified");
             } else {
                 ui.colored_label(egui::Color32::from_rgb(100, 200, 100), "✓ Saved");
             }
 
-            // === Right-aligned: History Stats ===
+            // === Right-aligned: History Stats, Last Save and FPS ===
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if let Some(fps) = diagnostics
+                    .get(&FrameTimeDiagnosticsPlugin::FPS)
+                    .and_then(|fps| fps.smoothed())
+                {
+                    ui.label(format!("{:.0} FPS", fps));
+                    ui.separator();
+                }
+                if let Some(saved) = editor_state.last_saved {
+                    ui.label(format!("Saved {}", format_elapsed(saved.elapsed())));
+                    ui.separator();
+                }
                 ui.label(format!(
                     "Undo: {} │ Redo: {}",
                     history.undo_count(),
@@ -126,12 +183,68 @@ pub fn render_status_bar(
 }
 
 /// Get tool icon and display name
-pub fn get_tool_display(tool: &state::EditorTool) -> (&'static str, &'static str) {
+pub fn get_tool_display(tool: &state::EditorTool) -> (&'static str, &str) {
     match tool {
         state::EditorTool::Select => ("🔲", "Select"),
         state::EditorTool::VoxelPlace { .. } => ("✏️", "Voxel Place"),
         state::EditorTool::VoxelRemove => ("🗑️", "Voxel Remove"),
         state::EditorTool::EntityPlace { .. } => ("📍", "Entity Place"),
         state::EditorTool::Camera => ("📷", "Camera"),
+        state::EditorTool::Sculpt { .. } => ("🖌", "Sculpt"),
+        state::EditorTool::Paint { .. } => ("🎨", "Paint"),
+        state::EditorTool::Spline { .. } => ("〰", "Spline"),
+        state::EditorTool::Staircase { .. } => ("⟋", "Staircase"),
+        state::EditorTool::Custom { name } => ("🧩", name),
+    }
+}
+
+/// Voxel the active tool would change if clicked now, for the locked-area
+/// warning.
+fn locked_edit_target(
+    editor_state: &EditorState,
+    cursor_state: &CursorState,
+) -> Option<(i32, i32, i32)> {
+    match editor_state.active_tool {
+        state::EditorTool::VoxelPlace { .. }
+        | state::EditorTool::Spline { .. }
+        | state::EditorTool::Staircase { .. } => cursor_state.placement_grid_pos,
+        state::EditorTool::VoxelRemove
+        | state::EditorTool::Sculpt { .. }
+        | state::EditorTool::Paint { .. } => cursor_state.grid_pos,
+        _ => None,
+    }
+}
+
+/// Short label for the face the cursor ray hit
+fn face_label(normal: Vec3) -> &'static str {
+    let abs = normal.abs();
+    if abs.x >= abs.y && abs.x >= abs.z {
+        if normal.x >= 0.0 {
+            "+X"
+        } else {
+            "-X"
+        }
+    } else if abs.y >= abs.z {
+        if normal.y >= 0.0 {
+            "+Y"
+        } else {
+            "-Y"
+        }
+    } else if normal.z >= 0.0 {
+        "+Z"
+    } else {
+        "-Z"
+    }
+}
+
+/// Coarse "how long ago" text for the last save
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        "just now".to_string()
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else {
+        format!("{}h ago", secs / 3600)
     }
 }
@@ -1,16 +1,21 @@
 //! UI rendering system.
 
 use super::status_bar::render_status_bar;
+use adrakestory::editor::bookmarks::BookmarkEvent;
+use adrakestory::editor::documents::{DocumentEvent, EditorDocuments};
+use adrakestory::editor::extensions::{EditorExtensions, ExtensionCommandEvent};
 use adrakestory::editor::play::{PlayMapEvent, PlayTestState, StopGameEvent};
+use adrakestory::editor::preferences::EditorPreferences;
 use adrakestory::editor::recent_files::{OpenRecentFileEvent, RecentFiles};
 use adrakestory::editor::tools::ActiveTransform;
-use adrakestory::editor::ui::dialogs::{AppExitEvent, MapDataChangedEvent};
+use adrakestory::editor::ui::dialogs::{AppExitEvent, FileSelectedEvent};
 use adrakestory::editor::ui::properties::TransformEvents;
 use adrakestory::editor::{state, tools, ui};
 use adrakestory::editor::{
     CursorState, EditorHistory, EditorState, KeyboardEditMode, RedoEvent, RenderMapEvent, UndoEvent,
 };
-use adrakestory::editor::{SaveMapAsEvent, SaveMapEvent};
+use adrakestory::editor::{MapSaveTask, SaveMapAsEvent, SaveMapEvent};
+use bevy::diagnostic::DiagnosticsStore;
 use bevy::prelude::*;
 use bevy_egui::EguiContexts;
 
@@ -24,15 +29,18 @@ pub struct SaveEvents<'w> {
 /// Bundle of event writers for UI operations
 #[derive(bevy::ecs::system::SystemParam)]
 pub struct UIEventWriters<'w> {
-    pub map_changed: MessageWriter<'w, MapDataChangedEvent>,
     pub selection: MessageWriter<'w, tools::UpdateSelectionHighlights>,
     pub render: MessageWriter<'w, RenderMapEvent>,
     pub exit: MessageWriter<'w, AppExitEvent>,
     pub open_recent: MessageWriter<'w, OpenRecentFileEvent>,
+    pub file_selected: MessageWriter<'w, FileSelectedEvent>,
+    pub documents: MessageWriter<'w, DocumentEvent>,
     pub play: MessageWriter<'w, PlayMapEvent>,
     pub stop: MessageWriter<'w, StopGameEvent>,
     pub undo: MessageWriter<'w, UndoEvent>,
     pub redo: MessageWriter<'w, RedoEvent>,
+    pub bookmarks: MessageWriter<'w, BookmarkEvent>,
+    pub extension_commands: MessageWriter<'w, ExtensionCommandEvent>,
 }
 
 /// Bundle of UI-related resources
@@ -43,6 +51,7 @@ pub struct UIResources<'w> {
     pub tool_memory: ResMut<'w, state::ToolMemory>,
     pub outliner_state: ResMut<'w, ui::OutlinerState>,
     pub recent_files: ResMut<'w, RecentFiles>,
+    pub preferences: ResMut<'w, EditorPreferences>,
     pub dialog_receiver: ResMut<'w, ui::dialogs::FileDialogReceiver>,
     pub play_state: ResMut<'w, PlayTestState>,
 }
@@ -54,6 +63,10 @@ pub struct EditorReadResources<'w> {
     pub history: ResMut<'w, EditorHistory>,
     pub active_transform: Res<'w, ActiveTransform>,
     pub keyboard_mode: Res<'w, KeyboardEditMode>,
+    pub diagnostics: Res<'w, DiagnosticsStore>,
+    pub documents: Res<'w, EditorDocuments>,
+    pub save_task: Res<'w, MapSaveTask>,
+    pub extensions: Res<'w, EditorExtensions>,
 }
 
 /// Render the UI
@@ -75,6 +88,7 @@ pub fn render_ui(
         &mut ui_resources.tool_memory,
         &read_resources.history,
         &mut ui_resources.recent_files,
+        &mut ui_resources.preferences,
         &mut ui_resources.play_state,
         &mut save_events.save,
         &mut save_events.save_as,
@@ -83,6 +97,17 @@ pub fn render_ui(
         &mut ui_events.stop,
         &mut ui_events.undo,
         &mut ui_events.redo,
+        &mut ui_events.bookmarks,
+        &read_resources.extensions,
+        &mut ui_events.extension_commands,
+    );
+
+    // Render map tabs (below the toolbar, above the viewport)
+    ui::render_tab_bar(
+        ctx,
+        &ui_resources.editor_state,
+        &read_resources.documents,
+        &mut ui_events.documents,
     );
 
     // Render status bar (before side panels and overlays so its height is known)
@@ -93,6 +118,8 @@ pub fn render_ui(
         &read_resources.history,
         &read_resources.keyboard_mode,
         &read_resources.active_transform,
+        &read_resources.diagnostics,
+        &read_resources.save_task,
     );
 
     // Render outliner panel (left side)
@@ -113,6 +140,7 @@ pub fn render_ui(
         &read_resources.active_transform,
         &mut transform_events,
         &mut read_resources.history,
+        &read_resources.extensions,
     );
 
     // Render viewport overlays (keyboard mode indicator, selection tooltip, etc.)
@@ -129,10 +157,12 @@ pub fn render_ui(
         ctx,
         &mut ui_resources.editor_state,
         &mut ui_resources.ui_state,
+        &mut read_resources.history,
         &mut save_events.save,
-        &mut ui_events.map_changed,
         &mut ui_events.exit,
         &mut ui_events.open_recent,
+        &mut ui_events.file_selected,
+        &mut ui_events.documents,
     );
 
     // Handle file operations
@@ -0,0 +1,236 @@
+//! Crash reports and recovery of unsaved maps.
+//!
+//! [`install`] sets a panic hook that writes `crashes/crash-<unix time>.txt`
+//! before the default hook runs. The report holds the panic message and
+//! location, a backtrace, the open map's path and system information (OS,
+//! CPU count, GPU). When the map editor has unsaved changes, the map is also
+//! written to `crashes/recovery-<unix time>.ron`, a normal map file.
+//!
+//! The panic hook can't reach the ECS world, so the apps keep a
+//! [`CrashContext`] up to date instead: the map path whenever it changes
+//! and, in the editor, a copy of the map every [`SNAPSHOT_INTERVAL`] seconds
+//! while it has unsaved changes.
+//!
+//! Each crash also leaves `crashes/<app>-pending.ron`. On the next launch
+//! [`take_pending`] returns it so the app can point at the report and, in
+//! the editor, offer to restore the recovery file.
+
+use crate::systems::game::map::format::MapData;
+use crate::systems::game::map::loader::MapLoader;
+use bevy::prelude::*;
+use bevy::render::renderer::RenderAdapterInfo;
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Folder crash reports and recovery files are written to.
+pub const CRASH_DIR: &str = "crashes";
+
+/// Seconds between copies of an unsaved map for the recovery file.
+#[allow(dead_code)]
+pub const SNAPSHOT_INTERVAL: f32 = 2.0;
+
+/// What the app was working on, for the panic hook.
+#[derive(Debug, Clone, Default)]
+pub struct CrashContext {
+    /// Map file open in the app; `None` for a new or built-in map
+    pub map_path: Option<PathBuf>,
+    /// Copy of the map while it has unsaved changes
+    pub unsaved_map: Option<MapData>,
+    /// Graphics adapter, once the renderer has started
+    pub gpu: Option<String>,
+}
+
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext {
+    map_path: None,
+    unsaved_map: None,
+    gpu: None,
+});
+
+fn context() -> MutexGuard<'static, CrashContext> {
+    // Nothing panics while holding the lock, but a crash report should
+    // never fail over a poisoned mutex
+    CONTEXT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Record the map file the app has open.
+pub fn set_map_path(path: Option<PathBuf>) {
+    context().map_path = path;
+}
+
+/// Record a copy of the unsaved map, or `None` once it is saved.
+#[allow(dead_code)]
+pub fn set_unsaved_map(map: Option<MapData>) {
+    context().unsaved_map = map;
+}
+
+/// Left behind by a crash for the next launch to report.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CrashNotice {
+    /// Unix time of the crash
+    pub time: u64,
+    pub report: PathBuf,
+    /// Map file open when the app crashed
+    pub map_path: Option<PathBuf>,
+    /// Unsaved map written at the crash
+    pub recovery: Option<PathBuf>,
+}
+
+fn pending_path(dir: &Path, app: &str) -> PathBuf {
+    dir.join(format!("{}-pending.ron", app))
+}
+
+/// Install the crash report panic hook for `app` (`"game"` or
+/// `"map_editor"`, used in file names). The default hook still runs after
+/// the report is written.
+pub fn install(app: &'static str) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let message = panic_message(info);
+        let location = info.location().map(|l| l.to_string());
+        let backtrace = Backtrace::force_capture().to_string();
+        let context = context().clone();
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        match write_crash_report(
+            Path::new(CRASH_DIR),
+            app,
+            time,
+            &format!(
+                "{} at {}",
+                message,
+                location.as_deref().unwrap_or("unknown")
+            ),
+            &backtrace,
+            &context,
+        ) {
+            Ok(notice) => eprintln!("Crash report written to {}", notice.report.display()),
+            Err(e) => eprintln!("Failed to write crash report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+/// OS, architecture, CPU count and GPU, one per line.
+fn system_info(context: &CrashContext) -> String {
+    let cpus = std::thread::available_parallelism().map_or(0, |n| n.get());
+    format!(
+        "OS: {} ({})\nCPUs: {}\nGPU: {}\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        cpus,
+        context.gpu.as_deref().unwrap_or("unknown")
+    )
+}
+
+/// Write the crash report, the recovery file if there is an unsaved map,
+/// and the notice for the next launch, all in `dir`.
+pub fn write_crash_report(
+    dir: &Path,
+    app: &str,
+    time: u64,
+    panic: &str,
+    backtrace: &str,
+    context: &CrashContext,
+) -> std::io::Result<CrashNotice> {
+    fs::create_dir_all(dir)?;
+
+    // The map matters most, so it is written first
+    let recovery = context.unsaved_map.as_ref().and_then(|map| {
+        let path = dir.join(format!("recovery-{}.ron", time));
+        match MapLoader::save_to_file(map, &path) {
+            Ok(()) => Some(path),
+            Err(e) => {
+                eprintln!("Failed to write recovery file: {}", e);
+                None
+            }
+        }
+    });
+
+    let mut report = format!(
+        "A Drake's Story {} {} crashed\nTime: {} (unix)\n",
+        app,
+        env!("CARGO_PKG_VERSION"),
+        time
+    );
+    let _ = writeln!(report, "Panic: {}", panic);
+    let _ = writeln!(
+        report,
+        "Map: {}",
+        context
+            .map_path
+            .as_ref()
+            .map_or("none".to_string(), |p| p.display().to_string())
+    );
+    if let Some(path) = &recovery {
+        let _ = writeln!(report, "Unsaved changes: {}", path.display());
+    }
+    report.push_str(&system_info(context));
+    let _ = write!(report, "\nBacktrace:\n{}\n", backtrace);
+
+    let report_path = dir.join(format!("crash-{}.txt", time));
+    fs::write(&report_path, report)?;
+
+    let notice = CrashNotice {
+        time,
+        report: report_path,
+        map_path: context.map_path.clone(),
+        recovery,
+    };
+    if let Ok(contents) = ron::ser::to_string_pretty(&notice, ron::ser::PrettyConfig::default()) {
+        fs::write(pending_path(dir, app), contents)?;
+    }
+    Ok(notice)
+}
+
+/// The crash `app` left behind on its last run, if any. The notice is
+/// removed, so each crash is reported once.
+pub fn take_pending(app: &str) -> Option<CrashNotice> {
+    take_pending_in(Path::new(CRASH_DIR), app)
+}
+
+fn take_pending_in(dir: &Path, app: &str) -> Option<CrashNotice> {
+    let path = pending_path(dir, app);
+    let contents = fs::read_to_string(&path).ok()?;
+    let _ = fs::remove_file(&path);
+    match ron::from_str(&contents) {
+        Ok(notice) => Some(notice),
+        Err(e) => {
+            warn!("Ignoring unreadable crash notice {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// System to record the graphics adapter once the renderer has started.
+pub fn track_render_adapter(adapter: Option<Res<RenderAdapterInfo>>, mut done: Local<bool>) {
+    if *done {
+        return;
+    }
+    if let Some(adapter) = adapter {
+        context().gpu = Some(format!(
+            "{} ({:?}, {} {})",
+            adapter.name, adapter.backend, adapter.driver, adapter.driver_info
+        ));
+        *done = true;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+use super::*;
+use crate::systems::game::map::format::VoxelData;
+use crate::systems::game::map::format::VoxelType;
+
+fn context_with_unsaved_map() -> CrashContext {
+    let mut map = MapData::empty_map();
+    map.world.voxels.push(VoxelData {
+        pos: (2, 0, 3),
+        voxel_type: VoxelType::Grass,
+        pattern: None,
+        rotation: None,
+        rotation_state: None,
+        group: None,
+    });
+    CrashContext {
+        map_path: Some(PathBuf::from("maps/forest.ron")),
+        unsaved_map: Some(map),
+        gpu: Some("Test GPU".to_string()),
+    }
+}
+
+#[test]
+fn crash_writes_report_recovery_file_and_notice() {
+    let dir = tempfile::tempdir().unwrap();
+    let context = context_with_unsaved_map();
+
+    let notice = write_crash_report(
+        dir.path(),
+        "map_editor",
+        1_700_000_000,
+        "index out of bounds at src/editor/tools.rs:10:5",
+        "0: main",
+        &context,
+    )
+    .unwrap();
+
+    let report = fs::read_to_string(&notice.report).unwrap();
+    assert!(report.contains("index out of bounds"));
+    assert!(report.contains("Map: maps/forest.ron"));
+    assert!(report.contains("GPU: Test GPU"));
+    assert!(report.contains("0: main"));
+
+    let recovery = notice.recovery.as_ref().expect("unsaved map is recovered");
+    let restored = MapLoader::read_unvalidated(recovery).unwrap();
+    assert_eq!(
+        restored
+            .world
+            .voxels
+            .iter()
+            .map(|v| v.pos)
+            .collect::<Vec<_>>(),
+        vec![(2, 0, 3)]
+    );
+
+    assert_eq!(take_pending_in(dir.path(), "map_editor"), Some(notice));
+    // Each crash is reported once, and only to its own app
+    assert_eq!(take_pending_in(dir.path(), "map_editor"), None);
+    assert_eq!(take_pending_in(dir.path(), "game"), None);
+}
+
+#[test]
+fn crash_without_unsaved_changes_has_no_recovery_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let context = CrashContext::default();
+
+    let notice =
+        write_crash_report(dir.path(), "game", 1, "boom at main.rs:1:1", "", &context).unwrap();
+
+    assert_eq!(notice.recovery, None);
+    assert!(fs::read_to_string(&notice.report)
+        .unwrap()
+        .contains("Map: none"));
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+}
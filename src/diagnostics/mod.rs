@@ -43,6 +43,8 @@
 //! awk -F',' 'NR>1 {sum[$2]+=$3; cnt[$2]++} END {for(l in sum) print l, sum[l]/cnt[l]}' profile.csv | sort -k2 -n
 //! ```
 
+pub mod crash_report;
+
 use bevy::prelude::*;
 use std::fs;
 use std::io::{BufWriter, Write};
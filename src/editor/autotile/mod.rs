@@ -0,0 +1,448 @@
+//! Autotile rules: change voxel types by what surrounds them.
+//!
+//! Rules such as "Grass with a voxel above becomes Dirt" are read from
+//! [`AUTOTILE_RULES_PATH`], a RON file of [`AutotileRule`]s. View → Autotile
+//! Rules lists them and runs them over the selection or the whole map as one
+//! undo step, with a preview boxing every voxel that would change. With
+//! **Apply while painting** on, each new edit is followed by a run over the
+//! voxels it touched and their neighbours, undone together with the edit.
+//!
+//! Rules only change voxel types; geometry, patterns and rotations are left
+//! alone, as with the paint tool. A run looks at the map as it was before the
+//! run, so the order voxels are visited in doesn't matter; running again can
+//! cascade (grass buried by grass turning to dirt, say).
+
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::locks::is_locked;
+use crate::editor::state::EditorState;
+use crate::systems::game::debug_boxes::{DebugBox, DebugBoxMesh};
+use crate::systems::game::map::format::{VoxelStore, VoxelType};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// File the editor reads autotile rules from
+pub const AUTOTILE_RULES_PATH: &str = "assets/autotile/rules.ron";
+
+/// Undo step name of a run from the panel
+pub const AUTOTILE_DESCRIPTION: &str = "Autotile";
+
+/// Color of the boxes around voxels a run would change.
+pub const AUTOTILE_PREVIEW_COLOR: LinearRgba = LinearRgba::rgb(1.0, 0.3, 0.9);
+
+/// Offsets of the six voxels sharing a face.
+const FACE_NEIGHBORS: [(i32, i32, i32); 6] = [
+    (0, 1, 0),
+    (0, -1, 0),
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// What a voxel's surroundings must look like for a rule to apply.
+///
+/// Neighbour types compare against the voxel there, `Air` meaning none.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutotileCondition {
+    /// The voxel directly above is this type
+    Above(VoxelType),
+    /// The voxel directly below is this type
+    Below(VoxelType),
+    /// One of the four voxels beside it is this type
+    Beside(VoxelType),
+    /// One of the six voxels sharing a face is this type
+    Adjacent(VoxelType),
+    /// The condition doesn't hold
+    Not(Box<AutotileCondition>),
+    /// Every condition holds
+    All(Vec<AutotileCondition>),
+}
+
+impl AutotileCondition {
+    /// Whether the condition holds for the voxel at `pos`.
+    pub fn holds(&self, voxels: &VoxelStore, pos: (i32, i32, i32)) -> bool {
+        let (x, y, z) = pos;
+        let any_of = |offsets: &[(i32, i32, i32)], wanted: VoxelType| {
+            offsets
+                .iter()
+                .any(|(dx, dy, dz)| type_at(voxels, (x + dx, y + dy, z + dz)) == wanted)
+        };
+        match self {
+            Self::Above(wanted) => type_at(voxels, (x, y + 1, z)) == *wanted,
+            Self::Below(wanted) => type_at(voxels, (x, y - 1, z)) == *wanted,
+            Self::Beside(wanted) => any_of(&FACE_NEIGHBORS[2..], *wanted),
+            Self::Adjacent(wanted) => any_of(&FACE_NEIGHBORS, *wanted),
+            Self::Not(condition) => !condition.holds(voxels, pos),
+            Self::All(conditions) => conditions
+                .iter()
+                .all(|condition| condition.holds(voxels, pos)),
+        }
+    }
+}
+
+/// Type of the voxel at `pos`, `Air` when there is none.
+fn type_at(voxels: &VoxelStore, pos: (i32, i32, i32)) -> VoxelType {
+    voxels
+        .get(pos)
+        .map_or(VoxelType::Air, |voxel| voxel.voxel_type)
+}
+
+/// One rule: voxels of type `from` for which `when` holds become `to`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutotileRule {
+    pub name: String,
+    pub from: VoxelType,
+    pub to: VoxelType,
+    pub when: AutotileCondition,
+    /// Rules that are off are listed but not run
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The rules the editor runs, in order: a voxel takes the first enabled
+/// rule that matches it.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutotileRules {
+    pub rules: Vec<AutotileRule>,
+}
+
+impl AutotileRules {
+    /// Read rules from a RON file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read autotile rules: {}", e))?;
+        ron::from_str(&contents).map_err(|e| format!("Failed to parse autotile rules: {}", e))
+    }
+
+    /// New type for the voxel at `pos`, if a rule changes it.
+    pub fn result_for(&self, voxels: &VoxelStore, pos: (i32, i32, i32)) -> Option<VoxelType> {
+        let current = voxels.get(pos)?.voxel_type;
+        self.rules
+            .iter()
+            .filter(|rule| rule.enabled && rule.from == current && rule.to != current)
+            .find(|rule| rule.when.holds(voxels, pos))
+            .map(|rule| rule.to)
+    }
+
+    /// Voxels among `positions` the rules change, with their new types, in
+    /// the order given. Empty positions are skipped.
+    pub fn changes(
+        &self,
+        voxels: &VoxelStore,
+        positions: impl IntoIterator<Item = (i32, i32, i32)>,
+    ) -> Vec<((i32, i32, i32), VoxelType)> {
+        positions
+            .into_iter()
+            .filter_map(|pos| Some((pos, self.result_for(voxels, pos)?)))
+            .collect()
+    }
+}
+
+/// Apply `changes` to the open map, skipping locked voxels and re-meshing
+/// only the chunks around them. Returns the actions applied; each change is
+/// recorded as a removal of the old voxel followed by placement of the new
+/// one, as the paint tool does.
+pub fn apply_autotile(
+    editor_state: &mut EditorState,
+    changes: &[((i32, i32, i32), VoxelType)],
+) -> Vec<EditorAction> {
+    let locks = editor_state.locked_areas();
+    let mut actions = Vec::with_capacity(changes.len() * 2);
+    let mut changed = Vec::with_capacity(changes.len());
+    for &(pos, voxel_type) in changes {
+        if is_locked(&locks, pos) {
+            continue;
+        }
+        let Some(voxel) = editor_state.current_map.world.voxels.get_mut(pos) else {
+            continue;
+        };
+        let old = voxel.clone();
+        voxel.voxel_type = voxel_type;
+        actions.push(EditorAction::RemoveVoxel { pos, data: old });
+        actions.push(EditorAction::PlaceVoxel {
+            pos,
+            data: voxel.clone(),
+        });
+        changed.push(pos);
+    }
+    if !changed.is_empty() {
+        editor_state.mark_voxels_modified(changed);
+    }
+    actions
+}
+
+/// Positions whose voxels `action` placed or removed, and their face
+/// neighbours: the voxels whose rules it may have changed the outcome of.
+pub fn touched_positions(action: &EditorAction, positions: &mut HashSet<(i32, i32, i32)>) {
+    match action {
+        EditorAction::PlaceVoxel { pos, .. } | EditorAction::RemoveVoxel { pos, .. } => {
+            let (x, y, z) = *pos;
+            positions.insert(*pos);
+            positions.extend(
+                FACE_NEIGHBORS
+                    .iter()
+                    .map(|(dx, dy, dz)| (x + dx, y + dy, z + dz)),
+            );
+        }
+        EditorAction::Batch { actions, .. } => {
+            for action in actions {
+                touched_positions(action, positions);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// What the autotile panel is set to.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AutotileSettings {
+    /// Box the voxels a run would change
+    pub preview: bool,
+    /// Run the rules after every edit
+    pub while_painting: bool,
+    /// Run over the selected voxels only, when there are any
+    pub selection_only: bool,
+}
+
+/// Positions a run from the panel covers.
+fn run_positions(editor_state: &EditorState, settings: &AutotileSettings) -> Vec<(i32, i32, i32)> {
+    if settings.selection_only && !editor_state.selected_voxels.is_empty() {
+        let mut positions: Vec<_> = editor_state.selected_voxels.iter().copied().collect();
+        positions.sort();
+        positions
+    } else {
+        editor_state
+            .current_map
+            .world
+            .voxels
+            .iter()
+            .map(|voxel| voxel.pos)
+            .collect()
+    }
+}
+
+/// Read the rules file into [`AutotileRules`], leaving no rules when it
+/// can't be read.
+pub fn load_autotile_rules(mut commands: Commands) {
+    let rules = AutotileRules::load(Path::new(AUTOTILE_RULES_PATH)).unwrap_or_else(|e| {
+        warn!("{}; no autotile rules loaded", e);
+        AutotileRules::default()
+    });
+    info!("Loaded {} autotile rules", rules.rules.len());
+    commands.insert_resource(rules);
+}
+
+/// Run the rules over what each new edit touched while **Apply while
+/// painting** is on, adding the changes to the edit's undo step.
+pub fn autotile_new_edits(
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    rules: Res<AutotileRules>,
+    settings: Res<AutotileSettings>,
+    mut seen_pushes: Local<u64>,
+) {
+    let pushes = history.push_count();
+    if pushes == *seen_pushes {
+        return;
+    }
+    *seen_pushes = pushes;
+    if !settings.while_painting {
+        return;
+    }
+    // Undo and redo leave the push count alone, so the last action is new
+    let mut touched = HashSet::new();
+    match history.last_action() {
+        // A run from the panel is not followed by another
+        Some(EditorAction::Batch { description, .. }) if description == AUTOTILE_DESCRIPTION => {
+            return;
+        }
+        Some(last) => touched_positions(last, &mut touched),
+        None => return,
+    }
+    let mut positions: Vec<_> = touched.into_iter().collect();
+    positions.sort();
+    let changes = rules.changes(&editor_state.current_map.world.voxels, positions);
+    let actions = apply_autotile(&mut editor_state, &changes);
+    if !actions.is_empty() {
+        info!("Autotiled {} voxels", actions.len() / 2);
+        history.append_to_last(AUTOTILE_DESCRIPTION, actions);
+    }
+}
+
+/// Marker for the autotile preview's debug boxes.
+#[derive(Component)]
+pub struct AutotilePreview;
+
+/// Box the voxels a run from the panel would change while the preview is on
+/// and the panel open, rebuilding when the map, rules or settings change.
+pub fn update_autotile_preview(
+    mut commands: Commands,
+    editor_state: Res<EditorState>,
+    rules: Res<AutotileRules>,
+    settings: Res<AutotileSettings>,
+    mesh: Res<DebugBoxMesh>,
+    existing: Query<Entity, With<AutotilePreview>>,
+    mut shown: Local<Option<(u64, usize)>>,
+) {
+    let wanted = settings.preview && editor_state.show_autotile;
+    let key = wanted.then_some((editor_state.revision, editor_state.selected_voxels.len()));
+    if key == *shown && !rules.is_changed() && !settings.is_changed() {
+        return;
+    }
+    *shown = key;
+
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+    if !wanted {
+        return;
+    }
+    let positions = run_positions(&editor_state, &settings);
+    let boxes: Vec<DebugBox> = rules
+        .changes(&editor_state.current_map.world.voxels, positions)
+        .into_iter()
+        .map(|((x, y, z), _)| {
+            let center = Vec3::new(x as f32, y as f32, z as f32);
+            DebugBox::from_bounds(
+                center - Vec3::splat(0.52),
+                center + Vec3::splat(0.52),
+                AUTOTILE_PREVIEW_COLOR,
+            )
+        })
+        .collect();
+    if !boxes.is_empty() {
+        commands.spawn((mesh.boxes(boxes), AutotilePreview));
+    }
+}
+
+/// Render the autotile panel while it is open.
+pub fn render_autotile_window(
+    mut contexts: EguiContexts,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut rules: ResMut<AutotileRules>,
+    mut settings: ResMut<AutotileSettings>,
+    mut pending: Local<Option<((u64, usize), usize)>>,
+) {
+    if !editor_state.show_autotile {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    // Count the changes again only after the map or rules change
+    let key = (editor_state.revision, editor_state.selected_voxels.len());
+    if rules.is_changed() || settings.is_changed() || pending.is_none_or(|(k, _)| k != key) {
+        let positions = run_positions(&editor_state, &settings);
+        let count = rules
+            .changes(&editor_state.current_map.world.voxels, positions)
+            .len();
+        *pending = Some((key, count));
+    }
+    let pending_count = pending.map_or(0, |(_, count)| count);
+    // Edited on a copy so the preview only rebuilds when a box is ticked
+    let mut edited = settings.clone();
+    let mut settings_changed = false;
+    let mut open = true;
+    let mut apply = false;
+    let mut reload = false;
+
+    egui::Window::new("Autotile Rules")
+        .open(&mut open)
+        .default_width(320.0)
+        .show(ctx, |ui| {
+            if rules.rules.is_empty() {
+                ui.label(format!("No rules. Add some to {}.", AUTOTILE_RULES_PATH));
+            }
+            // Only flag the rules as changed when a box is ticked
+            let mut toggled = None;
+            for (index, rule) in rules.rules.iter().enumerate() {
+                let mut enabled = rule.enabled;
+                if ui
+                    .checkbox(&mut enabled, &rule.name)
+                    .on_hover_text(format!(
+                        "{:?} → {:?} when {:?}",
+                        rule.from, rule.to, rule.when
+                    ))
+                    .changed()
+                {
+                    toggled = Some(index);
+                }
+            }
+            if let Some(index) = toggled {
+                let rule = &mut rules.rules[index];
+                rule.enabled = !rule.enabled;
+            }
+            ui.separator();
+
+            settings_changed |= ui
+                .checkbox(&mut edited.selection_only, "Selection only")
+                .on_hover_text("Run over the selected voxels instead of the whole map")
+                .changed();
+            settings_changed |= ui
+                .checkbox(&mut edited.preview, "👁 Preview")
+                .on_hover_text("Outline the voxels the rules would change")
+                .changed();
+            settings_changed |= ui
+                .checkbox(&mut edited.while_painting, "Apply while painting")
+                .on_hover_text("Run the rules around every edit; undo takes both back")
+                .changed();
+
+            ui.separator();
+            ui.label(format!("{} voxels would change", pending_count));
+            ui.horizontal(|ui| {
+                apply = ui
+                    .add_enabled(pending_count > 0, egui::Button::new("▶ Apply"))
+                    .on_hover_text("Run the rules once, as one undo step")
+                    .clicked();
+                reload = ui
+                    .button("⟳ Reload Rules")
+                    .on_hover_text(format!("Read {} again", AUTOTILE_RULES_PATH))
+                    .clicked();
+            });
+        });
+
+    if settings_changed {
+        info!(
+            "Autotile: preview {}, while painting {}, selection only {}",
+            edited.preview, edited.while_painting, edited.selection_only
+        );
+        *settings = edited;
+    }
+    if apply {
+        let positions = run_positions(&editor_state, &settings);
+        let changes = rules.changes(&editor_state.current_map.world.voxels, positions);
+        let actions = apply_autotile(&mut editor_state, &changes);
+        if !actions.is_empty() {
+            info!("Autotiled {} voxels", actions.len() / 2);
+            history.push(EditorAction::Batch {
+                description: AUTOTILE_DESCRIPTION.to_string(),
+                actions,
+            });
+        }
+    }
+    if reload {
+        match AutotileRules::load(Path::new(AUTOTILE_RULES_PATH)) {
+            Ok(loaded) => {
+                info!("Reloaded {} autotile rules", loaded.rules.len());
+                *rules = loaded;
+            }
+            Err(e) => warn!("{}", e),
+        }
+    }
+    if !open {
+        editor_state.show_autotile = false;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+use super::*;
+use crate::systems::game::map::format::{SubVoxelPattern, VoxelData};
+
+fn rule(from: VoxelType, to: VoxelType, when: AutotileCondition) -> AutotileRule {
+    AutotileRule {
+        name: format!("{:?} to {:?}", from, to),
+        from,
+        to,
+        when,
+        enabled: true,
+    }
+}
+
+/// A grass column three high next to a stone block beside lava.
+fn hillside() -> EditorState {
+    let mut state = EditorState::default();
+    let voxels = vec![
+        VoxelData::new((0, 0, 0), VoxelType::Grass, Some(SubVoxelPattern::Full)),
+        VoxelData::new((0, 1, 0), VoxelType::Grass, Some(SubVoxelPattern::Full)),
+        VoxelData::new((0, 2, 0), VoxelType::Grass, Some(SubVoxelPattern::Full)),
+        VoxelData::new((1, 0, 0), VoxelType::Stone, Some(SubVoxelPattern::Full)),
+        VoxelData::new((2, 0, 0), VoxelType::Lava, Some(SubVoxelPattern::Full)),
+    ];
+    state.current_map.world.voxels = voxels.into();
+    state
+}
+
+fn buried_grass() -> AutotileRules {
+    AutotileRules {
+        rules: vec![rule(
+            VoxelType::Grass,
+            VoxelType::Dirt,
+            AutotileCondition::Not(Box::new(AutotileCondition::Above(VoxelType::Air))),
+        )],
+    }
+}
+
+fn type_at(state: &EditorState, pos: (i32, i32, i32)) -> VoxelType {
+    state.current_map.world.voxels.get(pos).unwrap().voxel_type
+}
+
+#[test]
+fn conditions_look_at_the_right_neighbours() {
+    let state = hillside();
+    let voxels = &state.current_map.world.voxels;
+    assert!(AutotileCondition::Above(VoxelType::Grass).holds(voxels, (0, 0, 0)));
+    assert!(AutotileCondition::Above(VoxelType::Air).holds(voxels, (0, 2, 0)));
+    assert!(AutotileCondition::Below(VoxelType::Air).holds(voxels, (0, 0, 0)));
+    assert!(AutotileCondition::Beside(VoxelType::Lava).holds(voxels, (1, 0, 0)));
+    assert!(!AutotileCondition::Beside(VoxelType::Grass).holds(voxels, (0, 1, 0)));
+    assert!(AutotileCondition::Adjacent(VoxelType::Grass).holds(voxels, (0, 1, 0)));
+    assert!(AutotileCondition::All(vec![
+        AutotileCondition::Beside(VoxelType::Stone),
+        AutotileCondition::Above(VoxelType::Grass),
+    ])
+    .holds(voxels, (0, 0, 0)));
+}
+
+#[test]
+fn a_run_sees_the_map_as_it_was_before_it() {
+    let state = hillside();
+    let positions = state.current_map.world.voxels.iter().map(|voxel| voxel.pos);
+    let changes = buried_grass().changes(&state.current_map.world.voxels, positions);
+    assert_eq!(
+        changes,
+        vec![((0, 0, 0), VoxelType::Dirt), ((0, 1, 0), VoxelType::Dirt)]
+    );
+}
+
+#[test]
+fn the_first_enabled_matching_rule_wins() {
+    let state = hillside();
+    let voxels = &state.current_map.world.voxels;
+    let mut rules = buried_grass();
+    rules.rules.insert(
+        0,
+        rule(
+            VoxelType::Grass,
+            VoxelType::Stone,
+            AutotileCondition::Beside(VoxelType::Stone),
+        ),
+    );
+    assert_eq!(rules.result_for(voxels, (0, 0, 0)), Some(VoxelType::Stone));
+    assert_eq!(rules.result_for(voxels, (0, 1, 0)), Some(VoxelType::Dirt));
+
+    rules.rules[0].enabled = false;
+    assert_eq!(rules.result_for(voxels, (0, 0, 0)), Some(VoxelType::Dirt));
+    assert_eq!(rules.result_for(voxels, (5, 5, 5)), None);
+}
+
+#[test]
+fn applying_records_an_undoable_repaint() {
+    let mut state = hillside();
+    let changes = vec![((0, 0, 0), VoxelType::Dirt)];
+    let actions = apply_autotile(&mut state, &changes);
+    assert_eq!(type_at(&state, (0, 0, 0)), VoxelType::Dirt);
+    assert!(state.is_modified);
+    assert!(matches!(
+        &actions[..],
+        [
+            EditorAction::RemoveVoxel { data: old, .. },
+            EditorAction::PlaceVoxel { data: new, .. },
+        ] if old.voxel_type == VoxelType::Grass && new.voxel_type == VoxelType::Dirt
+    ));
+}
+
+#[test]
+fn edits_touch_their_voxels_and_neighbours() {
+    let action = EditorAction::Batch {
+        description: "Paint".to_string(),
+        actions: vec![EditorAction::PlaceVoxel {
+            pos: (0, 0, 0),
+            data: VoxelData::new((0, 0, 0), VoxelType::Grass, Some(SubVoxelPattern::Full)),
+        }],
+    };
+    let mut positions = HashSet::new();
+    touched_positions(&action, &mut positions);
+    assert_eq!(positions.len(), 7);
+    assert!(positions.contains(&(0, -1, 0)));
+    assert!(positions.contains(&(0, 0, 1)));
+}
+
+#[test]
+fn shipped_rules_parse() {
+    let rules = AutotileRules::load(Path::new(AUTOTILE_RULES_PATH)).unwrap();
+    assert!(!rules.rules.is_empty());
+}
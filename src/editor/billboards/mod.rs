@@ -0,0 +1,196 @@
+//! Camera-facing entity icons for the editor viewport.
+//!
+//! Each entity is drawn as a small textured quad with a procedurally drawn
+//! icon for its [`EntityType`]: a colored disc with a white glyph, plus a
+//! yellow ring when selected. [`face_camera_billboards`] turns every icon
+//! towards the editor camera each frame and scales it with distance, clamped
+//! so icons stay readable far away without swamping the view up close.
+//! Selection picking uses the same [`billboard_world_size`] so the clickable
+//! area matches what is drawn.
+
+use crate::editor::camera::EditorCamera;
+use crate::editor::renderer::EditorEntityMarker;
+use crate::systems::game::map::format::EntityType;
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::collections::HashMap;
+
+/// Side length of the generated icon textures, in pixels.
+pub const ICON_SIZE: u32 = 32;
+
+/// Icon size in world units per unit of camera distance.
+const SIZE_PER_DISTANCE: f32 = 0.06;
+
+/// Smallest icon size in world units (close to the camera).
+const MIN_WORLD_SIZE: f32 = 0.5;
+
+/// Largest icon size in world units (far from the camera).
+const MAX_WORLD_SIZE: f32 = 2.5;
+
+/// World-space side length of an icon seen from `distance` away.
+pub fn billboard_world_size(distance: f32) -> f32 {
+    (distance * SIZE_PER_DISTANCE).clamp(MIN_WORLD_SIZE, MAX_WORLD_SIZE)
+}
+
+/// Background color of the icon disc for an entity type.
+fn icon_color(entity_type: EntityType) -> [u8; 3] {
+    match entity_type {
+        EntityType::PlayerSpawn => [40, 190, 70],
+        EntityType::Npc => [40, 120, 230],
+        EntityType::Enemy => [220, 45, 45],
+        EntityType::Item => [230, 190, 30],
+        EntityType::Trigger => [190, 60, 200],
+        EntityType::LightSource => [240, 150, 40],
+        EntityType::Door => [140, 90, 50],
+        EntityType::Teleporter => [40, 170, 210],
+        EntityType::Goal => [70, 190, 90],
+    }
+}
+
+/// Whether the white glyph covers point (`u`, `v`) in [-1, 1] icon space (v up).
+fn glyph_covers(entity_type: EntityType, u: f32, v: f32) -> bool {
+    match entity_type {
+        // Person: head and shoulders
+        EntityType::PlayerSpawn => {
+            let head = u * u + (v - 0.28).powi(2) < 0.2 * 0.2;
+            let body = u.abs() < 0.32 - (v + 0.1).max(0.0) * 0.5 && (-0.5..0.02).contains(&v);
+            head || body
+        }
+        // Speech bubble dots
+        EntityType::Npc => [-0.35, 0.0, 0.35]
+            .iter()
+            .any(|&cx| (u - cx).powi(2) + v * v < 0.13 * 0.13),
+        // Cross
+        EntityType::Enemy => {
+            u.abs() < 0.45 && v.abs() < 0.45 && ((u - v).abs() < 0.16 || (u + v).abs() < 0.16)
+        }
+        // Diamond gem
+        EntityType::Item => u.abs() + v.abs() < 0.48,
+        // Hollow square
+        EntityType::Trigger => (0.3..0.46).contains(&u.abs().max(v.abs())),
+        // Sun: core with eight rays
+        EntityType::LightSource => {
+            let r = (u * u + v * v).sqrt();
+            let angle = v.atan2(u).rem_euclid(std::f32::consts::FRAC_PI_4);
+            let on_ray = angle.min(std::f32::consts::FRAC_PI_4 - angle) < 0.18;
+            r < 0.24 || ((0.34..0.55).contains(&r) && on_ray)
+        }
+        // Door panel with a knob cut out
+        EntityType::Door => {
+            let panel = u.abs() < 0.28 && (-0.5..0.5).contains(&v);
+            let knob = (u - 0.13).powi(2) + v * v < 0.07 * 0.07;
+            panel && !knob
+        }
+        // Ring around a dot
+        EntityType::Teleporter => {
+            let r = (u * u + v * v).sqrt();
+            (0.3..0.46).contains(&r) || r < 0.12
+        }
+        // Flag on a pole
+        EntityType::Goal => {
+            let pole = (-0.34..-0.22).contains(&u) && (-0.5..0.5).contains(&v);
+            let flag = (-0.22..0.4).contains(&u) && (0.02..0.5).contains(&v);
+            pole || flag
+        }
+    }
+}
+
+/// RGBA pixel of an icon at (`x`, `y`), with `y` growing downwards.
+fn icon_pixel(entity_type: EntityType, selected: bool, x: u32, y: u32) -> [u8; 4] {
+    let half = ICON_SIZE as f32 / 2.0;
+    let u = (x as f32 + 0.5 - half) / half;
+    let v = (half - y as f32 - 0.5) / half;
+    let r = (u * u + v * v).sqrt();
+
+    if r > 1.0 {
+        return [0, 0, 0, 0];
+    }
+    if r > 0.84 {
+        // Outer ring: yellow when selected, dark otherwise
+        return if selected {
+            [255, 230, 40, 255]
+        } else {
+            [20, 20, 20, 200]
+        };
+    }
+    if glyph_covers(entity_type, u, v) {
+        return [255, 255, 255, 255];
+    }
+    let [red, green, blue] = icon_color(entity_type);
+    [red, green, blue, 235]
+}
+
+/// Build the icon texture for an entity type.
+pub fn entity_icon_image(entity_type: EntityType, selected: bool) -> Image {
+    let data: Vec<u8> = (0..ICON_SIZE)
+        .flat_map(|y| (0..ICON_SIZE).map(move |x| (x, y)))
+        .flat_map(|(x, y)| icon_pixel(entity_type, selected, x, y))
+        .collect();
+    Image::new(
+        Extent3d {
+            width: ICON_SIZE,
+            height: ICON_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Quad mesh and per-type materials shared by all entity icons.
+pub struct EntityIconAssets {
+    pub quad: Handle<Mesh>,
+    materials: HashMap<(EntityType, bool), Handle<StandardMaterial>>,
+}
+
+impl EntityIconAssets {
+    /// Create the shared quad; icon materials are built on first use.
+    pub fn new(meshes: &mut Assets<Mesh>) -> Self {
+        Self {
+            quad: meshes.add(Rectangle::new(1.0, 1.0)),
+            materials: HashMap::new(),
+        }
+    }
+
+    /// Material showing the icon for `entity_type`, highlighted when `selected`.
+    pub fn material(
+        &mut self,
+        entity_type: EntityType,
+        selected: bool,
+        images: &mut Assets<Image>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        self.materials
+            .entry((entity_type, selected))
+            .or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color_texture: Some(images.add(entity_icon_image(entity_type, selected))),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true, // Icons stay readable regardless of lighting
+                    cull_mode: None,
+                    ..default()
+                })
+            })
+            .clone()
+    }
+}
+
+/// Turn entity icons towards the editor camera and scale them with distance.
+pub fn face_camera_billboards(
+    camera: Single<&GlobalTransform, With<EditorCamera>>,
+    mut markers: Query<&mut Transform, With<EditorEntityMarker>>,
+) {
+    let camera_position = camera.translation();
+    let camera_rotation = camera.rotation();
+    for mut transform in &mut markers {
+        let distance = camera_position.distance(transform.translation);
+        transform.rotation = camera_rotation;
+        transform.scale = Vec3::splat(billboard_world_size(distance));
+    }
+}
+
+#[cfg(test)]
+mod tests;
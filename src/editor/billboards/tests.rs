@@ -0,0 +1,60 @@
+use super::*;
+
+const ALL_TYPES: [EntityType; 9] = [
+    EntityType::PlayerSpawn,
+    EntityType::Npc,
+    EntityType::Enemy,
+    EntityType::Item,
+    EntityType::Trigger,
+    EntityType::LightSource,
+    EntityType::Door,
+    EntityType::Teleporter,
+    EntityType::Goal,
+];
+
+fn pixels(entity_type: EntityType, selected: bool) -> Vec<u8> {
+    entity_icon_image(entity_type, selected).data.unwrap()
+}
+
+#[test]
+fn size_grows_with_distance_and_is_clamped() {
+    assert_eq!(billboard_world_size(0.0), MIN_WORLD_SIZE);
+    assert_eq!(billboard_world_size(1000.0), MAX_WORLD_SIZE);
+    assert!(billboard_world_size(20.0) > billboard_world_size(10.0));
+}
+
+#[test]
+fn every_entity_type_has_a_distinct_icon() {
+    for (i, a) in ALL_TYPES.iter().enumerate() {
+        let image = pixels(*a, false);
+        assert_eq!(image.len(), (ICON_SIZE * ICON_SIZE * 4) as usize);
+        for b in &ALL_TYPES[i + 1..] {
+            assert_ne!(
+                image,
+                pixels(*b, false),
+                "{:?} and {:?} look the same",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn icons_are_round_with_transparent_corners() {
+    let image = pixels(EntityType::Npc, false);
+    // Top-left corner pixel is fully transparent; the center is opaque
+    assert_eq!(image[3], 0);
+    let center = ((ICON_SIZE / 2 * ICON_SIZE + ICON_SIZE / 2) * 4) as usize;
+    assert!(image[center + 3] > 200);
+}
+
+#[test]
+fn selected_icon_gets_a_yellow_ring() {
+    let normal = pixels(EntityType::Item, false);
+    let selected = pixels(EntityType::Item, true);
+    assert_ne!(normal, selected);
+    // Leftmost pixel on the middle row sits on the ring
+    let ring = ((ICON_SIZE / 2 * ICON_SIZE) * 4) as usize;
+    assert_eq!(&selected[ring..ring + 3], &[255, 230, 40]);
+}
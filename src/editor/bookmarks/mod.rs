@@ -0,0 +1,139 @@
+//! Named camera bookmarks.
+//!
+//! Each map has nine bookmark slots holding a camera position and
+//! orientation. Ctrl+Shift+1..9 stores the current view in a slot and
+//! Ctrl+1..9 jumps back to it (Cmd on macOS); the View menu lists, renames
+//! and clears them. Bookmarks live in [`EditorState`], travel with their
+//! tab and are saved with the map's [session](crate::editor::session).
+
+use crate::editor::camera::EditorCamera;
+use crate::editor::state::EditorState;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Number of bookmark slots, one per digit key
+pub const BOOKMARK_SLOTS: usize = 9;
+
+/// Keys selecting each bookmark slot, in slot order
+pub const BOOKMARK_KEYS: [KeyCode; BOOKMARK_SLOTS] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// A saved camera view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub position: (f32, f32, f32),
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl CameraBookmark {
+    /// Bookmark the current view of `camera`
+    pub fn capture(name: String, camera: &EditorCamera) -> Self {
+        Self {
+            name,
+            position: camera.position.into(),
+            yaw: camera.yaw,
+            pitch: camera.pitch,
+        }
+    }
+
+    /// Move `camera` to the bookmarked view
+    pub fn apply_to(&self, camera: &mut EditorCamera) {
+        camera.position = self.position.into();
+        camera.yaw = self.yaw;
+        camera.pitch = self.pitch.clamp(-1.5, 1.5);
+    }
+}
+
+/// The bookmark slots of one map
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmarks {
+    slots: [Option<CameraBookmark>; BOOKMARK_SLOTS],
+}
+
+impl CameraBookmarks {
+    /// Bookmark in `slot` (0-based), if one is stored
+    pub fn get(&self, slot: usize) -> Option<&CameraBookmark> {
+        self.slots.get(slot).and_then(Option::as_ref)
+    }
+
+    /// Mutable bookmark in `slot`, for renaming
+    pub fn get_mut(&mut self, slot: usize) -> Option<&mut CameraBookmark> {
+        self.slots.get_mut(slot).and_then(Option::as_mut)
+    }
+
+    /// Store the view of `camera` in `slot`, keeping the name of a bookmark
+    /// already there.
+    pub fn store(&mut self, slot: usize, camera: &EditorCamera) {
+        let Some(entry) = self.slots.get_mut(slot) else {
+            return;
+        };
+        let name = entry
+            .take()
+            .map(|bookmark| bookmark.name)
+            .unwrap_or_else(|| format!("Bookmark {}", slot + 1));
+        *entry = Some(CameraBookmark::capture(name, camera));
+    }
+
+    /// Remove the bookmark in `slot`
+    pub fn clear(&mut self, slot: usize) {
+        if let Some(entry) = self.slots.get_mut(slot) {
+            *entry = None;
+        }
+    }
+
+    /// Whether no slot holds a bookmark
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+}
+
+/// Requests to store or recall a bookmark slot (0-based)
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookmarkEvent {
+    Store(usize),
+    Recall(usize),
+}
+
+/// Bookmark slot whose digit key was just pressed, if any
+pub fn just_pressed_slot(keyboard: &ButtonInput<KeyCode>) -> Option<usize> {
+    BOOKMARK_KEYS
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+}
+
+/// System to store the camera view in, or move the camera to, bookmarks
+pub fn handle_bookmark_events(
+    mut events: MessageReader<BookmarkEvent>,
+    mut editor_state: ResMut<EditorState>,
+    mut camera: Single<&mut EditorCamera>,
+) {
+    for event in events.read() {
+        match *event {
+            BookmarkEvent::Store(slot) => {
+                editor_state.camera_bookmarks.store(slot, &camera);
+                info!("Stored camera bookmark {}", slot + 1);
+            }
+            BookmarkEvent::Recall(slot) => match editor_state.camera_bookmarks.get(slot) {
+                Some(bookmark) => {
+                    bookmark.apply_to(&mut camera);
+                    info!("Jumped to camera bookmark {}: {}", slot + 1, bookmark.name);
+                }
+                None => info!("No camera bookmark in slot {}", slot + 1),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+use super::*;
+
+fn camera_at(position: Vec3, yaw: f32, pitch: f32) -> EditorCamera {
+    EditorCamera {
+        position,
+        yaw,
+        pitch,
+        ..default()
+    }
+}
+
+#[test]
+fn stored_bookmarks_restore_the_view() {
+    let mut bookmarks = CameraBookmarks::default();
+    assert!(bookmarks.is_empty());
+    bookmarks.store(2, &camera_at(Vec3::new(10.0, 4.0, -3.0), 0.75, -0.4));
+
+    let bookmark = bookmarks.get(2).unwrap();
+    assert_eq!(bookmark.name, "Bookmark 3");
+    assert!(bookmarks.get(0).is_none());
+
+    let mut camera = EditorCamera::default();
+    bookmark.apply_to(&mut camera);
+    assert_eq!(camera.position, Vec3::new(10.0, 4.0, -3.0));
+    assert_eq!(camera.yaw, 0.75);
+    assert_eq!(camera.pitch, -0.4);
+}
+
+#[test]
+fn storing_again_keeps_the_name() {
+    let mut bookmarks = CameraBookmarks::default();
+    bookmarks.store(0, &EditorCamera::default());
+    bookmarks.get_mut(0).unwrap().name = "Boss arena".to_string();
+
+    bookmarks.store(0, &camera_at(Vec3::splat(50.0), 0.0, 0.0));
+    let bookmark = bookmarks.get(0).unwrap();
+    assert_eq!(bookmark.name, "Boss arena");
+    assert_eq!(bookmark.position, (50.0, 50.0, 50.0));
+
+    bookmarks.clear(0);
+    assert!(bookmarks.is_empty());
+}
+
+#[test]
+fn out_of_range_slots_are_ignored() {
+    let mut bookmarks = CameraBookmarks::default();
+    bookmarks.store(BOOKMARK_SLOTS, &EditorCamera::default());
+    bookmarks.clear(BOOKMARK_SLOTS);
+    assert!(bookmarks.is_empty());
+    assert!(bookmarks.get(BOOKMARK_SLOTS).is_none());
+}
+
+#[test]
+fn digit_keys_select_slots() {
+    let mut keyboard = ButtonInput::<KeyCode>::default();
+    assert_eq!(just_pressed_slot(&keyboard), None);
+    keyboard.press(KeyCode::Digit4);
+    assert_eq!(just_pressed_slot(&keyboard), Some(3));
+    keyboard.clear();
+    keyboard.press(KeyCode::Digit0);
+    assert_eq!(just_pressed_slot(&keyboard), None);
+}
@@ -1,8 +1,21 @@
 //! Editor camera system with first-person fly controls (Minecraft Creative mode style).
 
+use bevy::input::gestures::{PanGesture, PinchGesture, RotationGesture};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
 use bevy_egui::EguiContexts;
 
+use crate::editor::preferences::{EditorPreferences, GestureSensitivity};
+
+/// Voxels the camera moves per line of mouse wheel scrolling
+const ZOOM_PER_LINE: f32 = 1.5;
+/// Voxels the camera moves per pixel of touchpad scrolling towards the view
+const ZOOM_PER_PIXEL: f32 = 0.05;
+/// Voxels the camera moves per unit of pinch magnification
+const ZOOM_PER_PINCH: f32 = 25.0;
+/// Voxels the camera slides per pixel of touchpad panning
+const PAN_PER_PIXEL: f32 = 0.02;
+
 /// Resource to track camera control state
 #[derive(Resource, Default)]
 pub struct GamepadCameraState {
@@ -140,6 +153,92 @@ impl EditorCamera {
     pub fn rotation(&self) -> Vec2 {
         Vec2::new(self.yaw, self.pitch)
     }
+
+    /// Apply a scroll or touchpad gesture
+    pub fn apply_gesture(&mut self, motion: GestureMotion) {
+        let delta =
+            self.forward() * motion.zoom + self.right() * motion.pan.x + Vec3::Y * motion.pan.y;
+        self.move_by(delta);
+        self.yaw += motion.turn;
+    }
+}
+
+/// Camera motion from scrolling and touchpad gestures.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GestureMotion {
+    /// Distance to move along the view direction (positive moves forward)
+    pub zoom: f32,
+    /// Distance to slide right (x) and up (y)
+    pub pan: Vec2,
+    /// Yaw change in radians
+    pub turn: f32,
+}
+
+impl GestureMotion {
+    /// Motion for a mouse wheel or two-finger scroll.
+    ///
+    /// Vertical scrolling zooms and horizontal scrolling pans sideways; with
+    /// Shift held both axes pan, grabbing the scene like a drag.
+    pub fn from_scroll(unit: MouseScrollUnit, delta: Vec2, shift: bool) -> Self {
+        let (zoom_scale, pan_scale) = match unit {
+            MouseScrollUnit::Line => (ZOOM_PER_LINE, ZOOM_PER_LINE),
+            MouseScrollUnit::Pixel => (ZOOM_PER_PIXEL, PAN_PER_PIXEL),
+        };
+        if shift {
+            Self {
+                pan: -delta * pan_scale,
+                ..default()
+            }
+        } else {
+            Self {
+                zoom: delta.y * zoom_scale,
+                pan: Vec2::new(-delta.x * pan_scale, 0.0),
+                ..default()
+            }
+        }
+    }
+
+    /// Motion for a pinch; spreading the fingers zooms in.
+    pub fn from_pinch(magnification: f32) -> Self {
+        Self {
+            zoom: magnification * ZOOM_PER_PINCH,
+            ..default()
+        }
+    }
+
+    /// Motion for a two-finger rotation (radians, counterclockwise positive).
+    pub fn from_rotation(radians: f32) -> Self {
+        Self {
+            turn: radians,
+            ..default()
+        }
+    }
+
+    /// Motion for a two-finger pan gesture in screen pixels.
+    pub fn from_pan(delta: Vec2) -> Self {
+        Self {
+            pan: Vec2::new(-delta.x, delta.y) * PAN_PER_PIXEL,
+            ..default()
+        }
+    }
+
+    /// Combine two motions from the same frame.
+    pub fn combine(self, other: Self) -> Self {
+        Self {
+            zoom: self.zoom + other.zoom,
+            pan: self.pan + other.pan,
+            turn: self.turn + other.turn,
+        }
+    }
+
+    /// Scale the motion by the user's gesture preferences.
+    pub fn scaled(self, sensitivity: &GestureSensitivity) -> Self {
+        Self {
+            zoom: self.zoom * sensitivity.zoom,
+            pan: self.pan * sensitivity.pan,
+            turn: self.turn * sensitivity.rotate,
+        }
+    }
 }
 
 /// System to update camera transform from EditorCamera state
@@ -170,9 +269,13 @@ pub fn handle_camera_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     mut gamepad_state: ResMut<GamepadCameraState>,
+    ui_navigation: Res<crate::editor::controller::GamepadUiNavigation>,
+    radial_menu: Res<crate::editor::controller::RadialMenu>,
     editor_state: Res<crate::editor::state::EditorState>,
+    voxel_index: Res<crate::editor::cursor::VoxelIndex>,
     mut contexts: EguiContexts,
     mut cursor: Single<&mut bevy::window::CursorOptions>,
+    layout: Res<crate::editor::viewports::ViewportLayout>,
     time: Res<Time>,
 ) {
     let dt = time.delta_secs();
@@ -229,12 +332,7 @@ pub fn handle_camera_input(
         };
 
         // Raycast against voxels to find what we're looking at
-        if let Some((voxel_pos, hit_info)) =
-            crate::editor::cursor::raycasting::find_closest_voxel_intersection_with_face(
-                &editor_state,
-                &ray,
-            )
-        {
+        if let Some((voxel_pos, hit_info)) = voxel_index.raycast(&ray) {
             let placement_pos = (
                 voxel_pos.0 + hit_info.face_normal.x as i32,
                 voxel_pos.1 + hit_info.face_normal.y as i32,
@@ -248,12 +346,13 @@ pub fn handle_camera_input(
             ));
             gamepad_state.action_grid_pos = Some(placement_pos);
             gamepad_state.target_voxel_pos = Some(voxel_pos);
-        } else if let Some(ground_pos) =
-            crate::editor::cursor::raycasting::intersect_ground_plane(&ray)
-        {
-            let grid_pos = (ground_pos.x.round() as i32, 0, ground_pos.z.round() as i32);
-            gamepad_state.action_position =
-                Some(Vec3::new(grid_pos.0 as f32, 0.0, grid_pos.2 as f32));
+        } else if let Some(plane_pos) = editor_state.work_plane.intersect(&ray) {
+            let grid_pos = editor_state.work_plane.grid_pos(plane_pos);
+            gamepad_state.action_position = Some(Vec3::new(
+                grid_pos.0 as f32,
+                grid_pos.1 as f32,
+                grid_pos.2 as f32,
+            ));
             gamepad_state.action_grid_pos = Some(grid_pos);
             gamepad_state.target_voxel_pos = None;
         } else {
@@ -268,9 +367,11 @@ pub fn handle_camera_input(
         }
     }
 
-    // Check if pointer is over UI
+    // Check if pointer is over UI or another viewport (which has its own camera)
     let ctx = contexts.ctx_mut().expect("egui context");
-    let pointer_over_ui = ctx.is_pointer_over_area() || ctx.is_using_pointer();
+    let pointer_over_ui = ctx.is_pointer_over_area()
+        || ctx.is_using_pointer()
+        || layout.hovered != crate::editor::viewports::ViewportKind::Perspective;
     let wants_keyboard = ctx.wants_keyboard_input();
 
     // === WASD Movement (keyboard) ===
@@ -348,7 +449,8 @@ pub fn handle_camera_input(
             .unwrap_or(0.0);
         let right_stick = Vec2::new(right_x, right_y);
 
-        if right_stick.length() > deadzone {
+        // An open radial menu takes the right stick
+        if right_stick.length() > deadzone && !radial_menu.is_open() {
             let scaled = (right_stick.length() - deadzone) / (1.0 - deadzone);
             let look_input = right_stick.normalize() * scaled;
 
@@ -377,6 +479,11 @@ pub fn handle_camera_input(
             camera.move_by(movement);
         }
 
+        // The face buttons and D-pad drive the egui panels in UI navigation mode
+        if ui_navigation.active {
+            continue;
+        }
+
         // A button = fly up, B button = fly down
         if gamepad.pressed(bevy::input::gamepad::GamepadButton::South) {
             camera.position.y += move_speed * dt;
@@ -412,6 +519,7 @@ pub fn handle_camera_input(
 
 /// System to handle gamepad voxel actions (RT to execute tool action, LT to remove)
 /// Note: Mouse actions are handled by the tool systems in tools/ module
+#[allow(clippy::too_many_arguments)]
 pub fn handle_gamepad_voxel_actions(
     gamepad_state: Res<GamepadCameraState>,
     gamepads: Query<&Gamepad>,
@@ -466,20 +574,16 @@ pub fn handle_gamepad_voxel_actions(
                 voxel_type,
                 pattern,
             } => {
-                let exists = editor_state
-                    .current_map
-                    .world
-                    .voxels
-                    .iter()
-                    .any(|v| v.pos == grid_pos);
-
-                if !exists {
+                let exists = editor_state.current_map.world.voxels.contains(grid_pos);
+
+                if !exists && editor_state.allows_placement_at(grid_pos) {
                     let voxel_data = crate::systems::game::map::format::VoxelData {
                         pos: grid_pos,
                         voxel_type,
                         pattern: Some(pattern),
-                        rotation: None,
+                        rotation: editor_state.placement_rotation(),
                         rotation_state: None,
+                        group: None,
                     };
 
                     editor_state
@@ -501,14 +605,11 @@ pub fn handle_gamepad_voxel_actions(
             crate::editor::state::EditorTool::VoxelRemove => {
                 let remove_pos = gamepad_state.target_voxel_pos.unwrap_or(grid_pos);
 
-                if let Some(idx) = editor_state
-                    .current_map
-                    .world
-                    .voxels
-                    .iter()
-                    .position(|v| v.pos == remove_pos)
+                if !editor_state.allows_edit_at(remove_pos) {
+                    *cooldown = 0.15;
+                } else if let Some(removed) =
+                    editor_state.current_map.world.voxels.remove(remove_pos)
                 {
-                    let removed = editor_state.current_map.world.voxels.remove(idx);
                     editor_state.mark_modified();
                     render_events.write(crate::editor::renderer::RenderMapEvent);
 
@@ -524,6 +625,7 @@ pub fn handle_gamepad_voxel_actions(
                 use crate::systems::game::map::format::EntityData;
 
                 let entity_data = EntityData {
+                    id: editor_state.current_map.next_entity_id(),
                     entity_type,
                     position: (
                         grid_pos.0 as f32 + 0.5,
@@ -554,8 +656,13 @@ pub fn handle_gamepad_voxel_actions(
                     *cooldown = 0.2;
                 }
             }
-            crate::editor::state::EditorTool::Camera => {
-                // Camera tool has no action
+            crate::editor::state::EditorTool::Camera
+            | crate::editor::state::EditorTool::Sculpt { .. }
+            | crate::editor::state::EditorTool::Paint { .. }
+            | crate::editor::state::EditorTool::Spline { .. }
+            | crate::editor::state::EditorTool::Staircase { .. }
+            | crate::editor::state::EditorTool::Custom { .. } => {
+                // Brush, path and plugin tools have no gamepad action
             }
         }
     }
@@ -564,14 +671,9 @@ pub fn handle_gamepad_voxel_actions(
     if secondary_action {
         let remove_pos = gamepad_state.target_voxel_pos.unwrap_or(grid_pos);
 
-        if let Some(idx) = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .position(|v| v.pos == remove_pos)
-        {
-            let removed = editor_state.current_map.world.voxels.remove(idx);
+        if !editor_state.allows_edit_at(remove_pos) {
+            *cooldown = 0.15;
+        } else if let Some(removed) = editor_state.current_map.world.voxels.remove(remove_pos) {
             editor_state.mark_modified();
             render_events.write(crate::editor::renderer::RenderMapEvent);
 
@@ -585,9 +687,10 @@ pub fn handle_gamepad_voxel_actions(
     }
 }
 
-/// System to handle RB/LB and Q/E for cycling through patterns/entities
+/// System to handle [ and ] for cycling through patterns/entities
+///
+/// The controller picks patterns and entities from the radial menu instead.
 pub fn handle_gamepad_tool_cycling(
-    gamepads: Query<&Gamepad>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mut editor_state: ResMut<crate::editor::state::EditorState>,
     mut contexts: EguiContexts,
@@ -607,16 +710,6 @@ pub fn handle_gamepad_tool_cycling(
     let mut next_pressed = false;
     let mut prev_pressed = false;
 
-    // Gamepad bumpers
-    for gamepad in gamepads.iter() {
-        if gamepad.just_pressed(bevy::input::gamepad::GamepadButton::RightTrigger) {
-            next_pressed = true;
-        }
-        if gamepad.just_pressed(bevy::input::gamepad::GamepadButton::LeftTrigger) {
-            prev_pressed = true;
-        }
-    }
-
     // [ / ] keys (only when UI doesn't want keyboard and modifier not held)
     if !wants_keyboard && !crate::editor::shortcuts::modifier_pressed(&keyboard) {
         if keyboard.just_pressed(KeyCode::BracketRight) {
@@ -658,13 +751,16 @@ pub fn handle_gamepad_tool_cycling(
         crate::editor::state::EditorTool::EntityPlace { entity_type } => {
             use crate::systems::game::map::format::EntityType;
 
-            const ENTITIES: [EntityType; 6] = [
+            const ENTITIES: [EntityType; 9] = [
                 EntityType::PlayerSpawn,
                 EntityType::Npc,
                 EntityType::Enemy,
                 EntityType::Item,
                 EntityType::Trigger,
                 EntityType::LightSource,
+                EntityType::Door,
+                EntityType::Teleporter,
+                EntityType::Goal,
             ];
 
             let current_idx = ENTITIES.iter().position(|e| e == entity_type).unwrap_or(0);
@@ -680,5 +776,49 @@ pub fn handle_gamepad_tool_cycling(
     }
 }
 
+/// System moving the camera with the mouse wheel and touchpad gestures
+/// (two-finger scroll, pinch, rotate and pan) over the perspective view.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_camera_gestures(
+    mut camera: Single<&mut EditorCamera>,
+    mut mouse_wheel: MessageReader<MouseWheel>,
+    mut pinch: MessageReader<PinchGesture>,
+    mut rotation: MessageReader<RotationGesture>,
+    mut pan: MessageReader<PanGesture>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    preferences: Res<EditorPreferences>,
+    layout: Res<crate::editor::viewports::ViewportLayout>,
+    mut contexts: EguiContexts,
+) {
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let motion = mouse_wheel
+        .read()
+        .map(|event| GestureMotion::from_scroll(event.unit, Vec2::new(event.x, event.y), shift))
+        .chain(pinch.read().map(|event| GestureMotion::from_pinch(event.0)))
+        .chain(
+            rotation
+                .read()
+                .map(|event| GestureMotion::from_rotation(event.0)),
+        )
+        .chain(pan.read().map(|event| GestureMotion::from_pan(event.0)))
+        .fold(GestureMotion::default(), GestureMotion::combine);
+    if motion == GestureMotion::default() {
+        return;
+    }
+
+    // Scrolling over panels or another viewport belongs to them
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if ctx.is_pointer_over_area()
+        || ctx.is_using_pointer()
+        || layout.hovered != crate::editor::viewports::ViewportKind::Perspective
+    {
+        return;
+    }
+
+    camera.apply_gesture(motion.scaled(&preferences.gestures));
+}
+
 #[cfg(test)]
 mod tests;
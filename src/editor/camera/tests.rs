@@ -1,3 +1,5 @@
+#![allow(clippy::field_reassign_with_default)]
+
 use super::*;
 
 #[test]
@@ -106,7 +108,7 @@ fn test_camera_reset() {
 
     // Modify camera state
     camera.position = Vec3::new(100.0, 100.0, 100.0);
-    camera.yaw = 3.14;
+    camera.yaw = 2.5;
     camera.pitch = 1.0;
 
     camera.reset();
@@ -266,3 +268,52 @@ fn test_entity_cycling_finds_current() {
         assert_eq!(found_idx, Some(idx));
     }
 }
+
+#[test]
+fn test_vertical_scroll_zooms_and_horizontal_pans() {
+    let motion = GestureMotion::from_scroll(MouseScrollUnit::Line, Vec2::new(0.0, 2.0), false);
+    assert_eq!(motion.zoom, 3.0);
+    assert_eq!(motion.pan, Vec2::ZERO);
+
+    let motion = GestureMotion::from_scroll(MouseScrollUnit::Pixel, Vec2::new(50.0, 0.0), false);
+    assert_eq!(motion.zoom, 0.0);
+    assert!((motion.pan.x + 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_shift_scroll_pans_both_axes() {
+    let motion = GestureMotion::from_scroll(MouseScrollUnit::Pixel, Vec2::new(50.0, -100.0), true);
+    assert_eq!(motion.zoom, 0.0);
+    assert!((motion.pan - Vec2::new(-1.0, 2.0)).length() < 1e-5);
+}
+
+#[test]
+fn test_gesture_sensitivity_scales_each_motion() {
+    let motion = GestureMotion::from_pinch(0.1)
+        .combine(GestureMotion::from_rotation(0.2))
+        .combine(GestureMotion::from_pan(Vec2::new(0.0, 50.0)));
+    let sensitivity = GestureSensitivity {
+        zoom: 2.0,
+        pan: 0.5,
+        rotate: 0.0,
+    };
+
+    let scaled = motion.scaled(&sensitivity);
+    assert!((scaled.zoom - 5.0).abs() < 1e-5);
+    assert!((scaled.pan.y - 0.5).abs() < 1e-5);
+    assert_eq!(scaled.turn, 0.0);
+}
+
+#[test]
+fn test_apply_gesture_zooms_along_view() {
+    let mut camera = EditorCamera::default();
+    let start = camera.position;
+    let forward = camera.forward();
+
+    camera.apply_gesture(GestureMotion {
+        zoom: 2.0,
+        ..Default::default()
+    });
+
+    assert!((camera.position - (start + forward * 2.0)).length() < 1e-5);
+}
@@ -0,0 +1,456 @@
+//! Camera sequence timeline: author and preview cinematic camera sequences.
+//!
+//! The "Camera Sequences" window (File menu) lists the map's sequences and
+//! the keyframes of the selected one. Keyframes are captured from the editor
+//! camera and can be fine-tuned by value. Scrubbing the timeline strip or
+//! pressing Play flies the editor camera along the path the game plays;
+//! stopping the preview puts the camera back where it was.
+
+use crate::editor::camera::EditorCamera;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::systems::game::cinematics::{sample_sequence, CameraPose};
+use crate::systems::game::map::format::{CameraEasing, CameraKeyframeData, CameraSequenceData};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// Duration given to new keyframes, in seconds
+pub const DEFAULT_KEYFRAME_SECONDS: f32 = 2.0;
+
+/// How far ahead of the editor camera a captured keyframe looks
+const LOOK_AT_DISTANCE: f32 = 10.0;
+
+/// Color of the selected sequence's path in the viewport
+const PATH_COLOR: Color = Color::srgb(1.0, 0.75, 0.2);
+
+/// Undo coalescing key for timeline edits
+const COALESCE_KEY: &str = "camera_sequences";
+
+/// Scrubbing or playback of the selected sequence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimelinePreview {
+    /// Seconds into the sequence
+    pub time: f32,
+    pub playing: bool,
+    /// Editor camera pose before the preview, restored when it stops
+    pub start: CameraPose,
+}
+
+/// Selection and preview state of the camera timeline window.
+#[derive(Resource, Debug, Default)]
+pub struct CameraTimeline {
+    /// Index of the sequence being edited
+    pub selected: usize,
+    pub preview: Option<TimelinePreview>,
+}
+
+/// Pose of the editor camera, looking [`LOOK_AT_DISTANCE`] ahead.
+pub fn editor_camera_pose(camera: &EditorCamera) -> CameraPose {
+    CameraPose {
+        position: camera.position,
+        look_at: camera.position + camera.forward() * LOOK_AT_DISTANCE,
+    }
+}
+
+/// A keyframe at the editor camera's current view.
+pub fn keyframe_from_camera(camera: &EditorCamera, duration: f32) -> CameraKeyframeData {
+    let pose = editor_camera_pose(camera);
+    CameraKeyframeData {
+        position: pose.position.into(),
+        look_at: pose.look_at.into(),
+        duration,
+        easing: CameraEasing::default(),
+    }
+}
+
+/// First `sequence_N` name not taken by `sequences`.
+pub fn new_sequence_name(sequences: &[CameraSequenceData]) -> String {
+    (1..)
+        .map(|n| format!("sequence_{}", n))
+        .find(|name| sequences.iter().all(|sequence| &sequence.name != name))
+        .expect("an unused sequence name")
+}
+
+/// Seconds into `sequence` at which each keyframe is reached.
+pub fn keyframe_times(sequence: &CameraSequenceData) -> Vec<f32> {
+    sequence
+        .keyframes
+        .iter()
+        .scan(0.0, |time, keyframe| {
+            *time += keyframe.duration.max(0.0);
+            Some(*time)
+        })
+        .collect()
+}
+
+/// Edit a `(f32, f32, f32)` with three drag values; returns true if changed.
+fn drag_vec3(ui: &mut egui::Ui, value: &mut (f32, f32, f32)) -> bool {
+    let mut changed = false;
+    for component in [&mut value.0, &mut value.1, &mut value.2] {
+        changed |= ui
+            .add(egui::DragValue::new(component).speed(0.1).max_decimals(2))
+            .changed();
+    }
+    changed
+}
+
+/// Strip showing the keyframes along the sequence with a draggable playhead.
+/// Returns the time the user clicked or dragged to.
+fn timeline_strip(ui: &mut egui::Ui, times: &[f32], time: Option<f32>) -> Option<f32> {
+    let duration = times.last().copied().unwrap_or(0.0);
+    let size = egui::vec2(ui.available_width(), 22.0);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 3.0, visuals.extreme_bg_color);
+
+    let x_at = |t: f32| {
+        let fraction = if duration > 0.0 { t / duration } else { 0.0 };
+        rect.left() + rect.width() * fraction.clamp(0.0, 1.0)
+    };
+    for &t in times {
+        painter.circle_filled(
+            egui::pos2(x_at(t), rect.center().y),
+            4.0,
+            egui::Color32::from_rgb(255, 190, 50),
+        );
+    }
+    if let Some(time) = time {
+        let x = x_at(time);
+        painter.line_segment(
+            [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+            egui::Stroke::new(2.0, visuals.strong_text_color()),
+        );
+    }
+
+    let pointer = response.interact_pointer_pos()?;
+    if duration <= 0.0 {
+        return None;
+    }
+    let fraction = ((pointer.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+    Some(fraction * duration)
+}
+
+/// System to render the camera sequence timeline while it is open, and to
+/// move the editor camera while previewing.
+pub fn render_camera_timeline(
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    mut ui_state: ResMut<EditorUIState>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut timeline: ResMut<CameraTimeline>,
+    mut camera: Single<&mut EditorCamera>,
+) {
+    if !ui_state.camera_timeline_open {
+        stop_preview(&mut timeline, &mut camera);
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let old = editor_state.current_map.camera_sequences.clone();
+    let mut sequences = old.clone();
+    let mut selected = timeline.selected.min(sequences.len().saturating_sub(1));
+    let mut preview = timeline.preview;
+    let mut open = true;
+
+    egui::Window::new("🎬 Camera Sequences")
+        .open(&mut open)
+        .default_width(560.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Sequence");
+                let current = sequences
+                    .get(selected)
+                    .map_or("(none)", |sequence| sequence.name.as_str());
+                egui::ComboBox::from_id_salt("camera_sequence_select")
+                    .selected_text(current)
+                    .show_ui(ui, |ui| {
+                        for (index, sequence) in sequences.iter().enumerate() {
+                            if ui
+                                .selectable_label(index == selected, &sequence.name)
+                                .clicked()
+                            {
+                                selected = index;
+                            }
+                        }
+                    });
+                if ui.button("➕ New").clicked() {
+                    sequences.push(CameraSequenceData {
+                        name: new_sequence_name(&sequences),
+                        keyframes: Vec::new(),
+                    });
+                    selected = sequences.len() - 1;
+                }
+                if ui
+                    .add_enabled(!sequences.is_empty(), egui::Button::new("🗑 Delete"))
+                    .clicked()
+                {
+                    sequences.remove(selected);
+                    selected = selected.saturating_sub(1);
+                }
+            });
+
+            let Some(sequence) = sequences.get_mut(selected) else {
+                ui.label("No camera sequences. Map actions play one with `cinematic <name>`.");
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                ui.label("Name");
+                ui.text_edit_singleline(&mut sequence.name)
+                    .on_hover_text("Played by the map action `cinematic <name>`");
+            });
+
+            ui.separator();
+
+            // Timeline strip and playback controls
+            let times = keyframe_times(sequence);
+            let duration = times.last().copied().unwrap_or(0.0);
+            let scrub = timeline_strip(ui, &times, preview.map(|p| p.time));
+            ui.horizontal(|ui| {
+                let playing = preview.is_some_and(|p| p.playing);
+                let label = if playing { "⏸ Pause" } else { "▶ Play" };
+                if ui
+                    .add_enabled(duration > 0.0, egui::Button::new(label))
+                    .clicked()
+                {
+                    let start = editor_camera_pose(&camera);
+                    let p = preview.get_or_insert(TimelinePreview {
+                        time: 0.0,
+                        playing: false,
+                        start,
+                    });
+                    if p.time >= duration {
+                        p.time = 0.0;
+                    }
+                    p.playing = !playing;
+                }
+                if ui
+                    .add_enabled(preview.is_some(), egui::Button::new("⏹ Stop"))
+                    .on_hover_text("End the preview and put the camera back")
+                    .clicked()
+                {
+                    if let Some(p) = preview.take() {
+                        camera.set_view(p.start.position, p.start.look_at);
+                    }
+                }
+                let now = preview.map_or(0.0, |p| p.time);
+                ui.label(format!("{:.2}s / {:.2}s", now, duration));
+            });
+            if let Some(t) = scrub {
+                let start = editor_camera_pose(&camera);
+                let p = preview.get_or_insert(TimelinePreview {
+                    time: t,
+                    playing: false,
+                    start,
+                });
+                p.time = t;
+                p.playing = false;
+            }
+            ui.label(
+                egui::RichText::new(
+                    "The preview starts from the current view; in game the sequence \
+                     starts from the gameplay camera.",
+                )
+                .small()
+                .weak(),
+            );
+
+            ui.separator();
+
+            // Keyframes
+            let mut remove = None;
+            let mut swap = None;
+            let count = sequence.keyframes.len();
+            egui::ScrollArea::vertical()
+                .max_height(260.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("camera_keyframes_grid")
+                        .num_columns(6)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("#");
+                            ui.label("Position");
+                            ui.label("Look at");
+                            ui.label("Seconds");
+                            ui.label("Easing");
+                            ui.label("");
+                            ui.end_row();
+
+                            for (index, keyframe) in sequence.keyframes.iter_mut().enumerate() {
+                                ui.label(format!("{}", index + 1));
+                                ui.horizontal(|ui| drag_vec3(ui, &mut keyframe.position));
+                                ui.horizontal(|ui| drag_vec3(ui, &mut keyframe.look_at));
+                                ui.add(
+                                    egui::DragValue::new(&mut keyframe.duration)
+                                        .speed(0.05)
+                                        .range(0.0..=60.0)
+                                        .max_decimals(2),
+                                );
+                                egui::ComboBox::from_id_salt(("camera_keyframe_easing", index))
+                                    .selected_text(keyframe.easing.label())
+                                    .show_ui(ui, |ui| {
+                                        for easing in CameraEasing::ALL {
+                                            ui.selectable_value(
+                                                &mut keyframe.easing,
+                                                easing,
+                                                easing.label(),
+                                            );
+                                        }
+                                    });
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .small_button("👁")
+                                        .on_hover_text("Look through this keyframe")
+                                        .clicked()
+                                    {
+                                        preview = None;
+                                        camera.set_view(
+                                            keyframe.position.into(),
+                                            keyframe.look_at.into(),
+                                        );
+                                    }
+                                    if ui
+                                        .small_button("📷")
+                                        .on_hover_text("Set to the current view")
+                                        .clicked()
+                                    {
+                                        let pose = editor_camera_pose(&camera);
+                                        keyframe.position = pose.position.into();
+                                        keyframe.look_at = pose.look_at.into();
+                                    }
+                                    if ui
+                                        .add_enabled(index > 0, egui::Button::new("⬆").small())
+                                        .clicked()
+                                    {
+                                        swap = Some((index - 1, index));
+                                    }
+                                    if ui
+                                        .add_enabled(
+                                            index + 1 < count,
+                                            egui::Button::new("⬇").small(),
+                                        )
+                                        .clicked()
+                                    {
+                                        swap = Some((index, index + 1));
+                                    }
+                                    if ui.small_button("🗑").clicked() {
+                                        remove = Some(index);
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+            if let Some((a, b)) = swap {
+                sequence.keyframes.swap(a, b);
+            }
+            if let Some(index) = remove {
+                sequence.keyframes.remove(index);
+            }
+            if ui
+                .button("📷 Add Keyframe From View")
+                .on_hover_text("Append a keyframe at the editor camera")
+                .clicked()
+            {
+                sequence
+                    .keyframes
+                    .push(keyframe_from_camera(&camera, DEFAULT_KEYFRAME_SECONDS));
+            }
+        });
+
+    if selected != timeline.selected {
+        // A different sequence starts its own preview
+        preview = preview.map(|p| TimelinePreview {
+            time: 0.0,
+            playing: false,
+            ..p
+        });
+    }
+    timeline.selected = selected;
+
+    // Advance playback and move the camera along the path
+    let sequence = sequences.get(selected);
+    match (preview.as_mut(), sequence) {
+        (Some(p), Some(sequence)) => {
+            let duration = sequence.duration();
+            if p.playing {
+                p.time += time.delta_secs();
+                if p.time >= duration {
+                    p.time = duration;
+                    p.playing = false;
+                }
+            }
+            let pose = sample_sequence(p.start, sequence, p.time);
+            camera.set_view(pose.position, pose.look_at);
+        }
+        (Some(p), None) => {
+            camera.set_view(p.start.position, p.start.look_at);
+            preview = None;
+        }
+        (None, _) => {}
+    }
+    timeline.preview = preview;
+
+    if sequences != old {
+        history.push_coalesced(
+            COALESCE_KEY,
+            EditorAction::ModifyCameraSequences {
+                old,
+                new: sequences.clone(),
+            },
+        );
+        editor_state.current_map.camera_sequences = sequences;
+        // Sequences don't change the meshes, so skip the re-render
+        editor_state.mark_modified_without_render();
+    }
+
+    if !open {
+        ui_state.camera_timeline_open = false;
+        stop_preview(&mut timeline, &mut camera);
+    }
+}
+
+/// End any preview, putting the editor camera back where it started.
+fn stop_preview(timeline: &mut CameraTimeline, camera: &mut EditorCamera) {
+    if let Some(preview) = timeline.preview.take() {
+        camera.set_view(preview.start.position, preview.start.look_at);
+    }
+}
+
+/// System to draw the selected sequence's path while the timeline is open.
+pub fn draw_camera_sequence_path(
+    ui_state: Res<EditorUIState>,
+    editor_state: Res<EditorState>,
+    timeline: Res<CameraTimeline>,
+    mut gizmos: Gizmos,
+) {
+    if !ui_state.camera_timeline_open {
+        return;
+    }
+    let Some(sequence) = editor_state
+        .current_map
+        .camera_sequences
+        .get(timeline.selected)
+    else {
+        return;
+    };
+
+    let mut previous: Option<Vec3> = None;
+    for keyframe in &sequence.keyframes {
+        let position = Vec3::from(keyframe.position);
+        let look_at = Vec3::from(keyframe.look_at);
+        gizmos.sphere(Isometry3d::from_translation(position), 0.2, PATH_COLOR);
+        gizmos.line(position, look_at, PATH_COLOR.with_alpha(0.4));
+        if let Some(previous) = previous {
+            gizmos.arrow(previous, position, PATH_COLOR);
+        }
+        previous = Some(position);
+    }
+}
+
+#[cfg(test)]
+mod tests;
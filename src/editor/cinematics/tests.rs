@@ -0,0 +1,42 @@
+use super::*;
+
+fn keyframe(x: f32, duration: f32) -> CameraKeyframeData {
+    CameraKeyframeData {
+        position: (x, 5.0, 0.0),
+        look_at: (x, 0.0, 0.0),
+        duration,
+        easing: CameraEasing::Linear,
+    }
+}
+
+#[test]
+fn keyframe_times_accumulate_durations() {
+    let sequence = CameraSequenceData {
+        name: "intro".to_string(),
+        keyframes: vec![keyframe(0.0, 1.0), keyframe(4.0, 2.5), keyframe(8.0, 0.5)],
+    };
+    assert_eq!(keyframe_times(&sequence), vec![1.0, 3.5, 4.0]);
+}
+
+#[test]
+fn new_sequence_names_skip_taken_ones() {
+    let named = |name: &str| CameraSequenceData {
+        name: name.to_string(),
+        keyframes: Vec::new(),
+    };
+    assert_eq!(new_sequence_name(&[]), "sequence_1");
+    assert_eq!(
+        new_sequence_name(&[named("sequence_1"), named("sequence_3")]),
+        "sequence_2"
+    );
+}
+
+#[test]
+fn captured_keyframe_looks_where_the_camera_looks() {
+    let camera = EditorCamera::looking_at(Vec3::new(0.0, 10.0, 10.0), Vec3::ZERO);
+    let keyframe = keyframe_from_camera(&camera, 1.5);
+    assert_eq!(keyframe.position, (0.0, 10.0, 10.0));
+    assert_eq!(keyframe.duration, 1.5);
+    let look_dir = (Vec3::from(keyframe.look_at) - camera.position).normalize();
+    assert!(look_dir.distance(camera.forward()) < 1e-4);
+}
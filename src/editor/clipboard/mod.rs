@@ -0,0 +1,317 @@
+//! Copy and paste of selected voxels and entities.
+//!
+//! The clipboard is a single resource shared by every open document, so a
+//! structure copied in one map tab can be pasted into another. Copied objects
+//! are stored relative to the selection's minimum corner, and voxel rotations
+//! are kept as matrices rather than indices into the source map's orientation
+//! table; pasting re-registers them in the target map. That makes the
+//! contents self-contained, so [prefab files](crate::editor::prefabs) store
+//! them as-is.
+
+use crate::editor::cursor::CursorState;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::locks::is_locked;
+use crate::editor::renderer::RenderMapEvent;
+use crate::editor::shortcuts::apply_action;
+use crate::editor::state::EditorState;
+use crate::editor::tools::UpdateSelectionHighlights;
+use crate::systems::game::map::format::{
+    find_or_insert_orientation, EntityData, EntityId, OrientationMatrix, VoxelData,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Event to copy the current selection to the clipboard
+#[derive(Message)]
+pub struct CopySelectionEvent;
+
+/// Event to paste the clipboard at the cursor
+#[derive(Message)]
+pub struct PasteClipboardEvent;
+
+/// A copied voxel, positioned relative to the copy's minimum corner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ClipboardVoxel {
+    data: VoxelData,
+    orientation: Option<OrientationMatrix>,
+}
+
+/// Voxels and entities copied from any open map.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EditorClipboard {
+    voxels: Vec<ClipboardVoxel>,
+    entities: Vec<EntityData>,
+}
+
+impl EditorClipboard {
+    /// Clipboard holding `voxels`, each with its orientation. Positions are
+    /// relative to where the voxels will be pasted.
+    pub fn from_voxels(
+        voxels: impl IntoIterator<Item = (VoxelData, Option<OrientationMatrix>)>,
+    ) -> Self {
+        Self {
+            voxels: voxels
+                .into_iter()
+                .map(|(data, orientation)| ClipboardVoxel {
+                    data: VoxelData {
+                        rotation: None,
+                        ..data
+                    },
+                    orientation,
+                })
+                .collect(),
+            entities: Vec::new(),
+        }
+    }
+
+    /// Whether nothing has been copied yet
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty() && self.entities.is_empty()
+    }
+
+    /// Number of copied voxels and entities
+    pub fn len(&self) -> usize {
+        self.voxels.len() + self.entities.len()
+    }
+
+    /// Positions of the copied voxels, relative to the minimum corner
+    pub fn voxel_positions(&self) -> impl Iterator<Item = (i32, i32, i32)> + '_ {
+        self.voxels.iter().map(|voxel| voxel.data.pos)
+    }
+
+    /// Positions of the copied entities, relative to the minimum corner
+    pub fn entity_positions(&self) -> impl Iterator<Item = (f32, f32, f32)> + '_ {
+        self.entities.iter().map(|entity| entity.position)
+    }
+
+    /// Replace the clipboard with the selected voxels and entities.
+    ///
+    /// Returns the number of objects copied; an empty selection leaves the
+    /// clipboard unchanged.
+    pub fn copy_selection(&mut self, editor_state: &EditorState) -> usize {
+        let map = &editor_state.current_map;
+        let voxels: Vec<&VoxelData> = map
+            .world
+            .voxels
+            .iter()
+            .filter(|voxel| editor_state.selected_voxels.contains(&voxel.pos))
+            .collect();
+        let entities: Vec<&EntityData> = map
+            .entities
+            .iter()
+            .filter(|entity| editor_state.selected_entities.contains(&entity.id))
+            .collect();
+
+        let corners = voxels.iter().map(|voxel| voxel.pos).chain(
+            entities
+                .iter()
+                .map(|entity| round_position(entity.position)),
+        );
+        let Some(origin) = corners.reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2))) else {
+            return 0;
+        };
+
+        self.voxels = voxels
+            .into_iter()
+            .map(|voxel| ClipboardVoxel {
+                data: VoxelData {
+                    pos: offset(voxel.pos, origin, -1),
+                    rotation: None,
+                    ..voxel.clone()
+                },
+                orientation: voxel
+                    .rotation
+                    .and_then(|index| map.orientations.get(index).copied()),
+            })
+            .collect();
+        self.entities = entities
+            .into_iter()
+            .map(|entity| EntityData {
+                position: shift(entity.position, origin, -1.0),
+                ..entity.clone()
+            })
+            .collect();
+        self.len()
+    }
+
+    /// Actions that paste the clipboard with its minimum corner at `anchor`.
+    ///
+    /// Voxels already at a pasted position are removed first so undo restores
+    /// them. Orientations missing from `editor_state`'s map are added to it.
+    /// Pasted entities get fresh ids. Voxels landing in a locked area are
+    /// skipped.
+    pub fn paste_actions(
+        &self,
+        editor_state: &mut EditorState,
+        anchor: (i32, i32, i32),
+    ) -> Vec<EditorAction> {
+        let locks = editor_state.locked_areas();
+        let map = &mut editor_state.current_map;
+        let mut actions = Vec::new();
+
+        for voxel in &self.voxels {
+            let pos = offset(voxel.data.pos, anchor, 1);
+            if is_locked(&locks, pos) {
+                continue;
+            }
+            if let Some(existing) = map.world.voxels.get(pos) {
+                actions.push(EditorAction::RemoveVoxel {
+                    pos,
+                    data: existing.clone(),
+                });
+            }
+            let rotation = voxel
+                .orientation
+                .map(|matrix| find_or_insert_orientation(&mut map.orientations, matrix));
+            actions.push(EditorAction::PlaceVoxel {
+                pos,
+                data: VoxelData {
+                    pos,
+                    rotation,
+                    ..voxel.data.clone()
+                },
+            });
+        }
+
+        let first_index = map.entities.len();
+        let first_id = map.next_entity_id().0;
+        for (i, entity) in self.entities.iter().enumerate() {
+            actions.push(EditorAction::PlaceEntity {
+                index: first_index + i,
+                data: EntityData {
+                    id: EntityId(first_id + i as u32),
+                    position: shift(entity.position, anchor, 1.0),
+                    ..entity.clone()
+                },
+            });
+        }
+        actions
+    }
+}
+
+fn round_position(position: (f32, f32, f32)) -> (i32, i32, i32) {
+    (
+        position.0.round() as i32,
+        position.1.round() as i32,
+        position.2.round() as i32,
+    )
+}
+
+fn offset(pos: (i32, i32, i32), by: (i32, i32, i32), sign: i32) -> (i32, i32, i32) {
+    (
+        pos.0 + by.0 * sign,
+        pos.1 + by.1 * sign,
+        pos.2 + by.2 * sign,
+    )
+}
+
+fn shift(position: (f32, f32, f32), by: (i32, i32, i32), sign: f32) -> (f32, f32, f32) {
+    (
+        position.0 + by.0 as f32 * sign,
+        position.1 + by.1 as f32 * sign,
+        position.2 + by.2 as f32 * sign,
+    )
+}
+
+/// Paste the clipboard at `anchor` as one undo step and select what was pasted.
+///
+/// Returns the number of objects pasted.
+pub fn paste_clipboard(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    clipboard: &EditorClipboard,
+    anchor: (i32, i32, i32),
+) -> usize {
+    place_objects(editor_state, history, clipboard, anchor, "Paste")
+}
+
+/// Place copied objects at `anchor` as one undo step named `description`
+/// and select what was placed.
+///
+/// Returns the number of objects placed.
+pub fn place_objects(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    clipboard: &EditorClipboard,
+    anchor: (i32, i32, i32),
+    description: &str,
+) -> usize {
+    if clipboard.is_empty() {
+        return 0;
+    }
+
+    let actions = clipboard.paste_actions(editor_state, anchor);
+    for action in &actions {
+        apply_action(action, editor_state);
+    }
+
+    // Voxels and entities are never selected together; prefer the voxels
+    editor_state.clear_selections();
+    if clipboard.voxels.is_empty() {
+        editor_state.selected_entities = actions
+            .iter()
+            .filter_map(|action| match action {
+                EditorAction::PlaceEntity { data, .. } => Some(data.id),
+                _ => None,
+            })
+            .collect();
+    } else {
+        editor_state.selected_voxels = actions
+            .iter()
+            .filter_map(|action| match action {
+                EditorAction::PlaceVoxel { pos, .. } => Some(*pos),
+                _ => None,
+            })
+            .collect();
+    }
+
+    history.push(EditorAction::Batch {
+        description: description.to_string(),
+        actions,
+    });
+    editor_state.mark_modified();
+    clipboard.len()
+}
+
+/// System to copy the selection when a [`CopySelectionEvent`] arrives
+pub fn handle_copy_selection(
+    mut events: MessageReader<CopySelectionEvent>,
+    editor_state: Res<EditorState>,
+    mut clipboard: ResMut<EditorClipboard>,
+) {
+    for _event in events.read() {
+        let count = clipboard.copy_selection(&editor_state);
+        if count == 0 {
+            info!("Nothing selected to copy");
+        } else {
+            info!("Copied {} objects", count);
+        }
+    }
+}
+
+/// System to paste the clipboard at the cursor's placement position
+pub fn handle_paste_clipboard(
+    mut events: MessageReader<PasteClipboardEvent>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    clipboard: Res<EditorClipboard>,
+    cursor_state: Res<CursorState>,
+    mut render_events: MessageWriter<RenderMapEvent>,
+    mut selection_events: MessageWriter<UpdateSelectionHighlights>,
+) {
+    for _event in events.read() {
+        let Some(anchor) = cursor_state.placement_grid_pos else {
+            info!("Paste needs the cursor over the viewport");
+            continue;
+        };
+        let count = paste_clipboard(&mut editor_state, &mut history, &clipboard, anchor);
+        if count > 0 {
+            info!("Pasted {} objects at {:?}", count, anchor);
+            render_events.write(RenderMapEvent);
+            selection_events.write(UpdateSelectionHighlights);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
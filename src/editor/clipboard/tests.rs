@@ -0,0 +1,130 @@
+use super::*;
+use crate::systems::game::map::format::{axis_angle_to_matrix, EntityType, VoxelType};
+use crate::systems::game::map::geometry::RotationAxis;
+use std::collections::HashMap;
+
+fn npc_at(position: (f32, f32, f32)) -> EntityData {
+    EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Npc,
+        position,
+        properties: HashMap::new(),
+    }
+}
+
+fn voxel_at(state: &EditorState, pos: (i32, i32, i32)) -> Option<&VoxelData> {
+    state.current_map.world.voxels.get(pos)
+}
+
+/// A source map with a two-voxel stack at (5, 1, 5), the top one rotated.
+fn source_state() -> EditorState {
+    let mut state = EditorState::default();
+    let quarter_turn = axis_angle_to_matrix(RotationAxis::Y, 1);
+    state.current_map.orientations = vec![quarter_turn];
+    state.current_map.world.voxels = vec![
+        VoxelData::new((5, 1, 5), VoxelType::Stone, None),
+        VoxelData {
+            rotation: Some(0),
+            ..VoxelData::new((5, 2, 5), VoxelType::Dirt, None)
+        },
+        VoxelData::new((9, 0, 9), VoxelType::Grass, None),
+    ]
+    .into();
+    state.selected_voxels = [(5, 1, 5), (5, 2, 5)].into_iter().collect();
+    state
+}
+
+#[test]
+fn empty_selection_leaves_clipboard_unchanged() {
+    let mut clipboard = EditorClipboard::default();
+    clipboard.copy_selection(&source_state());
+
+    assert_eq!(clipboard.copy_selection(&EditorState::default()), 0);
+    assert_eq!(clipboard.len(), 2);
+}
+
+#[test]
+fn paste_into_another_map_offsets_from_anchor_and_remaps_rotation() {
+    let mut clipboard = EditorClipboard::default();
+    assert_eq!(clipboard.copy_selection(&source_state()), 2);
+
+    // Target map already has an unrelated orientation at index 0
+    let mut target = EditorState::default();
+    target.current_map.orientations = vec![axis_angle_to_matrix(RotationAxis::X, 2)];
+    let mut history = EditorHistory::default();
+
+    assert_eq!(
+        paste_clipboard(&mut target, &mut history, &clipboard, (0, 0, 0)),
+        2
+    );
+
+    assert_eq!(
+        voxel_at(&target, (0, 0, 0)).unwrap().voxel_type,
+        VoxelType::Stone
+    );
+    let top = voxel_at(&target, (0, 1, 0)).unwrap();
+    assert_eq!(top.voxel_type, VoxelType::Dirt);
+    let rotation = top.rotation.expect("rotation kept");
+    assert_eq!(
+        target.current_map.orientations[rotation],
+        axis_angle_to_matrix(RotationAxis::Y, 1)
+    );
+    assert!(target.is_modified);
+    assert_eq!(target.selected_voxels.len(), 2);
+    assert!(target.selected_voxels.contains(&(0, 1, 0)));
+}
+
+#[test]
+fn paste_replacing_a_voxel_undoes_to_the_original() {
+    let mut clipboard = EditorClipboard::default();
+    clipboard.copy_selection(&source_state());
+
+    let mut target = EditorState::default();
+    target.current_map.world.voxels =
+        vec![VoxelData::new((2, 2, 2), VoxelType::Grass, None)].into();
+    let mut history = EditorHistory::default();
+    paste_clipboard(&mut target, &mut history, &clipboard, (2, 1, 2));
+    assert_eq!(
+        voxel_at(&target, (2, 2, 2)).unwrap().voxel_type,
+        VoxelType::Dirt
+    );
+    assert_eq!(target.current_map.world.voxels.len(), 2);
+
+    let undo = history.undo().expect("paste is one undo step");
+    apply_action(&undo.inverse(), &mut target);
+    assert_eq!(target.current_map.world.voxels.len(), 1);
+    assert_eq!(
+        voxel_at(&target, (2, 2, 2)).unwrap().voxel_type,
+        VoxelType::Grass
+    );
+}
+
+#[test]
+fn entities_keep_their_offset_and_are_selected_after_paste() {
+    let mut source = EditorState::default();
+    source.current_map.entities = vec![npc_at((3.0, 1.0, 4.5)), npc_at((4.0, 1.0, 4.0))];
+    source.current_map.assign_entity_ids();
+    source.selected_entities = [EntityId(1), EntityId(2)].into_iter().collect();
+
+    let mut clipboard = EditorClipboard::default();
+    assert_eq!(clipboard.copy_selection(&source), 2);
+
+    let mut target = EditorState::default();
+    target.current_map.entities = vec![npc_at((0.0, 0.0, 0.0))];
+    target.current_map.assign_entity_ids();
+    let mut history = EditorHistory::default();
+    paste_clipboard(&mut target, &mut history, &clipboard, (10, 0, 10));
+
+    let entities = &target.current_map.entities;
+    assert_eq!(entities.len(), 3);
+    assert_eq!(entities[1].position, (10.0, 0.0, 10.5));
+    assert_eq!(entities[2].position, (11.0, 0.0, 10.0));
+    // Pasted entities get ids of their own
+    assert_eq!(entities[1].id, EntityId(2));
+    assert_eq!(entities[2].id, EntityId(3));
+    assert_eq!(
+        target.selected_entities,
+        [EntityId(2), EntityId(3)].into_iter().collect()
+    );
+    assert!(target.selected_voxels.is_empty());
+}
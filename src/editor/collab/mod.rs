@@ -0,0 +1,55 @@
+//! Collaborative editing sessions.
+//!
+//! File → Collaborate opens a window where one editor hosts the active map
+//! and others join it by address. Every change that goes through the undo
+//! history of the shared tab (placing, removing, undo, redo, ...) is sent
+//! to the other collaborators as an [`EditorAction`] and applied to their
+//! copy of the map; concurrent changes to the same voxel, entity or map
+//! setting resolve to the last write (see [`EditLog`]). Remote edits do
+//! not enter the local undo history.
+//!
+//! Collaborators' cursors are drawn as coloured outlines in the viewport,
+//! and the window doubles as the session chat. Joining opens the host's
+//! map in a new tab; edits arriving while another tab is active are held
+//! back until the shared tab is shown again.
+//!
+//! [`protocol`] holds the messages, their framing and the edit log,
+//! [`session`] the host and client ends of a session, and [`systems`] the
+//! Bevy systems connecting a session to the editor.
+//!
+//! [`EditorAction`]: crate::editor::history::EditorAction
+//! [`EditLog`]: protocol::EditLog
+
+pub mod protocol;
+pub mod session;
+pub mod systems;
+
+pub use protocol::{connect, resolve_address, EditLog, EditTarget, PeerId, Stamp};
+pub use session::{ChatLine, CollabEvent, CollabSession, Collaborator};
+pub use systems::{
+    collaborator_color, draw_collaborator_cursors, render_collab_window, run_collab_session,
+    CollabState,
+};
+
+use std::time::Duration;
+
+/// Port hosts listen on unless the address says otherwise
+pub const DEFAULT_COLLAB_PORT: u16 = 7878;
+
+/// Most people in one session, host included
+pub const MAX_COLLABORATORS: usize = 8;
+
+/// Bumped whenever [`CollabMessage`](protocol::CollabMessage) changes shape
+const PROTOCOL_VERSION: u32 = 4;
+
+/// Largest frame accepted; the welcome frame carries the whole map
+const MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
+
+/// Chat lines kept for the session window
+const MAX_CHAT_LINES: usize = 200;
+
+/// How long joining waits for the host to answer
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,274 @@
+//! Messages between collaborators, their framing on the wire and the
+//! last-writer-wins log that orders edits.
+//!
+//! Peers talk over TCP with length-prefixed bincode frames. Edits carry a
+//! Lamport stamp; [`EditLog`] keeps the newest stamp per voxel, entity or map
+//! setting and drops anything older, so all peers settle on the last write
+//! whatever order the edits arrived in.
+
+use crate::editor::history::EditorAction;
+use crate::systems::game::map::format::{EditorMetadata, EntityId};
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+
+use super::{CONNECT_TIMEOUT, DEFAULT_COLLAB_PORT, MAX_FRAME_BYTES};
+
+/// Identifies a collaborator within a session; the host is always 0
+pub type PeerId = u8;
+
+/// Lamport clock value and author of an edit, compared in that order
+pub type Stamp = (u64, PeerId);
+
+/// Part of the map an edit overwrites, for last-writer-wins
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditTarget {
+    Voxel((i32, i32, i32)),
+    Entity(EntityId),
+    Metadata,
+    Bounds,
+    Weather,
+    Edges,
+    Meshing,
+    VoxelGroups,
+    CameraSequences,
+}
+
+impl EditTarget {
+    /// Target of a single (non-batch) action. Entity insertions and
+    /// removals don't overwrite anything, so they have none and always
+    /// apply.
+    fn of(action: &EditorAction) -> Option<Self> {
+        match action {
+            EditorAction::PlaceVoxel { pos, .. } | EditorAction::RemoveVoxel { pos, .. } => {
+                Some(Self::Voxel(*pos))
+            }
+            EditorAction::ModifyEntity { old_data, .. } => Some(Self::Entity(old_data.id)),
+            EditorAction::PlaceEntity { .. }
+            | EditorAction::RemoveEntity { .. }
+            | EditorAction::Batch { .. } => None,
+            EditorAction::ModifyMetadata { .. } => Some(Self::Metadata),
+            EditorAction::ModifyBounds { .. } => Some(Self::Bounds),
+            EditorAction::ModifyWeather { .. } => Some(Self::Weather),
+            EditorAction::ModifyEdges { .. } => Some(Self::Edges),
+            EditorAction::ModifyMeshing { .. } => Some(Self::Meshing),
+            EditorAction::ModifyVoxelGroups { .. } => Some(Self::VoxelGroups),
+            EditorAction::ModifyCameraSequences { .. } => Some(Self::CameraSequences),
+        }
+    }
+}
+
+/// Append the non-batch actions of `action` to `leaves`, in order.
+fn flatten<'a>(action: &'a EditorAction, leaves: &mut Vec<&'a EditorAction>) {
+    match action {
+        EditorAction::Batch { actions, .. } => {
+            for action in actions {
+                flatten(action, leaves);
+            }
+        }
+        action => leaves.push(action),
+    }
+}
+
+/// Lamport clock and the newest stamp applied to each edit target.
+#[derive(Debug, Default, Clone)]
+pub struct EditLog {
+    pub(super) clock: u64,
+    pub(super) stamps: HashMap<EditTarget, Stamp>,
+}
+
+impl EditLog {
+    /// Stamp an edit `peer` has already applied locally.
+    pub fn stamp_local(&mut self, peer: PeerId, action: &EditorAction) -> Stamp {
+        self.clock += 1;
+        let stamp = (self.clock, peer);
+        let mut leaves = Vec::new();
+        flatten(action, &mut leaves);
+        for target in leaves.into_iter().filter_map(EditTarget::of) {
+            self.stamps.insert(target, stamp);
+        }
+        stamp
+    }
+
+    /// Accept a remote edit, returning the parts newer than what was
+    /// already applied to their targets, or `None` if nothing is left.
+    pub fn accept(&mut self, stamp: Stamp, action: &EditorAction) -> Option<EditorAction> {
+        self.clock = self.clock.max(stamp.0);
+        let mut leaves = Vec::new();
+        flatten(action, &mut leaves);
+        let mut winners: Vec<EditorAction> = leaves
+            .into_iter()
+            .filter(|leaf| match EditTarget::of(leaf) {
+                None => true,
+                // Equal stamps are earlier parts of this same edit
+                Some(target) => match self.stamps.get(&target) {
+                    Some(newest) if *newest > stamp => false,
+                    _ => {
+                        self.stamps.insert(target, stamp);
+                        true
+                    }
+                },
+            })
+            .cloned()
+            .collect();
+        match winners.len() {
+            0 => None,
+            1 => winners.pop(),
+            _ => Some(EditorAction::Batch {
+                description: action.description(),
+                actions: winners,
+            }),
+        }
+    }
+}
+
+/// Everything peers send each other
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(super) enum CollabMessage {
+    /// First message from a joining client
+    Hello {
+        version: u32,
+        name: String,
+    },
+    /// Host's answer: the client's id and the map as it stands
+    Welcome {
+        id: PeerId,
+        clock: u64,
+        stamps: Vec<(EditTarget, Stamp)>,
+        peers: Vec<(PeerId, String)>,
+        map: Vec<u8>,
+        /// Binary maps leave out the editor data, so it travels on its own
+        editor_metadata: Option<EditorMetadata>,
+    },
+    /// Host turned the client away
+    Rejected {
+        reason: String,
+    },
+    Joined {
+        id: PeerId,
+        name: String,
+    },
+    Left {
+        id: PeerId,
+    },
+    Edit {
+        stamp: Stamp,
+        action: EditorAction,
+    },
+    Cursor {
+        from: PeerId,
+        position: Option<(i32, i32, i32)>,
+    },
+    Chat {
+        from: PeerId,
+        text: String,
+    },
+}
+
+fn codec() -> impl Options {
+    bincode::DefaultOptions::new().with_limit(MAX_FRAME_BYTES as u64)
+}
+
+/// A non-blocking stream exchanging framed messages.
+pub(super) struct Connection {
+    stream: TcpStream,
+    inbox: Vec<u8>,
+    outbox: Vec<u8>,
+    pub(super) open: bool,
+}
+
+impl Connection {
+    pub(super) fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            inbox: Vec::new(),
+            outbox: Vec::new(),
+            open: true,
+        })
+    }
+
+    pub(super) fn send(&mut self, message: &CollabMessage) {
+        let Ok(body) = codec().serialize(message) else {
+            return;
+        };
+        self.outbox
+            .extend_from_slice(&(body.len() as u32).to_le_bytes());
+        self.outbox.extend_from_slice(&body);
+    }
+
+    /// Write as much of the outbox as the socket takes without blocking.
+    pub(super) fn flush(&mut self) {
+        while self.open && !self.outbox.is_empty() {
+            match self.stream.write(&self.outbox) {
+                Ok(0) => self.open = false,
+                Ok(written) => {
+                    self.outbox.drain(..written);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.open = false,
+            }
+        }
+    }
+
+    /// Read whatever has arrived and decode the complete frames.
+    pub(super) fn receive(&mut self) -> Vec<CollabMessage> {
+        let mut buffer = [0u8; 16 * 1024];
+        while self.open {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => self.open = false,
+                Ok(read) => self.inbox.extend_from_slice(&buffer[..read]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.open = false,
+            }
+        }
+
+        let mut messages = Vec::new();
+        while self.inbox.len() >= 4 {
+            let length =
+                u32::from_le_bytes([self.inbox[0], self.inbox[1], self.inbox[2], self.inbox[3]])
+                    as usize;
+            if length > MAX_FRAME_BYTES {
+                self.open = false;
+                break;
+            }
+            if self.inbox.len() < 4 + length {
+                break;
+            }
+            match codec().deserialize(&self.inbox[4..4 + length]) {
+                Ok(message) => messages.push(message),
+                Err(_) => {
+                    self.open = false;
+                    break;
+                }
+            }
+            self.inbox.drain(..4 + length);
+        }
+        messages
+    }
+}
+
+/// Resolve `address`, adding [`DEFAULT_COLLAB_PORT`] when it has no port.
+pub fn resolve_address(address: &str) -> io::Result<SocketAddr> {
+    let address = address.trim();
+    let with_port = if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, DEFAULT_COLLAB_PORT)
+    };
+    with_port
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("Unknown address {}", address)))
+}
+
+/// Open a connection to a host. Blocks for up to a few seconds, so the
+/// editor runs it on a separate thread.
+pub fn connect(address: &str) -> io::Result<TcpStream> {
+    TcpStream::connect_timeout(&resolve_address(address)?, CONNECT_TIMEOUT)
+}
@@ -0,0 +1,482 @@
+//! One end of a collaboration session: the host with its clients, or a
+//! client with its host.
+//!
+//! The host relays every message it receives to the other collaborators, so
+//! clients only ever hold one connection.
+
+use crate::editor::history::EditorAction;
+use crate::systems::game::map::format::MapData;
+use crate::systems::game::map::loader::binary::{decode_binary, encode_binary};
+use std::collections::BTreeMap;
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+
+use super::protocol::{CollabMessage, Connection, EditLog, PeerId};
+use super::{MAX_CHAT_LINES, MAX_COLLABORATORS, PROTOCOL_VERSION};
+
+/// Someone else in the session
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collaborator {
+    pub name: String,
+    /// Voxel they are pointing at, if any
+    pub cursor: Option<(i32, i32, i32)>,
+}
+
+/// A line in the session chat
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatLine {
+    /// Author's name; `None` for join/leave notices
+    pub from: Option<String>,
+    pub text: String,
+}
+
+/// What a poll of the session produced for the editor to act on
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum CollabEvent {
+    /// The host accepted us and sent the map being edited
+    Welcomed(MapData),
+    /// A remote edit that won against what we already had; apply it
+    Edit(EditorAction),
+    /// The session ended for this reason
+    Disconnected(String),
+}
+
+/// A client connection on the host, before and after its welcome
+struct Peer {
+    id: Option<PeerId>,
+    name: Option<String>,
+    connection: Connection,
+}
+
+enum Link {
+    Host {
+        listener: TcpListener,
+        peers: Vec<Peer>,
+    },
+    Client {
+        host: Connection,
+        welcomed: bool,
+    },
+}
+
+/// One end of a collaboration session.
+pub struct CollabSession {
+    link: Link,
+    id: PeerId,
+    name: String,
+    log: EditLog,
+    collaborators: BTreeMap<PeerId, Collaborator>,
+    chat: Vec<ChatLine>,
+    cursor: Option<(i32, i32, i32)>,
+}
+
+impl CollabSession {
+    /// Start hosting on `port` (0 picks a free one).
+    pub fn host(port: u16, name: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(
+            Link::Host {
+                listener,
+                peers: Vec::new(),
+            },
+            name,
+        ))
+    }
+
+    /// Ask the host at the other end of `stream` to let us in.
+    pub fn join(stream: TcpStream, name: &str) -> io::Result<Self> {
+        let mut host = Connection::new(stream)?;
+        host.send(&CollabMessage::Hello {
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+        });
+        host.flush();
+        Ok(Self::new(
+            Link::Client {
+                host,
+                welcomed: false,
+            },
+            name,
+        ))
+    }
+
+    fn new(link: Link, name: &str) -> Self {
+        Self {
+            link,
+            id: 0,
+            name: name.to_string(),
+            log: EditLog::default(),
+            collaborators: BTreeMap::new(),
+            chat: Vec::new(),
+            cursor: None,
+        }
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.link, Link::Host { .. })
+    }
+
+    /// Whether edits are being exchanged (always true for the host)
+    pub fn is_connected(&self) -> bool {
+        match &self.link {
+            Link::Host { .. } => true,
+            Link::Client { welcomed, .. } => *welcomed,
+        }
+    }
+
+    pub fn local_id(&self) -> PeerId {
+        self.id
+    }
+
+    /// Address the host listens on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match &self.link {
+            Link::Host { listener, .. } => listener.local_addr().ok(),
+            Link::Client { .. } => None,
+        }
+    }
+
+    /// Everyone else in the session, by id
+    pub fn collaborators(&self) -> &BTreeMap<PeerId, Collaborator> {
+        &self.collaborators
+    }
+
+    pub fn chat(&self) -> &[ChatLine] {
+        &self.chat
+    }
+
+    /// Accept joiners and handle what arrived. The host welcomes joiners
+    /// only while `shared_map` is given, i.e. while the shared tab is active.
+    pub fn poll(&mut self, shared_map: Option<&MapData>) -> Vec<CollabEvent> {
+        let mut events = Vec::new();
+        match &mut self.link {
+            Link::Host { listener, peers } => {
+                while let Ok((stream, _)) = listener.accept() {
+                    if let Ok(connection) = Connection::new(stream) {
+                        peers.push(Peer {
+                            id: None,
+                            name: None,
+                            connection,
+                        });
+                    }
+                }
+                let mut received = Vec::new();
+                for (slot, peer) in peers.iter_mut().enumerate() {
+                    for message in peer.connection.receive() {
+                        received.push((slot, message));
+                    }
+                }
+                for (slot, message) in received {
+                    self.handle_on_host(slot, message, &mut events);
+                }
+                self.welcome_waiting(shared_map);
+                self.drop_closed_peers();
+            }
+            Link::Client { host, .. } => {
+                let messages = host.receive();
+                let open = host.open;
+                for message in messages {
+                    self.handle_on_client(message, &mut events);
+                }
+                if !open
+                    && !events
+                        .iter()
+                        .any(|e| matches!(e, CollabEvent::Disconnected(_)))
+                {
+                    events.push(CollabEvent::Disconnected(
+                        "Lost the connection to the host".to_string(),
+                    ));
+                }
+            }
+        }
+        events
+    }
+
+    fn handle_on_host(
+        &mut self,
+        slot: usize,
+        message: CollabMessage,
+        events: &mut Vec<CollabEvent>,
+    ) {
+        let Link::Host { peers, .. } = &mut self.link else {
+            return;
+        };
+        let peer = &mut peers[slot];
+        let Some(from) = peer.id else {
+            // Only a hello is expected before the welcome
+            if let CollabMessage::Hello { version, name } = message {
+                if version != PROTOCOL_VERSION {
+                    peer.connection.send(&CollabMessage::Rejected {
+                        reason: "The host runs a different editor version".to_string(),
+                    });
+                    peer.connection.flush();
+                    peer.connection.open = false;
+                } else {
+                    peer.name = Some(name);
+                }
+            }
+            return;
+        };
+
+        match message {
+            CollabMessage::Edit { stamp, action } => {
+                if let Some(action) = self.log.accept(stamp, &action) {
+                    events.push(CollabEvent::Edit(action.clone()));
+                }
+                // Everyone else judges the edit against their own log
+                self.broadcast(&CollabMessage::Edit { stamp, action }, Some(from));
+            }
+            CollabMessage::Cursor { position, .. } => {
+                if let Some(collaborator) = self.collaborators.get_mut(&from) {
+                    collaborator.cursor = position;
+                }
+                self.broadcast(&CollabMessage::Cursor { from, position }, Some(from));
+            }
+            CollabMessage::Chat { text, .. } => {
+                self.push_chat(Some(from), text.clone());
+                self.broadcast(&CollabMessage::Chat { from, text }, Some(from));
+            }
+            _ => {}
+        }
+    }
+
+    /// Welcome clients that said hello, or turn them away when full.
+    fn welcome_waiting(&mut self, shared_map: Option<&MapData>) {
+        let Link::Host { peers, .. } = &mut self.link else {
+            return;
+        };
+        let waiting: Vec<usize> = (0..peers.len())
+            .filter(|&slot| peers[slot].id.is_none() && peers[slot].name.is_some())
+            .collect();
+        if waiting.is_empty() {
+            return;
+        }
+        let Some(map) = shared_map else {
+            return;
+        };
+        let editor_metadata = map.editor_metadata.clone();
+        let map = match encode_binary(map) {
+            Ok(map) => map,
+            Err(e) => {
+                reject(
+                    peers,
+                    &waiting,
+                    &format!("The host could not send the map: {}", e),
+                );
+                return;
+            }
+        };
+
+        for slot in waiting {
+            let Link::Host { peers, .. } = &mut self.link else {
+                return;
+            };
+            let Some(id) = (1..MAX_COLLABORATORS as PeerId)
+                .find(|id| !peers.iter().any(|peer| peer.id == Some(*id)))
+            else {
+                reject(peers, &[slot], "The session is full");
+                continue;
+            };
+            let name = peers[slot].name.clone().unwrap_or_default();
+            let mut everyone: Vec<(PeerId, String)> = vec![(self.id, self.name.clone())];
+            everyone.extend(
+                self.collaborators
+                    .iter()
+                    .map(|(id, collaborator)| (*id, collaborator.name.clone())),
+            );
+            peers[slot].id = Some(id);
+            peers[slot].connection.send(&CollabMessage::Welcome {
+                id,
+                clock: self.log.clock,
+                stamps: self.log.stamps.iter().map(|(k, v)| (*k, *v)).collect(),
+                peers: everyone,
+                map: map.clone(),
+                editor_metadata: editor_metadata.clone(),
+            });
+            self.broadcast(
+                &CollabMessage::Joined {
+                    id,
+                    name: name.clone(),
+                },
+                Some(id),
+            );
+            self.add_collaborator(id, name);
+        }
+    }
+
+    /// Forget closed connections and tell the others who left.
+    fn drop_closed_peers(&mut self) {
+        let Link::Host { peers, .. } = &mut self.link else {
+            return;
+        };
+        let mut left = Vec::new();
+        peers.retain_mut(|peer| {
+            // Send what is queued (such as a rejection) before dropping
+            peer.connection.flush();
+            if !peer.connection.open {
+                left.extend(peer.id);
+            }
+            peer.connection.open
+        });
+        for id in left {
+            self.broadcast(&CollabMessage::Left { id }, None);
+            self.remove_collaborator(id);
+        }
+    }
+
+    fn handle_on_client(&mut self, message: CollabMessage, events: &mut Vec<CollabEvent>) {
+        match message {
+            CollabMessage::Welcome {
+                id,
+                clock,
+                stamps,
+                peers,
+                map,
+                editor_metadata,
+            } => match decode_binary(&map) {
+                Ok(mut map) => {
+                    map.editor_metadata = editor_metadata;
+                    if let Link::Client { welcomed, .. } = &mut self.link {
+                        *welcomed = true;
+                    }
+                    self.id = id;
+                    self.log.clock = clock;
+                    self.log.stamps = stamps.into_iter().collect();
+                    self.collaborators = peers
+                        .into_iter()
+                        .filter(|(peer, _)| *peer != id)
+                        .map(|(id, name)| (id, Collaborator { name, cursor: None }))
+                        .collect();
+                    events.push(CollabEvent::Welcomed(map));
+                }
+                Err(e) => events.push(CollabEvent::Disconnected(format!(
+                    "Could not read the host's map: {}",
+                    e
+                ))),
+            },
+            CollabMessage::Rejected { reason } => events.push(CollabEvent::Disconnected(reason)),
+            CollabMessage::Joined { id, name } => self.add_collaborator(id, name),
+            CollabMessage::Left { id } => self.remove_collaborator(id),
+            CollabMessage::Edit { stamp, action } => {
+                if let Some(action) = self.log.accept(stamp, &action) {
+                    events.push(CollabEvent::Edit(action));
+                }
+            }
+            CollabMessage::Cursor { from, position } => {
+                if let Some(collaborator) = self.collaborators.get_mut(&from) {
+                    collaborator.cursor = position;
+                }
+            }
+            CollabMessage::Chat { from, text } => self.push_chat(Some(from), text),
+            CollabMessage::Hello { .. } => {}
+        }
+    }
+
+    /// Send an edit already applied to the local copy of the map.
+    pub fn share_edit(&mut self, action: EditorAction) {
+        if !self.is_connected() {
+            return;
+        }
+        let stamp = self.log.stamp_local(self.id, &action);
+        self.send(&CollabMessage::Edit { stamp, action });
+    }
+
+    /// Tell the others where our cursor is, if it moved.
+    pub fn set_cursor(&mut self, position: Option<(i32, i32, i32)>) {
+        if position == self.cursor || !self.is_connected() {
+            return;
+        }
+        self.cursor = position;
+        self.send(&CollabMessage::Cursor {
+            from: self.id,
+            position,
+        });
+    }
+
+    /// Post a chat message.
+    pub fn say(&mut self, text: &str) {
+        let text = text.trim();
+        if text.is_empty() || !self.is_connected() {
+            return;
+        }
+        self.chat_line(Some(self.name.clone()), text.to_string());
+        self.send(&CollabMessage::Chat {
+            from: self.id,
+            text: text.to_string(),
+        });
+    }
+
+    /// Write queued messages to the network.
+    pub fn flush(&mut self) {
+        match &mut self.link {
+            Link::Host { peers, .. } => {
+                for peer in peers {
+                    peer.connection.flush();
+                }
+            }
+            Link::Client { host, .. } => host.flush(),
+        }
+    }
+
+    /// Send to the host, or from the host to every client.
+    fn send(&mut self, message: &CollabMessage) {
+        match &mut self.link {
+            Link::Host { .. } => self.broadcast(message, None),
+            Link::Client { host, .. } => host.send(message),
+        }
+    }
+
+    /// Send to every welcomed client except `except`.
+    fn broadcast(&mut self, message: &CollabMessage, except: Option<PeerId>) {
+        if let Link::Host { peers, .. } = &mut self.link {
+            for peer in peers {
+                if peer.id.is_some() && peer.id != except {
+                    peer.connection.send(message);
+                }
+            }
+        }
+    }
+
+    fn add_collaborator(&mut self, id: PeerId, name: String) {
+        if id == self.id {
+            return;
+        }
+        self.chat_line(None, format!("{} joined", name));
+        self.collaborators
+            .insert(id, Collaborator { name, cursor: None });
+    }
+
+    fn remove_collaborator(&mut self, id: PeerId) {
+        if let Some(collaborator) = self.collaborators.remove(&id) {
+            self.chat_line(None, format!("{} left", collaborator.name));
+        }
+    }
+
+    fn push_chat(&mut self, from: Option<PeerId>, text: String) {
+        let name = from
+            .and_then(|id| self.collaborators.get(&id))
+            .map(|collaborator| collaborator.name.clone());
+        self.chat_line(name, text);
+    }
+
+    fn chat_line(&mut self, from: Option<String>, text: String) {
+        self.chat.push(ChatLine { from, text });
+        if self.chat.len() > MAX_CHAT_LINES {
+            self.chat.remove(0);
+        }
+    }
+}
+
+/// Queue a rejection for the clients in `slots` and close them.
+fn reject(peers: &mut [Peer], slots: &[usize], reason: &str) {
+    for &slot in slots {
+        let connection = &mut peers[slot].connection;
+        connection.send(&CollabMessage::Rejected {
+            reason: reason.to_string(),
+        });
+        connection.flush();
+        connection.open = false;
+    }
+}
@@ -0,0 +1,385 @@
+//! Bevy systems connecting a session to the editor: the Collaborate window,
+//! exchanging edits with the shared tab and drawing collaborators' cursors.
+
+use crate::editor::cursor::CursorState;
+use crate::editor::documents::EditorDocuments;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::renderer::RenderMapEvent;
+use crate::editor::shortcuts::apply_action;
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::editor::ui::dialogs::MapDataChangedEvent;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::io;
+use std::net::TcpStream;
+use std::sync::{
+    mpsc::{channel, Receiver},
+    Arc, Mutex,
+};
+
+use super::protocol::{connect, PeerId};
+use super::session::{CollabEvent, CollabSession};
+use super::{DEFAULT_COLLAB_PORT, MAX_COLLABORATORS};
+
+/// Outline colours for collaborators, picked by peer id
+const COLLABORATOR_COLORS: [[u8; 3]; MAX_COLLABORATORS] = [
+    [240, 90, 90],
+    [90, 170, 240],
+    [110, 210, 110],
+    [240, 190, 70],
+    [190, 110, 230],
+    [70, 210, 200],
+    [240, 130, 190],
+    [200, 200, 200],
+];
+
+/// Colour representing collaborator `id` in the viewport and chat
+pub fn collaborator_color(id: PeerId) -> [u8; 3] {
+    COLLABORATOR_COLORS[id as usize % COLLABORATOR_COLORS.len()]
+}
+
+/// The running session and the contents of the Collaborate window.
+#[derive(Resource)]
+pub struct CollabState {
+    pub session: Option<CollabSession>,
+    /// Receiver for the thread opening a connection to a host
+    connecting: Option<Arc<Mutex<Receiver<io::Result<TcpStream>>>>>,
+    /// Remote edits received while the shared tab was not active
+    deferred: Vec<EditorAction>,
+    /// Name shown to the other collaborators
+    pub name: String,
+    /// Host address typed into the join field
+    pub address: String,
+    /// Chat message being typed
+    pub message: String,
+    /// Why the last session failed or ended
+    pub status: Option<String>,
+}
+
+impl Default for CollabState {
+    fn default() -> Self {
+        let name = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "Editor".to_string());
+        Self {
+            session: None,
+            connecting: None,
+            deferred: Vec::new(),
+            name,
+            address: String::new(),
+            message: String::new(),
+            status: None,
+        }
+    }
+}
+
+impl CollabState {
+    /// Share the active tab with a new session on `port`.
+    fn host(&mut self, port: u16, history: &mut EditorHistory, documents: &mut EditorDocuments) {
+        match CollabSession::host(port, &self.name) {
+            Ok(session) => {
+                info!("Hosting a collaboration session on port {}", port);
+                documents.stop_sharing();
+                history.start_sharing();
+                self.session = Some(session);
+                self.status = None;
+            }
+            Err(e) => self.status = Some(format!("Could not host on port {}: {}", port, e)),
+        }
+    }
+
+    /// Start connecting to `address` in the background.
+    fn join(&mut self) {
+        let (sender, receiver) = channel();
+        let address = self.address.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(connect(&address));
+        });
+        self.connecting = Some(Arc::new(Mutex::new(receiver)));
+        self.status = None;
+    }
+
+    /// Leave the session and stop sharing every tab.
+    fn end(
+        &mut self,
+        status: Option<String>,
+        history: &mut EditorHistory,
+        documents: &mut EditorDocuments,
+    ) {
+        if let Some(reason) = &status {
+            info!("Collaboration session ended: {}", reason);
+        }
+        self.session = None;
+        self.connecting = None;
+        self.deferred.clear();
+        self.status = status;
+        history.stop_sharing();
+        documents.stop_sharing();
+    }
+}
+
+/// System to render the Collaborate window while it is open.
+pub fn render_collab_window(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<EditorUIState>,
+    mut collab: ResMut<CollabState>,
+    mut history: ResMut<EditorHistory>,
+    mut documents: ResMut<EditorDocuments>,
+) {
+    if !ui_state.collab_window_open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let collab = &mut *collab;
+    let shared_tab_active = history.is_shared();
+    let mut host = false;
+    let mut join = false;
+    let mut leave = false;
+    let mut say = false;
+    let mut open = true;
+
+    egui::Window::new("Collaborate")
+        .open(&mut open)
+        .default_width(320.0)
+        .show(ctx, |ui| {
+            let Some(session) = &collab.session else {
+                let connecting = collab.connecting.is_some();
+                ui.add_enabled_ui(!connecting, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Your name:");
+                        ui.text_edit_singleline(&mut collab.name);
+                    });
+                    ui.separator();
+                    if ui
+                        .button("📡 Host this map")
+                        .on_hover_text(format!(
+                            "Others join with this computer's address (port {})",
+                            DEFAULT_COLLAB_PORT
+                        ))
+                        .clicked()
+                    {
+                        host = true;
+                    }
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label("Host:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut collab.address)
+                                .hint_text("192.168.1.20")
+                                .desired_width(160.0),
+                        );
+                        if ui
+                            .add_enabled(
+                                !collab.address.trim().is_empty(),
+                                egui::Button::new("Join"),
+                            )
+                            .clicked()
+                        {
+                            join = true;
+                        }
+                    });
+                });
+                if connecting {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Connecting...");
+                    });
+                }
+                if let Some(status) = &collab.status {
+                    ui.colored_label(egui::Color32::from_rgb(220, 110, 110), status);
+                }
+                return;
+            };
+
+            if session.is_host() {
+                let port = session
+                    .local_addr()
+                    .map_or(DEFAULT_COLLAB_PORT, |address| address.port());
+                ui.label(format!("Hosting on port {}", port));
+            } else if session.is_connected() {
+                ui.label(format!("Connected as {}", collab.name));
+            } else {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Waiting for the host to share the map...");
+                });
+            }
+            if session.is_connected() && !shared_tab_active {
+                ui.label(
+                    egui::RichText::new(
+                        "The shared map is in another tab; edits arrive when you switch back.",
+                    )
+                    .weak(),
+                );
+            }
+
+            ui.separator();
+            ui.label(egui::RichText::new("Collaborators").strong());
+            if session.collaborators().is_empty() {
+                ui.label(egui::RichText::new("Nobody else yet").weak());
+            }
+            for (id, collaborator) in session.collaborators() {
+                let [r, g, b] = collaborator_color(*id);
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::from_rgb(r, g, b), "●");
+                    ui.label(&collaborator.name);
+                    if let Some((x, y, z)) = collaborator.cursor {
+                        ui.label(egui::RichText::new(format!("at {} {} {}", x, y, z)).weak());
+                    }
+                });
+            }
+
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .stick_to_bottom(true)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for line in session.chat() {
+                        match &line.from {
+                            Some(from) => ui.label(format!("{}: {}", from, line.text)),
+                            None => ui.label(egui::RichText::new(&line.text).italics().weak()),
+                        };
+                    }
+                });
+            ui.horizontal(|ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut collab.message)
+                        .hint_text("Message")
+                        .desired_width(220.0),
+                );
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    say = true;
+                    response.request_focus();
+                }
+                if ui.button("Send").clicked() {
+                    say = true;
+                }
+            });
+
+            ui.separator();
+            if ui.button("Leave session").clicked() {
+                leave = true;
+            }
+        });
+
+    if host {
+        collab.host(DEFAULT_COLLAB_PORT, &mut history, &mut documents);
+    }
+    if join {
+        collab.join();
+    }
+    if say {
+        if let Some(session) = &mut collab.session {
+            session.say(&collab.message);
+        }
+        collab.message.clear();
+    }
+    if leave {
+        collab.end(None, &mut history, &mut documents);
+    }
+    if !open {
+        ui_state.collab_window_open = false;
+    }
+}
+
+/// System to exchange edits, cursors and chat with the session.
+pub fn run_collab_session(
+    mut collab: ResMut<CollabState>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut documents: ResMut<EditorDocuments>,
+    cursor_state: Res<CursorState>,
+    mut render_events: MessageWriter<RenderMapEvent>,
+    mut map_changed_events: MessageWriter<MapDataChangedEvent>,
+) {
+    let collab = &mut *collab;
+
+    // Finish joining once the connection thread reports back
+    let connected = collab
+        .connecting
+        .as_ref()
+        .and_then(|receiver| receiver.lock().ok()?.try_recv().ok());
+    if let Some(result) = connected {
+        collab.connecting = None;
+        match result.and_then(|stream| CollabSession::join(stream, &collab.name)) {
+            Ok(session) => collab.session = Some(session),
+            Err(e) => collab.status = Some(format!("Could not join: {}", e)),
+        }
+    }
+
+    let Some(session) = &mut collab.session else {
+        return;
+    };
+
+    let shared_map = history.is_shared().then_some(&editor_state.current_map);
+    let mut ended = None;
+    let mut welcomed = None;
+    for event in session.poll(shared_map) {
+        match event {
+            CollabEvent::Welcomed(map) => welcomed = Some(map),
+            CollabEvent::Edit(action) => collab.deferred.push(action),
+            CollabEvent::Disconnected(reason) => ended = Some(reason),
+        }
+    }
+
+    let mut changed = false;
+    if let Some(map) = welcomed {
+        documents.stop_sharing();
+        documents.open_new(&mut editor_state, &mut history);
+        editor_state.current_map = map;
+        history.start_sharing();
+        changed = true;
+    }
+
+    if history.is_shared() {
+        for action in collab.deferred.drain(..) {
+            apply_action(&action, &mut editor_state);
+            changed = true;
+        }
+        for action in history.take_shared_edits() {
+            session.share_edit(action);
+        }
+        session.set_cursor(cursor_state.grid_pos);
+    } else {
+        session.set_cursor(None);
+    }
+    session.flush();
+
+    if changed {
+        editor_state.mark_modified();
+        render_events.write(RenderMapEvent);
+        map_changed_events.write(MapDataChangedEvent);
+    }
+    if let Some(reason) = ended {
+        collab.end(Some(reason), &mut history, &mut documents);
+    }
+}
+
+/// System to outline the voxels collaborators are pointing at.
+pub fn draw_collaborator_cursors(
+    collab: Res<CollabState>,
+    history: Res<EditorHistory>,
+    mut gizmos: Gizmos,
+) {
+    let Some(session) = &collab.session else {
+        return;
+    };
+    if !history.is_shared() {
+        return;
+    }
+    for (id, collaborator) in session.collaborators() {
+        let Some((x, y, z)) = collaborator.cursor else {
+            continue;
+        };
+        let [r, g, b] = collaborator_color(*id);
+        gizmos.cube(
+            Transform::from_translation(Vec3::new(x as f32, y as f32, z as f32))
+                .with_scale(Vec3::splat(1.06)),
+            Color::srgb_u8(r, g, b),
+        );
+    }
+}
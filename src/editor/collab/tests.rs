@@ -0,0 +1,277 @@
+use super::*;
+use crate::editor::history::EditorAction;
+use crate::systems::game::components::VoxelType;
+use crate::systems::game::map::format::{
+    EntityData, EntityType, MapData, MapMetadata, SubVoxelPattern, VoxelData,
+};
+use std::collections::HashMap;
+use std::thread::sleep;
+use std::time::Duration;
+
+fn place(pos: (i32, i32, i32), voxel_type: VoxelType) -> EditorAction {
+    EditorAction::PlaceVoxel {
+        pos,
+        data: VoxelData::new(pos, voxel_type, Some(SubVoxelPattern::Full)),
+    }
+}
+
+fn rename(name: &str) -> EditorAction {
+    let old = MapData::empty_map().metadata;
+    let new = MapMetadata {
+        name: name.to_string(),
+        ..old.clone()
+    };
+    EditorAction::ModifyMetadata { old, new }
+}
+
+fn placed_type(action: &EditorAction) -> Option<VoxelType> {
+    match action {
+        EditorAction::PlaceVoxel { data, .. } => Some(data.voxel_type),
+        _ => None,
+    }
+}
+
+/// Give loopback traffic time to arrive.
+fn settle() {
+    sleep(Duration::from_millis(30));
+}
+
+/// Host `map` as Ana and let Ben join.
+fn host_and_join(map: &MapData) -> (CollabSession, CollabSession, Vec<CollabEvent>) {
+    let mut host = CollabSession::host(0, "Ana").unwrap();
+    let address = format!("127.0.0.1:{}", host.local_addr().unwrap().port());
+    let mut client = CollabSession::join(connect(&address).unwrap(), "Ben").unwrap();
+    settle();
+    host.poll(Some(map));
+    settle();
+    let events = client.poll(None);
+    (host, client, events)
+}
+
+#[test]
+fn later_stamps_win_per_target() {
+    let mut log = EditLog::default();
+    let stone = place((1, 0, 0), VoxelType::Stone);
+    let local = log.stamp_local(0, &stone);
+
+    let older = (local.0 - 1, 3);
+    assert!(log
+        .accept(older, &place((1, 0, 0), VoxelType::Dirt))
+        .is_none());
+    // Other targets are unaffected by the stamp on (1, 0, 0)
+    assert!(log
+        .accept(older, &place((2, 0, 0), VoxelType::Dirt))
+        .is_some());
+
+    let newer = (local.0 + 1, 3);
+    let accepted = log.accept(newer, &place((1, 0, 0), VoxelType::Dirt));
+    assert_eq!(
+        accepted.as_ref().and_then(placed_type),
+        Some(VoxelType::Dirt)
+    );
+
+    // Local edits stamp after everything seen so far
+    assert!(log.stamp_local(0, &stone) > newer);
+}
+
+#[test]
+fn concurrent_edits_converge_in_any_order() {
+    let mut ana = EditLog::default();
+    let mut ben = EditLog::default();
+    let ana_edit = place((0, 0, 0), VoxelType::Grass);
+    let ben_edit = place((0, 0, 0), VoxelType::Stone);
+
+    // Both edit the same voxel before hearing of the other's edit
+    let ana_stamp = ana.stamp_local(0, &ana_edit);
+    let ben_stamp = ben.stamp_local(1, &ben_edit);
+    assert_eq!(ana_stamp.0, ben_stamp.0);
+
+    // The higher peer id breaks the tie on both sides
+    let at_ana = ana.accept(ben_stamp, &ben_edit);
+    let at_ben = ben.accept(ana_stamp, &ana_edit);
+    assert_eq!(
+        at_ana.as_ref().and_then(placed_type),
+        Some(VoxelType::Stone)
+    );
+    assert!(at_ben.is_none());
+}
+
+#[test]
+fn batches_keep_only_the_winning_parts() {
+    let mut log = EditLog::default();
+    log.stamp_local(0, &place((9, 0, 0), VoxelType::Stone));
+    log.stamp_local(0, &rename("Mine"));
+    let entity = EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Npc,
+        position: (0.0, 0.0, 0.0),
+        properties: HashMap::new(),
+    };
+    let batch = EditorAction::Batch {
+        description: "Paste".to_string(),
+        actions: vec![
+            rename("Theirs"),
+            EditorAction::Batch {
+                description: "Nested".to_string(),
+                actions: vec![
+                    EditorAction::RemoveVoxel {
+                        pos: (4, 0, 0),
+                        data: VoxelData::new(
+                            (4, 0, 0),
+                            VoxelType::Stone,
+                            Some(SubVoxelPattern::Full),
+                        ),
+                    },
+                    place((4, 0, 0), VoxelType::Dirt),
+                ],
+            },
+            EditorAction::PlaceEntity {
+                index: 0,
+                data: entity,
+            },
+        ],
+    };
+
+    // Stamped before the local rename: the rename loses, the rest applies
+    match log.accept((1, 3), &batch) {
+        Some(EditorAction::Batch { actions, .. }) => {
+            assert_eq!(actions.len(), 3);
+            assert!(matches!(actions[0], EditorAction::RemoveVoxel { .. }));
+            assert_eq!(placed_type(&actions[1]), Some(VoxelType::Dirt));
+            assert!(matches!(actions[2], EditorAction::PlaceEntity { .. }));
+        }
+        other => panic!("unexpected result {other:?}"),
+    }
+}
+
+#[test]
+fn addresses_default_to_the_collab_port() {
+    assert_eq!(
+        resolve_address("127.0.0.1").unwrap().port(),
+        DEFAULT_COLLAB_PORT
+    );
+    assert_eq!(resolve_address(" 127.0.0.1:9100 ").unwrap().port(), 9100);
+    assert!(resolve_address("127.0.0.1:port").is_err());
+}
+
+#[test]
+fn joining_receives_the_hosts_map() {
+    let mut map = MapData::empty_map();
+    map.metadata.name = "Shared".to_string();
+    map.world.voxels.insert(VoxelData::new(
+        (2, 0, 1),
+        VoxelType::Stone,
+        Some(SubVoxelPattern::Full),
+    ));
+
+    let (host, client, events) = host_and_join(&map);
+    match events.as_slice() {
+        [CollabEvent::Welcomed(received)] => {
+            assert_eq!(received.metadata.name, "Shared");
+            assert!(received.world.voxels.get((2, 0, 1)).is_some());
+        }
+        other => panic!("unexpected events {other:?}"),
+    }
+    assert!(client.is_connected());
+    assert_eq!(client.local_id(), 1);
+    assert_eq!(client.collaborators()[&0].name, "Ana");
+    assert_eq!(host.collaborators()[&1].name, "Ben");
+}
+
+#[test]
+fn hosts_without_the_shared_tab_keep_joiners_waiting() {
+    let mut host = CollabSession::host(0, "Ana").unwrap();
+    let address = format!("127.0.0.1:{}", host.local_addr().unwrap().port());
+    let mut client = CollabSession::join(connect(&address).unwrap(), "Ben").unwrap();
+    settle();
+    host.poll(None);
+    settle();
+    assert!(client.poll(None).is_empty());
+    assert!(!client.is_connected());
+
+    host.poll(Some(&MapData::empty_map()));
+    settle();
+    assert!(matches!(
+        client.poll(None).as_slice(),
+        [CollabEvent::Welcomed(_)]
+    ));
+}
+
+#[test]
+fn edits_cursors_and_chat_reach_the_other_side() {
+    let (mut host, mut client, _) = host_and_join(&MapData::empty_map());
+
+    client.share_edit(place((3, 1, 0), VoxelType::Dirt));
+    client.set_cursor(Some((3, 1, 0)));
+    client.say("hi");
+    client.flush();
+    settle();
+    let events = host.poll(None);
+    match events.as_slice() {
+        [CollabEvent::Edit(action)] => assert_eq!(placed_type(action), Some(VoxelType::Dirt)),
+        other => panic!("unexpected events {other:?}"),
+    }
+    assert_eq!(host.collaborators()[&1].cursor, Some((3, 1, 0)));
+    assert_eq!(
+        host.chat().last(),
+        Some(&ChatLine {
+            from: Some("Ben".to_string()),
+            text: "hi".to_string()
+        })
+    );
+
+    host.share_edit(rename("Renamed"));
+    host.flush();
+    settle();
+    assert!(matches!(
+        client.poll(None).as_slice(),
+        [CollabEvent::Edit(EditorAction::ModifyMetadata { .. })]
+    ));
+}
+
+#[test]
+fn edits_are_relayed_between_clients() {
+    let map = MapData::empty_map();
+    let (mut host, mut ben, _) = host_and_join(&map);
+    let address = format!("127.0.0.1:{}", host.local_addr().unwrap().port());
+    let mut cleo = CollabSession::join(connect(&address).unwrap(), "Cleo").unwrap();
+    settle();
+    host.poll(Some(&map));
+    host.flush();
+    settle();
+    cleo.poll(None);
+    ben.poll(None);
+    assert_eq!(ben.collaborators()[&2].name, "Cleo");
+
+    ben.share_edit(place((0, 0, 5), VoxelType::Grass));
+    ben.flush();
+    settle();
+    host.poll(None);
+    host.flush();
+    settle();
+    match cleo.poll(None).as_slice() {
+        [CollabEvent::Edit(action)] => assert_eq!(placed_type(action), Some(VoxelType::Grass)),
+        other => panic!("unexpected events {other:?}"),
+    }
+}
+
+#[test]
+fn leaving_is_reported_to_the_host() {
+    let (mut host, client, _) = host_and_join(&MapData::empty_map());
+    drop(client);
+    settle();
+    host.poll(None);
+    assert!(host.collaborators().is_empty());
+    assert_eq!(host.chat().last().unwrap().text, "Ben left");
+}
+
+#[test]
+fn the_host_leaving_ends_the_session() {
+    let (host, mut client, _) = host_and_join(&MapData::empty_map());
+    drop(host);
+    settle();
+    assert!(matches!(
+        client.poll(None).as_slice(),
+        [CollabEvent::Disconnected(_)]
+    ));
+}
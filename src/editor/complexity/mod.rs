@@ -0,0 +1,154 @@
+//! Chunk complexity overlay for finding expensive parts of a map.
+//!
+//! With View → Chunk Complexity enabled, every rendered chunk is outlined in
+//! a colour from blue to red by its quad count after greedy meshing, the
+//! number the renderer's statistics report. Colours are relative to the
+//! heaviest chunk in the map. A small window lists the heaviest chunks with
+//! their quad counts and how much of the chunk sub-voxels fill, and jumps
+//! the camera to one on request.
+//!
+//! The counts come from the editor's own meshing, which matches the game's
+//! full-detail level; distant chunks in the game use fewer quads.
+
+use crate::editor::go_to::GoToEvent;
+use crate::editor::playtest_heatmap::heat_color;
+use crate::editor::renderer::EditorChunk;
+use crate::editor::state::EditorState;
+use crate::systems::game::go_to::GoToDestination;
+use crate::systems::game::map::spawner::{CHUNK_SIZE, SUB_VOXEL_COUNT};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// Number of chunks listed in the complexity window
+pub const LISTED_CHUNKS: usize = 10;
+
+/// Mesher statistics of one chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkComplexity {
+    pub chunk_pos: IVec3,
+    pub quads: usize,
+    pub sub_voxels: usize,
+}
+
+impl ChunkComplexity {
+    pub fn of(chunk: &EditorChunk) -> Self {
+        Self {
+            chunk_pos: chunk.chunk_pos,
+            quads: chunk.quads,
+            sub_voxels: chunk.sub_voxels,
+        }
+    }
+
+    /// Share of the chunk's sub-voxel cells that are filled, 0.0 to 1.0.
+    pub fn density(&self) -> f32 {
+        let side = (CHUNK_SIZE * SUB_VOXEL_COUNT) as f32;
+        self.sub_voxels as f32 / (side * side * side)
+    }
+}
+
+/// World position of the centre of the chunk at `chunk_pos`.
+pub fn chunk_center(chunk_pos: IVec3) -> Vec3 {
+    (chunk_pos.as_vec3() + Vec3::splat(0.5)) * CHUNK_SIZE as f32
+}
+
+/// Chunks from most to fewest quads; ties go to the denser chunk.
+pub fn rank_chunks(chunks: impl IntoIterator<Item = ChunkComplexity>) -> Vec<ChunkComplexity> {
+    let mut ranked: Vec<ChunkComplexity> = chunks.into_iter().collect();
+    ranked.sort_by(|a, b| {
+        b.quads
+            .cmp(&a.quads)
+            .then(b.sub_voxels.cmp(&a.sub_voxels))
+            .then(a.chunk_pos.to_array().cmp(&b.chunk_pos.to_array()))
+    });
+    ranked
+}
+
+/// Outline each chunk in its complexity colour while the overlay is enabled.
+pub fn draw_chunk_complexity(
+    editor_state: Res<EditorState>,
+    chunks: Query<&EditorChunk>,
+    mut gizmos: Gizmos,
+) {
+    if !editor_state.show_chunk_complexity {
+        return;
+    }
+    let Some(max_quads) = chunks.iter().map(|chunk| chunk.quads).max() else {
+        return;
+    };
+    let size = Vec3::splat(CHUNK_SIZE as f32);
+    for chunk in &chunks {
+        let heat = chunk.quads as f32 / max_quads.max(1) as f32;
+        // Shrink slightly so neighbouring outlines don't overdraw each other
+        let transform =
+            Transform::from_translation(chunk_center(chunk.chunk_pos)).with_scale(size * 0.98);
+        gizmos.cube(transform, heat_color(heat));
+    }
+}
+
+/// Render the list of the heaviest chunks while the overlay is enabled.
+pub fn render_complexity_window(
+    mut contexts: EguiContexts,
+    mut editor_state: ResMut<EditorState>,
+    chunks: Query<&EditorChunk>,
+    mut go_to_events: MessageWriter<GoToEvent>,
+) {
+    if !editor_state.show_chunk_complexity {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let ranked = rank_chunks(chunks.iter().map(ChunkComplexity::of));
+    let total_quads: usize = ranked.iter().map(|chunk| chunk.quads).sum();
+    let mut open = true;
+
+    egui::Window::new("Chunk Complexity")
+        .open(&mut open)
+        .default_width(320.0)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "{} chunks, {} quads in total",
+                ranked.len(),
+                total_quads
+            ));
+            ui.label(
+                egui::RichText::new("Outlines run from blue to red (the heaviest chunk).")
+                    .small()
+                    .weak(),
+            );
+            ui.separator();
+
+            if ranked.is_empty() {
+                ui.label("No chunks rendered.");
+            }
+            egui::Grid::new("chunk_complexity")
+                .striped(true)
+                .show(ui, |ui| {
+                    for chunk in ranked.iter().take(LISTED_CHUNKS) {
+                        let pos = chunk.chunk_pos;
+                        ui.label(format!("({}, {}, {})", pos.x, pos.y, pos.z));
+                        ui.label(format!("{} quads", chunk.quads));
+                        ui.label(format!("{:.1}% filled", chunk.density() * 100.0));
+                        if ui
+                            .small_button("Go")
+                            .on_hover_text("Move the camera to this chunk")
+                            .clicked()
+                        {
+                            go_to_events.write(GoToEvent(GoToDestination {
+                                position: chunk_center(pos),
+                                entity: None,
+                            }));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if !open {
+        editor_state.show_chunk_complexity = false;
+    }
+}
+
+#[cfg(test)]
+mod tests;
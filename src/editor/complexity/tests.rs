@@ -0,0 +1,36 @@
+use super::*;
+
+fn chunk(x: i32, quads: usize, sub_voxels: usize) -> ChunkComplexity {
+    ChunkComplexity {
+        chunk_pos: IVec3::new(x, 0, 0),
+        quads,
+        sub_voxels,
+    }
+}
+
+#[test]
+fn heaviest_chunks_come_first() {
+    let ranked = rank_chunks([chunk(0, 10, 5), chunk(1, 300, 5), chunk(2, 10, 50)]);
+    let order: Vec<i32> = ranked.iter().map(|c| c.chunk_pos.x).collect();
+    assert_eq!(order, vec![1, 2, 0]);
+}
+
+#[test]
+fn density_is_the_filled_share_of_the_chunk() {
+    let side = (CHUNK_SIZE * SUB_VOXEL_COUNT) as usize;
+    assert_eq!(chunk(0, 0, 0).density(), 0.0);
+    assert_eq!(chunk(0, 6, side * side * side).density(), 1.0);
+    // A one-voxel-thick floor across the chunk
+    let floor = side * side * SUB_VOXEL_COUNT as usize;
+    assert_eq!(chunk(0, 6, floor).density(), 1.0 / CHUNK_SIZE as f32);
+}
+
+#[test]
+fn chunk_centers_are_in_world_units() {
+    let half = CHUNK_SIZE as f32 / 2.0;
+    assert_eq!(chunk_center(IVec3::ZERO), Vec3::splat(half));
+    assert_eq!(
+        chunk_center(IVec3::new(-1, 0, 2)),
+        Vec3::new(-half, half, 5.0 * half)
+    );
+}
@@ -2,17 +2,12 @@
 //!
 //! Provides raycast-based targeting to show where voxels will be placed/removed.
 
-use crate::editor::state::EditorState;
+use crate::editor::cursor::VoxelIndex;
 use bevy::prelude::*;
 
 use super::camera::{ControllerCamera, ControllerCameraMode};
 
-/// Information about a ray-box intersection (local copy to avoid private module access)
-#[derive(Debug, Clone, Copy)]
-pub struct RayHitInfo {
-    pub distance: f32,
-    pub face_normal: Vec3,
-}
+pub use crate::editor::cursor::raycasting::RayHitInfo;
 
 /// Resource tracking the controller cursor state.
 #[derive(Resource, Default)]
@@ -80,126 +75,6 @@ impl ControllerCursor {
     }
 }
 
-/// Ray-box intersection test (AABB) with face detection
-fn ray_box_intersection_with_face(
-    ray_origin: Vec3,
-    ray_dir: Vec3,
-    box_center: Vec3,
-    box_size: Vec3,
-) -> Option<RayHitInfo> {
-    let box_min = box_center - box_size * 0.5;
-    let box_max = box_center + box_size * 0.5;
-
-    let ray_dir = ray_dir.normalize();
-
-    let mut tmin = f32::NEG_INFINITY;
-    let mut tmax = f32::INFINITY;
-    let mut hit_axis = 0;
-    let mut hit_min_face = true;
-
-    // X axis
-    if ray_dir.x.abs() > 0.0001 {
-        let tx1 = (box_min.x - ray_origin.x) / ray_dir.x;
-        let tx2 = (box_max.x - ray_origin.x) / ray_dir.x;
-        let tx_min = tx1.min(tx2);
-        let tx_max = tx1.max(tx2);
-
-        if tx_min > tmin {
-            tmin = tx_min;
-            hit_axis = 0;
-            hit_min_face = tx1 < tx2;
-        }
-        tmax = tmax.min(tx_max);
-    } else if ray_origin.x < box_min.x || ray_origin.x > box_max.x {
-        return None;
-    }
-
-    // Y axis
-    if ray_dir.y.abs() > 0.0001 {
-        let ty1 = (box_min.y - ray_origin.y) / ray_dir.y;
-        let ty2 = (box_max.y - ray_origin.y) / ray_dir.y;
-        let ty_min = ty1.min(ty2);
-        let ty_max = ty1.max(ty2);
-
-        if ty_min > tmin {
-            tmin = ty_min;
-            hit_axis = 1;
-            hit_min_face = ty1 < ty2;
-        }
-        tmax = tmax.min(ty_max);
-    } else if ray_origin.y < box_min.y || ray_origin.y > box_max.y {
-        return None;
-    }
-
-    // Z axis
-    if ray_dir.z.abs() > 0.0001 {
-        let tz1 = (box_min.z - ray_origin.z) / ray_dir.z;
-        let tz2 = (box_max.z - ray_origin.z) / ray_dir.z;
-        let tz_min = tz1.min(tz2);
-        let tz_max = tz1.max(tz2);
-
-        if tz_min > tmin {
-            tmin = tz_min;
-            hit_axis = 2;
-            hit_min_face = tz1 < tz2;
-        }
-        tmax = tmax.min(tz_max);
-    } else if ray_origin.z < box_min.z || ray_origin.z > box_max.z {
-        return None;
-    }
-
-    if tmax >= tmin && tmax >= 0.0 {
-        let distance = if tmin >= 0.0 { tmin } else { tmax };
-
-        let face_normal = match (hit_axis, hit_min_face) {
-            (0, true) => Vec3::NEG_X,
-            (0, false) => Vec3::X,
-            (1, true) => Vec3::NEG_Y,
-            (1, false) => Vec3::Y,
-            (2, true) => Vec3::NEG_Z,
-            (2, false) => Vec3::Z,
-            _ => Vec3::Y,
-        };
-
-        Some(RayHitInfo {
-            distance,
-            face_normal,
-        })
-    } else {
-        None
-    }
-}
-
-/// Find the closest voxel that the ray intersects
-fn find_closest_voxel_intersection(
-    editor_state: &EditorState,
-    ray_origin: Vec3,
-    ray_dir: Vec3,
-) -> Option<((i32, i32, i32), RayHitInfo)> {
-    let mut closest_distance = f32::MAX;
-    let mut closest_result = None;
-
-    for voxel_data in &editor_state.current_map.world.voxels {
-        let voxel_pos = voxel_data.pos;
-        let center = Vec3::new(
-            voxel_pos.0 as f32 + 0.5,
-            voxel_pos.1 as f32 + 0.5,
-            voxel_pos.2 as f32 + 0.5,
-        );
-
-        if let Some(hit_info) =
-            ray_box_intersection_with_face(ray_origin, ray_dir, center, Vec3::splat(1.0))
-        {
-            if hit_info.distance < closest_distance && hit_info.distance > 0.0 {
-                closest_distance = hit_info.distance;
-                closest_result = Some((voxel_pos, hit_info));
-            }
-        }
-    }
-
-    closest_result
-}
-
 /// Intersect ray with ground plane (y=0)
 fn intersect_ground_plane(ray_origin: Vec3, ray_dir: Vec3) -> Option<Vec3> {
     let ray_dir = ray_dir.normalize();
@@ -221,7 +96,7 @@ fn intersect_ground_plane(ray_origin: Vec3, ray_dir: Vec3) -> Option<Vec3> {
 pub fn update_controller_cursor(
     _mode: Res<ControllerCameraMode>,
     controller_cam: Single<&ControllerCamera, With<Camera3d>>,
-    editor_state: Res<EditorState>,
+    voxel_index: Res<VoxelIndex>,
     mut cursor: ResMut<ControllerCursor>,
 ) {
     // Mode check removed - cursor updates for all input methods
@@ -229,10 +104,13 @@ pub fn update_controller_cursor(
     let ray_origin = controller_cam.position;
     let ray_dir = controller_cam.forward_3d();
 
-    // Try to hit a voxel first
-    if let Some((voxel_pos, hit_info)) =
-        find_closest_voxel_intersection(&editor_state, ray_origin, ray_dir)
-    {
+    // Try to hit a voxel first. This cursor treats voxels as spanning
+    // pos..pos + 1, so shift the ray into the index's centered cells.
+    let ray = Ray3d {
+        origin: ray_origin - Vec3::splat(0.5),
+        direction: Dir3::new(ray_dir).unwrap_or(Dir3::NEG_Z),
+    };
+    if let Some((voxel_pos, hit_info)) = voxel_index.raycast(&ray) {
         cursor.update_from_hit(voxel_pos, hit_info);
     } else {
         // Fall back to ground plane
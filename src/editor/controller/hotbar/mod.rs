@@ -38,6 +38,10 @@ impl HotbarItem {
                     VoxelType::Grass => "Grass",
                     VoxelType::Dirt => "Dirt",
                     VoxelType::Stone => "Stone",
+                    VoxelType::Water => "Water",
+                    VoxelType::Lava => "Lava",
+                    VoxelType::Glowstone => "Glowstone",
+                    VoxelType::Glass => "Glass",
                 };
                 let pattern_name = match pattern {
                     SubVoxelPattern::Full => "",
@@ -61,6 +65,9 @@ impl HotbarItem {
                 EntityType::Item => "Item".to_string(),
                 EntityType::Trigger => "Trigger".to_string(),
                 EntityType::LightSource => "Light".to_string(),
+                EntityType::Door => "Door".to_string(),
+                EntityType::Teleporter => "Teleporter".to_string(),
+                EntityType::Goal => "Goal".to_string(),
             },
             Self::Tool(tool) => tool.name().to_string(),
         }
@@ -75,6 +82,10 @@ impl HotbarItem {
                 VoxelType::Grass => "🟩",
                 VoxelType::Dirt => "🟫",
                 VoxelType::Stone => "⬜",
+                VoxelType::Water => "🟦",
+                VoxelType::Lava => "🟧",
+                VoxelType::Glowstone => "🟨",
+                VoxelType::Glass => "◻",
             },
             Self::Entity { entity_type } => match entity_type {
                 EntityType::PlayerSpawn => "🟢",
@@ -83,6 +94,9 @@ impl HotbarItem {
                 EntityType::Item => "🟡",
                 EntityType::Trigger => "🟣",
                 EntityType::LightSource => "💡",
+                EntityType::Door => "🚪",
+                EntityType::Teleporter => "🌀",
+                EntityType::Goal => "🏁",
             },
             Self::Tool(tool) => match tool {
                 EditorTool::VoxelPlace { .. } => "✏️",
@@ -90,6 +104,11 @@ impl HotbarItem {
                 EditorTool::EntityPlace { .. } => "📍",
                 EditorTool::Select => "🔲",
                 EditorTool::Camera => "📷",
+                EditorTool::Sculpt { .. } => "🖌",
+                EditorTool::Paint { .. } => "🎨",
+                EditorTool::Spline { .. } => "〰",
+                EditorTool::Staircase { .. } => "⟋",
+                EditorTool::Custom { .. } => "🧩",
             },
         }
     }
@@ -374,21 +374,10 @@ pub fn handle_controller_editing(
                     let pos = (placement_pos.x, placement_pos.y, placement_pos.z);
 
                     // Check if voxel already exists at this position
-                    let exists = editor_state
-                        .current_map
-                        .world
-                        .voxels
-                        .iter()
-                        .any(|v| v.pos == pos);
-
-                    if !exists {
-                        let voxel_data = VoxelData {
-                            pos,
-                            voxel_type: *voxel_type,
-                            pattern: Some(*pattern),
-                            rotation: None,
-                            rotation_state: None,
-                        };
+                    let exists = editor_state.current_map.world.voxels.contains(pos);
+
+                    if !exists && editor_state.allows_placement_at(pos) {
+                        let voxel_data = VoxelData::new(pos, *voxel_type, Some(*pattern));
 
                         editor_state
                             .current_map
@@ -409,6 +398,7 @@ pub fn handle_controller_editing(
                 }
                 HotbarItem::Entity { entity_type } => {
                     let entity_data = EntityData {
+                        id: editor_state.current_map.next_entity_id(),
                         entity_type: *entity_type,
                         position: (
                             placement_pos.x as f32 + 0.5,
@@ -444,14 +434,9 @@ pub fn handle_controller_editing(
             let pos = (target_pos.x, target_pos.y, target_pos.z);
 
             // Find and remove the voxel
-            if let Some(idx) = editor_state
-                .current_map
-                .world
-                .voxels
-                .iter()
-                .position(|v| v.pos == pos)
-            {
-                let removed = editor_state.current_map.world.voxels.remove(idx);
+            if !editor_state.allows_edit_at(pos) {
+                edit_mode.action_cooldown = 0.15;
+            } else if let Some(removed) = editor_state.current_map.world.voxels.remove(pos) {
                 editor_state.mark_modified();
                 render_events.write(RenderMapEvent);
 
@@ -502,13 +487,7 @@ pub fn handle_controller_pick_block(
     if let Some(target_pos) = cursor.target_voxel {
         let pos = (target_pos.x, target_pos.y, target_pos.z);
 
-        if let Some(voxel) = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .find(|v| v.pos == pos)
-        {
+        if let Some(voxel) = editor_state.current_map.world.voxels.get(pos) {
             let item = HotbarItem::Voxel {
                 voxel_type: voxel.voxel_type,
                 pattern: voxel.pattern.unwrap_or(SubVoxelPattern::Full),
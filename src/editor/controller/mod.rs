@@ -8,12 +8,16 @@
 //! - Hotbar system for quick item switching
 //! - Direct voxel placement/removal with triggers
 //! - Full-screen item palette for selecting blocks and entities
+//! - Radial menus for picking tools, patterns and entities
+//! - D-pad navigation of the egui panels
 
 pub mod camera;
 pub mod cursor;
 pub mod hotbar;
 pub mod input;
 pub mod palette;
+pub mod radial_menu;
+pub mod ui_navigation;
 
 pub use camera::{
     update_controller_camera, ControllerCamera, ControllerCameraMode, ControllerModeToggleEvent,
@@ -25,3 +29,7 @@ pub use input::{
     ControllerEditMode,
 };
 pub use palette::render_controller_palette;
+pub use radial_menu::{handle_radial_menu, render_radial_menu, RadialMenu};
+pub use ui_navigation::{
+    inject_gamepad_ui_navigation, render_ui_navigation_hint, GamepadUiNavigation,
+};
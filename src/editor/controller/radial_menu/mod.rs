@@ -0,0 +1,416 @@
+//! Radial menus for picking tools, patterns and entities with a controller.
+//!
+//! Holding RB opens a wheel of tools and holding LB opens a wheel of the
+//! current tool's patterns (or entity types). The right stick highlights a
+//! slot and releasing the bumper picks it; releasing with the stick centred
+//! picks nothing.
+
+use bevy::input::gamepad::{GamepadAxis, GamepadButton};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::f32::consts::TAU;
+
+use crate::editor::state::{EditorState, EditorTool, ToolMemory};
+use crate::editor::ui::toolbar::{entity_type_display, pattern_short_name};
+use crate::systems::game::map::format::{EntityType, SubVoxelPattern};
+
+/// How far the right stick must be pushed before it highlights a slot.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Radius of the wheel's background disc in points.
+const WHEEL_RADIUS: f32 = 150.0;
+
+/// Distance from the wheel's centre to each slot label in points.
+const SLOT_RADIUS: f32 = 105.0;
+
+/// Patterns on the pattern wheel, clockwise from the top.
+pub const PATTERNS: [SubVoxelPattern; 8] = [
+    SubVoxelPattern::Full,
+    SubVoxelPattern::PlatformXZ,
+    SubVoxelPattern::PlatformXY,
+    SubVoxelPattern::PlatformYZ,
+    SubVoxelPattern::Staircase,
+    SubVoxelPattern::Pillar,
+    SubVoxelPattern::CenterCube,
+    SubVoxelPattern::Fence,
+];
+
+/// Entity types on the entity wheel, clockwise from the top.
+pub const ENTITIES: [EntityType; 9] = [
+    EntityType::PlayerSpawn,
+    EntityType::Npc,
+    EntityType::Enemy,
+    EntityType::Item,
+    EntityType::Trigger,
+    EntityType::LightSource,
+    EntityType::Door,
+    EntityType::Teleporter,
+    EntityType::Goal,
+];
+
+/// Tools on the tool wheel, clockwise from the top.
+pub const TOOLS: [ToolSlot; 8] = [
+    ToolSlot::VoxelPlace,
+    ToolSlot::VoxelRemove,
+    ToolSlot::EntityPlace,
+    ToolSlot::Select,
+    ToolSlot::Sculpt,
+    ToolSlot::Paint,
+    ToolSlot::Spline,
+    ToolSlot::Camera,
+];
+
+/// Which wheel is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadialWheel {
+    /// Editor tools (RB)
+    Tools,
+    /// Patterns or entity types of the current tool (LB)
+    Items,
+}
+
+/// A built-in tool that can be picked from the tool wheel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolSlot {
+    VoxelPlace,
+    VoxelRemove,
+    EntityPlace,
+    Select,
+    Camera,
+    Sculpt,
+    Paint,
+    Spline,
+}
+
+impl ToolSlot {
+    /// Label shown on the wheel.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::VoxelPlace => "🧱 Place",
+            Self::VoxelRemove => "❌ Remove",
+            Self::EntityPlace => "📍 Entity",
+            Self::Select => "👆 Select",
+            Self::Camera => "📷 Camera",
+            Self::Sculpt => "⛰ Sculpt",
+            Self::Paint => "🖌 Paint",
+            Self::Spline => "〰 Spline",
+        }
+    }
+
+    /// Whether `tool` is this slot's tool.
+    pub fn matches(self, tool: &EditorTool) -> bool {
+        matches!(
+            (self, tool),
+            (Self::VoxelPlace, EditorTool::VoxelPlace { .. })
+                | (Self::VoxelRemove, EditorTool::VoxelRemove)
+                | (Self::EntityPlace, EditorTool::EntityPlace { .. })
+                | (Self::Select, EditorTool::Select)
+                | (Self::Camera, EditorTool::Camera)
+                | (Self::Sculpt, EditorTool::Sculpt { .. })
+                | (Self::Paint, EditorTool::Paint { .. })
+                | (Self::Spline, EditorTool::Spline { .. })
+        )
+    }
+
+    /// This slot's tool, with the parameters it was last used with.
+    pub fn tool(self, memory: &ToolMemory) -> EditorTool {
+        match self {
+            Self::VoxelPlace => EditorTool::VoxelPlace {
+                voxel_type: memory.voxel_type,
+                pattern: memory.voxel_pattern,
+            },
+            Self::VoxelRemove => EditorTool::VoxelRemove,
+            Self::EntityPlace => EditorTool::EntityPlace {
+                entity_type: memory.entity_type,
+            },
+            Self::Select => EditorTool::Select,
+            Self::Camera => EditorTool::Camera,
+            Self::Sculpt => EditorTool::Sculpt {
+                mode: memory.sculpt_mode,
+                radius: memory.sculpt_radius,
+                jitter: memory.sculpt_jitter,
+            },
+            Self::Paint => EditorTool::Paint {
+                voxel_type: memory.voxel_type,
+                radius: memory.paint_radius,
+                mask: memory.paint_mask,
+            },
+            Self::Spline => EditorTool::Spline {
+                voxel_type: memory.voxel_type,
+                pattern: memory.voxel_pattern,
+                width: memory.spline_width,
+            },
+        }
+    }
+}
+
+/// Resource tracking the open radial menu.
+#[derive(Resource, Default)]
+pub struct RadialMenu {
+    /// The wheel being shown, if any
+    pub open: Option<RadialWheel>,
+    /// Slot the right stick last pointed at
+    pub highlighted: Option<usize>,
+}
+
+impl RadialMenu {
+    /// Whether a wheel is on screen (the right stick belongs to it).
+    pub fn is_open(&self) -> bool {
+        self.open.is_some()
+    }
+}
+
+/// The slot the stick points at on a wheel of `count` slots.
+///
+/// Slot 0 is at the top and slots run clockwise; a stick inside the
+/// threshold points at nothing.
+pub fn wheel_slot(stick: Vec2, count: usize) -> Option<usize> {
+    if count == 0 || stick.length() < STICK_THRESHOLD {
+        return None;
+    }
+    let angle = stick.x.atan2(stick.y).rem_euclid(TAU);
+    let step = TAU / count as f32;
+    Some((angle / step).round() as usize % count)
+}
+
+/// Screen-space direction of a slot (egui's y axis points down).
+pub fn slot_direction(slot: usize, count: usize) -> egui::Vec2 {
+    let angle = slot as f32 * TAU / count as f32;
+    egui::vec2(angle.sin(), -angle.cos())
+}
+
+/// Labels of a wheel's slots for the current tool.
+///
+/// The item wheel is empty for tools without patterns or entity types.
+pub fn wheel_labels(wheel: RadialWheel, tool: &EditorTool) -> Vec<&'static str> {
+    match (wheel, tool) {
+        (RadialWheel::Tools, _) => TOOLS.iter().map(|slot| slot.label()).collect(),
+        (RadialWheel::Items, EditorTool::VoxelPlace { .. } | EditorTool::Spline { .. }) => {
+            PATTERNS.iter().map(pattern_short_name).collect()
+        }
+        (RadialWheel::Items, EditorTool::EntityPlace { .. }) => {
+            ENTITIES.iter().map(entity_type_display).collect()
+        }
+        (RadialWheel::Items, _) => Vec::new(),
+    }
+}
+
+/// The slot holding the current tool, pattern or entity type.
+pub fn current_slot(wheel: RadialWheel, tool: &EditorTool) -> Option<usize> {
+    match (wheel, tool) {
+        (RadialWheel::Tools, _) => TOOLS.iter().position(|slot| slot.matches(tool)),
+        (
+            RadialWheel::Items,
+            EditorTool::VoxelPlace { pattern, .. } | EditorTool::Spline { pattern, .. },
+        ) => PATTERNS.iter().position(|p| p == pattern),
+        (RadialWheel::Items, EditorTool::EntityPlace { entity_type }) => {
+            ENTITIES.iter().position(|e| e == entity_type)
+        }
+        (RadialWheel::Items, _) => None,
+    }
+}
+
+/// Pick a wheel slot, remembering the outgoing tool's parameters on a tool switch.
+pub fn pick_slot(
+    wheel: RadialWheel,
+    slot: usize,
+    editor_state: &mut EditorState,
+    tool_memory: &mut ToolMemory,
+) {
+    match wheel {
+        RadialWheel::Tools => {
+            let Some(&tool_slot) = TOOLS.get(slot) else {
+                return;
+            };
+            if tool_slot.matches(&editor_state.active_tool) {
+                return;
+            }
+            remember_tool(&editor_state.active_tool, tool_memory);
+            editor_state.active_tool = tool_slot.tool(tool_memory);
+            info!("Switched to {} tool", editor_state.active_tool.name());
+        }
+        RadialWheel::Items => match &mut editor_state.active_tool {
+            EditorTool::VoxelPlace { pattern, .. } | EditorTool::Spline { pattern, .. } => {
+                if let Some(&picked) = PATTERNS.get(slot) {
+                    *pattern = picked;
+                }
+            }
+            EditorTool::EntityPlace { entity_type } => {
+                if let Some(&picked) = ENTITIES.get(slot) {
+                    *entity_type = picked;
+                }
+            }
+            _ => {}
+        },
+    }
+}
+
+/// Store a tool's parameters so switching back to it restores them.
+fn remember_tool(tool: &EditorTool, memory: &mut ToolMemory) {
+    match tool {
+        EditorTool::VoxelPlace {
+            voxel_type,
+            pattern,
+        } => {
+            memory.voxel_type = *voxel_type;
+            memory.voxel_pattern = *pattern;
+        }
+        EditorTool::EntityPlace { entity_type } => {
+            memory.entity_type = *entity_type;
+        }
+        EditorTool::Sculpt {
+            mode,
+            radius,
+            jitter,
+        } => {
+            memory.sculpt_mode = *mode;
+            memory.sculpt_radius = *radius;
+            memory.sculpt_jitter = *jitter;
+        }
+        EditorTool::Paint {
+            voxel_type,
+            radius,
+            mask,
+        } => {
+            memory.voxel_type = *voxel_type;
+            memory.paint_radius = *radius;
+            memory.paint_mask = *mask;
+        }
+        EditorTool::Spline {
+            voxel_type,
+            pattern,
+            width,
+        } => {
+            memory.voxel_type = *voxel_type;
+            memory.voxel_pattern = *pattern;
+            memory.spline_width = *width;
+        }
+        EditorTool::Staircase { voxel_type } => {
+            memory.voxel_type = *voxel_type;
+        }
+        _ => {}
+    }
+}
+
+/// System opening the wheels on the bumpers, tracking the right stick and
+/// picking the highlighted slot when the bumper is released.
+pub fn handle_radial_menu(
+    gamepads: Query<&Gamepad>,
+    ui_navigation: Res<super::GamepadUiNavigation>,
+    mut menu: ResMut<RadialMenu>,
+    mut editor_state: ResMut<EditorState>,
+    mut tool_memory: ResMut<ToolMemory>,
+) {
+    // The bumpers move egui focus in UI navigation mode
+    if ui_navigation.active {
+        *menu = RadialMenu::default();
+        return;
+    }
+
+    let bumper_held = |button| gamepads.iter().any(|gamepad| gamepad.pressed(button));
+    let bumper_pressed = |button| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+
+    let Some(wheel) = menu.open else {
+        if bumper_pressed(GamepadButton::RightTrigger) {
+            menu.open = Some(RadialWheel::Tools);
+        } else if bumper_pressed(GamepadButton::LeftTrigger)
+            && !wheel_labels(RadialWheel::Items, &editor_state.active_tool).is_empty()
+        {
+            menu.open = Some(RadialWheel::Items);
+        }
+        menu.highlighted = None;
+        return;
+    };
+
+    let count = wheel_labels(wheel, &editor_state.active_tool).len();
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| {
+            Vec2::new(
+                gamepad.get(GamepadAxis::RightStickX).unwrap_or(0.0),
+                gamepad.get(GamepadAxis::RightStickY).unwrap_or(0.0),
+            )
+        })
+        .max_by(|a, b| a.length().total_cmp(&b.length()))
+        .unwrap_or(Vec2::ZERO);
+    if let Some(slot) = wheel_slot(stick, count) {
+        menu.highlighted = Some(slot);
+    }
+
+    let button = match wheel {
+        RadialWheel::Tools => GamepadButton::RightTrigger,
+        RadialWheel::Items => GamepadButton::LeftTrigger,
+    };
+    if !bumper_held(button) {
+        if let Some(slot) = menu.highlighted {
+            pick_slot(wheel, slot, &mut editor_state, &mut tool_memory);
+        }
+        *menu = RadialMenu::default();
+    }
+}
+
+/// System drawing the open wheel in the middle of the screen.
+pub fn render_radial_menu(
+    menu: Res<RadialMenu>,
+    editor_state: Res<EditorState>,
+    mut contexts: EguiContexts,
+) {
+    let Some(wheel) = menu.open else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let labels = wheel_labels(wheel, &editor_state.active_tool);
+    let current = current_slot(wheel, &editor_state.active_tool);
+    let visuals = ctx.style().visuals.clone();
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("controller_radial_menu"),
+    ));
+    let center = ctx.content_rect().center();
+
+    painter.circle(
+        center,
+        WHEEL_RADIUS,
+        egui::Color32::from_black_alpha(200),
+        visuals.window_stroke,
+    );
+
+    for (slot, label) in labels.iter().enumerate() {
+        let pos = center + slot_direction(slot, labels.len()) * SLOT_RADIUS;
+        if menu.highlighted == Some(slot) {
+            painter.circle_filled(pos, 34.0, visuals.selection.bg_fill);
+        }
+        let color = if current == Some(slot) {
+            visuals.warn_fg_color
+        } else {
+            egui::Color32::WHITE
+        };
+        painter.text(
+            pos,
+            egui::Align2::CENTER_CENTER,
+            label,
+            egui::FontId::proportional(13.0),
+            color,
+        );
+    }
+
+    let title = match (menu.highlighted, wheel, &editor_state.active_tool) {
+        (Some(slot), _, _) => labels.get(slot).copied().unwrap_or_default(),
+        (None, RadialWheel::Tools, _) => "Tools",
+        (None, RadialWheel::Items, EditorTool::EntityPlace { .. }) => "Entities",
+        (None, RadialWheel::Items, _) => "Patterns",
+    };
+    painter.text(
+        center,
+        egui::Align2::CENTER_CENTER,
+        title,
+        egui::FontId::proportional(16.0),
+        egui::Color32::WHITE,
+    );
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,102 @@
+use super::*;
+use crate::systems::game::map::format::VoxelType;
+
+#[test]
+fn test_wheel_slot_runs_clockwise_from_top() {
+    assert_eq!(wheel_slot(Vec2::new(0.0, 1.0), 8), Some(0));
+    assert_eq!(wheel_slot(Vec2::new(1.0, 0.0), 8), Some(2));
+    assert_eq!(wheel_slot(Vec2::new(0.0, -1.0), 8), Some(4));
+    assert_eq!(wheel_slot(Vec2::new(-1.0, 0.0), 8), Some(6));
+    assert_eq!(wheel_slot(Vec2::new(-0.7, 0.7), 8), Some(7));
+}
+
+#[test]
+fn test_wheel_slot_ignores_centred_stick() {
+    assert_eq!(wheel_slot(Vec2::new(0.2, 0.3), 8), None);
+    assert_eq!(wheel_slot(Vec2::new(0.0, 1.0), 0), None);
+}
+
+#[test]
+fn test_wheel_slot_wraps_near_top() {
+    // Just left of straight up still picks the top slot
+    assert_eq!(wheel_slot(Vec2::new(-0.05, 1.0), 9), Some(0));
+}
+
+#[test]
+fn test_slot_direction_matches_wheel_slot() {
+    for slot in 0..9 {
+        let dir = slot_direction(slot, 9);
+        // egui's y axis points down, the stick's points up
+        assert_eq!(wheel_slot(Vec2::new(dir.x, -dir.y), 9), Some(slot));
+    }
+}
+
+#[test]
+fn test_item_wheel_follows_tool() {
+    let place = EditorTool::VoxelPlace {
+        voxel_type: VoxelType::Grass,
+        pattern: SubVoxelPattern::Pillar,
+    };
+    assert_eq!(
+        wheel_labels(RadialWheel::Items, &place).len(),
+        PATTERNS.len()
+    );
+    assert_eq!(current_slot(RadialWheel::Items, &place), Some(5));
+
+    let entity = EditorTool::EntityPlace {
+        entity_type: EntityType::Goal,
+    };
+    assert_eq!(
+        wheel_labels(RadialWheel::Items, &entity).len(),
+        ENTITIES.len()
+    );
+    assert_eq!(current_slot(RadialWheel::Items, &entity), Some(8));
+
+    assert!(wheel_labels(RadialWheel::Items, &EditorTool::Select).is_empty());
+    assert_eq!(
+        current_slot(RadialWheel::Tools, &EditorTool::Select),
+        Some(3)
+    );
+}
+
+#[test]
+fn test_pick_pattern() {
+    let mut state = EditorState::new();
+    let mut memory = ToolMemory::default();
+    state.active_tool = EditorTool::VoxelPlace {
+        voxel_type: VoxelType::Stone,
+        pattern: SubVoxelPattern::Full,
+    };
+
+    pick_slot(RadialWheel::Items, 7, &mut state, &mut memory);
+
+    assert!(matches!(
+        state.active_tool,
+        EditorTool::VoxelPlace {
+            voxel_type: VoxelType::Stone,
+            pattern: SubVoxelPattern::Fence,
+        }
+    ));
+}
+
+#[test]
+fn test_pick_tool_remembers_parameters() {
+    let mut state = EditorState::new();
+    let mut memory = ToolMemory::default();
+    state.active_tool = EditorTool::VoxelPlace {
+        voxel_type: VoxelType::Stone,
+        pattern: SubVoxelPattern::Staircase,
+    };
+
+    pick_slot(RadialWheel::Tools, 3, &mut state, &mut memory);
+    assert!(matches!(state.active_tool, EditorTool::Select));
+
+    pick_slot(RadialWheel::Tools, 0, &mut state, &mut memory);
+    assert!(matches!(
+        state.active_tool,
+        EditorTool::VoxelPlace {
+            voxel_type: VoxelType::Stone,
+            pattern: SubVoxelPattern::Staircase,
+        }
+    ));
+}
@@ -0,0 +1,216 @@
+//! Gamepad navigation of the editor's egui panels.
+//!
+//! Select (the View button) toggles UI navigation mode. While it is on, the
+//! D-pad, bumpers and face buttons are translated into the keyboard events egui
+//! already uses to move focus and activate widgets, and the gamepad camera
+//! ignores them.
+
+use bevy::input::gamepad::GamepadButton;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext, EguiContexts, EguiInput, PrimaryEguiContext};
+
+/// Seconds a D-pad direction must be held before focus starts repeating.
+const REPEAT_DELAY: f32 = 0.4;
+/// Seconds between focus moves while a D-pad direction stays held.
+const REPEAT_INTERVAL: f32 = 0.12;
+
+/// D-pad directions, which repeat while held.
+const DIRECTION_BUTTONS: [GamepadButton; 4] = [
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+/// Every button forwarded to egui while navigating.
+const NAVIGATION_BUTTONS: [GamepadButton; 8] = [
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+    GamepadButton::LeftTrigger,
+    GamepadButton::RightTrigger,
+    GamepadButton::South,
+    GamepadButton::East,
+];
+
+/// Resource tracking whether the gamepad drives the egui panels.
+#[derive(Resource, Default)]
+pub struct GamepadUiNavigation {
+    /// Whether UI navigation mode is on
+    pub active: bool,
+    /// D-pad direction being held and for how many seconds
+    held: Option<(GamepadButton, f32)>,
+    /// Focus stroke to restore when navigation mode is turned off
+    saved_focus_stroke: Option<egui::Stroke>,
+}
+
+impl GamepadUiNavigation {
+    /// Advance the hold timer for the held D-pad direction.
+    ///
+    /// Returns true when the held direction should move focus again this frame.
+    pub fn repeat(&mut self, held: Option<GamepadButton>, dt: f32) -> bool {
+        match (held, self.held) {
+            (Some(button), Some((previous, secs))) if button == previous => {
+                let after = secs + dt;
+                self.held = Some((button, after));
+                repeat_fires(secs, after)
+            }
+            (Some(button), _) => {
+                self.held = Some((button, 0.0));
+                false
+            }
+            (None, _) => {
+                self.held = None;
+                false
+            }
+        }
+    }
+
+    /// Turn navigation mode on or off, thickening the focus outline while on.
+    fn set_active(&mut self, active: bool, ctx: &egui::Context) {
+        if self.active == active {
+            return;
+        }
+        self.active = active;
+        self.held = None;
+
+        if active {
+            ctx.style_mut(|style| {
+                let visuals = &mut style.visuals;
+                self.saved_focus_stroke = Some(visuals.widgets.active.bg_stroke);
+                visuals.widgets.active.bg_stroke =
+                    egui::Stroke::new(2.0, visuals.selection.stroke.color);
+            });
+        } else if let Some(stroke) = self.saved_focus_stroke.take() {
+            ctx.style_mut(|style| style.visuals.widgets.active.bg_stroke = stroke);
+        }
+    }
+}
+
+/// The egui key (and modifiers) a gamepad button sends while navigating.
+///
+/// With nothing focused the D-pad walks the focus order, since egui's
+/// directional focus moves need a widget to start from.
+pub fn navigation_key(
+    button: GamepadButton,
+    has_focus: bool,
+) -> Option<(egui::Key, egui::Modifiers)> {
+    let none = egui::Modifiers::NONE;
+    let shift = egui::Modifiers::SHIFT;
+    match button {
+        GamepadButton::DPadUp if has_focus => Some((egui::Key::ArrowUp, none)),
+        GamepadButton::DPadDown if has_focus => Some((egui::Key::ArrowDown, none)),
+        GamepadButton::DPadLeft if has_focus => Some((egui::Key::ArrowLeft, none)),
+        GamepadButton::DPadRight if has_focus => Some((egui::Key::ArrowRight, none)),
+        GamepadButton::DPadUp | GamepadButton::DPadLeft | GamepadButton::LeftTrigger => {
+            Some((egui::Key::Tab, shift))
+        }
+        GamepadButton::DPadDown | GamepadButton::DPadRight | GamepadButton::RightTrigger => {
+            Some((egui::Key::Tab, none))
+        }
+        GamepadButton::South => Some((egui::Key::Enter, none)),
+        GamepadButton::East => Some((egui::Key::Escape, none)),
+        _ => None,
+    }
+}
+
+/// Whether a direction held from `before` to `after` seconds fires a repeat.
+pub fn repeat_fires(before: f32, after: f32) -> bool {
+    if after < REPEAT_DELAY {
+        return false;
+    }
+    if before < REPEAT_DELAY {
+        return true;
+    }
+    let repeats = |secs: f32| ((secs - REPEAT_DELAY) / REPEAT_INTERVAL).floor();
+    repeats(after) > repeats(before)
+}
+
+/// System feeding gamepad navigation into the primary egui context's input.
+///
+/// Runs between bevy_egui's input processing and the start of the egui pass.
+pub fn inject_gamepad_ui_navigation(
+    gamepads: Query<&Gamepad>,
+    mut navigation: ResMut<GamepadUiNavigation>,
+    mut contexts: Query<(&mut EguiContext, &mut EguiInput), With<PrimaryEguiContext>>,
+    time: Res<Time>,
+) {
+    let Ok((mut context, mut input)) = contexts.single_mut() else {
+        return;
+    };
+    let ctx = context.get_mut().clone();
+
+    if gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(GamepadButton::Select))
+    {
+        let active = !navigation.active;
+        navigation.set_active(active, &ctx);
+    }
+    if !navigation.active {
+        return;
+    }
+
+    let mut presses: Vec<GamepadButton> = gamepads
+        .iter()
+        .flat_map(|gamepad| {
+            NAVIGATION_BUTTONS
+                .into_iter()
+                .filter(|button| gamepad.just_pressed(*button))
+        })
+        .collect();
+    let held = gamepads.iter().find_map(|gamepad| {
+        DIRECTION_BUTTONS
+            .into_iter()
+            .find(|button| gamepad.pressed(*button))
+    });
+    if navigation.repeat(held, time.delta_secs()) {
+        presses.extend(held);
+    }
+
+    let has_focus = ctx.memory(|memory| memory.focused().is_some());
+    for button in presses {
+        // B with nothing focused leaves navigation mode entirely
+        if button == GamepadButton::East && !has_focus {
+            navigation.set_active(false, &ctx);
+        }
+        let Some((key, modifiers)) = navigation_key(button, has_focus) else {
+            continue;
+        };
+        for pressed in [true, false] {
+            input.0.events.push(egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers,
+            });
+        }
+    }
+}
+
+/// System showing a hint bar while UI navigation mode is on.
+pub fn render_ui_navigation_hint(navigation: Res<GamepadUiNavigation>, mut contexts: EguiContexts) {
+    if !navigation.active {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Area::new(egui::Id::new("gamepad_ui_navigation_hint"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -12.0])
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(
+                    "🎮 UI navigation — D-pad/LB/RB: move focus · A: activate · B: back · Select: exit",
+                );
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,81 @@
+use super::*;
+
+#[test]
+fn test_dpad_walks_focus_order_without_focus() {
+    assert_eq!(
+        navigation_key(GamepadButton::DPadDown, false),
+        Some((egui::Key::Tab, egui::Modifiers::NONE))
+    );
+    assert_eq!(
+        navigation_key(GamepadButton::DPadUp, false),
+        Some((egui::Key::Tab, egui::Modifiers::SHIFT))
+    );
+}
+
+#[test]
+fn test_dpad_moves_focus_spatially_with_focus() {
+    assert_eq!(
+        navigation_key(GamepadButton::DPadLeft, true),
+        Some((egui::Key::ArrowLeft, egui::Modifiers::NONE))
+    );
+    assert_eq!(
+        navigation_key(GamepadButton::DPadDown, true),
+        Some((egui::Key::ArrowDown, egui::Modifiers::NONE))
+    );
+}
+
+#[test]
+fn test_face_buttons_and_bumpers() {
+    assert_eq!(
+        navigation_key(GamepadButton::South, true),
+        Some((egui::Key::Enter, egui::Modifiers::NONE))
+    );
+    assert_eq!(
+        navigation_key(GamepadButton::East, true),
+        Some((egui::Key::Escape, egui::Modifiers::NONE))
+    );
+    assert_eq!(
+        navigation_key(GamepadButton::RightTrigger, true),
+        Some((egui::Key::Tab, egui::Modifiers::NONE))
+    );
+    assert_eq!(
+        navigation_key(GamepadButton::LeftTrigger, true),
+        Some((egui::Key::Tab, egui::Modifiers::SHIFT))
+    );
+    assert_eq!(navigation_key(GamepadButton::North, true), None);
+}
+
+#[test]
+fn test_repeat_waits_for_delay_then_fires_at_interval() {
+    assert!(!repeat_fires(0.0, 0.2));
+    assert!(repeat_fires(0.35, 0.45));
+    assert!(!repeat_fires(0.45, 0.5));
+    assert!(repeat_fires(0.5, 0.55));
+}
+
+#[test]
+fn test_repeat_restarts_when_direction_changes() {
+    let mut navigation = GamepadUiNavigation::default();
+    assert!(!navigation.repeat(Some(GamepadButton::DPadDown), 0.016));
+    assert!(!navigation.repeat(Some(GamepadButton::DPadDown), 0.3));
+    assert!(navigation.repeat(Some(GamepadButton::DPadDown), 0.2));
+
+    assert!(!navigation.repeat(Some(GamepadButton::DPadUp), 0.5));
+    assert!(!navigation.repeat(None, 0.5));
+    assert!(!navigation.repeat(Some(GamepadButton::DPadUp), 0.5));
+}
+
+#[test]
+fn test_focus_outline_restored_when_leaving() {
+    let ctx = egui::Context::default();
+    let original = ctx.style().visuals.widgets.active.bg_stroke;
+    let mut navigation = GamepadUiNavigation::default();
+
+    navigation.set_active(true, &ctx);
+    assert!(navigation.active);
+    assert_eq!(ctx.style().visuals.widgets.active.bg_stroke.width, 2.0);
+
+    navigation.set_active(false, &ctx);
+    assert!(!navigation.active);
+    assert_eq!(ctx.style().visuals.widgets.active.bg_stroke, original);
+}
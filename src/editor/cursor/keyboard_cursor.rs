@@ -99,6 +99,7 @@ pub fn handle_keyboard_cursor_movement(
 
         // For keyboard movement, assume placement on top (+Y direction)
         cursor_state.hit_face_normal = Some(Vec3::Y);
+        cursor_state.hit_point = None;
         let placement_grid = (new_pos.0, new_pos.1 + 1, new_pos.2);
         cursor_state.placement_grid_pos = Some(placement_grid);
         cursor_state.placement_pos = Some(Vec3::new(
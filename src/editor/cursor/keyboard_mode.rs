@@ -87,6 +87,36 @@ pub fn handle_tool_switching(
                 EditorTool::EntityPlace { entity_type } => {
                     tool_memory.entity_type = *entity_type;
                 }
+                EditorTool::Sculpt {
+                    mode,
+                    radius,
+                    jitter,
+                } => {
+                    tool_memory.sculpt_mode = *mode;
+                    tool_memory.sculpt_radius = *radius;
+                    tool_memory.sculpt_jitter = *jitter;
+                }
+                EditorTool::Paint {
+                    voxel_type,
+                    radius,
+                    mask,
+                } => {
+                    tool_memory.voxel_type = *voxel_type;
+                    tool_memory.paint_radius = *radius;
+                    tool_memory.paint_mask = *mask;
+                }
+                EditorTool::Spline {
+                    voxel_type,
+                    pattern,
+                    width,
+                } => {
+                    tool_memory.voxel_type = *voxel_type;
+                    tool_memory.voxel_pattern = *pattern;
+                    tool_memory.spline_width = *width;
+                }
+                EditorTool::Staircase { voxel_type } => {
+                    tool_memory.voxel_type = *voxel_type;
+                }
                 _ => {}
             }
         };
@@ -132,6 +162,45 @@ pub fn handle_tool_switching(
         info!("Switched to EntityPlace tool");
     }
 
+    // Switch to Sculpt tool with M key
+    if keyboard.just_pressed(KeyCode::KeyM)
+        && !matches!(editor_state.active_tool, EditorTool::Sculpt { .. })
+    {
+        save_current_params(&editor_state, &mut tool_memory);
+        editor_state.active_tool = EditorTool::Sculpt {
+            mode: tool_memory.sculpt_mode,
+            radius: tool_memory.sculpt_radius,
+            jitter: tool_memory.sculpt_jitter,
+        };
+        info!("Switched to Sculpt tool");
+    }
+
+    // Switch to Paint tool with T key
+    if keyboard.just_pressed(KeyCode::KeyT)
+        && !matches!(editor_state.active_tool, EditorTool::Paint { .. })
+    {
+        save_current_params(&editor_state, &mut tool_memory);
+        editor_state.active_tool = EditorTool::Paint {
+            voxel_type: tool_memory.voxel_type,
+            radius: tool_memory.paint_radius,
+            mask: tool_memory.paint_mask,
+        };
+        info!("Switched to Paint tool");
+    }
+
+    // Switch to Spline tool with P key
+    if keyboard.just_pressed(KeyCode::KeyP)
+        && !matches!(editor_state.active_tool, EditorTool::Spline { .. })
+    {
+        save_current_params(&editor_state, &mut tool_memory);
+        editor_state.active_tool = EditorTool::Spline {
+            voxel_type: tool_memory.voxel_type,
+            pattern: tool_memory.voxel_pattern,
+            width: tool_memory.spline_width,
+        };
+        info!("Switched to Spline tool");
+    }
+
     // Switch to Camera tool with C key
     if keyboard.just_pressed(KeyCode::KeyC)
         && !matches!(editor_state.active_tool, EditorTool::Camera)
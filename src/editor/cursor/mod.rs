@@ -4,10 +4,12 @@ mod keyboard_cursor;
 mod keyboard_mode;
 mod mouse_cursor;
 pub mod raycasting;
+mod voxel_index;
 
 pub use keyboard_cursor::{handle_keyboard_cursor_movement, handle_keyboard_selection};
 pub use keyboard_mode::{handle_play_shortcuts, handle_tool_switching, toggle_keyboard_edit_mode};
 pub use mouse_cursor::update_cursor_position;
+pub use voxel_index::VoxelIndex;
 
 use bevy::prelude::*;
 
@@ -29,6 +31,10 @@ pub struct CursorState {
 
     /// Grid position where a new voxel would be placed
     pub placement_grid_pos: Option<(i32, i32, i32)>,
+
+    /// Exact point where the mouse ray hit a voxel face or the work plane.
+    /// `None` for gamepad and keyboard cursors, which only move in whole voxels.
+    pub hit_point: Option<Vec3>,
 }
 
 impl CursorState {
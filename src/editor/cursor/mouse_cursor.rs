@@ -1,32 +1,38 @@
 //! Mouse-based cursor position updates.
 
-use super::raycasting::{find_closest_voxel_intersection_with_face, intersect_ground_plane};
-use super::CursorState;
+use super::{CursorState, VoxelIndex};
 use crate::editor::camera::{EditorCamera, GamepadCameraState};
+use crate::editor::guides::{map_guides, snap_to_guides, GuideSettings};
 use crate::editor::state::{EditorState, KeyboardEditMode};
+use crate::editor::viewports::{viewport_camera, ViewportCamera, ViewportLayout};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
 /// System to update cursor position from mouse or center-screen raycast
 /// When gamepad is active: cursor follows center of screen (raycast from camera forward)
-/// When mouse is active: cursor follows mouse pointer (raycast from mouse position)
+/// When mouse is active: cursor follows mouse pointer (raycast from mouse position),
+/// through the camera of the viewport under the pointer
+#[allow(clippy::too_many_arguments)]
 pub fn update_cursor_position(
     mut cursor_state: ResMut<CursorState>,
     gamepad_state: Res<GamepadCameraState>,
     editor_state: Res<EditorState>,
-    camera: Single<(&Camera, &GlobalTransform, &EditorCamera)>,
+    voxel_index: Res<VoxelIndex>,
+    editor_cam: Single<&EditorCamera>,
+    cameras: Query<(&Camera, &GlobalTransform, &ViewportCamera)>,
+    layout: Res<ViewportLayout>,
     window: Single<&Window, With<PrimaryWindow>>,
     keyboard_mode: Res<KeyboardEditMode>,
+    guide_settings: Res<GuideSettings>,
 ) {
     // Don't update cursor from raycasting when in keyboard edit mode
     if keyboard_mode.enabled {
         return;
     }
 
-    let (camera_comp, camera_transform, editor_cam) = camera.into_inner();
-
     // If gamepad is active, use center-screen raycast from GamepadCameraState
     if gamepad_state.active {
+        cursor_state.hit_point = None;
         if let Some(grid_pos) = gamepad_state.action_grid_pos {
             cursor_state.placement_grid_pos = Some(grid_pos);
             cursor_state.placement_pos = Some(Vec3::new(
@@ -79,14 +85,15 @@ pub fn update_cursor_position(
             return;
         };
 
+        let Some((camera_comp, camera_transform)) = viewport_camera(&layout, cameras) else {
+            return;
+        };
         let Ok(ray) = camera_comp.viewport_to_world(camera_transform, cursor_position) else {
             return;
         };
 
         // Try to find a voxel intersection
-        if let Some((voxel_pos, hit_info)) =
-            find_closest_voxel_intersection_with_face(&editor_state, &ray)
-        {
+        if let Some((voxel_pos, hit_info)) = voxel_index.raycast(&ray) {
             // Hit a voxel - set grid position to hit voxel
             cursor_state.grid_pos = Some(voxel_pos);
             cursor_state.position = Some(Vec3::new(
@@ -95,6 +102,7 @@ pub fn update_cursor_position(
                 voxel_pos.2 as f32,
             ));
             cursor_state.hit_face_normal = Some(hit_info.face_normal);
+            cursor_state.hit_point = Some(ray.get_point(hit_info.distance));
 
             // Calculate placement position (adjacent to hit face)
             let placement_pos = (
@@ -108,26 +116,50 @@ pub fn update_cursor_position(
                 placement_pos.1 as f32,
                 placement_pos.2 as f32,
             ));
-        } else if let Some(ground_pos) = intersect_ground_plane(&ray) {
-            // No voxel hit, use ground plane intersection
-            let grid_x = ground_pos.x.floor() as i32;
-            let grid_z = ground_pos.z.floor() as i32;
-            let grid_pos = (grid_x, 0, grid_z);
+        } else if let Some(plane_pos) = editor_state.work_plane.intersect(&ray) {
+            // No voxel hit, use the work plane (the ground unless changed)
+            let grid_pos = editor_state.work_plane.grid_pos(plane_pos);
+            let position = Vec3::new(grid_pos.0 as f32, grid_pos.1 as f32, grid_pos.2 as f32);
+
+            // Face the side of the plane the camera is on
+            let normal = editor_state.work_plane.axis.normal();
+            let facing_normal = if ray.direction.dot(normal) > 0.0 {
+                -normal
+            } else {
+                normal
+            };
 
             cursor_state.grid_pos = Some(grid_pos);
-            cursor_state.position = Some(Vec3::new(grid_x as f32, 0.0, grid_z as f32));
-            cursor_state.hit_face_normal = Some(Vec3::Y);
+            cursor_state.position = Some(position);
+            cursor_state.hit_face_normal = Some(facing_normal);
+            cursor_state.hit_point = Some(plane_pos);
 
-            // For ground plane, placement is at the same position
+            // On the work plane, placement is at the same position
             cursor_state.placement_grid_pos = Some(grid_pos);
-            cursor_state.placement_pos = Some(Vec3::new(grid_x as f32, 0.0, grid_z as f32));
+            cursor_state.placement_pos = Some(position);
         } else {
             // No hit at all - clear cursor state
             cursor_state.grid_pos = None;
             cursor_state.position = None;
             cursor_state.hit_face_normal = None;
+            cursor_state.hit_point = None;
             cursor_state.placement_grid_pos = None;
             cursor_state.placement_pos = None;
         }
     }
+
+    // Pull the placement position onto nearby construction guides
+    if guide_settings.snap {
+        if let Some(pos) = cursor_state.placement_grid_pos {
+            let snapped = snap_to_guides(pos, &map_guides(&editor_state.current_map));
+            if snapped != pos {
+                cursor_state.placement_grid_pos = Some(snapped);
+                cursor_state.placement_pos = Some(Vec3::new(
+                    snapped.0 as f32,
+                    snapped.1 as f32,
+                    snapped.2 as f32,
+                ));
+            }
+        }
+    }
 }
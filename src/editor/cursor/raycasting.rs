@@ -1,6 +1,7 @@
 //! Ray casting utilities for cursor position detection.
+//!
+//! Rays are cast against the map through [`VoxelIndex`](super::VoxelIndex).
 
-use crate::editor::state::EditorState;
 use bevy::prelude::*;
 
 /// Information about a ray-box intersection
@@ -10,34 +11,6 @@ pub struct RayHitInfo {
     pub face_normal: Vec3,
 }
 
-/// Find the closest voxel that the ray intersects with face information
-pub fn find_closest_voxel_intersection_with_face(
-    editor_state: &EditorState,
-    ray: &Ray3d,
-) -> Option<((i32, i32, i32), RayHitInfo)> {
-    let mut closest_distance = f32::MAX;
-    let mut closest_result = None;
-
-    // Check each voxel in the map
-    for voxel_data in &editor_state.current_map.world.voxels {
-        let voxel_pos = voxel_data.pos;
-
-        // Check if ray intersects this voxel's bounding box
-        if let Some(hit_info) = ray_box_intersection_with_face(
-            ray,
-            Vec3::new(voxel_pos.0 as f32, voxel_pos.1 as f32, voxel_pos.2 as f32),
-            Vec3::splat(1.0), // Voxel size is 1x1x1
-        ) {
-            if hit_info.distance < closest_distance {
-                closest_distance = hit_info.distance;
-                closest_result = Some((voxel_pos, hit_info));
-            }
-        }
-    }
-
-    closest_result
-}
-
 /// Ray-box intersection test (AABB) with face detection
 /// Returns hit information including which face was hit
 pub fn ray_box_intersection_with_face(
@@ -131,24 +104,3 @@ pub fn ray_box_intersection_with_face(
         None
     }
 }
-
-/// Intersect ray with ground plane (y=0) as fallback
-pub fn intersect_ground_plane(ray: &Ray3d) -> Option<Vec3> {
-    let ray_origin = ray.origin;
-    let ray_direction = ray.direction.normalize();
-
-    // Check if ray is parallel to ground
-    if ray_direction.y.abs() < 0.001 {
-        return None;
-    }
-
-    // Calculate t where ray intersects y=0
-    let t = -ray_origin.y / ray_direction.y;
-
-    if t < 0.0 {
-        return None;
-    }
-
-    // Calculate world position at intersection
-    Some(ray_origin + ray_direction * t)
-}
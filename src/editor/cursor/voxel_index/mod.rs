@@ -0,0 +1,154 @@
+//! Spatial index of the map's voxels for fast ray casts.
+//!
+//! Picking the voxel under the cursor used to test the ray against every
+//! voxel in the map. [`VoxelIndex`] keeps the occupied positions in a hash
+//! set instead, and [`VoxelIndex::raycast`] steps through the grid cells the
+//! ray crosses, nearest first, stopping at the first occupied one. The cost
+//! depends on how far the ray travels, not on how many voxels the map has.
+//!
+//! The index follows the same change tracking as the renderer:
+//! `detect_map_changes` rebuilds it when the whole map is marked dirty and
+//! refreshes only the touched positions after edits like sculpt strokes.
+
+use super::raycasting::{ray_box_intersection_with_face, RayHitInfo};
+use crate::systems::game::map::format::MapData;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Occupied voxel positions of the map being edited.
+#[derive(Resource, Debug, Default)]
+pub struct VoxelIndex {
+    occupied: HashSet<(i32, i32, i32)>,
+    /// Box around every position added since the last rebuild. Removals
+    /// don't shrink it; a loose box only makes rays walk a little further.
+    bounds: Option<(IVec3, IVec3)>,
+}
+
+impl VoxelIndex {
+    /// Index every voxel of `map`.
+    pub fn from_map(map: &MapData) -> Self {
+        let mut index = Self::default();
+        index.rebuild(map);
+        index
+    }
+
+    /// Throw the index away and index every voxel of `map` again.
+    pub fn rebuild(&mut self, map: &MapData) {
+        self.occupied.clear();
+        self.bounds = None;
+        for voxel in &map.world.voxels {
+            self.insert(voxel.pos);
+        }
+    }
+
+    /// Bring `positions` up to date with `map`, after an edit that only
+    /// touched them.
+    pub fn refresh(&mut self, map: &MapData, positions: impl IntoIterator<Item = (i32, i32, i32)>) {
+        for pos in positions {
+            if map.world.voxels.contains(pos) {
+                self.insert(pos);
+            } else {
+                self.remove(pos);
+            }
+        }
+    }
+
+    pub fn insert(&mut self, pos: (i32, i32, i32)) {
+        let cell = IVec3::new(pos.0, pos.1, pos.2);
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(cell), max.max(cell)),
+            None => (cell, cell),
+        });
+        self.occupied.insert(pos);
+    }
+
+    pub fn remove(&mut self, pos: (i32, i32, i32)) {
+        self.occupied.remove(&pos);
+    }
+
+    pub fn contains(&self, pos: (i32, i32, i32)) -> bool {
+        self.occupied.contains(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.occupied.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.occupied.is_empty()
+    }
+
+    /// Find the nearest voxel the ray hits, with the face it enters through.
+    ///
+    /// Voxels are unit cubes centered on their positions. A ray starting
+    /// inside a voxel hits that voxel.
+    pub fn raycast(&self, ray: &Ray3d) -> Option<((i32, i32, i32), RayHitInfo)> {
+        let (min, max) = self.bounds?;
+        let origin = ray.origin;
+        let direction = *ray.direction;
+        let (enter, exit) =
+            clip_to_box(origin, direction, min.as_vec3() - 0.5, max.as_vec3() + 0.5)?;
+
+        // Cell the ray starts in (voxel p spans p - 0.5 to p + 0.5)
+        let start = origin + direction * enter;
+        let mut cell = (start + 0.5).floor().as_ivec3().clamp(min, max);
+        let mut step = IVec3::ZERO;
+        let mut next_boundary = Vec3::INFINITY;
+        let mut boundary_spacing = Vec3::INFINITY;
+        for axis in 0..3 {
+            let speed = direction[axis];
+            if speed == 0.0 {
+                continue;
+            }
+            step[axis] = if speed > 0.0 { 1 } else { -1 };
+            let boundary = cell[axis] as f32 + 0.5 * step[axis] as f32;
+            next_boundary[axis] = (boundary - origin[axis]) / speed;
+            boundary_spacing[axis] = 1.0 / speed.abs();
+        }
+
+        loop {
+            let pos = (cell.x, cell.y, cell.z);
+            if self.occupied.contains(&pos) {
+                if let Some(hit) = ray_box_intersection_with_face(ray, cell.as_vec3(), Vec3::ONE) {
+                    return Some((pos, hit));
+                }
+            }
+            let axis = if next_boundary.x <= next_boundary.y && next_boundary.x <= next_boundary.z {
+                0
+            } else if next_boundary.y <= next_boundary.z {
+                1
+            } else {
+                2
+            };
+            if next_boundary[axis] > exit {
+                return None;
+            }
+            cell[axis] += step[axis];
+            next_boundary[axis] += boundary_spacing[axis];
+        }
+    }
+}
+
+/// Distances along a ray at which it enters and leaves a box, starting no
+/// earlier than the origin.
+fn clip_to_box(origin: Vec3, direction: Vec3, min: Vec3, max: Vec3) -> Option<(f32, f32)> {
+    let mut enter = 0.0_f32;
+    let mut exit = f32::INFINITY;
+    for axis in 0..3 {
+        let speed = direction[axis];
+        if speed == 0.0 {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let a = (min[axis] - origin[axis]) / speed;
+        let b = (max[axis] - origin[axis]) / speed;
+        enter = enter.max(a.min(b));
+        exit = exit.min(a.max(b));
+    }
+    (enter <= exit).then_some((enter, exit))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+use super::*;
+use crate::systems::game::components::VoxelType;
+use crate::systems::game::map::format::VoxelData;
+
+fn map_with(positions: &[(i32, i32, i32)]) -> MapData {
+    let mut map = MapData::empty_map();
+    map.world.voxels = positions
+        .iter()
+        .copied()
+        .map(|pos| VoxelData::new(pos, VoxelType::Stone, None))
+        .collect();
+    map
+}
+
+fn ray(origin: Vec3, direction: Vec3) -> Ray3d {
+    Ray3d::new(origin, Dir3::new(direction).unwrap())
+}
+
+/// The old approach: test the ray against every voxel.
+fn brute_force(map: &MapData, ray: &Ray3d) -> Option<((i32, i32, i32), RayHitInfo)> {
+    map.world
+        .voxels
+        .iter()
+        .filter_map(|voxel| {
+            let center = Vec3::new(voxel.pos.0 as f32, voxel.pos.1 as f32, voxel.pos.2 as f32);
+            ray_box_intersection_with_face(ray, center, Vec3::ONE).map(|hit| (voxel.pos, hit))
+        })
+        .min_by(|a, b| a.1.distance.total_cmp(&b.1.distance))
+}
+
+#[test]
+fn raycasts_hit_the_nearest_voxel_and_its_face() {
+    let index = VoxelIndex::from_map(&map_with(&[(3, 0, 0), (6, 0, 0), (0, 0, 5)]));
+    let (pos, hit) = index
+        .raycast(&ray(Vec3::new(-2.0, 0.0, 0.0), Vec3::X))
+        .unwrap();
+    assert_eq!(pos, (3, 0, 0));
+    assert!((hit.distance - 4.5).abs() < 1e-5);
+    assert_eq!(hit.face_normal, Vec3::NEG_X);
+
+    assert!(index
+        .raycast(&ray(Vec3::new(-2.0, 0.0, 0.0), Vec3::NEG_X))
+        .is_none());
+    assert!(index
+        .raycast(&ray(Vec3::new(-2.0, 3.0, 0.0), Vec3::X))
+        .is_none());
+}
+
+#[test]
+fn raycasts_from_above_land_on_top_faces() {
+    let index = VoxelIndex::from_map(&map_with(&[(0, 0, 0), (1, 0, 0), (1, 1, 0)]));
+    let camera = ray(Vec3::new(1.2, 10.0, 0.1), Vec3::NEG_Y);
+    let (pos, hit) = index.raycast(&camera).unwrap();
+    assert_eq!(pos, (1, 1, 0));
+    assert_eq!(hit.face_normal, Vec3::Y);
+}
+
+#[test]
+fn raycasts_match_testing_every_voxel() {
+    // A rough terrain with overhangs, cast at from many angles
+    let mut positions = Vec::new();
+    for x in -6i32..6 {
+        for z in -6..6 {
+            let height = (x * 7 + z * 3).rem_euclid(4);
+            for y in 0..=height {
+                positions.push((x, y, z));
+            }
+            if (x + z) % 5 == 0 {
+                positions.push((x, 6, z));
+            }
+        }
+    }
+    let map = map_with(&positions);
+    let index = VoxelIndex::from_map(&map);
+
+    for i in 0..200 {
+        let angle = i as f32 * 0.37;
+        let origin = Vec3::new(angle.cos() * 12.0, 4.0 + (i % 7) as f32, angle.sin() * 12.0);
+        let target = Vec3::new(
+            ((i * 5) % 11) as f32 - 5.0,
+            (i % 3) as f32,
+            ((i * 3) % 9) as f32 - 4.0,
+        );
+        let ray = ray(origin, target - origin);
+        let expected = brute_force(&map, &ray);
+        let found = index.raycast(&ray);
+        // Rays through an edge may pick either voxel, at the same distance
+        match (found, expected) {
+            (None, None) => {}
+            (Some((_, a)), Some((_, b))) => assert!(
+                (a.distance - b.distance).abs() < 1e-4,
+                "ray {i} from {origin} towards {target}: {} vs {}",
+                a.distance,
+                b.distance
+            ),
+            _ => panic!("ray {i} from {origin} towards {target}: {found:?} vs {expected:?}"),
+        }
+    }
+}
+
+#[test]
+fn refreshing_touched_positions_follows_the_map() {
+    let mut map = map_with(&[(0, 0, 0), (1, 0, 0)]);
+    let mut index = VoxelIndex::from_map(&map);
+
+    map.world.voxels.retain(|voxel| voxel.pos != (0, 0, 0));
+    map.world
+        .voxels
+        .push(VoxelData::new((5, 5, 5), VoxelType::Stone, None));
+    index.refresh(&map, [(0, 0, 0), (5, 5, 5), (9, 9, 9)]);
+
+    assert!(!index.contains((0, 0, 0)));
+    assert!(index.contains((1, 0, 0)), "untouched voxels stay");
+    assert!(index.contains((5, 5, 5)));
+    assert_eq!(index.len(), 2);
+    let (pos, _) = index
+        .raycast(&ray(Vec3::new(5.0, 20.0, 5.0), Vec3::NEG_Y))
+        .unwrap();
+    assert_eq!(pos, (5, 5, 5));
+}
+
+#[test]
+fn empty_maps_have_nothing_to_hit() {
+    let index = VoxelIndex::default();
+    assert!(index.is_empty());
+    assert!(index.raycast(&ray(Vec3::ZERO, Vec3::X)).is_none());
+}
@@ -0,0 +1,380 @@
+//! Map diffing for reviewing and merging changes between two map files.
+//!
+//! [`diff_maps`] compares a base map against another version and lists the
+//! voxels and entities that were added, removed or changed. Each change can be
+//! turned into an [`EditorAction`] that applies the other map's version to the
+//! base map, so accepted changes go through the normal undo history.
+//!
+//! Voxels are matched by grid position. Entities have no stable identity in
+//! the map format, so they are matched by type and position: an entity that
+//! kept its type and position but changed properties is reported as changed,
+//! while a moved entity shows up as one removal plus one addition.
+//!
+//! Voxel orientations are compared by matrix rather than by index, since each
+//! map keeps its own `orientations` table.
+
+use crate::editor::history::EditorAction;
+use crate::systems::game::map::format::{
+    find_or_insert_orientation, EntityData, MapData, OrientationMatrix, VoxelData, IDENTITY,
+};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Kind of difference between the base map and the other map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Present only in the other map.
+    Added,
+    /// Present only in the base map.
+    Removed,
+    /// Present in both with different contents.
+    Changed,
+}
+
+impl ChangeKind {
+    /// Single-character marker used in summaries (`+`, `-`, `~`).
+    pub fn symbol(self) -> char {
+        match self {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        }
+    }
+
+    fn from_sides<T>(before: &Option<T>, after: &Option<T>) -> Self {
+        match (before, after) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Removed,
+            _ => ChangeKind::Changed,
+        }
+    }
+}
+
+/// A voxel that differs between the two maps.
+#[derive(Debug, Clone)]
+pub struct VoxelChange {
+    /// Grid position of the voxel.
+    pub pos: (i32, i32, i32),
+    /// Voxel in the base map (`None` when added).
+    pub before: Option<VoxelData>,
+    /// Voxel in the other map (`None` when removed).
+    pub after: Option<VoxelData>,
+    /// Orientation of `after`, resolved from the other map's table.
+    pub after_orientation: Option<OrientationMatrix>,
+}
+
+impl VoxelChange {
+    pub fn kind(&self) -> ChangeKind {
+        ChangeKind::from_sides(&self.before, &self.after)
+    }
+
+    /// Build the action that applies the other map's version of this voxel to
+    /// `target`. The orientation is registered in `target.orientations`.
+    pub fn to_action(&self, target: &mut MapData) -> EditorAction {
+        let after = self.after.clone().map(|mut voxel| {
+            voxel.rotation = self
+                .after_orientation
+                .filter(|m| *m != IDENTITY)
+                .map(|m| find_or_insert_orientation(&mut target.orientations, m));
+            voxel
+        });
+
+        match (&self.before, after) {
+            (None, Some(data)) => EditorAction::PlaceVoxel {
+                pos: self.pos,
+                data,
+            },
+            (Some(data), None) => EditorAction::RemoveVoxel {
+                pos: self.pos,
+                data: data.clone(),
+            },
+            (Some(old), Some(new)) => EditorAction::Batch {
+                description: format!("Merge voxel at {:?}", self.pos),
+                actions: vec![
+                    EditorAction::RemoveVoxel {
+                        pos: self.pos,
+                        data: old.clone(),
+                    },
+                    EditorAction::PlaceVoxel {
+                        pos: self.pos,
+                        data: new,
+                    },
+                ],
+            },
+            (None, None) => EditorAction::Batch {
+                description: "Empty merge".to_string(),
+                actions: Vec::new(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for VoxelChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (x, y, z) = self.pos;
+        write!(f, "{} voxel ({x}, {y}, {z})", self.kind().symbol())?;
+        match (&self.before, &self.after) {
+            (Some(old), Some(new)) => write!(f, " {}", describe_voxel_change(old, new)),
+            (Some(voxel), None) | (None, Some(voxel)) => write!(f, " {:?}", voxel.voxel_type),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// An entity that differs between the two maps.
+#[derive(Debug, Clone)]
+pub struct EntityChange {
+    /// Entity in the base map (`None` when added).
+    pub before: Option<EntityData>,
+    /// Entity in the other map (`None` when removed).
+    pub after: Option<EntityData>,
+}
+
+impl EntityChange {
+    pub fn kind(&self) -> ChangeKind {
+        ChangeKind::from_sides(&self.before, &self.after)
+    }
+
+    /// Build the action that applies the other map's version of this entity
+    /// to `target`.
+    ///
+    /// Returns `None` when the base entity is no longer present in `target`
+    /// (e.g. it was edited after the diff was taken).
+    pub fn to_action(&self, target: &MapData) -> Option<EditorAction> {
+        let find_before = |before: &EntityData| {
+            target
+                .entities
+                .iter()
+                .position(|e| entities_equal(e, before))
+        };
+
+        // Ids are only unique within a map, so actions use `target`'s own
+        match (&self.before, &self.after) {
+            (None, Some(data)) => Some(EditorAction::PlaceEntity {
+                index: target.entities.len(),
+                data: EntityData {
+                    id: target.next_entity_id(),
+                    ..data.clone()
+                },
+            }),
+            (Some(before), None) => find_before(before).map(|index| EditorAction::RemoveEntity {
+                index,
+                data: target.entities[index].clone(),
+            }),
+            (Some(before), Some(after)) => find_before(before).map(|index| {
+                let old_data = target.entities[index].clone();
+                EditorAction::ModifyEntity {
+                    index,
+                    new_data: EntityData {
+                        id: old_data.id,
+                        ..after.clone()
+                    },
+                    old_data,
+                }
+            }),
+            (None, None) => None,
+        }
+    }
+}
+
+impl fmt::Display for EntityChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(entity) = self.after.as_ref().or(self.before.as_ref()) else {
+            return Ok(());
+        };
+        let (x, y, z) = entity.position;
+        write!(
+            f,
+            "{} {:?} at ({x:.2}, {y:.2}, {z:.2})",
+            self.kind().symbol(),
+            entity.entity_type
+        )?;
+        if let (Some(old), Some(new)) = (&self.before, &self.after) {
+            let mut keys: Vec<&String> = old
+                .properties
+                .keys()
+                .chain(new.properties.keys())
+                .filter(|k| old.properties.get(*k) != new.properties.get(*k))
+                .collect();
+            keys.sort();
+            keys.dedup();
+            let keys: Vec<&str> = keys.into_iter().map(String::as_str).collect();
+            write!(f, " properties: {}", keys.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Differences between a base map and another version of it.
+#[derive(Debug, Clone, Default)]
+pub struct MapDiff {
+    /// Voxel changes, sorted by position.
+    pub voxels: Vec<VoxelChange>,
+    /// Entity changes: changed first, then removed, then added.
+    pub entities: Vec<EntityChange>,
+}
+
+impl MapDiff {
+    /// Whether the two maps have identical voxels and entities.
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty() && self.entities.is_empty()
+    }
+
+    /// Number of voxel changes of `kind`.
+    pub fn voxel_count(&self, kind: ChangeKind) -> usize {
+        self.voxels.iter().filter(|c| c.kind() == kind).count()
+    }
+
+    /// Number of entity changes of `kind`.
+    pub fn entity_count(&self, kind: ChangeKind) -> usize {
+        self.entities.iter().filter(|c| c.kind() == kind).count()
+    }
+
+    /// One-line-per-category count of the differences.
+    pub fn summary(&self) -> String {
+        format!(
+            "Voxels: {} added, {} removed, {} changed\nEntities: {} added, {} removed, {} changed",
+            self.voxel_count(ChangeKind::Added),
+            self.voxel_count(ChangeKind::Removed),
+            self.voxel_count(ChangeKind::Changed),
+            self.entity_count(ChangeKind::Added),
+            self.entity_count(ChangeKind::Removed),
+            self.entity_count(ChangeKind::Changed),
+        )
+    }
+}
+
+/// Compare `base` against `other`.
+///
+/// "Added" means present only in `other`; accepting every change turns `base`
+/// into `other` (for voxels and entities).
+pub fn diff_maps(base: &MapData, other: &MapData) -> MapDiff {
+    MapDiff {
+        voxels: diff_voxels(base, other),
+        entities: diff_entities(&base.entities, &other.entities),
+    }
+}
+
+fn diff_voxels(base: &MapData, other: &MapData) -> Vec<VoxelChange> {
+    let base_by_pos: HashMap<_, _> = base.world.voxels.iter().map(|v| (v.pos, v)).collect();
+    let other_by_pos: HashMap<_, _> = other.world.voxels.iter().map(|v| (v.pos, v)).collect();
+
+    let mut changes = Vec::new();
+    for (pos, before) in &base_by_pos {
+        match other_by_pos.get(pos) {
+            Some(after) => {
+                let before_orientation = resolve_orientation(base, before);
+                let after_orientation = resolve_orientation(other, after);
+                if before.voxel_type != after.voxel_type
+                    || before.pattern != after.pattern
+                    || before_orientation != after_orientation
+                {
+                    changes.push(VoxelChange {
+                        pos: *pos,
+                        before: Some((*before).clone()),
+                        after: Some((*after).clone()),
+                        after_orientation: Some(after_orientation),
+                    });
+                }
+            }
+            None => changes.push(VoxelChange {
+                pos: *pos,
+                before: Some((*before).clone()),
+                after: None,
+                after_orientation: None,
+            }),
+        }
+    }
+    for (pos, after) in &other_by_pos {
+        if !base_by_pos.contains_key(pos) {
+            changes.push(VoxelChange {
+                pos: *pos,
+                before: None,
+                after: Some((*after).clone()),
+                after_orientation: Some(resolve_orientation(other, after)),
+            });
+        }
+    }
+    changes.sort_by_key(|c| c.pos);
+    changes
+}
+
+fn diff_entities(base: &[EntityData], other: &[EntityData]) -> Vec<EntityChange> {
+    let mut unmatched_base: Vec<&EntityData> = base.iter().collect();
+    let mut unmatched_other: Vec<&EntityData> = Vec::new();
+
+    // Identical entities cancel out.
+    for entity in other {
+        match unmatched_base
+            .iter()
+            .position(|e| entities_equal(e, entity))
+        {
+            Some(i) => {
+                unmatched_base.remove(i);
+            }
+            None => unmatched_other.push(entity),
+        }
+    }
+
+    // Same type and position but different properties → changed.
+    let mut changes = Vec::new();
+    unmatched_other.retain(|after| {
+        let same_slot = unmatched_base
+            .iter()
+            .position(|before| same_type_and_position(before, after));
+        match same_slot {
+            Some(i) => {
+                changes.push(EntityChange {
+                    before: Some(unmatched_base.remove(i).clone()),
+                    after: Some((*after).clone()),
+                });
+                false
+            }
+            None => true,
+        }
+    });
+
+    changes.extend(unmatched_base.into_iter().map(|before| EntityChange {
+        before: Some(before.clone()),
+        after: None,
+    }));
+    changes.extend(unmatched_other.into_iter().map(|after| EntityChange {
+        before: None,
+        after: Some(after.clone()),
+    }));
+    changes
+}
+
+/// Orientation matrix of `voxel`, with "no rotation" normalised to identity.
+fn resolve_orientation(map: &MapData, voxel: &VoxelData) -> OrientationMatrix {
+    voxel
+        .rotation
+        .and_then(|i| map.orientations.get(i))
+        .copied()
+        .unwrap_or(IDENTITY)
+}
+
+fn same_type_and_position(a: &EntityData, b: &EntityData) -> bool {
+    a.entity_type == b.entity_type && a.position == b.position
+}
+
+fn entities_equal(a: &EntityData, b: &EntityData) -> bool {
+    same_type_and_position(a, b) && a.properties == b.properties
+}
+
+fn describe_voxel_change(old: &VoxelData, new: &VoxelData) -> String {
+    let mut parts = Vec::new();
+    if old.voxel_type != new.voxel_type {
+        parts.push(format!("{:?} -> {:?}", old.voxel_type, new.voxel_type));
+    }
+    if old.pattern != new.pattern {
+        parts.push(format!("pattern {:?} -> {:?}", old.pattern, new.pattern));
+    }
+    if parts.is_empty() {
+        parts.push(format!("{:?} rotated", new.voxel_type));
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,191 @@
+use super::*;
+use crate::editor::shortcuts::apply_action;
+use crate::editor::state::EditorState;
+use crate::systems::game::map::format::{axis_angle_to_matrix, EntityType, VoxelType};
+use crate::systems::game::map::geometry::RotationAxis;
+
+fn entity(entity_type: EntityType, position: (f32, f32, f32)) -> EntityData {
+    EntityData {
+        id: Default::default(),
+        entity_type,
+        position,
+        properties: HashMap::new(),
+    }
+}
+
+fn map_with(voxels: Vec<VoxelData>, entities: Vec<EntityData>) -> MapData {
+    let mut map = MapData::empty_map();
+    map.world.voxels = voxels.into();
+    map.entities = entities;
+    map
+}
+
+/// Apply every change in `diff` to `base`, as the review window's "Accept All" does.
+fn accept_all(base: MapData, diff: &MapDiff) -> MapData {
+    let mut state = EditorState::new();
+    state.current_map = base;
+    for change in &diff.voxels {
+        let action = change.to_action(&mut state.current_map);
+        apply_action(&action, &mut state);
+    }
+    for change in &diff.entities {
+        if let Some(action) = change.to_action(&state.current_map) {
+            apply_action(&action, &mut state);
+        }
+    }
+    state.current_map
+}
+
+#[test]
+fn identical_maps_have_empty_diff() {
+    let map = map_with(
+        vec![VoxelData::new((0, 0, 0), VoxelType::Grass, None)],
+        vec![entity(EntityType::PlayerSpawn, (1.0, 1.0, 1.0))],
+    );
+    assert!(diff_maps(&map, &map.clone()).is_empty());
+}
+
+#[test]
+fn detects_added_removed_and_changed_voxels() {
+    let base = map_with(
+        vec![
+            VoxelData::new((0, 0, 0), VoxelType::Grass, None),
+            VoxelData::new((1, 0, 0), VoxelType::Dirt, None),
+        ],
+        vec![],
+    );
+    let other = map_with(
+        vec![
+            VoxelData::new((0, 0, 0), VoxelType::Stone, None),
+            VoxelData::new((2, 0, 0), VoxelType::Grass, None),
+        ],
+        vec![],
+    );
+    let diff = diff_maps(&base, &other);
+    assert_eq!(diff.voxel_count(ChangeKind::Added), 1);
+    assert_eq!(diff.voxel_count(ChangeKind::Removed), 1);
+    assert_eq!(diff.voxel_count(ChangeKind::Changed), 1);
+    // Sorted by position
+    let positions: Vec<_> = diff.voxels.iter().map(|c| c.pos).collect();
+    assert_eq!(positions, vec![(0, 0, 0), (1, 0, 0), (2, 0, 0)]);
+}
+
+#[test]
+fn orientation_is_compared_by_matrix_not_index() {
+    let rotated = axis_angle_to_matrix(RotationAxis::Y, 1);
+    let mut base = map_with(
+        vec![VoxelData::new((0, 0, 0), VoxelType::Stone, None)],
+        vec![],
+    );
+    base.orientations = vec![IDENTITY, rotated];
+    base.world.voxels[0].rotation = Some(1);
+
+    let mut other = map_with(
+        vec![VoxelData::new((0, 0, 0), VoxelType::Stone, None)],
+        vec![],
+    );
+    other.orientations = vec![rotated];
+    other.world.voxels[0].rotation = Some(0);
+
+    assert!(diff_maps(&base, &other).is_empty());
+}
+
+#[test]
+fn explicit_identity_orientation_equals_no_rotation() {
+    let base = map_with(
+        vec![VoxelData::new((0, 0, 0), VoxelType::Stone, None)],
+        vec![],
+    );
+    let mut other = base.clone();
+    other.orientations = vec![IDENTITY];
+    other.world.voxels[0].rotation = Some(0);
+    assert!(diff_maps(&base, &other).is_empty());
+}
+
+#[test]
+fn entity_with_changed_properties_is_reported_as_changed() {
+    let base = map_with(vec![], vec![entity(EntityType::Npc, (1.0, 0.0, 1.0))]);
+    let mut other = base.clone();
+    other.entities[0]
+        .properties
+        .insert("name".to_string(), "Smith".to_string());
+
+    let diff = diff_maps(&base, &other);
+    assert_eq!(diff.entities.len(), 1);
+    assert_eq!(diff.entities[0].kind(), ChangeKind::Changed);
+    assert!(diff.entities[0].to_string().contains("name"));
+}
+
+#[test]
+fn moved_entity_is_a_removal_and_an_addition() {
+    let base = map_with(vec![], vec![entity(EntityType::Npc, (1.0, 0.0, 1.0))]);
+    let other = map_with(vec![], vec![entity(EntityType::Npc, (2.0, 0.0, 1.0))]);
+    let diff = diff_maps(&base, &other);
+    assert_eq!(diff.entity_count(ChangeKind::Removed), 1);
+    assert_eq!(diff.entity_count(ChangeKind::Added), 1);
+}
+
+#[test]
+fn duplicate_entities_are_matched_one_to_one() {
+    let spawn = entity(EntityType::Item, (0.0, 0.0, 0.0));
+    let base = map_with(vec![], vec![spawn.clone()]);
+    let other = map_with(vec![], vec![spawn.clone(), spawn]);
+    let diff = diff_maps(&base, &other);
+    assert_eq!(diff.entity_count(ChangeKind::Added), 1);
+    assert_eq!(diff.entities.len(), 1);
+}
+
+#[test]
+fn accepting_every_change_produces_the_other_map() {
+    let rotated = axis_angle_to_matrix(RotationAxis::Y, 2);
+    let base = map_with(
+        vec![
+            VoxelData::new((0, 0, 0), VoxelType::Grass, None),
+            VoxelData::new((1, 0, 0), VoxelType::Dirt, None),
+        ],
+        vec![
+            entity(EntityType::Npc, (1.0, 0.0, 1.0)),
+            entity(EntityType::Enemy, (3.0, 0.0, 3.0)),
+        ],
+    );
+    let mut other = map_with(
+        vec![
+            VoxelData::new((0, 0, 0), VoxelType::Stone, None),
+            VoxelData::new((5, 0, 0), VoxelType::Grass, None),
+        ],
+        vec![
+            entity(EntityType::Npc, (1.0, 0.0, 1.0)),
+            entity(EntityType::LightSource, (2.0, 2.0, 2.0)),
+        ],
+    );
+    other.orientations = vec![rotated];
+    other.world.voxels[1].rotation = Some(0);
+    other.entities[0]
+        .properties
+        .insert("name".to_string(), "Smith".to_string());
+
+    let diff = diff_maps(&base, &other);
+    let merged = accept_all(base, &diff);
+    assert!(diff_maps(&merged, &other).is_empty());
+}
+
+#[test]
+fn entity_change_without_matching_base_entity_yields_no_action() {
+    let base = map_with(vec![], vec![entity(EntityType::Npc, (1.0, 0.0, 1.0))]);
+    let other = map_with(vec![], vec![]);
+    let diff = diff_maps(&base, &other);
+    let edited = map_with(vec![], vec![]);
+    assert!(diff.entities[0].to_action(&edited).is_none());
+}
+
+#[test]
+fn summary_lists_counts_per_category() {
+    let base = map_with(
+        vec![VoxelData::new((0, 0, 0), VoxelType::Grass, None)],
+        vec![],
+    );
+    let other = map_with(vec![], vec![entity(EntityType::Item, (0.0, 0.0, 0.0))]);
+    let summary = diff_maps(&base, &other).summary();
+    assert!(summary.contains("Voxels: 0 added, 1 removed, 0 changed"));
+    assert!(summary.contains("Entities: 1 added, 0 removed, 0 changed"));
+}
@@ -0,0 +1,294 @@
+//! Multiple open maps, shown as tabs above the viewport.
+//!
+//! The active document always lives in [`EditorState`] and [`EditorHistory`],
+//! so tools and panels keep working on those resources unchanged.
+//! [`EditorDocuments`] parks every other open map together with its file
+//! path, undo history, selection and camera bookmarks; switching tabs swaps
+//! the active document out and the chosen one in. Tool settings, camera and
+//! the [clipboard](crate::editor::clipboard) are shared by all tabs.
+
+use crate::editor::bookmarks::CameraBookmarks;
+use crate::editor::history::EditorHistory;
+use crate::editor::state::{EditorState, EditorUIState, PendingAction};
+use crate::editor::tools::UpdateSelectionHighlights;
+use crate::editor::ui::dialogs::MapDataChangedEvent;
+use crate::systems::game::map::format::{EntityId, MapData};
+use crate::systems::game::map::loader::LoadDiagnostic;
+use bevy::prelude::*;
+use std::collections::HashSet;
+use std::mem;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Requests to open, switch or close document tabs
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentEvent {
+    /// Open a new empty map in its own tab
+    New,
+    /// Make the tab at this index active
+    Switch(usize),
+    /// Activate the next tab, wrapping around
+    Next,
+    /// Activate the previous tab, wrapping around
+    Previous,
+    /// Close the tab at this index, asking first if it has unsaved changes
+    Close(usize),
+    /// Close the active tab, asking first if it has unsaved changes
+    CloseActive,
+    /// Close the active tab; sent once the unsaved changes prompt is answered
+    CloseActiveConfirmed,
+}
+
+/// An open map that is not the active tab.
+pub struct EditorDocument {
+    pub map: MapData,
+    pub file_path: Option<PathBuf>,
+    pub is_modified: bool,
+    pub last_saved: Option<Instant>,
+    pub history: EditorHistory,
+    pub selected_voxels: HashSet<(i32, i32, i32)>,
+    pub selected_entities: HashSet<EntityId>,
+    pub camera_bookmarks: CameraBookmarks,
+    pub load_diagnostics: Vec<LoadDiagnostic>,
+}
+
+impl Default for EditorDocument {
+    fn default() -> Self {
+        Self {
+            map: MapData::empty_map(),
+            file_path: None,
+            is_modified: false,
+            last_saved: None,
+            history: EditorHistory::default(),
+            selected_voxels: HashSet::new(),
+            selected_entities: HashSet::new(),
+            camera_bookmarks: CameraBookmarks::default(),
+            load_diagnostics: Vec::new(),
+        }
+    }
+}
+
+impl EditorDocument {
+    /// Move the active document out of the editor resources.
+    fn take_active(editor_state: &mut EditorState, history: &mut EditorHistory) -> Self {
+        Self {
+            map: mem::replace(&mut editor_state.current_map, MapData::empty_map()),
+            file_path: editor_state.file_path.take(),
+            is_modified: mem::take(&mut editor_state.is_modified),
+            last_saved: editor_state.last_saved.take(),
+            history: mem::take(history),
+            selected_voxels: mem::take(&mut editor_state.selected_voxels),
+            selected_entities: mem::take(&mut editor_state.selected_entities),
+            camera_bookmarks: mem::take(&mut editor_state.camera_bookmarks),
+            load_diagnostics: mem::take(&mut editor_state.load_diagnostics),
+        }
+    }
+
+    /// Make this document the active one.
+    fn activate(self, editor_state: &mut EditorState, history: &mut EditorHistory) {
+        editor_state.current_map = self.map;
+        editor_state.file_path = self.file_path;
+        editor_state.is_modified = self.is_modified;
+        editor_state.last_saved = self.last_saved;
+        editor_state.selected_voxels = self.selected_voxels;
+        editor_state.selected_entities = self.selected_entities;
+        editor_state.camera_bookmarks = self.camera_bookmarks;
+        editor_state.load_diagnostics = self.load_diagnostics;
+        editor_state.outliner_scroll_to = None;
+        editor_state.mark_needs_render();
+        *history = self.history;
+    }
+
+    /// Tab label: the file name, or "Untitled" for maps never saved
+    fn display_name(&self) -> String {
+        self.file_path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or("Untitled")
+            .to_string()
+    }
+}
+
+/// Every open map. The active tab's data lives in [`EditorState`] and
+/// [`EditorHistory`]; its slot here is an empty placeholder.
+#[derive(Resource)]
+pub struct EditorDocuments {
+    slots: Vec<EditorDocument>,
+    active: usize,
+    /// Close the active tab once its in-flight save clears the modified flag
+    close_after_save: bool,
+}
+
+impl Default for EditorDocuments {
+    fn default() -> Self {
+        Self {
+            slots: vec![EditorDocument::default()],
+            active: 0,
+            close_after_save: false,
+        }
+    }
+}
+
+impl EditorDocuments {
+    /// Number of open tabs
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Always false: at least one tab is open
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Index of the active tab
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Label for the tab at `index`, with `*` when it has unsaved changes
+    pub fn tab_label(&self, index: usize, editor_state: &EditorState) -> String {
+        let (name, modified) = if index == self.active {
+            (editor_state.get_display_name(), editor_state.is_modified)
+        } else {
+            let document = &self.slots[index];
+            (document.display_name(), document.is_modified)
+        };
+        if modified {
+            format!("{}*", name)
+        } else {
+            name
+        }
+    }
+
+    /// Whether any open tab has unsaved changes
+    pub fn any_modified(&self, editor_state: &EditorState) -> bool {
+        editor_state.is_modified
+            || self
+                .slots
+                .iter()
+                .enumerate()
+                .any(|(index, document)| index != self.active && document.is_modified)
+    }
+
+    /// Make the tab at `index` active. Returns `false` if it already was, or
+    /// doesn't exist.
+    pub fn switch_to(
+        &mut self,
+        index: usize,
+        editor_state: &mut EditorState,
+        history: &mut EditorHistory,
+    ) -> bool {
+        if index == self.active || index >= self.slots.len() {
+            return false;
+        }
+        self.slots[self.active] = EditorDocument::take_active(editor_state, history);
+        mem::take(&mut self.slots[index]).activate(editor_state, history);
+        self.active = index;
+        true
+    }
+
+    /// Stop sharing the history of every background tab with a
+    /// [collaboration session](crate::editor::collab).
+    pub fn stop_sharing(&mut self) {
+        for slot in &mut self.slots {
+            slot.history.stop_sharing();
+        }
+    }
+
+    /// Open a new empty map in a tab after the others and make it active.
+    pub fn open_new(&mut self, editor_state: &mut EditorState, history: &mut EditorHistory) {
+        self.slots.push(EditorDocument::default());
+        self.switch_to(self.slots.len() - 1, editor_state, history);
+    }
+
+    /// Close the active tab, discarding its changes, and activate its
+    /// neighbour. Closing the last tab leaves a single empty map.
+    pub fn close_active(&mut self, editor_state: &mut EditorState, history: &mut EditorHistory) {
+        if self.slots.len() == 1 {
+            EditorDocument::default().activate(editor_state, history);
+            return;
+        }
+        self.slots.remove(self.active);
+        self.active = self.active.min(self.slots.len() - 1);
+        mem::take(&mut self.slots[self.active]).activate(editor_state, history);
+    }
+}
+
+/// System to apply [`DocumentEvent`]s.
+pub fn handle_document_events(
+    mut events: MessageReader<DocumentEvent>,
+    mut documents: ResMut<EditorDocuments>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut ui_state: ResMut<EditorUIState>,
+    mut map_changed_events: MessageWriter<MapDataChangedEvent>,
+    mut selection_events: MessageWriter<UpdateSelectionHighlights>,
+) {
+    let mut changed = false;
+
+    for event in events.read() {
+        let count = documents.len();
+        let active = documents.active();
+        if *event != DocumentEvent::CloseActiveConfirmed {
+            documents.close_after_save = false;
+        }
+
+        match *event {
+            DocumentEvent::New => {
+                documents.open_new(&mut editor_state, &mut history);
+                changed = true;
+            }
+            DocumentEvent::Switch(index) => {
+                changed |= documents.switch_to(index, &mut editor_state, &mut history);
+            }
+            DocumentEvent::Next => {
+                changed |=
+                    documents.switch_to((active + 1) % count, &mut editor_state, &mut history);
+            }
+            DocumentEvent::Previous => {
+                changed |= documents.switch_to(
+                    (active + count - 1) % count,
+                    &mut editor_state,
+                    &mut history,
+                );
+            }
+            DocumentEvent::Close(_) | DocumentEvent::CloseActive => {
+                if let DocumentEvent::Close(index) = *event {
+                    changed |= documents.switch_to(index, &mut editor_state, &mut history);
+                }
+                if editor_state.is_modified {
+                    ui_state.unsaved_changes_dialog_open = true;
+                    ui_state.pending_action = Some(PendingAction::CloseTab);
+                } else {
+                    documents.close_active(&mut editor_state, &mut history);
+                    changed = true;
+                }
+            }
+            DocumentEvent::CloseActiveConfirmed => {
+                // After "Save" the write may still be in flight; wait for it
+                documents.close_after_save = true;
+            }
+        }
+    }
+
+    if documents.close_after_save && !editor_state.is_modified {
+        documents.close_after_save = false;
+        documents.close_active(&mut editor_state, &mut history);
+        changed = true;
+    }
+
+    if changed {
+        info!(
+            "Active tab {}/{}: {}",
+            documents.active() + 1,
+            documents.len(),
+            editor_state.get_display_name()
+        );
+        map_changed_events.write(MapDataChangedEvent);
+        selection_events.write(UpdateSelectionHighlights);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+use super::*;
+use crate::editor::history::EditorAction;
+use crate::systems::game::map::format::{VoxelData, VoxelType};
+
+fn place_stone(pos: (i32, i32, i32)) -> EditorAction {
+    EditorAction::PlaceVoxel {
+        pos,
+        data: VoxelData {
+            pos,
+            voxel_type: VoxelType::Stone,
+            pattern: None,
+            rotation: None,
+            rotation_state: None,
+            group: None,
+        },
+    }
+}
+
+/// Editor state with a saved-to-disk map named `name` holding one edit.
+fn edited_state(name: &str, history: &mut EditorHistory) -> EditorState {
+    let mut state = EditorState::default();
+    state.current_map.metadata.name = name.to_string();
+    state.file_path = Some(PathBuf::from(format!("maps/{}.ron", name)));
+    state.selected_voxels.insert((1, 2, 3));
+    history.push(place_stone((1, 2, 3)));
+    state.mark_modified();
+    state
+}
+
+#[test]
+fn starts_with_a_single_tab() {
+    let documents = EditorDocuments::default();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents.active(), 0);
+    assert_eq!(documents.tab_label(0, &EditorState::default()), "Untitled");
+}
+
+#[test]
+fn switching_tabs_keeps_each_maps_history_and_selection() {
+    let mut history = EditorHistory::default();
+    let mut state = edited_state("castle", &mut history);
+    let mut documents = EditorDocuments::default();
+
+    documents.open_new(&mut state, &mut history);
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents.active(), 1);
+    assert_eq!(state.current_map.metadata.name, "Untitled Map");
+    assert!(!state.is_modified);
+    assert!(!history.can_undo());
+    assert!(state.selected_voxels.is_empty());
+    assert_eq!(documents.tab_label(0, &state), "castle.ron*");
+    assert!(documents.any_modified(&state));
+
+    assert!(documents.switch_to(0, &mut state, &mut history));
+    assert_eq!(state.current_map.metadata.name, "castle");
+    assert_eq!(state.file_path, Some(PathBuf::from("maps/castle.ron")));
+    assert!(state.is_modified);
+    assert!(state.render_dirty);
+    assert_eq!(history.undo_count(), 1);
+    assert!(state.selected_voxels.contains(&(1, 2, 3)));
+    assert_eq!(documents.tab_label(1, &state), "Untitled");
+}
+
+#[test]
+fn switching_to_the_active_or_a_missing_tab_does_nothing() {
+    let mut history = EditorHistory::default();
+    let mut state = edited_state("castle", &mut history);
+    let mut documents = EditorDocuments::default();
+
+    assert!(!documents.switch_to(0, &mut state, &mut history));
+    assert!(!documents.switch_to(5, &mut state, &mut history));
+    assert_eq!(state.current_map.metadata.name, "castle");
+}
+
+#[test]
+fn closing_the_active_tab_activates_its_neighbour() {
+    let mut history = EditorHistory::default();
+    let mut state = edited_state("castle", &mut history);
+    let mut documents = EditorDocuments::default();
+    documents.open_new(&mut state, &mut history);
+    state.current_map.metadata.name = "dungeon".to_string();
+    documents.open_new(&mut state, &mut history);
+
+    // Close the middle tab ("dungeon"); the last tab slides into its place
+    documents.switch_to(1, &mut state, &mut history);
+    documents.close_active(&mut state, &mut history);
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents.active(), 1);
+    assert_eq!(state.current_map.metadata.name, "Untitled Map");
+
+    // Closing the last tab of the list falls back to the one before it
+    documents.close_active(&mut state, &mut history);
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents.active(), 0);
+    assert_eq!(state.current_map.metadata.name, "castle");
+}
+
+#[test]
+fn closing_the_only_tab_leaves_an_empty_map() {
+    let mut history = EditorHistory::default();
+    let mut state = edited_state("castle", &mut history);
+    let mut documents = EditorDocuments::default();
+
+    documents.close_active(&mut state, &mut history);
+    assert_eq!(documents.len(), 1);
+    assert!(state.file_path.is_none());
+    assert!(!state.is_modified);
+    assert!(!history.can_undo());
+    assert!(!documents.any_modified(&state));
+}
@@ -0,0 +1,163 @@
+//! Arranging selected entities: aligning them along an axis, spacing them
+//! evenly and snapping them to the grid.
+//!
+//! The position helpers are pure so they can be tested without an editor;
+//! [`move_entities`] applies their result as one undo step.
+
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::state::{EditorState, SnapMode};
+
+/// World axis entities are aligned or distributed along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrangeAxis {
+    #[default]
+    X,
+    Y,
+    Z,
+}
+
+impl ArrangeAxis {
+    pub const ALL: [Self; 3] = [Self::X, Self::Y, Self::Z];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::X => "X",
+            Self::Y => "Y",
+            Self::Z => "Z",
+        }
+    }
+
+    fn get(self, position: (f32, f32, f32)) -> f32 {
+        match self {
+            Self::X => position.0,
+            Self::Y => position.1,
+            Self::Z => position.2,
+        }
+    }
+
+    fn with(self, position: (f32, f32, f32), value: f32) -> (f32, f32, f32) {
+        match self {
+            Self::X => (value, position.1, position.2),
+            Self::Y => (position.0, value, position.2),
+            Self::Z => (position.0, position.1, value),
+        }
+    }
+}
+
+/// Where along the axis aligned entities line up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignTo {
+    Min,
+    Center,
+    Max,
+}
+
+/// `positions` moved onto the lowest, middle or highest of their
+/// coordinates along `axis`; the other coordinates are kept.
+pub fn align_positions(
+    positions: &[(f32, f32, f32)],
+    axis: ArrangeAxis,
+    to: AlignTo,
+) -> Vec<(f32, f32, f32)> {
+    let Some((min, max)) = extent(positions, axis) else {
+        return Vec::new();
+    };
+    let target = match to {
+        AlignTo::Min => min,
+        AlignTo::Center => (min + max) / 2.0,
+        AlignTo::Max => max,
+    };
+    positions
+        .iter()
+        .map(|&position| axis.with(position, target))
+        .collect()
+}
+
+/// `positions` spaced evenly along `axis` between the two outermost, which
+/// stay put. Entities keep their order along the axis.
+pub fn distribute_positions(
+    positions: &[(f32, f32, f32)],
+    axis: ArrangeAxis,
+) -> Vec<(f32, f32, f32)> {
+    let Some((min, max)) = extent(positions, axis) else {
+        return Vec::new();
+    };
+    if positions.len() < 3 {
+        return positions.to_vec();
+    }
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by(|&a, &b| axis.get(positions[a]).total_cmp(&axis.get(positions[b])));
+
+    let gap = (max - min) / (positions.len() - 1) as f32;
+    let mut result = positions.to_vec();
+    for (rank, &index) in order.iter().enumerate() {
+        result[index] = axis.with(positions[index], min + gap * rank as f32);
+    }
+    result
+}
+
+/// `positions` rounded to `snap`'s grid; free snapping uses whole voxels.
+pub fn snap_positions(positions: &[(f32, f32, f32)], snap: SnapMode) -> Vec<(f32, f32, f32)> {
+    let snap = if snap == SnapMode::Free {
+        SnapMode::Full
+    } else {
+        snap
+    };
+    positions
+        .iter()
+        .map(|&position| snap.snap_position(position))
+        .collect()
+}
+
+fn extent(positions: &[(f32, f32, f32)], axis: ArrangeAxis) -> Option<(f32, f32)> {
+    positions
+        .iter()
+        .map(|&position| axis.get(position))
+        .fold(None, |extent, value| match extent {
+            None => Some((value, value)),
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+        })
+}
+
+/// Move the entities at `indices` to `positions` (in the same order) as one
+/// undo step named `description`.
+///
+/// Returns the number of entities that moved; nothing is recorded when none
+/// did.
+pub fn move_entities(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    indices: &[usize],
+    positions: &[(f32, f32, f32)],
+    description: &str,
+) -> usize {
+    let mut actions = Vec::new();
+    for (&index, &position) in indices.iter().zip(positions) {
+        let Some(entity) = editor_state.current_map.entities.get_mut(index) else {
+            continue;
+        };
+        if entity.position == position {
+            continue;
+        }
+        let old_data = entity.clone();
+        entity.position = position;
+        actions.push(EditorAction::ModifyEntity {
+            index,
+            old_data,
+            new_data: entity.clone(),
+        });
+    }
+
+    let moved = actions.len();
+    if moved > 0 {
+        history.push(EditorAction::Batch {
+            description: description.to_string(),
+            actions,
+        });
+        editor_state.mark_modified();
+    }
+    moved
+}
+
+#[cfg(test)]
+mod tests;
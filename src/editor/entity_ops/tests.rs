@@ -0,0 +1,84 @@
+use super::*;
+use crate::systems::game::map::format::{EntityData, EntityId, EntityType, MapData};
+
+const POSITIONS: [(f32, f32, f32); 3] = [(1.0, 0.0, 5.0), (4.0, 2.0, 1.0), (10.0, 1.0, 3.0)];
+
+#[test]
+fn align_moves_only_the_chosen_axis() {
+    assert_eq!(
+        align_positions(&POSITIONS, ArrangeAxis::X, AlignTo::Min),
+        vec![(1.0, 0.0, 5.0), (1.0, 2.0, 1.0), (1.0, 1.0, 3.0)]
+    );
+    assert_eq!(
+        align_positions(&POSITIONS, ArrangeAxis::Z, AlignTo::Center),
+        vec![(1.0, 0.0, 3.0), (4.0, 2.0, 3.0), (10.0, 1.0, 3.0)]
+    );
+    assert_eq!(
+        align_positions(&POSITIONS, ArrangeAxis::Y, AlignTo::Max),
+        vec![(1.0, 2.0, 5.0), (4.0, 2.0, 1.0), (10.0, 2.0, 3.0)]
+    );
+    assert!(align_positions(&[], ArrangeAxis::X, AlignTo::Min).is_empty());
+}
+
+#[test]
+fn distribute_spaces_evenly_and_keeps_order() {
+    // Along Z the order is entity 1, 2, 0
+    assert_eq!(
+        distribute_positions(&POSITIONS, ArrangeAxis::Z),
+        vec![(1.0, 0.0, 5.0), (4.0, 2.0, 1.0), (10.0, 1.0, 3.0)]
+    );
+    assert_eq!(
+        distribute_positions(&POSITIONS, ArrangeAxis::X),
+        vec![(1.0, 0.0, 5.0), (5.5, 2.0, 1.0), (10.0, 1.0, 3.0)]
+    );
+    // Two entities are already evenly spaced
+    assert_eq!(
+        distribute_positions(&POSITIONS[..2], ArrangeAxis::X),
+        POSITIONS[..2].to_vec()
+    );
+}
+
+#[test]
+fn snapping_rounds_to_the_grid() {
+    let positions = [(1.3, 0.6, 2.2)];
+    assert_eq!(
+        snap_positions(&positions, SnapMode::Half),
+        vec![(1.5, 0.5, 2.0)]
+    );
+    // Free snapping still rounds to whole voxels
+    assert_eq!(
+        snap_positions(&positions, SnapMode::Free),
+        vec![(1.0, 1.0, 2.0)]
+    );
+}
+
+#[test]
+fn moving_entities_is_one_undo_step_of_the_ones_that_moved() {
+    let mut map = MapData::empty_map();
+    map.entities = POSITIONS
+        .iter()
+        .enumerate()
+        .map(|(i, &position)| EntityData {
+            id: EntityId(i as u32 + 1),
+            entity_type: EntityType::Npc,
+            position,
+            properties: Default::default(),
+        })
+        .collect();
+    let mut state = EditorState::with_map(map);
+    let mut history = EditorHistory::default();
+
+    let aligned = align_positions(&POSITIONS, ArrangeAxis::X, AlignTo::Min);
+    let moved = move_entities(&mut state, &mut history, &[0, 1, 2], &aligned, "Align");
+
+    // The first entity was already on the minimum
+    assert_eq!(moved, 2);
+    assert_eq!(history.undo_count(), 1);
+    assert_eq!(state.current_map.entities[2].position, (1.0, 1.0, 3.0));
+
+    assert_eq!(
+        move_entities(&mut state, &mut history, &[0, 1, 2], &aligned, "Align"),
+        0
+    );
+    assert_eq!(history.undo_count(), 1);
+}
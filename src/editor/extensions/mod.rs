@@ -0,0 +1,377 @@
+//! Extension hooks for building on the editor without forking it.
+//!
+//! A downstream binary implements [`EditorPlugin`] and adds it to its app
+//! with [`AddEditorPlugin::add_editor_plugin`]. In [`EditorPlugin::build`]
+//! the plugin adds its own Bevy systems and resources to the app and
+//! registers UI hooks in [`EditorExtensions`]:
+//!
+//! - **Tools** get a toolbar button and a Tools menu entry. Selecting one
+//!   sets [`EditorTool::Custom`]; the plugin's systems handle input while
+//!   its tool is active.
+//! - **Panels** are drawn at the bottom of the properties panel.
+//! - **Entity inspectors** add a section to the properties of selected
+//!   entities of the types they name. Their edits are undoable.
+//! - **Commands** are listed in the Extensions menu and run with full
+//!   [`World`] access.
+
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::state::{EditorState, EditorTool};
+use crate::systems::game::map::format::{EntityData, EntityType};
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+/// Draws a properties panel section
+pub type PanelUi = Box<dyn Fn(&mut egui::Ui, &mut EditorState, &mut EditorHistory) + Send + Sync>;
+
+/// Draws an entity inspector; returns whether the entity was edited
+pub type InspectorUi = Box<dyn Fn(&mut egui::Ui, &mut EntityData) -> bool + Send + Sync>;
+
+/// Runs a command
+pub type CommandFn = Box<dyn Fn(&mut World) + Send + Sync>;
+
+/// An editor extension, added with [`AddEditorPlugin::add_editor_plugin`]
+pub trait EditorPlugin: Send + Sync + 'static {
+    /// Name shown in the Extensions menu
+    fn name(&self) -> &str;
+
+    /// Add the plugin's systems and resources to `app` and register its
+    /// tools, panels, inspectors and commands in `extensions`.
+    fn build(&self, app: &mut App, extensions: &mut EditorExtensions);
+}
+
+/// Adds [`EditorPlugin`]s to an [`App`]
+pub trait AddEditorPlugin {
+    fn add_editor_plugin(&mut self, plugin: impl EditorPlugin) -> &mut Self;
+}
+
+impl AddEditorPlugin for App {
+    fn add_editor_plugin(&mut self, plugin: impl EditorPlugin) -> &mut Self {
+        let mut extensions = self
+            .world_mut()
+            .remove_resource::<EditorExtensions>()
+            .unwrap_or_default();
+        plugin.build(self, &mut extensions);
+        info!("Added editor plugin '{}'", plugin.name());
+        extensions.plugins.push(plugin.name().to_string());
+        self.insert_resource(extensions);
+        self
+    }
+}
+
+/// A tool added by a plugin
+pub struct ExtensionTool {
+    /// Unique name, stored in [`EditorTool::Custom`] and shown in the UI
+    pub name: String,
+    pub icon: String,
+    pub description: String,
+}
+
+/// A properties panel section added by a plugin
+pub struct ExtensionPanel {
+    pub title: String,
+    pub ui: PanelUi,
+}
+
+/// Extra properties for some entity types, added by a plugin
+pub struct EntityInspector {
+    pub title: String,
+    pub entity_types: Vec<EntityType>,
+    pub ui: InspectorUi,
+}
+
+/// A command added by a plugin
+pub struct EditorCommand {
+    /// Unique id, sent in [`ExtensionCommandEvent`]
+    pub id: String,
+    pub label: String,
+    /// Submenu of the Extensions menu the command is listed in
+    pub menu: String,
+    pub run: CommandFn,
+}
+
+/// Event to run the registered command with this id
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionCommandEvent {
+    pub id: String,
+}
+
+/// Resource holding everything registered by editor plugins
+#[derive(Resource, Default)]
+pub struct EditorExtensions {
+    plugins: Vec<String>,
+    tools: Vec<ExtensionTool>,
+    panels: Vec<ExtensionPanel>,
+    inspectors: Vec<EntityInspector>,
+    commands: Vec<EditorCommand>,
+}
+
+impl EditorExtensions {
+    /// Register a tool. A tool with the same name replaces the earlier one.
+    pub fn add_tool(
+        &mut self,
+        name: impl Into<String>,
+        icon: impl Into<String>,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        let tool = ExtensionTool {
+            name: name.into(),
+            icon: icon.into(),
+            description: description.into(),
+        };
+        self.tools.retain(|existing| existing.name != tool.name);
+        self.tools.push(tool);
+        self
+    }
+
+    /// Register a properties panel section
+    pub fn add_panel(
+        &mut self,
+        title: impl Into<String>,
+        ui: impl Fn(&mut egui::Ui, &mut EditorState, &mut EditorHistory) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.panels.push(ExtensionPanel {
+            title: title.into(),
+            ui: Box::new(ui),
+        });
+        self
+    }
+
+    /// Register an inspector for entities of `entity_types`
+    pub fn add_entity_inspector(
+        &mut self,
+        title: impl Into<String>,
+        entity_types: impl IntoIterator<Item = EntityType>,
+        ui: impl Fn(&mut egui::Ui, &mut EntityData) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.inspectors.push(EntityInspector {
+            title: title.into(),
+            entity_types: entity_types.into_iter().collect(),
+            ui: Box::new(ui),
+        });
+        self
+    }
+
+    /// Register a command. A command with the same id replaces the earlier
+    /// one.
+    pub fn add_command(
+        &mut self,
+        id: impl Into<String>,
+        label: impl Into<String>,
+        menu: impl Into<String>,
+        run: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let command = EditorCommand {
+            id: id.into(),
+            label: label.into(),
+            menu: menu.into(),
+            run: Box::new(run),
+        };
+        self.commands.retain(|existing| existing.id != command.id);
+        self.commands.push(command);
+        self
+    }
+
+    /// Names of the added plugins, in the order they were added
+    pub fn plugins(&self) -> &[String] {
+        &self.plugins
+    }
+
+    pub fn tools(&self) -> &[ExtensionTool] {
+        &self.tools
+    }
+
+    /// Registered tool called `name`
+    pub fn tool(&self, name: &str) -> Option<&ExtensionTool> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    pub fn panels(&self) -> &[ExtensionPanel] {
+        &self.panels
+    }
+
+    /// Inspectors for entities of `entity_type`
+    pub fn inspectors_for(
+        &self,
+        entity_type: EntityType,
+    ) -> impl Iterator<Item = &EntityInspector> + '_ {
+        self.inspectors
+            .iter()
+            .filter(move |inspector| inspector.entity_types.contains(&entity_type))
+    }
+
+    /// Registered command with `id`
+    pub fn command(&self, id: &str) -> Option<&EditorCommand> {
+        self.commands.iter().find(|command| command.id == id)
+    }
+
+    /// Submenu names of the registered commands, in registration order
+    pub fn command_menus(&self) -> Vec<&str> {
+        let mut menus: Vec<&str> = Vec::new();
+        for command in &self.commands {
+            if !menus.contains(&command.menu.as_str()) {
+                menus.push(&command.menu);
+            }
+        }
+        menus
+    }
+
+    /// Commands listed in the submenu `menu`
+    pub fn commands_in<'a>(&'a self, menu: &'a str) -> impl Iterator<Item = &'a EditorCommand> {
+        self.commands
+            .iter()
+            .filter(move |command| command.menu == menu)
+    }
+
+    /// Whether no plugin registered anything
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+            && self.panels.is_empty()
+            && self.inspectors.is_empty()
+            && self.commands.is_empty()
+    }
+}
+
+/// Render the toolbar buttons of the registered tools.
+///
+/// Returns the tool to switch to when an inactive one was clicked.
+pub fn render_extension_tool_buttons(
+    ui: &mut egui::Ui,
+    editor_state: &EditorState,
+    extensions: &EditorExtensions,
+) -> Option<EditorTool> {
+    let mut clicked = None;
+    for tool in extensions.tools() {
+        let is_active =
+            matches!(&editor_state.active_tool, EditorTool::Custom { name } if *name == tool.name);
+        let button = egui::Button::new(&tool.icon).min_size(egui::vec2(28.0, 24.0));
+        let button = if is_active {
+            button.fill(egui::Color32::from_rgb(70, 100, 150))
+        } else {
+            button
+        };
+        if ui
+            .add(button)
+            .on_hover_text(format!("{}\n{}", tool.name, tool.description))
+            .clicked()
+            && !is_active
+        {
+            clicked = Some(EditorTool::Custom {
+                name: tool.name.clone(),
+            });
+        }
+    }
+    clicked
+}
+
+/// Render the Extensions menu: registered commands grouped by submenu,
+/// then the added plugins. Hidden when no plugin was added.
+pub fn render_extensions_menu(
+    ui: &mut egui::Ui,
+    extensions: &EditorExtensions,
+    command_events: &mut MessageWriter<ExtensionCommandEvent>,
+) {
+    if extensions.plugins.is_empty() {
+        return;
+    }
+    ui.menu_button("Extensions", |ui| {
+        for menu in extensions.command_menus() {
+            ui.menu_button(menu, |ui| {
+                for command in extensions.commands_in(menu) {
+                    if ui.button(&command.label).clicked() {
+                        command_events.write(ExtensionCommandEvent {
+                            id: command.id.clone(),
+                        });
+                        ui.close();
+                    }
+                }
+            });
+        }
+
+        if !extensions.commands.is_empty() {
+            ui.separator();
+        }
+        for plugin in extensions.plugins() {
+            ui.weak(format!("🧩 {}", plugin));
+        }
+    });
+}
+
+/// Render the registered panels at the bottom of the properties panel
+pub fn render_extension_panels(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    extensions: &EditorExtensions,
+) {
+    for panel in extensions.panels() {
+        ui.separator();
+        egui::CollapsingHeader::new(&panel.title)
+            .default_open(true)
+            .show(ui, |ui| (panel.ui)(ui, editor_state, history));
+    }
+}
+
+/// Render the inspectors registered for the entity at `index`, recording
+/// their edits in the history.
+pub fn render_entity_inspectors(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    extensions: &EditorExtensions,
+    index: usize,
+) {
+    let Some(entity_type) = editor_state
+        .current_map
+        .entities
+        .get(index)
+        .map(|entity| entity.entity_type)
+    else {
+        return;
+    };
+    for inspector in extensions.inspectors_for(entity_type) {
+        ui.add_space(8.0);
+        ui.group(|ui| {
+            ui.label(&inspector.title);
+            let old_data = editor_state.current_map.entities[index].clone();
+            let mut new_data = old_data.clone();
+            if (inspector.ui)(ui, &mut new_data) {
+                editor_state.current_map.entities[index] = new_data.clone();
+                history.push_coalesced(
+                    format!("entity:{index}:extension:{}", inspector.title),
+                    EditorAction::ModifyEntity {
+                        index,
+                        old_data,
+                        new_data,
+                    },
+                );
+                editor_state.mark_modified();
+            }
+        });
+    }
+}
+
+/// System to run the commands requested through [`ExtensionCommandEvent`]
+pub fn run_extension_commands(world: &mut World) {
+    let ids: Vec<String> = world
+        .resource_mut::<Messages<ExtensionCommandEvent>>()
+        .drain()
+        .map(|event| event.id)
+        .collect();
+    if ids.is_empty() {
+        return;
+    }
+    world.resource_scope(|world, extensions: Mut<EditorExtensions>| {
+        for id in ids {
+            match extensions.command(&id) {
+                Some(command) => {
+                    info!("Running extension command '{}'", id);
+                    (command.run)(world);
+                }
+                None => warn!("Unknown extension command '{}'", id),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+use super::*;
+
+#[derive(Resource, Default)]
+struct Counter(u32);
+
+struct CounterPlugin;
+
+impl EditorPlugin for CounterPlugin {
+    fn name(&self) -> &str {
+        "Counter"
+    }
+
+    fn build(&self, app: &mut App, extensions: &mut EditorExtensions) {
+        app.init_resource::<Counter>();
+        extensions
+            .add_tool("Measure", "📏", "Measure distances")
+            .add_command("counter.bump", "Bump", "Counter", |world| {
+                world.resource_mut::<Counter>().0 += 1;
+            });
+    }
+}
+
+#[test]
+fn plugins_register_into_the_app() {
+    let mut app = App::new();
+    app.add_editor_plugin(CounterPlugin);
+
+    let extensions = app.world().resource::<EditorExtensions>();
+    assert_eq!(extensions.plugins(), ["Counter".to_string()]);
+    assert_eq!(extensions.tool("Measure").unwrap().icon, "📏");
+    assert!(extensions.command("counter.bump").is_some());
+    assert!(app.world().contains_resource::<Counter>());
+}
+
+#[test]
+fn command_events_run_registered_commands() {
+    let mut app = App::new();
+    app.add_message::<ExtensionCommandEvent>()
+        .add_editor_plugin(CounterPlugin);
+    let world = app.world_mut();
+
+    world.write_message(ExtensionCommandEvent {
+        id: "counter.bump".to_string(),
+    });
+    world.write_message(ExtensionCommandEvent {
+        id: "missing".to_string(),
+    });
+    run_extension_commands(world);
+    run_extension_commands(world);
+
+    assert_eq!(world.resource::<Counter>().0, 1);
+}
+
+#[test]
+fn later_registrations_replace_earlier_ones() {
+    let mut extensions = EditorExtensions::default();
+    assert!(extensions.is_empty());
+    extensions
+        .add_tool("Measure", "📏", "old")
+        .add_tool("Measure", "📐", "new")
+        .add_command("a", "A", "Layout", |_| {})
+        .add_command("b", "B", "Export", |_| {})
+        .add_command("a", "A again", "Layout", |_| {});
+
+    assert_eq!(extensions.tools().len(), 1);
+    assert_eq!(extensions.tool("Measure").unwrap().description, "new");
+    assert_eq!(extensions.command("a").unwrap().label, "A again");
+    assert_eq!(extensions.command_menus(), vec!["Export", "Layout"]);
+    assert_eq!(extensions.commands_in("Layout").count(), 1);
+}
+
+#[test]
+fn inspectors_match_their_entity_types() {
+    let mut extensions = EditorExtensions::default();
+    extensions.add_entity_inspector("Loot", [EntityType::Item, EntityType::Npc], |_, _| false);
+
+    assert_eq!(extensions.inspectors_for(EntityType::Npc).count(), 1);
+    assert_eq!(extensions.inspectors_for(EntityType::Door).count(), 0);
+}
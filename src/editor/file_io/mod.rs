@@ -2,13 +2,16 @@
 
 use crate::editor::state::{EditorState, EditorUIState};
 use crate::systems::game::map::format::MapData;
+use crate::systems::game::map::loader::compression::{GZIP_EXTENSION, ZSTD_EXTENSION};
+use crate::systems::game::map::loader::MapLoader;
 use bevy::prelude::*;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{
-    mpsc::{channel, Receiver},
+    mpsc::{channel, Receiver, TryRecvError},
     Arc, Mutex,
 };
+use std::time::Instant;
 
 /// Event sent when the user wants to save the current map
 #[derive(Message)]
@@ -22,6 +25,8 @@ pub struct SaveMapAsEvent;
 #[derive(Message)]
 pub struct FileSavedEvent {
     pub path: PathBuf,
+    /// `EditorState::revision` of the map that was written
+    pub revision: u64,
 }
 
 /// Resource to track the save file dialog receiver
@@ -30,28 +35,117 @@ pub struct SaveFileDialogReceiver {
     pub receiver: Option<Arc<Mutex<Receiver<Option<PathBuf>>>>>,
 }
 
+/// A save running on a background thread.
+struct PendingSave {
+    path: PathBuf,
+    revision: u64,
+    started: Instant,
+    receiver: Arc<Mutex<Receiver<Result<(), String>>>>,
+}
+
+/// Resource that writes maps to disk on a background thread, so saving a
+/// large map doesn't stall the editor.
+///
+/// Only one save runs at a time. A save requested while another is running
+/// waits for it, and a newer request replaces one that is still waiting.
+#[derive(Resource, Default)]
+pub struct MapSaveTask {
+    running: Option<PendingSave>,
+    queued: Option<(MapData, PathBuf, u64)>,
+}
+
+impl MapSaveTask {
+    /// Save a copy of the map in `editor_state` to `path`.
+    pub fn request(&mut self, editor_state: &EditorState, path: PathBuf) {
+        let map = editor_state.current_map.clone();
+        let revision = editor_state.revision;
+        if self.running.is_some() {
+            info!("Save to {:?} queued behind the running save", path);
+            self.queued = Some((map, path, revision));
+        } else {
+            self.start(map, path, revision);
+        }
+    }
+
+    fn start(&mut self, map: MapData, path: PathBuf, revision: u64) {
+        let (sender, receiver) = channel();
+        let thread_path = path.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(write_map_file(map, &thread_path));
+        });
+        info!("Saving map to {:?} in background thread", path);
+        self.running = Some(PendingSave {
+            path,
+            revision,
+            started: Instant::now(),
+            receiver: Arc::new(Mutex::new(receiver)),
+        });
+    }
+
+    /// Whether a save is running or waiting to run.
+    pub fn is_saving(&self) -> bool {
+        self.running.is_some() || self.queued.is_some()
+    }
+
+    /// File being written and when writing began, while a save runs.
+    pub fn current(&self) -> Option<(&PathBuf, Instant)> {
+        self.running.as_ref().map(|save| (&save.path, save.started))
+    }
+
+    /// Take the result of the running save if it has finished, and start
+    /// the queued save, if any. Returns the path and revision that were
+    /// written, or the error.
+    pub fn poll(&mut self) -> Option<Result<(PathBuf, u64), String>> {
+        let result = {
+            let save = self.running.as_ref()?;
+            let receiver = save.receiver.lock().ok()?;
+            match receiver.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    Err("Save thread stopped unexpectedly".to_string())
+                }
+            }
+        };
+        Some(self.finish(result))
+    }
+
+    /// Block until every running and queued save has finished, returning
+    /// their results in order. Used before the editor exits.
+    pub fn wait(&mut self) -> Vec<Result<(PathBuf, u64), String>> {
+        let mut results = Vec::new();
+        while let Some(save) = &self.running {
+            let result = match save.receiver.lock() {
+                Ok(receiver) => receiver
+                    .recv()
+                    .unwrap_or_else(|_| Err("Save thread stopped unexpectedly".to_string())),
+                Err(_) => Err("Save thread stopped unexpectedly".to_string()),
+            };
+            results.push(self.finish(result));
+        }
+        results
+    }
+
+    fn finish(&mut self, result: Result<(), String>) -> Result<(PathBuf, u64), String> {
+        let save = self.running.take().expect("a save was running");
+        if let Some((map, path, revision)) = self.queued.take() {
+            self.start(map, path, revision);
+        }
+        result.map(|()| (save.path, save.revision))
+    }
+}
+
 /// System to handle SaveMapEvent - saves to existing path or triggers Save As
 pub fn handle_save_map(
     mut save_events: MessageReader<SaveMapEvent>,
     editor_state: Res<EditorState>,
     mut save_as_events: MessageWriter<SaveMapAsEvent>,
-    mut file_saved_events: MessageWriter<FileSavedEvent>,
-    mut ui_state: ResMut<EditorUIState>,
+    mut save_task: ResMut<MapSaveTask>,
 ) {
     for _event in save_events.read() {
         if let Some(path) = &editor_state.file_path {
             // We have a file path, save directly
-            match save_map_to_file(&editor_state.current_map, path) {
-                Ok(()) => {
-                    info!("Map saved successfully to: {:?}", path);
-                    file_saved_events.write(FileSavedEvent { path: path.clone() });
-                }
-                Err(e) => {
-                    error!("Failed to save map: {}", e);
-                    ui_state.error_message = format!("Failed to save map:\n{}", e);
-                    ui_state.error_dialog_open = true;
-                }
-            }
+            save_task.request(&editor_state, path.clone());
         } else {
             // No file path, trigger Save As dialog
             info!("No file path set, triggering Save As dialog");
@@ -74,6 +168,7 @@ pub fn handle_save_map_as(
         std::thread::spawn(move || {
             let result = rfd::FileDialog::new()
                 .add_filter("RON Map Files", &["ron"])
+                .add_filter("Compressed Map Files", &[GZIP_EXTENSION, ZSTD_EXTENSION])
                 .set_title("Save Map File")
                 .save_file();
 
@@ -89,8 +184,7 @@ pub fn handle_save_map_as(
 pub fn check_save_dialog_result(
     mut dialog_receiver: ResMut<SaveFileDialogReceiver>,
     editor_state: Res<EditorState>,
-    mut file_saved_events: MessageWriter<FileSavedEvent>,
-    mut ui_state: ResMut<EditorUIState>,
+    mut save_task: ResMut<MapSaveTask>,
 ) {
     // Check if we have a receiver
     let should_clear = if let Some(receiver_arc) = &dialog_receiver.receiver {
@@ -102,17 +196,7 @@ pub fn check_save_dialog_result(
                     info!("Save file selected: {:?}", path);
 
                     // Save the map to the selected file
-                    match save_map_to_file(&editor_state.current_map, &path) {
-                        Ok(()) => {
-                            info!("Map saved successfully to: {:?}", path);
-                            file_saved_events.write(FileSavedEvent { path });
-                        }
-                        Err(e) => {
-                            error!("Failed to save map: {}", e);
-                            ui_state.error_message = format!("Failed to save map:\n{}", e);
-                            ui_state.error_dialog_open = true;
-                        }
-                    }
+                    save_task.request(&editor_state, path);
                 } else {
                     info!("Save file dialog cancelled");
                 }
@@ -133,15 +217,50 @@ pub fn check_save_dialog_result(
     }
 }
 
+/// System to collect finished background saves
+pub fn poll_map_save(
+    mut save_task: ResMut<MapSaveTask>,
+    mut file_saved_events: MessageWriter<FileSavedEvent>,
+    mut ui_state: ResMut<EditorUIState>,
+) {
+    match save_task.poll() {
+        Some(Ok((path, revision))) => {
+            info!("Map saved successfully to: {:?}", path);
+            file_saved_events.write(FileSavedEvent { path, revision });
+        }
+        Some(Err(e)) => {
+            error!("Failed to save map: {}", e);
+            ui_state.error_message = format!("Failed to save map:\n{}", e);
+            ui_state.error_dialog_open = true;
+        }
+        None => {}
+    }
+}
+
 /// System to handle file saved events - updates editor state
 pub fn handle_file_saved(
     mut events: MessageReader<FileSavedEvent>,
     mut editor_state: ResMut<EditorState>,
 ) {
     for event in events.read() {
+        if editor_state.revision != event.revision {
+            // The map was edited or swapped for another while it was written,
+            // so the file is behind and the map stays modified
+            if editor_state.file_path.as_ref() == Some(&event.path) {
+                editor_state.last_saved = Some(std::time::Instant::now());
+            }
+            info!("Map changed while saving to {:?}", event.path);
+            continue;
+        }
         // Update the file path and clear modified flag
         editor_state.file_path = Some(event.path.clone());
         editor_state.clear_modified();
+        editor_state.last_saved = Some(std::time::Instant::now());
+        // Mirror the recalculated bounds unless the saved copy was shifted
+        let (min_x, _, min_y, _, min_z, _) = calculate_map_bounds(&editor_state.current_map);
+        if min_x >= 0 && min_y >= 0 && min_z >= 0 {
+            recalculate_world_bounds(&mut editor_state.current_map);
+        }
         info!("Editor state updated after save: {:?}", event.path);
     }
 }
@@ -174,13 +293,60 @@ pub(crate) fn calculate_map_bounds(map: &MapData) -> (i32, i32, i32, i32, i32, i
     (min_x, max_x, min_y, max_y, min_z, max_z)
 }
 
+/// World dimensions that exactly fit the map's voxels.
+///
+/// Expects non-negative coordinates (see [`normalize_map_coordinates`]); an
+/// empty map keeps its declared size, clamped to at least 1 on each axis.
+pub fn fitted_world_bounds(map: &MapData) -> (i32, i32, i32) {
+    if map.world.voxels.is_empty() {
+        let (width, height, depth) = map.world.dimensions();
+        return (width.max(1), height.max(1), depth.max(1));
+    }
+    let (_, max_x, _, max_y, _, max_z) = calculate_map_bounds(map);
+    ((max_x + 1).max(1), (max_y + 1).max(1), (max_z + 1).max(1))
+}
+
+/// Recalculate the world dimensions so every voxel fits.
+///
+/// Maps with fixed bounds (see `MapData::has_fixed_bounds`) keep their
+/// declared size and only grow to cover voxels outside it; other maps are
+/// fitted exactly to their voxels. Returns true if the dimensions changed.
+pub(crate) fn recalculate_world_bounds(map: &mut MapData) -> bool {
+    let old = map.world.dimensions();
+    let fitted = fitted_world_bounds(map);
+    let (width, height, depth) = if map.has_fixed_bounds() {
+        (
+            fitted.0.max(old.0),
+            fitted.1.max(old.1),
+            fitted.2.max(old.2),
+        )
+    } else {
+        fitted
+    };
+
+    map.world.width = width;
+    map.world.height = height;
+    map.world.depth = depth;
+
+    if map.world.dimensions() != old {
+        info!(
+            "Map dimensions adjusted: {:?} -> {:?}",
+            old,
+            map.world.dimensions()
+        );
+        true
+    } else {
+        false
+    }
+}
+
 /// Normalize map coordinates to ensure all voxels start at (0, 0, 0).
 ///
 /// This function handles maps with negative coordinates by:
 /// 1. Calculating the bounding box of all voxels
 /// 2. Determining the offset needed to shift minimum coordinates to (0, 0, 0)
 /// 3. Applying the offset to all voxels, entities, and camera positions
-/// 4. Setting map dimensions to match the actual span of voxels
+/// 4. Recalculating map dimensions with [`recalculate_world_bounds`]
 ///
 /// This ensures saved maps are always valid and can be loaded without errors.
 ///
@@ -188,9 +354,7 @@ pub(crate) fn calculate_map_bounds(map: &MapData) -> (i32, i32, i32, i32, i32, i
 pub(crate) fn normalize_map_coordinates(map: &mut MapData) -> bool {
     // Handle empty maps
     if map.world.voxels.is_empty() {
-        map.world.width = map.world.width.max(1);
-        map.world.height = map.world.height.max(1);
-        map.world.depth = map.world.depth.max(1);
+        recalculate_world_bounds(map);
         return false;
     }
 
@@ -232,38 +396,17 @@ pub(crate) fn normalize_map_coordinates(map: &mut MapData) -> bool {
         map.camera.look_at.0 += offset_x as f32;
         map.camera.look_at.1 += offset_y as f32;
         map.camera.look_at.2 += offset_z as f32;
-    }
-
-    // Calculate dimensions based on actual span of voxels
-    // After normalization, min values are guaranteed to be 0
-    let required_width = (max_x - min_x + 1).max(1);
-    let required_height = (max_y - min_y + 1).max(1);
-    let required_depth = (max_z - min_z + 1).max(1);
-
-    let old_width = map.world.width;
-    let old_height = map.world.height;
-    let old_depth = map.world.depth;
 
-    // Set dimensions to match actual voxel span
-    map.world.width = required_width;
-    map.world.height = required_height;
-    map.world.depth = required_depth;
-
-    if needs_normalization {
-        info!(
-            "Map normalized: dimensions=({}, {}, {}) -> ({}, {}, {})",
-            old_width, old_height, old_depth, map.world.width, map.world.height, map.world.depth
-        );
-    } else if required_width != old_width
-        || required_height != old_height
-        || required_depth != old_depth
-    {
-        info!(
-            "Map dimensions adjusted: ({}, {}, {}) -> ({}, {}, {})",
-            old_width, old_height, old_depth, map.world.width, map.world.height, map.world.depth
-        );
+        // Fixed bounds move with the content, so they grow by the shift
+        if map.has_fixed_bounds() {
+            map.world.width += offset_x;
+            map.world.height += offset_y;
+            map.world.depth += offset_z;
+        }
     }
 
+    recalculate_world_bounds(map);
+
     needs_normalization
 }
 
@@ -272,22 +415,26 @@ pub(crate) fn normalize_map_coordinates(map: &mut MapData) -> bool {
 /// This function:
 /// 1. Clones the map to avoid modifying the editor state
 /// 2. Normalizes coordinates to ensure all voxels start at (0, 0, 0)
-/// 3. Adjusts dimensions to match the actual voxel span
-/// 4. Serializes to RON format with pretty printing
+/// 3. Recalculates dimensions so every voxel is inside the world bounds
+/// 4. Encodes it as the file name asks for (see `MapLoader::encode_for_path`):
+///    pretty-printed RON with voxel regions packed, binary for `.bin`, and
+///    compressed for `.gz` or `.zst`
 /// 5. Writes to the specified file path
 pub fn save_map_to_file(map: &MapData, path: &PathBuf) -> Result<(), String> {
     // Clone the map so we can modify coordinates without affecting the editor state
-    let mut map_to_save = map.clone();
+    write_map_file(map.clone(), path)
+}
 
+/// Write a copy of a map to a file, as [`save_map_to_file`] does.
+fn write_map_file(mut map_to_save: MapData, path: &PathBuf) -> Result<(), String> {
     // Normalize coordinates and adjust dimensions to fit all voxels
     normalize_map_coordinates(&mut map_to_save);
 
-    // Serialize to RON format with pretty printing
-    let ron_string = ron::ser::to_string_pretty(&map_to_save, ron::ser::PrettyConfig::default())
-        .map_err(|e| format!("Failed to serialize map: {}", e))?;
+    // Serialize (and compress) in the format the file name asks for
+    let bytes = MapLoader::encode_for_path(&map_to_save, path).map_err(|e| e.to_string())?;
 
     // Write to file
-    fs::write(path, ron_string).map_err(|e| format!("Failed to write file: {}", e))?;
+    fs::write(path, bytes).map_err(|e| format!("Failed to write file: {}", e))?;
 
     Ok(())
 }
@@ -1,8 +1,8 @@
 use super::*;
 use crate::systems::game::components::VoxelType;
 use crate::systems::game::map::format::{
-    CameraData, EntityData, EntityType, LightingData, MapMetadata, SubVoxelPattern, VoxelData,
-    WorldData,
+    CameraData, EdgeData, EntityData, EntityType, LightingData, MapMetadata, MeshingData,
+    SubVoxelPattern, VoxelData, WeatherData, WorldData,
 };
 use std::collections::HashMap;
 
@@ -13,6 +13,7 @@ fn create_test_voxel(x: i32, y: i32, z: i32) -> VoxelData {
         pattern: Some(SubVoxelPattern::Full),
         rotation: None,
         rotation_state: None,
+        group: None,
     }
 }
 
@@ -29,13 +30,20 @@ fn create_test_map_with_voxels(voxels: Vec<VoxelData>) -> MapData {
             width: 10,
             height: 10,
             depth: 10,
-            voxels,
+            voxels: voxels.into(),
+            voxel_regions: Vec::new(),
         },
         entities: vec![],
         lighting: LightingData::default(),
+        weather: WeatherData::default(),
+        edges: EdgeData::default(),
+        meshing: MeshingData::default(),
         camera: CameraData::default(),
         custom_properties: HashMap::new(),
         orientations: vec![],
+        voxel_groups: Vec::new(),
+        camera_sequences: Vec::new(),
+        editor_metadata: None,
     }
 }
 
@@ -154,6 +162,7 @@ fn test_normalize_updates_dimensions() {
 fn test_normalize_shifts_entities() {
     let mut map = create_test_map_with_voxels(vec![create_test_voxel(-5, -3, -2)]);
     map.entities.push(EntityData {
+        id: Default::default(),
         entity_type: EntityType::PlayerSpawn,
         position: (0.0, 0.0, 0.0),
         properties: HashMap::new(),
@@ -261,3 +270,120 @@ fn test_save_normalizes_negative_coords() {
         assert!(voxel.pos.2 >= 0, "Z is negative: {}", voxel.pos.2);
     }
 }
+
+#[test]
+fn test_normalize_fits_bounds_to_furthest_voxel() {
+    // No voxel touches the origin: bounds still have to reach the far corner
+    let mut map =
+        create_test_map_with_voxels(vec![create_test_voxel(5, 2, 3), create_test_voxel(7, 4, 6)]);
+
+    normalize_map_coordinates(&mut map);
+
+    assert_eq!(map.world.dimensions(), (8, 5, 7));
+    assert!(map.world.voxels.iter().all(|v| map.world.contains(v.pos)));
+}
+
+#[test]
+fn test_fixed_bounds_survive_save_and_grow_to_fit() {
+    let mut map = create_test_map_with_voxels(vec![create_test_voxel(2, 2, 2)]);
+    map.set_fixed_bounds(true);
+
+    normalize_map_coordinates(&mut map);
+    assert_eq!(map.world.dimensions(), (10, 10, 10));
+
+    map.world.voxels.push(create_test_voxel(12, 0, 0));
+    assert!(recalculate_world_bounds(&mut map));
+    assert_eq!(map.world.dimensions(), (13, 10, 10));
+}
+
+#[test]
+fn test_fixed_bounds_shift_with_negative_coordinates() {
+    let mut map = create_test_map_with_voxels(vec![create_test_voxel(-2, 0, 0)]);
+    map.set_fixed_bounds(true);
+
+    normalize_map_coordinates(&mut map);
+
+    assert_eq!(map.world.dimensions(), (12, 10, 10));
+}
+
+#[test]
+fn test_fitted_world_bounds() {
+    let map =
+        create_test_map_with_voxels(vec![create_test_voxel(0, 0, 0), create_test_voxel(3, 1, 2)]);
+    assert_eq!(fitted_world_bounds(&map), (4, 2, 3));
+
+    let empty = create_test_map_with_voxels(vec![]);
+    assert_eq!(fitted_world_bounds(&empty), (10, 10, 10));
+}
+
+#[test]
+fn test_background_saves_run_one_at_a_time_and_report_their_revision() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let first = dir.path().join("first.ron");
+    let second = dir.path().join("second.ron");
+
+    let map = create_test_map_with_voxels(vec![create_test_voxel(0, 0, 0)]);
+    let mut editor_state = EditorState::with_map(map);
+    let mut task = MapSaveTask::default();
+    task.request(&editor_state, first.clone());
+    let first_revision = editor_state.revision;
+
+    // Edits made while the first save runs go into a queued save
+    editor_state
+        .current_map
+        .world
+        .voxels
+        .insert(create_test_voxel(1, 0, 0));
+    editor_state.mark_modified();
+    task.request(&editor_state, second.clone());
+    assert!(task.is_saving());
+    assert_eq!(task.current().map(|(path, _)| path), Some(&first));
+
+    let results = task.wait();
+    assert_eq!(
+        results,
+        vec![
+            Ok((first.clone(), first_revision)),
+            Ok((second.clone(), editor_state.revision)),
+        ]
+    );
+    assert!(!task.is_saving());
+    assert!(task.poll().is_none());
+
+    let load = |path: &PathBuf| MapLoader::read_unvalidated(path).expect("Failed to load");
+    assert_eq!(load(&first).world.voxels.len(), 1);
+    assert_eq!(load(&second).world.voxels.len(), 2);
+}
+
+#[test]
+fn test_background_save_errors_are_reported() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("missing").join("map.ron");
+
+    let mut task = MapSaveTask::default();
+    task.request(&EditorState::new(), path);
+    let results = task.wait();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
+
+#[test]
+fn test_save_compresses_by_extension_and_packs_voxel_regions() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let voxels = (0..8).map(|x| create_test_voxel(x, 0, 0)).collect();
+    let map = create_test_map_with_voxels(voxels);
+
+    let plain = dir.path().join("map.ron");
+    save_map_to_file(&map, &plain).expect("Save failed");
+    let content = std::fs::read_to_string(&plain).expect("Failed to read file");
+    assert!(content.contains("voxel_regions"));
+
+    let compressed = dir.path().join("map.ron.zst");
+    save_map_to_file(&map, &compressed).expect("Save failed");
+    let bytes = std::fs::read(&compressed).expect("Failed to read file");
+    assert!(std::str::from_utf8(&bytes).is_err(), "not plain text");
+
+    let loaded = MapLoader::read_unvalidated(&compressed).expect("Failed to load");
+    assert_eq!(loaded.world.voxels.len(), 8);
+    assert!(loaded.world.voxel_regions.is_empty());
+}
@@ -0,0 +1,234 @@
+//! Frame the selection: `F` flies the camera to where the selected voxels
+//! and entities fill the view, or the whole map when nothing is selected.
+//!
+//! The camera keeps the direction it looks in, whether it was last steered
+//! by flying or by right-drag orbiting, and backs off along it until the
+//! bounds' enclosing sphere fits the field of view. When voxels outside the
+//! bounds stand between that spot and what it frames, the camera stops in
+//! front of them instead, so the view is never blocked. The move is eased
+//! over [`FRAME_DURATION`] and given up as soon as anything else moves the
+//! camera. In quad view the orthographic views are centred and zoomed on
+//! the same bounds.
+
+use crate::editor::camera::EditorCamera;
+use crate::editor::selection_boxes::ENTITY_BOX_HALF_EXTENTS;
+use crate::editor::shortcuts::modifier_pressed;
+use crate::editor::state::EditorState;
+use crate::editor::viewports::OrthoView;
+use crate::systems::game::map::format::VoxelStore;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+
+/// Seconds the camera takes to fly to a framed view
+pub const FRAME_DURATION: f32 = 0.35;
+
+/// Room left around framed bounds, as a factor of their size
+pub const FRAME_MARGIN: f32 = 1.2;
+
+/// Smallest radius framed, so a single voxel isn't filled edge to edge
+pub const MIN_FRAME_RADIUS: f32 = 1.5;
+
+/// Step, in world units, of the line of sight check
+const SIGHT_STEP: f32 = 0.25;
+
+/// Event to frame the selection, or the whole map when nothing is selected
+#[derive(Message)]
+pub struct FrameSelectionEvent;
+
+/// Bounds of the selected voxels and entities, or of the whole map when
+/// nothing is selected. `None` for an empty map.
+pub fn framing_bounds(editor_state: &EditorState) -> Option<(Vec3, Vec3)> {
+    let map = &editor_state.current_map;
+    let nothing_selected =
+        editor_state.selected_voxels.is_empty() && editor_state.selected_entities.is_empty();
+
+    let voxel_boxes: Vec<(Vec3, Vec3)> = if nothing_selected {
+        map.world
+            .voxels
+            .iter()
+            .map(|voxel| voxel_box(voxel.pos))
+            .collect()
+    } else {
+        editor_state
+            .selected_voxels
+            .iter()
+            .map(|&pos| voxel_box(pos))
+            .collect()
+    };
+    let entity_boxes = map
+        .entities
+        .iter()
+        .filter(|entity| nothing_selected || editor_state.selected_entities.contains(&entity.id))
+        .map(|entity| {
+            let center = Vec3::from(entity.position);
+            (
+                center - ENTITY_BOX_HALF_EXTENTS,
+                center + ENTITY_BOX_HALF_EXTENTS,
+            )
+        });
+
+    voxel_boxes
+        .into_iter()
+        .chain(entity_boxes)
+        .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+}
+
+/// The unit cube centred on a voxel position.
+fn voxel_box(pos: (i32, i32, i32)) -> (Vec3, Vec3) {
+    let center = Vec3::new(pos.0 as f32, pos.1 as f32, pos.2 as f32);
+    (center - Vec3::splat(0.5), center + Vec3::splat(0.5))
+}
+
+/// Distance at which a sphere of `radius` fits a perspective view with
+/// vertical field of view `fov` (radians) and width/height `aspect_ratio`.
+pub fn framing_distance(radius: f32, fov: f32, aspect_ratio: f32) -> f32 {
+    let half_vertical = fov * 0.5;
+    let half_horizontal = (half_vertical.tan() * aspect_ratio).atan();
+    radius / half_vertical.min(half_horizontal).sin()
+}
+
+/// Where a camera looking along `forward` fits `min`..`max` in view.
+pub fn framing_position(min: Vec3, max: Vec3, forward: Vec3, fov: f32, aspect_ratio: f32) -> Vec3 {
+    let center = (min + max) * 0.5;
+    let radius = ((max - min).length() * 0.5 * FRAME_MARGIN).max(MIN_FRAME_RADIUS);
+    center - forward * framing_distance(radius, fov, aspect_ratio)
+}
+
+/// `position`, or the first spot past the voxels blocking its view of the
+/// centre of `min`..`max`. Voxels inside the bounds don't block the view.
+pub fn unobstructed_position(voxels: &VoxelStore, min: Vec3, max: Vec3, position: Vec3) -> Vec3 {
+    let center = (min + max) * 0.5;
+    let distance = position.distance(center);
+    let toward_center = (center - position).normalize_or_zero();
+    let steps = (distance / SIGHT_STEP) as usize;
+
+    // The last blocked sample before the bounds; the camera goes just past it
+    let mut clear_from = None;
+    for step in 0..=steps {
+        let sample = position + toward_center * (step as f32 * SIGHT_STEP);
+        if sample.cmpge(min).all() && sample.cmple(max).all() {
+            break;
+        }
+        let cell = sample.round();
+        if voxels.contains((cell.x as i32, cell.y as i32, cell.z as i32)) {
+            clear_from = Some(step + 1);
+        }
+    }
+    match clear_from {
+        Some(step) => position + toward_center * (step as f32 * SIGHT_STEP),
+        None => position,
+    }
+}
+
+/// An eased move of the camera to a framed view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraFlight {
+    pub from: Vec3,
+    pub to: Vec3,
+    /// Seconds since the flight started
+    pub elapsed: f32,
+    /// Position the flight last put the camera at
+    pub last: Vec3,
+}
+
+impl CameraFlight {
+    pub fn new(from: Vec3, to: Vec3) -> Self {
+        Self {
+            from,
+            to,
+            elapsed: 0.0,
+            last: from,
+        }
+    }
+
+    /// Advance by `dt` seconds, returning the camera's new position and
+    /// whether the flight has arrived.
+    pub fn advance(&mut self, dt: f32) -> (Vec3, bool) {
+        self.elapsed += dt;
+        let t = (self.elapsed / FRAME_DURATION).clamp(0.0, 1.0);
+        // Smoothstep: starts and stops gently
+        let eased = t * t * (3.0 - 2.0 * t);
+        self.last = self.from.lerp(self.to, eased);
+        (self.last, t >= 1.0)
+    }
+}
+
+/// The camera flight in progress, if any.
+#[derive(Resource, Default)]
+pub struct CameraFraming {
+    pub flight: Option<CameraFlight>,
+}
+
+/// System sending [`FrameSelectionEvent`] when `F` is pressed.
+pub fn frame_selection_on_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut contexts: EguiContexts,
+    mut frame_events: MessageWriter<FrameSelectionEvent>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyF) || modifier_pressed(&keyboard) {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if ctx.wants_keyboard_input() {
+        return;
+    }
+    frame_events.write(FrameSelectionEvent);
+}
+
+/// System starting the camera's flight to the framed bounds and framing the
+/// orthographic views on them.
+pub fn handle_frame_selection(
+    mut events: MessageReader<FrameSelectionEvent>,
+    editor_state: Res<EditorState>,
+    camera: Single<(&EditorCamera, &Projection)>,
+    mut ortho_views: Query<&mut OrthoView>,
+    mut framing: ResMut<CameraFraming>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+    let Some((min, max)) = framing_bounds(&editor_state) else {
+        info!("Nothing to frame: the map is empty");
+        return;
+    };
+
+    let (camera, projection) = *camera;
+    let (fov, aspect_ratio) = match projection {
+        Projection::Perspective(perspective) => (perspective.fov, perspective.aspect_ratio),
+        _ => (PerspectiveProjection::default().fov, 1.0),
+    };
+    let framed = framing_position(min, max, camera.forward(), fov, aspect_ratio);
+    let target = unobstructed_position(&editor_state.current_map.world.voxels, min, max, framed);
+    framing.flight = Some(CameraFlight::new(camera.position, target));
+
+    let center = (min + max) * 0.5;
+    for mut view in &mut ortho_views {
+        view.fit(center, (max - min).max_element() * FRAME_MARGIN);
+    }
+}
+
+/// System moving the camera along its flight. Moving the camera any other
+/// way (flying, scrolling, a bookmark) ends the flight.
+pub fn animate_camera_framing(
+    mut framing: ResMut<CameraFraming>,
+    mut camera: Single<&mut EditorCamera>,
+    time: Res<Time>,
+) {
+    let Some(flight) = framing.flight.as_mut() else {
+        return;
+    };
+    if camera.position != flight.last {
+        framing.flight = None;
+        return;
+    }
+    let (position, arrived) = flight.advance(time.delta_secs());
+    camera.position = position;
+    if arrived {
+        framing.flight = None;
+    }
+}
+
+#[cfg(test)]
+mod tests;
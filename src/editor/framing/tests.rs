@@ -0,0 +1,88 @@
+use super::*;
+use crate::systems::game::map::format::{EntityData, EntityId, EntityType, VoxelData, VoxelType};
+use std::collections::HashMap;
+
+fn map_with(voxels: &[(i32, i32, i32)]) -> EditorState {
+    let mut state = EditorState::default();
+    state.current_map.world.voxels = voxels
+        .iter()
+        .map(|&pos| VoxelData::new(pos, VoxelType::Stone, None))
+        .collect();
+    state.current_map.entities.clear();
+    state
+}
+
+#[test]
+fn nothing_selected_frames_the_whole_map() {
+    let mut state = map_with(&[(0, 0, 0), (9, 2, -4)]);
+    state.current_map.entities.push(EntityData {
+        id: EntityId(7),
+        entity_type: EntityType::Npc,
+        position: (3.0, 10.0, 0.0),
+        properties: HashMap::new(),
+    });
+    let (min, max) = framing_bounds(&state).unwrap();
+    assert_eq!(min, Vec3::new(-0.5, -0.5, -4.5));
+    assert_eq!(max, Vec3::new(9.5, 10.0 + ENTITY_BOX_HALF_EXTENTS.y, 0.5));
+}
+
+#[test]
+fn a_selection_frames_only_what_is_selected() {
+    let mut state = map_with(&[(0, 0, 0), (9, 2, -4)]);
+    state.selected_voxels.insert((9, 2, -4));
+    let (min, max) = framing_bounds(&state).unwrap();
+    assert_eq!(min, Vec3::new(8.5, 1.5, -4.5));
+    assert_eq!(max, Vec3::new(9.5, 2.5, -3.5));
+}
+
+#[test]
+fn an_empty_map_has_nothing_to_frame() {
+    assert_eq!(framing_bounds(&map_with(&[])), None);
+}
+
+#[test]
+fn framed_bounds_fit_the_narrower_field_of_view() {
+    let fov = std::f32::consts::FRAC_PI_2;
+    // A square view: half angle of 45°
+    let distance = framing_distance(1.0, fov, 1.0);
+    assert!((distance - std::f32::consts::SQRT_2).abs() < 1e-4);
+    // A tall view is limited by its width
+    assert!(framing_distance(1.0, fov, 0.5) > distance);
+    assert!((framing_distance(1.0, fov, 2.0) - distance).abs() < 1e-4);
+}
+
+#[test]
+fn the_camera_keeps_its_direction_and_looks_at_the_centre() {
+    let forward = Vec3::new(0.0, -1.0, -1.0).normalize();
+    let (min, max) = (Vec3::splat(-2.0), Vec3::splat(2.0));
+    let position = framing_position(min, max, forward, 1.0, 1.5);
+    let to_center = (Vec3::ZERO - position).normalize();
+    assert!(to_center.distance(forward) < 1e-4);
+    assert!(position.length() > (max - min).length() * 0.5);
+}
+
+#[test]
+fn the_camera_stops_in_front_of_voxels_blocking_the_view() {
+    // A wall at z = 5 between a camera at z = 10 and a voxel at the origin
+    let state = map_with(&[(0, 0, 0), (0, 0, 5)]);
+    let voxels = &state.current_map.world.voxels;
+    let (min, max) = (Vec3::splat(-0.5), Vec3::splat(0.5));
+
+    let position = unobstructed_position(voxels, min, max, Vec3::new(0.0, 0.0, 10.0));
+    assert!(position.z < 4.5 && position.z > 0.5);
+
+    let clear = Vec3::new(0.0, 10.0, 0.0);
+    assert_eq!(unobstructed_position(voxels, min, max, clear), clear);
+}
+
+#[test]
+fn flights_ease_to_the_target() {
+    let mut flight = CameraFlight::new(Vec3::ZERO, Vec3::X * 10.0);
+    let (halfway, arrived) = flight.advance(FRAME_DURATION * 0.5);
+    assert!(!arrived);
+    assert!((halfway.x - 5.0).abs() < 1e-4);
+    assert_eq!(flight.last, halfway);
+    let (end, arrived) = flight.advance(FRAME_DURATION);
+    assert!(arrived);
+    assert_eq!(end, Vec3::X * 10.0);
+}
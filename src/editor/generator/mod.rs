@@ -0,0 +1,579 @@
+//! Structure generator: houses, trees and bridges stamped at the cursor.
+//!
+//! **Tools → Structure Generator...** opens a window to pick a structure and
+//! its parameters. A ghost of the structure follows the cursor, centred on
+//! the placement position, and Enter or **Stamp at Cursor** places it as one
+//! undo step, like an imported [prefab](crate::editor::prefabs). The seed
+//! varies the details (door and window spots, leaf shapes, broken railings),
+//! and the same seed and parameters always give the same structure.
+
+use crate::editor::clipboard::{place_objects, EditorClipboard};
+use crate::editor::cursor::CursorState;
+use crate::editor::history::EditorHistory;
+use crate::editor::renderer::RenderMapEvent;
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::editor::tools::UpdateSelectionHighlights;
+use crate::systems::game::map::format::{
+    axis_angle_to_matrix, OrientationMatrix, SubVoxelPattern, VoxelData, VoxelType,
+};
+use crate::systems::game::map::geometry::RotationAxis;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// A generated voxel, relative to the structure's origin, and its orientation
+pub type GeneratedVoxel = (VoxelData, Option<OrientationMatrix>);
+
+/// Materials offered for walls, roofs and decks
+const MATERIALS: [VoxelType; 3] = [VoxelType::Stone, VoxelType::Dirt, VoxelType::Grass];
+
+/// Kinds of structure the generator builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StructureKind {
+    #[default]
+    House,
+    Tree,
+    Bridge,
+}
+
+impl StructureKind {
+    pub const ALL: [Self; 3] = [Self::House, Self::Tree, Self::Bridge];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::House => "🏠 House",
+            Self::Tree => "🌳 Tree",
+            Self::Bridge => "🌉 Bridge",
+        }
+    }
+}
+
+/// Shape of a house's roof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoofStyle {
+    /// One flat layer
+    Flat,
+    /// Layers shrinking towards a peak
+    Pyramid,
+    /// Two staircase slopes meeting at a ridge along the longer side
+    #[default]
+    Gable,
+}
+
+impl RoofStyle {
+    pub const ALL: [Self; 3] = [Self::Flat, Self::Pyramid, Self::Gable];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Flat => "Flat",
+            Self::Pyramid => "Pyramid",
+            Self::Gable => "Gable",
+        }
+    }
+}
+
+/// Parameters of a house.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HouseParams {
+    /// Outer size along X, walls included
+    pub width: i32,
+    /// Outer size along Z, walls included
+    pub depth: i32,
+    /// Wall height above the floor
+    pub height: i32,
+    pub roof: RoofStyle,
+    pub wall: VoxelType,
+    pub roof_material: VoxelType,
+}
+
+impl Default for HouseParams {
+    fn default() -> Self {
+        Self {
+            width: 7,
+            depth: 5,
+            height: 3,
+            roof: RoofStyle::Gable,
+            wall: VoxelType::Stone,
+            roof_material: VoxelType::Dirt,
+        }
+    }
+}
+
+/// Parameters of a tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreeParams {
+    pub trunk_height: i32,
+    pub canopy_radius: i32,
+}
+
+impl Default for TreeParams {
+    fn default() -> Self {
+        Self {
+            trunk_height: 4,
+            canopy_radius: 2,
+        }
+    }
+}
+
+/// Parameters of a bridge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BridgeParams {
+    pub length: i32,
+    pub width: i32,
+    /// How much higher the middle of the deck is than its ends
+    pub arch: i32,
+    pub railings: bool,
+    /// Span along Z instead of X
+    pub along_z: bool,
+    pub deck: VoxelType,
+}
+
+impl Default for BridgeParams {
+    fn default() -> Self {
+        Self {
+            length: 9,
+            width: 3,
+            arch: 1,
+            railings: true,
+            along_z: false,
+            deck: VoxelType::Dirt,
+        }
+    }
+}
+
+/// Repeatable pseudo-random value in `[0, 1)` for a position and seed.
+fn random(seed: u32, x: i32, y: i32, z: i32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ (z as u32).wrapping_mul(0xcb1a_b31f)
+        ^ seed.wrapping_mul(0x9e37_79b9);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb_352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846c_a68b);
+    h ^= h >> 16;
+    (h >> 8) as f32 / (1u32 << 24) as f32
+}
+
+fn block(pos: (i32, i32, i32), voxel_type: VoxelType, pattern: SubVoxelPattern) -> GeneratedVoxel {
+    (VoxelData::new(pos, voxel_type, Some(pattern)), None)
+}
+
+/// A staircase ascending `direction` quarter turns about Y from +X: 0 is
+/// +X, 1 is -Z, 2 is -X and 3 is +Z.
+fn stair(pos: (i32, i32, i32), voxel_type: VoxelType, direction: usize) -> GeneratedVoxel {
+    let (data, _) = block(pos, voxel_type, SubVoxelPattern::Staircase);
+    let orientation =
+        (direction != 0).then(|| axis_angle_to_matrix(RotationAxis::Y, direction as i32));
+    (data, orientation)
+}
+
+/// First coordinate of a run of `size` voxels centred on 0.
+fn start(size: i32) -> i32 {
+    -(size / 2)
+}
+
+/// A house standing on y = 0, centred on the origin.
+///
+/// The floor is at y = 0, the walls above it are `height` tall with a door
+/// on a random side and random windows, and the roof sits on top.
+pub fn generate_house(params: &HouseParams, seed: u32) -> Vec<GeneratedVoxel> {
+    let (w, d, h) = (
+        params.width.max(3),
+        params.depth.max(3),
+        params.height.max(2),
+    );
+    let (x0, z0) = (start(w), start(d));
+    let (x1, z1) = (x0 + w - 1, z0 + d - 1);
+    let mut voxels = Vec::new();
+
+    for x in x0..=x1 {
+        for z in z0..=z1 {
+            voxels.push(block((x, 0, z), params.wall, SubVoxelPattern::Full));
+        }
+    }
+
+    // Door: one voxel wide and two tall, away from the corners
+    let door_side = (random(seed, 0, -1, 0) * 4.0) as usize;
+    let side_length = if door_side.is_multiple_of(2) { d } else { w };
+    let door_at = 1 + (random(seed, 0, -2, 0) * (side_length - 2) as f32) as i32;
+    let door = |x: i32, y: i32, z: i32| {
+        if y > 2 {
+            return false;
+        }
+        match door_side {
+            0 => x == x1 && z == z0 + door_at,
+            1 => z == z0 && x == x0 + door_at,
+            2 => x == x0 && z == z0 + door_at,
+            _ => z == z1 && x == x0 + door_at,
+        }
+    };
+    // Windows at eye level, away from the corners
+    let window = |x: i32, y: i32, z: i32| {
+        let corner = (x == x0 || x == x1) && (z == z0 || z == z1);
+        h >= 3 && y == 2 && !corner && random(seed, x, y, z) < 0.25
+    };
+
+    for y in 1..=h {
+        for x in x0..=x1 {
+            for z in z0..=z1 {
+                let wall = x == x0 || x == x1 || z == z0 || z == z1;
+                if wall && !door(x, y, z) && !window(x, y, z) {
+                    voxels.push(block((x, y, z), params.wall, SubVoxelPattern::Full));
+                }
+            }
+        }
+    }
+
+    let roof_y = h + 1;
+    let material = params.roof_material;
+    match params.roof {
+        RoofStyle::Flat => {
+            for x in x0..=x1 {
+                for z in z0..=z1 {
+                    voxels.push(block((x, roof_y, z), material, SubVoxelPattern::Full));
+                }
+            }
+        }
+        RoofStyle::Pyramid => {
+            for layer in 0.. {
+                let (lx0, lx1, lz0, lz1) = (x0 + layer, x1 - layer, z0 + layer, z1 - layer);
+                if lx0 > lx1 || lz0 > lz1 {
+                    break;
+                }
+                for x in lx0..=lx1 {
+                    for z in lz0..=lz1 {
+                        // Hollow inside, except for the top layer
+                        let edge = x == lx0 || x == lx1 || z == lz0 || z == lz1;
+                        let top = lx0 + 1 >= lx1 || lz0 + 1 >= lz1;
+                        if edge || top {
+                            voxels.push(block(
+                                (x, roof_y + layer, z),
+                                material,
+                                SubVoxelPattern::Full,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        RoofStyle::Gable => {
+            voxels.extend(gable_roof(x0, x1, z0, z1, roof_y, params.wall, material));
+        }
+    }
+    voxels
+}
+
+/// Gable roof over the box from `(x0, z0)` to `(x1, z1)`, with its ridge
+/// along the longer side and gable walls of `wall` closing the ends.
+fn gable_roof(
+    x0: i32,
+    x1: i32,
+    z0: i32,
+    z1: i32,
+    y: i32,
+    wall: VoxelType,
+    material: VoxelType,
+) -> Vec<GeneratedVoxel> {
+    // Build with the ridge along X, swapping X and Z when Z is longer
+    let swap = z1 - z0 > x1 - x0;
+    let (a0, a1, b0, b1) = if swap {
+        (z0, z1, x0, x1)
+    } else {
+        (x0, x1, z0, z1)
+    };
+    let place = |a: i32, y: i32, b: i32| if swap { (b, y, a) } else { (a, y, b) };
+    // Swapping X and Z mirrors directions: +X <-> +Z and -Z <-> -X
+    let turn = |direction: usize| if swap { 3 - direction } else { direction };
+
+    let mut voxels = Vec::new();
+    for layer in 0.. {
+        let (low, high) = (b0 + layer, b1 - layer);
+        if low > high {
+            break;
+        }
+        let y = y + layer;
+        for a in a0..=a1 {
+            if low == high {
+                voxels.push(block(place(a, y, low), material, SubVoxelPattern::Full));
+            } else {
+                // Slopes climb towards the ridge: +Z on the low side, -Z on the high
+                voxels.push(stair(place(a, y, low), material, turn(3)));
+                voxels.push(stair(place(a, y, high), material, turn(1)));
+            }
+        }
+        for b in low + 1..high {
+            voxels.push(block(place(a0, y, b), wall, SubVoxelPattern::Full));
+            voxels.push(block(place(a1, y, b), wall, SubVoxelPattern::Full));
+        }
+    }
+    voxels
+}
+
+/// A tree growing up from y = 0 at the origin: a dirt trunk under a round
+/// grass canopy with ragged edges.
+pub fn generate_tree(params: &TreeParams, seed: u32) -> Vec<GeneratedVoxel> {
+    let trunk = params.trunk_height.max(1);
+    let r = params.canopy_radius.max(1);
+    let mut voxels: Vec<GeneratedVoxel> = (0..trunk)
+        .map(|y| block((0, y, 0), VoxelType::Dirt, SubVoxelPattern::Full))
+        .collect();
+
+    let center_y = trunk + r / 2;
+    let reach = (r as f32 + 0.5).powi(2);
+    for y in (center_y - r)..=(center_y + r) {
+        for x in -r..=r {
+            for z in -r..=r {
+                if x == 0 && z == 0 && y < trunk {
+                    continue;
+                }
+                let dist = (x * x + (y - center_y) * (y - center_y) + z * z) as f32;
+                if dist > reach || y < trunk - 1 {
+                    continue;
+                }
+                // Thin out the outermost leaves
+                let outer = dist > (r as f32 - 0.5).powi(2);
+                if outer && random(seed, x, y, z) < 0.35 {
+                    continue;
+                }
+                voxels.push(block((x, y, z), VoxelType::Grass, SubVoxelPattern::Full));
+            }
+        }
+    }
+    voxels
+}
+
+/// A bridge centred on the origin and spanning `length` voxels along X (or
+/// Z), its deck arching up in the middle, with fence railings that have a
+/// few random gaps.
+pub fn generate_bridge(params: &BridgeParams, seed: u32) -> Vec<GeneratedVoxel> {
+    let length = params.length.max(2);
+    let width = params.width.max(1);
+    let (w0, w1) = (start(width), start(width) + width - 1);
+    let a0 = start(length);
+    let mut voxels = Vec::new();
+
+    for step in 0..length {
+        let a = a0 + step;
+        // Half a sine wave from end to end
+        let t = step as f32 / (length - 1) as f32;
+        let y = (params.arch as f32 * (t * std::f32::consts::PI).sin()).round() as i32;
+        for b in w0..=w1 {
+            voxels.push(block((a, y, b), params.deck, SubVoxelPattern::Full));
+        }
+        if params.railings {
+            for b in [w0 - 1, w1 + 1] {
+                let broken = step != 0 && step != length - 1 && random(seed, a, y, b) < 0.1;
+                voxels.push(block((a, y, b), params.deck, SubVoxelPattern::Full));
+                if !broken {
+                    voxels.push(block(
+                        (a, y + 1, b),
+                        VoxelType::Dirt,
+                        SubVoxelPattern::Fence,
+                    ));
+                }
+            }
+        }
+    }
+
+    if params.along_z {
+        for (data, _) in &mut voxels {
+            let (x, y, z) = data.pos;
+            data.pos = (z, y, x);
+        }
+    }
+    voxels
+}
+
+/// Generator window state: what to build, its parameters and where
+#[derive(Resource, Default)]
+pub struct StructureGenerator {
+    pub kind: StructureKind,
+    pub house: HouseParams,
+    pub tree: TreeParams,
+    pub bridge: BridgeParams,
+    pub seed: u32,
+    /// Where the structure was last previewed. Kept while the cursor is off
+    /// the viewport so the Stamp button uses it.
+    pub anchor: Option<(i32, i32, i32)>,
+}
+
+impl StructureGenerator {
+    /// The voxels of the structure the window describes.
+    pub fn generate(&self) -> Vec<GeneratedVoxel> {
+        match self.kind {
+            StructureKind::House => generate_house(&self.house, self.seed),
+            StructureKind::Tree => generate_tree(&self.tree, self.seed),
+            StructureKind::Bridge => generate_bridge(&self.bridge, self.seed),
+        }
+    }
+}
+
+fn material_combo(ui: &mut egui::Ui, id: &str, label: &str, value: &mut VoxelType) {
+    ui.label(label);
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(format!("{:?}", value))
+        .show_ui(ui, |ui| {
+            for option in MATERIALS {
+                ui.selectable_value(value, option, format!("{:?}", option));
+            }
+        });
+    ui.end_row();
+}
+
+fn slider(ui: &mut egui::Ui, label: &str, value: &mut i32, range: std::ops::RangeInclusive<i32>) {
+    ui.label(label);
+    ui.add(egui::Slider::new(value, range));
+    ui.end_row();
+}
+
+/// System to render the Structure Generator window and stamp the structure
+/// on Enter or the Stamp button.
+#[allow(clippy::too_many_arguments)]
+pub fn render_generator_window(
+    mut contexts: EguiContexts,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cursor_state: Res<CursorState>,
+    mut ui_state: ResMut<EditorUIState>,
+    mut generator: ResMut<StructureGenerator>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut render_events: MessageWriter<RenderMapEvent>,
+    mut selection_events: MessageWriter<UpdateSelectionHighlights>,
+) {
+    if !ui_state.generator_window_open {
+        generator.anchor = None;
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if let Some(anchor) = cursor_state.placement_grid_pos {
+        generator.anchor = Some(anchor);
+    }
+
+    let mut open = true;
+    let mut stamp = keyboard.just_pressed(KeyCode::Enter) && !ctx.wants_keyboard_input();
+    egui::Window::new("🏗 Structure Generator")
+        .open(&mut open)
+        .default_width(260.0)
+        .show(ctx, |ui| {
+            let generator = &mut *generator;
+            ui.horizontal(|ui| {
+                for kind in StructureKind::ALL {
+                    ui.selectable_value(&mut generator.kind, kind, kind.label());
+                }
+            });
+            ui.separator();
+
+            egui::Grid::new("generator_params")
+                .num_columns(2)
+                .show(ui, |ui| match generator.kind {
+                    StructureKind::House => {
+                        let house = &mut generator.house;
+                        slider(ui, "Width", &mut house.width, 3..=24);
+                        slider(ui, "Depth", &mut house.depth, 3..=24);
+                        slider(ui, "Height", &mut house.height, 2..=12);
+                        ui.label("Roof");
+                        egui::ComboBox::from_id_salt("generator_roof")
+                            .selected_text(house.roof.label())
+                            .show_ui(ui, |ui| {
+                                for style in RoofStyle::ALL {
+                                    ui.selectable_value(&mut house.roof, style, style.label());
+                                }
+                            });
+                        ui.end_row();
+                        material_combo(ui, "generator_wall", "Walls", &mut house.wall);
+                        material_combo(
+                            ui,
+                            "generator_roof_material",
+                            "Roof material",
+                            &mut house.roof_material,
+                        );
+                    }
+                    StructureKind::Tree => {
+                        let tree = &mut generator.tree;
+                        slider(ui, "Trunk height", &mut tree.trunk_height, 1..=16);
+                        slider(ui, "Canopy radius", &mut tree.canopy_radius, 1..=8);
+                    }
+                    StructureKind::Bridge => {
+                        let bridge = &mut generator.bridge;
+                        slider(ui, "Length", &mut bridge.length, 2..=48);
+                        slider(ui, "Width", &mut bridge.width, 1..=7);
+                        slider(ui, "Arch", &mut bridge.arch, 0..=6);
+                        ui.label("Railings");
+                        ui.checkbox(&mut bridge.railings, "");
+                        ui.end_row();
+                        ui.label("Span along Z");
+                        ui.checkbox(&mut bridge.along_z, "");
+                        ui.end_row();
+                        material_combo(ui, "generator_deck", "Deck", &mut bridge.deck);
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                ui.label("Seed");
+                ui.add(egui::DragValue::new(&mut generator.seed));
+                if ui.button("🎲").on_hover_text("New random seed").clicked() {
+                    generator.seed = random(generator.seed, 1, 2, 3).to_bits();
+                }
+            });
+
+            ui.separator();
+            ui.weak("Move the cursor to position it");
+            stamp |= ui
+                .add_enabled(
+                    generator.anchor.is_some(),
+                    egui::Button::new("Stamp at Cursor (Enter)"),
+                )
+                .clicked();
+        });
+
+    if !open {
+        ui_state.generator_window_open = false;
+    }
+    let Some(anchor) = generator.anchor.filter(|_| stamp) else {
+        return;
+    };
+
+    let objects = EditorClipboard::from_voxels(generator.generate());
+    let description = format!("Generate {}", generator.kind.label());
+    let count = place_objects(
+        &mut editor_state,
+        &mut history,
+        &objects,
+        anchor,
+        &description,
+    );
+    info!("Generated {} voxels at {:?}", count, anchor);
+    render_events.write(RenderMapEvent);
+    selection_events.write(UpdateSelectionHighlights);
+}
+
+/// System to draw a ghost of the structure at the cursor while the
+/// generator window is open
+pub fn draw_generator_preview(
+    ui_state: Res<EditorUIState>,
+    generator: Res<StructureGenerator>,
+    mut gizmos: Gizmos,
+) {
+    if !ui_state.generator_window_open {
+        return;
+    }
+    let Some(anchor) = generator.anchor else {
+        return;
+    };
+    let anchor = Vec3::new(anchor.0 as f32, anchor.1 as f32, anchor.2 as f32);
+
+    let color = Color::srgba(0.5, 1.0, 0.6, 0.4);
+    for (data, _) in generator.generate() {
+        let (x, y, z) = data.pos;
+        gizmos.cube(
+            Transform::from_translation(anchor + Vec3::new(x as f32, y as f32, z as f32))
+                .with_scale(Vec3::splat(0.98)),
+            color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
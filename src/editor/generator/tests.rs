@@ -0,0 +1,247 @@
+use super::*;
+use std::collections::HashSet;
+
+fn positions(voxels: &[GeneratedVoxel]) -> HashSet<(i32, i32, i32)> {
+    voxels.iter().map(|(data, _)| data.pos).collect()
+}
+
+/// Everything that tells two generated voxels apart
+type Summary = Vec<(
+    (i32, i32, i32),
+    VoxelType,
+    Option<SubVoxelPattern>,
+    Option<OrientationMatrix>,
+)>;
+
+fn summary(voxels: &[GeneratedVoxel]) -> Summary {
+    voxels
+        .iter()
+        .map(|(data, orientation)| (data.pos, data.voxel_type, data.pattern, *orientation))
+        .collect()
+}
+
+fn assert_no_duplicates(voxels: &[GeneratedVoxel]) {
+    assert_eq!(positions(voxels).len(), voxels.len());
+}
+
+#[test]
+fn test_random_is_repeatable_and_in_range() {
+    for x in -20..20 {
+        let value = random(7, x, 3, -x);
+        assert_eq!(value, random(7, x, 3, -x));
+        assert!((0.0..1.0).contains(&value));
+    }
+    assert_ne!(random(1, 0, 0, 0), random(2, 0, 0, 0));
+}
+
+#[test]
+fn test_house_fits_its_dimensions() {
+    let params = HouseParams {
+        width: 7,
+        depth: 5,
+        height: 3,
+        roof: RoofStyle::Flat,
+        ..Default::default()
+    };
+    let voxels = generate_house(&params, 1);
+    let positions = positions(&voxels);
+    assert_no_duplicates(&voxels);
+
+    let xs: HashSet<i32> = positions.iter().map(|p| p.0).collect();
+    let zs: HashSet<i32> = positions.iter().map(|p| p.2).collect();
+    assert_eq!(xs.len(), 7);
+    assert_eq!(zs.len(), 5);
+    // Floor, three wall layers and a flat roof
+    assert_eq!(positions.iter().map(|p| p.1).max(), Some(4));
+    assert_eq!(positions.iter().filter(|p| p.1 == 0).count(), 35);
+    assert_eq!(positions.iter().filter(|p| p.1 == 4).count(), 35);
+    // The inside is empty
+    assert!(!positions.contains(&(0, 1, 0)));
+}
+
+#[test]
+fn test_house_has_a_door() {
+    for seed in 0..20 {
+        let params = HouseParams::default();
+        let positions = positions(&generate_house(&params, seed));
+        let (x0, z0) = (start(params.width), start(params.depth));
+        let (x1, z1) = (x0 + params.width - 1, z0 + params.depth - 1);
+
+        // Some wall column is open at y = 1 and y = 2, and not at a corner
+        let door = (x0..=x1)
+            .flat_map(|x| (z0..=z1).map(move |z| (x, z)))
+            .filter(|&(x, z)| x == x0 || x == x1 || z == z0 || z == z1)
+            .filter(|&(x, z)| !((x == x0 || x == x1) && (z == z0 || z == z1)))
+            .any(|(x, z)| !positions.contains(&(x, 1, z)) && !positions.contains(&(x, 2, z)));
+        assert!(door, "seed {} has no door", seed);
+    }
+}
+
+#[test]
+fn test_same_seed_gives_same_structure() {
+    let generator = StructureGenerator {
+        seed: 42,
+        ..Default::default()
+    };
+    for kind in StructureKind::ALL {
+        let generator = StructureGenerator { kind, ..generator };
+        assert_eq!(
+            summary(&generator.generate()),
+            summary(&generator.generate())
+        );
+    }
+}
+
+#[test]
+fn test_seed_changes_details() {
+    let params = TreeParams {
+        trunk_height: 4,
+        canopy_radius: 4,
+    };
+    let first = summary(&generate_tree(&params, 0));
+    let differ = (1..10).any(|seed| summary(&generate_tree(&params, seed)) != first);
+    assert!(differ);
+}
+
+#[test]
+fn test_gable_roof_slopes_towards_ridge() {
+    let params = HouseParams {
+        width: 9,
+        depth: 5,
+        height: 2,
+        roof: RoofStyle::Gable,
+        ..Default::default()
+    };
+    let voxels = generate_house(&params, 0);
+    assert_no_duplicates(&voxels);
+    let stair_at = |pos: (i32, i32, i32)| {
+        voxels
+            .iter()
+            .find(|(data, _)| data.pos == pos && data.pattern == Some(SubVoxelPattern::Staircase))
+            .map(|(_, orientation)| *orientation)
+    };
+
+    // The ridge runs along X; the low-Z slope climbs +Z, the high-Z one -Z
+    assert_eq!(
+        stair_at((0, 3, -2)),
+        Some(Some(axis_angle_to_matrix(RotationAxis::Y, 3)))
+    );
+    assert_eq!(
+        stair_at((0, 3, 2)),
+        Some(Some(axis_angle_to_matrix(RotationAxis::Y, 1)))
+    );
+    // A full ridge row on top
+    assert!(voxels
+        .iter()
+        .any(|(data, _)| data.pos == (0, 5, 0) && data.pattern == Some(SubVoxelPattern::Full)));
+}
+
+#[test]
+fn test_gable_ridge_follows_longer_side() {
+    let params = HouseParams {
+        width: 5,
+        depth: 9,
+        height: 2,
+        roof: RoofStyle::Gable,
+        ..Default::default()
+    };
+    let voxels = generate_house(&params, 0);
+    assert_no_duplicates(&voxels);
+    let low_x = voxels
+        .iter()
+        .find(|(data, _)| data.pos == (-2, 3, 0))
+        .expect("slope over the low-X wall");
+    // Climbs +X, which needs no rotation
+    assert_eq!(low_x.0.pattern, Some(SubVoxelPattern::Staircase));
+    assert_eq!(low_x.1, None);
+}
+
+#[test]
+fn test_pyramid_roof_has_a_peak() {
+    let params = HouseParams {
+        width: 5,
+        depth: 5,
+        height: 2,
+        roof: RoofStyle::Pyramid,
+        ..Default::default()
+    };
+    let voxels = generate_house(&params, 0);
+    assert_no_duplicates(&voxels);
+    let top = voxels.iter().map(|(data, _)| data.pos.1).max();
+    assert_eq!(top, Some(5));
+    assert!(positions(&voxels).contains(&(0, 5, 0)));
+}
+
+#[test]
+fn test_tree_has_trunk_and_canopy() {
+    let params = TreeParams {
+        trunk_height: 5,
+        canopy_radius: 3,
+    };
+    let voxels = generate_tree(&params, 9);
+    assert_no_duplicates(&voxels);
+    for y in 0..5 {
+        assert!(voxels
+            .iter()
+            .any(|(data, _)| data.pos == (0, y, 0) && data.voxel_type == VoxelType::Dirt));
+    }
+    let leaves = voxels
+        .iter()
+        .filter(|(data, _)| data.voxel_type == VoxelType::Grass)
+        .count();
+    assert!(leaves > 20);
+    assert!(voxels
+        .iter()
+        .all(|(data, _)| data.pos.0.abs() <= 3 && data.pos.2.abs() <= 3));
+}
+
+#[test]
+fn test_bridge_spans_its_length_and_arches() {
+    let params = BridgeParams {
+        length: 11,
+        width: 3,
+        arch: 2,
+        railings: false,
+        along_z: false,
+        deck: VoxelType::Stone,
+    };
+    let voxels = generate_bridge(&params, 0);
+    assert_no_duplicates(&voxels);
+    assert_eq!(voxels.len(), 33);
+    let height_at = |x: i32| {
+        voxels
+            .iter()
+            .find(|(data, _)| data.pos.0 == x && data.pos.2 == 0)
+            .map(|(data, _)| data.pos.1)
+    };
+    assert_eq!(height_at(-5), Some(0));
+    assert_eq!(height_at(0), Some(2));
+    assert_eq!(height_at(5), Some(0));
+}
+
+#[test]
+fn test_bridge_railings_and_direction() {
+    let params = BridgeParams {
+        length: 6,
+        width: 1,
+        arch: 0,
+        railings: true,
+        along_z: true,
+        deck: VoxelType::Stone,
+    };
+    let voxels = generate_bridge(&params, 3);
+    assert_no_duplicates(&voxels);
+    // Spans Z, with railings either side in X
+    assert!(voxels.iter().all(|(data, _)| data.pos.0.abs() <= 1));
+    let fences: Vec<_> = voxels
+        .iter()
+        .filter(|(data, _)| data.pattern == Some(SubVoxelPattern::Fence))
+        .collect();
+    assert!(!fences.is_empty());
+    assert!(fences.iter().all(|(data, _)| data.pos.1 == 1));
+    // The ends always have posts
+    for z in [-3, 2] {
+        assert!(fences.iter().any(|(data, _)| data.pos == (-1, 1, z)));
+        assert!(fences.iter().any(|(data, _)| data.pos == (1, 1, z)));
+    }
+}
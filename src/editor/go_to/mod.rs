@@ -0,0 +1,167 @@
+//! "Go To" dialog: jump the camera to coordinates or a named entity.
+//!
+//! Ctrl+G opens a small window where the user types `x y z` or (part of) an
+//! entity name. Names are looked up in the outliner's name index, so they
+//! match what the outliner lists. Jumping keeps the camera's orientation and
+//! backs it off from the target; a matched entity is also selected.
+
+use crate::editor::camera::EditorCamera;
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::editor::tools::UpdateSelectionHighlights;
+use crate::editor::ui::OutlinerState;
+use crate::systems::game::go_to::{GoToDestination, GoToHistory, GoToTarget};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// Distance the camera stops in front of a jump target
+pub const GO_TO_VIEW_DISTANCE: f32 = 8.0;
+
+/// Maximum number of entity name suggestions listed under the query
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Event to move the camera to a resolved jump target
+#[derive(Message)]
+pub struct GoToEvent(pub GoToDestination);
+
+/// Query text and recent jumps of the "Go To" dialog.
+#[derive(Resource, Default)]
+pub struct GoToDialog {
+    pub query: String,
+    /// Why the last query could not be resolved
+    pub error: Option<String>,
+    pub history: GoToHistory,
+}
+
+/// Position the camera `GO_TO_VIEW_DISTANCE` back from `target` along its
+/// current view direction, so the target ends up in the centre of the view.
+pub fn jump_camera_to(camera: &mut EditorCamera, target: Vec3) {
+    camera.position = target - camera.forward() * GO_TO_VIEW_DISTANCE;
+}
+
+/// System to render the "Go To" dialog while it is open.
+pub fn render_go_to_dialog(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<EditorUIState>,
+    mut dialog: ResMut<GoToDialog>,
+    outliner_state: Res<OutlinerState>,
+    mut go_to_events: MessageWriter<GoToEvent>,
+    mut shown_last_frame: Local<bool>,
+) {
+    let just_opened = !*shown_last_frame;
+    *shown_last_frame = ui_state.go_to_dialog_open;
+    if !ui_state.go_to_dialog_open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut submit: Option<String> = None;
+    let mut close = false;
+    let mut open = true;
+
+    egui::Window::new("Go To")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(300.0)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+        .show(ctx, |ui| {
+            ui.label("Coordinates (x y z) or entity name:");
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut dialog.query)
+                    .hint_text("e.g. 4 2 -7 or Elder")
+                    .desired_width(f32::INFINITY),
+            );
+            if just_opened {
+                response.request_focus();
+            }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                submit = Some(dialog.query.clone());
+            }
+            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                close = true;
+            }
+
+            if let Some(error) = &dialog.error {
+                ui.colored_label(egui::Color32::from_rgb(220, 110, 110), error);
+            }
+
+            // Entity names matching the typed text
+            if let Some(GoToTarget::Entity(name)) = GoToTarget::parse(&dialog.query) {
+                let matches = outliner_state.name_index.matches(&name);
+                if !matches.is_empty() {
+                    ui.separator();
+                    for (name, _) in matches.into_iter().take(MAX_SUGGESTIONS) {
+                        if ui.selectable_label(false, format!("📍 {}", name)).clicked() {
+                            submit = Some(name.to_string());
+                        }
+                    }
+                }
+            }
+
+            if !dialog.history.is_empty() {
+                ui.separator();
+                ui.label(egui::RichText::new("Recent").small().weak());
+                for query in dialog.history.entries() {
+                    if ui
+                        .selectable_label(false, format!("🕐 {}", query))
+                        .clicked()
+                    {
+                        submit = Some(query.to_string());
+                    }
+                }
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Go").clicked() {
+                    submit = Some(dialog.query.clone());
+                }
+                if ui.button("Cancel").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    if let Some(query) = submit {
+        let destination =
+            GoToTarget::parse(&query).and_then(|target| outliner_state.name_index.resolve(&target));
+        match destination {
+            Some(destination) => {
+                info!("Go to {:?}: {:?}", query.trim(), destination.position);
+                dialog.history.record(&query);
+                dialog.query.clear();
+                dialog.error = None;
+                go_to_events.write(GoToEvent(destination));
+                close = true;
+            }
+            None if query.trim().is_empty() => {}
+            None => dialog.error = Some(format!("No entity named \"{}\"", query.trim())),
+        }
+    }
+
+    if close || !open {
+        ui_state.go_to_dialog_open = false;
+        dialog.error = None;
+    }
+}
+
+/// System to move the camera to jump targets and select jumped-to entities
+pub fn handle_go_to(
+    mut events: MessageReader<GoToEvent>,
+    mut camera: Single<&mut EditorCamera>,
+    mut editor_state: ResMut<EditorState>,
+    mut selection_events: MessageWriter<UpdateSelectionHighlights>,
+) {
+    for GoToEvent(destination) in events.read() {
+        jump_camera_to(&mut camera, destination.position);
+        if let Some(index) = destination.entity {
+            editor_state.select_entity(index);
+            selection_events.write(UpdateSelectionHighlights);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
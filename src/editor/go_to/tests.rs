@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn jump_keeps_orientation_and_centres_the_target() {
+    let mut camera = EditorCamera {
+        position: Vec3::new(100.0, 50.0, 100.0),
+        yaw: 0.7,
+        pitch: 0.4,
+        ..default()
+    };
+    let target = Vec3::new(3.0, 1.0, -2.0);
+
+    jump_camera_to(&mut camera, target);
+
+    assert_eq!(camera.yaw, 0.7);
+    assert_eq!(camera.pitch, 0.4);
+    assert!((camera.position.distance(target) - GO_TO_VIEW_DISTANCE).abs() < 1e-4);
+    let looked_at = camera.position + camera.forward() * GO_TO_VIEW_DISTANCE;
+    assert!(looked_at.distance(target) < 1e-4);
+}
@@ -37,13 +37,19 @@ pub fn create_infinite_grid_mesh(
         config.opacity * 1.5,
     ];
 
+    let minor_color = [
+        config.minor_color.to_srgba().red,
+        config.minor_color.to_srgba().green,
+        config.minor_color.to_srgba().blue,
+        config.opacity * 0.5,
+    ];
+
     // Helper to add a line
-    let mut add_line = |start: Vec3, end: Vec3, is_major: bool| {
+    let mut add_line = |start: Vec3, end: Vec3, color: [f32; 4]| {
         let start_idx = positions.len() as u32;
         positions.push([start.x, start.y, start.z]);
         positions.push([end.x, end.y, end.z]);
 
-        let color = if is_major { major_color } else { regular_color };
         colors.push(color);
         colors.push(color);
 
@@ -51,34 +57,48 @@ pub fn create_infinite_grid_mesh(
         indices.push(start_idx + 1);
     };
 
+    // Lines are counted rather than accumulated so sub-grid lines don't drift
+    let subdivisions = config.subdivisions.max(1);
+    let step = config.spacing / subdivisions as f32;
+    let line_color = |line: u32, coord: f32| {
+        if !line.is_multiple_of(subdivisions) {
+            return minor_color;
+        }
+        // Major lines at integer boundaries (every Nth voxel)
+        let voxel = (coord + 0.5).floor() as i32;
+        if voxel % config.major_line_interval == 0 {
+            major_color
+        } else {
+            regular_color
+        }
+    };
+
     // Grid lines parallel to X axis (running along width)
     // Lines at half-integer positions: -0.5, 0.5, 1.5, 2.5, ... (voxel boundaries)
+    let mut line = 0;
     let mut z = bounds.min_z;
     while z <= bounds.max_z {
-        // Major lines at integer boundaries (every Nth voxel)
-        let z_voxel = (z + 0.5).floor() as i32;
-        let is_major = z_voxel % config.major_line_interval == 0;
         add_line(
             Vec3::new(bounds.min_x, 0.0, z),
             Vec3::new(bounds.max_x, 0.0, z),
-            is_major,
+            line_color(line, z),
         );
-        z += config.spacing;
+        line += 1;
+        z = bounds.min_z + line as f32 * step;
     }
 
     // Grid lines parallel to Z axis (running along depth)
     // Lines at half-integer positions: -0.5, 0.5, 1.5, 2.5, ... (voxel boundaries)
+    let mut line = 0;
     let mut x = bounds.min_x;
     while x <= bounds.max_x {
-        // Major lines at integer boundaries (every Nth voxel)
-        let x_voxel = (x + 0.5).floor() as i32;
-        let is_major = x_voxel % config.major_line_interval == 0;
         add_line(
             Vec3::new(x, 0.0, bounds.min_z),
             Vec3::new(x, 0.0, bounds.max_z),
-            is_major,
+            line_color(line, x),
         );
-        x += config.spacing;
+        line += 1;
+        x = bounds.min_x + line as f32 * step;
     }
 
     let mut mesh = Mesh::new(PrimitiveTopology::LineList, Default::default());
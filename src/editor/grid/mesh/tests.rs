@@ -10,3 +10,23 @@ fn test_create_infinite_grid_mesh() {
     assert!(mesh.attribute(Mesh::ATTRIBUTE_POSITION).is_some());
     assert!(mesh.indices().is_some());
 }
+
+#[test]
+fn test_subdivisions_add_sub_grid_lines() {
+    let config = InfiniteGridConfig::default();
+    let line_count = |mesh: &Mesh| mesh.indices().unwrap().len() / 2;
+    let plain = create_infinite_grid_mesh(&config, Vec3::ZERO, None);
+
+    let quarter = InfiniteGridConfig {
+        subdivisions: 4,
+        ..config
+    };
+    let fine = create_infinite_grid_mesh(&quarter, Vec3::ZERO, None);
+
+    // Three extra lines per cell along each axis
+    let cells_per_axis = line_count(&plain) / 2 - 1;
+    assert_eq!(
+        line_count(&fine),
+        line_count(&plain) + 2 * 3 * cells_per_axis
+    );
+}
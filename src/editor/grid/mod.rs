@@ -2,6 +2,7 @@
 //!
 //! This module provides an infinite grid that spans in all directions,
 //! dynamically regenerating based on camera position for efficient rendering.
+//! The grid is drawn on the editor's [`WorkPlane`], the ground by default.
 //!
 //! ## Optimizations
 //! - **Distance culling**: Only renders grid within render_distance of camera
@@ -12,10 +13,12 @@ mod bounds;
 mod cursor_indicator;
 mod mesh;
 mod systems;
+mod work_plane;
 
 pub use cursor_indicator::{spawn_cursor_indicator, update_cursor_indicator, CursorIndicator};
 pub use mesh::{create_infinite_grid_mesh, spawn_infinite_grid};
 pub use systems::{update_grid_visibility, update_infinite_grid};
+pub use work_plane::{WorkPlane, WorkPlaneAxis};
 
 use bevy::prelude::*;
 
@@ -35,6 +38,9 @@ pub struct InfiniteGridConfig {
     /// Every Nth line is rendered as a major line (thicker/different color)
     pub major_line_interval: i32,
 
+    /// Fainter lines drawn per cell to show fine snap points (1 for none)
+    pub subdivisions: u32,
+
     /// Grid opacity
     pub opacity: f32,
 
@@ -44,6 +50,9 @@ pub struct InfiniteGridConfig {
     /// Major grid line color (every Nth line)
     pub major_color: Color,
 
+    /// Sub-grid line color (between regular lines)
+    pub minor_color: Color,
+
     /// Last camera position (for regeneration detection)
     pub last_camera_pos: Vec3,
 
@@ -57,9 +66,11 @@ impl Default for InfiniteGridConfig {
             spacing: 1.0,
             render_distance: 100.0, // Base render distance, scales with camera distance
             major_line_interval: 10,
+            subdivisions: 1,
             opacity: 0.3,
             color: Color::srgba(0.5, 0.5, 0.5, 0.3),
             major_color: Color::srgba(0.7, 0.7, 0.7, 0.5),
+            minor_color: Color::srgba(0.4, 0.4, 0.4, 0.15),
             last_camera_pos: Vec3::ZERO,
             regeneration_threshold: 2.0,
         }
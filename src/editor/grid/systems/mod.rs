@@ -1,7 +1,7 @@
 //! Grid update systems.
 
 use super::mesh::create_infinite_grid_mesh;
-use super::{EditorGrid, InfiniteGridConfig};
+use super::{EditorGrid, InfiniteGridConfig, WorkPlane};
 use bevy::prelude::*;
 
 /// Check if grid should be regenerated based on camera movement
@@ -11,6 +11,7 @@ pub fn should_regenerate_grid(camera_pos: Vec3, last_pos: Vec3, threshold: f32)
 }
 
 /// System to update infinite grid based on camera movement with frustum culling
+#[allow(clippy::type_complexity)]
 pub fn update_infinite_grid(
     mut config: ResMut<InfiniteGridConfig>,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -22,11 +23,40 @@ pub fn update_infinite_grid(
         ),
         With<crate::editor::camera::EditorCamera>,
     >,
-    grid_query: Query<(Entity, &Mesh3d), With<EditorGrid>>,
+    mut grid_query: Query<
+        (&Mesh3d, &mut Transform),
+        (
+            With<EditorGrid>,
+            Without<crate::editor::camera::EditorCamera>,
+        ),
+    >,
+    editor_state: Res<crate::editor::state::EditorState>,
 ) {
     let (camera_transform, frustum, _editor_camera) = camera.into_inner();
 
-    let camera_pos = camera_transform.translation;
+    // Move the grid onto the work plane; the mesh itself is always built in
+    // the plane's local XZ coordinates
+    let plane_transform = editor_state.work_plane.transform();
+    let mut plane_changed = false;
+    for (_mesh, mut transform) in grid_query.iter_mut() {
+        if *transform != plane_transform {
+            *transform = plane_transform;
+            plane_changed = true;
+        }
+    }
+
+    // Show sub-grid lines while a fine snap mode is active
+    let subdivisions = editor_state.active_snap(false).subdivisions();
+    let subdivisions_changed = subdivisions != config.subdivisions;
+    if subdivisions_changed {
+        config.subdivisions = subdivisions;
+    }
+
+    // Camera position relative to the plane, so its height is the distance to it
+    let camera_pos = plane_transform
+        .compute_affine()
+        .inverse()
+        .transform_point3(camera_transform.translation);
 
     // Scale render distance based on camera height
     // The further the camera is from ground, the larger the grid render area
@@ -46,6 +76,8 @@ pub fn update_infinite_grid(
         config.last_camera_pos,
         config.regeneration_threshold,
     ) && !height_changed
+        && !subdivisions_changed
+        && !plane_changed
     {
         return;
     }
@@ -60,10 +92,13 @@ pub fn update_infinite_grid(
     };
 
     // Regenerate grid mesh with frustum culling and dynamic render distance
-    let new_mesh = create_infinite_grid_mesh(&dynamic_config, camera_pos, Some(frustum));
+    // The frustum culling tests world-space boxes around Y = 0, so it only
+    // applies to the ground plane
+    let frustum = (editor_state.work_plane == WorkPlane::default()).then_some(frustum);
+    let new_mesh = create_infinite_grid_mesh(&dynamic_config, camera_pos, frustum);
 
     // Update existing grid entity
-    for (_entity, mesh_handle) in grid_query.iter() {
+    for (mesh_handle, _transform) in grid_query.iter() {
         if let Some(mesh) = meshes.get_mut(mesh_handle.0.id()) {
             *mesh = new_mesh.clone();
         }
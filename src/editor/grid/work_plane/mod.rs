@@ -0,0 +1,108 @@
+//! Work plane the grid is drawn on and the cursor falls back to.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_2;
+
+/// Orientation of the work plane, named by the two axes it spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WorkPlaneAxis {
+    /// Horizontal, for floors and ceilings
+    #[default]
+    XZ,
+    /// Vertical, facing along Z
+    XY,
+    /// Vertical, facing along X
+    YZ,
+}
+
+impl WorkPlaneAxis {
+    pub const ALL: [WorkPlaneAxis; 3] = [Self::XZ, Self::XY, Self::YZ];
+
+    /// Axis perpendicular to the plane.
+    pub fn normal(self) -> Vec3 {
+        match self {
+            Self::XZ => Vec3::Y,
+            Self::XY => Vec3::Z,
+            Self::YZ => Vec3::X,
+        }
+    }
+
+    /// Name of the axis the offset moves the plane along.
+    pub fn offset_axis(self) -> &'static str {
+        match self {
+            Self::XZ => "Y",
+            Self::XY => "Z",
+            Self::YZ => "X",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::XZ => "XZ (Floor)",
+            Self::XY => "XY (Wall)",
+            Self::YZ => "YZ (Wall)",
+        }
+    }
+}
+
+/// Plane the cursor lands on when no voxel is under it.
+///
+/// The plane passes through the centers of one layer of voxels, `offset`
+/// voxels along its normal, so hits on it map to voxels in that layer. The
+/// default is the ground plane (Y = 0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct WorkPlane {
+    pub axis: WorkPlaneAxis,
+    pub offset: i32,
+}
+
+impl WorkPlane {
+    /// Point where `ray` crosses the plane, if it does so in front of its origin.
+    pub fn intersect(&self, ray: &Ray3d) -> Option<Vec3> {
+        let normal = self.axis.normal();
+        let facing = ray.direction.dot(normal);
+
+        // Check if ray is parallel to the plane
+        if facing.abs() < 0.001 {
+            return None;
+        }
+
+        let t = (self.offset as f32 - ray.origin.dot(normal)) / facing;
+        if t < 0.0 {
+            return None;
+        }
+
+        Some(ray.get_point(t))
+    }
+
+    /// Voxel position containing `point`, which should lie on the plane.
+    pub fn grid_pos(&self, point: Vec3) -> (i32, i32, i32) {
+        let (x, y, z) = (
+            point.x.round() as i32,
+            point.y.round() as i32,
+            point.z.round() as i32,
+        );
+        // Use the exact layer so float error can't push hits into a neighbor
+        match self.axis {
+            WorkPlaneAxis::XZ => (x, self.offset, z),
+            WorkPlaneAxis::XY => (x, y, self.offset),
+            WorkPlaneAxis::YZ => (self.offset, y, z),
+        }
+    }
+
+    /// Transform placing a grid mesh built in the XZ plane onto this plane.
+    pub fn transform(&self) -> Transform {
+        let rotation = match self.axis {
+            WorkPlaneAxis::XZ => Quat::IDENTITY,
+            // Local Y (the grid's normal) onto Z
+            WorkPlaneAxis::XY => Quat::from_rotation_x(FRAC_PI_2),
+            // Local Y onto X
+            WorkPlaneAxis::YZ => Quat::from_rotation_z(-FRAC_PI_2),
+        };
+        Transform::from_translation(self.axis.normal() * self.offset as f32).with_rotation(rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests;
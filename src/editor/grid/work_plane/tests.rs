@@ -0,0 +1,43 @@
+use super::*;
+
+fn ray(origin: Vec3, direction: Vec3) -> Ray3d {
+    Ray3d::new(origin, Dir3::new(direction).unwrap())
+}
+
+#[test]
+fn test_default_work_plane_is_ground() {
+    let plane = WorkPlane::default();
+    let hit = plane
+        .intersect(&ray(Vec3::new(2.2, 10.0, -3.4), Vec3::NEG_Y))
+        .unwrap();
+    assert!((hit - Vec3::new(2.2, 0.0, -3.4)).length() < 1e-5);
+    assert_eq!(plane.grid_pos(hit), (2, 0, -3));
+}
+
+#[test]
+fn test_vertical_work_plane_intersection() {
+    let plane = WorkPlane {
+        axis: WorkPlaneAxis::XY,
+        offset: 5,
+    };
+    let hit = plane
+        .intersect(&ray(Vec3::new(1.0, 3.0, -2.0), Vec3::Z))
+        .unwrap();
+    assert!((hit - Vec3::new(1.0, 3.0, 5.0)).length() < 1e-5);
+    assert_eq!(plane.grid_pos(hit), (1, 3, 5));
+
+    // Parallel rays and rays pointing away never hit
+    assert!(plane.intersect(&ray(Vec3::ZERO, Vec3::X)).is_none());
+    assert!(plane.intersect(&ray(Vec3::ZERO, Vec3::NEG_Z)).is_none());
+}
+
+#[test]
+fn test_transform_maps_grid_normal_onto_plane_normal() {
+    for axis in WorkPlaneAxis::ALL {
+        let plane = WorkPlane { axis, offset: -2 };
+        let transform = plane.transform();
+        let normal = transform.rotation * Vec3::Y;
+        assert!((normal - axis.normal()).length() < 1e-5, "{axis:?}");
+        assert_eq!(transform.translation, axis.normal() * -2.0);
+    }
+}
@@ -0,0 +1,416 @@
+//! Construction guides: axis-aligned planes and lines the cursor snaps to.
+//!
+//! Guides help line up structures far apart from each other, such as two
+//! towers of a wall or the pillars of a long bridge. They are editor-only:
+//! the map keeps them in its `editor_metadata` section, which the game never
+//! reads. The Guides window (View menu) adds, edits and
+//! removes them and turns showing and snapping on and off.
+//!
+//! While snapping is on, a placement position within [`SNAP_REACH`] voxels
+//! of a guide moves onto it. Lines win over planes, and planes across
+//! different axes combine, so two crossing planes snap like a line.
+
+use crate::editor::camera::EditorCamera;
+use crate::editor::cursor::CursorState;
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::systems::game::map::format::MapData;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// How many voxels away from a guide the placement position snaps onto it
+pub const SNAP_REACH: i32 = 1;
+
+/// Half the size of the area guides are drawn over, around the camera
+const DRAW_EXTENT: i32 = 32;
+
+/// Voxels between the lines drawn across a guide plane
+const PLANE_LINE_SPACING: i32 = 4;
+
+const GUIDE_COLOR: Color = Color::srgba(0.2, 0.85, 1.0, 0.9);
+const PLANE_COLOR: Color = Color::srgba(0.2, 0.85, 1.0, 0.3);
+
+/// World axis a guide is aligned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GuideAxis {
+    #[default]
+    X,
+    Y,
+    Z,
+}
+
+impl GuideAxis {
+    pub const ALL: [GuideAxis; 3] = [Self::X, Self::Y, Self::Z];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::X => "X",
+            Self::Y => "Y",
+            Self::Z => "Z",
+        }
+    }
+
+    pub fn unit(self) -> Vec3 {
+        match self {
+            Self::X => Vec3::X,
+            Self::Y => Vec3::Y,
+            Self::Z => Vec3::Z,
+        }
+    }
+
+    /// The two axes perpendicular to this one.
+    pub fn others(self) -> [GuideAxis; 2] {
+        match self {
+            Self::X => [Self::Y, Self::Z],
+            Self::Y => [Self::X, Self::Z],
+            Self::Z => [Self::X, Self::Y],
+        }
+    }
+
+    /// This axis' coordinate of `pos`.
+    pub fn of(self, pos: (i32, i32, i32)) -> i32 {
+        match self {
+            Self::X => pos.0,
+            Self::Y => pos.1,
+            Self::Z => pos.2,
+        }
+    }
+
+    /// `pos` with this axis' coordinate replaced by `value`.
+    pub fn with(self, pos: (i32, i32, i32), value: i32) -> (i32, i32, i32) {
+        match self {
+            Self::X => (value, pos.1, pos.2),
+            Self::Y => (pos.0, value, pos.2),
+            Self::Z => (pos.0, pos.1, value),
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "x" | "X" => Some(Self::X),
+            "y" | "Y" => Some(Self::Y),
+            "z" | "Z" => Some(Self::Z),
+            _ => None,
+        }
+    }
+}
+
+/// A plane or line the cursor snaps to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstructionGuide {
+    /// The voxels whose `axis` coordinate is `offset`
+    Plane { axis: GuideAxis, offset: i32 },
+    /// The voxels running along `axis` through `through`, whose own `axis`
+    /// coordinate is always 0
+    Line {
+        axis: GuideAxis,
+        through: (i32, i32, i32),
+    },
+}
+
+impl ConstructionGuide {
+    /// Plane across `axis` containing `pos`.
+    pub fn plane_through(axis: GuideAxis, pos: (i32, i32, i32)) -> Self {
+        Self::Plane {
+            axis,
+            offset: axis.of(pos),
+        }
+    }
+
+    /// Line along `axis` containing `pos`.
+    pub fn line_through(axis: GuideAxis, pos: (i32, i32, i32)) -> Self {
+        Self::Line {
+            axis,
+            through: axis.with(pos, 0),
+        }
+    }
+
+    /// Short description for the Guides window.
+    pub fn label(&self) -> String {
+        match *self {
+            Self::Plane { axis, offset } => format!("Plane {} = {}", axis.label(), offset),
+            Self::Line { axis, through } => {
+                let [a, b] = axis.others();
+                format!(
+                    "Line along {} at {} = {}, {} = {}",
+                    axis.label(),
+                    a.label(),
+                    a.of(through),
+                    b.label(),
+                    b.of(through)
+                )
+            }
+        }
+    }
+
+    /// Text stored in the map, e.g. `plane x 4` or `line y 3 0 -2`.
+    pub fn encode(&self) -> String {
+        match *self {
+            Self::Plane { axis, offset } => {
+                format!("plane {} {}", axis.label().to_lowercase(), offset)
+            }
+            Self::Line { axis, through } => format!(
+                "line {} {} {} {}",
+                axis.label().to_lowercase(),
+                through.0,
+                through.1,
+                through.2
+            ),
+        }
+    }
+
+    /// Read a guide written by [`encode`](Self::encode).
+    pub fn decode(text: &str) -> Option<Self> {
+        let mut words = text.split_whitespace();
+        let kind = words.next()?;
+        let axis = GuideAxis::parse(words.next()?)?;
+        let numbers: Vec<i32> = words.map(str::parse).collect::<Result<_, _>>().ok()?;
+        match (kind, numbers.as_slice()) {
+            ("plane", &[offset]) => Some(Self::Plane { axis, offset }),
+            ("line", &[x, y, z]) => Some(Self::line_through(axis, (x, y, z))),
+            _ => None,
+        }
+    }
+}
+
+/// Guides stored in `map`, skipping any that can't be read.
+pub fn map_guides(map: &MapData) -> Vec<ConstructionGuide> {
+    map.editor_metadata
+        .iter()
+        .flat_map(|metadata| &metadata.guides)
+        .filter_map(|guide| ConstructionGuide::decode(guide))
+        .collect()
+}
+
+/// Store `guides` in the editor data of `map`.
+pub fn set_map_guides(map: &mut MapData, guides: &[ConstructionGuide]) {
+    map.edit_editor_metadata(|metadata| {
+        metadata.guides = guides.iter().map(ConstructionGuide::encode).collect();
+    });
+}
+
+/// Move `pos` onto the nearest guide within [`SNAP_REACH`], if any.
+pub fn snap_to_guides(pos: (i32, i32, i32), guides: &[ConstructionGuide]) -> (i32, i32, i32) {
+    // A line within reach fixes two coordinates at once
+    let line = guides
+        .iter()
+        .filter_map(|guide| match *guide {
+            ConstructionGuide::Line { axis, through } => {
+                let distance = axis
+                    .others()
+                    .map(|other| (other.of(pos) - other.of(through)).abs());
+                (distance.iter().all(|d| *d <= SNAP_REACH))
+                    .then(|| (distance[0] + distance[1], axis.with(through, axis.of(pos))))
+            }
+            ConstructionGuide::Plane { .. } => None,
+        })
+        .min_by_key(|(distance, _)| *distance);
+    if let Some((_, snapped)) = line {
+        return snapped;
+    }
+
+    let mut snapped = pos;
+    for axis in GuideAxis::ALL {
+        let nearest = guides
+            .iter()
+            .filter_map(|guide| match *guide {
+                ConstructionGuide::Plane {
+                    axis: plane_axis,
+                    offset,
+                } if plane_axis == axis => Some(offset),
+                _ => None,
+            })
+            .filter(|offset| (axis.of(pos) - offset).abs() <= SNAP_REACH)
+            .min_by_key(|offset| (axis.of(pos) - offset).abs());
+        if let Some(offset) = nearest {
+            snapped = axis.with(snapped, offset);
+        }
+    }
+    snapped
+}
+
+/// Guide display and snapping settings, and the Guides window's draft guide.
+#[derive(Resource)]
+pub struct GuideSettings {
+    /// Whether guides are drawn in the viewport
+    pub show: bool,
+    /// Whether the placement position snaps onto guides
+    pub snap: bool,
+    /// Axis of the guide the window adds next
+    pub draft_axis: GuideAxis,
+    /// Whether the window adds a line rather than a plane
+    pub draft_line: bool,
+    /// Voxel the guide the window adds next passes through
+    pub draft_through: (i32, i32, i32),
+}
+
+impl Default for GuideSettings {
+    fn default() -> Self {
+        Self {
+            show: true,
+            snap: true,
+            draft_axis: GuideAxis::default(),
+            draft_line: false,
+            draft_through: (0, 0, 0),
+        }
+    }
+}
+
+/// System to render the Guides window while it is open.
+pub fn render_guides_window(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<EditorUIState>,
+    mut editor_state: ResMut<EditorState>,
+    mut settings: ResMut<GuideSettings>,
+    cursor_state: Res<CursorState>,
+    mut viewport_cursor: Local<Option<(i32, i32, i32)>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    // Remember where the cursor was while it was over the viewport, since it
+    // points at whatever is behind the window once the mouse moves onto it
+    if !ctx.is_pointer_over_area() {
+        *viewport_cursor = cursor_state.grid_pos.or(*viewport_cursor);
+    }
+    if !ui_state.guides_window_open {
+        return;
+    }
+
+    let old = map_guides(&editor_state.current_map);
+    let mut guides = old.clone();
+    let mut open = true;
+
+    egui::Window::new("📏 Construction Guides")
+        .open(&mut open)
+        .default_width(300.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut settings.show, "Show");
+                ui.checkbox(&mut settings.snap, "Snap")
+                    .on_hover_text(format!(
+                        "Move placement onto guides within {} voxel",
+                        SNAP_REACH
+                    ));
+            });
+
+            ui.separator();
+
+            if guides.is_empty() {
+                ui.label(egui::RichText::new("No guides yet").weak());
+            }
+            let mut remove = None;
+            for (index, guide) in guides.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.small_button("🗑").on_hover_text("Remove guide").clicked() {
+                        remove = Some(index);
+                    }
+                    ui.label(guide.label());
+                });
+            }
+            if let Some(index) = remove {
+                guides.remove(index);
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut settings.draft_line, false, "Plane across");
+                ui.radio_value(&mut settings.draft_line, true, "Line along");
+                for axis in GuideAxis::ALL {
+                    ui.radio_value(&mut settings.draft_axis, axis, axis.label());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Through");
+                let through = &mut settings.draft_through;
+                ui.add(egui::DragValue::new(&mut through.0).prefix("x "));
+                ui.add(egui::DragValue::new(&mut through.1).prefix("y "));
+                ui.add(egui::DragValue::new(&mut through.2).prefix("z "));
+                if ui
+                    .add_enabled(viewport_cursor.is_some(), egui::Button::new("⌖"))
+                    .on_hover_text("Use the voxel last under the cursor")
+                    .clicked()
+                {
+                    if let Some(pos) = *viewport_cursor {
+                        *through = pos;
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("➕ Add Guide").clicked() {
+                    let guide = if settings.draft_line {
+                        ConstructionGuide::line_through(settings.draft_axis, settings.draft_through)
+                    } else {
+                        ConstructionGuide::plane_through(
+                            settings.draft_axis,
+                            settings.draft_through,
+                        )
+                    };
+                    if !guides.contains(&guide) {
+                        guides.push(guide);
+                    }
+                }
+                if ui
+                    .add_enabled(!guides.is_empty(), egui::Button::new("Clear All"))
+                    .clicked()
+                {
+                    guides.clear();
+                }
+            });
+        });
+
+    if guides != old {
+        set_map_guides(&mut editor_state.current_map, &guides);
+        editor_state.mark_modified();
+        info!("Construction guides: {}", guides.len());
+    }
+    if !open {
+        ui_state.guides_window_open = false;
+    }
+}
+
+/// System to draw the guides around the camera.
+pub fn draw_construction_guides(
+    editor_state: Res<EditorState>,
+    settings: Res<GuideSettings>,
+    camera: Single<&EditorCamera>,
+    mut gizmos: Gizmos,
+) {
+    if !settings.show {
+        return;
+    }
+    let center = camera.position.round();
+    let extent = DRAW_EXTENT as f32;
+
+    for guide in map_guides(&editor_state.current_map) {
+        match guide {
+            ConstructionGuide::Plane { axis, offset } => {
+                let [a, b] = axis.others().map(GuideAxis::unit);
+                let origin =
+                    center - axis.unit() * center.dot(axis.unit()) + axis.unit() * offset as f32;
+                for step in (-DRAW_EXTENT..=DRAW_EXTENT).step_by(PLANE_LINE_SPACING as usize) {
+                    let step = step as f32;
+                    gizmos.line(
+                        origin + a * step - b * extent,
+                        origin + a * step + b * extent,
+                        PLANE_COLOR,
+                    );
+                    gizmos.line(
+                        origin + b * step - a * extent,
+                        origin + b * step + a * extent,
+                        PLANE_COLOR,
+                    );
+                }
+            }
+            ConstructionGuide::Line { axis, through } => {
+                let unit = axis.unit();
+                let point = Vec3::new(through.0 as f32, through.1 as f32, through.2 as f32)
+                    + unit * center.dot(unit);
+                gizmos.line(point - unit * extent, point + unit * extent, GUIDE_COLOR);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
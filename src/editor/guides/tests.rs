@@ -0,0 +1,91 @@
+use super::*;
+
+#[test]
+fn test_guides_round_trip_through_the_map() {
+    let mut map = MapData::default_map();
+    let guides = vec![
+        ConstructionGuide::plane_through(GuideAxis::X, (4, 1, 2)),
+        ConstructionGuide::line_through(GuideAxis::Y, (3, 7, -2)),
+    ];
+
+    set_map_guides(&mut map, &guides);
+    assert_eq!(
+        map.editor_metadata().guides,
+        vec!["plane x 4", "line y 3 0 -2"]
+    );
+    assert_eq!(map_guides(&map), guides);
+
+    set_map_guides(&mut map, &[]);
+    assert!(map.editor_metadata.is_none());
+    assert!(map_guides(&map).is_empty());
+}
+
+#[test]
+fn test_unreadable_guides_are_skipped() {
+    let mut map = MapData::default_map();
+    map.edit_editor_metadata(|metadata| {
+        metadata.guides = ["plane w 1", "plane z 5", "line x 1 2", "circle y 3"]
+            .map(String::from)
+            .to_vec();
+    });
+    assert_eq!(
+        map_guides(&map),
+        vec![ConstructionGuide::Plane {
+            axis: GuideAxis::Z,
+            offset: 5
+        }]
+    );
+}
+
+#[test]
+fn test_snaps_onto_plane_within_reach() {
+    let guides = [ConstructionGuide::Plane {
+        axis: GuideAxis::X,
+        offset: 10,
+    }];
+    assert_eq!(snap_to_guides((9, 3, 4), &guides), (10, 3, 4));
+    assert_eq!(snap_to_guides((11, 3, 4), &guides), (10, 3, 4));
+    assert_eq!(snap_to_guides((12, 3, 4), &guides), (12, 3, 4));
+}
+
+#[test]
+fn test_crossing_planes_combine() {
+    let guides = [
+        ConstructionGuide::Plane {
+            axis: GuideAxis::X,
+            offset: 0,
+        },
+        ConstructionGuide::Plane {
+            axis: GuideAxis::Z,
+            offset: 20,
+        },
+    ];
+    assert_eq!(snap_to_guides((1, 5, 19), &guides), (0, 5, 20));
+}
+
+#[test]
+fn test_line_wins_over_planes() {
+    let guides = [
+        ConstructionGuide::Plane {
+            axis: GuideAxis::Y,
+            offset: 3,
+        },
+        ConstructionGuide::line_through(GuideAxis::Z, (5, 2, 0)),
+    ];
+    // Near the line: both of its fixed coordinates snap, the plane is ignored
+    assert_eq!(snap_to_guides((4, 3, -30), &guides), (5, 2, -30));
+    // Only one coordinate near the line: falls back to the plane
+    assert_eq!(snap_to_guides((9, 2, -30), &guides), (9, 3, -30));
+}
+
+#[test]
+fn test_labels() {
+    assert_eq!(
+        ConstructionGuide::plane_through(GuideAxis::Y, (1, 6, 2)).label(),
+        "Plane Y = 6"
+    );
+    assert_eq!(
+        ConstructionGuide::line_through(GuideAxis::X, (9, 6, 2)).label(),
+        "Line along X at Y = 6, Z = 2"
+    );
+}
@@ -1,7 +1,11 @@
 //! Undo/redo history system for the map editor.
 
-use crate::systems::game::map::format::{EntityData, MapMetadata, VoxelData};
+use crate::systems::game::map::format::{
+    CameraSequenceData, EdgeData, EntityData, MapMetadata, MeshingData, VoxelData, VoxelGroupData,
+    WeatherData,
+};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Maximum number of actions to keep in history
 const MAX_HISTORY_SIZE: usize = 100;
@@ -17,6 +21,16 @@ pub struct EditorHistory {
 
     /// Maximum history size
     max_history: usize,
+
+    /// Key of the edit currently being coalesced by `push_coalesced`
+    coalesce_key: Option<String>,
+
+    /// Changes applied since the last `take_shared_edits`, while the
+    /// history is shared with a collaboration session
+    shared_edits: Option<Vec<EditorAction>>,
+
+    /// Number of actions pushed so far
+    pushes: u64,
 }
 
 impl Default for EditorHistory {
@@ -25,6 +39,9 @@ impl Default for EditorHistory {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             max_history: MAX_HISTORY_SIZE,
+            coalesce_key: None,
+            shared_edits: None,
+            pushes: 0,
         }
     }
 }
@@ -41,11 +58,18 @@ impl EditorHistory {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             max_history,
+            coalesce_key: None,
+            shared_edits: None,
+            pushes: 0,
         }
     }
 
     /// Push a new action onto the undo stack
     pub fn push(&mut self, action: EditorAction) {
+        self.coalesce_key = None;
+        self.record_shared(&action);
+        self.pushes += 1;
+
         // Clear redo stack when new action is performed
         self.redo_stack.clear();
 
@@ -58,9 +82,78 @@ impl EditorHistory {
         }
     }
 
+    /// Push an action, merging it into the previous one while `key` stays the same.
+    ///
+    /// Used for continuous edits such as dragging a value in the properties
+    /// panel, which change the map every frame but should undo in one step.
+    /// Only `ModifyEntity` (same entity), `ModifyMetadata`, `ModifyBounds`,
+    /// `ModifyWeather`, `ModifyEdges`, `ModifyMeshing` and
+    /// `ModifyCameraSequences` actions, and batches of them, merge; anything
+    /// else is pushed as usual. Coalescing stops at the next `push`, `undo`,
+    /// `redo`, `clear` or `end_coalescing` call.
+    pub fn push_coalesced(&mut self, key: impl Into<String>, action: EditorAction) {
+        let key = key.into();
+        if self.coalesce_key.as_ref() == Some(&key) {
+            if let Some(last) = self.undo_stack.last_mut() {
+                if last.merge(&action) {
+                    self.redo_stack.clear();
+                    self.record_shared(&action);
+                    return;
+                }
+            }
+        }
+        self.push(action);
+        self.coalesce_key = Some(key);
+    }
+
+    /// Add follow-up changes, already applied to the map, to the last
+    /// action so they undo and redo with it, such as the autotiling after a
+    /// paint stroke. With nothing to undo they are pushed as a new batch.
+    pub fn append_to_last(&mut self, description: &str, actions: Vec<EditorAction>) {
+        if actions.is_empty() {
+            return;
+        }
+        let Some(last) = self.undo_stack.last_mut() else {
+            self.push(EditorAction::Batch {
+                description: description.to_string(),
+                actions,
+            });
+            return;
+        };
+        for action in &actions {
+            if let Some(edits) = &mut self.shared_edits {
+                edits.push(action.clone());
+            }
+        }
+        match last {
+            EditorAction::Batch { actions: batch, .. } => batch.extend(actions),
+            last => {
+                let first = last.clone();
+                *last = EditorAction::Batch {
+                    description: first.description(),
+                    actions: std::iter::once(first).chain(actions).collect(),
+                };
+            }
+        }
+    }
+
+    /// Number of actions pushed since the history was created. Unlike
+    /// `undo_count` it keeps growing once the history is full, and undo and
+    /// redo leave it alone, so systems can spot new edits.
+    pub fn push_count(&self) -> u64 {
+        self.pushes
+    }
+
+    /// Stop merging further `push_coalesced` calls into the last action.
+    pub fn end_coalescing(&mut self) {
+        self.coalesce_key = None;
+    }
+
     /// Pop an action from the undo stack
     pub fn undo(&mut self) -> Option<EditorAction> {
+        self.coalesce_key = None;
         if let Some(action) = self.undo_stack.pop() {
+            self.record_shared(&action.inverse());
             self.redo_stack.push(action.clone());
             Some(action)
         } else {
@@ -70,7 +163,9 @@ impl EditorHistory {
 
     /// Pop an action from the redo stack
     pub fn redo(&mut self) -> Option<EditorAction> {
+        self.coalesce_key = None;
         if let Some(action) = self.redo_stack.pop() {
+            self.record_shared(&action);
             self.undo_stack.push(action.clone());
             Some(action)
         } else {
@@ -100,10 +195,16 @@ impl EditorHistory {
 
     /// Clear all history
     pub fn clear(&mut self) {
+        self.coalesce_key = None;
         self.undo_stack.clear();
         self.redo_stack.clear();
     }
 
+    /// The last action that can be undone
+    pub fn last_action(&self) -> Option<&EditorAction> {
+        self.undo_stack.last()
+    }
+
     /// Get a description of the last action that can be undone
     pub fn undo_description(&self) -> Option<String> {
         self.undo_stack.last().map(|a| a.description())
@@ -113,10 +214,41 @@ impl EditorHistory {
     pub fn redo_description(&self) -> Option<String> {
         self.redo_stack.last().map(|a| a.description())
     }
+
+    /// Start recording every change made through this history (pushes,
+    /// undos and redos) for a collaboration session.
+    pub fn start_sharing(&mut self) {
+        self.shared_edits.get_or_insert_with(Vec::new);
+    }
+
+    /// Stop recording changes and drop those not yet taken.
+    pub fn stop_sharing(&mut self) {
+        self.shared_edits = None;
+    }
+
+    /// Whether changes are being recorded for a collaboration session
+    pub fn is_shared(&self) -> bool {
+        self.shared_edits.is_some()
+    }
+
+    /// Take the changes recorded since the last call, in the order they
+    /// were applied to the map.
+    pub fn take_shared_edits(&mut self) -> Vec<EditorAction> {
+        self.shared_edits
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    fn record_shared(&mut self, action: &EditorAction) {
+        if let Some(edits) = &mut self.shared_edits {
+            edits.push(action.clone());
+        }
+    }
 }
 
 /// Actions that can be undone/redone
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EditorAction {
     /// Place a voxel
     PlaceVoxel {
@@ -133,10 +265,10 @@ pub enum EditorAction {
     /// Place an entity
     PlaceEntity { index: usize, data: EntityData },
 
-    /// Remove an entity
+    /// Remove an entity; `index` is only used when `data` has no id
     RemoveEntity { index: usize, data: EntityData },
 
-    /// Modify entity
+    /// Modify entity, found by `old_data`'s id like `RemoveEntity`
     ModifyEntity {
         index: usize,
         old_data: EntityData,
@@ -146,6 +278,33 @@ pub enum EditorAction {
     /// Modify metadata
     ModifyMetadata { old: MapMetadata, new: MapMetadata },
 
+    /// Change the declared world size as `(width, height, depth)`
+    ModifyBounds {
+        old: (i32, i32, i32),
+        new: (i32, i32, i32),
+    },
+
+    /// Change the map's weather
+    ModifyWeather { old: WeatherData, new: WeatherData },
+
+    /// Change how the map's voxel edges are drawn
+    ModifyEdges { old: EdgeData, new: EdgeData },
+
+    /// Change how the map's chunk meshes merge colors
+    ModifyMeshing { old: MeshingData, new: MeshingData },
+
+    /// Change the starting state of voxel groups
+    ModifyVoxelGroups {
+        old: Vec<VoxelGroupData>,
+        new: Vec<VoxelGroupData>,
+    },
+
+    /// Change the map's cinematic camera sequences
+    ModifyCameraSequences {
+        old: Vec<CameraSequenceData>,
+        new: Vec<CameraSequenceData>,
+    },
+
     /// Batch of multiple actions
     Batch {
         description: String,
@@ -171,6 +330,14 @@ impl EditorAction {
             }
             Self::ModifyEntity { .. } => "Modify entity".to_string(),
             Self::ModifyMetadata { .. } => "Modify metadata".to_string(),
+            Self::ModifyBounds { new, .. } => {
+                format!("Resize map to {}×{}×{}", new.0, new.1, new.2)
+            }
+            Self::ModifyWeather { .. } => "Change weather".to_string(),
+            Self::ModifyEdges { .. } => "Change edge style".to_string(),
+            Self::ModifyMeshing { .. } => "Change meshing".to_string(),
+            Self::ModifyVoxelGroups { .. } => "Change voxel groups".to_string(),
+            Self::ModifyCameraSequences { .. } => "Change camera sequences".to_string(),
             Self::Batch {
                 description,
                 actions,
@@ -180,6 +347,74 @@ impl EditorAction {
         }
     }
 
+    /// Fold `next` into this action so that undoing it reverts both.
+    ///
+    /// Returns `false` (leaving `self` unchanged) when the actions can't merge.
+    fn merge(&mut self, next: &EditorAction) -> bool {
+        match (self, next) {
+            (
+                Self::ModifyEntity {
+                    index, new_data, ..
+                },
+                Self::ModifyEntity {
+                    index: next_index,
+                    new_data: next_data,
+                    ..
+                },
+            ) if index == next_index && new_data.id == next_data.id => {
+                *new_data = next_data.clone();
+                true
+            }
+            (Self::ModifyMetadata { new, .. }, Self::ModifyMetadata { new: next_new, .. }) => {
+                *new = next_new.clone();
+                true
+            }
+            (Self::ModifyBounds { new, .. }, Self::ModifyBounds { new: next_new, .. }) => {
+                *new = *next_new;
+                true
+            }
+            (Self::ModifyWeather { new, .. }, Self::ModifyWeather { new: next_new, .. }) => {
+                *new = next_new.clone();
+                true
+            }
+            (Self::ModifyEdges { new, .. }, Self::ModifyEdges { new: next_new, .. }) => {
+                *new = next_new.clone();
+                true
+            }
+            (Self::ModifyMeshing { new, .. }, Self::ModifyMeshing { new: next_new, .. }) => {
+                *new = next_new.clone();
+                true
+            }
+            (
+                Self::ModifyCameraSequences { new, .. },
+                Self::ModifyCameraSequences { new: next_new, .. },
+            ) => {
+                *new = next_new.clone();
+                true
+            }
+            // Edits of the same entities' property, such as dragging a value
+            // with several entities selected
+            (
+                Self::Batch { actions, .. },
+                Self::Batch {
+                    actions: next_actions,
+                    ..
+                },
+            ) if actions.len() == next_actions.len() => {
+                let mut merged = actions.clone();
+                let all_merged = merged
+                    .iter_mut()
+                    .zip(next_actions)
+                    .all(|(action, next)| action.merge(next));
+                if all_merged {
+                    *actions = merged;
+                }
+                all_merged
+            }
+            _ => false,
+        }
+    }
+
     /// Get the inverse action (for undo)
     pub fn inverse(&self) -> Self {
         match self {
@@ -212,6 +447,30 @@ impl EditorAction {
                 old: new.clone(),
                 new: old.clone(),
             },
+            Self::ModifyBounds { old, new } => Self::ModifyBounds {
+                old: *new,
+                new: *old,
+            },
+            Self::ModifyWeather { old, new } => Self::ModifyWeather {
+                old: new.clone(),
+                new: old.clone(),
+            },
+            Self::ModifyEdges { old, new } => Self::ModifyEdges {
+                old: new.clone(),
+                new: old.clone(),
+            },
+            Self::ModifyMeshing { old, new } => Self::ModifyMeshing {
+                old: new.clone(),
+                new: old.clone(),
+            },
+            Self::ModifyVoxelGroups { old, new } => Self::ModifyVoxelGroups {
+                old: new.clone(),
+                new: old.clone(),
+            },
+            Self::ModifyCameraSequences { old, new } => Self::ModifyCameraSequences {
+                old: new.clone(),
+                new: old.clone(),
+            },
             Self::Batch {
                 description,
                 actions,
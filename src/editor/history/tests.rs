@@ -14,6 +14,7 @@ fn test_history_push_and_undo() {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     };
 
@@ -39,6 +40,7 @@ fn test_history_redo() {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     };
 
@@ -63,6 +65,7 @@ fn test_history_clear_redo_on_new_action() {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     };
 
@@ -74,6 +77,7 @@ fn test_history_clear_redo_on_new_action() {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     };
 
@@ -84,3 +88,202 @@ fn test_history_clear_redo_on_new_action() {
     history.push(action2);
     assert!(!history.can_redo());
 }
+
+fn npc_at(x: f32) -> EntityData {
+    EntityData {
+        id: Default::default(),
+        entity_type: crate::systems::game::map::format::EntityType::Npc,
+        position: (x, 0.0, 0.0),
+        properties: std::collections::HashMap::new(),
+    }
+}
+
+fn move_npc(index: usize, from: f32, to: f32) -> EditorAction {
+    EditorAction::ModifyEntity {
+        index,
+        old_data: npc_at(from),
+        new_data: npc_at(to),
+    }
+}
+
+#[test]
+fn test_push_coalesced_merges_same_key_into_one_entry() {
+    let mut history = EditorHistory::new();
+
+    history.push_coalesced("entity:0:position", move_npc(0, 0.0, 0.5));
+    history.push_coalesced("entity:0:position", move_npc(0, 0.5, 1.0));
+    history.push_coalesced("entity:0:position", move_npc(0, 1.0, 1.5));
+
+    assert_eq!(history.undo_count(), 1);
+    match history.undo() {
+        Some(EditorAction::ModifyEntity {
+            old_data, new_data, ..
+        }) => {
+            assert_eq!(old_data.position.0, 0.0);
+            assert_eq!(new_data.position.0, 1.5);
+        }
+        other => panic!("expected merged ModifyEntity, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_push_coalesced_starts_new_entry_after_end_or_other_key() {
+    let mut history = EditorHistory::new();
+
+    history.push_coalesced("entity:0:position", move_npc(0, 0.0, 1.0));
+    history.end_coalescing();
+    history.push_coalesced("entity:0:position", move_npc(0, 1.0, 2.0));
+    history.push_coalesced("entity:0:radius", move_npc(0, 2.0, 2.0));
+    assert_eq!(history.undo_count(), 3);
+
+    // A regular push also ends the merge window
+    history.push(move_npc(1, 0.0, 1.0));
+    history.push_coalesced("entity:0:radius", move_npc(0, 2.0, 2.0));
+    assert_eq!(history.undo_count(), 5);
+}
+
+#[test]
+fn test_push_coalesced_does_not_merge_into_entry_restored_by_redo() {
+    let mut history = EditorHistory::new();
+
+    history.push_coalesced("entity:0:position", move_npc(0, 0.0, 1.0));
+    history.undo();
+    history.redo();
+    history.push_coalesced("entity:0:position", move_npc(0, 1.0, 2.0));
+
+    assert_eq!(history.undo_count(), 2);
+}
+
+#[test]
+fn test_modify_bounds_coalesces_and_inverts() {
+    let mut history = EditorHistory::new();
+    history.push_coalesced(
+        "map_bounds",
+        EditorAction::ModifyBounds {
+            old: (4, 4, 4),
+            new: (5, 4, 4),
+        },
+    );
+    history.push_coalesced(
+        "map_bounds",
+        EditorAction::ModifyBounds {
+            old: (5, 4, 4),
+            new: (6, 4, 4),
+        },
+    );
+    assert_eq!(history.undo_count(), 1);
+
+    match history.undo().unwrap().inverse() {
+        EditorAction::ModifyBounds { old, new } => {
+            assert_eq!(old, (6, 4, 4));
+            assert_eq!(new, (4, 4, 4));
+        }
+        other => panic!("unexpected inverse {other:?}"),
+    }
+}
+
+#[test]
+fn test_modify_weather_coalesces_and_inverts() {
+    let fog = |fog_density| WeatherData {
+        fog_density,
+        ..WeatherData::default()
+    };
+    let mut history = EditorHistory::new();
+    for (old, new) in [(0.0, 0.1), (0.1, 0.2), (0.2, 0.3)] {
+        history.push_coalesced(
+            "map_weather",
+            EditorAction::ModifyWeather {
+                old: fog(old),
+                new: fog(new),
+            },
+        );
+    }
+    assert_eq!(history.undo_count(), 1);
+
+    match history.undo().unwrap().inverse() {
+        EditorAction::ModifyWeather { old, new } => {
+            assert_eq!(old, fog(0.3));
+            assert_eq!(new, fog(0.0));
+        }
+        other => panic!("unexpected inverse {other:?}"),
+    }
+}
+
+#[test]
+fn test_shared_history_records_applied_changes() {
+    let place = |x| EditorAction::PlaceVoxel {
+        pos: (x, 0, 0),
+        data: VoxelData {
+            pos: (x, 0, 0),
+            voxel_type: VoxelType::Grass,
+            pattern: Some(SubVoxelPattern::Full),
+            rotation: None,
+            rotation_state: None,
+            group: None,
+        },
+    };
+    let mut history = EditorHistory::new();
+    history.push(place(0));
+    assert!(!history.is_shared());
+    assert!(history.take_shared_edits().is_empty());
+
+    history.start_sharing();
+    history.push(place(1));
+    history.undo();
+    history.redo();
+    let edits = history.take_shared_edits();
+    assert_eq!(edits.len(), 3);
+    assert!(matches!(
+        edits[0],
+        EditorAction::PlaceVoxel { pos: (1, 0, 0), .. }
+    ));
+    assert!(matches!(
+        edits[1],
+        EditorAction::RemoveVoxel { pos: (1, 0, 0), .. }
+    ));
+    assert!(matches!(
+        edits[2],
+        EditorAction::PlaceVoxel { pos: (1, 0, 0), .. }
+    ));
+    assert!(history.take_shared_edits().is_empty());
+
+    history.stop_sharing();
+    history.push(place(2));
+    assert!(history.take_shared_edits().is_empty());
+}
+
+#[test]
+fn test_append_to_last_undoes_with_the_last_action() {
+    let place = |x| EditorAction::PlaceVoxel {
+        pos: (x, 0, 0),
+        data: VoxelData {
+            pos: (x, 0, 0),
+            voxel_type: VoxelType::Grass,
+            pattern: Some(SubVoxelPattern::Full),
+            rotation: None,
+            rotation_state: None,
+            group: None,
+        },
+    };
+    let mut history = EditorHistory::new();
+    history.append_to_last("Autotile", vec![place(0)]);
+    assert_eq!(history.undo_count(), 1);
+    assert_eq!(history.push_count(), 1);
+
+    history.push(place(1));
+    history.append_to_last("Autotile", vec![place(2), place(3)]);
+    history.append_to_last("Autotile", Vec::new());
+    assert_eq!(history.undo_count(), 2);
+    assert_eq!(history.push_count(), 2);
+    match history.undo().unwrap() {
+        EditorAction::Batch {
+            description,
+            actions,
+        } => {
+            assert_eq!(description, "Place Grass at (1, 0, 0)");
+            assert_eq!(actions.len(), 3);
+        }
+        other => panic!("expected a batch, got {:?}", other),
+    }
+    assert_eq!(history.push_count(), 2);
+}
@@ -0,0 +1,527 @@
+//! Input recording and playback for scripted editor runs.
+//!
+//! `map_editor --record-input <file>` records the raw keyboard, mouse,
+//! gamepad and file drop events the editor receives, each with the time in
+//! seconds since recording began, and writes them to `<file>` as RON when the
+//! editor exits. `map_editor --play-input <file>` feeds a recording back in
+//! as if it came from the devices, so a session like "open a map, place ten
+//! voxels, undo five, save" can be replayed and its result checked. With
+//! `--exit-after-playback` the editor quits once the recording runs out,
+//! waiting for saves like a normal exit.
+//!
+//! Playback writes the same messages winit and gilrs would, before
+//! [`InputSystems`], so `ButtonInput`, egui and every editor system see them
+//! unchanged. It runs on real time, which a headless app can step with
+//! `TimeUpdateStrategy::ManualDuration`. Played back cursor moves also move
+//! the primary window's cursor, which is what the editor raycasts from.
+//!
+//! Recordings are plain RON, so they can also be written by hand or built in
+//! a test. Key repeats are not recorded; events that arrive in the same frame
+//! share a timestamp.
+
+use crate::editor::ui::dialogs::AppExitEvent;
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::{
+    GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+    RawGamepadButtonChangedEvent, RawGamepadEvent,
+};
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::input::{ButtonState, InputSystems};
+use bevy::prelude::*;
+use bevy::window::{CursorMoved, FileDragAndDrop, PrimaryWindow};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Name played back gamepads connect with.
+pub const PLAYBACK_GAMEPAD_NAME: &str = "Input playback";
+
+/// Errors reading or writing an input recording.
+#[derive(Error, Debug)]
+pub enum InputRecordingError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid input recording: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Input recording encoding error: {0}")]
+    Encoding(#[from] ron::Error),
+}
+
+/// One raw input event.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RecordedInput {
+    /// A key was pressed or released. Without a logical key or text the key
+    /// plays back as unidentified, which only matters to text fields.
+    Key {
+        key: KeyCode,
+        pressed: bool,
+        #[serde(default)]
+        logical_key: Option<Key>,
+        #[serde(default)]
+        text: Option<String>,
+    },
+    MouseButton {
+        button: MouseButton,
+        pressed: bool,
+    },
+    /// The cursor moved to a position in the window, in logical pixels from
+    /// the top left
+    CursorMoved {
+        position: Vec2,
+    },
+    MouseWheel {
+        unit: MouseScrollUnit,
+        x: f32,
+        y: f32,
+    },
+    /// Raw mouse movement, as used for camera look
+    MouseMotion {
+        delta: Vec2,
+    },
+    /// A file was dropped on the window, which opens it as a map
+    FileDropped {
+        path: PathBuf,
+    },
+    /// A button of the nth gamepad seen changed, from 0.0 (released) to 1.0
+    GamepadButton {
+        gamepad: usize,
+        button: GamepadButton,
+        value: f32,
+    },
+    /// A stick or trigger axis of the nth gamepad seen changed
+    GamepadAxis {
+        gamepad: usize,
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+/// An input event and when it happened.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+    /// Seconds since the recording began
+    pub time: f32,
+    pub input: RecordedInput,
+}
+
+/// Input events in the order they happened.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct InputRecording {
+    pub events: Vec<RecordedEvent>,
+}
+
+impl InputRecording {
+    pub fn push(&mut self, time: f32, input: RecordedInput) {
+        self.events.push(RecordedEvent { time, input });
+    }
+
+    /// Time of the last event in seconds.
+    pub fn duration(&self) -> f32 {
+        self.events.last().map_or(0.0, |event| event.time)
+    }
+
+    pub fn from_ron(contents: &str) -> Result<Self, InputRecordingError> {
+        Ok(ron::from_str(contents)?)
+    }
+
+    pub fn to_ron(&self) -> Result<String, InputRecordingError> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, InputRecordingError> {
+        Self::from_ron(&fs::read_to_string(path)?)
+    }
+
+    /// Write to `path`, creating its folder if needed.
+    pub fn save(&self, path: &Path) -> Result<(), InputRecordingError> {
+        if let Some(folder) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, self.to_ron()?)?;
+        Ok(())
+    }
+}
+
+/// Input being recorded, set up by `--record-input`.
+#[derive(Resource, Debug)]
+pub struct InputRecorder {
+    /// File the recording is written to on exit
+    pub path: PathBuf,
+    pub recording: InputRecording,
+    /// Real time the recording began at
+    start: Option<f32>,
+    /// Gamepads in the order they were first seen
+    gamepads: Vec<Entity>,
+}
+
+impl InputRecorder {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            recording: InputRecording::default(),
+            start: None,
+            gamepads: Vec::new(),
+        }
+    }
+
+    /// Index of `gamepad` in the recording, adding it when new.
+    fn gamepad_index(&mut self, gamepad: Entity) -> usize {
+        match self.gamepads.iter().position(|&seen| seen == gamepad) {
+            Some(index) => index,
+            None => {
+                self.gamepads.push(gamepad);
+                self.gamepads.len() - 1
+            }
+        }
+    }
+}
+
+/// A recording being played back, set up by `--play-input`.
+#[derive(Resource, Debug)]
+pub struct InputPlayback {
+    pub recording: InputRecording,
+    /// Index of the next event to play
+    pub next: usize,
+    /// Quit the editor once every event has played
+    pub exit_when_done: bool,
+    /// Real time playback began at
+    start: Option<f32>,
+    /// Entities standing in for the recorded gamepads
+    gamepads: Vec<Entity>,
+    /// Last played back cursor position, for cursor deltas
+    cursor: Option<Vec2>,
+}
+
+impl InputPlayback {
+    pub fn new(recording: InputRecording, exit_when_done: bool) -> Self {
+        Self {
+            recording,
+            next: 0,
+            exit_when_done,
+            start: None,
+            gamepads: Vec::new(),
+            cursor: None,
+        }
+    }
+
+    /// Events due `elapsed` seconds into playback that have not played yet.
+    /// They count as played once returned.
+    pub fn take_due(&mut self, elapsed: f32) -> Vec<RecordedInput> {
+        let due = self.recording.events[self.next..]
+            .iter()
+            .take_while(|event| event.time <= elapsed)
+            .map(|event| event.input.clone())
+            .collect::<Vec<_>>();
+        self.next += due.len();
+        due
+    }
+
+    /// Whether every event has played.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.events.len()
+    }
+}
+
+/// Readers for the raw input messages that get recorded.
+#[derive(SystemParam)]
+pub struct RawInputReaders<'w, 's> {
+    pub keyboard: MessageReader<'w, 's, KeyboardInput>,
+    pub mouse_buttons: MessageReader<'w, 's, MouseButtonInput>,
+    pub cursor_moved: MessageReader<'w, 's, CursorMoved>,
+    pub mouse_wheel: MessageReader<'w, 's, MouseWheel>,
+    pub mouse_motion: MessageReader<'w, 's, MouseMotion>,
+    pub file_drops: MessageReader<'w, 's, FileDragAndDrop>,
+    pub gamepads: MessageReader<'w, 's, RawGamepadEvent>,
+}
+
+/// Writers for the raw input messages that playback produces.
+#[derive(SystemParam)]
+pub struct RawInputWriters<'w> {
+    pub keyboard: MessageWriter<'w, KeyboardInput>,
+    pub mouse_buttons: MessageWriter<'w, MouseButtonInput>,
+    pub cursor_moved: MessageWriter<'w, CursorMoved>,
+    pub mouse_wheel: MessageWriter<'w, MouseWheel>,
+    pub mouse_motion: MessageWriter<'w, MouseMotion>,
+    pub file_drops: MessageWriter<'w, FileDragAndDrop>,
+    pub gamepad_connections: MessageWriter<'w, GamepadConnectionEvent>,
+    pub gamepads: MessageWriter<'w, RawGamepadEvent>,
+}
+
+fn button_state(pressed: bool) -> ButtonState {
+    if pressed {
+        ButtonState::Pressed
+    } else {
+        ButtonState::Released
+    }
+}
+
+/// System to append this frame's raw input to the recording.
+pub fn record_input(
+    time: Res<Time<Real>>,
+    mut recorder: ResMut<InputRecorder>,
+    mut input: RawInputReaders,
+) {
+    let now = time.elapsed_secs();
+    let t = now - *recorder.start.get_or_insert(now);
+    let recording = &mut recorder.recording;
+
+    for event in input.keyboard.read().filter(|event| !event.repeat) {
+        recording.push(
+            t,
+            RecordedInput::Key {
+                key: event.key_code,
+                pressed: event.state.is_pressed(),
+                logical_key: Some(event.logical_key.clone()),
+                text: event.text.as_ref().map(|text| text.to_string()),
+            },
+        );
+    }
+    for event in input.mouse_buttons.read() {
+        recording.push(
+            t,
+            RecordedInput::MouseButton {
+                button: event.button,
+                pressed: event.state.is_pressed(),
+            },
+        );
+    }
+    for event in input.cursor_moved.read() {
+        recording.push(
+            t,
+            RecordedInput::CursorMoved {
+                position: event.position,
+            },
+        );
+    }
+    for event in input.mouse_wheel.read() {
+        recording.push(
+            t,
+            RecordedInput::MouseWheel {
+                unit: event.unit,
+                x: event.x,
+                y: event.y,
+            },
+        );
+    }
+    for event in input.mouse_motion.read() {
+        recording.push(t, RecordedInput::MouseMotion { delta: event.delta });
+    }
+    for event in input.file_drops.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            recording.push(
+                t,
+                RecordedInput::FileDropped {
+                    path: path_buf.clone(),
+                },
+            );
+        }
+    }
+
+    let gamepad_events: Vec<RawGamepadEvent> = input.gamepads.read().cloned().collect();
+    for event in gamepad_events {
+        let input = match event {
+            RawGamepadEvent::Button(button) => RecordedInput::GamepadButton {
+                gamepad: recorder.gamepad_index(button.gamepad),
+                button: button.button,
+                value: button.value,
+            },
+            RawGamepadEvent::Axis(axis) => RecordedInput::GamepadAxis {
+                gamepad: recorder.gamepad_index(axis.gamepad),
+                axis: axis.axis,
+                value: axis.value,
+            },
+            // Played back gamepads connect when first used
+            RawGamepadEvent::Connection(_) => continue,
+        };
+        recorder.recording.push(t, input);
+    }
+}
+
+/// System to write the recording to its file when the editor exits.
+pub fn save_input_recording(
+    mut exit_events: MessageReader<AppExitEvent>,
+    recorder: Res<InputRecorder>,
+) {
+    if exit_events.read().count() == 0 {
+        return;
+    }
+    match recorder.recording.save(&recorder.path) {
+        Ok(()) => info!(
+            "Saved {} input events to {:?}",
+            recorder.recording.events.len(),
+            recorder.path
+        ),
+        Err(e) => error!("Failed to save input recording: {}", e),
+    }
+}
+
+/// System to feed the recorded events that are due to the input systems.
+pub fn play_input(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut playback: ResMut<InputPlayback>,
+    mut window: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+    mut output: RawInputWriters,
+    mut exit_events: MessageWriter<AppExitEvent>,
+) {
+    if playback.is_finished() {
+        return;
+    }
+
+    let now = time.elapsed_secs();
+    let elapsed = now - *playback.start.get_or_insert(now);
+    // Headless runs have no window; the messages then name a placeholder
+    let mut window = window.single_mut().ok();
+    let window_entity = window
+        .as_ref()
+        .map_or(Entity::PLACEHOLDER, |(entity, _)| *entity);
+
+    for input in playback.take_due(elapsed) {
+        match input {
+            RecordedInput::Key {
+                key,
+                pressed,
+                logical_key,
+                text,
+            } => {
+                output.keyboard.write(KeyboardInput {
+                    key_code: key,
+                    logical_key: logical_key.unwrap_or(Key::Unidentified(NativeKey::Unidentified)),
+                    state: button_state(pressed),
+                    text: text.map(Into::into),
+                    repeat: false,
+                    window: window_entity,
+                });
+            }
+            RecordedInput::MouseButton { button, pressed } => {
+                output.mouse_buttons.write(MouseButtonInput {
+                    button,
+                    state: button_state(pressed),
+                    window: window_entity,
+                });
+            }
+            RecordedInput::CursorMoved { position } => {
+                let delta = playback.cursor.map(|last| position - last);
+                playback.cursor = Some(position);
+                if let Some((_, window)) = window.as_mut() {
+                    window.set_cursor_position(Some(position));
+                }
+                output.cursor_moved.write(CursorMoved {
+                    window: window_entity,
+                    position,
+                    delta,
+                });
+            }
+            RecordedInput::MouseWheel { unit, x, y } => {
+                output.mouse_wheel.write(MouseWheel {
+                    unit,
+                    x,
+                    y,
+                    window: window_entity,
+                });
+            }
+            RecordedInput::MouseMotion { delta } => {
+                output.mouse_motion.write(MouseMotion { delta });
+            }
+            RecordedInput::FileDropped { path } => {
+                output.file_drops.write(FileDragAndDrop::DroppedFile {
+                    window: window_entity,
+                    path_buf: path,
+                });
+            }
+            RecordedInput::GamepadButton {
+                gamepad,
+                button,
+                value,
+            } => {
+                let gamepad = playback_gamepad(&mut commands, &mut playback, &mut output, gamepad);
+                output
+                    .gamepads
+                    .write(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                        gamepad, button, value,
+                    )));
+            }
+            RecordedInput::GamepadAxis {
+                gamepad,
+                axis,
+                value,
+            } => {
+                let gamepad = playback_gamepad(&mut commands, &mut playback, &mut output, gamepad);
+                output
+                    .gamepads
+                    .write(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+                        gamepad, axis, value,
+                    )));
+            }
+        }
+    }
+
+    if playback.is_finished() {
+        info!(
+            "Played back {} input events",
+            playback.recording.events.len()
+        );
+        if playback.exit_when_done {
+            exit_events.write(AppExitEvent);
+        }
+    }
+}
+
+/// Entity standing in for recorded gamepad `index`, connecting gamepads up
+/// to it the first time it is used.
+fn playback_gamepad(
+    commands: &mut Commands,
+    playback: &mut InputPlayback,
+    output: &mut RawInputWriters,
+    index: usize,
+) -> Entity {
+    while playback.gamepads.len() <= index {
+        let gamepad = commands.spawn_empty().id();
+        output
+            .gamepad_connections
+            .write(GamepadConnectionEvent::new(
+                gamepad,
+                GamepadConnection::Connected {
+                    name: PLAYBACK_GAMEPAD_NAME.to_string(),
+                    vendor_id: None,
+                    product_id: None,
+                },
+            ));
+        playback.gamepads.push(gamepad);
+    }
+    playback.gamepads[index]
+}
+
+/// Plugin for `--record-input` and `--play-input`; does nothing when neither
+/// is set.
+#[derive(Default)]
+pub struct InputRecordingPlugin {
+    /// File to record input to
+    pub record: Option<PathBuf>,
+    /// Recording to play back
+    pub playback: Option<InputRecording>,
+    /// Quit once the playback has run out
+    pub exit_when_done: bool,
+}
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(path) = &self.record {
+            app.insert_resource(InputRecorder::new(path.clone()))
+                .add_systems(PreUpdate, record_input.after(InputSystems))
+                .add_systems(Last, save_input_recording);
+        }
+        if let Some(recording) = &self.playback {
+            app.insert_resource(InputPlayback::new(recording.clone(), self.exit_when_done))
+                .add_systems(PreUpdate, play_input.before(InputSystems));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
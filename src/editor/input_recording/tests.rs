@@ -0,0 +1,108 @@
+use super::*;
+
+fn key(time: f32, key: KeyCode, pressed: bool) -> RecordedEvent {
+    RecordedEvent {
+        time,
+        input: RecordedInput::Key {
+            key,
+            pressed,
+            logical_key: None,
+            text: None,
+        },
+    }
+}
+
+#[test]
+fn hand_written_recordings_parse() {
+    let recording = InputRecording::from_ron(
+        r#"(
+            events: [
+                (time: 0.0, input: Key(key: KeyI, pressed: true)),
+                (time: 0.1, input: CursorMoved(position: (640.0, 360.0))),
+                (time: 0.2, input: MouseButton(button: Left, pressed: true)),
+                (time: 0.5, input: GamepadButton(gamepad: 0, button: South, value: 1.0)),
+            ],
+        )"#,
+    )
+    .unwrap();
+
+    assert_eq!(recording.events.len(), 4);
+    assert_eq!(recording.events[0], key(0.0, KeyCode::KeyI, true));
+    assert_eq!(
+        recording.events[1].input,
+        RecordedInput::CursorMoved {
+            position: Vec2::new(640.0, 360.0)
+        }
+    );
+    assert_eq!(recording.duration(), 0.5);
+}
+
+#[test]
+fn recordings_round_trip_through_ron() {
+    let mut recording = InputRecording::default();
+    recording.push(
+        0.0,
+        RecordedInput::Key {
+            key: KeyCode::KeyA,
+            pressed: true,
+            logical_key: Some(Key::Character("a".into())),
+            text: Some("a".to_string()),
+        },
+    );
+    recording.push(
+        0.25,
+        RecordedInput::MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: -1.0,
+        },
+    );
+    recording.push(
+        0.5,
+        RecordedInput::FileDropped {
+            path: PathBuf::from("maps/forest.ron"),
+        },
+    );
+    recording.push(
+        1.0,
+        RecordedInput::GamepadAxis {
+            gamepad: 1,
+            axis: GamepadAxis::LeftStickX,
+            value: -0.5,
+        },
+    );
+
+    let ron = recording.to_ron().unwrap();
+    assert_eq!(InputRecording::from_ron(&ron).unwrap(), recording);
+}
+
+#[test]
+fn playback_takes_each_event_once_when_due() {
+    let recording = InputRecording {
+        events: vec![
+            key(0.0, KeyCode::KeyZ, true),
+            key(0.0, KeyCode::KeyZ, false),
+            key(0.5, KeyCode::KeyY, true),
+            key(1.0, KeyCode::KeyY, false),
+        ],
+    };
+    let mut playback = InputPlayback::new(recording, false);
+
+    assert_eq!(playback.take_due(0.0).len(), 2);
+    assert!(playback.take_due(0.4).is_empty());
+    // A late frame plays everything it missed
+    assert_eq!(playback.take_due(2.0).len(), 2);
+    assert!(playback.is_finished());
+    assert!(playback.take_due(3.0).is_empty());
+}
+
+#[test]
+fn recorded_gamepads_are_numbered_in_order_seen() {
+    let mut recorder = InputRecorder::new(PathBuf::from("input.ron"));
+    let first = Entity::from_raw_u32(7).unwrap();
+    let second = Entity::from_raw_u32(3).unwrap();
+
+    assert_eq!(recorder.gamepad_index(first), 0);
+    assert_eq!(recorder.gamepad_index(second), 1);
+    assert_eq!(recorder.gamepad_index(first), 0);
+}
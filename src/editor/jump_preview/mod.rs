@@ -0,0 +1,204 @@
+//! Jump arc preview for designing platforming sections.
+//!
+//! With View → Jump Arcs enabled, the editor draws the player's jump
+//! trajectory in eight directions from a takeoff voxel and marks every voxel
+//! top the player can land on. The takeoff voxel is the single selected
+//! voxel, or the voxel under the cursor when the selection isn't exactly one
+//! voxel.
+//!
+//! Predictions use the game's [`PhysicsConfig`] (gravity, jump velocity,
+//! walking speed and collider size). Voxels are treated as full cubes and arcs
+//! are not blocked by walls along the way, so the result is an upper bound —
+//! close enough to tune gap widths without playtesting every change.
+
+use crate::editor::cursor::CursorState;
+use crate::editor::state::EditorState;
+use crate::systems::game::resources::PhysicsConfig;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// How far below the takeoff height landings are searched, in voxels
+pub const MAX_DROP: i32 = 6;
+
+/// Number of arc directions drawn around the takeoff voxel
+const ARC_DIRECTIONS: usize = 8;
+
+/// Time step used to sample arcs, in seconds
+const ARC_TIME_STEP: f32 = 1.0 / 30.0;
+
+/// Slack for floating point error when comparing heights and distances
+const EPSILON: f32 = 1e-4;
+
+/// A voxel top the player can land on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Landing {
+    /// The voxel landed on
+    pub pos: (i32, i32, i32),
+    /// Height of the landing voxel relative to the takeoff voxel
+    pub rise: i32,
+}
+
+/// Highest a jump can climb above its takeoff height.
+pub fn max_jump_height(physics: &PhysicsConfig) -> f32 {
+    physics.jump_velocity.powi(2) / (2.0 * -physics.gravity)
+}
+
+/// Time from takeoff until the falling player is `rise` units above the
+/// takeoff height, or `None` if the jump never gets that high.
+pub fn airtime(physics: &PhysicsConfig, rise: f32) -> Option<f32> {
+    let g = -physics.gravity;
+    let v = physics.jump_velocity;
+    if g <= 0.0 {
+        return None;
+    }
+    // rise = v t - g t² / 2, solved for the later (descending) root
+    let discriminant = v * v - 2.0 * g * rise;
+    if discriminant < -EPSILON {
+        return None;
+    }
+    Some((v + discriminant.max(0.0).sqrt()) / g)
+}
+
+/// Feet positions along a jump from `takeoff` in `direction` (horizontal,
+/// normalized), sampled until the player is `MAX_DROP` below the takeoff.
+pub fn jump_arc(physics: &PhysicsConfig, takeoff: Vec3, direction: Vec3) -> Vec<Vec3> {
+    let horizontal = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+    let end_time = airtime(physics, -(MAX_DROP as f32)).unwrap_or(0.0);
+    let steps = (end_time / ARC_TIME_STEP).ceil() as usize;
+    (0..=steps)
+        .map(|i| {
+            let t = (i as f32 * ARC_TIME_STEP).min(end_time);
+            let y = physics.jump_velocity * t + 0.5 * physics.gravity * t * t;
+            takeoff + horizontal * physics.move_speed * t + Vec3::Y * y
+        })
+        .collect()
+}
+
+/// Horizontal distance between the nearest edges of two voxel columns
+/// `dx`, `dz` cells apart.
+fn column_gap(dx: i32, dz: i32) -> f32 {
+    let gap_x = (dx.abs() - 1).max(0) as f32;
+    let gap_z = (dz.abs() - 1).max(0) as f32;
+    (gap_x * gap_x + gap_z * gap_z).sqrt()
+}
+
+/// Voxel tops reachable by jumping (or walking off) from the top of `origin`.
+///
+/// A landing needs a free voxel above it for the player to stand in. The
+/// player takes off anywhere on the origin voxel and lands as soon as its
+/// collider overlaps the target, so the center only has to cross the gap
+/// between the columns minus the collider's diameter.
+pub fn reachable_landings(
+    physics: &PhysicsConfig,
+    solid: &HashSet<(i32, i32, i32)>,
+    origin: (i32, i32, i32),
+) -> Vec<Landing> {
+    let diameter = physics.player_radius * 2.0;
+    let max_rise = (max_jump_height(physics) + EPSILON).floor() as i32;
+    let longest = airtime(physics, -(MAX_DROP as f32)).unwrap_or(0.0);
+    let radius = (physics.move_speed * longest + diameter).ceil() as i32 + 1;
+
+    let mut landings = Vec::new();
+    for rise in -MAX_DROP..=max_rise {
+        let Some(time) = airtime(physics, rise as f32) else {
+            continue;
+        };
+        let reach = physics.move_speed * time;
+        for dx in -radius..=radius {
+            for dz in -radius..=radius {
+                if dx == 0 && dz == 0 {
+                    continue;
+                }
+                let pos = (origin.0 + dx, origin.1 + rise, origin.2 + dz);
+                let above = (pos.0, pos.1 + 1, pos.2);
+                if !solid.contains(&pos) || solid.contains(&above) {
+                    continue;
+                }
+                if column_gap(dx, dz) - diameter <= reach + EPSILON {
+                    landings.push(Landing { pos, rise });
+                }
+            }
+        }
+    }
+    landings
+}
+
+/// Voxel the preview starts from, if any
+fn takeoff_voxel(
+    editor_state: &EditorState,
+    cursor_state: &CursorState,
+    solid: &HashSet<(i32, i32, i32)>,
+) -> Option<(i32, i32, i32)> {
+    let origin = if editor_state.selected_voxels.len() == 1 {
+        editor_state.selected_voxels.iter().next().copied()
+    } else {
+        cursor_state.grid_pos
+    }?;
+    let above = (origin.0, origin.1 + 1, origin.2);
+    (solid.contains(&origin) && !solid.contains(&above)).then_some(origin)
+}
+
+fn cell_of(point: Vec3) -> (i32, i32, i32) {
+    (
+        point.x.round() as i32,
+        point.y.round() as i32,
+        point.z.round() as i32,
+    )
+}
+
+/// Draw jump arcs and reachable landings while the preview is enabled.
+pub fn draw_jump_preview(
+    editor_state: Res<EditorState>,
+    cursor_state: Res<CursorState>,
+    physics: Res<PhysicsConfig>,
+    mut solid: Local<HashSet<(i32, i32, i32)>>,
+    mut gizmos: Gizmos,
+) {
+    if !editor_state.show_jump_arcs {
+        return;
+    }
+    if editor_state.is_changed() {
+        *solid = editor_state
+            .current_map
+            .world
+            .voxels
+            .iter()
+            .map(|voxel| voxel.pos)
+            .collect();
+    }
+    let Some(origin) = takeoff_voxel(&editor_state, &cursor_state, &solid) else {
+        return;
+    };
+
+    let top = Vec3::new(origin.0 as f32, origin.1 as f32 + 0.5, origin.2 as f32);
+    let arc_color = Color::srgba(1.0, 1.0, 1.0, 0.7);
+    for i in 0..ARC_DIRECTIONS {
+        let angle = i as f32 * std::f32::consts::TAU / ARC_DIRECTIONS as f32;
+        let direction = Vec3::new(angle.cos(), 0.0, angle.sin());
+        // Take off from the edge of the voxel, where the longest jumps start
+        let takeoff = top + direction * (0.5 + physics.player_radius);
+        let points: Vec<Vec3> = jump_arc(&physics, takeoff, direction)
+            .into_iter()
+            .take_while(|point| !solid.contains(&cell_of(*point)))
+            .collect();
+        gizmos.linestrip(points, arc_color);
+    }
+
+    for landing in reachable_landings(&physics, &solid, origin) {
+        let color = match landing.rise {
+            rise if rise > 0 => Color::srgb(0.3, 0.8, 1.0),
+            0 => Color::srgb(0.3, 1.0, 0.4),
+            _ => Color::srgb(1.0, 0.7, 0.2),
+        };
+        let (x, y, z) = landing.pos;
+        let center = Vec3::new(x as f32, y as f32 + 0.52, z as f32);
+        gizmos.rect(
+            Isometry3d::new(center, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            Vec2::splat(0.8),
+            color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
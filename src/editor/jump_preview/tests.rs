@@ -0,0 +1,83 @@
+use super::*;
+
+fn solid(cells: &[(i32, i32, i32)]) -> HashSet<(i32, i32, i32)> {
+    cells.iter().copied().collect()
+}
+
+fn reaches(landings: &[Landing], pos: (i32, i32, i32)) -> bool {
+    landings.iter().any(|landing| landing.pos == pos)
+}
+
+#[test]
+fn default_jump_climbs_one_voxel() {
+    let physics = PhysicsConfig::default();
+    assert!((max_jump_height(&physics) - 1.0).abs() < 1e-5);
+    assert!(airtime(&physics, 1.0).is_some());
+    assert!(airtime(&physics, 1.5).is_none());
+    // Landing at takeoff height takes the full up-and-down time
+    assert!((airtime(&physics, 0.0).unwrap() - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn arc_starts_at_takeoff_and_peaks_at_max_height() {
+    let physics = PhysicsConfig::default();
+    let takeoff = Vec3::new(0.0, 1.5, 0.0);
+    let arc = jump_arc(&physics, takeoff, Vec3::X);
+
+    assert_eq!(arc[0], takeoff);
+    let peak = arc.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+    assert!(peak <= takeoff.y + max_jump_height(&physics) + 1e-4);
+    assert!(peak > takeoff.y + 0.9);
+    let last = arc.last().unwrap();
+    assert!((last.y - (takeoff.y - MAX_DROP as f32)).abs() < 1e-3);
+    assert!(arc.iter().all(|p| p.z == 0.0 && p.x >= 0.0));
+}
+
+#[test]
+fn one_voxel_gap_is_jumpable_but_two_are_not() {
+    let physics = PhysicsConfig::default();
+    let cells = solid(&[(0, 0, 0), (2, 0, 0), (0, 0, 3), (-3, 0, 0)]);
+    let landings = reachable_landings(&physics, &cells, (0, 0, 0));
+
+    assert!(reaches(&landings, (2, 0, 0)));
+    assert!(!reaches(&landings, (0, 0, 3)));
+    assert!(!reaches(&landings, (-3, 0, 0)));
+}
+
+#[test]
+fn dropping_down_extends_reach_and_climbing_shortens_it() {
+    let physics = PhysicsConfig::default();
+    let cells = solid(&[(0, 0, 0), (3, -1, 0), (0, 1, 2), (0, 2, 1), (-1, 1, 0)]);
+    let landings = reachable_landings(&physics, &cells, (0, 0, 0));
+
+    assert!(reaches(&landings, (3, -1, 0)));
+    assert!(reaches(&landings, (0, 1, 2)));
+    assert!(reaches(&landings, (-1, 1, 0)));
+    // Two voxels up is higher than a jump
+    assert!(!reaches(&landings, (0, 2, 1)));
+    let up = landings.iter().find(|l| l.pos == (-1, 1, 0)).unwrap();
+    assert_eq!(up.rise, 1);
+}
+
+#[test]
+fn covered_voxels_are_not_landings() {
+    let physics = PhysicsConfig::default();
+    let cells = solid(&[(0, 0, 0), (1, 0, 0), (1, 1, 0), (1, 2, 0)]);
+    let landings = reachable_landings(&physics, &cells, (0, 0, 0));
+
+    assert!(!reaches(&landings, (1, 0, 0)));
+    assert!(!reaches(&landings, (1, 1, 0)));
+    assert!(!reaches(&landings, (0, 0, 0)));
+}
+
+#[test]
+fn stronger_jumps_reach_further() {
+    let physics = PhysicsConfig {
+        jump_velocity: 12.0,
+        ..default()
+    };
+    let cells = solid(&[(0, 0, 0), (0, 2, 1), (3, 0, 0)]);
+    let landings = reachable_landings(&physics, &cells, (0, 0, 0));
+    assert!(reaches(&landings, (0, 2, 1)));
+    assert!(reaches(&landings, (3, 0, 0)));
+}
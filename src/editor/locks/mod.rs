@@ -0,0 +1,271 @@
+//! Locked areas: parts of the map protected from accidental edits.
+//!
+//! A locked area is a box of voxels or a whole horizontal layer. Placing,
+//! removing, painting, sculpting, moving or rotating voxels inside one is
+//! refused, undo and redo excepted. Locked voxels get a faint blue tint, and
+//! the status bar warns while the active tool points into a locked area.
+//!
+//! Like the construction guides, locks are editor-only data kept in the
+//! map's `editor_metadata` section, which the game never reads.
+//! **File → Locked Areas...** adds and removes them.
+
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::systems::game::map::format::MapData;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// Tint drawn over locked voxels
+const TINT_COLOR: Color = Color::srgba(0.35, 0.55, 1.0, 0.12);
+
+/// A part of the map that can't be edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockedArea {
+    /// The voxels from `min` to `max`, both included
+    Region {
+        min: (i32, i32, i32),
+        max: (i32, i32, i32),
+    },
+    /// Every voxel at height `y`
+    Layer { y: i32 },
+}
+
+impl LockedArea {
+    /// Box with corners `a` and `b`, in any order.
+    pub fn region(a: (i32, i32, i32), b: (i32, i32, i32)) -> Self {
+        Self::Region {
+            min: (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2)),
+            max: (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2)),
+        }
+    }
+
+    /// Smallest box holding every position in `positions`, if there are any.
+    pub fn bounding(positions: impl IntoIterator<Item = (i32, i32, i32)>) -> Option<Self> {
+        let mut positions = positions.into_iter();
+        let first = positions.next()?;
+        let (min, max) = positions.fold((first, first), |(min, max), pos| {
+            (
+                (min.0.min(pos.0), min.1.min(pos.1), min.2.min(pos.2)),
+                (max.0.max(pos.0), max.1.max(pos.1), max.2.max(pos.2)),
+            )
+        });
+        Some(Self::Region { min, max })
+    }
+
+    pub fn contains(&self, pos: (i32, i32, i32)) -> bool {
+        match *self {
+            Self::Region { min, max } => {
+                (min.0..=max.0).contains(&pos.0)
+                    && (min.1..=max.1).contains(&pos.1)
+                    && (min.2..=max.2).contains(&pos.2)
+            }
+            Self::Layer { y } => pos.1 == y,
+        }
+    }
+
+    /// Short description for the Locked Areas window.
+    pub fn label(&self) -> String {
+        match *self {
+            Self::Region { min, max } => format!(
+                "Region ({}, {}, {}) to ({}, {}, {})",
+                min.0, min.1, min.2, max.0, max.1, max.2
+            ),
+            Self::Layer { y } => format!("Layer Y = {}", y),
+        }
+    }
+
+    /// Text stored in the map, e.g. `region 0 0 0 4 2 4` or `layer 3`.
+    pub fn encode(&self) -> String {
+        match *self {
+            Self::Region { min, max } => format!(
+                "region {} {} {} {} {} {}",
+                min.0, min.1, min.2, max.0, max.1, max.2
+            ),
+            Self::Layer { y } => format!("layer {}", y),
+        }
+    }
+
+    /// Read an area written by [`encode`](Self::encode).
+    pub fn decode(text: &str) -> Option<Self> {
+        let mut words = text.split_whitespace();
+        let kind = words.next()?;
+        let numbers: Vec<i32> = words.map(str::parse).collect::<Result<_, _>>().ok()?;
+        match (kind, numbers.as_slice()) {
+            ("region", &[x0, y0, z0, x1, y1, z1]) => Some(Self::region((x0, y0, z0), (x1, y1, z1))),
+            ("layer", &[y]) => Some(Self::Layer { y }),
+            _ => None,
+        }
+    }
+}
+
+/// Locked areas stored in `map`, skipping any that can't be read.
+pub fn map_locks(map: &MapData) -> Vec<LockedArea> {
+    map.editor_metadata
+        .iter()
+        .flat_map(|metadata| &metadata.locked_areas)
+        .filter_map(|area| LockedArea::decode(area))
+        .collect()
+}
+
+/// Store `areas` in the editor data of `map`.
+pub fn set_map_locks(map: &mut MapData, areas: &[LockedArea]) {
+    map.edit_editor_metadata(|metadata| {
+        metadata.locked_areas = areas.iter().map(LockedArea::encode).collect();
+    });
+}
+
+/// Whether any of `areas` contains `pos`.
+pub fn is_locked(areas: &[LockedArea], pos: (i32, i32, i32)) -> bool {
+    areas.iter().any(|area| area.contains(pos))
+}
+
+/// What the tint boxes were last built from: the stored locks and the world
+/// dimensions layers span
+type TintKey = (Vec<String>, (i32, i32, i32));
+
+/// Marker for the translucent boxes tinting locked voxels
+#[derive(Component)]
+pub struct LockedAreaTint;
+
+/// Layer height typed into the Locked Areas window.
+#[derive(Resource, Default)]
+pub struct LocksWindow {
+    pub layer_y: i32,
+}
+
+/// System to render the Locked Areas window while it is open.
+pub fn render_locks_window(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<EditorUIState>,
+    mut editor_state: ResMut<EditorState>,
+    mut window: ResMut<LocksWindow>,
+) {
+    if !ui_state.locks_window_open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let old = map_locks(&editor_state.current_map);
+    let mut areas = old.clone();
+    let selection = LockedArea::bounding(editor_state.selected_voxels.iter().copied());
+    let mut open = true;
+
+    egui::Window::new("🔒 Locked Areas")
+        .open(&mut open)
+        .default_width(300.0)
+        .show(ctx, |ui| {
+            ui.label("Voxels in these areas can't be placed, removed or changed.");
+
+            ui.separator();
+
+            if areas.is_empty() {
+                ui.label(egui::RichText::new("Nothing is locked").weak());
+            }
+            let mut unlock = None;
+            for (index, area) in areas.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.small_button("🔓").on_hover_text("Unlock").clicked() {
+                        unlock = Some(index);
+                    }
+                    ui.label(area.label());
+                });
+            }
+            if let Some(index) = unlock {
+                areas.remove(index);
+            }
+
+            ui.separator();
+
+            if ui
+                .add_enabled(
+                    selection.is_some(),
+                    egui::Button::new("🔒 Lock Selection Bounds"),
+                )
+                .on_hover_text("Lock the box around the selected voxels")
+                .clicked()
+            {
+                if let Some(area) = selection.filter(|area| !areas.contains(area)) {
+                    areas.push(area);
+                }
+            }
+            ui.horizontal(|ui| {
+                if ui.button("🔒 Lock Layer").clicked() {
+                    let area = LockedArea::Layer { y: window.layer_y };
+                    if !areas.contains(&area) {
+                        areas.push(area);
+                    }
+                }
+                ui.add(egui::DragValue::new(&mut window.layer_y).prefix("Y "));
+            });
+            if ui
+                .add_enabled(!areas.is_empty(), egui::Button::new("Unlock All"))
+                .clicked()
+            {
+                areas.clear();
+            }
+        });
+
+    if areas != old {
+        set_map_locks(&mut editor_state.current_map, &areas);
+        editor_state.mark_modified_without_render();
+        info!("Locked areas: {}", areas.len());
+    }
+    if !open {
+        ui_state.locks_window_open = false;
+    }
+}
+
+/// System to respawn the tint boxes when the locks or world bounds change.
+///
+/// Layers span the declared world bounds.
+pub fn update_locked_area_tint(
+    mut commands: Commands,
+    editor_state: Res<EditorState>,
+    tints: Query<Entity, With<LockedAreaTint>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut shown: Local<Option<TintKey>>,
+) {
+    let map = &editor_state.current_map;
+    let key = (map.editor_metadata().locked_areas, map.world.dimensions());
+    if shown.as_ref() == Some(&key) {
+        return;
+    }
+    *shown = Some(key);
+
+    for entity in &tints {
+        commands.entity(entity).despawn();
+    }
+    let areas = map_locks(map);
+    if areas.is_empty() {
+        return;
+    }
+
+    let material = materials.add(StandardMaterial {
+        base_color: TINT_COLOR,
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    let (width, _, depth) = map.world.dimensions();
+    for area in areas {
+        let (min, max) = match area {
+            LockedArea::Region { min, max } => (min, max),
+            LockedArea::Layer { y } => ((0, y, 0), (width.max(1) - 1, y, depth.max(1) - 1)),
+        };
+        let min = Vec3::new(min.0 as f32, min.1 as f32, min.2 as f32) - 0.5;
+        let max = Vec3::new(max.0 as f32, max.1 as f32, max.2 as f32) + 0.5;
+        // Slightly larger than the voxels so the tint doesn't z-fight their faces
+        let size = max - min + 0.02;
+        commands.spawn((
+            Mesh3d(meshes.add(Cuboid::from_size(size))),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation((min + max) / 2.0),
+            LockedAreaTint,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests;
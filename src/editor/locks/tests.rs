@@ -0,0 +1,91 @@
+use super::*;
+use crate::editor::history::EditorHistory;
+use crate::editor::tools::remove_voxel_group;
+use crate::systems::game::components::VoxelType;
+use crate::systems::game::map::format::VoxelData;
+
+#[test]
+fn test_region_and_layer_contain() {
+    let region = LockedArea::region((4, 2, 4), (0, 0, 0));
+    assert!(region.contains((0, 0, 0)));
+    assert!(region.contains((4, 2, 4)));
+    assert!(!region.contains((5, 2, 4)));
+
+    let layer = LockedArea::Layer { y: 3 };
+    assert!(layer.contains((-100, 3, 55)));
+    assert!(!layer.contains((0, 2, 0)));
+}
+
+#[test]
+fn test_bounding_box_of_selection() {
+    assert_eq!(LockedArea::bounding([]), None);
+    assert_eq!(
+        LockedArea::bounding([(1, 5, -2), (3, 0, 4), (2, 2, 2)]),
+        Some(LockedArea::Region {
+            min: (1, 0, -2),
+            max: (3, 5, 4)
+        })
+    );
+}
+
+#[test]
+fn test_locks_round_trip_through_the_map() {
+    let mut map = MapData::default_map();
+    let areas = vec![
+        LockedArea::region((0, 0, 0), (4, 2, 4)),
+        LockedArea::Layer { y: -1 },
+    ];
+
+    set_map_locks(&mut map, &areas);
+    assert_eq!(
+        map.editor_metadata().locked_areas,
+        vec!["region 0 0 0 4 2 4", "layer -1"]
+    );
+    assert_eq!(map_locks(&map), areas);
+
+    map.edit_editor_metadata(|metadata| {
+        metadata.locked_areas = ["layer", "region 1 2 3", "layer 7"]
+            .map(String::from)
+            .to_vec();
+    });
+    assert_eq!(map_locks(&map), vec![LockedArea::Layer { y: 7 }]);
+
+    set_map_locks(&mut map, &[]);
+    assert!(map.editor_metadata.is_none());
+}
+
+#[test]
+fn test_locked_positions_refuse_placement() {
+    let mut state = EditorState::new();
+    set_map_locks(
+        &mut state.current_map,
+        &[LockedArea::region((0, 0, 0), (1, 1, 1))],
+    );
+
+    assert!(!state.allows_edit_at((1, 1, 1)));
+    assert!(!state.allows_placement_at((0, 1, 0)));
+    assert!(state.allows_edit_at((2, 1, 1)));
+}
+
+#[test]
+fn test_group_removal_keeps_locked_voxels() {
+    let mut state = EditorState::new();
+    let mut history = EditorHistory::new();
+    state
+        .current_map
+        .world
+        .voxels
+        .push(VoxelData::new((0, 0, 0), VoxelType::Stone, None));
+    state
+        .current_map
+        .world
+        .voxels
+        .push(VoxelData::new((0, 1, 0), VoxelType::Stone, None));
+    set_map_locks(&mut state.current_map, &[LockedArea::Layer { y: 0 }]);
+
+    let removed = remove_voxel_group(&mut state, &mut history, &[(0, 0, 0), (0, 1, 0)], "Remove");
+
+    assert_eq!(removed, 1);
+    assert!(state.current_map.world.voxels.contains((0, 0, 0)));
+    assert!(!state.current_map.world.voxels.contains((0, 1, 0)));
+}
@@ -0,0 +1,213 @@
+//! Whole-map statistics and clean-up used by the editor's headless commands.
+//!
+//! [`MapStats`] summarises a map's contents, and [`optimize_map`] removes data
+//! that has no effect in game: duplicate voxels, identity rotations, unused
+//! orientation matrices and entity properties that belong to another entity
+//! type (left behind when an entity's type is changed).
+
+use crate::systems::game::map::format::{
+    find_or_insert_orientation, EntityType, MapData, VoxelType, IDENTITY,
+};
+use std::fmt;
+
+/// Engine-read entity properties that only apply to one entity type.
+///
+/// `name` is read for every entity type and is therefore never stripped.
+/// Keys not listed here are treated as author data and always kept.
+const TYPE_SPECIFIC_PROPERTIES: &[(EntityType, &[&str])] = &[
+    (EntityType::Npc, &["radius"]),
+    (
+        EntityType::LightSource,
+        &[
+            "intensity",
+            "range",
+            "shadows",
+            "color",
+            "flicker",
+            "flicker_amplitude",
+            "flicker_speed",
+        ],
+    ),
+];
+
+/// Whether `key` is an engine property of some entity type other than `entity_type`.
+fn belongs_to_other_type(entity_type: EntityType, key: &str) -> bool {
+    let own = TYPE_SPECIFIC_PROPERTIES
+        .iter()
+        .any(|(t, keys)| *t == entity_type && keys.contains(&key));
+    let known = TYPE_SPECIFIC_PROPERTIES
+        .iter()
+        .any(|(_, keys)| keys.contains(&key));
+    known && !own
+}
+
+/// Summary of a map's contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapStats {
+    pub name: String,
+    pub author: String,
+    pub version: String,
+    /// World dimensions (width, height, depth).
+    pub dimensions: (i32, i32, i32),
+    pub voxel_count: usize,
+    /// Voxel count per type, most common first.
+    pub voxels_by_type: Vec<(VoxelType, usize)>,
+    /// Voxels with a non-identity orientation.
+    pub rotated_voxels: usize,
+    pub orientation_count: usize,
+    pub entity_count: usize,
+    /// Entity count per type, in first-seen order.
+    pub entities_by_type: Vec<(EntityType, usize)>,
+    pub custom_property_count: usize,
+}
+
+impl MapStats {
+    pub fn from_map(map: &MapData) -> Self {
+        let mut voxels_by_type: Vec<(VoxelType, usize)> = Vec::new();
+        for voxel in &map.world.voxels {
+            increment(&mut voxels_by_type, voxel.voxel_type);
+        }
+        voxels_by_type.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let mut entities_by_type: Vec<(EntityType, usize)> = Vec::new();
+        for entity in &map.entities {
+            increment(&mut entities_by_type, entity.entity_type);
+        }
+
+        let rotated_voxels = map
+            .world
+            .voxels
+            .iter()
+            .filter(|v| {
+                v.rotation
+                    .and_then(|i| map.orientations.get(i))
+                    .is_some_and(|m| *m != IDENTITY)
+            })
+            .count();
+
+        Self {
+            name: map.metadata.name.clone(),
+            author: map.metadata.author.clone(),
+            version: map.metadata.version.clone(),
+            dimensions: (map.world.width, map.world.height, map.world.depth),
+            voxel_count: map.world.voxels.len(),
+            voxels_by_type,
+            rotated_voxels,
+            orientation_count: map.orientations.len(),
+            entity_count: map.entities.len(),
+            entities_by_type,
+            custom_property_count: map.custom_properties.len(),
+        }
+    }
+}
+
+fn increment<T: PartialEq>(counts: &mut Vec<(T, usize)>, key: T) {
+    match counts.iter_mut().find(|(k, _)| *k == key) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((key, 1)),
+    }
+}
+
+impl fmt::Display for MapStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (w, h, d) = self.dimensions;
+        writeln!(f, "Name:         {}", self.name)?;
+        writeln!(f, "Author:       {}", self.author)?;
+        writeln!(f, "Version:      {}", self.version)?;
+        writeln!(f, "Dimensions:   {w} x {h} x {d}")?;
+        writeln!(
+            f,
+            "Voxels:       {} ({} rotated, {} orientations)",
+            self.voxel_count, self.rotated_voxels, self.orientation_count
+        )?;
+        for (voxel_type, count) in &self.voxels_by_type {
+            writeln!(f, "  {:<12}{count}", format!("{voxel_type:?}"))?;
+        }
+        writeln!(f, "Entities:     {}", self.entity_count)?;
+        for (entity_type, count) in &self.entities_by_type {
+            writeln!(f, "  {:<12}{count}", format!("{entity_type:?}"))?;
+        }
+        write!(f, "Custom props: {}", self.custom_property_count)
+    }
+}
+
+/// What [`optimize_map`] removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptimizeReport {
+    /// Voxels sharing a position with a later voxel.
+    pub duplicate_voxels: usize,
+    /// Rotation indices pointing at the identity matrix (or past the end of
+    /// the table), replaced by `None`.
+    pub identity_rotations: usize,
+    /// Orientation matrices no voxel references (or duplicates of another).
+    pub unused_orientations: usize,
+    /// Entity properties that only apply to another entity type.
+    pub unused_properties: usize,
+}
+
+impl OptimizeReport {
+    /// Whether the map was left unchanged.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for OptimizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Removed {} duplicate voxels, {} identity rotations, {} unused orientations, {} unused entity properties",
+            self.duplicate_voxels,
+            self.identity_rotations,
+            self.unused_orientations,
+            self.unused_properties
+        )
+    }
+}
+
+/// Remove data from `map` that has no effect in game.
+///
+/// When several voxels share a position the last one is kept, since it is
+/// the one drawn on top. Voxel order is otherwise preserved.
+pub fn optimize_map(map: &mut MapData) -> OptimizeReport {
+    let mut report = OptimizeReport {
+        // Duplicate voxels: keep the last entry at each position
+        duplicate_voxels: map.world.voxels.remove_duplicates(),
+        ..Default::default()
+    };
+
+    // Orientations: drop identity references, then rebuild the table from
+    // the matrices still in use
+    let old_orientations = std::mem::take(&mut map.orientations);
+    for voxel in &mut map.world.voxels {
+        let Some(index) = voxel.rotation else {
+            continue;
+        };
+        // Out-of-range indices already render unrotated
+        match old_orientations.get(index).filter(|m| **m != IDENTITY) {
+            Some(matrix) => {
+                voxel.rotation = Some(find_or_insert_orientation(&mut map.orientations, *matrix));
+            }
+            None => {
+                voxel.rotation = None;
+                report.identity_rotations += 1;
+            }
+        }
+    }
+    report.unused_orientations = old_orientations.len() - map.orientations.len();
+
+    // Properties belonging to another entity type
+    for entity in &mut map.entities {
+        let entity_type = entity.entity_type;
+        let before = entity.properties.len();
+        entity
+            .properties
+            .retain(|key, _| !belongs_to_other_type(entity_type, key));
+        report.unused_properties += before - entity.properties.len();
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,143 @@
+use super::*;
+use crate::systems::game::map::format::{axis_angle_to_matrix, EntityData, VoxelData};
+use crate::systems::game::map::geometry::RotationAxis;
+use std::collections::HashMap;
+
+fn entity(entity_type: EntityType, properties: &[(&str, &str)]) -> EntityData {
+    EntityData {
+        id: Default::default(),
+        entity_type,
+        position: (0.0, 0.0, 0.0),
+        properties: properties
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+#[test]
+fn stats_count_voxels_and_entities_by_type() {
+    let mut map = MapData::empty_map();
+    let rotated = axis_angle_to_matrix(RotationAxis::Y, 1);
+    map.orientations = vec![rotated];
+    map.world.voxels = vec![
+        VoxelData::new((0, 0, 0), VoxelType::Grass, None),
+        VoxelData {
+            rotation: Some(0),
+            ..VoxelData::new((1, 0, 0), VoxelType::Stone, None)
+        },
+        VoxelData::new((2, 0, 0), VoxelType::Stone, None),
+    ]
+    .into();
+    map.entities = vec![
+        entity(EntityType::PlayerSpawn, &[]),
+        entity(EntityType::Npc, &[]),
+        entity(EntityType::Npc, &[]),
+    ];
+
+    let stats = MapStats::from_map(&map);
+    assert_eq!(stats.voxel_count, 3);
+    assert_eq!(
+        stats.voxels_by_type,
+        vec![(VoxelType::Stone, 2), (VoxelType::Grass, 1)]
+    );
+    assert_eq!(stats.rotated_voxels, 1);
+    assert_eq!(
+        stats.entities_by_type,
+        vec![(EntityType::PlayerSpawn, 1), (EntityType::Npc, 2)]
+    );
+    assert!(stats.to_string().contains("Voxels:       3"));
+}
+
+#[test]
+fn optimize_keeps_last_duplicate_voxel_in_order() {
+    let mut map = MapData::empty_map();
+    map.world.voxels = vec![
+        VoxelData::new((0, 0, 0), VoxelType::Grass, None),
+        VoxelData::new((1, 0, 0), VoxelType::Dirt, None),
+        VoxelData::new((0, 0, 0), VoxelType::Stone, None),
+    ]
+    .into();
+
+    let report = optimize_map(&mut map);
+    assert_eq!(report.duplicate_voxels, 1);
+    let kept: Vec<_> = map
+        .world
+        .voxels
+        .iter()
+        .map(|v| (v.pos, v.voxel_type))
+        .collect();
+    assert_eq!(
+        kept,
+        vec![((1, 0, 0), VoxelType::Dirt), ((0, 0, 0), VoxelType::Stone)]
+    );
+}
+
+#[test]
+fn optimize_compacts_orientation_table() {
+    let a = axis_angle_to_matrix(RotationAxis::Y, 1);
+    let b = axis_angle_to_matrix(RotationAxis::X, 1);
+    let mut map = MapData::empty_map();
+    // IDENTITY, an unused matrix, a duplicate of `a`
+    map.orientations = vec![IDENTITY, b, a, a];
+    map.world.voxels = vec![
+        VoxelData {
+            rotation: Some(0),
+            ..VoxelData::new((0, 0, 0), VoxelType::Stone, None)
+        },
+        VoxelData {
+            rotation: Some(2),
+            ..VoxelData::new((1, 0, 0), VoxelType::Stone, None)
+        },
+        VoxelData {
+            rotation: Some(3),
+            ..VoxelData::new((2, 0, 0), VoxelType::Stone, None)
+        },
+        VoxelData {
+            rotation: Some(9),
+            ..VoxelData::new((3, 0, 0), VoxelType::Stone, None)
+        },
+    ]
+    .into();
+
+    let report = optimize_map(&mut map);
+    assert_eq!(report.identity_rotations, 2);
+    assert_eq!(report.unused_orientations, 3);
+    assert_eq!(map.orientations, vec![a]);
+    let rotations: Vec<_> = map.world.voxels.iter().map(|v| v.rotation).collect();
+    assert_eq!(rotations, vec![None, Some(0), Some(0), None]);
+}
+
+#[test]
+fn optimize_strips_properties_of_other_entity_types_only() {
+    let mut map = MapData::empty_map();
+    map.entities = vec![
+        entity(
+            EntityType::Npc,
+            &[
+                ("name", "Bob"),
+                ("radius", "0.4"),
+                ("intensity", "5"),
+                ("quest", "q1"),
+            ],
+        ),
+        entity(
+            EntityType::LightSource,
+            &[("intensity", "5"), ("radius", "1")],
+        ),
+    ];
+
+    let report = optimize_map(&mut map);
+    assert_eq!(report.unused_properties, 2);
+    let mut npc_keys: Vec<_> = map.entities[0].properties.keys().cloned().collect();
+    npc_keys.sort();
+    assert_eq!(npc_keys, vec!["name", "quest", "radius"]);
+    assert_eq!(map.entities[1].properties.len(), 1);
+}
+
+#[test]
+fn optimizing_an_optimized_map_is_a_no_op() {
+    let mut map = MapData::default_map();
+    optimize_map(&mut map);
+    assert!(optimize_map(&mut map).is_empty());
+}
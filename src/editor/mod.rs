@@ -4,30 +4,67 @@
 //! map files in RON format. It uses bevy_egui for the UI and reuses the game's
 //! rendering code for 3D preview.
 
+pub mod autotile;
+pub mod billboards;
+pub mod bookmarks;
 pub mod camera;
+pub mod cinematics;
+pub mod clipboard;
+pub mod collab;
+pub mod complexity;
 pub mod controller;
 pub mod cursor;
+pub mod diff;
+pub mod documents;
+pub mod entity_ops;
+pub mod extensions;
 pub mod file_io;
+pub mod framing;
+pub mod generator;
+pub mod go_to;
 pub mod grid;
+pub mod guides;
 pub mod history;
+pub mod input_recording;
+pub mod jump_preview;
+pub mod locks;
+pub mod map_tools;
 pub mod play;
+pub mod playtest_heatmap;
+pub mod prefabs;
+pub mod preferences;
 pub mod recent_files;
+pub mod recovery;
 pub mod renderer;
+pub mod selection_boxes;
+pub mod session;
 pub mod shortcuts;
 pub mod state;
+pub mod templates;
 pub mod tools;
 pub mod ui;
+pub mod validation;
+pub mod viewports;
+pub mod voxel_ops;
 
+pub use clipboard::EditorClipboard;
 pub use cursor::{
     handle_keyboard_cursor_movement, handle_keyboard_selection, handle_play_shortcuts,
     handle_tool_switching, toggle_keyboard_edit_mode, CursorState,
 };
-pub use file_io::{FileSavedEvent, SaveFileDialogReceiver, SaveMapAsEvent, SaveMapEvent};
+pub use documents::{DocumentEvent, EditorDocuments};
+pub use file_io::{
+    FileSavedEvent, MapSaveTask, SaveFileDialogReceiver, SaveMapAsEvent, SaveMapEvent,
+};
 pub use history::{EditorAction, EditorHistory};
 pub use play::{PlayMapEvent, PlayTestState, StopGameEvent};
+pub use preferences::EditorPreferences;
 pub use recent_files::{OpenRecentFileEvent, RecentFiles};
 pub use renderer::{
     render_entities_system, EditorChunk, EditorEntityMarker, MapRenderState, RenderMapEvent,
 };
 pub use shortcuts::{handle_global_shortcuts, handle_redo, handle_undo, RedoEvent, UndoEvent};
-pub use state::{EditorState, EditorTool, KeyboardEditMode, ToolMemory};
+pub use state::{
+    BoundsEnforcement, EditorState, EditorTool, KeyboardEditMode, SelectionFilter, SnapMode,
+    ToolMemory,
+};
@@ -0,0 +1,197 @@
+//! Playtest heatmap overlay.
+//!
+//! View → Load Playtest Log reads a log written by the game's
+//! `--playtest-log` mode (see [`crate::systems::game::playtest_log`]) and
+//! draws where the player went: every voxel column the position trace passed
+//! through gets a square at the height the player walked at, coloured from
+//! blue (rarely visited) to red (most visited). Deaths are drawn as red
+//! crosses and interactions as yellow rings. View → Playtest Heatmap toggles
+//! the overlay.
+//!
+//! The log isn't checked against the open map, so a log from another map
+//! (or an older version of this one) draws wherever its player went.
+
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::systems::game::playtest_log::{PlaytestEventKind, PlaytestLog};
+use crate::systems::game::resources::PhysicsConfig;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{
+    mpsc::{channel, Receiver},
+    Arc, Mutex,
+};
+
+/// Position samples in one voxel column.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HeatCell {
+    pub samples: u32,
+    /// Sum of the samples' heights, for their average
+    height_sum: f32,
+}
+
+impl HeatCell {
+    /// Average height of the player's collider centre in this column.
+    pub fn height(&self) -> f32 {
+        self.height_sum / self.samples.max(1) as f32
+    }
+}
+
+/// A playtest log binned for drawing.
+#[derive(Debug, Clone, Default)]
+pub struct Heatmap {
+    /// Samples by voxel column (x, z)
+    pub cells: HashMap<(i32, i32), HeatCell>,
+    /// Most samples in any column
+    pub max_samples: u32,
+    pub deaths: Vec<Vec3>,
+    pub interactions: Vec<Vec3>,
+}
+
+impl Heatmap {
+    pub fn from_log(log: &PlaytestLog) -> Self {
+        let mut heatmap = Self::default();
+        for event in &log.events {
+            let position = event.position;
+            match event.kind {
+                PlaytestEventKind::Position => {
+                    let column = (position.x.round() as i32, position.z.round() as i32);
+                    let cell = heatmap.cells.entry(column).or_default();
+                    cell.samples += 1;
+                    cell.height_sum += position.y;
+                    heatmap.max_samples = heatmap.max_samples.max(cell.samples);
+                }
+                PlaytestEventKind::Death => heatmap.deaths.push(position),
+                PlaytestEventKind::Interact => heatmap.interactions.push(position),
+                PlaytestEventKind::Goal => {}
+            }
+        }
+        heatmap
+    }
+
+    /// How visited `cell` is, from 0.0 (once) to 1.0 (the most visited
+    /// column). Counts are compared on a log scale so a spot the player
+    /// idled at doesn't wash out the rest.
+    pub fn heat(&self, cell: &HeatCell) -> f32 {
+        if self.max_samples <= 1 {
+            return 1.0;
+        }
+        (cell.samples as f32).ln() / (self.max_samples as f32).ln()
+    }
+}
+
+/// Colour for a heat from 0.0 (blue) through green and yellow to 1.0 (red).
+pub fn heat_color(heat: f32) -> Color {
+    // Hue 240° is blue, 0° red
+    Color::hsla(240.0 * (1.0 - heat.clamp(0.0, 1.0)), 0.9, 0.5, 0.8)
+}
+
+/// The loaded playtest log, and the picker loading one.
+#[derive(Resource, Default)]
+pub struct PlaytestHeatmap {
+    /// Receiver for the "pick a log" dialog thread
+    receiver: Option<Arc<Mutex<Receiver<Option<PathBuf>>>>>,
+    /// Log file shown
+    pub path: Option<PathBuf>,
+    pub heatmap: Option<Heatmap>,
+}
+
+/// System to open the log picker and load the chosen log.
+pub fn load_playtest_log(
+    mut playtest: ResMut<PlaytestHeatmap>,
+    mut editor_state: ResMut<EditorState>,
+    mut ui_state: ResMut<EditorUIState>,
+) {
+    // Spawn the file picker in a separate thread to avoid blocking
+    if ui_state.playtest_log_dialog_open {
+        ui_state.playtest_log_dialog_open = false;
+
+        let (sender, receiver) = channel();
+        playtest.receiver = Some(Arc::new(Mutex::new(receiver)));
+        std::thread::spawn(move || {
+            let result = rfd::FileDialog::new()
+                .add_filter("Playtest Logs", &["csv"])
+                .set_title("Load Playtest Log")
+                .pick_file();
+            let _ = sender.send(result);
+        });
+    }
+
+    let picked = playtest
+        .receiver
+        .as_ref()
+        .and_then(|receiver| receiver.lock().ok()?.try_recv().ok());
+    let Some(result) = picked else {
+        return;
+    };
+    playtest.receiver = None;
+    let Some(path) = result else {
+        return;
+    };
+    match PlaytestLog::load(&path) {
+        Ok(log) => {
+            info!(
+                "Loaded playtest log {:?}: {:.0}s of play, {} deaths, {} interactions",
+                path,
+                log.elapsed,
+                log.count(PlaytestEventKind::Death),
+                log.count(PlaytestEventKind::Interact)
+            );
+            playtest.heatmap = Some(Heatmap::from_log(&log));
+            playtest.path = Some(path);
+            editor_state.show_playtest_heatmap = true;
+        }
+        Err(e) => {
+            error!("Failed to load playtest log: {}", e);
+            ui_state.error_message = format!("Failed to load playtest log:\n{}", e);
+            ui_state.error_dialog_open = true;
+        }
+    }
+}
+
+/// Draw the loaded heatmap while the overlay is enabled.
+pub fn draw_playtest_heatmap(
+    editor_state: Res<EditorState>,
+    playtest: Res<PlaytestHeatmap>,
+    physics: Res<PhysicsConfig>,
+    mut gizmos: Gizmos,
+) {
+    if !editor_state.show_playtest_heatmap {
+        return;
+    }
+    let Some(heatmap) = &playtest.heatmap else {
+        return;
+    };
+
+    // Just above the floor the player walked on
+    let floor = -physics.player_half_height + 0.02;
+    let flat = Quat::from_rotation_x(std::f32::consts::FRAC_PI_2);
+    for (&(x, z), cell) in &heatmap.cells {
+        let center = Vec3::new(x as f32, cell.height() + floor, z as f32);
+        gizmos.rect(
+            Isometry3d::new(center, flat),
+            Vec2::splat(0.9),
+            heat_color(heatmap.heat(cell)),
+        );
+    }
+
+    let death_color = Color::srgb(1.0, 0.15, 0.15);
+    for &death in &heatmap.deaths {
+        let center = death + Vec3::Y * floor;
+        for direction in [Vec3::new(0.3, 0.0, 0.3), Vec3::new(0.3, 0.0, -0.3)] {
+            gizmos.line(center - direction, center + direction, death_color);
+        }
+    }
+
+    let interaction_color = Color::srgb(1.0, 0.85, 0.2);
+    for &interaction in &heatmap.interactions {
+        gizmos.circle(
+            Isometry3d::new(interaction + Vec3::Y * floor, flat),
+            0.35,
+            interaction_color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
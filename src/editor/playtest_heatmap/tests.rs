@@ -0,0 +1,51 @@
+use super::*;
+
+fn log_with_trace(positions: &[Vec3]) -> PlaytestLog {
+    let mut log = PlaytestLog::new(None);
+    for &position in positions {
+        log.push(PlaytestEventKind::Position, position, String::new());
+    }
+    log
+}
+
+#[test]
+fn samples_are_binned_by_voxel_column() {
+    let log = log_with_trace(&[
+        Vec3::new(0.1, 1.0, 0.2),
+        Vec3::new(-0.3, 3.0, 0.4),
+        Vec3::new(2.0, 1.0, 0.0),
+    ]);
+    let heatmap = Heatmap::from_log(&log);
+
+    assert_eq!(heatmap.cells.len(), 2);
+    let cell = heatmap.cells[&(0, 0)];
+    assert_eq!(cell.samples, 2);
+    assert_eq!(cell.height(), 2.0);
+    assert_eq!(heatmap.max_samples, 2);
+}
+
+#[test]
+fn most_visited_column_is_hottest() {
+    let mut trace = vec![Vec3::ZERO; 100];
+    trace.extend([Vec3::X * 5.0; 10]);
+    trace.push(Vec3::X * 9.0);
+    let heatmap = Heatmap::from_log(&log_with_trace(&trace));
+
+    let heat = |x: i32| heatmap.heat(&heatmap.cells[&(x, 0)]);
+    assert_eq!(heat(0), 1.0);
+    assert_eq!(heat(9), 0.0);
+    assert!(heat(5) > 0.4 && heat(5) < 0.6);
+}
+
+#[test]
+fn deaths_and_interactions_are_kept_apart() {
+    let mut log = log_with_trace(&[Vec3::ZERO]);
+    log.push(PlaytestEventKind::Death, Vec3::X, "fall".to_string());
+    log.push(PlaytestEventKind::Interact, Vec3::Z, "door".to_string());
+    log.push(PlaytestEventKind::Goal, Vec3::Y, "12.00s".to_string());
+    let heatmap = Heatmap::from_log(&log);
+
+    assert_eq!(heatmap.deaths, vec![Vec3::X]);
+    assert_eq!(heatmap.interactions, vec![Vec3::Z]);
+    assert_eq!(heatmap.cells.len(), 1);
+}
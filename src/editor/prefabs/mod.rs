@@ -0,0 +1,295 @@
+//! Prefab files: selections saved to disk for reuse in other maps.
+//!
+//! File → Export Selection as Prefab writes the selected voxels and entities
+//! to a standalone `.prefab.ron` file, stored the way the
+//! [clipboard](crate::editor::clipboard) holds them: relative to the
+//! selection's minimum corner, with rotations as matrices. The files are
+//! plain RON, so building pieces can be shared and reviewed in version
+//! control.
+//!
+//! File → Import Prefab loads such a file into any map. A ghost of the
+//! prefab follows the cursor until Enter places it as one undo step;
+//! Escape cancels.
+
+use crate::editor::clipboard::{place_objects, EditorClipboard};
+use crate::editor::cursor::CursorState;
+use crate::editor::history::EditorHistory;
+use crate::editor::renderer::RenderMapEvent;
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::editor::tools::UpdateSelectionHighlights;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{
+    mpsc::{channel, Receiver},
+    Arc, Mutex,
+};
+
+/// File name suffix of prefab files
+pub const PREFAB_EXTENSION: &str = "prefab.ron";
+
+/// A reusable piece of a map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prefab {
+    pub name: String,
+    pub objects: EditorClipboard,
+}
+
+impl Prefab {
+    /// Prefab of the selected voxels and entities, or `None` when nothing
+    /// is selected.
+    pub fn from_selection(name: String, editor_state: &EditorState) -> Option<Self> {
+        let mut objects = EditorClipboard::default();
+        if objects.copy_selection(editor_state) == 0 {
+            return None;
+        }
+        Some(Self { name, objects })
+    }
+
+    /// Write the prefab to `path` as pretty-printed RON
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| format!("Failed to serialize prefab: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Failed to write prefab: {}", e))
+    }
+
+    /// Read a prefab from `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read prefab: {}", e))?;
+        let prefab: Prefab =
+            ron::from_str(&contents).map_err(|e| format!("Failed to parse prefab: {}", e))?;
+        if prefab.objects.is_empty() {
+            return Err("The prefab contains no voxels or entities".to_string());
+        }
+        Ok(prefab)
+    }
+}
+
+/// `path` with the prefab suffix appended if it doesn't already end in it
+pub fn with_prefab_extension(path: PathBuf) -> PathBuf {
+    if path.to_string_lossy().ends_with(PREFAB_EXTENSION) {
+        return path;
+    }
+    let stem = path
+        .file_name()
+        .map(|name| name.to_string_lossy().trim_end_matches(".ron").to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.{}", stem, PREFAB_EXTENSION))
+}
+
+/// Prefab name derived from its file name, without the suffix
+pub fn prefab_name(path: &Path) -> String {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    file_name
+        .strip_suffix(PREFAB_EXTENSION)
+        .map(|name| name.trim_end_matches('.'))
+        .unwrap_or(file_name.trim_end_matches(".ron"))
+        .to_string()
+}
+
+/// Resource tracking prefab file dialogs and the prefab being placed
+#[derive(Resource, Default)]
+pub struct PrefabFiles {
+    /// Receiver for the export save dialog thread
+    export_receiver: Option<Arc<Mutex<Receiver<Option<PathBuf>>>>>,
+    /// Receiver for the import open dialog thread
+    import_receiver: Option<Arc<Mutex<Receiver<Option<PathBuf>>>>>,
+    /// Imported prefab following the cursor, waiting to be placed
+    pub placing: Option<Prefab>,
+    /// Where the prefab's minimum corner was last previewed. Kept while the
+    /// cursor is off the viewport so the Place button uses it.
+    pub anchor: Option<(i32, i32, i32)>,
+}
+
+fn poll(receiver: &mut Option<Arc<Mutex<Receiver<Option<PathBuf>>>>>) -> Option<Option<PathBuf>> {
+    let result = receiver
+        .as_ref()
+        .and_then(|receiver| receiver.lock().ok()?.try_recv().ok());
+    if result.is_some() {
+        *receiver = None;
+    }
+    result
+}
+
+/// System to open the prefab export/import dialogs and handle the chosen
+/// files.
+pub fn handle_prefab_dialogs(
+    mut ui_state: ResMut<EditorUIState>,
+    mut prefabs: ResMut<PrefabFiles>,
+    editor_state: Res<EditorState>,
+) {
+    // Spawn the file pickers in a separate thread to avoid blocking
+    if ui_state.export_prefab_dialog_open {
+        ui_state.export_prefab_dialog_open = false;
+
+        let (sender, receiver) = channel();
+        prefabs.export_receiver = Some(Arc::new(Mutex::new(receiver)));
+        std::thread::spawn(move || {
+            let result = rfd::FileDialog::new()
+                .add_filter("Prefab Files", &["ron"])
+                .set_file_name(format!("prefab.{}", PREFAB_EXTENSION))
+                .set_title("Export Selection as Prefab")
+                .save_file();
+            let _ = sender.send(result);
+        });
+    }
+
+    if ui_state.import_prefab_dialog_open {
+        ui_state.import_prefab_dialog_open = false;
+
+        let (sender, receiver) = channel();
+        prefabs.import_receiver = Some(Arc::new(Mutex::new(receiver)));
+        std::thread::spawn(move || {
+            let result = rfd::FileDialog::new()
+                .add_filter("Prefab Files", &["ron"])
+                .set_title("Import Prefab")
+                .pick_file();
+            let _ = sender.send(result);
+        });
+    }
+
+    if let Some(Some(path)) = poll(&mut prefabs.export_receiver) {
+        let path = with_prefab_extension(path);
+        let result = match Prefab::from_selection(prefab_name(&path), &editor_state) {
+            Some(prefab) => prefab.save(&path).map(|()| prefab.objects.len()),
+            None => Err("Select voxels or entities to export as a prefab".to_string()),
+        };
+        match result {
+            Ok(count) => info!("Exported {} objects as prefab {:?}", count, path),
+            Err(e) => {
+                error!("{}", e);
+                ui_state.error_message = e;
+                ui_state.error_dialog_open = true;
+            }
+        }
+    }
+
+    if let Some(Some(path)) = poll(&mut prefabs.import_receiver) {
+        match Prefab::load(&path) {
+            Ok(prefab) => {
+                info!(
+                    "Imported prefab '{}' ({} objects) from {:?}",
+                    prefab.name,
+                    prefab.objects.len(),
+                    path
+                );
+                prefabs.placing = Some(prefab);
+                prefabs.anchor = None;
+            }
+            Err(e) => {
+                error!("{}", e);
+                ui_state.error_message = format!("Failed to import prefab:\n{}", e);
+                ui_state.error_dialog_open = true;
+            }
+        }
+    }
+}
+
+/// System to move the imported prefab with the cursor and place or cancel
+/// it, from the keyboard or the placement window.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_prefab_placement(
+    mut contexts: EguiContexts,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cursor_state: Res<CursorState>,
+    mut prefabs: ResMut<PrefabFiles>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut render_events: MessageWriter<RenderMapEvent>,
+    mut selection_events: MessageWriter<UpdateSelectionHighlights>,
+) {
+    if prefabs.placing.is_none() {
+        return;
+    }
+    if let Some(anchor) = cursor_state.placement_grid_pos {
+        prefabs.anchor = Some(anchor);
+    }
+
+    let mut place = keyboard.just_pressed(KeyCode::Enter);
+    let mut cancel = keyboard.just_pressed(KeyCode::Escape);
+
+    if let (Ok(ctx), Some(prefab)) = (contexts.ctx_mut(), &prefabs.placing) {
+        egui::Window::new("Place Prefab")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -40.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} ({} objects)",
+                    prefab.name,
+                    prefab.objects.len()
+                ));
+                ui.weak("Move the cursor to position it");
+                ui.horizontal(|ui| {
+                    place |= ui
+                        .add_enabled(prefabs.anchor.is_some(), egui::Button::new("Place (Enter)"))
+                        .clicked();
+                    cancel |= ui.button("Cancel (Esc)").clicked();
+                });
+            });
+    }
+
+    if cancel {
+        info!("Cancelled prefab placement");
+        prefabs.placing = None;
+        prefabs.anchor = None;
+        return;
+    }
+    let Some(anchor) = prefabs.anchor.filter(|_| place) else {
+        return;
+    };
+    let Some(prefab) = prefabs.placing.take() else {
+        return;
+    };
+    prefabs.anchor = None;
+
+    let description = format!("Place Prefab '{}'", prefab.name);
+    let count = place_objects(
+        &mut editor_state,
+        &mut history,
+        &prefab.objects,
+        anchor,
+        &description,
+    );
+    info!(
+        "Placed prefab '{}' ({} objects) at {:?}",
+        prefab.name, count, anchor
+    );
+    render_events.write(RenderMapEvent);
+    selection_events.write(UpdateSelectionHighlights);
+}
+
+/// System to draw a ghost of the prefab being placed at its anchor
+pub fn draw_prefab_preview(prefabs: Res<PrefabFiles>, mut gizmos: Gizmos) {
+    let (Some(prefab), Some(anchor)) = (&prefabs.placing, prefabs.anchor) else {
+        return;
+    };
+    let anchor = Vec3::new(anchor.0 as f32, anchor.1 as f32, anchor.2 as f32);
+
+    let voxel_color = Color::srgba(0.4, 0.8, 1.0, 0.5);
+    for pos in prefab.objects.voxel_positions() {
+        let center = anchor + Vec3::new(pos.0 as f32, pos.1 as f32, pos.2 as f32);
+        gizmos.cube(
+            Transform::from_translation(center).with_scale(Vec3::splat(0.98)),
+            voxel_color,
+        );
+    }
+
+    let entity_color = Color::srgb(1.0, 0.8, 0.2);
+    for position in prefab.objects.entity_positions() {
+        gizmos.sphere(
+            Isometry3d::from_translation(anchor + Vec3::from(position)),
+            0.3,
+            entity_color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
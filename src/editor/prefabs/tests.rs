@@ -0,0 +1,102 @@
+use super::*;
+use crate::systems::game::map::format::{
+    axis_angle_to_matrix, EntityData, EntityId, EntityType, VoxelData, VoxelType,
+};
+use crate::systems::game::map::geometry::RotationAxis;
+use std::collections::HashMap;
+
+/// A map with a rotated voxel and an NPC selected.
+fn source_state() -> EditorState {
+    let mut state = EditorState::default();
+    state.current_map.orientations = vec![axis_angle_to_matrix(RotationAxis::Y, 1)];
+    state.current_map.world.voxels = vec![
+        VoxelData {
+            rotation: Some(0),
+            ..VoxelData::new((4, 1, 4), VoxelType::Stone, None)
+        },
+        VoxelData::new((8, 0, 8), VoxelType::Stone, None),
+    ]
+    .into();
+    state.current_map.entities = vec![EntityData {
+        id: EntityId(1),
+        entity_type: EntityType::Npc,
+        position: (5.0, 2.0, 4.0),
+        properties: HashMap::new(),
+    }];
+    state.selected_voxels = [(4, 1, 4)].into_iter().collect();
+    state.selected_entities = [EntityId(1)].into_iter().collect();
+    state
+}
+
+#[test]
+fn empty_selection_exports_nothing() {
+    assert!(Prefab::from_selection("empty".to_string(), &EditorState::default()).is_none());
+}
+
+#[test]
+fn prefab_survives_a_file_round_trip() {
+    let prefab = Prefab::from_selection("tower".to_string(), &source_state()).unwrap();
+    let path = std::env::temp_dir().join(format!(
+        "adrakestory_prefab_test_{}.{}",
+        std::process::id(),
+        PREFAB_EXTENSION
+    ));
+    prefab.save(&path).unwrap();
+    let loaded = Prefab::load(&path);
+    let _ = fs::remove_file(&path);
+    let loaded = loaded.unwrap();
+
+    assert_eq!(loaded.name, "tower");
+    assert_eq!(loaded.objects.len(), 2);
+    assert_eq!(
+        loaded.objects.voxel_positions().collect::<Vec<_>>(),
+        vec![(0, 0, 0)]
+    );
+    assert_eq!(
+        loaded.objects.entity_positions().collect::<Vec<_>>(),
+        vec![(1.0, 1.0, 0.0)]
+    );
+}
+
+#[test]
+fn placing_into_another_map_registers_orientations() {
+    let prefab = Prefab::from_selection("tower".to_string(), &source_state()).unwrap();
+    let mut target = EditorState::default();
+    let mut history = EditorHistory::default();
+
+    let count = place_objects(
+        &mut target,
+        &mut history,
+        &prefab.objects,
+        (10, 0, 10),
+        "Place Prefab",
+    );
+
+    assert_eq!(count, 2);
+    let placed = target.current_map.world.voxels.get((10, 0, 10)).unwrap();
+    assert_eq!(placed.rotation, Some(0));
+    assert_eq!(
+        target.current_map.orientations,
+        source_state().current_map.orientations
+    );
+    assert_eq!(target.current_map.entities[0].position, (11.0, 1.0, 10.0));
+    assert!(history.can_undo());
+}
+
+#[test]
+fn file_names_map_to_prefab_names() {
+    assert_eq!(
+        with_prefab_extension(PathBuf::from("pieces/tower")),
+        PathBuf::from("pieces/tower.prefab.ron")
+    );
+    assert_eq!(
+        with_prefab_extension(PathBuf::from("pieces/tower.ron")),
+        PathBuf::from("pieces/tower.prefab.ron")
+    );
+    assert_eq!(
+        with_prefab_extension(PathBuf::from("tower.prefab.ron")),
+        PathBuf::from("tower.prefab.ron")
+    );
+    assert_eq!(prefab_name(Path::new("pieces/tower.prefab.ron")), "tower");
+    assert_eq!(prefab_name(Path::new("gate.ron")), "gate");
+}
@@ -0,0 +1,162 @@
+//! Editor-wide preferences.
+//!
+//! Unlike per-map sessions, preferences apply to every map and live in
+//! `editor_preferences.ron` in the config directory.
+//!
+//! The editor only redraws when there is input or something to animate
+//! unless [`EditorPreferences::continuous_rendering`] is on, so an idle
+//! editor doesn't keep the GPU busy. [`update_render_mode`] applies the
+//! preference and keeps frames coming while keys are held or weather is
+//! falling in the preview.
+//!
+//! [`GestureSensitivity`] scales how far touchpad scrolling, pinching and
+//! rotating move the camera (see [`handle_camera_gestures`]).
+//!
+//! [`handle_camera_gestures`]: crate::editor::camera::handle_camera_gestures
+
+use crate::systems::game::weather::{active_drops, Weather};
+use bevy::prelude::*;
+use bevy::window::RequestRedraw;
+use bevy::winit::{UpdateMode, WinitSettings};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Name of the preferences config file
+const PREFERENCES_FILENAME: &str = "editor_preferences.ron";
+
+/// Longest wait between frames with focus when nothing happens, so
+/// background work (saves, play-test processes) is still noticed.
+const FOCUSED_WAIT: Duration = Duration::from_secs(1);
+
+/// Longest wait between frames without focus.
+const UNFOCUSED_WAIT: Duration = Duration::from_secs(5);
+
+/// Resource holding the editor preferences
+#[derive(Resource, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorPreferences {
+    /// Redraw every frame while focused instead of only on input. Smoother
+    /// camera motion (gamepad, eased camera moves) at the cost of power.
+    pub continuous_rendering: bool,
+    /// How strongly touchpad gestures move the camera
+    pub gestures: GestureSensitivity,
+}
+
+/// Multipliers for how far scroll and touchpad gestures move the camera.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GestureSensitivity {
+    /// Scroll and pinch zoom
+    pub zoom: f32,
+    /// Two-finger panning
+    pub pan: f32,
+    /// Two-finger rotation
+    pub rotate: f32,
+}
+
+impl Default for GestureSensitivity {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            pan: 1.0,
+            rotate: 1.0,
+        }
+    }
+}
+
+impl EditorPreferences {
+    /// Load preferences from the config directory
+    pub fn load() -> Self {
+        let config_path = get_config_path();
+        if !config_path.exists() {
+            return Self::default();
+        }
+        match fs::read_to_string(&config_path) {
+            Ok(contents) => match ron::from_str::<EditorPreferences>(&contents) {
+                Ok(preferences) => {
+                    info!("Loaded editor preferences from {:?}", config_path);
+                    preferences
+                }
+                Err(e) => {
+                    warn!("Failed to parse editor preferences: {}", e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                warn!("Failed to read editor preferences: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Save preferences to the config directory
+    pub fn save(&self) {
+        let config_path = get_config_path();
+        if let Some(parent) = config_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Failed to create config directory: {}", e);
+                return;
+            }
+        }
+        match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => {
+                if let Err(e) = fs::write(&config_path, contents) {
+                    error!("Failed to write editor preferences: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to serialize editor preferences: {}", e),
+        }
+    }
+
+    /// How often the editor should update with these preferences.
+    pub fn winit_settings(&self) -> WinitSettings {
+        let focused_mode = if self.continuous_rendering {
+            UpdateMode::Continuous
+        } else {
+            UpdateMode::reactive(FOCUSED_WAIT)
+        };
+        WinitSettings {
+            focused_mode,
+            unfocused_mode: UpdateMode::reactive_low_power(UNFOCUSED_WAIT),
+        }
+    }
+}
+
+/// Get the path to the preferences config file
+fn get_config_path() -> PathBuf {
+    if let Some(config_dir) = dirs::config_dir() {
+        config_dir.join("adrakestory").join(PREFERENCES_FILENAME)
+    } else {
+        PathBuf::from(PREFERENCES_FILENAME)
+    }
+}
+
+/// Whether the editor should keep drawing frames without new input: held
+/// keys move the camera every frame, and precipitation animates.
+pub fn needs_redraw(keys: &ButtonInput<KeyCode>, weather: &Weather) -> bool {
+    keys.get_pressed().next().is_some() || active_drops(&weather.current) > 0
+}
+
+/// System applying the rendering preference and requesting redraws while
+/// something moves on its own in reactive mode.
+pub fn update_render_mode(
+    preferences: Res<EditorPreferences>,
+    keys: Res<ButtonInput<KeyCode>>,
+    weather: Res<Weather>,
+    mut winit_settings: ResMut<WinitSettings>,
+    mut redraw: MessageWriter<RequestRedraw>,
+) {
+    let settings = preferences.winit_settings();
+    if winit_settings.focused_mode != settings.focused_mode {
+        *winit_settings = settings;
+    }
+
+    if !preferences.continuous_rendering && needs_redraw(&keys, &weather) {
+        redraw.write(RequestRedraw);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+use crate::systems::game::map::format::{Precipitation, WeatherData};
+
+#[test]
+fn rendering_is_reactive_by_default() {
+    let settings = EditorPreferences::default().winit_settings();
+    assert!(matches!(settings.focused_mode, UpdateMode::Reactive { .. }));
+    assert!(matches!(
+        settings.unfocused_mode,
+        UpdateMode::Reactive {
+            react_to_device_events: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn continuous_rendering_only_applies_with_focus() {
+    let preferences = EditorPreferences {
+        continuous_rendering: true,
+        ..Default::default()
+    };
+    let settings = preferences.winit_settings();
+    assert_eq!(settings.focused_mode, UpdateMode::Continuous);
+    assert!(matches!(
+        settings.unfocused_mode,
+        UpdateMode::Reactive { .. }
+    ));
+}
+
+#[test]
+fn held_keys_and_falling_weather_keep_frames_coming() {
+    let mut keys = ButtonInput::<KeyCode>::default();
+    let mut weather = Weather::default();
+    assert!(!needs_redraw(&keys, &weather));
+
+    keys.press(KeyCode::KeyW);
+    assert!(needs_redraw(&keys, &weather));
+    keys.release(KeyCode::KeyW);
+    assert!(!needs_redraw(&keys, &weather));
+
+    weather.current = WeatherData {
+        precipitation: Precipitation::Rain,
+        ..default()
+    };
+    assert!(needs_redraw(&keys, &weather));
+}
+
+#[test]
+fn missing_fields_fall_back_to_defaults() {
+    let preferences: EditorPreferences = ron::from_str("()").unwrap();
+    assert_eq!(preferences, EditorPreferences::default());
+}
+
+#[test]
+fn gesture_sensitivity_defaults_to_one() {
+    let preferences: EditorPreferences =
+        ron::from_str("(continuous_rendering: true, gestures: (pan: 2.0))").unwrap();
+    assert!(preferences.continuous_rendering);
+    assert_eq!(preferences.gestures.pan, 2.0);
+    assert_eq!(preferences.gestures.zoom, 1.0);
+    assert_eq!(preferences.gestures.rotate, 1.0);
+}
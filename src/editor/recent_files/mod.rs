@@ -1,9 +1,20 @@
 //! Recent files management for the map editor.
 //!
-//! Tracks recently opened files and persists them to disk.
+//! Tracks recently opened files and persists them to disk, along with each
+//! map's name, voxel count and modification time. The File menu shows them
+//! with thumbnails (see [`preview`]).
+
+mod preview;
+
+pub use preview::{
+    format_modified, modified_secs, render_thumbnail, MapPreview, RecentFileInfo, THUMBNAIL_SIZE,
+};
 
 use bevy::prelude::*;
+use bevy_egui::egui;
+use preview::RecentPreviews;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -18,12 +29,17 @@ const RECENT_FILES_FILENAME: &str = "recent_files.ron";
 pub struct RecentFiles {
     /// List of recent file paths, most recent first
     pub files: Vec<PathBuf>,
+    /// Details of each file, as of when its preview was last read
+    #[serde(default)]
+    pub details: HashMap<PathBuf, RecentFileInfo>,
+    #[serde(skip)]
+    previews: RecentPreviews,
 }
 
 impl RecentFiles {
     /// Create a new empty recent files list
     pub fn new() -> Self {
-        Self { files: Vec::new() }
+        Self::default()
     }
 
     /// Load recent files from the config directory
@@ -36,6 +52,8 @@ impl RecentFiles {
                         Ok(mut recent) => {
                             // Filter out files that no longer exist
                             recent.files.retain(|p| p.exists());
+                            let files = &recent.files;
+                            recent.details.retain(|p, _| files.contains(p));
                             info!(
                                 "Loaded {} recent files from {:?}",
                                 recent.files.len(),
@@ -104,6 +122,8 @@ impl RecentFiles {
         if self.files.len() > MAX_RECENT_FILES {
             self.files.truncate(MAX_RECENT_FILES);
         }
+        let files = &self.files;
+        self.details.retain(|p, _| files.contains(p));
 
         // Save to disk
         self.save();
@@ -112,6 +132,8 @@ impl RecentFiles {
     /// Clear all recent files
     pub fn clear(&mut self) {
         self.files.clear();
+        self.details.clear();
+        self.previews = RecentPreviews::default();
         self.save();
     }
 
@@ -125,6 +147,49 @@ impl RecentFiles {
         self.files.len()
     }
 
+    /// Details of `path`, if its preview has been read
+    pub fn info(&self, path: &Path) -> Option<&RecentFileInfo> {
+        self.details.get(path)
+    }
+
+    /// Thumbnail of `path`, if it has been read this session
+    pub fn thumbnail(&self, path: &Path) -> Option<&egui::TextureHandle> {
+        self.previews.thumbnails.get(path)
+    }
+
+    /// Pick up previews read in the background, and start reading those of
+    /// files not read yet or changed since. Saves the list when details
+    /// change.
+    pub fn update_previews(&mut self, ctx: &egui::Context) {
+        let mut changed = false;
+        for (path, preview) in self.previews.take_finished() {
+            let Some(preview) = preview else {
+                continue;
+            };
+            let texture = ctx.load_texture(
+                format!("recent_file:{}", path.display()),
+                preview.thumbnail,
+                egui::TextureOptions::NEAREST,
+            );
+            self.previews.thumbnails.insert(path.clone(), texture);
+            if self.details.get(&path) != Some(&preview.info) {
+                self.details.insert(path, preview.info);
+                changed = true;
+            }
+        }
+
+        for path in &self.files {
+            let modified = modified_secs(path);
+            if self.previews.requested.get(path) != Some(&modified) {
+                self.previews.request(path, modified, ctx);
+            }
+        }
+
+        if changed {
+            self.save();
+        }
+    }
+
     /// Get a display name for a file path (filename only)
     pub fn get_display_name(path: &Path) -> String {
         path.file_name()
@@ -173,3 +238,6 @@ pub fn update_recent_on_save(
         recent_files.add(event.path.clone());
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,169 @@
+//! Thumbnails and details of recently opened maps.
+//!
+//! Reading a map can take a while, so previews are built on a background
+//! thread the first time the Recent Files menu shows a file, and again after
+//! the file changes. Thumbnails are a top-down view of each map, shaded by
+//! height, and only kept in memory; the details are saved with the list.
+
+use crate::editor::ui::properties::get_voxel_color;
+use crate::systems::game::map::format::MapData;
+use crate::systems::game::map::loader::MapLoader;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Width and height of a thumbnail in pixels
+pub const THUMBNAIL_SIZE: usize = 48;
+
+/// Thumbnail pixels where no voxel is seen from above
+const BACKGROUND: egui::Color32 = egui::Color32::from_rgb(30, 30, 34);
+
+/// What the Recent Files menu shows about a map besides its thumbnail
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentFileInfo {
+    /// Name from the map's metadata
+    pub name: String,
+    pub voxel_count: usize,
+    /// When the file was last modified, in seconds since the Unix epoch
+    pub modified: u64,
+}
+
+/// Details and thumbnail of one map file
+pub struct MapPreview {
+    pub info: RecentFileInfo,
+    pub thumbnail: egui::ColorImage,
+}
+
+impl MapPreview {
+    /// Read the map at `path`, or `None` if it can't be read.
+    pub fn read(path: &Path) -> Option<Self> {
+        let modified = modified_secs(path)?;
+        let map = MapLoader::read_unvalidated(path).ok()?;
+        Some(Self {
+            info: RecentFileInfo {
+                name: map.metadata.name.clone(),
+                voxel_count: map.world.voxels.len(),
+                modified,
+            },
+            thumbnail: render_thumbnail(&map),
+        })
+    }
+}
+
+/// When the file at `path` was last modified, in seconds since the Unix epoch.
+pub fn modified_secs(path: &Path) -> Option<u64> {
+    let modified = path.metadata().and_then(|meta| meta.modified()).ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|age| age.as_secs())
+}
+
+/// Coarse "how long ago" text for a modification time.
+pub fn format_modified(modified: u64, now: SystemTime) -> String {
+    let now = now
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0);
+    let secs = now.saturating_sub(modified);
+    if secs < 60 {
+        "just now".to_string()
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86400)
+    }
+}
+
+/// Top-down view of `map` fitted into a [`THUMBNAIL_SIZE`] square.
+///
+/// Each pixel shows the highest voxel of the column under it, brighter the
+/// higher it is.
+pub fn render_thumbnail(map: &MapData) -> egui::ColorImage {
+    let mut pixels = vec![BACKGROUND; THUMBNAIL_SIZE * THUMBNAIL_SIZE];
+    let voxels = &map.world.voxels;
+    let Some(first) = voxels.first() else {
+        return egui::ColorImage::new([THUMBNAIL_SIZE; 2], pixels);
+    };
+
+    let (mut min, mut max) = (first.pos, first.pos);
+    for voxel in voxels.iter() {
+        let (x, y, z) = voxel.pos;
+        min = (min.0.min(x), min.1.min(y), min.2.min(z));
+        max = (max.0.max(x), max.1.max(y), max.2.max(z));
+    }
+    // Highest voxel of each column
+    let mut tops = HashMap::new();
+    for voxel in voxels.iter() {
+        let (x, y, z) = voxel.pos;
+        let top = tops.entry((x, z)).or_insert((y, voxel.voxel_type));
+        if y > top.0 {
+            *top = (y, voxel.voxel_type);
+        }
+    }
+
+    // Keep the aspect ratio: the longer side fills the thumbnail
+    let span = (max.0 - min.0).max(max.2 - min.2) + 1;
+    let voxels_per_pixel = span as f32 / THUMBNAIL_SIZE as f32;
+    let height_span = (max.1 - min.1).max(1) as f32;
+    for (index, pixel) in pixels.iter_mut().enumerate() {
+        let column = |offset: usize| (offset as f32 * voxels_per_pixel) as i32;
+        let x = min.0 + column(index % THUMBNAIL_SIZE);
+        let z = min.2 + column(index / THUMBNAIL_SIZE);
+        let Some(&(y, voxel_type)) = tops.get(&(x, z)) else {
+            continue;
+        };
+        let brightness = 0.45 + 0.55 * (y - min.1) as f32 / height_span;
+        let color = get_voxel_color(&voxel_type);
+        *pixel = egui::Color32::from_rgb(
+            (color.r() as f32 * brightness) as u8,
+            (color.g() as f32 * brightness) as u8,
+            (color.b() as f32 * brightness) as u8,
+        );
+    }
+    egui::ColorImage::new([THUMBNAIL_SIZE; 2], pixels)
+}
+
+/// Previews read in the background, by file, `None` where a file couldn't be read
+type FinishedPreviews = Vec<(PathBuf, Option<MapPreview>)>;
+
+/// Previews loaded this session, and the ones still being read
+#[derive(Default)]
+pub(super) struct RecentPreviews {
+    /// Thumbnail of each file, uploaded to egui
+    pub thumbnails: HashMap<PathBuf, egui::TextureHandle>,
+    /// Modification time of each file when its preview was last requested
+    pub requested: HashMap<PathBuf, Option<u64>>,
+    /// Previews read by background threads, waiting to be picked up
+    pub finished: Arc<Mutex<FinishedPreviews>>,
+}
+
+impl RecentPreviews {
+    /// Read the preview of `path` on a background thread.
+    pub fn request(&mut self, path: &Path, modified: Option<u64>, ctx: &egui::Context) {
+        self.requested.insert(path.to_path_buf(), modified);
+        let finished = Arc::clone(&self.finished);
+        let path = path.to_path_buf();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let preview = MapPreview::read(&path);
+            if let Ok(mut finished) = finished.lock() {
+                finished.push((path, preview));
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// Previews read since the last call.
+    pub fn take_finished(&mut self) -> FinishedPreviews {
+        self.finished
+            .lock()
+            .map(|mut finished| std::mem::take(&mut *finished))
+            .unwrap_or_default()
+    }
+}
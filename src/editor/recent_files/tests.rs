@@ -0,0 +1,78 @@
+use super::*;
+use crate::systems::game::map::format::{MapData, VoxelData, VoxelType};
+use std::time::{Duration, UNIX_EPOCH};
+
+fn pixel(image: &egui::ColorImage, x: usize, y: usize) -> egui::Color32 {
+    image.pixels[y * THUMBNAIL_SIZE + x]
+}
+
+#[test]
+fn test_thumbnail_of_empty_map_is_blank() {
+    let image = render_thumbnail(&MapData::empty_map());
+    assert_eq!(image.size, [THUMBNAIL_SIZE; 2]);
+    assert!(image.pixels.iter().all(|&p| p == image.pixels[0]));
+}
+
+#[test]
+fn test_thumbnail_shows_highest_voxel_brighter() {
+    let mut map = MapData::empty_map();
+    map.world
+        .voxels
+        .push(VoxelData::new((0, 0, 0), VoxelType::Stone, None));
+    map.world
+        .voxels
+        .push(VoxelData::new((1, 0, 0), VoxelType::Stone, None));
+    map.world
+        .voxels
+        .push(VoxelData::new((1, 3, 0), VoxelType::Stone, None));
+    map.world
+        .voxels
+        .push(VoxelData::new((0, 0, 1), VoxelType::Grass, None));
+    map.world
+        .voxels
+        .push(VoxelData::new((1, 0, 1), VoxelType::Grass, None));
+
+    let image = render_thumbnail(&map);
+    let low = pixel(&image, 0, 0);
+    let high = pixel(&image, THUMBNAIL_SIZE - 1, 0);
+    assert!(high.r() > low.r());
+    // Grass fills the bottom half of the square footprint
+    let grass = pixel(&image, 0, THUMBNAIL_SIZE - 1);
+    assert!(grass.g() > grass.r());
+}
+
+#[test]
+fn test_format_modified() {
+    let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+    assert_eq!(format_modified(1_000_000 - 5, now), "just now");
+    assert_eq!(format_modified(1_000_000 - 600, now), "10m ago");
+    assert_eq!(format_modified(1_000_000 - 7200, now), "2h ago");
+    assert_eq!(format_modified(1_000_000 - 3 * 86400, now), "3d ago");
+    assert_eq!(format_modified(2_000_000, now), "just now");
+}
+
+#[test]
+fn test_preview_reads_map_details() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("preview.ron");
+    let mut map = MapData::empty_map();
+    map.metadata.name = "Preview".to_string();
+    map.world
+        .voxels
+        .push(VoxelData::new((0, 0, 0), VoxelType::Dirt, None));
+    fs::write(&path, ron::to_string(&map).unwrap()).unwrap();
+
+    let preview = MapPreview::read(&path).unwrap();
+    assert_eq!(preview.info.name, "Preview");
+    assert_eq!(preview.info.voxel_count, 1);
+    assert_eq!(Some(preview.info.modified), modified_secs(&path));
+
+    assert!(MapPreview::read(&dir.path().join("missing.ron")).is_none());
+}
+
+#[test]
+fn test_old_config_without_details_still_loads() {
+    let recent: RecentFiles = ron::from_str("(files: [\"a.ron\"])").unwrap();
+    assert_eq!(recent.files, vec![PathBuf::from("a.ron")]);
+    assert!(recent.details.is_empty());
+}
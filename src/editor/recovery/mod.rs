@@ -0,0 +1,153 @@
+//! Crash recovery for the map editor.
+//!
+//! [`track_unsaved_map`] keeps the crash reporter's
+//! [`CrashContext`](crate::diagnostics::crash_report::CrashContext) up to
+//! date with the active tab's path and, while it has unsaved changes, a copy
+//! of its map, so a crash writes them to a recovery file. Other tabs are not
+//! covered.
+//!
+//! After a crash, the next launch shows [`render_recovery_dialog`]: it names
+//! the crash report and offers to restore the recovery file into the editor
+//! as unsaved changes to the original map. Reports and recovery files stay
+//! in `crashes/`, so a recovery file can also be opened later with
+//! File → Open.
+
+use crate::diagnostics::crash_report::{self, CrashNotice, SNAPSHOT_INTERVAL};
+use crate::editor::state::EditorState;
+use crate::editor::ui::dialogs::MapDataChangedEvent;
+use crate::systems::game::map::loader::MapLoader;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::path::PathBuf;
+
+/// The crash left by the previous run, until the user has seen it.
+#[derive(Resource, Default)]
+pub struct PendingRecovery {
+    pub notice: Option<CrashNotice>,
+    /// Why the recovery file could not be restored, if it could not
+    pub error: Option<String>,
+}
+
+/// What [`track_unsaved_map`] last handed to the crash reporter.
+#[derive(Default)]
+pub struct RecoveryTracker {
+    path: Option<PathBuf>,
+    /// Revision of the map copy, if one is held
+    snapshot: Option<u64>,
+    /// Seconds since the last copy
+    since_snapshot: f32,
+}
+
+/// System to keep the crash reporter's copy of the open map current.
+pub fn track_unsaved_map(
+    time: Res<Time<Real>>,
+    editor_state: Res<EditorState>,
+    mut tracker: Local<RecoveryTracker>,
+) {
+    tracker.since_snapshot += time.delta_secs();
+
+    if editor_state.file_path != tracker.path {
+        tracker.path = editor_state.file_path.clone();
+        crash_report::set_map_path(tracker.path.clone());
+    }
+
+    if !editor_state.is_modified {
+        if tracker.snapshot.take().is_some() {
+            crash_report::set_unsaved_map(None);
+        }
+        return;
+    }
+
+    // Copying a large map every frame of a brush stroke would stall the
+    // editor, so copies are spaced out
+    if tracker.snapshot != Some(editor_state.revision)
+        && (tracker.snapshot.is_none() || tracker.since_snapshot >= SNAPSHOT_INTERVAL)
+    {
+        crash_report::set_unsaved_map(Some(editor_state.current_map.clone()));
+        tracker.snapshot = Some(editor_state.revision);
+        tracker.since_snapshot = 0.0;
+    }
+}
+
+/// System to show the crash notice from the previous run and restore its
+/// recovery file on request.
+pub fn render_recovery_dialog(
+    mut contexts: EguiContexts,
+    mut pending: ResMut<PendingRecovery>,
+    mut editor_state: ResMut<EditorState>,
+    mut map_changed_events: MessageWriter<MapDataChangedEvent>,
+) {
+    let Some(notice) = pending.notice.clone() else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut restore = false;
+    let mut dismiss = false;
+
+    egui::Window::new("Editor Crashed")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("The map editor closed unexpectedly last time.");
+            ui.label(format!("Crash report: {}", notice.report.display()));
+            match &notice.recovery {
+                Some(recovery) => {
+                    let map = notice
+                        .map_path
+                        .as_ref()
+                        .map_or("an unsaved map".to_string(), |p| p.display().to_string());
+                    ui.label(format!(
+                        "Unsaved changes to {} were saved to {}.",
+                        map,
+                        recovery.display()
+                    ));
+                    ui.label("Restore them now, or open the recovery file later with File → Open.");
+                }
+                None => {
+                    ui.label("There were no unsaved changes to recover.");
+                }
+            }
+            if let Some(error) = &pending.error {
+                ui.colored_label(egui::Color32::from_rgb(220, 110, 110), error);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if notice.recovery.is_some() && ui.button("Restore").clicked() {
+                    restore = true;
+                }
+                if ui.button("Dismiss").clicked() {
+                    dismiss = true;
+                }
+            });
+        });
+
+    if restore {
+        let Some(recovery) = &notice.recovery else {
+            return;
+        };
+        match MapLoader::read_unvalidated(recovery) {
+            Ok(map) => {
+                info!("Restored unsaved changes from {:?}", recovery);
+                editor_state.current_map = map;
+                editor_state.file_path = notice.map_path.clone();
+                editor_state.clear_selections();
+                editor_state.mark_modified();
+                editor_state.mark_needs_render();
+                map_changed_events.write(MapDataChangedEvent);
+                pending.notice = None;
+            }
+            Err(e) => {
+                error!("Failed to restore {:?}: {}", recovery, e);
+                pending.error = Some(format!("Failed to restore: {}", e));
+            }
+        }
+    } else if dismiss {
+        pending.notice = None;
+    }
+}
@@ -11,30 +11,46 @@
 //! - **Tier 5: Greedy Meshing** - Merges adjacent same-color faces into larger quads
 //! - **Frustum Culling** - Chunks outside camera view are not rendered
 //!
+//! Most edits re-mesh the whole map. Edits that repeat every frame (sculpt
+//! strokes) mark only the voxels they touched, and just the chunks around
+//! those voxels are rebuilt.
+//!
 //! Note: LOD (Tier 6) is disabled for the editor since full detail is needed when editing.
 
+use crate::editor::billboards::EntityIconAssets;
+use crate::editor::cursor::VoxelIndex;
 use crate::editor::state::EditorState;
 use crate::editor::tools::UpdateSelectionHighlights;
+use crate::systems::accessibility::ColorPalette;
 use crate::systems::game::map::format::{
-    apply_orientation_matrix, world_dir_to_local, EntityType, SubVoxelPattern,
+    EntityType, MapData, SubVoxelPattern, VoxelData, VoxelType,
 };
 use crate::systems::game::map::spawner::{
-    ChunkMeshBuilder, Face, GreedyMesher, OccupancyGrid, VoxelMaterialPalette, CHUNK_SIZE,
-    SUB_VOXEL_COUNT, SUB_VOXEL_SIZE,
+    face_color_for, sub_voxel_color_for, voxel_type_material, ChunkMeshBuilder, Face, GreedyMesher,
+    OccupancyGrid, VoxelMaterialPalette, CHUNK_SIZE, SUB_VOXEL_COUNT, SUB_VOXEL_SIZE,
 };
+use crate::systems::game::occlusion::{OcclusionExtension, OcclusionMaterial};
+use crate::systems::game::speedrun::{DEFAULT_GOAL_RADIUS, GOAL_COLOR};
+use crate::systems::game::teleporters::{DEFAULT_TELEPORTER_RADIUS, TELEPORTER_COLOR};
 use bevy::camera::primitives::Aabb;
 use bevy::math::Vec3A;
+use bevy::pbr::ExtendedMaterial;
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 
-/// (voxel_x, voxel_y, voxel_z, sub_x, sub_y, sub_z, world_pos, color_index, color)
-type SubVoxelEntry = (i32, i32, i32, i32, i32, i32, Vec3, usize, Color);
+/// (voxel_x, voxel_y, voxel_z, sub_x, sub_y, sub_z, world_pos, color_index, color, voxel_type)
+type SubVoxelEntry = (i32, i32, i32, i32, i32, i32, Vec3, usize, Color, VoxelType);
 
 /// Marker component for chunk entities spawned by the editor
 #[derive(Component)]
 pub struct EditorChunk {
     /// The chunk position in chunk coordinates
     pub chunk_pos: IVec3,
+    /// Quads in the chunk's mesh after greedy meshing
+    pub quads: usize,
+    /// Sub-voxels inside the chunk
+    pub sub_voxels: usize,
 }
 
 /// Marker component for entity indicators spawned by the editor
@@ -55,14 +71,78 @@ pub struct MapRenderState {
     pub last_entity_count: usize,
 }
 
-/// Resource to cache the chunk material (uses vertex colors)
+/// Resource to cache the chunk material (uses vertex colors).
+///
+/// It's the game's chunk material with occlusion turned off, so the map's
+/// `edges` look the same as in game.
 #[derive(Resource)]
-pub struct EditorChunkMaterial(pub Handle<StandardMaterial>);
+pub struct EditorChunkMaterial(pub Handle<OcclusionMaterial>);
+
+/// Materials for voxel types with a [`voxel_type_material`], created the
+/// first time each type is rendered.
+///
+/// Those voxels get chunk meshes of their own with one of these, so lava and
+/// glowstone glow, and glass is see-through, roughly as they do in game.
+#[derive(Resource, Default)]
+pub struct EditorTypeMaterials(pub HashMap<VoxelType, Handle<StandardMaterial>>);
+
+/// Hazard voxels with an exposed top face, refreshed on every re-render.
+///
+/// [`draw_hazard_badges`] marks each one so water and lava stand out while
+/// editing even where their tint is hard to read.
+#[derive(Resource, Default)]
+pub struct HazardBadges(pub Vec<(IVec3, VoxelType)>);
+
+impl HazardBadges {
+    /// Collect hazard voxels in `map` that have no voxel directly above them.
+    pub fn from_map(map: &MapData) -> Self {
+        Self(
+            map.world
+                .voxels
+                .iter()
+                .filter(|v| v.voxel_type.is_hazard())
+                .filter(|v| !map.world.voxels.contains((v.pos.0, v.pos.1 + 1, v.pos.2)))
+                .map(|v| (IVec3::new(v.pos.0, v.pos.1, v.pos.2), v.voxel_type))
+                .collect(),
+        )
+    }
+}
 
 /// Event sent when the map should be re-rendered
 #[derive(Message)]
 pub struct RenderMapEvent;
 
+/// Event sent when only some chunks need re-meshing
+#[derive(Message)]
+pub struct RenderChunksEvent(pub HashSet<IVec3>);
+
+/// Chunks holding sub-voxels of the voxel at `pos` or of any voxel within
+/// `margin` voxels of it. A voxel can straddle a chunk boundary, so this may
+/// be more than one chunk even with no margin.
+pub fn chunks_near_voxel(pos: (i32, i32, i32), margin: i32) -> impl Iterator<Item = IVec3> {
+    // Sub-voxel centers lie strictly inside the voxel, so inset the extent
+    // slightly to avoid counting a chunk the voxel only touches
+    let extent = margin as f32 + 0.5 - SUB_VOXEL_SIZE * 0.5;
+    let range = move |p: i32| {
+        let lo = ((p as f32 - extent) / CHUNK_SIZE as f32).floor() as i32;
+        let hi = ((p as f32 + extent) / CHUNK_SIZE as f32).floor() as i32;
+        lo..=hi
+    };
+    range(pos.0).flat_map(move |x| {
+        range(pos.1).flat_map(move |y| range(pos.2).map(move |z| IVec3::new(x, y, z)))
+    })
+}
+
+/// Chunks whose meshes change when the voxels at `positions` change: their
+/// own chunks and those of their neighbours, whose faces and fence rails
+/// depend on them.
+pub fn affected_chunks(positions: impl IntoIterator<Item = (i32, i32, i32)>) -> HashSet<IVec3> {
+    positions
+        .into_iter()
+        .flat_map(|pos| chunks_near_voxel(pos, 1))
+        .collect()
+}
+
 /// System to detect when the map has changed and needs re-rendering.
 ///
 /// Checks `EditorState::render_dirty` (set by every mutation path via
@@ -72,32 +152,33 @@ pub struct RenderMapEvent;
 ///
 /// The flag is cleared immediately after the event is emitted so that the
 /// re-render fires exactly once per mutation batch, not every frame.
+///
+/// Voxels marked with `mark_voxels_modified` re-mesh just their chunks via
+/// [`RenderChunksEvent`], unless a full re-render is pending anyway.
+///
+/// The cursor's [`VoxelIndex`] follows the same changes: it is rebuilt with a
+/// full re-render and refreshed at the marked voxels otherwise.
 pub fn detect_map_changes(
     mut editor_state: ResMut<EditorState>,
     mut render_state: ResMut<MapRenderState>,
     mut render_events: MessageWriter<RenderMapEvent>,
+    mut chunk_events: MessageWriter<RenderChunksEvent>,
+    mut voxel_index: ResMut<VoxelIndex>,
 ) {
     if editor_state.render_dirty {
         editor_state.render_dirty = false;
+        editor_state.dirty_voxels.clear();
+        voxel_index.rebuild(&editor_state.current_map);
         render_state.needs_render = true;
         render_events.write(RenderMapEvent);
         info!("Map changed, triggering re-render");
+    } else if !editor_state.dirty_voxels.is_empty() {
+        let dirty = std::mem::take(&mut editor_state.dirty_voxels);
+        voxel_index.refresh(&editor_state.current_map, dirty.iter().copied());
+        chunk_events.write(RenderChunksEvent(affected_chunks(dirty)));
     }
 }
 
-/// Calculate color for a sub-voxel based on its position.
-/// Uses the same hash-based coloring as the material palette for consistency.
-#[inline]
-fn get_sub_voxel_color(x: i32, y: i32, z: i32, sub_x: i32, sub_y: i32, sub_z: i32) -> Color {
-    let index = VoxelMaterialPalette::get_material_index(x, y, z, sub_x, sub_y, sub_z);
-    let t = index as f32 / VoxelMaterialPalette::PALETTE_SIZE as f32;
-    Color::srgb(
-        0.2 + t * 0.6,
-        0.3 + ((t * std::f32::consts::PI * 2.0).sin() * 0.5 + 0.5) * 0.4,
-        0.4 + ((t * std::f32::consts::PI * 3.0).cos() * 0.5 + 0.5) * 0.4,
-    )
-}
-
 /// Calculate world position for a sub-voxel.
 #[inline]
 fn calculate_sub_voxel_pos(x: i32, y: i32, z: i32, sub_x: i32, sub_y: i32, sub_z: i32) -> Vec3 {
@@ -115,35 +196,61 @@ fn calculate_sub_voxel_pos(x: i32, y: i32, z: i32, sub_x: i32, sub_y: i32, sub_z
 /// - Chunk-based meshing (Tier 3)
 /// - Hidden face culling (Tier 4)
 /// - Greedy meshing (Tier 5)
+///
+/// A [`RenderMapEvent`] rebuilds every chunk; [`RenderChunksEvent`]s alone
+/// rebuild only the listed chunks.
+#[allow(clippy::too_many_arguments)]
 pub fn render_map_system(
     mut commands: Commands,
     mut render_events: MessageReader<RenderMapEvent>,
+    mut chunk_events: MessageReader<RenderChunksEvent>,
     editor_state: Res<EditorState>,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    existing_chunks: Query<Entity, With<EditorChunk>>,
+    mut materials: ResMut<Assets<OcclusionMaterial>>,
+    mut standard_materials: ResMut<Assets<StandardMaterial>>,
+    existing_chunks: Query<(Entity, &EditorChunk)>,
     chunk_material_res: Option<Res<EditorChunkMaterial>>,
+    mut type_materials: ResMut<EditorTypeMaterials>,
+    mut hazard_badges: ResMut<HazardBadges>,
 ) {
+    let full_render = render_events.read().count() > 0;
+    let dirty_chunks: HashSet<IVec3> = chunk_events
+        .read()
+        .flat_map(|event| event.0.iter().copied())
+        .collect();
+
     // Only render if we received an event
-    if render_events.read().count() == 0 {
+    if !full_render && dirty_chunks.is_empty() {
         return;
     }
+    // Chunks to rebuild, or `None` for all of them
+    let only: Option<&HashSet<IVec3>> = (!full_render).then_some(&dirty_chunks);
+
+    *hazard_badges = HazardBadges::from_map(&editor_state.current_map);
 
     let total_voxels = editor_state.current_map.world.voxels.len();
-    info!("Rendering map with {} voxels (optimized)", total_voxels);
+    match only {
+        None => info!("Rendering map with {} voxels (optimized)", total_voxels),
+        Some(chunks) => debug!("Re-meshing {} chunks", chunks.len()),
+    }
 
-    // Despawn all existing editor chunks
-    for entity in existing_chunks.iter() {
-        commands.entity(entity).despawn();
+    // Despawn the editor chunks being rebuilt
+    for (entity, chunk) in existing_chunks.iter() {
+        if only.is_none_or(|chunks| chunks.contains(&chunk.chunk_pos)) {
+            commands.entity(entity).despawn();
+        }
     }
 
     // Get or create chunk material (uses vertex colors)
     let chunk_material = if let Some(ref m) = chunk_material_res {
         m.0.clone()
     } else {
-        let new_material = materials.add(StandardMaterial {
-            base_color: Color::WHITE,
-            ..default()
+        let new_material = materials.add(ExtendedMaterial {
+            base: StandardMaterial {
+                base_color: Color::WHITE,
+                ..default()
+            },
+            extension: OcclusionExtension::edges_only(&editor_state.current_map.edges),
         });
         commands.insert_resource(EditorChunkMaterial(new_material.clone()));
         new_material
@@ -156,112 +263,121 @@ pub fn render_map_system(
     }
 
     // ========== TIER 4: Build Occupancy Grid for Hidden Face Culling ==========
+    // Every sub-voxel hides transparent faces, only opaque ones hide opaque faces
     let mut occupancy = OccupancyGrid::new();
+    let mut opaque = OccupancyGrid::new();
 
     // Collect all sub-voxel data for subsequent passes
     let mut all_sub_voxels: Vec<SubVoxelEntry> = Vec::new();
 
-    // Build a set of fence positions for neighbor lookups
-    let fence_positions: std::collections::HashSet<(i32, i32, i32)> = editor_state
+    // Voxels that can affect the rebuilt chunks: those inside them and their
+    // neighbours (for face culling and fence connections)
+    let voxels: Vec<&VoxelData> = editor_state
         .current_map
         .world
         .voxels
+        .iter()
+        .filter(|v| {
+            only.is_none_or(|chunks| chunks_near_voxel(v.pos, 1).any(|c| chunks.contains(&c)))
+        })
+        .collect();
+
+    // Build a set of fence positions for neighbor lookups
+    let fence_positions: std::collections::HashSet<(i32, i32, i32)> = voxels
         .iter()
         .filter(|v| v.pattern.is_some_and(|p| p.is_fence()))
         .map(|v| v.pos)
         .collect();
 
-    for voxel_data in &editor_state.current_map.world.voxels {
+    for voxel_data in voxels {
         let (x, y, z) = voxel_data.pos;
-        let pattern = voxel_data.pattern.unwrap_or(SubVoxelPattern::Full);
-
-        // For fence patterns, check neighbors and generate context-aware geometry
-        let geometry = if pattern.is_fence() {
-            // Look up this voxel's orientation once; used for both neighbour mapping and geometry.
-            let orientation = voxel_data
-                .rotation
-                .and_then(|i| editor_state.current_map.orientations.get(i));
-
-            // World-axis neighbour directions paired with their neighbour positions.
-            // Includes Y neighbours so that rotated fences (e.g. oriented as wall panels)
-            // can connect to fences stacked above or below them.
-            let world_dirs: [([i32; 3], (i32, i32, i32)); 6] = [
-                ([-1, 0, 0], (x - 1, y, z)), // world −X
-                ([1, 0, 0], (x + 1, y, z)),  // world +X
-                ([0, 0, -1], (x, y, z - 1)), // world −Z
-                ([0, 0, 1], (x, y, z + 1)),  // world +Z
-                ([0, -1, 0], (x, y - 1, z)), // world −Y
-                ([0, 1, 0], (x, y + 1, z)),  // world +Y
-            ];
-
-            // Map each world direction into the fence's local frame (Mᵀ × d).
-            // fence_geometry_with_neighbors expects (neg_x, pos_x, neg_z, pos_z) in LOCAL space.
-            let mut local_neg_x = false;
-            let mut local_pos_x = false;
-            let mut local_neg_z = false;
-            let mut local_pos_z = false;
-
-            for (world_dir, neighbor_pos) in &world_dirs {
-                if fence_positions.contains(neighbor_pos) {
-                    match world_dir_to_local(orientation, *world_dir) {
-                        [-1, 0, 0] => local_neg_x = true,
-                        [1, 0, 0] => local_pos_x = true,
-                        [0, 0, -1] => local_neg_z = true,
-                        [0, 0, 1] => local_pos_z = true,
-                        _ => {} // diagonal or unexpected direction, ignore
-                    }
-                }
+        // Glowing and glass types get a material of their own, and are
+        // meshed apart from the rest of their chunk
+        if let Entry::Vacant(entry) = type_materials.0.entry(voxel_data.voxel_type) {
+            if let Some(material) = voxel_type_material(voxel_data.voxel_type) {
+                entry.insert(standard_materials.add(material));
             }
+        }
+        let pattern = voxel_data.pattern.unwrap_or(SubVoxelPattern::Full);
 
-            let fence_geo = pattern.fence_geometry_with_neighbors((
-                local_neg_x,
-                local_pos_x,
-                local_neg_z,
-                local_pos_z,
-            ));
-            // Rotate the locally-correct geometry into world space.
-            if let Some(matrix) = orientation {
-                apply_orientation_matrix(fence_geo, matrix)
-            } else {
-                fence_geo
-            }
-        } else {
-            let orientation = voxel_data
-                .rotation
-                .and_then(|i| editor_state.current_map.orientations.get(i));
-            pattern.geometry_with_rotation(orientation)
-        };
+        // Fences get rails towards neighbouring fences
+        let orientation = voxel_data
+            .rotation
+            .and_then(|i| editor_state.current_map.orientations.get(i));
+        let geometry = pattern.connected_geometry(voxel_data.pos, orientation, |pos| {
+            fence_positions.contains(&pos)
+        });
 
         for (sub_x, sub_y, sub_z) in geometry.occupied_positions() {
-            // Add to occupancy grid for neighbor lookups
+            // Add to occupancy grids for neighbor lookups
             occupancy.insert(x, y, z, sub_x, sub_y, sub_z);
+            if !voxel_data.voxel_type.is_transparent() {
+                opaque.insert(x, y, z, sub_x, sub_y, sub_z);
+            }
 
             let world_pos = calculate_sub_voxel_pos(x, y, z, sub_x, sub_y, sub_z);
             let color_index =
                 VoxelMaterialPalette::get_material_index(x, y, z, sub_x, sub_y, sub_z);
-            let color = get_sub_voxel_color(x, y, z, sub_x, sub_y, sub_z);
-            all_sub_voxels.push((x, y, z, sub_x, sub_y, sub_z, world_pos, color_index, color));
+            // The editor always shows the standard palette
+            let color = sub_voxel_color_for(
+                ColorPalette::Standard,
+                voxel_data.voxel_type,
+                x,
+                y,
+                z,
+                sub_x,
+                sub_y,
+                sub_z,
+            );
+            all_sub_voxels.push((
+                x,
+                y,
+                z,
+                sub_x,
+                sub_y,
+                sub_z,
+                world_pos,
+                color_index,
+                color,
+                voxel_data.voxel_type,
+            ));
         }
     }
 
     // ========== TIER 3 & 5: Chunk-Based Meshing with Greedy Meshing ==========
-    // Group visible faces into per-chunk greedy meshers
-    let mut chunk_meshers: HashMap<IVec3, GreedyMesher> = HashMap::new();
+    // Group visible faces into per-chunk greedy meshers, with a separate
+    // mesher per glowing or glass voxel type
+    let mut chunk_meshers: HashMap<(IVec3, Option<VoxelType>), GreedyMesher> = HashMap::new();
+    let mut chunk_sub_voxels: HashMap<(IVec3, Option<VoxelType>), usize> = HashMap::new();
 
-    for (x, y, z, sub_x, sub_y, sub_z, world_pos, color_index, color) in all_sub_voxels {
+    for (x, y, z, sub_x, sub_y, sub_z, world_pos, color_index, color, voxel_type) in all_sub_voxels
+    {
         // Determine which chunk this sub-voxel belongs to
         let chunk_pos = IVec3::new(
             (world_pos.x / CHUNK_SIZE as f32).floor() as i32,
             (world_pos.y / CHUNK_SIZE as f32).floor() as i32,
             (world_pos.z / CHUNK_SIZE as f32).floor() as i32,
         );
+        if only.is_some_and(|chunks| !chunks.contains(&chunk_pos)) {
+            continue;
+        }
 
         // Global sub-voxel coordinates for the greedy mesher
         let global_x = x * SUB_VOXEL_COUNT + sub_x;
         let global_y = y * SUB_VOXEL_COUNT + sub_y;
         let global_z = z * SUB_VOXEL_COUNT + sub_z;
 
-        let mesher = chunk_meshers.entry(chunk_pos).or_default();
+        let own_mesh = type_materials
+            .0
+            .contains_key(&voxel_type)
+            .then_some(voxel_type);
+        let mesher = chunk_meshers.entry((chunk_pos, own_mesh)).or_default();
+        *chunk_sub_voxels.entry((chunk_pos, own_mesh)).or_default() += 1;
+        let hiding = if voxel_type.is_transparent() {
+            &occupancy
+        } else {
+            &opaque
+        };
 
         // TIER 4: Check each face and add visible ones to the mesher
         let faces = [
@@ -273,7 +389,9 @@ pub fn render_map_system(
             Face::NegZ,
         ];
         for face in faces {
-            if !occupancy.has_neighbor(x, y, z, sub_x, sub_y, sub_z, face) {
+            if !hiding.has_neighbor(x, y, z, sub_x, sub_y, sub_z, face) {
+                let (color_index, color) =
+                    face_color_for(ColorPalette::Standard, voxel_type, face, color_index, color);
                 mesher.add_face(global_x, global_y, global_z, face, color_index, color);
             }
         }
@@ -283,7 +401,7 @@ pub fn render_map_system(
     let total_chunks = chunk_meshers.len();
     let mut total_quads = 0usize;
 
-    for (chunk_pos, mesher) in chunk_meshers {
+    for ((chunk_pos, own_mesh), mesher) in chunk_meshers {
         // Build full-detail mesh
         let mut builder = ChunkMeshBuilder::default();
         mesher.build_into(&mut builder);
@@ -293,7 +411,8 @@ pub fn render_map_system(
         }
 
         // Count quads for stats
-        total_quads += builder.quad_count();
+        let quads = builder.quad_count();
+        total_quads += quads;
 
         // Create mesh and spawn chunk entity
         let mesh = meshes.add(builder.build());
@@ -308,11 +427,17 @@ pub fn render_map_system(
         let half_extent = Vec3::splat(CHUNK_SIZE as f32 / 2.0);
 
         // Spawn chunk with explicit AABB for proper frustum culling
-        commands.spawn((
+        let mut chunk = commands.spawn((
             Mesh3d(mesh),
-            MeshMaterial3d(chunk_material.clone()),
             Transform::default(),
-            EditorChunk { chunk_pos },
+            EditorChunk {
+                chunk_pos,
+                quads,
+                sub_voxels: chunk_sub_voxels
+                    .get(&(chunk_pos, own_mesh))
+                    .copied()
+                    .unwrap_or(0),
+            },
             // Explicit AABB enables Bevy's automatic frustum culling
             Aabb {
                 center: Vec3A::from(chunk_center),
@@ -321,19 +446,165 @@ pub fn render_map_system(
             // Ensure visibility component is present for culling to work
             Visibility::default(),
         ));
+        let type_material = own_mesh.and_then(|voxel_type| type_materials.0.get(&voxel_type));
+        match type_material {
+            Some(material) => {
+                chunk.insert(MeshMaterial3d(material.clone()));
+            }
+            None => {
+                chunk.insert(MeshMaterial3d(chunk_material.clone()));
+            }
+        }
     }
 
-    info!(
-        "Map rendering complete: {} chunks, {} quads (greedy meshing enabled)",
-        total_chunks, total_quads
-    );
+    if only.is_none() {
+        info!(
+            "Map rendering complete: {} chunks, {} quads (greedy meshing enabled)",
+            total_chunks, total_quads
+        );
+    }
 }
 
 /// Event sent when entities should be re-rendered
 #[derive(Message)]
 pub struct RenderEntitiesEvent;
 
-/// System to render entity markers in the viewport
+/// Draw a warning diamond floating above each exposed hazard voxel.
+pub fn draw_hazard_badges(badges: Res<HazardBadges>, mut gizmos: Gizmos) {
+    const SIZE: f32 = 0.18;
+    for (pos, voxel_type) in &badges.0 {
+        let color = voxel_type.tint().unwrap_or(Color::srgb(1.0, 0.8, 0.0));
+        let center = pos.as_vec3() + Vec3::Y * 0.8;
+        let corners = [
+            center + Vec3::Y * SIZE,
+            center + Vec3::X * SIZE,
+            center - Vec3::Y * SIZE,
+            center - Vec3::X * SIZE,
+        ];
+        for i in 0..corners.len() {
+            gizmos.line(corners[i], corners[(i + 1) % corners.len()], color);
+        }
+        // Exclamation mark inside the diamond
+        gizmos.line(center + Vec3::Y * SIZE * 0.6, center, Color::WHITE);
+        gizmos.line(
+            center - Vec3::Y * SIZE * 0.25,
+            center - Vec3::Y * SIZE * 0.4,
+            Color::WHITE,
+        );
+    }
+}
+
+/// Outline the voxels each door hides when it opens, with a line from the
+/// door to them.
+pub fn draw_door_footprints(editor_state: Res<EditorState>, mut gizmos: Gizmos) {
+    const COLOR: Color = Color::srgb(0.85, 0.6, 0.35);
+    let map = &editor_state.current_map;
+    let doors: Vec<(Vec3, &str)> = map
+        .entities
+        .iter()
+        .filter(|entity| entity.entity_type == EntityType::Door)
+        .filter_map(|entity| {
+            let group = entity.properties.get("group")?.trim();
+            Some((Vec3::from(entity.position), group))
+        })
+        .collect();
+    if doors.is_empty() {
+        return;
+    }
+
+    let mut footprints: HashMap<&str, Vec<Vec3>> = HashMap::new();
+    for voxel in &map.world.voxels {
+        let Some(group) = voxel.group.as_deref() else {
+            continue;
+        };
+        if doors.iter().any(|(_, door_group)| *door_group == group) {
+            let (x, y, z) = voxel.pos;
+            footprints
+                .entry(group)
+                .or_default()
+                .push(Vec3::new(x as f32, y as f32, z as f32));
+        }
+    }
+
+    for (door, group) in doors {
+        let Some(voxels) = footprints.get(group) else {
+            continue;
+        };
+        for &center in voxels {
+            gizmos.cube(
+                Transform::from_translation(center).with_scale(Vec3::splat(1.02)),
+                COLOR,
+            );
+        }
+        let centroid = voxels.iter().sum::<Vec3>() / voxels.len() as f32;
+        gizmos.line(door, centroid, COLOR);
+    }
+}
+
+/// Draw an arrow from each teleporter to the teleporter it sends the player
+/// to, and its radius on the ground.
+pub fn draw_teleporter_links(editor_state: Res<EditorState>, mut gizmos: Gizmos) {
+    let teleporters: Vec<(Vec3, &HashMap<String, String>)> = editor_state
+        .current_map
+        .entities
+        .iter()
+        .filter(|entity| entity.entity_type == EntityType::Teleporter)
+        .map(|entity| (Vec3::from(entity.position), &entity.properties))
+        .collect();
+
+    for &(position, properties) in &teleporters {
+        let radius = properties
+            .get("radius")
+            .and_then(|r| r.parse::<f32>().ok())
+            .filter(|r| *r > 0.0)
+            .unwrap_or(DEFAULT_TELEPORTER_RADIUS);
+        gizmos.circle(
+            Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            radius,
+            TELEPORTER_COLOR,
+        );
+
+        let Some(target) = properties.get("target").map(|t| t.trim()) else {
+            continue;
+        };
+        let destination = teleporters.iter().find(|(other, other_properties)| {
+            *other != position && other_properties.get("name").map(|n| n.trim()) == Some(target)
+        });
+        if let Some(&(destination, _)) = destination {
+            // Lifted so the arrow clears the floor
+            let lift = Vec3::Y * 0.3;
+            gizmos.arrow(position + lift, destination + lift, TELEPORTER_COLOR);
+        }
+    }
+}
+
+/// Draw each goal's finishing radius on the ground.
+pub fn draw_goal_radii(editor_state: Res<EditorState>, mut gizmos: Gizmos) {
+    for entity in &editor_state.current_map.entities {
+        if entity.entity_type != EntityType::Goal {
+            continue;
+        }
+        let radius = entity
+            .properties
+            .get("radius")
+            .and_then(|r| r.parse::<f32>().ok())
+            .filter(|r| *r > 0.0)
+            .unwrap_or(DEFAULT_GOAL_RADIUS);
+        gizmos.circle(
+            Isometry3d::new(
+                Vec3::from(entity.position),
+                Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+            ),
+            radius,
+            GOAL_COLOR,
+        );
+    }
+}
+
+/// System to render entity markers in the viewport.
+///
+/// Each entity is drawn as a camera-facing icon; see [`crate::editor::billboards`].
+#[allow(clippy::too_many_arguments)]
 pub fn render_entities_system(
     mut commands: Commands,
     mut render_events: MessageReader<RenderMapEvent>,
@@ -341,7 +612,9 @@ pub fn render_entities_system(
     editor_state: Res<EditorState>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
     existing_markers: Query<Entity, With<EditorEntityMarker>>,
+    mut icon_assets: Local<Option<EntityIconAssets>>,
 ) {
     // Only render if we received a render event or selection changed
     let render_count = render_events.read().count();
@@ -360,6 +633,8 @@ pub fn render_entities_system(
         editor_state.current_map.entities.len()
     );
 
+    let icon_assets = icon_assets.get_or_insert_with(|| EntityIconAssets::new(&mut meshes));
+
     // Spawn markers for each entity
     for (index, entity_data) in editor_state.current_map.entities.iter().enumerate() {
         let (x, y, z) = entity_data.position;
@@ -367,52 +642,22 @@ pub fn render_entities_system(
         // Entity positions should be at integer coordinates (grid cell centers)
         let position = Vec3::new(x.round(), y.round(), z.round());
 
-        // Get color and size based on entity type
-        let (color, size) = match entity_data.entity_type {
-            EntityType::PlayerSpawn => (Color::srgba(0.0, 1.0, 0.0, 0.8), 0.4),
-            EntityType::Npc => (Color::srgba(0.0, 0.5, 1.0, 0.8), 0.35),
-            EntityType::Enemy => (Color::srgba(1.0, 0.0, 0.0, 0.8), 0.35),
-            EntityType::Item => (Color::srgba(1.0, 1.0, 0.0, 0.8), 0.25),
-            EntityType::Trigger => (Color::srgba(1.0, 0.0, 1.0, 0.5), 0.5),
-            EntityType::LightSource => (Color::srgba(1.0, 1.0, 0.8, 0.9), 0.3),
-        };
-
-        // Check if this entity is selected
-        let is_selected = editor_state.selected_entities.contains(&index);
-        let final_color = if is_selected {
-            // Make selected entities brighter/more saturated
-            Color::srgba(
-                (color.to_srgba().red + 0.3).min(1.0),
-                (color.to_srgba().green + 0.3).min(1.0),
-                (color.to_srgba().blue + 0.3).min(1.0),
-                1.0,
-            )
-        } else {
-            color
-        };
-
-        // Create sphere mesh for marker
-        let mesh = meshes.add(Sphere::new(size));
-        let material = materials.add(StandardMaterial {
-            base_color: final_color,
-            alpha_mode: AlphaMode::Blend,
-            unlit: true, // Make markers always visible regardless of lighting
-            ..default()
-        });
+        let is_selected = editor_state.is_entity_selected(index);
+        let material = icon_assets.material(
+            entity_data.entity_type,
+            is_selected,
+            &mut images,
+            &mut materials,
+        );
 
-        // Spawn the entity marker with AABB for frustum culling
+        // Rotation and scale are set every frame by `face_camera_billboards`
         commands.spawn((
-            Mesh3d(mesh),
+            Mesh3d(icon_assets.quad.clone()),
             MeshMaterial3d(material),
             Transform::from_translation(position),
             EditorEntityMarker {
                 entity_index: index,
             },
-            // AABB for frustum culling (sphere bounds)
-            Aabb {
-                center: Vec3A::ZERO, // Local space center
-                half_extents: Vec3A::splat(size),
-            },
             Visibility::default(),
         ));
     }
@@ -422,7 +667,6 @@ pub fn render_entities_system(
 
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::editor::state::EditorState;
 
     /// Verify that `mark_modified` sets the `render_dirty` flag, and that
@@ -462,6 +706,42 @@ mod tests {
         );
     }
 
+    /// Voxels marked individually are collected for a partial re-mesh
+    /// without requesting a full re-render.
+    #[test]
+    fn mark_voxels_modified_queues_only_those_voxels() {
+        let mut state = EditorState::new();
+        state.mark_voxels_modified([(1, 2, 3), (4, 5, 6)]);
+        assert!(state.is_modified);
+        assert!(!state.render_dirty);
+        assert_eq!(state.dirty_voxels.len(), 2);
+    }
+
+    #[test]
+    fn voxels_map_to_the_chunks_their_sub_voxels_fall_in() {
+        use super::{affected_chunks, chunks_near_voxel};
+        use bevy::math::IVec3;
+
+        // Well inside chunk (0, 0, 0)
+        let inside: Vec<IVec3> = chunks_near_voxel((5, 5, 5), 0).collect();
+        assert_eq!(inside, vec![IVec3::ZERO]);
+
+        // Voxel 16 spans 15.5..16.5, straddling the boundary between chunks 0 and 1
+        let straddling: Vec<IVec3> = chunks_near_voxel((16, 5, 5), 0).collect();
+        assert_eq!(straddling, vec![IVec3::ZERO, IVec3::X]);
+
+        // Voxel 15's neighbour 16 reaches into the next chunk; voxel 14's doesn't
+        assert_eq!(affected_chunks([(14, 5, 5)]).len(), 1);
+        let chunks = affected_chunks([(15, 5, 5)]);
+        assert!(chunks.contains(&IVec3::ZERO));
+        assert!(chunks.contains(&IVec3::X));
+        assert_eq!(chunks.len(), 2);
+
+        // Negative coordinates round down
+        let negative: Vec<IVec3> = chunks_near_voxel((-1, 5, 5), 0).collect();
+        assert_eq!(negative, vec![IVec3::new(-1, 0, 0)]);
+    }
+
     /// Verify that no render event is emitted when the map is unmodified.
     #[test]
     fn detect_map_changes_does_not_fire_when_clean() {
@@ -0,0 +1,98 @@
+//! Instanced boxes around the editor's selection.
+//!
+//! Selected entities are boxed in the selection color, and with View →
+//! Collision Bounds enabled the selected voxels' sub-voxels are outlined as
+//! the game's spatial grid sees them. Both are drawn through the shared
+//! [`DebugBoxesPlugin`](crate::systems::game::debug_boxes::DebugBoxesPlugin),
+//! so selecting a large area costs one draw call.
+
+use crate::editor::state::EditorState;
+use crate::editor::tools::UpdateSelectionHighlights;
+use crate::systems::game::debug_boxes::{
+    DebugBox, DebugBoxMesh, COLLISION_BOUNDS_COLOR, SELECTION_BOX_COLOR,
+};
+use crate::systems::game::map::format::EntityId;
+use crate::systems::game::map::spawner::sub_voxel_bounds;
+use bevy::prelude::*;
+
+/// Half size of the box drawn around a selected entity.
+pub const ENTITY_BOX_HALF_EXTENTS: Vec3 = Vec3::splat(0.4);
+
+/// Marker for the selection's debug boxes.
+#[derive(Component)]
+pub struct SelectionBoxes;
+
+/// Boxes for the selection in `editor_state`.
+pub fn selection_boxes(editor_state: &EditorState) -> Vec<DebugBox> {
+    let map = &editor_state.current_map;
+    let entities = map
+        .entities
+        .iter()
+        .filter(|entity| editor_state.selected_entities.contains(&entity.id))
+        .map(|entity| {
+            let center = Vec3::from(entity.position);
+            DebugBox::from_bounds(
+                center - ENTITY_BOX_HALF_EXTENTS,
+                center + ENTITY_BOX_HALF_EXTENTS,
+                SELECTION_BOX_COLOR,
+            )
+        });
+    let mut boxes: Vec<DebugBox> = entities.collect();
+
+    if editor_state.show_collision_bounds && !editor_state.selected_voxels.is_empty() {
+        let bounds = sub_voxel_bounds(map, |voxel| {
+            editor_state.selected_voxels.contains(&voxel.pos)
+        });
+        boxes.extend(
+            bounds
+                .into_iter()
+                .map(|(min, max)| DebugBox::from_bounds(min, max, COLLISION_BOUNDS_COLOR)),
+        );
+    }
+    boxes
+}
+
+/// What the selection boxes were last built from.
+#[derive(PartialEq)]
+pub struct SelectionKey {
+    revision: u64,
+    show_collision_bounds: bool,
+    entities: Vec<EntityId>,
+    voxel_count: usize,
+}
+
+/// Rebuild the selection boxes when the selection, the map or the
+/// collision bounds toggle changes.
+pub fn update_selection_boxes(
+    mut commands: Commands,
+    editor_state: Res<EditorState>,
+    mesh: Res<DebugBoxMesh>,
+    existing: Query<Entity, With<SelectionBoxes>>,
+    mut selection_events: MessageReader<UpdateSelectionHighlights>,
+    mut shown: Local<Option<SelectionKey>>,
+) {
+    let mut entities: Vec<EntityId> = editor_state.selected_entities.iter().copied().collect();
+    entities.sort();
+    let key = Some(SelectionKey {
+        revision: editor_state.revision,
+        show_collision_bounds: editor_state.show_collision_bounds,
+        entities,
+        voxel_count: editor_state.selected_voxels.len(),
+    });
+    // Not every selection change is announced, so the key catches the rest
+    if selection_events.read().count() == 0 && *shown == key {
+        return;
+    }
+    *shown = key;
+
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+    let boxes = selection_boxes(&editor_state);
+    if !boxes.is_empty() {
+        commands.spawn((mesh.boxes(boxes), SelectionBoxes));
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,55 @@
+use super::*;
+use crate::systems::game::map::format::{EntityData, EntityType, VoxelData, VoxelType};
+use crate::systems::game::map::spawner::SUB_VOXEL_COUNT;
+use std::collections::HashMap;
+
+fn state() -> EditorState {
+    let mut state = EditorState::default();
+    state.current_map.world.voxels.push(VoxelData {
+        pos: (2, 0, 1),
+        voxel_type: VoxelType::Stone,
+        pattern: None,
+        rotation: None,
+        rotation_state: None,
+        group: None,
+    });
+    state.current_map.entities.push(EntityData {
+        id: EntityId(7),
+        entity_type: EntityType::Npc,
+        position: (1.0, 2.0, 3.0),
+        properties: HashMap::new(),
+    });
+    state
+}
+
+#[test]
+fn nothing_selected_draws_nothing() {
+    let mut state = state();
+    state.show_collision_bounds = true;
+    assert!(selection_boxes(&state).is_empty());
+}
+
+#[test]
+fn selected_entities_are_boxed() {
+    let mut state = state();
+    state.selected_entities.insert(EntityId(7));
+    let boxes = selection_boxes(&state);
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].center, Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(boxes[0].half_extents, ENTITY_BOX_HALF_EXTENTS);
+    assert_eq!(boxes[0].color, SELECTION_BOX_COLOR);
+}
+
+#[test]
+fn collision_bounds_follow_the_toggle() {
+    let mut state = state();
+    state.selected_voxels.insert((2, 0, 1));
+    assert!(selection_boxes(&state).is_empty());
+
+    state.show_collision_bounds = true;
+    let boxes = selection_boxes(&state);
+    assert_eq!(boxes.len(), (SUB_VOXEL_COUNT as usize).pow(3));
+    assert!(boxes
+        .iter()
+        .all(|debug_box| debug_box.color == COLLISION_BOUNDS_COLOR));
+}
@@ -0,0 +1,303 @@
+//! Per-map editor session persistence.
+//!
+//! Remembers how each map was being edited — camera, active tool, grid
+//! settings, panel widths, selection and camera bookmarks — and restores it
+//! when the map is opened again. Sessions live in `editor_sessions.ron` in
+//! the config directory, next to the recent files list, so map files stay
+//! untouched.
+//!
+//! [`track_map_session`] keeps a snapshot of the open map's session. When
+//! the open map changes (opening a file, switching tabs) the snapshot is
+//! stored for the old map and the new map's session, if any, is restored.
+//! Snapshots are also stored when the map is saved and when the editor exits.
+
+use crate::editor::bookmarks::CameraBookmarks;
+use crate::editor::camera::EditorCamera;
+use crate::editor::file_io::FileSavedEvent;
+use crate::editor::grid::WorkPlane;
+use crate::editor::state::{EditorState, EditorTool, SnapMode};
+use crate::editor::tools::UpdateSelectionHighlights;
+use crate::editor::ui::dialogs::AppExitEvent;
+use crate::systems::game::map::format::EntityId;
+use bevy::prelude::*;
+use bevy_egui::egui::containers::panel::PanelState;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of maps to remember sessions for
+const MAX_SESSIONS: usize = 32;
+
+/// Name of the sessions config file
+const SESSIONS_FILENAME: &str = "editor_sessions.ron";
+
+/// egui ids of the resizable side panels
+const OUTLINER_PANEL_ID: &str = "outliner";
+const PROPERTIES_PANEL_ID: &str = "properties";
+
+/// How one map was being edited when it was last left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapSession {
+    pub camera_position: (f32, f32, f32),
+    pub camera_yaw: f32,
+    pub camera_pitch: f32,
+    pub active_tool: EditorTool,
+    pub show_grid: bool,
+    pub grid_opacity: f32,
+    pub snap_to_grid: bool,
+    pub snap_mode: SnapMode,
+    pub work_plane: WorkPlane,
+    pub outliner_width: Option<f32>,
+    pub properties_width: Option<f32>,
+    pub selected_voxels: Vec<(i32, i32, i32)>,
+    pub selected_entities: Vec<EntityId>,
+    pub camera_bookmarks: CameraBookmarks,
+}
+
+impl Default for MapSession {
+    fn default() -> Self {
+        Self::capture(&EditorState::default(), &EditorCamera::default())
+    }
+}
+
+impl MapSession {
+    /// Snapshot the editor and camera settings (panel widths are added separately).
+    pub fn capture(editor_state: &EditorState, camera: &EditorCamera) -> Self {
+        let mut selected_voxels: Vec<_> = editor_state.selected_voxels.iter().copied().collect();
+        selected_voxels.sort_unstable();
+        let mut selected_entities: Vec<_> =
+            editor_state.selected_entities.iter().copied().collect();
+        selected_entities.sort_unstable();
+
+        Self {
+            camera_position: camera.position.into(),
+            camera_yaw: camera.yaw,
+            camera_pitch: camera.pitch,
+            active_tool: editor_state.active_tool.clone(),
+            show_grid: editor_state.show_grid,
+            grid_opacity: editor_state.grid_opacity,
+            snap_to_grid: editor_state.snap_to_grid,
+            snap_mode: editor_state.snap_mode,
+            work_plane: editor_state.work_plane,
+            outliner_width: None,
+            properties_width: None,
+            selected_voxels,
+            selected_entities,
+            camera_bookmarks: editor_state.camera_bookmarks.clone(),
+        }
+    }
+
+    /// Restore the tool, grid settings, selection and bookmarks.
+    ///
+    /// Selected voxels and entities that no longer exist in the map (it may
+    /// have been edited elsewhere) are dropped.
+    pub fn apply_to_state(&self, editor_state: &mut EditorState) {
+        editor_state.active_tool = self.active_tool.clone();
+        editor_state.show_grid = self.show_grid;
+        editor_state.grid_opacity = self.grid_opacity.clamp(0.0, 1.0);
+        editor_state.snap_to_grid = self.snap_to_grid;
+        editor_state.snap_mode = self.snap_mode;
+        editor_state.work_plane = self.work_plane;
+        editor_state.camera_bookmarks = self.camera_bookmarks.clone();
+
+        let map = &editor_state.current_map;
+        editor_state.selected_voxels = self
+            .selected_voxels
+            .iter()
+            .copied()
+            .filter(|pos| map.world.voxels.contains(*pos))
+            .collect();
+        editor_state.selected_entities = self
+            .selected_entities
+            .iter()
+            .copied()
+            .filter(|id| map.entity_index(*id).is_some())
+            .collect();
+    }
+
+    /// Move the camera back to where it was.
+    pub fn apply_to_camera(&self, camera: &mut EditorCamera) {
+        camera.position = self.camera_position.into();
+        camera.yaw = self.camera_yaw;
+        camera.pitch = self.camera_pitch.clamp(-1.5, 1.5);
+    }
+
+    /// Record the current side panel widths.
+    fn capture_panel_widths(&mut self, ctx: &egui::Context) {
+        let width = |id: &str| PanelState::load(ctx, egui::Id::new(id)).map(|s| s.size().x);
+        self.outliner_width = width(OUTLINER_PANEL_ID);
+        self.properties_width = width(PROPERTIES_PANEL_ID);
+    }
+
+    /// Resize the side panels to the recorded widths.
+    fn apply_panel_widths(&self, ctx: &egui::Context) {
+        for (id, width) in [
+            (OUTLINER_PANEL_ID, self.outliner_width),
+            (PROPERTIES_PANEL_ID, self.properties_width),
+        ] {
+            let Some(width) = width else {
+                continue;
+            };
+            // Panels read their width back from this state on the next frame
+            let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(width, 0.0));
+            ctx.data_mut(|d| d.insert_persisted(egui::Id::new(id), PanelState { rect }));
+        }
+    }
+}
+
+/// Sessions of recently edited maps, most recent first.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct EditorSessions {
+    pub maps: Vec<(PathBuf, MapSession)>,
+}
+
+impl EditorSessions {
+    /// Load sessions from the config directory
+    pub fn load() -> Self {
+        let config_path = get_config_path();
+        if !config_path.exists() {
+            return Self::default();
+        }
+        match fs::read_to_string(&config_path) {
+            Ok(contents) => match ron::from_str::<EditorSessions>(&contents) {
+                Ok(sessions) => {
+                    info!(
+                        "Loaded {} editor sessions from {:?}",
+                        sessions.maps.len(),
+                        config_path
+                    );
+                    sessions
+                }
+                Err(e) => {
+                    warn!("Failed to parse editor sessions: {}", e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                warn!("Failed to read editor sessions: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Save sessions to the config directory
+    pub fn save(&self) {
+        let config_path = get_config_path();
+        if let Some(parent) = config_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Failed to create config directory: {}", e);
+                return;
+            }
+        }
+        match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => {
+                if let Err(e) = fs::write(&config_path, contents) {
+                    error!("Failed to write editor sessions: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to serialize editor sessions: {}", e),
+        }
+    }
+
+    /// Session last recorded for the map at `path`
+    pub fn get(&self, path: &Path) -> Option<&MapSession> {
+        let path = canonical(path);
+        self.maps
+            .iter()
+            .find(|(p, _)| *p == path)
+            .map(|(_, session)| session)
+    }
+
+    /// Record `session` for the map at `path`, moving it to the front and
+    /// forgetting the oldest maps beyond the limit.
+    pub fn remember(&mut self, path: &Path, session: MapSession) {
+        let path = canonical(path);
+        self.maps.retain(|(p, _)| *p != path);
+        self.maps.insert(0, (path, session));
+        self.maps.truncate(MAX_SESSIONS);
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Get the path to the sessions config file
+fn get_config_path() -> PathBuf {
+    if let Some(config_dir) = dirs::config_dir() {
+        config_dir.join("adrakestory").join(SESSIONS_FILENAME)
+    } else {
+        PathBuf::from(SESSIONS_FILENAME)
+    }
+}
+
+/// Open map and its latest session snapshot.
+#[derive(Default)]
+pub struct SessionTracker {
+    path: Option<PathBuf>,
+    snapshot: Option<MapSession>,
+}
+
+/// System to store and restore per-map sessions as maps are opened and left.
+#[allow(clippy::too_many_arguments)]
+pub fn track_map_session(
+    mut contexts: EguiContexts,
+    mut editor_state: ResMut<EditorState>,
+    mut sessions: ResMut<EditorSessions>,
+    mut camera: Single<&mut EditorCamera>,
+    mut saved_events: MessageReader<FileSavedEvent>,
+    mut exit_events: MessageReader<AppExitEvent>,
+    mut selection_events: MessageWriter<UpdateSelectionHighlights>,
+    mut tracker: Local<SessionTracker>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let saved_as: Vec<PathBuf> = saved_events.read().map(|e| e.path.clone()).collect();
+    let exiting = exit_events.read().count() > 0;
+
+    if editor_state.file_path != tracker.path {
+        // Leaving a map: keep its last snapshot
+        if let (Some(path), Some(snapshot)) = (tracker.path.take(), tracker.snapshot.take()) {
+            sessions.remember(&path, snapshot);
+            sessions.save();
+        }
+
+        // Opening a map (not just saving the current one under a new name)
+        let new_path = editor_state.file_path.clone();
+        if let Some(path) = new_path.as_ref().filter(|p| !saved_as.contains(p)) {
+            if let Some(session) = sessions.get(path).cloned() {
+                info!("Restoring editor session for {:?}", path);
+                session.apply_to_state(&mut editor_state);
+                session.apply_to_camera(&mut camera);
+                session.apply_panel_widths(ctx);
+                selection_events.write(UpdateSelectionHighlights);
+            } else {
+                // Bookmarks belong to the map that was open before
+                editor_state.camera_bookmarks = CameraBookmarks::default();
+            }
+        }
+        tracker.path = new_path;
+    }
+
+    let Some(path) = tracker.path.clone() else {
+        return;
+    };
+    if tracker.snapshot.is_none() || editor_state.is_changed() || camera.is_changed() {
+        let mut snapshot = MapSession::capture(&editor_state, &camera);
+        snapshot.capture_panel_widths(ctx);
+        tracker.snapshot = Some(snapshot);
+    }
+
+    if !saved_as.is_empty() || exiting {
+        if let Some(snapshot) = tracker.snapshot.clone() {
+            sessions.remember(&path, snapshot);
+            sessions.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
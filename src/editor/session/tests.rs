@@ -0,0 +1,107 @@
+use super::*;
+use crate::editor::grid::WorkPlaneAxis;
+use crate::systems::game::map::format::{EntityData, EntityId, EntityType, VoxelData, VoxelType};
+use std::collections::HashMap;
+
+fn edited_state() -> EditorState {
+    let mut state = EditorState::default();
+    state.current_map.world.voxels = vec![
+        VoxelData::new((1, 0, 1), VoxelType::Stone, None),
+        VoxelData::new((2, 0, 1), VoxelType::Stone, None),
+    ]
+    .into();
+    state.current_map.entities = vec![EntityData {
+        id: EntityId(1),
+        entity_type: EntityType::Npc,
+        position: (1.0, 1.0, 1.0),
+        properties: HashMap::new(),
+    }];
+    state.active_tool = EditorTool::Select;
+    state.show_grid = false;
+    state.grid_opacity = 0.6;
+    state.snap_mode = SnapMode::Quarter;
+    state.work_plane = WorkPlane {
+        axis: WorkPlaneAxis::XY,
+        offset: 3,
+    };
+    state.selected_voxels = [(1, 0, 1), (2, 0, 1)].into_iter().collect();
+    state.camera_bookmarks.store(4, &EditorCamera::default());
+    state
+}
+
+#[test]
+fn captured_session_restores_tool_grid_selection_and_camera() {
+    let camera = EditorCamera {
+        position: Vec3::new(4.0, 9.0, -2.0),
+        yaw: 1.25,
+        pitch: -0.5,
+        ..default()
+    };
+    let session = MapSession::capture(&edited_state(), &camera);
+
+    let mut state = EditorState::with_map(edited_state().current_map);
+    let mut restored_camera = EditorCamera::default();
+    session.apply_to_state(&mut state);
+    session.apply_to_camera(&mut restored_camera);
+
+    assert_eq!(state.active_tool, EditorTool::Select);
+    assert!(!state.show_grid);
+    assert_eq!(state.grid_opacity, 0.6);
+    assert_eq!(state.snap_mode, SnapMode::Quarter);
+    assert_eq!(state.work_plane.offset, 3);
+    assert_eq!(state.selected_voxels, edited_state().selected_voxels);
+    assert_eq!(state.camera_bookmarks, edited_state().camera_bookmarks);
+    assert_eq!(restored_camera.position, camera.position);
+    assert_eq!(restored_camera.yaw, 1.25);
+    assert_eq!(restored_camera.pitch, -0.5);
+}
+
+#[test]
+fn restoring_drops_selection_missing_from_the_map() {
+    let mut session = MapSession::capture(&edited_state(), &EditorCamera::default());
+    session.selected_entities = vec![EntityId(1), EntityId(7)];
+
+    // The map lost a voxel since the session was recorded
+    let mut state = edited_state();
+    state.current_map.world.voxels.remove((2, 0, 1));
+    session.apply_to_state(&mut state);
+
+    assert_eq!(state.selected_voxels, [(1, 0, 1)].into_iter().collect());
+    assert_eq!(state.selected_entities, [EntityId(1)].into_iter().collect());
+}
+
+#[test]
+fn session_round_trips_through_ron_and_tolerates_missing_fields() {
+    let mut session = MapSession::capture(&edited_state(), &EditorCamera::default());
+    session.outliner_width = Some(240.0);
+    let text = ron::to_string(&session).unwrap();
+    assert_eq!(ron::from_str::<MapSession>(&text).unwrap(), session);
+
+    // Older or hand-edited files may lack fields; the rest fall back to defaults
+    let partial: MapSession = ron::from_str("(show_grid: false)").unwrap();
+    assert!(!partial.show_grid);
+    assert_eq!(partial.grid_opacity, EditorState::default().grid_opacity);
+}
+
+#[test]
+fn remember_moves_map_to_front_and_caps_the_list() {
+    let mut sessions = EditorSessions::default();
+    for i in 0..MAX_SESSIONS + 5 {
+        sessions.remember(
+            Path::new(&format!("missing/map_{}.ron", i)),
+            MapSession::default(),
+        );
+    }
+    assert_eq!(sessions.maps.len(), MAX_SESSIONS);
+
+    let first = Path::new("missing/map_10.ron");
+    let session = MapSession {
+        show_grid: false,
+        ..default()
+    };
+    sessions.remember(first, session.clone());
+    assert_eq!(sessions.maps[0].0, first);
+    assert_eq!(sessions.get(first), Some(&session));
+    assert_eq!(sessions.maps.len(), MAX_SESSIONS);
+    assert!(sessions.get(Path::new("missing/map_0.ron")).is_none());
+}
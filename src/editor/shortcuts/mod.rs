@@ -1,13 +1,18 @@
 //! Global keyboard shortcuts for the map editor.
 //!
 //! This module provides keyboard shortcut handling for common editor operations
-//! such as Save (Ctrl+S), Open (Ctrl+O), New (Ctrl+N), and Undo/Redo (Ctrl+Z/Y).
+//! such as Save (Ctrl+S), Open (Ctrl+O), New (Ctrl+N), Undo/Redo (Ctrl+Z/Y),
+//! Copy/Paste (Ctrl+C/V), Go To (Ctrl+G) and map tabs (Ctrl+T/W/Tab).
 
+use crate::editor::bookmarks::{just_pressed_slot, BookmarkEvent};
+use crate::editor::clipboard::{CopySelectionEvent, PasteClipboardEvent};
+use crate::editor::documents::DocumentEvent;
 use crate::editor::file_io::{SaveMapAsEvent, SaveMapEvent};
 use crate::editor::history::{EditorAction, EditorHistory};
 use crate::editor::renderer::RenderMapEvent;
 use crate::editor::state::{EditorState, EditorUIState, PendingAction};
 use crate::editor::ui::dialogs::MapDataChangedEvent;
+use crate::systems::game::map::format::EntityData;
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_egui::EguiContexts;
@@ -59,6 +64,10 @@ pub struct ShortcutEvents<'w> {
     pub save_as: MessageWriter<'w, SaveMapAsEvent>,
     pub undo: MessageWriter<'w, UndoEvent>,
     pub redo: MessageWriter<'w, RedoEvent>,
+    pub copy: MessageWriter<'w, CopySelectionEvent>,
+    pub paste: MessageWriter<'w, PasteClipboardEvent>,
+    pub documents: MessageWriter<'w, DocumentEvent>,
+    pub bookmarks: MessageWriter<'w, BookmarkEvent>,
 }
 
 /// System to handle global keyboard shortcuts for the editor
@@ -70,6 +79,12 @@ pub struct ShortcutEvents<'w> {
 /// - Ctrl+N: New
 /// - Ctrl+Z: Undo
 /// - Ctrl+Y / Ctrl+Shift+Z: Redo
+/// - Ctrl+C / Ctrl+V: Copy / Paste
+/// - Ctrl+G: Go To coordinates or entity
+/// - Ctrl+T: New tab
+/// - Ctrl+W: Close tab
+/// - Ctrl+Tab / Ctrl+Shift+Tab: Next / previous tab
+/// - Ctrl+1..9 / Ctrl+Shift+1..9: Recall / store camera bookmark
 pub fn handle_global_shortcuts(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut contexts: EguiContexts,
@@ -154,6 +169,48 @@ pub fn handle_global_shortcuts(
         events.redo.write(RedoEvent);
         info!("Redo triggered via Ctrl+Y");
     }
+
+    // Ctrl+C / Ctrl+V: Copy / Paste
+    if keyboard.just_pressed(KeyCode::KeyC) && !shift_pressed {
+        events.copy.write(CopySelectionEvent);
+    }
+    if keyboard.just_pressed(KeyCode::KeyV) && !shift_pressed {
+        events.paste.write(PasteClipboardEvent);
+    }
+
+    // Ctrl+G: Go To
+    if keyboard.just_pressed(KeyCode::KeyG) && !shift_pressed {
+        ui_state.go_to_dialog_open = true;
+        info!("Go To triggered via Ctrl+G");
+    }
+
+    // Ctrl+T: New tab / Ctrl+W: Close tab
+    if keyboard.just_pressed(KeyCode::KeyT) && !shift_pressed {
+        events.documents.write(DocumentEvent::New);
+        info!("New tab triggered via Ctrl+T");
+    }
+    if keyboard.just_pressed(KeyCode::KeyW) && !shift_pressed {
+        events.documents.write(DocumentEvent::CloseActive);
+        info!("Close tab triggered via Ctrl+W");
+    }
+
+    // Ctrl+Tab: Next tab / Ctrl+Shift+Tab: Previous tab
+    if keyboard.just_pressed(KeyCode::Tab) {
+        events.documents.write(if shift_pressed {
+            DocumentEvent::Previous
+        } else {
+            DocumentEvent::Next
+        });
+    }
+
+    // Ctrl+1..9: Recall camera bookmark / Ctrl+Shift+1..9: Store it
+    if let Some(slot) = just_pressed_slot(&keyboard) {
+        events.bookmarks.write(if shift_pressed {
+            BookmarkEvent::Store(slot)
+        } else {
+            BookmarkEvent::Recall(slot)
+        });
+    }
 }
 
 /// System to handle undo events and apply undo operations
@@ -207,29 +264,15 @@ pub fn handle_redo(
 }
 
 /// Apply an editor action to the editor state
-fn apply_action(action: &EditorAction, editor_state: &mut EditorState) {
+pub fn apply_action(action: &EditorAction, editor_state: &mut EditorState) {
     match action {
-        EditorAction::PlaceVoxel { pos, data } => {
+        EditorAction::PlaceVoxel { data, .. } => {
             // Add or update voxel
-            if let Some(existing) = editor_state
-                .current_map
-                .world
-                .voxels
-                .iter_mut()
-                .find(|v| v.pos == *pos)
-            {
-                *existing = data.clone();
-            } else {
-                editor_state.current_map.world.voxels.push(data.clone());
-            }
+            editor_state.current_map.world.voxels.insert(data.clone());
         }
         EditorAction::RemoveVoxel { pos, .. } => {
             // Remove voxel at position
-            editor_state
-                .current_map
-                .world
-                .voxels
-                .retain(|v| v.pos != *pos);
+            editor_state.current_map.world.voxels.remove(*pos);
         }
         EditorAction::PlaceEntity { index, data } => {
             // Insert entity at index
@@ -242,23 +285,45 @@ fn apply_action(action: &EditorAction, editor_state: &mut EditorState) {
                 editor_state.current_map.entities.push(data.clone());
             }
         }
-        EditorAction::RemoveEntity { index, .. } => {
-            // Remove entity at index
-            if *index < editor_state.current_map.entities.len() {
-                editor_state.current_map.entities.remove(*index);
+        EditorAction::RemoveEntity { index, data } => {
+            // Remove the entity with the recorded id, wherever it is now
+            if let Some(index) = entity_action_index(editor_state, *index, data) {
+                editor_state.current_map.entities.remove(index);
+                editor_state.selected_entities.remove(&data.id);
             }
         }
         EditorAction::ModifyEntity {
-            index, new_data, ..
+            index,
+            old_data,
+            new_data,
         } => {
             // Update entity data
-            if let Some(entity) = editor_state.current_map.entities.get_mut(*index) {
-                *entity = new_data.clone();
+            if let Some(index) = entity_action_index(editor_state, *index, old_data) {
+                editor_state.current_map.entities[index] = new_data.clone();
             }
         }
         EditorAction::ModifyMetadata { new, .. } => {
             editor_state.current_map.metadata = new.clone();
         }
+        EditorAction::ModifyBounds { new, .. } => {
+            let world = &mut editor_state.current_map.world;
+            (world.width, world.height, world.depth) = *new;
+        }
+        EditorAction::ModifyWeather { new, .. } => {
+            editor_state.current_map.weather = new.clone();
+        }
+        EditorAction::ModifyEdges { new, .. } => {
+            editor_state.current_map.edges = new.clone();
+        }
+        EditorAction::ModifyMeshing { new, .. } => {
+            editor_state.current_map.meshing = new.clone();
+        }
+        EditorAction::ModifyVoxelGroups { new, .. } => {
+            editor_state.current_map.voxel_groups = new.clone();
+        }
+        EditorAction::ModifyCameraSequences { new, .. } => {
+            editor_state.current_map.camera_sequences = new.clone();
+        }
         EditorAction::Batch { actions, .. } => {
             // Apply all actions in order
             for sub_action in actions {
@@ -268,6 +333,23 @@ fn apply_action(action: &EditorAction, editor_state: &mut EditorState) {
     }
 }
 
+/// Where the entity an action recorded as `data` at `index` is now.
+///
+/// Entities are found by id, so actions stay correct after other entities
+/// were added or removed; entities without an id fall back to `index`.
+fn entity_action_index(
+    editor_state: &EditorState,
+    index: usize,
+    data: &EntityData,
+) -> Option<usize> {
+    let map = &editor_state.current_map;
+    if data.id.is_assigned() {
+        map.entity_index(data.id)
+    } else {
+        (index < map.entities.len()).then_some(index)
+    }
+}
+
 /// Apply the inverse of an editor action (for undo)
 fn apply_action_inverse(action: &EditorAction, editor_state: &mut EditorState) {
     // Use the action's inverse method to get the reversed action, then apply it
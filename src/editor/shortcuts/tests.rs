@@ -1,6 +1,8 @@
 use super::*;
 use crate::systems::game::components::VoxelType;
-use crate::systems::game::map::format::{MapData, SubVoxelPattern, VoxelData};
+use crate::systems::game::map::format::{
+    EntityData, EntityId, EntityType, MapData, SubVoxelPattern, VoxelData,
+};
 
 fn create_test_editor_state() -> EditorState {
     EditorState {
@@ -20,6 +22,7 @@ fn test_apply_place_voxel() {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     };
 
@@ -37,6 +40,7 @@ fn test_apply_remove_voxel() {
         pattern: Some(SubVoxelPattern::Full),
         rotation: None,
         rotation_state: None,
+        group: None,
     });
 
     let action = EditorAction::RemoveVoxel {
@@ -47,6 +51,7 @@ fn test_apply_remove_voxel() {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     };
 
@@ -67,6 +72,7 @@ fn test_undo_via_inverse() {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     };
     apply_action(&place_action, &mut state);
@@ -76,3 +82,44 @@ fn test_undo_via_inverse() {
     apply_action_inverse(&place_action, &mut state);
     assert!(state.current_map.world.voxels.is_empty());
 }
+
+fn npc(id: u32, x: f32) -> EntityData {
+    EntityData {
+        id: EntityId(id),
+        entity_type: EntityType::Npc,
+        position: (x, 0.0, 0.0),
+        properties: Default::default(),
+    }
+}
+
+#[test]
+fn test_entity_actions_follow_ids_when_indices_shift() {
+    let mut state = create_test_editor_state();
+    state.current_map.entities = vec![npc(1, 0.0), npc(2, 1.0), npc(3, 2.0)];
+    state.selected_entities.insert(EntityId(3));
+
+    // Recorded while entity 3 was at index 2
+    let modify = EditorAction::ModifyEntity {
+        index: 2,
+        old_data: npc(3, 2.0),
+        new_data: npc(3, 5.0),
+    };
+    let remove = EditorAction::RemoveEntity {
+        index: 2,
+        data: npc(3, 5.0),
+    };
+
+    // Another entity goes away first, moving entity 3 to index 1
+    state.current_map.entities.remove(0);
+    apply_action(&modify, &mut state);
+    assert_eq!(state.current_map.entities[1].position, (5.0, 0.0, 0.0));
+
+    apply_action(&remove, &mut state);
+    let ids: Vec<_> = state.current_map.entities.iter().map(|e| e.id).collect();
+    assert_eq!(ids, vec![EntityId(2)]);
+    assert!(state.selected_entities.is_empty());
+
+    // Undoing the removal brings back the same id
+    apply_action_inverse(&remove, &mut state);
+    assert_eq!(state.current_map.entity_index(EntityId(3)), Some(1));
+}
@@ -1,8 +1,17 @@
 //! Editor state management.
 
+use crate::editor::bookmarks::CameraBookmarks;
+use crate::editor::grid::WorkPlane;
+use crate::editor::locks::{is_locked, map_locks, LockedArea};
 use crate::systems::game::components::VoxelType;
-use crate::systems::game::map::format::{EntityType, MapData, SubVoxelPattern};
+use crate::systems::game::map::format::{
+    axis_angle_to_matrix, find_or_insert_orientation, multiply_matrices, EntityId, EntityType,
+    MapData, OrientationMatrix, SubVoxelPattern, VoxelData, IDENTITY,
+};
+use crate::systems::game::map::geometry::RotationAxis;
+use crate::systems::game::map::loader::LoadDiagnostic;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -16,6 +25,20 @@ pub struct ToolMemory {
     pub voxel_pattern: SubVoxelPattern,
     /// Last-used entity type for EntityPlace tool
     pub entity_type: EntityType,
+    /// Whether VoxelRemove's connected removal only follows the clicked voxel's type
+    pub remove_connected_same_type: bool,
+    /// Last-used operation for Sculpt tool
+    pub sculpt_mode: SculptMode,
+    /// Last-used brush radius for Sculpt tool
+    pub sculpt_radius: u32,
+    /// Last-used noise jitter for Sculpt tool
+    pub sculpt_jitter: f32,
+    /// Last-used brush radius for Paint tool
+    pub paint_radius: u32,
+    /// Last-used type mask for Paint tool
+    pub paint_mask: Option<VoxelType>,
+    /// Last-used road width for Spline tool
+    pub spline_width: u32,
 }
 
 impl Default for ToolMemory {
@@ -24,6 +47,13 @@ impl Default for ToolMemory {
             voxel_type: VoxelType::Grass,
             voxel_pattern: SubVoxelPattern::Full,
             entity_type: EntityType::PlayerSpawn,
+            remove_connected_same_type: false,
+            sculpt_mode: SculptMode::Raise,
+            sculpt_radius: 3,
+            sculpt_jitter: 0.0,
+            paint_radius: 3,
+            paint_mask: None,
+            spline_width: 3,
         }
     }
 }
@@ -40,6 +70,14 @@ pub struct EditorState {
     /// Whether the map has unsaved changes
     pub is_modified: bool,
 
+    /// When the map was last written to disk this session
+    pub last_saved: Option<std::time::Instant>,
+
+    /// Changes whenever the map is edited or replaced, and is never reused,
+    /// so a background save can tell whether the map it wrote is still the
+    /// one being edited.
+    pub revision: u64,
+
     /// Whether the map needs to be re-rendered.
     ///
     /// Set to `true` by every mutation path (via `mark_modified`).
@@ -47,14 +85,22 @@ pub struct EditorState {
     /// Distinct from `is_modified` so that saving does not suppress pending renders.
     pub render_dirty: bool,
 
+    /// Voxel positions changed since the last render by edits that only need
+    /// the chunks around them re-meshed (see `mark_voxels_modified`).
+    /// Superseded by `render_dirty`, which re-renders everything.
+    pub dirty_voxels: HashSet<(i32, i32, i32)>,
+
     /// Currently active tool
     pub active_tool: EditorTool,
 
     /// Set of selected voxel positions
     pub selected_voxels: HashSet<(i32, i32, i32)>,
 
-    /// Set of selected entity indices
-    pub selected_entities: HashSet<usize>,
+    /// Set of selected entity ids
+    pub selected_entities: HashSet<EntityId>,
+
+    /// Which kinds of objects the Select tool picks
+    pub selection_filter: SelectionFilter,
 
     /// Whether to show the grid
     pub show_grid: bool,
@@ -62,12 +108,53 @@ pub struct EditorState {
     /// Grid opacity (0.0 to 1.0)
     pub grid_opacity: f32,
 
+    /// Plane the grid is drawn on and the cursor falls back to
+    pub work_plane: WorkPlane,
+
+    /// Orientation newly placed voxels get (identity for unrotated)
+    pub placement_orientation: OrientationMatrix,
+
     /// Whether to snap cursor to grid
     pub snap_to_grid: bool,
 
+    /// Grid increment entity placement and moves snap to while `snap_to_grid` is on
+    pub snap_mode: SnapMode,
+
+    /// What happens when voxels are placed outside the declared world bounds
+    pub bounds_enforcement: BoundsEnforcement,
+
     /// Whether to show floating name labels above entities in the viewport
     pub show_entity_labels: bool,
 
+    /// Whether to draw the player's jump arcs and reachable landings from the
+    /// selected or hovered voxel
+    pub show_jump_arcs: bool,
+
+    /// Whether to draw the loaded playtest log's heatmap
+    pub show_playtest_heatmap: bool,
+
+    /// Whether to outline chunks by mesh complexity and list the heaviest
+    pub show_chunk_complexity: bool,
+
+    /// Whether to draw the collision bounds of the selected voxels' sub-voxels
+    pub show_collision_bounds: bool,
+
+    /// Whether the map validation panel is open
+    pub show_validation: bool,
+
+    /// Whether the autotile rules panel is open
+    pub show_autotile: bool,
+
+    /// What safe mode skipped when the open map was loaded, listed in the
+    /// validation panel. Empty for maps that loaded normally.
+    pub load_diagnostics: Vec<LoadDiagnostic>,
+
+    /// Whether the viewport is split into perspective, top, front and side views
+    pub quad_view: bool,
+
+    /// Camera bookmarks of the open map
+    pub camera_bookmarks: CameraBookmarks,
+
     /// One-frame bridge: when `render_entity_name_labels` handles a label click it
     /// writes the entity index here so that the outliner (rendered in the previous
     /// system) can call `scroll_to_me` on the corresponding row in the *next* frame.
@@ -81,17 +168,34 @@ impl Default for EditorState {
             current_map: MapData::empty_map(),
             file_path: None,
             is_modified: false,
+            last_saved: None,
+            revision: next_revision(),
             render_dirty: false,
+            dirty_voxels: HashSet::new(),
             active_tool: EditorTool::VoxelPlace {
                 voxel_type: VoxelType::Grass,
                 pattern: SubVoxelPattern::Full,
             },
             selected_voxels: HashSet::new(),
             selected_entities: HashSet::new(),
+            selection_filter: SelectionFilter::default(),
             show_grid: true,
             grid_opacity: 0.3,
+            work_plane: WorkPlane::default(),
+            placement_orientation: IDENTITY,
             snap_to_grid: true,
+            snap_mode: SnapMode::default(),
+            bounds_enforcement: BoundsEnforcement::default(),
             show_entity_labels: true,
+            show_jump_arcs: false,
+            show_playtest_heatmap: false,
+            show_chunk_complexity: false,
+            show_collision_bounds: false,
+            show_validation: false,
+            show_autotile: false,
+            load_diagnostics: Vec::new(),
+            quad_view: false,
+            camera_bookmarks: CameraBookmarks::default(),
             outliner_scroll_to: None,
         }
     }
@@ -104,7 +208,9 @@ impl EditorState {
     }
 
     /// Create a new editor state with a specific map
-    pub fn with_map(map: MapData) -> Self {
+    pub fn with_map(mut map: MapData) -> Self {
+        // Maps built in code rather than loaded may lack entity ids
+        map.assign_entity_ids();
         Self {
             current_map: map,
             ..Default::default()
@@ -113,10 +219,25 @@ impl EditorState {
 
     /// Mark the map as modified
     pub fn mark_modified(&mut self) {
-        self.is_modified = true;
+        self.mark_modified_without_render();
         self.render_dirty = true;
     }
 
+    /// Mark the map as modified by an edit that doesn't change how it looks
+    /// in the viewport, like weather or voxel groups.
+    pub fn mark_modified_without_render(&mut self) {
+        self.is_modified = true;
+        self.revision = next_revision();
+    }
+
+    /// Mark the map as modified where only the voxels at `positions` changed,
+    /// so the viewport re-meshes the chunks around them instead of the whole
+    /// map. Used by edits that repeat every frame, like sculpt strokes.
+    pub fn mark_voxels_modified(&mut self, positions: impl IntoIterator<Item = (i32, i32, i32)>) {
+        self.mark_modified_without_render();
+        self.dirty_voxels.extend(positions);
+    }
+
     /// Clear the modified flag (after saving).
     ///
     /// Does NOT clear `render_dirty`; a pending re-render must still complete
@@ -133,6 +254,80 @@ impl EditorState {
     /// "unsaved changes" indicator.
     pub fn mark_needs_render(&mut self) {
         self.render_dirty = true;
+        self.revision = next_revision();
+    }
+
+    /// Turn the orientation for newly placed voxels by `angle` quarter turns
+    /// about the world `axis`, on top of its current orientation.
+    pub fn rotate_placement(&mut self, axis: RotationAxis, angle: i32) {
+        let delta = axis_angle_to_matrix(axis, angle);
+        self.placement_orientation = multiply_matrices(&delta, &self.placement_orientation);
+    }
+
+    /// `rotation` index for a newly placed voxel, adding the placement
+    /// orientation to the map's table if it isn't there yet.
+    pub fn placement_rotation(&mut self) -> Option<usize> {
+        if self.placement_orientation == IDENTITY {
+            return None;
+        }
+        Some(find_or_insert_orientation(
+            &mut self.current_map.orientations,
+            self.placement_orientation,
+        ))
+    }
+
+    /// Areas of the map locked against editing (see [`crate::editor::locks`]).
+    pub fn locked_areas(&self) -> Vec<LockedArea> {
+        map_locks(&self.current_map)
+    }
+
+    /// Whether the voxel at `pos` may be changed, i.e. lies in no locked area.
+    /// Refused edits are logged.
+    pub fn allows_edit_at(&self, pos: (i32, i32, i32)) -> bool {
+        if !is_locked(&self.locked_areas(), pos) {
+            return true;
+        }
+        warn!("Refused edit at {:?}: the voxel is locked", pos);
+        false
+    }
+
+    /// Whether a voxel may be placed at `pos` under `bounds_enforcement`.
+    ///
+    /// Locked positions are refused. Positions outside the declared world
+    /// bounds are logged when enforcement is on, and refused in `Clamp` mode.
+    pub fn allows_placement_at(&self, pos: (i32, i32, i32)) -> bool {
+        if !self.allows_edit_at(pos) {
+            return false;
+        }
+        if self.bounds_enforcement == BoundsEnforcement::Off || self.current_map.world.contains(pos)
+        {
+            return true;
+        }
+        let bounds = self.current_map.world.dimensions();
+        match self.bounds_enforcement {
+            BoundsEnforcement::Clamp => {
+                warn!(
+                    "Refused voxel at {:?}: outside map bounds {:?}",
+                    pos, bounds
+                );
+                false
+            }
+            _ => {
+                warn!("Voxel at {:?} is outside map bounds {:?}", pos, bounds);
+                true
+            }
+        }
+    }
+
+    /// Snap mode in effect, accounting for the snap toggle.
+    ///
+    /// `bypass` is true while Ctrl is held, which temporarily disables snapping.
+    pub fn active_snap(&self, bypass: bool) -> SnapMode {
+        if self.snap_to_grid && !bypass {
+            self.snap_mode
+        } else {
+            SnapMode::Free
+        }
     }
 
     /// Get the display name for the current file
@@ -159,10 +354,271 @@ impl EditorState {
         self.selected_voxels.clear();
         self.selected_entities.clear();
     }
+
+    /// Change the selection filter, dropping selected objects it excludes.
+    pub fn set_selection_filter(&mut self, filter: SelectionFilter) {
+        self.selection_filter = filter;
+        if !filter.allows_voxels() {
+            self.selected_voxels.clear();
+        }
+        if !filter.allows_entities() {
+            self.selected_entities.clear();
+        }
+    }
+
+    /// Replace the selection with every voxel of `voxel_type`.
+    pub fn select_voxels_of_type(&mut self, voxel_type: VoxelType) {
+        self.select_voxels_where(|voxel| voxel.voxel_type == voxel_type);
+    }
+
+    /// Replace the selection with every voxel using `pattern`.
+    ///
+    /// Voxels without an explicit pattern count as `Full`.
+    pub fn select_voxels_with_pattern(&mut self, pattern: SubVoxelPattern) {
+        self.select_voxels_where(|voxel| voxel.pattern.unwrap_or_default() == pattern);
+    }
+
+    fn select_voxels_where(&mut self, predicate: impl Fn(&VoxelData) -> bool) {
+        self.selected_entities.clear();
+        self.selected_voxels = self
+            .current_map
+            .world
+            .voxels
+            .iter()
+            .filter(|voxel| predicate(voxel))
+            .map(|voxel| voxel.pos)
+            .collect();
+    }
+
+    /// Replace the selection with every entity of `entity_type`.
+    pub fn select_entities_of_type(&mut self, entity_type: EntityType) {
+        self.selected_voxels.clear();
+        self.selected_entities = self
+            .current_map
+            .entities
+            .iter()
+            .filter(|entity| entity.entity_type == entity_type)
+            .map(|entity| entity.id)
+            .collect();
+    }
+
+    /// Whether the entity at `index` is selected.
+    pub fn is_entity_selected(&self, index: usize) -> bool {
+        self.current_map
+            .entities
+            .get(index)
+            .is_some_and(|entity| self.selected_entities.contains(&entity.id))
+    }
+
+    /// Indices of the selected entities, in map order.
+    pub fn selected_entity_indices(&self) -> Vec<usize> {
+        (0..self.current_map.entities.len())
+            .filter(|&index| self.is_entity_selected(index))
+            .collect()
+    }
+
+    /// Replace the selection with the entity at `index`.
+    pub fn select_entity(&mut self, index: usize) {
+        self.selected_voxels.clear();
+        self.selected_entities.clear();
+        if let Some(entity) = self.current_map.entities.get(index) {
+            self.selected_entities.insert(entity.id);
+        }
+    }
+
+    /// Select everything that isn't selected.
+    ///
+    /// Voxels and entities are never selected together, so this inverts the
+    /// entity selection when entities are selected (or the filter only allows
+    /// entities) and the voxel selection otherwise.
+    pub fn invert_selection(&mut self) {
+        let invert_entities = match self.selection_filter {
+            SelectionFilter::VoxelsOnly => false,
+            SelectionFilter::EntitiesOnly => true,
+            SelectionFilter::All => !self.selected_entities.is_empty(),
+        };
+
+        if invert_entities {
+            self.selected_voxels.clear();
+            self.selected_entities = self
+                .current_map
+                .entities
+                .iter()
+                .map(|entity| entity.id)
+                .filter(|id| !self.selected_entities.contains(id))
+                .collect();
+        } else {
+            self.selected_entities.clear();
+            self.selected_voxels = self
+                .current_map
+                .world
+                .voxels
+                .iter()
+                .map(|voxel| voxel.pos)
+                .filter(|pos| !self.selected_voxels.contains(pos))
+                .collect();
+        }
+    }
+}
+
+/// A revision number no editor state has used yet.
+fn next_revision() -> u64 {
+    static NEXT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Which kinds of objects the Select tool picks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionFilter {
+    /// Voxels and entities
+    #[default]
+    All,
+    /// Voxels only; clicks pass through entities
+    VoxelsOnly,
+    /// Entities only; clicks on voxels do nothing
+    EntitiesOnly,
+}
+
+impl SelectionFilter {
+    pub fn allows_voxels(self) -> bool {
+        self != Self::EntitiesOnly
+    }
+
+    pub fn allows_entities(self) -> bool {
+        self != Self::VoxelsOnly
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "Voxels & Entities",
+            Self::VoxelsOnly => "Voxels Only",
+            Self::EntitiesOnly => "Entities Only",
+        }
+    }
+}
+
+/// How the editor treats voxel placement outside the declared world bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundsEnforcement {
+    /// Place anywhere; bounds are recalculated on save
+    #[default]
+    Off,
+    /// Place anywhere, but log a warning and flag the cursor
+    Warn,
+    /// Refuse placements outside the bounds
+    Clamp,
+}
+
+impl BoundsEnforcement {
+    pub const ALL: [BoundsEnforcement; 3] = [Self::Off, Self::Warn, Self::Clamp];
+
+    /// Label shown in the Map Settings dialog.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Warn => "Warn",
+            Self::Clamp => "Clamp",
+        }
+    }
+}
+
+/// Increment entity positions snap to.
+///
+/// Voxel centers sit on whole numbers, so every mode includes them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SnapMode {
+    /// Voxel centers
+    #[default]
+    Full,
+    /// Voxel centers, edges and half-way points
+    Half,
+    /// Quarter-voxel increments
+    Quarter,
+    /// No snapping
+    Free,
+}
+
+impl SnapMode {
+    pub const ALL: [SnapMode; 4] = [Self::Full, Self::Half, Self::Quarter, Self::Free];
+
+    /// Distance between snap points, or `None` when snapping is off.
+    pub fn step(self) -> Option<f32> {
+        match self {
+            Self::Full => Some(1.0),
+            Self::Half => Some(0.5),
+            Self::Quarter => Some(0.25),
+            Self::Free => None,
+        }
+    }
+
+    /// Distance moved by one arrow-key press; Free nudges by a tenth of a voxel.
+    pub fn nudge(self) -> f32 {
+        self.step().unwrap_or(0.1)
+    }
+
+    /// Round `value` to the nearest snap point.
+    pub fn snap(self, value: f32) -> f32 {
+        match self.step() {
+            Some(step) => (value / step).round() * step,
+            None => value,
+        }
+    }
+
+    /// Round each component of `position` to the nearest snap point.
+    pub fn snap_position(self, position: (f32, f32, f32)) -> (f32, f32, f32) {
+        (
+            self.snap(position.0),
+            self.snap(position.1),
+            self.snap(position.2),
+        )
+    }
+
+    /// Grid lines drawn per voxel to show the snap points.
+    pub fn subdivisions(self) -> u32 {
+        match self {
+            Self::Half => 2,
+            Self::Quarter => 4,
+            Self::Full | Self::Free => 1,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Full => "Full Voxel",
+            Self::Half => "Half Voxel",
+            Self::Quarter => "Quarter Voxel",
+            Self::Free => "Free",
+        }
+    }
+}
+
+/// What the Sculpt tool does to terrain under the brush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SculptMode {
+    /// Build columns up
+    #[default]
+    Raise,
+    /// Dig columns down
+    Lower,
+    /// Even out column heights with their neighbours
+    Smooth,
+}
+
+impl SculptMode {
+    pub const ALL: [SculptMode; 3] = [Self::Raise, Self::Lower, Self::Smooth];
+
+    /// Label shown in the tool options.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Raise => "⬆ Raise",
+            Self::Lower => "⬇ Lower",
+            Self::Smooth => "〜 Smooth",
+        }
+    }
 }
 
 /// Editor tools available for map editing.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EditorTool {
     /// Place voxels with specified type and pattern
     VoxelPlace {
@@ -181,6 +637,38 @@ pub enum EditorTool {
 
     /// Camera control tool
     Camera,
+
+    /// Raise, lower or smooth terrain columns by dragging a brush
+    Sculpt {
+        mode: SculptMode,
+        /// Brush radius in voxels
+        radius: u32,
+        /// How much random noise roughens each dab (0 to 1)
+        jitter: f32,
+    },
+
+    /// Change the type of surface voxels under a brush, keeping their shape
+    Paint {
+        voxel_type: VoxelType,
+        /// Brush radius in voxels
+        radius: u32,
+        /// Only repaint voxels of this type
+        mask: Option<VoxelType>,
+    },
+
+    /// Lay voxels along a curve through clicked control points
+    Spline {
+        voxel_type: VoxelType,
+        pattern: SubVoxelPattern,
+        /// Road width in voxels
+        width: u32,
+    },
+
+    /// Build a walkable staircase between two clicked voxels
+    Staircase { voxel_type: VoxelType },
+
+    /// A tool registered by an [editor plugin](crate::editor::extensions)
+    Custom { name: String },
 }
 
 impl EditorTool {
@@ -192,6 +680,11 @@ impl EditorTool {
             Self::EntityPlace { .. } => "Entity Place",
             Self::Select => "Select",
             Self::Camera => "Camera",
+            Self::Sculpt { .. } => "Sculpt",
+            Self::Paint { .. } => "Paint",
+            Self::Spline { .. } => "Spline",
+            Self::Staircase { .. } => "Staircase",
+            Self::Custom { name } => name,
         }
     }
 
@@ -203,6 +696,11 @@ impl EditorTool {
             Self::EntityPlace { .. } => "Click to place entities",
             Self::Select => "Click to select objects",
             Self::Camera => "Drag to move camera",
+            Self::Sculpt { .. } => "Drag over terrain to reshape it",
+            Self::Paint { .. } => "Drag over surfaces to change their type",
+            Self::Spline { .. } => "Click to add curve points, Enter to lay voxels",
+            Self::Staircase { .. } => "Click a start and an end voxel, Enter to build stairs",
+            Self::Custom { .. } => "Tool provided by an editor plugin",
         }
     }
 }
@@ -213,26 +711,65 @@ pub struct EditorUIState {
     /// Whether the file dialog is open
     pub file_dialog_open: bool,
 
+    /// Whether the "Compare With File" picker should be opened
+    pub compare_dialog_open: bool,
+
+    /// Whether the "Export Selection as Prefab" save dialog should be opened
+    pub export_prefab_dialog_open: bool,
+
+    /// Whether the "Import Prefab" picker should be opened
+    pub import_prefab_dialog_open: bool,
+
+    /// Whether the "Load Playtest Log" picker should be opened
+    pub playtest_log_dialog_open: bool,
+
     /// Whether the new map dialog is open
     pub new_map_dialog_open: bool,
 
+    /// Whether the Save as Template dialog is open
+    pub save_template_dialog_open: bool,
+
     /// Whether the unsaved changes dialog is open
     pub unsaved_changes_dialog_open: bool,
 
     /// Pending action after unsaved changes dialog
     pub pending_action: Option<PendingAction>,
 
+    /// Whether the Map Settings dialog is open
+    pub map_settings_dialog_open: bool,
+
     /// Whether the about dialog is open
     pub about_dialog_open: bool,
 
     /// Whether the keyboard shortcuts help is open
     pub shortcuts_help_open: bool,
 
+    /// Whether the "Go To" dialog is open
+    pub go_to_dialog_open: bool,
+
+    /// Whether the camera sequence timeline is open
+    pub camera_timeline_open: bool,
+
+    /// Whether the Construction Guides window is open
+    pub guides_window_open: bool,
+
+    /// Whether the Locked Areas window is open
+    pub locks_window_open: bool,
+
+    /// Whether the Structure Generator window is open
+    pub generator_window_open: bool,
+
+    /// Whether the Collaborate window is open
+    pub collab_window_open: bool,
+
     /// Whether the error dialog is open
     pub error_dialog_open: bool,
 
     /// Error message to display in the error dialog
     pub error_message: String,
+
+    /// Connected voxels waiting for the user to confirm their removal
+    pub pending_voxel_removal: Option<Vec<(i32, i32, i32)>>,
 }
 
 /// Actions that can be pending after user confirmation
@@ -241,6 +778,10 @@ pub enum PendingAction {
     NewMap,
     OpenMap,
     OpenRecentFile(PathBuf),
+    /// A file dropped onto the editor window
+    OpenFile(PathBuf),
+    /// Close the active map tab
+    CloseTab,
     Quit,
 }
 
@@ -22,6 +22,14 @@ fn test_editor_state_default() {
     assert_eq!(state.grid_opacity, 0.3);
     assert!(state.snap_to_grid);
     assert!(state.show_entity_labels);
+    assert!(!state.show_jump_arcs);
+    assert!(!state.show_playtest_heatmap);
+    assert!(!state.show_chunk_complexity);
+    assert!(!state.show_collision_bounds);
+    assert!(!state.show_validation);
+    assert!(!state.show_autotile);
+    assert!(state.load_diagnostics.is_empty());
+    assert!(!state.quad_view);
     assert!(state.outliner_scroll_to.is_none());
 }
 
@@ -104,8 +112,8 @@ fn test_clear_selections() {
     let mut state = EditorState::new();
     state.selected_voxels.insert((0, 0, 0));
     state.selected_voxels.insert((1, 2, 3));
-    state.selected_entities.insert(0);
-    state.selected_entities.insert(5);
+    state.selected_entities.insert(EntityId(1));
+    state.selected_entities.insert(EntityId(5));
 
     assert_eq!(state.selected_voxels.len(), 2);
     assert_eq!(state.selected_entities.len(), 2);
@@ -116,6 +124,20 @@ fn test_clear_selections() {
     assert!(state.selected_entities.is_empty());
 }
 
+#[test]
+fn test_revision_changes_with_every_edit() {
+    let mut editor_state = EditorState::new();
+    let loaded = editor_state.revision;
+    editor_state.mark_modified_without_render();
+    let edited = editor_state.revision;
+    assert_ne!(edited, loaded);
+    editor_state.clear_modified();
+    assert_eq!(editor_state.revision, edited, "saving is not an edit");
+    editor_state.mark_voxels_modified([(0, 0, 0)]);
+    assert_ne!(editor_state.revision, edited);
+    assert_ne!(EditorState::new().revision, editor_state.revision);
+}
+
 // EditorTool tests
 #[test]
 fn test_editor_tool_name() {
@@ -137,6 +159,22 @@ fn test_editor_tool_name() {
     );
     assert_eq!(EditorTool::Select.name(), "Select");
     assert_eq!(EditorTool::Camera.name(), "Camera");
+    assert_eq!(
+        EditorTool::Spline {
+            voxel_type: VoxelType::Stone,
+            pattern: SubVoxelPattern::Full,
+            width: 3
+        }
+        .name(),
+        "Spline"
+    );
+    assert_eq!(
+        EditorTool::Custom {
+            name: "Measure".to_string()
+        }
+        .name(),
+        "Measure"
+    );
 }
 
 #[test]
@@ -235,3 +273,180 @@ fn test_keyboard_edit_mode_toggle() {
     mode.toggle();
     assert!(!mode.enabled);
 }
+
+// Selection helper tests
+fn selection_test_state() -> EditorState {
+    use crate::systems::game::map::format::EntityData;
+    use std::collections::HashMap;
+
+    let mut map = MapData::empty_map();
+    let voxel = |pos, voxel_type, pattern| VoxelData {
+        pos,
+        voxel_type,
+        pattern,
+        rotation: None,
+        rotation_state: None,
+        group: None,
+    };
+    map.world.voxels = vec![
+        voxel((0, 0, 0), VoxelType::Stone, None),
+        voxel((1, 0, 0), VoxelType::Grass, Some(SubVoxelPattern::Full)),
+        voxel(
+            (2, 0, 0),
+            VoxelType::Stone,
+            Some(SubVoxelPattern::Staircase),
+        ),
+    ]
+    .into();
+    let entity = |entity_type| EntityData {
+        id: Default::default(),
+        entity_type,
+        position: (0.0, 1.0, 0.0),
+        properties: HashMap::new(),
+    };
+    map.entities = vec![
+        entity(EntityType::Npc),
+        entity(EntityType::PlayerSpawn),
+        entity(EntityType::Npc),
+    ];
+    EditorState::with_map(map)
+}
+
+#[test]
+fn test_select_voxels_of_type_replaces_selection() {
+    let mut state = selection_test_state();
+    state.selected_entities.insert(EntityId(2));
+
+    state.select_voxels_of_type(VoxelType::Stone);
+    assert_eq!(state.selected_voxels, HashSet::from([(0, 0, 0), (2, 0, 0)]));
+    assert!(state.selected_entities.is_empty());
+}
+
+#[test]
+fn test_select_voxels_with_pattern_treats_none_as_full() {
+    let mut state = selection_test_state();
+    state.select_voxels_with_pattern(SubVoxelPattern::Full);
+    assert_eq!(state.selected_voxels, HashSet::from([(0, 0, 0), (1, 0, 0)]));
+}
+
+#[test]
+fn test_select_entities_of_type() {
+    let mut state = selection_test_state();
+    state.selected_voxels.insert((0, 0, 0));
+
+    state.select_entities_of_type(EntityType::Npc);
+    assert_eq!(
+        state.selected_entities,
+        HashSet::from([EntityId(1), EntityId(3)])
+    );
+    assert!(state.selected_voxels.is_empty());
+}
+
+#[test]
+fn test_invert_selection_inverts_the_selected_kind() {
+    let mut state = selection_test_state();
+
+    state.selected_voxels.insert((1, 0, 0));
+    state.invert_selection();
+    assert_eq!(state.selected_voxels, HashSet::from([(0, 0, 0), (2, 0, 0)]));
+
+    state.selected_voxels.clear();
+    state.selected_entities.insert(EntityId(1));
+    state.invert_selection();
+    assert_eq!(
+        state.selected_entities,
+        HashSet::from([EntityId(2), EntityId(3)])
+    );
+    assert!(state.selected_voxels.is_empty());
+}
+
+#[test]
+fn test_invert_selection_respects_filter() {
+    let mut state = selection_test_state();
+    state.set_selection_filter(SelectionFilter::EntitiesOnly);
+    state.invert_selection();
+    assert_eq!(state.selected_entities.len(), 3);
+    assert!(state.selected_voxels.is_empty());
+}
+
+#[test]
+fn test_set_selection_filter_drops_excluded_objects() {
+    let mut state = selection_test_state();
+    state.selected_entities.insert(EntityId(1));
+    state.set_selection_filter(SelectionFilter::VoxelsOnly);
+    assert!(state.selected_entities.is_empty());
+    assert!(!SelectionFilter::VoxelsOnly.allows_entities());
+    assert!(SelectionFilter::All.allows_voxels() && SelectionFilter::All.allows_entities());
+}
+
+#[test]
+fn test_snap_mode_rounds_to_step() {
+    assert_eq!(SnapMode::Full.snap(2.4), 2.0);
+    assert_eq!(SnapMode::Half.snap(2.3), 2.5);
+    assert_eq!(SnapMode::Quarter.snap(-0.2), -0.25);
+    assert_eq!(SnapMode::Free.snap(1.234), 1.234);
+    assert_eq!(
+        SnapMode::Half.snap_position((0.2, 1.3, -0.8)),
+        (0.0, 1.5, -1.0)
+    );
+    assert_eq!(SnapMode::Free.nudge(), 0.1);
+    assert_eq!(SnapMode::Quarter.subdivisions(), 4);
+}
+
+#[test]
+fn test_active_snap_respects_toggle_and_bypass() {
+    let mut state = EditorState::new();
+    state.snap_mode = SnapMode::Half;
+    assert_eq!(state.active_snap(false), SnapMode::Half);
+    assert_eq!(state.active_snap(true), SnapMode::Free);
+
+    state.snap_to_grid = false;
+    assert_eq!(state.active_snap(false), SnapMode::Free);
+}
+
+#[test]
+fn test_placement_rotation_composes_and_registers_orientation() {
+    let mut state = EditorState::new();
+    assert_eq!(state.placement_rotation(), None);
+
+    state.rotate_placement(RotationAxis::Y, 1);
+    state.rotate_placement(RotationAxis::X, 2);
+    let expected = multiply_matrices(
+        &axis_angle_to_matrix(RotationAxis::X, 2),
+        &axis_angle_to_matrix(RotationAxis::Y, 1),
+    );
+    assert_eq!(state.placement_orientation, expected);
+
+    let index = state.placement_rotation().unwrap();
+    assert_eq!(state.current_map.orientations[index], expected);
+    // Placing again reuses the same table entry
+    assert_eq!(state.placement_rotation(), Some(index));
+    assert_eq!(state.current_map.orientations.len(), 1);
+
+    // Four quarter turns about one axis return to the start
+    let mut state = EditorState::new();
+    for _ in 0..4 {
+        state.rotate_placement(RotationAxis::Z, 1);
+    }
+    assert_eq!(state.placement_rotation(), None);
+}
+
+#[test]
+fn test_allows_placement_at_respects_bounds_enforcement() {
+    let mut state = EditorState::new();
+    state.current_map.world.width = 4;
+    state.current_map.world.height = 4;
+    state.current_map.world.depth = 4;
+    let inside = (3, 0, 3);
+    let outside = (4, 0, 0);
+
+    assert!(state.allows_placement_at(outside));
+
+    state.bounds_enforcement = BoundsEnforcement::Warn;
+    assert!(state.allows_placement_at(outside));
+
+    state.bounds_enforcement = BoundsEnforcement::Clamp;
+    assert!(state.allows_placement_at(inside));
+    assert!(!state.allows_placement_at(outside));
+    assert!(!state.allows_placement_at((0, -1, 0)));
+}
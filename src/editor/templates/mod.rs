@@ -0,0 +1,283 @@
+//! Map templates: starter maps a new map can begin from.
+//!
+//! The editor ships a few templates in [`TEMPLATES_DIR`] (a platforming gym,
+//! a village and an arena). **File → Save as Template...** adds the current
+//! map to the user's own templates in [`user_templates_dir`]. The New Map
+//! dialog lists both, after a blank map, and creates the new map as a copy
+//! of the chosen one.
+
+use crate::editor::file_io::save_map_to_file;
+use crate::editor::state::{EditorState, EditorUIState};
+use crate::editor::ui::dialogs::MapDataChangedEvent;
+use crate::systems::game::map::format::MapData;
+use crate::systems::game::map::loader::MapLoader;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of the templates shipped with the game
+pub const TEMPLATES_DIR: &str = "assets/templates";
+
+/// Name of the user templates directory inside the config directory
+const USER_TEMPLATES_DIRNAME: &str = "templates";
+
+/// A map a new map can be created from
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapTemplate {
+    /// Name from the map's metadata
+    pub name: String,
+    /// Description from the map's metadata
+    pub description: String,
+    pub path: PathBuf,
+}
+
+/// Directory "Save as Template" writes to
+pub fn user_templates_dir() -> PathBuf {
+    if let Some(config_dir) = dirs::config_dir() {
+        config_dir.join("adrakestory").join(USER_TEMPLATES_DIRNAME)
+    } else {
+        // Fallback to current directory
+        PathBuf::from(USER_TEMPLATES_DIRNAME)
+    }
+}
+
+/// Templates in `dir`, sorted by name. Files that aren't readable maps are
+/// skipped, and a missing directory has none.
+pub fn list_templates(dir: &Path) -> Vec<MapTemplate> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<MapTemplate> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .filter_map(|path| {
+            let metadata = MapLoader::read_metadata(&path).ok()?;
+            Some(MapTemplate {
+                name: metadata.name,
+                description: metadata.description,
+                path,
+            })
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+/// File name for a template called `name`: lowercase letters and digits,
+/// with anything else turned into underscores.
+pub fn template_file_name(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = stem.trim_matches('_');
+    format!("{}.ron", if stem.is_empty() { "template" } else { stem })
+}
+
+/// Write `map` into `dir` as a template called `name`, returning its path.
+pub fn save_as_template(map: &MapData, name: &str, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create templates directory: {}", e))?;
+    let path = dir.join(template_file_name(name));
+    let mut template = map.clone();
+    template.metadata.name = name.trim().to_string();
+    save_map_to_file(&template, &path)?;
+    Ok(path)
+}
+
+/// A new map copied from the template at `path`.
+///
+/// The copy is untitled so saving it doesn't look like editing the template.
+pub fn map_from_template(path: &Path) -> Result<MapData, String> {
+    let mut map = MapLoader::read_unvalidated(path).map_err(|e| e.to_string())?;
+    map.metadata.name = MapData::empty_map().metadata.name;
+    map.metadata.created = String::new();
+    Ok(map)
+}
+
+/// Templates listed in the New Map dialog and the name typed into the Save
+/// as Template dialog
+#[derive(Resource, Default)]
+pub struct MapTemplates {
+    /// Templates shipped with the game
+    pub builtin: Vec<MapTemplate>,
+    /// Templates the user saved
+    pub user: Vec<MapTemplate>,
+    /// Template chosen in the New Map dialog; `None` for a blank map
+    pub selected: Option<PathBuf>,
+    /// Whether the lists were read since the New Map dialog opened
+    scanned: bool,
+    /// Name typed into the Save as Template dialog
+    pub save_name: String,
+}
+
+impl MapTemplates {
+    /// Read both template directories again.
+    pub fn rescan(&mut self) {
+        self.builtin = list_templates(Path::new(TEMPLATES_DIR));
+        self.user = list_templates(&user_templates_dir());
+        self.scanned = true;
+    }
+}
+
+/// System to render the New Map dialog: a blank map or one of the templates.
+pub fn render_new_map_dialog(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<EditorUIState>,
+    mut editor_state: ResMut<EditorState>,
+    mut templates: ResMut<MapTemplates>,
+    mut map_changed_events: MessageWriter<MapDataChangedEvent>,
+) {
+    if !ui_state.new_map_dialog_open {
+        templates.scanned = false;
+        return;
+    }
+    if !templates.scanned {
+        templates.rescan();
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut create = false;
+    let mut cancel = false;
+    egui::Window::new("New Map")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("Start from:");
+            let templates = &mut *templates;
+            ui.radio_value(&mut templates.selected, None, "📄 Blank Map")
+                .on_hover_text("The default starting map");
+            for (heading, list) in [
+                ("Templates", &templates.builtin),
+                ("Your Templates", &templates.user),
+            ] {
+                if list.is_empty() {
+                    continue;
+                }
+                ui.separator();
+                ui.label(egui::RichText::new(heading).strong());
+                for template in list {
+                    let mut response = ui.radio_value(
+                        &mut templates.selected,
+                        Some(template.path.clone()),
+                        &template.name,
+                    );
+                    if !template.description.is_empty() {
+                        response = response.on_hover_text(&template.description);
+                    }
+                    if response.double_clicked() {
+                        create = true;
+                    }
+                }
+            }
+
+            ui.separator();
+            ui.label("This will replace the current map.");
+
+            ui.horizontal(|ui| {
+                if ui.button("Create").clicked() {
+                    create = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if create {
+        let map = match &templates.selected {
+            Some(path) => map_from_template(path),
+            None => Ok(MapData::default_map()),
+        };
+        match map {
+            Ok(map) => {
+                *editor_state = EditorState::new();
+                editor_state.current_map = map;
+                info!("Created new map");
+                // Send event to trigger lighting update
+                map_changed_events.write(MapDataChangedEvent);
+            }
+            Err(e) => {
+                ui_state.error_message = format!("Failed to open template: {}", e);
+                ui_state.error_dialog_open = true;
+            }
+        }
+        ui_state.new_map_dialog_open = false;
+    } else if cancel {
+        ui_state.new_map_dialog_open = false;
+    }
+}
+
+/// System to render the Save as Template dialog.
+pub fn render_save_template_dialog(
+    mut contexts: EguiContexts,
+    mut ui_state: ResMut<EditorUIState>,
+    editor_state: Res<EditorState>,
+    mut templates: ResMut<MapTemplates>,
+) {
+    if !ui_state.save_template_dialog_open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if templates.save_name.is_empty() {
+        templates.save_name = editor_state.current_map.metadata.name.clone();
+    }
+
+    let dir = user_templates_dir();
+    let mut open = true;
+    let mut save = false;
+    egui::Window::new("Save as Template")
+        .collapsible(false)
+        .resizable(false)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut templates.save_name);
+            });
+            let path = dir.join(template_file_name(&templates.save_name));
+            ui.small(format!("Saved to {}", path.display()));
+            if path.exists() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 200, 80),
+                    "⚠ Replaces the template with this name",
+                );
+            }
+            ui.horizontal(|ui| {
+                let valid = !templates.save_name.trim().is_empty();
+                if ui.add_enabled(valid, egui::Button::new("Save")).clicked() {
+                    save = true;
+                }
+            });
+        });
+
+    if save {
+        match save_as_template(&editor_state.current_map, &templates.save_name, &dir) {
+            Ok(path) => info!("Saved template to {:?}", path),
+            Err(e) => {
+                ui_state.error_message = e;
+                ui_state.error_dialog_open = true;
+            }
+        }
+        open = false;
+    }
+    if !open {
+        ui_state.save_template_dialog_open = false;
+        templates.save_name.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,47 @@
+use super::*;
+use crate::systems::game::map::validation::validate_map;
+
+#[test]
+fn test_shipped_templates_are_valid_maps() {
+    let templates = list_templates(Path::new(TEMPLATES_DIR));
+    let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["Arena", "Platforming Gym", "Village"]);
+
+    for template in &templates {
+        let map = MapLoader::read_unvalidated(&template.path).unwrap();
+        assert!(!map.world.voxels.is_empty(), "{} is empty", template.name);
+        validate_map(&map).unwrap_or_else(|e| panic!("{} is invalid: {}", template.name, e));
+    }
+}
+
+#[test]
+fn test_template_file_names() {
+    assert_eq!(template_file_name("My Castle"), "my_castle.ron");
+    assert_eq!(template_file_name("  Arena #2 "), "arena__2.ron");
+    assert_eq!(template_file_name("???"), "template.ron");
+}
+
+#[test]
+fn test_saved_template_is_listed_and_copied() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut map = MapData::default_map();
+    map.metadata.description = "Mine".to_string();
+
+    let path = save_as_template(&map, "Starter Hill", dir.path()).unwrap();
+    assert_eq!(path, dir.path().join("starter_hill.ron"));
+
+    let templates = list_templates(dir.path());
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].name, "Starter Hill");
+    assert_eq!(templates[0].description, "Mine");
+
+    let copy = map_from_template(&path).unwrap();
+    assert_eq!(copy.metadata.name, MapData::empty_map().metadata.name);
+    assert_eq!(copy.world.voxels.len(), map.world.voxels.len());
+}
+
+#[test]
+fn test_missing_directory_has_no_templates() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(list_templates(&dir.path().join("missing")).is_empty());
+}
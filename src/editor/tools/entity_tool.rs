@@ -2,7 +2,7 @@
 
 use crate::editor::cursor::CursorState;
 use crate::editor::history::{EditorAction, EditorHistory};
-use crate::editor::state::{EditorState, EditorTool};
+use crate::editor::state::{EditorState, EditorTool, SnapMode};
 use crate::systems::game::map::format::EntityData;
 use bevy::prelude::*;
 use bevy_egui::EguiContexts;
@@ -14,6 +14,7 @@ pub fn handle_entity_placement(
     mut editor_state: ResMut<EditorState>,
     mut history: ResMut<EditorHistory>,
     mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
     mut contexts: EguiContexts,
 ) {
     // Check if entity place tool is active
@@ -34,16 +35,16 @@ pub fn handle_entity_placement(
         return;
     }
 
-    // Get cursor grid position for grid-aligned entity placement
-    let Some(grid_pos) = cursor_state.placement_grid_pos else {
+    // Holding Ctrl places the entity exactly under the cursor
+    let bypass_snap = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let snap = editor_state.active_snap(bypass_snap);
+    let Some(position) = entity_placement_position(&cursor_state, snap) else {
         return;
     };
 
-    // Convert grid position to world coordinates (centered on grid cell)
-    let position = (grid_pos.0 as f32, grid_pos.1 as f32, grid_pos.2 as f32);
-
     // Create new entity data
     let entity_data = EntityData {
+        id: editor_state.current_map.next_entity_id(),
         entity_type,
         position,
         properties: HashMap::new(),
@@ -60,5 +61,38 @@ pub fn handle_entity_placement(
         data: entity_data,
     });
 
-    info!("Placed {:?} entity at grid {:?}", entity_type, grid_pos);
+    info!("Placed {:?} entity at {:?}", entity_type, position);
+}
+
+/// Where a new entity goes for the current cursor and snap mode.
+///
+/// Full snapping uses the center of the cell a voxel would be placed in. Finer
+/// modes keep that cell's coordinate along the hit face's normal, so the entity
+/// still sits on the surface, and snap the other two axes from the exact hit
+/// point. Gamepad and keyboard cursors have no hit point and always use the
+/// cell center.
+fn entity_placement_position(
+    cursor_state: &CursorState,
+    snap: SnapMode,
+) -> Option<(f32, f32, f32)> {
+    let cell = cursor_state.placement_pos?;
+    if snap == SnapMode::Full {
+        return Some((cell.x, cell.y, cell.z));
+    }
+    let (Some(hit), Some(normal)) = (cursor_state.hit_point, cursor_state.hit_face_normal) else {
+        return Some((cell.x, cell.y, cell.z));
+    };
+
+    let along_surface = |axis_cell: f32, axis_hit: f32, axis_normal: f32| {
+        if axis_normal.abs() > 0.5 {
+            axis_cell
+        } else {
+            snap.snap(axis_hit)
+        }
+    };
+    Some((
+        along_surface(cell.x, hit.x, normal.x),
+        along_surface(cell.y, hit.y, normal.y),
+        along_surface(cell.z, hit.z, normal.z),
+    ))
 }
@@ -1,6 +1,7 @@
 //! Helper functions for input handling operations.
 
 use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::locks::is_locked;
 use crate::editor::renderer::RenderMapEvent;
 use crate::editor::state::EditorState;
 use crate::editor::tools::UpdateSelectionHighlights;
@@ -27,18 +28,15 @@ pub fn delete_selected_items(
 
     // --- Delete selected entities ---
     // Sort indices in descending order to safely remove from the vector
-    let mut entity_indices: Vec<usize> = editor_state.selected_entities.iter().copied().collect();
-    entity_indices.sort_by(|a, b| b.cmp(a)); // Descending order
+    let mut entity_indices = editor_state.selected_entity_indices();
+    entity_indices.reverse(); // Descending order
 
     for index in entity_indices {
-        if index < editor_state.current_map.entities.len() {
-            let entity_data = editor_state.current_map.entities[index].clone();
-            actions.push(EditorAction::RemoveEntity {
-                index,
-                data: entity_data,
-            });
-            editor_state.current_map.entities.remove(index);
-        }
+        let entity_data = editor_state.current_map.entities.remove(index);
+        actions.push(EditorAction::RemoveEntity {
+            index,
+            data: entity_data,
+        });
     }
 
     // Clear entity selection
@@ -49,21 +47,18 @@ pub fn delete_selected_items(
     let selected_positions: Vec<(i32, i32, i32)> =
         editor_state.selected_voxels.iter().copied().collect();
 
-    // Find and remove each selected voxel
+    // Find and remove each selected voxel, leaving locked ones in place
+    let locks = editor_state.locked_areas();
     for pos in selected_positions {
-        if let Some(index) = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .position(|v| v.pos == pos)
-        {
-            let voxel_data = editor_state.current_map.world.voxels[index].clone();
+        if is_locked(&locks, pos) {
+            warn!("Kept selected voxel at {:?}: it is locked", pos);
+            continue;
+        }
+        if let Some(voxel_data) = editor_state.current_map.world.voxels.remove(pos) {
             actions.push(EditorAction::RemoveVoxel {
                 pos,
                 data: voxel_data,
             });
-            editor_state.current_map.world.voxels.remove(index);
         }
     }
 
@@ -101,40 +96,46 @@ pub fn delete_selected_items(
     update_events.write(UpdateSelectionHighlights);
 }
 
-/// Move selected entities by an offset
+/// Move selected entities by `steps` snap increments
+///
+/// One step is the active snap mode's nudge distance, and moved entities land
+/// on that mode's snap points.
 pub fn move_selected_entities(
     editor_state: &mut EditorState,
     history: &mut EditorHistory,
     render_events: &mut MessageWriter<RenderMapEvent>,
-    offset: Vec3,
+    steps: Vec3,
 ) {
     // Nothing to move
     if editor_state.selected_entities.is_empty() {
         return;
     }
 
+    let snap = editor_state.active_snap(false);
+    let offset = steps * snap.nudge();
+
     let mut actions = Vec::new();
     let entity_count = editor_state.selected_entities.len();
 
     // Move each selected entity
-    for &index in &editor_state.selected_entities {
-        if index < editor_state.current_map.entities.len() {
-            let old_data = editor_state.current_map.entities[index].clone();
-            let mut new_data = old_data.clone();
-            new_data.position.0 += offset.x;
-            new_data.position.1 += offset.y;
-            new_data.position.2 += offset.z;
-
-            // Record for undo
-            actions.push(EditorAction::ModifyEntity {
-                index,
-                old_data,
-                new_data: new_data.clone(),
-            });
+    for index in editor_state.selected_entity_indices() {
+        let old_data = editor_state.current_map.entities[index].clone();
+        let mut new_data = old_data.clone();
+        new_data.position = snap.snap_position((
+            new_data.position.0 + offset.x,
+            new_data.position.1 + offset.y,
+            new_data.position.2 + offset.z,
+        ));
+
+        // Record for undo
+        actions.push(EditorAction::ModifyEntity {
+            index,
+            old_data,
+            new_data: new_data.clone(),
+        });
 
-            // Apply the change
-            editor_state.current_map.entities[index] = new_data;
-        }
+        // Apply the change
+        editor_state.current_map.entities[index] = new_data;
     }
 
     // Push to history
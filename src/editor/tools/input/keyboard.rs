@@ -27,12 +27,12 @@ pub fn handle_selection_mode_input(
         events.write(EditorInputEvent::DeselectAll);
     }
 
-    // Entity movement with arrow keys (grid-aligned movement for entities)
-    // Entities should move in full grid units to stay aligned with voxels
+    // Entity movement with arrow keys, in snap increments (scaled by the
+    // active snap mode when applied)
     let step = if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
-        5.0 // Move 5 grid units with Shift
+        5.0 // Move 5 increments with Shift
     } else {
-        1.0 // Move 1 grid unit normally
+        1.0 // Move 1 increment normally
     };
 
     let mut offset = Vec3::ZERO;
@@ -34,7 +34,7 @@ pub enum EditorInputEvent {
     // Transform operations - Move mode
     UpdateMoveOffset(IVec3),
 
-    // Entity operations
+    // Entity operations (offset in snap increments)
     MoveSelectedEntities(Vec3),
 
     // Transform operations - Rotate mode
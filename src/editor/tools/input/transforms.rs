@@ -27,13 +27,7 @@ pub fn start_move_operation_internal(
     let mut sum_pos = Vec3::ZERO;
 
     for &pos in &editor_state.selected_voxels {
-        if let Some(voxel) = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .find(|v| v.pos == pos)
-        {
+        if let Some(voxel) = editor_state.current_map.world.voxels.get(pos) {
             selected_voxels.push(voxel.clone());
             sum_pos += Vec3::new(pos.0 as f32, pos.1 as f32, pos.2 as f32);
         }
@@ -70,13 +64,7 @@ pub fn start_rotate_operation_internal(
     let mut sum_pos = Vec3::ZERO;
 
     for &pos in &editor_state.selected_voxels {
-        if let Some(voxel) = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .find(|v| v.pos == pos)
-        {
+        if let Some(voxel) = editor_state.current_map.world.voxels.get(pos) {
             selected_voxels.push(voxel.clone());
             sum_pos += Vec3::new(pos.0 as f32, pos.1 as f32, pos.2 as f32);
         }
@@ -99,6 +87,14 @@ pub fn start_rotate_operation_internal(
     );
 }
 
+/// Take the map's voxels at the positions of `selected` out of the map.
+fn lift_voxels(editor_state: &mut EditorState, selected: &[VoxelData]) -> Vec<VoxelData> {
+    selected
+        .iter()
+        .filter_map(|voxel| editor_state.current_map.world.voxels.remove(voxel.pos))
+        .collect()
+}
+
 /// Confirm and apply a move operation
 pub fn confirm_move_internal(
     active_transform: &mut ActiveTransform,
@@ -111,7 +107,7 @@ pub fn confirm_move_internal(
     // Check if all previews are valid (no collisions)
     let has_collision = preview_query.iter().any(|p| !p.is_valid);
     if has_collision {
-        warn!("Cannot confirm move: collision detected");
+        warn!("Cannot confirm move: collision or locked voxel");
         return;
     }
 
@@ -119,25 +115,19 @@ pub fn confirm_move_internal(
     let offset = active_transform.current_offset;
     let mut moved_voxels = Vec::new();
 
-    for voxel in &active_transform.selected_voxels {
-        let old_pos = voxel.pos;
+    // Lift every voxel before placing any, so one moving into another's
+    // old spot doesn't replace it
+    let lifted = lift_voxels(editor_state, &active_transform.selected_voxels);
+    for mut map_voxel in lifted {
+        let old_pos = map_voxel.pos;
         let new_pos = (
             old_pos.0 + offset.x,
             old_pos.1 + offset.y,
             old_pos.2 + offset.z,
         );
-
-        // Find and update voxel in map
-        if let Some(map_voxel) = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter_mut()
-            .find(|v| v.pos == old_pos)
-        {
-            map_voxel.pos = new_pos;
-            moved_voxels.push((old_pos, new_pos));
-        }
+        map_voxel.pos = new_pos;
+        editor_state.current_map.world.voxels.push(map_voxel);
+        moved_voxels.push((old_pos, new_pos));
     }
 
     // Create history action
@@ -209,49 +199,41 @@ pub fn confirm_rotate_internal(
     // Check if all previews are valid (no collisions)
     let has_collision = preview_query.iter().any(|p| !p.is_valid);
     if has_collision {
-        warn!("Cannot confirm rotation: collision detected");
+        warn!("Cannot confirm rotation: collision or locked voxel");
         return;
     }
 
     // Apply the rotation
     let mut rotated_voxels = Vec::new();
 
-    for voxel in &active_transform.selected_voxels {
-        let old_pos = voxel.pos;
+    let lifted = lift_voxels(editor_state, &active_transform.selected_voxels);
+    for mut map_voxel in lifted {
+        let old_pos = map_voxel.pos;
         let new_pos = super::helpers::rotate_position(
             old_pos,
             active_transform.pivot,
             active_transform.rotation_axis,
             active_transform.rotation_angle,
         );
+        map_voxel.pos = new_pos;
+
+        // Compose the current orientation matrix with the new single-axis rotation.
+        let current_matrix = map_voxel
+            .rotation
+            .and_then(|i| editor_state.current_map.orientations.get(i))
+            .copied()
+            .unwrap_or(IDENTITY);
+        let new_single = axis_angle_to_matrix(
+            active_transform.rotation_axis,
+            active_transform.rotation_angle,
+        );
+        let composed = multiply_matrices(&new_single, &current_matrix);
+        let new_index =
+            find_or_insert_orientation(&mut editor_state.current_map.orientations, composed);
+        map_voxel.rotation = Some(new_index);
 
-        // Find and update voxel in map
-        if let Some(map_voxel) = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter_mut()
-            .find(|v| v.pos == old_pos)
-        {
-            map_voxel.pos = new_pos;
-
-            // Compose the current orientation matrix with the new single-axis rotation.
-            let current_matrix = map_voxel
-                .rotation
-                .and_then(|i| editor_state.current_map.orientations.get(i))
-                .copied()
-                .unwrap_or(IDENTITY);
-            let new_single = axis_angle_to_matrix(
-                active_transform.rotation_axis,
-                active_transform.rotation_angle,
-            );
-            let composed = multiply_matrices(&new_single, &current_matrix);
-            let new_index =
-                find_or_insert_orientation(&mut editor_state.current_map.orientations, composed);
-            map_voxel.rotation = Some(new_index);
-
-            rotated_voxels.push((old_pos, new_pos));
-        }
+        editor_state.current_map.world.voxels.push(map_voxel);
+        rotated_voxels.push((old_pos, new_pos));
     }
 
     // Create history action
@@ -280,8 +262,7 @@ pub fn confirm_rotate_internal(
                         .current_map
                         .world
                         .voxels
-                        .iter()
-                        .find(|v| v.pos == *new_pos)
+                        .get(*new_pos)
                         .and_then(|v| v.rotation);
 
                     // Create a remove and place action pair
@@ -2,7 +2,11 @@
 
 pub mod entity_tool;
 pub mod input;
+pub mod paint_tool;
+pub mod sculpt_tool;
 pub mod selection_tool;
+pub mod spline_tool;
+pub mod staircase_tool;
 pub mod voxel_tool;
 
 pub use entity_tool::handle_entity_placement;
@@ -31,9 +35,25 @@ pub use selection_tool::{
     UpdateTransformPreview,
 };
 
+pub use paint_tool::{draw_paint_brush, handle_paint_brush, PaintStroke};
+
+pub use sculpt_tool::{draw_sculpt_brush, handle_sculpt_brush, SculptStroke};
+
+pub use spline_tool::{
+    draw_spline_preview, handle_generate_spline, handle_spline_input, GenerateSplineEvent,
+    SplineToolState,
+};
+
+pub use staircase_tool::{
+    draw_staircase_preview, handle_build_staircase, handle_staircase_input, BuildStaircaseEvent,
+    StaircaseToolState,
+};
+
 pub use voxel_tool::{
-    handle_voxel_drag_placement, handle_voxel_drag_removal, handle_voxel_placement,
-    handle_voxel_removal, VoxelDragState, VoxelRemoveDragState,
+    assign_voxel_group, handle_placement_rotation_keys, handle_voxel_drag_placement,
+    handle_voxel_drag_removal, handle_voxel_placement, handle_voxel_removal, place_voxel_group,
+    remove_voxel_group, render_placement_preview, PlacementPreview, VoxelDragState,
+    VoxelRemoveDragState, FLOOD_REMOVE_CONFIRM_THRESHOLD,
 };
 
 // Re-export RotationAxis from geometry module for convenience
@@ -0,0 +1,207 @@
+//! Paint tool: re-theme terrain by changing the type of surface voxels.
+//!
+//! Dragging the brush changes the `voxel_type` of every surface voxel (one
+//! with no voxel directly above it) within its radius of the voxel under the
+//! cursor. Geometry, patterns and rotations are left alone, so structures keep
+//! their shape. An optional type mask limits painting to voxels of one type,
+//! e.g. turning grass to stone without touching dirt paths.
+//!
+//! Painted voxels only re-mesh the chunks around them, and the whole stroke is
+//! one undo step.
+
+use crate::editor::cursor::CursorState;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::locks::is_locked;
+use crate::editor::state::{EditorState, EditorTool};
+use crate::systems::game::components::VoxelType;
+use crate::systems::game::map::format::VoxelData;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use std::collections::HashSet;
+
+/// Largest brush radius, in voxels
+pub const MAX_PAINT_RADIUS: u32 = 8;
+
+/// Indices of the voxels a dab at `center` repaints to `voxel_type`: surface
+/// voxels within `radius` of `center` (a circle across, `radius` up and down)
+/// that pass the `mask` and aren't that type already.
+pub fn paint_targets(
+    voxels: &[VoxelData],
+    center: (i32, i32, i32),
+    radius: u32,
+    voxel_type: VoxelType,
+    mask: Option<VoxelType>,
+) -> Vec<usize> {
+    let radius = radius.clamp(1, MAX_PAINT_RADIUS) as i32;
+    let near = |pos: (i32, i32, i32), reach: i32| {
+        (pos.0 - center.0).abs() <= reach
+            && (pos.1 - center.1).abs() <= reach
+            && (pos.2 - center.2).abs() <= reach
+    };
+    // One voxel past the brush so the top layer can see what's above it
+    let occupied: HashSet<(i32, i32, i32)> = voxels
+        .iter()
+        .map(|voxel| voxel.pos)
+        .filter(|&pos| near(pos, radius + 1))
+        .collect();
+
+    let max_distance = radius as f32 + 0.5;
+    voxels
+        .iter()
+        .enumerate()
+        .filter(|(_, voxel)| {
+            let (x, y, z) = voxel.pos;
+            let (dx, dz) = ((x - center.0) as f32, (z - center.2) as f32);
+            near(voxel.pos, radius)
+                && (dx * dx + dz * dz).sqrt() <= max_distance
+                && voxel.voxel_type != voxel_type
+                && mask.is_none_or(|mask| voxel.voxel_type == mask)
+                && !occupied.contains(&(x, y + 1, z))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Repaint the voxels a dab at `center` covers, re-meshing only the chunks
+/// around them. Returns the actions applied; each repaint is recorded as a
+/// removal of the old voxel followed by placement of the new one.
+pub fn apply_paint(
+    editor_state: &mut EditorState,
+    center: (i32, i32, i32),
+    radius: u32,
+    voxel_type: VoxelType,
+    mask: Option<VoxelType>,
+) -> Vec<EditorAction> {
+    let targets = paint_targets(
+        &editor_state.current_map.world.voxels,
+        center,
+        radius,
+        voxel_type,
+        mask,
+    );
+    let locks = editor_state.locked_areas();
+    let mut actions = Vec::with_capacity(targets.len() * 2);
+    let mut changed = Vec::with_capacity(targets.len());
+    for index in targets {
+        let pos = editor_state.current_map.world.voxels[index].pos;
+        if is_locked(&locks, pos) {
+            continue;
+        }
+        let Some(voxel) = editor_state.current_map.world.voxels.get_mut(pos) else {
+            continue;
+        };
+        let old = voxel.clone();
+        voxel.voxel_type = voxel_type;
+        actions.push(EditorAction::RemoveVoxel {
+            pos: old.pos,
+            data: old,
+        });
+        actions.push(EditorAction::PlaceVoxel {
+            pos: voxel.pos,
+            data: voxel.clone(),
+        });
+        changed.push(voxel.pos);
+    }
+    if !changed.is_empty() {
+        editor_state.mark_voxels_modified(changed);
+    }
+    actions
+}
+
+/// The paint stroke in progress.
+#[derive(Resource, Default)]
+pub struct PaintStroke {
+    /// Whether the brush is held down
+    pub active: bool,
+    /// Actions applied so far, pushed to history as one batch when the stroke ends
+    actions: Vec<EditorAction>,
+    /// Voxel the last dab was centered on
+    last_center: Option<(i32, i32, i32)>,
+}
+
+impl PaintStroke {
+    /// End the stroke, recording everything it painted as one undo step.
+    pub fn finish(&mut self, history: &mut EditorHistory) {
+        let actions = std::mem::take(&mut self.actions);
+        if !actions.is_empty() {
+            history.push(EditorAction::Batch {
+                description: "Paint".to_string(),
+                actions,
+            });
+        }
+        *self = Self::default();
+    }
+}
+
+/// System to paint while the left mouse button is held
+pub fn handle_paint_brush(
+    cursor_state: Res<CursorState>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut stroke: ResMut<PaintStroke>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mut contexts: EguiContexts,
+) {
+    let EditorTool::Paint {
+        voxel_type,
+        radius,
+        mask,
+    } = editor_state.active_tool
+    else {
+        if stroke.active {
+            stroke.finish(&mut history);
+        }
+        return;
+    };
+
+    if stroke.active && !mouse_button.pressed(MouseButton::Left) {
+        stroke.finish(&mut history);
+        return;
+    }
+
+    let ctx = contexts.ctx_mut().expect("egui context");
+    if ctx.is_pointer_over_area() || ctx.is_using_pointer() {
+        return;
+    }
+
+    if !stroke.active {
+        if !mouse_button.just_pressed(MouseButton::Left) {
+            return;
+        }
+        stroke.active = true;
+    }
+
+    let Some(center) = cursor_state.grid_pos else {
+        return;
+    };
+    if stroke.last_center == Some(center) {
+        return;
+    }
+    stroke.last_center = Some(center);
+
+    let actions = apply_paint(&mut editor_state, center, radius, voxel_type, mask);
+    stroke.actions.extend(actions);
+}
+
+/// Draw the brush outline on the voxel under the cursor
+pub fn draw_paint_brush(
+    editor_state: Res<EditorState>,
+    cursor_state: Res<CursorState>,
+    mut gizmos: Gizmos,
+) {
+    let EditorTool::Paint { radius, .. } = editor_state.active_tool else {
+        return;
+    };
+    let Some((x, y, z)) = cursor_state.grid_pos else {
+        return;
+    };
+    let center = Vec3::new(x as f32, y as f32 + 0.52, z as f32);
+    gizmos.circle(
+        Isometry3d::new(center, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        radius.clamp(1, MAX_PAINT_RADIUS) as f32 + 0.5,
+        Color::srgb(1.0, 0.4, 0.9),
+    );
+}
+
+#[cfg(test)]
+mod tests;
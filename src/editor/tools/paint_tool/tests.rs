@@ -0,0 +1,115 @@
+use super::*;
+use crate::systems::game::map::format::SubVoxelPattern;
+
+/// Grass over dirt on a 7×7 patch, a dirt path along z = 0, and a stone
+/// pillar at (2, _, 2).
+fn meadow() -> EditorState {
+    let mut state = EditorState::default();
+    let mut voxels = Vec::new();
+    for x in -3..=3 {
+        for z in -3..=3 {
+            voxels.push(VoxelData::new(
+                (x, 0, z),
+                VoxelType::Dirt,
+                Some(SubVoxelPattern::Full),
+            ));
+            let top = if z == 0 {
+                VoxelType::Dirt
+            } else {
+                VoxelType::Grass
+            };
+            voxels.push(VoxelData::new((x, 1, z), top, Some(SubVoxelPattern::Full)));
+        }
+    }
+    voxels.push(VoxelData::new(
+        (2, 2, 2),
+        VoxelType::Stone,
+        Some(SubVoxelPattern::Full),
+    ));
+    voxels.push(VoxelData::new(
+        (2, 3, 2),
+        VoxelType::Stone,
+        Some(SubVoxelPattern::Full),
+    ));
+    state.current_map.world.voxels = voxels.into();
+    state
+}
+
+fn type_at(state: &EditorState, pos: (i32, i32, i32)) -> Option<VoxelType> {
+    state
+        .current_map
+        .world
+        .voxels
+        .get(pos)
+        .map(|voxel| voxel.voxel_type)
+}
+
+#[test]
+fn paints_only_surface_voxels_inside_the_brush() {
+    let mut state = meadow();
+    apply_paint(&mut state, (0, 1, 1), 2, VoxelType::Stone, None);
+
+    assert_eq!(type_at(&state, (0, 1, 1)), Some(VoxelType::Stone));
+    assert_eq!(type_at(&state, (1, 1, 2)), Some(VoxelType::Stone));
+    // Buried under the surface
+    assert_eq!(type_at(&state, (0, 0, 1)), Some(VoxelType::Dirt));
+    // Outside the circle
+    assert_eq!(type_at(&state, (2, 1, 3)), Some(VoxelType::Grass));
+    // Covered by the pillar
+    assert_eq!(type_at(&state, (2, 1, 2)), Some(VoxelType::Grass));
+}
+
+#[test]
+fn the_mask_limits_which_types_are_painted() {
+    let mut state = meadow();
+    apply_paint(
+        &mut state,
+        (0, 1, 0),
+        3,
+        VoxelType::Stone,
+        Some(VoxelType::Grass),
+    );
+
+    assert_eq!(type_at(&state, (0, 1, 1)), Some(VoxelType::Stone));
+    assert_eq!(type_at(&state, (0, 1, 0)), Some(VoxelType::Dirt), "path");
+}
+
+#[test]
+fn painting_keeps_geometry_and_marks_only_changed_voxels() {
+    let mut state = meadow();
+    let count = state.current_map.world.voxels.len();
+    let actions = apply_paint(&mut state, (0, 1, 0), 1, VoxelType::Stone, None);
+
+    assert_eq!(state.current_map.world.voxels.len(), count);
+    assert!(!state.render_dirty);
+    assert_eq!(state.dirty_voxels.len() * 2, actions.len());
+    assert!(state.is_modified);
+
+    // Already painted voxels are skipped on the next dab
+    assert!(apply_paint(&mut state, (0, 1, 0), 1, VoxelType::Stone, None).is_empty());
+}
+
+#[test]
+fn a_stroke_undoes_in_one_step() {
+    let mut state = meadow();
+    let mut history = EditorHistory::default();
+    let mut stroke = PaintStroke::default();
+
+    for center in [(-2, 1, 1), (0, 1, 1), (2, 1, 1)] {
+        let actions = apply_paint(&mut state, center, 1, VoxelType::Stone, None);
+        stroke.actions.extend(actions);
+    }
+    stroke.finish(&mut history);
+    assert_eq!(history.undo_count(), 1);
+
+    let undo = history.undo().unwrap();
+    crate::editor::shortcuts::apply_action(&undo.inverse(), &mut state);
+    let restored = meadow();
+    for voxel in &restored.current_map.world.voxels {
+        assert_eq!(type_at(&state, voxel.pos), Some(voxel.voxel_type));
+    }
+    assert_eq!(
+        state.current_map.world.voxels.len(),
+        restored.current_map.world.voxels.len()
+    );
+}
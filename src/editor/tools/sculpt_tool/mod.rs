@@ -0,0 +1,355 @@
+//! Sculpt brush: raise, lower or smooth terrain by dragging over it.
+//!
+//! The map is treated as columns of voxels. While the left button is held the
+//! brush applies a "dab" every [`SCULPT_INTERVAL`] to the columns within its
+//! radius of the voxel under the cursor, changing each column's height by an
+//! amount that falls off towards the brush edge. Fractional changes carry over
+//! between dabs, so the edge rises more slowly than the middle. Optional noise
+//! jitter varies the amount per column for a rougher, more natural surface.
+//!
+//! - **Raise** stacks full voxels of the column's top type on top of it
+//! - **Lower** removes voxels from the top, never the column's lowest voxel
+//! - **Smooth** moves each column towards the average height of its neighbours
+//!
+//! Only the chunks around changed columns are re-meshed during the stroke
+//! (see [`EditorState::mark_voxels_modified`]), and the whole stroke is one
+//! undo step.
+
+use crate::editor::cursor::CursorState;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::locks::is_locked;
+use crate::editor::state::{EditorState, EditorTool, SculptMode};
+use crate::systems::game::map::format::{SubVoxelPattern, VoxelData};
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use std::collections::HashMap;
+
+/// Largest brush radius, in voxels
+pub const MAX_SCULPT_RADIUS: u32 = 8;
+
+/// Seconds between dabs while the brush is held
+pub const SCULPT_INTERVAL: f32 = 0.1;
+
+/// Most a column rises or sinks in one raise/lower dab, in voxels
+const MAX_STEP_PER_DAB: i32 = 1;
+
+/// The voxels of one terrain column.
+#[derive(Debug, Clone)]
+pub struct Column {
+    /// Height of the lowest voxel
+    pub bottom: i32,
+    /// Height of the highest voxel
+    pub top: i32,
+    /// Voxels in the column, in map order
+    pub voxels: Vec<VoxelData>,
+}
+
+impl Column {
+    fn top_voxel(&self) -> Option<&VoxelData> {
+        self.voxels.iter().find(|voxel| voxel.pos.1 == self.top)
+    }
+}
+
+/// Columns within `reach` (horizontally, in both axes) of `center`.
+pub fn scan_columns(
+    voxels: &[VoxelData],
+    center: (i32, i32),
+    reach: i32,
+) -> HashMap<(i32, i32), Column> {
+    let mut columns: HashMap<(i32, i32), Column> = HashMap::new();
+    for voxel in voxels {
+        let (x, y, z) = voxel.pos;
+        if (x - center.0).abs() > reach || (z - center.1).abs() > reach {
+            continue;
+        }
+        let column = columns.entry((x, z)).or_insert_with(|| Column {
+            bottom: y,
+            top: y,
+            voxels: Vec::new(),
+        });
+        column.bottom = column.bottom.min(y);
+        column.top = column.top.max(y);
+        column.voxels.push(voxel.clone());
+    }
+    columns
+}
+
+/// Brush strength at `distance` voxels from the center: 1 in the middle,
+/// easing smoothly to 0 just past `radius`.
+pub fn falloff(distance: f32, radius: u32) -> f32 {
+    let t = (distance / (radius as f32 + 0.5)).clamp(0.0, 1.0);
+    1.0 - t * t * (3.0 - 2.0 * t)
+}
+
+/// Repeatable pseudo-random value in `[-1, 1]` for a column and dab.
+pub fn hash_noise(x: i32, z: i32, seed: u32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x27d4_eb2d)
+        ^ (z as u32).wrapping_mul(0x1656_67b1)
+        ^ seed.wrapping_mul(0x9e37_79b9);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2_ae35);
+    h ^= h >> 16;
+    h as f32 / u32::MAX as f32 * 2.0 - 1.0
+}
+
+/// Brush settings for one dab.
+#[derive(Debug, Clone, Copy)]
+pub struct Brush {
+    pub center: (i32, i32),
+    pub mode: SculptMode,
+    pub radius: u32,
+    pub jitter: f32,
+    /// Noise seed, different for every dab
+    pub seed: u32,
+}
+
+/// New top heights for the columns a dab changes. `carry` holds the
+/// fractional raise/lower amounts left over from earlier dabs of the stroke.
+pub fn dab_heights(
+    columns: &HashMap<(i32, i32), Column>,
+    brush: &Brush,
+    carry: &mut HashMap<(i32, i32), f32>,
+) -> Vec<((i32, i32), i32)> {
+    let radius = brush.radius.clamp(1, MAX_SCULPT_RADIUS);
+    let reach = radius as i32;
+    let mut changes = Vec::new();
+
+    for dx in -reach..=reach {
+        for dz in -reach..=reach {
+            let key = (brush.center.0 + dx, brush.center.1 + dz);
+            let Some(column) = columns.get(&key) else {
+                continue;
+            };
+            let distance = ((dx * dx + dz * dz) as f32).sqrt();
+            if distance > radius as f32 + 0.5 {
+                continue;
+            }
+            let weight = falloff(distance, radius);
+            let noise = brush.jitter * hash_noise(key.0, key.1, brush.seed);
+
+            let new_top = match brush.mode {
+                SculptMode::Raise | SculptMode::Lower => {
+                    let amount = carry.entry(key).or_default();
+                    *amount += (weight * (1.0 + noise)).max(0.0);
+                    let steps = (amount.floor() as i32).min(MAX_STEP_PER_DAB);
+                    *amount -= steps as f32;
+                    if brush.mode == SculptMode::Raise {
+                        column.top + steps
+                    } else {
+                        column.top - steps
+                    }
+                }
+                SculptMode::Smooth => {
+                    let neighbours: Vec<i32> = (-1..=1)
+                        .flat_map(|nx| (-1..=1).map(move |nz| (key.0 + nx, key.1 + nz)))
+                        .filter_map(|pos| columns.get(&pos).map(|c| c.top))
+                        .collect();
+                    let average = neighbours.iter().sum::<i32>() as f32 / neighbours.len() as f32;
+                    let target = column.top as f32 + (average - column.top as f32) * weight;
+                    (target + noise * 0.5).round() as i32
+                }
+            };
+
+            let new_top = new_top.max(column.bottom);
+            if new_top != column.top {
+                changes.push((key, new_top));
+            }
+        }
+    }
+    changes
+}
+
+/// Actions that bring `column` at `key` to height `new_top`: full voxels of
+/// its top voxel's type stacked above it, or its voxels above `new_top`
+/// removed. Locked voxels and placements outside the allowed bounds are
+/// skipped.
+pub fn column_actions(
+    editor_state: &EditorState,
+    key: (i32, i32),
+    column: &Column,
+    new_top: i32,
+) -> Vec<EditorAction> {
+    let locks = editor_state.locked_areas();
+    if new_top < column.top {
+        return column
+            .voxels
+            .iter()
+            .filter(|voxel| voxel.pos.1 > new_top && !is_locked(&locks, voxel.pos))
+            .map(|voxel| EditorAction::RemoveVoxel {
+                pos: voxel.pos,
+                data: voxel.clone(),
+            })
+            .collect();
+    }
+
+    let Some(top) = column.top_voxel() else {
+        return Vec::new();
+    };
+    (column.top + 1..=new_top)
+        .map(|y| (key.0, y, key.1))
+        .filter(|&pos| !is_locked(&locks, pos) && editor_state.allows_placement_at(pos))
+        .map(|pos| EditorAction::PlaceVoxel {
+            pos,
+            data: VoxelData::new(pos, top.voxel_type, Some(SubVoxelPattern::Full)),
+        })
+        .collect()
+}
+
+/// Apply one dab to the map, re-meshing only the chunks it touched. Returns
+/// the actions applied.
+pub fn apply_dab(
+    editor_state: &mut EditorState,
+    brush: &Brush,
+    carry: &mut HashMap<(i32, i32), f32>,
+) -> Vec<EditorAction> {
+    // Smoothing looks one column past the brush edge
+    let reach = brush.radius.clamp(1, MAX_SCULPT_RADIUS) as i32 + 1;
+    let columns = scan_columns(&editor_state.current_map.world.voxels, brush.center, reach);
+
+    let actions: Vec<EditorAction> = dab_heights(&columns, brush, carry)
+        .into_iter()
+        .flat_map(|(key, new_top)| column_actions(editor_state, key, &columns[&key], new_top))
+        .collect();
+    if actions.is_empty() {
+        return actions;
+    }
+
+    // Placements are always above a column's top, so they never replace a voxel
+    let mut changed = Vec::with_capacity(actions.len());
+    for action in &actions {
+        match action {
+            EditorAction::RemoveVoxel { pos, .. } => {
+                editor_state.current_map.world.voxels.remove(*pos);
+                changed.push(*pos);
+            }
+            EditorAction::PlaceVoxel { pos, data } => {
+                editor_state.current_map.world.voxels.push(data.clone());
+                changed.push(*pos);
+            }
+            _ => {}
+        }
+    }
+    editor_state.mark_voxels_modified(changed);
+    actions
+}
+
+/// The brush stroke in progress.
+#[derive(Resource, Default)]
+pub struct SculptStroke {
+    /// Whether the brush is held down
+    pub active: bool,
+    /// Actions applied so far, pushed to history as one batch when the stroke ends
+    actions: Vec<EditorAction>,
+    /// Fractional height change carried between dabs, per column
+    carry: HashMap<(i32, i32), f32>,
+    /// Seconds until the next dab
+    cooldown: f32,
+    /// Dabs applied so far, used to vary the noise
+    dabs: u32,
+}
+
+impl SculptStroke {
+    /// End the stroke, recording everything it changed as one undo step.
+    pub fn finish(&mut self, history: &mut EditorHistory) {
+        let actions = std::mem::take(&mut self.actions);
+        if !actions.is_empty() {
+            history.push(EditorAction::Batch {
+                description: "Sculpt".to_string(),
+                actions,
+            });
+        }
+        *self = Self::default();
+    }
+}
+
+/// System to apply the sculpt brush while the left mouse button is held
+pub fn handle_sculpt_brush(
+    cursor_state: Res<CursorState>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut stroke: ResMut<SculptStroke>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    time: Res<Time>,
+    mut contexts: EguiContexts,
+) {
+    let EditorTool::Sculpt {
+        mode,
+        radius,
+        jitter,
+    } = editor_state.active_tool
+    else {
+        if stroke.active {
+            stroke.finish(&mut history);
+        }
+        return;
+    };
+
+    if stroke.active && !mouse_button.pressed(MouseButton::Left) {
+        stroke.finish(&mut history);
+        return;
+    }
+
+    let ctx = contexts.ctx_mut().expect("egui context");
+    if ctx.is_pointer_over_area() || ctx.is_using_pointer() {
+        return;
+    }
+
+    if !stroke.active {
+        if !mouse_button.just_pressed(MouseButton::Left) {
+            return;
+        }
+        stroke.active = true;
+    }
+
+    stroke.cooldown -= time.delta_secs();
+    if stroke.cooldown > 0.0 {
+        return;
+    }
+    stroke.cooldown = SCULPT_INTERVAL;
+
+    let Some((x, _, z)) = cursor_state.grid_pos else {
+        return;
+    };
+    let brush = Brush {
+        center: (x, z),
+        mode,
+        radius,
+        jitter,
+        seed: stroke.dabs,
+    };
+    stroke.dabs = stroke.dabs.wrapping_add(1);
+
+    let stroke = &mut *stroke;
+    let actions = apply_dab(&mut editor_state, &brush, &mut stroke.carry);
+    stroke.actions.extend(actions);
+}
+
+/// Draw the brush outline on the terrain under the cursor
+pub fn draw_sculpt_brush(
+    editor_state: Res<EditorState>,
+    cursor_state: Res<CursorState>,
+    mut gizmos: Gizmos,
+) {
+    let EditorTool::Sculpt { mode, radius, .. } = editor_state.active_tool else {
+        return;
+    };
+    let Some((x, y, z)) = cursor_state.grid_pos else {
+        return;
+    };
+    let color = match mode {
+        SculptMode::Raise => Color::srgb(0.3, 1.0, 0.4),
+        SculptMode::Lower => Color::srgb(1.0, 0.5, 0.2),
+        SculptMode::Smooth => Color::srgb(0.3, 0.8, 1.0),
+    };
+    let center = Vec3::new(x as f32, y as f32 + 0.52, z as f32);
+    gizmos.circle(
+        Isometry3d::new(center, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        radius.clamp(1, MAX_SCULPT_RADIUS) as f32 + 0.5,
+        color,
+    );
+}
+
+#[cfg(test)]
+mod tests;
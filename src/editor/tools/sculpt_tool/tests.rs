@@ -0,0 +1,146 @@
+use super::*;
+use crate::systems::game::components::VoxelType;
+
+/// A flat 9×9 patch of dirt topped with grass, centered on the origin.
+fn flat_terrain() -> EditorState {
+    let mut state = EditorState::default();
+    state.current_map.world.voxels = (-4..=4)
+        .flat_map(|x| (-4..=4).map(move |z| (x, z)))
+        .flat_map(|(x, z)| {
+            [
+                VoxelData::new((x, 0, z), VoxelType::Dirt, Some(SubVoxelPattern::Full)),
+                VoxelData::new((x, 1, z), VoxelType::Grass, Some(SubVoxelPattern::Full)),
+            ]
+        })
+        .collect();
+    state
+}
+
+fn top_at(state: &EditorState, x: i32, z: i32) -> Option<i32> {
+    state
+        .current_map
+        .world
+        .voxels
+        .iter()
+        .filter(|voxel| voxel.pos.0 == x && voxel.pos.2 == z)
+        .map(|voxel| voxel.pos.1)
+        .max()
+}
+
+fn brush(mode: SculptMode, radius: u32) -> Brush {
+    Brush {
+        center: (0, 0),
+        mode,
+        radius,
+        jitter: 0.0,
+        seed: 0,
+    }
+}
+
+#[test]
+fn falloff_is_full_in_the_middle_and_fades_out() {
+    assert_eq!(falloff(0.0, 3), 1.0);
+    assert!(falloff(1.0, 3) > falloff(2.0, 3));
+    assert!(falloff(3.0, 3) > 0.0);
+    assert_eq!(falloff(3.5, 3), 0.0);
+}
+
+#[test]
+fn noise_is_repeatable_and_bounded() {
+    assert_eq!(hash_noise(3, -7, 11), hash_noise(3, -7, 11));
+    assert_ne!(hash_noise(3, -7, 11), hash_noise(3, -7, 12));
+    for i in 0..200 {
+        let n = hash_noise(i, i * 31 - 50, i as u32);
+        assert!((-1.0..=1.0).contains(&n));
+    }
+}
+
+#[test]
+fn raising_builds_the_middle_faster_than_the_edge() {
+    let mut state = flat_terrain();
+    let mut carry = HashMap::new();
+    for _ in 0..3 {
+        apply_dab(&mut state, &brush(SculptMode::Raise, 3), &mut carry);
+    }
+
+    assert_eq!(top_at(&state, 0, 0), Some(4));
+    assert!(top_at(&state, 3, 0).unwrap() < 4);
+    assert_eq!(top_at(&state, 4, 4), Some(1), "outside the brush");
+
+    // New voxels copy the column's top type
+    let raised = state
+        .current_map
+        .world
+        .voxels
+        .iter()
+        .find(|voxel| voxel.pos == (0, 4, 0))
+        .unwrap();
+    assert_eq!(raised.voxel_type, VoxelType::Grass);
+}
+
+#[test]
+fn lowering_never_empties_a_column() {
+    let mut state = flat_terrain();
+    let mut carry = HashMap::new();
+    for _ in 0..5 {
+        apply_dab(&mut state, &brush(SculptMode::Lower, 2), &mut carry);
+    }
+    assert_eq!(top_at(&state, 0, 0), Some(0));
+    assert_eq!(top_at(&state, 4, 4), Some(1));
+}
+
+#[test]
+fn smoothing_pulls_a_spike_towards_its_neighbours() {
+    let mut state = flat_terrain();
+    state.current_map.world.voxels.extend(
+        (2..=9).map(|y| VoxelData::new((0, y, 0), VoxelType::Stone, Some(SubVoxelPattern::Full))),
+    );
+    let mut carry = HashMap::new();
+
+    apply_dab(&mut state, &brush(SculptMode::Smooth, 1), &mut carry);
+
+    let spike = top_at(&state, 0, 0).unwrap();
+    assert!(spike < 9, "spike should sink, got {}", spike);
+    assert!(top_at(&state, 1, 0).unwrap() >= 1);
+}
+
+#[test]
+fn dabs_only_mark_the_changed_voxels_for_rendering() {
+    let mut state = flat_terrain();
+    let actions = apply_dab(
+        &mut state,
+        &brush(SculptMode::Raise, 1),
+        &mut HashMap::new(),
+    );
+
+    assert!(!actions.is_empty());
+    assert!(state.is_modified);
+    assert!(
+        !state.render_dirty,
+        "a dab must not re-render the whole map"
+    );
+    assert!(state.dirty_voxels.contains(&(0, 2, 0)));
+    assert_eq!(state.dirty_voxels.len(), actions.len());
+}
+
+#[test]
+fn a_stroke_is_one_undo_step() {
+    let mut state = flat_terrain();
+    let before = state.current_map.world.voxels.len();
+    let mut history = EditorHistory::default();
+    let mut stroke = SculptStroke::default();
+
+    for _ in 0..4 {
+        let actions = apply_dab(&mut state, &brush(SculptMode::Raise, 2), &mut stroke.carry);
+        stroke.actions.extend(actions);
+    }
+    stroke.finish(&mut history);
+
+    assert_eq!(history.undo_count(), 1);
+    assert!(!stroke.active);
+
+    let undo = history.undo().unwrap();
+    crate::editor::shortcuts::apply_action(&undo.inverse(), &mut state);
+    assert_eq!(state.current_map.world.voxels.len(), before);
+    assert_eq!(top_at(&state, 0, 0), Some(1));
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+fn selection(positions: &[VoxelPos]) -> HashSet<VoxelPos> {
+    positions.iter().copied().collect()
+}
+
+#[test]
+fn single_voxel_has_six_faces_and_twelve_edges() {
+    let selected = selection(&[(0, 0, 0)]);
+    assert_eq!(selection_shell_faces(&selected).len(), 6);
+    assert_eq!(selection_outline_edges(&selected).len(), 12);
+}
+
+#[test]
+fn shared_faces_and_coplanar_edges_are_skipped() {
+    // A 2x1x1 bar outlines like a single box: 10 faces, and the box's 12
+    // edges in unit segments (the 4 long ones are split in two)
+    let bar = selection(&[(0, 0, 0), (1, 0, 0)]);
+    assert_eq!(selection_shell_faces(&bar).len(), 10);
+    assert_eq!(selection_outline_edges(&bar).len(), 16);
+
+    // A 3x3 floor is a single slab outline: two 12-unit rims and 4 corners
+    let floor: Vec<VoxelPos> = (0..3)
+        .flat_map(|x| (0..3).map(move |z| (x, 0, z)))
+        .collect();
+    let floor = selection(&floor);
+    assert_eq!(selection_shell_faces(&floor).len(), 9 * 2 + 12);
+    assert_eq!(selection_outline_edges(&floor).len(), 28);
+}
+
+#[test]
+fn concave_corner_edges_are_drawn_once() {
+    // L shape: 8-unit front and back rims plus one Z edge per corner,
+    // including the inner crease
+    let l_shape = selection(&[(0, 0, 0), (1, 0, 0), (0, 1, 0)]);
+    let edges = selection_outline_edges(&l_shape);
+    assert_eq!(edges.len(), 22);
+
+    // The crease along Z at (0.5, 0.5) is nudged into the empty voxel at (1, 1, 0)
+    let crease = edges
+        .iter()
+        .find(|[a, b]| (a.x - 0.5).abs() < 0.1 && (a.y - 0.5).abs() < 0.1 && a.z != b.z)
+        .expect("inner crease edge");
+    assert!(crease[0].x > 0.5 && crease[0].y > 0.5);
+}
+
+#[test]
+fn outline_is_deterministic() {
+    let selected = selection(&[(0, 0, 0), (1, 0, 0), (0, 1, 0), (5, 5, 5)]);
+    assert_eq!(
+        selection_outline_edges(&selected),
+        selection_outline_edges(&selected)
+    );
+}
@@ -13,7 +13,7 @@ pub use highlights::render_selection_highlights;
 pub use preview::{render_transform_preview, rotate_position};
 pub use selection::{handle_drag_selection, handle_selection};
 
-use crate::editor::camera::EditorCamera;
+use crate::editor::viewports::{viewport_camera, ViewportCamera, ViewportLayout};
 use crate::systems::game::map::format::VoxelData;
 use crate::systems::game::map::geometry::RotationAxis;
 use bevy::prelude::*;
@@ -22,16 +22,30 @@ use bevy::window::PrimaryWindow;
 /// Bundle of queries needed for viewport raycasting
 #[derive(bevy::ecs::system::SystemParam)]
 pub struct ViewportRaycast<'w, 's> {
-    pub camera: Single<'w, 's, (&'static Camera, &'static GlobalTransform), With<EditorCamera>>,
+    pub cameras: Query<
+        'w,
+        's,
+        (
+            &'static Camera,
+            &'static GlobalTransform,
+            &'static ViewportCamera,
+        ),
+    >,
+    pub layout: Res<'w, ViewportLayout>,
     pub window: Single<'w, 's, &'static Window, With<PrimaryWindow>>,
 }
 
-/// Marker component for selection highlight visuals
-#[derive(Component)]
-pub struct SelectionHighlight {
-    pub voxel_pos: (i32, i32, i32),
+impl ViewportRaycast<'_, '_> {
+    /// Camera of the viewport under the pointer
+    pub fn camera(&self) -> Option<(&Camera, &GlobalTransform)> {
+        viewport_camera(&self.layout, self.cameras)
+    }
 }
 
+/// Marker component for the merged selection highlight meshes
+#[derive(Component)]
+pub struct SelectionHighlight;
+
 /// Marker component for transform preview visuals
 #[derive(Component)]
 pub struct TransformPreview {
@@ -1,6 +1,7 @@
 //! Transform and rotation preview rendering.
 
 use super::{ActiveTransform, TransformMode, TransformPreview};
+use crate::editor::locks::is_locked;
 use crate::editor::state::EditorState;
 use crate::systems::game::map::format::{
     apply_orientation_matrix, axis_angle_to_matrix, multiply_matrices, world_dir_to_local,
@@ -67,6 +68,7 @@ fn spawn_move_previews(
     materials: &mut Assets<StandardMaterial>,
 ) {
     let offset = active_transform.current_offset;
+    let locks = editor_state.locked_areas();
     let original_positions: std::collections::HashSet<_> = active_transform
         .selected_voxels
         .iter()
@@ -82,12 +84,10 @@ fn spawn_move_previews(
             voxel.pos.2 + offset.z,
         );
 
-        let is_valid = !editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .any(|v| v.pos == new_pos && !original_positions.contains(&v.pos));
+        let is_valid = (!editor_state.current_map.world.voxels.contains(new_pos)
+            || original_positions.contains(&new_pos))
+            && !is_locked(&locks, voxel.pos)
+            && !is_locked(&locks, new_pos);
 
         let material = materials.add(StandardMaterial {
             base_color: if is_valid {
@@ -121,6 +121,7 @@ fn spawn_rotation_previews(
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<StandardMaterial>,
 ) {
+    let locks = editor_state.locked_areas();
     let original_positions: std::collections::HashSet<_> = active_transform
         .selected_voxels
         .iter()
@@ -148,12 +149,10 @@ fn spawn_rotation_previews(
             active_transform.rotation_angle,
         );
 
-        let is_valid = !editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .any(|v| v.pos == new_pos && !original_positions.contains(&v.pos));
+        let is_valid = (!editor_state.current_map.world.voxels.contains(new_pos)
+            || original_positions.contains(&new_pos))
+            && !is_locked(&locks, voxel.pos)
+            && !is_locked(&locks, new_pos);
 
         let pattern = voxel.pattern.unwrap_or(SubVoxelPattern::Full);
         let delta_matrix = axis_angle_to_matrix(
@@ -1,6 +1,7 @@
 //! Selection handling for voxels and entities.
 
 use super::{DragSelectState, UpdateSelectionHighlights, ViewportRaycast};
+use crate::editor::billboards::billboard_world_size;
 use crate::editor::cursor::CursorState;
 use crate::editor::state::{EditorState, EditorTool};
 use bevy::prelude::*;
@@ -56,25 +57,32 @@ pub fn handle_selection(
     }
 
     // Get mouse ray for entity selection
-    let (camera, camera_transform) = viewport.camera.into_inner();
-    let window = viewport.window.into_inner();
-    let Some(cursor_position) = window.cursor_position() else {
+    let Some((camera, camera_transform)) = viewport.camera() else {
+        return;
+    };
+    let Some(cursor_position) = viewport.window.cursor_position() else {
         return;
     };
     let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
         return;
     };
 
-    // First, try to select an entity using ray-sphere intersection
-    let entity_selection_radius = 0.5; // Radius for entity "hitbox"
+    // First, try to select an entity by its icon, using ray-sphere intersection
     let mut closest_entity_index: Option<usize> = None;
     let mut closest_distance = f32::MAX;
 
-    for (index, entity_data) in editor_state.current_map.entities.iter().enumerate() {
+    let entities = if editor_state.selection_filter.allows_entities() {
+        editor_state.current_map.entities.as_slice()
+    } else {
+        &[]
+    };
+    for (index, entity_data) in entities.iter().enumerate() {
         let (ex, ey, ez) = entity_data.position;
         let entity_pos = Vec3::new(ex, ey, ez);
 
-        // Ray-sphere intersection test
+        // Hitbox follows the icon's on-screen size, never smaller than one voxel
+        let icon_size = billboard_world_size(ray.origin.distance(entity_pos));
+        let entity_selection_radius = (icon_size * 0.5).max(0.5);
         if let Some(distance) = ray_sphere_intersection(&ray, entity_pos, entity_selection_radius) {
             if distance < closest_distance {
                 closest_distance = distance;
@@ -85,16 +93,15 @@ pub fn handle_selection(
 
     // If we found an entity, select/deselect it (no drag for entities)
     if let Some(entity_idx) = closest_entity_index {
-        // Clear voxel selection when selecting entities
-        editor_state.selected_voxels.clear();
-
-        if editor_state.selected_entities.contains(&entity_idx) {
-            editor_state.selected_entities.remove(&entity_idx);
-            info!("Deselected entity at index {}", entity_idx);
+        let entity_id = editor_state.current_map.entities[entity_idx].id;
+        if editor_state.selected_entities.contains(&entity_id) {
+            editor_state.selected_voxels.clear();
+            editor_state.selected_entities.remove(&entity_id);
+            info!("Deselected entity {}", entity_id);
         } else {
-            editor_state.selected_entities.clear(); // Single selection for now
-            editor_state.selected_entities.insert(entity_idx);
-            info!("Selected entity at index {}", entity_idx);
+            // Single selection for now; also clears the voxel selection
+            editor_state.select_entity(entity_idx);
+            info!("Selected entity {}", entity_id);
         }
 
         // Trigger highlight update
@@ -103,6 +110,10 @@ pub fn handle_selection(
     }
 
     // If no entity was clicked, try voxel selection
+    if !editor_state.selection_filter.allows_voxels() {
+        return;
+    }
+
     // Get cursor grid position
     let Some(grid_pos) = cursor_state.grid_pos else {
         return;
@@ -0,0 +1,338 @@
+//! Spline tool: lay voxels along a Catmull-Rom curve.
+//!
+//! Click to add control points at the cursor's placement position; drag a
+//! point to move it and right-click it to remove it. The curve and the voxels
+//! it will produce are previewed live. Enter writes the voxels into the map as
+//! one undo step; Backspace removes the last point and Escape discards the
+//! curve.
+//!
+//! The curve passes through every control point. Each sample is widened
+//! sideways (horizontally, perpendicular to the curve) to the tool's width, so
+//! paths, rivers and walls can follow any direction.
+
+use super::selection_tool::ViewportRaycast;
+use crate::editor::cursor::CursorState;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::renderer::RenderMapEvent;
+use crate::editor::shortcuts::apply_action;
+use crate::editor::state::{EditorState, EditorTool};
+use crate::systems::game::components::VoxelType;
+use crate::systems::game::map::format::{SubVoxelPattern, VoxelData};
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use std::collections::HashSet;
+
+/// Widest road the tool generates, in voxels
+pub const MAX_SPLINE_WIDTH: u32 = 9;
+
+/// Curve samples per unit of control polygon length
+const SAMPLES_PER_UNIT: f32 = 4.0;
+
+/// How close (in pixels) a click must be to grab a control point
+const POINT_PICK_RADIUS: f32 = 12.0;
+
+/// Most voxel outlines drawn in the preview
+const MAX_PREVIEW_CELLS: usize = 2000;
+
+/// Control points of the curve being edited.
+#[derive(Resource, Default)]
+pub struct SplineToolState {
+    pub points: Vec<(i32, i32, i32)>,
+    /// Control point being dragged
+    pub dragging: Option<usize>,
+}
+
+/// Event to generate voxels along the current curve
+#[derive(Message)]
+pub struct GenerateSplineEvent;
+
+fn to_vec3(pos: (i32, i32, i32)) -> Vec3 {
+    Vec3::new(pos.0 as f32, pos.1 as f32, pos.2 as f32)
+}
+
+fn to_cell(point: Vec3) -> (i32, i32, i32) {
+    (
+        point.x.round() as i32,
+        point.y.round() as i32,
+        point.z.round() as i32,
+    )
+}
+
+/// Uniform Catmull-Rom position at `t` in `[0, 1]` between `p1` and `p2`.
+pub fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * ((2.0 * p1)
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+/// Points along the curve through `points`, densely enough that consecutive
+/// samples are well under a voxel apart. The end points are repeated so the
+/// curve starts and ends exactly on them.
+pub fn sample_curve(points: &[(i32, i32, i32)]) -> Vec<Vec3> {
+    let points: Vec<Vec3> = points.iter().copied().map(to_vec3).collect();
+    let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    if points.len() == 1 {
+        return vec![first];
+    }
+
+    let mut samples = Vec::new();
+    for i in 0..points.len() - 1 {
+        let p0 = if i == 0 { first } else { points[i - 1] };
+        let p1 = points[i];
+        let p2 = points[i + 1];
+        let p3 = points.get(i + 2).copied().unwrap_or(last);
+        let steps = ((p2 - p1).length() * SAMPLES_PER_UNIT).ceil().max(1.0) as usize;
+        samples.extend((0..steps).map(|s| catmull_rom(p0, p1, p2, p3, s as f32 / steps as f32)));
+    }
+    samples.push(last);
+    samples
+}
+
+/// Voxel positions covered by the curve through `points` at `width` voxels
+/// wide, in curve order without duplicates.
+pub fn spline_cells(points: &[(i32, i32, i32)], width: u32) -> Vec<(i32, i32, i32)> {
+    let samples = sample_curve(points);
+    let width = width.clamp(1, MAX_SPLINE_WIDTH);
+    let half = (width - 1) as f32 / 2.0;
+
+    let mut seen = HashSet::new();
+    let mut cells = Vec::new();
+    for (i, &sample) in samples.iter().enumerate() {
+        // Direction of travel from the neighbouring samples
+        let before = samples[i.saturating_sub(1)];
+        let after = samples[(i + 1).min(samples.len() - 1)];
+        let tangent = Vec3::new(after.x - before.x, 0.0, after.z - before.z);
+        let side = Vec3::new(-tangent.z, 0.0, tangent.x).normalize_or(Vec3::X);
+
+        for k in 0..width {
+            let cell = to_cell(sample + side * (k as f32 - half));
+            if seen.insert(cell) {
+                cells.push(cell);
+            }
+        }
+    }
+    cells
+}
+
+/// Actions placing `voxel_type`/`pattern` voxels at `cells`, replacing any
+/// voxels already there (removed first so undo restores them) and using the
+/// orientation index `rotation`. Cells outside the allowed bounds are skipped.
+pub fn spline_actions(
+    editor_state: &EditorState,
+    cells: &[(i32, i32, i32)],
+    voxel_type: VoxelType,
+    pattern: SubVoxelPattern,
+    rotation: Option<usize>,
+) -> Vec<EditorAction> {
+    let mut actions = Vec::new();
+    for &pos in cells {
+        if !editor_state.allows_placement_at(pos) {
+            continue;
+        }
+        if let Some(existing) = editor_state.current_map.world.voxels.get(pos) {
+            actions.push(EditorAction::RemoveVoxel {
+                pos,
+                data: existing.clone(),
+            });
+        }
+        actions.push(EditorAction::PlaceVoxel {
+            pos,
+            data: VoxelData {
+                pos,
+                voxel_type,
+                pattern: Some(pattern),
+                rotation,
+                rotation_state: None,
+                group: None,
+            },
+        });
+    }
+    actions
+}
+
+/// System to add, drag and remove control points and run the keyboard commands
+#[allow(clippy::too_many_arguments)]
+pub fn handle_spline_input(
+    cursor_state: Res<CursorState>,
+    editor_state: Res<EditorState>,
+    mut spline: ResMut<SplineToolState>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut contexts: EguiContexts,
+    viewport: ViewportRaycast,
+    mut generate_events: MessageWriter<GenerateSplineEvent>,
+) {
+    if !matches!(editor_state.active_tool, EditorTool::Spline { .. }) {
+        spline.dragging = None;
+        return;
+    }
+    let ctx = contexts.ctx_mut().expect("egui context");
+
+    if !ctx.wants_keyboard_input() && !crate::editor::shortcuts::modifier_pressed(&keyboard) {
+        if keyboard.just_pressed(KeyCode::Enter) {
+            generate_events.write(GenerateSplineEvent);
+        }
+        if keyboard.just_pressed(KeyCode::Backspace) {
+            spline.points.pop();
+        }
+        if keyboard.just_pressed(KeyCode::Escape) {
+            spline.points.clear();
+        }
+    }
+
+    if mouse_button.just_released(MouseButton::Left) {
+        spline.dragging = None;
+    }
+
+    // Keep dragging even if the pointer passes over a panel
+    if let Some(index) = spline.dragging {
+        if let Some(pos) = cursor_state.placement_grid_pos {
+            if spline.points.get(index).is_some_and(|p| *p != pos) {
+                spline.points[index] = pos;
+            }
+        }
+        return;
+    }
+
+    if ctx.is_pointer_over_area() || ctx.is_using_pointer() {
+        return;
+    }
+
+    let Some((camera, camera_transform)) = viewport.camera() else {
+        return;
+    };
+    let hovered_point = viewport.window.cursor_position().and_then(|cursor| {
+        spline
+            .points
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pos)| {
+                let screen = camera
+                    .world_to_viewport(camera_transform, to_vec3(*pos))
+                    .ok()?;
+                Some((i, screen.distance(cursor)))
+            })
+            .filter(|(_, distance)| *distance <= POINT_PICK_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    });
+
+    if mouse_button.just_pressed(MouseButton::Left) {
+        if let Some(index) = hovered_point {
+            spline.dragging = Some(index);
+        } else if let Some(pos) = cursor_state.placement_grid_pos {
+            spline.points.push(pos);
+        }
+    }
+    if mouse_button.just_pressed(MouseButton::Right) {
+        if let Some(index) = hovered_point {
+            spline.points.remove(index);
+        }
+    }
+}
+
+/// Lay voxels along the curve through `points` as a single undo step.
+/// Returns the number of voxels placed.
+pub fn lay_spline(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    points: &[(i32, i32, i32)],
+    voxel_type: VoxelType,
+    pattern: SubVoxelPattern,
+    width: u32,
+) -> usize {
+    let cells = spline_cells(points, width);
+    let rotation = editor_state.placement_rotation();
+    let actions = spline_actions(editor_state, &cells, voxel_type, pattern, rotation);
+    let placed = actions
+        .iter()
+        .filter(|action| matches!(action, EditorAction::PlaceVoxel { .. }))
+        .count();
+    if placed == 0 {
+        return 0;
+    }
+    for action in &actions {
+        apply_action(action, editor_state);
+    }
+    history.push(EditorAction::Batch {
+        description: "Spline".to_string(),
+        actions,
+    });
+    editor_state.mark_modified();
+    placed
+}
+
+/// System to write the curve's voxels into the map as one undo step
+pub fn handle_generate_spline(
+    mut events: MessageReader<GenerateSplineEvent>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut spline: ResMut<SplineToolState>,
+    mut render_events: MessageWriter<RenderMapEvent>,
+) {
+    for _event in events.read() {
+        let EditorTool::Spline {
+            voxel_type,
+            pattern,
+            width,
+        } = editor_state.active_tool
+        else {
+            continue;
+        };
+        let placed = lay_spline(
+            &mut editor_state,
+            &mut history,
+            &spline.points,
+            voxel_type,
+            pattern,
+            width,
+        );
+        if placed == 0 {
+            continue;
+        }
+        render_events.write(RenderMapEvent);
+        info!("Laid {} voxels along the spline", placed);
+        spline.points.clear();
+        spline.dragging = None;
+    }
+}
+
+/// Draw the control points, the curve and the voxels it will produce
+pub fn draw_spline_preview(
+    editor_state: Res<EditorState>,
+    spline: Res<SplineToolState>,
+    mut gizmos: Gizmos,
+) {
+    let EditorTool::Spline { width, .. } = editor_state.active_tool else {
+        return;
+    };
+
+    for (i, pos) in spline.points.iter().enumerate() {
+        let color = if spline.dragging == Some(i) {
+            Color::srgb(1.0, 0.9, 0.2)
+        } else {
+            Color::srgb(1.0, 0.5, 0.1)
+        };
+        gizmos.sphere(Isometry3d::from_translation(to_vec3(*pos)), 0.25, color);
+    }
+    gizmos.linestrip(sample_curve(&spline.points), Color::srgb(1.0, 0.5, 0.1));
+
+    let cell_color = Color::srgba(1.0, 1.0, 1.0, 0.35);
+    for cell in spline_cells(&spline.points, width)
+        .into_iter()
+        .take(MAX_PREVIEW_CELLS)
+    {
+        gizmos.cube(
+            Transform::from_translation(to_vec3(cell)).with_scale(Vec3::splat(0.98)),
+            cell_color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+use crate::editor::state::BoundsEnforcement;
+
+fn voxel_at(state: &EditorState, pos: (i32, i32, i32)) -> Option<&VoxelData> {
+    state.current_map.world.voxels.get(pos)
+}
+
+fn empty_state() -> EditorState {
+    let mut state = EditorState::default();
+    state.current_map.world.voxels.clear();
+    state
+}
+
+#[test]
+fn curve_passes_through_every_control_point() {
+    let points = [(0, 0, 0), (4, 0, 2), (8, 1, -3), (12, 0, 0)];
+    let samples = sample_curve(&points);
+    for point in points {
+        let target = to_vec3(point);
+        assert!(
+            samples.iter().any(|s| s.distance(target) < 1e-4),
+            "curve misses {:?}",
+            point
+        );
+    }
+    assert_eq!(samples.first(), Some(&Vec3::ZERO));
+    assert_eq!(samples.last(), Some(&Vec3::new(12.0, 0.0, 0.0)));
+}
+
+#[test]
+fn samples_are_closer_than_a_voxel() {
+    let samples = sample_curve(&[(0, 0, 0), (10, 0, 7), (-5, 3, 12)]);
+    for pair in samples.windows(2) {
+        assert!(pair[0].distance(pair[1]) < 0.5);
+    }
+}
+
+#[test]
+fn two_points_make_a_straight_line() {
+    assert_eq!(
+        spline_cells(&[(0, 0, 0), (4, 0, 0)], 1),
+        vec![(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0), (4, 0, 0)]
+    );
+    assert!(spline_cells(&[], 3).is_empty());
+    assert_eq!(spline_cells(&[(2, 1, 2)], 1), vec![(2, 1, 2)]);
+}
+
+#[test]
+fn width_spreads_sideways_without_duplicates() {
+    let cells = spline_cells(&[(0, 0, 0), (0, 0, 6)], 3);
+    assert_eq!(cells.len(), 7 * 3);
+    for z in 0..=6 {
+        for x in -1..=1 {
+            assert!(cells.contains(&(x, 0, z)), "missing {:?}", (x, 0, z));
+        }
+    }
+    let unique: HashSet<_> = cells.iter().collect();
+    assert_eq!(unique.len(), cells.len());
+}
+
+#[test]
+fn laying_a_spline_is_one_undo_step() {
+    let mut state = empty_state();
+    state.current_map.world.voxels.push(VoxelData {
+        pos: (2, 0, 0),
+        voxel_type: VoxelType::Grass,
+        pattern: Some(SubVoxelPattern::Full),
+        rotation: None,
+        rotation_state: None,
+        group: None,
+    });
+    let mut history = EditorHistory::default();
+
+    let placed = lay_spline(
+        &mut state,
+        &mut history,
+        &[(0, 0, 0), (4, 0, 0)],
+        VoxelType::Stone,
+        SubVoxelPattern::Full,
+        1,
+    );
+    assert_eq!(placed, 5);
+    assert_eq!(history.undo_count(), 1);
+    assert!(state.is_modified);
+    assert_eq!(state.current_map.world.voxels.len(), 5);
+    assert_eq!(
+        voxel_at(&state, (2, 0, 0)).unwrap().voxel_type,
+        VoxelType::Stone
+    );
+
+    let undo = history.undo().expect("spline is one undo step");
+    apply_action(&undo.inverse(), &mut state);
+    assert_eq!(state.current_map.world.voxels.len(), 1);
+    assert_eq!(
+        voxel_at(&state, (2, 0, 0)).unwrap().voxel_type,
+        VoxelType::Grass
+    );
+}
+
+#[test]
+fn clamped_bounds_skip_cells_outside_the_map() {
+    let mut state = empty_state();
+    state.bounds_enforcement = BoundsEnforcement::Clamp;
+    let mut history = EditorHistory::default();
+
+    let placed = lay_spline(
+        &mut state,
+        &mut history,
+        &[(-20, 0, -20), (-30, 0, -20)],
+        VoxelType::Stone,
+        SubVoxelPattern::Full,
+        1,
+    );
+    assert_eq!(placed, 0);
+    assert_eq!(history.undo_count(), 0);
+    assert!(state.current_map.world.voxels.is_empty());
+}
@@ -0,0 +1,442 @@
+//! Staircase tool: build a walkable staircase between two voxels.
+//!
+//! Click the voxel to start from, then the voxel to end on. The tool plans a
+//! path that climbs one voxel per staircase block, laying floor blocks where
+//! it has to walk level, and detours around voxels that are in the way. The
+//! plan is previewed live; Enter builds it as one undo step and Escape clears
+//! the points.
+//!
+//! Planning always runs from the lower end to the higher one. Each staircase
+//! block is rotated to ascend in the direction of travel, and the block after
+//! it continues in that direction, since stairs are only left from their high
+//! side. Every step keeps two voxels of headroom above the walking surface.
+
+use super::selection_tool::ViewportRaycast;
+use crate::editor::cursor::CursorState;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::locks::is_locked;
+use crate::editor::renderer::RenderMapEvent;
+use crate::editor::shortcuts::apply_action;
+use crate::editor::state::{EditorState, EditorTool};
+use crate::systems::game::components::VoxelType;
+use crate::systems::game::map::format::{
+    axis_angle_to_matrix, find_or_insert_orientation, SubVoxelPattern, VoxelData,
+};
+use crate::systems::game::map::geometry::RotationAxis;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Horizontal directions as (dx, dz): +X, -Z, -X, +Z.
+///
+/// Index `i` is reached from +X by `i` quarter turns about Y, which is the
+/// rotation that makes a staircase (ascending +X) ascend that way.
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, -1), (-1, 0), (0, 1)];
+
+/// Extra columns around the end points the path may detour through
+const DETOUR_MARGIN: i32 = 8;
+
+/// Most search states expanded before giving up
+const MAX_EXPANSIONS: usize = 200_000;
+
+/// Cost of one step; turning adds half a step so straight flights win
+const STEP_COST: u32 = 2;
+const TURN_COST: u32 = 1;
+
+/// A block the staircase places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StairPiece {
+    /// A full block to walk over
+    Floor,
+    /// A staircase block ascending in `DIRECTIONS[direction]`
+    Stair { direction: usize },
+}
+
+/// Blocks of a staircase in walking order, with where each goes.
+pub type StairPlan = Vec<((i32, i32, i32), StairPiece)>;
+
+/// How the search reached a node, and the block that step places.
+type CameFrom = HashMap<Node, (Node, Option<((i32, i32, i32), StairPiece)>)>;
+
+/// Start and end voxels picked with the tool, and the plan between them.
+#[derive(Resource, Default)]
+pub struct StaircaseToolState {
+    pub start: Option<(i32, i32, i32)>,
+    pub end: Option<(i32, i32, i32)>,
+    /// Blocks of the planned staircase: `None` until planned, empty when no
+    /// path fits
+    pub plan: Option<StairPlan>,
+}
+
+impl StaircaseToolState {
+    /// Pick the next end point: the first click sets the start, the second
+    /// the end, and a third starts over.
+    pub fn pick(&mut self, pos: (i32, i32, i32)) {
+        if self.start.is_none() || self.end.is_some() {
+            self.start = Some(pos);
+            self.end = None;
+        } else {
+            self.end = Some(pos);
+        }
+        self.plan = None;
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Event to build the planned staircase
+#[derive(Message)]
+pub struct BuildStaircaseEvent;
+
+/// Search state: standing on column (x, z) with feet at height `h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Node {
+    x: i32,
+    z: i32,
+    h: i32,
+    /// Direction of the step that got here (4 at the start)
+    heading: usize,
+    /// Whether that step climbed a staircase, so the next must go straight on
+    on_stair: bool,
+}
+
+/// Plan a staircase from standing on `start` to standing on `end`.
+///
+/// `is_solid` reports existing voxels and `can_place` whether a block may
+/// be added at a position. Returns the blocks to place in walking order from
+/// the lower end, or `None` when no path fits.
+pub fn plan_staircase(
+    start: (i32, i32, i32),
+    end: (i32, i32, i32),
+    is_solid: impl Fn((i32, i32, i32)) -> bool,
+    can_place: impl Fn((i32, i32, i32)) -> bool,
+) -> Option<StairPlan> {
+    let (low, high) = if start.1 <= end.1 {
+        (start, end)
+    } else {
+        (end, start)
+    };
+    let goal_h = high.1 + 1;
+    let rise = goal_h - (low.1 + 1);
+    let margin = DETOUR_MARGIN + rise;
+    let (min_x, max_x) = (low.0.min(high.0) - margin, low.0.max(high.0) + margin);
+    let (min_z, max_z) = (low.2.min(high.2) - margin, low.2.max(high.2) + margin);
+
+    let clear = |pos: (i32, i32, i32)| !is_solid(pos);
+    let heuristic = |node: &Node| {
+        let distance = (node.x - high.0).abs() + (node.z - high.2).abs();
+        distance.max(goal_h - node.h) as u32 * STEP_COST
+    };
+
+    let origin = Node {
+        x: low.0,
+        z: low.2,
+        h: low.1 + 1,
+        heading: DIRECTIONS.len(),
+        on_stair: false,
+    };
+    let mut open = BinaryHeap::new();
+    let mut cost: HashMap<Node, u32> = HashMap::new();
+    let mut came_from: CameFrom = HashMap::new();
+    cost.insert(origin, 0);
+    open.push(Reverse((heuristic(&origin), origin)));
+
+    let mut expansions = 0;
+    let goal = loop {
+        let Reverse((_, node)) = open.pop()?;
+        if node.x == high.0 && node.z == high.2 && node.h == goal_h && !node.on_stair {
+            break node;
+        }
+        expansions += 1;
+        if expansions > MAX_EXPANSIONS {
+            return None;
+        }
+        let node_cost = cost[&node];
+
+        for (direction, (dx, dz)) in DIRECTIONS.into_iter().enumerate() {
+            if node.on_stair && direction != node.heading {
+                continue;
+            }
+            let (x, z) = (node.x + dx, node.z + dz);
+            if x < min_x || x > max_x || z < min_z || z > max_z {
+                continue;
+            }
+
+            // Walk level onto (x, z), laying a floor block if there is none
+            let floor = (x, node.h - 1, z);
+            let level = if clear((x, node.h, z)) && clear((x, node.h + 1, z)) {
+                if is_solid(floor) {
+                    Some((node.h, None))
+                } else if can_place(floor) {
+                    Some((node.h, Some((floor, StairPiece::Floor))))
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            // Climb a staircase block at (x, z) towards the goal height
+            let stair = (x, node.h, z);
+            let climb = (node.h < goal_h
+                && clear(stair)
+                && can_place(stair)
+                && clear((x, node.h + 1, z))
+                && clear((x, node.h + 2, z)))
+            .then_some((node.h + 1, Some((stair, StairPiece::Stair { direction }))));
+
+            for (h, piece) in [level, climb].into_iter().flatten() {
+                let next = Node {
+                    x,
+                    z,
+                    h,
+                    heading: direction,
+                    on_stair: h > node.h,
+                };
+                if crosses_path(node, &came_from, next, piece.map(|(pos, _)| pos)) {
+                    continue;
+                }
+                let turn = node.heading != DIRECTIONS.len() && node.heading != direction;
+                let next_cost = node_cost + STEP_COST + if turn { TURN_COST } else { 0 };
+                if cost.get(&next).is_some_and(|&c| c <= next_cost) {
+                    continue;
+                }
+                cost.insert(next, next_cost);
+                came_from.insert(next, (node, piece));
+                open.push(Reverse((next_cost + heuristic(&next), next)));
+            }
+        }
+    };
+
+    let mut pieces = Vec::new();
+    let mut node = goal;
+    while let Some(&(previous, piece)) = came_from.get(&node) {
+        pieces.extend(piece);
+        node = previous;
+    }
+    if !is_solid(low) && can_place(low) {
+        pieces.push((low, StairPiece::Floor));
+    }
+    pieces.reverse();
+
+    Some(pieces)
+}
+
+/// Whether stepping to `next`, placing `piece`, would run into the path that
+/// led to `node`: a block in the headroom of an earlier step, or an earlier
+/// block in the headroom of this one.
+fn crosses_path(
+    node: Node,
+    came_from: &CameFrom,
+    next: Node,
+    piece: Option<(i32, i32, i32)>,
+) -> bool {
+    let headroom = |step: Node| [(step.x, step.h, step.z), (step.x, step.h + 1, step.z)];
+    let next_headroom = headroom(next);
+    let mut step = node;
+    loop {
+        if piece.is_some_and(|pos| headroom(step).contains(&pos)) {
+            return true;
+        }
+        let Some(&(previous, placed)) = came_from.get(&step) else {
+            return false;
+        };
+        if let Some((pos, _)) = placed {
+            if next_headroom.contains(&pos) || piece == Some(pos) {
+                return true;
+            }
+        }
+        step = previous;
+    }
+}
+
+/// Plan a staircase between `start` and `end` in the current map.
+pub fn plan_in_map(
+    editor_state: &EditorState,
+    start: (i32, i32, i32),
+    end: (i32, i32, i32),
+) -> Option<StairPlan> {
+    let voxels = &editor_state.current_map.world.voxels;
+    let locks = editor_state.locked_areas();
+    plan_staircase(
+        start,
+        end,
+        |pos| voxels.contains(pos),
+        |pos| !is_locked(&locks, pos) && editor_state.allows_placement_at(pos),
+    )
+}
+
+/// Build `pieces` out of `voxel_type` as a single undo step.
+/// Returns the number of voxels placed.
+pub fn build_staircase(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    pieces: &[((i32, i32, i32), StairPiece)],
+    voxel_type: VoxelType,
+) -> usize {
+    if pieces.is_empty() {
+        return 0;
+    }
+    let actions: Vec<EditorAction> = pieces
+        .iter()
+        .map(|&(pos, piece)| {
+            let (pattern, rotation) = match piece {
+                StairPiece::Floor => (SubVoxelPattern::Full, None),
+                StairPiece::Stair { direction: 0 } => (SubVoxelPattern::Staircase, None),
+                StairPiece::Stair { direction } => (
+                    SubVoxelPattern::Staircase,
+                    Some(find_or_insert_orientation(
+                        &mut editor_state.current_map.orientations,
+                        axis_angle_to_matrix(RotationAxis::Y, direction as i32),
+                    )),
+                ),
+            };
+            EditorAction::PlaceVoxel {
+                pos,
+                data: VoxelData {
+                    pos,
+                    voxel_type,
+                    pattern: Some(pattern),
+                    rotation,
+                    rotation_state: None,
+                    group: None,
+                },
+            }
+        })
+        .collect();
+
+    for action in &actions {
+        apply_action(action, editor_state);
+    }
+    history.push(EditorAction::Batch {
+        description: "Staircase".to_string(),
+        actions,
+    });
+    editor_state.mark_modified();
+    pieces.len()
+}
+
+/// System to pick the end points and run the keyboard commands
+#[allow(clippy::too_many_arguments)]
+pub fn handle_staircase_input(
+    cursor_state: Res<CursorState>,
+    editor_state: Res<EditorState>,
+    mut staircase: ResMut<StaircaseToolState>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut contexts: EguiContexts,
+    viewport: ViewportRaycast,
+    mut build_events: MessageWriter<BuildStaircaseEvent>,
+) {
+    if !matches!(editor_state.active_tool, EditorTool::Staircase { .. }) {
+        return;
+    }
+    let ctx = contexts.ctx_mut().expect("egui context");
+
+    if !ctx.wants_keyboard_input() && !crate::editor::shortcuts::modifier_pressed(&keyboard) {
+        if keyboard.just_pressed(KeyCode::Enter) {
+            build_events.write(BuildStaircaseEvent);
+        }
+        if keyboard.just_pressed(KeyCode::Escape) {
+            staircase.clear();
+        }
+    }
+
+    if ctx.is_pointer_over_area() || ctx.is_using_pointer() || viewport.camera().is_none() {
+        return;
+    }
+    if mouse_button.just_pressed(MouseButton::Left) {
+        if let Some(pos) = cursor_state.grid_pos {
+            staircase.pick(pos);
+        }
+    }
+
+    if let (Some(start), Some(end), None) = (staircase.start, staircase.end, &staircase.plan) {
+        let plan = plan_in_map(&editor_state, start, end);
+        if plan.is_none() {
+            warn!(
+                "No walkable staircase fits between {:?} and {:?}",
+                start, end
+            );
+        }
+        staircase.plan = Some(plan.unwrap_or_default());
+    }
+}
+
+/// System to build the planned staircase as one undo step
+pub fn handle_build_staircase(
+    mut events: MessageReader<BuildStaircaseEvent>,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut staircase: ResMut<StaircaseToolState>,
+    mut render_events: MessageWriter<RenderMapEvent>,
+) {
+    for _event in events.read() {
+        let EditorTool::Staircase { voxel_type } = editor_state.active_tool else {
+            continue;
+        };
+        let (Some(start), Some(end)) = (staircase.start, staircase.end) else {
+            continue;
+        };
+        // Replan in case the map changed since the preview
+        let Some(pieces) = plan_in_map(&editor_state, start, end) else {
+            continue;
+        };
+        let placed = build_staircase(&mut editor_state, &mut history, &pieces, voxel_type);
+        if placed == 0 {
+            continue;
+        }
+        render_events.write(RenderMapEvent);
+        info!("Built a staircase of {} voxels", placed);
+        staircase.clear();
+    }
+}
+
+/// Draw the end points and the blocks the staircase will place
+pub fn draw_staircase_preview(
+    editor_state: Res<EditorState>,
+    staircase: Res<StaircaseToolState>,
+    mut gizmos: Gizmos,
+) {
+    if !matches!(editor_state.active_tool, EditorTool::Staircase { .. }) {
+        return;
+    }
+    let to_vec3 = |pos: (i32, i32, i32)| Vec3::new(pos.0 as f32, pos.1 as f32, pos.2 as f32);
+    let endpoint_color = Color::srgb(1.0, 0.5, 0.1);
+    for pos in [staircase.start, staircase.end].into_iter().flatten() {
+        gizmos.cube(
+            Transform::from_translation(to_vec3(pos)).with_scale(Vec3::splat(1.04)),
+            endpoint_color,
+        );
+    }
+
+    let Some(pieces) = &staircase.plan else {
+        return;
+    };
+    if pieces.is_empty() {
+        // No path: a red line between the end points
+        if let (Some(start), Some(end)) = (staircase.start, staircase.end) {
+            gizmos.line(to_vec3(start), to_vec3(end), Color::srgb(1.0, 0.2, 0.2));
+        }
+        return;
+    }
+    for &(pos, piece) in pieces {
+        let center = to_vec3(pos);
+        gizmos.cube(
+            Transform::from_translation(center).with_scale(Vec3::splat(0.98)),
+            Color::srgba(1.0, 1.0, 1.0, 0.35),
+        );
+        if let StairPiece::Stair { direction } = piece {
+            // Arrow up the stairs
+            let (dx, dz) = DIRECTIONS[direction];
+            let up = Vec3::new(dx as f32, 1.0, dz as f32) * 0.4;
+            gizmos.arrow(center - up, center + up, Color::srgb(0.3, 0.9, 1.0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
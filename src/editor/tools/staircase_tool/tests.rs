@@ -0,0 +1,137 @@
+use super::*;
+use std::collections::HashSet;
+
+fn plan(
+    start: (i32, i32, i32),
+    end: (i32, i32, i32),
+    solid: &HashSet<(i32, i32, i32)>,
+) -> Option<StairPlan> {
+    plan_staircase(start, end, |pos| solid.contains(&pos), |_| true)
+}
+
+/// Walk the planned blocks from `low`, checking every step is a level walk or
+/// a one-voxel climb up a staircase facing the way it is climbed.
+fn assert_walkable(
+    low: (i32, i32, i32),
+    high: (i32, i32, i32),
+    pieces: &[((i32, i32, i32), StairPiece)],
+) {
+    let mut feet = (low.0, low.1 + 1, low.2);
+    for &(pos, piece) in pieces {
+        match piece {
+            StairPiece::Floor if pos == low => continue,
+            StairPiece::Floor => {
+                assert_eq!(pos.1, feet.1 - 1, "floor {:?} off the walking level", pos);
+                assert_eq!((pos.0 - feet.0).abs() + (pos.2 - feet.2).abs(), 1);
+                feet = (pos.0, feet.1, pos.2);
+            }
+            StairPiece::Stair { direction } => {
+                let (dx, dz) = DIRECTIONS[direction];
+                assert_eq!((pos.0, pos.1, pos.2), (feet.0 + dx, feet.1, feet.2 + dz));
+                feet = (pos.0, feet.1 + 1, pos.2);
+            }
+        }
+    }
+    assert_eq!(feet.1, high.1 + 1);
+}
+
+#[test]
+fn straight_flight_climbs_one_voxel_per_stair() {
+    let solid: HashSet<_> = [(0, 0, 0), (6, 3, 0)].into_iter().collect();
+    let pieces = plan((0, 0, 0), (6, 3, 0), &solid).expect("path");
+
+    let stairs: Vec<_> = pieces
+        .iter()
+        .filter(|(_, piece)| matches!(piece, StairPiece::Stair { .. }))
+        .collect();
+    assert_eq!(stairs.len(), 3);
+    assert!(stairs
+        .iter()
+        .all(|(_, piece)| *piece == StairPiece::Stair { direction: 0 }));
+    assert_walkable((0, 0, 0), (6, 3, 0), &pieces);
+}
+
+#[test]
+fn descending_plans_from_the_lower_end() {
+    let solid: HashSet<_> = [(0, 4, 0), (0, 0, -7)].into_iter().collect();
+    let pieces = plan((0, 4, 0), (0, 0, -7), &solid).expect("path");
+    assert_walkable((0, 0, -7), (0, 4, 0), &pieces);
+    assert!(pieces
+        .iter()
+        .any(|(_, piece)| *piece == StairPiece::Stair { direction: 3 }));
+}
+
+#[test]
+fn steep_climb_turns_to_fit() {
+    // Four up but only two across: the flight has to turn back on itself
+    let solid: HashSet<_> = [(0, 0, 0), (2, 4, 0)].into_iter().collect();
+    let pieces = plan((0, 0, 0), (2, 4, 0), &solid).expect("path");
+    assert_walkable((0, 0, 0), (2, 4, 0), &pieces);
+}
+
+#[test]
+fn detours_around_a_wall() {
+    let mut solid: HashSet<_> = [(0, 0, 0), (8, 2, 0)].into_iter().collect();
+    for y in 0..8 {
+        for z in -2..=2 {
+            solid.insert((4, y, z));
+        }
+    }
+    let pieces = plan((0, 0, 0), (8, 2, 0), &solid).expect("path");
+    assert!(pieces.iter().all(|(pos, _)| !solid.contains(pos)));
+    assert!(pieces.iter().any(|((_, _, z), _)| z.abs() > 2));
+    assert_walkable((0, 0, 0), (8, 2, 0), &pieces);
+}
+
+#[test]
+fn enclosed_end_has_no_path() {
+    let mut solid: HashSet<_> = [(0, 0, 0)].into_iter().collect();
+    for x in 4..=8 {
+        for y in 0..=6 {
+            for z in -2..=2 {
+                solid.insert((x, y, z));
+            }
+        }
+    }
+    // The end voxel is buried: nothing can stand on it
+    assert!(plan((0, 0, 0), (6, 2, 0), &solid).is_none());
+}
+
+#[test]
+fn build_places_rotated_stairs_as_one_undo_step() {
+    let mut state = EditorState::default();
+    state.current_map.world.voxels.clear();
+    let mut history = EditorHistory::new();
+    let pieces = [
+        ((1, 1, 0), StairPiece::Stair { direction: 0 }),
+        ((1, 2, -1), StairPiece::Stair { direction: 1 }),
+        ((1, 2, -2), StairPiece::Floor),
+    ];
+
+    let placed = build_staircase(&mut state, &mut history, &pieces, VoxelType::Stone);
+
+    assert_eq!(placed, 3);
+    assert_eq!(history.undo_count(), 1);
+    let voxels = &state.current_map.world.voxels;
+    let first = voxels.get((1, 1, 0)).unwrap();
+    assert_eq!(first.pattern, Some(SubVoxelPattern::Staircase));
+    assert_eq!(first.rotation, None);
+    let turned = voxels.get((1, 2, -1)).unwrap();
+    let matrix = state.current_map.orientations[turned.rotation.unwrap()];
+    assert_eq!(matrix, axis_angle_to_matrix(RotationAxis::Y, 1));
+    assert_eq!(
+        voxels.get((1, 2, -2)).unwrap().pattern,
+        Some(SubVoxelPattern::Full)
+    );
+}
+
+#[test]
+fn picking_cycles_start_then_end() {
+    let mut state = StaircaseToolState::default();
+    state.pick((0, 0, 0));
+    assert_eq!((state.start, state.end), (Some((0, 0, 0)), None));
+    state.pick((3, 2, 0));
+    assert_eq!(state.end, Some((3, 2, 0)));
+    state.pick((5, 5, 5));
+    assert_eq!((state.start, state.end), (Some((5, 5, 5)), None));
+}
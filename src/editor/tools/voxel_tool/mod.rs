@@ -2,16 +2,24 @@
 
 mod drag_state;
 mod placement;
+mod preview;
 mod removal;
 
 pub use drag_state::{VoxelDragState, VoxelRemoveDragState};
-pub use placement::{handle_voxel_drag_placement, handle_voxel_placement};
-pub use removal::{handle_voxel_drag_removal, handle_voxel_removal};
+pub use placement::{
+    handle_placement_rotation_keys, handle_voxel_drag_placement, handle_voxel_placement,
+};
+pub use preview::{render_placement_preview, PlacementPreview};
+pub use removal::{
+    handle_voxel_drag_removal, handle_voxel_removal, FLOOD_REMOVE_CONFIRM_THRESHOLD,
+};
 
 use crate::editor::history::EditorHistory;
+use crate::editor::locks::is_locked;
 use crate::editor::state::EditorState;
 use crate::systems::game::map::format::VoxelData;
 use bevy::prelude::*;
+use std::collections::HashSet;
 
 /// Bundle of input resources for voxel tools
 #[derive(bevy::ecs::system::SystemParam)]
@@ -33,15 +41,12 @@ pub(crate) fn try_remove_voxel(
 ) {
     use crate::editor::history::EditorAction;
 
+    if !editor_state.allows_edit_at(grid_pos) {
+        return;
+    }
+
     // Find and remove voxel at this position
-    if let Some(index) = editor_state
-        .current_map
-        .world
-        .voxels
-        .iter()
-        .position(|v| v.pos == grid_pos)
-    {
-        let voxel_data = editor_state.current_map.world.voxels.remove(index);
+    if let Some(voxel_data) = editor_state.current_map.world.voxels.remove(grid_pos) {
         editor_state.mark_modified();
 
         // Record action in history
@@ -54,6 +59,131 @@ pub(crate) fn try_remove_voxel(
     }
 }
 
+/// Remove every voxel at `positions` as a single undo step named `description`.
+///
+/// Positions without a voxel are skipped. Returns the number removed.
+pub fn remove_voxel_group(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    positions: &[(i32, i32, i32)],
+    description: &str,
+) -> usize {
+    use crate::editor::history::EditorAction;
+
+    let locks = editor_state.locked_areas();
+    let mut actions = Vec::new();
+    let mut skipped = 0;
+    for &pos in positions {
+        if is_locked(&locks, pos) {
+            skipped += 1;
+            continue;
+        }
+        if let Some(data) = editor_state.current_map.world.voxels.remove(pos) {
+            actions.push(EditorAction::RemoveVoxel { pos, data });
+        }
+    }
+    if skipped > 0 {
+        warn!("{}: skipped {} locked voxels", description, skipped);
+    }
+
+    let count = actions.len();
+    if count > 0 {
+        history.push(EditorAction::Batch {
+            description: description.to_string(),
+            actions,
+        });
+        editor_state.mark_modified();
+        info!("{}: removed {} voxels", description, count);
+    }
+    count
+}
+
+/// Place full voxels of `voxel_type` at every empty position in `positions`
+/// as a single undo step named `description`. Returns the number placed.
+pub fn place_voxel_group(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    positions: &[(i32, i32, i32)],
+    voxel_type: crate::systems::game::components::VoxelType,
+    description: &str,
+) -> usize {
+    use crate::editor::history::EditorAction;
+
+    let mut actions = Vec::new();
+    for &pos in positions {
+        if !editor_state.allows_placement_at(pos)
+            || editor_state.current_map.world.voxels.contains(pos)
+        {
+            continue;
+        }
+        let data = VoxelData::new(
+            pos,
+            voxel_type,
+            Some(crate::systems::game::map::format::SubVoxelPattern::Full),
+        );
+        editor_state.current_map.world.voxels.push(data.clone());
+        actions.push(EditorAction::PlaceVoxel { pos, data });
+    }
+
+    let count = actions.len();
+    if count > 0 {
+        history.push(EditorAction::Batch {
+            description: description.to_string(),
+            actions,
+        });
+        editor_state.mark_modified();
+        info!("{}: placed {} voxels", description, count);
+    }
+    count
+}
+
+/// Put the voxels at `positions` into the voxel group `group`, or take them
+/// out of any group with `None`, as a single undo step.
+///
+/// Voxels already in that group are skipped. Returns the number changed.
+pub fn assign_voxel_group(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    positions: &HashSet<(i32, i32, i32)>,
+    group: Option<&str>,
+) -> usize {
+    use crate::editor::history::EditorAction;
+
+    let mut actions = Vec::new();
+    for &pos in positions {
+        let Some(voxel) = editor_state.current_map.world.voxels.get_mut(pos) else {
+            continue;
+        };
+        if voxel.group.as_deref() == group {
+            continue;
+        }
+        let old = voxel.clone();
+        voxel.group = group.map(str::to_string);
+        actions.push(EditorAction::RemoveVoxel {
+            pos: old.pos,
+            data: old,
+        });
+        actions.push(EditorAction::PlaceVoxel {
+            pos: voxel.pos,
+            data: voxel.clone(),
+        });
+    }
+
+    let count = actions.len() / 2;
+    if count > 0 {
+        history.push(EditorAction::Batch {
+            description: match group {
+                Some(name) => format!("Add to group '{}'", name),
+                None => "Remove from group".to_string(),
+            },
+            actions,
+        });
+        // Groups don't change how voxels look in the editor
+        editor_state.mark_modified_without_render();
+    }
+    count
+}
+
 /// Helper function to place a voxel and update drag state
 pub(crate) fn try_place_voxel(
     editor_state: &mut ResMut<EditorState>,
@@ -66,14 +196,9 @@ pub(crate) fn try_place_voxel(
     use crate::editor::history::EditorAction;
 
     // Check if voxel already exists at this position
-    let voxel_exists = editor_state
-        .current_map
-        .world
-        .voxels
-        .iter()
-        .any(|v| v.pos == grid_pos);
+    let voxel_exists = editor_state.current_map.world.voxels.contains(grid_pos);
 
-    if voxel_exists {
+    if voxel_exists || !editor_state.allows_placement_at(grid_pos) {
         return;
     }
 
@@ -85,8 +210,9 @@ pub(crate) fn try_place_voxel(
         pos: grid_pos,
         voxel_type,
         pattern: Some(pattern),
-        rotation: None,
+        rotation: editor_state.placement_rotation(),
         rotation_state: None,
+        group: None,
     };
 
     // Add to map
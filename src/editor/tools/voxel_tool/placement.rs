@@ -6,6 +6,7 @@ use crate::editor::cursor::CursorState;
 use crate::editor::history::{EditorAction, EditorHistory};
 use crate::editor::state::{EditorState, EditorTool};
 use crate::systems::game::map::format::VoxelData;
+use crate::systems::game::map::geometry::RotationAxis;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_egui::EguiContexts;
@@ -69,25 +70,25 @@ pub fn handle_voxel_placement(
         drag_state.drag_start_screen_pos = window_query.cursor_position();
 
         // Check if voxel already exists at this position
-        let voxel_exists = editor_state
-            .current_map
-            .world
-            .voxels
-            .iter()
-            .any(|v| v.pos == grid_pos);
+        let voxel_exists = editor_state.current_map.world.voxels.contains(grid_pos);
 
         if voxel_exists {
             info!("Voxel already exists at {:?}", grid_pos);
             return;
         }
 
+        if !editor_state.allows_placement_at(grid_pos) {
+            return;
+        }
+
         // Create new voxel data
         let voxel_data = VoxelData {
             pos: grid_pos,
             voxel_type,
             pattern: Some(pattern),
-            rotation: None,
+            rotation: editor_state.placement_rotation(),
             rotation_state: None,
+            group: None,
         };
 
         // Add to map
@@ -233,3 +234,41 @@ pub fn handle_voxel_drag_placement(
         &mut drag_state,
     );
 }
+
+/// Cycle the orientation of the voxel about to be placed.
+///
+/// R turns a quarter about Y, Shift+R about X and Alt+R about Z.
+pub fn handle_placement_rotation_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut contexts: EguiContexts,
+    mut editor_state: ResMut<EditorState>,
+) {
+    if !matches!(editor_state.active_tool, EditorTool::VoxelPlace { .. }) {
+        return;
+    }
+
+    if contexts
+        .ctx_mut()
+        .expect("egui context")
+        .wants_keyboard_input()
+    {
+        return;
+    }
+
+    // Leave Ctrl/Cmd+R to the shortcut layer
+    if crate::editor::shortcuts::modifier_pressed(&keyboard)
+        || !keyboard.just_pressed(KeyCode::KeyR)
+    {
+        return;
+    }
+
+    let axis = if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        RotationAxis::X
+    } else if keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        RotationAxis::Z
+    } else {
+        RotationAxis::Y
+    };
+    editor_state.rotate_placement(axis, 1);
+    info!("Placement rotated 90° about {:?}", axis);
+}
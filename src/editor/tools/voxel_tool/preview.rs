@@ -0,0 +1,154 @@
+//! Ghost preview of the voxel about to be placed.
+
+use crate::editor::cursor::CursorState;
+use crate::editor::state::{EditorState, EditorTool};
+use crate::systems::game::map::format::{
+    apply_orientation_matrix, OrientationMatrix, SubVoxelPattern,
+};
+use crate::systems::game::map::{ChunkMeshBuilder, Face, SUB_VOXEL_SIZE};
+use bevy::prelude::*;
+
+/// Local fence rails `(neg_x, pos_x, neg_z, pos_z)`.
+type FenceConnections = (bool, bool, bool, bool);
+
+/// What the preview mesh was last built for.
+type PreviewKey = (SubVoxelPattern, OrientationMatrix, FenceConnections);
+
+/// A fence with no neighbours, shown as a lone post.
+const NO_CONNECTIONS: FenceConnections = (false, false, false, false);
+
+/// Marker for the placement preview entity.
+#[derive(Component)]
+pub struct PlacementPreview;
+
+const FACES: [Face; 6] = [
+    Face::PosX,
+    Face::NegX,
+    Face::PosY,
+    Face::NegY,
+    Face::PosZ,
+    Face::NegZ,
+];
+
+/// Show the pattern of the VoxelPlace tool, in the current placement
+/// orientation, at the placement position.
+///
+/// Fences are previewed with rails towards the fences around the placement
+/// position, matching how they render once placed.
+///
+/// A single preview entity is kept alive; its mesh is only rebuilt when the
+/// pattern, orientation or fence connections change.
+pub fn render_placement_preview(
+    mut commands: Commands,
+    editor_state: Res<EditorState>,
+    cursor_state: Res<CursorState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut preview: Query<(&mut Mesh3d, &mut Transform, &mut Visibility), With<PlacementPreview>>,
+    mut shown: Local<Option<PreviewKey>>,
+) {
+    let pattern = match &editor_state.active_tool {
+        EditorTool::VoxelPlace { pattern, .. } => Some(*pattern),
+        _ => None,
+    };
+    let orientation = editor_state.placement_orientation;
+
+    let Ok((mut mesh, mut transform, mut visibility)) = preview.single_mut() else {
+        // Spawn lazily the first time the place tool is used
+        if let Some(pattern) = pattern {
+            commands.spawn((
+                Mesh3d(meshes.add(build_preview_mesh(pattern, &orientation, NO_CONNECTIONS))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::srgba(1.0, 1.0, 1.0, 0.35),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                })),
+                Transform::from_scale(Vec3::splat(0.98)),
+                Visibility::Hidden,
+                PlacementPreview,
+            ));
+            *shown = Some((pattern, orientation, NO_CONNECTIONS));
+        }
+        return;
+    };
+
+    let (Some(pattern), Some(position)) = (pattern, cursor_state.placement_pos) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let connections = if pattern.is_fence() {
+        fence_connections_at(&editor_state, position, &orientation)
+    } else {
+        NO_CONNECTIONS
+    };
+    if *shown != Some((pattern, orientation, connections)) {
+        mesh.0 = meshes.add(build_preview_mesh(pattern, &orientation, connections));
+        *shown = Some((pattern, orientation, connections));
+    }
+    transform.translation = position;
+    *visibility = Visibility::Visible;
+}
+
+/// Rails `(neg_x, pos_x, neg_z, pos_z)` a fence placed at `position` would get.
+fn fence_connections_at(
+    editor_state: &EditorState,
+    position: Vec3,
+    orientation: &OrientationMatrix,
+) -> FenceConnections {
+    let pos = (
+        position.x.round() as i32,
+        position.y.round() as i32,
+        position.z.round() as i32,
+    );
+    let voxels = &editor_state.current_map.world.voxels;
+    SubVoxelPattern::fence_connections(pos, Some(orientation), |p| {
+        voxels
+            .get(p)
+            .is_some_and(|v| v.pattern.is_some_and(|p| p.is_fence()))
+    })
+}
+
+/// Mesh of the outer faces of `pattern` under `orientation`, centred on the origin.
+///
+/// `connections` are the local rails of a fence and are ignored for other patterns.
+fn build_preview_mesh(
+    pattern: SubVoxelPattern,
+    orientation: &OrientationMatrix,
+    connections: FenceConnections,
+) -> Mesh {
+    let geometry = if pattern.is_fence() {
+        apply_orientation_matrix(
+            pattern.fence_geometry_with_neighbors(connections),
+            orientation,
+        )
+    } else {
+        pattern.geometry_with_rotation(Some(orientation))
+    };
+    let offset = -0.5 + SUB_VOXEL_SIZE * 0.5;
+    let mut builder = ChunkMeshBuilder::default();
+
+    for (x, y, z) in geometry.occupied_positions() {
+        let center = Vec3::new(
+            offset + x as f32 * SUB_VOXEL_SIZE,
+            offset + y as f32 * SUB_VOXEL_SIZE,
+            offset + z as f32 * SUB_VOXEL_SIZE,
+        );
+        for face in FACES {
+            let (dx, dy, dz) = face.offset();
+            if geometry.is_occupied(x + dx, y + dy, z + dz) {
+                continue;
+            }
+            let normal = Vec3::from_array(face.normal());
+            builder.add_face(
+                center + normal * SUB_VOXEL_SIZE * 0.5,
+                SUB_VOXEL_SIZE,
+                face,
+                Color::WHITE,
+            );
+        }
+    }
+
+    builder.build()
+}
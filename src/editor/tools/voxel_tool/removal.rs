@@ -1,15 +1,21 @@
 //! Voxel removal handling.
 
 use super::drag_state::VoxelRemoveDragState;
-use super::{try_remove_voxel, VoxelToolInput, DRAG_MOVEMENT_THRESHOLD};
+use super::{remove_voxel_group, try_remove_voxel, VoxelToolInput, DRAG_MOVEMENT_THRESHOLD};
 use crate::editor::cursor::CursorState;
 use crate::editor::history::EditorHistory;
-use crate::editor::state::{EditorState, EditorTool};
+use crate::editor::shortcuts::modifier_pressed;
+use crate::editor::state::{EditorState, EditorTool, EditorUIState, ToolMemory};
+use crate::editor::voxel_ops::connected_voxels;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_egui::EguiContexts;
 
+/// Connected removals larger than this ask for confirmation first
+pub const FLOOD_REMOVE_CONFIRM_THRESHOLD: usize = 250;
+
 /// Handle voxel removal when the tool is active
+#[allow(clippy::too_many_arguments)]
 pub fn handle_voxel_removal(
     cursor_state: Res<CursorState>,
     mut editor_state: ResMut<EditorState>,
@@ -18,6 +24,8 @@ pub fn handle_voxel_removal(
     mut contexts: EguiContexts,
     mut drag_state: ResMut<VoxelRemoveDragState>,
     window_query: Single<&Window, With<PrimaryWindow>>,
+    tool_memory: Res<ToolMemory>,
+    mut ui_state: ResMut<EditorUIState>,
 ) {
     // Check if voxel remove tool is active
     if !matches!(editor_state.active_tool, EditorTool::VoxelRemove) {
@@ -52,6 +60,32 @@ pub fn handle_voxel_removal(
         return;
     }
 
+    // Ctrl+click removes the whole connected group instead of starting a drag
+    if !pointer_over_ui
+        && input.mouse_button.just_pressed(MouseButton::Left)
+        && modifier_pressed(&input.keyboard)
+    {
+        let Some(grid_pos) = cursor_state.grid_pos else {
+            return;
+        };
+        let group = connected_voxels(
+            &editor_state.current_map,
+            grid_pos,
+            tool_memory.remove_connected_same_type,
+        );
+        if group.len() > FLOOD_REMOVE_CONFIRM_THRESHOLD {
+            ui_state.pending_voxel_removal = Some(group);
+        } else {
+            remove_voxel_group(
+                &mut editor_state,
+                &mut history,
+                &group,
+                "Remove connected voxels",
+            );
+        }
+        return;
+    }
+
     // Check if left mouse button was just pressed - start drag
     if !pointer_over_ui && input.mouse_button.just_pressed(MouseButton::Left) {
         drag_state.is_dragging = true;
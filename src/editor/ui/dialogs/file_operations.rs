@@ -1,10 +1,12 @@
 //! File dialog operations and handlers.
 
 use crate::editor::state::{EditorState, EditorUIState};
+use crate::editor::validation::set_load_diagnostics;
 use crate::systems::game::map::format::MapData;
+use crate::systems::game::map::loader::compression::{GZIP_EXTENSION, ZSTD_EXTENSION};
+use crate::systems::game::map::loader::{LoadDiagnostic, MapLoader};
 use bevy::prelude::*;
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{mpsc::channel, Arc, Mutex};
 
 use super::events::{FileDialogReceiver, FileSelectedEvent, MapDataChangedEvent};
@@ -25,7 +27,7 @@ pub fn handle_file_operations(
         // Spawn file dialog in a separate thread to avoid blocking
         std::thread::spawn(move || {
             let result = rfd::FileDialog::new()
-                .add_filter("RON Map Files", &["ron"])
+                .add_filter("Map Files", OPENABLE_MAP_EXTENSIONS)
                 .set_title("Open Map File")
                 .pick_file();
 
@@ -81,13 +83,14 @@ pub fn handle_file_selected(
 ) {
     for event in events.read() {
         match load_map_from_file(&event.path) {
-            Ok(map_data) => {
+            Ok((map_data, diagnostics)) => {
                 info!("Successfully loaded map from: {:?}", event.path);
                 editor_state.current_map = map_data;
                 editor_state.file_path = Some(event.path.clone());
                 editor_state.clear_modified();
                 editor_state.mark_needs_render();
                 editor_state.clear_selections();
+                set_load_diagnostics(&mut editor_state, diagnostics);
                 // Update recent files
                 recent_files.add(event.path.clone());
                 // Send event to trigger lighting update
@@ -102,14 +105,28 @@ pub fn handle_file_selected(
     }
 }
 
-/// Load a map from a file
-fn load_map_from_file(path: &PathBuf) -> Result<MapData, String> {
-    // Read file contents
-    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+/// File extensions the editor can open as maps (RON, plain or compressed)
+pub const OPENABLE_MAP_EXTENSIONS: &[&str] = &["ron", GZIP_EXTENSION, ZSTD_EXTENSION];
 
-    // Parse RON format
-    let map_data: MapData =
-        ron::from_str(&contents).map_err(|e| format!("Failed to parse map file: {}", e))?;
+/// Whether a path has an extension the editor can open as a map
+pub fn is_openable_map_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            OPENABLE_MAP_EXTENSIONS
+                .iter()
+                .any(|openable| ext.eq_ignore_ascii_case(openable))
+        })
+}
+
+/// Load a map from a file, in safe mode if it doesn't parse, with what safe
+/// mode skipped
+pub(super) fn load_map_from_file(path: &PathBuf) -> Result<(MapData, Vec<LoadDiagnostic>), String> {
+    // Read and parse the file, unpacking compression and voxel regions
+    let (map_data, diagnostics) = MapLoader::read_tolerant(path).map_err(|e| e.to_string())?;
+    for diagnostic in &diagnostics {
+        warn!("{:?}: {}", path, diagnostic);
+    }
 
     // Validate the map
     if map_data.world.width == 0 || map_data.world.height == 0 || map_data.world.depth == 0 {
@@ -118,5 +135,15 @@ fn load_map_from_file(path: &PathBuf) -> Result<MapData, String> {
         );
     }
 
-    Ok(map_data)
+    if let Some(metadata) = &map_data.editor_metadata {
+        if !metadata.is_supported() {
+            warn!(
+                "{:?} has editor data version {}, newer than this editor understands; \
+                 saving will drop what it can't read",
+                path, metadata.version
+            );
+        }
+    }
+
+    Ok((map_data, diagnostics))
 }
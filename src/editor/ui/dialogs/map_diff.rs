@@ -0,0 +1,264 @@
+//! "Compare With File" window for reviewing and merging another map version.
+
+use crate::editor::diff::{diff_maps, ChangeKind, EntityChange, MapDiff, VoxelChange};
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::renderer::RenderMapEvent;
+use crate::editor::shortcuts::apply_action;
+use crate::editor::state::{EditorState, EditorUIState};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::path::PathBuf;
+use std::sync::{
+    mpsc::{channel, Receiver},
+    Arc, Mutex,
+};
+
+use super::events::MapDataChangedEvent;
+use super::file_operations::{load_map_from_file, OPENABLE_MAP_EXTENSIONS};
+
+/// One reviewable difference.
+enum ReviewItem {
+    Voxel(VoxelChange),
+    Entity(EntityChange),
+}
+
+impl ReviewItem {
+    fn kind(&self) -> ChangeKind {
+        match self {
+            ReviewItem::Voxel(change) => change.kind(),
+            ReviewItem::Entity(change) => change.kind(),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            ReviewItem::Voxel(change) => change.to_string(),
+            ReviewItem::Entity(change) => change.to_string(),
+        }
+    }
+
+    /// Build the action applying this change to the current map.
+    fn to_action(&self, editor_state: &mut EditorState) -> Option<EditorAction> {
+        match self {
+            ReviewItem::Voxel(change) => Some(change.to_action(&mut editor_state.current_map)),
+            ReviewItem::Entity(change) => change.to_action(&editor_state.current_map),
+        }
+    }
+}
+
+/// Resource holding the map being compared and the changes left to review.
+#[derive(Resource, Default)]
+pub struct MapDiffReview {
+    /// Receiver for the "pick a file to compare" dialog thread
+    receiver: Option<Arc<Mutex<Receiver<Option<PathBuf>>>>>,
+    /// File being compared against; `None` when the window is closed
+    other_path: Option<PathBuf>,
+    /// Counts from the original diff, shown as the summary
+    summary: String,
+    /// Changes not yet accepted or rejected
+    items: Vec<ReviewItem>,
+}
+
+impl MapDiffReview {
+    fn open(&mut self, path: PathBuf, diff: MapDiff) {
+        self.summary = diff.summary();
+        self.items = diff
+            .voxels
+            .into_iter()
+            .map(ReviewItem::Voxel)
+            .chain(diff.entities.into_iter().map(ReviewItem::Entity))
+            .collect();
+        self.other_path = Some(path);
+    }
+
+    fn close(&mut self) {
+        self.other_path = None;
+        self.items.clear();
+        self.summary.clear();
+    }
+}
+
+/// Apply `item` to the current map and record it in the undo history.
+fn accept_item(
+    item: &ReviewItem,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) -> bool {
+    match item.to_action(editor_state) {
+        Some(action) => {
+            apply_action(&action, editor_state);
+            history.push(action);
+            true
+        }
+        None => {
+            warn!(
+                "Skipping merge of '{}': base entity no longer exists",
+                item.label()
+            );
+            false
+        }
+    }
+}
+
+/// System to open the compare dialog, diff the chosen file against the
+/// current map, and render the review window.
+pub fn render_map_diff_review(
+    mut contexts: EguiContexts,
+    mut review: ResMut<MapDiffReview>,
+    mut editor_state: ResMut<EditorState>,
+    mut ui_state: ResMut<EditorUIState>,
+    mut history: ResMut<EditorHistory>,
+    mut render_events: MessageWriter<RenderMapEvent>,
+    mut map_changed_events: MessageWriter<MapDataChangedEvent>,
+) {
+    // Spawn the file picker in a separate thread to avoid blocking
+    if ui_state.compare_dialog_open {
+        ui_state.compare_dialog_open = false;
+
+        let (sender, receiver) = channel();
+        review.receiver = Some(Arc::new(Mutex::new(receiver)));
+        std::thread::spawn(move || {
+            let result = rfd::FileDialog::new()
+                .add_filter("Map Files", OPENABLE_MAP_EXTENSIONS)
+                .set_title("Compare With Map File")
+                .pick_file();
+            let _ = sender.send(result);
+        });
+    }
+
+    // Check for a picked file
+    let picked = review
+        .receiver
+        .as_ref()
+        .and_then(|receiver| receiver.lock().ok()?.try_recv().ok());
+    if let Some(result) = picked {
+        review.receiver = None;
+        if let Some(path) = result {
+            match load_map_from_file(&path) {
+                Ok((other, _)) => {
+                    let diff = diff_maps(&editor_state.current_map, &other);
+                    info!("Compared with {:?}:\n{}", path, diff.summary());
+                    review.open(path, diff);
+                }
+                Err(e) => {
+                    error!("Failed to load map for comparison: {}", e);
+                    ui_state.error_message = format!("Failed to load map for comparison:\n{}", e);
+                    ui_state.error_dialog_open = true;
+                }
+            }
+        }
+    }
+
+    let Some(other_path) = review.other_path.clone() else {
+        return;
+    };
+
+    let ctx = contexts.ctx_mut().expect("egui context");
+    let file_name = other_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| other_path.display().to_string());
+
+    let mut accepted = None;
+    let mut rejected = None;
+    let mut accept_all = false;
+    let mut close = false;
+    let mut open = true;
+
+    egui::Window::new(format!("Compare: {}", file_name))
+        .open(&mut open)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            ui.label(&review.summary);
+            ui.label(
+                egui::RichText::new("Accept applies the other file's version to this map.")
+                    .small()
+                    .weak(),
+            );
+            ui.separator();
+
+            if review.items.is_empty() {
+                ui.label("No differences left to review.");
+            } else {
+                let row_height = ui.spacing().interact_size.y;
+                egui::ScrollArea::vertical().max_height(360.0).show_rows(
+                    ui,
+                    row_height,
+                    review.items.len(),
+                    |ui, range| {
+                        for i in range {
+                            let item = &review.items[i];
+                            let color = match item.kind() {
+                                ChangeKind::Added => egui::Color32::from_rgb(120, 200, 120),
+                                ChangeKind::Removed => egui::Color32::from_rgb(220, 110, 110),
+                                ChangeKind::Changed => egui::Color32::from_rgb(220, 190, 100),
+                            };
+                            ui.horizontal(|ui| {
+                                if ui.small_button("✔").on_hover_text("Accept").clicked() {
+                                    accepted = Some(i);
+                                }
+                                if ui.small_button("✖").on_hover_text("Reject").clicked() {
+                                    rejected = Some(i);
+                                }
+                                ui.colored_label(color, item.label());
+                            });
+                        }
+                    },
+                );
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                let has_items = !review.items.is_empty();
+                if ui
+                    .add_enabled(has_items, egui::Button::new("Accept All"))
+                    .clicked()
+                {
+                    accept_all = true;
+                }
+                if ui
+                    .add_enabled(has_items, egui::Button::new("Reject All"))
+                    .clicked()
+                {
+                    review.items.clear();
+                }
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    let mut changed = false;
+    if let Some(i) = accepted {
+        let item = review.items.remove(i);
+        changed = accept_item(&item, &mut editor_state, &mut history);
+    } else if let Some(i) = rejected {
+        review.items.remove(i);
+    } else if accept_all {
+        // Record the whole merge as a single undo step
+        let mut actions = Vec::new();
+        for item in review.items.drain(..) {
+            if let Some(action) = item.to_action(&mut editor_state) {
+                apply_action(&action, &mut editor_state);
+                actions.push(action);
+            }
+        }
+        changed = !actions.is_empty();
+        if changed {
+            history.push(EditorAction::Batch {
+                description: format!("Merge from {}", file_name),
+                actions,
+            });
+        }
+    }
+
+    if changed {
+        editor_state.mark_modified();
+        render_events.write(RenderMapEvent);
+        map_changed_events.write(MapDataChangedEvent);
+    }
+
+    if close || !open {
+        review.close();
+    }
+}
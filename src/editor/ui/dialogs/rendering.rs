@@ -1,24 +1,31 @@
 //! Dialog window rendering functions.
 
-use crate::editor::file_io::SaveMapEvent;
+use crate::editor::documents::DocumentEvent;
+use crate::editor::file_io::{fitted_world_bounds, SaveMapEvent};
+use crate::editor::history::{EditorAction, EditorHistory};
 use crate::editor::recent_files::OpenRecentFileEvent;
 use crate::editor::shortcuts::modifier_key_label;
-use crate::editor::state::{EditorState, EditorUIState, PendingAction};
-use crate::systems::game::map::format::MapData;
+use crate::editor::state::{BoundsEnforcement, EditorState, EditorUIState, PendingAction};
+use crate::editor::tools::remove_voxel_group;
+use crate::systems::game::map::format::{EdgeStyle, Precipitation};
+use crate::systems::game::map::spawner::LOD_LEVELS;
 use bevy::prelude::*;
 use bevy_egui::egui;
 
-use super::events::{AppExitEvent, MapDataChangedEvent};
+use super::events::{AppExitEvent, FileSelectedEvent};
 
 /// Render all dialog windows
+#[allow(clippy::too_many_arguments)]
 pub fn render_dialogs(
     ctx: &egui::Context,
     editor_state: &mut EditorState,
     ui_state: &mut EditorUIState,
+    history: &mut EditorHistory,
     save_events: &mut MessageWriter<SaveMapEvent>,
-    map_changed_events: &mut MessageWriter<MapDataChangedEvent>,
     exit_events: &mut MessageWriter<AppExitEvent>,
     open_recent_events: &mut MessageWriter<OpenRecentFileEvent>,
+    file_selected_events: &mut MessageWriter<FileSelectedEvent>,
+    document_events: &mut MessageWriter<DocumentEvent>,
 ) {
     // Unsaved changes dialog
     if ui_state.unsaved_changes_dialog_open {
@@ -29,12 +36,14 @@ pub fn render_dialogs(
             save_events,
             exit_events,
             open_recent_events,
+            file_selected_events,
+            document_events,
         );
     }
 
-    // New map dialog
-    if ui_state.new_map_dialog_open {
-        render_new_map_dialog(ctx, editor_state, ui_state, map_changed_events);
+    // Map settings dialog
+    if ui_state.map_settings_dialog_open {
+        render_map_settings_dialog(ctx, editor_state, ui_state, history);
     }
 
     // About dialog
@@ -51,9 +60,15 @@ pub fn render_dialogs(
     if ui_state.error_dialog_open {
         render_error_dialog(ctx, ui_state);
     }
+
+    // Large connected removal confirmation
+    if ui_state.pending_voxel_removal.is_some() {
+        render_voxel_removal_dialog(ctx, editor_state, ui_state, history);
+    }
 }
 
 /// Render unsaved changes confirmation dialog
+#[allow(clippy::too_many_arguments)]
 fn render_unsaved_changes_dialog(
     ctx: &egui::Context,
     editor_state: &mut EditorState,
@@ -61,6 +76,8 @@ fn render_unsaved_changes_dialog(
     save_events: &mut MessageWriter<SaveMapEvent>,
     exit_events: &mut MessageWriter<AppExitEvent>,
     open_recent_events: &mut MessageWriter<OpenRecentFileEvent>,
+    file_selected_events: &mut MessageWriter<FileSelectedEvent>,
+    document_events: &mut MessageWriter<DocumentEvent>,
 ) {
     egui::Window::new("Unsaved Changes")
         .collapsible(false)
@@ -76,13 +93,27 @@ fn render_unsaved_changes_dialog(
                 if ui.button("Save").clicked() {
                     save_events.write(SaveMapEvent);
                     ui_state.unsaved_changes_dialog_open = false;
-                    handle_pending_action(editor_state, ui_state, exit_events, open_recent_events);
+                    handle_pending_action(
+                        editor_state,
+                        ui_state,
+                        exit_events,
+                        open_recent_events,
+                        file_selected_events,
+                        document_events,
+                    );
                 }
 
                 if ui.button("Don't Save").clicked() {
                     editor_state.clear_modified();
                     ui_state.unsaved_changes_dialog_open = false;
-                    handle_pending_action(editor_state, ui_state, exit_events, open_recent_events);
+                    handle_pending_action(
+                        editor_state,
+                        ui_state,
+                        exit_events,
+                        open_recent_events,
+                        file_selected_events,
+                        document_events,
+                    );
                 }
 
                 if ui.button("Cancel").clicked() {
@@ -99,6 +130,8 @@ fn handle_pending_action(
     ui_state: &mut EditorUIState,
     exit_events: &mut MessageWriter<AppExitEvent>,
     open_recent_events: &mut MessageWriter<OpenRecentFileEvent>,
+    file_selected_events: &mut MessageWriter<FileSelectedEvent>,
+    document_events: &mut MessageWriter<DocumentEvent>,
 ) {
     if let Some(action) = ui_state.pending_action.take() {
         match action {
@@ -111,6 +144,12 @@ fn handle_pending_action(
             PendingAction::OpenRecentFile(path) => {
                 open_recent_events.write(OpenRecentFileEvent { path });
             }
+            PendingAction::OpenFile(path) => {
+                file_selected_events.write(FileSelectedEvent { path });
+            }
+            PendingAction::CloseTab => {
+                document_events.write(DocumentEvent::CloseActiveConfirmed);
+            }
             PendingAction::Quit => {
                 info!("Quitting editor");
                 exit_events.write(AppExitEvent);
@@ -119,38 +158,292 @@ fn handle_pending_action(
     }
 }
 
-/// Render new map dialog
-fn render_new_map_dialog(
+/// Render the Map Settings dialog: world bounds, out-of-bounds placement, weather,
+/// edges and meshing
+fn render_map_settings_dialog(
     ctx: &egui::Context,
     editor_state: &mut EditorState,
     ui_state: &mut EditorUIState,
-    map_changed_events: &mut MessageWriter<MapDataChangedEvent>,
+    history: &mut EditorHistory,
 ) {
-    egui::Window::new("New Map")
+    let mut open = true;
+    egui::Window::new("Map Settings")
         .collapsible(false)
         .resizable(false)
-        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .open(&mut open)
         .show(ctx, |ui| {
-            ui.label("Create a new map?");
-            ui.label("This will replace the current map.");
+            ui.heading("World Bounds");
 
-            ui.separator();
+            let old = editor_state.current_map.world.dimensions();
+            let mut dims = old;
+            egui::Grid::new("map_bounds_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (label, value) in [
+                        ("Width (X)", &mut dims.0),
+                        ("Height (Y)", &mut dims.1),
+                        ("Depth (Z)", &mut dims.2),
+                    ] {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(value).range(1..=4096));
+                        ui.end_row();
+                    }
+                });
+            if dims != old {
+                set_world_bounds(editor_state, history, old, dims, true);
+                // Hand-edited bounds should survive saving
+                editor_state.current_map.set_fixed_bounds(true);
+            }
 
-            ui.horizontal(|ui| {
-                if ui.button("Create").clicked() {
-                    *editor_state = EditorState::new();
-                    editor_state.current_map = MapData::default_map();
-                    ui_state.new_map_dialog_open = false;
-                    info!("Created new map");
-                    // Send event to trigger lighting update
-                    map_changed_events.write(MapDataChangedEvent);
-                }
+            let world = &editor_state.current_map.world;
+            let outside = world
+                .voxels
+                .iter()
+                .filter(|v| !world.contains(v.pos))
+                .count();
+            if outside > 0 {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 200, 80),
+                    format!("⚠ {outside} voxels are outside the bounds"),
+                );
+            }
 
-                if ui.button("Cancel").clicked() {
-                    ui_state.new_map_dialog_open = false;
-                }
+            let mut fixed = editor_state.current_map.has_fixed_bounds();
+            if ui
+                .checkbox(&mut fixed, "Keep these bounds when saving")
+                .on_hover_text(
+                    "When off, saving fits the bounds to the voxels. \
+                     Either way, bounds grow to cover every voxel.",
+                )
+                .changed()
+            {
+                editor_state.current_map.set_fixed_bounds(fixed);
+                editor_state.mark_modified();
+            }
+
+            let fitted = fitted_world_bounds(&editor_state.current_map);
+            ui.add_enabled_ui(
+                fitted != editor_state.current_map.world.dimensions(),
+                |ui| {
+                    if ui
+                        .button(format!(
+                            "Fit to Voxels ({}×{}×{})",
+                            fitted.0, fitted.1, fitted.2
+                        ))
+                        .clicked()
+                    {
+                        let old = editor_state.current_map.world.dimensions();
+                        set_world_bounds(editor_state, history, old, fitted, false);
+                    }
+                },
+            );
+
+            ui.separator();
+            ui.heading("Placing Outside Bounds");
+            egui::ComboBox::from_id_salt("bounds_enforcement")
+                .selected_text(editor_state.bounds_enforcement.label())
+                .show_ui(ui, |ui| {
+                    for mode in BoundsEnforcement::ALL {
+                        ui.selectable_value(
+                            &mut editor_state.bounds_enforcement,
+                            mode,
+                            mode.label(),
+                        );
+                    }
+                });
+            ui.label(match editor_state.bounds_enforcement {
+                BoundsEnforcement::Off => "Voxels can be placed anywhere.",
+                BoundsEnforcement::Warn => "Out-of-bounds placements are allowed but flagged.",
+                BoundsEnforcement::Clamp => "Out-of-bounds placements are refused.",
             });
+
+            ui.separator();
+            ui.heading("Weather");
+            render_weather_settings(ui, editor_state, history);
+
+            ui.separator();
+            ui.heading("Edges");
+            render_edge_settings(ui, editor_state, history);
+
+            ui.separator();
+            ui.heading("Meshing");
+            render_meshing_settings(ui, editor_state, history);
         });
+
+    if !open {
+        ui_state.map_settings_dialog_open = false;
+    }
+}
+
+/// Weather section of the Map Settings dialog; the viewport previews it live
+fn render_weather_settings(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) {
+    let old = editor_state.current_map.weather.clone();
+    let mut weather = old.clone();
+
+    egui::Grid::new("map_weather_grid")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Precipitation");
+            egui::ComboBox::from_id_salt("map_weather_precipitation")
+                .selected_text(weather.precipitation.label())
+                .show_ui(ui, |ui| {
+                    for kind in Precipitation::ALL {
+                        ui.selectable_value(&mut weather.precipitation, kind, kind.label());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Intensity");
+            ui.add_enabled(
+                weather.precipitation != Precipitation::None,
+                egui::Slider::new(&mut weather.intensity, 0.0..=1.0),
+            );
+            ui.end_row();
+
+            ui.label("Fog");
+            ui.add(egui::Slider::new(&mut weather.fog_density, 0.0..=1.0));
+            ui.end_row();
+
+            ui.label("Fog color");
+            let (r, g, b) = weather.fog_color;
+            let mut color = [r, g, b];
+            if ui.color_edit_button_rgb(&mut color).changed() {
+                weather.fog_color = (color[0], color[1], color[2]);
+            }
+            ui.end_row();
+
+            ui.label("Wetness")
+                .on_hover_text("Darkens the terrain as if it had been raining");
+            ui.add(egui::Slider::new(&mut weather.wetness, 0.0..=1.0));
+            ui.end_row();
+        });
+    ui.label("The viewport previews the weather as it looks in game.");
+
+    if weather != old {
+        history.push_coalesced(
+            "map_weather",
+            EditorAction::ModifyWeather {
+                old,
+                new: weather.clone(),
+            },
+        );
+        editor_state.current_map.weather = weather;
+        // Weather doesn't change the meshes, so skip the re-render
+        editor_state.mark_modified_without_render();
+    }
+}
+
+/// Edges section of the Map Settings dialog; the viewport previews it live
+fn render_edge_settings(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) {
+    let old = editor_state.current_map.edges.clone();
+    let mut edges = old.clone();
+
+    egui::Grid::new("map_edges_grid")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Style");
+            egui::ComboBox::from_id_salt("map_edges_style")
+                .selected_text(edges.style.label())
+                .show_ui(ui, |ui| {
+                    for style in EdgeStyle::ALL {
+                        ui.selectable_value(&mut edges.style, style, style.label());
+                    }
+                });
+            ui.end_row();
+
+            let enabled = edges.style != EdgeStyle::None;
+            ui.label("Strength");
+            ui.add_enabled(enabled, egui::Slider::new(&mut edges.strength, 0.0..=1.0));
+            ui.end_row();
+
+            ui.label("Width")
+                .on_hover_text("Width of the edge as a fraction of a voxel");
+            ui.add_enabled(enabled, egui::Slider::new(&mut edges.width, 0.01..=0.5));
+            ui.end_row();
+        });
+
+    if edges != old {
+        history.push_coalesced(
+            "map_edges",
+            EditorAction::ModifyEdges {
+                old,
+                new: edges.clone(),
+            },
+        );
+        editor_state.current_map.edges = edges;
+        // Edges are drawn by the chunk material, so skip the re-render
+        editor_state.mark_modified_without_render();
+    }
+}
+
+/// Meshing section of the Map Settings dialog. The editor always meshes at
+/// full detail, so it only shows in game.
+fn render_meshing_settings(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) {
+    let old = editor_state.current_map.meshing.clone();
+    let mut meshing = old.clone();
+
+    egui::Grid::new("map_meshing_grid")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Color tolerance").on_hover_text(
+                "Faces whose colors differ by at most this much merge into one quad; 0 keeps every color",
+            );
+            ui.add(egui::Slider::new(&mut meshing.color_tolerance, 0.0..=1.0));
+            ui.end_row();
+
+            ui.label("From LOD")
+                .on_hover_text("First level of detail quantized; 0 is full detail");
+            ui.add_enabled(
+                meshing.color_tolerance > 0.0,
+                egui::Slider::new(&mut meshing.from_lod, 0..=LOD_LEVELS - 1),
+            );
+            ui.end_row();
+        });
+
+    if meshing != old {
+        history.push_coalesced(
+            "map_meshing",
+            EditorAction::ModifyMeshing {
+                old,
+                new: meshing.clone(),
+            },
+        );
+        editor_state.current_map.meshing = meshing;
+        editor_state.mark_modified_without_render();
+    }
+}
+
+/// Resize the world bounds as an undoable edit.
+///
+/// `coalesce` merges consecutive changes (e.g. dragging a value) into one step.
+fn set_world_bounds(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    old: (i32, i32, i32),
+    new: (i32, i32, i32),
+    coalesce: bool,
+) {
+    let world = &mut editor_state.current_map.world;
+    (world.width, world.height, world.depth) = new;
+    let action = EditorAction::ModifyBounds { old, new };
+    if coalesce {
+        history.push_coalesced("map_bounds", action);
+    } else {
+        history.push(action);
+    }
+    editor_state.mark_modified();
 }
 
 /// Render about dialog
@@ -189,11 +482,17 @@ fn render_shortcuts_help(ctx: &egui::Context, ui_state: &mut EditorUIState) {
             ui.label(format!("{mod_key}+O - Open Map"));
             ui.label(format!("{mod_key}+S - Save"));
             ui.label(format!("{mod_key}+Shift+S - Save As"));
+            ui.label(format!("{mod_key}+T - New Tab"));
+            ui.label(format!("{mod_key}+W - Close Tab"));
+            ui.label(format!("{mod_key}+Tab - Next Tab"));
 
             ui.separator();
             ui.heading("Edit Operations");
             ui.label(format!("{mod_key}+Z - Undo"));
             ui.label(format!("{mod_key}+Y - Redo"));
+            ui.label(format!("{mod_key}+C - Copy Selection"));
+            ui.label(format!("{mod_key}+V - Paste at Cursor"));
+            ui.label(format!("{mod_key}+G - Go To Coordinates/Entity"));
             ui.label("Delete/Backspace - Remove");
 
             ui.separator();
@@ -201,6 +500,9 @@ fn render_shortcuts_help(ctx: &egui::Context, ui_state: &mut EditorUIState) {
             ui.label("G - Toggle Grid");
             ui.label("Shift+G - Toggle Snap");
             ui.label("Home - Reset Camera");
+            ui.label("F - Frame Selection");
+            ui.label(format!("{mod_key}+1..9 - Go To Camera Bookmark"));
+            ui.label(format!("{mod_key}+Shift+1..9 - Store Camera Bookmark"));
 
             ui.separator();
             ui.heading("Tools");
@@ -239,3 +541,39 @@ fn render_error_dialog(ctx: &egui::Context, ui_state: &mut EditorUIState) {
             }
         });
 }
+
+/// Render confirmation for removing a large group of connected voxels
+fn render_voxel_removal_dialog(
+    ctx: &egui::Context,
+    editor_state: &mut EditorState,
+    ui_state: &mut EditorUIState,
+    history: &mut EditorHistory,
+) {
+    let count = ui_state.pending_voxel_removal.as_ref().map_or(0, Vec::len);
+
+    egui::Window::new("Remove Connected Voxels")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!("Remove {} connected voxels?", count));
+            ui.label("This can be undone in one step.");
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button("Remove").clicked() {
+                    if let Some(group) = ui_state.pending_voxel_removal.take() {
+                        remove_voxel_group(
+                            editor_state,
+                            history,
+                            &group,
+                            "Remove connected voxels",
+                        );
+                    }
+                }
+                if ui.button("Cancel").clicked() {
+                    ui_state.pending_voxel_removal = None;
+                }
+            });
+        });
+}
@@ -1,21 +1,25 @@
 //! Window close and app exit handling.
 
+use crate::editor::documents::EditorDocuments;
+use crate::editor::file_io::MapSaveTask;
 use crate::editor::play::PlayTestState;
 use crate::editor::state::{EditorState, EditorUIState, PendingAction};
 use bevy::prelude::*;
 
-use super::events::AppExitEvent;
+use super::events::{AppExitEvent, FileSelectedEvent};
+use super::file_operations::{is_openable_map_file, OPENABLE_MAP_EXTENSIONS};
 
 /// System to intercept window close requests and prompt for unsaved changes
 pub fn handle_window_close_request(
     mut window_close_events: MessageReader<bevy::window::WindowCloseRequested>,
     editor_state: Res<EditorState>,
+    documents: Res<EditorDocuments>,
     mut ui_state: ResMut<EditorUIState>,
     mut exit_events: MessageWriter<AppExitEvent>,
 ) {
     for _event in window_close_events.read() {
-        // Check if there are unsaved changes
-        if editor_state.is_modified {
+        // Check if there are unsaved changes in any open tab
+        if documents.any_modified(&editor_state) {
             info!("Window close requested with unsaved changes");
 
             // Show unsaved changes dialog
@@ -28,11 +32,58 @@ pub fn handle_window_close_request(
     }
 }
 
+/// System to open map files dropped onto the editor window.
+///
+/// Dropped files go through [`FileSelectedEvent`] like the Open dialog, so
+/// recent files and rendering update the same way. With unsaved changes the
+/// user is asked to save first.
+pub fn handle_file_drop(
+    mut drop_events: MessageReader<bevy::window::FileDragAndDrop>,
+    editor_state: Res<EditorState>,
+    mut ui_state: ResMut<EditorUIState>,
+    mut file_selected_events: MessageWriter<FileSelectedEvent>,
+) {
+    for event in drop_events.read() {
+        let bevy::window::FileDragAndDrop::DroppedFile { path_buf, .. } = event else {
+            continue;
+        };
+
+        if !is_openable_map_file(path_buf) {
+            warn!(
+                "Ignoring dropped file with unsupported type: {:?}",
+                path_buf
+            );
+            ui_state.error_message = format!(
+                "Cannot open {}:\nSupported file types: {}",
+                path_buf.display(),
+                OPENABLE_MAP_EXTENSIONS
+                    .iter()
+                    .map(|ext| format!(".{}", ext))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            ui_state.error_dialog_open = true;
+            continue;
+        }
+
+        info!("File dropped: {:?}", path_buf);
+        if editor_state.is_modified {
+            ui_state.unsaved_changes_dialog_open = true;
+            ui_state.pending_action = Some(PendingAction::OpenFile(path_buf.clone()));
+        } else {
+            file_selected_events.write(FileSelectedEvent {
+                path: path_buf.clone(),
+            });
+        }
+    }
+}
+
 /// System to handle the actual app exit
 pub fn handle_app_exit(
     mut exit_events: MessageReader<AppExitEvent>,
     mut app_exit: MessageWriter<bevy::app::AppExit>,
     mut play_state: ResMut<PlayTestState>,
+    mut save_task: ResMut<MapSaveTask>,
 ) {
     for _ in exit_events.read() {
         // Stop any running game before exiting
@@ -41,6 +92,17 @@ pub fn handle_app_exit(
             play_state.stop_game();
         }
 
+        // Let background saves finish so no file is left half-written
+        if save_task.is_saving() {
+            info!("Waiting for the map save to finish before exit");
+        }
+        for result in save_task.wait() {
+            match result {
+                Ok((path, _)) => info!("Map saved successfully to: {:?}", path),
+                Err(e) => error!("Failed to save map: {}", e),
+            }
+        }
+
         info!("Application exit requested");
         app_exit.write(bevy::app::AppExit::Success);
     }
@@ -3,12 +3,14 @@
 pub mod dialogs;
 pub mod outliner;
 pub mod properties;
+pub mod tab_bar;
 pub mod toolbar;
 pub mod viewport;
 
 // Note: dialogs functions are used directly from the module, not re-exported
 pub use outliner::{render_outliner_panel, OutlinerState};
 pub use properties::render_properties_panel;
+pub use tab_bar::render_tab_bar;
 pub use toolbar::render_toolbar;
 pub use viewport::render_entity_name_labels;
 pub use viewport::render_viewport_controls;
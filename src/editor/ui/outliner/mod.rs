@@ -8,6 +8,7 @@ use crate::editor::renderer::RenderMapEvent;
 use crate::editor::state::EditorState;
 use crate::editor::tools::UpdateSelectionHighlights;
 use crate::systems::game::components::VoxelType;
+use crate::systems::game::go_to::EntityNameIndex;
 use crate::systems::game::map::format::{EntityData, EntityType};
 use bevy::prelude::*;
 use bevy_egui::egui;
@@ -30,6 +31,9 @@ pub struct OutlinerState {
     /// Set when rename mode is entered via context menu or F2 (not double-click, which
     /// already has the row on screen).
     pub scroll_to_rename: bool,
+    /// Entity names of the current map, rebuilt every time the panel is drawn.
+    /// Shared with the "Go To" dialog for name lookups.
+    pub name_index: EntityNameIndex,
 }
 
 impl OutlinerState {
@@ -41,6 +45,7 @@ impl OutlinerState {
             voxel_type_expanded: HashMap::new(),
             renaming_index: None,
             scroll_to_rename: false,
+            name_index: EntityNameIndex::default(),
         }
     }
 }
@@ -54,6 +59,8 @@ pub fn render_outliner_panel(
     selection_events: &mut MessageWriter<UpdateSelectionHighlights>,
     render_events: &mut MessageWriter<RenderMapEvent>,
 ) {
+    outliner_state.name_index = EntityNameIndex::build(&editor_state.current_map.entities);
+
     let response = egui::SidePanel::left("outliner")
         .default_width(200.0)
         .min_width(150.0)
@@ -289,11 +296,10 @@ fn render_entities_section(
                 && ui.input(|i| i.key_pressed(egui::Key::F2))
                 && editor_state.selected_entities.len() == 1
             {
-                let sel_index = *editor_state.selected_entities.iter().next().unwrap();
-                if sel_index < editor_state.current_map.entities.len()
-                    && editor_state.current_map.entities[sel_index].entity_type
-                        != EntityType::PlayerSpawn
-                {
+                let sel_index = editor_state.selected_entity_indices().first().copied();
+                if let Some(sel_index) = sel_index.filter(|&index| {
+                    editor_state.current_map.entities[index].entity_type != EntityType::PlayerSpawn
+                }) {
                     let cancel_id =
                         egui::Id::new("outliner_rename_cancel_snapshot").with(sel_index);
                     ui.data_mut(|d| {
@@ -317,10 +323,10 @@ fn render_entities_section(
                 let type_name = format!("{:?}", entity_type);
 
                 // Get display name (use custom property or type name)
-                let display_name = editor_state.current_map.entities[index]
-                    .properties
-                    .get("name")
-                    .cloned()
+                let display_name = outliner_state
+                    .name_index
+                    .name(index)
+                    .map(str::to_string)
                     .unwrap_or_else(|| type_name.clone());
 
                 // Skip if doesn't match filter
@@ -331,7 +337,7 @@ fn render_entities_section(
                     continue;
                 }
 
-                let is_selected = editor_state.selected_entities.contains(&index);
+                let is_selected = editor_state.is_entity_selected(index);
 
                 if outliner_state.renaming_index == Some(index) {
                     // --- Rename mode ---
@@ -453,12 +459,10 @@ fn render_entities_section(
 
                         if response.clicked() {
                             // Clear other selections and select this entity
-                            editor_state.selected_voxels.clear();
                             if is_selected {
-                                editor_state.selected_entities.remove(&index);
+                                editor_state.clear_selections();
                             } else {
-                                editor_state.selected_entities.clear();
-                                editor_state.selected_entities.insert(index);
+                                editor_state.select_entity(index);
                             }
                             selection_events.write(UpdateSelectionHighlights);
                         }
@@ -574,6 +578,10 @@ fn get_voxel_type_icon(voxel_type: &VoxelType) -> &'static str {
         VoxelType::Grass => "🟩",
         VoxelType::Dirt => "🟫",
         VoxelType::Stone => "⬜",
+        VoxelType::Water => "🟦",
+        VoxelType::Lava => "🟧",
+        VoxelType::Glowstone => "🟨",
+        VoxelType::Glass => "◻",
     }
 }
 
@@ -586,6 +594,9 @@ fn get_entity_type_icon(entity_type: &EntityType) -> &'static str {
         EntityType::Item => "🟡",
         EntityType::Trigger => "🟣",
         EntityType::LightSource => "💡",
+        EntityType::Door => "🚪",
+        EntityType::Teleporter => "🌀",
+        EntityType::Goal => "🏁",
     }
 }
 
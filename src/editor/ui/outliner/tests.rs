@@ -8,6 +8,7 @@ fn make_entity(entity_type: EntityType, name: Option<&str>) -> EntityData {
         props.insert("name".to_string(), n.to_string());
     }
     EntityData {
+        id: Default::default(),
         entity_type,
         position: (0.0, 0.0, 0.0),
         properties: props,
@@ -1,9 +1,15 @@
 //! Entity-specific property editing panels.
 
 use super::entity_tools::get_entity_icon;
+use crate::editor::extensions::{render_entity_inspectors, EditorExtensions};
 use crate::editor::history::{EditorAction, EditorHistory};
-use crate::editor::state::EditorState;
+use crate::editor::state::{EditorState, SnapMode};
+use crate::systems::game::actions::{
+    parse_actions, MapAction, ACTIONS_KEY, DEFAULT_TRIGGER_RADIUS,
+};
 use crate::systems::game::map::format::{EntityData, EntityType};
+use crate::systems::game::speedrun::DEFAULT_GOAL_RADIUS;
+use crate::systems::game::teleporters::DEFAULT_TELEPORTER_RADIUS;
 use bevy_egui::egui;
 
 /// Render properties for a single selected entity
@@ -11,16 +17,16 @@ pub fn render_single_entity_properties(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     history: &mut EditorHistory,
+    extensions: &EditorExtensions,
 ) {
-    let index = match editor_state.selected_entities.iter().next() {
-        Some(&idx) => idx,
-        None => return,
+    let Some(&id) = editor_state.selected_entities.iter().next() else {
+        return;
     };
 
-    if index >= editor_state.current_map.entities.len() {
+    let Some(index) = editor_state.current_map.entity_index(id) else {
         ui.label("Invalid entity");
         return;
-    }
+    };
 
     let entity_type = editor_state.current_map.entities[index].entity_type;
     let icon = get_entity_icon(&entity_type);
@@ -38,40 +44,18 @@ pub fn render_single_entity_properties(
     ui.group(|ui| {
         ui.label("Position");
 
-        let mut position_changed = false;
+        // Dragged values land on snap points; hold Ctrl to drag freely
+        let snap = editor_state.active_snap(ui.input(|i| i.modifiers.ctrl));
         let (mut x, mut y, mut z) = editor_state.current_map.entities[index].position;
 
-        ui.horizontal(|ui| {
-            ui.label("X:");
-            if ui
-                .add(egui::DragValue::new(&mut x).speed(0.1).fixed_decimals(1))
-                .changed()
-            {
-                position_changed = true;
-            }
-        });
-        ui.horizontal(|ui| {
-            ui.label("Y:");
-            if ui
-                .add(egui::DragValue::new(&mut y).speed(0.1).fixed_decimals(1))
-                .changed()
-            {
-                position_changed = true;
-            }
-        });
-        ui.horizontal(|ui| {
-            ui.label("Z:");
-            if ui
-                .add(egui::DragValue::new(&mut z).speed(0.1).fixed_decimals(1))
-                .changed()
-            {
-                position_changed = true;
-            }
-        });
+        let mut position_changed = position_drag_value(ui, "X:", &mut x, snap);
+        position_changed |= position_drag_value(ui, "Y:", &mut y, snap);
+        position_changed |= position_drag_value(ui, "Z:", &mut z, snap);
 
         if position_changed {
-            editor_state.current_map.entities[index].position = (x, y, z);
-            editor_state.mark_modified();
+            edit_entity(editor_state, history, index, "position", |entity| {
+                entity.position = (x, y, z);
+            });
         }
     });
 
@@ -88,14 +72,51 @@ pub fn render_single_entity_properties(
     } else if entity_type == EntityType::LightSource {
         ui.add_space(8.0);
         render_light_source_properties(ui, editor_state, history, index);
+    } else if entity_type == EntityType::Door {
+        ui.add_space(8.0);
+        render_door_properties(ui, editor_state, history, index);
+    } else if entity_type == EntityType::Teleporter {
+        ui.add_space(8.0);
+        render_teleporter_properties(ui, editor_state, history, index);
+    } else if entity_type == EntityType::Goal {
+        ui.add_space(8.0);
+        render_goal_properties(ui, editor_state, history, index);
+    } else if entity_type == EntityType::Item {
+        ui.add_space(8.0);
+        ui.group(|ui| {
+            ui.label("Item Properties");
+            render_text_property(
+                ui,
+                editor_state,
+                history,
+                index,
+                "key",
+                "Key:",
+                "Unlocks doors with the same key",
+            );
+        });
+    }
+
+    // Scripted actions run by triggers and interactions; doors,
+    // teleporters and goals do their own thing instead
+    if !matches!(
+        entity_type,
+        EntityType::PlayerSpawn | EntityType::Door | EntityType::Teleporter | EntityType::Goal
+    ) {
+        ui.add_space(8.0);
+        render_entity_actions_field(ui, editor_state, history, index);
     }
 
+    // Properties added by editor plugins
+    render_entity_inspectors(ui, editor_state, history, extensions, index);
+
     ui.add_space(8.0);
 
     // Actions
     ui.horizontal(|ui| {
         if ui.button("🗑 Delete").clicked() {
-            editor_state.current_map.entities.remove(index);
+            let data = editor_state.current_map.entities.remove(index);
+            history.push(EditorAction::RemoveEntity { index, data });
             editor_state.selected_entities.clear();
             editor_state.mark_modified();
         }
@@ -105,11 +126,28 @@ pub fn render_single_entity_properties(
     });
 }
 
+/// Render one position coordinate, snapping edited values to `snap`.
+fn position_drag_value(ui: &mut egui::Ui, label: &str, value: &mut f32, snap: SnapMode) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let before = *value;
+        if ui
+            .add(egui::DragValue::new(value).speed(0.1).max_decimals(2))
+            .changed()
+        {
+            *value = snap.snap(*value);
+        }
+        // Small drags can round back to the starting snap point
+        *value != before
+    })
+    .inner
+}
+
 /// Render the shared "Name:" field for all label-capable entity types.
 ///
 /// Uses write-through on every keystroke so the field doesn't reset each frame.
-/// Captures a pre-edit snapshot on focus-gained and pushes a single
-/// `EditorAction::ModifyEntity` undo entry when the field loses focus.
+/// Keystrokes are coalesced into a single `EditorAction::ModifyEntity` undo
+/// entry while the field keeps focus.
 fn render_entity_name_field(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
@@ -118,69 +156,394 @@ fn render_entity_name_field(
 ) {
     ui.group(|ui| {
         // Read the current stored value — this is the ground truth between edits.
-        let current_name = editor_state.current_map.entities[index]
+        let mut name = editor_state.current_map.entities[index]
             .properties
             .get("name")
             .cloned()
             .unwrap_or_default();
-        let mut name = current_name.clone();
 
         ui.horizontal(|ui| {
             ui.label("Name:");
 
-            // Stable ID keyed to this entity so the snapshot persists across
-            // frames while the field has focus.
-            let snapshot_id = egui::Id::new("entity_name_snapshot").with(index);
-
-            let response = ui.text_edit_singleline(&mut name);
-
-            if response.gained_focus() {
-                // Save pre-edit entity state so we produce one undo entry per
-                // edit session, not one per keystroke.
-                ui.data_mut(|d| {
-                    d.insert_temp(
-                        snapshot_id,
-                        editor_state.current_map.entities[index].clone(),
-                    )
-                });
-            }
+            let response = ui.text_edit_singleline(&mut name).on_hover_text(
+                "Prefix with \"loc:\" to show a translated name in game (e.g. loc:npc.smith)",
+            );
 
             if response.changed() {
                 // Write through immediately so the next frame reads the updated
                 // value. Without this the local `name` is discarded each frame
                 // and typed characters appear to vanish.
-                editor_state.current_map.entities[index]
-                    .properties
-                    .insert("name".to_string(), name.clone());
-                editor_state.mark_modified();
+                edit_entity(editor_state, history, index, "name", |entity| {
+                    entity.properties.insert("name".to_string(), name);
+                });
             }
+        });
+    });
+}
+
+/// Render the scripted "Actions" list, with a parse check, and its options.
+///
+/// Triggers run the actions when the player comes within their radius; other
+/// entities when the player interacts with them.
+fn render_entity_actions_field(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    index: usize,
+) {
+    let entity = &editor_state.current_map.entities[index];
+    let is_trigger = entity.entity_type == EntityType::Trigger;
+    let mut script = entity
+        .properties
+        .get(ACTIONS_KEY)
+        .cloned()
+        .unwrap_or_default();
+    let mut repeat = entity
+        .properties
+        .get("repeat")
+        .is_some_and(|r| r == "true" || r == "1");
 
-            if response.lost_focus() {
-                // Push a single undo entry covering the whole edit session.
-                let old_data = ui.data_mut(|d| d.get_temp::<EntityData>(snapshot_id));
-                ui.data_mut(|d| d.remove::<EntityData>(snapshot_id));
-                if let Some(old_data) = old_data {
-                    let old_name = old_data
+    ui.group(|ui| {
+        ui.label(if is_trigger {
+            "Actions (on enter)"
+        } else {
+            "Actions (on interact)"
+        });
+        let response = ui
+            .add(
+                egui::TextEdit::multiline(&mut script)
+                    .desired_rows(3)
+                    .hint_text("say Hello; wait 1; hide bridge_1"),
+            )
+            .on_hover_text(
+                "One action per line or separated by ';':\n\
+                 say <text>, wait <seconds>, teleport <x y z | name>,\n\
+                 show/hide/toggle/solidify/dissolve <group>, sound <path>,\n\
+                 weather <clear|rain|snow> [intensity]",
+            );
+        if response.changed() {
+            edit_entity(editor_state, history, index, ACTIONS_KEY, |entity| {
+                if script.trim().is_empty() {
+                    entity.properties.remove(ACTIONS_KEY);
+                } else {
+                    entity
                         .properties
-                        .get("name")
-                        .map(String::as_str)
-                        .unwrap_or("");
-                    let new_name = editor_state.current_map.entities[index]
+                        .insert(ACTIONS_KEY.to_string(), script.clone());
+                }
+            });
+        }
+        let problem = match parse_actions(&script) {
+            Err(reason) => Some(reason),
+            Ok(actions) => actions.iter().find_map(|action| match action {
+                MapAction::Group { name, .. }
+                    if !editor_state
+                        .current_map
+                        .world
+                        .voxels
+                        .iter()
+                        .any(|voxel| voxel.group.as_ref() == Some(name)) =>
+                {
+                    Some(format!("no voxels are in group '{}'", name))
+                }
+                _ => None,
+            }),
+        };
+        if let Some(problem) = problem {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 120, 100),
+                format!("⚠ {problem}"),
+            );
+        }
+
+        if ui.checkbox(&mut repeat, "Run every time").changed() {
+            edit_entity(editor_state, history, index, "repeat", |entity| {
+                if repeat {
+                    entity
                         .properties
-                        .get("name")
-                        .map(String::as_str)
-                        .unwrap_or("");
-                    if new_name != old_name {
-                        let new_data = editor_state.current_map.entities[index].clone();
-                        history.push(EditorAction::ModifyEntity {
-                            index,
-                            old_data,
-                            new_data,
-                        });
-                    }
+                        .insert("repeat".to_string(), "true".to_string());
+                } else {
+                    entity.properties.remove("repeat");
                 }
+            });
+        }
+
+        if is_trigger {
+            let mut radius = editor_state.current_map.entities[index]
+                .properties
+                .get("radius")
+                .and_then(|r| r.parse().ok())
+                .unwrap_or(DEFAULT_TRIGGER_RADIUS);
+            ui.horizontal(|ui| {
+                ui.label("Radius:");
+                if ui
+                    .add(egui::Slider::new(&mut radius, 0.5..=10.0).step_by(0.5))
+                    .changed()
+                {
+                    edit_entity(editor_state, history, index, "radius", |entity| {
+                        entity
+                            .properties
+                            .insert("radius".to_string(), format!("{:.1}", radius));
+                    });
+                }
+            });
+        }
+    });
+}
+
+/// Render Door-specific properties: the voxel groups it swaps, its key and
+/// whether it starts open.
+fn render_door_properties(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    index: usize,
+) {
+    ui.group(|ui| {
+        ui.label("Door Properties");
+        render_text_property(
+            ui,
+            editor_state,
+            history,
+            index,
+            "group",
+            "Group:",
+            "Voxel group drawn while the door is closed",
+        );
+        render_text_property(
+            ui,
+            editor_state,
+            history,
+            index,
+            "open_group",
+            "Open group:",
+            "Optional voxel group drawn while the door is open",
+        );
+        render_text_property(
+            ui,
+            editor_state,
+            history,
+            index,
+            "key",
+            "Key:",
+            "Optional key an Item must give before the door opens",
+        );
+
+        let properties = &editor_state.current_map.entities[index].properties;
+        let has_group = |key: &str| {
+            properties.get(key).is_none_or(|name| {
+                name.trim().is_empty()
+                    || editor_state
+                        .current_map
+                        .world
+                        .voxels
+                        .iter()
+                        .any(|voxel| voxel.group.as_deref() == Some(name.trim()))
+            })
+        };
+        let problem = if properties.get("group").is_none_or(|g| g.trim().is_empty()) {
+            Some("a door needs a group".to_string())
+        } else {
+            ["group", "open_group"]
+                .into_iter()
+                .find(|key| !has_group(key))
+                .map(|key| format!("no voxels are in group '{}'", properties[key].trim()))
+        };
+        if let Some(problem) = problem {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 120, 100),
+                format!("⚠ {problem}"),
+            );
+        }
+
+        let mut open = properties
+            .get("open")
+            .is_some_and(|o| o == "true" || o == "1");
+        if ui.checkbox(&mut open, "Starts open").changed() {
+            edit_entity(editor_state, history, index, "open", |entity| {
+                if open {
+                    entity
+                        .properties
+                        .insert("open".to_string(), "true".to_string());
+                } else {
+                    entity.properties.remove("open");
+                }
+            });
+        }
+    });
+}
+
+/// Render Teleporter-specific properties: its target, radius, how it is set
+/// off and whether the player keeps their velocity.
+fn render_teleporter_properties(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    index: usize,
+) {
+    ui.group(|ui| {
+        ui.label("Teleporter Properties");
+        render_text_property(
+            ui,
+            editor_state,
+            history,
+            index,
+            "target",
+            "Target:",
+            "Name of the teleporter the player is sent to",
+        );
+
+        let entities = &editor_state.current_map.entities;
+        let properties = &entities[index].properties;
+        let target = properties.get("target").map(|t| t.trim()).unwrap_or("");
+        let problem = if target.is_empty() {
+            Some("a teleporter needs a target".to_string())
+        } else if properties.get("name").map(|n| n.trim()) == Some(target) {
+            Some("a teleporter can't target itself".to_string())
+        } else if !entities.iter().any(|other| {
+            other.entity_type == EntityType::Teleporter
+                && other.properties.get("name").map(|n| n.trim()) == Some(target)
+        }) {
+            Some(format!("no teleporter is named '{}'", target))
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 120, 100),
+                format!("⚠ {problem}"),
+            );
+        }
+
+        let mut radius = properties
+            .get("radius")
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(DEFAULT_TELEPORTER_RADIUS);
+        let mut interact = properties.get("activation").map(String::as_str) == Some("interact");
+        let mut keep_velocity = properties
+            .get("keep_velocity")
+            .is_some_and(|k| k == "true" || k == "1");
+
+        ui.horizontal(|ui| {
+            ui.label("Radius:");
+            if ui
+                .add(egui::Slider::new(&mut radius, 0.25..=5.0).step_by(0.25))
+                .changed()
+            {
+                edit_entity(editor_state, history, index, "radius", |entity| {
+                    entity
+                        .properties
+                        .insert("radius".to_string(), format!("{:.2}", radius));
+                });
             }
         });
+        if ui
+            .checkbox(&mut interact, "Needs interact")
+            .on_hover_text("Otherwise the player stands on it for a moment")
+            .changed()
+        {
+            edit_entity(editor_state, history, index, "activation", |entity| {
+                if interact {
+                    entity
+                        .properties
+                        .insert("activation".to_string(), "interact".to_string());
+                } else {
+                    entity.properties.remove("activation");
+                }
+            });
+        }
+        if ui.checkbox(&mut keep_velocity, "Keep velocity").changed() {
+            edit_entity(editor_state, history, index, "keep_velocity", |entity| {
+                if keep_velocity {
+                    entity
+                        .properties
+                        .insert("keep_velocity".to_string(), "true".to_string());
+                } else {
+                    entity.properties.remove("keep_velocity");
+                }
+            });
+        }
+    });
+}
+
+/// Render Goal-specific properties: the radius that finishes the run.
+fn render_goal_properties(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    index: usize,
+) {
+    ui.group(|ui| {
+        ui.label("Goal Properties");
+        let mut radius = editor_state.current_map.entities[index]
+            .properties
+            .get("radius")
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(DEFAULT_GOAL_RADIUS);
+        ui.horizontal(|ui| {
+            ui.label("Radius:");
+            if ui
+                .add(egui::Slider::new(&mut radius, 0.25..=5.0).step_by(0.25))
+                .on_hover_text("Distance at which the player finishes the run")
+                .changed()
+            {
+                edit_entity(editor_state, history, index, "radius", |entity| {
+                    entity
+                        .properties
+                        .insert("radius".to_string(), format!("{:.2}", radius));
+                });
+            }
+        });
+        render_text_property(
+            ui,
+            editor_state,
+            history,
+            index,
+            "next_map",
+            "Next map:",
+            "Optional map file offered after the run, relative to this map's folder",
+        );
+        render_text_property(
+            ui,
+            editor_state,
+            history,
+            index,
+            "fanfare",
+            "Fanfare:",
+            "Optional sound asset played instead of the built-in fanfare",
+        );
+    });
+}
+
+/// Render a single-line text property, removing it when cleared.
+fn render_text_property(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    index: usize,
+    key: &'static str,
+    label: &str,
+    hover: &str,
+) {
+    let mut value = editor_state.current_map.entities[index]
+        .properties
+        .get(key)
+        .cloned()
+        .unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.label(label);
+        if ui
+            .text_edit_singleline(&mut value)
+            .on_hover_text(hover)
+            .changed()
+        {
+            edit_entity(editor_state, history, index, key, |entity| {
+                if value.is_empty() {
+                    entity.properties.remove(key);
+                } else {
+                    entity.properties.insert(key.to_string(), value);
+                }
+            });
+        }
     });
 }
 
@@ -208,17 +571,11 @@ fn render_npc_specific_properties(
                 .add(egui::Slider::new(&mut radius, 0.1..=1.0).step_by(0.05))
                 .changed()
             {
-                let old_data = editor_state.current_map.entities[index].clone();
-                editor_state.current_map.entities[index]
-                    .properties
-                    .insert("radius".to_string(), format!("{:.2}", radius));
-                let new_data = editor_state.current_map.entities[index].clone();
-                history.push(EditorAction::ModifyEntity {
-                    index,
-                    old_data,
-                    new_data,
+                edit_entity(editor_state, history, index, "radius", |entity| {
+                    entity
+                        .properties
+                        .insert("radius".to_string(), format!("{:.2}", radius));
                 });
-                editor_state.mark_modified();
             }
         });
     });
@@ -248,17 +605,11 @@ fn render_light_source_properties(
                 .add(egui::Slider::new(&mut intensity, 0.0..=100000.0).logarithmic(true))
                 .changed()
             {
-                let old_data = editor_state.current_map.entities[index].clone();
-                editor_state.current_map.entities[index]
-                    .properties
-                    .insert("intensity".to_string(), format!("{:.0}", intensity));
-                let new_data = editor_state.current_map.entities[index].clone();
-                history.push(EditorAction::ModifyEntity {
-                    index,
-                    old_data,
-                    new_data,
+                edit_entity(editor_state, history, index, "intensity", |entity| {
+                    entity
+                        .properties
+                        .insert("intensity".to_string(), format!("{:.0}", intensity));
                 });
-                editor_state.mark_modified();
             }
         });
 
@@ -276,17 +627,11 @@ fn render_light_source_properties(
                 .add(egui::Slider::new(&mut range, 0.1..=100.0).step_by(0.5))
                 .changed()
             {
-                let old_data = editor_state.current_map.entities[index].clone();
-                editor_state.current_map.entities[index]
-                    .properties
-                    .insert("range".to_string(), format!("{:.1}", range));
-                let new_data = editor_state.current_map.entities[index].clone();
-                history.push(EditorAction::ModifyEntity {
-                    index,
-                    old_data,
-                    new_data,
+                edit_entity(editor_state, history, index, "range", |entity| {
+                    entity
+                        .properties
+                        .insert("range".to_string(), format!("{:.1}", range));
                 });
-                editor_state.mark_modified();
             }
         });
 
@@ -299,17 +644,11 @@ fn render_light_source_properties(
         let mut shadows = current_shadows;
 
         if ui.checkbox(&mut shadows, "Cast Shadows").changed() {
-            let old_data = editor_state.current_map.entities[index].clone();
-            editor_state.current_map.entities[index]
-                .properties
-                .insert("shadows".to_string(), shadows.to_string());
-            let new_data = editor_state.current_map.entities[index].clone();
-            history.push(EditorAction::ModifyEntity {
-                index,
-                old_data,
-                new_data,
+            edit_entity(editor_state, history, index, "shadows", |entity| {
+                entity
+                    .properties
+                    .insert("shadows".to_string(), shadows.to_string());
             });
-            editor_state.mark_modified();
         }
 
         // Color (RGB sliders)
@@ -364,17 +703,11 @@ fn render_light_source_properties(
         });
 
         if color_changed {
-            let old_data = editor_state.current_map.entities[index].clone();
-            editor_state.current_map.entities[index]
-                .properties
-                .insert("color".to_string(), format!("{:.2},{:.2},{:.2}", r, g, b));
-            let new_data = editor_state.current_map.entities[index].clone();
-            history.push(EditorAction::ModifyEntity {
-                index,
-                old_data,
-                new_data,
+            edit_entity(editor_state, history, index, "color", |entity| {
+                entity
+                    .properties
+                    .insert("color".to_string(), format!("{:.2},{:.2},{:.2}", r, g, b));
             });
-            editor_state.mark_modified();
         }
 
         // Color preview
@@ -395,17 +728,11 @@ fn render_light_source_properties(
         let mut flicker = current_flicker;
 
         if ui.checkbox(&mut flicker, "Enable Flicker").changed() {
-            let old_data = editor_state.current_map.entities[index].clone();
-            editor_state.current_map.entities[index]
-                .properties
-                .insert("flicker".to_string(), flicker.to_string());
-            let new_data = editor_state.current_map.entities[index].clone();
-            history.push(EditorAction::ModifyEntity {
-                index,
-                old_data,
-                new_data,
+            edit_entity(editor_state, history, index, "flicker", |entity| {
+                entity
+                    .properties
+                    .insert("flicker".to_string(), flicker.to_string());
             });
-            editor_state.mark_modified();
         }
 
         if flicker {
@@ -422,17 +749,18 @@ fn render_light_source_properties(
                     .add(egui::Slider::new(&mut amplitude, 0.0..=50000.0).logarithmic(true))
                     .changed()
                 {
-                    let old_data = editor_state.current_map.entities[index].clone();
-                    editor_state.current_map.entities[index]
-                        .properties
-                        .insert("flicker_amplitude".to_string(), format!("{:.0}", amplitude));
-                    let new_data = editor_state.current_map.entities[index].clone();
-                    history.push(EditorAction::ModifyEntity {
+                    edit_entity(
+                        editor_state,
+                        history,
                         index,
-                        old_data,
-                        new_data,
-                    });
-                    editor_state.mark_modified();
+                        "flicker_amplitude",
+                        |entity| {
+                            entity.properties.insert(
+                                "flicker_amplitude".to_string(),
+                                format!("{:.0}", amplitude),
+                            );
+                        },
+                    );
                 }
             });
 
@@ -449,19 +777,39 @@ fn render_light_source_properties(
                     .add(egui::Slider::new(&mut speed, 0.1..=20.0).step_by(0.1))
                     .changed()
                 {
-                    let old_data = editor_state.current_map.entities[index].clone();
-                    editor_state.current_map.entities[index]
-                        .properties
-                        .insert("flicker_speed".to_string(), format!("{:.1}", speed));
-                    let new_data = editor_state.current_map.entities[index].clone();
-                    history.push(EditorAction::ModifyEntity {
-                        index,
-                        old_data,
-                        new_data,
+                    edit_entity(editor_state, history, index, "flicker_speed", |entity| {
+                        entity
+                            .properties
+                            .insert("flicker_speed".to_string(), format!("{:.1}", speed));
                     });
-                    editor_state.mark_modified();
                 }
             });
         }
     });
 }
+
+/// Apply `edit` to entity `index` and record it as a `ModifyEntity` undo entry.
+///
+/// Repeated edits of the same `field` are merged into one entry until the
+/// pointer is released (see `render_properties_panel`), so dragging a value
+/// undoes in a single step.
+fn edit_entity(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    index: usize,
+    field: &str,
+    edit: impl FnOnce(&mut EntityData),
+) {
+    let old_data = editor_state.current_map.entities[index].clone();
+    edit(&mut editor_state.current_map.entities[index]);
+    let new_data = editor_state.current_map.entities[index].clone();
+    history.push_coalesced(
+        format!("entity:{index}:{field}"),
+        EditorAction::ModifyEntity {
+            index,
+            old_data,
+            new_data,
+        },
+    );
+    editor_state.mark_modified();
+}
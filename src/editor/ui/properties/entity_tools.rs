@@ -20,6 +20,9 @@ pub fn render_entity_place_content(ui: &mut egui::Ui, entity_type: &mut EntityTy
                     ui.selectable_value(entity_type, EntityType::Item, "🟡 Item");
                     ui.selectable_value(entity_type, EntityType::Trigger, "🟣 Trigger");
                     ui.selectable_value(entity_type, EntityType::LightSource, "💡 Light Source");
+                    ui.selectable_value(entity_type, EntityType::Door, "🚪 Door");
+                    ui.selectable_value(entity_type, EntityType::Teleporter, "🌀 Teleporter");
+                    ui.selectable_value(entity_type, EntityType::Goal, "🏁 Goal");
                 });
         });
     });
@@ -35,6 +38,9 @@ pub fn render_entity_place_content(ui: &mut egui::Ui, entity_type: &mut EntityTy
             EntityType::Item => "Collectible item or interactive object.",
             EntityType::Trigger => "Invisible trigger zone for events.",
             EntityType::LightSource => "Point light that illuminates in all directions.",
+            EntityType::Door => "Door that opens by swapping one voxel group for another.",
+            EntityType::Teleporter => "Pad that sends the player to another named teleporter.",
+            EntityType::Goal => "Finish line that stops the speedrun timer.",
         };
         ui.small(description);
     });
@@ -57,5 +63,8 @@ pub fn get_entity_icon(entity_type: &EntityType) -> &'static str {
         EntityType::Item => "🟡",
         EntityType::Trigger => "🟣",
         EntityType::LightSource => "💡",
+        EntityType::Door => "🚪",
+        EntityType::Teleporter => "🌀",
+        EntityType::Goal => "🏁",
     }
 }
@@ -8,6 +8,7 @@
 
 mod entity_props;
 mod entity_tools;
+mod multi_entity_props;
 mod selection;
 mod voxel_tools;
 
@@ -15,20 +16,23 @@ pub use entity_props::render_single_entity_properties;
 pub use entity_tools::{get_entity_icon, render_entity_place_content};
 pub use selection::{calculate_selection_bounds, render_select_content, SelectionBounds};
 pub use voxel_tools::{
-    get_pattern_name, get_voxel_color, render_voxel_place_content, render_voxel_remove_content,
+    get_pattern_name, get_voxel_color, render_paint_content, render_paint_mask_combo,
+    render_sculpt_content, render_spline_content, render_staircase_content,
+    render_voxel_place_content, render_voxel_remove_content,
 };
 
 use crate::editor::cursor::CursorState;
+use crate::editor::extensions::{render_extension_panels, EditorExtensions};
 use crate::editor::history::EditorHistory;
 use crate::editor::state::{EditorState, EditorTool};
 use crate::editor::tools::{
     ActiveTransform, CancelTransform, ConfirmTransform, DeleteSelectedVoxels, StartMoveOperation,
-    StartRotateOperation,
+    StartRotateOperation, UpdateSelectionHighlights,
 };
 use bevy::prelude::*;
 use bevy_egui::egui;
 
-/// Bundle of event writers for transform operations
+/// Bundle of event writers for transform and selection operations
 #[derive(bevy::ecs::system::SystemParam)]
 pub struct TransformEvents<'w> {
     pub delete: MessageWriter<'w, DeleteSelectedVoxels>,
@@ -36,6 +40,7 @@ pub struct TransformEvents<'w> {
     pub rotate_start: MessageWriter<'w, StartRotateOperation>,
     pub confirm: MessageWriter<'w, ConfirmTransform>,
     pub cancel: MessageWriter<'w, CancelTransform>,
+    pub highlights: MessageWriter<'w, UpdateSelectionHighlights>,
 }
 
 /// Render the right-side properties panel
@@ -47,6 +52,7 @@ pub fn render_properties_panel(
     active_transform: &ActiveTransform,
     events: &mut TransformEvents,
     history: &mut EditorHistory,
+    extensions: &EditorExtensions,
 ) {
     let response = egui::SidePanel::right("properties")
         .default_width(280.0)
@@ -54,14 +60,30 @@ pub fn render_properties_panel(
         .max_width(400.0)
         .show(ctx, |ui| {
             // Tool header with icon
-            render_tool_header(ui, &editor_state.active_tool);
+            render_tool_header(ui, &editor_state.active_tool, extensions);
 
             ui.separator();
 
             // Tool-specific content
-            render_tool_content(ui, editor_state, active_transform, events, history);
+            render_tool_content(
+                ui,
+                editor_state,
+                active_transform,
+                events,
+                history,
+                extensions,
+            );
+
+            // Sections added by editor plugins
+            render_extension_panels(ui, editor_state, history, extensions);
         });
 
+    // A property drag or typed value counts as one edit until the pointer is
+    // released and no widget has keyboard focus
+    if !ctx.input(|i| i.pointer.any_down()) && ctx.memory(|m| m.focused().is_none()) {
+        history.end_coalescing();
+    }
+
     // Store panel width in egui memory for viewport overlays to use
     let panel_width = response.response.rect.width();
     ctx.memory_mut(|mem| {
@@ -73,13 +95,23 @@ pub fn render_properties_panel(
 }
 
 /// Render the tool header with icon and name
-fn render_tool_header(ui: &mut egui::Ui, tool: &EditorTool) {
+fn render_tool_header(ui: &mut egui::Ui, tool: &EditorTool, extensions: &EditorExtensions) {
     let (icon, name) = match tool {
         EditorTool::VoxelPlace { .. } => ("✏️", "Voxel Place"),
         EditorTool::VoxelRemove => ("🗑️", "Voxel Remove"),
         EditorTool::EntityPlace { .. } => ("📍", "Entity Place"),
         EditorTool::Select => ("🔲", "Select"),
         EditorTool::Camera => ("📷", "Camera"),
+        EditorTool::Sculpt { .. } => ("🖌", "Sculpt"),
+        EditorTool::Paint { .. } => ("🎨", "Paint"),
+        EditorTool::Spline { .. } => ("〰", "Spline"),
+        EditorTool::Staircase { .. } => ("⟋", "Staircase"),
+        EditorTool::Custom { name } => (
+            extensions
+                .tool(name)
+                .map_or("🧩", |tool| tool.icon.as_str()),
+            name.as_str(),
+        ),
     };
 
     ui.horizontal(|ui| {
@@ -94,6 +126,7 @@ fn render_tool_content(
     active_transform: &ActiveTransform,
     events: &mut TransformEvents,
     history: &mut EditorHistory,
+    extensions: &EditorExtensions,
 ) {
     match &mut editor_state.active_tool {
         EditorTool::VoxelPlace {
@@ -109,11 +142,50 @@ fn render_tool_content(
             render_entity_place_content(ui, entity_type);
         }
         EditorTool::Select => {
-            render_select_content(ui, editor_state, active_transform, events, history);
+            render_select_content(
+                ui,
+                editor_state,
+                active_transform,
+                events,
+                history,
+                extensions,
+            );
         }
         EditorTool::Camera => {
             render_camera_content(ui);
         }
+        EditorTool::Sculpt {
+            mode,
+            radius,
+            jitter,
+        } => {
+            render_sculpt_content(ui, mode, radius, jitter);
+        }
+        EditorTool::Paint {
+            voxel_type,
+            radius,
+            mask,
+        } => {
+            render_paint_content(ui, voxel_type, radius, mask);
+        }
+        EditorTool::Spline {
+            voxel_type,
+            pattern,
+            width,
+        } => {
+            render_spline_content(ui, voxel_type, pattern, width);
+        }
+        EditorTool::Staircase { voxel_type } => {
+            render_staircase_content(ui, voxel_type);
+        }
+        EditorTool::Custom { name } => {
+            let description = extensions
+                .tool(name)
+                .map_or("Tool provided by an editor plugin", |tool| {
+                    tool.description.as_str()
+                });
+            ui.label(description);
+        }
     }
 }
 
@@ -132,6 +204,7 @@ fn render_camera_content(ui: &mut egui::Ui) {
     ui.group(|ui| {
         ui.label("Shortcuts");
         ui.small("• Home: Reset camera");
+        ui.small("• F: Frame selection");
         ui.small("• Numpad 7: Top view");
         ui.small("• Numpad 1: Front view");
     });
@@ -0,0 +1,378 @@
+//! Editing the properties of several selected entities at once.
+//!
+//! When every selected entity has the same type, the panel lists the
+//! properties that make sense to share. Each field shows the value the
+//! entities agree on, or a mixed indicator when they differ, and an edit
+//! writes the new value to all of them as one undo step.
+
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::state::EditorState;
+use crate::systems::game::actions::DEFAULT_TRIGGER_RADIUS;
+use crate::systems::game::map::format::{EntityData, EntityType};
+use crate::systems::game::speedrun::DEFAULT_GOAL_RADIUS;
+use crate::systems::game::teleporters::DEFAULT_TELEPORTER_RADIUS;
+use bevy_egui::egui;
+
+/// How a shared property is edited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropertyKind {
+    /// Single-line text, removed when cleared
+    Text,
+    /// Number edited with a slider
+    Number {
+        min: f32,
+        max: f32,
+        step: f64,
+        default: f32,
+        decimals: usize,
+        logarithmic: bool,
+    },
+    /// Checkbox that is on when the value is `on`; turning it off writes
+    /// `off`, or removes the property when `off` is `None`
+    Flag {
+        on: &'static str,
+        off: Option<&'static str>,
+    },
+}
+
+/// A property that can be edited on several entities together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SharedProperty {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: PropertyKind,
+}
+
+impl SharedProperty {
+    const fn text(key: &'static str, label: &'static str) -> Self {
+        Self {
+            key,
+            label,
+            kind: PropertyKind::Text,
+        }
+    }
+
+    const fn number(
+        key: &'static str,
+        label: &'static str,
+        (min, max, step): (f32, f32, f64),
+        default: f32,
+        decimals: usize,
+    ) -> Self {
+        Self {
+            key,
+            label,
+            kind: PropertyKind::Number {
+                min,
+                max,
+                step,
+                default,
+                decimals,
+                logarithmic: false,
+            },
+        }
+    }
+
+    const fn flag(key: &'static str, label: &'static str, off: Option<&'static str>) -> Self {
+        Self {
+            key,
+            label,
+            kind: PropertyKind::Flag { on: "true", off },
+        }
+    }
+}
+
+/// Properties that entities of `entity_type` can share. Names are left
+/// out, since they identify single entities (such as teleporter targets).
+pub fn shared_properties(entity_type: EntityType) -> Vec<SharedProperty> {
+    match entity_type {
+        EntityType::Npc => vec![SharedProperty::number(
+            "radius",
+            "Radius:",
+            (0.1, 1.0, 0.05),
+            0.3,
+            2,
+        )],
+        EntityType::LightSource => vec![
+            SharedProperty {
+                key: "intensity",
+                label: "Intensity:",
+                kind: PropertyKind::Number {
+                    min: 0.0,
+                    max: 100000.0,
+                    step: 0.0,
+                    default: 1000.0,
+                    decimals: 0,
+                    logarithmic: true,
+                },
+            },
+            SharedProperty::number("range", "Range:", (0.1, 100.0, 0.5), 10.0, 1),
+            SharedProperty::flag("shadows", "Cast Shadows", Some("false")),
+            SharedProperty::flag("flicker", "Enable Flicker", Some("false")),
+        ],
+        EntityType::Item => vec![SharedProperty::text("key", "Key:")],
+        EntityType::Trigger => vec![
+            SharedProperty::number(
+                "radius",
+                "Radius:",
+                (0.5, 10.0, 0.5),
+                DEFAULT_TRIGGER_RADIUS,
+                1,
+            ),
+            SharedProperty::flag("repeat", "Run every time", None),
+        ],
+        EntityType::Door => vec![
+            SharedProperty::text("key", "Key:"),
+            SharedProperty::flag("open", "Starts open", None),
+        ],
+        EntityType::Teleporter => vec![
+            SharedProperty::number(
+                "radius",
+                "Radius:",
+                (0.25, 5.0, 0.25),
+                DEFAULT_TELEPORTER_RADIUS,
+                2,
+            ),
+            SharedProperty {
+                key: "activation",
+                label: "Needs interact",
+                kind: PropertyKind::Flag {
+                    on: "interact",
+                    off: None,
+                },
+            },
+            SharedProperty::flag("keep_velocity", "Keep velocity", None),
+        ],
+        EntityType::Goal => vec![
+            SharedProperty::number(
+                "radius",
+                "Radius:",
+                (0.25, 5.0, 0.25),
+                DEFAULT_GOAL_RADIUS,
+                2,
+            ),
+            SharedProperty::text("next_map", "Next map:"),
+            SharedProperty::text("fanfare", "Fanfare:"),
+        ],
+        EntityType::PlayerSpawn | EntityType::Enemy => Vec::new(),
+    }
+}
+
+/// Value of a property across several entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharedValue<'a> {
+    /// Every entity has this value (`None` when none of them set it)
+    Same(Option<&'a str>),
+    /// The entities disagree
+    Mixed,
+}
+
+/// The value of `key` the `entities` agree on, if they do.
+pub fn shared_value<'a>(
+    entities: impl IntoIterator<Item = &'a EntityData>,
+    key: &str,
+) -> SharedValue<'a> {
+    let mut values = entities
+        .into_iter()
+        .map(|entity| entity.properties.get(key).map(String::as_str));
+    let Some(first) = values.next() else {
+        return SharedValue::Same(None);
+    };
+    if values.all(|value| value == first) {
+        SharedValue::Same(first)
+    } else {
+        SharedValue::Mixed
+    }
+}
+
+/// Apply `edit` to every entity in `indices` as one undo step.
+///
+/// Repeated edits of the same `field` on the same entities are merged until
+/// the pointer is released, like single-entity edits.
+pub fn edit_entities(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    indices: &[usize],
+    field: &str,
+    edit: impl Fn(&mut EntityData),
+) {
+    let mut actions = Vec::new();
+    for &index in indices {
+        let Some(entity) = editor_state.current_map.entities.get_mut(index) else {
+            continue;
+        };
+        let old_data = entity.clone();
+        edit(entity);
+        actions.push(EditorAction::ModifyEntity {
+            index,
+            old_data,
+            new_data: entity.clone(),
+        });
+    }
+    if actions.is_empty() {
+        return;
+    }
+
+    let ids: Vec<String> = actions
+        .iter()
+        .filter_map(|action| match action {
+            EditorAction::ModifyEntity { new_data, .. } => Some(new_data.id.0.to_string()),
+            _ => None,
+        })
+        .collect();
+    history.push_coalesced(
+        format!("entities:{}:{field}", ids.join(",")),
+        EditorAction::Batch {
+            description: format!("Edit {} on {} entities", field, actions.len()),
+            actions,
+        },
+    );
+    editor_state.mark_modified();
+}
+
+/// Render the properties of several selected entities.
+pub fn render_multi_entity_properties(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) {
+    let indices = editor_state.selected_entity_indices();
+    let entities = &editor_state.current_map.entities;
+    let entity_type = indices
+        .first()
+        .map(|&index| entities[index].entity_type)
+        .filter(|&first| indices.iter().all(|&i| entities[i].entity_type == first));
+
+    ui.group(|ui| {
+        match entity_type {
+            Some(entity_type) => ui.label(format!(
+                "📍 {} {:?} entities selected",
+                indices.len(),
+                entity_type
+            )),
+            None => ui.label(format!("📍 {} entities selected", indices.len())),
+        };
+    });
+
+    ui.add_space(8.0);
+
+    let properties = entity_type.map(shared_properties).unwrap_or_default();
+    if properties.is_empty() {
+        ui.label(if entity_type.is_some() {
+            "These entities have no properties to edit together"
+        } else {
+            "Select entities of one type to edit them together"
+        });
+    } else {
+        ui.group(|ui| {
+            ui.label("Shared Properties");
+            for property in &properties {
+                render_shared_property(ui, editor_state, history, &indices, property);
+            }
+        });
+    }
+
+    ui.add_space(8.0);
+
+    if ui.button("Clear Selection").clicked() {
+        editor_state.selected_entities.clear();
+    }
+}
+
+/// Render one shared property, showing "mixed" when the entities differ.
+fn render_shared_property(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    indices: &[usize],
+    property: &SharedProperty,
+) {
+    let key = property.key;
+    let shared = shared_value(
+        indices
+            .iter()
+            .map(|&index| &editor_state.current_map.entities[index]),
+        key,
+    );
+    let mixed = shared == SharedValue::Mixed;
+    let current = match shared {
+        SharedValue::Same(value) => value.map(str::to_string),
+        SharedValue::Mixed => None,
+    };
+
+    match property.kind {
+        PropertyKind::Text => {
+            let mut value = current.unwrap_or_default();
+            ui.horizontal(|ui| {
+                ui.label(property.label);
+                let mut edit = egui::TextEdit::singleline(&mut value);
+                if mixed {
+                    edit = edit.hint_text("(mixed)");
+                }
+                if ui.add(edit).changed() {
+                    edit_entities(editor_state, history, indices, key, |entity| {
+                        if value.is_empty() {
+                            entity.properties.remove(key);
+                        } else {
+                            entity.properties.insert(key.to_string(), value.clone());
+                        }
+                    });
+                }
+            });
+        }
+        PropertyKind::Number {
+            min,
+            max,
+            step,
+            default,
+            decimals,
+            logarithmic,
+        } => {
+            // A mixed slider starts from the first entity's value
+            let mut value = editor_state.current_map.entities[indices[0]]
+                .properties
+                .get(key)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default);
+            ui.horizontal(|ui| {
+                ui.label(property.label);
+                let mut slider = egui::Slider::new(&mut value, min..=max).logarithmic(logarithmic);
+                if step > 0.0 {
+                    slider = slider.step_by(step);
+                }
+                let changed = ui.add(slider).changed();
+                if mixed {
+                    ui.weak("(mixed)");
+                }
+                if changed {
+                    edit_entities(editor_state, history, indices, key, |entity| {
+                        entity
+                            .properties
+                            .insert(key.to_string(), format!("{:.*}", decimals, value));
+                    });
+                }
+            });
+        }
+        PropertyKind::Flag { on, off } => {
+            let is_on = |value: Option<&str>| value.is_some_and(|v| v == on || v == "1");
+            let mut checked = !mixed && is_on(current.as_deref());
+            if ui
+                .add(egui::Checkbox::new(&mut checked, property.label).indeterminate(mixed))
+                .changed()
+            {
+                edit_entities(editor_state, history, indices, key, |entity| {
+                    match (checked, off) {
+                        (true, _) => entity.properties.insert(key.to_string(), on.to_string()),
+                        (false, Some(off)) => {
+                            entity.properties.insert(key.to_string(), off.to_string())
+                        }
+                        (false, None) => entity.properties.remove(key),
+                    };
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
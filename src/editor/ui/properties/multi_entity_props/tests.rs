@@ -0,0 +1,87 @@
+use super::*;
+use crate::editor::shortcuts::apply_action;
+use crate::systems::game::map::format::{EntityId, MapData};
+use std::collections::HashMap;
+
+fn light(id: u32, props: &[(&str, &str)]) -> EntityData {
+    EntityData {
+        id: EntityId(id),
+        entity_type: EntityType::LightSource,
+        position: (id as f32, 0.0, 0.0),
+        properties: props
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+#[test]
+fn shared_value_reports_agreement_or_mixed() {
+    let a = light(1, &[("range", "5.0"), ("shadows", "true")]);
+    let b = light(2, &[("range", "5.0")]);
+    assert_eq!(
+        shared_value([&a, &b], "range"),
+        SharedValue::Same(Some("5.0"))
+    );
+    assert_eq!(shared_value([&a, &b], "shadows"), SharedValue::Mixed);
+    assert_eq!(shared_value([&a, &b], "flicker"), SharedValue::Same(None));
+}
+
+#[test]
+fn every_type_but_spawns_and_enemies_has_shared_properties() {
+    assert!(shared_properties(EntityType::PlayerSpawn).is_empty());
+    assert!(shared_properties(EntityType::LightSource)
+        .iter()
+        .any(|property| property.key == "range"));
+    // Names identify single entities
+    for entity_type in [EntityType::Npc, EntityType::Door, EntityType::Teleporter] {
+        assert!(shared_properties(entity_type)
+            .iter()
+            .all(|property| property.key != "name"));
+    }
+}
+
+#[test]
+fn edits_apply_to_all_entities_and_undo_in_one_step() {
+    let mut map = MapData::empty_map();
+    map.entities = vec![
+        light(1, &[("range", "5.0")]),
+        light(2, &[("range", "8.0")]),
+        light(3, &[]),
+    ];
+    let mut state = EditorState::with_map(map);
+    let mut history = EditorHistory::default();
+
+    // Dragging a slider edits every frame
+    for range in ["6.0", "7.0"] {
+        edit_entities(&mut state, &mut history, &[0, 1], "range", |entity| {
+            entity
+                .properties
+                .insert("range".to_string(), range.to_string());
+        });
+    }
+    history.end_coalescing();
+
+    let ranges: Vec<_> = state
+        .current_map
+        .entities
+        .iter()
+        .map(|entity| entity.properties.get("range").cloned())
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![Some("7.0".to_string()), Some("7.0".to_string()), None]
+    );
+
+    assert_eq!(history.undo_count(), 1);
+    let undo = history.undo().unwrap();
+    apply_action(&undo.inverse(), &mut state);
+    assert_eq!(
+        state.current_map.entities[0].properties["range"],
+        "5.0".to_string()
+    );
+    assert_eq!(
+        state.current_map.entities[1].properties["range"],
+        "8.0".to_string()
+    );
+}
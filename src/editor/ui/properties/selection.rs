@@ -1,15 +1,76 @@
 //! Selection tool property panels for voxel and entity selection.
 
 use super::entity_props::render_single_entity_properties;
+use super::entity_tools::get_entity_icon;
+use super::multi_entity_props::render_multi_entity_properties;
+use super::voxel_tools::get_pattern_name;
 use super::TransformEvents;
-use crate::editor::history::EditorHistory;
-use crate::editor::state::EditorState;
+use crate::editor::entity_ops::{
+    align_positions, distribute_positions, move_entities, snap_positions, AlignTo, ArrangeAxis,
+};
+use crate::editor::extensions::EditorExtensions;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::state::{EditorState, SelectionFilter};
 use crate::editor::tools::{
-    ActiveTransform, CancelTransform, ConfirmTransform, DeleteSelectedVoxels, StartMoveOperation,
-    StartRotateOperation, TransformMode,
+    assign_voxel_group, place_voxel_group, remove_voxel_group, ActiveTransform, CancelTransform,
+    ConfirmTransform, DeleteSelectedVoxels, StartMoveOperation, StartRotateOperation,
+    TransformMode, UpdateSelectionHighlights,
 };
+use crate::editor::voxel_ops::{hollow_interior, shell_positions};
+use crate::systems::game::map::format::{EntityType, SubVoxelPattern, VoxelGroupData, VoxelType};
 use bevy_egui::egui;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
+
+/// Object kind picked in the "Select All of Type" dropdown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TypeChoice {
+    Voxel(VoxelType),
+    Entity(EntityType),
+}
+
+impl TypeChoice {
+    fn label(self) -> String {
+        match self {
+            Self::Voxel(voxel_type) => format!("{:?} voxels", voxel_type),
+            Self::Entity(entity_type) => {
+                format!("{} {:?}", get_entity_icon(&entity_type), entity_type)
+            }
+        }
+    }
+}
+
+const VOXEL_TYPES: [VoxelType; 7] = [
+    VoxelType::Grass,
+    VoxelType::Dirt,
+    VoxelType::Stone,
+    VoxelType::Water,
+    VoxelType::Lava,
+    VoxelType::Glowstone,
+    VoxelType::Glass,
+];
+
+const ENTITY_TYPES: [EntityType; 9] = [
+    EntityType::PlayerSpawn,
+    EntityType::Npc,
+    EntityType::Enemy,
+    EntityType::Item,
+    EntityType::Trigger,
+    EntityType::LightSource,
+    EntityType::Door,
+    EntityType::Teleporter,
+    EntityType::Goal,
+];
+
+const PATTERNS: [SubVoxelPattern; 8] = [
+    SubVoxelPattern::Full,
+    SubVoxelPattern::PlatformXZ,
+    SubVoxelPattern::PlatformXY,
+    SubVoxelPattern::PlatformYZ,
+    SubVoxelPattern::Staircase,
+    SubVoxelPattern::Pillar,
+    SubVoxelPattern::CenterCube,
+    SubVoxelPattern::Fence,
+];
 
 /// Bounding box of a selection as (min, max) voxel coordinates, each component optional
 /// when the selection is empty.
@@ -22,6 +83,7 @@ pub fn render_select_content(
     active_transform: &ActiveTransform,
     events: &mut TransformEvents,
     history: &mut EditorHistory,
+    extensions: &EditorExtensions,
 ) {
     // Check if in transform mode
     if active_transform.mode != TransformMode::None {
@@ -29,15 +91,18 @@ pub fn render_select_content(
         return;
     }
 
+    render_selection_tools(ui, editor_state, events);
+    ui.add_space(8.0);
+
     // Check if entities are selected
     if !editor_state.selected_entities.is_empty() {
-        render_entity_selection_content(ui, editor_state, history);
+        render_entity_selection_content(ui, editor_state, history, extensions);
         return;
     }
 
     // Check if voxels are selected
     if !editor_state.selected_voxels.is_empty() {
-        render_voxel_selection_content(ui, editor_state, events);
+        render_voxel_selection_content(ui, editor_state, events, history);
         return;
     }
 
@@ -59,6 +124,113 @@ pub fn render_select_content(
     });
 }
 
+/// Render the selection filter and the select-by-type/pattern/invert actions
+fn render_selection_tools(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    events: &mut TransformEvents,
+) {
+    let filter = editor_state.selection_filter;
+    let type_id = egui::Id::new("select_all_of_type_choice");
+    let pattern_id = egui::Id::new("select_by_pattern_choice");
+    let mut selection_changed = false;
+
+    ui.group(|ui| {
+        ui.label("Selection");
+
+        // Filter
+        let mut new_filter = filter;
+        ui.horizontal(|ui| {
+            ui.label("Pick:");
+            egui::ComboBox::from_id_salt("selection_filter")
+                .selected_text(filter.label())
+                .show_ui(ui, |ui| {
+                    for option in [
+                        SelectionFilter::All,
+                        SelectionFilter::VoxelsOnly,
+                        SelectionFilter::EntitiesOnly,
+                    ] {
+                        ui.selectable_value(&mut new_filter, option, option.label());
+                    }
+                });
+        });
+        if new_filter != filter {
+            editor_state.set_selection_filter(new_filter);
+            selection_changed = true;
+        }
+
+        // Select All of Type
+        let mut choices: Vec<TypeChoice> = Vec::new();
+        if new_filter.allows_voxels() {
+            choices.extend(VOXEL_TYPES.map(TypeChoice::Voxel));
+        }
+        if new_filter.allows_entities() {
+            choices.extend(ENTITY_TYPES.map(TypeChoice::Entity));
+        }
+        let mut choice = ui
+            .data(|d| d.get_temp::<TypeChoice>(type_id))
+            .filter(|c| choices.contains(c))
+            .unwrap_or(choices[0]);
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("select_all_of_type")
+                .selected_text(choice.label())
+                .show_ui(ui, |ui| {
+                    for option in &choices {
+                        ui.selectable_value(&mut choice, *option, option.label());
+                    }
+                });
+            if ui
+                .button("Select All")
+                .on_hover_text("Replace the selection with every object of this type")
+                .clicked()
+            {
+                match choice {
+                    TypeChoice::Voxel(voxel_type) => editor_state.select_voxels_of_type(voxel_type),
+                    TypeChoice::Entity(entity_type) => {
+                        editor_state.select_entities_of_type(entity_type)
+                    }
+                }
+                selection_changed = true;
+            }
+        });
+        ui.data_mut(|d| d.insert_temp(type_id, choice));
+
+        // Select by Pattern
+        if new_filter.allows_voxels() {
+            let mut pattern = ui
+                .data(|d| d.get_temp::<SubVoxelPattern>(pattern_id))
+                .unwrap_or_default();
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("select_by_pattern")
+                    .selected_text(get_pattern_name(&pattern))
+                    .show_ui(ui, |ui| {
+                        for option in PATTERNS {
+                            ui.selectable_value(&mut pattern, option, get_pattern_name(&option));
+                        }
+                    });
+                if ui
+                    .button("Select Pattern")
+                    .on_hover_text("Replace the selection with every voxel using this pattern")
+                    .clicked()
+                {
+                    editor_state.select_voxels_with_pattern(pattern);
+                    selection_changed = true;
+                }
+            });
+            ui.data_mut(|d| d.insert_temp(pattern_id, pattern));
+        }
+
+        if ui.button("⇄ Invert Selection").clicked() {
+            editor_state.invert_selection();
+            selection_changed = true;
+        }
+    });
+
+    if selection_changed {
+        events.highlights.write(UpdateSelectionHighlights);
+    }
+}
+
 /// Render content when in transform mode
 fn render_transform_mode_content(
     ui: &mut egui::Ui,
@@ -122,6 +294,7 @@ fn render_voxel_selection_content(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     events: &mut TransformEvents,
+    history: &mut EditorHistory,
 ) {
     let count = editor_state.selected_voxels.len();
 
@@ -159,11 +332,20 @@ fn render_voxel_selection_content(
         }
         if ui.button("Clear").on_hover_text("Escape").clicked() {
             editor_state.clear_selections();
+            events.highlights.write(UpdateSelectionHighlights);
         }
     });
 
     ui.add_space(8.0);
 
+    render_hollow_shell_operations(ui, editor_state, events, history);
+
+    ui.add_space(8.0);
+
+    render_voxel_group_operations(ui, editor_state, history);
+
+    ui.add_space(8.0);
+
     ui.group(|ui| {
         ui.label("Shortcuts");
         ui.small("• G: Start move");
@@ -172,29 +354,280 @@ fn render_voxel_selection_content(
     });
 }
 
+/// Render the Hollow and Shell buttons for the voxel selection
+fn render_hollow_shell_operations(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    events: &mut TransformEvents,
+    history: &mut EditorHistory,
+) {
+    let thickness_id = egui::Id::new("hollow_wall_thickness");
+    let shell_type_id = egui::Id::new("shell_voxel_type");
+    let mut thickness = ui.data(|d| d.get_temp::<u32>(thickness_id)).unwrap_or(1);
+    let mut shell_type = ui
+        .data(|d| d.get_temp::<VoxelType>(shell_type_id))
+        .unwrap_or(VoxelType::Stone);
+
+    ui.group(|ui| {
+        ui.label("Shape");
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("Hollow")
+                .on_hover_text("Remove selected voxels that are not exposed to air, keeping walls")
+                .clicked()
+            {
+                let interior = hollow_interior(
+                    &editor_state.current_map,
+                    &editor_state.selected_voxels,
+                    thickness,
+                );
+                remove_voxel_group(editor_state, history, &interior, "Hollow selection");
+                for pos in &interior {
+                    editor_state.selected_voxels.remove(pos);
+                }
+                events.highlights.write(UpdateSelectionHighlights);
+            }
+            ui.label("Wall:");
+            ui.add(egui::DragValue::new(&mut thickness).range(1..=16));
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("Shell")
+                .on_hover_text("Surround the selection with a one-voxel wall")
+                .clicked()
+            {
+                let shell =
+                    shell_positions(&editor_state.current_map, &editor_state.selected_voxels);
+                place_voxel_group(editor_state, history, &shell, shell_type, "Shell selection");
+            }
+            egui::ComboBox::from_id_salt("shell_voxel_type")
+                .selected_text(format!("{:?}", shell_type))
+                .show_ui(ui, |ui| {
+                    for option in VOXEL_TYPES {
+                        ui.selectable_value(&mut shell_type, option, format!("{:?}", option));
+                    }
+                });
+        });
+    });
+
+    ui.data_mut(|d| {
+        d.insert_temp(thickness_id, thickness);
+        d.insert_temp(shell_type_id, shell_type);
+    });
+}
+
+/// Render the voxel group of the selection, with controls to change it and,
+/// for a single group, how it starts in game.
+fn render_voxel_group_operations(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) {
+    let name_id = egui::Id::new("voxel_group_name");
+    let groups: BTreeSet<Option<String>> = editor_state
+        .current_map
+        .world
+        .voxels
+        .iter()
+        .filter(|voxel| editor_state.selected_voxels.contains(&voxel.pos))
+        .map(|voxel| voxel.group.clone())
+        .collect();
+    let single = match groups.iter().collect::<Vec<_>>().as_slice() {
+        [Some(name)] => Some(name.clone()),
+        _ => None,
+    };
+    let mut name = ui
+        .data(|d| d.get_temp::<String>(name_id))
+        .or_else(|| single.clone())
+        .unwrap_or_default();
+
+    ui.group(|ui| {
+        ui.label(match (&single, groups.len()) {
+            (Some(group), _) => format!("Group: {}", group),
+            (None, 1) => "Group: none".to_string(),
+            _ => "Group: mixed".to_string(),
+        })
+        .on_hover_text("Scripted actions show, hide, solidify and dissolve groups as a whole");
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut name)
+                    .desired_width(100.0)
+                    .hint_text("bridge_1"),
+            );
+            let valid = !name.trim().is_empty() && !name.contains(';');
+            if ui
+                .add_enabled(valid, egui::Button::new("Assign"))
+                .on_hover_text("Put the selected voxels in this group")
+                .clicked()
+            {
+                let selected = editor_state.selected_voxels.clone();
+                assign_voxel_group(editor_state, history, &selected, Some(name.trim()));
+            }
+            if ui
+                .add_enabled(
+                    groups.iter().any(Option::is_some),
+                    egui::Button::new("Clear"),
+                )
+                .on_hover_text("Take the selected voxels out of their group")
+                .clicked()
+            {
+                let selected = editor_state.selected_voxels.clone();
+                assign_voxel_group(editor_state, history, &selected, None);
+            }
+        });
+
+        if let Some(group) = single {
+            render_voxel_group_start_state(ui, editor_state, history, &group);
+        }
+    });
+
+    ui.data_mut(|d| d.insert_temp(name_id, name));
+}
+
+/// Render "Visible/Solid at start" checkboxes for `group`, stored in
+/// `MapData::voxel_groups` only while one of them is off.
+fn render_voxel_group_start_state(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+    group: &str,
+) {
+    let old = editor_state.current_map.voxel_groups.clone();
+    let mut state = old
+        .iter()
+        .find(|g| g.name == group)
+        .cloned()
+        .unwrap_or(VoxelGroupData {
+            name: group.to_string(),
+            visible: true,
+            solid: true,
+        });
+
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        changed |= ui.checkbox(&mut state.visible, "Visible").changed();
+        changed |= ui.checkbox(&mut state.solid, "Solid").changed();
+        ui.label("at start");
+    });
+    if !changed {
+        return;
+    }
+
+    let mut new: Vec<VoxelGroupData> = old.iter().filter(|g| g.name != group).cloned().collect();
+    if !state.visible || !state.solid {
+        new.push(state);
+    }
+    editor_state.current_map.voxel_groups = new.clone();
+    editor_state.mark_modified_without_render();
+    history.push(EditorAction::ModifyVoxelGroups { old, new });
+}
+
 /// Render content when entities are selected
 fn render_entity_selection_content(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     history: &mut EditorHistory,
+    extensions: &EditorExtensions,
 ) {
     let count = editor_state.selected_entities.len();
 
     if count == 1 {
         // Single entity - show full properties
-        render_single_entity_properties(ui, editor_state, history);
+        render_single_entity_properties(ui, editor_state, history, extensions);
     } else {
-        // Multiple entities
-        ui.group(|ui| {
-            ui.label(format!("📍 {} entities selected", count));
+        // Multiple entities - shared properties edit all of them
+        render_multi_entity_properties(ui, editor_state, history);
+    }
+
+    ui.add_space(8.0);
+    render_entity_arrange_operations(ui, editor_state, history);
+}
+
+/// Render the Align, Distribute and Snap buttons for the entity selection
+fn render_entity_arrange_operations(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) {
+    let axis_id = egui::Id::new("entity_arrange_axis");
+    let mut axis = ui
+        .data(|d| d.get_temp::<ArrangeAxis>(axis_id))
+        .unwrap_or_default();
+    let indices = editor_state.selected_entity_indices();
+    let positions: Vec<_> = indices
+        .iter()
+        .map(|&index| editor_state.current_map.entities[index].position)
+        .collect();
+    let several = indices.len() >= 2;
+    let mut arranged = None;
+
+    ui.group(|ui| {
+        ui.label("Arrange");
+
+        ui.horizontal(|ui| {
+            ui.label("Axis:");
+            for option in ArrangeAxis::ALL {
+                ui.selectable_value(&mut axis, option, option.label());
+            }
         });
 
-        ui.add_space(8.0);
+        ui.add_enabled_ui(several, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Align:");
+                for (to, label, hover) in [
+                    (AlignTo::Min, "Min", "Line up on the lowest coordinate"),
+                    (
+                        AlignTo::Center,
+                        "Center",
+                        "Line up halfway between the extremes",
+                    ),
+                    (AlignTo::Max, "Max", "Line up on the highest coordinate"),
+                ] {
+                    if ui.button(label).on_hover_text(hover).clicked() {
+                        arranged = Some((
+                            align_positions(&positions, axis, to),
+                            format!("Align {} entities", indices.len()),
+                        ));
+                    }
+                }
+            });
+            if ui
+                .button("Distribute")
+                .on_hover_text("Space evenly between the two outermost entities")
+                .clicked()
+            {
+                arranged = Some((
+                    distribute_positions(&positions, axis),
+                    format!("Distribute {} entities", indices.len()),
+                ));
+            }
+        });
 
-        if ui.button("Clear Selection").clicked() {
-            editor_state.selected_entities.clear();
+        if ui
+            .button("Snap to Grid")
+            .on_hover_text("Round every selected entity to the snap grid")
+            .clicked()
+        {
+            arranged = Some((
+                snap_positions(&positions, editor_state.snap_mode),
+                format!("Snap {} entities to grid", indices.len()),
+            ));
         }
+    });
+
+    if let Some((new_positions, description)) = arranged {
+        move_entities(
+            editor_state,
+            history,
+            &indices,
+            &new_positions,
+            &description,
+        );
     }
+    ui.data_mut(|d| d.insert_temp(axis_id, axis));
 }
 
 /// Calculate selection bounds for display
@@ -1,5 +1,9 @@
 //! Voxel tool property panels for place and remove operations.
 
+use crate::editor::state::SculptMode;
+use crate::editor::tools::paint_tool::MAX_PAINT_RADIUS;
+use crate::editor::tools::sculpt_tool::MAX_SCULPT_RADIUS;
+use crate::editor::tools::spline_tool::MAX_SPLINE_WIDTH;
 use crate::systems::game::components::VoxelType;
 use crate::systems::game::map::format::SubVoxelPattern;
 use bevy_egui::egui;
@@ -24,6 +28,10 @@ pub fn render_voxel_place_content(
                     ui.selectable_value(voxel_type, VoxelType::Grass, "🟩 Grass");
                     ui.selectable_value(voxel_type, VoxelType::Dirt, "🟫 Dirt");
                     ui.selectable_value(voxel_type, VoxelType::Stone, "⬜ Stone");
+                    ui.selectable_value(voxel_type, VoxelType::Water, "🟦 Water ⚠");
+                    ui.selectable_value(voxel_type, VoxelType::Lava, "🟧 Lava ⚠");
+                    ui.selectable_value(voxel_type, VoxelType::Glowstone, "🟨 Glowstone");
+                    ui.selectable_value(voxel_type, VoxelType::Glass, "◻ Glass");
                 });
         });
     });
@@ -215,6 +223,191 @@ pub fn render_voxel_remove_content(ui: &mut egui::Ui) {
     });
 }
 
+/// Paint tool content
+pub fn render_paint_content(
+    ui: &mut egui::Ui,
+    voxel_type: &mut VoxelType,
+    radius: &mut u32,
+    mask: &mut Option<VoxelType>,
+) {
+    ui.group(|ui| {
+        ui.label("Paint With");
+        ui.horizontal(|ui| {
+            let color = get_voxel_color(voxel_type);
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, color);
+
+            egui::ComboBox::from_id_salt("paint_type_prop")
+                .selected_text(format!("{:?}", voxel_type))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(voxel_type, VoxelType::Grass, "🟩 Grass");
+                    ui.selectable_value(voxel_type, VoxelType::Dirt, "🟫 Dirt");
+                    ui.selectable_value(voxel_type, VoxelType::Stone, "⬜ Stone");
+                    ui.selectable_value(voxel_type, VoxelType::Water, "🟦 Water ⚠");
+                    ui.selectable_value(voxel_type, VoxelType::Lava, "🟧 Lava ⚠");
+                    ui.selectable_value(voxel_type, VoxelType::Glowstone, "🟨 Glowstone");
+                    ui.selectable_value(voxel_type, VoxelType::Glass, "◻ Glass");
+                });
+        });
+    });
+
+    ui.add_space(8.0);
+
+    ui.group(|ui| {
+        ui.label("Brush");
+        ui.add(egui::Slider::new(radius, 1..=MAX_PAINT_RADIUS).text("Radius"));
+        ui.horizontal(|ui| {
+            ui.label("Only over:");
+            render_paint_mask_combo(ui, "paint_mask_prop", mask);
+        });
+    });
+
+    ui.add_space(8.0);
+
+    ui.group(|ui| {
+        ui.label("Usage");
+        ui.small("• Drag over terrain to repaint its surface");
+        ui.small("• Only the type changes, never the shape");
+        ui.small("• Each stroke is one undo step");
+    });
+}
+
+/// Combo box choosing the voxel type the Paint tool is limited to
+pub fn render_paint_mask_combo(ui: &mut egui::Ui, id: &str, mask: &mut Option<VoxelType>) -> bool {
+    let selected = match mask {
+        Some(voxel_type) => format!("{:?}", voxel_type),
+        None => "Any type".to_string(),
+    };
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            let mut changed = false;
+            changed |= ui.selectable_value(mask, None, "Any type").changed();
+            changed |= ui
+                .selectable_value(mask, Some(VoxelType::Grass), "🟩 Grass")
+                .changed();
+            changed |= ui
+                .selectable_value(mask, Some(VoxelType::Dirt), "🟫 Dirt")
+                .changed();
+            changed |= ui
+                .selectable_value(mask, Some(VoxelType::Stone), "⬜ Stone")
+                .changed();
+            changed |= ui
+                .selectable_value(mask, Some(VoxelType::Water), "🟦 Water")
+                .changed();
+            changed |= ui
+                .selectable_value(mask, Some(VoxelType::Lava), "🟧 Lava")
+                .changed();
+            changed |= ui
+                .selectable_value(mask, Some(VoxelType::Glowstone), "🟨 Glowstone")
+                .changed();
+            changed |= ui
+                .selectable_value(mask, Some(VoxelType::Glass), "◻ Glass")
+                .changed();
+            changed
+        })
+        .inner
+        .unwrap_or(false)
+}
+
+/// Sculpt tool content
+pub fn render_sculpt_content(
+    ui: &mut egui::Ui,
+    mode: &mut SculptMode,
+    radius: &mut u32,
+    jitter: &mut f32,
+) {
+    ui.group(|ui| {
+        ui.label("Brush");
+        ui.horizontal(|ui| {
+            for option in SculptMode::ALL {
+                ui.selectable_value(mode, option, option.label());
+            }
+        });
+        ui.add(egui::Slider::new(radius, 1..=MAX_SCULPT_RADIUS).text("Radius"));
+        ui.add(egui::Slider::new(jitter, 0.0..=1.0).text("Noise"))
+            .on_hover_text("Randomly vary each column for rougher terrain");
+    });
+
+    ui.add_space(8.0);
+
+    ui.group(|ui| {
+        ui.label("Usage");
+        ui.small("• Drag over terrain to sculpt");
+        ui.small("• The middle of the brush changes fastest");
+        ui.small("• Lower never removes a column's last voxel");
+        ui.small("• Each stroke is one undo step");
+    });
+}
+
+/// Spline tool content
+pub fn render_spline_content(
+    ui: &mut egui::Ui,
+    voxel_type: &mut VoxelType,
+    pattern: &mut SubVoxelPattern,
+    width: &mut u32,
+) {
+    render_voxel_place_content(ui, voxel_type, pattern);
+
+    ui.add_space(8.0);
+
+    ui.group(|ui| {
+        ui.label("Width");
+        ui.add(egui::Slider::new(width, 1..=MAX_SPLINE_WIDTH).suffix(" voxels"));
+    });
+
+    ui.add_space(8.0);
+
+    ui.group(|ui| {
+        ui.label("Shortcuts");
+        ui.small("• Click: Add control point");
+        ui.small("• Drag a point: Move it");
+        ui.small("• Right-click a point: Remove it");
+        ui.small("• Enter: Lay voxels along the curve");
+        ui.small("• Backspace: Remove last point");
+        ui.small("• Esc: Clear the curve");
+    });
+}
+
+/// Staircase tool content
+pub fn render_staircase_content(ui: &mut egui::Ui, voxel_type: &mut VoxelType) {
+    ui.group(|ui| {
+        ui.label("Build With");
+        ui.horizontal(|ui| {
+            let color = get_voxel_color(voxel_type);
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, color);
+
+            egui::ComboBox::from_id_salt("staircase_type_prop")
+                .selected_text(format!("{:?}", voxel_type))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(voxel_type, VoxelType::Grass, "🟩 Grass");
+                    ui.selectable_value(voxel_type, VoxelType::Dirt, "🟫 Dirt");
+                    ui.selectable_value(voxel_type, VoxelType::Stone, "⬜ Stone");
+                });
+        });
+    });
+
+    ui.add_space(8.0);
+
+    ui.group(|ui| {
+        ui.label("Usage");
+        ui.small("• Click the voxel to start from");
+        ui.small("• Click the voxel to end on");
+        ui.small("• Stairs climb one voxel per step");
+        ui.small("• The path turns and detours to fit");
+        ui.small("• Red line: no walkable path fits");
+    });
+
+    ui.add_space(8.0);
+
+    ui.group(|ui| {
+        ui.label("Shortcuts");
+        ui.small("• Enter: Build the staircase");
+        ui.small("• Esc: Clear the end points");
+    });
+}
+
 /// Get color for a voxel type
 pub fn get_voxel_color(voxel_type: &VoxelType) -> egui::Color32 {
     match voxel_type {
@@ -222,6 +415,10 @@ pub fn get_voxel_color(voxel_type: &VoxelType) -> egui::Color32 {
         VoxelType::Grass => egui::Color32::from_rgb(76, 153, 0),
         VoxelType::Dirt => egui::Color32::from_rgb(139, 90, 43),
         VoxelType::Stone => egui::Color32::from_rgb(128, 128, 128),
+        VoxelType::Water => egui::Color32::from_rgb(38, 102, 217),
+        VoxelType::Lava => egui::Color32::from_rgb(255, 90, 13),
+        VoxelType::Glowstone => egui::Color32::from_rgb(255, 217, 115),
+        VoxelType::Glass => egui::Color32::from_rgba_unmultiplied(191, 230, 255, 128),
     }
 }
 
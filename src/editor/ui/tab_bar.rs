@@ -0,0 +1,50 @@
+//! Tab bar listing the open maps, shown between the toolbar and the viewport.
+
+use crate::editor::documents::{DocumentEvent, EditorDocuments};
+use crate::editor::shortcuts::modifier_key_label;
+use crate::editor::state::EditorState;
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+/// Render one tab per open map, with close buttons and a "+" for a new tab
+pub fn render_tab_bar(
+    ctx: &egui::Context,
+    editor_state: &EditorState,
+    documents: &EditorDocuments,
+    document_events: &mut MessageWriter<DocumentEvent>,
+) {
+    let mod_key = modifier_key_label();
+
+    egui::TopBottomPanel::top("document_tabs").show(ctx, |ui| {
+        egui::ScrollArea::horizontal().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 2.0;
+
+                for index in 0..documents.len() {
+                    let label = documents.tab_label(index, editor_state);
+                    let is_active = index == documents.active();
+
+                    if ui.selectable_label(is_active, &label).clicked() {
+                        document_events.write(DocumentEvent::Switch(index));
+                    }
+                    if ui
+                        .small_button("×")
+                        .on_hover_text(format!("Close tab ({mod_key}+W)"))
+                        .clicked()
+                    {
+                        document_events.write(DocumentEvent::Close(index));
+                    }
+                    ui.separator();
+                }
+
+                if ui
+                    .small_button("+")
+                    .on_hover_text(format!("New tab ({mod_key}+T)"))
+                    .clicked()
+                {
+                    document_events.write(DocumentEvent::New);
+                }
+            });
+        });
+    });
+}
@@ -1,7 +1,7 @@
 //! Helper UI components for the toolbar.
 
 use crate::editor::play::{PlayMapEvent, PlayTestState, StopGameEvent};
-use crate::editor::state::EditorState;
+use crate::editor::state::{EditorState, SnapMode};
 use bevy::prelude::*;
 use bevy_egui::egui;
 
@@ -35,6 +35,25 @@ pub fn render_view_toggles(ui: &mut egui::Ui, editor_state: &mut EditorState) {
         info!("Snap toggled: {}", editor_state.snap_to_grid);
     }
 
+    // Snap increment for entity placement and moves
+    ui.add_enabled_ui(editor_state.snap_to_grid, |ui| {
+        egui::ComboBox::from_id_salt("toolbar_snap_mode")
+            .selected_text(editor_state.snap_mode.label())
+            .width(100.0)
+            .show_ui(ui, |ui| {
+                for mode in SnapMode::ALL {
+                    if ui
+                        .selectable_value(&mut editor_state.snap_mode, mode, mode.label())
+                        .changed()
+                    {
+                        info!("Snap mode: {:?}", mode);
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Entity snap increment (hold Ctrl to place or drag freely)");
+    });
+
     // Entity labels toggle
     if ui
         .selectable_label(editor_state.show_entity_labels, "🏷 Labels")
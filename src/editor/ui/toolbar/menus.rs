@@ -1,13 +1,21 @@
 //! Menu bar rendering functions.
 
+use crate::editor::bookmarks::{BookmarkEvent, BOOKMARK_SLOTS};
+use crate::editor::extensions::EditorExtensions;
 use crate::editor::file_io::{SaveMapAsEvent, SaveMapEvent};
+use crate::editor::grid::{WorkPlane, WorkPlaneAxis};
 use crate::editor::history::EditorHistory;
 use crate::editor::play::{PlayMapEvent, PlayTestState, StopGameEvent};
-use crate::editor::recent_files::{OpenRecentFileEvent, RecentFiles};
+use crate::editor::preferences::EditorPreferences;
+use crate::editor::recent_files::{
+    format_modified, OpenRecentFileEvent, RecentFiles, THUMBNAIL_SIZE,
+};
 use crate::editor::shortcuts::{modifier_key_label, RedoEvent, UndoEvent};
 use crate::editor::state::{EditorState, EditorTool, EditorUIState, PendingAction, ToolMemory};
 use bevy::prelude::*;
 use bevy_egui::egui;
+use std::path::Path;
+use std::time::SystemTime;
 
 /// Render the File menu
 pub fn render_file_menu(
@@ -46,20 +54,29 @@ pub fn render_file_menu(
             if recent_files.is_empty() {
                 ui.label("No recent files");
             } else {
-                for path in recent_files.files.iter() {
-                    let display_name = RecentFiles::get_display_name(path);
-                    let tooltip = path.display().to_string();
-
-                    if ui.button(&display_name).on_hover_text(&tooltip).clicked() {
-                        if editor_state.is_modified {
-                            ui_state.unsaved_changes_dialog_open = true;
-                            ui_state.pending_action =
-                                Some(PendingAction::OpenRecentFile(path.clone()));
-                        } else {
-                            open_recent_events.write(OpenRecentFileEvent { path: path.clone() });
+                recent_files.update_previews(ui.ctx());
+                let mut chosen = None;
+                egui::Grid::new("recent_files_grid")
+                    .spacing([8.0, 8.0])
+                    .show(ui, |ui| {
+                        for (i, path) in recent_files.files.iter().enumerate() {
+                            if render_recent_file_tile(ui, recent_files, path) {
+                                chosen = Some(path.clone());
+                            }
+                            if i % RECENT_FILE_COLUMNS == RECENT_FILE_COLUMNS - 1 {
+                                ui.end_row();
+                            }
                         }
-                        ui.close();
+                    });
+
+                if let Some(path) = chosen {
+                    if editor_state.is_modified {
+                        ui_state.unsaved_changes_dialog_open = true;
+                        ui_state.pending_action = Some(PendingAction::OpenRecentFile(path));
+                    } else {
+                        open_recent_events.write(OpenRecentFileEvent { path });
                     }
+                    ui.close();
                 }
 
                 ui.separator();
@@ -71,6 +88,92 @@ pub fn render_file_menu(
             }
         });
 
+        if ui
+            .button("🔀 Compare With File...")
+            .on_hover_text("Review differences with another map file and merge them")
+            .clicked()
+        {
+            ui_state.compare_dialog_open = true;
+            ui.close();
+        }
+
+        let has_selection =
+            !editor_state.selected_voxels.is_empty() || !editor_state.selected_entities.is_empty();
+        if ui
+            .add_enabled(
+                has_selection,
+                egui::Button::new("📦 Export Selection as Prefab..."),
+            )
+            .on_hover_text("Save the selection to a .prefab.ron file to reuse in other maps")
+            .clicked()
+        {
+            ui_state.export_prefab_dialog_open = true;
+            ui.close();
+        }
+
+        if ui
+            .button("📥 Import Prefab...")
+            .on_hover_text("Place a .prefab.ron file into this map")
+            .clicked()
+        {
+            ui_state.import_prefab_dialog_open = true;
+            ui.close();
+        }
+
+        if ui
+            .button("🏗 Structure Generator...")
+            .on_hover_text("Stamp generated houses, trees and bridges at the cursor")
+            .clicked()
+        {
+            ui_state.generator_window_open = true;
+            ui.close();
+        }
+
+        if ui
+            .button("📋 Save as Template...")
+            .on_hover_text("Offer this map as a starting point in the New Map dialog")
+            .clicked()
+        {
+            ui_state.save_template_dialog_open = true;
+            ui.close();
+        }
+
+        if ui
+            .button("🗺 Map Settings...")
+            .on_hover_text("World bounds and out-of-bounds placement")
+            .clicked()
+        {
+            ui_state.map_settings_dialog_open = true;
+            ui.close();
+        }
+
+        if ui
+            .button("🔒 Locked Areas...")
+            .on_hover_text("Protect finished parts of the map from edits")
+            .clicked()
+        {
+            ui_state.locks_window_open = true;
+            ui.close();
+        }
+
+        if ui
+            .button("🎬 Camera Sequences...")
+            .on_hover_text("Author and preview cinematic camera sequences")
+            .clicked()
+        {
+            ui_state.camera_timeline_open = true;
+            ui.close();
+        }
+
+        if ui
+            .button("👥 Collaborate...")
+            .on_hover_text("Edit this map together with others over the network")
+            .clicked()
+        {
+            ui_state.collab_window_open = true;
+            ui.close();
+        }
+
         ui.separator();
 
         if ui.button(format!("💾 Save ({mod_key}+S)")).clicked() {
@@ -100,6 +203,45 @@ pub fn render_file_menu(
     });
 }
 
+/// Recent files per row of the Recent Files grid
+const RECENT_FILE_COLUMNS: usize = 3;
+
+/// Width of a tile in the Recent Files grid
+const RECENT_FILE_TILE_WIDTH: f32 = 120.0;
+
+/// Draw one recent file as a thumbnail with its map name, voxel count and
+/// age. Returns whether it was clicked.
+fn render_recent_file_tile(ui: &mut egui::Ui, recent_files: &RecentFiles, path: &Path) -> bool {
+    let size = egui::vec2(THUMBNAIL_SIZE as f32 * 2.0, THUMBNAIL_SIZE as f32 * 2.0);
+    let info = recent_files.info(path);
+    let tooltip = path.display().to_string();
+
+    ui.vertical(|ui| {
+        ui.set_width(RECENT_FILE_TILE_WIDTH);
+        let thumbnail = match recent_files.thumbnail(path) {
+            Some(texture) => ui.add(egui::Button::image(
+                egui::Image::new(texture).fit_to_exact_size(size),
+            )),
+            None => ui.add(egui::Button::new("⏳").min_size(size)),
+        };
+        let name = match info {
+            Some(info) if !info.name.is_empty() => info.name.clone(),
+            _ => RecentFiles::get_display_name(path),
+        };
+        let title = ui.add(egui::Button::new(egui::RichText::new(name).strong()).frame(false));
+        match info {
+            Some(info) => ui.small(format!(
+                "{} voxels · {}",
+                info.voxel_count,
+                format_modified(info.modified, SystemTime::now())
+            )),
+            None => ui.small(RecentFiles::get_display_name(path)),
+        };
+        (thumbnail | title).on_hover_text(tooltip).clicked()
+    })
+    .inner
+}
+
 /// Render the Edit menu
 pub fn render_edit_menu(
     ui: &mut egui::Ui,
@@ -142,8 +284,64 @@ pub fn render_edit_menu(
     });
 }
 
+/// Render the camera bookmarks submenu: recall, store, rename and clear
+/// each slot.
+fn render_bookmarks_menu(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    bookmark_events: &mut MessageWriter<BookmarkEvent>,
+) {
+    ui.menu_button("📌 Bookmarks", |ui| {
+        let mod_key = modifier_key_label();
+        for slot in 0..BOOKMARK_SLOTS {
+            let number = slot + 1;
+            ui.horizontal(|ui| {
+                match editor_state.camera_bookmarks.get_mut(slot) {
+                    Some(bookmark) => {
+                        if ui
+                            .button(format!("{number}"))
+                            .on_hover_text(format!("Go to bookmark ({mod_key}+{number})"))
+                            .clicked()
+                        {
+                            bookmark_events.write(BookmarkEvent::Recall(slot));
+                            ui.close();
+                        }
+                        ui.add(egui::TextEdit::singleline(&mut bookmark.name).desired_width(120.0));
+                    }
+                    None => {
+                        ui.add_enabled(false, egui::Button::new(format!("{number}")));
+                        ui.weak("Empty");
+                    }
+                }
+
+                if ui
+                    .small_button("💾")
+                    .on_hover_text(format!("Store current view ({mod_key}+Shift+{number})"))
+                    .clicked()
+                {
+                    bookmark_events.write(BookmarkEvent::Store(slot));
+                }
+                if editor_state.camera_bookmarks.get(slot).is_some()
+                    && ui
+                        .small_button("🗑")
+                        .on_hover_text("Clear bookmark")
+                        .clicked()
+                {
+                    editor_state.camera_bookmarks.clear(slot);
+                }
+            });
+        }
+    });
+}
+
 /// Render the View menu
-pub fn render_view_menu(ui: &mut egui::Ui, editor_state: &mut EditorState) {
+pub fn render_view_menu(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    ui_state: &mut EditorUIState,
+    preferences: &mut EditorPreferences,
+    bookmark_events: &mut MessageWriter<BookmarkEvent>,
+) {
     ui.menu_button("View", |ui| {
         if ui
             .checkbox(&mut editor_state.show_grid, "▦ Show Grid")
@@ -166,10 +364,166 @@ pub fn render_view_menu(ui: &mut egui::Ui, editor_state: &mut EditorState) {
             info!("Entity labels: {}", editor_state.show_entity_labels);
         }
 
+        if ui
+            .checkbox(&mut editor_state.show_jump_arcs, "⤴ Jump Arcs")
+            .on_hover_text("Preview jumps from the selected or hovered voxel")
+            .clicked()
+        {
+            info!("Jump arcs: {}", editor_state.show_jump_arcs);
+        }
+
+        if ui
+            .checkbox(
+                &mut editor_state.show_chunk_complexity,
+                "📊 Chunk Complexity",
+            )
+            .on_hover_text("Color chunks by quad count to find areas that are expensive to draw")
+            .clicked()
+        {
+            info!("Chunk complexity: {}", editor_state.show_chunk_complexity);
+        }
+
+        if ui
+            .checkbox(
+                &mut editor_state.show_collision_bounds,
+                "🧱 Collision Bounds",
+            )
+            .on_hover_text("Outline the collision boxes of the selected voxels' sub-voxels")
+            .clicked()
+        {
+            info!("Collision bounds: {}", editor_state.show_collision_bounds);
+        }
+
+        if ui
+            .checkbox(&mut editor_state.show_validation, "✔ Validation")
+            .on_hover_text("Check the map for problems the game would reject or work around")
+            .clicked()
+        {
+            info!("Validation panel: {}", editor_state.show_validation);
+        }
+
+        if ui
+            .checkbox(&mut editor_state.show_autotile, "🧩 Autotile Rules")
+            .on_hover_text("Change voxel types by their neighbours, such as buried grass to dirt")
+            .clicked()
+        {
+            info!("Autotile panel: {}", editor_state.show_autotile);
+        }
+
+        if ui
+            .checkbox(
+                &mut editor_state.show_playtest_heatmap,
+                "🔥 Playtest Heatmap",
+            )
+            .on_hover_text("Show where the player went in the loaded playtest log")
+            .clicked()
+        {
+            info!("Playtest heatmap: {}", editor_state.show_playtest_heatmap);
+        }
+
+        if ui
+            .button("📈 Load Playtest Log...")
+            .on_hover_text("Load a log recorded with the game's --playtest-log option")
+            .clicked()
+        {
+            ui_state.playtest_log_dialog_open = true;
+            ui.close();
+        }
+
+        if ui
+            .checkbox(&mut editor_state.quad_view, "▣ Quad View")
+            .on_hover_text("Split the viewport into perspective, top, front and side views")
+            .clicked()
+        {
+            info!("Quad view: {}", editor_state.quad_view);
+        }
+
+        if ui
+            .checkbox(
+                &mut preferences.continuous_rendering,
+                "⟳ Continuous Rendering",
+            )
+            .on_hover_text(
+                "Redraw every frame for smooth camera motion. When off, the editor \
+                 only redraws on input to save power.",
+            )
+            .clicked()
+        {
+            info!("Continuous rendering: {}", preferences.continuous_rendering);
+            preferences.save();
+        }
+
+        ui.menu_button("🖐 Touchpad Gestures", |ui| {
+            ui.label("Sensitivity of scrolling, pinching and rotating over the viewport");
+            let gestures = &mut preferences.gestures;
+            let mut save = false;
+            for (label, value) in [
+                ("Zoom", &mut gestures.zoom),
+                ("Pan", &mut gestures.pan),
+                ("Rotate", &mut gestures.rotate),
+            ] {
+                let response = ui.add(egui::Slider::new(value, 0.0..=3.0).text(label));
+                save |= response.drag_stopped() || (response.changed() && !response.dragged());
+            }
+            if ui.button("Reset").clicked() {
+                *gestures = Default::default();
+                save = true;
+            }
+            if save {
+                preferences.save();
+            }
+        });
+
+        ui.separator();
+
+        render_bookmarks_menu(ui, editor_state, bookmark_events);
+
         ui.separator();
 
         ui.label("Grid Opacity");
         ui.add(egui::Slider::new(&mut editor_state.grid_opacity, 0.0..=1.0));
+
+        ui.separator();
+
+        if ui
+            .button("📏 Construction Guides...")
+            .on_hover_text("Planes and lines the cursor snaps to when placing")
+            .clicked()
+        {
+            ui_state.guides_window_open = true;
+            ui.close();
+        }
+
+        ui.separator();
+
+        // Work plane: where the grid is drawn and where the cursor lands
+        // when it isn't over a voxel
+        ui.label("Work Plane");
+        let plane = &mut editor_state.work_plane;
+        for axis in WorkPlaneAxis::ALL {
+            if ui
+                .radio_value(&mut plane.axis, axis, axis.label())
+                .changed()
+            {
+                info!("Work plane: {:?}", plane);
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label(format!("{} Offset", plane.axis.offset_axis()));
+            if ui.add(egui::DragValue::new(&mut plane.offset)).changed() {
+                info!("Work plane: {:?}", plane);
+            }
+        });
+        if ui
+            .add_enabled(
+                *plane != WorkPlane::default(),
+                egui::Button::new("Reset to Ground"),
+            )
+            .clicked()
+        {
+            *plane = WorkPlane::default();
+            info!("Work plane reset to ground");
+        }
     });
 }
 
@@ -231,6 +585,7 @@ pub fn render_tools_menu(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     tool_memory: &mut ToolMemory,
+    extensions: &EditorExtensions,
 ) {
     ui.menu_button("Tools", |ui| {
         // Helper to save current tool parameters before switching
@@ -248,6 +603,36 @@ pub fn render_tools_menu(
                 EditorTool::EntityPlace { entity_type } => {
                     tool_memory.entity_type = *entity_type;
                 }
+                EditorTool::Sculpt {
+                    mode,
+                    radius,
+                    jitter,
+                } => {
+                    tool_memory.sculpt_mode = *mode;
+                    tool_memory.sculpt_radius = *radius;
+                    tool_memory.sculpt_jitter = *jitter;
+                }
+                EditorTool::Paint {
+                    voxel_type,
+                    radius,
+                    mask,
+                } => {
+                    tool_memory.voxel_type = *voxel_type;
+                    tool_memory.paint_radius = *radius;
+                    tool_memory.paint_mask = *mask;
+                }
+                EditorTool::Spline {
+                    voxel_type,
+                    pattern,
+                    width,
+                } => {
+                    tool_memory.voxel_type = *voxel_type;
+                    tool_memory.voxel_pattern = *pattern;
+                    tool_memory.spline_width = *width;
+                }
+                EditorTool::Staircase { voxel_type } => {
+                    tool_memory.voxel_type = *voxel_type;
+                }
                 _ => {}
             };
 
@@ -301,6 +686,56 @@ pub fn render_tools_menu(
             ui.close();
         }
 
+        let is_sculpt = matches!(editor_state.active_tool, EditorTool::Sculpt { .. });
+        if ui.selectable_label(is_sculpt, "🖌 Sculpt (M)").clicked() {
+            if !is_sculpt {
+                save_current_params(editor_state, tool_memory);
+                editor_state.active_tool = EditorTool::Sculpt {
+                    mode: tool_memory.sculpt_mode,
+                    radius: tool_memory.sculpt_radius,
+                    jitter: tool_memory.sculpt_jitter,
+                };
+            }
+            ui.close();
+        }
+
+        let is_paint = matches!(editor_state.active_tool, EditorTool::Paint { .. });
+        if ui.selectable_label(is_paint, "🎨 Paint (T)").clicked() {
+            if !is_paint {
+                save_current_params(editor_state, tool_memory);
+                editor_state.active_tool = EditorTool::Paint {
+                    voxel_type: tool_memory.voxel_type,
+                    radius: tool_memory.paint_radius,
+                    mask: tool_memory.paint_mask,
+                };
+            }
+            ui.close();
+        }
+
+        let is_spline = matches!(editor_state.active_tool, EditorTool::Spline { .. });
+        if ui.selectable_label(is_spline, "〰 Spline (P)").clicked() {
+            if !is_spline {
+                save_current_params(editor_state, tool_memory);
+                editor_state.active_tool = EditorTool::Spline {
+                    voxel_type: tool_memory.voxel_type,
+                    pattern: tool_memory.voxel_pattern,
+                    width: tool_memory.spline_width,
+                };
+            }
+            ui.close();
+        }
+
+        let is_staircase = matches!(editor_state.active_tool, EditorTool::Staircase { .. });
+        if ui.selectable_label(is_staircase, "⟋ Staircase").clicked() {
+            if !is_staircase {
+                save_current_params(editor_state, tool_memory);
+                editor_state.active_tool = EditorTool::Staircase {
+                    voxel_type: tool_memory.voxel_type,
+                };
+            }
+            ui.close();
+        }
+
         let is_camera = matches!(editor_state.active_tool, EditorTool::Camera);
         if ui.selectable_label(is_camera, "📷 Camera (C)").clicked() {
             if !is_camera {
@@ -309,6 +744,30 @@ pub fn render_tools_menu(
             }
             ui.close();
         }
+
+        // Tools added by editor plugins
+        if !extensions.tools().is_empty() {
+            ui.separator();
+        }
+        for tool in extensions.tools() {
+            let is_active = matches!(
+                &editor_state.active_tool,
+                EditorTool::Custom { name } if *name == tool.name
+            );
+            if ui
+                .selectable_label(is_active, format!("{} {}", tool.icon, tool.name))
+                .on_hover_text(&tool.description)
+                .clicked()
+            {
+                if !is_active {
+                    save_current_params(editor_state, tool_memory);
+                    editor_state.active_tool = EditorTool::Custom {
+                        name: tool.name.clone(),
+                    };
+                }
+                ui.close();
+            }
+        }
     });
 }
 
@@ -20,9 +20,12 @@ pub use menus::{
 pub use tool_buttons::render_tool_buttons;
 pub use tool_options::{entity_type_display, pattern_short_name, render_tool_options};
 
+use crate::editor::bookmarks::BookmarkEvent;
+use crate::editor::extensions::{render_extensions_menu, EditorExtensions, ExtensionCommandEvent};
 use crate::editor::file_io::{SaveMapAsEvent, SaveMapEvent};
 use crate::editor::history::EditorHistory;
 use crate::editor::play::{PlayMapEvent, PlayTestState, StopGameEvent};
+use crate::editor::preferences::EditorPreferences;
 use crate::editor::recent_files::{OpenRecentFileEvent, RecentFiles};
 use crate::editor::shortcuts::{RedoEvent, UndoEvent};
 use crate::editor::state::{EditorState, EditorUIState, ToolMemory};
@@ -38,6 +41,7 @@ pub fn render_toolbar(
     tool_memory: &mut ToolMemory,
     history: &EditorHistory,
     recent_files: &mut RecentFiles,
+    preferences: &mut EditorPreferences,
     play_state: &mut PlayTestState,
     save_events: &mut MessageWriter<SaveMapEvent>,
     save_as_events: &mut MessageWriter<SaveMapAsEvent>,
@@ -46,6 +50,9 @@ pub fn render_toolbar(
     stop_events: &mut MessageWriter<StopGameEvent>,
     undo_events: &mut MessageWriter<UndoEvent>,
     redo_events: &mut MessageWriter<RedoEvent>,
+    bookmark_events: &mut MessageWriter<BookmarkEvent>,
+    extensions: &EditorExtensions,
+    command_events: &mut MessageWriter<ExtensionCommandEvent>,
 ) {
     // Menu bar panel
     egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
@@ -60,9 +67,10 @@ pub fn render_toolbar(
                 open_recent_events,
             );
             render_edit_menu(ui, history, undo_events, redo_events);
-            render_view_menu(ui, editor_state);
+            render_view_menu(ui, editor_state, ui_state, preferences, bookmark_events);
             render_run_menu(ui, play_state, play_events, stop_events);
-            render_tools_menu(ui, editor_state, tool_memory);
+            render_tools_menu(ui, editor_state, tool_memory, extensions);
+            render_extensions_menu(ui, extensions, command_events);
             render_help_menu(ui, ui_state);
 
             // Spacer to push map name to the right
@@ -80,7 +88,7 @@ pub fn render_toolbar(
             ui.spacing_mut().item_spacing.x = 4.0;
 
             // === Tool Buttons ===
-            render_tool_buttons(ui, editor_state, tool_memory);
+            render_tool_buttons(ui, editor_state, tool_memory, extensions);
 
             ui.separator();
 
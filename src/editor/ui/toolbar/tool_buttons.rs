@@ -1,5 +1,6 @@
 //! Tool button rendering for the toolbar.
 
+use crate::editor::extensions::{render_extension_tool_buttons, EditorExtensions};
 use crate::editor::state::{EditorState, EditorTool, ToolMemory};
 use bevy_egui::egui;
 
@@ -8,6 +9,7 @@ pub fn render_tool_buttons(
     ui: &mut egui::Ui,
     editor_state: &mut EditorState,
     tool_memory: &mut ToolMemory,
+    extensions: &EditorExtensions,
 ) {
     // Get current tool state for highlighting
     let is_select = matches!(editor_state.active_tool, EditorTool::Select);
@@ -15,6 +17,10 @@ pub fn render_tool_buttons(
     let is_voxel_remove = matches!(editor_state.active_tool, EditorTool::VoxelRemove);
     let is_entity_place = matches!(editor_state.active_tool, EditorTool::EntityPlace { .. });
     let is_camera = matches!(editor_state.active_tool, EditorTool::Camera);
+    let is_sculpt = matches!(editor_state.active_tool, EditorTool::Sculpt { .. });
+    let is_paint = matches!(editor_state.active_tool, EditorTool::Paint { .. });
+    let is_spline = matches!(editor_state.active_tool, EditorTool::Spline { .. });
+    let is_staircase = matches!(editor_state.active_tool, EditorTool::Staircase { .. });
 
     // Tool button style helper
     let tool_button = |ui: &mut egui::Ui, icon: &str, tooltip: &str, is_active: bool| -> bool {
@@ -42,6 +48,36 @@ pub fn render_tool_buttons(
             EditorTool::EntityPlace { entity_type } => {
                 tool_memory.entity_type = *entity_type;
             }
+            EditorTool::Sculpt {
+                mode,
+                radius,
+                jitter,
+            } => {
+                tool_memory.sculpt_mode = *mode;
+                tool_memory.sculpt_radius = *radius;
+                tool_memory.sculpt_jitter = *jitter;
+            }
+            EditorTool::Paint {
+                voxel_type,
+                radius,
+                mask,
+            } => {
+                tool_memory.voxel_type = *voxel_type;
+                tool_memory.paint_radius = *radius;
+                tool_memory.paint_mask = *mask;
+            }
+            EditorTool::Spline {
+                voxel_type,
+                pattern,
+                width,
+            } => {
+                tool_memory.voxel_type = *voxel_type;
+                tool_memory.voxel_pattern = *pattern;
+                tool_memory.spline_width = *width;
+            }
+            EditorTool::Staircase { voxel_type } => {
+                tool_memory.voxel_type = *voxel_type;
+            }
             _ => {}
         }
     };
@@ -101,6 +137,68 @@ pub fn render_tool_buttons(
         };
     }
 
+    // Sculpt Tool (M)
+    if tool_button(
+        ui,
+        "🖌",
+        "Sculpt Tool (M)\nDrag over terrain to raise, lower or smooth it",
+        is_sculpt,
+    ) && !is_sculpt
+    {
+        save_current_params(editor_state, tool_memory);
+        editor_state.active_tool = EditorTool::Sculpt {
+            mode: tool_memory.sculpt_mode,
+            radius: tool_memory.sculpt_radius,
+            jitter: tool_memory.sculpt_jitter,
+        };
+    }
+
+    // Paint Tool (T)
+    if tool_button(
+        ui,
+        "🎨",
+        "Paint Tool (T)\nDrag over surfaces to change their voxel type",
+        is_paint,
+    ) && !is_paint
+    {
+        save_current_params(editor_state, tool_memory);
+        editor_state.active_tool = EditorTool::Paint {
+            voxel_type: tool_memory.voxel_type,
+            radius: tool_memory.paint_radius,
+            mask: tool_memory.paint_mask,
+        };
+    }
+
+    // Spline Tool (P)
+    if tool_button(
+        ui,
+        "〰",
+        "Spline Tool (P)\nClick to add curve points, Enter to lay voxels",
+        is_spline,
+    ) && !is_spline
+    {
+        save_current_params(editor_state, tool_memory);
+        editor_state.active_tool = EditorTool::Spline {
+            voxel_type: tool_memory.voxel_type,
+            pattern: tool_memory.voxel_pattern,
+            width: tool_memory.spline_width,
+        };
+    }
+
+    // Staircase Tool
+    if tool_button(
+        ui,
+        "⟋",
+        "Staircase Tool\nClick a start and an end voxel, Enter to build stairs",
+        is_staircase,
+    ) && !is_staircase
+    {
+        save_current_params(editor_state, tool_memory);
+        editor_state.active_tool = EditorTool::Staircase {
+            voxel_type: tool_memory.voxel_type,
+        };
+    }
+
     // Camera Tool (C)
     if tool_button(
         ui,
@@ -112,4 +210,10 @@ pub fn render_tool_buttons(
         save_current_params(editor_state, tool_memory);
         editor_state.active_tool = EditorTool::Camera;
     }
+
+    // Tools added by editor plugins
+    if let Some(tool) = render_extension_tool_buttons(ui, editor_state, extensions) {
+        save_current_params(editor_state, tool_memory);
+        editor_state.active_tool = tool;
+    }
 }
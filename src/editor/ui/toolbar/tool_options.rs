@@ -1,8 +1,14 @@
 //! Tool-specific option rendering (dropdowns, selection info).
 
-use crate::editor::state::{EditorState, EditorTool, ToolMemory};
+use crate::editor::shortcuts::modifier_key_label;
+use crate::editor::state::{EditorState, EditorTool, SculptMode, ToolMemory};
+use crate::editor::tools::paint_tool::MAX_PAINT_RADIUS;
+use crate::editor::tools::sculpt_tool::MAX_SCULPT_RADIUS;
+use crate::editor::tools::spline_tool::MAX_SPLINE_WIDTH;
+use crate::editor::ui::properties::render_paint_mask_combo;
 use crate::systems::game::components::VoxelType;
-use crate::systems::game::map::format::{EntityType, SubVoxelPattern};
+use crate::systems::game::map::format::{EntityType, SubVoxelPattern, IDENTITY};
+use crate::systems::game::map::geometry::RotationAxis;
 use bevy::prelude::*;
 use bevy_egui::egui;
 
@@ -18,6 +24,7 @@ pub fn render_tool_options(
             pattern,
         } => {
             render_voxel_place_options(ui, voxel_type, pattern, tool_memory);
+            render_placement_rotation(ui, editor_state);
         }
 
         EditorTool::EntityPlace { entity_type } => {
@@ -29,13 +36,88 @@ pub fn render_tool_options(
         }
 
         EditorTool::VoxelRemove => {
-            ui.label("Click voxels to remove");
+            ui.label(format!(
+                "Click voxels to remove | {}+Click: remove connected",
+                modifier_key_label()
+            ));
+            ui.checkbox(&mut tool_memory.remove_connected_same_type, "Same type")
+                .on_hover_text("Connected removal only follows voxels of the clicked type");
         }
 
         EditorTool::Camera => {
             ui.label("RMB: Orbit | MMB: Pan | Scroll: Zoom");
         }
+
+        EditorTool::Sculpt {
+            mode,
+            radius,
+            jitter,
+        } => {
+            render_sculpt_options(ui, mode, radius, jitter, tool_memory);
+        }
+
+        EditorTool::Paint {
+            voxel_type,
+            radius,
+            mask,
+        } => {
+            render_paint_options(ui, voxel_type, radius, mask, tool_memory);
+        }
+
+        EditorTool::Spline {
+            voxel_type,
+            pattern,
+            width,
+        } => {
+            render_voxel_place_options(ui, voxel_type, pattern, tool_memory);
+            ui.label("Width:");
+            if ui
+                .add(egui::DragValue::new(width).range(1..=MAX_SPLINE_WIDTH))
+                .changed()
+            {
+                tool_memory.spline_width = *width;
+            }
+            ui.label("Enter: Lay voxels | Backspace: Undo point | Esc: Clear");
+        }
+
+        EditorTool::Staircase { voxel_type } => {
+            render_staircase_options(ui, voxel_type, tool_memory);
+        }
+
+        EditorTool::Custom { name } => {
+            ui.label(format!("🧩 {}", name));
+        }
+    }
+}
+
+/// Render staircase tool options
+fn render_staircase_options(
+    ui: &mut egui::Ui,
+    voxel_type: &mut VoxelType,
+    tool_memory: &mut ToolMemory,
+) {
+    ui.label("Type:");
+    let type_changed = egui::ComboBox::from_id_salt("toolbar_staircase_type")
+        .selected_text(format!("{:?}", voxel_type))
+        .width(80.0)
+        .show_ui(ui, |ui| {
+            let mut changed = false;
+            for (option, label) in [
+                (VoxelType::Grass, "🟩 Grass"),
+                (VoxelType::Dirt, "🟫 Dirt"),
+                (VoxelType::Stone, "⬜ Stone"),
+            ] {
+                changed |= ui.selectable_value(voxel_type, option, label).changed();
+            }
+            changed
+        })
+        .inner
+        .unwrap_or(false);
+    if type_changed {
+        tool_memory.voxel_type = *voxel_type;
     }
+
+    ui.label("Click: Start, then end | Enter: Build | Esc: Clear");
 }
 
 /// Render voxel place tool options
@@ -61,6 +143,18 @@ fn render_voxel_place_options(
             changed |= ui
                 .selectable_value(voxel_type, VoxelType::Stone, "⬜ Stone")
                 .changed();
+            changed |= ui
+                .selectable_value(voxel_type, VoxelType::Water, "🟦 Water ⚠")
+                .changed();
+            changed |= ui
+                .selectable_value(voxel_type, VoxelType::Lava, "🟧 Lava ⚠")
+                .changed();
+            changed |= ui
+                .selectable_value(voxel_type, VoxelType::Glowstone, "🟨 Glowstone")
+                .changed();
+            changed |= ui
+                .selectable_value(voxel_type, VoxelType::Glass, "◻ Glass")
+                .changed();
             changed
         })
         .inner
@@ -111,6 +205,130 @@ fn render_voxel_place_options(
     }
 }
 
+/// Render paint tool options
+fn render_paint_options(
+    ui: &mut egui::Ui,
+    voxel_type: &mut VoxelType,
+    radius: &mut u32,
+    mask: &mut Option<VoxelType>,
+    tool_memory: &mut ToolMemory,
+) {
+    ui.label("Type:");
+    let type_changed = egui::ComboBox::from_id_salt("toolbar_paint_type")
+        .selected_text(format!("{:?}", voxel_type))
+        .width(80.0)
+        .show_ui(ui, |ui| {
+            let mut changed = false;
+            for (option, label) in [
+                (VoxelType::Grass, "🟩 Grass"),
+                (VoxelType::Dirt, "🟫 Dirt"),
+                (VoxelType::Stone, "⬜ Stone"),
+                (VoxelType::Water, "🟦 Water ⚠"),
+                (VoxelType::Lava, "🟧 Lava ⚠"),
+                (VoxelType::Glowstone, "🟨 Glowstone"),
+                (VoxelType::Glass, "◻ Glass"),
+            ] {
+                changed |= ui.selectable_value(voxel_type, option, label).changed();
+            }
+            changed
+        })
+        .inner
+        .unwrap_or(false);
+    if type_changed {
+        tool_memory.voxel_type = *voxel_type;
+    }
+
+    ui.label("Radius:");
+    if ui
+        .add(egui::DragValue::new(radius).range(1..=MAX_PAINT_RADIUS))
+        .changed()
+    {
+        tool_memory.paint_radius = *radius;
+    }
+
+    ui.label("Only over:");
+    if render_paint_mask_combo(ui, "toolbar_paint_mask", mask) {
+        tool_memory.paint_mask = *mask;
+    }
+}
+
+/// Render sculpt tool options
+fn render_sculpt_options(
+    ui: &mut egui::Ui,
+    mode: &mut SculptMode,
+    radius: &mut u32,
+    jitter: &mut f32,
+    tool_memory: &mut ToolMemory,
+) {
+    for option in SculptMode::ALL {
+        if ui.selectable_value(mode, option, option.label()).changed() {
+            tool_memory.sculpt_mode = *mode;
+        }
+    }
+    ui.label("Radius:");
+    if ui
+        .add(egui::DragValue::new(radius).range(1..=MAX_SCULPT_RADIUS))
+        .changed()
+    {
+        tool_memory.sculpt_radius = *radius;
+    }
+    ui.label("Noise:");
+    if ui
+        .add(
+            egui::DragValue::new(jitter)
+                .range(0.0..=1.0)
+                .speed(0.01)
+                .fixed_decimals(2),
+        )
+        .on_hover_text("Randomly vary each column for rougher terrain")
+        .changed()
+    {
+        tool_memory.sculpt_jitter = *jitter;
+    }
+}
+
+/// Render rotate/flip buttons for the orientation of placed voxels
+fn render_placement_rotation(ui: &mut egui::Ui, editor_state: &mut EditorState) {
+    ui.separator();
+    ui.label("Rotate:");
+    if ui
+        .button("⟳Y")
+        .on_hover_text("Rotate 90° about Y (R)")
+        .clicked()
+    {
+        editor_state.rotate_placement(RotationAxis::Y, 1);
+    }
+    if ui
+        .button("⟳X")
+        .on_hover_text("Rotate 90° about X (Shift+R)")
+        .clicked()
+    {
+        editor_state.rotate_placement(RotationAxis::X, 1);
+    }
+    if ui
+        .button("⟳Z")
+        .on_hover_text("Rotate 90° about Z (Alt+R)")
+        .clicked()
+    {
+        editor_state.rotate_placement(RotationAxis::Z, 1);
+    }
+    if ui
+        .button("Flip")
+        .on_hover_text("Turn upside down")
+        .clicked()
+    {
+        editor_state.rotate_placement(RotationAxis::X, 2);
+    }
+    let rotated = editor_state.placement_orientation != IDENTITY;
+    if ui
+        .add_enabled(rotated, egui::Button::new("Reset"))
+        .on_hover_text("Place voxels unrotated")
+        .clicked()
+    {
+        editor_state.placement_orientation = IDENTITY;
+    }
+}
+
 /// Render entity place tool options
 fn render_entity_place_options(
     ui: &mut egui::Ui,
@@ -141,6 +359,15 @@ fn render_entity_place_options(
             changed |= ui
                 .selectable_value(entity_type, EntityType::LightSource, "💡 Light Source")
                 .changed();
+            changed |= ui
+                .selectable_value(entity_type, EntityType::Door, "🚪 Door")
+                .changed();
+            changed |= ui
+                .selectable_value(entity_type, EntityType::Teleporter, "🌀 Teleporter")
+                .changed();
+            changed |= ui
+                .selectable_value(entity_type, EntityType::Goal, "🏁 Goal")
+                .changed();
             changed
         })
         .inner
@@ -213,5 +440,8 @@ pub fn entity_type_display(entity_type: &EntityType) -> &'static str {
         EntityType::Item => "🟡 Item",
         EntityType::Trigger => "🟣 Trigger",
         EntityType::LightSource => "💡 Light Source",
+        EntityType::Door => "🚪 Door",
+        EntityType::Teleporter => "🌀 Teleporter",
+        EntityType::Goal => "🏁 Goal",
     }
 }
@@ -1,5 +1,6 @@
 //! Viewport controls and status display.
 
+use crate::editor::camera::EditorCamera;
 use crate::editor::cursor::CursorState;
 use crate::editor::renderer::EditorEntityMarker;
 use crate::editor::state::{EditorState, EditorTool, KeyboardEditMode};
@@ -162,6 +163,25 @@ fn render_keyboard_mode_indicator(
                             ui.label("Camera tool active");
                             ui.label("Use mouse to control");
                         }
+                        EditorTool::Sculpt { .. } => {
+                            ui.label("Sculpt tool active");
+                            ui.label("Use mouse to sculpt");
+                        }
+                        EditorTool::Paint { .. } => {
+                            ui.label("Paint tool active");
+                            ui.label("Use mouse to paint");
+                        }
+                        EditorTool::Spline { .. } => {
+                            ui.label("Spline tool active");
+                            ui.label("Use mouse to add points");
+                        }
+                        EditorTool::Staircase { .. } => {
+                            ui.label("Staircase tool active");
+                            ui.label("Use mouse to pick end points");
+                        }
+                        EditorTool::Custom { name } => {
+                            ui.label(format!("{} tool active", name));
+                        }
                     }
 
                     ui.separator();
@@ -357,6 +377,27 @@ fn render_tool_hint(
                                 ui.small("RMB: Orbit │ MMB: Pan");
                                 ui.small("Scroll: Zoom │ Home: Reset");
                             }
+                            EditorTool::Sculpt { mode, radius, .. } => {
+                                ui.small(format!("{} │ Radius {}", mode.label(), radius));
+                                ui.small("Drag: Sculpt terrain");
+                            }
+                            EditorTool::Paint {
+                                voxel_type, radius, ..
+                            } => {
+                                ui.small(format!("{:?} │ Radius {}", voxel_type, radius));
+                                ui.small("Drag: Paint surfaces");
+                            }
+                            EditorTool::Spline { width, .. } => {
+                                ui.small(format!("Width {}", width));
+                                ui.small("Click: Add point │ Enter: Lay voxels");
+                            }
+                            EditorTool::Staircase { voxel_type } => {
+                                ui.small(format!("{:?} │ Stairs", voxel_type));
+                                ui.small("Click: Start, end │ Enter: Build");
+                            }
+                            EditorTool::Custom { name } => {
+                                ui.small(format!("🧩 {}", name));
+                            }
                         }
                     }
                 });
@@ -387,7 +428,7 @@ pub fn render_entity_name_labels(
     mut contexts: EguiContexts,
     mut editor_state: ResMut<EditorState>,
     markers: Query<(&EditorEntityMarker, &GlobalTransform)>,
-    camera: Single<(&Camera, &GlobalTransform), With<Camera3d>>,
+    camera: Single<(&Camera, &GlobalTransform), With<EditorCamera>>,
 ) {
     // Early-exit: user has hidden all entity labels.
     if !editor_state.show_entity_labels {
@@ -396,6 +437,7 @@ pub fn render_entity_name_labels(
 
     let ctx = contexts.ctx_mut().expect("egui context");
     let (camera_comp, camera_transform) = camera.into_inner();
+    let viewport_rect = camera_comp.logical_viewport_rect();
 
     for (marker, marker_transform) in &markers {
         let index = marker.entity_index;
@@ -412,6 +454,9 @@ pub fn render_entity_name_labels(
             EntityType::Item => (egui::Color32::from_rgb(255, 215, 0), "Item"),
             EntityType::Trigger => (egui::Color32::from_rgb(100, 220, 220), "Trigger"),
             EntityType::LightSource => (egui::Color32::from_rgb(255, 180, 50), "LightSource"),
+            EntityType::Door => (egui::Color32::from_rgb(210, 160, 110), "Door"),
+            EntityType::Teleporter => (egui::Color32::from_rgb(80, 200, 255), "Teleporter"),
+            EntityType::Goal => (egui::Color32::from_rgb(120, 230, 120), "Goal"),
             // PlayerSpawn does not receive a label.
             EntityType::PlayerSpawn => continue,
         };
@@ -436,6 +481,12 @@ pub fn render_entity_name_labels(
         let Ok(screen_pos) = camera_comp.world_to_viewport(camera_transform, world_pos) else {
             continue;
         };
+        // Keep labels out of the other views in quad view
+        if camera_comp.viewport.is_some()
+            && !viewport_rect.is_some_and(|rect| rect.contains(screen_pos))
+        {
+            continue;
+        }
 
         let area_resp = egui::Area::new(egui::Id::new(("entity_label", index)))
             .fixed_pos(egui::pos2(screen_pos.x, screen_pos.y))
@@ -464,8 +515,7 @@ pub fn render_entity_name_labels(
         let interact_resp = area_resp.inner;
 
         if interact_resp.clicked() {
-            editor_state.selected_entities.clear();
-            editor_state.selected_entities.insert(index);
+            editor_state.select_entity(index);
             editor_state.outliner_scroll_to = Some(index);
         }
 
@@ -75,6 +75,7 @@ fn named_light_source_is_shown() {
 
 fn make_entity(entity_type: EntityType, props: &[(&str, &str)]) -> EntityData {
     EntityData {
+        id: Default::default(),
         entity_type,
         position: (1.0, 2.0, 3.0),
         properties: props
@@ -0,0 +1,267 @@
+//! Map validation panel.
+//!
+//! View → Validation runs the game's map validation on the open map and
+//! lists what it would reject. Duplicate voxels (several voxels at one
+//! position, common in maps exported or merged by hand) are reported on
+//! their own with a one-click cleanup that keeps the last voxel at each
+//! position, the one the game keeps when it loads the map. The panel opens
+//! by itself when a map with duplicates is loaded.
+//!
+//! Maps that don't parse are opened in safe mode, which skips the malformed
+//! sections and items (see `loader::safe_mode`); the panel lists what was
+//! skipped, with line numbers, so it can be fixed in the file before saving
+//! over it.
+
+use crate::editor::go_to::GoToEvent;
+use crate::editor::history::{EditorAction, EditorHistory};
+use crate::editor::state::EditorState;
+use crate::systems::game::go_to::GoToDestination;
+use crate::systems::game::map::format::MapData;
+use crate::systems::game::map::loader::LoadDiagnostic;
+use crate::systems::game::map::validation::validate_map;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+/// Number of duplicate positions listed in the panel
+pub const LISTED_DUPLICATES: usize = 10;
+
+/// What validation found in a map.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Voxels sharing their position with a later voxel
+    pub duplicate_voxels: usize,
+    /// Positions holding more than one voxel, in ascending order
+    pub duplicate_positions: Vec<(i32, i32, i32)>,
+    /// First problem other than duplicates, as the game reports it
+    pub error: Option<String>,
+}
+
+impl ValidationReport {
+    /// Whether the map passes validation.
+    pub fn is_valid(&self) -> bool {
+        self.duplicate_voxels == 0 && self.error.is_none()
+    }
+}
+
+/// Validate `map`, reporting duplicates apart from the other problems.
+pub fn check_map(map: &MapData) -> ValidationReport {
+    let duplicate_voxels = map.world.voxels.duplicate_count();
+    let error = if duplicate_voxels == 0 {
+        validate_map(map).err()
+    } else {
+        // Validation stops at the first duplicate; look past them
+        let mut deduplicated = map.clone();
+        deduplicated.world.voxels.remove_duplicates();
+        validate_map(&deduplicated).err()
+    };
+    ValidationReport {
+        duplicate_voxels,
+        duplicate_positions: map.world.voxels.duplicate_positions(),
+        error: error.map(|e| e.to_string()),
+    }
+}
+
+/// Remove duplicate voxels from the open map, keeping the last voxel at
+/// each position, as one undo step. Returns how many were removed.
+///
+/// History places voxels by replacing what is at their position, so it
+/// can't recreate duplicates. Each position is recorded as removing every
+/// voxel there and placing the kept one again, which undoes back to the
+/// voxel the game shows rather than to a dropped copy.
+pub fn remove_duplicate_voxels(
+    editor_state: &mut EditorState,
+    history: &mut EditorHistory,
+) -> usize {
+    let positions = editor_state.current_map.world.voxels.duplicate_positions();
+    if positions.is_empty() {
+        return 0;
+    }
+    let voxels = &mut editor_state.current_map.world.voxels;
+    let mut actions = Vec::new();
+    let mut removed = 0;
+    for pos in positions {
+        // Removal takes the voxel lookups find, the last one listed, first
+        let mut kept = None;
+        while let Some(data) = voxels.remove(pos) {
+            if kept.is_none() {
+                kept = Some(data.clone());
+            } else {
+                removed += 1;
+            }
+            actions.push(EditorAction::RemoveVoxel { pos, data });
+        }
+        let Some(kept) = kept else {
+            continue;
+        };
+        voxels.insert(kept.clone());
+        actions.push(EditorAction::PlaceVoxel { pos, data: kept });
+    }
+    history.push(EditorAction::Batch {
+        description: "Remove Duplicate Voxels".to_string(),
+        actions,
+    });
+    editor_state.mark_modified();
+    info!("Removed {} duplicate voxels", removed);
+    removed
+}
+
+/// Record what safe mode skipped loading the open map, opening the panel
+/// when it skipped anything.
+pub fn set_load_diagnostics(editor_state: &mut EditorState, diagnostics: Vec<LoadDiagnostic>) {
+    if !diagnostics.is_empty() {
+        warn!(
+            "The map was opened in safe mode, skipping {} malformed parts; see View → Validation",
+            diagnostics.len()
+        );
+        editor_state.show_validation = true;
+    }
+    editor_state.load_diagnostics = diagnostics;
+}
+
+/// Open the validation panel when a map with duplicate voxels is opened or
+/// an edit brings some in.
+pub fn open_validation_on_duplicates(
+    mut editor_state: ResMut<EditorState>,
+    mut checked: Local<Option<(u64, bool)>>,
+) {
+    let revision = editor_state.revision;
+    if checked.is_some_and(|(checked, _)| checked == revision) {
+        return;
+    }
+    let had_duplicates = checked.is_some_and(|(_, had)| had);
+    let has_duplicates = editor_state.current_map.world.voxels.duplicate_count() > 0;
+    *checked = Some((revision, has_duplicates));
+    if has_duplicates && !had_duplicates && !editor_state.show_validation {
+        warn!("The map has duplicate voxels; see View → Validation");
+        editor_state.show_validation = true;
+    }
+}
+
+/// Render the validation panel while it is open.
+pub fn render_validation_window(
+    mut contexts: EguiContexts,
+    mut editor_state: ResMut<EditorState>,
+    mut history: ResMut<EditorHistory>,
+    mut go_to_events: MessageWriter<GoToEvent>,
+    mut report: Local<Option<(u64, ValidationReport)>>,
+) {
+    if !editor_state.show_validation {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    // Validate again only after the map changes
+    let revision = editor_state.revision;
+    if report
+        .as_ref()
+        .is_none_or(|(checked, _)| *checked != revision)
+    {
+        *report = Some((revision, check_map(&editor_state.current_map)));
+    }
+    let Some((_, report)) = report.as_ref() else {
+        return;
+    };
+    let mut open = true;
+    let mut clean_up = false;
+
+    egui::Window::new("Map Validation")
+        .open(&mut open)
+        .default_width(340.0)
+        .show(ctx, |ui| {
+            if report.is_valid() && editor_state.load_diagnostics.is_empty() {
+                ui.label("✔ No problems found.");
+            }
+            if let Some(error) = &report.error {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("⚠ {}", error));
+            }
+
+            if !editor_state.load_diagnostics.is_empty() {
+                ui.separator();
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!(
+                        "⚠ Opened in safe mode: {} malformed parts skipped",
+                        editor_state.load_diagnostics.len()
+                    ),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Saving writes the map without them. Fix the file in a text \
+                         editor and reopen it, or save under a new name.",
+                    )
+                    .small()
+                    .weak(),
+                );
+                egui::ScrollArea::vertical()
+                    .id_salt("load_diagnostics")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for diagnostic in &editor_state.load_diagnostics {
+                            ui.label(egui::RichText::new(diagnostic.to_string()).monospace());
+                        }
+                    });
+            }
+
+            if report.duplicate_voxels > 0 {
+                ui.separator();
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "⚠ {} duplicate voxels at {} positions",
+                        report.duplicate_voxels,
+                        report.duplicate_positions.len()
+                    ),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Duplicates draw twice and make removing voxels leave one behind. \
+                         The game keeps the last voxel listed at each position.",
+                    )
+                    .small()
+                    .weak(),
+                );
+                egui::Grid::new("duplicate_voxels")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for &(x, y, z) in report.duplicate_positions.iter().take(LISTED_DUPLICATES)
+                        {
+                            ui.label(format!("({}, {}, {})", x, y, z));
+                            if ui
+                                .small_button("Go")
+                                .on_hover_text("Move the camera to this voxel")
+                                .clicked()
+                            {
+                                go_to_events.write(GoToEvent(GoToDestination {
+                                    position: Vec3::new(x as f32, y as f32, z as f32),
+                                    entity: None,
+                                }));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                let hidden = report
+                    .duplicate_positions
+                    .len()
+                    .saturating_sub(LISTED_DUPLICATES);
+                if hidden > 0 {
+                    ui.label(format!("…and {} more", hidden));
+                }
+                clean_up = ui
+                    .button("🧹 Remove Duplicates")
+                    .on_hover_text("Keep the last voxel at each position, as the game does")
+                    .clicked();
+            }
+        });
+
+    if clean_up {
+        remove_duplicate_voxels(&mut editor_state, &mut history);
+    }
+    if !open {
+        editor_state.show_validation = false;
+    }
+}
+
+#[cfg(test)]
+mod tests;
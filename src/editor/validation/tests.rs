@@ -0,0 +1,100 @@
+use super::*;
+use crate::editor::shortcuts::apply_action;
+use crate::systems::game::map::format::VoxelType;
+
+/// The default map with its first voxel listed `copies` more times.
+fn map_with_duplicates(copies: usize) -> MapData {
+    let mut map = MapData::default_map();
+    let duplicate = map.world.voxels[0].clone();
+    for _ in 0..copies {
+        map.world.voxels.push(duplicate.clone());
+    }
+    map
+}
+
+#[test]
+fn default_map_is_valid() {
+    let report = check_map(&MapData::default_map());
+    assert!(report.is_valid(), "{:?}", report);
+}
+
+#[test]
+fn duplicates_are_reported_apart_from_other_errors() {
+    let map = map_with_duplicates(2);
+    let report = check_map(&map);
+    assert!(!report.is_valid());
+    assert_eq!(report.duplicate_voxels, 2);
+    assert_eq!(report.duplicate_positions, vec![map.world.voxels[0].pos]);
+    assert_eq!(report.error, None);
+}
+
+#[test]
+fn errors_past_the_duplicates_are_found() {
+    let mut map = map_with_duplicates(1);
+    map.world.voxels[1].pos = (-1, 0, 0);
+    let report = check_map(&map);
+    assert_eq!(report.duplicate_voxels, 1);
+    assert!(report.error.is_some());
+}
+
+#[test]
+fn removing_duplicates_marks_the_map_modified() {
+    let mut state = EditorState::with_map(map_with_duplicates(3));
+    let mut history = EditorHistory::new();
+    let voxels = state.current_map.world.voxels.len();
+    assert_eq!(remove_duplicate_voxels(&mut state, &mut history), 3);
+    assert_eq!(state.current_map.world.voxels.len(), voxels - 3);
+    assert!(state.is_modified);
+    assert!(check_map(&state.current_map).is_valid());
+    assert_eq!(history.undo_count(), 1);
+
+    state.clear_modified();
+    assert_eq!(remove_duplicate_voxels(&mut state, &mut history), 0);
+    assert!(!state.is_modified);
+    assert_eq!(history.undo_count(), 1);
+}
+
+fn type_at(state: &EditorState, pos: (i32, i32, i32)) -> VoxelType {
+    state.current_map.world.voxels.get(pos).unwrap().voxel_type
+}
+
+#[test]
+fn undoing_the_cleanup_keeps_the_voxel_the_game_shows() {
+    let mut map = map_with_duplicates(1);
+    let pos = map.world.voxels[0].pos;
+    let mut shown = map.world.voxels[0].clone();
+    shown.voxel_type = match shown.voxel_type {
+        VoxelType::Stone => VoxelType::Dirt,
+        _ => VoxelType::Stone,
+    };
+    map.world.voxels.push(shown.clone());
+    let mut state = EditorState::with_map(map);
+    let mut history = EditorHistory::new();
+    assert_eq!(remove_duplicate_voxels(&mut state, &mut history), 2);
+    assert_eq!(type_at(&state, pos), shown.voxel_type);
+
+    let cleanup = history.undo().unwrap();
+    apply_action(&cleanup.inverse(), &mut state);
+    assert_eq!(type_at(&state, pos), shown.voxel_type);
+    assert_eq!(state.current_map.world.voxels.duplicate_count(), 0);
+
+    let cleanup = history.redo().unwrap();
+    apply_action(&cleanup, &mut state);
+    assert_eq!(type_at(&state, pos), shown.voxel_type);
+}
+
+#[test]
+fn safe_mode_diagnostics_open_the_panel() {
+    let mut state = EditorState::new();
+    set_load_diagnostics(&mut state, Vec::new());
+    assert!(!state.show_validation);
+
+    let diagnostic = LoadDiagnostic {
+        item: "entities[0]".to_string(),
+        line: 12,
+        message: "Unexpected token; skipped".to_string(),
+    };
+    set_load_diagnostics(&mut state, vec![diagnostic.clone()]);
+    assert!(state.show_validation);
+    assert_eq!(state.load_diagnostics, vec![diagnostic]);
+}
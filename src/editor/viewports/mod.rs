@@ -0,0 +1,420 @@
+//! Split-viewport layout (quad view).
+//!
+//! With [`EditorState::quad_view`] on, the 3D area between the panels is
+//! split into four viewports: the perspective fly camera top-left and
+//! orthographic top, front and side views. Each view has its own camera;
+//! the map, cursor and selection are shared, so editing works in whichever
+//! view the pointer is over.
+//!
+//! [`update_viewports`] lays the cameras out once the UI has claimed its
+//! panels and tracks the hovered view. Mouse raycasts go through
+//! [`viewport_camera`] so they use the camera of the hovered view.
+//! Orthographic views pan with a right or middle drag and zoom with the
+//! scroll wheel.
+
+use crate::editor::camera::EditorCamera;
+use crate::editor::cursor::CursorState;
+use crate::editor::state::EditorState;
+use bevy::camera::{ScalingMode, Viewport};
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+
+/// Distance of orthographic cameras from the point they look at
+const ORTHO_DISTANCE: f32 = 500.0;
+
+/// Height of the area an orthographic view shows at first, in world units
+const DEFAULT_ORTHO_ZOOM: f32 = 24.0;
+
+/// Limits of the area an orthographic view shows, in world units
+const MIN_ORTHO_ZOOM: f32 = 2.0;
+const MAX_ORTHO_ZOOM: f32 = 400.0;
+
+/// Zoom factor per scroll wheel line
+const ORTHO_ZOOM_STEP: f32 = 1.15;
+
+/// Scroll distance in pixels (touchpads) counted as one wheel line
+const PIXELS_PER_SCROLL_LINE: f32 = 40.0;
+
+/// Distance in front of the perspective camera orthographic views centre on
+/// when quad view opens without a cursor position
+const FOCUS_DISTANCE: f32 = 10.0;
+
+/// One of the editor's viewports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ViewportKind {
+    /// The fly camera
+    #[default]
+    Perspective,
+    /// Orthographic, looking down the Y axis
+    Top,
+    /// Orthographic, looking down the Z axis
+    Front,
+    /// Orthographic, looking down the X axis
+    Side,
+}
+
+impl ViewportKind {
+    /// The orthographic views, in quad view order
+    pub const ORTHOGRAPHIC: [ViewportKind; 3] = [Self::Top, Self::Front, Self::Side];
+
+    /// Name shown in the corner of the view
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Perspective => "Perspective",
+            Self::Top => "Top",
+            Self::Front => "Front",
+            Self::Side => "Side",
+        }
+    }
+
+    /// Direction an orthographic view looks in and the world direction
+    /// that is up on screen; `None` for the perspective view.
+    pub fn ortho_axes(self) -> Option<(Vec3, Vec3)> {
+        match self {
+            Self::Perspective => None,
+            Self::Top => Some((Vec3::NEG_Y, Vec3::NEG_Z)),
+            Self::Front => Some((Vec3::NEG_Z, Vec3::Y)),
+            Self::Side => Some((Vec3::NEG_X, Vec3::Y)),
+        }
+    }
+}
+
+/// Component naming the viewport a camera renders
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ViewportCamera {
+    pub kind: ViewportKind,
+}
+
+/// Component holding an orthographic view's camera state
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct OrthoView {
+    /// World point at the centre of the view
+    pub focus: Vec3,
+    /// Height of the area shown, in world units
+    pub zoom: f32,
+}
+
+impl Default for OrthoView {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            zoom: DEFAULT_ORTHO_ZOOM,
+        }
+    }
+}
+
+impl OrthoView {
+    /// Camera transform showing this view as `kind`
+    pub fn transform(&self, kind: ViewportKind) -> Transform {
+        let (direction, up) = kind.ortho_axes().unwrap_or((Vec3::NEG_Z, Vec3::Y));
+        Transform::from_translation(self.focus - direction * ORTHO_DISTANCE)
+            .looking_to(direction, up)
+    }
+
+    /// Move the view so the content follows a pointer drag of `delta`
+    /// logical pixels in a viewport `viewport_height` pixels tall.
+    pub fn pan(&mut self, kind: ViewportKind, delta: Vec2, viewport_height: f32) {
+        let Some((direction, up)) = kind.ortho_axes() else {
+            return;
+        };
+        let right = direction.cross(up);
+        let units_per_pixel = self.zoom / viewport_height.max(1.0);
+        self.focus += (up * delta.y - right * delta.x) * units_per_pixel;
+    }
+
+    /// Zoom in by `lines` scroll wheel lines (out when negative).
+    pub fn zoom_by(&mut self, lines: f32) {
+        self.zoom =
+            (self.zoom * ORTHO_ZOOM_STEP.powf(-lines)).clamp(MIN_ORTHO_ZOOM, MAX_ORTHO_ZOOM);
+    }
+
+    /// Centre the view on `focus`, showing an area `size` world units tall.
+    pub fn fit(&mut self, focus: Vec3, size: f32) {
+        self.focus = focus;
+        self.zoom = size.clamp(MIN_ORTHO_ZOOM, MAX_ORTHO_ZOOM);
+    }
+}
+
+/// Resource describing where the viewports are on screen
+#[derive(Resource, Default)]
+pub struct ViewportLayout {
+    /// Area between the panels the viewports share, in logical pixels
+    pub area: Option<Rect>,
+    /// View under the pointer. Kept while a mouse button is held, so a drag
+    /// stays in the view it started in.
+    pub hovered: ViewportKind,
+}
+
+/// Screen rectangle of every visible viewport within `area`.
+pub fn viewport_rects(area: Rect, quad_view: bool) -> Vec<(ViewportKind, Rect)> {
+    if !quad_view {
+        return vec![(ViewportKind::Perspective, area)];
+    }
+    let center = area.center();
+    vec![
+        (
+            ViewportKind::Perspective,
+            Rect::from_corners(area.min, center),
+        ),
+        (
+            ViewportKind::Top,
+            Rect::new(center.x, area.min.y, area.max.x, center.y),
+        ),
+        (
+            ViewportKind::Front,
+            Rect::new(area.min.x, center.y, center.x, area.max.y),
+        ),
+        (ViewportKind::Side, Rect::from_corners(center, area.max)),
+    ]
+}
+
+/// Viewport containing `point`, if any.
+pub fn viewport_at(rects: &[(ViewportKind, Rect)], point: Vec2) -> Option<ViewportKind> {
+    rects
+        .iter()
+        .find(|(_, rect)| rect.contains(point))
+        .map(|(kind, _)| *kind)
+}
+
+/// Camera viewport covering the logical `rect`, kept inside a render target
+/// of `target_size` physical pixels.
+pub fn physical_viewport(rect: Rect, scale_factor: f32, target_size: UVec2) -> Viewport {
+    let max = target_size.max(UVec2::ONE);
+    let position = (rect.min * scale_factor).round().as_uvec2().min(max - 1);
+    let size = (rect.size() * scale_factor)
+        .round()
+        .as_uvec2()
+        .clamp(UVec2::ONE, max - position);
+    Viewport {
+        physical_position: position,
+        physical_size: size,
+        ..default()
+    }
+}
+
+/// Camera of the hovered viewport, for mouse raycasts.
+pub fn viewport_camera<'a>(
+    layout: &ViewportLayout,
+    cameras: impl IntoIterator<Item = (&'a Camera, &'a GlobalTransform, &'a ViewportCamera)>,
+) -> Option<(&'a Camera, &'a GlobalTransform)> {
+    let mut perspective = None;
+    for (camera, transform, viewport) in cameras {
+        if !camera.is_active {
+            continue;
+        }
+        if viewport.kind == layout.hovered {
+            return Some((camera, transform));
+        }
+        if viewport.kind == ViewportKind::Perspective {
+            perspective = Some((camera, transform));
+        }
+    }
+    perspective
+}
+
+/// Spawn the orthographic view cameras; they stay inactive until quad view
+/// is turned on.
+pub fn spawn_ortho_cameras(commands: &mut Commands) {
+    for (order, kind) in ViewportKind::ORTHOGRAPHIC.into_iter().enumerate() {
+        let view = OrthoView::default();
+        commands.spawn((
+            Camera3d::default(),
+            Camera {
+                // Render after the perspective camera, each into its own quarter
+                order: order as isize + 1,
+                is_active: false,
+                ..default()
+            },
+            Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical {
+                    viewport_height: view.zoom,
+                },
+                ..OrthographicProjection::default_3d()
+            }),
+            view.transform(kind),
+            ViewportCamera { kind },
+            view,
+        ));
+    }
+}
+
+/// System laying the viewport cameras out in the area the UI left free,
+/// tracking the hovered view and labelling the views in quad view.
+///
+/// Must run after every system that adds egui panels.
+pub fn update_viewports(
+    mut contexts: EguiContexts,
+    editor_state: Res<EditorState>,
+    mut layout: ResMut<ViewportLayout>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Camera, &ViewportCamera)>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let available = ctx.available_rect();
+    let area = Rect::new(
+        available.min.x,
+        available.min.y,
+        available.max.x,
+        available.max.y,
+    );
+    layout.area = Some(area);
+
+    let rects = viewport_rects(area, editor_state.quad_view);
+    if !mouse_button.any_pressed([MouseButton::Left, MouseButton::Right, MouseButton::Middle]) {
+        if let Some(kind) = window
+            .cursor_position()
+            .and_then(|pos| viewport_at(&rects, pos))
+        {
+            layout.hovered = kind;
+        }
+    }
+    if !editor_state.quad_view {
+        layout.hovered = ViewportKind::Perspective;
+    }
+
+    for (mut camera, viewport_camera) in &mut cameras {
+        let rect = rects
+            .iter()
+            .find(|(kind, _)| *kind == viewport_camera.kind)
+            .map(|(_, rect)| *rect);
+        let is_active = rect.is_some();
+        // Outside quad view the perspective camera fills the window behind the panels
+        let viewport = rect
+            .filter(|_| editor_state.quad_view)
+            .map(|rect| physical_viewport(rect, window.scale_factor(), window.physical_size()));
+
+        let current = camera
+            .viewport
+            .as_ref()
+            .map(|v| (v.physical_position, v.physical_size));
+        let wanted = viewport
+            .as_ref()
+            .map(|v| (v.physical_position, v.physical_size));
+        if camera.is_active != is_active || current != wanted {
+            camera.is_active = is_active;
+            camera.viewport = viewport;
+        }
+    }
+
+    if editor_state.quad_view {
+        draw_viewport_frames(ctx, &rects, layout.hovered);
+    }
+}
+
+/// Outline each viewport and name it in its top-left corner.
+fn draw_viewport_frames(
+    ctx: &egui::Context,
+    rects: &[(ViewportKind, Rect)],
+    hovered: ViewportKind,
+) {
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    for (kind, rect) in rects {
+        let egui_rect = egui::Rect::from_min_max(
+            egui::pos2(rect.min.x, rect.min.y),
+            egui::pos2(rect.max.x, rect.max.y),
+        );
+        let color = if *kind == hovered {
+            egui::Color32::from_rgb(100, 180, 255)
+        } else {
+            egui::Color32::from_gray(60)
+        };
+        painter.rect_stroke(
+            egui_rect.shrink(0.5),
+            0.0,
+            egui::Stroke::new(1.0, color),
+            egui::StrokeKind::Inside,
+        );
+        painter.text(
+            egui_rect.min + egui::vec2(8.0, 6.0),
+            egui::Align2::LEFT_TOP,
+            kind.label(),
+            egui::FontId::proportional(13.0),
+            color,
+        );
+    }
+}
+
+/// System panning (right or middle drag) and zooming (scroll wheel) the
+/// hovered orthographic view.
+pub fn handle_ortho_view_input(
+    layout: Res<ViewportLayout>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    mut contexts: EguiContexts,
+    mut views: Query<(&Camera, &ViewportCamera, &mut OrthoView)>,
+) {
+    let Some((camera, viewport_camera, mut view)) = views
+        .iter_mut()
+        .find(|(camera, viewport, _)| camera.is_active && viewport.kind == layout.hovered)
+    else {
+        return;
+    };
+    let kind = viewport_camera.kind;
+
+    let dragging = mouse_button.any_pressed([MouseButton::Right, MouseButton::Middle]);
+    if dragging && mouse_motion.delta != Vec2::ZERO {
+        let height = camera.logical_viewport_size().map_or(1.0, |size| size.y);
+        view.pan(kind, mouse_motion.delta, height);
+    }
+
+    // Scrolling over a panel scrolls the panel
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if mouse_scroll.delta.y != 0.0 && !ctx.is_pointer_over_area() {
+        let lines = match mouse_scroll.unit {
+            MouseScrollUnit::Line => mouse_scroll.delta.y,
+            MouseScrollUnit::Pixel => mouse_scroll.delta.y / PIXELS_PER_SCROLL_LINE,
+        };
+        view.zoom_by(lines);
+    }
+}
+
+/// System moving the orthographic cameras to match their views.
+pub fn update_ortho_cameras(
+    mut cameras: Query<
+        (&ViewportCamera, &OrthoView, &mut Transform, &mut Projection),
+        Changed<OrthoView>,
+    >,
+) {
+    for (viewport_camera, view, mut transform, mut projection) in &mut cameras {
+        *transform = view.transform(viewport_camera.kind);
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scaling_mode = ScalingMode::FixedVertical {
+                viewport_height: view.zoom,
+            };
+        }
+    }
+}
+
+/// System centring the orthographic views on what the perspective camera
+/// looks at whenever quad view is turned on.
+pub fn focus_ortho_views_on_open(
+    editor_state: Res<EditorState>,
+    cursor_state: Res<CursorState>,
+    camera: Single<&EditorCamera>,
+    mut views: Query<&mut OrthoView>,
+    mut was_open: Local<bool>,
+) {
+    let opened = editor_state.quad_view && !*was_open;
+    *was_open = editor_state.quad_view;
+    if !opened {
+        return;
+    }
+
+    let focus = cursor_state
+        .position
+        .unwrap_or(camera.position + camera.forward() * FOCUS_DISTANCE);
+    for mut view in &mut views {
+        view.focus = focus;
+    }
+}
+
+#[cfg(test)]
+mod tests;
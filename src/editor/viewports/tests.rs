@@ -0,0 +1,111 @@
+use super::*;
+
+fn area() -> Rect {
+    Rect::new(200.0, 50.0, 1000.0, 650.0)
+}
+
+#[test]
+fn single_view_fills_the_area() {
+    let rects = viewport_rects(area(), false);
+    assert_eq!(rects, vec![(ViewportKind::Perspective, area())]);
+}
+
+#[test]
+fn quad_view_splits_the_area_into_quarters() {
+    let rects = viewport_rects(area(), true);
+    assert_eq!(rects.len(), 4);
+    for (_, rect) in &rects {
+        assert_eq!(rect.size(), Vec2::new(400.0, 300.0));
+    }
+    assert_eq!(
+        viewport_at(&rects, Vec2::new(300.0, 100.0)),
+        Some(ViewportKind::Perspective)
+    );
+    assert_eq!(
+        viewport_at(&rects, Vec2::new(900.0, 100.0)),
+        Some(ViewportKind::Top)
+    );
+    assert_eq!(
+        viewport_at(&rects, Vec2::new(300.0, 600.0)),
+        Some(ViewportKind::Front)
+    );
+    assert_eq!(
+        viewport_at(&rects, Vec2::new(900.0, 600.0)),
+        Some(ViewportKind::Side)
+    );
+    assert_eq!(viewport_at(&rects, Vec2::new(100.0, 100.0)), None);
+}
+
+#[test]
+fn physical_viewports_scale_and_stay_inside_the_window() {
+    let viewport = physical_viewport(
+        Rect::new(200.0, 50.0, 600.0, 350.0),
+        2.0,
+        UVec2::new(2000, 1300),
+    );
+    assert_eq!(viewport.physical_position, UVec2::new(400, 100));
+    assert_eq!(viewport.physical_size, UVec2::new(800, 600));
+
+    let clamped = physical_viewport(
+        Rect::new(900.0, 600.0, 1100.0, 700.0),
+        1.0,
+        UVec2::new(1000, 650),
+    );
+    assert_eq!(clamped.physical_position, UVec2::new(900, 600));
+    assert_eq!(clamped.physical_size, UVec2::new(100, 50));
+}
+
+#[test]
+fn ortho_cameras_look_at_their_focus() {
+    let view = OrthoView {
+        focus: Vec3::new(3.0, 1.0, -2.0),
+        zoom: 10.0,
+    };
+    for kind in ViewportKind::ORTHOGRAPHIC {
+        let (direction, up) = kind.ortho_axes().unwrap();
+        let transform = view.transform(kind);
+        assert!(transform.forward().as_vec3().abs_diff_eq(direction, 1e-5));
+        assert!(transform.up().as_vec3().abs_diff_eq(up, 1e-5));
+        let to_focus = view.focus - transform.translation;
+        assert!(to_focus.normalize().abs_diff_eq(direction, 1e-5));
+    }
+}
+
+#[test]
+fn panning_moves_the_content_with_the_pointer() {
+    let mut view = OrthoView {
+        focus: Vec3::ZERO,
+        zoom: 20.0,
+    };
+    // Dragging right by a whole viewport height moves the top view a zoom's
+    // width towards -X; dragging down moves it towards -Z (up on screen)
+    view.pan(ViewportKind::Top, Vec2::new(100.0, 0.0), 100.0);
+    assert!(view.focus.abs_diff_eq(Vec3::new(-20.0, 0.0, 0.0), 1e-4));
+    view.pan(ViewportKind::Top, Vec2::new(0.0, 50.0), 100.0);
+    assert!(view.focus.abs_diff_eq(Vec3::new(-20.0, 0.0, -10.0), 1e-4));
+
+    let mut front = OrthoView::default();
+    front.pan(ViewportKind::Front, Vec2::new(0.0, 100.0), 100.0);
+    assert!(front.focus.y > 0.0);
+}
+
+#[test]
+fn zoom_is_clamped() {
+    let mut view = OrthoView::default();
+    view.zoom_by(1.0);
+    assert!(view.zoom < DEFAULT_ORTHO_ZOOM);
+    view.zoom_by(100.0);
+    assert_eq!(view.zoom, MIN_ORTHO_ZOOM);
+    view.zoom_by(-100.0);
+    assert_eq!(view.zoom, MAX_ORTHO_ZOOM);
+}
+
+#[test]
+fn fitting_centres_and_clamps_the_zoom() {
+    let mut view = OrthoView::default();
+    view.fit(Vec3::new(4.0, 2.0, -3.0), 30.0);
+    assert_eq!(view.focus, Vec3::new(4.0, 2.0, -3.0));
+    assert_eq!(view.zoom, 30.0);
+    view.fit(Vec3::ZERO, 0.1);
+    assert_eq!(view.zoom, MIN_ORTHO_ZOOM);
+}
@@ -0,0 +1,183 @@
+//! Operations on groups of voxels, independent of the tool that triggers them:
+//! connected-group search for the Remove tool and the Hollow and Shell
+//! selection operations.
+
+use crate::systems::game::map::format::{MapData, SubVoxelPattern, VoxelData};
+use crate::systems::game::map::{Face, OccupancyGrid};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+const FACES: [Face; 6] = [
+    Face::PosX,
+    Face::NegX,
+    Face::PosY,
+    Face::NegY,
+    Face::PosZ,
+    Face::NegZ,
+];
+
+/// Offsets to the six face-adjacent neighbours of a voxel.
+pub const FACE_NEIGHBORS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// Positions of all voxels connected to `start` through shared faces,
+/// including `start` itself, in breadth-first order.
+///
+/// With `same_type`, only voxels of the same type as `start` are followed.
+/// Returns an empty list when there is no voxel at `start`.
+pub fn connected_voxels(
+    map: &MapData,
+    start: (i32, i32, i32),
+    same_type: bool,
+) -> Vec<(i32, i32, i32)> {
+    let occupied: HashMap<_, _> = map
+        .world
+        .voxels
+        .iter()
+        .map(|v| (v.pos, v.voxel_type))
+        .collect();
+    let Some(&start_type) = occupied.get(&start) else {
+        return Vec::new();
+    };
+
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    let mut group = Vec::new();
+    while let Some(pos) = queue.pop_front() {
+        group.push(pos);
+        for (dx, dy, dz) in FACE_NEIGHBORS {
+            let neighbor = (pos.0 + dx, pos.1 + dy, pos.2 + dz);
+            let Some(&voxel_type) = occupied.get(&neighbor) else {
+                continue;
+            };
+            if same_type && voxel_type != start_type {
+                continue;
+            }
+            if visited.insert(neighbor) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    group
+}
+
+/// Sub-voxels `voxel` occupies, after its pattern and rotation.
+fn occupied_sub_voxels(map: &MapData, voxel: &VoxelData) -> Vec<(i32, i32, i32)> {
+    let orientation = voxel.rotation.and_then(|i| map.orientations.get(i));
+    voxel
+        .pattern
+        .unwrap_or(SubVoxelPattern::Full)
+        .geometry_with_rotation(orientation)
+        .occupied_positions()
+        .collect()
+}
+
+/// Positions in `candidates` whose voxel has at least one face open to air.
+///
+/// Uses the same sub-voxel occupancy test as the mesher's hidden face culling,
+/// so a voxel counts as exposed exactly when it would draw a face. Partial
+/// patterns (stairs, platforms) therefore leave their neighbours exposed.
+pub fn exposed_voxels(
+    map: &MapData,
+    candidates: &HashSet<(i32, i32, i32)>,
+) -> HashSet<(i32, i32, i32)> {
+    let mut occupancy = OccupancyGrid::new();
+    let mut candidate_geometry = Vec::new();
+    for voxel in &map.world.voxels {
+        let (x, y, z) = voxel.pos;
+        let subs = occupied_sub_voxels(map, voxel);
+        for &(sub_x, sub_y, sub_z) in &subs {
+            occupancy.insert(x, y, z, sub_x, sub_y, sub_z);
+        }
+        if candidates.contains(&voxel.pos) {
+            candidate_geometry.push((voxel.pos, subs));
+        }
+    }
+
+    candidate_geometry
+        .into_iter()
+        .filter(|((x, y, z), subs)| {
+            subs.iter().any(|&(sub_x, sub_y, sub_z)| {
+                FACES
+                    .iter()
+                    .any(|&face| !occupancy.has_neighbor(*x, *y, *z, sub_x, sub_y, sub_z, face))
+            })
+        })
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// Voxels to remove to hollow out `selection`, leaving walls `wall_thickness`
+/// voxels thick.
+///
+/// Walls are measured in face steps inward from the selected voxels that are
+/// exposed to air. Selected voxels further in than that, or not reachable from
+/// an exposed voxel at all, are interior. Unselected voxels are never removed
+/// but still cover the faces of selected voxels next to them.
+pub fn hollow_interior(
+    map: &MapData,
+    selection: &HashSet<(i32, i32, i32)>,
+    wall_thickness: u32,
+) -> Vec<(i32, i32, i32)> {
+    let wall_thickness = wall_thickness.max(1);
+    let exposed = exposed_voxels(map, selection);
+
+    // Breadth-first from every exposed voxel at once gives each selected voxel
+    // its distance to the nearest surface
+    let mut depth: HashMap<(i32, i32, i32), u32> = exposed.iter().map(|&p| (p, 0)).collect();
+    let mut queue: VecDeque<_> = exposed.iter().copied().collect();
+    while let Some(pos) = queue.pop_front() {
+        let next = depth[&pos] + 1;
+        if next >= wall_thickness {
+            continue;
+        }
+        for (dx, dy, dz) in FACE_NEIGHBORS {
+            let neighbor = (pos.0 + dx, pos.1 + dy, pos.2 + dz);
+            if selection.contains(&neighbor) && !depth.contains_key(&neighbor) {
+                depth.insert(neighbor, next);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    let mut interior: Vec<_> = selection
+        .iter()
+        .filter(|pos| map.world.voxels.contains(**pos) && !depth.contains_key(pos))
+        .copied()
+        .collect();
+    interior.sort();
+    interior
+}
+
+/// Empty positions touching `selection`, including along edges and corners,
+/// which together form a closed one-voxel wall around it.
+pub fn shell_positions(
+    map: &MapData,
+    selection: &HashSet<(i32, i32, i32)>,
+) -> Vec<(i32, i32, i32)> {
+    let mut shell = HashSet::new();
+    for &(x, y, z) in selection {
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let pos = (x + dx, y + dy, z + dz);
+                    if !map.world.voxels.contains(pos) && !selection.contains(&pos) {
+                        shell.insert(pos);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut shell: Vec<_> = shell.into_iter().collect();
+    shell.sort();
+    shell
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+use super::*;
+use crate::systems::game::components::VoxelType;
+
+fn map_with(voxels: Vec<VoxelData>) -> MapData {
+    let mut map = MapData::empty_map();
+    map.world.voxels = voxels.into();
+    map
+}
+
+#[test]
+fn test_connected_voxels_follows_faces_only() {
+    let map = map_with(vec![
+        VoxelData::new((0, 0, 0), VoxelType::Grass, None),
+        VoxelData::new((1, 0, 0), VoxelType::Grass, None),
+        VoxelData::new((1, 1, 0), VoxelType::Stone, None),
+        // Diagonal neighbour of (1, 1, 0): not connected
+        VoxelData::new((2, 2, 0), VoxelType::Grass, None),
+    ]);
+
+    let mut group = connected_voxels(&map, (0, 0, 0), false);
+    group.sort();
+    assert_eq!(group, vec![(0, 0, 0), (1, 0, 0), (1, 1, 0)]);
+}
+
+#[test]
+fn test_connected_voxels_same_type() {
+    let map = map_with(vec![
+        VoxelData::new((0, 0, 0), VoxelType::Grass, None),
+        VoxelData::new((1, 0, 0), VoxelType::Stone, None),
+        VoxelData::new((2, 0, 0), VoxelType::Grass, None),
+        VoxelData::new((0, 0, 1), VoxelType::Grass, None),
+    ]);
+
+    let mut group = connected_voxels(&map, (0, 0, 0), true);
+    group.sort();
+    assert_eq!(group, vec![(0, 0, 0), (0, 0, 1)]);
+}
+
+#[test]
+fn test_connected_voxels_empty_start() {
+    let map = map_with(vec![VoxelData::new((0, 0, 0), VoxelType::Grass, None)]);
+    assert!(connected_voxels(&map, (5, 0, 0), false).is_empty());
+}
+
+/// Solid `size`³ cube of stone with its minimum corner at the origin.
+fn cube(size: i32) -> (MapData, HashSet<(i32, i32, i32)>) {
+    let mut voxels = Vec::new();
+    for x in 0..size {
+        for y in 0..size {
+            for z in 0..size {
+                voxels.push(VoxelData::new((x, y, z), VoxelType::Stone, None));
+            }
+        }
+    }
+    let selection = voxels.iter().map(|v| v.pos).collect();
+    (map_with(voxels), selection)
+}
+
+#[test]
+fn test_exposed_voxels_uses_sub_voxel_occupancy() {
+    let (mut map, selection) = cube(3);
+    let exposed = exposed_voxels(&map, &selection);
+    assert_eq!(exposed.len(), 26);
+    assert!(!exposed.contains(&(1, 1, 1)));
+
+    // A partial pattern next to the center no longer covers its face
+    let top = map
+        .world
+        .voxels
+        .iter_mut()
+        .find(|v| v.pos == (1, 2, 1))
+        .unwrap();
+    top.pattern = Some(SubVoxelPattern::Pillar);
+    assert!(exposed_voxels(&map, &selection).contains(&(1, 1, 1)));
+}
+
+#[test]
+fn test_hollow_interior_keeps_wall_thickness() {
+    let (map, selection) = cube(5);
+
+    // One-voxel walls leave the 3x3x3 core to remove
+    assert_eq!(hollow_interior(&map, &selection, 1).len(), 27);
+    // Two-voxel walls leave only the center
+    assert_eq!(hollow_interior(&map, &selection, 2), vec![(2, 2, 2)]);
+    assert!(hollow_interior(&map, &selection, 3).is_empty());
+}
+
+#[test]
+fn test_hollow_interior_counts_unselected_neighbours_as_cover() {
+    let (map, _) = cube(3);
+    // Only the bottom layer is selected; the layer above covers it
+    let selection: HashSet<_> = (0..3)
+        .flat_map(|x| (0..3).map(move |z| (x, 0, z)))
+        .collect();
+    // The middle of the bottom layer is covered on every side but -Y
+    assert!(hollow_interior(&map, &selection, 1).is_empty());
+}
+
+#[test]
+fn test_shell_positions_enclose_selection() {
+    let map = map_with(vec![VoxelData::new((0, 0, 0), VoxelType::Stone, None)]);
+    let selection = HashSet::from([(0, 0, 0)]);
+    let shell = shell_positions(&map, &selection);
+    assert_eq!(shell.len(), 26);
+    assert!(!shell.contains(&(0, 0, 0)));
+
+    // Occupied neighbours are left alone
+    let map = map_with(vec![
+        VoxelData::new((0, 0, 0), VoxelType::Stone, None),
+        VoxelData::new((1, 0, 0), VoxelType::Grass, None),
+    ]);
+    let shell = shell_positions(&map, &selection);
+    assert_eq!(shell.len(), 25);
+    assert!(!shell.contains(&(1, 0, 0)));
+}
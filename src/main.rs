@@ -1,6 +1,8 @@
+use crate::diagnostics::crash_report;
 use crate::diagnostics::FrameProfilerPlugin;
 use bevy::{
     prelude::*,
+    tasks::{futures::check_ready, AsyncComputeTaskPool, Task},
     window::{PresentMode, WindowMode},
 };
 use std::path::PathBuf;
@@ -11,13 +13,48 @@ mod states;
 mod systems;
 
 use states::GameState;
+use systems::accessibility::AccessibilityPlugin;
+use systems::game::actions::MapActionsPlugin;
+use systems::game::bloom::BloomPlugin;
+use systems::game::captions::CaptionsPlugin;
+use systems::game::cinematics::{cinematic_inactive, CinematicsPlugin};
+use systems::game::debug_boxes::{
+    toggle_grid_cells, update_debug_box_views, DebugBoxView, DebugBoxesPlugin,
+};
+use systems::game::debug_console::{debug_console_closed, DebugConsolePlugin};
+use systems::game::doors::DoorsPlugin;
+use systems::game::dynamic_quality::DynamicQualityPlugin;
+use systems::game::fixed_step::{
+    begin_physics_step, end_physics_step, latch_fixed_step_input, take_fixed_step_input,
+    FixedStepPlugin,
+};
 use systems::game::fps_counter::FpsCounterPlugin;
 use systems::game::gamepad::{
     gather_gamepad_input, gather_keyboard_input, handle_gamepad_connections, reset_player_input,
     update_cursor_visibility, ActiveGamepad, GamepadSettings, PlayerInput,
 };
+use systems::game::hazards::HazardPlugin;
+use systems::game::health::HealthPlugin;
+use systems::game::interaction::{find_interaction_target, InteractionPlugin};
+use systems::game::map_transition::MapTransitionPlugin;
+use systems::game::network::{NetworkPlugin, StartNetworkSession};
 use systems::game::occlusion::OcclusionPlugin;
-use systems::game::resources::PreFetchedCollisionEntities;
+use systems::game::particles::ParticlesPlugin;
+use systems::game::photo_mode::PhotoModePlugin;
+use systems::game::playtest_log::PlaytestLogPlugin;
+use systems::game::render_scale::{RenderScaleCamera, RenderScalePlugin};
+use systems::game::replay::{
+    apply_replay_input, not_playing_replay, record_replay_frame, restart_replay_on_spawn,
+    sync_replay_frame, Replay, ReplayPlugin,
+};
+use systems::game::resources::{PhysicsConfig, PreFetchedCollisionEntities, SpatialGrid};
+use systems::game::speedrun::{NextMapRequest, SpeedrunPlugin};
+use systems::game::split_screen::SplitScreenPlugin;
+use systems::game::surface::SurfacePlugin;
+use systems::game::teleporters::TeleportersPlugin;
+use systems::game::voxel_groups::VoxelGroupsPlugin;
+use systems::game::weather::WeatherPlugin;
+use systems::localization::LocalizationPlugin;
 use systems::settings::SettingsPlugin;
 
 /// Command-line arguments for the game
@@ -25,6 +62,14 @@ use systems::settings::SettingsPlugin;
 struct GameArgs {
     /// Path to map file to load directly (skips intro and title screen)
     map_path: Option<PathBuf>,
+    /// Path to a replay to play back on its map (skips intro and title screen)
+    replay_path: Option<PathBuf>,
+    /// Host a co-op game
+    host: bool,
+    /// Address of a co-op game to join
+    connect: Option<String>,
+    /// Log play to `playtests/` for the editor's heatmap
+    playtest_log: bool,
 }
 
 /// Resource to hold command-line specified map path for direct loading
@@ -49,6 +94,28 @@ fn parse_args() -> GameArgs {
                     eprintln!("Warning: --map requires a path argument");
                 }
             }
+            "--replay" | "-r" => {
+                if i + 1 < args.len() {
+                    game_args.replay_path = Some(PathBuf::from(&args[i + 1]));
+                    i += 1;
+                } else {
+                    eprintln!("Warning: --replay requires a path argument");
+                }
+            }
+            "--host" => {
+                game_args.host = true;
+            }
+            "--playtest-log" => {
+                game_args.playtest_log = true;
+            }
+            "--connect" | "-c" => {
+                if i + 1 < args.len() {
+                    game_args.connect = Some(args[i + 1].clone());
+                    i += 1;
+                } else {
+                    eprintln!("Warning: --connect requires an address argument");
+                }
+            }
             "--help" | "-h" => {
                 println!("A Drake's Story");
                 println!();
@@ -56,9 +123,15 @@ fn parse_args() -> GameArgs {
                 println!();
                 println!("Options:");
                 println!(
-                    "  -m, --map <PATH>  Load a specific map file directly (skips title screen)"
+                    "  -m, --map <PATH>     Load a specific map file directly (skips title screen)"
                 );
-                println!("  -h, --help        Show this help message");
+                println!("  -r, --replay <PATH>  Play back a recorded run on its map");
+                println!("      --host           Host a co-op game (on the --map if given)");
+                println!("  -c, --connect <ADDR> Join the co-op game hosted at ADDR[:PORT]");
+                println!(
+                    "      --playtest-log   Log movement, deaths and interactions to playtests/"
+                );
+                println!("  -h, --help           Show this help message");
                 std::process::exit(0);
             }
             _ => {
@@ -73,64 +146,118 @@ fn parse_args() -> GameArgs {
 
 /// System sets for organizing game loop execution order.
 /// These sets ensure proper sequencing of game logic phases.
+/// Movement and Physics run in `FixedUpdate`, the rest in `Update`.
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 enum GameSystemSet {
     /// Handle user input (keyboard, mouse, gamepad)
     Input,
-    /// Process player movement based on input
+    /// Process player movement based on input, once per physics step
     Movement,
-    /// Apply physics simulation (gravity, collisions)
+    /// Apply physics simulation (gravity, collisions), once per physics step
     Physics,
     /// Update visual elements (collision box, effects, etc.)
     Visual,
     /// Update camera position and rotation
     Camera,
 }
+use systems::game::cleanup::despawn_game_entities;
 use systems::game::hot_reload::{
-    cleanup_hot_reload, handle_hot_reload_toggle, handle_map_reload, handle_reload_hotkey,
-    poll_hot_reload, restore_player_position, setup_hot_reload_on_enter, show_reload_notification,
-    update_reload_notifications, HotReloadState, MapPathForHotReload, MapReloadEvent,
-    MapReloadedEvent,
+    cleanup_hot_reload, cleanup_hot_reload_indicator, handle_hot_reload_toggle, handle_map_reload,
+    handle_reload_hotkey, poll_hot_reload, restore_player_position, setup_hot_reload_on_enter,
+    show_reload_notification, update_hot_reload_indicator, update_reload_notifications,
+    HotReloadState, MapPathForHotReload, MapReloadEvent, MapReloadedEvent,
 };
+use systems::game::hud_layout::HudLayoutPlugin;
+use systems::game::map::error::MapResult;
+use systems::game::map::format::MapData;
 use systems::game::map::{
-    apply_shadow_quality_system, spawn_map_system, update_chunk_lods, LoadedMapData, LodConfig,
-    MapLoadProgress, MapLoader,
+    apply_shadow_quality_system, spawn_map_system, update_chunk_lods, LoadDiagnostic,
+    LoadedMapData, LodConfig, MapLoadDiagnostics, MapLoadProgress, MapLoader,
 };
 use systems::game::npc_labels::{
-    cleanup_npc_labels, despawn_removed_npc_labels, spawn_npc_label, tick_npc_label_fade,
-    update_npc_label_visibility,
+    despawn_removed_npc_labels, spawn_npc_label, tick_npc_label_fade, update_npc_label_visibility,
 };
 use systems::game::systems::{
     apply_gravity, apply_npc_collision, apply_physics, flicker_lights, follow_player_camera,
     handle_escape_key, move_player, rotate_camera, rotate_character_model, sync_light_sources,
-    toggle_collision_box, toggle_flashlight, toggle_fullscreen, update_collision_box,
-    update_flashlight_rotation,
+    sync_npc_grid, toggle_collision_box, toggle_flashlight, toggle_fullscreen,
+    update_collision_box, update_flashlight_rotation,
 };
+use systems::game_over::systems as game_over;
 use systems::intro_animation::systems::{animate_intro, cleanup_intro, setup_intro};
 use systems::loading_screen::{
-    cleanup_loading_screen, setup_loading_screen, update_loading_progress,
+    acknowledge_load_diagnostics, animate_loading_screen, cleanup_loading_screen,
+    setup_loading_screen, show_load_diagnostics, update_loading_progress, LoadingMapInfo,
 };
+use systems::pause_menu::resources::PendingConfirmation;
 use systems::pause_menu::systems as pause_menu;
+use systems::preload::{PreloadPlugin, PreloadedAssets, DEFAULT_MAP_PATH};
+use systems::results::systems as results;
+use systems::title_screen::flythrough::TitleFlythroughPlugin;
 use systems::title_screen::systems::{
-    button_interaction, cleanup_title_screen, fade_in_title_screen, keyboard_navigation,
-    scale_text_on_resize, setup_title_screen, update_selected_button_visual,
+    button_interaction, cleanup_title_screen, edit_join_prompt, fade_in_title_screen,
+    join_prompt_closed, keyboard_navigation, scale_text_on_resize, setup_title_screen,
+    update_network_status_text, update_selected_button_visual,
 };
 
 fn main() {
+    // Write a crash report to crashes/ on a panic
+    crash_report::install("game");
+    if let Some(notice) = crash_report::take_pending("game") {
+        eprintln!(
+            "The game crashed last time; the crash report is {}",
+            notice.report.display()
+        );
+    }
+
     // Parse command-line arguments
     let args = parse_args();
-    let has_map_arg = args.map_path.is_some();
 
-    // Determine initial state based on CLI arguments
-    // If a map is specified, skip intro and title screen
-    let initial_state = if has_map_arg {
-        GameState::LoadingMap
+    // A replay plays on the map it was recorded on
+    let replay = args.replay_path.map(|path| match Replay::load(&path) {
+        Ok(replay) => replay,
+        Err(e) => {
+            eprintln!("Error: could not load replay {:?}: {}", path, e);
+            std::process::exit(1);
+        }
+    });
+    let map_path = match &replay {
+        Some(replay) => {
+            if args.map_path.is_some() {
+                eprintln!("Warning: --map is ignored when playing a replay");
+            }
+            replay.map.clone()
+        }
+        None => args.map_path,
+    };
+    let has_map_arg = map_path.is_some() || replay.is_some();
+
+    // Replays play alone; joining players play the host's map
+    let network = if replay.is_some() {
+        if args.host || args.connect.is_some() {
+            eprintln!("Warning: --host and --connect are ignored when playing a replay");
+        }
+        None
+    } else if let Some(address) = args.connect {
+        if map_path.is_some() {
+            eprintln!("Warning: --map is ignored when joining a co-op game");
+        }
+        Some(StartNetworkSession::Join(address))
+    } else if args.host {
+        Some(StartNetworkSession::Host)
     } else {
-        GameState::IntroAnimation
+        None
     };
 
-    // Store map path for hot reload (clone before moving into resource)
-    let hot_reload_path = args.map_path.clone();
+    // Determine initial state based on CLI arguments
+    // If a map is specified or a game is hosted, skip intro and title screen;
+    // joining waits on the title screen for the host's map
+    let initial_state = match &network {
+        Some(StartNetworkSession::Join(_)) => GameState::TitleScreen,
+        Some(StartNetworkSession::Host) => GameState::LoadingMap,
+        None if has_map_arg => GameState::LoadingMap,
+        None => GameState::IntroAnimation,
+    };
 
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
@@ -141,19 +268,86 @@ fn main() {
             }),
             ..default()
         }))
+        // Player movement and physics at a fixed timestep, drawn interpolated
+        .add_plugins(FixedStepPlugin)
         // FPS counter overlay (toggle with F3)
         .add_plugins(FpsCounterPlugin)
         .add_plugins(FrameProfilerPlugin)
+        // Map and GPU details for crash reports
+        .add_systems(
+            Update,
+            (
+                crash_report::track_render_adapter,
+                track_crash_map_path.run_if(resource_changed::<MapPathForHotReload>),
+            ),
+        )
+        // Debug console with `goto` teleport (toggle with `)
+        .add_plugins(DebugConsolePlugin)
         // Occlusion transparency system for voxels above the player
         .add_plugins(OcclusionPlugin)
+        // LOD and shadow distances tuned at runtime to hold a frame rate
+        .add_plugins(DynamicQualityPlugin)
+        // 3D view rendered below or above the window's resolution, with optional sharpening
+        .add_plugins(RenderScalePlugin)
+        // HDR glow around emissive voxels, with intensity from the settings screen
+        .add_plugins(BloomPlugin)
+        // Instanced collision bounds (C) and spatial grid cells (G) around the player
+        .add_plugins(DebugBoxesPlugin)
+        .init_resource::<DebugBoxView>()
+        // Character model, chunk material and default map header loaded during the intro
+        .add_plugins(PreloadPlugin)
+        // Color-blind palettes, high-contrast menus and UI text scale
+        .add_plugins(AccessibilityPlugin)
+        // Pooled particle bursts (voxel debris, item pickups)
+        .add_plugins(ParticlesPlugin)
+        // Rain, snow, fog and wet terrain from the map and weather triggers
+        .add_plugins(WeatherPlugin)
+        // Interact presses resolved to the nearest interactable entity
+        .add_plugins(InteractionPlugin)
+        // Scripted map events (trigger and interaction action lists)
+        .add_plugins(MapActionsPlugin)
+        // Queued dialog and sound-cue captions (accessibility setting)
+        .add_plugins(CaptionsPlugin)
+        // Voxel groups shown, hidden, solidified and dissolved by scripts
+        .add_plugins(VoxelGroupsPlugin)
+        // Doors that swap voxel groups, and the keys that unlock them
+        .add_plugins(DoorsPlugin)
+        // Teleporter pads that fade the player to their paired pad
+        .add_plugins(TeleportersPlugin)
+        // Fade between maps on load_map actions
+        .add_plugins(MapTransitionPlugin)
+        // Keyframed camera sequences played by the `cinematic` action
+        .add_plugins(CinematicsPlugin)
+        // Free-fly photo mode opened from the pause menu
+        .add_plugins(PhotoModePlugin)
+        // Footsteps with per-surface dust
+        .add_plugins(SurfacePlugin)
+        // Speedrun timer, goals and per-map best times
+        .add_plugins(SpeedrunPlugin)
+        // HUD scale, safe-area margin and widget corners
+        .add_plugins(HudLayoutPlugin)
+        // Run recording, and playback of the `--replay` file
+        .add_plugins(ReplayPlugin { playback: replay })
+        // Co-op exploration over UDP (`--host`, `--connect` or the title screen)
+        .add_plugins(NetworkPlugin { start: network })
+        // Opt-in local playtest logs (`--playtest-log`)
+        .add_plugins(PlaytestLogPlugin {
+            enabled: args.playtest_log,
+        })
+        // Local split-screen for a second gamepad
+        .add_plugins(SplitScreenPlugin)
+        // Player health and hazard voxel damage
+        .add_plugins(HealthPlugin)
+        .add_plugins(HazardPlugin)
+        // UI string tables (language is restored by the settings loader)
+        .add_plugins(LocalizationPlugin)
         // In-game settings screen
         .add_plugins(SettingsPlugin)
         .insert_state(initial_state)
-        .insert_resource(CommandLineMapPath {
-            path: args.map_path,
-        })
-        .insert_resource(MapPathForHotReload(hot_reload_path))
+        .insert_resource(CommandLineMapPath { path: map_path })
+        .init_resource::<MapPathForHotReload>()
         .init_resource::<MapLoadProgress>()
+        .init_resource::<MapLoadDiagnostics>()
         .init_resource::<HotReloadState>()
         .init_resource::<LodConfig>()
         .add_message::<MapReloadEvent>()
@@ -163,6 +357,7 @@ fn main() {
         .init_resource::<GamepadSettings>()
         .init_resource::<PlayerInput>()
         .init_resource::<PreFetchedCollisionEntities>()
+        .init_resource::<PhysicsConfig>()
         .add_systems(Startup, setup)
         // Global systems that run in any state
         .add_systems(Update, (toggle_fullscreen, handle_gamepad_connections))
@@ -172,26 +367,52 @@ fn main() {
             animate_intro.run_if(in_state(GameState::IntroAnimation)),
         )
         .add_systems(OnExit(GameState::IntroAnimation), cleanup_intro)
-        .add_systems(OnEnter(GameState::TitleScreen), setup_title_screen)
+        // The game world is despawned on leaving the game for the title or loading screen
+        .add_systems(
+            OnEnter(GameState::TitleScreen),
+            (despawn_game_entities, setup_title_screen).chain(),
+        )
         .add_systems(
             Update,
             (
                 fade_in_title_screen,
                 button_interaction,
-                keyboard_navigation,
+                edit_join_prompt,
+                keyboard_navigation
+                    .after(edit_join_prompt)
+                    .run_if(join_prompt_closed),
                 update_selected_button_visual,
                 scale_text_on_resize,
+                update_network_status_text,
             )
                 .run_if(in_state(GameState::TitleScreen)),
         )
         .add_systems(OnExit(GameState::TitleScreen), cleanup_title_screen)
+        // Showcase map orbited behind the title screen menu
+        .add_plugins(TitleFlythroughPlugin)
         .add_systems(
             OnEnter(GameState::LoadingMap),
-            (setup_loading_screen, load_map_on_enter),
+            (
+                despawn_game_entities,
+                take_next_map_request,
+                read_loading_map_info,
+                setup_loading_screen,
+                load_map_on_enter,
+            )
+                .chain(),
         )
         .add_systems(
             Update,
-            (update_loading_progress, check_map_loaded).run_if(in_state(GameState::LoadingMap)),
+            (
+                poll_map_load,
+                update_loading_progress,
+                show_load_diagnostics,
+                acknowledge_load_diagnostics,
+                animate_loading_screen,
+                check_map_loaded,
+            )
+                .chain()
+                .run_if(in_state(GameState::LoadingMap)),
         )
         .add_systems(OnExit(GameState::LoadingMap), cleanup_loading_screen)
         .add_systems(
@@ -208,8 +429,10 @@ fn main() {
             Update,
             (
                 poll_hot_reload,
-                handle_reload_hotkey,     // F5 or Ctrl+R to manually reload map
-                handle_hot_reload_toggle, // Ctrl+H to toggle hot reload on/off
+                // F5 or Ctrl+R to manually reload map
+                handle_reload_hotkey.run_if(debug_console_closed),
+                // Ctrl+H to toggle hot reload on/off
+                handle_hot_reload_toggle.run_if(debug_console_closed),
                 handle_map_reload.after(poll_hot_reload),
                 // spawn_map_system runs when GameInitialized is false (set by handle_map_reload)
                 spawn_map_system.after(handle_map_reload),
@@ -217,6 +440,7 @@ fn main() {
                 // Visual feedback for hot reload
                 show_reload_notification.after(handle_map_reload),
                 update_reload_notifications,
+                update_hot_reload_indicator,
             )
                 .run_if(in_state(GameState::InGame)),
         )
@@ -225,34 +449,66 @@ fn main() {
             Update,
             (
                 GameSystemSet::Input,
-                GameSystemSet::Movement,
-                GameSystemSet::Physics,
                 GameSystemSet::Visual,
                 GameSystemSet::Camera,
             )
                 .chain()
                 .run_if(in_state(GameState::InGame)),
         )
+        // Movement and physics run at the fixed timestep of FixedStepPlugin
+        .configure_sets(
+            FixedUpdate,
+            (GameSystemSet::Movement, GameSystemSet::Physics)
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        )
         // Input phase: Gather input from all sources, then handle game-specific input
         .add_systems(
             Update,
             (
                 reset_player_input,
-                gather_gamepad_input,
-                gather_keyboard_input,
+                // Cinematics hold the player still
+                gather_gamepad_input.run_if(cinematic_inactive),
+                gather_keyboard_input
+                    .run_if(debug_console_closed)
+                    .run_if(cinematic_inactive),
+                // Hold jumps and interact presses for the next physics step
+                latch_fixed_step_input,
                 handle_escape_key,
-                toggle_collision_box,
+                toggle_collision_box.run_if(debug_console_closed),
+                toggle_grid_cells.run_if(debug_console_closed),
                 toggle_flashlight,
             )
                 .chain()
                 .in_set(GameSystemSet::Input),
         )
         // Movement phase: Process player movement
-        .add_systems(Update, move_player.in_set(GameSystemSet::Movement))
+        .add_systems(
+            FixedUpdate,
+            (
+                take_fixed_step_input,
+                // A replay's recorded input replaces the player's
+                restart_replay_on_spawn,
+                apply_replay_input,
+                find_interaction_target,
+                begin_physics_step,
+                move_player,
+            )
+                .chain()
+                .in_set(GameSystemSet::Movement),
+        )
         // Physics phase: Apply gravity and physics (in order)
         .add_systems(
-            Update,
-            (apply_gravity, apply_physics, apply_npc_collision)
+            FixedUpdate,
+            (
+                apply_gravity,
+                apply_physics,
+                sync_npc_grid.run_if(resource_exists::<SpatialGrid>),
+                apply_npc_collision,
+                record_replay_frame.run_if(not_playing_replay),
+                sync_replay_frame,
+                end_physics_step,
+            )
                 .chain()
                 .in_set(GameSystemSet::Physics),
         )
@@ -262,6 +518,7 @@ fn main() {
             (
                 rotate_character_model,
                 update_collision_box,
+                update_debug_box_views,
                 update_flashlight_rotation,
                 flicker_lights.before(sync_light_sources),
                 sync_light_sources,
@@ -281,25 +538,74 @@ fn main() {
             Update,
             (follow_player_camera, rotate_camera)
                 .chain()
+                .run_if(cinematic_inactive)
+                .run_if(not(in_state(GameState::PhotoMode)))
                 .in_set(GameSystemSet::Camera),
         )
         .add_systems(OnEnter(GameState::Paused), pause_menu::setup_pause_menu)
         .add_systems(
             Update,
             (
-                pause_menu::pause_menu_input,
-                pause_menu::keyboard_navigation,
-                pause_menu::pause_menu_button_interaction,
+                (
+                    pause_menu::pause_menu_input,
+                    pause_menu::keyboard_navigation,
+                    pause_menu::pause_menu_button_interaction,
+                )
+                    .run_if(not(resource_exists::<PendingConfirmation>)),
+                // Restart Map and Return to Title ask first while a run is on
+                (
+                    pause_menu::spawn_confirm_dialog.run_if(resource_added::<PendingConfirmation>),
+                    pause_menu::confirm_dialog_input,
+                    pause_menu::confirm_button_interaction,
+                    pause_menu::update_confirm_button_visual,
+                )
+                    .chain()
+                    .distributive_run_if(resource_exists::<PendingConfirmation>),
+            )
+                .chain()
+                .run_if(in_state(GameState::Paused)),
+        )
+        .add_systems(
+            Update,
+            (
                 pause_menu::update_selected_button_visual,
                 pause_menu::scale_text_on_resize,
+                pause_menu::update_hot_reload_button_text,
             )
                 .run_if(in_state(GameState::Paused)),
         )
         .add_systems(OnExit(GameState::Paused), pause_menu::cleanup_pause_menu)
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            game_over::setup_game_over_screen,
+        )
+        .add_systems(
+            Update,
+            (
+                game_over::game_over_navigation,
+                game_over::game_over_button_interaction,
+                game_over::update_selected_button_visual,
+                pause_menu::scale_text_on_resize,
+            )
+                .run_if(in_state(GameState::GameOver)),
+        )
+        .add_systems(OnExit(GameState::GameOver), game_over::cleanup_game_over)
+        .add_systems(OnEnter(GameState::Results), results::setup_results_screen)
+        .add_systems(
+            Update,
+            (
+                results::results_navigation,
+                results::results_button_interaction,
+                results::update_selected_button_visual,
+                pause_menu::scale_text_on_resize,
+            )
+                .run_if(in_state(GameState::Results)),
+        )
+        .add_systems(OnExit(GameState::Results), results::cleanup_results)
         // Cleanup hot reload when leaving InGame (going to Paused doesn't count as leaving)
         .add_systems(
             OnExit(GameState::InGame),
-            (cleanup_hot_reload, cleanup_npc_labels),
+            (cleanup_hot_reload, cleanup_hot_reload_indicator),
         )
         .run();
 }
@@ -311,14 +617,79 @@ fn setup(mut commands: Commands) {
 
 /// System to despawn the 2D camera when entering InGame state.
 /// This prevents camera order ambiguity with the 3D game camera.
-fn cleanup_2d_camera(mut commands: Commands, camera_query: Query<Entity, With<Camera2d>>) {
+fn cleanup_2d_camera(
+    mut commands: Commands,
+    camera_query: Query<Entity, (With<Camera2d>, Without<RenderScaleCamera>)>,
+) {
     for entity in &camera_query {
         commands.entity(entity).despawn();
         info!("Despawned 2D camera before entering InGame state");
     }
 }
 
-/// System to load the map when entering LoadingMap state.
+/// Map file to load: the command-line argument if given, else the default map.
+fn map_path_to_load(cli_map_path: &CommandLineMapPath) -> String {
+    match &cli_map_path.path {
+        Some(path) => path.to_string_lossy().to_string(),
+        None => DEFAULT_MAP_PATH.to_string(),
+    }
+}
+
+/// System to switch to the map a goal leads to when the results screen's
+/// Next Map choice asked for it; later loads (Retry) stay on that map.
+fn take_next_map_request(
+    mut request: ResMut<NextMapRequest>,
+    mut cli_map_path: ResMut<CommandLineMapPath>,
+) {
+    if let Some(path) = request.0.take() {
+        info!("Loading next map: {:?}", path);
+        cli_map_path.path = Some(path);
+    }
+}
+
+/// System to read the map header for the loading screen before the full load.
+///
+/// The default map's header is usually already in [`PreloadedAssets`].
+fn read_loading_map_info(
+    mut commands: Commands,
+    cli_map_path: Res<CommandLineMapPath>,
+    preloaded: Option<Res<PreloadedAssets>>,
+) {
+    let map_path = map_path_to_load(&cli_map_path);
+    let preloaded_header = preloaded
+        .as_ref()
+        .and_then(|preloaded| preloaded.header_for(&map_path));
+    if let Some(metadata) = preloaded_header {
+        commands.insert_resource(LoadingMapInfo {
+            metadata: Some(metadata.clone()),
+        });
+        return;
+    }
+    let metadata = match MapLoader::read_metadata(&map_path) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            warn!("Could not read map header from '{}': {}", map_path, e);
+            None
+        }
+    };
+    commands.insert_resource(LoadingMapInfo { metadata });
+}
+
+/// Map load running on the async compute pool.
+///
+/// The task carries its own progress tracker, which is copied into
+/// [`MapLoadProgress`] when the task finishes.
+#[derive(Resource)]
+struct PendingMapLoad(Task<(PathBuf, SafeMapLoad, MapLoadProgress)>);
+
+/// A loaded map and what safe mode skipped to load it.
+type SafeMapLoad = MapResult<(MapData, Vec<LoadDiagnostic>)>;
+
+/// System to start loading the map when entering LoadingMap state.
+///
+/// The file is read, parsed and validated off the main thread so the loading
+/// screen keeps animating; [`poll_map_load`] picks up the result. Files that
+/// don't parse are loaded in safe mode (see `MapLoader::load_safe`).
 fn load_map_on_enter(
     mut commands: Commands,
     mut progress: ResMut<MapLoadProgress>,
@@ -326,28 +697,78 @@ fn load_map_on_enter(
 ) {
     info!("Loading map...");
     progress.clear();
+    progress.update(systems::game::map::LoadProgress::Started);
+    // Drop the previous map so check_map_loaded waits for this load.
+    commands.remove_resource::<LoadedMapData>();
+    commands.insert_resource(MapLoadDiagnostics::default());
 
     // Determine which map file to load
-    // Priority: CLI argument > default map
-    let map_path = if let Some(path) = &cli_map_path.path {
+    // Priority: CLI argument (or the map a goal led to) > default map
+    if let Some(path) = &cli_map_path.path {
         info!("Loading map from command-line argument: {:?}", path);
-        path.to_string_lossy().to_string()
-    } else {
-        "assets/maps/default.ron".to_string()
+    }
+    let map_path = map_path_to_load(&cli_map_path);
+
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let mut task_progress = MapLoadProgress::default();
+        let result = MapLoader::load_safe(&map_path, &mut task_progress);
+        if let Err(e) = &result {
+            warn!(
+                "Failed to load map file '{}': {}. Using default map.",
+                map_path, e
+            );
+        }
+        (PathBuf::from(map_path), result, task_progress)
+    });
+    commands.insert_resource(PendingMapLoad(task));
+}
+
+/// System to collect the finished map load and publish it as [`LoadedMapData`].
+///
+/// Also records the loaded file in [`MapPathForHotReload`] so hot reload
+/// watches whichever map is in play, not only one given on the command line,
+/// and publishes what safe mode skipped in [`MapLoadDiagnostics`].
+fn poll_map_load(
+    mut commands: Commands,
+    mut progress: ResMut<MapLoadProgress>,
+    mut hot_reload_path: ResMut<MapPathForHotReload>,
+    pending: Option<ResMut<PendingMapLoad>>,
+) {
+    let Some(mut pending) = pending else {
+        return;
     };
+    let Some((map_path, result, task_progress)) = check_ready(&mut pending.0) else {
+        return;
+    };
+    commands.remove_resource::<PendingMapLoad>();
+
+    for event in task_progress.events {
+        progress.update(event);
+    }
 
     // Try to load the specified map file
-    let map = match MapLoader::load_from_file(&map_path, &mut progress) {
-        Ok(map) => {
-            info!("Successfully loaded map: {}", map.metadata.name);
+    let map = match result {
+        Ok((map, diagnostics)) => {
+            if diagnostics.is_empty() {
+                info!("Successfully loaded map: {}", map.metadata.name);
+            } else {
+                warn!(
+                    "Loaded map {} in safe mode, skipping {} malformed parts",
+                    map.metadata.name,
+                    diagnostics.len()
+                );
+                commands.insert_resource(MapLoadDiagnostics {
+                    diagnostics,
+                    acknowledged: false,
+                });
+            }
+            hot_reload_path.0 = Some(map_path);
             map
         }
         Err(e) => {
-            warn!(
-                "Failed to load map file '{}': {}. Using default map.",
-                map_path, e
-            );
             progress.update(systems::game::map::LoadProgress::Error(e.to_string()));
+            // The built-in default map has no file to watch
+            hot_reload_path.0 = None;
             MapLoader::load_default()
         }
     };
@@ -355,14 +776,26 @@ fn load_map_on_enter(
     commands.insert_resource(LoadedMapData { map });
 }
 
+/// System to name the map in play in crash reports.
+fn track_crash_map_path(map_path: Res<MapPathForHotReload>) {
+    crash_report::set_map_path(map_path.0.clone());
+}
+
 /// System to check if map loading is complete and transition to InGame state.
+///
+/// A map loaded in safe mode waits on the loading screen until the player
+/// has seen what was skipped.
 fn check_map_loaded(
     progress: Res<MapLoadProgress>,
     map_data: Option<Res<LoadedMapData>>,
+    diagnostics: Res<MapLoadDiagnostics>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     // If we have map data and loading is complete (or errored but we have fallback)
-    if map_data.is_some() && (progress.is_complete() || progress.percentage() >= 0.6) {
+    if map_data.is_some()
+        && diagnostics.allows_start()
+        && (progress.is_complete() || progress.percentage() >= 0.6)
+    {
         info!("Map loading complete, transitioning to InGame");
         next_state.set(GameState::InGame);
     }
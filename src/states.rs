@@ -9,4 +9,7 @@ pub enum GameState {
     InGame,
     Settings,
     Paused,
+    PhotoMode,
+    GameOver,
+    Results,
 }
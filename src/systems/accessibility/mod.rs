@@ -0,0 +1,241 @@
+//! Accessibility options: color-blind friendly voxel palettes, a
+//! high-contrast theme for the title and pause menus, UI text scale and
+//! [captions](crate::systems::game::captions).
+//!
+//! The palette feeds the per-sub-voxel colors chunks are meshed with, so
+//! changing it mid-game re-meshes every chunk. The deuteranopia and
+//! protanopia palettes vary terrain along the blue–yellow axis and in
+//! brightness instead of between red and green, and give lava a yellow that
+//! stays bright for players who see red as dark. Text scale is applied as
+//! Bevy's [`UiScale`], so it grows every UI element sized in pixels along
+//! with its text.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::states::GameState;
+use crate::systems::game::map::format::{VoxelFace, VoxelType};
+use crate::systems::game::map::spawner::{
+    rebuild_chunks, ChunkMaterials, ChunkSpawnContext, VoxelChunk,
+};
+use crate::systems::game::map::LoadedMapData;
+use crate::systems::game::occlusion::OcclusionConfig;
+use crate::systems::game::resources::SpatialGrid;
+use crate::systems::game::voxel_groups::VoxelGroups;
+
+/// Smallest UI text scale.
+pub const MIN_TEXT_SCALE: f32 = 0.8;
+
+/// Largest UI text scale.
+pub const MAX_TEXT_SCALE: f32 = 1.6;
+
+/// Text scale change per settings step.
+pub const TEXT_SCALE_STEP: f32 = 0.1;
+
+/// Color scheme for voxel terrain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    /// Safe for red-green color blindness with weak green cones
+    Deuteranopia,
+    /// Safe for red-green color blindness with weak red cones
+    Protanopia,
+}
+
+impl ColorPalette {
+    pub const ALL: [ColorPalette; 3] = [
+        ColorPalette::Standard,
+        ColorPalette::Deuteranopia,
+        ColorPalette::Protanopia,
+    ];
+
+    /// The palette `delta` steps along [`ColorPalette::ALL`], wrapping around.
+    pub fn cycled(self, delta: i32) -> Self {
+        let len = Self::ALL.len() as i32;
+        let index = Self::ALL.iter().position(|&p| p == self).unwrap_or(0) as i32;
+        Self::ALL[(index + delta).rem_euclid(len) as usize]
+    }
+
+    /// Terrain color at `t`, a palette position from 0.0 to 1.0.
+    pub fn color(self, t: f32) -> Color {
+        use std::f32::consts::PI;
+        let wave = (t * PI * 2.0).sin() * 0.5 + 0.5;
+        match self {
+            ColorPalette::Standard => Color::srgb(
+                0.2 + t * 0.6,
+                0.3 + wave * 0.4,
+                0.4 + ((t * PI * 3.0).cos() * 0.5 + 0.5) * 0.4,
+            ),
+            // Blue to ochre, with the wave varying brightness
+            ColorPalette::Deuteranopia => {
+                let light = 0.75 + wave * 0.25;
+                Color::srgb(
+                    (0.2 + t * 0.65) * light,
+                    (0.35 + t * 0.35) * light,
+                    (0.75 - t * 0.55) * light,
+                )
+            }
+            // Like deuteranopia but lighter overall, since reds look darker
+            ColorPalette::Protanopia => {
+                let light = 0.8 + wave * 0.2;
+                Color::srgb(
+                    (0.3 + t * 0.6) * light,
+                    (0.45 + t * 0.35) * light,
+                    (0.85 - t * 0.6) * light,
+                )
+            }
+        }
+    }
+
+    /// Fixed color of `voxel_type` in this palette, for types that have one.
+    pub fn tint(self, voxel_type: VoxelType) -> Option<Color> {
+        match (self, voxel_type) {
+            (ColorPalette::Standard, _) => voxel_type.tint(),
+            (_, VoxelType::Lava) => Some(Color::srgb(1.0, 0.8, 0.1)),
+            (_, VoxelType::Water) => Some(Color::srgb(0.1, 0.35, 0.9)),
+            (_, VoxelType::Glowstone) => Some(Color::srgb(0.95, 0.95, 0.9)),
+            _ => voxel_type.tint(),
+        }
+    }
+
+    /// Fixed color of `face` of `voxel_type` in this palette, for types
+    /// whose faces differ.
+    pub fn face_tint(self, voxel_type: VoxelType, face: VoxelFace) -> Option<Color> {
+        match (self, voxel_type, face) {
+            (ColorPalette::Standard, _, _) => voxel_type.face_tint(face),
+            // A light blue top reads apart from the ochre sides without
+            // relying on green
+            (_, VoxelType::Grass, VoxelFace::Top) => Some(Color::srgb(0.55, 0.75, 0.95)),
+            _ => voxel_type.face_tint(face),
+        }
+    }
+}
+
+/// Accessibility options, set from the settings screen.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct AccessibilitySettings {
+    pub palette: ColorPalette,
+    /// High-contrast title and pause menus
+    pub high_contrast: bool,
+    /// Multiplier on UI size, [`MIN_TEXT_SCALE`] to [`MAX_TEXT_SCALE`]
+    pub text_scale: f32,
+    /// Show dialog and sound cues as captions
+    pub captions: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            palette: ColorPalette::Standard,
+            high_contrast: false,
+            text_scale: 1.0,
+            captions: false,
+        }
+    }
+}
+
+impl AccessibilitySettings {
+    /// Move the text scale `delta` steps, staying in range.
+    pub fn step_text_scale(&mut self, delta: i32) {
+        let scale = self.text_scale + delta as f32 * TEXT_SCALE_STEP;
+        self.text_scale = ((scale * 10.0).round() / 10.0).clamp(MIN_TEXT_SCALE, MAX_TEXT_SCALE);
+    }
+}
+
+/// Colors of the title and pause menus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MenuTheme {
+    pub button: Color,
+    pub button_hovered: Color,
+    pub button_pressed: Color,
+    pub text: Color,
+    /// Backdrop behind the pause menu
+    pub backdrop: Color,
+}
+
+impl MenuTheme {
+    pub fn new(high_contrast: bool) -> Self {
+        if high_contrast {
+            Self {
+                button: Color::srgba(0.0, 0.0, 0.0, 0.9),
+                button_hovered: Color::srgb(0.0, 0.3, 0.8),
+                button_pressed: Color::srgb(0.0, 0.2, 0.55),
+                text: Color::WHITE,
+                backdrop: Color::srgba(0.0, 0.0, 0.0, 0.92),
+            }
+        } else {
+            Self {
+                button: Color::srgba(0.15, 0.15, 0.15, 0.0),
+                button_hovered: Color::srgba(1.0, 0.8, 0.2, 0.3),
+                button_pressed: Color::srgba(1.0, 0.8, 0.2, 0.5),
+                text: Color::srgb(0.9, 0.9, 0.9),
+                backdrop: Color::srgba(0.0, 0.0, 0.0, 0.7),
+            }
+        }
+    }
+}
+
+/// Apply the text scale to the UI.
+pub fn apply_text_scale(settings: Res<AccessibilitySettings>, mut ui_scale: ResMut<UiScale>) {
+    if settings.is_changed() && ui_scale.0 != settings.text_scale {
+        ui_scale.0 = settings.text_scale;
+    }
+}
+
+/// Re-mesh every chunk after the palette changed mid-game.
+#[allow(clippy::too_many_arguments)]
+pub fn recolor_chunks(
+    mut commands: Commands,
+    settings: Res<AccessibilitySettings>,
+    map: Res<LoadedMapData>,
+    groups: Res<VoxelGroups>,
+    mut spatial_grid: ResMut<SpatialGrid>,
+    mut materials: ResMut<ChunkMaterials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    occlusion_config: Res<OcclusionConfig>,
+    chunks: Query<(Entity, &VoxelChunk)>,
+) {
+    if materials.palette == settings.palette {
+        return;
+    }
+    materials.palette = settings.palette;
+
+    let mut chunk_positions = HashSet::new();
+    for (entity, chunk) in &chunks {
+        chunk_positions.insert(chunk.chunk_pos);
+        commands.entity(entity).despawn();
+    }
+    let mut ctx = ChunkSpawnContext {
+        commands,
+        spatial_grid: &mut spatial_grid,
+        meshes: &mut meshes,
+        chunk_material: materials.chunk_material.clone(),
+        type_materials: materials.type_materials.clone(),
+        shadow_quality: occlusion_config.shadow_quality,
+        palette: settings.palette,
+    };
+    rebuild_chunks(&mut ctx, &map.map, &groups, &chunk_positions);
+    info!("Recolored {} chunks", chunk_positions.len());
+}
+
+/// Plugin that sets up the accessibility options.
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccessibilitySettings>()
+            .add_systems(Update, apply_text_scale)
+            .add_systems(
+                Update,
+                recolor_chunks
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(resource_exists::<SpatialGrid>)
+                    .run_if(resource_exists::<ChunkMaterials>),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+
+#[test]
+fn palettes_cycle_in_both_directions() {
+    assert_eq!(ColorPalette::Standard.cycled(1), ColorPalette::Deuteranopia);
+    assert_eq!(ColorPalette::Standard.cycled(-1), ColorPalette::Protanopia);
+}
+
+#[test]
+fn color_blind_palettes_recolor_terrain() {
+    for palette in [ColorPalette::Deuteranopia, ColorPalette::Protanopia] {
+        assert_ne!(palette.color(0.5), ColorPalette::Standard.color(0.5));
+        // Low and high ends sit on the blue-yellow axis
+        let low = palette.color(0.0).to_srgba();
+        let high = palette.color(1.0).to_srgba();
+        assert!(low.blue > low.red);
+        assert!(high.red > high.blue);
+    }
+}
+
+#[test]
+fn lava_stays_bright_in_color_blind_palettes() {
+    let lava = ColorPalette::Protanopia
+        .tint(VoxelType::Lava)
+        .unwrap()
+        .to_srgba();
+    assert!(lava.green > 0.6);
+    assert_eq!(
+        ColorPalette::Standard.tint(VoxelType::Lava),
+        VoxelType::Lava.tint()
+    );
+    assert_eq!(ColorPalette::Deuteranopia.tint(VoxelType::Stone), None);
+}
+
+#[test]
+fn text_scale_steps_stay_in_range() {
+    let mut settings = AccessibilitySettings::default();
+    settings.step_text_scale(2);
+    assert_eq!(settings.text_scale, 1.2);
+    settings.step_text_scale(10);
+    assert_eq!(settings.text_scale, MAX_TEXT_SCALE);
+    settings.step_text_scale(-20);
+    assert_eq!(settings.text_scale, MIN_TEXT_SCALE);
+}
+
+#[test]
+fn high_contrast_menus_use_opaque_buttons() {
+    let theme = MenuTheme::new(true);
+    assert_eq!(theme.button_hovered.alpha(), 1.0);
+    assert_eq!(theme.text, Color::WHITE);
+    assert!(MenuTheme::new(false).button.alpha() < theme.button.alpha());
+}
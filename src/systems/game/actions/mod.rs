@@ -0,0 +1,573 @@
+//! Scripted map events: action lists run by triggers and interactions.
+//!
+//! Any map entity can carry an `actions` property, a list of actions
+//! separated by `;` or new lines. Each action is a verb and its arguments:
+//!
+//! - `say <text>` — show text on screen (`loc:` keys are translated)
+//! - `wait <seconds>` — pause before the next action
+//! - `teleport <x y z>` / `teleport <entity name>` — move the player
+//! - `show <group>` / `hide <group>` / `toggle <group>` — make a voxel group
+//!   appear or vanish
+//! - `solidify <group>` / `dissolve <group>` — make a voxel group block the
+//!   player or let them through
+//! - `sound <asset path>` — play a sound, captioned with its file name
+//! - `weather <clear|rain|snow> [intensity]` — change the map's weather
+//! - `cinematic <sequence>` — play one of the map's camera sequences
+//! - `script <path>` — run a Rhai [script](super::scripting), relative to
+//!   the map file
+//! - `load_map <path> [x y z]` — [move to another map](super::map_transition),
+//!   relative to the map file, arriving at `x y z` or its player spawn
+//!
+//! A `Trigger` runs its actions when the player comes within its `radius`
+//! (default [`DEFAULT_TRIGGER_RADIUS`]); any other entity runs them when the
+//! player presses interact within [`INTERACTION_RANGE`]. Actions run once
+//! unless the entity sets `repeat` to `true`. Every action list goes through
+//! one [`ActionRunner`] queue, so a second list starts after the first ends.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use super::captions::{sound_caption, CaptionRequest};
+use super::cinematics::CinematicRequest;
+use super::components::{GameEntity, Player};
+use super::go_to::{EntityNameIndex, GoToTarget};
+use super::hot_reload::MapPathForHotReload;
+use super::interaction::{horizontal_distance, InteractionTarget};
+use super::map::format::{EntityType, Precipitation};
+use super::map::LoadedMapData;
+use super::map_transition::LoadMapRequest;
+use super::npc_labels::INTERACTION_RANGE;
+use super::scripting::{resolve_script_path, run_script, ScriptContext, ScriptLibrary};
+use super::speedrun::resolve_next_map;
+use super::voxel_groups::{GroupChange, VoxelGroupRequest};
+use super::weather::Weather;
+use crate::states::GameState;
+use crate::systems::accessibility::AccessibilitySettings;
+use crate::systems::localization::Localization;
+
+/// Entity property holding an action list.
+pub const ACTIONS_KEY: &str = "actions";
+
+/// Trigger radius used when the trigger doesn't set `radius` (the same as
+/// weather zones use).
+pub const DEFAULT_TRIGGER_RADIUS: f32 = 3.0;
+
+/// Shortest time text from `say` stays on screen, in seconds.
+const SAY_MIN_SECONDS: f32 = 2.0;
+
+/// Longest time text from `say` stays on screen, in seconds.
+const SAY_MAX_SECONDS: f32 = 8.0;
+
+/// One step of a scripted event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapAction {
+    /// Show text on screen
+    Say(String),
+    /// Pause before the next action, in seconds
+    Wait(f32),
+    /// Move the player to a position or named entity
+    Teleport(GoToTarget),
+    /// Change a voxel group
+    Group { name: String, change: GroupChange },
+    /// Play a sound asset
+    Sound(String),
+    /// Change the map's weather
+    Weather {
+        precipitation: Precipitation,
+        intensity: Option<f32>,
+    },
+    /// Play a camera sequence from the map
+    Cinematic(String),
+    /// Run a script file, relative to the map file
+    Script(String),
+    /// Move to another map, relative to the map file
+    LoadMap {
+        map: String,
+        spawn_point: Option<Vec3>,
+    },
+}
+
+impl MapAction {
+    /// Parse a single action such as `say Hello` or `wait 1.5`.
+    pub fn parse(action: &str) -> Result<Self, String> {
+        let action = action.trim();
+        let (verb, args) = action
+            .split_once(char::is_whitespace)
+            .map_or((action, ""), |(verb, args)| (verb, args.trim()));
+        let require = |what: &str| {
+            if args.is_empty() {
+                Err(format!("'{}' needs {}", verb, what))
+            } else {
+                Ok(args.to_string())
+            }
+        };
+
+        match verb.to_lowercase().as_str() {
+            "say" => require("some text").map(Self::Say),
+            "wait" => match args.parse::<f32>() {
+                Ok(seconds) if seconds >= 0.0 && seconds.is_finite() => Ok(Self::Wait(seconds)),
+                _ => Err(format!("'wait' needs a number of seconds, got {:?}", args)),
+            },
+            "teleport" => GoToTarget::parse(args)
+                .map(Self::Teleport)
+                .ok_or_else(|| "'teleport' needs coordinates or an entity name".to_string()),
+            "show" | "hide" | "toggle" | "solidify" | "dissolve" => {
+                let change = match verb.to_lowercase().as_str() {
+                    "show" => GroupChange::Show,
+                    "hide" => GroupChange::Hide,
+                    "toggle" => GroupChange::Toggle,
+                    "solidify" => GroupChange::Solidify,
+                    _ => GroupChange::Dissolve,
+                };
+                require("a group name").map(|name| Self::Group { name, change })
+            }
+            "sound" => require("an asset path").map(Self::Sound),
+            "cinematic" => require("a camera sequence name").map(Self::Cinematic),
+            "script" => require("a script path").map(Self::Script),
+            "load_map" => {
+                let map = require("a map path")?;
+                // Three trailing numbers are the spawn point
+                let words: Vec<&str> = map.split_whitespace().collect();
+                let coords: Vec<f32> = words
+                    .iter()
+                    .rev()
+                    .take(3)
+                    .rev()
+                    .filter_map(|word| word.parse::<f32>().ok())
+                    .collect();
+                if words.len() > 3 && coords.len() == 3 {
+                    Ok(Self::LoadMap {
+                        map: words[..words.len() - 3].join(" "),
+                        spawn_point: Some(Vec3::new(coords[0], coords[1], coords[2])),
+                    })
+                } else {
+                    Ok(Self::LoadMap {
+                        map,
+                        spawn_point: None,
+                    })
+                }
+            }
+            "weather" => {
+                let mut parts = args.split_whitespace();
+                let precipitation = match parts.next().map(str::to_lowercase).as_deref() {
+                    Some("clear") | Some("none") => Precipitation::None,
+                    Some("rain") => Precipitation::Rain,
+                    Some("snow") => Precipitation::Snow,
+                    _ => {
+                        return Err(format!(
+                            "'weather' needs clear, rain or snow, got {:?}",
+                            args
+                        ))
+                    }
+                };
+                let intensity = match parts.next() {
+                    None => None,
+                    Some(raw) => match raw.parse::<f32>() {
+                        Ok(value) if (0.0..=1.0).contains(&value) => Some(value),
+                        _ => {
+                            return Err(format!(
+                                "'weather' intensity must be between 0.0 and 1.0, got {:?}",
+                                raw
+                            ))
+                        }
+                    },
+                };
+                Ok(Self::Weather {
+                    precipitation,
+                    intensity,
+                })
+            }
+            "" => Err("empty action".to_string()),
+            other => Err(format!("unknown action '{}'", other)),
+        }
+    }
+}
+
+/// Parse an action list: actions separated by `;` or new lines. Blank entries
+/// are skipped.
+pub fn parse_actions(script: &str) -> Result<Vec<MapAction>, String> {
+    script
+        .split([';', '\n'])
+        .filter(|action| !action.trim().is_empty())
+        .map(MapAction::parse)
+        .collect()
+}
+
+/// What makes an entity run its actions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Activation {
+    /// The player comes within `radius`
+    Enter { radius: f32 },
+    /// The player presses interact within [`INTERACTION_RANGE`]
+    Interact,
+}
+
+impl Activation {
+    /// How an entity of `entity_type` runs its actions.
+    pub fn for_entity(
+        entity_type: EntityType,
+        properties: &std::collections::HashMap<String, String>,
+    ) -> Self {
+        match entity_type {
+            EntityType::Trigger => Self::Enter {
+                radius: properties
+                    .get("radius")
+                    .and_then(|r| r.parse::<f32>().ok())
+                    .filter(|r| *r > 0.0)
+                    .unwrap_or(DEFAULT_TRIGGER_RADIUS),
+            },
+            _ => Self::Interact,
+        }
+    }
+}
+
+/// Actions attached to a map entity.
+#[derive(Component, Debug, Clone)]
+pub struct ScriptedActions {
+    pub actions: Vec<MapAction>,
+    pub activation: Activation,
+    /// Run every time instead of only the first
+    pub repeat: bool,
+    /// Whether the actions have run
+    fired: bool,
+    /// Whether the player was inside the trigger last frame
+    inside: bool,
+}
+
+impl ScriptedActions {
+    pub fn new(actions: Vec<MapAction>, activation: Activation, repeat: bool) -> Self {
+        Self {
+            actions,
+            activation,
+            repeat,
+            fired: false,
+            inside: false,
+        }
+    }
+
+    /// Update with the player `distance` away; returns true when the actions
+    /// should run now. `interact` is whether interact was pressed while this
+    /// is the nearest interactable entity.
+    pub fn update(&mut self, distance: f32, interact: bool) -> bool {
+        let activated = match self.activation {
+            Activation::Enter { radius } => {
+                let inside = distance <= radius;
+                let entered = inside && !self.inside;
+                self.inside = inside;
+                entered
+            }
+            Activation::Interact => interact && distance <= INTERACTION_RANGE,
+        };
+        if !activated || (self.fired && !self.repeat) {
+            return false;
+        }
+        self.fired = true;
+        true
+    }
+}
+
+/// Queue of actions waiting to run.
+#[derive(Resource, Debug, Default)]
+pub struct ActionRunner {
+    queue: VecDeque<MapAction>,
+    /// Seconds left of the current `wait`
+    wait: f32,
+}
+
+impl ActionRunner {
+    /// Queue `actions` after anything already running.
+    pub fn run(&mut self, actions: &[MapAction]) {
+        self.queue.extend(actions.iter().cloned());
+    }
+
+    /// Whether any actions are queued or waiting.
+    pub fn is_idle(&self) -> bool {
+        self.queue.is_empty() && self.wait <= 0.0
+    }
+
+    /// Queue `actions` to run before anything already queued.
+    pub fn run_next(&mut self, actions: Vec<MapAction>) {
+        for action in actions.into_iter().rev() {
+            self.queue.push_front(action);
+        }
+    }
+
+    /// Advance by `delta` seconds and take the actions due now, stopping at
+    /// the next `wait` that hasn't elapsed or after a `script`, whose actions
+    /// go ahead of the rest.
+    pub fn advance(&mut self, delta: f32) -> Vec<MapAction> {
+        let mut due = Vec::new();
+        self.wait -= delta;
+        while self.wait <= 0.0 {
+            match self.queue.pop_front() {
+                Some(MapAction::Wait(seconds)) => self.wait += seconds,
+                Some(script @ MapAction::Script(_)) => {
+                    due.push(script);
+                    break;
+                }
+                Some(action) => due.push(action),
+                None => {
+                    self.wait = 0.0;
+                    break;
+                }
+            }
+        }
+        due
+    }
+
+    /// Drop everything queued.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.wait = 0.0;
+    }
+}
+
+/// Seconds `text` stays on screen.
+pub fn say_duration(text: &str) -> f32 {
+    (SAY_MIN_SECONDS + text.chars().count() as f32 * 0.05).min(SAY_MAX_SECONDS)
+}
+
+/// Start the actions of triggers the player enters and entities they interact with.
+pub fn activate_scripted_actions(
+    player: Option<Single<&Transform, With<Player>>>,
+    target: Res<InteractionTarget>,
+    mut sources: Query<(Entity, &mut ScriptedActions, &GlobalTransform)>,
+    mut runner: ResMut<ActionRunner>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    let player_pos = player.translation;
+
+    for (entity, mut source, transform) in &mut sources {
+        let distance = match source.activation {
+            Activation::Enter { .. } => transform.translation().distance(player_pos),
+            Activation::Interact => horizontal_distance(transform.translation(), player_pos),
+        };
+        // Interact only reaches the nearest interactable entity in range
+        let interact = target.0 == Some(entity);
+        if source.update(distance, interact) {
+            runner.run(&source.actions);
+        }
+    }
+}
+
+/// Text box that shows `say` text.
+#[derive(Component, Debug, Default)]
+pub struct ActionTextBox {
+    /// Seconds left on screen
+    remaining: f32,
+}
+
+/// Marker for the text inside the [`ActionTextBox`].
+#[derive(Component)]
+pub struct ActionTextBoxText;
+
+fn setup_action_text_box(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(20.0),
+                right: Val::Percent(20.0),
+                bottom: Val::Px(80.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            GlobalZIndex(50),
+            Visibility::Hidden,
+            ActionTextBox::default(),
+        ))
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font_size: 22.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            TextLayout::new_with_justify(Justify::Center),
+            ActionTextBoxText,
+        ));
+}
+
+/// Messages actions send to other systems.
+#[derive(SystemParam)]
+pub struct ActionMessages<'w> {
+    groups: MessageWriter<'w, VoxelGroupRequest>,
+    cinematics: MessageWriter<'w, CinematicRequest>,
+    captions: MessageWriter<'w, CaptionRequest>,
+    maps: MessageWriter<'w, LoadMapRequest>,
+}
+
+/// Run the queued actions that are due.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_map_actions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut runner: ResMut<ActionRunner>,
+    asset_server: Res<AssetServer>,
+    localization: Option<Res<Localization>>,
+    map: Option<Res<LoadedMapData>>,
+    mut player: Option<Single<(&mut Transform, &mut Player)>>,
+    mut weather: ResMut<Weather>,
+    mut messages: ActionMessages,
+    accessibility: Res<AccessibilitySettings>,
+    mut scripts: ResMut<ScriptLibrary>,
+    map_path: Option<Res<MapPathForHotReload>>,
+    text_box: Single<(&mut ActionTextBox, &mut Visibility, &Children)>,
+    mut texts: Query<&mut Text, With<ActionTextBoxText>>,
+) {
+    if runner.is_idle() {
+        return;
+    }
+    let (mut text_box, mut visibility, children) = text_box.into_inner();
+
+    for action in runner.advance(time.delta_secs()) {
+        match action {
+            MapAction::Say(text) => {
+                let text = match &localization {
+                    Some(localization) => localization.resolve(&text),
+                    None => text,
+                };
+                // Captions queue overlapping lines instead of replacing them
+                if accessibility.captions {
+                    messages.captions.write(CaptionRequest::dialog(text));
+                    continue;
+                }
+                text_box.remaining = say_duration(&text);
+                *visibility = Visibility::Visible;
+                for child in children {
+                    if let Ok(mut shown) = texts.get_mut(*child) {
+                        shown.0 = text.clone();
+                    }
+                }
+            }
+            MapAction::Teleport(target) => {
+                let index = map
+                    .as_ref()
+                    .map(|map| EntityNameIndex::build(&map.map.entities))
+                    .unwrap_or_default();
+                let (Some(destination), Some((transform, player))) =
+                    (index.resolve(&target), player.as_deref_mut())
+                else {
+                    warn!("Scripted teleport to {:?} found no destination", target);
+                    continue;
+                };
+                transform.translation = destination.position;
+                player.velocity = Vec3::ZERO;
+                player.is_grounded = false;
+            }
+            MapAction::Group { name, change } => {
+                info!("Scripted {:?} of voxel group '{}'", change, name);
+                messages.groups.write(VoxelGroupRequest {
+                    group: name,
+                    change,
+                });
+            }
+            MapAction::Sound(path) => {
+                messages
+                    .captions
+                    .write(CaptionRequest::sound(sound_caption(&path)));
+                commands.spawn((
+                    AudioPlayer::new(asset_server.load::<AudioSource>(path)),
+                    PlaybackSettings::DESPAWN,
+                    GameEntity,
+                ));
+            }
+            MapAction::Weather {
+                precipitation,
+                intensity,
+            } => {
+                let mut changed = weather.base.clone();
+                changed.precipitation = precipitation;
+                if let Some(intensity) = intensity {
+                    changed.intensity = intensity;
+                }
+                weather.base = changed.clone();
+                weather.target = changed;
+            }
+            MapAction::Cinematic(name) => {
+                messages.cinematics.write(CinematicRequest(name));
+            }
+            MapAction::Script(script) => {
+                let path = resolve_script_path(
+                    map_path.as_ref().and_then(|map_path| map_path.0.as_deref()),
+                    &script,
+                );
+                let context = ScriptContext {
+                    player_position: player.as_ref().map(|player| player.0.translation),
+                };
+                match scripts.load(&path).and_then(|ast| run_script(ast, context)) {
+                    Ok(actions) => runner.run_next(actions),
+                    Err(reason) => warn!("Script {:?} failed: {}", path, reason),
+                }
+            }
+            MapAction::LoadMap { map, spawn_point } => {
+                let path = resolve_next_map(
+                    &map,
+                    map_path.as_ref().and_then(|map_path| map_path.0.as_deref()),
+                );
+                messages.maps.write(LoadMapRequest { path, spawn_point });
+            }
+            // The runner holds back actions behind a wait
+            MapAction::Wait(_) => {}
+        }
+    }
+}
+
+/// Hide `say` text once its time is up.
+pub fn update_action_text(
+    time: Res<Time>,
+    text_box: Single<(&mut ActionTextBox, &mut Visibility)>,
+) {
+    let (mut text_box, mut visibility) = text_box.into_inner();
+    if text_box.remaining <= 0.0 {
+        return;
+    }
+    text_box.remaining -= time.delta_secs();
+    if text_box.remaining <= 0.0 {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+/// Stop running actions and hide text when the game ends, on entering the
+/// title or loading screen. Pausing, photo mode and the settings screen keep
+/// them, so queued actions carry on on return.
+pub fn cleanup_map_actions(
+    mut runner: ResMut<ActionRunner>,
+    text_box: Single<(&mut ActionTextBox, &mut Visibility)>,
+) {
+    runner.clear();
+    let (mut text_box, mut visibility) = text_box.into_inner();
+    text_box.remaining = 0.0;
+    *visibility = Visibility::Hidden;
+}
+
+/// Plugin that runs scripted map events.
+pub struct MapActionsPlugin;
+
+impl Plugin for MapActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionRunner>()
+            .init_resource::<ScriptLibrary>()
+            .add_systems(Startup, setup_action_text_box)
+            .add_systems(
+                Update,
+                (
+                    activate_scripted_actions,
+                    execute_map_actions,
+                    update_action_text,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::TitleScreen), cleanup_map_actions)
+            .add_systems(OnEnter(GameState::LoadingMap), cleanup_map_actions);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,214 @@
+use super::*;
+use bevy::state::app::StatesPlugin;
+
+#[test]
+fn parses_every_action_kind() {
+    let actions = parse_actions(
+        "say The bridge is down!; wait 1.5\n\
+         teleport 4 2 -7; teleport Shrine; hide bridge_1; toggle secret_door; \
+         dissolve bridge_1; sound sounds/click.ogg; weather rain 0.5; weather clear; \
+         cinematic intro; script scripts/door.rhai",
+    )
+    .unwrap();
+
+    assert_eq!(
+        actions,
+        vec![
+            MapAction::Say("The bridge is down!".to_string()),
+            MapAction::Wait(1.5),
+            MapAction::Teleport(GoToTarget::Coordinates(Vec3::new(4.0, 2.0, -7.0))),
+            MapAction::Teleport(GoToTarget::Entity("Shrine".to_string())),
+            MapAction::Group {
+                name: "bridge_1".to_string(),
+                change: GroupChange::Hide,
+            },
+            MapAction::Group {
+                name: "secret_door".to_string(),
+                change: GroupChange::Toggle,
+            },
+            MapAction::Group {
+                name: "bridge_1".to_string(),
+                change: GroupChange::Dissolve,
+            },
+            MapAction::Sound("sounds/click.ogg".to_string()),
+            MapAction::Weather {
+                precipitation: Precipitation::Rain,
+                intensity: Some(0.5),
+            },
+            MapAction::Weather {
+                precipitation: Precipitation::None,
+                intensity: None,
+            },
+            MapAction::Cinematic("intro".to_string()),
+            MapAction::Script("scripts/door.rhai".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn rejects_malformed_actions() {
+    for bad in [
+        "dance",
+        "say",
+        "wait soon",
+        "wait -1",
+        "teleport",
+        "show",
+        "weather hail",
+        "weather rain 2",
+        "cinematic",
+        "script",
+        "load_map",
+    ] {
+        assert!(parse_actions(bad).is_err(), "{:?} should not parse", bad);
+    }
+    assert_eq!(parse_actions(" ; \n "), Ok(vec![]));
+}
+
+#[test]
+fn load_map_takes_an_optional_spawn_point() {
+    assert_eq!(
+        parse_actions("load_map cave.ron; load_map my maps/cave.ron 1 2.5 -3").unwrap(),
+        vec![
+            MapAction::LoadMap {
+                map: "cave.ron".to_string(),
+                spawn_point: None,
+            },
+            MapAction::LoadMap {
+                map: "my maps/cave.ron".to_string(),
+                spawn_point: Some(Vec3::new(1.0, 2.5, -3.0)),
+            },
+        ]
+    );
+}
+
+#[test]
+fn waits_hold_back_the_actions_after_them() {
+    let mut runner = ActionRunner::default();
+    runner.run(&parse_actions("say one; wait 1; say two; say three").unwrap());
+
+    assert_eq!(runner.advance(0.1), vec![MapAction::Say("one".to_string())]);
+    assert!(runner.advance(0.5).is_empty());
+    assert!(!runner.is_idle());
+    assert_eq!(runner.advance(0.5).len(), 2);
+    assert!(runner.is_idle());
+}
+
+#[test]
+fn a_second_list_runs_after_the_first() {
+    let mut runner = ActionRunner::default();
+    runner.run(&parse_actions("wait 2; say first").unwrap());
+    runner.run(&parse_actions("say second").unwrap());
+
+    assert!(runner.advance(1.0).is_empty());
+    assert_eq!(
+        runner.advance(1.0),
+        vec![
+            MapAction::Say("first".to_string()),
+            MapAction::Say("second".to_string())
+        ]
+    );
+}
+
+#[test]
+fn script_actions_run_before_the_rest() {
+    let mut runner = ActionRunner::default();
+    runner.run(&parse_actions("say before; script door.rhai; say after").unwrap());
+
+    assert_eq!(
+        runner.advance(0.1),
+        vec![
+            MapAction::Say("before".to_string()),
+            MapAction::Script("door.rhai".to_string())
+        ]
+    );
+    runner.run_next(vec![
+        MapAction::Say("scripted".to_string()),
+        MapAction::Wait(1.0),
+    ]);
+    assert_eq!(
+        runner.advance(0.1),
+        vec![MapAction::Say("scripted".to_string())]
+    );
+    assert_eq!(
+        runner.advance(1.0),
+        vec![MapAction::Say("after".to_string())]
+    );
+    assert!(runner.is_idle());
+}
+
+#[test]
+fn triggers_fire_on_entering_once_unless_repeating() {
+    let enter = Activation::Enter { radius: 2.0 };
+    let mut once = ScriptedActions::new(vec![], enter, false);
+    let mut again = ScriptedActions::new(vec![], enter, true);
+
+    for source in [&mut once, &mut again] {
+        assert!(!source.update(5.0, false));
+        assert!(source.update(1.0, false), "entering fires");
+        assert!(!source.update(0.5, false), "staying inside doesn't");
+        assert!(!source.update(5.0, false));
+    }
+    assert!(!once.update(1.0, false));
+    assert!(again.update(1.0, false));
+}
+
+#[test]
+fn interactions_need_the_button_within_range() {
+    let mut source = ScriptedActions::new(vec![], Activation::Interact, true);
+    assert!(!source.update(1.0, false));
+    assert!(!source.update(INTERACTION_RANGE + 1.0, true));
+    assert!(source.update(1.0, true));
+    assert!(source.update(1.0, true), "repeating");
+}
+
+#[test]
+fn triggers_use_their_radius_and_others_interact() {
+    let radius = [("radius".to_string(), "4".to_string())].into();
+    assert_eq!(
+        Activation::for_entity(EntityType::Trigger, &radius),
+        Activation::Enter { radius: 4.0 }
+    );
+    assert_eq!(
+        Activation::for_entity(EntityType::Trigger, &Default::default()),
+        Activation::Enter {
+            radius: DEFAULT_TRIGGER_RADIUS
+        }
+    );
+    assert_eq!(
+        Activation::for_entity(EntityType::Npc, &radius),
+        Activation::Interact
+    );
+}
+
+#[test]
+fn long_text_stays_longer_up_to_a_limit() {
+    assert!(say_duration("Hi") < say_duration("A much longer line of text to read"));
+    assert_eq!(say_duration(&"x".repeat(1000)), SAY_MAX_SECONDS);
+}
+
+fn set_state(app: &mut App, state: GameState) {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(state);
+    app.world_mut().run_schedule(StateTransition);
+}
+
+#[test]
+fn pausing_keeps_queued_actions_until_the_game_ends() {
+    let mut app = App::new();
+    app.add_plugins((StatesPlugin, MapActionsPlugin))
+        .init_state::<GameState>();
+    app.world_mut().run_schedule(Startup);
+    set_state(&mut app, GameState::InGame);
+    app.world_mut()
+        .resource_mut::<ActionRunner>()
+        .run(&parse_actions("wait 1; say later").unwrap());
+
+    set_state(&mut app, GameState::Paused);
+    set_state(&mut app, GameState::InGame);
+    assert!(!app.world().resource::<ActionRunner>().is_idle());
+
+    set_state(&mut app, GameState::TitleScreen);
+    assert!(app.world().resource::<ActionRunner>().is_idle());
+}
@@ -0,0 +1,94 @@
+//! Bloom: bright light bleeding into its surroundings.
+//!
+//! Emissive voxels such as lava and glowstone write colors brighter than
+//! white into the camera's HDR image, and Bevy's bloom pass spreads that
+//! light into a soft glow. The intensity is set from the settings screen;
+//! at zero the game cameras render without HDR or bloom at all.
+
+use bevy::post_process::bloom::Bloom;
+use bevy::prelude::*;
+use bevy::render::view::Hdr;
+
+use super::components::GameCamera;
+use super::split_screen::SplitScreenCamera;
+
+/// Highest bloom intensity.
+pub const MAX_BLOOM_INTENSITY: f32 = 1.0;
+
+/// Bloom intensity change per settings step.
+pub const BLOOM_STEP: f32 = 0.05;
+
+/// Bloom options, set from the settings screen.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct BloomSettings {
+    /// How much bright light scatters (0.0 = off)
+    pub intensity: f32,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            intensity: Bloom::NATURAL.intensity,
+        }
+    }
+}
+
+impl BloomSettings {
+    /// Move the intensity `delta` steps, staying in range.
+    pub fn step(&mut self, delta: i32) {
+        let intensity = self.intensity + delta as f32 * BLOOM_STEP;
+        self.intensity = ((intensity * 100.0).round() / 100.0).clamp(0.0, MAX_BLOOM_INTENSITY);
+    }
+
+    /// Whether bloom is drawn at all.
+    pub fn is_enabled(&self) -> bool {
+        self.intensity > 0.0
+    }
+
+    /// Bloom component for the game cameras at this intensity.
+    pub fn bloom(&self) -> Bloom {
+        Bloom {
+            intensity: self.intensity,
+            ..Bloom::NATURAL
+        }
+    }
+}
+
+/// Keep bloom on the game cameras in step with the settings, including
+/// cameras spawned with a new map or for split-screen.
+#[allow(clippy::type_complexity)]
+pub fn apply_bloom(
+    mut commands: Commands,
+    settings: Res<BloomSettings>,
+    mut cameras: Query<
+        (Entity, Option<&mut Bloom>),
+        Or<(With<GameCamera>, With<SplitScreenCamera>)>,
+    >,
+) {
+    for (entity, bloom) in &mut cameras {
+        match (bloom, settings.is_enabled()) {
+            (Some(bloom), true) if bloom.intensity == settings.intensity => {}
+            (Some(mut bloom), true) => bloom.intensity = settings.intensity,
+            (Some(_), false) => {
+                commands.entity(entity).remove::<(Bloom, Hdr)>();
+            }
+            (None, true) => {
+                commands.entity(entity).insert(settings.bloom());
+            }
+            (None, false) => {}
+        }
+    }
+}
+
+/// Plugin that adds bloom to the game cameras.
+pub struct BloomPlugin;
+
+impl Plugin for BloomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BloomSettings>()
+            .add_systems(Update, apply_bloom);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn bloom_defaults_to_natural() {
+    let settings = BloomSettings::default();
+    assert_eq!(settings.intensity, Bloom::NATURAL.intensity);
+    assert!(settings.is_enabled());
+}
+
+#[test]
+fn steps_stay_in_range() {
+    let mut settings = BloomSettings { intensity: 0.1 };
+    settings.step(-1);
+    assert_eq!(settings.intensity, 0.05);
+    settings.step(-5);
+    assert_eq!(settings.intensity, 0.0);
+    assert!(!settings.is_enabled());
+    settings.step(100);
+    assert_eq!(settings.intensity, MAX_BLOOM_INTENSITY);
+}
+
+#[test]
+fn bloom_component_uses_the_intensity() {
+    let settings = BloomSettings { intensity: 0.4 };
+    let bloom = settings.bloom();
+    assert_eq!(bloom.intensity, 0.4);
+    assert_eq!(
+        bloom.low_frequency_boost,
+        Bloom::NATURAL.low_frequency_boost
+    );
+}
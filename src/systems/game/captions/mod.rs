@@ -0,0 +1,302 @@
+//! Captions: dialog lines and sound cues as timed text at the bottom of the
+//! screen.
+//!
+//! With captions switched on in the accessibility settings, `say` lines from
+//! [map actions](super::actions) show here instead of in the action text box,
+//! and doors, key pickups and scripted `sound` actions add a bracketed cue
+//! such as "[Door opens]". Up to [`MAX_VISIBLE_CAPTIONS`] captions show at
+//! once, oldest on top; when more arrive together the rest wait in a queue
+//! and appear as earlier ones expire. A caption that arrives while the same
+//! one is still showing or waiting is merged into it.
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::path::Path;
+
+use super::actions::say_duration;
+use super::doors::KeyCollected;
+use crate::states::GameState;
+use crate::systems::accessibility::AccessibilitySettings;
+use crate::systems::localization::Localization;
+
+/// Most captions on screen at once.
+pub const MAX_VISIBLE_CAPTIONS: usize = 3;
+
+/// Seconds a sound cue stays on screen.
+pub const SOUND_CAPTION_SECONDS: f32 = 2.5;
+
+/// What a caption describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptionKind {
+    /// A line of dialog, shown as written
+    Dialog,
+    /// A sound, shown in brackets
+    Sound,
+}
+
+/// Ask for a caption. `text` may be a `loc:` key. Ignored while captions
+/// are off.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct CaptionRequest {
+    pub kind: CaptionKind,
+    pub text: String,
+}
+
+impl CaptionRequest {
+    pub fn dialog(text: impl Into<String>) -> Self {
+        Self {
+            kind: CaptionKind::Dialog,
+            text: text.into(),
+        }
+    }
+
+    pub fn sound(text: impl Into<String>) -> Self {
+        Self {
+            kind: CaptionKind::Sound,
+            text: text.into(),
+        }
+    }
+}
+
+/// A caption on screen or waiting for room.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Caption {
+    pub kind: CaptionKind,
+    pub text: String,
+    /// Seconds left on screen
+    pub remaining: f32,
+}
+
+impl Caption {
+    /// A caption shown for as long as its kind and length call for.
+    pub fn new(kind: CaptionKind, text: String) -> Self {
+        let remaining = match kind {
+            CaptionKind::Dialog => say_duration(&text),
+            CaptionKind::Sound => SOUND_CAPTION_SECONDS,
+        };
+        Self {
+            kind,
+            text,
+            remaining,
+        }
+    }
+
+    /// Text to display.
+    pub fn label(&self) -> String {
+        match self.kind {
+            CaptionKind::Dialog => self.text.clone(),
+            CaptionKind::Sound => format!("[{}]", self.text),
+        }
+    }
+
+    fn same_as(&self, other: &Caption) -> bool {
+        self.kind == other.kind && self.text == other.text
+    }
+}
+
+/// Captions on screen and those waiting for room.
+#[derive(Resource, Debug, Default)]
+pub struct CaptionQueue {
+    /// Shown captions, oldest first
+    pub visible: Vec<Caption>,
+    pub pending: VecDeque<Caption>,
+}
+
+impl CaptionQueue {
+    /// Show `caption` now if there is room, otherwise queue it. A caption
+    /// already showing is kept up for at least as long as the new one.
+    pub fn push(&mut self, caption: Caption) {
+        if let Some(shown) = self.visible.iter_mut().find(|c| c.same_as(&caption)) {
+            shown.remaining = shown.remaining.max(caption.remaining);
+        } else if self.pending.iter().any(|c| c.same_as(&caption)) {
+            // Already waiting
+        } else if self.visible.len() < MAX_VISIBLE_CAPTIONS {
+            self.visible.push(caption);
+        } else {
+            self.pending.push_back(caption);
+        }
+    }
+
+    /// Count down the shown captions, drop expired ones and move waiting ones
+    /// up. Returns whether the shown captions changed.
+    pub fn advance(&mut self, delta: f32) -> bool {
+        let before = self.visible.len();
+        for caption in &mut self.visible {
+            caption.remaining -= delta;
+        }
+        self.visible.retain(|caption| caption.remaining > 0.0);
+        let mut changed = self.visible.len() != before;
+
+        while self.visible.len() < MAX_VISIBLE_CAPTIONS {
+            let Some(next) = self.pending.pop_front() else {
+                break;
+            };
+            self.visible.push(next);
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn clear(&mut self) {
+        self.visible.clear();
+        self.pending.clear();
+    }
+}
+
+/// Caption for a sound asset: its file name, without the extension, with
+/// `_` and `-` as spaces and the first letter capitalized.
+pub fn sound_caption(path: &str) -> String {
+    let stem = Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(path)
+        .replace(['_', '-'], " ");
+    let mut chars = stem.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Column holding the caption lines.
+#[derive(Component)]
+pub struct CaptionArea;
+
+/// One caption line; `0` is its index into [`CaptionQueue::visible`].
+#[derive(Component)]
+pub struct CaptionLine(pub usize);
+
+fn setup_captions(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(20.0),
+                right: Val::Percent(20.0),
+                bottom: Val::Px(80.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            GlobalZIndex(50),
+            Visibility::Hidden,
+            CaptionArea,
+        ))
+        .with_children(|parent| {
+            for index in 0..MAX_VISIBLE_CAPTIONS {
+                parent.spawn((
+                    Text::new(""),
+                    TextFont {
+                        font_size: 22.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    TextLayout::new_with_justify(Justify::Center),
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                    Visibility::Hidden,
+                    CaptionLine(index),
+                ));
+            }
+        });
+}
+
+/// Queue captions for requests and key pickups.
+pub fn queue_captions(
+    settings: Res<AccessibilitySettings>,
+    localization: Option<Res<Localization>>,
+    mut requests: MessageReader<CaptionRequest>,
+    mut keys: MessageReader<KeyCollected>,
+    mut queue: ResMut<CaptionQueue>,
+) {
+    if !settings.captions {
+        requests.clear();
+        keys.clear();
+        return;
+    }
+    let resolve = |text: &str| match &localization {
+        Some(localization) => localization.resolve(text),
+        None => text.to_string(),
+    };
+    for request in requests.read() {
+        queue.push(Caption::new(request.kind, resolve(&request.text)));
+    }
+    for _ in keys.read() {
+        queue.push(Caption::new(
+            CaptionKind::Sound,
+            resolve("loc:caption.key_pickup"),
+        ));
+    }
+}
+
+/// Count down captions, touching the queue only when the lines change.
+pub fn tick_captions(time: Res<Time>, mut queue: ResMut<CaptionQueue>) {
+    if queue.bypass_change_detection().advance(time.delta_secs()) {
+        queue.set_changed();
+    }
+}
+
+/// Show the queue's captions in the caption lines.
+pub fn update_caption_lines(
+    queue: Res<CaptionQueue>,
+    mut lines: Query<(&CaptionLine, &mut Text, &mut Visibility)>,
+) {
+    if !queue.is_changed() {
+        return;
+    }
+    for (line, mut text, mut visibility) in &mut lines {
+        match queue.visible.get(line.0) {
+            Some(caption) => {
+                text.0 = caption.label();
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+/// Drop the previous map's captions.
+pub fn clear_captions(mut queue: ResMut<CaptionQueue>) {
+    queue.clear();
+}
+
+/// Show the caption area when play resumes.
+pub fn show_caption_area(mut areas: Query<&mut Visibility, With<CaptionArea>>) {
+    for mut visibility in &mut areas {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Hide the caption area while paused or in menus.
+pub fn hide_caption_area(mut areas: Query<&mut Visibility, With<CaptionArea>>) {
+    for mut visibility in &mut areas {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+/// Plugin that shows dialog and sound captions.
+pub struct CaptionsPlugin;
+
+impl Plugin for CaptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CaptionQueue>()
+            .add_message::<CaptionRequest>()
+            .add_systems(Startup, setup_captions)
+            .add_systems(
+                Update,
+                (queue_captions, tick_captions, update_caption_lines)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::LoadingMap), clear_captions)
+            .add_systems(OnEnter(GameState::InGame), show_caption_area)
+            .add_systems(OnExit(GameState::InGame), hide_caption_area);
+    }
+}
+
+#[cfg(test)]
+mod tests;
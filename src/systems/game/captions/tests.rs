@@ -0,0 +1,55 @@
+use super::*;
+
+fn sound(text: &str) -> Caption {
+    Caption::new(CaptionKind::Sound, text.to_string())
+}
+
+#[test]
+fn sound_captions_are_bracketed() {
+    assert_eq!(sound("Door opens").label(), "[Door opens]");
+    let line = Caption::new(CaptionKind::Dialog, "Hello".to_string());
+    assert_eq!(line.label(), "Hello");
+    assert_eq!(line.remaining, say_duration("Hello"));
+}
+
+#[test]
+fn overflow_waits_until_a_caption_expires() {
+    let mut queue = CaptionQueue::default();
+    for text in ["a", "b", "c", "d"] {
+        queue.push(sound(text));
+    }
+    assert_eq!(queue.visible.len(), MAX_VISIBLE_CAPTIONS);
+    assert_eq!(queue.pending.len(), 1);
+
+    assert!(!queue.advance(1.0));
+    assert!(queue.advance(SOUND_CAPTION_SECONDS));
+    let shown: Vec<_> = queue.visible.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(shown, ["d"]);
+    assert!(queue.pending.is_empty());
+}
+
+#[test]
+fn repeated_captions_merge() {
+    let mut queue = CaptionQueue::default();
+    queue.push(sound("Door opens"));
+    queue.advance(2.0);
+    queue.push(sound("Door opens"));
+    assert_eq!(queue.visible.len(), 1);
+    assert_eq!(queue.visible[0].remaining, SOUND_CAPTION_SECONDS);
+
+    // A dialog line with the same text is a separate caption
+    queue.push(Caption::new(CaptionKind::Dialog, "Door opens".to_string()));
+    assert_eq!(queue.visible.len(), 2);
+
+    for text in ["b", "c", "c"] {
+        queue.push(sound(text));
+    }
+    assert_eq!(queue.pending.len(), 1);
+}
+
+#[test]
+fn sound_captions_come_from_file_names() {
+    assert_eq!(sound_caption("sounds/door_creak.ogg"), "Door creak");
+    assert_eq!(sound_caption("bell-ring.wav"), "Bell ring");
+    assert_eq!(sound_caption("thud"), "Thud");
+}
@@ -46,48 +46,54 @@ pub fn rotate_character_model(
     mut transform_query: Query<&mut Transform, With<SceneRoot>>,
 ) {
     for (mut player, children) in player_query.iter_mut() {
-        // Calculate the angle difference from start to target (shortest path)
-        let mut angle_diff = player.target_rotation - player.start_rotation;
+        ease_character_rotation(&mut player, time.delta_secs(), player_input.input_source);
 
-        // Normalize angle difference to [-PI, PI] for shortest path rotation
-        while angle_diff > PI {
-            angle_diff -= 2.0 * PI;
-        }
-        while angle_diff < -PI {
-            angle_diff += 2.0 * PI;
+        // Find and update the character model child entity
+        for child in children.iter() {
+            if let Ok(mut transform) = transform_query.get_mut(child) {
+                // Apply Y-axis rotation to the character model
+                transform.rotation = Quat::from_rotation_y(player.current_rotation);
+            }
         }
+    }
+}
+
+/// Advance a character's eased turn towards its target rotation by
+/// `delta` seconds. Gamepad players turn faster than keyboard players.
+pub fn ease_character_rotation(player: &mut Player, delta: f32, input_source: InputSource) {
+    // Calculate the angle difference from start to target (shortest path)
+    let mut angle_diff = player.target_rotation - player.start_rotation;
 
-        // Only rotate if there's a significant difference
-        if angle_diff.abs() > 0.001 {
-            // Update elapsed time
-            player.rotation_elapsed += time.delta_secs();
+    // Normalize angle difference to [-PI, PI] for shortest path rotation
+    while angle_diff > PI {
+        angle_diff -= 2.0 * PI;
+    }
+    while angle_diff < -PI {
+        angle_diff += 2.0 * PI;
+    }
 
-            // Use faster rotation for gamepad (0.08s) vs keyboard (0.2s)
-            // This makes controller movement feel more responsive and smooth
-            let effective_duration = match player_input.input_source {
-                InputSource::Gamepad => 0.08, // Very quick rotation for analog stick
-                InputSource::KeyboardMouse => player.rotation_duration, // Use default (0.2s)
-            };
+    // Only rotate if there's a significant difference
+    if angle_diff.abs() > 0.001 {
+        // Update elapsed time
+        player.rotation_elapsed += delta;
 
-            // Calculate progress (0.0 to 1.0) clamped to max 1.0
-            let progress = (player.rotation_elapsed / effective_duration).min(1.0);
+        // Use faster rotation for gamepad (0.08s) vs keyboard (0.2s)
+        // This makes controller movement feel more responsive and smooth
+        let effective_duration = match input_source {
+            InputSource::Gamepad => 0.08, // Very quick rotation for analog stick
+            InputSource::KeyboardMouse => player.rotation_duration, // Use default (0.2s)
+        };
 
-            // Apply easing to progress
-            let eased_progress = ease_in_out_cubic(progress);
+        // Calculate progress (0.0 to 1.0) clamped to max 1.0
+        let progress = (player.rotation_elapsed / effective_duration).min(1.0);
 
-            // Lerp from start_rotation to target_rotation using eased progress
-            player.current_rotation = player.start_rotation + (angle_diff * eased_progress);
+        // Apply easing to progress
+        let eased_progress = ease_in_out_cubic(progress);
 
-            // Normalize current rotation to [0, 2*PI] range
-            player.current_rotation = player.current_rotation.rem_euclid(2.0 * PI);
-        }
+        // Lerp from start_rotation to target_rotation using eased progress
+        player.current_rotation = player.start_rotation + (angle_diff * eased_progress);
 
-        // Find and update the character model child entity
-        for child in children.iter() {
-            if let Ok(mut transform) = transform_query.get_mut(child) {
-                // Apply Y-axis rotation to the character model
-                transform.rotation = Quat::from_rotation_y(player.current_rotation);
-            }
-        }
+        // Normalize current rotation to [0, 2*PI] range
+        player.current_rotation = player.current_rotation.rem_euclid(2.0 * PI);
     }
 }
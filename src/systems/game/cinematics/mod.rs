@@ -0,0 +1,189 @@
+//! Cinematic camera sequences.
+//!
+//! A map lists keyframed camera paths in `MapData::camera_sequences`; the
+//! `cinematic <name>` action plays one. The camera flies from wherever it is
+//! through each keyframe in turn, then eases back to the gameplay camera.
+//! Player input and the follow camera are paused until it is back (see
+//! [`cinematic_inactive`]).
+
+use bevy::prelude::*;
+
+use super::components::{GameCamera, Player};
+use super::map::format::{CameraEasing, CameraSequenceData};
+use super::map::LoadedMapData;
+use crate::states::GameState;
+
+/// Seconds the camera takes to return to the player after a sequence.
+pub const RETURN_SECONDS: f32 = 0.8;
+
+/// Where a camera is and what it looks at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub position: Vec3,
+    pub look_at: Vec3,
+}
+
+impl CameraPose {
+    /// Pose of a camera `transform`, looking `distance` ahead.
+    pub fn from_transform(transform: &Transform, distance: f32) -> Self {
+        Self {
+            position: transform.translation,
+            look_at: transform.translation + transform.forward() * distance.max(0.1),
+        }
+    }
+
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            position: self.position.lerp(other.position, t),
+            look_at: self.look_at.lerp(other.look_at, t),
+        }
+    }
+
+    pub fn transform(self) -> Transform {
+        Transform::from_translation(self.position).looking_at(self.look_at, Vec3::Y)
+    }
+}
+
+/// Pose `t` seconds into `sequence`, starting out from `start`. Times past
+/// the end hold the last keyframe.
+pub fn sample_sequence(start: CameraPose, sequence: &CameraSequenceData, t: f32) -> CameraPose {
+    let mut from = start;
+    let mut remaining = t.max(0.0);
+    for keyframe in &sequence.keyframes {
+        let to = CameraPose {
+            position: keyframe.position.into(),
+            look_at: keyframe.look_at.into(),
+        };
+        if remaining < keyframe.duration {
+            let progress = keyframe.easing.apply(remaining / keyframe.duration);
+            return from.lerp(to, progress);
+        }
+        remaining -= keyframe.duration;
+        from = to;
+    }
+    from
+}
+
+/// A sequence being played.
+#[derive(Debug, Clone)]
+pub struct ActiveCinematic {
+    pub sequence: CameraSequenceData,
+    /// Camera pose when the sequence started
+    start: CameraPose,
+    /// Gameplay camera rotation and offset from the player to return to
+    return_rotation: Quat,
+    return_offset: Vec3,
+    /// Seconds since the sequence started
+    pub elapsed: f32,
+}
+
+impl ActiveCinematic {
+    /// Total seconds including the return to the player.
+    pub fn duration(&self) -> f32 {
+        self.sequence.duration() + RETURN_SECONDS
+    }
+
+    /// Camera transform at the current time, with the player at `player_pos`.
+    pub fn camera_transform(&self, player_pos: Vec3) -> Transform {
+        let path = sample_sequence(self.start, &self.sequence, self.elapsed);
+        let returning = self.elapsed - self.sequence.duration();
+        if returning <= 0.0 {
+            return path.transform();
+        }
+        let gameplay = Transform::from_translation(player_pos + self.return_offset)
+            .with_rotation(self.return_rotation);
+        let from = path.transform();
+        let t = CameraEasing::EaseInOut.apply(returning / RETURN_SECONDS);
+        Transform::from_translation(from.translation.lerp(gameplay.translation, t))
+            .with_rotation(from.rotation.slerp(gameplay.rotation, t))
+    }
+}
+
+/// The game camera, kept apart from the player's `Transform`.
+type GameCameraFilter = (With<GameCamera>, Without<Player>);
+
+/// The sequence playing, if any; reset whenever a map spawns.
+#[derive(Resource, Debug, Default)]
+pub struct Cinematic(pub Option<ActiveCinematic>);
+
+/// Request to play the camera sequence with this name.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct CinematicRequest(pub String);
+
+/// Run condition: no cinematic is holding the camera and player input.
+pub fn cinematic_inactive(cinematic: Option<Res<Cinematic>>) -> bool {
+    cinematic.is_none_or(|cinematic| cinematic.0.is_none())
+}
+
+/// Start requested sequences from the loaded map.
+pub fn start_cinematics(
+    mut requests: MessageReader<CinematicRequest>,
+    mut cinematic: ResMut<Cinematic>,
+    map: Option<Res<LoadedMapData>>,
+    player: Option<Single<&Transform, With<Player>>>,
+    camera: Option<Single<&Transform, GameCameraFilter>>,
+) {
+    let (Some(player), Some(camera)) = (player, camera) else {
+        requests.clear();
+        return;
+    };
+    for CinematicRequest(name) in requests.read() {
+        let sequence = map.as_ref().and_then(|map| {
+            map.map
+                .camera_sequences
+                .iter()
+                .find(|sequence| &sequence.name == name)
+        });
+        let Some(sequence) = sequence.filter(|sequence| !sequence.keyframes.is_empty()) else {
+            warn!("Camera sequence '{}' not found", name);
+            continue;
+        };
+        info!("Playing camera sequence '{}'", name);
+        let first = &sequence.keyframes[0];
+        let distance = Vec3::from(first.position).distance(first.look_at.into());
+        cinematic.0 = Some(ActiveCinematic {
+            sequence: sequence.clone(),
+            start: CameraPose::from_transform(&camera, distance),
+            return_rotation: camera.rotation,
+            return_offset: camera.translation - player.translation,
+            elapsed: 0.0,
+        });
+    }
+}
+
+/// Move the camera along the playing sequence.
+pub fn play_cinematic(
+    time: Res<Time>,
+    mut cinematic: ResMut<Cinematic>,
+    player: Option<Single<&Transform, With<Player>>>,
+    camera: Option<Single<&mut Transform, GameCameraFilter>>,
+) {
+    let (Some(active), Some(player), Some(mut camera)) = (cinematic.0.as_mut(), player, camera)
+    else {
+        return;
+    };
+    active.elapsed += time.delta_secs();
+    **camera = active.camera_transform(player.translation);
+    if active.elapsed >= active.duration() {
+        cinematic.0 = None;
+    }
+}
+
+/// Plugin that plays cinematic camera sequences.
+pub struct CinematicsPlugin;
+
+impl Plugin for CinematicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Cinematic>()
+            .add_message::<CinematicRequest>()
+            .add_systems(
+                Update,
+                (start_cinematics, play_cinematic)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,99 @@
+use super::*;
+use crate::systems::game::map::format::CameraKeyframeData;
+
+fn sequence() -> CameraSequenceData {
+    CameraSequenceData {
+        name: "intro".to_string(),
+        keyframes: vec![
+            CameraKeyframeData {
+                position: (10.0, 0.0, 0.0),
+                look_at: (10.0, 0.0, -5.0),
+                duration: 2.0,
+                easing: CameraEasing::Linear,
+            },
+            CameraKeyframeData {
+                position: (10.0, 10.0, 0.0),
+                look_at: (10.0, 10.0, -5.0),
+                duration: 1.0,
+                easing: CameraEasing::EaseIn,
+            },
+        ],
+    }
+}
+
+fn start() -> CameraPose {
+    CameraPose {
+        position: Vec3::ZERO,
+        look_at: Vec3::new(0.0, 0.0, -5.0),
+    }
+}
+
+#[test]
+fn sequences_fly_from_the_start_through_each_keyframe() {
+    let sequence = sequence();
+    assert_eq!(sample_sequence(start(), &sequence, 0.0), start());
+    assert_eq!(
+        sample_sequence(start(), &sequence, 1.0).position,
+        Vec3::new(5.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        sample_sequence(start(), &sequence, 2.0).position,
+        Vec3::new(10.0, 0.0, 0.0)
+    );
+    // Ease-in covers a quarter of the way at half time
+    assert_eq!(
+        sample_sequence(start(), &sequence, 2.5).position,
+        Vec3::new(10.0, 2.5, 0.0)
+    );
+    assert_eq!(
+        sample_sequence(start(), &sequence, 9.0).position,
+        Vec3::new(10.0, 10.0, 0.0),
+        "holds the last keyframe"
+    );
+}
+
+#[test]
+fn cinematics_end_back_on_the_gameplay_camera() {
+    let gameplay = Transform::from_xyz(0.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y);
+    let player_pos = Vec3::new(1.0, 0.0, 1.0);
+    let mut active = ActiveCinematic {
+        sequence: sequence(),
+        start: CameraPose::from_transform(&gameplay, 5.0),
+        return_rotation: gameplay.rotation,
+        return_offset: gameplay.translation - Vec3::ZERO,
+        elapsed: 3.0,
+    };
+    assert_eq!(active.duration(), 3.0 + RETURN_SECONDS);
+    assert!(active
+        .camera_transform(player_pos)
+        .translation
+        .abs_diff_eq(Vec3::new(10.0, 10.0, 0.0), 1e-4));
+
+    active.elapsed = active.duration();
+    let end = active.camera_transform(player_pos);
+    assert!(end
+        .translation
+        .abs_diff_eq(gameplay.translation + player_pos, 1e-4));
+    assert!(end.rotation.abs_diff_eq(gameplay.rotation, 1e-4));
+}
+
+#[test]
+fn cinematic_inactive_without_a_sequence() {
+    let mut app = App::new();
+    app.init_resource::<Cinematic>();
+    let inactive = |app: &mut App| {
+        app.world_mut()
+            .run_system_cached(cinematic_inactive)
+            .unwrap()
+    };
+    assert!(inactive(&mut app));
+
+    app.world_mut().resource_mut::<Cinematic>().0 = Some(ActiveCinematic {
+        sequence: sequence(),
+        start: start(),
+        return_rotation: Quat::IDENTITY,
+        return_offset: Vec3::ZERO,
+        elapsed: 0.0,
+    });
+    assert!(!inactive(&mut app));
+}
@@ -0,0 +1,59 @@
+//! Despawning the game world when play ends.
+//!
+//! Every top-level entity spawned for a running game carries [`GameEntity`];
+//! children go with their parents. Pausing, photo mode, the settings screen
+//! and the game-over and results screens all leave `InGame` with the world
+//! still drawn behind them, so the world is despawned on entering the loading
+//! or title screen instead, before either is set up.
+
+use bevy::prelude::*;
+
+use super::components::GameEntity;
+use super::hazards::HazardExposure;
+use super::interior_detection::InteriorState;
+use super::map::spawner::ChunkMaterials;
+use super::occlusion::OcclusionMaterialHandle;
+use super::render_scale::RenderScaleCamera;
+use super::resources::{GameInitialized, SpatialGrid};
+
+/// Despawn every [`GameEntity`] and reset the spawner's resources, so the
+/// spawner spawns a fresh map the next time it runs.
+pub fn despawn_game_world(commands: &mut Commands, entities: &Query<Entity, With<GameEntity>>) {
+    if !entities.is_empty() {
+        info!("Despawning {} game entities", entities.iter().len());
+    }
+    for entity in entities {
+        commands.entity(entity).despawn();
+    }
+    commands.insert_resource(GameInitialized(false));
+    commands.remove_resource::<SpatialGrid>();
+    commands.remove_resource::<ChunkMaterials>();
+    commands.remove_resource::<OcclusionMaterialHandle>();
+    commands.insert_resource(InteriorState::default());
+    commands.insert_resource(HazardExposure::default());
+}
+
+/// Despawn the game world with [`despawn_game_world`] on leaving the game.
+/// A 2D camera is restored for the loading and title screens if none is
+/// left.
+#[allow(clippy::type_complexity)]
+pub fn despawn_game_entities(
+    mut commands: Commands,
+    entities: Query<Entity, With<GameEntity>>,
+    ui_cameras: Query<
+        (),
+        (
+            With<Camera2d>,
+            Without<RenderScaleCamera>,
+            Without<GameEntity>,
+        ),
+    >,
+) {
+    despawn_game_world(&mut commands, &entities);
+    if ui_cameras.is_empty() {
+        commands.spawn(Camera2d);
+    }
+}
+
+#[cfg(test)]
+mod tests;
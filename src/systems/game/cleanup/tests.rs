@@ -0,0 +1,106 @@
+use super::*;
+use crate::states::GameState;
+use crate::systems::game::map::format::{Precipitation, WeatherData};
+use crate::systems::game::particles::{Particle, ParticleBurst, ParticlesPlugin};
+use crate::systems::game::weather::{
+    update_precipitation, Weather, WeatherCamera, WeatherDrop, WeatherDrops,
+};
+use bevy::state::app::StatesPlugin;
+
+#[test]
+fn only_game_entities_are_despawned() {
+    let mut world = World::new();
+    let game = world.spawn((Transform::default(), GameEntity)).id();
+    let child = world.spawn(ChildOf(game)).id();
+    let other = world.spawn(Transform::default()).id();
+    world.insert_resource(GameInitialized(true));
+    world.insert_resource(SpatialGrid::default());
+
+    world.run_system_cached(despawn_game_entities).unwrap();
+
+    assert!(world.get_entity(game).is_err());
+    assert!(world.get_entity(child).is_err());
+    assert!(world.get_entity(other).is_ok());
+    assert!(!world.resource::<GameInitialized>().0);
+    assert!(!world.contains_resource::<SpatialGrid>());
+}
+
+#[test]
+fn a_ui_camera_is_restored_only_when_missing() {
+    let mut world = World::new();
+    let cameras = |world: &mut World| {
+        world
+            .query_filtered::<(), With<Camera2d>>()
+            .iter(world)
+            .count()
+    };
+
+    world.run_system_cached(despawn_game_entities).unwrap();
+    assert_eq!(cameras(&mut world), 1);
+
+    world.run_system_cached(despawn_game_entities).unwrap();
+    assert_eq!(cameras(&mut world), 1);
+}
+
+/// The game world with live weather and particles, switchable between states.
+fn world_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((StatesPlugin, ParticlesPlugin))
+        .init_state::<GameState>()
+        .init_resource::<Assets<Mesh>>()
+        .init_resource::<Assets<StandardMaterial>>()
+        .init_resource::<Time>()
+        .insert_resource(Weather::settled(WeatherData {
+            precipitation: Precipitation::Rain,
+            intensity: 0.1,
+            ..WeatherData::default()
+        }))
+        .init_resource::<WeatherDrops>()
+        .add_systems(
+            Update,
+            update_precipitation.run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(OnEnter(GameState::TitleScreen), despawn_game_entities);
+    app.world_mut()
+        .spawn((WeatherCamera, GlobalTransform::default()));
+    app
+}
+
+fn enter(app: &mut App, state: GameState) {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(state);
+    app.update();
+}
+
+fn count<C: Component>(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), (With<C>, With<GameEntity>)>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn weather_and_particles_stay_in_photo_mode_until_the_game_ends() {
+    let mut app = world_app();
+    enter(&mut app, GameState::InGame);
+    app.world_mut().write_message(ParticleBurst {
+        position: Vec3::ZERO,
+        color: Color::WHITE,
+        count: 4,
+        speed: 1.0,
+        lifetime: 10.0,
+    });
+    app.update();
+    let drops = count::<WeatherDrop>(&mut app);
+    assert!(drops > 0);
+    assert_eq!(count::<Particle>(&mut app), 4);
+
+    enter(&mut app, GameState::PhotoMode);
+    assert_eq!(count::<WeatherDrop>(&mut app), drops);
+    assert_eq!(count::<Particle>(&mut app), 4);
+
+    enter(&mut app, GameState::TitleScreen);
+    assert_eq!(count::<WeatherDrop>(&mut app), 0);
+    assert_eq!(count::<Particle>(&mut app), 0);
+}
@@ -3,78 +3,15 @@
 //! This module provides helper functions for:
 //! - Calculating sub-voxel world positions
 //! - Getting sub-voxel bounding boxes
-//! - Checking collisions between player and sub-voxels
+//! - Sweeping the player's cylinder through sub-voxels, with wall sliding
+//!   and step-up, and vertically onto floors and against ceilings
 
-use super::components::{Player, SubVoxel};
+use super::components::{Player, SubVoxel, VoxelType};
 use super::resources::SpatialGrid;
 use bevy::prelude::*;
 
-pub(super) const SUB_VOXEL_SIZE: f32 = 1.0 / 8.0;
 pub(super) const STEP_UP_TOLERANCE: f32 = 0.02;
 
-/// Result of a collision check, including step-up information.
-#[derive(Debug, Clone, Copy)]
-pub struct CollisionResult {
-    /// Whether a collision was detected
-    pub has_collision: bool,
-    /// Whether the player can step up onto this obstacle
-    pub can_step_up: bool,
-    /// The height to step up (if can_step_up is true) — part of unfinished step-up feature.
-    #[allow(dead_code)]
-    pub step_up_height: f32,
-    /// The new Y position after step-up (center of player cylinder)
-    pub new_y: f32,
-}
-
-impl CollisionResult {
-    /// No collision detected
-    pub fn no_collision() -> Self {
-        Self {
-            has_collision: false,
-            can_step_up: false,
-            step_up_height: 0.0,
-            new_y: 0.0,
-        }
-    }
-
-    /// Blocking collision (cannot step up)
-    pub fn blocking() -> Self {
-        Self {
-            has_collision: true,
-            can_step_up: false,
-            step_up_height: 0.0,
-            new_y: 0.0,
-        }
-    }
-
-    /// Step-up collision (can walk up)
-    pub fn step_up(height: f32, new_y: f32) -> Self {
-        Self {
-            has_collision: true,
-            can_step_up: true,
-            step_up_height: height,
-            new_y,
-        }
-    }
-}
-
-/// Parameters for collision checking, grouping position and cylinder dimensions.
-#[derive(Debug, Clone, Copy)]
-pub struct CollisionParams {
-    /// X position of the cylinder center
-    pub x: f32,
-    /// Y position of the cylinder center
-    pub y: f32,
-    /// Z position of the cylinder center
-    pub z: f32,
-    /// Horizontal radius of the collision cylinder
-    pub radius: f32,
-    /// Vertical half-height of the collision cylinder
-    pub half_height: f32,
-    /// Y position of the floor the player is currently standing on
-    pub current_floor_y: f32,
-}
-
 /// Get the axis-aligned bounding box (AABB) of a sub-voxel.
 ///
 /// This function now returns the cached bounds from the SubVoxel component,
@@ -90,178 +27,377 @@ pub fn get_sub_voxel_bounds(sub_voxel: &SubVoxel) -> (Vec3, Vec3) {
     sub_voxel.bounds
 }
 
-/// Check if a player cylinder collides with any sub-voxels at the given position.
-///
-/// This function uses the spatial grid for efficient collision detection,
-/// only checking sub-voxels that are near the player's position.
-///
-/// The player uses a cylinder collider with:
-/// - `radius` for horizontal collision (XZ plane)
-/// - `half_height` for vertical extent from center (Y axis)
+/// Gap left between the player and a wall after a sweep, so the next sweep
+/// doesn't start out touching it.
+const SKIN_WIDTH: f32 = 0.001;
+
+/// How far above the feet an obstacle's top can be and still count as the
+/// floor being stood on.
+const FLOOR_TOLERANCE: f32 = 0.01;
+
+/// Most times one move slides along a new wall before it gives up.
+const MAX_SLIDES: usize = 3;
+
+/// Slack when deciding whether a vertical sweep reaches a floor or ceiling,
+/// so a body resting exactly on a floor stays on it.
+const CONTACT_EPSILON: f32 = 0.001;
+
+/// Where a horizontal sweep first touches an obstacle.
 ///
-/// # Arguments
-/// * `spatial_grid` - The spatial partitioning grid for efficient queries
-/// * `sub_voxel_query` - Query to access sub-voxel components
-/// * `params` - Collision parameters (position, dimensions, floor height)
-/// * `prefetched` - Optional pre-fetched entity slice from the caller. When `Some`,
-///   the slice is used directly for both the initial check and the step-up re-check,
-///   avoiding any `get_entities_in_aabb` calls. The caller must ensure the slice
-///   covers the full cylinder bounds plus step-up height. When `None`, the spatial
-///   grid is queried as normal.
+/// Vectors are in the XZ plane, with `Vec2::x` = world X and `Vec2::y` =
+/// world Z.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepHit {
+    /// Fraction of the motion travelled before contact (0–1)
+    pub fraction: f32,
+    /// Contact normal, pointing away from the obstacle
+    pub normal: Vec2,
+}
+
+/// The player's collision cylinder and how high it can step.
+#[derive(Debug, Clone, Copy)]
+pub struct CharacterBody {
+    /// Horizontal radius of the cylinder
+    pub radius: f32,
+    /// Vertical half-height of the cylinder
+    pub half_height: f32,
+    /// Tallest ledge stepped onto without jumping; 0 disables step-up
+    pub step_height: f32,
+}
+
+/// Outcome of [`move_and_slide`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlideResult {
+    /// Cylinder center after the move
+    pub position: Vec3,
+    /// Whether the move ended on a ledge stepped up onto
+    pub stepped_up: bool,
+}
+
+/// Outcome of [`move_vertically`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerticalMove {
+    /// Cylinder center height after the move
+    pub y: f32,
+    /// Whether a floor or ceiling stopped the move
+    pub blocked: bool,
+    /// Whether the move ended standing on a floor
+    pub grounded: bool,
+}
+
+/// Sweep a circle (the cylinder's footprint) along `motion` against XZ
+/// rectangles given as `(min, max)`, returning the first contact.
 ///
-/// # Returns
-/// A `CollisionResult` indicating collision status and step-up information
-pub fn check_sub_voxel_collision(
-    spatial_grid: &SpatialGrid,
-    sub_voxel_query: &Query<&SubVoxel, Without<Player>>,
-    params: CollisionParams,
-    prefetched: Option<&[Entity]>,
-) -> CollisionResult {
-    let collision_radius = params.radius;
+/// Each rectangle is grown by `radius` into a rounded rectangle and a ray is
+/// cast against its faces and corner circles. A circle that already overlaps
+/// a rectangle is stopped at once when moving into it and ignores it when
+/// moving out, so a body pushed into a wall can always walk free.
+pub fn sweep_circle_xz(
+    center: Vec2,
+    radius: f32,
+    motion: Vec2,
+    obstacles: impl IntoIterator<Item = (Vec2, Vec2)>,
+) -> Option<SweepHit> {
+    obstacles
+        .into_iter()
+        .filter_map(|(min, max)| sweep_circle_rect(center, radius, motion, min, max))
+        .min_by(|a, b| a.fraction.total_cmp(&b.fraction))
+}
 
-    // Calculate player's AABB for grid lookup (cylinder bounds)
-    let player_min = Vec3::new(
-        params.x - collision_radius,
-        params.y - params.half_height,
-        params.z - collision_radius,
-    );
-    let player_max = Vec3::new(
-        params.x + collision_radius,
-        params.y + params.half_height,
-        params.z + collision_radius,
-    );
+fn sweep_circle_rect(
+    center: Vec2,
+    radius: f32,
+    motion: Vec2,
+    min: Vec2,
+    max: Vec2,
+) -> Option<SweepHit> {
+    // Already overlapping: only block motion further in
+    let offset = center - center.clamp(min, max);
+    if offset.length_squared() < radius * radius {
+        let normal = if offset.length_squared() > 1e-12 {
+            offset.normalize()
+        } else {
+            // Center inside the rectangle: push out through the nearest face
+            [
+                (center.x - min.x, Vec2::NEG_X),
+                (max.x - center.x, Vec2::X),
+                (center.y - min.y, Vec2::NEG_Y),
+                (max.y - center.y, Vec2::Y),
+            ]
+            .into_iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, normal)| normal)
+            .unwrap_or(Vec2::X)
+        };
+        return (motion.dot(normal) < 0.0).then_some(SweepHit {
+            fraction: 0.0,
+            normal,
+        });
+    }
 
-    // Use the pre-fetched entity slice if provided, otherwise query the spatial grid.
-    let relevant_owned: Vec<Entity>;
-    let relevant: &[Entity] = match prefetched {
-        Some(pre) => pre,
-        None => {
-            relevant_owned = spatial_grid.get_entities_in_aabb(player_min, player_max);
-            &relevant_owned
+    let mut best: Option<SweepHit> = None;
+    let mut consider = |fraction: f32, normal: Vec2| {
+        if (0.0..=1.0).contains(&fraction) && best.is_none_or(|hit| fraction < hit.fraction) {
+            best = Some(SweepHit { fraction, normal });
         }
     };
 
-    // Track potential step-up collision
-    let mut step_up_candidate: Option<f32> = None;
-
-    // Check all relevant sub-voxels for collision
-    for entity in relevant {
-        if let Ok(sub_voxel) = sub_voxel_query.get(*entity) {
-            let (min, max) = get_sub_voxel_bounds(sub_voxel);
+    // Faces, pushed out by the radius
+    for axis in 0..2 {
+        let other = 1 - axis;
+        let speed = motion[axis];
+        if speed == 0.0 {
+            continue;
+        }
+        let (face, sign) = if speed > 0.0 {
+            (min[axis] - radius, -1.0)
+        } else {
+            (max[axis] + radius, 1.0)
+        };
+        let fraction = (face - center[axis]) / speed;
+        let along = center[other] + motion[other] * fraction;
+        if along >= min[other] && along <= max[other] {
+            let mut normal = Vec2::ZERO;
+            normal[axis] = sign;
+            consider(fraction, normal);
+        }
+    }
 
-            // Skip sub-voxels that are floor/ground (below player's feet)
-            // We use a small threshold to avoid blocking movement on flat ground
-            let player_bottom = params.y - params.half_height;
-            if max.y <= player_bottom + 0.01 {
+    // Rounded corners
+    let a = motion.length_squared();
+    if a > 0.0 {
+        for corner in [min, Vec2::new(min.x, max.y), Vec2::new(max.x, min.y), max] {
+            let from_corner = center - corner;
+            let b = from_corner.dot(motion);
+            let c = from_corner.length_squared() - radius * radius;
+            let discriminant = b * b - a * c;
+            if discriminant < 0.0 {
                 continue;
             }
-
-            // Skip if sub-voxel is too far above the player's top
-            if min.y > params.y + params.half_height {
-                continue;
+            let fraction = (-b - discriminant.sqrt()) / a;
+            let contact = center + motion * fraction;
+            let beside_x = contact.x < min.x || contact.x > max.x;
+            let beside_z = contact.y < min.y || contact.y > max.y;
+            if beside_x && beside_z {
+                consider(fraction, (contact - corner) / radius);
             }
+        }
+    }
 
-            // Quick AABB check for horizontal bounds
-            if params.x + collision_radius < min.x
-                || params.x - collision_radius > max.x
-                || params.z + collision_radius < min.z
-                || params.z - collision_radius > max.z
-            {
-                continue;
-            }
+    best
+}
 
-            // Find closest point on sub-voxel AABB to player center (horizontal only)
-            let closest_x = params.x.clamp(min.x, max.x);
-            let closest_z = params.z.clamp(min.z, max.z);
+/// Whether a circle overlaps an XZ rectangle (touching doesn't count).
+fn circle_overlaps_rect(center: Vec2, radius: f32, min: Vec2, max: Vec2) -> bool {
+    center.distance_squared(center.clamp(min, max)) < radius * radius
+}
 
-            // Check horizontal distance only
-            let dx = params.x - closest_x;
-            let dz = params.z - closest_z;
-            let distance_squared = dx * dx + dz * dz;
+/// Move a character cylinder horizontally by `motion` (world X, Z) through
+/// sub-voxel AABBs, sliding along walls and stepping up low ledges.
+///
+/// The body is swept rather than moved and pushed back out, so it can't
+/// tunnel through thin walls or snag on the seams between sub-voxels. On
+/// contact the remaining motion is projected onto the wall, keeping the part
+/// that runs along it, for up to [`MAX_SLIDES`] walls per move.
+///
+/// Obstacles whose top is no more than `body.step_height` above the feet
+/// don't block; when the move ends over one the body is lifted onto the
+/// highest such ledge, provided it fits there. If it doesn't fit, the move is
+/// redone with those ledges treated as walls.
+pub fn move_and_slide(
+    position: Vec3,
+    motion: Vec2,
+    body: CharacterBody,
+    obstacles: &[(Vec3, Vec3)],
+) -> SlideResult {
+    let feet = position.y - body.half_height;
+    // Tops up to here are the floor being stood on
+    let floor = feet + FLOOR_TOLERANCE;
+    let step_limit = (feet + body.step_height).max(floor);
+    let level = |center: Vec2| SlideResult {
+        position: Vec3::new(center.x, position.y, center.y),
+        stepped_up: false,
+    };
 
-            if distance_squared < collision_radius * collision_radius {
-                // Collision detected - check if it's a step-up candidate
-                let obstacle_height = max.y - params.current_floor_y;
+    let center = slide(position, motion, body, step_limit, obstacles);
+    let overlapping = |center: Vec2| {
+        obstacles
+            .iter()
+            .filter(move |(min, max)| circle_overlaps_rect(center, body.radius, min.xz(), max.xz()))
+    };
+    let Some(ledge) = overlapping(center)
+        .map(|(_, max)| max.y)
+        .filter(|&top| top > floor && top <= step_limit)
+        .reduce(f32::max)
+    else {
+        return level(center);
+    };
 
-                // Check if obstacle is within valid step-up range
-                // Allow stepping up to one sub-voxel height (with tolerance for floating-point errors)
-                // The obstacle must be above the current floor but not too tall
-                if obstacle_height > 0.0 && obstacle_height <= SUB_VOXEL_SIZE + STEP_UP_TOLERANCE {
-                    // This is a step-up candidate - track the highest one
-                    step_up_candidate = Some(step_up_candidate.unwrap_or(0.0).max(obstacle_height));
-                } else if obstacle_height > SUB_VOXEL_SIZE + STEP_UP_TOLERANCE {
-                    // This is a blocking collision (too tall to step up)
-                    return CollisionResult::blocking();
-                }
-                // If obstacle_height <= 0.0, it's below us, so we ignore it
-            }
+    // The body must fit between the ledge and whatever is above it
+    let fits = !overlapping(center).any(|(min, max)| {
+        max.y > ledge + FLOOR_TOLERANCE && min.y < ledge + body.half_height * 2.0
+    });
+    if fits {
+        SlideResult {
+            position: Vec3::new(center.x, ledge + body.half_height, center.y),
+            stepped_up: true,
         }
+    } else {
+        level(slide(position, motion, body, floor, obstacles))
     }
+}
 
-    // If we found a step-up candidate, verify that the stepped-up position is collision-free
-    if let Some(height) = step_up_candidate {
-        let new_y = params.current_floor_y + height + params.half_height;
+/// Sweep and slide the body's footprint, blocked by obstacles rising above
+/// `wall_above` within its height. Returns the final XZ center.
+fn slide(
+    position: Vec3,
+    motion: Vec2,
+    body: CharacterBody,
+    wall_above: f32,
+    obstacles: &[(Vec3, Vec3)],
+) -> Vec2 {
+    let head = position.y + body.half_height;
+    let walls = || {
+        obstacles
+            .iter()
+            .filter(|(min, max)| max.y > wall_above && min.y < head)
+            .map(|(min, max)| (min.xz(), max.xz()))
+    };
 
-        // When prefetched is provided the caller widened its Y bounds to cover the
-        // step-up height, so we reuse the same slice to avoid a second grid query.
-        let stepped_owned: Vec<Entity>;
-        let stepped: &[Entity] = if prefetched.is_some() {
-            relevant
-        } else {
-            let stepped_min = Vec3::new(
-                params.x - collision_radius,
-                new_y - params.half_height,
-                params.z - collision_radius,
-            );
-            let stepped_max = Vec3::new(
-                params.x + collision_radius,
-                new_y + params.half_height,
-                params.z + collision_radius,
-            );
-            stepped_owned = spatial_grid.get_entities_in_aabb(stepped_min, stepped_max);
-            &stepped_owned
+    let mut center = position.xz();
+    let mut remaining = motion;
+    for _ in 0..MAX_SLIDES {
+        let length = remaining.length();
+        if length < 1e-6 {
+            break;
+        }
+        let Some(hit) = sweep_circle_xz(center, body.radius, remaining, walls()) else {
+            center += remaining;
+            break;
         };
+        let travel = (hit.fraction * length - SKIN_WIDTH).max(0.0);
+        center += remaining / length * travel;
+        let left = remaining * (1.0 - travel / length);
+        remaining = left - hit.normal * left.dot(hit.normal);
+    }
+    center
+}
 
-        // Check if player body would collide at the new height
-        for entity in stepped {
-            if let Ok(sub_voxel) = sub_voxel_query.get(*entity) {
-                let (min, max) = get_sub_voxel_bounds(sub_voxel);
-
-                // At the new height, check if this sub-voxel would block the player's body
-                // The player's new bottom is at the stepped-up floor level
-                let new_bottom = new_y - params.half_height;
-                let new_top = new_y + params.half_height;
+/// Move a character cylinder vertically by `motion` through sub-voxel AABBs,
+/// landing it on floors and stopping it at ceilings.
+///
+/// The whole path is swept: falling, the feet stop on the highest top they
+/// pass; rising, the head stops at the lowest underside it reaches. However
+/// fast the body moves, it can't pass through a floor between two steps.
+/// A body resting on a floor with no `motion` stays grounded on it.
+pub fn move_vertically(
+    position: Vec3,
+    motion: f32,
+    body: CharacterBody,
+    obstacles: &[(Vec3, Vec3)],
+) -> VerticalMove {
+    let center = position.xz();
+    let in_column = obstacles
+        .iter()
+        .filter(|(min, max)| circle_overlaps_rect(center, body.radius, min.xz(), max.xz()));
 
-                // Skip sub-voxels that are now below the player's feet (floor we're standing on)
-                if max.y <= new_bottom + 0.01 {
-                    continue;
-                }
+    if motion <= 0.0 {
+        let feet = position.y - body.half_height;
+        let floor = in_column
+            .map(|(_, max)| max.y)
+            .filter(|&top| top <= feet + CONTACT_EPSILON && top >= feet + motion - CONTACT_EPSILON)
+            .reduce(f32::max);
+        if let Some(top) = floor {
+            return VerticalMove {
+                y: top + body.half_height,
+                blocked: true,
+                grounded: true,
+            };
+        }
+    } else {
+        let head = position.y + body.half_height;
+        let ceiling = in_column
+            .map(|(min, _)| min.y)
+            .filter(|&bottom| {
+                bottom >= head - CONTACT_EPSILON && bottom <= head + motion + CONTACT_EPSILON
+            })
+            .reduce(f32::min);
+        if let Some(bottom) = ceiling {
+            return VerticalMove {
+                y: bottom - body.half_height,
+                blocked: true,
+                grounded: false,
+            };
+        }
+    }
+    VerticalMove {
+        y: position.y + motion,
+        blocked: false,
+        grounded: false,
+    }
+}
 
-                // Skip sub-voxels above the player
-                if min.y >= new_top {
-                    continue;
-                }
+/// Find the voxel type of the surface directly under `position`.
+///
+/// Returns the type of the highest sub-voxel whose footprint contains
+/// `position` in XZ and whose top lies between `max_depth` below `position`
+/// and `STEP_UP_TOLERANCE` above it. Pass a character's feet position to get
+/// what it is standing on; usable for players, NPCs and dropped items alike.
+///
+/// Casts a ray down through the spatial grid, skipping sub-voxels that reach
+/// above its start.
+pub fn surface_type_at(
+    spatial_grid: &SpatialGrid,
+    sub_voxel_query: &Query<&SubVoxel, Without<Player>>,
+    position: Vec3,
+    max_depth: f32,
+) -> Option<VoxelType> {
+    let start = position + Vec3::Y * STEP_UP_TOLERANCE;
+    let hit = spatial_grid.raycast(
+        start,
+        Vec3::NEG_Y,
+        STEP_UP_TOLERANCE + max_depth,
+        |entity| {
+            sub_voxel_query
+                .get(entity)
+                .is_ok_and(|sub_voxel| sub_voxel.bounds.1.y <= start.y)
+        },
+    )?;
+    sub_voxel_query
+        .get(hit.entity)
+        .ok()
+        .map(|sub_voxel| sub_voxel.voxel_type)
+}
 
-                // Check horizontal overlap with cylinder
-                let closest_x = params.x.clamp(min.x, max.x);
-                let closest_z = params.z.clamp(min.z, max.z);
-                let dx = params.x - closest_x;
-                let dz = params.z - closest_z;
-                let distance_squared = dx * dx + dz * dz;
+/// Find the most dangerous hazard voxel touching a character's cylinder.
+///
+/// The cylinder (approximated by its AABB) is extended down by
+/// `STEP_UP_TOLERANCE` so standing on a hazard counts as well as being inside
+/// one. When several hazards touch, the one dealing the most damage wins.
+pub fn hazard_contact(
+    spatial_grid: &SpatialGrid,
+    sub_voxel_query: &Query<&SubVoxel, Without<Player>>,
+    center: Vec3,
+    radius: f32,
+    half_height: f32,
+) -> Option<VoxelType> {
+    let min = center - Vec3::new(radius, half_height + STEP_UP_TOLERANCE, radius);
+    let max = center + Vec3::new(radius, half_height, radius);
 
-                if distance_squared < collision_radius * collision_radius {
-                    // There's a collision at the stepped-up position - block movement
-                    return CollisionResult::blocking();
-                }
-            }
+    let mut worst: Option<(u32, VoxelType)> = None;
+    // The grid only reports boxes overlapping the query
+    for (entity, _) in spatial_grid.query_aabb(min, max) {
+        let Ok(sub_voxel) = sub_voxel_query.get(entity) else {
+            continue;
+        };
+        let Some(hazard) = sub_voxel.voxel_type.hazard() else {
+            continue;
+        };
+        if worst.is_none_or(|(damage, _)| hazard.damage > damage) {
+            worst = Some((hazard.damage, sub_voxel.voxel_type));
         }
-
-        // Step-up is valid and the new position is collision-free
-        return CollisionResult::step_up(height, new_y);
     }
-
-    // No collision
-    CollisionResult::no_collision()
+    worst.map(|(_, voxel_type)| voxel_type)
 }
 
 #[cfg(test)]
@@ -1,136 +1,343 @@
+#![allow(clippy::type_complexity)]
+
 use super::*;
 
-// CollisionResult tests
+const BODY: CharacterBody = CharacterBody {
+    radius: 0.2,
+    half_height: 0.4,
+    step_height: 0.15,
+};
+
+/// A sub-voxel-sized box with its min corner at `(x, y, z)`.
+fn cell(x: f32, y: f32, z: f32) -> (Vec3, Vec3) {
+    let min = Vec3::new(x, y, z);
+    (min, min + Vec3::splat(0.125))
+}
+
+/// A wall along X = `x`, from the floor (y = 0) up to y = 2, spanning z -2..2.
+fn wall_at_x(x: f32) -> (Vec3, Vec3) {
+    (Vec3::new(x, 0.0, -2.0), Vec3::new(x + 0.5, 2.0, 2.0))
+}
+
+// sweep_circle_xz tests
+#[test]
+fn sweep_stops_at_the_face_grown_by_the_radius() {
+    let rect = (Vec2::new(1.0, -1.0), Vec2::new(2.0, 1.0));
+    let hit = sweep_circle_xz(Vec2::ZERO, 0.2, Vec2::new(2.0, 0.0), [rect]).unwrap();
+    assert!((hit.fraction - 0.4).abs() < 1e-6);
+    assert_eq!(hit.normal, Vec2::NEG_X);
+
+    assert_eq!(
+        sweep_circle_xz(Vec2::ZERO, 0.2, Vec2::new(0.5, 0.0), [rect]),
+        None,
+        "too short to reach"
+    );
+    assert_eq!(
+        sweep_circle_xz(Vec2::ZERO, 0.2, Vec2::new(0.0, 2.0), [rect]),
+        None,
+        "moving past"
+    );
+}
+
+#[test]
+fn sweep_rounds_the_corners() {
+    let rect = (Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0));
+    // Heading at the corner diagonally, contact is on the corner circle
+    let hit = sweep_circle_xz(Vec2::ZERO, 0.2, Vec2::new(2.0, 2.0), [rect]).unwrap();
+    let contact = Vec2::new(2.0, 2.0) * hit.fraction;
+    assert!((contact.distance(Vec2::ONE) - 0.2).abs() < 1e-5);
+    assert!((hit.normal - Vec2::NEG_ONE.normalize()).length() < 1e-5);
+
+    // Grazing past the corner just outside the radius misses
+    let motion = Vec2::new(2.0, 0.0);
+    assert_eq!(
+        sweep_circle_xz(Vec2::new(0.0, 0.79), 0.2, motion, [rect]),
+        None
+    );
+}
+
+#[test]
+fn sweep_returns_the_nearest_of_several_obstacles() {
+    let near = (Vec2::new(1.0, -1.0), Vec2::new(1.5, 1.0));
+    let far = (Vec2::new(3.0, -1.0), Vec2::new(3.5, 1.0));
+    let hit = sweep_circle_xz(Vec2::ZERO, 0.2, Vec2::new(4.0, 0.0), [far, near]).unwrap();
+    assert!((hit.fraction - 0.2).abs() < 1e-6);
+}
+
+#[test]
+fn overlapping_obstacles_block_moving_in_but_not_out() {
+    let rect = (Vec2::new(0.1, -1.0), Vec2::new(1.0, 1.0));
+    let into = sweep_circle_xz(Vec2::ZERO, 0.2, Vec2::new(0.5, 0.0), [rect]).unwrap();
+    assert_eq!(into.fraction, 0.0);
+    assert_eq!(into.normal, Vec2::NEG_X);
+    assert_eq!(
+        sweep_circle_xz(Vec2::ZERO, 0.2, Vec2::new(-0.5, 0.0), [rect]),
+        None
+    );
+}
+
+// move_and_slide tests
+#[test]
+fn open_ground_moves_the_full_distance() {
+    let floor = (Vec3::new(-2.0, -0.5, -2.0), Vec3::new(2.0, 0.0, 2.0));
+    let start = Vec3::new(0.0, 0.4, 0.0);
+    let moved = move_and_slide(start, Vec2::new(0.3, -0.2), BODY, &[floor]);
+    assert_eq!(moved.position, Vec3::new(0.3, 0.4, -0.2));
+    assert!(!moved.stepped_up);
+}
+
+#[test]
+fn walls_stop_the_body_at_its_radius() {
+    let start = Vec3::new(0.0, 0.4, 0.0);
+    let moved = move_and_slide(start, Vec2::new(1.0, 0.0), BODY, &[wall_at_x(0.5)]);
+    assert!(moved.position.x < 0.3 && moved.position.x > 0.29);
+}
+
+#[test]
+fn diagonal_moves_slide_along_walls() {
+    let start = Vec3::new(0.29, 0.4, 0.0);
+    let moved = move_and_slide(start, Vec2::new(0.2, 0.2), BODY, &[wall_at_x(0.5)]);
+    assert!(moved.position.x <= 0.3, "still outside the wall");
+    assert!(
+        (moved.position.z - 0.2).abs() < 1e-4,
+        "the motion along the wall is kept, got {}",
+        moved.position.z
+    );
+}
+
 #[test]
-fn test_collision_result_no_collision() {
-    let result = CollisionResult::no_collision();
-    assert!(!result.has_collision);
-    assert!(!result.can_step_up);
-    assert_eq!(result.step_up_height, 0.0);
+fn corners_between_sub_voxels_do_not_snag() {
+    // A wall made of separate sub-voxel columns, walked along while touching it
+    let wall: Vec<_> = (0..16)
+        .flat_map(|i| (0..8).map(move |j| cell(0.5, j as f32 * 0.125, i as f32 * 0.125)))
+        .collect();
+    let start = Vec3::new(0.295, 0.4, 0.1);
+    let moved = move_and_slide(start, Vec2::new(0.05, 1.0), BODY, &wall);
+    assert!((moved.position.z - 1.1).abs() < 1e-4);
 }
 
 #[test]
-fn test_collision_result_blocking() {
-    let result = CollisionResult::blocking();
-    assert!(result.has_collision);
-    assert!(!result.can_step_up);
-    assert_eq!(result.step_up_height, 0.0);
+fn thin_walls_cannot_be_tunnelled_through() {
+    let thin = (Vec3::new(0.5, 0.0, -2.0), Vec3::new(0.52, 2.0, 2.0));
+    let start = Vec3::new(0.0, 0.4, 0.0);
+    let moved = move_and_slide(start, Vec2::new(3.0, 0.0), BODY, &[thin]);
+    assert!(moved.position.x < 0.3);
 }
 
 #[test]
-fn test_collision_result_step_up() {
-    let result = CollisionResult::step_up(0.125, 1.5);
-    assert!(result.has_collision);
-    assert!(result.can_step_up);
-    assert_eq!(result.step_up_height, 0.125);
-    assert_eq!(result.new_y, 1.5);
+fn one_sub_voxel_lips_are_stepped_onto() {
+    let lip = (Vec3::new(0.5, 0.0, -2.0), Vec3::new(2.0, 0.125, 2.0));
+    let start = Vec3::new(0.0, 0.4, 0.0);
+    let moved = move_and_slide(start, Vec2::new(0.5, 0.0), BODY, &[lip]);
+    assert!(moved.stepped_up);
+    assert!((moved.position - Vec3::new(0.5, 0.525, 0.0)).length() < 1e-5);
 }
 
-// CollisionParams tests
 #[test]
-fn test_collision_params_creation() {
-    let params = CollisionParams {
-        x: 1.0,
-        y: 2.0,
-        z: 3.0,
-        radius: 0.2,
-        half_height: 0.4,
-        current_floor_y: 1.6,
+fn ledges_above_the_step_height_block() {
+    let ledge = (Vec3::new(0.5, 0.0, -2.0), Vec3::new(2.0, 0.25, 2.0));
+    let start = Vec3::new(0.0, 0.4, 0.0);
+    let moved = move_and_slide(start, Vec2::new(0.5, 0.0), BODY, &[ledge]);
+    assert!(!moved.stepped_up);
+    assert!(moved.position.x < 0.3);
+    assert_eq!(moved.position.y, 0.4);
+
+    // A taller step height climbs it
+    let tall = CharacterBody {
+        step_height: 0.3,
+        ..BODY
+    };
+    assert!(move_and_slide(start, Vec2::new(0.5, 0.0), tall, &[ledge]).stepped_up);
+}
+
+#[test]
+fn zero_step_height_treats_lips_as_walls() {
+    let lip = (Vec3::new(0.5, 0.0, -2.0), Vec3::new(2.0, 0.125, 2.0));
+    let airborne = CharacterBody {
+        step_height: 0.0,
+        ..BODY
     };
-    assert_eq!(params.x, 1.0);
-    assert_eq!(params.y, 2.0);
-    assert_eq!(params.z, 3.0);
-    assert_eq!(params.radius, 0.2);
-    assert_eq!(params.half_height, 0.4);
-    assert_eq!(params.current_floor_y, 1.6);
+    let moved = move_and_slide(
+        Vec3::new(0.0, 0.4, 0.0),
+        Vec2::new(0.5, 0.0),
+        airborne,
+        &[lip],
+    );
+    assert!(!moved.stepped_up);
+    assert!(moved.position.x < 0.3);
+}
+
+#[test]
+fn lips_under_a_low_ceiling_block_instead() {
+    let lip = (Vec3::new(0.5, 0.0, -2.0), Vec3::new(2.0, 0.125, 2.0));
+    // Room for the body on the floor (0.8 tall) but not on the lip
+    let ceiling = (Vec3::new(-2.0, 0.85, -2.0), Vec3::new(2.0, 1.0, 2.0));
+    let start = Vec3::new(0.0, 0.4, 0.0);
+    let moved = move_and_slide(start, Vec2::new(0.5, 0.0), BODY, &[lip, ceiling]);
+    assert!(!moved.stepped_up);
+    assert!(moved.position.x < 0.3);
+    assert_eq!(moved.position.y, 0.4);
+}
+
+// move_vertically tests
+#[test]
+fn falls_land_on_the_highest_floor_passed() {
+    let floor = (Vec3::new(-2.0, -1.0, -2.0), Vec3::new(2.0, 0.0, 2.0));
+    let shelf = cell(-0.0625, 0.5, -0.0625);
+    let moved = move_vertically(Vec3::new(0.0, 2.0, 0.0), -3.0, BODY, &[floor, shelf]);
+    assert!(moved.blocked && moved.grounded);
+    assert!((moved.y - 1.025).abs() < 1e-5);
+}
+
+#[test]
+fn bodies_resting_on_a_floor_stay_grounded() {
+    let floor = (Vec3::new(-2.0, -1.0, -2.0), Vec3::new(2.0, 0.0, 2.0));
+    let moved = move_vertically(Vec3::new(0.0, 0.4, 0.0), 0.0, BODY, &[floor]);
+    assert!(moved.grounded);
+    assert_eq!(moved.y, 0.4);
+
+    // Walking off the edge
+    let beside = move_vertically(Vec3::new(2.5, 0.4, 0.0), -0.01, BODY, &[floor]);
+    assert!(!beside.grounded && !beside.blocked);
+    assert!((beside.y - 0.39).abs() < 1e-6);
+}
+
+#[test]
+fn jumps_stop_at_the_ceiling() {
+    let ceiling = (Vec3::new(-2.0, 2.0, -2.0), Vec3::new(2.0, 3.0, 2.0));
+    let moved = move_vertically(Vec3::new(0.0, 1.0, 0.0), 2.0, BODY, &[ceiling]);
+    assert!(moved.blocked && !moved.grounded);
+    assert!((moved.y - 1.6).abs() < 1e-6);
 }
 
-// check_sub_voxel_collision prefetch tests (using SystemState to access queries)
 #[test]
-fn prefetched_empty_slice_skips_grid_query() {
+fn surface_type_at_returns_highest_surface_under_feet() {
     use super::super::resources::SpatialGrid;
     use bevy::ecs::system::SystemState;
-    use bevy::prelude::IVec3;
 
     let mut world = bevy::prelude::World::new();
-
-    // Spawn a sub-voxel that would block movement if the grid were queried
-    let bounds = (Vec3::new(-0.1, 0.2, -0.1), Vec3::new(0.1, 0.8, 0.1));
-    let entity = world.spawn(SubVoxel { bounds }).id();
-
-    // Place entity in the grid cell the player AABB would hit
     let mut grid = SpatialGrid::default();
-    grid.cells
-        .entry(IVec3::new(0, 0, 0))
-        .or_default()
-        .push(entity);
+    // Dirt layer with a thin grass top, and stone off to the side
+    for (bounds, voxel_type) in [
+        (
+            (Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.5, 0.375, 0.5)),
+            VoxelType::Dirt,
+        ),
+        (
+            (Vec3::new(0.0, 0.375, 0.0), Vec3::new(0.5, 0.5, 0.5)),
+            VoxelType::Grass,
+        ),
+        (
+            (Vec3::new(2.0, 0.0, 2.0), Vec3::new(2.5, 0.5, 2.5)),
+            VoxelType::Stone,
+        ),
+    ] {
+        let entity = world.spawn(SubVoxel { bounds, voxel_type }).id();
+        grid.insert(entity, bounds.0, bounds.1);
+    }
     world.insert_resource(grid);
 
-    let params = CollisionParams {
-        x: 0.0,
-        y: 0.8,
-        z: 0.0,
-        radius: 0.2,
-        half_height: 0.4,
-        current_floor_y: 0.0,
-    };
-
     let mut state: SystemState<(
         bevy::prelude::Res<SpatialGrid>,
         bevy::prelude::Query<&SubVoxel, bevy::prelude::Without<Player>>,
     )> = SystemState::new(&mut world);
     let (spatial_grid, sub_voxel_query) = state.get(&world);
 
-    // Empty prefetched slice — function skips the grid regardless of what's there
-    let result = check_sub_voxel_collision(&spatial_grid, &sub_voxel_query, params, Some(&[]));
-    assert!(
-        !result.has_collision,
-        "Expected no collision when prefetched slice is empty (grid bypassed)"
+    let feet = Vec3::new(0.25, 0.5, 0.25);
+    assert_eq!(
+        surface_type_at(&spatial_grid, &sub_voxel_query, feet, 0.2),
+        Some(VoxelType::Grass)
+    );
+    assert_eq!(
+        surface_type_at(
+            &spatial_grid,
+            &sub_voxel_query,
+            Vec3::new(2.25, 0.5, 2.25),
+            0.2
+        ),
+        Some(VoxelType::Stone)
+    );
+    // Airborne: ground is further down than max_depth
+    assert_eq!(
+        surface_type_at(&spatial_grid, &sub_voxel_query, feet + Vec3::Y, 0.2),
+        None
     );
 }
 
 #[test]
-fn prefetched_none_queries_grid_and_finds_blocking_collision() {
+fn hazard_contact_detects_standing_on_and_prefers_worst_hazard() {
     use super::super::resources::SpatialGrid;
     use bevy::ecs::system::SystemState;
-    use bevy::prelude::IVec3;
 
     let mut world = bevy::prelude::World::new();
-
-    // A tall blocking sub-voxel (obstacle_height > SUB_VOXEL_SIZE + STEP_UP_TOLERANCE)
-    let bounds = (Vec3::new(-0.1, 0.2, -0.1), Vec3::new(0.1, 0.8, 0.1));
-    let entity = world.spawn(SubVoxel { bounds }).id();
-
     let mut grid = SpatialGrid::default();
-    grid.cells
-        .entry(IVec3::new(0, 0, 0))
-        .or_default()
-        .push(entity);
+    // Water pool at the origin, lava beside it, plain stone further away
+    for (bounds, voxel_type) in [
+        (
+            (Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.5, 0.5, 0.5)),
+            VoxelType::Water,
+        ),
+        (
+            (Vec3::new(0.5, 0.0, 0.0), Vec3::new(1.0, 0.5, 0.5)),
+            VoxelType::Lava,
+        ),
+        (
+            (Vec3::new(3.0, 0.0, 3.0), Vec3::new(3.5, 0.5, 3.5)),
+            VoxelType::Stone,
+        ),
+    ] {
+        let entity = world.spawn(SubVoxel { bounds, voxel_type }).id();
+        grid.insert(entity, bounds.0, bounds.1);
+    }
     world.insert_resource(grid);
 
-    let params = CollisionParams {
-        x: 0.0,
-        y: 0.8,
-        z: 0.0,
-        radius: 0.2,
-        half_height: 0.4,
-        current_floor_y: 0.0,
-    };
-
     let mut state: SystemState<(
         bevy::prelude::Res<SpatialGrid>,
         bevy::prelude::Query<&SubVoxel, bevy::prelude::Without<Player>>,
     )> = SystemState::new(&mut world);
     let (spatial_grid, sub_voxel_query) = state.get(&world);
 
-    // None — function queries the grid and finds the blocking entity
-    let result = check_sub_voxel_collision(&spatial_grid, &sub_voxel_query, params, None);
-    assert!(
-        result.has_collision,
-        "Expected collision when prefetched is None and grid contains blocking entity"
+    // Standing on the water only
+    assert_eq!(
+        hazard_contact(
+            &spatial_grid,
+            &sub_voxel_query,
+            Vec3::new(0.2, 0.9, 0.25),
+            0.2,
+            0.4
+        ),
+        Some(VoxelType::Water)
     );
-    assert!(
-        !result.can_step_up,
-        "Expected blocking collision (too tall to step up)"
+    // Straddling water and lava: lava hurts more
+    assert_eq!(
+        hazard_contact(
+            &spatial_grid,
+            &sub_voxel_query,
+            Vec3::new(0.5, 0.9, 0.25),
+            0.2,
+            0.4
+        ),
+        Some(VoxelType::Lava)
+    );
+    // Stone is not a hazard; airborne above water is not contact
+    assert_eq!(
+        hazard_contact(
+            &spatial_grid,
+            &sub_voxel_query,
+            Vec3::new(3.25, 0.9, 3.25),
+            0.2,
+            0.4
+        ),
+        None
+    );
+    assert_eq!(
+        hazard_contact(
+            &spatial_grid,
+            &sub_voxel_query,
+            Vec3::new(0.2, 1.5, 0.25),
+            0.2,
+            0.4
+        ),
+        None
     );
 }
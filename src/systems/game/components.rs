@@ -1,5 +1,12 @@
 use bevy::prelude::*;
 
+/// Marker for every top-level entity spawned for a running game: the map's
+/// chunks and entities, the player, lights, the game camera and the HUD.
+/// They are despawned together when the game is left; see
+/// `despawn_game_entities`.
+#[derive(Component, Default)]
+pub struct GameEntity;
+
 /// Marker component for the player's flashlight.
 /// Used to identify the flashlight entity for toggling and rotation.
 #[derive(Component)]
@@ -38,6 +45,8 @@ pub struct SubVoxel {
     /// Cached bounding box (min, max) to avoid recalculation every frame.
     /// Calculated once at spawn time and reused for all collision checks.
     pub bounds: (Vec3, Vec3),
+    /// Type of the voxel this sub-voxel belongs to (for surface lookups)
+    pub voxel_type: VoxelType,
 }
 
 pub use crate::systems::game::map::format::VoxelType;
@@ -53,6 +62,8 @@ pub struct GameCamera {
     pub follow_speed: f32,
     /// Current target position the camera is following (typically the player's position)
     pub target_position: Vec3,
+    /// Cut away walls between the camera and the player while indoors (per-map setting)
+    pub interior_cutaway: bool,
 }
 
 /// Component for NPC entities.
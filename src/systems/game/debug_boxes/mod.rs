@@ -0,0 +1,264 @@
+//! Instanced debug boxes: collision bounds, spatial grid cells and selection
+//! boxes drawn as one wire cube mesh with a transform per instance.
+//!
+//! An entity with [`DebugBoxes`] and the shared [`DebugBoxMesh`] draws one
+//! wire box per entry in a single draw call (see `render`), so tens of
+//! thousands of boxes cost about as much as one. The boxes are uploaded to
+//! the GPU only when the component's list is replaced.
+//!
+//! In game, `C` shows the collision bounds of the sub-voxels around the
+//! player alongside the player's collision cylinder, and `G` shows the
+//! occupied cells of the [`SpatialGrid`] around them. The editor draws its
+//! selected entities' boxes and, from the View menu, the collision bounds of
+//! the selected voxels.
+
+mod render;
+
+use super::components::{GameEntity, Player, SubVoxel};
+use super::resources::{SpatialGrid, GRID_CELL_SIZE};
+use bevy::camera::visibility::NoFrustumCulling;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use bevy::render::extract_component::ExtractComponent;
+use bevy::render::view::NoIndirectDrawing;
+use std::sync::Arc;
+
+/// Color of sub-voxel collision bounds.
+pub const COLLISION_BOUNDS_COLOR: LinearRgba = LinearRgba::rgb(0.1, 1.0, 0.3);
+
+/// Color of occupied spatial grid cells.
+pub const GRID_CELL_COLOR: LinearRgba = LinearRgba::rgb(0.2, 0.6, 1.0);
+
+/// Color of selection boxes.
+pub const SELECTION_BOX_COLOR: LinearRgba = LinearRgba::rgb(1.0, 0.9, 0.1);
+
+/// How far from the player, in world units, boxes are shown in game.
+pub const DEBUG_BOX_RADIUS: f32 = 6.0;
+
+/// One box, as drawn by the instanced pipeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugBox {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+    pub color: LinearRgba,
+}
+
+impl DebugBox {
+    /// The box spanning `min`..`max`.
+    pub fn from_bounds(min: Vec3, max: Vec3, color: LinearRgba) -> Self {
+        Self {
+            center: (min + max) * 0.5,
+            half_extents: (max - min) * 0.5,
+            color,
+        }
+    }
+
+    /// Per-instance vertex data: center, half extents and color, each padded
+    /// to four floats.
+    pub fn instance_data(&self) -> [f32; 12] {
+        let (c, h) = (self.center, self.half_extents);
+        let color = self.color.to_f32_array();
+        [
+            c.x, c.y, c.z, 0.0, h.x, h.y, h.z, 0.0, color[0], color[1], color[2], color[3],
+        ]
+    }
+}
+
+/// Boxes drawn with the entity's [`DebugBoxMesh`], one instance each.
+///
+/// Replace the whole list to change it; the GPU copy is refreshed when the
+/// list is no longer the one uploaded.
+#[derive(Component, Clone, ExtractComponent)]
+pub struct DebugBoxes(pub Arc<[DebugBox]>);
+
+/// Wire cube with corners at ±1, scaled and moved into place per instance.
+pub fn wire_box_mesh() -> Mesh {
+    let positions: Vec<[f32; 3]> = (0..8)
+        .map(|corner| {
+            let sign = |bit: u32| if corner & bit == 0 { -1.0 } else { 1.0 };
+            [sign(1), sign(2), sign(4)]
+        })
+        .collect();
+    // Corners one bit apart share an edge
+    let indices: Vec<u32> = (0..8u32)
+        .flat_map(|corner| [1, 2, 4].map(move |bit| (corner, corner ^ bit)))
+        .filter(|&(a, b)| a < b)
+        .flat_map(|(a, b)| [a, b])
+        .collect();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList, Default::default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+/// The wire cube shared by every [`DebugBoxes`] entity.
+#[derive(Resource)]
+pub struct DebugBoxMesh(pub Handle<Mesh>);
+
+impl FromWorld for DebugBoxMesh {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.resource_mut::<Assets<Mesh>>().add(wire_box_mesh()))
+    }
+}
+
+impl DebugBoxMesh {
+    /// Components drawing `boxes`.
+    pub fn boxes(&self, boxes: Vec<DebugBox>) -> impl Bundle {
+        (
+            Mesh3d(self.0.clone()),
+            DebugBoxes(boxes.into()),
+            Transform::default(),
+            // Instances are placed in the shader, far from the mesh's own bounds
+            NoFrustumCulling,
+        )
+    }
+}
+
+/// Marks cameras given [`NoIndirectDrawing`] for debug boxes, so it is only
+/// taken away from those.
+#[derive(Component)]
+struct DebugBoxCamera;
+
+/// Turn indirect drawing off on the 3D cameras while debug boxes exist: the
+/// instanced draw issues its own draw calls.
+fn sync_indirect_drawing(
+    mut commands: Commands,
+    boxes: Query<(), With<DebugBoxes>>,
+    cameras: Query<(Entity, Has<NoIndirectDrawing>, Has<DebugBoxCamera>), With<Camera3d>>,
+) {
+    let wanted = !boxes.is_empty();
+    for (camera, no_indirect, ours) in &cameras {
+        if wanted && !no_indirect {
+            commands
+                .entity(camera)
+                .insert((NoIndirectDrawing, DebugBoxCamera));
+        } else if !wanted && ours {
+            commands
+                .entity(camera)
+                .remove::<(NoIndirectDrawing, DebugBoxCamera)>();
+        }
+    }
+}
+
+/// Plugin drawing [`DebugBoxes`], used by both the game and the editor.
+pub struct DebugBoxesPlugin;
+
+impl Plugin for DebugBoxesPlugin {
+    fn build(&self, app: &mut App) {
+        render::build(app);
+        app.init_resource::<DebugBoxMesh>()
+            .add_systems(PostUpdate, sync_indirect_drawing);
+    }
+}
+
+/// Which debug boxes the game shows.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DebugBoxView {
+    /// Sub-voxel collision bounds around the player
+    pub collision: bool,
+    /// Occupied spatial grid cells around the player
+    pub grid_cells: bool,
+}
+
+/// Marker for the game's collision bounds boxes.
+#[derive(Component)]
+pub struct CollisionBoundsView;
+
+/// Marker for the game's grid cell boxes.
+#[derive(Component)]
+pub struct GridCellsView;
+
+/// System that toggles the spatial grid cell view with the 'G' key.
+pub fn toggle_grid_cells(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut view: ResMut<DebugBoxView>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyG) {
+        view.grid_cells = !view.grid_cells;
+    }
+}
+
+/// Collision bounds of the sub-voxels in `grid` within `radius` of `center`.
+pub fn collision_boxes(
+    grid: &SpatialGrid,
+    center: Vec3,
+    radius: f32,
+    is_sub_voxel: impl Fn(Entity) -> bool,
+) -> Vec<DebugBox> {
+    grid.query_sphere(center, radius)
+        .filter(|&(entity, _)| is_sub_voxel(entity))
+        .map(|(_, (min, max))| DebugBox::from_bounds(min, max, COLLISION_BOUNDS_COLOR))
+        .collect()
+}
+
+/// Occupied cells of `grid` within `radius` of `center`, as boxes.
+pub fn grid_cell_boxes(grid: &SpatialGrid, center: Vec3, radius: f32) -> Vec<DebugBox> {
+    let min = SpatialGrid::world_to_grid_coords(center - Vec3::splat(radius));
+    let max = SpatialGrid::world_to_grid_coords(center + Vec3::splat(radius));
+    grid.occupied_cells()
+        .filter(|cell| cell.cmpge(min).all() && cell.cmple(max).all())
+        .map(|cell| {
+            let min = cell.as_vec3() * GRID_CELL_SIZE;
+            DebugBox::from_bounds(min, min + Vec3::splat(GRID_CELL_SIZE), GRID_CELL_COLOR)
+        })
+        .collect()
+}
+
+/// What a game debug box view was last built from: the player's grid cell
+/// and the number of entities and cells in the grid.
+type ViewKey = (IVec3, usize, usize);
+
+/// Rebuild the game's debug boxes when a view is toggled, the player moves
+/// to another grid cell or the grid gains or loses entities or cells.
+#[allow(clippy::too_many_arguments)]
+pub fn update_debug_box_views(
+    mut commands: Commands,
+    view: Res<DebugBoxView>,
+    grid: Option<Res<SpatialGrid>>,
+    mesh: Res<DebugBoxMesh>,
+    player: Option<Single<&Transform, With<Player>>>,
+    sub_voxels: Query<(), With<SubVoxel>>,
+    collision_views: Query<Entity, With<CollisionBoundsView>>,
+    cell_views: Query<Entity, With<GridCellsView>>,
+    mut shown: Local<(Option<ViewKey>, Option<ViewKey>)>,
+) {
+    let key = grid.as_ref().zip(player.as_ref()).map(|(grid, player)| {
+        (
+            SpatialGrid::world_to_grid_coords(player.translation),
+            grid.len(),
+            grid.occupied_cells().len(),
+        )
+    });
+    let center = player.map(|player| player.translation);
+
+    // Despawned views (such as on leaving the game) are rebuilt when wanted
+    let collision_key = key.filter(|_| view.collision);
+    if collision_key != shown.0 || (collision_key.is_some() && collision_views.is_empty()) {
+        shown.0 = collision_key;
+        for entity in &collision_views {
+            commands.entity(entity).despawn();
+        }
+        if let (Some(grid), Some(center), Some(_)) = (&grid, center, collision_key) {
+            let boxes = collision_boxes(grid, center, DEBUG_BOX_RADIUS, |entity| {
+                sub_voxels.contains(entity)
+            });
+            commands.spawn((mesh.boxes(boxes), CollisionBoundsView, GameEntity));
+        }
+    }
+
+    let cells_key = key.filter(|_| view.grid_cells);
+    if cells_key != shown.1 || (cells_key.is_some() && cell_views.is_empty()) {
+        shown.1 = cells_key;
+        for entity in &cell_views {
+            commands.entity(entity).despawn();
+        }
+        if let (Some(grid), Some(center), Some(_)) = (&grid, center, cells_key) {
+            let boxes = grid_cell_boxes(grid, center, DEBUG_BOX_RADIUS);
+            commands.spawn((mesh.boxes(boxes), GridCellsView, GameEntity));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
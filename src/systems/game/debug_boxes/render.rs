@@ -0,0 +1,270 @@
+//! Render-world side of [`DebugBoxes`]: an instanced draw of the entity's
+//! box mesh, one instance per box.
+//!
+//! Each [`DebugBoxes`] entity's boxes are uploaded as a per-instance vertex
+//! buffer (only when they change), and the entity is queued in the
+//! transparent phase with a pipeline built on Bevy's mesh pipeline whose
+//! shader places and colors every instance.
+
+use super::DebugBoxes;
+use bevy::core_pipeline::core_3d::Transparent3d;
+use bevy::ecs::system::{lifetimeless::*, SystemParamItem};
+use bevy::mesh::{MeshVertexBufferLayoutRef, VertexBufferLayout};
+use bevy::pbr::{
+    MeshPipeline, MeshPipelineKey, RenderMeshInstances, SetMeshBindGroup, SetMeshViewBindGroup,
+    SetMeshViewBindingArrayBindGroup,
+};
+use bevy::prelude::*;
+use bevy::render::{
+    extract_component::ExtractComponentPlugin,
+    mesh::{allocator::MeshAllocator, RenderMesh, RenderMeshBufferInfo},
+    render_asset::RenderAssets,
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+        RenderCommandResult, SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+    },
+    render_resource::*,
+    renderer::RenderDevice,
+    sync_world::MainEntity,
+    view::ExtractedView,
+    Render, RenderApp, RenderStartup, RenderSystems,
+};
+use std::sync::Arc;
+
+/// Shader placing and coloring the box instances
+const SHADER_ASSET_PATH: &str = "shaders/debug_boxes.wgsl";
+
+/// Floats per instance: center, half extents and color, each padded to four
+const INSTANCE_FLOATS: usize = 12;
+
+/// Registers the extraction, pipeline and draw command of [`DebugBoxes`].
+pub(super) fn build(app: &mut App) {
+    app.add_plugins(ExtractComponentPlugin::<DebugBoxes>::default());
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    render_app
+        .add_render_command::<Transparent3d, DrawDebugBoxes>()
+        .init_resource::<SpecializedMeshPipelines<DebugBoxPipeline>>()
+        .add_systems(RenderStartup, init_debug_box_pipeline)
+        .add_systems(
+            Render,
+            (
+                queue_debug_boxes.in_set(RenderSystems::QueueMeshes),
+                prepare_instance_buffers.in_set(RenderSystems::PrepareResources),
+            ),
+        );
+}
+
+/// GPU copy of an entity's boxes.
+#[derive(Component)]
+struct InstanceBuffer {
+    buffer: Buffer,
+    length: usize,
+    /// The boxes uploaded, to skip re-uploading them every frame
+    source: Arc<[super::DebugBox]>,
+}
+
+fn prepare_instance_buffers(
+    mut commands: Commands,
+    query: Query<(Entity, &DebugBoxes, Option<&InstanceBuffer>)>,
+    render_device: Res<RenderDevice>,
+) {
+    for (entity, boxes, uploaded) in &query {
+        if uploaded.is_some_and(|uploaded| Arc::ptr_eq(&uploaded.source, &boxes.0)) {
+            continue;
+        }
+        if boxes.0.is_empty() {
+            commands.entity(entity).remove::<InstanceBuffer>();
+            continue;
+        }
+        let contents: Vec<u8> = boxes
+            .0
+            .iter()
+            .flat_map(|debug_box| debug_box.instance_data())
+            .flat_map(f32::to_ne_bytes)
+            .collect();
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("debug box instance buffer"),
+            contents: &contents,
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+        });
+        commands.entity(entity).insert(InstanceBuffer {
+            buffer,
+            length: boxes.0.len(),
+            source: boxes.0.clone(),
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_debug_boxes(
+    transparent_3d_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    pipeline: Res<DebugBoxPipeline>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<DebugBoxPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<RenderMesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    debug_boxes: Query<(Entity, &MainEntity), With<DebugBoxes>>,
+    mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    views: Query<(&ExtractedView, &Msaa)>,
+) {
+    let draw_debug_boxes = transparent_3d_draw_functions.read().id::<DrawDebugBoxes>();
+
+    for (view, msaa) in &views {
+        let Some(transparent_phase) = transparent_render_phases.get_mut(&view.retained_view_entity)
+        else {
+            continue;
+        };
+        let view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
+            | MeshPipelineKey::from_hdr(view.hdr);
+        let rangefinder = view.rangefinder3d();
+        for (entity, main_entity) in &debug_boxes {
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(*main_entity)
+            else {
+                continue;
+            };
+            let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+            let key =
+                view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology());
+            let Ok(pipeline) = pipelines.specialize(&pipeline_cache, &pipeline, key, &mesh.layout)
+            else {
+                continue;
+            };
+            transparent_phase.add(Transparent3d {
+                entity: (entity, *main_entity),
+                pipeline,
+                draw_function: draw_debug_boxes,
+                distance: rangefinder.distance(&mesh_instance.center),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::None,
+                indexed: true,
+            });
+        }
+    }
+}
+
+#[derive(Resource)]
+struct DebugBoxPipeline {
+    shader: Handle<Shader>,
+    mesh_pipeline: MeshPipeline,
+}
+
+fn init_debug_box_pipeline(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mesh_pipeline: Res<MeshPipeline>,
+) {
+    commands.insert_resource(DebugBoxPipeline {
+        shader: asset_server.load(SHADER_ASSET_PATH),
+        mesh_pipeline: mesh_pipeline.clone(),
+    });
+}
+
+impl SpecializedMeshPipeline for DebugBoxPipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+
+        descriptor.vertex.shader = self.shader.clone();
+        // Shader locations 0-2 are left to the mesh's own attributes
+        let vec4 = VertexFormat::Float32x4;
+        descriptor.vertex.buffers.push(VertexBufferLayout {
+            array_stride: (INSTANCE_FLOATS * size_of::<f32>()) as u64,
+            step_mode: VertexStepMode::Instance,
+            attributes: (0..3)
+                .map(|i| VertexAttribute {
+                    format: vec4,
+                    offset: i * vec4.size(),
+                    shader_location: 3 + i as u32,
+                })
+                .collect(),
+        });
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader = self.shader.clone();
+        }
+        Ok(descriptor)
+    }
+}
+
+type DrawDebugBoxes = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshViewBindingArrayBindGroup<1>,
+    SetMeshBindGroup<2>,
+    DrawMeshInstanced,
+);
+
+/// Draws the entity's mesh once per instance in its [`InstanceBuffer`].
+struct DrawMeshInstanced;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawMeshInstanced {
+    type Param = (
+        SRes<RenderAssets<RenderMesh>>,
+        SRes<RenderMeshInstances>,
+        SRes<MeshAllocator>,
+    );
+    type ViewQuery = ();
+    type ItemQuery = Read<InstanceBuffer>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        instance_buffer: Option<&'w InstanceBuffer>,
+        (meshes, render_mesh_instances, mesh_allocator): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let mesh_allocator = mesh_allocator.into_inner();
+
+        let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(item.main_entity())
+        else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(instance_buffer) = instance_buffer else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(vertex_buffer_slice) =
+            mesh_allocator.mesh_vertex_slice(&mesh_instance.mesh_asset_id)
+        else {
+            return RenderCommandResult::Skip;
+        };
+
+        pass.set_vertex_buffer(0, vertex_buffer_slice.buffer.slice(..));
+        pass.set_vertex_buffer(1, instance_buffer.buffer.slice(..));
+        let instances = 0..instance_buffer.length as u32;
+
+        match &gpu_mesh.buffer_info {
+            RenderMeshBufferInfo::Indexed {
+                index_format,
+                count,
+            } => {
+                let Some(index_buffer_slice) =
+                    mesh_allocator.mesh_index_slice(&mesh_instance.mesh_asset_id)
+                else {
+                    return RenderCommandResult::Skip;
+                };
+                pass.set_index_buffer(index_buffer_slice.buffer.slice(..), *index_format);
+                pass.draw_indexed(
+                    index_buffer_slice.range.start..(index_buffer_slice.range.start + count),
+                    vertex_buffer_slice.range.start as i32,
+                    instances,
+                );
+            }
+            RenderMeshBufferInfo::NonIndexed => {
+                pass.draw(vertex_buffer_slice.range, instances);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
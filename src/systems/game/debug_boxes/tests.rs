@@ -0,0 +1,83 @@
+use super::*;
+use bevy::mesh::VertexAttributeValues;
+
+fn entity(index: u32) -> Entity {
+    Entity::from_raw_u32(index).unwrap()
+}
+
+#[test]
+fn from_bounds_centers_the_box() {
+    let debug_box = DebugBox::from_bounds(
+        Vec3::new(-1.0, 0.0, 2.0),
+        Vec3::new(3.0, 1.0, 4.0),
+        LinearRgba::RED,
+    );
+    assert_eq!(debug_box.center, Vec3::new(1.0, 0.5, 3.0));
+    assert_eq!(debug_box.half_extents, Vec3::new(2.0, 0.5, 1.0));
+}
+
+#[test]
+fn instance_data_pads_each_vector_to_four_floats() {
+    let debug_box = DebugBox {
+        center: Vec3::new(1.0, 2.0, 3.0),
+        half_extents: Vec3::new(4.0, 5.0, 6.0),
+        color: LinearRgba::new(0.1, 0.2, 0.3, 0.4),
+    };
+    assert_eq!(
+        debug_box.instance_data(),
+        [1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0, 0.0, 0.1, 0.2, 0.3, 0.4]
+    );
+}
+
+#[test]
+fn wire_box_has_twelve_unit_edges() {
+    let mesh = wire_box_mesh();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("wire box has no positions");
+    };
+    assert_eq!(positions.len(), 8);
+    let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+    assert_eq!(indices.len(), 24);
+    for edge in indices.chunks(2) {
+        let (a, b) = (
+            Vec3::from(positions[edge[0]]),
+            Vec3::from(positions[edge[1]]),
+        );
+        // Edges run along one axis, corner to corner
+        assert_eq!(a.distance(b), 2.0);
+    }
+}
+
+#[test]
+fn collision_boxes_keep_nearby_sub_voxels_only() {
+    let mut grid = SpatialGrid::default();
+    grid.insert(entity(1), Vec3::ZERO, Vec3::splat(0.125));
+    grid.insert(entity(2), Vec3::splat(20.0), Vec3::splat(20.125));
+    grid.insert(entity(3), Vec3::X, Vec3::X + Vec3::splat(0.5));
+
+    let boxes = collision_boxes(&grid, Vec3::ZERO, 4.0, |e| e != entity(3));
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].center, Vec3::splat(0.0625));
+    assert_eq!(boxes[0].color, COLLISION_BOUNDS_COLOR);
+}
+
+#[test]
+fn grid_cell_boxes_cover_occupied_cells_in_reach() {
+    let mut grid = SpatialGrid::default();
+    // Spans two cells along X
+    grid.insert(
+        entity(1),
+        Vec3::new(0.5, 0.2, 0.2),
+        Vec3::new(1.5, 0.4, 0.4),
+    );
+    grid.insert(entity(2), Vec3::splat(30.2), Vec3::splat(30.4));
+
+    let mut boxes = grid_cell_boxes(&grid, Vec3::ZERO, 4.0);
+    boxes.sort_by(|a, b| a.center.x.total_cmp(&b.center.x));
+    assert_eq!(boxes.len(), 2);
+    assert_eq!(boxes[0].center, Vec3::splat(GRID_CELL_SIZE * 0.5));
+    assert_eq!(boxes[1].center.x, GRID_CELL_SIZE * 1.5);
+    assert_eq!(boxes[0].half_extents, Vec3::splat(GRID_CELL_SIZE * 0.5));
+}
@@ -0,0 +1,274 @@
+//! In-game debug console.
+//!
+//! Toggled with the backquote key (`` ` ``) while playing. Supported commands:
+//!
+//! - `goto <x y z>` / `goto <entity name>` — teleport the player
+//! - `goto` — list recent jumps
+//! - `help` — list commands
+//!
+//! Up/Down recall previous commands. While the console is open, keyboard
+//! gameplay input is suspended (see [`debug_console_closed`]); typing is read
+//! in `PostUpdate` so the key that closes the console (Escape) is not also
+//! seen by gameplay systems in the same frame.
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+use super::components::Player;
+use super::go_to::{EntityNameIndex, GoToHistory, GoToTarget};
+use super::map::LoadedMapData;
+use crate::states::GameState;
+
+/// Number of output lines kept on screen
+const OUTPUT_LINES: usize = 8;
+
+/// Plugin that adds the debug console.
+pub struct DebugConsolePlugin;
+
+impl Plugin for DebugConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugConsole>()
+            .add_systems(Startup, setup_debug_console)
+            .add_systems(
+                PostUpdate,
+                (
+                    handle_console_input.run_if(in_state(GameState::InGame)),
+                    update_console_text,
+                )
+                    .chain()
+                    .before(TransformSystems::Propagate),
+            )
+            .add_systems(OnExit(GameState::InGame), close_debug_console);
+    }
+}
+
+/// A parsed console line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleCommand {
+    /// `goto <target>`
+    GoTo(GoToTarget),
+    /// `goto` without arguments
+    ListJumps,
+    Help,
+    Unknown(String),
+    Empty,
+}
+
+impl ConsoleCommand {
+    pub fn parse(line: &str) -> Self {
+        let line = line.trim();
+        let (name, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match name.to_lowercase().as_str() {
+            "" => Self::Empty,
+            "goto" | "tp" => GoToTarget::parse(args).map_or(Self::ListJumps, Self::GoTo),
+            "help" | "?" => Self::Help,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+}
+
+/// Console state: visibility, the line being typed, output and history.
+#[derive(Resource, Default)]
+pub struct DebugConsole {
+    pub open: bool,
+    pub input: String,
+    pub output: Vec<String>,
+    /// Recent `goto` targets, most recent first
+    pub jumps: GoToHistory,
+    /// Position while browsing `jumps` with Up/Down
+    recall: Option<usize>,
+}
+
+impl DebugConsole {
+    fn print(&mut self, line: impl Into<String>) {
+        self.output.push(line.into());
+        let excess = self.output.len().saturating_sub(OUTPUT_LINES);
+        self.output.drain(..excess);
+    }
+
+    /// Step through recent jumps; `older` moves back in time.
+    fn recall(&mut self, older: bool) {
+        let next = match (self.recall, older) {
+            (None, true) => Some(0),
+            (None, false) => None,
+            (Some(i), true) => Some((i + 1).min(self.jumps.len().saturating_sub(1))),
+            (Some(0), false) => None,
+            (Some(i), false) => Some(i - 1),
+        };
+        self.recall = next.filter(|_| !self.jumps.is_empty());
+        self.input = match self.recall.and_then(|i| self.jumps.get(i)) {
+            Some(query) => format!("goto {}", query),
+            None => String::new(),
+        };
+    }
+}
+
+/// Run condition: true while the console is closed, for gating keyboard
+/// gameplay input.
+pub fn debug_console_closed(console: Res<DebugConsole>) -> bool {
+    !console.open
+}
+
+/// Marker for the console panel.
+#[derive(Component)]
+struct DebugConsoleRoot;
+
+/// Marker for the console text.
+#[derive(Component)]
+struct DebugConsoleText;
+
+fn setup_debug_console(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            GlobalZIndex(100),
+            Visibility::Hidden,
+            DebugConsoleRoot,
+        ))
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.85, 0.95, 0.85)),
+            DebugConsoleText,
+        ));
+}
+
+/// System that toggles the console and edits and runs the typed line.
+fn handle_console_input(
+    mut key_events: MessageReader<KeyboardInput>,
+    mut console: ResMut<DebugConsole>,
+    map: Option<Res<LoadedMapData>>,
+    mut player: Option<Single<(&mut Transform, &mut Player)>>,
+) {
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        if event.key_code == KeyCode::Backquote {
+            console.open = !console.open;
+            console.input.clear();
+            console.recall = None;
+            continue;
+        }
+        if !console.open {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Escape => console.open = false,
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::ArrowUp => console.recall(true),
+            Key::ArrowDown => console.recall(false),
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                console.recall = None;
+                run_command(&line, &mut console, map.as_deref(), player.as_deref_mut());
+            }
+            _ => {
+                if let Some(text) = &event.text {
+                    console
+                        .input
+                        .extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+}
+
+fn run_command(
+    line: &str,
+    console: &mut DebugConsole,
+    map: Option<&LoadedMapData>,
+    player: Option<&mut (Mut<Transform>, Mut<Player>)>,
+) {
+    console.print(format!("> {}", line.trim()));
+    match ConsoleCommand::parse(line) {
+        ConsoleCommand::Empty => {}
+        ConsoleCommand::Help => {
+            console.print("goto <x y z> | goto <entity name> - teleport the player");
+            console.print("goto - list recent jumps");
+        }
+        ConsoleCommand::ListJumps => {
+            if console.jumps.is_empty() {
+                console.print("No recent jumps");
+            }
+            let jumps: Vec<String> = console.jumps.entries().map(str::to_string).collect();
+            for (i, query) in jumps.into_iter().enumerate() {
+                console.print(format!("{}: {}", i + 1, query));
+            }
+        }
+        ConsoleCommand::Unknown(name) => {
+            console.print(format!("Unknown command '{}' (try 'help')", name));
+        }
+        ConsoleCommand::GoTo(target) => {
+            let index = map
+                .map(|map| EntityNameIndex::build(&map.map.entities))
+                .unwrap_or_default();
+            let Some(destination) = index.resolve(&target) else {
+                console.print("No entity with that name");
+                return;
+            };
+            let Some((transform, player)) = player else {
+                console.print("No player to teleport");
+                return;
+            };
+            transform.translation = destination.position;
+            player.velocity = Vec3::ZERO;
+            player.is_grounded = false;
+
+            let query = line
+                .trim()
+                .split_once(char::is_whitespace)
+                .map_or("", |s| s.1);
+            console.jumps.record(query);
+            let p = destination.position;
+            console.print(format!(
+                "Teleported to ({:.1}, {:.1}, {:.1})",
+                p.x, p.y, p.z
+            ));
+            info!("Debug console: teleported player to {:?}", p);
+        }
+    }
+}
+
+/// System that shows the console and its text while open.
+fn update_console_text(
+    console: Res<DebugConsole>,
+    mut root: Single<&mut Visibility, With<DebugConsoleRoot>>,
+    mut text: Single<&mut Text, With<DebugConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    **root = if console.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    let mut lines = console.output.join("\n");
+    if !lines.is_empty() {
+        lines.push('\n');
+    }
+    text.0 = format!("{}> {}_", lines, console.input);
+}
+
+fn close_debug_console(mut console: ResMut<DebugConsole>) {
+    console.open = false;
+}
+
+#[cfg(test)]
+mod tests;
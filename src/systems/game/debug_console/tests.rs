@@ -0,0 +1,56 @@
+use super::*;
+
+#[test]
+fn parses_goto_with_coordinates_or_a_name() {
+    assert_eq!(
+        ConsoleCommand::parse("goto 1 2 3"),
+        ConsoleCommand::GoTo(GoToTarget::Coordinates(Vec3::new(1.0, 2.0, 3.0)))
+    );
+    assert_eq!(
+        ConsoleCommand::parse("  TP  Village Elder "),
+        ConsoleCommand::GoTo(GoToTarget::Entity("Village Elder".to_string()))
+    );
+    assert_eq!(ConsoleCommand::parse("goto"), ConsoleCommand::ListJumps);
+}
+
+#[test]
+fn parses_other_commands() {
+    assert_eq!(ConsoleCommand::parse(""), ConsoleCommand::Empty);
+    assert_eq!(ConsoleCommand::parse("help"), ConsoleCommand::Help);
+    assert_eq!(
+        ConsoleCommand::parse("fly on"),
+        ConsoleCommand::Unknown("fly".to_string())
+    );
+}
+
+#[test]
+fn output_keeps_only_the_latest_lines() {
+    let mut console = DebugConsole::default();
+    for i in 0..OUTPUT_LINES + 4 {
+        console.print(i.to_string());
+    }
+    assert_eq!(console.output.len(), OUTPUT_LINES);
+    assert_eq!(console.output[0], "4");
+}
+
+#[test]
+fn up_and_down_walk_through_recent_jumps() {
+    let mut console = DebugConsole::default();
+    console.recall(true);
+    assert!(console.input.is_empty());
+
+    console.jumps.record("1 2 3");
+    console.jumps.record("Elder");
+
+    console.recall(true);
+    assert_eq!(console.input, "goto Elder");
+    console.recall(true);
+    assert_eq!(console.input, "goto 1 2 3");
+    // Stops at the oldest jump
+    console.recall(true);
+    assert_eq!(console.input, "goto 1 2 3");
+    console.recall(false);
+    assert_eq!(console.input, "goto Elder");
+    console.recall(false);
+    assert!(console.input.is_empty());
+}
@@ -0,0 +1,228 @@
+//! Doors that open and close by swapping voxel groups.
+//!
+//! A `Door` entity names the voxel group that forms the closed door
+//! (`group`) and, optionally, one drawn while it is open (`open_group`).
+//! Interacting with the door toggles it: opening hides `group` — which also
+//! lets the player through — and shows `open_group`, closing does the
+//! reverse. A door with a `key` property stays locked until the player
+//! carries that key, picked up from an `Item` entity with the same `key`.
+
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use super::actions::{ActionRunner, MapAction};
+use super::captions::CaptionRequest;
+use super::components::Player;
+use super::interaction::InteractionTarget;
+use super::particles::ParticleBurst;
+use super::voxel_groups::{GroupChange, VoxelGroupRequest};
+use crate::states::GameState;
+
+/// Distance within which the player picks up a key item.
+pub const KEY_PICKUP_RADIUS: f32 = 0.8;
+
+/// Color of key items and their pickup sparkle.
+pub const KEY_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+
+/// A door entity.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Door {
+    /// Voxel group drawn while the door is closed
+    pub group: String,
+    /// Voxel group drawn while the door is open, if any
+    pub open_group: Option<String>,
+    /// Key the player needs to open the door; cleared once unlocked
+    pub key: Option<String>,
+    /// Whether the door is open
+    pub open: bool,
+}
+
+impl Door {
+    /// Read a door from entity properties: `group` (required), `open_group`,
+    /// `key` and `open` (`true`/`false`).
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Self, String> {
+        let non_empty = |key: &str| {
+            properties
+                .get(key)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let group = non_empty("group").ok_or("a door needs a 'group' of voxels")?;
+        let open = match properties.get("open").map(String::as_str) {
+            None | Some("false") | Some("0") => false,
+            Some("true") | Some("1") => true,
+            Some(other) => {
+                return Err(format!(
+                    "expected true/false/1/0 for 'open', got {:?}",
+                    other
+                ))
+            }
+        };
+        Ok(Self {
+            group,
+            open_group: non_empty("open_group"),
+            key: non_empty("key"),
+            open,
+        })
+    }
+
+    /// Voxel group changes that make the door look the way it is now.
+    pub fn group_requests(&self) -> Vec<VoxelGroupRequest> {
+        let (closed, open) = if self.open {
+            (GroupChange::Hide, GroupChange::Show)
+        } else {
+            (GroupChange::Show, GroupChange::Hide)
+        };
+        let mut requests = vec![VoxelGroupRequest {
+            group: self.group.clone(),
+            change: closed,
+        }];
+        if let Some(open_group) = &self.open_group {
+            requests.push(VoxelGroupRequest {
+                group: open_group.clone(),
+                change: open,
+            });
+        }
+        requests
+    }
+
+    /// Open a closed door or close an open one.
+    ///
+    /// A locked door unlocks for good when `keys` holds its key; without it
+    /// the door stays shut and the missing key is returned.
+    pub fn toggle(&mut self, keys: &PlayerKeys) -> Result<(), String> {
+        if let Some(key) = &self.key {
+            if !keys.has(key) {
+                return Err(key.clone());
+            }
+            self.key = None;
+        }
+        self.open = !self.open;
+        Ok(())
+    }
+}
+
+/// Keys the player has picked up; reset whenever a map spawns.
+#[derive(Resource, Debug, Default)]
+pub struct PlayerKeys(HashSet<String>);
+
+impl PlayerKeys {
+    pub fn has(&self, key: &str) -> bool {
+        self.0.contains(key)
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>) {
+        self.0.insert(key.into());
+    }
+}
+
+/// An item the player picks up as a door key.
+#[derive(Component, Debug, Clone)]
+pub struct KeyItem {
+    pub key: String,
+}
+
+/// Sent when the player picks up a key item.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct KeyCollected {
+    pub key: String,
+    pub position: Vec3,
+}
+
+/// Show every door as open or closed when it spawns.
+pub fn init_door_states(
+    doors: Query<&Door, Added<Door>>,
+    mut requests: MessageWriter<VoxelGroupRequest>,
+) {
+    for door in &doors {
+        requests.write_batch(door.group_requests());
+    }
+}
+
+/// Open or close the door the player interacts with.
+pub fn use_doors(
+    target: Res<InteractionTarget>,
+    keys: Res<PlayerKeys>,
+    mut doors: Query<&mut Door>,
+    mut runner: ResMut<ActionRunner>,
+    mut requests: MessageWriter<VoxelGroupRequest>,
+    mut captions: MessageWriter<CaptionRequest>,
+) {
+    let Some(mut door) = target.0.and_then(|entity| doors.get_mut(entity).ok()) else {
+        return;
+    };
+    let key = door.key.clone();
+    match door.toggle(&keys) {
+        Ok(()) => {
+            if let Some(key) = key {
+                runner.run(&[MapAction::Say(format!("Unlocked with the {}.", key))]);
+            }
+            requests.write_batch(door.group_requests());
+            captions.write(CaptionRequest::sound(if door.open {
+                "loc:caption.door_opens"
+            } else {
+                "loc:caption.door_closes"
+            }));
+        }
+        Err(key) => {
+            runner.run(&[MapAction::Say(format!("Locked. It needs the {}.", key))]);
+            captions.write(CaptionRequest::sound("loc:caption.door_locked"));
+        }
+    }
+}
+
+/// Pick up key items the player walks into.
+pub fn collect_keys(
+    mut commands: Commands,
+    player: Option<Single<&Transform, With<Player>>>,
+    items: Query<(Entity, &KeyItem, &GlobalTransform)>,
+    mut keys: ResMut<PlayerKeys>,
+    mut runner: ResMut<ActionRunner>,
+    mut bursts: MessageWriter<ParticleBurst>,
+    mut collected: MessageWriter<KeyCollected>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    for (entity, item, transform) in &items {
+        let position = transform.translation();
+        if position.distance(player.translation) > KEY_PICKUP_RADIUS {
+            continue;
+        }
+        info!("Picked up key '{}'", item.key);
+        keys.insert(item.key.clone());
+        bursts.write(ParticleBurst::item_collected(position, KEY_COLOR));
+        runner.run(&[MapAction::Say(format!("Picked up the {}.", item.key))]);
+        collected.write(KeyCollected {
+            key: item.key.clone(),
+            position,
+        });
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Spin key items so they catch the eye.
+pub fn spin_key_items(time: Res<Time>, mut items: Query<&mut Transform, With<KeyItem>>) {
+    for mut transform in &mut items {
+        transform.rotate_y(time.delta_secs() * 2.0);
+    }
+}
+
+/// Plugin that runs doors and key pickups.
+pub struct DoorsPlugin;
+
+impl Plugin for DoorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerKeys>()
+            .add_message::<KeyCollected>()
+            .add_systems(
+                Update,
+                (init_door_states, use_doors, collect_keys, spin_key_items)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
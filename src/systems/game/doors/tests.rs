@@ -0,0 +1,78 @@
+use super::*;
+
+fn props(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn doors_read_their_properties() {
+    let door = Door::from_properties(&props(&[
+        ("group", "gate"),
+        ("open_group", "gate_open"),
+        ("key", "brass key"),
+        ("open", "true"),
+    ]))
+    .unwrap();
+    assert_eq!(
+        door,
+        Door {
+            group: "gate".to_string(),
+            open_group: Some("gate_open".to_string()),
+            key: Some("brass key".to_string()),
+            open: true,
+        }
+    );
+
+    assert!(Door::from_properties(&props(&[])).is_err(), "needs a group");
+    assert!(Door::from_properties(&props(&[("group", "gate"), ("open", "ajar")])).is_err());
+    assert_eq!(
+        Door::from_properties(&props(&[("group", "gate"), ("key", " ")])).map(|d| d.key),
+        Ok(None)
+    );
+}
+
+#[test]
+fn opening_swaps_the_closed_group_for_the_open_one() {
+    let mut door =
+        Door::from_properties(&props(&[("group", "gate"), ("open_group", "ajar")])).unwrap();
+    let changes = |door: &Door| {
+        door.group_requests()
+            .into_iter()
+            .map(|r| (r.group, r.change))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        changes(&door),
+        vec![
+            ("gate".to_string(), GroupChange::Show),
+            ("ajar".to_string(), GroupChange::Hide)
+        ]
+    );
+
+    door.toggle(&PlayerKeys::default()).unwrap();
+    assert_eq!(
+        changes(&door),
+        vec![
+            ("gate".to_string(), GroupChange::Hide),
+            ("ajar".to_string(), GroupChange::Show)
+        ]
+    );
+}
+
+#[test]
+fn locked_doors_open_only_with_their_key() {
+    let mut door =
+        Door::from_properties(&props(&[("group", "gate"), ("key", "brass key")])).unwrap();
+    let mut keys = PlayerKeys::default();
+    assert_eq!(door.toggle(&keys), Err("brass key".to_string()));
+    assert!(!door.open);
+
+    keys.insert("brass key");
+    assert_eq!(door.toggle(&keys), Ok(()));
+    assert!(door.open);
+    assert_eq!(door.key, None, "stays unlocked");
+    assert_eq!(door.toggle(&PlayerKeys::default()), Ok(()));
+}
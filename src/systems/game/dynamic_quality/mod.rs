@@ -0,0 +1,218 @@
+//! Dynamic quality: LOD and shadow distances tuned to hold a frame rate.
+//!
+//! While playing, the frame time is averaged and about once a second compared
+//! with the mode's target. Too slow and [`LodConfig::distance_scale`] shrinks,
+//! so chunks switch to coarser meshes closer to the camera; comfortably fast
+//! and it grows back. The shadow distance follows the same scale but never
+//! goes past its normal range.
+//!
+//! Two thresholds either side of the target keep the scale from bouncing:
+//! it only drops while frames are over 10% too slow and only rises while
+//! they are at least 20% faster than needed.
+//!
+//! The mode is picked on the settings screen; [`QualityMode::Off`] leaves the
+//! distances as they are.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::map::spawner::LodConfig;
+use crate::states::GameState;
+use crate::systems::settings::{MonitorInfo, VsyncConfig};
+
+/// Seconds between adjustments.
+const ADJUST_INTERVAL: f32 = 1.0;
+
+/// Weight of the newest frame in the running average frame time.
+const SMOOTHING: f32 = 0.05;
+
+/// Scale down while the average frame time is this much over the target's.
+const SLOW_MARGIN: f32 = 1.1;
+
+/// Scale up while the average frame time is under this share of the target's.
+const FAST_MARGIN: f32 = 0.8;
+
+/// Factor applied to the scale when frames are too slow.
+const STEP_DOWN: f32 = 0.85;
+
+/// Factor applied to the scale when frames have room to spare.
+const STEP_UP: f32 = 1.08;
+
+/// How hard dynamic quality works to keep the frame rate up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum QualityMode {
+    /// Fixed LOD and shadow distances
+    Off,
+    /// Far detail first; only gives way below 30 fps
+    Quality,
+    /// Aims for 60 fps without dropping much detail
+    #[default]
+    Balanced,
+    /// Aims for 60 fps and drops detail early
+    Performance,
+}
+
+impl QualityMode {
+    pub const ALL: [QualityMode; 4] = [
+        QualityMode::Off,
+        QualityMode::Quality,
+        QualityMode::Balanced,
+        QualityMode::Performance,
+    ];
+
+    /// Frame rate this mode tries to hold, or `None` when off.
+    pub fn target_fps(self) -> Option<f32> {
+        match self {
+            QualityMode::Off => None,
+            QualityMode::Quality => Some(30.0),
+            QualityMode::Balanced | QualityMode::Performance => Some(60.0),
+        }
+    }
+
+    /// Range the distance scale is kept in.
+    pub fn scale_range(self) -> (f32, f32) {
+        match self {
+            QualityMode::Off => (1.0, 1.0),
+            QualityMode::Quality => (0.5, 1.5),
+            QualityMode::Balanced => (0.35, 1.25),
+            QualityMode::Performance => (0.25, 1.0),
+        }
+    }
+
+    /// The mode `delta` steps along [`QualityMode::ALL`], wrapping around.
+    pub fn cycled(self, delta: i32) -> Self {
+        let len = Self::ALL.len() as i32;
+        let index = Self::ALL.iter().position(|&mode| mode == self).unwrap_or(0) as i32;
+        Self::ALL[(index + delta).rem_euclid(len) as usize]
+    }
+}
+
+/// Dynamic quality options, set from the settings screen.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DynamicQualitySettings {
+    pub mode: QualityMode,
+}
+
+/// Running state of the frame-time controller.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct FrameTimeTuner {
+    /// Exponential average of recent frame times, in seconds
+    pub average: Option<f32>,
+    /// Seconds since the last adjustment
+    pub since_adjust: f32,
+    /// Current distance scale
+    pub scale: f32,
+}
+
+impl Default for FrameTimeTuner {
+    fn default() -> Self {
+        Self {
+            average: None,
+            since_adjust: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl FrameTimeTuner {
+    /// Fold a frame time into the running average.
+    pub fn sample(&mut self, frame_time: f32) {
+        self.average = Some(match self.average {
+            Some(average) => average + (frame_time - average) * SMOOTHING,
+            None => frame_time,
+        });
+        self.since_adjust += frame_time;
+    }
+
+    /// Rescale towards `target_fps` if an adjustment is due. Returns whether
+    /// the scale changed.
+    pub fn adjust(&mut self, target_fps: f32, (min, max): (f32, f32)) -> bool {
+        if self.since_adjust < ADJUST_INTERVAL {
+            return false;
+        }
+        self.since_adjust = 0.0;
+        let Some(average) = self.average else {
+            return false;
+        };
+
+        let target = 1.0 / target_fps;
+        let scale = if average > target * SLOW_MARGIN {
+            self.scale * STEP_DOWN
+        } else if average < target * FAST_MARGIN {
+            self.scale * STEP_UP
+        } else {
+            self.scale
+        }
+        .clamp(min, max);
+
+        let changed = (scale - self.scale).abs() > f32::EPSILON;
+        self.scale = scale;
+        changed
+    }
+}
+
+/// Frame rate to aim for: the mode's target, but no more than VSync allows.
+pub fn effective_target_fps(
+    mode: QualityMode,
+    vsync: &VsyncConfig,
+    monitor: &MonitorInfo,
+) -> Option<f32> {
+    let target = mode.target_fps()?;
+    if vsync.vsync_enabled {
+        Some(target.min(monitor.refresh_hz * vsync.vsync_multiplier.min(1.0)))
+    } else {
+        Some(target)
+    }
+}
+
+/// Measure the frame time and retune the LOD and shadow distances.
+pub fn tune_quality(
+    time: Res<Time<Real>>,
+    settings: Res<DynamicQualitySettings>,
+    vsync: Res<VsyncConfig>,
+    monitor: Res<MonitorInfo>,
+    mut tuner: ResMut<FrameTimeTuner>,
+    mut lod_config: ResMut<LodConfig>,
+) {
+    let Some(target_fps) = effective_target_fps(settings.mode, &vsync, &monitor) else {
+        // Switched off: put the distances back once
+        if settings.is_changed() {
+            *tuner = FrameTimeTuner::default();
+            lod_config.distance_scale = 1.0;
+            lod_config.shadow_distance_scale = 1.0;
+        }
+        return;
+    };
+
+    let range = settings.mode.scale_range();
+    if settings.is_changed() {
+        tuner.scale = tuner.scale.clamp(range.0, range.1);
+    }
+    tuner.sample(time.delta_secs());
+    if tuner.adjust(target_fps, range) || settings.is_changed() {
+        lod_config.distance_scale = tuner.scale;
+        lod_config.shadow_distance_scale = tuner.scale.min(1.0);
+    }
+}
+
+/// Start each map from the normal distances.
+pub fn reset_quality(mut tuner: ResMut<FrameTimeTuner>, mut lod_config: ResMut<LodConfig>) {
+    *tuner = FrameTimeTuner::default();
+    lod_config.distance_scale = 1.0;
+    lod_config.shadow_distance_scale = 1.0;
+}
+
+/// Plugin that tunes LOD and shadow distances to the frame rate.
+pub struct DynamicQualityPlugin;
+
+impl Plugin for DynamicQualityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DynamicQualitySettings>()
+            .init_resource::<FrameTimeTuner>()
+            .add_systems(OnEnter(GameState::LoadingMap), reset_quality)
+            .add_systems(Update, tune_quality.run_if(in_state(GameState::InGame)));
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,86 @@
+use super::*;
+
+/// A tuner that has seen `frame_time` for a while and is due to adjust.
+fn settled_at(frame_time: f32, scale: f32) -> FrameTimeTuner {
+    FrameTimeTuner {
+        average: Some(frame_time),
+        since_adjust: ADJUST_INTERVAL,
+        scale,
+    }
+}
+
+#[test]
+fn slow_frames_shrink_the_scale() {
+    let mut tuner = settled_at(1.0 / 40.0, 1.0);
+    assert!(tuner.adjust(60.0, (0.25, 1.25)));
+    assert!(tuner.scale < 1.0);
+}
+
+#[test]
+fn fast_frames_grow_the_scale() {
+    let mut tuner = settled_at(1.0 / 120.0, 1.0);
+    assert!(tuner.adjust(60.0, (0.25, 1.25)));
+    assert!(tuner.scale > 1.0);
+}
+
+#[test]
+fn frames_near_the_target_hold_the_scale() {
+    // Between the two margins: slightly slow and slightly fast
+    for fps in [56.0, 70.0] {
+        let mut tuner = settled_at(1.0 / fps, 0.7);
+        assert!(!tuner.adjust(60.0, (0.25, 1.25)));
+        assert_eq!(tuner.scale, 0.7);
+    }
+}
+
+#[test]
+fn scale_stays_in_the_mode_range() {
+    let range = QualityMode::Performance.scale_range();
+    let mut tuner = settled_at(1.0 / 10.0, 0.28);
+    assert!(tuner.adjust(60.0, range));
+    assert_eq!(tuner.scale, 0.25);
+    tuner.since_adjust = ADJUST_INTERVAL;
+    assert!(!tuner.adjust(60.0, range));
+}
+
+#[test]
+fn adjustments_wait_for_the_interval() {
+    let mut tuner = FrameTimeTuner::default();
+    tuner.sample(1.0 / 20.0);
+    assert!(!tuner.adjust(60.0, (0.25, 1.25)));
+    for _ in 0..20 {
+        tuner.sample(1.0 / 20.0);
+    }
+    assert!(tuner.adjust(60.0, (0.25, 1.25)));
+    assert_eq!(tuner.since_adjust, 0.0);
+}
+
+#[test]
+fn vsync_caps_the_target() {
+    let monitor = MonitorInfo { refresh_hz: 60.0 };
+    let mut vsync = VsyncConfig {
+        vsync_enabled: true,
+        vsync_multiplier: 0.5,
+        ..default()
+    };
+    assert_eq!(
+        effective_target_fps(QualityMode::Balanced, &vsync, &monitor),
+        Some(30.0)
+    );
+    vsync.vsync_enabled = false;
+    assert_eq!(
+        effective_target_fps(QualityMode::Balanced, &vsync, &monitor),
+        Some(60.0)
+    );
+    assert_eq!(
+        effective_target_fps(QualityMode::Off, &vsync, &monitor),
+        None
+    );
+}
+
+#[test]
+fn modes_cycle_in_both_directions() {
+    assert_eq!(QualityMode::Off.cycled(-1), QualityMode::Performance);
+    assert_eq!(QualityMode::Performance.cycled(1), QualityMode::Off);
+    assert_eq!(QualityMode::default().cycled(1), QualityMode::Performance);
+}
@@ -0,0 +1,165 @@
+//! Fixed physics timestep with interpolated rendering.
+//!
+//! Player movement and physics run in `FixedUpdate` at [`PHYSICS_HZ`], so a
+//! run plays out the same at any frame rate. Between steps the player's
+//! `Transform` is drawn interpolated from the last two physics states:
+//!
+//! - Before the fixed loop, [`restore_physics_translation`] puts the player
+//!   back on its latest physics position.
+//! - Each step records its start and end positions in
+//!   [`PhysicsInterpolation`] ([`begin_physics_step`], [`end_physics_step`]).
+//! - After the loop, [`interpolate_physics_translation`] blends them by how
+//!   far time has run into the next step.
+//!
+//! Gameplay systems in `Update` (teleporters, respawns, hot reload) keep
+//! writing the `Transform` directly. A translation that differs from the
+//! one last drawn is taken as such a move and becomes the new physics
+//! position, without blending.
+//!
+//! Steps run on the input gathered in the frame before them. Presses that
+//! last one frame, a jump or interact, are held in [`FixedStepInput`] until
+//! a step uses them, so none are lost or repeated at high or low frame rates.
+
+use bevy::prelude::*;
+
+use super::components::Player;
+use super::gamepad::PlayerInput;
+use super::split_screen::SplitScreenPlayer;
+use crate::states::GameState;
+
+/// Physics steps per second.
+pub const PHYSICS_HZ: f64 = 60.0;
+
+/// The player's positions around the latest physics step.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct PhysicsInterpolation {
+    /// Position before the latest step
+    pub start: Vec3,
+    /// Position after the latest step
+    pub end: Vec3,
+    /// Position last drawn, to tell interpolation apart from other moves
+    pub rendered: Option<Vec3>,
+}
+
+impl PhysicsInterpolation {
+    /// Physics position to continue from, given the current `translation`.
+    /// A translation other than the one drawn was moved outside physics and
+    /// is taken as is.
+    pub fn restore(&mut self, translation: Vec3) -> Vec3 {
+        if self.rendered != Some(translation) {
+            self.start = translation;
+            self.end = translation;
+        }
+        self.rendered = None;
+        self.end
+    }
+
+    pub fn begin_step(&mut self, translation: Vec3) {
+        self.start = translation;
+    }
+
+    pub fn end_step(&mut self, translation: Vec3) {
+        self.end = translation;
+    }
+
+    /// Position to draw `alpha` (0–1) of the way into the next step.
+    pub fn interpolate(&mut self, alpha: f32) -> Vec3 {
+        let position = self.start.lerp(self.end, alpha.clamp(0.0, 1.0));
+        self.rendered = Some(position);
+        position
+    }
+}
+
+/// Characters moved by the physics steps: the player and a split-screen
+/// player two.
+type Character = Or<(With<Player>, With<SplitScreenPlayer>)>;
+
+/// One-frame presses held until a physics step uses them.
+#[derive(Resource, Debug, Default)]
+pub struct FixedStepInput {
+    pub jump_just_pressed: bool,
+    pub interact_pressed: bool,
+}
+
+impl FixedStepInput {
+    /// Hold this frame's presses.
+    pub fn latch(&mut self, input: &PlayerInput) {
+        self.jump_just_pressed |= input.jump_just_pressed;
+        self.interact_pressed |= input.interact_pressed;
+    }
+
+    /// Hand the held presses to a step, which uses them up.
+    pub fn take_into(&mut self, input: &mut PlayerInput) {
+        input.jump_just_pressed = std::mem::take(&mut self.jump_just_pressed);
+        input.interact_pressed = std::mem::take(&mut self.interact_pressed);
+    }
+}
+
+/// Hold this frame's one-frame presses for the next physics step; runs after
+/// input is gathered.
+pub fn latch_fixed_step_input(input: Res<PlayerInput>, mut latched: ResMut<FixedStepInput>) {
+    latched.latch(&input);
+}
+
+/// Give a physics step the presses held since the last one.
+pub fn take_fixed_step_input(mut latched: ResMut<FixedStepInput>, mut input: ResMut<PlayerInput>) {
+    latched.take_into(&mut input);
+}
+
+/// Put the player back on its physics position before the fixed loop.
+pub fn restore_physics_translation(
+    mut player: Query<(&mut Transform, &mut PhysicsInterpolation), Character>,
+) {
+    for (mut transform, mut interpolation) in &mut player {
+        transform.translation = interpolation.restore(transform.translation);
+    }
+}
+
+/// Record the player's position at the start of a physics step.
+pub fn begin_physics_step(mut player: Query<(&Transform, &mut PhysicsInterpolation), Character>) {
+    for (transform, mut interpolation) in &mut player {
+        interpolation.begin_step(transform.translation);
+    }
+}
+
+/// Record the player's position at the end of a physics step.
+pub fn end_physics_step(mut player: Query<(&Transform, &mut PhysicsInterpolation), Character>) {
+    for (transform, mut interpolation) in &mut player {
+        interpolation.end_step(transform.translation);
+    }
+}
+
+/// Draw the player between its last two physics positions.
+pub fn interpolate_physics_translation(
+    fixed_time: Res<Time<Fixed>>,
+    mut player: Query<(&mut Transform, &mut PhysicsInterpolation), Character>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    for (mut transform, mut interpolation) in &mut player {
+        transform.translation = interpolation.interpolate(alpha);
+    }
+}
+
+/// Plugin for the fixed physics timestep and its interpolation. The physics
+/// systems themselves are added by the app in its movement and physics sets.
+pub struct FixedStepPlugin;
+
+impl Plugin for FixedStepPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Time::<Fixed>::from_hz(PHYSICS_HZ))
+            .init_resource::<FixedStepInput>()
+            .add_systems(
+                RunFixedMainLoop,
+                (
+                    restore_physics_translation
+                        .in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop),
+                    interpolate_physics_translation
+                        .in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn interpolation_blends_the_last_two_physics_positions() {
+    let mut interpolation = PhysicsInterpolation::default();
+    let start = Vec3::new(1.0, 0.5, 1.0);
+    assert_eq!(interpolation.restore(start), start);
+
+    interpolation.begin_step(start);
+    interpolation.end_step(Vec3::new(2.0, 0.5, 1.0));
+    assert_eq!(interpolation.interpolate(0.25), Vec3::new(1.25, 0.5, 1.0));
+    assert_eq!(interpolation.interpolate(1.5), Vec3::new(2.0, 0.5, 1.0));
+}
+
+#[test]
+fn restoring_returns_to_the_physics_position() {
+    let mut interpolation = PhysicsInterpolation::default();
+    interpolation.restore(Vec3::ZERO);
+    interpolation.begin_step(Vec3::ZERO);
+    interpolation.end_step(Vec3::X);
+    let drawn = interpolation.interpolate(0.5);
+
+    assert_eq!(interpolation.restore(drawn), Vec3::X);
+    assert_eq!(interpolation.start, Vec3::ZERO);
+}
+
+#[test]
+fn moves_outside_physics_are_taken_without_blending() {
+    let mut interpolation = PhysicsInterpolation::default();
+    interpolation.restore(Vec3::ZERO);
+    interpolation.begin_step(Vec3::ZERO);
+    interpolation.end_step(Vec3::X);
+    interpolation.interpolate(0.5);
+
+    // A teleporter moved the player after it was drawn
+    let teleported = Vec3::new(10.0, 2.0, -4.0);
+    assert_eq!(interpolation.restore(teleported), teleported);
+    assert_eq!(interpolation.interpolate(0.3), teleported);
+}
+
+#[test]
+fn presses_are_held_until_one_step_uses_them() {
+    let mut latched = FixedStepInput::default();
+    let mut input = PlayerInput {
+        jump_just_pressed: true,
+        interact_pressed: true,
+        ..default()
+    };
+    latched.latch(&input);
+
+    // A frame without a step, then the press is released
+    input.jump_just_pressed = false;
+    input.interact_pressed = false;
+    latched.latch(&input);
+
+    let mut step_input = PlayerInput::default();
+    latched.take_into(&mut step_input);
+    assert!(step_input.jump_just_pressed, "the first step jumps");
+    assert!(step_input.interact_pressed, "and interacts");
+    latched.take_into(&mut step_input);
+    assert!(!step_input.jump_just_pressed, "a second step doesn't");
+    assert!(!step_input.interact_pressed);
+}
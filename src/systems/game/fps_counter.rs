@@ -1,10 +1,14 @@
 //! FPS counter overlay for in-game performance monitoring.
 //!
-//! This module provides an FPS counter that can be toggled with F3.
+//! This module provides an FPS counter that can be toggled with F3. It also
+//! shows the occlusion transparency technique, which F4 switches while the
+//! overlay is visible.
 
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::prelude::*;
 
+use super::occlusion::OcclusionConfig;
+
 /// Plugin that adds FPS counter functionality to the game.
 pub struct FpsCounterPlugin;
 
@@ -73,6 +77,7 @@ fn toggle_fps_counter(
 fn update_fps_counter(
     diagnostics: Res<DiagnosticsStore>,
     state: Res<FpsCounterState>,
+    occlusion: Option<Res<OcclusionConfig>>,
     mut fps_query: Query<&mut Text, With<FpsText>>,
 ) {
     // Only update if visible to save performance
@@ -84,8 +89,11 @@ fn update_fps_counter(
         .get(&bevy::diagnostic::FrameTimeDiagnosticsPlugin::FPS)
         .and_then(|fps| fps.smoothed())
     {
+        let technique = occlusion
+            .map(|config| format!("\nOcclusion: {:?} (F4)", config.technique))
+            .unwrap_or_default();
         for mut text in &mut fps_query {
-            **text = format!("FPS: {:.0}", fps);
+            **text = format!("FPS: {:.0}{}", fps, technique);
         }
     }
 }
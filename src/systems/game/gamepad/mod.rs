@@ -9,10 +9,13 @@
 use bevy::input::gamepad::{GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent};
 use bevy::prelude::*;
 
+use super::split_screen::SplitScreen;
+
 /// Resource tracking the currently active gamepad.
 ///
-/// Only one gamepad is supported at a time. The first connected gamepad
-/// becomes active, and if it disconnects, the next connected one takes over.
+/// This is player one's gamepad. The first connected gamepad becomes
+/// active, and if it disconnects, the next connected one not used by a
+/// [split-screen](super::split_screen) player takes over.
 #[derive(Resource, Default)]
 pub struct ActiveGamepad(pub Option<Entity>);
 
@@ -59,11 +62,15 @@ pub enum InputSource {
 /// This resource is populated each frame by the input gathering systems
 /// and can be read by movement, camera, and other systems to handle
 /// input in a source-agnostic way.
-#[derive(Resource, Default)]
+///
+/// Split-screen players carry their own copy as a component, filled from
+/// their gamepad alone.
+#[derive(Resource, Component, Default)]
 pub struct PlayerInput {
     /// Normalized movement direction from left stick or WASD
     pub movement: Vec2,
     /// Camera rotation delta from right stick or mouse
+    #[allow(dead_code)]
     pub camera_delta: Vec2,
     /// Look direction from right stick (for character facing direction)
     /// When this is non-zero, the character faces this direction instead of movement direction
@@ -82,9 +89,11 @@ pub struct PlayerInput {
     pub flashlight_toggle_just_pressed: bool,
     /// Left trigger axis value [0.0, 1.0], after trigger_deadzone is applied.
     /// Zero when the raw value is below the deadzone threshold.
+    #[allow(dead_code)]
     pub left_trigger: f32,
     /// Right trigger axis value [0.0, 1.0], after trigger_deadzone is applied.
     /// Zero when the raw value is below the deadzone threshold.
+    #[allow(dead_code)]
     pub right_trigger: f32,
     /// The current active input source
     pub input_source: InputSource,
@@ -114,7 +123,10 @@ pub fn handle_gamepad_connections(
     mut active_gamepad: ResMut<ActiveGamepad>,
     mut connection_events: MessageReader<GamepadConnectionEvent>,
     gamepads: Query<Entity, With<Gamepad>>,
+    split_screen: Option<Res<SplitScreen>>,
 ) {
+    // Player two's gamepad never takes over player one
+    let second_player = split_screen.and_then(|split_screen| split_screen.gamepad);
     for event in connection_events.read() {
         match &event.connection {
             GamepadConnection::Connected { name, .. } => {
@@ -130,7 +142,9 @@ pub fn handle_gamepad_connections(
                 // If this was the active gamepad, find another or set to None
                 if active_gamepad.0 == Some(event.gamepad) {
                     // Find another connected gamepad
-                    active_gamepad.0 = gamepads.iter().find(|&entity| entity != event.gamepad);
+                    active_gamepad.0 = gamepads
+                        .iter()
+                        .find(|&entity| entity != event.gamepad && Some(entity) != second_player);
 
                     if let Some(new_active) = active_gamepad.0 {
                         info!("New active gamepad: {:?}", new_active);
@@ -143,6 +157,67 @@ pub fn handle_gamepad_connections(
     }
 }
 
+/// Read one gamepad into a [`PlayerInput`] with `input_source` set to
+/// [`InputSource::Gamepad`].
+pub fn read_gamepad(gamepad: &Gamepad, settings: &GamepadSettings) -> PlayerInput {
+    // Left stick for movement
+    let left_stick = Vec2::new(
+        gamepad.get(GamepadAxis::LeftStickX).unwrap_or(0.0),
+        gamepad.get(GamepadAxis::LeftStickY).unwrap_or(0.0),
+    );
+    let movement =
+        apply_deadzone(left_stick, settings.stick_deadzone) * settings.movement_sensitivity;
+
+    // Right stick for character look direction (not camera)
+    let right_stick = Vec2::new(
+        gamepad.get(GamepadAxis::RightStickX).unwrap_or(0.0),
+        gamepad.get(GamepadAxis::RightStickY).unwrap_or(0.0),
+    );
+    let mut look_direction = apply_deadzone(right_stick, settings.stick_deadzone);
+    if settings.invert_camera_y {
+        look_direction.y = -look_direction.y;
+    }
+
+    // Trigger axes — apply custom deadzone
+    let trigger = |axis| {
+        let raw = gamepad.get(axis).unwrap_or(0.0);
+        if raw >= settings.trigger_deadzone {
+            raw
+        } else {
+            0.0
+        }
+    };
+
+    PlayerInput {
+        movement,
+        // Camera delta is not used from right stick anymore
+        camera_delta: Vec2::ZERO,
+        look_direction,
+        jump_pressed: gamepad.pressed(GamepadButton::South),
+        jump_just_pressed: gamepad.just_pressed(GamepadButton::South),
+        interact_pressed: gamepad.just_pressed(GamepadButton::West),
+        pause_just_pressed: gamepad.just_pressed(GamepadButton::Start),
+        camera_reset_just_pressed: gamepad.just_pressed(GamepadButton::RightThumb),
+        flashlight_toggle_just_pressed: gamepad.just_pressed(GamepadButton::North), // Y button
+        left_trigger: trigger(GamepadAxis::LeftZ),
+        right_trigger: trigger(GamepadAxis::RightZ),
+        input_source: InputSource::Gamepad,
+    }
+}
+
+impl PlayerInput {
+    /// Whether any stick or button of a gamepad reading is in use.
+    fn is_active(&self) -> bool {
+        self.movement.length() > 0.01
+            || self.look_direction.length() > 0.01
+            || self.jump_pressed
+            || self.interact_pressed
+            || self.pause_just_pressed
+            || self.camera_reset_just_pressed
+            || self.flashlight_toggle_just_pressed
+    }
+}
+
 /// System that gathers all gamepad input into the PlayerInput resource.
 ///
 /// This system reads from both keyboard/mouse and gamepad, automatically
@@ -153,95 +228,22 @@ pub fn gather_gamepad_input(
     gamepad_query: Query<&Gamepad>,
     mut player_input: ResMut<PlayerInput>,
 ) {
-    // Reset gamepad-specific inputs (keyboard handler will set its own)
-    let mut gamepad_movement = Vec2::ZERO;
-    let mut gamepad_camera = Vec2::ZERO;
-    let mut gamepad_look_direction = Vec2::ZERO;
-    let mut gamepad_jump_pressed = false;
-    let mut gamepad_jump_just_pressed = false;
-    let mut gamepad_interact = false;
-    let mut gamepad_pause = false;
-    let mut gamepad_camera_reset = false;
-    let mut gamepad_flashlight_toggle = false;
-    let mut gamepad_active = false;
-    let mut gamepad_left_trigger: f32 = 0.0;
-    let mut gamepad_right_trigger: f32 = 0.0;
-
-    if let Some(gamepad_entity) = active_gamepad.0 {
-        if let Ok(gamepad) = gamepad_query.get(gamepad_entity) {
-            // Left stick for movement - In Bevy 0.15, use GamepadAxis enum variants directly
-            let left_stick = Vec2::new(
-                gamepad.get(GamepadAxis::LeftStickX).unwrap_or(0.0),
-                gamepad.get(GamepadAxis::LeftStickY).unwrap_or(0.0),
-            );
-            gamepad_movement =
-                apply_deadzone(left_stick, settings.stick_deadzone) * settings.movement_sensitivity;
-
-            // Right stick for character look direction (not camera)
-            let right_stick = Vec2::new(
-                gamepad.get(GamepadAxis::RightStickX).unwrap_or(0.0),
-                gamepad.get(GamepadAxis::RightStickY).unwrap_or(0.0),
-            );
-            let mut look = apply_deadzone(right_stick, settings.stick_deadzone);
-            if settings.invert_camera_y {
-                look.y = -look.y;
-            }
-            gamepad_look_direction = look;
-
-            // Camera delta is not used from right stick anymore
-            gamepad_camera = Vec2::ZERO;
-
-            // Button inputs - In Bevy 0.15, GamepadButton is an enum directly
-            gamepad_jump_pressed = gamepad.pressed(GamepadButton::South);
-            gamepad_jump_just_pressed = gamepad.just_pressed(GamepadButton::South);
-            gamepad_interact = gamepad.just_pressed(GamepadButton::West);
-            gamepad_pause = gamepad.just_pressed(GamepadButton::Start);
-            gamepad_camera_reset = gamepad.just_pressed(GamepadButton::RightThumb);
-            gamepad_flashlight_toggle = gamepad.just_pressed(GamepadButton::North); // Y button
-
-            // Trigger axes — apply custom deadzone
-            let left_trigger_raw = gamepad.get(GamepadAxis::LeftZ).unwrap_or(0.0);
-            let right_trigger_raw = gamepad.get(GamepadAxis::RightZ).unwrap_or(0.0);
-            gamepad_left_trigger = if left_trigger_raw >= settings.trigger_deadzone {
-                left_trigger_raw
-            } else {
-                0.0
-            };
-            gamepad_right_trigger = if right_trigger_raw >= settings.trigger_deadzone {
-                right_trigger_raw
-            } else {
-                0.0
-            };
-
-            // Detect if gamepad is being used (any significant input)
-            gamepad_active = gamepad_movement.length() > 0.01
-                || gamepad_look_direction.length() > 0.01
-                || gamepad_jump_pressed
-                || gamepad_interact
-                || gamepad_pause
-                || gamepad_camera_reset
-                || gamepad_flashlight_toggle;
-        }
-    }
+    let gamepad_input = active_gamepad
+        .0
+        .and_then(|entity| gamepad_query.get(entity).ok())
+        .map(|gamepad| read_gamepad(gamepad, &settings));
 
     // Update input source based on activity
-    if gamepad_active {
+    if gamepad_input.as_ref().is_some_and(PlayerInput::is_active) {
         player_input.input_source = InputSource::Gamepad;
     }
 
     // Merge gamepad input (keyboard input is handled separately and merged in gather_keyboard_input)
     if player_input.input_source == InputSource::Gamepad {
-        player_input.movement = gamepad_movement;
-        player_input.camera_delta = gamepad_camera;
-        player_input.look_direction = gamepad_look_direction;
-        player_input.jump_pressed = gamepad_jump_pressed;
-        player_input.jump_just_pressed = gamepad_jump_just_pressed;
-        player_input.interact_pressed = gamepad_interact;
-        player_input.pause_just_pressed = gamepad_pause;
-        player_input.camera_reset_just_pressed = gamepad_camera_reset;
-        player_input.flashlight_toggle_just_pressed = gamepad_flashlight_toggle;
-        player_input.left_trigger = gamepad_left_trigger;
-        player_input.right_trigger = gamepad_right_trigger;
+        *player_input = gamepad_input.unwrap_or(PlayerInput {
+            input_source: InputSource::Gamepad,
+            ..default()
+        });
     }
 }
 
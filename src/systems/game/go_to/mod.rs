@@ -0,0 +1,184 @@
+//! "Go To" jump targets shared by the map editor and the in-game debug console.
+//!
+//! A jump query is either coordinates (`"4 2 -7"`, `"4, 2, -7"`) or the name
+//! of a map entity. Entity names come from the `name` property, falling back
+//! to the entity type, exactly as the editor outliner lists them.
+//! [`EntityNameIndex`] resolves a name case-insensitively, preferring exact
+//! matches over partial ones, and [`GoToHistory`] remembers recent jumps.
+
+use crate::systems::game::map::format::EntityData;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Number of recent jumps remembered
+pub const GO_TO_HISTORY_LEN: usize = 10;
+
+/// What a jump query refers to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoToTarget {
+    /// A world position
+    Coordinates(Vec3),
+    /// The name (or part of the name) of a map entity
+    Entity(String),
+}
+
+impl GoToTarget {
+    /// Parse a query. Three numbers separated by spaces and/or commas are
+    /// coordinates; anything else non-empty is an entity name.
+    pub fn parse(query: &str) -> Option<Self> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+
+        let parts: Vec<&str> = query
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .collect();
+        if let [x, y, z] = parts[..] {
+            let coords = (x.parse::<f32>(), y.parse::<f32>(), z.parse::<f32>());
+            if let (Ok(x), Ok(y), Ok(z)) = coords {
+                let position = Vec3::new(x, y, z);
+                if position.is_finite() {
+                    return Some(Self::Coordinates(position));
+                }
+            }
+        }
+        Some(Self::Entity(query.to_string()))
+    }
+}
+
+/// Where a jump query resolved to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoToDestination {
+    pub position: Vec3,
+    /// Index of the matched entity in the map's entity list
+    pub entity: Option<usize>,
+}
+
+/// Name the outliner and "Go To" use for an entity.
+pub fn entity_display_name(entity: &EntityData) -> String {
+    entity
+        .properties
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| format!("{:?}", entity.entity_type))
+}
+
+/// One indexed entity name.
+#[derive(Debug, Clone)]
+struct IndexedName {
+    lowercase: String,
+    display: String,
+    position: Vec3,
+}
+
+/// Entity names of a map, lowercased for case-insensitive lookup.
+///
+/// Entries are kept in map order, so an entity's index in the map is also its
+/// index here.
+#[derive(Debug, Clone, Default)]
+pub struct EntityNameIndex {
+    entries: Vec<IndexedName>,
+}
+
+impl EntityNameIndex {
+    /// Index the names of `entities`.
+    pub fn build(entities: &[EntityData]) -> Self {
+        let entries = entities
+            .iter()
+            .map(|entity| {
+                let display = entity_display_name(entity);
+                IndexedName {
+                    lowercase: display.to_lowercase(),
+                    display,
+                    position: entity.position.into(),
+                }
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Display name of the entity at `index`
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(|entry| entry.display.as_str())
+    }
+
+    /// Display names containing `query`, exact matches first, as
+    /// `(display name, entity index)`.
+    pub fn matches(&self, query: &str) -> Vec<(&str, usize)> {
+        let query = query.trim().to_lowercase();
+        let mut exact = Vec::new();
+        let mut partial = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.lowercase == query {
+                exact.push((entry.display.as_str(), index));
+            } else if entry.lowercase.contains(&query) {
+                partial.push((entry.display.as_str(), index));
+            }
+        }
+        exact.extend(partial);
+        exact
+    }
+
+    /// Best entity for `query`: the first exact name match, otherwise the
+    /// first name containing it.
+    pub fn find(&self, query: &str) -> Option<GoToDestination> {
+        let (_, index) = *self.matches(query).first()?;
+        Some(GoToDestination {
+            position: self.entries[index].position,
+            entity: Some(index),
+        })
+    }
+
+    /// Resolve a parsed target to a position.
+    pub fn resolve(&self, target: &GoToTarget) -> Option<GoToDestination> {
+        match target {
+            GoToTarget::Coordinates(position) => Some(GoToDestination {
+                position: *position,
+                entity: None,
+            }),
+            GoToTarget::Entity(name) => self.find(name),
+        }
+    }
+}
+
+/// Recently used jump queries, most recent first.
+#[derive(Debug, Clone, Default)]
+pub struct GoToHistory {
+    entries: VecDeque<String>,
+}
+
+impl GoToHistory {
+    /// Remember `query`, moving it to the front if it was already there.
+    pub fn record(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.push_front(query.to_string());
+        self.entries.truncate(GO_TO_HISTORY_LEN);
+    }
+
+    /// Recent queries, most recent first
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// The query `steps_back` jumps ago (0 is the latest)
+    pub fn get(&self, steps_back: usize) -> Option<&str> {
+        self.entries.get(steps_back).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests;
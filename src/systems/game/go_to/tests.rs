@@ -0,0 +1,105 @@
+use super::*;
+use crate::systems::game::map::format::EntityType;
+use std::collections::HashMap;
+
+fn entity(entity_type: EntityType, name: Option<&str>, position: (f32, f32, f32)) -> EntityData {
+    let mut properties = HashMap::new();
+    if let Some(name) = name {
+        properties.insert("name".to_string(), name.to_string());
+    }
+    EntityData {
+        id: Default::default(),
+        entity_type,
+        position,
+        properties,
+    }
+}
+
+fn sample_entities() -> Vec<EntityData> {
+    vec![
+        entity(EntityType::PlayerSpawn, None, (1.0, 1.0, 1.0)),
+        entity(EntityType::Npc, Some("Village Elder"), (4.0, 1.0, 9.0)),
+        entity(EntityType::Npc, Some("Elder"), (-3.0, 2.0, 5.0)),
+    ]
+}
+
+#[test]
+fn parses_coordinates_with_spaces_or_commas() {
+    let expected = Some(GoToTarget::Coordinates(Vec3::new(4.0, 2.5, -7.0)));
+    assert_eq!(GoToTarget::parse("4 2.5 -7"), expected);
+    assert_eq!(GoToTarget::parse(" 4, 2.5, -7 "), expected);
+    assert_eq!(GoToTarget::parse("4,2.5,-7"), expected);
+}
+
+#[test]
+fn anything_else_is_an_entity_name() {
+    assert_eq!(
+        GoToTarget::parse("Elder"),
+        Some(GoToTarget::Entity("Elder".to_string()))
+    );
+    assert_eq!(
+        GoToTarget::parse("4 2"),
+        Some(GoToTarget::Entity("4 2".to_string()))
+    );
+    assert_eq!(
+        GoToTarget::parse("Guard 2 3"),
+        Some(GoToTarget::Entity("Guard 2 3".to_string()))
+    );
+    assert_eq!(GoToTarget::parse("   "), None);
+}
+
+#[test]
+fn names_fall_back_to_the_entity_type() {
+    let entities = sample_entities();
+    assert_eq!(entity_display_name(&entities[0]), "PlayerSpawn");
+    assert_eq!(entity_display_name(&entities[1]), "Village Elder");
+}
+
+#[test]
+fn exact_name_matches_win_over_partial_ones() {
+    let index = EntityNameIndex::build(&sample_entities());
+
+    let found = index.find("elder").unwrap();
+    assert_eq!(found.entity, Some(2));
+    assert_eq!(found.position, Vec3::new(-3.0, 2.0, 5.0));
+
+    assert_eq!(index.find("VILLAGE").unwrap().entity, Some(1));
+    assert_eq!(index.find("spawn").unwrap().entity, Some(0));
+    assert!(index.find("dragon").is_none());
+    assert_eq!(
+        index.matches("elder"),
+        vec![("Elder", 2), ("Village Elder", 1)]
+    );
+}
+
+#[test]
+fn resolves_coordinates_without_an_entity() {
+    let index = EntityNameIndex::build(&sample_entities());
+    let target = GoToTarget::parse("1 2 3").unwrap();
+    assert_eq!(
+        index.resolve(&target),
+        Some(GoToDestination {
+            position: Vec3::new(1.0, 2.0, 3.0),
+            entity: None,
+        })
+    );
+}
+
+#[test]
+fn history_keeps_recent_unique_queries_first() {
+    let mut history = GoToHistory::default();
+    for i in 0..GO_TO_HISTORY_LEN + 3 {
+        history.record(&format!("{} 0 0", i));
+    }
+    assert_eq!(history.len(), GO_TO_HISTORY_LEN);
+
+    history.record(" 5 0 0 ");
+    history.record("");
+    assert_eq!(history.len(), GO_TO_HISTORY_LEN);
+    assert_eq!(history.get(0), Some("5 0 0"));
+    assert_eq!(history.entries().filter(|q| *q == "5 0 0").count(), 1);
+    assert_eq!(
+        history.get(1),
+        Some(&*format!("{} 0 0", GO_TO_HISTORY_LEN + 2))
+    );
+}
@@ -0,0 +1,104 @@
+//! Hazard voxels that hurt the player on contact.
+//!
+//! Voxel types with a [`VoxelType::hazard`] profile (water, lava) damage the
+//! player while they stand on or inside them. Damage repeats every
+//! [`Hazard::interval`] seconds of contact and goes through the health
+//! pipeline as a [`DamageEvent`].
+
+use bevy::prelude::*;
+
+use super::collision::hazard_contact;
+use super::components::{Player, SubVoxel, VoxelType};
+use super::health::{apply_damage, DamageEvent, DamageSource};
+use super::map::format::Hazard;
+use super::particles::{emit_particle_bursts, ParticleBurst};
+use super::resources::SpatialGrid;
+use crate::states::GameState;
+
+/// Cooldown between hazard damage ticks.
+///
+/// The cooldown keeps running after the player leaves a hazard, so hopping in
+/// and out cannot dodge the interval.
+#[derive(Resource, Default)]
+pub struct HazardExposure {
+    cooldown: f32,
+}
+
+impl HazardExposure {
+    /// Advance by `delta` seconds; returns true when `contact` should deal damage now.
+    pub fn tick(&mut self, contact: Option<Hazard>, delta: f32) -> bool {
+        self.cooldown = (self.cooldown - delta).max(0.0);
+        let Some(hazard) = contact else {
+            return false;
+        };
+        if self.cooldown > 0.0 {
+            return false;
+        }
+        self.cooldown = hazard.interval;
+        true
+    }
+}
+
+/// Damage the player while they touch a hazard voxel.
+pub fn apply_hazard_contact(
+    time: Res<Time>,
+    player: Option<Single<(&Transform, &Player)>>,
+    spatial_grid: Option<Res<SpatialGrid>>,
+    sub_voxels: Query<&SubVoxel, Without<Player>>,
+    mut exposure: ResMut<HazardExposure>,
+    mut damage: MessageWriter<DamageEvent>,
+    mut bursts: MessageWriter<ParticleBurst>,
+) {
+    let (Some(player), Some(spatial_grid)) = (player, spatial_grid) else {
+        return;
+    };
+    let (transform, player) = *player;
+
+    let contact = hazard_contact(
+        &spatial_grid,
+        &sub_voxels,
+        transform.translation,
+        player.radius,
+        player.half_height,
+    );
+    let hazard = contact.and_then(VoxelType::hazard);
+    if !exposure.tick(hazard, time.delta_secs()) {
+        return;
+    }
+    let (Some(voxel_type), Some(hazard)) = (contact, hazard) else {
+        return;
+    };
+
+    damage.write(DamageEvent {
+        amount: hazard.damage,
+        source: DamageSource::Hazard(voxel_type),
+        knockback: hazard.knockback,
+    });
+    if let Some(color) = voxel_type.tint() {
+        bursts.write(ParticleBurst {
+            position: transform.translation - Vec3::Y * player.half_height,
+            color,
+            count: 6,
+            speed: 1.5,
+            lifetime: 0.5,
+        });
+    }
+}
+
+/// Periodic damage from hazard voxels.
+pub struct HazardPlugin;
+
+impl Plugin for HazardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HazardExposure>().add_systems(
+            Update,
+            apply_hazard_contact
+                .before(apply_damage)
+                .before(emit_particle_bursts)
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
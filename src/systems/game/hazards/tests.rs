@@ -0,0 +1,49 @@
+use super::*;
+
+#[test]
+fn only_water_and_lava_are_hazards() {
+    assert!(VoxelType::Water.is_hazard());
+    assert!(VoxelType::Lava.is_hazard());
+    for safe in [
+        VoxelType::Air,
+        VoxelType::Grass,
+        VoxelType::Dirt,
+        VoxelType::Stone,
+        VoxelType::Glowstone,
+        VoxelType::Glass,
+    ] {
+        assert!(!safe.is_hazard());
+    }
+    let lava = VoxelType::Lava.hazard().unwrap();
+    let water = VoxelType::Water.hazard().unwrap();
+    assert!(lava.damage > water.damage);
+    assert!(lava.knockback > 0.0);
+    // Lava and glowstone glow
+    assert!(VoxelType::Lava.emissive().is_some());
+    assert!(VoxelType::Glowstone.emissive().is_some());
+    assert!(VoxelType::Water.emissive().is_none());
+}
+
+#[test]
+fn exposure_damages_immediately_then_every_interval() {
+    let lava = VoxelType::Lava.hazard();
+    let interval = lava.unwrap().interval;
+    let mut exposure = HazardExposure::default();
+
+    assert!(exposure.tick(lava, 0.016));
+    assert!(!exposure.tick(lava, interval * 0.5));
+    assert!(exposure.tick(lava, interval * 0.5));
+}
+
+#[test]
+fn leaving_a_hazard_does_not_reset_the_cooldown() {
+    let lava = VoxelType::Lava.hazard();
+    let interval = lava.unwrap().interval;
+    let mut exposure = HazardExposure::default();
+
+    assert!(exposure.tick(lava, 0.0));
+    assert!(!exposure.tick(None, interval * 0.25));
+    assert!(!exposure.tick(lava, interval * 0.25));
+    assert!(!exposure.tick(None, interval * 0.25));
+    assert!(exposure.tick(lava, interval * 0.25));
+}
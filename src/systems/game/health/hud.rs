@@ -0,0 +1,82 @@
+//! Health bar HUD: one segment per point of the player's maximum health.
+
+use bevy::prelude::*;
+
+use super::Health;
+use crate::systems::game::components::{GameEntity, Player};
+use crate::systems::game::hud_layout::HudLayout;
+
+const SEGMENT_FULL: Color = Color::srgb(0.85, 0.12, 0.15);
+const SEGMENT_EMPTY: Color = Color::srgba(0.15, 0.05, 0.05, 0.6);
+
+/// Root node of the health bar.
+#[derive(Component)]
+pub struct HealthHud;
+
+/// One segment of the health bar; lit while its index is below current health.
+#[derive(Component)]
+pub struct HealthSegment(pub u32);
+
+fn segment_color(index: u32, health: &Health) -> Color {
+    if index < health.current {
+        SEGMENT_FULL
+    } else {
+        SEGMENT_EMPTY
+    }
+}
+
+/// Spawn the health bar when a player with [`Health`] appears or the HUD
+/// layout changes, replacing any bar left from before.
+pub fn spawn_health_hud(
+    mut commands: Commands,
+    player: Option<Single<Ref<Health>, With<Player>>>,
+    layout: Res<HudLayout>,
+    existing: Query<Entity, With<HealthHud>>,
+) {
+    let Some(health) = player.filter(|health| health.is_added() || layout.is_changed()) else {
+        return;
+    };
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+
+    commands
+        .spawn((
+            Node {
+                column_gap: layout.px(4.0),
+                ..layout.anchored_node(layout.health_anchor)
+            },
+            HealthHud,
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            for index in 0..health.max {
+                parent.spawn((
+                    Node {
+                        width: layout.px(18.0),
+                        height: layout.px(18.0),
+                        border: UiRect::all(layout.px(2.0)),
+                        border_radius: BorderRadius::all(layout.px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(segment_color(index, &health)),
+                    BorderColor::all(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                    HealthSegment(index),
+                ));
+            }
+        });
+}
+
+/// Relight the health bar segments when the player's health changes.
+#[allow(clippy::type_complexity)]
+pub fn update_health_hud(
+    player: Option<Single<&Health, (With<Player>, Changed<Health>)>>,
+    mut segments: Query<(&HealthSegment, &mut BackgroundColor)>,
+) {
+    let Some(health) = player else {
+        return;
+    };
+    for (segment, mut color) in &mut segments {
+        *color = BackgroundColor(segment_color(segment.0, &health));
+    }
+}
@@ -0,0 +1,202 @@
+//! Player health and damage.
+//!
+//! Anything that hurts the player writes a [`DamageEvent`]: hazard voxels,
+//! long falls (see [`detect_fall_damage`]) and, once they spawn, enemies.
+//! [`apply_damage`] subtracts it from the player's [`Health`] and applies any
+//! knockback, and [`enter_game_over`] switches to [`GameState::GameOver`]
+//! when health runs out. The [`hud`] submodule draws the health bar.
+
+pub mod hud;
+
+use bevy::prelude::*;
+
+use super::components::{Player, VoxelType};
+use crate::states::GameState;
+
+/// Health the player spawns with.
+pub const PLAYER_MAX_HEALTH: u32 = 10;
+
+/// Height a fall may drop, in world units, before it hurts.
+pub const FALL_DAMAGE_THRESHOLD: f32 = 3.0;
+
+/// Extra fall height per point of damage beyond the threshold.
+pub const FALL_DAMAGE_STEP: f32 = 1.5;
+
+/// Falling below this height means the player left the world; it is lethal.
+pub const VOID_Y: f32 = -32.0;
+
+/// Hit points of a character.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Health {
+    /// Full health with the given maximum.
+    pub fn new(max: u32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Subtract `amount`, saturating at zero; returns the health actually lost.
+    pub fn take(&mut self, amount: u32) -> u32 {
+        let lost = amount.min(self.current);
+        self.current -= lost;
+        lost
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current == 0
+    }
+}
+
+/// What caused a [`DamageEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageSource {
+    /// Standing on or inside a hazard voxel
+    Hazard(VoxelType),
+    /// Landing from too high, or falling out of the world
+    Fall,
+    /// Hit by an enemy
+    // Constructed once enemies spawn from `EntityType::Enemy`.
+    #[allow(dead_code)]
+    Enemy,
+}
+
+/// Damage for landing after falling `height` world units.
+pub fn fall_damage(height: f32) -> u32 {
+    if height <= FALL_DAMAGE_THRESHOLD {
+        return 0;
+    }
+    ((height - FALL_DAMAGE_THRESHOLD) / FALL_DAMAGE_STEP).ceil() as u32
+}
+
+/// Highest point reached since the player last stood on the ground.
+#[derive(Resource, Default)]
+pub struct FallTracker {
+    peak: Option<f32>,
+}
+
+impl FallTracker {
+    /// Record the player at height `y`; returns the fall height on landing.
+    pub fn update(&mut self, y: f32, grounded: bool) -> Option<f32> {
+        if grounded {
+            return self.peak.take().map(|peak| (peak - y).max(0.0));
+        }
+        self.peak = Some(self.peak.map_or(y, |peak| peak.max(y)));
+        None
+    }
+}
+
+/// Damage dealt to the player.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub amount: u32,
+    pub source: DamageSource,
+    /// Upward velocity applied to the player (0.0 = none)
+    pub knockback: f32,
+}
+
+/// Apply pending damage and knockback to the player.
+pub fn apply_damage(
+    mut events: MessageReader<DamageEvent>,
+    player: Option<Single<(&mut Player, &mut Health)>>,
+) {
+    let Some(mut player) = player else {
+        events.clear();
+        return;
+    };
+    let (player, health) = &mut *player;
+
+    for event in events.read() {
+        if health.is_dead() {
+            break;
+        }
+        let lost = health.take(event.amount);
+        if event.knockback > 0.0 {
+            player.velocity.y = player.velocity.y.max(event.knockback);
+            player.is_grounded = false;
+        }
+        debug!(
+            "Player took {} damage from {:?} ({}/{})",
+            lost, event.source, health.current, health.max
+        );
+        if health.is_dead() {
+            info!("Player health depleted");
+        }
+    }
+}
+
+/// Hurt the player for long falls, and kill them for leaving the world.
+pub fn detect_fall_damage(
+    player: Option<Single<(&Transform, &Player, &Health)>>,
+    mut tracker: ResMut<FallTracker>,
+    mut damage: MessageWriter<DamageEvent>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    let (transform, player, health) = *player;
+    let y = transform.translation.y;
+
+    if y < VOID_Y {
+        if !health.is_dead() {
+            damage.write(DamageEvent {
+                amount: health.current,
+                source: DamageSource::Fall,
+                knockback: 0.0,
+            });
+        }
+        return;
+    }
+
+    let Some(height) = tracker.update(y, player.is_grounded) else {
+        return;
+    };
+    let amount = fall_damage(height);
+    if amount > 0 {
+        damage.write(DamageEvent {
+            amount,
+            source: DamageSource::Fall,
+            knockback: 0.0,
+        });
+    }
+}
+
+/// Switch to the game-over screen once the player's health runs out.
+#[allow(clippy::type_complexity)]
+pub fn enter_game_over(
+    player: Option<Single<&Health, (With<Player>, Changed<Health>)>>,
+    mut tracker: ResMut<FallTracker>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if player.is_some_and(|health| health.is_dead()) {
+        *tracker = FallTracker::default();
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// Player health, the damage pipeline and the health bar HUD.
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<DamageEvent>()
+            .init_resource::<FallTracker>()
+            .add_systems(
+                Update,
+                (
+                    detect_fall_damage,
+                    apply_damage,
+                    enter_game_over,
+                    hud::spawn_health_hud,
+                    hud::update_health_hud,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+
+#[test]
+fn take_saturates_at_zero() {
+    let mut health = Health::new(3);
+    assert_eq!(health.take(2), 2);
+    assert_eq!(health.current, 1);
+    assert!(!health.is_dead());
+    assert_eq!(health.take(5), 1);
+    assert!(health.is_dead());
+}
+
+#[test]
+fn damage_event_reduces_health_and_knocks_back() {
+    let mut app = App::new();
+    app.add_message::<DamageEvent>()
+        .add_systems(Update, apply_damage);
+    let player = app
+        .world_mut()
+        .spawn((
+            Player {
+                speed: 3.0,
+                velocity: Vec3::ZERO,
+                is_grounded: true,
+                radius: 0.2,
+                half_height: 0.4,
+                target_rotation: 0.0,
+                current_rotation: 0.0,
+                start_rotation: 0.0,
+                rotation_elapsed: 0.0,
+                rotation_duration: 0.2,
+            },
+            Health::new(PLAYER_MAX_HEALTH),
+        ))
+        .id();
+
+    app.world_mut().write_message(DamageEvent {
+        amount: 2,
+        source: DamageSource::Hazard(VoxelType::Lava),
+        knockback: 6.0,
+    });
+    app.update();
+
+    let health = app.world().get::<Health>(player).unwrap();
+    assert_eq!(health.current, PLAYER_MAX_HEALTH - 2);
+    let player = app.world().get::<Player>(player).unwrap();
+    assert_eq!(player.velocity.y, 6.0);
+    assert!(!player.is_grounded);
+}
+
+#[test]
+fn short_falls_are_free_and_long_falls_scale() {
+    assert_eq!(fall_damage(1.0), 0);
+    assert_eq!(fall_damage(FALL_DAMAGE_THRESHOLD), 0);
+    assert_eq!(fall_damage(FALL_DAMAGE_THRESHOLD + 0.1), 1);
+    assert_eq!(
+        fall_damage(FALL_DAMAGE_THRESHOLD + FALL_DAMAGE_STEP * 3.0),
+        3
+    );
+}
+
+#[test]
+fn fall_tracker_measures_from_peak_to_landing() {
+    let mut tracker = FallTracker::default();
+    assert_eq!(tracker.update(2.0, true), None);
+    // Jump up to 3.0, then fall to 0.5
+    assert_eq!(tracker.update(2.5, false), None);
+    assert_eq!(tracker.update(3.0, false), None);
+    assert_eq!(tracker.update(1.0, false), None);
+    assert_eq!(tracker.update(0.5, true), Some(2.5));
+    // Already landed: walking on adds nothing
+    assert_eq!(tracker.update(0.5, true), None);
+}
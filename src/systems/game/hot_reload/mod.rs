@@ -8,7 +8,10 @@ mod reload_handler;
 mod state;
 mod systems;
 
-pub use notifications::{show_reload_notification, update_reload_notifications};
+pub use notifications::{
+    cleanup_hot_reload_indicator, show_reload_notification, update_hot_reload_indicator,
+    update_reload_notifications,
+};
 pub use reload_handler::{handle_map_reload, restore_player_position};
 pub use state::HotReloadState;
 pub use systems::{
@@ -35,7 +38,8 @@ pub struct MapReloadedEvent {
     pub message: String,
 }
 
-/// Resource to pass the map path from main.rs to hot reload system
-/// This avoids referencing CommandLineMapPath which is in the binary crate
+/// Resource holding the file of the map currently in play
+/// Set by the game whenever a map finishes loading; `None` when the built-in
+/// default map is used because no file could be loaded
 #[derive(Resource, Default)]
 pub struct MapPathForHotReload(pub Option<PathBuf>);
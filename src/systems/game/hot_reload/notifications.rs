@@ -1,6 +1,8 @@
 //! Reload notification UI components and systems.
 
+use super::state::HotReloadState;
 use super::MapReloadedEvent;
+use crate::systems::localization::Localization;
 use bevy::prelude::*;
 
 /// Component for reload notification UI displayed in-game
@@ -74,3 +76,69 @@ pub fn update_reload_notifications(
         }
     }
 }
+
+/// Marker for the on-screen indicator shown while a map file is being watched
+#[derive(Component)]
+pub struct HotReloadIndicator;
+
+/// Text shown by the hot reload indicator for the watched `path`
+pub fn indicator_text(path: &std::path::Path, localization: &Localization) -> String {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_else(|| path.to_string_lossy());
+    localization.format("hot_reload.watching", &[("file", &name)])
+}
+
+/// System to show a small indicator in the corner while hot reload is active
+/// The indicator is hidden when nothing is watched or hot reload is disabled (Ctrl+H)
+pub fn update_hot_reload_indicator(
+    mut commands: Commands,
+    hot_reload: Res<HotReloadState>,
+    localization: Res<Localization>,
+    mut query: Query<(Entity, &mut Text), With<HotReloadIndicator>>,
+) {
+    let active_path = hot_reload
+        .watched_path()
+        .filter(|_| hot_reload.enabled && hot_reload.is_watching());
+
+    match (active_path, query.single_mut()) {
+        (Some(path), Ok((_, mut text))) => {
+            let wanted = indicator_text(path, &localization);
+            if text.0 != wanted {
+                text.0 = wanted;
+            }
+        }
+        (Some(path), Err(_)) => {
+            commands.spawn((
+                Text::new(indicator_text(path, &localization)),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.4, 0.9, 1.0, 0.8)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    ..default()
+                },
+                HotReloadIndicator,
+            ));
+        }
+        (None, Ok((entity, _))) => {
+            commands.entity(entity).despawn();
+        }
+        (None, Err(_)) => {}
+    }
+}
+
+/// System to remove the hot reload indicator when leaving InGame state
+pub fn cleanup_hot_reload_indicator(
+    mut commands: Commands,
+    query: Query<Entity, With<HotReloadIndicator>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -5,10 +5,13 @@ use bevy::prelude::*;
 
 use super::{MapReloadEvent, MapReloadedEvent};
 use crate::systems::game::components::{GameCamera, Npc, Player, SubVoxel};
+use crate::systems::game::doors::{Door, KeyItem};
 use crate::systems::game::map::loader::MapLoadProgress;
 use crate::systems::game::map::spawner::VoxelChunk;
 use crate::systems::game::map::{LoadedMapData, MapLoader};
 use crate::systems::game::resources::{GameInitialized, SpatialGrid};
+use crate::systems::game::speedrun::Goal;
+use crate::systems::game::teleporters::Teleporter;
 
 /// Resource to store player and camera state during reload
 /// This allows restoring the player and camera to their previous state after map respawn
@@ -42,6 +45,10 @@ pub struct ReloadDespawnQueries<'w, 's> {
     pub subvoxels: Query<'w, 's, Entity, With<SubVoxel>>,
     pub directional_lights: Query<'w, 's, Entity, With<DirectionalLight>>,
     pub cameras: Query<'w, 's, Entity, With<GameCamera>>,
+    pub doors: Query<'w, 's, Entity, With<Door>>,
+    pub key_items: Query<'w, 's, Entity, With<KeyItem>>,
+    pub teleporters: Query<'w, 's, Entity, With<Teleporter>>,
+    pub goals: Query<'w, 's, Entity, With<Goal>>,
 }
 
 /// System to handle map reload events
@@ -108,6 +115,15 @@ pub fn handle_map_reload(
                 for entity in despawn_queries.cameras.iter() {
                     commands.entity(entity).despawn();
                 }
+                for entity in despawn_queries
+                    .doors
+                    .iter()
+                    .chain(despawn_queries.key_items.iter())
+                    .chain(despawn_queries.teleporters.iter())
+                    .chain(despawn_queries.goals.iter())
+                {
+                    commands.entity(entity).despawn();
+                }
 
                 info!(
                     "Hot reload: despawned {} chunks, {} sub-voxels",
@@ -143,7 +143,6 @@ impl HotReloadState {
     }
 
     /// Check if currently watching a file
-    #[allow(dead_code)]
     pub fn is_watching(&self) -> bool {
         self.watcher.is_some() && self.watched_path.is_some()
     }
@@ -73,13 +73,13 @@ pub fn cleanup_hot_reload(mut hot_reload: ResMut<HotReloadState>) {
 }
 
 /// System to setup hot reload watcher when entering InGame state
-/// Takes the map path from CommandLineMapPath resource
+/// Watches the file of the currently loaded map (see `MapPathForHotReload`)
 pub fn setup_hot_reload_on_enter(
     mut hot_reload: ResMut<HotReloadState>,
-    cli_map_path: Option<Res<MapPathForHotReload>>,
+    map_path: Option<Res<MapPathForHotReload>>,
 ) {
-    // Only setup if a map path was specified
-    if let Some(map_path) = cli_map_path {
+    // Only setup if the loaded map came from a file
+    if let Some(map_path) = map_path {
         if let Some(path) = &map_path.0 {
             if let Err(e) = hot_reload.watch_file(path.clone()) {
                 warn!("Failed to setup hot reload: {}", e);
@@ -0,0 +1,146 @@
+//! HUD layout: the size of the HUD, a safe-area margin and the screen corner
+//! each HUD widget sits in.
+//!
+//! The safe area keeps widgets clear of the screen's edges, as a percentage
+//! of its width and height, for TVs that crop the picture (overscan). HUD
+//! scale multiplies the widgets' sizes on top of the UI text scale. Widgets
+//! read the layout when they are spawned and are spawned again when it
+//! changes.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Smallest HUD scale.
+pub const MIN_HUD_SCALE: f32 = 0.5;
+
+/// Largest HUD scale.
+pub const MAX_HUD_SCALE: f32 = 2.0;
+
+/// HUD scale change per settings step.
+pub const HUD_SCALE_STEP: f32 = 0.1;
+
+/// Largest safe-area margin, in percent of the screen.
+pub const MAX_SAFE_AREA: u32 = 10;
+
+/// Gap between a widget and the safe area's edge, in pixels at scale 1.
+const EDGE_GAP: f32 = 16.0;
+
+/// Screen corner a HUD widget is placed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HudAnchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl HudAnchor {
+    pub const ALL: [HudAnchor; 4] = [
+        HudAnchor::TopLeft,
+        HudAnchor::TopRight,
+        HudAnchor::BottomLeft,
+        HudAnchor::BottomRight,
+    ];
+
+    /// The anchor `delta` steps along [`HudAnchor::ALL`], wrapping around.
+    pub fn cycled(self, delta: i32) -> Self {
+        let len = Self::ALL.len() as i32;
+        let index = Self::ALL.iter().position(|&a| a == self).unwrap_or(0) as i32;
+        Self::ALL[(index + delta).rem_euclid(len) as usize]
+    }
+
+    pub fn is_top(self) -> bool {
+        matches!(self, HudAnchor::TopLeft | HudAnchor::TopRight)
+    }
+
+    pub fn is_left(self) -> bool {
+        matches!(self, HudAnchor::TopLeft | HudAnchor::BottomLeft)
+    }
+}
+
+/// HUD layout options, set from the settings screen.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HudLayout {
+    /// Multiplier on HUD widget sizes, [`MIN_HUD_SCALE`] to [`MAX_HUD_SCALE`]
+    pub scale: f32,
+    /// Margin kept clear at the screen's edges, in percent
+    pub safe_area: u32,
+    /// Corner of the health bar
+    pub health_anchor: HudAnchor,
+    /// Corner of the speedrun timer
+    pub timer_anchor: HudAnchor,
+}
+
+impl Default for HudLayout {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            safe_area: 0,
+            health_anchor: HudAnchor::BottomLeft,
+            timer_anchor: HudAnchor::TopRight,
+        }
+    }
+}
+
+impl HudLayout {
+    /// Move the HUD scale `delta` steps, staying in range.
+    pub fn step_scale(&mut self, delta: i32) {
+        let scale = self.scale + delta as f32 * HUD_SCALE_STEP;
+        self.scale = ((scale * 10.0).round() / 10.0).clamp(MIN_HUD_SCALE, MAX_HUD_SCALE);
+    }
+
+    /// Move the safe-area margin `delta` percent, staying in range.
+    pub fn step_safe_area(&mut self, delta: i32) {
+        self.safe_area = (self.safe_area as i32 + delta).clamp(0, MAX_SAFE_AREA as i32) as u32;
+    }
+
+    /// This layout with out-of-range values, such as from a hand-edited
+    /// settings file, pulled back into range.
+    pub fn clamped(self) -> Self {
+        Self {
+            scale: self.scale.clamp(MIN_HUD_SCALE, MAX_HUD_SCALE),
+            safe_area: self.safe_area.min(MAX_SAFE_AREA),
+            ..self
+        }
+    }
+
+    /// `size` pixels at the HUD scale.
+    pub fn px(&self, size: f32) -> Val {
+        Val::Px(size * self.scale)
+    }
+
+    /// Absolutely positioned node in `anchor`'s corner, inside the safe area.
+    pub fn anchored_node(&self, anchor: HudAnchor) -> Node {
+        let horizontal = Val::Vw(self.safe_area as f32);
+        let vertical = Val::Vh(self.safe_area as f32);
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            margin: UiRect::all(self.px(EDGE_GAP)),
+            ..default()
+        };
+        if anchor.is_top() {
+            node.top = vertical;
+        } else {
+            node.bottom = vertical;
+        }
+        if anchor.is_left() {
+            node.left = horizontal;
+        } else {
+            node.right = horizontal;
+        }
+        node
+    }
+}
+
+/// Plugin that holds the HUD layout.
+pub struct HudLayoutPlugin;
+
+impl Plugin for HudLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HudLayout>();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn anchors_cycle_through_every_corner() {
+    assert_eq!(HudAnchor::TopLeft.cycled(1), HudAnchor::TopRight);
+    assert_eq!(HudAnchor::TopLeft.cycled(-1), HudAnchor::BottomRight);
+    assert_eq!(HudAnchor::BottomLeft.cycled(4), HudAnchor::BottomLeft);
+}
+
+#[test]
+fn anchored_node_insets_by_the_safe_area() {
+    let layout = HudLayout {
+        safe_area: 5,
+        ..default()
+    };
+    let node = layout.anchored_node(HudAnchor::BottomRight);
+    assert_eq!(node.bottom, Val::Vh(5.0));
+    assert_eq!(node.right, Val::Vw(5.0));
+    assert_eq!(node.top, Val::Auto);
+    assert_eq!(node.left, Val::Auto);
+    assert_eq!(node.margin.left, Val::Px(16.0));
+
+    let node = layout.anchored_node(HudAnchor::TopLeft);
+    assert_eq!(node.top, Val::Vh(5.0));
+    assert_eq!(node.left, Val::Vw(5.0));
+}
+
+#[test]
+fn steps_stay_in_range() {
+    let mut layout = HudLayout::default();
+    layout.step_scale(5);
+    assert_eq!(layout.scale, 1.5);
+    assert_eq!(layout.px(10.0), Val::Px(15.0));
+    layout.step_scale(20);
+    assert_eq!(layout.scale, MAX_HUD_SCALE);
+
+    layout.step_safe_area(-1);
+    assert_eq!(layout.safe_area, 0);
+    layout.step_safe_area(50);
+    assert_eq!(layout.safe_area, MAX_SAFE_AREA);
+}
+
+#[test]
+fn loaded_layouts_are_clamped_and_filled_in() {
+    let layout: HudLayout = ron::from_str("(scale: 9.0, safe_area: 40)").unwrap();
+    let layout = layout.clamped();
+    assert_eq!(layout.scale, MAX_HUD_SCALE);
+    assert_eq!(layout.safe_area, MAX_SAFE_AREA);
+    assert_eq!(layout.timer_anchor, HudAnchor::TopRight);
+}
@@ -8,6 +8,7 @@
 //! - Light source synchronization
 
 use super::components::{CollisionBox, FlickerLight, LightSource, Player, PlayerFlashlight};
+use super::debug_boxes::DebugBoxView;
 use super::gamepad::PlayerInput;
 use bevy::prelude::*;
 use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
@@ -27,18 +28,23 @@ pub fn handle_escape_key(
 
 /// System that toggles the visibility of the collision box.
 ///
-/// When the 'C' key is pressed, the collision box visibility is toggled
-/// between visible and hidden. This is useful for debugging collision detection.
+/// When the 'C' key is pressed, the player's collision box and the collision
+/// bounds of the sub-voxels around them are shown or hidden together. This is
+/// useful for debugging collision detection.
 pub fn toggle_collision_box(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut view: ResMut<DebugBoxView>,
     mut collision_box_query: Query<&mut Visibility, With<CollisionBox>>,
 ) {
     if keyboard_input.just_pressed(KeyCode::KeyC) {
-        for mut visibility in &mut collision_box_query {
-            *visibility = match *visibility {
-                Visibility::Hidden => Visibility::Visible,
-                _ => Visibility::Hidden,
-            };
+        view.collision = !view.collision;
+        let visibility = if view.collision {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        for mut box_visibility in &mut collision_box_query {
+            *box_visibility = visibility;
         }
     }
 }
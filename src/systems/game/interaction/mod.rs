@@ -0,0 +1,89 @@
+//! Picking what the interact button acts on.
+//!
+//! Entities the player can use — NPCs with scripted actions, doors — carry
+//! [`Interactable`]. When interact is pressed, [`find_interaction_target`]
+//! picks the nearest one within [`INTERACTION_RANGE`] (horizontal distance)
+//! so a single press never reaches two entities at once.
+//!
+//! The press is resolved in the physics step that receives it, after a
+//! replay's recorded input is applied, so replays open the same doors at the
+//! same moment. The target then stays set for the rest of the frame, for
+//! systems in `Update` to act on.
+
+use bevy::prelude::*;
+
+use super::components::Player;
+use super::gamepad::PlayerInput;
+use super::npc_labels::INTERACTION_RANGE;
+
+/// Marks an entity the player can interact with.
+#[derive(Component, Debug, Default)]
+pub struct Interactable;
+
+/// The entity interact reaches this frame; `None` unless a physics step this
+/// frame had interact pressed near an [`Interactable`].
+#[derive(Resource, Debug, Default)]
+pub struct InteractionTarget(pub Option<Entity>);
+
+/// Distance between two points on the XZ plane.
+pub fn horizontal_distance(a: Vec3, b: Vec3) -> f32 {
+    Vec2::new(a.x - b.x, a.z - b.z).length()
+}
+
+/// The candidate nearest to `player_pos` within [`INTERACTION_RANGE`].
+pub fn nearest_interactable(
+    player_pos: Vec3,
+    candidates: impl IntoIterator<Item = (Entity, Vec3)>,
+) -> Option<Entity> {
+    candidates
+        .into_iter()
+        .map(|(entity, pos)| (entity, horizontal_distance(pos, player_pos)))
+        .filter(|(_, distance)| *distance <= INTERACTION_RANGE)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Pick the entity interact reaches when a physics step has it pressed.
+pub fn find_interaction_target(
+    player: Option<Single<&Transform, With<Player>>>,
+    input: Res<PlayerInput>,
+    interactables: Query<(Entity, &GlobalTransform), With<Interactable>>,
+    mut target: ResMut<InteractionTarget>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    if !input.interact_pressed {
+        return;
+    }
+    if let Some(entity) = nearest_interactable(
+        player.translation,
+        interactables
+            .iter()
+            .map(|(entity, transform)| (entity, transform.translation())),
+    ) {
+        target.0 = Some(entity);
+    }
+}
+
+/// Forget the target once the frame's systems have acted on it.
+pub fn clear_interaction_target(mut target: ResMut<InteractionTarget>) {
+    target.0 = None;
+}
+
+/// Plugin that holds the [`InteractionTarget`] and clears it every frame.
+///
+/// [`find_interaction_target`] is added by the app with the other per-step
+/// input systems in `FixedUpdate`; systems acting on the target run in
+/// `Update`, after every step of the frame.
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionTarget>()
+            .add_systems(PostUpdate, clear_interaction_target);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,22 @@
+use super::*;
+
+#[test]
+fn the_nearest_entity_in_range_is_picked() {
+    let near = Entity::from_raw_u32(1).unwrap();
+    let far = Entity::from_raw_u32(2).unwrap();
+    let candidates = [
+        (far, Vec3::new(2.5, 0.0, 0.0)),
+        (near, Vec3::new(0.0, 0.0, 1.0)),
+    ];
+    assert_eq!(nearest_interactable(Vec3::ZERO, candidates), Some(near));
+}
+
+#[test]
+fn height_does_not_count_towards_range() {
+    let above = Entity::from_raw_u32(1).unwrap();
+    let candidates = [(above, Vec3::new(1.0, 10.0, 0.0))];
+    assert_eq!(nearest_interactable(Vec3::ZERO, candidates), Some(above));
+
+    let out_of_range = [(above, Vec3::new(INTERACTION_RANGE + 0.5, 0.0, 0.0))];
+    assert_eq!(nearest_interactable(Vec3::ZERO, out_of_range), None);
+}
@@ -224,22 +224,19 @@ fn build_occupied_voxel_set(
 ) -> HashSet<IVec3> {
     let mut occupied = HashSet::new();
 
-    // Iterate through all cells in the spatial grid
-    for (_, entities) in spatial_grid.cells.iter() {
-        for entity in entities {
-            if let Ok(sub_voxel) = sub_voxels.get(*entity) {
-                let (min, _) = sub_voxel.bounds;
-                // Convert sub-voxel position to parent voxel position
-                // Sub-voxels within a voxel have positions like (x + 0.0625, y + 0.0625, z + 0.0625)
-                // to (x + 0.9375, y + 0.9375, z + 0.9375)
-                // The parent voxel is at floor(center)
-                let voxel_pos = IVec3::new(
-                    min.x.floor() as i32,
-                    min.y.floor() as i32,
-                    min.z.floor() as i32,
-                );
-                occupied.insert(voxel_pos);
-            }
+    // Iterate through every sub-voxel in the spatial grid
+    for (entity, (min, _)) in spatial_grid.iter() {
+        if sub_voxels.contains(entity) {
+            // Convert sub-voxel position to parent voxel position
+            // Sub-voxels within a voxel have positions like (x + 0.0625, y + 0.0625, z + 0.0625)
+            // to (x + 0.9375, y + 0.9375, z + 0.9375)
+            // The parent voxel is at floor(center)
+            let voxel_pos = IVec3::new(
+                min.x.floor() as i32,
+                min.y.floor() as i32,
+                min.z.floor() as i32,
+            );
+            occupied.insert(voxel_pos);
         }
     }
 
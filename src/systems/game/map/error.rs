@@ -13,6 +13,14 @@ pub enum MapLoadError {
     #[error("Failed to parse map data: {0}")]
     ParseError(#[from] ron::error::SpannedError),
 
+    /// Failed to encode or decode a binary map.
+    #[error("Failed to process binary map data: {0}")]
+    BinaryError(#[from] bincode::Error),
+
+    /// Failed to serialize or compress a map for saving.
+    #[error("Failed to encode map: {0}")]
+    EncodeError(String),
+
     /// Map validation failed.
     #[error("Map validation failed: {0}")]
     ValidationError(String),
@@ -27,6 +27,11 @@ pub struct CameraData {
     /// When absent, the engine default (~60°) is used. Recommended range: 5–150.
     #[serde(default)]
     pub fov_degrees: Option<f32>,
+    /// Whether walls between the camera and the player are cut away while the
+    /// player is inside a roofed interior.
+    /// When absent, the engine default (`true`) is used.
+    #[serde(default)]
+    pub interior_cutaway: Option<bool>,
 }
 
 impl Default for CameraData {
@@ -38,9 +43,86 @@ impl Default for CameraData {
             follow_speed: None,
             rotation_speed: None,
             fov_degrees: None,
+            interior_cutaway: None,
+        }
+    }
+}
+
+/// Easing curve of a camera keyframe's move.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CameraEasing {
+    Linear,
+    /// Starts slow
+    EaseIn,
+    /// Ends slow
+    EaseOut,
+    /// Starts and ends slow
+    #[default]
+    EaseInOut,
+}
+
+impl CameraEasing {
+    /// All easings, in the order the editor lists them.
+    pub const ALL: [CameraEasing; 4] = [
+        CameraEasing::Linear,
+        CameraEasing::EaseIn,
+        CameraEasing::EaseOut,
+        CameraEasing::EaseInOut,
+    ];
+
+    /// Name shown in the editor.
+    pub fn label(self) -> &'static str {
+        match self {
+            CameraEasing::Linear => "Linear",
+            CameraEasing::EaseIn => "Ease in",
+            CameraEasing::EaseOut => "Ease out",
+            CameraEasing::EaseInOut => "Ease in-out",
+        }
+    }
+
+    /// Eased progress for linear progress `t` in [0, 1].
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            CameraEasing::Linear => t,
+            CameraEasing::EaseIn => t * t,
+            CameraEasing::EaseOut => t * (2.0 - t),
+            CameraEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
         }
     }
 }
 
+/// One stop of a camera sequence.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CameraKeyframeData {
+    /// Camera position in world space (x, y, z).
+    pub position: (f32, f32, f32),
+    /// World-space point the camera looks at (x, y, z).
+    pub look_at: (f32, f32, f32),
+    /// Seconds taken to move here from the previous keyframe (or, for the
+    /// first keyframe, from wherever the camera was).
+    pub duration: f32,
+    /// How the move to this keyframe speeds up and slows down.
+    #[serde(default)]
+    pub easing: CameraEasing,
+}
+
+/// A named, keyframed camera path played by the `cinematic` action.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CameraSequenceData {
+    pub name: String,
+    pub keyframes: Vec<CameraKeyframeData>,
+}
+
+impl CameraSequenceData {
+    /// Total length of the sequence in seconds.
+    pub fn duration(&self) -> f32 {
+        self.keyframes
+            .iter()
+            .map(|keyframe| keyframe.duration)
+            .sum()
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -72,3 +72,50 @@ fn camera_data_default_impl_has_none_optionals() {
     assert!(cd.rotation_speed.is_none());
     assert!(cd.fov_degrees.is_none());
 }
+
+#[test]
+fn camera_data_interior_cutaway_defaults_to_none() {
+    let ron = r#"(
+        position: (1.0, 2.0, 3.0),
+        look_at: (0.0, 0.0, 0.0),
+        rotation_offset: 0.0,
+    )"#;
+    let cd: CameraData = ron::from_str(ron).expect("parse failed");
+    assert_eq!(cd.interior_cutaway, None);
+
+    let ron = r#"(
+        position: (1.0, 2.0, 3.0),
+        look_at: (0.0, 0.0, 0.0),
+        rotation_offset: 0.0,
+        interior_cutaway: Some(false),
+    )"#;
+    let cd: CameraData = ron::from_str(ron).expect("parse failed");
+    assert_eq!(cd.interior_cutaway, Some(false));
+}
+
+#[test]
+fn camera_easings_start_at_zero_and_end_at_one() {
+    for easing in CameraEasing::ALL {
+        assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+        assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+        assert_eq!(easing.apply(2.0), 1.0, "{:?} clamps", easing);
+    }
+    assert!(CameraEasing::EaseIn.apply(0.5) < 0.5);
+    assert!(CameraEasing::EaseOut.apply(0.5) > 0.5);
+    assert_eq!(CameraEasing::EaseInOut.apply(0.5), 0.5);
+}
+
+#[test]
+fn camera_sequence_easing_defaults_to_ease_in_out() {
+    let ron = r#"(
+        name: "intro",
+        keyframes: [
+            (position: (0.0, 5.0, 0.0), look_at: (1.0, 0.0, 1.0), duration: 2.0),
+            (position: (4.0, 5.0, 0.0), look_at: (1.0, 0.0, 1.0), duration: 1.5, easing: Linear),
+        ],
+    )"#;
+    let sequence: CameraSequenceData = ron::from_str(ron).expect("parse failed");
+    assert_eq!(sequence.keyframes[0].easing, CameraEasing::EaseInOut);
+    assert_eq!(sequence.keyframes[1].easing, CameraEasing::Linear);
+    assert_eq!(sequence.duration(), 3.5);
+}
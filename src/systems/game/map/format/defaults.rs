@@ -1,8 +1,8 @@
 //! Default map generation for testing and fallback.
 
 use super::{
-    CameraData, EntityData, EntityType, LightingData, MapData, MapMetadata, SubVoxelPattern,
-    VoxelData, WorldData,
+    CameraData, EdgeData, EntityData, EntityId, EntityType, LightingData, MapData, MapMetadata,
+    MeshingData, SubVoxelPattern, VoxelData, WeatherData, WorldData,
 };
 use crate::systems::game::components::VoxelType;
 use std::collections::HashMap;
@@ -22,16 +22,24 @@ impl MapData {
                 width: 4,
                 height: 3,
                 depth: 4,
-                voxels: create_default_voxels(),
+                voxels: create_default_voxels().into(),
+                voxel_regions: Vec::new(),
             },
             entities: vec![EntityData {
+                id: EntityId(1),
                 entity_type: EntityType::PlayerSpawn,
                 position: (1.5, 0.5, 1.5),
                 properties: HashMap::new(),
             }],
             lighting: LightingData::default(),
+            weather: WeatherData::default(),
+            edges: EdgeData::default(),
+            meshing: MeshingData::default(),
             camera: CameraData::default(),
             orientations: Vec::new(),
+            voxel_groups: Vec::new(),
+            camera_sequences: Vec::new(),
+            editor_metadata: None,
             custom_properties: HashMap::new(),
         }
     }
@@ -47,6 +55,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (1, 0, 0),
@@ -54,6 +63,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (2, 0, 0),
@@ -61,6 +71,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (3, 0, 0),
@@ -68,6 +79,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         // Floor layer - row 1
         VoxelData {
@@ -76,6 +88,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (1, 0, 1),
@@ -83,6 +96,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (2, 0, 1),
@@ -90,6 +104,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (3, 0, 1),
@@ -97,6 +112,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         // Floor layer - row 2
         VoxelData {
@@ -105,6 +121,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (1, 0, 2),
@@ -112,6 +129,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (2, 0, 2),
@@ -119,6 +137,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (3, 0, 2),
@@ -126,6 +145,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         // Floor layer - row 3
         VoxelData {
@@ -134,6 +154,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (1, 0, 3),
@@ -141,6 +162,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (2, 0, 3),
@@ -148,6 +170,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (3, 0, 3),
@@ -155,6 +178,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Full),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         // Corner pillars
         VoxelData {
@@ -163,6 +187,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Pillar),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (0, 1, 3),
@@ -170,6 +195,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Pillar),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (3, 1, 0),
@@ -177,6 +203,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Pillar),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (3, 1, 3),
@@ -184,6 +211,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Pillar),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         // Platforms
         VoxelData {
@@ -192,6 +220,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::PlatformXZ),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         VoxelData {
             pos: (2, 1, 2),
@@ -199,6 +228,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::PlatformXZ),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
         // Staircase
         VoxelData {
@@ -207,6 +237,7 @@ fn create_default_voxels() -> Vec<VoxelData> {
             pattern: Some(SubVoxelPattern::Staircase),
             rotation: None,
             rotation_state: None,
+            group: None,
         },
     ]
 }
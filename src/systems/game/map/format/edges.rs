@@ -0,0 +1,60 @@
+//! Edge rendering configuration structures.
+
+use serde::{Deserialize, Serialize};
+
+/// How voxel edges are drawn.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeStyle {
+    /// Faces are flat color, edges aren't marked
+    #[default]
+    None,
+    /// A thin dark line along every voxel edge
+    Lines,
+    /// Faces round off towards their edges and catch the light there
+    Bevel,
+}
+
+impl EdgeStyle {
+    /// All edge styles, in display order.
+    pub const ALL: [EdgeStyle; 3] = [Self::None, Self::Lines, Self::Bevel];
+
+    /// Human-readable label for UI.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Lines => "Lines",
+            Self::Bevel => "Bevel",
+        }
+    }
+}
+
+/// Edge rendering configuration for the map.
+///
+/// Every field is optional in RON; a map without an `edges` section has flat
+/// faces.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct EdgeData {
+    pub style: EdgeStyle,
+    /// How dark lines are, or how strongly bevels bend the light (0.0 to 1.0)
+    pub strength: f32,
+    /// Width of the edge band as a fraction of a voxel (0.0 to 0.5)
+    pub width: f32,
+}
+
+impl Default for EdgeData {
+    fn default() -> Self {
+        Self {
+            style: EdgeStyle::None,
+            strength: 0.35,
+            width: 0.06,
+        }
+    }
+}
+
+impl EdgeData {
+    /// Whether edges show at all.
+    pub fn is_visible(&self) -> bool {
+        self.style != EdgeStyle::None && self.strength > 0.0 && self.width > 0.0
+    }
+}
@@ -0,0 +1,121 @@
+//! Map editor data saved with the map.
+//!
+//! The editor keeps things like construction guides and locked areas in the
+//! map file so they travel with it, but they mean nothing to the game: the
+//! spawner never reads the `editor_metadata` section. The editor owns its
+//! schema and bumps [`EDITOR_METADATA_VERSION`] when the layout changes.
+//!
+//! Like `voxel_regions`, the section takes no space in binary maps, which
+//! are for shipping, so binary files written before it existed still load.
+
+use super::{MapData, EDITOR_GUIDES_KEY, EDITOR_LOCKS_KEY};
+use serde::{Deserialize, Serialize};
+
+/// Current version of the [`EditorMetadata`] schema.
+pub const EDITOR_METADATA_VERSION: u32 = 1;
+
+/// Editor-only data of a map.
+///
+/// Every field is optional in RON. Entries use the editor's own text forms,
+/// e.g. `"plane x 4"` for a guide or `"layer 3"` for a locked area.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct EditorMetadata {
+    /// Schema version the section was written with
+    pub version: u32,
+    /// Construction guides
+    pub guides: Vec<String>,
+    /// Areas locked against editing
+    pub locked_areas: Vec<String>,
+}
+
+impl Default for EditorMetadata {
+    fn default() -> Self {
+        Self {
+            version: EDITOR_METADATA_VERSION,
+            guides: Vec::new(),
+            locked_areas: Vec::new(),
+        }
+    }
+}
+
+impl EditorMetadata {
+    /// Whether the section holds nothing worth saving.
+    pub fn is_empty(&self) -> bool {
+        self.guides.is_empty() && self.locked_areas.is_empty()
+    }
+
+    /// Whether this editor understands the section's schema version.
+    pub fn is_supported(&self) -> bool {
+        self.version <= EDITOR_METADATA_VERSION
+    }
+}
+
+impl MapData {
+    /// The map's editor data, or an empty section if it has none.
+    pub fn editor_metadata(&self) -> EditorMetadata {
+        self.editor_metadata.clone().unwrap_or_default()
+    }
+
+    /// Change the map's editor data with `edit`, dropping the section when
+    /// it ends up empty.
+    pub fn edit_editor_metadata(&mut self, edit: impl FnOnce(&mut EditorMetadata)) {
+        let mut metadata = self.editor_metadata();
+        edit(&mut metadata);
+        self.editor_metadata = (!metadata.is_empty()).then_some(metadata);
+    }
+}
+
+/// Move editor data kept in custom properties by older editors into the
+/// `editor_metadata` section.
+pub fn migrate_editor_properties(map: &mut MapData) {
+    let guides = map.custom_properties.remove(EDITOR_GUIDES_KEY);
+    let locks = map.custom_properties.remove(EDITOR_LOCKS_KEY);
+    if guides.is_none() && locks.is_none() {
+        return;
+    }
+    let split = |value: Option<String>| -> Vec<String> {
+        value
+            .iter()
+            .flat_map(|value| value.split(';'))
+            .filter(|entry| !entry.trim().is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (guides, locks) = (split(guides), split(locks));
+    map.edit_editor_metadata(|metadata| {
+        metadata.guides.extend(guides);
+        metadata.locked_areas.extend(locks);
+    });
+}
+
+/// Serde helpers that keep `editor_metadata` out of non-text formats, so the
+/// binary encoding of `MapData` doesn't change.
+pub(super) mod text_only {
+    use super::EditorMetadata;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        metadata: &Option<EditorMetadata>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            metadata.serialize(serializer)
+        } else {
+            serializer.serialize_unit()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<EditorMetadata>, D::Error> {
+        if deserializer.is_human_readable() {
+            Option::deserialize(deserializer)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
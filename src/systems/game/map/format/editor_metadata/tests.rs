@@ -0,0 +1,66 @@
+use super::*;
+
+fn map_with_guides(guides: &[&str]) -> MapData {
+    let mut map = MapData::empty_map();
+    map.edit_editor_metadata(|metadata| {
+        metadata.guides = guides.iter().map(|guide| guide.to_string()).collect();
+    });
+    map
+}
+
+#[test]
+fn test_empty_section_is_dropped() {
+    let mut map = map_with_guides(&["plane x 4"]);
+    assert_eq!(map.editor_metadata().version, EDITOR_METADATA_VERSION);
+
+    map.edit_editor_metadata(|metadata| metadata.guides.clear());
+    assert!(map.editor_metadata.is_none());
+}
+
+#[test]
+fn test_section_round_trips_through_ron() {
+    let map = map_with_guides(&["plane x 4", "line y 3 0 -2"]);
+    let text = ron::to_string(&map).unwrap();
+    assert!(text.contains("editor_metadata"));
+
+    let loaded: MapData = ron::from_str(&text).unwrap();
+    assert_eq!(loaded.editor_metadata, map.editor_metadata);
+
+    let text = ron::to_string(&MapData::empty_map()).unwrap();
+    assert!(!text.contains("editor_metadata"));
+}
+
+#[test]
+fn test_missing_fields_and_newer_versions_still_load() {
+    let metadata: EditorMetadata = ron::from_str("(locked_areas: [\"layer 3\"])").unwrap();
+    assert_eq!(metadata.version, EDITOR_METADATA_VERSION);
+    assert!(metadata.guides.is_empty());
+    assert!(metadata.is_supported());
+
+    let metadata: EditorMetadata = ron::from_str("(version: 99, notes: [\"later\"])").unwrap();
+    assert!(!metadata.is_supported());
+}
+
+#[test]
+fn test_migrates_custom_properties_into_section() {
+    let mut map = MapData::empty_map();
+    map.custom_properties.insert(
+        EDITOR_GUIDES_KEY.to_string(),
+        "plane x 4;line y 3 0 -2".to_string(),
+    );
+    map.custom_properties
+        .insert(EDITOR_LOCKS_KEY.to_string(), "layer 3".to_string());
+    map.custom_properties
+        .insert("mytool:key".to_string(), "kept".to_string());
+
+    migrate_editor_properties(&mut map);
+
+    let metadata = map.editor_metadata();
+    assert_eq!(metadata.guides, vec!["plane x 4", "line y 3 0 -2"]);
+    assert_eq!(metadata.locked_areas, vec!["layer 3"]);
+    assert_eq!(map.custom_properties.len(), 1);
+
+    let mut plain = MapData::empty_map();
+    migrate_editor_properties(&mut plain);
+    assert!(plain.editor_metadata.is_none());
+}
@@ -3,9 +3,36 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Stable identifier of an entity within its map.
+///
+/// Unlike an entity's index in `MapData::entities`, the id survives other
+/// entities being added, removed or reordered. `0` means "not yet assigned";
+/// loading a map gives every entity a unique non-zero id (see
+/// `MapData::assign_entity_ids`).
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(transparent)]
+pub struct EntityId(pub u32);
+
+impl EntityId {
+    pub fn is_assigned(self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl std::fmt::Display for EntityId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
 /// Entity spawn data.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EntityData {
+    /// Stable id, unique within the map
+    #[serde(default)]
+    pub id: EntityId,
     /// Type of entity to spawn
     pub entity_type: EntityType,
     /// World position (x, y, z)
@@ -23,7 +50,7 @@ pub struct EntityData {
 }
 
 /// Types of entities that can be spawned.
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum EntityType {
     /// Player spawn point
     PlayerSpawn,
@@ -37,4 +64,10 @@ pub enum EntityType {
     Trigger,
     /// Point light source (omnidirectional)
     LightSource,
+    /// Door that opens and closes by swapping voxel groups
+    Door,
+    /// Teleporter that sends the player to another teleporter
+    Teleporter,
+    /// Finish line that stops the speedrun timer
+    Goal,
 }
@@ -0,0 +1,38 @@
+//! Chunk meshing configuration structures.
+
+use serde::{Deserialize, Serialize};
+
+/// How chunk meshes merge faces of different colors.
+///
+/// Every field is optional in RON; a map without a `meshing` section only
+/// merges faces of the same color.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct MeshingData {
+    /// Largest difference in any color channel (0.0 to 1.0) for two faces to
+    /// still merge into one quad, taking the first face's color. 0.0 turns
+    /// color quantization off.
+    pub color_tolerance: f32,
+    /// First LOD level whose meshes are quantized (0 is full detail)
+    pub from_lod: usize,
+}
+
+impl Default for MeshingData {
+    fn default() -> Self {
+        Self {
+            color_tolerance: 0.0,
+            from_lod: 1,
+        }
+    }
+}
+
+impl MeshingData {
+    /// Color tolerance the meshes of `lod_level` are built with.
+    pub fn tolerance_at(&self, lod_level: usize) -> f32 {
+        if lod_level >= self.from_lod {
+            self.color_tolerance
+        } else {
+            0.0
+        }
+    }
+}
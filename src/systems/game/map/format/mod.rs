@@ -2,17 +2,28 @@
 
 mod camera;
 mod defaults;
+mod edges;
+mod editor_metadata;
 mod entities;
 mod lighting;
+mod meshing;
 mod metadata;
 mod patterns;
 mod rotation;
+mod stress;
+mod voxel_palette;
+mod voxel_regions;
+mod voxel_store;
 mod voxel_type;
+mod weather;
 mod world;
 
-pub use camera::CameraData;
-pub use entities::{EntityData, EntityType};
+pub use camera::{CameraData, CameraEasing, CameraKeyframeData, CameraSequenceData};
+pub use edges::{EdgeData, EdgeStyle};
+pub use editor_metadata::{migrate_editor_properties, EditorMetadata};
+pub use entities::{EntityData, EntityId, EntityType};
 pub use lighting::LightingData;
+pub use meshing::MeshingData;
 pub use metadata::MapMetadata;
 pub use patterns::SubVoxelPattern;
 pub use rotation::{
@@ -20,8 +31,12 @@ pub use rotation::{
     is_valid_rotation_matrix, migrate_legacy_rotations, multiply_matrices,
     normalise_staircase_variants, world_dir_to_local, OrientationMatrix, IDENTITY,
 };
-pub use voxel_type::VoxelType;
-pub use world::{VoxelData, WorldData};
+pub use voxel_palette::{pack_voxel_chunks, unpack_voxel_chunks, VoxelChunk};
+pub use voxel_regions::VoxelRegion;
+pub use voxel_store::VoxelStore;
+pub use voxel_type::{Hazard, VoxelFace, VoxelType};
+pub use weather::{Precipitation, WeatherData};
+pub use world::{VoxelData, VoxelGroupData, WorldData};
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -37,6 +52,15 @@ pub struct MapData {
     pub entities: Vec<EntityData>,
     /// Lighting configuration
     pub lighting: LightingData,
+    /// Weather configuration (precipitation, fog, wetness)
+    #[serde(default)]
+    pub weather: WeatherData,
+    /// How voxel edges are drawn (lines, bevels or nothing)
+    #[serde(default)]
+    pub edges: EdgeData,
+    /// How chunk meshes merge faces of nearby colors
+    #[serde(default)]
+    pub meshing: MeshingData,
     /// Camera configuration
     pub camera: CameraData,
     /// Orientation matrices used by voxels in this map.
@@ -47,6 +71,24 @@ pub struct MapData {
     /// here — `rotation: None` always means identity.
     #[serde(default)]
     pub orientations: Vec<OrientationMatrix>,
+    /// Starting state of named voxel groups (see `VoxelData::group`).
+    ///
+    /// Only groups that start hidden or non-solid need an entry.
+    #[serde(default)]
+    pub voxel_groups: Vec<VoxelGroupData>,
+    /// Keyframed camera paths played by the `cinematic` action.
+    #[serde(default)]
+    pub camera_sequences: Vec<CameraSequenceData>,
+    /// Data only the map editor reads, such as construction guides.
+    ///
+    /// The game ignores it and binary maps leave it out (see
+    /// `editor_metadata`).
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "editor_metadata::text_only"
+    )]
+    pub editor_metadata: Option<EditorMetadata>,
     /// Custom properties for extensibility.
     ///
     /// Keys beginning with `adrakestory:` are reserved for engine use and must
@@ -59,7 +101,70 @@ pub struct MapData {
     pub custom_properties: HashMap<String, String>,
 }
 
+/// Custom property marking the world bounds as set by hand.
+///
+/// When `"true"`, saving keeps the declared dimensions (growing them only if
+/// voxels lie outside) instead of shrink-wrapping them to the voxels.
+pub const FIXED_BOUNDS_KEY: &str = "adrakestory:fixed_bounds";
+
+/// Custom property where older editors kept construction guides.
+///
+/// Guides now live in the `editor_metadata` section; loading a map moves them
+/// there (see [`migrate_editor_properties`]).
+pub const EDITOR_GUIDES_KEY: &str = "adrakestory:editor_guides";
+
+/// Custom property where older editors kept locked areas.
+///
+/// Locked areas now live in the `editor_metadata` section; loading a map moves
+/// them there (see [`migrate_editor_properties`]).
+pub const EDITOR_LOCKS_KEY: &str = "adrakestory:editor_locks";
+
 impl MapData {
+    /// Whether the world bounds were set by hand (see [`FIXED_BOUNDS_KEY`]).
+    pub fn has_fixed_bounds(&self) -> bool {
+        self.custom_properties
+            .get(FIXED_BOUNDS_KEY)
+            .is_some_and(|v| v == "true")
+    }
+
+    /// Mark the world bounds as set by hand, or let saving fit them to the voxels.
+    pub fn set_fixed_bounds(&mut self, fixed: bool) {
+        if fixed {
+            self.custom_properties
+                .insert(FIXED_BOUNDS_KEY.to_string(), "true".to_string());
+        } else {
+            self.custom_properties.remove(FIXED_BOUNDS_KEY);
+        }
+    }
+
+    /// An id no entity of this map has yet.
+    pub fn next_entity_id(&self) -> EntityId {
+        let max = self.entities.iter().map(|entity| entity.id.0).max();
+        EntityId(max.unwrap_or(0) + 1)
+    }
+
+    /// Index of the entity with `id`, if the map has one.
+    pub fn entity_index(&self, id: EntityId) -> Option<usize> {
+        if !id.is_assigned() {
+            return None;
+        }
+        self.entities.iter().position(|entity| entity.id == id)
+    }
+
+    /// Give a fresh id to every entity without one, or whose id an earlier
+    /// entity already has. Ids already unique are kept.
+    pub fn assign_entity_ids(&mut self) {
+        let mut next = self.next_entity_id().0;
+        let mut seen = std::collections::HashSet::new();
+        for entity in &mut self.entities {
+            if !entity.id.is_assigned() || !seen.insert(entity.id) {
+                entity.id = EntityId(next);
+                seen.insert(entity.id);
+                next += 1;
+            }
+        }
+    }
+
     /// Create an empty map with minimal dimensions for starting a new map.
     /// This provides a blank canvas for map creation.
     pub fn empty_map() -> Self {
@@ -75,12 +180,19 @@ impl MapData {
                 width: 1,
                 height: 1,
                 depth: 1,
-                voxels: vec![],
+                voxels: VoxelStore::new(),
+                voxel_regions: Vec::new(),
             },
             entities: vec![],
             lighting: LightingData::default(),
+            weather: WeatherData::default(),
+            edges: EdgeData::default(),
+            meshing: MeshingData::default(),
             camera: CameraData::default(),
             orientations: Vec::new(),
+            voxel_groups: Vec::new(),
+            camera_sequences: Vec::new(),
+            editor_metadata: None,
             custom_properties: HashMap::new(),
         }
     }
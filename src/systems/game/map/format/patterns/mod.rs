@@ -1,6 +1,6 @@
 //! Sub-voxel patterns for voxel geometry.
 
-use super::rotation::{apply_orientation_matrix, OrientationMatrix};
+use super::rotation::{apply_orientation_matrix, world_dir_to_local, OrientationMatrix};
 use crate::systems::game::map::geometry::SubVoxelGeometry;
 use serde::{Deserialize, Serialize};
 
@@ -124,6 +124,64 @@ impl SubVoxelPattern {
         SubVoxelGeometry::fence_with_connections(neighbors.0, neighbors.1, neighbors.2, neighbors.3)
     }
 
+    /// Which local rails `(neg_x, pos_x, neg_z, pos_z)` a fence at `pos` needs.
+    ///
+    /// `is_fence` reports whether a world position holds a fence. Neighbours
+    /// above and below count too, so fences rotated into wall panels connect to
+    /// fences stacked on them.
+    pub fn fence_connections(
+        pos: (i32, i32, i32),
+        orientation: Option<&OrientationMatrix>,
+        is_fence: impl Fn((i32, i32, i32)) -> bool,
+    ) -> (bool, bool, bool, bool) {
+        let (x, y, z) = pos;
+        let world_dirs: [([i32; 3], (i32, i32, i32)); 6] = [
+            ([-1, 0, 0], (x - 1, y, z)),
+            ([1, 0, 0], (x + 1, y, z)),
+            ([0, 0, -1], (x, y, z - 1)),
+            ([0, 0, 1], (x, y, z + 1)),
+            ([0, -1, 0], (x, y - 1, z)),
+            ([0, 1, 0], (x, y + 1, z)),
+        ];
+
+        let mut connections = (false, false, false, false);
+        for (world_dir, neighbor_pos) in world_dirs {
+            if !is_fence(neighbor_pos) {
+                continue;
+            }
+            // Mᵀ × d maps the world direction into the fence's local frame
+            match world_dir_to_local(orientation, world_dir) {
+                [-1, 0, 0] => connections.0 = true,
+                [1, 0, 0] => connections.1 = true,
+                [0, 0, -1] => connections.2 = true,
+                [0, 0, 1] => connections.3 = true,
+                _ => {} // Along the fence's local Y axis, no rail
+            }
+        }
+        connections
+    }
+
+    /// World-space geometry of a fence at `pos` with rails towards neighbouring
+    /// fences: a post, a straight run, a corner, a T or a cross.
+    ///
+    /// Non-fence patterns return their rotated geometry unchanged.
+    pub fn connected_geometry(
+        &self,
+        pos: (i32, i32, i32),
+        orientation: Option<&OrientationMatrix>,
+        is_fence: impl Fn((i32, i32, i32)) -> bool,
+    ) -> SubVoxelGeometry {
+        if !self.is_fence() {
+            return self.geometry_with_rotation(orientation);
+        }
+        let connections = Self::fence_connections(pos, orientation, is_fence);
+        let geometry = self.fence_geometry_with_neighbors(connections);
+        match orientation {
+            Some(matrix) => apply_orientation_matrix(geometry, matrix),
+            None => geometry,
+        }
+    }
+
     /// Get the geometry representation of this pattern with an orientation matrix applied.
     ///
     /// # Arguments
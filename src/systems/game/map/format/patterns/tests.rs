@@ -175,3 +175,46 @@ fn test_staircase_serializes_as_staircase_not_staircase_x() {
     let serialized = ron::to_string(&SubVoxelPattern::Staircase).unwrap();
     assert_eq!(serialized, "Staircase");
 }
+
+#[test]
+fn test_fence_connections_follow_neighbouring_fences() {
+    let fences = [(1, 0, 0), (0, 0, -1)];
+    let connections =
+        SubVoxelPattern::fence_connections((0, 0, 0), None, |pos| fences.contains(&pos));
+    assert_eq!(connections, (false, true, true, false));
+}
+
+#[test]
+fn test_fence_connections_map_into_rotated_frame() {
+    use crate::editor::tools::RotationAxis;
+    use crate::systems::game::map::format::axis_angle_to_matrix;
+
+    // Y+90°: world +X is local +Z
+    let matrix = axis_angle_to_matrix(RotationAxis::Y, 1);
+    let connections =
+        SubVoxelPattern::fence_connections((0, 0, 0), Some(&matrix), |pos| pos == (1, 0, 0));
+    assert_eq!(connections, (false, false, false, true));
+}
+
+#[test]
+fn test_connected_geometry_builds_corner_and_cross() {
+    let corner = [(1, 0, 0), (0, 0, 1)];
+    let geometry =
+        SubVoxelPattern::Fence.connected_geometry((0, 0, 0), None, |pos| corner.contains(&pos));
+    assert_eq!(
+        geometry,
+        SubVoxelGeometry::fence_with_connections(false, true, false, true)
+    );
+
+    let geometry = SubVoxelPattern::Fence.connected_geometry((0, 0, 0), None, |pos| pos.1 == 0);
+    assert_eq!(
+        geometry,
+        SubVoxelGeometry::fence_with_connections(true, true, true, true)
+    );
+}
+
+#[test]
+fn test_connected_geometry_ignores_neighbours_for_other_patterns() {
+    let geometry = SubVoxelPattern::Full.connected_geometry((0, 0, 0), None, |_| true);
+    assert_eq!(geometry, SubVoxelPattern::Full.geometry());
+}
@@ -221,6 +221,7 @@ fn test_legacy_migration_all_12_single_axis() {
                 axis: *axis,
                 angle: *angle,
             }),
+            group: None,
         }];
 
         migrate_legacy_rotations(&mut orientations, &mut voxels);
@@ -257,6 +258,7 @@ fn test_legacy_migration_deduplicates() {
                 axis: RotationAxis::Y,
                 angle: 1,
             }),
+            group: None,
         },
         VoxelData {
             pos: (1, 0, 0),
@@ -267,6 +269,7 @@ fn test_legacy_migration_deduplicates() {
                 axis: RotationAxis::Y,
                 angle: 1,
             }),
+            group: None,
         },
     ];
 
@@ -294,6 +297,7 @@ fn staircase_voxel(
         pattern: Some(pattern),
         rotation,
         rotation_state: None,
+        group: None,
     }
 }
 
@@ -432,6 +436,7 @@ fn normalise_non_staircase_pattern_is_unchanged() {
         pattern: Some(SubVoxelPattern::Full),
         rotation: None,
         rotation_state: None,
+        group: None,
     }];
 
     normalise_staircase_variants(&mut orientations, &mut voxels);
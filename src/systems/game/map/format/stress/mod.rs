@@ -0,0 +1,165 @@
+//! Synthetic stress maps for benchmarks and performance testing.
+
+use super::{
+    axis_angle_to_matrix, find_or_insert_orientation, CameraData, EdgeData, EntityData, EntityId,
+    EntityType, LightingData, MapData, MapMetadata, MeshingData, SubVoxelPattern, VoxelData,
+    WeatherData, WorldData,
+};
+use crate::systems::game::components::VoxelType;
+use crate::systems::game::map::geometry::RotationAxis;
+use std::collections::HashMap;
+
+/// World height of generated stress maps, in voxels.
+const STRESS_MAP_HEIGHT: i32 = 16;
+
+impl MapData {
+    /// Generate a large synthetic map for benchmarks and stress testing.
+    ///
+    /// The map is `size`×`size` voxels of rolling terrain (dirt under a grass
+    /// top) scattered with stone pillars, rotated staircases, floating platforms
+    /// and fences, so meshing, collision and loading see a realistic mix of full
+    /// and partial patterns. The same `seed` always produces the same map.
+    #[allow(dead_code)]
+    pub fn stress_map(size: i32, seed: u64) -> Self {
+        let size = size.max(1);
+        let mut rng = XorShift::new(seed);
+        let mut orientations = Vec::new();
+        let mut voxels = Vec::new();
+
+        for x in 0..size {
+            for z in 0..size {
+                let top = terrain_height(x, z);
+                for y in 0..top {
+                    let voxel_type = if y == top - 1 {
+                        VoxelType::Grass
+                    } else {
+                        VoxelType::Dirt
+                    };
+                    voxels.push(voxel(x, y, z, voxel_type, None, None));
+                }
+
+                match rng.next() % 40 {
+                    0 => {
+                        for y in top..top + 3 {
+                            voxels.push(voxel(x, y, z, VoxelType::Stone, None, None));
+                        }
+                    }
+                    1 => {
+                        let angle = (rng.next() % 4) as i32;
+                        let rotation = (angle != 0).then(|| {
+                            find_or_insert_orientation(
+                                &mut orientations,
+                                axis_angle_to_matrix(RotationAxis::Y, angle),
+                            )
+                        });
+                        voxels.push(voxel(
+                            x,
+                            top,
+                            z,
+                            VoxelType::Stone,
+                            Some(SubVoxelPattern::Staircase),
+                            rotation,
+                        ));
+                    }
+                    2 => voxels.push(voxel(
+                        x,
+                        top + 3,
+                        z,
+                        VoxelType::Stone,
+                        Some(SubVoxelPattern::PlatformXZ),
+                        None,
+                    )),
+                    3 => voxels.push(voxel(
+                        x,
+                        top,
+                        z,
+                        VoxelType::Dirt,
+                        Some(SubVoxelPattern::Fence),
+                        None,
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        let center = size / 2;
+        let spawn_y = terrain_height(center, center) as f32 + 4.0;
+
+        Self {
+            metadata: MapMetadata {
+                name: format!("Stress {size}x{size}"),
+                author: "System".to_string(),
+                description: format!("Synthetic stress map (seed {seed})"),
+                version: "1.0.0".to_string(),
+                created: String::new(),
+            },
+            world: WorldData {
+                width: size,
+                height: STRESS_MAP_HEIGHT,
+                depth: size,
+                voxels: voxels.into(),
+                voxel_regions: Vec::new(),
+            },
+            entities: vec![EntityData {
+                id: EntityId(1),
+                entity_type: EntityType::PlayerSpawn,
+                position: (center as f32 + 0.5, spawn_y, center as f32 + 0.5),
+                properties: HashMap::new(),
+            }],
+            lighting: LightingData::default(),
+            weather: WeatherData::default(),
+            edges: EdgeData::default(),
+            meshing: MeshingData::default(),
+            camera: CameraData::default(),
+            orientations,
+            voxel_groups: Vec::new(),
+            camera_sequences: Vec::new(),
+            editor_metadata: None,
+            custom_properties: HashMap::new(),
+        }
+    }
+}
+
+/// Number of solid voxels in the terrain column at (x, z), between 1 and 8.
+fn terrain_height(x: i32, z: i32) -> i32 {
+    let wave = (x as f32 * 0.3).sin() + (z as f32 * 0.23).cos();
+    (4.0 + wave * 1.5).round().clamp(1.0, 8.0) as i32
+}
+
+fn voxel(
+    x: i32,
+    y: i32,
+    z: i32,
+    voxel_type: VoxelType,
+    pattern: Option<SubVoxelPattern>,
+    rotation: Option<usize>,
+) -> VoxelData {
+    VoxelData {
+        rotation,
+        ..VoxelData::new(
+            (x, y, z),
+            voxel_type,
+            Some(pattern.unwrap_or(SubVoxelPattern::Full)),
+        )
+    }
+}
+
+/// Small deterministic PRNG so generated maps are reproducible across runs.
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // xorshift gets stuck at zero
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,38 @@
+use super::*;
+use crate::systems::game::map::validation::validate_map;
+
+#[test]
+fn stress_map_is_valid() {
+    let map = MapData::stress_map(48, 7);
+    assert!(validate_map(&map).is_ok());
+    assert_eq!(map.world.width, 48);
+    assert_eq!(map.world.depth, 48);
+    // Every column has at least one voxel
+    assert!(map.world.voxels.len() >= 48 * 48);
+}
+
+#[test]
+fn stress_map_is_deterministic_per_seed() {
+    let positions = |map: &MapData| {
+        map.world
+            .voxels
+            .iter()
+            .map(|v| (v.pos, v.pattern))
+            .collect::<Vec<_>>()
+    };
+    let a = MapData::stress_map(32, 42);
+    let b = MapData::stress_map(32, 42);
+    let c = MapData::stress_map(32, 43);
+    assert_eq!(positions(&a), positions(&b));
+    assert_ne!(positions(&a), positions(&c));
+}
+
+#[test]
+fn stress_map_mixes_patterns_and_rotations() {
+    let map = MapData::stress_map(64, 1);
+    let has = |pattern| map.world.voxels.iter().any(|v| v.pattern == Some(pattern));
+    assert!(has(SubVoxelPattern::Staircase));
+    assert!(has(SubVoxelPattern::PlatformXZ));
+    assert!(has(SubVoxelPattern::Fence));
+    assert!(!map.orientations.is_empty());
+}
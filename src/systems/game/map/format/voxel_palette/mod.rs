@@ -0,0 +1,220 @@
+//! Palette encoding of voxels for binary map files.
+//!
+//! The world is cut into [`PALETTE_CHUNK_SIZE`]³ chunks. Each chunk lists
+//! the distinct kinds of voxel it holds once, in a palette, and stores one
+//! small palette index per cell, bit-packed into 64-bit words. A chunk of a
+//! single material costs a palette entry and 64 words however many voxels it
+//! holds, so large maps encode compactly and decode without parsing one
+//! entry per voxel.
+//!
+//! [`pack_voxel_chunks`] and [`unpack_voxel_chunks`] convert between the
+//! voxel list the game and editor use and the chunks written to files.
+
+use super::{SubVoxelPattern, VoxelData, VoxelStore, VoxelType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Edge length of a palette chunk, in voxels.
+pub const PALETTE_CHUNK_SIZE: i32 = 16;
+
+/// Number of cells in a palette chunk.
+const CELLS: usize = (PALETTE_CHUNK_SIZE * PALETTE_CHUNK_SIZE * PALETTE_CHUNK_SIZE) as usize;
+
+/// Everything about a voxel except its position.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PaletteEntry {
+    pub voxel_type: VoxelType,
+    pub pattern: Option<SubVoxelPattern>,
+    pub rotation: Option<usize>,
+    pub group: Option<String>,
+}
+
+impl PaletteEntry {
+    fn of(voxel: &VoxelData) -> Self {
+        Self {
+            voxel_type: voxel.voxel_type,
+            pattern: voxel.pattern,
+            rotation: voxel.rotation,
+            group: voxel.group.clone(),
+        }
+    }
+
+    fn voxel_at(&self, pos: (i32, i32, i32)) -> VoxelData {
+        VoxelData {
+            pos,
+            voxel_type: self.voxel_type,
+            pattern: self.pattern,
+            rotation: self.rotation,
+            rotation_state: None,
+            group: self.group.clone(),
+        }
+    }
+}
+
+/// The voxels of one chunk as a palette and packed cell indices.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VoxelChunk {
+    /// Chunk coordinates; the chunk starts at `coords * PALETTE_CHUNK_SIZE`
+    pub coords: (i32, i32, i32),
+    /// Distinct voxels in the chunk. Cells refer to entry `i` as `i + 1`;
+    /// 0 is an empty cell.
+    pub palette: Vec<PaletteEntry>,
+    /// Cell indices, x fastest, then z, then y, packed [`index_bits`] bits
+    /// each into words from the lowest bit up. No index spans two words.
+    pub indices: Vec<u64>,
+}
+
+/// Bits needed per cell for a palette of `len` entries.
+pub fn index_bits(len: usize) -> u32 {
+    (usize::BITS - len.leading_zeros()).max(1)
+}
+
+/// Chunk holding `pos`, and the cell of `pos` within it.
+fn chunk_cell(pos: (i32, i32, i32)) -> ((i32, i32, i32), usize) {
+    let (x, y, z) = pos;
+    let n = PALETTE_CHUNK_SIZE;
+    let coords = (x.div_euclid(n), y.div_euclid(n), z.div_euclid(n));
+    let (lx, ly, lz) = (x.rem_euclid(n), y.rem_euclid(n), z.rem_euclid(n));
+    (coords, ((ly * n + lz) * n + lx) as usize)
+}
+
+/// Position of `cell` in the chunk at `coords`.
+fn cell_pos(coords: (i32, i32, i32), cell: usize) -> (i32, i32, i32) {
+    let n = PALETTE_CHUNK_SIZE;
+    let cell = cell as i32;
+    (
+        coords.0 * n + cell % n,
+        coords.1 * n + cell / (n * n),
+        coords.2 * n + (cell / n) % n,
+    )
+}
+
+impl VoxelChunk {
+    /// Build a chunk from the voxels in it, by cell.
+    fn from_cells(coords: (i32, i32, i32), mut cells: Vec<(usize, &VoxelData)>) -> Self {
+        // Number palette entries in cell order, so the encoding doesn't
+        // depend on the order of the voxel list
+        cells.sort_unstable_by_key(|&(cell, _)| cell);
+        let mut palette: Vec<PaletteEntry> = Vec::new();
+        let mut indices = vec![0u64; CELLS];
+        for (cell, voxel) in cells {
+            let entry = PaletteEntry::of(voxel);
+            let index = match palette.iter().position(|e| *e == entry) {
+                Some(i) => i,
+                None => {
+                    palette.push(entry);
+                    palette.len() - 1
+                }
+            };
+            indices[cell] = index as u64 + 1;
+        }
+
+        let bits = index_bits(palette.len());
+        let per_word = (u64::BITS / bits) as usize;
+        let indices = indices
+            .chunks(per_word)
+            .map(|group| {
+                group
+                    .iter()
+                    .enumerate()
+                    .fold(0, |word, (i, &index)| word | index << (i as u32 * bits))
+            })
+            .collect();
+        Self {
+            coords,
+            palette,
+            indices,
+        }
+    }
+
+    /// The voxels in the chunk, or a description of what is corrupt.
+    pub fn voxels(&self) -> Result<Vec<VoxelData>, String> {
+        if self.palette.len() > CELLS {
+            return Err(format!(
+                "chunk {:?} has {} palette entries, more than it has cells",
+                self.coords,
+                self.palette.len()
+            ));
+        }
+        let bits = index_bits(self.palette.len());
+        let per_word = (u64::BITS / bits) as usize;
+        if self.indices.len() != CELLS.div_ceil(per_word) {
+            return Err(format!(
+                "chunk {:?} has {} index words, expected {}",
+                self.coords,
+                self.indices.len(),
+                CELLS.div_ceil(per_word)
+            ));
+        }
+
+        let mask = (1u64 << bits) - 1;
+        let mut voxels = Vec::new();
+        for cell in 0..CELLS {
+            let word = self.indices[cell / per_word];
+            let index = (word >> ((cell % per_word) as u32 * bits)) & mask;
+            if index == 0 {
+                continue;
+            }
+            let entry = self.palette.get(index as usize - 1).ok_or_else(|| {
+                format!(
+                    "chunk {:?} refers to palette entry {} of {}",
+                    self.coords,
+                    index,
+                    self.palette.len()
+                )
+            })?;
+            voxels.push(entry.voxel_at(cell_pos(self.coords, cell)));
+        }
+        Ok(voxels)
+    }
+}
+
+/// Move every voxel that fits a palette chunk out of `voxels` and return the
+/// chunks, ordered by coordinates.
+///
+/// Voxels still carrying a legacy `rotation_state` stay in the list, as do
+/// all but the last of several voxels at one position, so unpacking keeps
+/// the last one the voxel lookups find.
+pub fn pack_voxel_chunks(voxels: &mut VoxelStore) -> Vec<VoxelChunk> {
+    let mut open: HashMap<(i32, i32, i32), usize> = HashMap::new();
+    for (i, voxel) in voxels.iter().enumerate() {
+        if voxel.rotation_state.is_none() {
+            open.insert(voxel.pos, i);
+        } else {
+            open.remove(&voxel.pos);
+        }
+    }
+
+    let mut by_chunk: HashMap<_, Vec<(usize, &VoxelData)>> = HashMap::new();
+    let mut packed = vec![false; voxels.len()];
+    for &i in open.values() {
+        let (coords, cell) = chunk_cell(voxels[i].pos);
+        by_chunk.entry(coords).or_default().push((cell, &voxels[i]));
+        packed[i] = true;
+    }
+
+    let mut chunks: Vec<_> = by_chunk
+        .into_iter()
+        .map(|(coords, cells)| VoxelChunk::from_cells(coords, cells))
+        .collect();
+    chunks.sort_unstable_by_key(|chunk| chunk.coords);
+
+    let mut i = 0;
+    voxels.retain(|_| {
+        let keep = !packed[i];
+        i += 1;
+        keep
+    });
+    chunks
+}
+
+/// Add the voxels of every chunk to `voxels`.
+pub fn unpack_voxel_chunks(chunks: &[VoxelChunk], voxels: &mut VoxelStore) -> Result<(), String> {
+    for chunk in chunks {
+        voxels.extend(chunk.voxels()?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
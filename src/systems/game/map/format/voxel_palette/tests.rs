@@ -0,0 +1,119 @@
+use super::*;
+
+fn sorted_positions(voxels: &VoxelStore) -> Vec<(i32, i32, i32)> {
+    let mut positions: Vec<_> = voxels.iter().map(|v| v.pos).collect();
+    positions.sort_unstable();
+    positions
+}
+
+#[test]
+fn cells_map_back_to_their_positions() {
+    for pos in [(0, 0, 0), (15, 3, 7), (16, 0, 0), (-1, -17, 40)] {
+        let (coords, cell) = chunk_cell(pos);
+        assert!(cell < CELLS);
+        assert_eq!(cell_pos(coords, cell), pos);
+    }
+    assert_eq!(chunk_cell((-1, 0, 0)).0, (-1, 0, 0));
+}
+
+#[test]
+fn index_width_grows_with_the_palette() {
+    assert_eq!(index_bits(1), 1);
+    assert_eq!(index_bits(2), 2);
+    assert_eq!(index_bits(3), 2);
+    assert_eq!(index_bits(4), 3);
+    assert_eq!(index_bits(CELLS), 13);
+}
+
+#[test]
+fn a_single_material_chunk_packs_into_one_bit_per_cell() {
+    let mut voxels: VoxelStore = (0..16)
+        .flat_map(|x| {
+            (0..16).map(move |z| {
+                VoxelData::new((x, 0, z), VoxelType::Grass, Some(SubVoxelPattern::Full))
+            })
+        })
+        .collect();
+    let chunks = pack_voxel_chunks(&mut voxels);
+    assert!(voxels.is_empty());
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].palette.len(), 1);
+    assert_eq!(chunks[0].indices.len(), CELLS / 64);
+    assert_eq!(chunks[0].voxels().unwrap().len(), 256);
+}
+
+#[test]
+fn packing_and_unpacking_keeps_every_voxel() {
+    let mut voxels = VoxelStore::new();
+    for x in -20..20 {
+        for z in 0..3 {
+            let voxel_type = if x % 3 == 0 {
+                VoxelType::Stone
+            } else {
+                VoxelType::Dirt
+            };
+            voxels.push(VoxelData::new(
+                (x, (x * 7).rem_euclid(5), z),
+                voxel_type,
+                Some(SubVoxelPattern::Full),
+            ));
+        }
+    }
+    let mut torch = VoxelData::new((3, 9, 1), VoxelType::Stone, Some(SubVoxelPattern::Full));
+    torch.rotation = Some(2);
+    torch.group = Some("torches".to_string());
+    voxels.push(torch);
+    let expected = sorted_positions(&voxels);
+
+    let chunks = pack_voxel_chunks(&mut voxels);
+    assert!(voxels.is_empty());
+    assert!(chunks.windows(2).all(|w| w[0].coords < w[1].coords));
+    unpack_voxel_chunks(&chunks, &mut voxels).unwrap();
+
+    assert_eq!(sorted_positions(&voxels), expected);
+    assert_eq!(voxels.get((-3, 4, 2)).unwrap().voxel_type, VoxelType::Stone);
+    assert_eq!(voxels.get((-2, 1, 2)).unwrap().voxel_type, VoxelType::Dirt);
+    let torch = voxels.get((3, 9, 1)).unwrap();
+    assert_eq!(torch.rotation, Some(2));
+    assert_eq!(torch.group.as_deref(), Some("torches"));
+}
+
+#[test]
+fn shadowed_duplicates_stay_in_the_list() {
+    let mut voxels = VoxelStore::new();
+    voxels.push(VoxelData::new(
+        (1, 0, 0),
+        VoxelType::Stone,
+        Some(SubVoxelPattern::Full),
+    ));
+    voxels.push(VoxelData::new(
+        (1, 0, 0),
+        VoxelType::Dirt,
+        Some(SubVoxelPattern::Full),
+    ));
+
+    let chunks = pack_voxel_chunks(&mut voxels);
+    assert_eq!(voxels.len(), 1);
+    unpack_voxel_chunks(&chunks, &mut voxels).unwrap();
+    assert_eq!(voxels.len(), 2);
+    assert_eq!(voxels.get((1, 0, 0)).unwrap().voxel_type, VoxelType::Dirt);
+}
+
+#[test]
+fn corrupt_chunks_are_reported() {
+    let mut voxels: VoxelStore = vec![VoxelData::new(
+        (0, 0, 0),
+        VoxelType::Stone,
+        Some(SubVoxelPattern::Full),
+    )]
+    .into();
+    let chunk = pack_voxel_chunks(&mut voxels).remove(0);
+
+    let mut truncated = chunk.clone();
+    truncated.indices.pop();
+    assert!(truncated.voxels().is_err());
+
+    let mut missing_entry = chunk;
+    missing_entry.palette.clear();
+    assert!(missing_entry.voxels().is_err());
+}
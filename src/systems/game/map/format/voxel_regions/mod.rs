@@ -0,0 +1,171 @@
+//! Run-length encoding of voxel blocks in map files.
+//!
+//! Terrain is mostly large blocks of identical voxels, and writing each one
+//! as its own `VoxelData` entry makes RON files huge. When a map is saved as
+//! RON, [`pack_voxel_regions`] moves every box of two or more identical voxels
+//! out of the voxel list into one [`VoxelRegion`], and loaders call
+//! [`unpack_voxel_regions`] to put them back before anything else sees the
+//! map.
+//!
+//! Binary maps store voxels as palette chunks instead (see `voxel_palette`);
+//! the `voxel_regions` field takes no space in them, so binary files written
+//! before regions existed still load.
+
+use super::{SubVoxelPattern, VoxelData, VoxelStore, VoxelType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A box of identical voxels, written in place of one entry per voxel.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VoxelRegion {
+    /// Lowest corner of the box
+    pub from: (i32, i32, i32),
+    /// Number of voxels along x, y and z
+    pub size: (u32, u32, u32),
+    /// Type of every voxel in the box
+    pub voxel_type: VoxelType,
+    #[serde(default)]
+    pub pattern: Option<SubVoxelPattern>,
+    #[serde(default)]
+    pub rotation: Option<usize>,
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+impl VoxelRegion {
+    /// The voxels in the box, row by row along x.
+    pub fn voxels(&self) -> impl Iterator<Item = VoxelData> + '_ {
+        let (x, y, z) = self.from;
+        let (w, h, d) = self.size;
+        (0..h as i32).flat_map(move |dy| {
+            (0..d as i32).flat_map(move |dz| {
+                (0..w as i32).map(move |dx| VoxelData {
+                    pos: (x + dx, y + dy, z + dz),
+                    voxel_type: self.voxel_type,
+                    pattern: self.pattern,
+                    rotation: self.rotation,
+                    rotation_state: None,
+                    group: self.group.clone(),
+                })
+            })
+        })
+    }
+}
+
+/// Whether `a` and `b` differ only in position.
+fn same_kind(a: &VoxelData, b: &VoxelData) -> bool {
+    a.voxel_type == b.voxel_type
+        && a.pattern == b.pattern
+        && a.rotation == b.rotation
+        && a.group == b.group
+}
+
+/// Move every box of two or more identical voxels out of `voxels` and
+/// return the boxes.
+///
+/// Voxels still carrying a legacy `rotation_state` are left in the list. Of
+/// several voxels at one position only the last can be packed, so unpacking
+/// keeps it the one lookups find.
+pub fn pack_voxel_regions(voxels: &mut VoxelStore) -> Vec<VoxelRegion> {
+    let mut open: HashMap<(i32, i32, i32), usize> = HashMap::new();
+    for (i, voxel) in voxels.iter().enumerate() {
+        if voxel.rotation_state.is_none() {
+            open.insert(voxel.pos, i);
+        } else {
+            open.remove(&voxel.pos);
+        }
+    }
+
+    // Boxes only grow towards +x, +y and +z, so visiting positions in this
+    // order starts each box at its lowest corner
+    let mut starts: Vec<_> = open.keys().copied().collect();
+    starts.sort_unstable_by_key(|&(x, y, z)| (y, z, x));
+
+    let mut packed = vec![false; voxels.len()];
+    let mut regions = Vec::new();
+    for (x, y, z) in starts {
+        let Some(&first) = open.get(&(x, y, z)) else {
+            continue;
+        };
+        let kind = &voxels[first];
+        let matches =
+            |pos: (i32, i32, i32)| open.get(&pos).is_some_and(|&i| same_kind(&voxels[i], kind));
+
+        let mut w = 1;
+        while matches((x + w, y, z)) {
+            w += 1;
+        }
+        let mut d = 1;
+        while (0..w).all(|dx| matches((x + dx, y, z + d))) {
+            d += 1;
+        }
+        let mut h = 1;
+        while (0..w).all(|dx| (0..d).all(|dz| matches((x + dx, y + h, z + dz)))) {
+            h += 1;
+        }
+        if w * h * d < 2 {
+            continue;
+        }
+
+        let region = VoxelRegion {
+            from: (x, y, z),
+            size: (w as u32, h as u32, d as u32),
+            voxel_type: kind.voxel_type,
+            pattern: kind.pattern,
+            rotation: kind.rotation,
+            group: kind.group.clone(),
+        };
+        for voxel in region.voxels() {
+            if let Some(i) = open.remove(&voxel.pos) {
+                packed[i] = true;
+            }
+        }
+        regions.push(region);
+    }
+
+    let mut i = 0;
+    voxels.retain(|_| {
+        let keep = !packed[i];
+        i += 1;
+        keep
+    });
+    regions
+}
+
+/// Add the voxels of every region to `voxels`.
+pub fn unpack_voxel_regions(regions: Vec<VoxelRegion>, voxels: &mut VoxelStore) {
+    for region in &regions {
+        voxels.extend(region.voxels());
+    }
+}
+
+/// Serde helpers that keep `voxel_regions` out of non-text formats, so the
+/// binary encoding of `WorldData` doesn't change.
+pub(super) mod text_only {
+    use super::VoxelRegion;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        regions: &[VoxelRegion],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            regions.serialize(serializer)
+        } else {
+            serializer.serialize_unit()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<VoxelRegion>, D::Error> {
+        if deserializer.is_human_readable() {
+            Vec::deserialize(deserializer)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,118 @@
+use super::*;
+use crate::systems::game::map::format::rotation::LegacyRotationState;
+use crate::systems::game::map::format::WorldData;
+use crate::systems::game::map::geometry::RotationAxis;
+
+/// A `w`×`h`×`d` block of stone at the origin.
+fn block(w: i32, h: i32, d: i32) -> VoxelStore {
+    let mut voxels = VoxelStore::new();
+    for y in 0..h {
+        for z in 0..d {
+            for x in 0..w {
+                voxels.push(VoxelData::new(
+                    (x, y, z),
+                    VoxelType::Stone,
+                    Some(SubVoxelPattern::Full),
+                ));
+            }
+        }
+    }
+    voxels
+}
+
+fn sorted_positions(voxels: &VoxelStore) -> Vec<(i32, i32, i32)> {
+    let mut positions: Vec<_> = voxels.iter().map(|v| v.pos).collect();
+    positions.sort_unstable();
+    positions
+}
+
+#[test]
+fn a_solid_block_packs_into_one_region() {
+    let mut voxels = block(4, 2, 3);
+    let regions = pack_voxel_regions(&mut voxels);
+    assert!(voxels.is_empty());
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].from, (0, 0, 0));
+    assert_eq!(regions[0].size, (4, 2, 3));
+    assert_eq!(regions[0].voxels().count(), 24);
+}
+
+#[test]
+fn packing_and_unpacking_keeps_every_voxel() {
+    let mut voxels = block(5, 3, 4);
+    voxels.remove((2, 1, 1));
+    voxels.insert(VoxelData::new(
+        (0, 0, 0),
+        VoxelType::Grass,
+        Some(SubVoxelPattern::Full),
+    ));
+    voxels.push(VoxelData::new(
+        (9, 9, 9),
+        VoxelType::Dirt,
+        Some(SubVoxelPattern::Full),
+    ));
+    let mut lamp = VoxelData::new((1, 2, 3), VoxelType::Stone, Some(SubVoxelPattern::Full));
+    lamp.group = Some("lamps".to_string());
+    voxels.insert(lamp);
+    let expected = sorted_positions(&voxels);
+
+    let regions = pack_voxel_regions(&mut voxels);
+    assert!(regions.len() > 1);
+    assert!(voxels.contains((9, 9, 9)), "lone voxels stay in the list");
+    unpack_voxel_regions(regions, &mut voxels);
+
+    assert_eq!(sorted_positions(&voxels), expected);
+    assert_eq!(voxels.get((0, 0, 0)).unwrap().voxel_type, VoxelType::Grass);
+    assert_eq!(
+        voxels.get((1, 2, 3)).unwrap().group.as_deref(),
+        Some("lamps")
+    );
+    assert!(voxels.get((1, 1, 3)).unwrap().group.is_none());
+}
+
+#[test]
+fn legacy_rotations_and_shadowed_duplicates_stay_in_the_list() {
+    let mut voxels = block(3, 1, 1);
+    let mut duplicate = VoxelData::new((1, 0, 0), VoxelType::Dirt, Some(SubVoxelPattern::Full));
+    voxels.push(duplicate.clone());
+    duplicate.pos = (2, 0, 0);
+    duplicate.rotation_state = Some(LegacyRotationState {
+        axis: RotationAxis::Y,
+        angle: 1,
+    });
+    voxels.push(duplicate);
+
+    let regions = pack_voxel_regions(&mut voxels);
+    assert!(regions.is_empty(), "no two neighbours match");
+    assert_eq!(voxels.len(), 5);
+
+    unpack_voxel_regions(regions, &mut voxels);
+    assert_eq!(voxels.get((1, 0, 0)).unwrap().voxel_type, VoxelType::Dirt);
+}
+
+#[test]
+fn regions_are_written_to_ron_only() {
+    let mut world = WorldData {
+        width: 4,
+        height: 2,
+        depth: 3,
+        voxels: block(4, 2, 3),
+        voxel_regions: Vec::new(),
+    };
+    let plain_bincode = bincode::serialize(&world).unwrap();
+    let plain_ron = ron::to_string(&world).unwrap();
+    assert!(!plain_ron.contains("voxel_regions"));
+
+    world.pack_voxel_regions();
+    let packed_ron = ron::to_string(&world).unwrap();
+    assert!(packed_ron.len() < plain_ron.len() / 5);
+    let mut read: WorldData = ron::from_str(&packed_ron).unwrap();
+    read.unpack_voxel_regions();
+    assert_eq!(read.voxels.len(), 24);
+
+    // Binary maps from before regions existed still decode
+    let legacy = (4i32, 2i32, 3i32, block(4, 2, 3).into_vec());
+    assert_eq!(bincode::serialize(&legacy).unwrap(), plain_bincode);
+    let read: WorldData = bincode::deserialize(&plain_bincode).unwrap();
+    assert_eq!(read.voxels.len(), 24);
+}
@@ -0,0 +1,257 @@
+//! Voxel storage indexed by position.
+//!
+//! [`VoxelStore`] keeps a world's voxels in a list, in file order, next to a
+//! map from position to list index. Looking up, placing and removing a voxel
+//! at a position is O(1) instead of a scan over the whole map, which keeps
+//! editing responsive on large maps. It reads and writes as a plain list, so
+//! map files are unchanged.
+//!
+//! Reading code uses the store as a `[VoxelData]` slice. Code that changes
+//! voxels through the slice may move them, so that drops the index, and the
+//! next lookup builds it again.
+//!
+//! Files may contain several voxels at one position (map validation reports
+//! them). The store keeps them all and lookups find the last one, the same
+//! one [`VoxelStore::remove_duplicates`] keeps.
+
+use super::VoxelData;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+
+/// List index of the last voxel at each position.
+type PositionIndex = HashMap<(i32, i32, i32), usize>;
+
+/// A world's voxels, indexed by position.
+#[derive(Clone, Debug, Default)]
+pub struct VoxelStore {
+    voxels: Vec<VoxelData>,
+    /// Built on the first lookup after `voxels` was handed out mutably
+    index: OnceLock<PositionIndex>,
+}
+
+impl VoxelStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The voxel at `pos`, if any.
+    pub fn get(&self, pos: (i32, i32, i32)) -> Option<&VoxelData> {
+        let index = self.index.get_or_init(|| build_index(&self.voxels));
+        index.get(&pos).map(|&i| &self.voxels[i])
+    }
+
+    /// The voxel at `pos`, to change in place.
+    ///
+    /// Changing its `pos` this way breaks lookups; move voxels with
+    /// [`remove`](Self::remove) and [`insert`](Self::insert) instead.
+    pub fn get_mut(&mut self, pos: (i32, i32, i32)) -> Option<&mut VoxelData> {
+        let i = *built_index(&mut self.index, &self.voxels).get(&pos)?;
+        Some(&mut self.voxels[i])
+    }
+
+    /// Whether a voxel is at `pos`.
+    pub fn contains(&self, pos: (i32, i32, i32)) -> bool {
+        self.get(pos).is_some()
+    }
+
+    /// Place `voxel` at its position, returning the voxel it replaced.
+    pub fn insert(&mut self, voxel: VoxelData) -> Option<VoxelData> {
+        let existing = built_index(&mut self.index, &self.voxels)
+            .get(&voxel.pos)
+            .copied();
+        match existing {
+            Some(i) => Some(std::mem::replace(&mut self.voxels[i], voxel)),
+            None => {
+                self.push(voxel);
+                None
+            }
+        }
+    }
+
+    /// Add `voxel` to the end of the list, even if another voxel is at its
+    /// position.
+    pub fn push(&mut self, voxel: VoxelData) {
+        built_index(&mut self.index, &self.voxels).insert(voxel.pos, self.voxels.len());
+        self.voxels.push(voxel);
+    }
+
+    /// Remove the voxel at `pos` and return it.
+    ///
+    /// The last voxel in the list takes its place, so the order of the
+    /// remaining voxels changes.
+    pub fn remove(&mut self, pos: (i32, i32, i32)) -> Option<VoxelData> {
+        let index = built_index(&mut self.index, &self.voxels);
+        let i = index.remove(&pos)?;
+        let has_duplicates = index.len() + 1 < self.voxels.len();
+        let removed = self.voxels.swap_remove(i);
+        if let Some(moved) = self.voxels.get(i) {
+            if index.get(&moved.pos) == Some(&self.voxels.len()) {
+                index.insert(moved.pos, i);
+            }
+        }
+        if has_duplicates {
+            if let Some(other) = self.voxels.iter().rposition(|voxel| voxel.pos == pos) {
+                index.insert(pos, other);
+            }
+        }
+        Some(removed)
+    }
+
+    /// Number of voxels sharing their position with a later voxel.
+    pub fn duplicate_count(&self) -> usize {
+        let index = self.index.get_or_init(|| build_index(&self.voxels));
+        self.voxels.len() - index.len()
+    }
+
+    /// Positions holding more than one voxel, in ascending order.
+    pub fn duplicate_positions(&self) -> Vec<(i32, i32, i32)> {
+        if self.duplicate_count() == 0 {
+            return Vec::new();
+        }
+        let index = self.index.get_or_init(|| build_index(&self.voxels));
+        let mut positions: Vec<_> = self
+            .voxels
+            .iter()
+            .enumerate()
+            .filter(|&(i, voxel)| index.get(&voxel.pos) != Some(&i))
+            .map(|(_, voxel)| voxel.pos)
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+
+    /// Drop every voxel sharing its position with a later one, keeping the
+    /// last voxel at each position (the one lookups find) and the order of
+    /// the rest. Returns how many were dropped.
+    pub fn remove_duplicates(&mut self) -> usize {
+        let duplicates = self.duplicate_count();
+        if duplicates == 0 {
+            return 0;
+        }
+        let index = built_index(&mut self.index, &self.voxels);
+        let mut i = 0;
+        self.voxels.retain(|voxel| {
+            let keep = index.get(&voxel.pos) == Some(&i);
+            i += 1;
+            keep
+        });
+        self.index = OnceLock::new();
+        duplicates
+    }
+
+    /// Keep only the voxels for which `keep` returns true.
+    pub fn retain(&mut self, keep: impl FnMut(&VoxelData) -> bool) {
+        self.voxels.retain(keep);
+        self.index = OnceLock::new();
+    }
+
+    pub fn clear(&mut self) {
+        self.voxels.clear();
+        self.index = OnceLock::new();
+    }
+
+    /// Take the voxels out as a list, in order.
+    pub fn into_vec(self) -> Vec<VoxelData> {
+        self.voxels
+    }
+}
+
+fn build_index(voxels: &[VoxelData]) -> PositionIndex {
+    voxels
+        .iter()
+        .enumerate()
+        .map(|(i, voxel)| (voxel.pos, i))
+        .collect()
+}
+
+/// `index`, built from `voxels` first if it was dropped.
+fn built_index<'a>(
+    index: &'a mut OnceLock<PositionIndex>,
+    voxels: &[VoxelData],
+) -> &'a mut PositionIndex {
+    index.get_or_init(|| build_index(voxels));
+    index.get_mut().expect("index was just built")
+}
+
+impl Deref for VoxelStore {
+    type Target = [VoxelData];
+
+    fn deref(&self) -> &[VoxelData] {
+        &self.voxels
+    }
+}
+
+impl DerefMut for VoxelStore {
+    fn deref_mut(&mut self) -> &mut [VoxelData] {
+        self.index = OnceLock::new();
+        &mut self.voxels
+    }
+}
+
+impl From<Vec<VoxelData>> for VoxelStore {
+    fn from(voxels: Vec<VoxelData>) -> Self {
+        Self {
+            voxels,
+            index: OnceLock::new(),
+        }
+    }
+}
+
+impl FromIterator<VoxelData> for VoxelStore {
+    fn from_iter<I: IntoIterator<Item = VoxelData>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl Extend<VoxelData> for VoxelStore {
+    fn extend<I: IntoIterator<Item = VoxelData>>(&mut self, iter: I) {
+        for voxel in iter {
+            self.push(voxel);
+        }
+    }
+}
+
+impl IntoIterator for VoxelStore {
+    type Item = VoxelData;
+    type IntoIter = std::vec::IntoIter<VoxelData>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.voxels.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a VoxelStore {
+    type Item = &'a VoxelData;
+    type IntoIter = std::slice::Iter<'a, VoxelData>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.voxels.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut VoxelStore {
+    type Item = &'a mut VoxelData;
+    type IntoIter = std::slice::IterMut<'a, VoxelData>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl Serialize for VoxelStore {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.voxels.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VoxelStore {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<VoxelData>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+use crate::systems::game::map::format::VoxelType;
+
+fn store(positions: &[(i32, i32, i32)]) -> VoxelStore {
+    positions
+        .iter()
+        .map(|&pos| VoxelData::new(pos, VoxelType::Stone, None))
+        .collect()
+}
+
+#[test]
+fn voxels_are_found_by_position() {
+    let store = store(&[(0, 0, 0), (1, 0, 0), (0, 2, 5)]);
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.get((0, 2, 5)).map(|v| v.pos), Some((0, 2, 5)));
+    assert!(store.contains((1, 0, 0)));
+    assert!(!store.contains((1, 1, 0)));
+}
+
+#[test]
+fn inserting_replaces_the_voxel_at_the_same_position() {
+    let mut store = store(&[(0, 0, 0)]);
+    assert!(store
+        .insert(VoxelData::new((1, 0, 0), VoxelType::Grass, None))
+        .is_none());
+    let replaced = store.insert(VoxelData::new((0, 0, 0), VoxelType::Dirt, None));
+    assert_eq!(replaced.map(|v| v.voxel_type), Some(VoxelType::Stone));
+    assert_eq!(store.len(), 2);
+    assert_eq!(store.get((0, 0, 0)).unwrap().voxel_type, VoxelType::Dirt);
+}
+
+#[test]
+fn removing_keeps_every_other_voxel_reachable() {
+    let positions = [(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0)];
+    let mut store = store(&positions);
+    assert_eq!(store.remove((1, 0, 0)).map(|v| v.pos), Some((1, 0, 0)));
+    assert!(store.remove((1, 0, 0)).is_none());
+    assert_eq!(store.len(), 3);
+    for pos in [(0, 0, 0), (2, 0, 0), (3, 0, 0)] {
+        assert_eq!(store.get(pos).map(|v| v.pos), Some(pos));
+    }
+
+    // Removing the last voxel in the list moves nothing
+    store.remove((2, 0, 0));
+    assert!(store.contains((3, 0, 0)));
+}
+
+#[test]
+fn duplicates_are_kept_and_the_last_one_is_found() {
+    let mut store = VoxelStore::new();
+    store.push(VoxelData::new((0, 0, 0), VoxelType::Stone, None));
+    store.push(VoxelData::new((1, 0, 0), VoxelType::Stone, None));
+    store.push(VoxelData::new((0, 0, 0), VoxelType::Grass, None));
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.get((0, 0, 0)).unwrap().voxel_type, VoxelType::Grass);
+
+    // Removing one uncovers the other
+    store.remove((0, 0, 0));
+    assert_eq!(store.get((0, 0, 0)).unwrap().voxel_type, VoxelType::Stone);
+    store.remove((0, 0, 0));
+    assert!(!store.contains((0, 0, 0)));
+    assert!(store.contains((1, 0, 0)));
+}
+
+#[test]
+fn removing_duplicates_keeps_the_last_voxel_in_order() {
+    let mut store = VoxelStore::new();
+    store.push(VoxelData::new((0, 0, 0), VoxelType::Stone, None));
+    store.push(VoxelData::new((1, 0, 0), VoxelType::Stone, None));
+    store.push(VoxelData::new((0, 0, 0), VoxelType::Grass, None));
+    store.push(VoxelData::new((2, 0, 0), VoxelType::Stone, None));
+    store.push(VoxelData::new((0, 0, 0), VoxelType::Dirt, None));
+    store.push(VoxelData::new((1, 0, 0), VoxelType::Water, None));
+    assert_eq!(store.duplicate_count(), 3);
+    assert_eq!(store.duplicate_positions(), vec![(0, 0, 0), (1, 0, 0)]);
+
+    assert_eq!(store.remove_duplicates(), 3);
+    let kept: Vec<_> = store.iter().map(|v| (v.pos, v.voxel_type)).collect();
+    assert_eq!(
+        kept,
+        vec![
+            ((2, 0, 0), VoxelType::Stone),
+            ((0, 0, 0), VoxelType::Dirt),
+            ((1, 0, 0), VoxelType::Water),
+        ]
+    );
+    assert_eq!(store.duplicate_count(), 0);
+    assert!(store.duplicate_positions().is_empty());
+    assert_eq!(store.get((1, 0, 0)).unwrap().voxel_type, VoxelType::Water);
+    assert_eq!(store.remove_duplicates(), 0);
+}
+
+#[test]
+fn voxels_moved_through_the_slice_are_found_at_their_new_position() {
+    let mut store = store(&[(0, 0, 0), (1, 0, 0)]);
+    for voxel in store.iter_mut() {
+        voxel.pos.1 += 1;
+    }
+    assert!(store.contains((0, 1, 0)));
+    assert!(!store.contains((0, 0, 0)));
+    assert!(store.remove((1, 1, 0)).is_some());
+    assert!(store.get_mut((0, 1, 0)).is_some());
+}
+
+#[test]
+fn stores_read_and_write_as_plain_lists() {
+    let store = store(&[(0, 0, 0), (4, 1, 2)]);
+    let text = ron::to_string(&store).unwrap();
+    let list: Vec<VoxelData> = ron::from_str(&text).unwrap();
+    assert_eq!(list.len(), 2);
+
+    let store: VoxelStore = ron::from_str(&ron::to_string(&list).unwrap()).unwrap();
+    assert!(store.contains((4, 1, 2)));
+
+    let bytes = bincode::serialize(&store).unwrap();
+    assert_eq!(bytes, bincode::serialize(&list).unwrap());
+}
@@ -1,5 +1,6 @@
 //! Voxel material type used in the map format.
 
+use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
@@ -8,4 +9,104 @@ pub enum VoxelType {
     Grass,
     Dirt,
     Stone,
+    /// Hazard: mild periodic damage while standing in it
+    Water,
+    /// Hazard: heavy periodic damage plus an upward knockback; glows
+    Lava,
+    /// Bright crystal block that glows but is safe to touch
+    Glowstone,
+    /// See-through block, meshed separately and alpha blended
+    Glass,
+}
+
+/// Part of a voxel's surface that can have a color of its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VoxelFace {
+    /// The upward-facing face
+    Top,
+    /// The four faces around the voxel
+    Side,
+    /// The downward-facing face
+    Bottom,
+}
+
+/// Damage dealt by a hazard voxel to the player standing on or inside it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hazard {
+    /// Health lost per damage tick
+    pub damage: u32,
+    /// Seconds between damage ticks while contact continues
+    pub interval: f32,
+    /// Upward velocity applied on each tick (0.0 = none)
+    pub knockback: f32,
+}
+
+impl VoxelType {
+    /// Hazard parameters for this type, or `None` for safe voxels.
+    pub fn hazard(self) -> Option<Hazard> {
+        match self {
+            VoxelType::Water => Some(Hazard {
+                damage: 1,
+                interval: 1.5,
+                knockback: 0.0,
+            }),
+            VoxelType::Lava => Some(Hazard {
+                damage: 2,
+                interval: 0.75,
+                knockback: 6.0,
+            }),
+            VoxelType::Air
+            | VoxelType::Grass
+            | VoxelType::Dirt
+            | VoxelType::Stone
+            | VoxelType::Glowstone
+            | VoxelType::Glass => None,
+        }
+    }
+
+    /// Whether touching this voxel hurts.
+    pub fn is_hazard(self) -> bool {
+        self.hazard().is_some()
+    }
+
+    /// Fixed base color for types that should read as a material at a
+    /// glance; other types use the position-hashed palette.
+    pub fn tint(self) -> Option<Color> {
+        match self {
+            VoxelType::Water => Some(Color::srgb(0.15, 0.4, 0.85)),
+            VoxelType::Lava => Some(Color::srgb(1.0, 0.35, 0.05)),
+            VoxelType::Glowstone => Some(Color::srgb(1.0, 0.85, 0.45)),
+            VoxelType::Glass => Some(Color::srgb(0.75, 0.9, 1.0)),
+            VoxelType::Air | VoxelType::Grass | VoxelType::Dirt | VoxelType::Stone => None,
+        }
+    }
+
+    /// Fixed color of `face` for types whose top looks different from their
+    /// sides (grass over dirt); overrides [`VoxelType::tint`] on that face.
+    pub fn face_tint(self, face: VoxelFace) -> Option<Color> {
+        match (self, face) {
+            (VoxelType::Grass, VoxelFace::Top) => Some(Color::srgb(0.35, 0.65, 0.2)),
+            (VoxelType::Grass, VoxelFace::Side | VoxelFace::Bottom) => {
+                Some(Color::srgb(0.5, 0.36, 0.22))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the voxel lets light and the view through.
+    ///
+    /// Transparent voxels hide the faces of other transparent voxels they
+    /// touch, but not those of opaque voxels behind them.
+    pub fn is_transparent(self) -> bool {
+        self == VoxelType::Glass
+    }
+
+    /// Light emitted by this type's chunk material, if it glows.
+    pub fn emissive(self) -> Option<LinearRgba> {
+        match self {
+            VoxelType::Lava => Some(LinearRgba::rgb(4.0, 1.2, 0.15)),
+            VoxelType::Glowstone => Some(LinearRgba::rgb(3.0, 2.4, 1.0)),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,108 @@
+//! Weather configuration structures.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Particles falling around the camera.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Precipitation {
+    /// Nothing falls
+    #[default]
+    None,
+    /// Fast, thin streaks
+    Rain,
+    /// Slow, drifting flakes
+    Snow,
+}
+
+impl Precipitation {
+    /// All precipitation kinds, in display order.
+    pub const ALL: [Precipitation; 3] = [Self::None, Self::Rain, Self::Snow];
+
+    /// Human-readable label for UI.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "Clear",
+            Self::Rain => "Rain",
+            Self::Snow => "Snow",
+        }
+    }
+}
+
+/// Weather configuration for the map.
+///
+/// Every field is optional in RON; a map without a `weather` section is clear.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct WeatherData {
+    /// What falls from the sky
+    pub precipitation: Precipitation,
+    /// How heavily it falls (0.0 to 1.0)
+    pub intensity: f32,
+    /// Fog thickness (0.0 = no fog, 1.0 = a few voxels of visibility)
+    pub fog_density: f32,
+    /// Fog color (r, g, b) in 0.0-1.0 range
+    pub fog_color: (f32, f32, f32),
+    /// How wet surfaces look (0.0 = dry, 1.0 = soaked); darkens the terrain
+    pub wetness: f32,
+}
+
+impl Default for WeatherData {
+    fn default() -> Self {
+        Self {
+            precipitation: Precipitation::None,
+            intensity: 1.0,
+            fog_density: 0.0,
+            fog_color: (0.7, 0.75, 0.8),
+            wetness: 0.0,
+        }
+    }
+}
+
+impl WeatherData {
+    /// Weather a trigger entity switches to, read from its properties.
+    ///
+    /// Returns `Ok(None)` when the entity has no `weather` property. `weather`
+    /// is `clear`, `rain` or `snow`; `intensity`, `fog_density` and `wetness`
+    /// are optional numbers in 0.0-1.0 that default as in [`WeatherData::default`].
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Option<Self>, String> {
+        let Some(kind) = properties.get("weather") else {
+            return Ok(None);
+        };
+        let precipitation = match kind.to_ascii_lowercase().as_str() {
+            "clear" | "none" => Precipitation::None,
+            "rain" => Precipitation::Rain,
+            "snow" => Precipitation::Snow,
+            _ => {
+                return Err(format!(
+                    "expected 'clear', 'rain' or 'snow' for 'weather', got {:?}",
+                    kind
+                ))
+            }
+        };
+
+        let mut weather = Self {
+            precipitation,
+            ..Self::default()
+        };
+        for (key, value) in [
+            ("intensity", &mut weather.intensity),
+            ("fog_density", &mut weather.fog_density),
+            ("wetness", &mut weather.wetness),
+        ] {
+            let Some(raw) = properties.get(key) else {
+                continue;
+            };
+            match raw.parse::<f32>() {
+                Ok(parsed) if (0.0..=1.0).contains(&parsed) => *value = parsed,
+                _ => {
+                    return Err(format!(
+                        "expected a number between 0.0 and 1.0 for '{}', got {:?}",
+                        key, raw
+                    ))
+                }
+            }
+        }
+        Ok(Some(weather))
+    }
+}
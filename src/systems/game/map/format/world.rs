@@ -2,7 +2,8 @@
 
 use super::patterns::SubVoxelPattern;
 use super::rotation::LegacyRotationState;
-use super::VoxelType;
+use super::voxel_regions;
+use super::{VoxelRegion, VoxelStore, VoxelType};
 use serde::{Deserialize, Serialize};
 
 /// World voxel data.
@@ -14,8 +15,46 @@ pub struct WorldData {
     pub height: i32,
     /// Depth of the world in voxels
     pub depth: i32,
-    /// List of non-air voxels with their positions and types
-    pub voxels: Vec<VoxelData>,
+    /// Non-air voxels with their positions and types, indexed by position
+    pub voxels: VoxelStore,
+    /// Boxes of identical voxels packed out of `voxels` in RON files.
+    ///
+    /// Loaders expand them into `voxels` right after parsing, so this is
+    /// empty everywhere else.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "voxel_regions::text_only"
+    )]
+    pub voxel_regions: Vec<VoxelRegion>,
+}
+
+impl WorldData {
+    /// Declared size as `(width, height, depth)`.
+    pub fn dimensions(&self) -> (i32, i32, i32) {
+        (self.width, self.height, self.depth)
+    }
+
+    /// Whether `pos` lies inside the declared bounds.
+    pub fn contains(&self, pos: (i32, i32, i32)) -> bool {
+        let (x, y, z) = pos;
+        (0..self.width).contains(&x)
+            && (0..self.height).contains(&y)
+            && (0..self.depth).contains(&z)
+    }
+
+    /// Move boxes of identical voxels into `voxel_regions` to shorten the
+    /// written file (see [`voxel_regions`]).
+    pub fn pack_voxel_regions(&mut self) {
+        let mut regions = voxel_regions::pack_voxel_regions(&mut self.voxels);
+        self.voxel_regions.append(&mut regions);
+    }
+
+    /// Expand `voxel_regions` back into `voxels`.
+    pub fn unpack_voxel_regions(&mut self) {
+        let regions = std::mem::take(&mut self.voxel_regions);
+        voxel_regions::unpack_voxel_regions(regions, &mut self.voxels);
+    }
 }
 
 /// Individual voxel data.
@@ -41,4 +80,47 @@ pub struct VoxelData {
     /// This field is never written on save; it will not appear in new files.
     #[serde(default)]
     pub rotation_state: Option<LegacyRotationState>,
+    /// Name of the voxel group this voxel belongs to, if any.
+    ///
+    /// Groups are shown, hidden, solidified and dissolved as a whole at
+    /// runtime; see `MapData::voxel_groups` for their starting state.
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+impl VoxelData {
+    /// An unrotated voxel outside any group.
+    pub fn new(
+        pos: (i32, i32, i32),
+        voxel_type: VoxelType,
+        pattern: Option<SubVoxelPattern>,
+    ) -> Self {
+        Self {
+            pos,
+            voxel_type,
+            pattern,
+            rotation: None,
+            rotation_state: None,
+            group: None,
+        }
+    }
+}
+
+/// Starting state of a named voxel group.
+///
+/// Groups that have no entry start visible and solid.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoxelGroupData {
+    /// Group name, as used by `VoxelData::group`
+    pub name: String,
+    /// Whether the group's voxels are drawn
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    /// Whether the group's voxels block movement
+    #[serde(default = "default_true")]
+    pub solid: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
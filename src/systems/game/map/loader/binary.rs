@@ -0,0 +1,64 @@
+//! Compact binary encoding of map files.
+//!
+//! A binary map is the [`BINARY_MAGIC`] header followed by the `MapData`
+//! encoded with bincode and then the map's voxels as palette chunks (see
+//! `format::voxel_palette`); the `MapData` only lists the voxels chunks
+//! can't hold. It holds exactly the same data as the RON form and is meant
+//! for shipping maps, not for hand editing.
+//!
+//! Version 1 files, which list every voxel in the `MapData` and have no
+//! chunks, still decode.
+
+use super::super::error::{MapLoadError, MapResult};
+use super::super::format::{pack_voxel_chunks, unpack_voxel_chunks, MapData, VoxelChunk};
+
+/// File header identifying a binary map; the last byte is the encoding version.
+pub const BINARY_MAGIC: &[u8; 8] = b"ADSMAP\0\x02";
+
+/// Header of version 1 binary maps, which have no palette chunks.
+const BINARY_MAGIC_V1: &[u8; 8] = b"ADSMAP\0\x01";
+
+/// File extension used for binary maps.
+#[allow(dead_code)]
+pub const BINARY_EXTENSION: &str = "bin";
+
+/// Whether `bytes` start with a binary map header of any version.
+pub fn is_binary_map(bytes: &[u8]) -> bool {
+    bytes.starts_with(&BINARY_MAGIC[..7])
+}
+
+/// Encode `map` as a binary map file.
+#[allow(dead_code)]
+pub fn encode_binary(map: &MapData) -> MapResult<Vec<u8>> {
+    let mut map = map.clone();
+    map.world.unpack_voxel_regions();
+    let chunks = pack_voxel_chunks(&mut map.world.voxels);
+
+    let mut bytes = BINARY_MAGIC.to_vec();
+    bincode::serialize_into(&mut bytes, &map)?;
+    bincode::serialize_into(&mut bytes, &chunks)?;
+    Ok(bytes)
+}
+
+/// Decode a binary map file produced by [`encode_binary`].
+pub fn decode_binary(bytes: &[u8]) -> MapResult<MapData> {
+    if let Some(body) = bytes.strip_prefix(BINARY_MAGIC_V1.as_slice()) {
+        return Ok(bincode::deserialize(body)?);
+    }
+    let Some(mut body) = bytes.strip_prefix(BINARY_MAGIC.as_slice()) else {
+        return Err(match bytes.get(..8) {
+            Some(header) if is_binary_map(header) => {
+                MapLoadError::UnsupportedVersion(format!("binary encoding version {}", header[7]))
+            }
+            _ => {
+                MapLoadError::ValidationError("Not a binary map file (missing header)".to_string())
+            }
+        });
+    };
+
+    let mut map: MapData = bincode::deserialize_from(&mut body)?;
+    let chunks: Vec<VoxelChunk> = bincode::deserialize_from(&mut body)?;
+    unpack_voxel_chunks(&chunks, &mut map.world.voxels)
+        .map_err(|e| MapLoadError::ValidationError(format!("Corrupt binary map: {}", e)))?;
+    Ok(map)
+}
@@ -0,0 +1,88 @@
+//! Compressed map files.
+//!
+//! RON and binary maps can be wrapped in gzip or zstd. The compressor is
+//! chosen by the file name on save (`forest.ron.gz`, `forest.bin.zst`) and
+//! recognised by its magic bytes on load, so loaders don't need the name.
+
+use std::borrow::Cow;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of a gzip stream.
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+/// Magic bytes at the start of a zstd frame.
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// File extension for gzip-compressed maps.
+pub const GZIP_EXTENSION: &str = "gz";
+/// File extension for zstd-compressed maps.
+pub const ZSTD_EXTENSION: &str = "zst";
+
+/// zstd level used when saving; favours speed over the last few percent.
+const ZSTD_LEVEL: i32 = 3;
+
+/// How a map file is compressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MapCompression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl MapCompression {
+    /// Compression asked for by the last extension of `path`.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case(GZIP_EXTENSION) => Self::Gzip,
+            Some(ext) if ext.eq_ignore_ascii_case(ZSTD_EXTENSION) => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    /// Compression of a file starting with `bytes`.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(GZIP_MAGIC) {
+            Self::Gzip
+        } else if bytes.starts_with(ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    /// Compress `bytes` with this compressor.
+    pub fn compress(self, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(bytes),
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&bytes)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::encode_all(bytes.as_slice(), ZSTD_LEVEL),
+        }
+    }
+}
+
+/// Unpack `bytes` if they are compressed, or return them as they are.
+pub fn decompress(bytes: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    match MapCompression::detect(bytes) {
+        MapCompression::None => Ok(Cow::Borrowed(bytes)),
+        MapCompression::Gzip => {
+            let mut out = Vec::new();
+            flate2::read::GzDecoder::new(bytes).read_to_end(&mut out)?;
+            Ok(Cow::Owned(out))
+        }
+        MapCompression::Zstd => zstd::decode_all(bytes).map(Cow::Owned),
+    }
+}
+
+/// `path` without its compression extension, if it has one.
+pub fn strip_compression_extension(path: &Path) -> Cow<'_, Path> {
+    match MapCompression::from_path(path) {
+        MapCompression::None => Cow::Borrowed(path),
+        _ => Cow::Owned(path.with_extension("")),
+    }
+}
@@ -1,11 +1,31 @@
 //! Map loading functionality with progress tracking.
+//!
+//! Map files are RON text or the binary encoding from [`binary`], optionally
+//! wrapped in gzip or zstd ([`compression`]); the loaders detect which by the
+//! file header.
+//!
+//! Maps exported or merged by hand can list several voxels at one position.
+//! The validating loaders keep the last voxel at each position, as the map
+//! optimizer does, and log a warning with the counts; the unvalidated reader
+//! leaves them for the editor to report and clean up.
+//!
+//! RON files that fail to parse as a whole can be read in safe mode
+//! ([`safe_mode`]), which skips the sections and list items that don't parse
+//! and returns a [`LoadDiagnostic`] for each so the author can fix the file.
+
+pub mod binary;
+pub mod compression;
+pub mod safe_mode;
 
 use super::error::{MapLoadError, MapResult};
+use super::format::migrate_editor_properties;
 use super::format::migrate_legacy_rotations;
 use super::format::normalise_staircase_variants;
-use super::format::MapData;
+use super::format::{MapData, MapMetadata};
 use super::validation::validate_map;
 use bevy::prelude::*;
+use compression::{decompress, strip_compression_extension, MapCompression};
+pub use safe_mode::LoadDiagnostic;
 use std::fs;
 use std::path::Path;
 
@@ -49,6 +69,7 @@ impl LoadProgress {
     }
 
     /// Get a human-readable description of the current progress.
+    #[allow(dead_code)]
     pub fn description(&self) -> String {
         match self {
             Self::Started => "Starting map load...".to_string(),
@@ -118,6 +139,22 @@ pub struct LoadedMapData {
     pub map: MapData,
 }
 
+/// Parts of the loaded map that safe mode skipped, shown on the loading
+/// screen. Empty when the file loaded normally.
+#[derive(Resource, Default)]
+pub struct MapLoadDiagnostics {
+    pub diagnostics: Vec<LoadDiagnostic>,
+    /// Whether the player has read them and asked to go on
+    pub acknowledged: bool,
+}
+
+impl MapLoadDiagnostics {
+    /// Whether the game can leave the loading screen.
+    pub fn allows_start(&self) -> bool {
+        self.diagnostics.is_empty() || self.acknowledged
+    }
+}
+
 /// Map loader with progress tracking.
 pub struct MapLoader;
 
@@ -135,16 +172,13 @@ impl MapLoader {
 
         // Stage 1: Load file (0-20%)
         progress.update(LoadProgress::LoadingFile(0.0));
-        let content = fs::read_to_string(path.as_ref())?;
+        let bytes = fs::read(path.as_ref())?;
         progress.update(LoadProgress::LoadingFile(1.0));
 
-        // Stage 2: Parse RON data (20-40%)
+        // Stage 2: Parse map data (20-40%)
         progress.update(LoadProgress::ParsingData(0.0));
-        let mut map: MapData = ron::from_str(&content)?;
-        // Migrate legacy rotation_state fields to the new orientation matrix system
-        migrate_legacy_rotations(&mut map.orientations, &mut map.world.voxels);
-        // Normalise directional staircase variants to Staircase + composed orientation matrix
-        normalise_staircase_variants(&mut map.orientations, &mut map.world.voxels);
+        let mut map = parse_map_bytes(&bytes)?;
+        resolve_duplicate_voxels(&mut map, path.as_ref());
         progress.update(LoadProgress::ParsingData(1.0));
 
         // Stage 3: Validate map (40-60%)
@@ -155,30 +189,104 @@ impl MapLoader {
         Ok(map)
     }
 
+    /// Load a map like [`MapLoader::load_from_file`], reading a RON file
+    /// that doesn't parse in safe mode instead of failing.
+    ///
+    /// Returns the map with a diagnostic for each section or item skipped;
+    /// the list is empty when the file parsed normally. The map is still
+    /// validated as a whole.
+    pub fn load_safe(
+        path: impl AsRef<Path>,
+        progress: &mut MapLoadProgress,
+    ) -> MapResult<(MapData, Vec<LoadDiagnostic>)> {
+        progress.update(LoadProgress::Started);
+
+        progress.update(LoadProgress::LoadingFile(0.0));
+        let bytes = fs::read(path.as_ref())?;
+        progress.update(LoadProgress::LoadingFile(1.0));
+
+        progress.update(LoadProgress::ParsingData(0.0));
+        let (mut map, diagnostics) = parse_map_bytes_tolerant(&bytes)?;
+        for diagnostic in &diagnostics {
+            warn!("{:?}: {}", path.as_ref(), diagnostic);
+        }
+        resolve_duplicate_voxels(&mut map, path.as_ref());
+        progress.update(LoadProgress::ParsingData(1.0));
+
+        progress.update(LoadProgress::ValidatingMap(0.0));
+        validate_map(&map)?;
+        progress.update(LoadProgress::ValidatingMap(1.0));
+
+        Ok((map, diagnostics))
+    }
+
     /// Load a map from a file path without progress tracking.
     ///
     /// This is a simpler version for cases where progress tracking is not needed.
     #[allow(dead_code)]
     pub fn load_simple(path: impl AsRef<Path>) -> MapResult<MapData> {
-        let content = fs::read_to_string(path.as_ref())?;
-        let mut map: MapData = ron::from_str(&content)?;
-        migrate_legacy_rotations(&mut map.orientations, &mut map.world.voxels);
-        normalise_staircase_variants(&mut map.orientations, &mut map.world.voxels);
+        let mut map = Self::read_unvalidated(path.as_ref())?;
+        resolve_duplicate_voxels(&mut map, path.as_ref());
         validate_map(&map)?;
         Ok(map)
     }
 
+    /// Read and migrate a map file without validating it.
+    ///
+    /// Used by tools that repair maps validation would reject (e.g. duplicate
+    /// voxels). Callers are responsible for running `validate_map` afterwards.
+    #[allow(dead_code)]
+    pub fn read_unvalidated(path: impl AsRef<Path>) -> MapResult<MapData> {
+        parse_map_bytes(&fs::read(path.as_ref())?)
+    }
+
+    /// [`MapLoader::read_unvalidated`], reading a RON file that doesn't
+    /// parse in safe mode instead of failing.
+    ///
+    /// Returns a diagnostic for each section or item skipped, none when the
+    /// file parsed normally.
+    pub fn read_tolerant(path: impl AsRef<Path>) -> MapResult<(MapData, Vec<LoadDiagnostic>)> {
+        parse_map_bytes_tolerant(&fs::read(path.as_ref())?)
+    }
+
     /// Save a map to a file.
     ///
-    /// This can be used by a map editor to save maps.
+    /// This can be used by a map editor to save maps. The format follows the
+    /// file name, see [`MapLoader::encode_for_path`].
     #[allow(dead_code)]
     pub fn save_to_file(map: &MapData, path: impl AsRef<Path>) -> MapResult<()> {
-        let ron_string = ron::ser::to_string_pretty(map, ron::ser::PrettyConfig::default())
-            .map_err(|e| {
-                MapLoadError::ValidationError(format!("Failed to serialize map: {}", e))
-            })?;
+        let bytes = Self::encode_for_path(map, path.as_ref())?;
+        fs::write(path.as_ref(), bytes)?;
+        Ok(())
+    }
+
+    /// Encode a map the way its file name asks for.
+    ///
+    /// Names ending in `.gz` or `.zst` are compressed, and the extension
+    /// before that picks the encoding: binary for `.bin`, RON with voxel
+    /// regions packed otherwise (`forest.ron.zst`, `forest.bin.gz`).
+    pub fn encode_for_path(map: &MapData, path: &Path) -> MapResult<Vec<u8>> {
+        let binary = strip_compression_extension(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(binary::BINARY_EXTENSION));
+        let bytes = if binary {
+            binary::encode_binary(map)?
+        } else {
+            let mut packed = map.clone();
+            packed.world.pack_voxel_regions();
+            ron::ser::to_string_pretty(&packed, ron::ser::PrettyConfig::default())
+                .map_err(|e| MapLoadError::EncodeError(e.to_string()))?
+                .into_bytes()
+        };
+        MapCompression::from_path(path)
+            .compress(bytes)
+            .map_err(|e| MapLoadError::EncodeError(e.to_string()))
+    }
 
-        fs::write(path.as_ref(), ron_string)?;
+    /// Save a map to a file in the binary encoding.
+    #[allow(dead_code)]
+    pub fn save_binary(map: &MapData, path: impl AsRef<Path>) -> MapResult<()> {
+        fs::write(path.as_ref(), binary::encode_binary(map)?)?;
         Ok(())
     }
 
@@ -186,6 +294,156 @@ impl MapLoader {
     pub fn load_default() -> MapData {
         MapData::default_map()
     }
+
+    /// Read only the `metadata` section of a map file.
+    ///
+    /// Skips parsing and validating the world data, so the loading screen can
+    /// show the map's name and author before the full load starts.
+    pub fn read_metadata(path: impl AsRef<Path>) -> MapResult<MapMetadata> {
+        let bytes = fs::read(path.as_ref())?;
+        let bytes = decompress(&bytes)?;
+        if binary::is_binary_map(&bytes) {
+            // Binary maps have no text header to scan; decoding is fast enough.
+            return Ok(binary::decode_binary(&bytes)?.metadata);
+        }
+        parse_metadata_header(&String::from_utf8_lossy(&bytes))
+    }
+}
+
+/// Parse a RON or binary map file, compressed or not, expand its voxel
+/// regions, migrate legacy rotation data and editor properties and give
+/// entities ids.
+fn parse_map_bytes(bytes: &[u8]) -> MapResult<MapData> {
+    let bytes = decompress(bytes)?;
+    let mut map: MapData = if binary::is_binary_map(&bytes) {
+        binary::decode_binary(&bytes)?
+    } else {
+        let content = std::str::from_utf8(&bytes).map_err(|e| {
+            MapLoadError::FileReadError(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })?;
+        ron::from_str(content)?
+    };
+    finish_parsed_map(&mut map);
+    Ok(map)
+}
+
+/// [`parse_map_bytes`], falling back to [`safe_mode`] when RON text fails
+/// to parse.
+fn parse_map_bytes_tolerant(bytes: &[u8]) -> MapResult<(MapData, Vec<LoadDiagnostic>)> {
+    match parse_map_bytes(bytes) {
+        Err(MapLoadError::ParseError(error)) => {
+            let bytes = decompress(bytes)?;
+            let content = String::from_utf8_lossy(&bytes);
+            let Some((mut map, diagnostics)) = safe_mode::parse_map_tolerant(&content) else {
+                return Err(MapLoadError::ParseError(error));
+            };
+            finish_parsed_map(&mut map);
+            Ok((map, diagnostics))
+        }
+        result => result.map(|map| (map, Vec::new())),
+    }
+}
+
+/// Expand voxel regions, run the format migrations and give entities ids.
+fn finish_parsed_map(map: &mut MapData) {
+    map.world.unpack_voxel_regions();
+    // Migrate legacy rotation_state fields to the new orientation matrix system
+    migrate_legacy_rotations(&mut map.orientations, &mut map.world.voxels);
+    // Normalise directional staircase variants to Staircase + composed orientation matrix
+    normalise_staircase_variants(&mut map.orientations, &mut map.world.voxels);
+    // Maps saved before entity ids existed
+    map.assign_entity_ids();
+    // Editor data kept in custom properties before the editor_metadata section
+    migrate_editor_properties(map);
+}
+
+/// Keep only the last voxel at each position of `map`, read from `path`,
+/// warning when any were dropped. Returns how many were.
+pub fn resolve_duplicate_voxels(map: &mut MapData, path: &Path) -> usize {
+    let voxels = &mut map.world.voxels;
+    let positions = voxels.duplicate_positions().len();
+    let removed = voxels.remove_duplicates();
+    if removed > 0 {
+        warn!(
+            "{:?} lists {} duplicate voxels at {} positions; kept the last voxel at each",
+            path, removed, positions
+        );
+    }
+    removed
+}
+
+/// Parse the `metadata: (...)` section out of a map file's RON text.
+///
+/// The section is located by bracket matching (string literals and comments
+/// are skipped) and deserialized on its own; the rest of the file is ignored.
+pub fn parse_metadata_header(content: &str) -> MapResult<MapMetadata> {
+    let section = find_metadata_section(content)
+        .ok_or_else(|| MapLoadError::MissingField("metadata".to_string()))?;
+    Ok(ron::from_str(section)?)
+}
+
+/// Returns the parenthesised value following the top-level `metadata:` key.
+fn find_metadata_section(content: &str) -> Option<&str> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    let mut value_start = None;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'(' | b'[' | b'{' => {
+                depth += 1;
+                if depth == 2 && value_start.is_none() && is_metadata_key_before(content, i) {
+                    value_start = Some(i);
+                }
+            }
+            b')' | b']' | b'}' => {
+                if depth == 2 {
+                    if let Some(start) = value_start {
+                        return Some(&content[start..=i]);
+                    }
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Whether the text before `open` (ignoring whitespace) is `metadata:`.
+fn is_metadata_key_before(content: &str, open: usize) -> bool {
+    content[..open]
+        .trim_end()
+        .strip_suffix(':')
+        .map(|before| before.trim_end())
+        .is_some_and(|before| {
+            before.ends_with("metadata")
+                && !before[..before.len() - "metadata".len()]
+                    .ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        })
 }
 
 /// System to load a map from a file path.
@@ -0,0 +1,342 @@
+//! Safe-mode parsing of RON map files that don't parse as a whole.
+//!
+//! One malformed entity or voxel makes `ron` reject the entire file. The
+//! tolerant parser splits the file into its top-level sections by bracket
+//! matching (string literals and comments are skipped), parses each section
+//! on its own and, in the voxel, region, entity, group and camera sequence
+//! lists, each item on its own. Items that fail are skipped and sections that
+//! fail keep the empty map's defaults; each leaves a [`LoadDiagnostic`]
+//! naming the item and the line of the problem.
+//!
+//! `orientations` is parsed as a whole: voxels refer to its entries by
+//! index, so dropping one would turn the voxels after it.
+
+use super::super::format::{
+    CameraData, CameraSequenceData, EdgeData, EditorMetadata, EntityData, LightingData, MapData,
+    MapMetadata, MeshingData, OrientationMatrix, VoxelData, VoxelGroupData, VoxelRegion,
+    WeatherData,
+};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// A part of a map file safe mode skipped or replaced with defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadDiagnostic {
+    /// The section or list item, such as `entities[3]` or `lighting`
+    pub item: String,
+    /// 1-based line of the problem in the file
+    pub line: usize,
+    /// What was wrong
+    pub message: String,
+}
+
+impl fmt::Display for LoadDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {}", self.line, self.item, self.message)
+    }
+}
+
+/// Sections `MapData` can't be read without; safe mode uses defaults.
+const REQUIRED_SECTIONS: [&str; 5] = ["metadata", "world", "entities", "lighting", "camera"];
+
+/// Parse a map's RON text section by section, skipping what doesn't parse.
+///
+/// Returns `None` when the text has no `( ... )` map body at all. The map
+/// is returned as written: voxel regions are still packed and nothing is
+/// migrated or validated.
+pub fn parse_map_tolerant(content: &str) -> Option<(MapData, Vec<LoadDiagnostic>)> {
+    let body = inner_group(content, 0..content.len())?;
+    let mut parser = TolerantParser {
+        content,
+        diagnostics: Vec::new(),
+    };
+    let mut map = MapData::empty_map();
+    let mut seen = Vec::new();
+
+    for (name, value) in parser.fields(body.clone()) {
+        let section = &content[name.clone()];
+        match section {
+            "metadata" => parser.parse_into::<MapMetadata>(section, value, &mut map.metadata),
+            "world" => parser.parse_world(value, &mut map),
+            "entities" => map.entities = parser.parse_list::<EntityData>(section, value),
+            "lighting" => parser.parse_into::<LightingData>(section, value, &mut map.lighting),
+            "weather" => parser.parse_into::<WeatherData>(section, value, &mut map.weather),
+            "edges" => parser.parse_into::<EdgeData>(section, value, &mut map.edges),
+            "meshing" => parser.parse_into::<MeshingData>(section, value, &mut map.meshing),
+            "camera" => parser.parse_into::<CameraData>(section, value, &mut map.camera),
+            "orientations" => {
+                parser.parse_into::<Vec<OrientationMatrix>>(section, value, &mut map.orientations)
+            }
+            "voxel_groups" => {
+                map.voxel_groups = parser.parse_list::<VoxelGroupData>(section, value)
+            }
+            "camera_sequences" => {
+                map.camera_sequences = parser.parse_list::<CameraSequenceData>(section, value)
+            }
+            "editor_metadata" => parser.parse_into::<Option<EditorMetadata>>(
+                section,
+                value,
+                &mut map.editor_metadata,
+            ),
+            "custom_properties" => parser.parse_into::<HashMap<String, String>>(
+                section,
+                value,
+                &mut map.custom_properties,
+            ),
+            // The strict parser ignores unknown sections too
+            _ => {}
+        }
+        seen.push(section);
+    }
+
+    let end = parser.line_of(body.end);
+    for section in REQUIRED_SECTIONS {
+        if !seen.contains(&section) {
+            parser.report(section, end, "missing; using defaults".to_string());
+        }
+    }
+    Some((map, parser.diagnostics))
+}
+
+struct TolerantParser<'a> {
+    content: &'a str,
+    diagnostics: Vec<LoadDiagnostic>,
+}
+
+impl TolerantParser<'_> {
+    /// The `name: value` fields of a struct body, as byte ranges. Items that
+    /// aren't fields are reported and skipped.
+    fn fields(&mut self, body: Range<usize>) -> Vec<(Range<usize>, Range<usize>)> {
+        let mut fields = Vec::new();
+        for item in split_items(self.content, body) {
+            let colon = Structural::new(self.content.as_bytes(), item.clone())
+                .find(|&(_, byte)| matches!(byte, b':' | b'(' | b'[' | b'{'))
+                .filter(|&(_, byte)| byte == b':');
+            match colon {
+                Some((colon, _)) => {
+                    let name = trim(self.content, item.start..colon);
+                    fields.push((name, trim(self.content, colon + 1..item.end)));
+                }
+                None => {
+                    let line = self.line_of(item.start);
+                    self.report("map", line, "expected `name: value`".to_string());
+                }
+            }
+        }
+        fields
+    }
+
+    fn parse_world(&mut self, value: Range<usize>, map: &mut MapData) {
+        let Some(body) = inner_group(self.content, value.clone()) else {
+            let line = self.line_of(value.start);
+            self.report(
+                "world",
+                line,
+                "expected `( ... )`; using defaults".to_string(),
+            );
+            return;
+        };
+        let world = &mut map.world;
+        let mut seen = Vec::new();
+        for (name, value) in self.fields(body.clone()) {
+            let field = &self.content[name];
+            let item = format!("world.{}", field);
+            match field {
+                "width" => self.parse_into::<i32>(&item, value, &mut world.width),
+                "height" => self.parse_into::<i32>(&item, value, &mut world.height),
+                "depth" => self.parse_into::<i32>(&item, value, &mut world.depth),
+                "voxels" => {
+                    world.voxels = self.parse_list::<VoxelData>(&item, value).into();
+                }
+                "voxel_regions" => {
+                    world.voxel_regions = self.parse_list::<VoxelRegion>(&item, value);
+                }
+                _ => {}
+            }
+            seen.push(field);
+        }
+        let end = self.line_of(body.end);
+        for field in ["width", "height", "depth", "voxels"] {
+            if !seen.contains(&field) {
+                self.report(&format!("world.{}", field), end, "missing".to_string());
+            }
+        }
+    }
+
+    /// Parse a whole section into `target`, leaving it as it is on failure.
+    fn parse_into<T: DeserializeOwned>(&mut self, item: &str, value: Range<usize>, target: &mut T) {
+        if let Some(parsed) = self.parse(item, value, "using defaults") {
+            *target = parsed;
+        }
+    }
+
+    /// Parse a `[ ... ]` list item by item, keeping the items that parse.
+    fn parse_list<T: DeserializeOwned>(&mut self, item: &str, value: Range<usize>) -> Vec<T> {
+        let Some(list) = inner_group(self.content, value.clone()) else {
+            let line = self.line_of(value.start);
+            self.report(item, line, "expected `[ ... ]`; left empty".to_string());
+            return Vec::new();
+        };
+        split_items(self.content, list)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, range)| {
+                self.parse(&format!("{}[{}]", item, index), range, "skipped")
+            })
+            .collect()
+    }
+
+    fn parse<T: DeserializeOwned>(
+        &mut self,
+        item: &str,
+        value: Range<usize>,
+        fallback: &str,
+    ) -> Option<T> {
+        match ron::from_str(&self.content[value.clone()]) {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                let line = self.line_of(value.start) + e.position.line.saturating_sub(1);
+                self.report(item, line, format!("{}; {}", e.code, fallback));
+                None
+            }
+        }
+    }
+
+    fn report(&mut self, item: &str, line: usize, message: String) {
+        self.diagnostics.push(LoadDiagnostic {
+            item: item.to_string(),
+            line,
+            message,
+        });
+    }
+
+    /// 1-based line holding byte `offset`.
+    fn line_of(&self, offset: usize) -> usize {
+        self.content.as_bytes()[..offset]
+            .iter()
+            .filter(|&&byte| byte == b'\n')
+            .count()
+            + 1
+    }
+}
+
+/// The inside of the first bracketed group in `range`. An unclosed group
+/// runs to the end of the range, so a truncated file keeps what it has.
+fn inner_group(content: &str, range: Range<usize>) -> Option<Range<usize>> {
+    let mut depth = 0usize;
+    let mut start = None;
+    for (i, byte) in Structural::new(content.as_bytes(), range.clone()) {
+        match byte {
+            b'(' | b'[' | b'{' => {
+                depth += 1;
+                start.get_or_insert(i + 1);
+            }
+            b')' | b']' | b'}' if start.is_some() => {
+                depth -= 1;
+                if depth == 0 {
+                    return start.map(|start| start..i);
+                }
+            }
+            _ => {}
+        }
+    }
+    start.map(|start| start..range.end)
+}
+
+/// Split the inside of a group at its top-level commas, trimmed, leaving out
+/// empty items such as the one after a trailing comma.
+fn split_items(content: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = range.start;
+    for (i, byte) in Structural::new(content.as_bytes(), range.clone()) {
+        match byte {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b',' if depth == 0 => {
+                items.push(start..i);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(start..range.end);
+    items
+        .into_iter()
+        .map(|item| trim(content, item))
+        .filter(|item| {
+            Structural::new(content.as_bytes(), item.clone())
+                .any(|(_, byte)| !byte.is_ascii_whitespace())
+        })
+        .collect()
+}
+
+/// `range` without surrounding whitespace.
+fn trim(content: &str, range: Range<usize>) -> Range<usize> {
+    let text = &content[range.clone()];
+    let start = range.start + (text.len() - text.trim_start().len());
+    start..(start + text.trim().len())
+}
+
+/// The bytes of a range outside comments, with each string literal reduced
+/// to its opening quote.
+struct Structural<'a> {
+    bytes: &'a [u8],
+    i: usize,
+    end: usize,
+}
+
+impl<'a> Structural<'a> {
+    fn new(bytes: &'a [u8], range: Range<usize>) -> Self {
+        Self {
+            bytes,
+            i: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl Iterator for Structural<'_> {
+    type Item = (usize, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+        while self.i < self.end {
+            let i = self.i;
+            match bytes[i] {
+                b'"' => {
+                    self.i += 1;
+                    while self.i < self.end && bytes[self.i] != b'"' {
+                        if bytes[self.i] == b'\\' {
+                            self.i += 1;
+                        }
+                        self.i += 1;
+                    }
+                    self.i += 1;
+                    return Some((i, b'"'));
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    while self.i < self.end && bytes[self.i] != b'\n' {
+                        self.i += 1;
+                    }
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    self.i += 2;
+                    while self.i + 1 < self.end
+                        && !(bytes[self.i] == b'*' && bytes[self.i + 1] == b'/')
+                    {
+                        self.i += 1;
+                    }
+                    self.i += 2;
+                }
+                byte => {
+                    self.i += 1;
+                    return Some((i, byte));
+                }
+            }
+        }
+        None
+    }
+}
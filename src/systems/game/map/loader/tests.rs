@@ -1,4 +1,5 @@
 use super::*;
+use crate::systems::game::map::format::{EntityData, EntityId, VoxelType};
 
 #[test]
 fn test_load_progress_percentage() {
@@ -44,3 +45,338 @@ fn test_load_default_map() {
     assert_eq!(map.metadata.name, "Default Map");
     assert!(validate_map(&map).is_ok());
 }
+
+#[test]
+fn test_parse_metadata_header_from_default_map_file() {
+    let content = fs::read_to_string("assets/maps/default.ron").unwrap();
+    let metadata = parse_metadata_header(&content).unwrap();
+    assert_eq!(metadata.name, "Default Map");
+    assert_eq!(metadata.author, "System");
+}
+
+#[test]
+fn test_parse_metadata_header_accepts_struct_name_prefix() {
+    let content = r#"MapData(metadata: (name: "A", author: "B", description: "C", version: "1.0.0", created: "x"), world: ())"#;
+    let metadata = parse_metadata_header(content).unwrap();
+    assert_eq!(metadata.name, "A");
+    assert_eq!(metadata.description, "C");
+}
+
+#[test]
+fn test_parse_metadata_header_ignores_brackets_in_strings_and_comments() {
+    let content = r#"(
+    // a comment with ) and (
+    metadata: (
+        name: "Cave (Part 2)",
+        author: "Quote \" and )",
+        description: "/* not a comment */",
+        version: "1.0.0",
+        created: "2025-01-10",
+    ),
+)"#;
+    let metadata = parse_metadata_header(content).unwrap();
+    assert_eq!(metadata.name, "Cave (Part 2)");
+    assert_eq!(metadata.author, "Quote \" and )");
+    assert_eq!(metadata.description, "/* not a comment */");
+}
+
+#[test]
+fn test_parse_metadata_header_ignores_nested_metadata_keys() {
+    // A `metadata` key inside another section must not be picked up.
+    let content = r#"(world: (metadata: (name: "wrong")), metadata: (name: "right", author: "", description: "", version: "1.0.0", created: ""))"#;
+    let metadata = parse_metadata_header(content).unwrap();
+    assert_eq!(metadata.name, "right");
+}
+
+#[test]
+fn test_parse_metadata_header_missing_section_is_error() {
+    let result = parse_metadata_header("(world: ())");
+    assert!(matches!(result, Err(MapLoadError::MissingField(_))));
+}
+
+#[test]
+fn test_binary_round_trip_preserves_map() {
+    let map = MapLoader::load_simple("assets/maps/default.ron").unwrap();
+    let bytes = binary::encode_binary(&map).unwrap();
+    assert!(binary::is_binary_map(&bytes));
+
+    let decoded = binary::decode_binary(&bytes).unwrap();
+    assert_eq!(decoded.metadata.name, map.metadata.name);
+    assert_eq!(decoded.world.voxels.len(), map.world.voxels.len());
+    assert_eq!(decoded.entities.len(), map.entities.len());
+    assert_eq!(decoded.orientations, map.orientations);
+}
+
+#[test]
+fn test_binary_maps_leave_out_editor_metadata() {
+    let mut map = MapLoader::load_default();
+    map.edit_editor_metadata(|metadata| metadata.guides.push("plane x 4".to_string()));
+    let plain = binary::encode_binary(&MapLoader::load_default()).unwrap();
+
+    let bytes = binary::encode_binary(&map).unwrap();
+    assert_eq!(bytes.len(), plain.len());
+    assert!(binary::decode_binary(&bytes)
+        .unwrap()
+        .editor_metadata
+        .is_none());
+}
+
+#[test]
+fn test_decode_binary_rejects_missing_header() {
+    assert!(binary::decode_binary(b"(metadata: ())").is_err());
+}
+
+#[test]
+fn test_decode_binary_reads_version_1_files() {
+    let map = MapLoader::load_default();
+    let mut bytes = b"ADSMAP\0\x01".to_vec();
+    bincode::serialize_into(&mut bytes, &map).unwrap();
+    assert!(binary::is_binary_map(&bytes));
+
+    let decoded = binary::decode_binary(&bytes).unwrap();
+    assert_eq!(decoded.world.voxels.len(), map.world.voxels.len());
+
+    bytes[7] = 9;
+    assert!(matches!(
+        binary::decode_binary(&bytes),
+        Err(MapLoadError::UnsupportedVersion(_))
+    ));
+}
+
+#[test]
+fn test_binary_maps_store_voxels_in_palette_chunks() {
+    let map = MapData::stress_map(64, 7);
+    let mut v1 = b"ADSMAP\0\x01".to_vec();
+    bincode::serialize_into(&mut v1, &map).unwrap();
+    let bytes = binary::encode_binary(&map).unwrap();
+    assert!(
+        bytes.len() * 4 < v1.len(),
+        "{} vs {}",
+        bytes.len(),
+        v1.len()
+    );
+
+    let decoded = binary::decode_binary(&bytes).unwrap();
+    assert_eq!(decoded.world.voxels.len(), map.world.voxels.len());
+    assert!(map
+        .world
+        .voxels
+        .iter()
+        .all(|v| decoded.world.voxels.get(v.pos).map(|d| d.voxel_type) == Some(v.voxel_type)));
+}
+
+#[test]
+fn test_loaders_accept_binary_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("map.bin");
+    let map = MapLoader::load_default();
+    MapLoader::save_binary(&map, &path).unwrap();
+
+    let loaded = MapLoader::load_simple(&path).unwrap();
+    assert_eq!(loaded.world.voxels.len(), map.world.voxels.len());
+    let metadata = MapLoader::read_metadata(&path).unwrap();
+    assert_eq!(metadata.name, map.metadata.name);
+}
+
+#[test]
+fn test_read_unvalidated_keeps_maps_that_fail_validation() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dup.ron");
+    let mut map = MapLoader::load_default();
+    let duplicate = map.world.voxels[0].clone();
+    map.world.voxels.push(duplicate);
+    MapLoader::save_to_file(&map, &path).unwrap();
+
+    let read = MapLoader::read_unvalidated(&path).unwrap();
+    assert_eq!(read.world.voxels.len(), map.world.voxels.len());
+    assert_eq!(read.world.voxels.duplicate_count(), 1);
+}
+
+#[test]
+fn test_loaders_drop_duplicate_voxels_keeping_the_last() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dup.ron");
+    let mut map = MapLoader::load_default();
+    let mut duplicate = map.world.voxels[0].clone();
+    duplicate.voxel_type = VoxelType::Glowstone;
+    map.world.voxels.push(duplicate.clone());
+    MapLoader::save_to_file(&map, &path).unwrap();
+
+    let loaded = MapLoader::load_simple(&path).unwrap();
+    assert_eq!(loaded.world.voxels.len(), map.world.voxels.len() - 1);
+    assert_eq!(
+        loaded.world.voxels.get(duplicate.pos).unwrap().voxel_type,
+        VoxelType::Glowstone
+    );
+
+    let mut progress = MapLoadProgress::new();
+    let loaded = MapLoader::load_from_file(&path, &mut progress).unwrap();
+    assert_eq!(loaded.world.voxels.duplicate_count(), 0);
+}
+
+#[test]
+fn test_loaders_accept_compressed_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let map = MapLoader::load_default();
+    for name in ["map.ron.gz", "map.ron.zst", "map.bin.gz", "map.bin.zst"] {
+        let path = dir.path().join(name);
+        MapLoader::save_to_file(&map, &path).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        assert_ne!(
+            MapCompression::detect(&bytes),
+            MapCompression::None,
+            "{name} is compressed"
+        );
+
+        let loaded = MapLoader::load_simple(&path).unwrap();
+        assert_eq!(loaded.world.voxels.len(), map.world.voxels.len(), "{name}");
+        let metadata = MapLoader::read_metadata(&path).unwrap();
+        assert_eq!(metadata.name, map.metadata.name);
+    }
+}
+
+#[test]
+fn test_ron_files_pack_voxel_regions_and_binary_files_do_not() {
+    let map = MapLoader::load_default();
+
+    let ron_bytes = MapLoader::encode_for_path(&map, Path::new("map.ron")).unwrap();
+    let text = std::str::from_utf8(&ron_bytes).unwrap();
+    assert!(text.contains("voxel_regions"));
+    let loaded = parse_map_bytes(&ron_bytes).unwrap();
+    assert_eq!(loaded.world.voxels.len(), map.world.voxels.len());
+    assert!(loaded.world.voxel_regions.is_empty());
+
+    let mut packed = map.clone();
+    packed.world.pack_voxel_regions();
+    let bin_bytes = binary::encode_binary(&packed).unwrap();
+    assert_eq!(bin_bytes, binary::encode_binary(&map).unwrap());
+}
+
+#[test]
+fn test_entities_get_ids_on_load_that_saving_keeps() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ids.ron");
+    let mut map = MapLoader::load_default();
+    let spawn = map.entities[0].clone();
+    map.entities = vec![
+        EntityData {
+            id: EntityId(7),
+            ..spawn.clone()
+        },
+        EntityData {
+            id: EntityId::default(),
+            ..spawn.clone()
+        },
+        EntityData {
+            id: EntityId(7),
+            ..spawn
+        },
+    ];
+    MapLoader::save_to_file(&map, &path).unwrap();
+
+    // Missing and duplicate ids are replaced; unique ones are kept
+    let loaded = MapLoader::read_unvalidated(&path).unwrap();
+    let ids: Vec<_> = loaded.entities.iter().map(|entity| entity.id).collect();
+    assert_eq!(ids, vec![EntityId(7), EntityId(8), EntityId(9)]);
+
+    MapLoader::save_to_file(&loaded, &path).unwrap();
+    let reloaded = MapLoader::read_unvalidated(&path).unwrap();
+    let reloaded_ids: Vec<_> = reloaded.entities.iter().map(|entity| entity.id).collect();
+    assert_eq!(reloaded_ids, ids);
+}
+
+/// The default map as RON with two player spawns, the first of an unknown
+/// entity type, and the first voxel's position mangled. Returns the text and
+/// the lines of the two problems.
+fn map_text_with_malformed_items() -> (String, usize, usize) {
+    let mut map = MapLoader::load_default();
+    let spawn = map.entities[0].clone();
+    map.entities = vec![spawn.clone(), spawn];
+    let text = ron::ser::to_string_pretty(&map, ron::ser::PrettyConfig::default()).unwrap();
+    let text = text.replacen("pos: (", "pos: (x", 1).replacen(
+        "entity_type: PlayerSpawn",
+        "entity_type: Dragon",
+        1,
+    );
+    let line_of = |needle: &str| text[..text.find(needle).unwrap()].lines().count() + 1;
+    let (voxel_line, entity_line) = (line_of("pos: (x"), line_of("Dragon"));
+    (text, voxel_line, entity_line)
+}
+
+#[test]
+fn test_safe_mode_skips_malformed_items_and_reports_their_lines() {
+    let (text, voxel_line, entity_line) = map_text_with_malformed_items();
+    assert!(parse_map_bytes(text.as_bytes()).is_err());
+
+    let (map, diagnostics) = parse_map_bytes_tolerant(text.as_bytes()).unwrap();
+    let default = MapLoader::load_default();
+    assert_eq!(map.world.voxels.len(), default.world.voxels.len() - 1);
+    assert_eq!(map.entities.len(), 1);
+    assert_eq!(map.metadata.name, default.metadata.name);
+    let located: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.item.as_str(), diagnostic.line))
+        .collect();
+    assert_eq!(
+        located,
+        vec![
+            ("world.voxels[0]", voxel_line),
+            ("entities[0]", entity_line)
+        ]
+    );
+}
+
+#[test]
+fn test_safe_mode_keeps_defaults_for_broken_or_missing_sections() {
+    let text = r#"(
+        metadata: (name: "Broken", author: "", description: "", version: "1.0.0", created: ""),
+        world: (width: 4, height: 4, depth: 4, voxels: [
+            (pos: (1, 0, 1), voxel_type: Grass),
+        ]),
+        entities: [],
+        lighting: (ambient_intensity: "bright"),
+    )"#;
+    let (map, diagnostics) = parse_map_bytes_tolerant(text.as_bytes()).unwrap();
+    assert_eq!(map.metadata.name, "Broken");
+    assert_eq!(map.world.dimensions(), (4, 4, 4));
+    assert_eq!(map.world.voxels.len(), 1);
+    let items: Vec<_> = diagnostics.iter().map(|d| d.item.as_str()).collect();
+    assert_eq!(items, vec!["lighting", "camera"]);
+    assert_eq!(diagnostics[0].line, 7);
+}
+
+#[test]
+fn test_safe_mode_reads_what_a_truncated_file_has() {
+    let (text, _, entity_line) = map_text_with_malformed_items();
+    let cut = text
+        .lines()
+        .take(entity_line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (map, diagnostics) = parse_map_bytes_tolerant(cut.as_bytes()).unwrap();
+    assert_eq!(
+        map.world.voxels.len(),
+        MapLoader::load_default().world.voxels.len() - 1
+    );
+    assert!(map.entities.is_empty());
+    assert!(diagnostics.iter().any(|d| d.item == "camera"));
+}
+
+#[test]
+fn test_load_safe_falls_back_to_safe_mode_only_when_parsing_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let good = dir.path().join("good.ron");
+    MapLoader::save_to_file(&MapLoader::load_default(), &good).unwrap();
+    let (_, diagnostics) = MapLoader::load_safe(&good, &mut MapLoadProgress::new()).unwrap();
+    assert!(diagnostics.is_empty());
+
+    let broken = dir.path().join("broken.ron");
+    fs::write(&broken, map_text_with_malformed_items().0).unwrap();
+    assert!(MapLoader::load_from_file(&broken, &mut MapLoadProgress::new()).is_err());
+    let (map, diagnostics) = MapLoader::load_safe(&broken, &mut MapLoadProgress::new()).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(map.entities.len(), 1);
+
+    let (_, diagnostics) = MapLoader::read_tolerant(&broken).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+}
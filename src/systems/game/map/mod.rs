@@ -28,7 +28,9 @@ pub mod loader;
 pub mod spawner;
 pub mod validation;
 
-pub use loader::{LoadProgress, LoadedMapData, MapLoadProgress, MapLoader};
+pub use loader::{
+    LoadDiagnostic, LoadProgress, LoadedMapData, MapLoadDiagnostics, MapLoadProgress, MapLoader,
+};
 // Exported for external use (game spawning, editor rendering, chunk management, LOD, material access)
 #[allow(unused_imports)]
 pub use spawner::{
@@ -1,22 +1,41 @@
 //! Chunk-based voxel spawning with greedy meshing.
 
-use super::super::super::components::SubVoxel;
+use super::super::super::components::{GameEntity, SubVoxel};
 use super::super::super::occlusion::{OcclusionMaterial, ShadowQuality};
 use super::super::super::resources::SpatialGrid;
+use super::super::super::voxel_groups::{VoxelGroupMember, VoxelGroups};
 use super::super::format::{
-    apply_orientation_matrix, world_dir_to_local, MapData, SubVoxelPattern,
+    apply_orientation_matrix, world_dir_to_local, MapData, MeshingData, SubVoxelPattern, VoxelData,
+    VoxelType,
 };
 use super::super::loader::{LoadProgress, MapLoadProgress};
-use super::meshing::{ChunkMeshBuilder, GreedyMesher, OccupancyGrid, VoxelMaterialPalette};
+use super::meshing::{
+    ChunkMeshBuilder, GreedyMesher, MeshStats, OccupancyGrid, VoxelMaterialPalette,
+};
 use super::{ChunkLOD, Face, VoxelChunk, CHUNK_SIZE, LOD_LEVELS, SUB_VOXEL_COUNT, SUB_VOXEL_SIZE};
+use crate::systems::accessibility::ColorPalette;
 use bevy::camera::primitives::Aabb;
 use bevy::light::NotShadowCaster;
 use bevy::math::Vec3A;
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet};
 
-/// (voxel_x, voxel_y, voxel_z, sub_x, sub_y, sub_z, world_pos, color_index, color)
-type SubVoxelEntry = (i32, i32, i32, i32, i32, i32, Vec3, usize, Color);
+/// Opacity of transparent voxels
+const GLASS_ALPHA: f32 = 0.35;
+
+/// A sub-voxel collected from the map, ready for meshing and collision.
+struct SubVoxelEntry<'m> {
+    /// Position of the voxel it belongs to
+    voxel: (i32, i32, i32),
+    /// Position within that voxel
+    sub: (i32, i32, i32),
+    world_pos: Vec3,
+    color_index: usize,
+    color: Color,
+    voxel_type: VoxelType,
+    /// Voxel group of the owning voxel
+    group: Option<&'m str>,
+}
 
 /// Enum to hold either material type for chunk rendering
 #[derive(Clone)]
@@ -25,27 +44,147 @@ pub enum ChunkMaterial {
     Standard(Handle<StandardMaterial>),
 }
 
+/// Materials chunks were spawned with, kept so chunks can be rebuilt when
+/// voxel groups or the color palette change.
+#[derive(Resource, Clone)]
+pub struct ChunkMaterials {
+    pub chunk_material: ChunkMaterial,
+    pub type_materials: HashMap<VoxelType, Handle<StandardMaterial>>,
+    /// Palette the chunks are currently colored with
+    pub palette: ColorPalette,
+}
+
 /// Context for chunk-based voxel spawning.
 pub struct ChunkSpawnContext<'w, 's, 'a> {
     pub commands: Commands<'w, 's>,
     pub spatial_grid: &'a mut SpatialGrid,
     pub meshes: &'a mut Assets<Mesh>,
     pub chunk_material: ChunkMaterial,
+    /// Materials for voxel types with a [`voxel_type_material`]; their faces
+    /// are meshed separately and rendered with these instead.
+    pub type_materials: HashMap<VoxelType, Handle<StandardMaterial>>,
     /// Shadow quality applied at chunk spawn time (inserts `NotShadowCaster` for `CharactersOnly`).
     pub shadow_quality: ShadowQuality,
+    /// Colors the sub-voxels are meshed with
+    pub palette: ColorPalette,
 }
 
 /// Calculate color for a sub-voxel based on its position.
 /// Uses the same hash-based coloring as the material palette for consistency.
 #[inline]
-fn get_sub_voxel_color(x: i32, y: i32, z: i32, sub_x: i32, sub_y: i32, sub_z: i32) -> Color {
+pub(crate) fn get_sub_voxel_color(
+    palette: ColorPalette,
+    x: i32,
+    y: i32,
+    z: i32,
+    sub_x: i32,
+    sub_y: i32,
+    sub_z: i32,
+) -> Color {
     let index = VoxelMaterialPalette::get_material_index(x, y, z, sub_x, sub_y, sub_z);
     let t = index as f32 / VoxelMaterialPalette::PALETTE_SIZE as f32;
-    Color::srgb(
-        0.2 + t * 0.6,
-        0.3 + ((t * std::f32::consts::PI * 2.0).sin() * 0.5 + 0.5) * 0.4,
-        0.4 + ((t * std::f32::consts::PI * 3.0).cos() * 0.5 + 0.5) * 0.4,
-    )
+    palette.color(t)
+}
+
+/// Calculate color for a sub-voxel of `voxel_type`.
+///
+/// Types with a tint in `palette` (hazards) keep their fixed color, with a
+/// slight per-sub-voxel brightness variation; everything else uses the
+/// position-hashed palette from [`get_sub_voxel_color`].
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sub_voxel_color_for(
+    palette: ColorPalette,
+    voxel_type: VoxelType,
+    x: i32,
+    y: i32,
+    z: i32,
+    sub_x: i32,
+    sub_y: i32,
+    sub_z: i32,
+) -> Color {
+    let Some(tint) = palette.tint(voxel_type) else {
+        return get_sub_voxel_color(palette, x, y, z, sub_x, sub_y, sub_z);
+    };
+    let index = VoxelMaterialPalette::get_material_index(x, y, z, sub_x, sub_y, sub_z);
+    shaded_tint(tint, index)
+}
+
+/// `tint` with the slight brightness variation of palette index `index`.
+#[inline]
+fn shaded_tint(tint: Color, index: usize) -> Color {
+    let t = index as f32 / VoxelMaterialPalette::PALETTE_SIZE as f32;
+    let srgb = tint.to_srgba();
+    let shade = 0.85 + t * 0.15;
+    Color::srgb(srgb.red * shade, srgb.green * shade, srgb.blue * shade)
+}
+
+/// Merge index and color of the `face` side of a sub-voxel of `voxel_type`
+/// whose own palette index and color are `color_index` and `color`.
+///
+/// Types with a [`ColorPalette::face_tint`] for that face (a grass top over
+/// dirt sides) get it, shaded like other tints, under an index of their own
+/// so the greedy mesher never merges it with untinted faces; others keep
+/// the sub-voxel's color.
+#[inline]
+pub(crate) fn face_color_for(
+    palette: ColorPalette,
+    voxel_type: VoxelType,
+    face: Face,
+    color_index: usize,
+    color: Color,
+) -> (usize, Color) {
+    let voxel_face = face.voxel_face();
+    match palette.face_tint(voxel_type, voxel_face) {
+        Some(tint) => {
+            let offset = 1 + voxel_face as usize;
+            (
+                color_index + offset * VoxelMaterialPalette::PALETTE_SIZE,
+                shaded_tint(tint, color_index),
+            )
+        }
+        None => (color_index, color),
+    }
+}
+
+/// Material for the chunk meshes of `voxel_type`, for types meshed apart
+/// from the rest of their chunk: glowing types ([`VoxelType::emissive`]) and
+/// see-through ones ([`VoxelType::is_transparent`]).
+pub fn voxel_type_material(voxel_type: VoxelType) -> Option<StandardMaterial> {
+    if let Some(emissive) = voxel_type.emissive() {
+        return Some(StandardMaterial {
+            base_color: Color::WHITE,
+            emissive,
+            perceptual_roughness: 0.6,
+            metallic: 0.0,
+            reflectance: 0.1,
+            ..default()
+        });
+    }
+    voxel_type.is_transparent().then(|| StandardMaterial {
+        base_color: Color::srgba(1.0, 1.0, 1.0, GLASS_ALPHA),
+        alpha_mode: AlphaMode::Blend,
+        perceptual_roughness: 0.1,
+        metallic: 0.0,
+        reflectance: 0.5,
+        ..default()
+    })
+}
+
+/// Mesh `mesher` into `builder` at `lod_level`, quantizing colors as
+/// `meshing` says for that level.
+fn build_chunk_lod(
+    mesher: &GreedyMesher,
+    builder: &mut ChunkMeshBuilder,
+    lod_level: usize,
+    meshing: &MeshingData,
+) {
+    let color_tolerance = meshing.tolerance_at(lod_level);
+    if lod_level == 0 && color_tolerance <= 0.0 {
+        mesher.build_into(builder);
+    } else {
+        mesher.build_lod(builder, lod_level, color_tolerance);
+    }
 }
 
 /// Calculate world position for a sub-voxel.
@@ -59,26 +198,55 @@ fn calculate_sub_voxel_pos(x: i32, y: i32, z: i32, sub_x: i32, sub_y: i32, sub_z
     )
 }
 
-/// Spawn all voxels using chunk-based meshing with greedy face merging.
+/// Chunk containing a world position.
+#[inline]
+fn chunk_of(world_pos: Vec3) -> IVec3 {
+    (world_pos / CHUNK_SIZE as f32).floor().as_ivec3()
+}
+
+/// Global sub-voxel coordinates (inclusive) of the sub-voxels in a chunk,
+/// matching [`chunk_of`].
+fn chunk_sub_voxel_bounds(chunk_pos: IVec3) -> (IVec3, IVec3) {
+    let offset = -0.5 + SUB_VOXEL_SIZE * 0.5;
+    let first = |chunk: IVec3| {
+        (((chunk * CHUNK_SIZE).as_vec3() - Vec3::splat(offset)) / SUB_VOXEL_SIZE)
+            .ceil()
+            .as_ivec3()
+    };
+    (first(chunk_pos), first(chunk_pos + IVec3::ONE) - IVec3::ONE)
+}
+
+/// Chunks within `reach` world units of the center of the voxel at `pos`.
 ///
-/// This function:
-/// 1. First pass: Collects all occupied sub-voxel positions into an OccupancyGrid
-/// 2. Second pass: For each sub-voxel, determine visible faces and add to GreedyMesher
-/// 3. Third pass: Greedy mesher merges adjacent same-color faces into larger quads
-/// 4. Spawns one entity per chunk with optimized mesh
+/// A voxel spans 0.5 units either side of its position, so a reach of 0.5
+/// gives the chunks its own sub-voxels fall into.
+pub fn voxel_chunks(pos: (i32, i32, i32), reach: f32) -> impl Iterator<Item = IVec3> {
+    let center = Vec3::new(pos.0 as f32, pos.1 as f32, pos.2 as f32);
+    let min = chunk_of(center - Vec3::splat(reach));
+    let max = chunk_of(center + Vec3::splat(reach));
+    (min.x..=max.x).flat_map(move |x| {
+        (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+    })
+}
+
+/// Report voxel spawning progress when building a whole map.
+fn report(progress: &mut Option<&mut MapLoadProgress>, value: f32) {
+    if let Some(progress) = progress {
+        progress.update(LoadProgress::SpawningVoxels(value));
+    }
+}
+
+/// Sub-voxels of every map voxel passing `include`, in map order.
 ///
-/// Greedy meshing reduces quad count by 90%+ for large flat surfaces.
-pub fn spawn_voxels_chunked(
-    ctx: &mut ChunkSpawnContext,
-    map: &MapData,
-    progress: &mut MapLoadProgress,
-) {
+/// Progress covers 0-15% of the voxel stage.
+fn collect_sub_voxels<'m>(
+    map: &'m MapData,
+    palette: ColorPalette,
+    include: impl Fn(&VoxelData) -> bool,
+    progress: &mut Option<&mut MapLoadProgress>,
+) -> Vec<SubVoxelEntry<'m>> {
     let total_voxels = map.world.voxels.len();
 
-    // First pass: Build occupancy grid for neighbor lookups
-    progress.update(LoadProgress::SpawningVoxels(0.0));
-    let mut occupancy = OccupancyGrid::new();
-
     // Collect all sub-voxel data for subsequent passes
     let mut all_sub_voxels: Vec<SubVoxelEntry> = Vec::new();
 
@@ -95,11 +263,13 @@ pub fn spawn_voxels_chunked(
         // Update progress (occupancy collection phase: 0-15%)
         if index % 100 == 0 {
             let voxel_progress = (index as f32) / (total_voxels as f32) * 0.15;
-            progress.update(LoadProgress::SpawningVoxels(voxel_progress));
+            report(progress, voxel_progress);
+        }
+        if !include(voxel_data) {
+            continue;
         }
 
         let (x, y, z) = voxel_data.pos;
-
         // Spawn parent voxel marker
 
         // Determine which pattern to use
@@ -158,45 +328,99 @@ pub fn spawn_voxels_chunked(
             pattern.geometry_with_rotation(orientation)
         };
 
-        // Add each sub-voxel to occupancy grid and collect data
+        // Collect data for each sub-voxel
         for (sub_x, sub_y, sub_z) in geometry.occupied_positions() {
-            occupancy.insert(x, y, z, sub_x, sub_y, sub_z);
-
             let world_pos = calculate_sub_voxel_pos(x, y, z, sub_x, sub_y, sub_z);
             let color_index =
                 VoxelMaterialPalette::get_material_index(x, y, z, sub_x, sub_y, sub_z);
-            let color = get_sub_voxel_color(x, y, z, sub_x, sub_y, sub_z);
-            all_sub_voxels.push((x, y, z, sub_x, sub_y, sub_z, world_pos, color_index, color));
+            let color =
+                sub_voxel_color_for(palette, voxel_data.voxel_type, x, y, z, sub_x, sub_y, sub_z);
+            all_sub_voxels.push(SubVoxelEntry {
+                voxel: (x, y, z),
+                sub: (sub_x, sub_y, sub_z),
+                world_pos,
+                color_index,
+                color,
+                voxel_type: voxel_data.voxel_type,
+                group: voxel_data.group.as_deref(),
+            });
         }
     }
 
-    // Second pass: Collect visible faces into per-chunk greedy meshers
-    let mut chunk_meshers: HashMap<IVec3, GreedyMesher> = HashMap::new();
-    let mut sub_voxel_positions: Vec<(Vec3, (Vec3, Vec3))> = Vec::new();
+    all_sub_voxels
+}
 
-    let total_sub_voxels_count = all_sub_voxels.len();
-    for (index, (x, y, z, sub_x, sub_y, sub_z, world_pos, color_index, color)) in
-        all_sub_voxels.into_iter().enumerate()
-    {
+/// Visible faces of the sub-voxels of visible groups, in one greedy mesher
+/// per chunk.
+///
+/// Types in `type_materials` get their own mesher per chunk (keyed by type)
+/// so they can be rendered with a glowing or alpha-blended material; their
+/// faces never merge with the rest of the chunk's. Faces against visible
+/// neighbors in `sub_voxels` are culled, except that transparent neighbors
+/// only hide the faces of other transparent sub-voxels, so `sub_voxels` must
+/// include every voxel next to the chunks wanted. Faces are colored per side
+/// with [`face_color_for`] in `palette`. When `only` is set, just those chunks are collected.
+/// Progress covers 15-35% of the voxel stage.
+fn collect_chunk_faces(
+    sub_voxels: &[SubVoxelEntry],
+    palette: ColorPalette,
+    groups: &VoxelGroups,
+    type_materials: &HashMap<VoxelType, Handle<StandardMaterial>>,
+    only: Option<&HashSet<IVec3>>,
+    progress: &mut Option<&mut MapLoadProgress>,
+) -> HashMap<(IVec3, Option<VoxelType>), GreedyMesher> {
+    // Occupancy of visible sub-voxels for neighbor lookups: all of them hide
+    // transparent faces, only opaque ones hide opaque faces
+    let mut occupancy = OccupancyGrid::new();
+    let mut opaque = OccupancyGrid::new();
+    for entry in sub_voxels.iter().filter(|e| groups.is_visible(e.group)) {
+        let ((x, y, z), (sub_x, sub_y, sub_z)) = (entry.voxel, entry.sub);
+        occupancy.insert(x, y, z, sub_x, sub_y, sub_z);
+        if !entry.voxel_type.is_transparent() {
+            opaque.insert(x, y, z, sub_x, sub_y, sub_z);
+        }
+    }
+
+    let mut chunk_meshers: HashMap<(IVec3, Option<VoxelType>), GreedyMesher> = HashMap::new();
+
+    let total_sub_voxels_count = sub_voxels.len();
+    for (index, entry) in sub_voxels.iter().enumerate() {
         // Update progress (face collection phase: 15-35%)
         if index % 1000 == 0 {
             let build_progress = 0.15 + (index as f32) / (total_sub_voxels_count as f32) * 0.2;
-            progress.update(LoadProgress::SpawningVoxels(build_progress));
+            report(progress, build_progress);
+        }
+        if !groups.is_visible(entry.group) {
+            continue;
         }
 
         // Determine which chunk this sub-voxel belongs to
-        let chunk_pos = IVec3::new(
-            (world_pos.x / CHUNK_SIZE as f32).floor() as i32,
-            (world_pos.y / CHUNK_SIZE as f32).floor() as i32,
-            (world_pos.z / CHUNK_SIZE as f32).floor() as i32,
-        );
+        let chunk_pos = chunk_of(entry.world_pos);
+        if only.is_some_and(|chunks| !chunks.contains(&chunk_pos)) {
+            continue;
+        }
+        let ((x, y, z), (sub_x, sub_y, sub_z)) = (entry.voxel, entry.sub);
+        let (color_index, color, voxel_type) = (entry.color_index, entry.color, entry.voxel_type);
 
         // Global sub-voxel coordinates for the greedy mesher
         let global_x = x * SUB_VOXEL_COUNT + sub_x;
         let global_y = y * SUB_VOXEL_COUNT + sub_y;
         let global_z = z * SUB_VOXEL_COUNT + sub_z;
 
-        let mesher = chunk_meshers.entry(chunk_pos).or_default();
+        let type_key = type_materials
+            .contains_key(&voxel_type)
+            .then_some(voxel_type);
+        let mesher = chunk_meshers
+            .entry((chunk_pos, type_key))
+            .or_insert_with(|| {
+                let (min, max) = chunk_sub_voxel_bounds(chunk_pos);
+                GreedyMesher::for_chunk(min, max)
+            });
+        let hiding = if voxel_type.is_transparent() {
+            &occupancy
+        } else {
+            &opaque
+        };
 
         // Check each face and add visible ones to the mesher
         // Note: PosY (top) face is ALWAYS rendered regardless of neighbor,
@@ -213,40 +437,68 @@ pub fn spawn_voxels_chunked(
         for face in faces {
             // Always render top faces (PosY) to handle interior occlusion
             let should_render =
-                face == Face::PosY || !occupancy.has_neighbor(x, y, z, sub_x, sub_y, sub_z, face);
+                face == Face::PosY || !hiding.has_neighbor(x, y, z, sub_x, sub_y, sub_z, face);
             if should_render {
+                let (color_index, color) =
+                    face_color_for(palette, voxel_type, face, color_index, color);
                 mesher.add_face(global_x, global_y, global_z, face, color_index, color);
             }
         }
-
-        // Calculate bounds for collision detection
-        let half_size = SUB_VOXEL_SIZE / 2.0;
-        let bounds = (
-            world_pos - Vec3::splat(half_size),
-            world_pos + Vec3::splat(half_size),
-        );
-        sub_voxel_positions.push((world_pos, bounds));
     }
 
-    // Third pass: Build greedy meshes with LOD levels and spawn chunk entities
+    chunk_meshers
+}
+
+/// Mesh the sub-voxels of visible groups into chunk entities.
+///
+/// See [`collect_chunk_faces`] for which faces are meshed. Progress covers
+/// 15-60% of the voxel stage. LOD meshes quantize colors as `meshing` says,
+/// and every mesh goes through [`ChunkMeshBuilder::optimize`]. Returns the
+/// number of chunk meshes built, their quad count, and the stats of all
+/// their LOD meshes before and after optimizing.
+fn spawn_chunk_meshes(
+    ctx: &mut ChunkSpawnContext,
+    sub_voxels: &[SubVoxelEntry],
+    meshing: &MeshingData,
+    groups: &VoxelGroups,
+    only: Option<&HashSet<IVec3>>,
+    progress: &mut Option<&mut MapLoadProgress>,
+) -> (usize, usize, MeshStats, MeshStats) {
+    let chunk_meshers = collect_chunk_faces(
+        sub_voxels,
+        ctx.palette,
+        groups,
+        &ctx.type_materials,
+        only,
+        progress,
+    );
+
+    // Build greedy meshes with LOD levels and spawn chunk entities
     let total_chunks = chunk_meshers.len();
     let mut total_quads = 0usize;
-
-    for (index, (chunk_pos, mesher)) in chunk_meshers.into_iter().enumerate() {
+    let (mut before, mut after) = (MeshStats::default(), MeshStats::default());
+    let mut optimize = |builder: &mut ChunkMeshBuilder| {
+        let (mesh_before, mesh_after) = builder.optimize();
+        before += mesh_before;
+        after += mesh_after;
+    };
+
+    for (index, ((chunk_pos, type_key), mesher)) in chunk_meshers.into_iter().enumerate() {
         // Update progress (mesh building phase: 35-60%)
         let spawn_progress = 0.35 + (index as f32) / (total_chunks as f32) * 0.25;
-        progress.update(LoadProgress::SpawningVoxels(spawn_progress));
+        report(progress, spawn_progress);
 
         // Build LOD 0 (full detail) first to check if chunk has geometry
         let mut builder_lod0 = ChunkMeshBuilder::default();
-        mesher.build_into(&mut builder_lod0);
+        build_chunk_lod(&mesher, &mut builder_lod0, 0, meshing);
 
         if builder_lod0.is_empty() {
             continue;
         }
 
         // Count quads for stats (LOD 0 only)
-        total_quads += builder_lod0.positions.len() / 4;
+        total_quads += builder_lod0.quad_count();
+        optimize(&mut builder_lod0);
 
         // Build all LOD levels
         let mut lod_meshes: [Handle<Mesh>; LOD_LEVELS] = Default::default();
@@ -257,12 +509,13 @@ pub fn spawn_voxels_chunked(
         // LOD 1-3: Build progressively lower detail meshes
         for lod_level in 1..LOD_LEVELS {
             let mut builder = ChunkMeshBuilder::default();
-            mesher.build_lod(&mut builder, lod_level);
+            build_chunk_lod(&mesher, &mut builder, lod_level, meshing);
 
             // If LOD mesh is empty, reuse previous LOD
             if builder.is_empty() {
                 lod_meshes[lod_level] = lod_meshes[lod_level - 1].clone();
             } else {
+                optimize(&mut builder);
                 lod_meshes[lod_level] = ctx.meshes.add(builder.build());
             }
         }
@@ -274,8 +527,14 @@ pub fn spawn_voxels_chunked(
             (chunk_pos.z as f32 + 0.5) * CHUNK_SIZE as f32,
         );
 
-        // Spawn chunk with appropriate material type
-        match &ctx.chunk_material {
+        // Spawn chunk with appropriate material type. Glowing and glass
+        // meshes use their type's StandardMaterial and skip occlusion
+        // transparency; glass doesn't cast shadows.
+        let material = type_key
+            .and_then(|voxel_type| ctx.type_materials.get(&voxel_type))
+            .map(|mat| ChunkMaterial::Standard(mat.clone()))
+            .unwrap_or_else(|| ctx.chunk_material.clone());
+        match &material {
             ChunkMaterial::Occlusion(mat) => {
                 let mut entity = ctx.commands.spawn((
                     Mesh3d(lod_meshes[0].clone()),
@@ -285,6 +544,7 @@ pub fn spawn_voxels_chunked(
                         chunk_pos,
                         center: chunk_center,
                     },
+                    GameEntity,
                     ChunkLOD {
                         lod_meshes: lod_meshes.clone(),
                         current_lod: 0,
@@ -307,6 +567,7 @@ pub fn spawn_voxels_chunked(
                         chunk_pos,
                         center: chunk_center,
                     },
+                    GameEntity,
                     ChunkLOD {
                         lod_meshes,
                         current_lod: 0,
@@ -316,38 +577,152 @@ pub fn spawn_voxels_chunked(
                         half_extents: Vec3A::splat(CHUNK_SIZE as f32 / 2.0),
                     },
                 ));
-                if ctx.shadow_quality == ShadowQuality::CharactersOnly {
+                let transparent = type_key.is_some_and(VoxelType::is_transparent);
+                if ctx.shadow_quality == ShadowQuality::CharactersOnly || transparent {
                     entity.insert(NotShadowCaster);
                 }
             }
         }
     }
 
+    (total_chunks, total_quads, before, after)
+}
+
+/// Spawn all voxels using chunk-based meshing with greedy face merging.
+///
+/// This function:
+/// 1. First pass: Collects every sub-voxel with its color and group
+/// 2. Second pass: For each visible sub-voxel, determine visible faces and add to GreedyMesher
+/// 3. Third pass: Greedy mesher merges adjacent same-color faces into larger quads
+/// 4. Spawns one entity per chunk with optimized mesh
+/// 5. Spawns a collision entity per sub-voxel, adding solid ones to the spatial grid
+///
+/// Voxels in hidden groups are left out of the meshes, and those in
+/// non-solid groups out of the spatial grid, as `groups` says.
+/// Greedy meshing reduces quad count by 90%+ for large flat surfaces.
+pub fn spawn_voxels_chunked(
+    ctx: &mut ChunkSpawnContext,
+    map: &MapData,
+    groups: &VoxelGroups,
+    progress: &mut MapLoadProgress,
+) {
+    let mut progress = Some(progress);
+    report(&mut progress, 0.0);
+    let sub_voxels = collect_sub_voxels(map, ctx.palette, |_| true, &mut progress);
+    let (total_chunks, total_quads, before, after) =
+        spawn_chunk_meshes(ctx, &sub_voxels, &map.meshing, groups, None, &mut progress);
+
     // Spawn invisible collision entities for the spatial grid
-    let total_sub_voxels = sub_voxel_positions.len();
-    for (index, (world_pos, bounds)) in sub_voxel_positions.into_iter().enumerate() {
+    let total_sub_voxels = sub_voxels.len();
+    for (index, entry) in sub_voxels.into_iter().enumerate() {
         // Update progress (collision setup phase: 60-100%)
         if index % 1000 == 0 {
             let collision_progress = 0.6 + (index as f32) / (total_sub_voxels as f32) * 0.4;
-            progress.update(LoadProgress::SpawningVoxels(collision_progress));
+            report(&mut progress, collision_progress);
         }
 
         // Spawn invisible entity for collision detection only
-        let sub_voxel_entity = ctx.commands.spawn(SubVoxel { bounds }).id();
+        let bounds = sub_voxel_box(entry.world_pos);
+        let mut sub_voxel = ctx.commands.spawn((
+            SubVoxel {
+                bounds,
+                voxel_type: entry.voxel_type,
+            },
+            GameEntity,
+        ));
+        if let Some(group) = entry.group {
+            sub_voxel.insert(VoxelGroupMember(group.to_string()));
+        }
+        let sub_voxel_entity = sub_voxel.id();
 
         // Add to spatial grid
-        let grid_coords = SpatialGrid::world_to_grid_coords(world_pos);
-        ctx.spatial_grid
-            .cells
-            .entry(grid_coords)
-            .or_default()
-            .push(sub_voxel_entity);
+        if groups.is_solid(entry.group) {
+            ctx.spatial_grid
+                .insert(sub_voxel_entity, bounds.0, bounds.1);
+        }
     }
 
     info!(
         "Spawned {} chunks with {} quads ({} collision entities) - greedy meshing enabled",
         total_chunks, total_quads, total_sub_voxels
     );
+    info!(
+        "Chunk meshes optimized from {} to {} vertices ({} to {} KiB), ACMR {:.2} to {:.2}",
+        before.vertices,
+        after.vertices,
+        before.vertex_bytes() / 1024,
+        after.vertex_bytes() / 1024,
+        before.acmr(),
+        after.acmr()
+    );
+}
+
+/// Collision box of the sub-voxel centered at `world_pos`.
+fn sub_voxel_box(world_pos: Vec3) -> (Vec3, Vec3) {
+    let half_size = Vec3::splat(SUB_VOXEL_SIZE / 2.0);
+    (world_pos - half_size, world_pos + half_size)
+}
+
+/// Collision boxes of the sub-voxels of every map voxel passing `include`,
+/// as the game puts them in the spatial grid.
+pub fn sub_voxel_bounds(map: &MapData, include: impl Fn(&VoxelData) -> bool) -> Vec<(Vec3, Vec3)> {
+    collect_sub_voxels(map, ColorPalette::Standard, include, &mut None)
+        .iter()
+        .map(|entry| sub_voxel_box(entry.world_pos))
+        .collect()
+}
+
+/// Re-mesh `chunks` after voxel groups were shown or hidden.
+///
+/// The caller despawns the old chunk entities first. Only voxels close
+/// enough to the chunks to affect their faces are looked at.
+pub fn rebuild_chunks(
+    ctx: &mut ChunkSpawnContext,
+    map: &MapData,
+    groups: &VoxelGroups,
+    chunks: &HashSet<IVec3>,
+) {
+    let near = |voxel: &VoxelData| voxel_chunks(voxel.pos, 1.5).any(|c| chunks.contains(&c));
+    let sub_voxels = collect_sub_voxels(map, ctx.palette, near, &mut None);
+    let (built, quads, _, _) = spawn_chunk_meshes(
+        ctx,
+        &sub_voxels,
+        &map.meshing,
+        groups,
+        Some(chunks),
+        &mut None,
+    );
+    debug!(
+        "Rebuilt {} chunks into {} meshes with {} quads",
+        chunks.len(),
+        built,
+        quads
+    );
+}
+
+/// Mesh the whole map at `lod` as scenery: one mesh per chunk, with no
+/// collision, chunk components or glowing materials.
+///
+/// Used for backdrops such as the title screen's, which only look at the
+/// map from afar.
+pub fn build_scenery_meshes(map: &MapData, palette: ColorPalette, lod: usize) -> Vec<Mesh> {
+    let groups = VoxelGroups::from_map(map);
+    let sub_voxels = collect_sub_voxels(map, palette, |_| true, &mut None);
+    collect_chunk_faces(
+        &sub_voxels,
+        palette,
+        &groups,
+        &HashMap::new(),
+        None,
+        &mut None,
+    )
+    .into_values()
+    .filter_map(|mesher| {
+        let mut builder = ChunkMeshBuilder::default();
+        build_chunk_lod(&mesher, &mut builder, lod.min(LOD_LEVELS - 1), &map.meshing);
+        (!builder.is_empty()).then(|| builder.build())
+    })
+    .collect()
 }
 
 #[cfg(test)]
@@ -38,8 +38,8 @@ fn test_calculate_sub_voxel_pos_adjacent_voxel() {
 
 #[test]
 fn test_get_sub_voxel_color_deterministic() {
-    let color1 = get_sub_voxel_color(5, 10, 15, 3, 4, 5);
-    let color2 = get_sub_voxel_color(5, 10, 15, 3, 4, 5);
+    let color1 = get_sub_voxel_color(ColorPalette::Standard, 5, 10, 15, 3, 4, 5);
+    let color2 = get_sub_voxel_color(ColorPalette::Standard, 5, 10, 15, 3, 4, 5);
     // Same input should produce same color
     assert_eq!(format!("{:?}", color1), format!("{:?}", color2));
 }
@@ -240,9 +240,139 @@ fn fence_y90_connects_to_world_x_neighbor() {
     // The rail should now extend in the world +X direction (sub_x > 4).
     let has_rail_in_pos_x = world_geo
         .occupied_positions()
-        .any(|(sx, _sy, sz)| sx > 4 && sz >= 3 && sz <= 4);
+        .any(|(sx, _sy, sz)| sx > 4 && (3..=4).contains(&sz));
     assert!(
         has_rail_in_pos_x,
         "after Y+90°, the local +Z rail must appear in the world +X half (sub_x > 4)"
     );
 }
+
+#[test]
+fn scenery_meshes_get_coarser_with_lod() {
+    let map = MapData::default_map();
+    let vertices = |lod| -> usize {
+        build_scenery_meshes(&map, ColorPalette::Standard, lod)
+            .iter()
+            .map(Mesh::count_vertices)
+            .sum()
+    };
+    let full = vertices(0);
+    assert!(full > 0);
+    assert!(vertices(LOD_LEVELS - 1) <= full);
+    // Levels past the last reuse it
+    assert_eq!(vertices(LOD_LEVELS + 3), vertices(LOD_LEVELS - 1));
+}
+
+/// Quads of `mesher` whose corners all pass `inside`.
+fn quads_where(mesher: Option<&GreedyMesher>, inside: impl Fn(Vec3) -> bool) -> usize {
+    let mut builder = ChunkMeshBuilder::default();
+    if let Some(mesher) = mesher {
+        mesher.build_into(&mut builder);
+    }
+    builder
+        .positions
+        .chunks(4)
+        .filter(|quad| quad.iter().all(|p| inside(Vec3::from_array(*p))))
+        .count()
+}
+
+#[test]
+fn glass_is_meshed_apart_and_culled_against_glass_only() {
+    let voxel = |pos, voxel_type| VoxelData {
+        pos,
+        voxel_type,
+        pattern: None,
+        rotation: None,
+        rotation_state: None,
+        group: None,
+    };
+    let mut map = MapData::empty_map();
+    map.world.voxels.push(voxel((0, 0, 0), VoxelType::Glass));
+    map.world.voxels.push(voxel((1, 0, 0), VoxelType::Glass));
+    map.world.voxels.push(voxel((0, 0, 1), VoxelType::Stone));
+
+    let sub_voxels = collect_sub_voxels(&map, ColorPalette::Standard, |_| true, &mut None);
+    let type_materials = HashMap::from([(VoxelType::Glass, Handle::default())]);
+    let meshers = collect_chunk_faces(
+        &sub_voxels,
+        ColorPalette::Standard,
+        &VoxelGroups::default(),
+        &type_materials,
+        None,
+        &mut None,
+    );
+    assert_eq!(meshers.len(), 2);
+    let glass = meshers.get(&(IVec3::ZERO, Some(VoxelType::Glass)));
+    let opaque = meshers.get(&(IVec3::ZERO, None));
+    let near = |a: f32, b: f32| (a - b).abs() < 0.001;
+
+    // Glass against glass is hidden, glass against air isn't
+    assert_eq!(quads_where(glass, |p| near(p.x, 0.5)), 0);
+    assert!(quads_where(glass, |p| near(p.x, -0.5)) > 0);
+    // Stone behind glass shows through it, while glass against stone is hidden
+    assert!(quads_where(opaque, |p| near(p.z, 0.5)) > 0);
+    assert_eq!(quads_where(glass, |p| near(p.z, 0.5) && p.x < 0.51), 0);
+    assert!(quads_where(glass, |p| near(p.z, 0.5) && p.x > 0.49) > 0);
+}
+
+#[test]
+fn grass_tops_differ_from_their_sides() {
+    let base = (3, Color::srgb(0.5, 0.5, 0.5));
+    let color =
+        |voxel_type, face| face_color_for(ColorPalette::Standard, voxel_type, face, base.0, base.1);
+    let (top_index, top) = color(VoxelType::Grass, Face::PosY);
+    let (side_index, side) = color(VoxelType::Grass, Face::PosX);
+    assert_ne!(top, side);
+    assert_eq!(color(VoxelType::Grass, Face::NegZ), (side_index, side));
+    // Tinted faces never share a merge index with untinted ones or each other
+    assert!(top_index >= VoxelMaterialPalette::PALETTE_SIZE);
+    assert!(side_index >= VoxelMaterialPalette::PALETTE_SIZE);
+    assert_ne!(top_index, side_index);
+    // Types without face tints keep the sub-voxel's color
+    assert_eq!(color(VoxelType::Stone, Face::PosY), base);
+}
+
+#[test]
+fn chunk_sub_voxel_bounds_match_chunk_of() {
+    assert_eq!(
+        chunk_sub_voxel_bounds(IVec3::ZERO),
+        (IVec3::splat(4), IVec3::splat(131))
+    );
+    let offset = -0.5 + SUB_VOXEL_SIZE * 0.5;
+    let world = |g: IVec3| g.as_vec3() * SUB_VOXEL_SIZE + Vec3::splat(offset);
+    for k in -2..2 {
+        let chunk = IVec3::new(k, -k, 1);
+        let (min, max) = chunk_sub_voxel_bounds(chunk);
+        assert_eq!(chunk_of(world(min)), chunk);
+        assert_eq!(chunk_of(world(max)), chunk);
+        assert_ne!(chunk_of(world(min - IVec3::ONE)), chunk);
+        assert_ne!(chunk_of(world(max + IVec3::ONE)), chunk);
+    }
+}
+
+#[test]
+fn sub_voxel_bounds_cover_included_voxels() {
+    let mut map = MapData::empty_map();
+    for pos in [(0, 0, 0), (3, 1, 0)] {
+        map.world.voxels.push(VoxelData {
+            pos,
+            voxel_type: VoxelType::Stone,
+            pattern: None,
+            rotation: None,
+            rotation_state: None,
+            group: None,
+        });
+    }
+    let bounds = sub_voxel_bounds(&map, |voxel| voxel.pos == (3, 1, 0));
+    assert_eq!(bounds.len(), (SUB_VOXEL_COUNT as usize).pow(3));
+    for (min, max) in bounds {
+        assert!(
+            (max - min - Vec3::splat(SUB_VOXEL_SIZE))
+                .abs()
+                .max_element()
+                < 1e-5
+        );
+        let center = (min + max) * 0.5;
+        assert!((center - Vec3::new(3.0, 1.0, 0.0)).abs().max_element() < 0.5);
+    }
+}
@@ -1,9 +1,23 @@
-//! Entity spawning functions for players, NPCs, and light sources.
+//! Entity spawning functions for players, NPCs, light sources, weather zones,
+//! doors, keys, teleporters, goals and scripted actions.
 
+use super::super::super::actions::{
+    parse_actions, Activation, MapAction, ScriptedActions, ACTIONS_KEY,
+};
 use super::super::super::character::CharacterModel;
 use super::super::super::components::{
-    CollisionBox, FlickerLight, LightSource, Npc, Player, PlayerFlashlight,
+    CollisionBox, FlickerLight, GameEntity, LightSource, Npc, Player, PlayerFlashlight,
 };
+use super::super::super::doors::{Door, KeyItem, KEY_COLOR};
+use super::super::super::fixed_step::PhysicsInterpolation;
+use super::super::super::health::{Health, PLAYER_MAX_HEALTH};
+use super::super::super::interaction::Interactable;
+use super::super::super::resources::PhysicsConfig;
+use super::super::super::scripting::SCRIPT_KEY;
+use super::super::super::speedrun::{Goal, GOAL_COLOR};
+use super::super::super::teleporters::{TeleportActivation, Teleporter, TELEPORTER_COLOR};
+use super::super::super::weather::WeatherZone;
+use super::super::format::{EntityData, WeatherData};
 use bevy::gltf::GltfAssetLabel;
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -23,19 +37,15 @@ pub struct EntitySpawnContext<'w, 's, 'a> {
 /// 2. A GLB character model as a child entity for visuals
 /// 3. An invisible collision box for debugging
 ///
-/// The physics collision uses a cylinder collider (radius: 0.2, half_height: 0.4)
-/// which is kept separate from the visual model for flexibility and performance.
+/// The physics collision uses a cylinder collider sized by [`PhysicsConfig`]
+/// (radius: 0.2, half_height: 0.4) which is kept separate from the visual model
+/// for flexibility and performance.
 pub fn spawn_player(ctx: &mut EntitySpawnContext, position: Vec3) {
-    let player_radius = 0.2;
-    let player_half_height = 0.4; // Total height = 0.8 units
+    let physics = PhysicsConfig::default();
+    let player_radius = physics.player_radius;
+    let player_half_height = physics.player_half_height; // Total height = 0.8 units
 
-    // Load the character model (GLB file) with explicit scene specification
-    // Using GltfAssetLabel::Scene(0) to load the first (default) scene from the GLB file
-    let character_scene: Handle<Scene> = ctx
-        .asset_server
-        .load(GltfAssetLabel::Scene(0).from_asset("characters/base_basic_pbr.glb"));
-
-    info!("Loading character model: characters/base_basic_pbr.glb#Scene0");
+    let character_scene = load_character_scene(ctx.asset_server);
 
     // Spawn the main player entity (parent) with physics components
     // No visible mesh - the GLB model will be the visual representation
@@ -44,30 +54,15 @@ pub fn spawn_player(ctx: &mut EntitySpawnContext, position: Vec3) {
         .spawn((
             Transform::from_translation(position),
             Visibility::default(),
-            Player {
-                speed: 3.0,
-                velocity: Vec3::ZERO,
-                is_grounded: true,
-                radius: player_radius,
-                half_height: player_half_height,
-                target_rotation: 0.0,
-                current_rotation: 0.0,
-                start_rotation: 0.0,
-                rotation_elapsed: 0.0,
-                rotation_duration: 0.2, // Fixed 0.2 second duration for all rotations
-            },
+            player_body(&physics),
+            PhysicsInterpolation::default(),
+            Health::new(PLAYER_MAX_HEALTH),
             CharacterModel::new(character_scene.clone()),
+            GameEntity,
         ))
         .id();
 
-    // Spawn the character model as a child entity
-    // Scale down to 0.3 and offset down by 0.3 units to align with collision sphere
-    ctx.commands
-        .spawn((
-            SceneRoot(character_scene),
-            Transform::from_translation(Vec3::new(0.0, -0.3, 0.0)).with_scale(Vec3::splat(0.5)),
-        ))
-        .insert(ChildOf(player_entity));
+    spawn_character_model(&mut ctx.commands, player_entity, character_scene);
 
     // Spawn a spotlight as a child entity to act as a flashlight
     // Points forward in the direction the character is facing
@@ -116,9 +111,45 @@ pub fn spawn_player(ctx: &mut EntitySpawnContext, position: Vec3) {
         Transform::from_translation(position),
         Visibility::Hidden,
         CollisionBox,
+        GameEntity,
     ));
 }
 
+/// Physics state of a player standing still, sized by [`PhysicsConfig`].
+pub fn player_body(physics: &PhysicsConfig) -> Player {
+    Player {
+        speed: physics.move_speed,
+        velocity: Vec3::ZERO,
+        is_grounded: true,
+        radius: physics.player_radius,
+        half_height: physics.player_half_height,
+        target_rotation: 0.0,
+        current_rotation: 0.0,
+        start_rotation: 0.0,
+        rotation_elapsed: 0.0,
+        rotation_duration: 0.2, // Fixed 0.2 second duration for all rotations
+    }
+}
+
+/// Load the player character model.
+pub fn load_character_scene(asset_server: &AssetServer) -> Handle<Scene> {
+    // Load the character model (GLB file) with explicit scene specification
+    // Using GltfAssetLabel::Scene(0) to load the first (default) scene from the GLB file
+    info!("Loading character model: characters/base_basic_pbr.glb#Scene0");
+    asset_server.load(GltfAssetLabel::Scene(0).from_asset("characters/base_basic_pbr.glb"))
+}
+
+/// Spawn the character model as a child of the player entity `parent`.
+pub fn spawn_character_model(commands: &mut Commands, parent: Entity, scene: Handle<Scene>) {
+    // Scale down to 0.3 and offset down by 0.3 units to align with collision sphere
+    commands
+        .spawn((
+            SceneRoot(scene),
+            Transform::from_translation(Vec3::new(0.0, -0.3, 0.0)).with_scale(Vec3::splat(0.5)),
+        ))
+        .insert(ChildOf(parent));
+}
+
 /// Spawn an NPC entity with a 3D character model.
 ///
 /// This function creates:
@@ -161,6 +192,7 @@ pub fn spawn_npc(
                 name: npc_name.clone(),
                 radius: npc_radius,
             },
+            GameEntity,
         ))
         .id();
 
@@ -246,6 +278,7 @@ pub fn spawn_light_source(
                 shadows_enabled,
                 ..default()
             },
+            GameEntity,
         ))
         .id();
 
@@ -263,6 +296,82 @@ pub fn spawn_light_source(
     );
 }
 
+/// Spawn a weather zone from a trigger with a `weather` property.
+///
+/// The zone is a sphere of the trigger's `radius` (default
+/// [`WeatherZone::DEFAULT_RADIUS`]) around its position.
+pub fn spawn_weather_zone(
+    ctx: &mut EntitySpawnContext,
+    position: Vec3,
+    weather: WeatherData,
+    properties: &HashMap<String, String>,
+) {
+    let radius = properties
+        .get("radius")
+        .and_then(|r| r.parse::<f32>().ok())
+        .filter(|r| *r > 0.0)
+        .unwrap_or(WeatherZone::DEFAULT_RADIUS);
+
+    info!(
+        "Spawned weather zone at {:?} ({:?}, radius: {})",
+        position, weather.precipitation, radius
+    );
+    ctx.commands.spawn((
+        Transform::from_translation(position),
+        GlobalTransform::default(),
+        WeatherZone { weather, radius },
+        GameEntity,
+    ));
+}
+
+/// Spawn the scripted actions of an entity with an `actions` or `script`
+/// property.
+///
+/// Triggers run them when the player comes within range; other entities when
+/// the player interacts with them. A `script` runs after the actions. Lists
+/// that fail to parse are skipped.
+pub fn spawn_scripted_actions(ctx: &mut EntitySpawnContext, entity: &EntityData) {
+    let script = entity.properties.get(ACTIONS_KEY);
+    let script_file = entity.properties.get(SCRIPT_KEY);
+    if script.is_none() && script_file.is_none() {
+        return;
+    }
+    let mut actions = match script.map_or(Ok(Vec::new()), |script| parse_actions(script)) {
+        Ok(actions) => actions,
+        Err(reason) => {
+            warn!("Skipping actions of {:?}: {}", entity.entity_type, reason);
+            return;
+        }
+    };
+    if let Some(path) = script_file.filter(|path| !path.trim().is_empty()) {
+        actions.push(MapAction::Script(path.trim().to_string()));
+    }
+    let activation = Activation::for_entity(entity.entity_type, &entity.properties);
+    let repeat = entity
+        .properties
+        .get("repeat")
+        .is_some_and(|r| r == "true" || r == "1");
+
+    let (x, y, z) = entity.position;
+    info!(
+        "Spawned {} scripted actions at ({}, {}, {}) ({:?})",
+        actions.len(),
+        x,
+        y,
+        z,
+        activation
+    );
+    let mut source = ctx.commands.spawn((
+        Transform::from_xyz(x, y, z),
+        GlobalTransform::default(),
+        ScriptedActions::new(actions, activation, repeat),
+        GameEntity,
+    ));
+    if activation == Activation::Interact {
+        source.insert(Interactable);
+    }
+}
+
 /// Parse light intensity from properties with defaults and clamping.
 /// Exposed for testing.
 #[allow(dead_code)]
@@ -365,3 +474,129 @@ pub(crate) fn parse_flicker_speed(properties: &HashMap<String, String>) -> f32 {
 
 #[cfg(test)]
 mod tests;
+
+/// Spawn a door entity from its `group`, `open_group`, `key` and `open`
+/// properties. Doors without a valid group are skipped.
+pub fn spawn_door(
+    ctx: &mut EntitySpawnContext,
+    position: Vec3,
+    properties: &HashMap<String, String>,
+) {
+    let door = match Door::from_properties(properties) {
+        Ok(door) => door,
+        Err(reason) => {
+            warn!("Skipping door at {:?}: {}", position, reason);
+            return;
+        }
+    };
+    info!(
+        "Spawned door at {:?} (group: '{}', open: {}, locked: {})",
+        position,
+        door.group,
+        door.open,
+        door.key.is_some()
+    );
+    ctx.commands.spawn((
+        Transform::from_translation(position),
+        GlobalTransform::default(),
+        door,
+        Interactable,
+        GameEntity,
+    ));
+}
+
+/// Spawn a key the player picks up to unlock doors with the same `key`.
+pub fn spawn_key_item(ctx: &mut EntitySpawnContext, position: Vec3, key: &str) {
+    let mesh = ctx.meshes.add(Cuboid::new(0.15, 0.3, 0.05));
+    let material = ctx.materials.add(StandardMaterial {
+        base_color: KEY_COLOR,
+        emissive: KEY_COLOR.to_linear() * 2.0,
+        metallic: 0.8,
+        perceptual_roughness: 0.3,
+        ..default()
+    });
+    ctx.commands.spawn((
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        Transform::from_translation(position),
+        KeyItem {
+            key: key.to_string(),
+        },
+        GameEntity,
+    ));
+    info!("Spawned key '{}' at {:?}", key, position);
+}
+
+/// Spawn a teleporter pad from its `target`, `radius`, `activation` and
+/// `keep_velocity` properties. Teleporters without a target are skipped.
+pub fn spawn_teleporter(
+    ctx: &mut EntitySpawnContext,
+    position: Vec3,
+    properties: &HashMap<String, String>,
+) {
+    let teleporter = match Teleporter::from_properties(properties) {
+        Ok(teleporter) => teleporter,
+        Err(reason) => {
+            warn!("Skipping teleporter at {:?}: {}", position, reason);
+            return;
+        }
+    };
+    info!(
+        "Spawned teleporter at {:?} (target: '{}', radius: {}, {:?})",
+        position, teleporter.target, teleporter.radius, teleporter.activation
+    );
+
+    let mesh = ctx.meshes.add(Cylinder::new(teleporter.radius, 0.04));
+    let material = ctx.materials.add(StandardMaterial {
+        base_color: TELEPORTER_COLOR.with_alpha(0.5),
+        emissive: TELEPORTER_COLOR.to_linear() * 1.5,
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    let interact = teleporter.activation == TeleportActivation::Interact;
+    let mut pad = ctx.commands.spawn((
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        Transform::from_translation(position),
+        teleporter,
+        GameEntity,
+    ));
+    if interact {
+        pad.insert(Interactable);
+    }
+}
+
+/// Spawn a goal from its `radius` property: a translucent beacon the player
+/// reaches to finish the run. Goals with an invalid radius are skipped.
+pub fn spawn_goal(
+    ctx: &mut EntitySpawnContext,
+    position: Vec3,
+    properties: &HashMap<String, String>,
+) {
+    let goal = match Goal::from_properties(properties) {
+        Ok(goal) => goal,
+        Err(reason) => {
+            warn!("Skipping goal at {:?}: {}", position, reason);
+            return;
+        }
+    };
+    info!("Spawned goal at {:?} (radius: {})", position, goal.radius);
+
+    let mesh = ctx.meshes.add(Cylinder::new(goal.radius, 2.0));
+    let material = ctx.materials.add(StandardMaterial {
+        base_color: GOAL_COLOR.with_alpha(0.25),
+        emissive: GOAL_COLOR.to_linear(),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    // The beacon stands on the goal's position
+    ctx.commands.spawn((
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        Transform::from_translation(position + Vec3::Y),
+        goal,
+        GameEntity,
+    ));
+}
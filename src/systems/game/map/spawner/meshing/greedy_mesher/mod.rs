@@ -31,9 +31,61 @@ pub struct GreedyMesher {
     /// where u,v are the 2D coordinates within the slice
     #[allow(clippy::type_complexity)]
     slices: HashMap<(Face, i32), Vec<(i32, i32, usize, Color)>>,
+    /// Global sub-voxel coordinates (inclusive) of the chunk being meshed,
+    /// if any; see [`GreedyMesher::for_chunk`]
+    bounds: Option<(IVec3, IVec3)>,
 }
 
 impl GreedyMesher {
+    /// Mesher for the faces of a chunk spanning global sub-voxel coordinates
+    /// `min` to `max` (inclusive).
+    ///
+    /// Its LOD meshes keep full detail wherever a LOD cell would cross the
+    /// chunk's border, so the chunk meets its neighbours without cracks
+    /// whatever LOD each of them is at.
+    pub fn for_chunk(min: IVec3, max: IVec3) -> Self {
+        Self {
+            bounds: Some((min, max)),
+            ..default()
+        }
+    }
+
+    /// Axes of the slice depth and of its (u, v) coordinates for `face`.
+    #[inline]
+    fn axes(face: Face) -> (usize, usize, usize) {
+        match face {
+            Face::PosX | Face::NegX => (0, 1, 2),
+            Face::PosY | Face::NegY => (1, 0, 2),
+            Face::PosZ | Face::NegZ => (2, 0, 1),
+        }
+    }
+
+    /// Whether the LOD cell of `sample_rate` sub-voxels holding the face at
+    /// (`depth`, `u`, `v`) lies wholly inside the chunk.
+    fn cell_inside(&self, face: Face, depth: i32, u: i32, v: i32, sample_rate: i32) -> bool {
+        let Some((min, max)) = self.bounds else {
+            return true;
+        };
+        let (depth_axis, u_axis, v_axis) = Self::axes(face);
+        let inside = |value: i32, axis: usize| {
+            let start = value.div_euclid(sample_rate) * sample_rate;
+            start >= min[axis] && start + sample_rate - 1 <= max[axis]
+        };
+        inside(depth, depth_axis) && inside(u, u_axis) && inside(v, v_axis)
+    }
+
+    /// Depth a face at `depth` is drawn at in LOD cells of `sample_rate`
+    /// sub-voxels: the cell's outer side in the direction the face points,
+    /// so whole-voxel faces stay in place at every LOD.
+    #[inline]
+    fn lod_depth(face: Face, depth: i32, sample_rate: i32) -> i32 {
+        let start = depth.div_euclid(sample_rate) * sample_rate;
+        match face {
+            Face::PosX | Face::PosY | Face::PosZ => start + sample_rate - 1,
+            Face::NegX | Face::NegY | Face::NegZ => start,
+        }
+    }
+
     /// Add a visible face to the mesher.
     #[inline]
     pub fn add_face(
@@ -169,21 +221,92 @@ impl GreedyMesher {
 
     /// Build greedy-meshed geometry at a specific LOD level.
     /// LOD 0 = full detail, LOD 1 = 1/2 resolution, LOD 2 = 1/4, LOD 3 = 1/8
-    pub fn build_lod(&self, builder: &mut ChunkMeshBuilder, lod_level: usize) {
+    ///
+    /// Slices are snapped to the LOD grid with [`GreedyMesher::lod_depth`]
+    /// rather than dropped, with the outermost face of each cell picking its
+    /// color. Faces whose cell crosses the chunk's border are meshed at full
+    /// detail instead.
+    ///
+    /// Cells whose colors differ by at most `color_tolerance` in every
+    /// channel merge as if they had the same color (see
+    /// [`MeshingData`](crate::systems::game::map::format::MeshingData)).
+    pub fn build_lod(
+        &self,
+        builder: &mut ChunkMeshBuilder,
+        lod_level: usize,
+        color_tolerance: f32,
+    ) {
         let sample_rate = 1 << lod_level; // 1, 2, 4, 8 for LOD 0-3
         let lod_voxel_size = SUB_VOXEL_SIZE * sample_rate as f32;
 
+        // Key: (Face direction, LOD depth) -> Vec of (depth, u, v, color_index, color)
+        #[allow(clippy::type_complexity)]
+        let mut lod_slices: HashMap<(Face, i32), Vec<(i32, i32, i32, usize, Color)>> =
+            HashMap::new();
         for ((face, depth), faces) in &self.slices {
-            // Only process slices at LOD intervals
-            if depth % sample_rate != 0 {
-                continue;
+            let (inner, border): (Vec<_>, Vec<_>) = faces
+                .iter()
+                .copied()
+                .partition(|&(u, v, _, _)| self.cell_inside(*face, *depth, u, v, sample_rate));
+            if !border.is_empty() {
+                Self::mesh_slice(builder, *face, *depth, &border);
             }
+            lod_slices
+                .entry((*face, Self::lod_depth(*face, *depth, sample_rate)))
+                .or_default()
+                .extend(
+                    inner
+                        .iter()
+                        .map(|&(u, v, ci, color)| (*depth, u, v, ci, color)),
+                );
+        }
+
+        for ((face, depth), mut faces) in lod_slices {
+            // Outermost faces first, so they color their cells
+            let outward = match face {
+                Face::PosX | Face::PosY | Face::PosZ => -1,
+                Face::NegX | Face::NegY | Face::NegZ => 1,
+            };
+            faces.sort_by_key(|&(face_depth, ..)| face_depth * outward);
+            let faces: Vec<_> = faces
+                .into_iter()
+                .map(|(_, u, v, ci, color)| (u, v, ci, color))
+                .collect();
+            Self::mesh_slice_lod(
+                builder,
+                face,
+                depth,
+                &faces,
+                sample_rate,
+                lod_voxel_size,
+                color_tolerance,
+            );
+        }
+    }
 
-            Self::mesh_slice_lod(builder, *face, *depth, faces, sample_rate, lod_voxel_size);
+    /// Whether a cell of `other_index` and `other` can join a quad started
+    /// by a cell of `color_index` and `color`.
+    #[inline]
+    fn merges(
+        (color_index, color): (usize, Color),
+        (other_index, other): (usize, Color),
+        color_tolerance: f32,
+    ) -> bool {
+        if other_index == color_index {
+            return true;
+        }
+        if color_tolerance <= 0.0 {
+            return false;
         }
+        let (a, b) = (color.to_srgba(), other.to_srgba());
+        (a.red - b.red).abs() <= color_tolerance
+            && (a.green - b.green).abs() <= color_tolerance
+            && (a.blue - b.blue).abs() <= color_tolerance
+            && (a.alpha - b.alpha).abs() <= color_tolerance
     }
 
     /// Mesh a single 2D slice at a specific LOD level.
+    #[allow(clippy::too_many_arguments)]
     fn mesh_slice_lod(
         builder: &mut ChunkMeshBuilder,
         face: Face,
@@ -191,6 +314,7 @@ impl GreedyMesher {
         faces: &[(i32, i32, usize, Color)],
         sample_rate: i32,
         lod_voxel_size: f32,
+        color_tolerance: f32,
     ) {
         if faces.is_empty() {
             return;
@@ -201,8 +325,8 @@ impl GreedyMesher {
         let mut grid: HashMap<(i32, i32), (usize, Color, bool)> = HashMap::new();
 
         for (u, v, color_index, color) in faces {
-            let lod_u = u / sample_rate;
-            let lod_v = v / sample_rate;
+            let lod_u = u.div_euclid(sample_rate);
+            let lod_v = v.div_euclid(sample_rate);
 
             // First face in each LOD cell determines color
             grid.entry((lod_u, lod_v))
@@ -227,8 +351,8 @@ impl GreedyMesher {
 
             // Expand in U direction
             let mut end_u = start_u;
-            while let Some((ci, _, used)) = grid.get(&(end_u + 1, start_v)) {
-                if *used || *ci != color_index {
+            while let Some((ci, c, used)) = grid.get(&(end_u + 1, start_v)) {
+                if *used || !Self::merges((color_index, color), (*ci, *c), color_tolerance) {
                     break;
                 }
                 end_u += 1;
@@ -240,7 +364,13 @@ impl GreedyMesher {
                 let next_v = end_v + 1;
                 for u in start_u..=end_u {
                     match grid.get(&(u, next_v)) {
-                        Some((ci, _, used)) if !*used && *ci == color_index => {}
+                        Some((ci, c, used))
+                            if !*used
+                                && Self::merges(
+                                    (color_index, color),
+                                    (*ci, *c),
+                                    color_tolerance,
+                                ) => {}
                         _ => break 'v_expand,
                     }
                 }
@@ -260,14 +390,14 @@ impl GreedyMesher {
             let width = (end_u - start_u + 1) as f32 * lod_voxel_size;
             let height = (end_v - start_v + 1) as f32 * lod_voxel_size;
 
-            // Calculate center position using LOD-scaled coordinates
+            // Center of the merged cells, in full-detail slice coordinates
             let (center_x, center_y, center_z) = Self::slice_to_world_lod(
                 face,
                 depth,
-                start_u as f32 * sample_rate as f32
-                    + (end_u - start_u) as f32 * sample_rate as f32 / 2.0,
-                start_v as f32 * sample_rate as f32
-                    + (end_v - start_v) as f32 * sample_rate as f32 / 2.0,
+                (start_u * sample_rate) as f32
+                    + ((end_u - start_u + 1) * sample_rate - 1) as f32 / 2.0,
+                (start_v * sample_rate) as f32
+                    + ((end_v - start_v + 1) * sample_rate - 1) as f32 / 2.0,
             );
 
             let center = Vec3::new(center_x, center_y, center_z);
@@ -293,3 +423,6 @@ impl GreedyMesher {
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
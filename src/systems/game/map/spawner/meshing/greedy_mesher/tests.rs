@@ -0,0 +1,99 @@
+use super::*;
+
+/// Top faces of the whole voxel at the origin
+fn voxel_top(mesher: &mut GreedyMesher) {
+    for x in 0..SUB_VOXEL_COUNT {
+        for z in 0..SUB_VOXEL_COUNT {
+            mesher.add_face(x, SUB_VOXEL_COUNT - 1, z, Face::PosY, 0, Color::WHITE);
+        }
+    }
+}
+
+fn lod_positions(mesher: &GreedyMesher, lod_level: usize) -> Vec<Vec3> {
+    let mut builder = ChunkMeshBuilder::default();
+    mesher.build_lod(&mut builder, lod_level, 0.0);
+    builder
+        .positions
+        .iter()
+        .map(|p| Vec3::from_array(*p))
+        .collect()
+}
+
+#[test]
+fn whole_voxel_faces_keep_their_place_at_every_lod() {
+    let mut mesher = GreedyMesher::default();
+    voxel_top(&mut mesher);
+    for lod_level in 0..4 {
+        let positions = lod_positions(&mesher, lod_level);
+        assert!(!positions.is_empty(), "LOD {} dropped the top", lod_level);
+        for p in positions {
+            assert!((p.y - 0.5).abs() < 1e-5, "LOD {}: {:?}", lod_level, p);
+            assert!(p.x.abs() <= 0.5 + 1e-5 && p.z.abs() <= 0.5 + 1e-5);
+        }
+    }
+}
+
+#[test]
+fn lod_cells_line_up_with_negative_coordinates() {
+    let mut mesher = GreedyMesher::default();
+    mesher.add_face(-1, 0, -1, Face::PosY, 0, Color::WHITE);
+    let positions = lod_positions(&mesher, 1);
+    // The two-sub-voxel cell holding -1 spans -2..=-1
+    let min_x = positions.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+    let max_x = positions.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+    assert!((min_x - (-2.0 * SUB_VOXEL_SIZE - 0.5)).abs() < 1e-5);
+    assert!((max_x - (-0.5)).abs() < 1e-5);
+}
+
+#[test]
+fn chunk_borders_keep_full_detail() {
+    let fill = |mesher: &mut GreedyMesher| {
+        for x in 4..16 {
+            for z in 4..16 {
+                mesher.add_face(x, 15, z, Face::PosY, 0, Color::WHITE);
+            }
+        }
+    };
+    let mut chunk = GreedyMesher::for_chunk(IVec3::splat(4), IVec3::splat(131));
+    fill(&mut chunk);
+    let mut loose = GreedyMesher::default();
+    fill(&mut loose);
+
+    let min_x = |positions: Vec<Vec3>| positions.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+    // A whole LOD 3 cell would stick out of the chunk past its first sub-voxel
+    assert!((min_x(lod_positions(&loose, 3)) - (-0.5)).abs() < 1e-5);
+    assert!(min_x(lod_positions(&chunk, 3)).abs() < 1e-5);
+    let max_x = lod_positions(&chunk, 3)
+        .iter()
+        .map(|p| p.x)
+        .fold(f32::MIN, f32::max);
+    assert!((max_x - 1.5).abs() < 1e-5);
+}
+
+#[test]
+fn color_tolerance_merges_nearby_colors() {
+    let mut mesher = GreedyMesher::default();
+    for (x, index, shade) in [(0, 1, 0.5), (1, 2, 0.52), (2, 3, 0.9)] {
+        mesher.add_face(x, 0, 0, Face::PosY, index, Color::srgb(shade, shade, shade));
+    }
+    let quads = |color_tolerance| {
+        let mut builder = ChunkMeshBuilder::default();
+        mesher.build_lod(&mut builder, 0, color_tolerance);
+        builder.positions.len() / 4
+    };
+    assert_eq!(quads(0.0), 3);
+    // The far brighter face stays apart
+    assert_eq!(quads(0.05), 2);
+    assert_eq!(quads(1.0), 1);
+}
+
+#[test]
+fn meshing_tolerance_starts_at_its_lod() {
+    let meshing = crate::systems::game::map::format::MeshingData {
+        color_tolerance: 0.1,
+        from_lod: 2,
+    };
+    assert_eq!(meshing.tolerance_at(1), 0.0);
+    assert_eq!(meshing.tolerance_at(2), 0.1);
+    assert_eq!(meshing.tolerance_at(3), 0.1);
+}
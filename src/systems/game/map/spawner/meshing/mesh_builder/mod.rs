@@ -1,9 +1,74 @@
 //! Chunk mesh builder for constructing meshes from voxel geometry.
 
+mod vertex_cache;
+
 use super::{Face, SUB_VOXEL_SIZE};
 use bevy::asset::RenderAssetUsages;
 use bevy::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
 use bevy::prelude::*;
+use std::collections::HashMap;
+use std::ops::AddAssign;
+use vertex_cache::{cache_misses, optimize_triangle_order};
+
+/// Bytes of vertex attributes per vertex: position, normal, UV and color
+const VERTEX_BYTES: usize = (3 + 3 + 2 + 4) * std::mem::size_of::<f32>();
+
+/// Steps per world unit positions are snapped to when welding vertices, so
+/// corners reached through different float sums still match
+const WELD_PRECISION: f32 = 1024.0;
+
+/// Size and vertex cache efficiency of a chunk mesh.
+///
+/// Stats add up, so they can be totalled over many meshes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MeshStats {
+    pub vertices: usize,
+    pub triangles: usize,
+    /// Vertex cache misses drawing the triangles in order
+    pub cache_misses: usize,
+}
+
+impl MeshStats {
+    /// GPU memory taken by the vertex attributes.
+    pub fn vertex_bytes(&self) -> usize {
+        self.vertices * VERTEX_BYTES
+    }
+
+    /// Average vertex cache misses per triangle (ACMR); 3.0 means no
+    /// vertex is ever reused, lower is better.
+    pub fn acmr(&self) -> f32 {
+        if self.triangles == 0 {
+            0.0
+        } else {
+            self.cache_misses as f32 / self.triangles as f32
+        }
+    }
+}
+
+impl AddAssign for MeshStats {
+    fn add_assign(&mut self, other: Self) {
+        self.vertices += other.vertices;
+        self.triangles += other.triangles;
+        self.cache_misses += other.cache_misses;
+    }
+}
+
+/// UV of a vertex at `position` on a face facing `normal`, tiling once per
+/// sub-voxel in the face's plane like [`ChunkMeshBuilder::add_quad`]'s.
+///
+/// It depends only on the vertex itself, so quads sharing a welded corner
+/// agree on it.
+fn planar_uv(position: [f32; 3], normal: [f32; 3]) -> [f32; 2] {
+    let [x, y, z] = position.map(|c| c / SUB_VOXEL_SIZE);
+    match normal {
+        [nx, _, _] if nx > 0.5 => [-z, -y],
+        [nx, _, _] if nx < -0.5 => [z, -y],
+        [_, ny, _] if ny > 0.5 => [x, z],
+        [_, ny, _] if ny < -0.5 => [x, -z],
+        [_, _, nz] if nz > 0.5 => [x, -y],
+        _ => [-x, -y],
+    }
+}
 
 /// Builder for constructing chunk meshes from multiple cubes.
 /// Combines all sub-voxels in a chunk into a single mesh with vertex colors.
@@ -302,8 +367,80 @@ impl ChunkMeshBuilder {
     }
 
     /// Get the number of quads in the builder (for statistics).
-    /// Each quad has 4 vertices.
+    /// Each quad has two triangles, whether or not its vertices are welded.
     pub fn quad_count(&self) -> usize {
-        self.positions.len() / 4
+        self.indices.len() / 6
+    }
+
+    /// Size and vertex cache efficiency of the geometry so far.
+    pub fn stats(&self) -> MeshStats {
+        MeshStats {
+            vertices: self.positions.len(),
+            triangles: self.indices.len() / 3,
+            cache_misses: cache_misses(&self.indices),
+        }
+    }
+
+    /// Post-process the geometry for a smaller, faster mesh, returning its
+    /// stats before and after.
+    ///
+    /// Vertices with the same position, normal and color are welded into
+    /// one, with UVs from [`planar_uv`]; triangles are reordered for the
+    /// vertex cache, and vertices renumbered in the order they are drawn.
+    /// The triangles themselves don't change.
+    pub fn optimize(&mut self) -> (MeshStats, MeshStats) {
+        let before = self.stats();
+
+        // Weld
+        // Key: (snapped position, normal bits, color bits) -> welded index
+        #[allow(clippy::type_complexity)]
+        let mut welded: HashMap<([i32; 3], [u32; 3], [u32; 4]), u32> = HashMap::new();
+        let mut remap = Vec::with_capacity(self.positions.len());
+        let (mut positions, mut normals, mut colors) = (Vec::new(), Vec::new(), Vec::new());
+        for ((position, normal), color) in
+            self.positions.iter().zip(&self.normals).zip(&self.colors)
+        {
+            let key = (
+                position.map(|c| (c * WELD_PRECISION).round() as i32),
+                normal.map(f32::to_bits),
+                color.map(f32::to_bits),
+            );
+            let index = *welded.entry(key).or_insert_with(|| {
+                positions.push(*position);
+                normals.push(*normal);
+                colors.push(*color);
+                positions.len() as u32 - 1
+            });
+            remap.push(index);
+        }
+        let indices: Vec<u32> = self.indices.iter().map(|&i| remap[i as usize]).collect();
+
+        // Reorder triangles, then vertices by first use
+        let indices = optimize_triangle_order(&indices, positions.len());
+        let mut order: Vec<Option<u32>> = vec![None; positions.len()];
+        let mut drawn = Vec::with_capacity(positions.len());
+        self.indices = indices
+            .iter()
+            .map(|&i| {
+                *order[i as usize].get_or_insert_with(|| {
+                    drawn.push(i as usize);
+                    drawn.len() as u32 - 1
+                })
+            })
+            .collect();
+        self.positions = drawn.iter().map(|&i| positions[i]).collect();
+        self.normals = drawn.iter().map(|&i| normals[i]).collect();
+        self.colors = drawn.iter().map(|&i| colors[i]).collect();
+        self.uvs = self
+            .positions
+            .iter()
+            .zip(&self.normals)
+            .map(|(position, normal)| planar_uv(*position, *normal))
+            .collect();
+
+        (before, self.stats())
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,100 @@
+use super::*;
+use std::collections::HashSet;
+
+/// Triangles as sets of corner positions, in drawing order
+fn triangles(builder: &ChunkMeshBuilder) -> Vec<[[i32; 3]; 3]> {
+    builder
+        .indices
+        .chunks_exact(3)
+        .map(|corners| {
+            let corner = |i: u32| builder.positions[i as usize].map(|c| (c * 64.0) as i32);
+            [corner(corners[0]), corner(corners[1]), corner(corners[2])]
+        })
+        .collect()
+}
+
+/// A flat `size`×`size` grid of single sub-voxel top faces
+fn grid(size: i32, color: impl Fn(i32, i32) -> Color) -> ChunkMeshBuilder {
+    let mut builder = ChunkMeshBuilder::default();
+    for x in 0..size {
+        for z in 0..size {
+            let position = Vec3::new(x as f32, 0.0, z as f32) * SUB_VOXEL_SIZE;
+            builder.add_face(position, SUB_VOXEL_SIZE, Face::PosY, color(x, z));
+        }
+    }
+    builder
+}
+
+#[test]
+fn welding_shares_corners_of_same_colored_quads() {
+    let mut builder = grid(4, |_, _| Color::WHITE);
+    let (before, after) = builder.optimize();
+    assert_eq!(before.vertices, 64);
+    // A 4×4 grid of quads has 5×5 corners
+    assert_eq!(after.vertices, 25);
+    assert_eq!(after.triangles, before.triangles);
+    assert_eq!(builder.quad_count(), 16);
+    assert!(after.vertex_bytes() < before.vertex_bytes());
+}
+
+#[test]
+fn welding_keeps_colors_and_normals_apart() {
+    let mut builder = grid(2, |x, _| if x == 0 { Color::WHITE } else { Color::BLACK });
+    builder.add_face(Vec3::ZERO, SUB_VOXEL_SIZE, Face::NegY, Color::WHITE);
+    let (_, after) = builder.optimize();
+    // Two columns of two white and two black quads, and a lone bottom face
+    assert_eq!(after.vertices, 6 + 6 + 4);
+}
+
+#[test]
+fn optimizing_keeps_every_triangle_and_its_winding() {
+    let mut builder = grid(6, |x, z| Color::srgb((x * z % 3) as f32 / 3.0, 0.5, 0.5));
+    builder.add_face(Vec3::Y, SUB_VOXEL_SIZE, Face::PosX, Color::WHITE);
+    let rotate = |mut t: [[i32; 3]; 3]| {
+        // Same winding whatever corner it starts at
+        let first = (0..3).min_by_key(|&i| t[i]).unwrap();
+        t.rotate_left(first);
+        t
+    };
+    let original: HashSet<_> = triangles(&builder).into_iter().map(rotate).collect();
+    builder.optimize();
+    let optimized: Vec<_> = triangles(&builder).into_iter().map(rotate).collect();
+    assert_eq!(optimized.len(), original.len());
+    assert_eq!(optimized.into_iter().collect::<HashSet<_>>(), original);
+    assert_eq!(builder.uvs.len(), builder.positions.len());
+    assert_eq!(builder.colors.len(), builder.positions.len());
+}
+
+#[test]
+fn optimizing_improves_cache_reuse() {
+    let mut builder = grid(16, |_, _| Color::WHITE);
+    let (before, after) = builder.optimize();
+    // Unwelded quads miss on every vertex
+    assert!((before.acmr() - 2.0).abs() < 1e-5);
+    assert!(after.acmr() < 1.0, "ACMR {}", after.acmr());
+    // Vertices are renumbered in drawing order
+    let mut seen = 0;
+    for &index in &builder.indices {
+        assert!(index <= seen);
+        seen = seen.max(index + 1);
+    }
+}
+
+#[test]
+fn welded_uvs_tile_per_sub_voxel() {
+    let mut builder = grid(2, |_, _| Color::WHITE);
+    builder.optimize();
+    for (position, uv) in builder.positions.iter().zip(&builder.uvs) {
+        assert!((uv[0] - position[0] / SUB_VOXEL_SIZE).abs() < 1e-4);
+        assert!((uv[1] - position[2] / SUB_VOXEL_SIZE).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn empty_builders_optimize_to_nothing() {
+    let mut builder = ChunkMeshBuilder::default();
+    let (before, after) = builder.optimize();
+    assert_eq!(before, MeshStats::default());
+    assert_eq!(after, MeshStats::default());
+    assert_eq!(after.acmr(), 0.0);
+}
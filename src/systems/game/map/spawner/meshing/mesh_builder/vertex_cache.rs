@@ -0,0 +1,161 @@
+//! Triangle ordering for the GPU's post-transform vertex cache.
+//!
+//! Uses Tom Forsyth's linear-speed vertex cache optimisation: triangles are
+//! emitted greedily by a score that favours vertices still in a modelled LRU
+//! cache and vertices with few triangles left to draw.
+
+/// Vertices the modelled cache holds
+pub const VERTEX_CACHE_SIZE: usize = 32;
+
+/// How fast a vertex's score falls as it ages in the cache
+const CACHE_DECAY_POWER: f32 = 1.5;
+/// Score of the vertices of the triangle just drawn
+const LAST_TRIANGLE_SCORE: f32 = 0.75;
+/// Bonus for vertices with few triangles left, so none are left stranded
+const VALENCE_BOOST_SCALE: f32 = 2.0;
+const VALENCE_BOOST_POWER: f32 = 0.5;
+
+/// Score of a vertex at `cache_position` with `remaining` triangles left.
+fn vertex_score(cache_position: Option<usize>, remaining: usize) -> f32 {
+    if remaining == 0 {
+        return -1.0;
+    }
+    let cache_score = match cache_position {
+        Some(position) if position < 3 => LAST_TRIANGLE_SCORE,
+        Some(position) => {
+            let scale = 1.0 / (VERTEX_CACHE_SIZE - 3) as f32;
+            (1.0 - (position - 3) as f32 * scale).powf(CACHE_DECAY_POWER)
+        }
+        None => 0.0,
+    };
+    cache_score + VALENCE_BOOST_SCALE * (remaining as f32).powf(-VALENCE_BOOST_POWER)
+}
+
+/// `indices` with their triangles reordered so consecutive triangles reuse
+/// recently transformed vertices. Every triangle keeps its winding.
+pub fn optimize_triangle_order(indices: &[u32], vertex_count: usize) -> Vec<u32> {
+    let triangle_count = indices.len() / 3;
+    if triangle_count == 0 {
+        return indices.to_vec();
+    }
+
+    // Triangles of each vertex, packed: those of vertex `v` start at
+    // `offsets[v]`, and the first `remaining[v]` of them are undrawn
+    let mut offsets = vec![0usize; vertex_count + 1];
+    for &index in indices {
+        offsets[index as usize + 1] += 1;
+    }
+    for vertex in 0..vertex_count {
+        offsets[vertex + 1] += offsets[vertex];
+    }
+    let mut adjacency = vec![0usize; indices.len()];
+    let mut fill = offsets.clone();
+    for (corner, &index) in indices.iter().enumerate() {
+        adjacency[fill[index as usize]] = corner / 3;
+        fill[index as usize] += 1;
+    }
+    let mut remaining: Vec<usize> = (0..vertex_count)
+        .map(|vertex| offsets[vertex + 1] - offsets[vertex])
+        .collect();
+
+    let mut cache_position: Vec<Option<usize>> = vec![None; vertex_count];
+    let mut vertex_scores: Vec<f32> = remaining
+        .iter()
+        .map(|&count| vertex_score(None, count))
+        .collect();
+    let mut triangle_scores: Vec<f32> = indices
+        .chunks_exact(3)
+        .map(|corners| corners.iter().map(|&v| vertex_scores[v as usize]).sum())
+        .collect();
+    let mut drawn = vec![false; triangle_count];
+    let mut cache: Vec<u32> = Vec::with_capacity(VERTEX_CACHE_SIZE + 3);
+    let mut output = Vec::with_capacity(indices.len());
+
+    let mut best =
+        (0..triangle_count).max_by(|&a, &b| triangle_scores[a].total_cmp(&triangle_scores[b]));
+    // Fallback when no cached vertex has triangles left
+    let mut next_undrawn = 0;
+
+    for _ in 0..triangle_count {
+        let triangle = match best {
+            Some(triangle) => triangle,
+            None => {
+                while drawn[next_undrawn] {
+                    next_undrawn += 1;
+                }
+                next_undrawn
+            }
+        };
+        drawn[triangle] = true;
+        let corners = &indices[3 * triangle..3 * triangle + 3];
+        output.extend_from_slice(corners);
+
+        for &vertex in corners {
+            let vertex = vertex as usize;
+            let start = offsets[vertex];
+            let undrawn = &mut adjacency[start..start + remaining[vertex]];
+            if let Some(position) = undrawn.iter().position(|&t| t == triangle) {
+                let last = undrawn.len() - 1;
+                undrawn.swap(position, last);
+                remaining[vertex] -= 1;
+            }
+        }
+
+        // The triangle's vertices move to the front of the cache
+        let mut new_cache: Vec<u32> = Vec::with_capacity(VERTEX_CACHE_SIZE + 3);
+        for &vertex in corners.iter().chain(cache.iter()) {
+            if !new_cache.contains(&vertex) {
+                new_cache.push(vertex);
+            }
+        }
+        for (position, &vertex) in new_cache.iter().enumerate() {
+            cache_position[vertex as usize] = (position < VERTEX_CACHE_SIZE).then_some(position);
+        }
+
+        // Rescore the vertices that moved, and pick the best triangle among
+        // those still cached
+        for &vertex in &new_cache {
+            let vertex = vertex as usize;
+            let score = vertex_score(cache_position[vertex], remaining[vertex]);
+            let delta = score - vertex_scores[vertex];
+            vertex_scores[vertex] = score;
+            let start = offsets[vertex];
+            for &t in &adjacency[start..start + remaining[vertex]] {
+                triangle_scores[t] += delta;
+            }
+        }
+        new_cache.truncate(VERTEX_CACHE_SIZE);
+        best = None;
+        let mut best_score = f32::MIN;
+        for &vertex in &new_cache {
+            let start = offsets[vertex as usize];
+            for &t in &adjacency[start..start + remaining[vertex as usize]] {
+                if triangle_scores[t] > best_score {
+                    best = Some(t);
+                    best_score = triangle_scores[t];
+                }
+            }
+        }
+        cache = new_cache;
+    }
+
+    output
+}
+
+/// Vertex cache misses drawing `indices` through an LRU cache of
+/// [`VERTEX_CACHE_SIZE`] vertices.
+pub fn cache_misses(indices: &[u32]) -> usize {
+    let mut cache: Vec<u32> = Vec::with_capacity(VERTEX_CACHE_SIZE + 1);
+    let mut misses = 0;
+    for &index in indices {
+        match cache.iter().position(|&v| v == index) {
+            Some(position) => {
+                cache.remove(position);
+            }
+            None => misses += 1,
+        }
+        cache.insert(0, index);
+        cache.truncate(VERTEX_CACHE_SIZE);
+    }
+    misses
+}
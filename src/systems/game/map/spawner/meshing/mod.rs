@@ -13,6 +13,6 @@ pub(crate) use super::{Face, SUB_VOXEL_COUNT, SUB_VOXEL_SIZE};
 
 // Public exports
 pub use greedy_mesher::GreedyMesher;
-pub use mesh_builder::ChunkMeshBuilder;
+pub use mesh_builder::{ChunkMeshBuilder, MeshStats};
 pub use occupancy::OccupancyGrid;
 pub use palette::VoxelMaterialPalette;
@@ -11,25 +11,46 @@ mod entities;
 mod meshing;
 mod shadow_quality;
 
-pub use chunks::{spawn_voxels_chunked, ChunkMaterial, ChunkSpawnContext};
-pub use entities::{spawn_light_source, spawn_npc, spawn_player, EntitySpawnContext};
+pub use chunks::{
+    build_scenery_meshes, rebuild_chunks, spawn_voxels_chunked, sub_voxel_bounds, voxel_chunks,
+    voxel_type_material, ChunkMaterial, ChunkMaterials, ChunkSpawnContext,
+};
+pub(crate) use chunks::{face_color_for, get_sub_voxel_color, sub_voxel_color_for};
+pub use entities::{
+    load_character_scene, player_body, spawn_character_model, spawn_door, spawn_goal,
+    spawn_key_item, spawn_light_source, spawn_npc, spawn_player, spawn_scripted_actions,
+    spawn_teleporter, spawn_weather_zone, EntitySpawnContext,
+};
 pub use meshing::{ChunkMeshBuilder, GreedyMesher, OccupancyGrid, VoxelMaterialPalette};
 pub use shadow_quality::apply_shadow_quality_system;
 
 use bevy::core_pipeline::prepass::DepthPrepass;
 
-use super::super::components::GameCamera;
+use super::super::actions::ACTIONS_KEY;
+use super::super::cinematics::Cinematic;
+use super::super::components::{GameCamera, GameEntity};
+use super::super::doors::PlayerKeys;
 use super::super::occlusion::{
-    create_occlusion_material, OcclusionConfig, OcclusionMaterialHandle, ShadowQuality,
+    create_occlusion_material, OcclusionConfig, OcclusionExtension, OcclusionMaterialHandle,
+    ShadowQuality,
 };
 use super::super::resources::{GameInitialized, SpatialGrid};
-use super::format::{EntityType, MapData};
+use super::super::scripting::SCRIPT_KEY;
+use super::super::speedrun::SpeedrunTimer;
+use super::super::teleporters::TeleportState;
+use super::super::voxel_groups::VoxelGroups;
+use super::super::weather::{Weather, WeatherCamera};
+use super::format::{EntityType, MapData, VoxelFace, WeatherData};
 use super::loader::{LoadProgress, LoadedMapData, MapLoadProgress};
 use crate::diagnostics::FrameProfiler;
 use crate::profile_scope;
+use crate::systems::accessibility::AccessibilitySettings;
+use crate::systems::preload::{chunk_standard_material, PreloadedAssets};
 use bevy::ecs::system::SystemParam;
-use bevy::light::{CascadeShadowConfig, CascadeShadowConfigBuilder};
+use bevy::light::{CascadeShadowConfig, CascadeShadowConfigBuilder, DirectionalLightShadowMap};
+use bevy::pbr::ExtendedMaterial;
 use bevy::prelude::*;
+use std::collections::HashMap;
 
 /// Number of sub-voxels per voxel axis (8x8x8 = 512 sub-voxels per voxel)
 pub const SUB_VOXEL_COUNT: i32 = 8;
@@ -64,8 +85,7 @@ pub struct SpawnAssets<'w> {
 /// Invariant: always spawned together with `ChunkLOD` — see `spawn_voxels_chunked`.
 #[derive(Component)]
 pub struct VoxelChunk {
-    /// The chunk position for potential future use (chunk updates, unloading)
-    #[allow(dead_code)]
+    /// The chunk position, used to find chunks to rebuild when voxel groups change
     pub chunk_pos: IVec3,
     /// Center of the chunk in world coordinates for LOD distance calculation
     pub center: Vec3,
@@ -87,16 +107,33 @@ pub struct LodConfig {
     /// Minimum camera movement (world units) required to trigger a LOD recalculation.
     /// Defaults to [`LOD_MOVEMENT_THRESHOLD`].
     pub movement_threshold: f32,
+    /// Multiplier on [`LOD_DISTANCES`]; below 1.0 chunks drop detail closer
+    /// to the camera. Tuned at runtime by dynamic quality.
+    pub distance_scale: f32,
+    /// Multiplier on the directional light's shadow distance, tuned like
+    /// `distance_scale`.
+    pub shadow_distance_scale: f32,
 }
 
 impl Default for LodConfig {
     fn default() -> Self {
         Self {
             movement_threshold: LOD_MOVEMENT_THRESHOLD,
+            distance_scale: 1.0,
+            shadow_distance_scale: 1.0,
         }
     }
 }
 
+/// LOD level for a chunk `distance` units from the camera, with the
+/// thresholds scaled by `distance_scale`.
+pub fn lod_for_distance(distance: f32, distance_scale: f32) -> usize {
+    LOD_DISTANCES
+        .iter()
+        .position(|&threshold| distance < threshold * distance_scale)
+        .unwrap_or(LOD_LEVELS - 1)
+}
+
 /// Face direction for hidden face culling.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Face {
@@ -128,6 +165,16 @@ impl Face {
         }
     }
 
+    /// Which part of a voxel's surface this face is, for per-face colors.
+    #[inline]
+    pub fn voxel_face(self) -> VoxelFace {
+        match self {
+            Face::PosY => VoxelFace::Top,
+            Face::NegY => VoxelFace::Bottom,
+            Face::PosX | Face::NegX | Face::PosZ | Face::NegZ => VoxelFace::Side,
+        }
+    }
+
     /// Returns the neighbor offset for this face direction.
     #[inline]
     pub fn offset(self) -> (i32, i32, i32) {
@@ -152,6 +199,7 @@ mod tests;
 ///
 /// Uses chunk-based meshing to combine sub-voxels into larger meshes,
 /// dramatically reducing entity count and improving performance.
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_map_system(
     mut commands: Commands,
     map_data: Res<LoadedMapData>,
@@ -159,6 +207,8 @@ pub fn spawn_map_system(
     mut assets: SpawnAssets,
     game_initialized: Option<Res<GameInitialized>>,
     occlusion_config: Res<OcclusionConfig>,
+    accessibility: Res<AccessibilitySettings>,
+    preloaded: Option<Res<PreloadedAssets>>,
     profiler: Option<Res<FrameProfiler>>,
 ) {
     profile_scope!(profiler, "spawn_map_system");
@@ -184,21 +234,47 @@ pub fn spawn_map_system(
         let occlusion_mat = create_occlusion_material(
             assets.occlusion_materials.as_mut(),
             occlusion_config.technique,
+            &map.edges,
         );
         commands.insert_resource(OcclusionMaterialHandle(occlusion_mat.clone()));
         ChunkMaterial::Occlusion(occlusion_mat)
-    } else {
-        // Standard PBR material with vertex colors
-        let standard_mat = assets.materials.add(StandardMaterial {
-            base_color: Color::WHITE,
-            perceptual_roughness: 0.9,
-            metallic: 0.0,
-            reflectance: 0.1,
-            ..default()
+    } else if map.edges.is_visible() {
+        // The edges need the custom shader too, with its occlusion turned off
+        let edge_mat = assets.occlusion_materials.add(ExtendedMaterial {
+            base: chunk_standard_material(),
+            extension: OcclusionExtension::edges_only(&map.edges),
         });
+        commands.insert_resource(OcclusionMaterialHandle(edge_mat.clone()));
+        ChunkMaterial::Occlusion(edge_mat)
+    } else {
+        // Standard PBR material with vertex colors, usually created during the intro
+        let standard_mat = match &preloaded {
+            Some(preloaded) => preloaded.chunk_material.clone(),
+            None => assets.materials.add(chunk_standard_material()),
+        };
         ChunkMaterial::Standard(standard_mat)
     };
 
+    // Materials for glowing (lava, glowstone) and glass voxel types present in the map
+    let mut type_materials = HashMap::new();
+    for voxel in &map.world.voxels {
+        let voxel_type = voxel.voxel_type;
+        if type_materials.contains_key(&voxel_type) {
+            continue;
+        }
+        if let Some(material) = voxel_type_material(voxel_type) {
+            type_materials.insert(voxel_type, assets.materials.add(material));
+        }
+    }
+
+    // Voxel groups start as the map says; chunks are rebuilt when they change
+    let voxel_groups = VoxelGroups::from_map(map);
+    commands.insert_resource(ChunkMaterials {
+        chunk_material: chunk_material.clone(),
+        type_materials: type_materials.clone(),
+        palette: accessibility.palette,
+    });
+
     // Stage 4: Spawn voxels using chunk-based meshing (60-90%)
     progress.update(LoadProgress::SpawningVoxels(0.0));
     commands = {
@@ -207,12 +283,15 @@ pub fn spawn_map_system(
             spatial_grid: &mut spatial_grid,
             meshes: assets.meshes.as_mut(),
             chunk_material,
+            type_materials,
             shadow_quality: occlusion_config.shadow_quality,
+            palette: accessibility.palette,
         };
         let _p_chunks = profiler.as_ref().map(|p| p.scope("spawn_voxels_chunked"));
-        spawn_voxels_chunked(&mut chunk_ctx, map, &mut progress);
+        spawn_voxels_chunked(&mut chunk_ctx, map, &voxel_groups, &mut progress);
         chunk_ctx.commands
     };
+    commands.insert_resource(voxel_groups);
     progress.update(LoadProgress::SpawningVoxels(1.0));
 
     // Stage 5: Spawn entities (90-95%)
@@ -232,12 +311,20 @@ pub fn spawn_map_system(
     // Stage 6: Setup lighting (95-97%)
     progress.update(LoadProgress::Finalizing(0.0));
     spawn_lighting(&mut commands, map, &occlusion_config);
+    commands.insert_resource(Weather::settled(map.weather.clone()));
+    commands.insert_resource(PlayerKeys::default());
+    commands.insert_resource(TeleportState::default());
+    commands.insert_resource(SpeedrunTimer::new(&map.metadata.name));
+    commands.insert_resource(Cinematic::default());
 
     // Stage 7: Setup camera (97-100%)
     progress.update(LoadProgress::Finalizing(0.5));
     spawn_camera(&mut commands, map);
 
     // Insert the spatial grid as a resource
+    if spatial_grid.is_empty() {
+        warn!("Map has no solid voxels; the player will fall through the world");
+    }
     commands.insert_resource(spatial_grid);
 
     // Complete
@@ -248,8 +335,9 @@ pub fn spawn_map_system(
 /// System that updates chunk LOD levels based on camera distance.
 ///
 /// Runs each frame but skips the O(N) chunk iteration when the camera has not moved
-/// more than [`LOD_MOVEMENT_THRESHOLD`] world units since the last pass AND no new
-/// chunks were just spawned. This keeps CPU cost O(1) when the camera is stationary.
+/// more than [`LOD_MOVEMENT_THRESHOLD`] world units since the last pass, no new
+/// chunks were just spawned and the [`LodConfig`] is unchanged. This keeps CPU cost
+/// O(1) when the camera is stationary.
 pub fn update_chunk_lods(
     camera_transform: Single<&Transform, With<Camera3d>>,
     mut chunks: Query<(&VoxelChunk, &mut ChunkLOD, &mut Mesh3d)>,
@@ -264,8 +352,9 @@ pub fn update_chunk_lods(
     let camera_moved = camera_pos.distance(*last_camera_pos) >= lod_config.movement_threshold;
     let new_chunks_present = !new_chunks.is_empty();
 
-    // Skip if the camera hasn't moved enough AND no new chunks just spawned.
-    if !camera_moved && !new_chunks_present {
+    // Skip if the camera hasn't moved enough, no new chunks just spawned and
+    // the LOD distances weren't retuned.
+    if !camera_moved && !new_chunks_present && !lod_config.is_changed() {
         return;
     }
     *last_camera_pos = camera_pos;
@@ -274,10 +363,7 @@ pub fn update_chunk_lods(
         let distance = camera_pos.distance(chunk.center);
 
         // Determine new LOD level based on distance thresholds
-        let new_lod = LOD_DISTANCES
-            .iter()
-            .position(|&threshold| distance < threshold)
-            .unwrap_or(LOD_LEVELS - 1);
+        let new_lod = lod_for_distance(distance, lod_config.distance_scale);
 
         // Only update if LOD changed
         if new_lod != lod.current_lod {
@@ -290,6 +376,7 @@ pub fn update_chunk_lods(
 /// Spawn entities from the map data.
 fn spawn_entities(ctx: &mut EntitySpawnContext, map: &MapData, progress: &mut MapLoadProgress) {
     let total_entities = map.entities.len();
+    let mut player_spawned = false;
 
     for (index, entity_data) in map.entities.iter().enumerate() {
         // Update progress
@@ -297,11 +384,16 @@ fn spawn_entities(ctx: &mut EntitySpawnContext, map: &MapData, progress: &mut Ma
         progress.update(LoadProgress::SpawningEntities(entity_progress));
 
         let (x, y, z) = entity_data.position;
+        let has_scripted_actions = entity_data.properties.contains_key(ACTIONS_KEY)
+            || entity_data.properties.contains_key(SCRIPT_KEY);
 
         match entity_data.entity_type {
-            EntityType::PlayerSpawn => {
+            // Further spawns are where split-screen players join
+            EntityType::PlayerSpawn if !player_spawned => {
                 spawn_player(ctx, Vec3::new(x, y, z));
+                player_spawned = true;
             }
+            EntityType::PlayerSpawn => {}
             EntityType::Npc => {
                 spawn_npc(ctx, Vec3::new(x, y, z), &entity_data.properties);
             }
@@ -310,53 +402,105 @@ fn spawn_entities(ctx: &mut EntitySpawnContext, map: &MapData, progress: &mut Ma
                 info!("Enemy spawn at ({}, {}, {}) - not yet implemented", x, y, z);
             }
             EntityType::Item => {
-                // TODO: Implement item spawning
-                info!("Item spawn at ({}, {}, {}) - not yet implemented", x, y, z);
+                if let Some(key) = entity_data.properties.get("key") {
+                    spawn_key_item(ctx, Vec3::new(x, y, z), key);
+                } else {
+                    // TODO: Implement other item kinds
+                    info!("Item spawn at ({}, {}, {}) - not yet implemented", x, y, z);
+                }
             }
             EntityType::Trigger => {
-                // TODO: Implement trigger spawning
-                info!(
-                    "Trigger spawn at ({}, {}, {}) - not yet implemented",
-                    x, y, z
-                );
+                if let Ok(Some(weather)) = WeatherData::from_properties(&entity_data.properties) {
+                    spawn_weather_zone(ctx, Vec3::new(x, y, z), weather, &entity_data.properties);
+                } else if !has_scripted_actions {
+                    // TODO: Implement other trigger kinds
+                    info!(
+                        "Trigger spawn at ({}, {}, {}) - not yet implemented",
+                        x, y, z
+                    );
+                }
             }
             EntityType::LightSource => {
                 spawn_light_source(ctx, Vec3::new(x, y, z), &entity_data.properties);
             }
+            EntityType::Door => {
+                spawn_door(ctx, Vec3::new(x, y, z), &entity_data.properties);
+            }
+            EntityType::Teleporter => {
+                spawn_teleporter(ctx, Vec3::new(x, y, z), &entity_data.properties);
+            }
+            EntityType::Goal => {
+                spawn_goal(ctx, Vec3::new(x, y, z), &entity_data.properties);
+            }
+        }
+
+        if has_scripted_actions {
+            spawn_scripted_actions(ctx, entity_data);
         }
     }
 }
 
-/// Spawn lighting from the map data.
+/// Shadow map resolution, cascade count and range of one [`ShadowQuality`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShadowPreset {
+    pub shadows_enabled: bool,
+    /// Width and height of each cascade's shadow map, a power of two
+    pub map_size: usize,
+    pub num_cascades: usize,
+    /// Far edge of the first cascade, in world units
+    pub first_cascade_far_bound: f32,
+    /// Distance past which nothing casts shadows, in world units
+    pub maximum_distance: f32,
+}
+
+/// The preset behind each shadow quality level.
+pub fn shadow_preset(quality: ShadowQuality) -> ShadowPreset {
+    let preset = |map_size, num_cascades, maximum_distance| ShadowPreset {
+        shadows_enabled: true,
+        map_size,
+        num_cascades,
+        first_cascade_far_bound: 4.0,
+        maximum_distance,
+    };
+    match quality {
+        ShadowQuality::None => ShadowPreset {
+            shadows_enabled: false,
+            ..preset(1024, 1, 0.0)
+        },
+        ShadowQuality::CharactersOnly | ShadowQuality::Low => preset(1024, 2, 20.0),
+        ShadowQuality::Medium => preset(2048, 3, 50.0),
+        ShadowQuality::High => preset(4096, 4, 100.0),
+    }
+}
+
 /// Returns `(shadows_enabled, CascadeShadowConfig)` for each shadow quality level.
 ///
 /// Used both at map-spawn time and by `apply_shadow_quality_system` for runtime changes.
 pub fn shadow_params_for_quality(quality: ShadowQuality) -> (bool, CascadeShadowConfig) {
-    match quality {
-        ShadowQuality::None => (false, CascadeShadowConfigBuilder::default().build()),
-        ShadowQuality::CharactersOnly | ShadowQuality::Low => (
-            true,
-            CascadeShadowConfigBuilder {
-                num_cascades: 2,
-                first_cascade_far_bound: 4.0,
-                maximum_distance: 20.0,
-                ..default()
-            }
-            .build(),
-        ),
-        ShadowQuality::High => (
-            true,
-            CascadeShadowConfigBuilder {
-                num_cascades: 4,
-                first_cascade_far_bound: 4.0,
-                maximum_distance: 100.0,
-                ..default()
-            }
-            .build(),
-        ),
+    shadow_params_with_distance(quality, 1.0)
+}
+
+/// [`shadow_params_for_quality`] with the cascade distances multiplied by
+/// `distance_scale` (see [`LodConfig::shadow_distance_scale`]).
+pub fn shadow_params_with_distance(
+    quality: ShadowQuality,
+    distance_scale: f32,
+) -> (bool, CascadeShadowConfig) {
+    let preset = shadow_preset(quality);
+    if !preset.shadows_enabled {
+        return (false, CascadeShadowConfigBuilder::default().build());
+    }
+    let config = CascadeShadowConfigBuilder {
+        num_cascades: preset.num_cascades,
+        first_cascade_far_bound: preset.first_cascade_far_bound * distance_scale,
+        maximum_distance: preset.maximum_distance * distance_scale,
+        ..default()
     }
+    .build();
+    (true, config)
 }
 
+/// Spawn lighting from the map data.
 fn spawn_lighting(commands: &mut Commands, map: &MapData, config: &OcclusionConfig) {
     let lighting = &map.lighting;
 
@@ -367,6 +511,9 @@ fn spawn_lighting(commands: &mut Commands, map: &MapData, config: &OcclusionConf
 
         let (shadows_enabled, cascade_shadow_config) =
             shadow_params_for_quality(config.shadow_quality);
+        commands.insert_resource(DirectionalLightShadowMap {
+            size: shadow_preset(config.shadow_quality).map_size,
+        });
 
         commands.spawn((
             DirectionalLight {
@@ -379,6 +526,7 @@ fn spawn_lighting(commands: &mut Commands, map: &MapData, config: &OcclusionConf
             },
             cascade_shadow_config,
             Transform::from_rotation(Quat::from_rotation_arc(Vec3::NEG_Z, direction)),
+            GameEntity,
         ));
     }
 
@@ -426,6 +574,7 @@ fn spawn_camera(commands: &mut Commands, map: &MapData) {
     // Resolve optional camera feel parameters; fall back to engine defaults when absent.
     let follow_speed = camera.follow_speed.unwrap_or(15.0);
     let rotation_speed = camera.rotation_speed.unwrap_or(5.0);
+    let interior_cutaway = camera.interior_cutaway.unwrap_or(true);
 
     let game_camera = GameCamera {
         original_rotation,
@@ -434,6 +583,7 @@ fn spawn_camera(commands: &mut Commands, map: &MapData) {
         follow_offset,
         follow_speed,
         target_position: look_at_point,
+        interior_cutaway,
     };
 
     if let Some(fov_deg) = camera.fov_degrees {
@@ -446,6 +596,8 @@ fn spawn_camera(commands: &mut Commands, map: &MapData) {
             camera_transform,
             game_camera,
             DepthPrepass,
+            WeatherCamera,
+            GameEntity,
         ));
     } else {
         commands.spawn((
@@ -453,6 +605,8 @@ fn spawn_camera(commands: &mut Commands, map: &MapData) {
             camera_transform,
             game_camera,
             DepthPrepass,
+            WeatherCamera,
+            GameEntity,
         ));
     }
 }
@@ -1,44 +1,64 @@
 //! Runtime shadow quality application system.
 //!
 //! Watches `OcclusionConfig` for changes and applies the `ShadowQuality` setting to
-//! the live scene — updating the `DirectionalLight`, `CascadeShadowConfig`, and
-//! `NotShadowCaster` components on all `VoxelChunk` entities.
+//! the live scene — updating the `DirectionalLight`, `CascadeShadowConfig`, the
+//! `DirectionalLightShadowMap` resolution, and `NotShadowCaster` components on all
+//! `VoxelChunk` entities.
 //!
 //! Also fires on `Added<VoxelChunk>` so hot-reload re-spawns always get the correct
 //! shadow state without a settings-menu round-trip.
+//!
+//! The shadow distance follows `LodConfig::shadow_distance_scale`, which dynamic
+//! quality tunes at runtime; newly spawned lights pick it up too.
 
-use super::{shadow_params_for_quality, VoxelChunk};
+use super::{shadow_params_with_distance, shadow_preset, LodConfig, VoxelChunk};
 use crate::systems::game::occlusion::{OcclusionConfig, ShadowQuality};
-use bevy::light::{CascadeShadowConfig, NotShadowCaster};
+use bevy::light::{CascadeShadowConfig, DirectionalLightShadowMap, NotShadowCaster};
 use bevy::prelude::*;
 
 /// Applies `OcclusionConfig::shadow_quality` to the live scene every time the
 /// setting changes or new `VoxelChunk` entities appear.
 ///
 /// Registered in `GameSystemSet::Visual` — runs after movement/physics, before camera.
+#[allow(clippy::too_many_arguments)]
 pub fn apply_shadow_quality_system(
     config: Res<OcclusionConfig>,
+    lod_config: Res<LodConfig>,
     new_chunks: Query<Entity, Added<VoxelChunk>>,
     all_chunks: Query<Entity, With<VoxelChunk>>,
+    new_lights: Query<(), Added<DirectionalLight>>,
     mut dir_lights: Query<(&mut DirectionalLight, &mut CascadeShadowConfig)>,
+    mut shadow_map: ResMut<DirectionalLightShadowMap>,
     mut commands: Commands,
 ) {
     let setting_changed = config.is_changed();
     let has_new_chunks = !new_chunks.is_empty();
+    let distance_changed = lod_config.is_changed() || !new_lights.is_empty();
 
-    if !setting_changed && !has_new_chunks {
+    if !setting_changed && !has_new_chunks && !distance_changed {
         return;
     }
 
-    // Update DirectionalLight when the setting changed (light survives hot-reload).
-    if setting_changed {
-        let (shadows_on, cascade_cfg) = shadow_params_for_quality(config.shadow_quality);
+    // Update DirectionalLight when the setting or shadow distance changed
+    // (light survives hot-reload).
+    if setting_changed || distance_changed {
+        let (shadows_on, cascade_cfg) =
+            shadow_params_with_distance(config.shadow_quality, lod_config.shadow_distance_scale);
         for (mut light, mut cascade) in dir_lights.iter_mut() {
             light.shadows_enabled = shadows_on;
             *cascade = cascade_cfg.clone();
         }
     }
 
+    if !setting_changed && !has_new_chunks {
+        return;
+    }
+
+    let map_size = shadow_preset(config.shadow_quality).map_size;
+    if shadow_map.size != map_size {
+        shadow_map.size = map_size;
+    }
+
     // Determine which chunks to update: all on setting change, only new on hot-reload.
     let chunks_to_update: Vec<Entity> = if setting_changed {
         all_chunks.iter().collect()
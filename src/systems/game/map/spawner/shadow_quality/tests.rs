@@ -1,4 +1,4 @@
-use super::super::shadow_params_for_quality;
+use super::super::{shadow_params_for_quality, shadow_params_with_distance, shadow_preset};
 use crate::systems::game::occlusion::ShadowQuality;
 
 #[test]
@@ -30,3 +30,36 @@ fn characters_only_enables_shadows() {
 fn shadow_quality_default_is_low() {
     assert_eq!(ShadowQuality::default(), ShadowQuality::Low);
 }
+
+#[test]
+fn shadow_distance_scales_the_cascades() {
+    let (_, full) = shadow_params_for_quality(ShadowQuality::High);
+    let (_, half) = shadow_params_with_distance(ShadowQuality::High, 0.5);
+    let far = |config: &bevy::light::CascadeShadowConfig| *config.bounds.last().unwrap();
+    assert!((far(&half) - far(&full) * 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn medium_sits_between_low_and_high() {
+    let low = shadow_preset(ShadowQuality::Low);
+    let medium = shadow_preset(ShadowQuality::Medium);
+    let high = shadow_preset(ShadowQuality::High);
+    assert!(medium.shadows_enabled);
+    assert!(low.map_size < medium.map_size && medium.map_size < high.map_size);
+    assert!(low.num_cascades < medium.num_cascades && medium.num_cascades < high.num_cascades);
+    assert!(low.maximum_distance < medium.maximum_distance);
+    assert!(medium.maximum_distance < high.maximum_distance);
+}
+
+#[test]
+fn shadow_map_sizes_are_powers_of_two() {
+    for quality in [
+        ShadowQuality::None,
+        ShadowQuality::CharactersOnly,
+        ShadowQuality::Low,
+        ShadowQuality::Medium,
+        ShadowQuality::High,
+    ] {
+        assert!(shadow_preset(quality).map_size.is_power_of_two());
+    }
+}
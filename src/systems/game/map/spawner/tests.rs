@@ -133,6 +133,7 @@ fn test_opposite_faces_have_opposite_normals() {
 // --- LOD movement threshold tests ---
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn lod_threshold_constant_is_well_below_lod_distances() {
     // Threshold must be much smaller than the smallest LOD transition distance
     // so it never causes a false skip near a LOD boundary.
@@ -154,6 +155,7 @@ fn lod_threshold_guard_runs_when_camera_moves_beyond_threshold() {
 }
 
 #[test]
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
 fn lod_threshold_exact_boundary_skips() {
     // At exactly LOD_MOVEMENT_THRESHOLD distance the strict < guard should skip.
     let last_pos = Vec3::ZERO;
@@ -176,4 +178,16 @@ fn lod_threshold_cold_start_passes_at_non_origin_position() {
 fn lod_config_default_matches_constant() {
     let config = LodConfig::default();
     assert_eq!(config.movement_threshold, LOD_MOVEMENT_THRESHOLD);
+    assert_eq!(config.distance_scale, 1.0);
+    assert_eq!(config.shadow_distance_scale, 1.0);
+}
+
+#[test]
+fn lod_distances_scale_with_the_config() {
+    assert_eq!(lod_for_distance(40.0, 1.0), 0);
+    assert_eq!(lod_for_distance(60.0, 1.0), 1);
+    // Halved thresholds drop detail at half the distance
+    assert_eq!(lod_for_distance(40.0, 0.5), 1);
+    assert_eq!(lod_for_distance(60.0, 1.5), 0);
+    assert_eq!(lod_for_distance(f32::MAX, 1.0), LOD_LEVELS - 1);
 }
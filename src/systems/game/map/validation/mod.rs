@@ -1,8 +1,16 @@
 //! Map validation logic.
 
 use super::error::{MapLoadError, MapResult};
-use super::format::{is_valid_rotation_matrix, MapData};
+use super::format::{
+    is_valid_rotation_matrix, EntityType, MapData, WeatherData, EDITOR_GUIDES_KEY,
+    EDITOR_LOCKS_KEY, FIXED_BOUNDS_KEY,
+};
+use crate::systems::game::actions::{parse_actions, MapAction, ACTIONS_KEY};
+use crate::systems::game::doors::Door;
+use crate::systems::game::speedrun::Goal;
+use crate::systems::game::teleporters::Teleporter;
 use bevy::log::warn;
+use std::collections::HashSet;
 
 /// Prefix reserved for engine-owned keys in `MapData::custom_properties`
 /// and `EntityData::properties`.
@@ -18,9 +26,7 @@ const ENGINE_KEY_PREFIX: &str = "adrakestory:";
 /// Add entries here before introducing a new engine feature that writes
 /// to this map. The validator will warn on unknown `adrakestory:` keys
 /// to catch typos and forward-compat mismatches early.
-const KNOWN_MAP_ENGINE_KEYS: &[&str] = &[
-    // (none yet — convention established for future use)
-];
+const KNOWN_MAP_ENGINE_KEYS: &[&str] = &[FIXED_BOUNDS_KEY, EDITOR_GUIDES_KEY, EDITOR_LOCKS_KEY];
 
 /// Engine-owned keys permitted in `EntityData::properties`.
 ///
@@ -49,6 +55,22 @@ pub fn validate_map(map: &MapData) -> MapResult<()> {
     // Validate lighting
     validate_lighting(map)?;
 
+    // Validate weather
+    validate_weather(map)?;
+
+    // Validate edge rendering
+    validate_edges(map)?;
+    validate_meshing(map)?;
+
+    // Validate voxel group names and the actions that use them
+    validate_voxel_groups(map)?;
+
+    // Validate that teleporters lead to other teleporters
+    validate_teleporters(map)?;
+
+    // Validate camera sequences and the actions that play them
+    validate_camera_sequences(map)?;
+
     // Warn on unknown adrakestory:-prefixed keys (soft check, never fails)
     validate_custom_property_namespaces(map);
 
@@ -189,7 +211,22 @@ fn validate_entities(map: &MapData) -> MapResult<()> {
 /// forward-compatibility. The spawner's fallback logic is not changed by this
 /// function; this validator and the spawner are independent layers.
 fn validate_entity_properties(entity: &super::format::EntityData) -> MapResult<()> {
-    use super::format::EntityType;
+    if let Some(script) = entity.properties.get(ACTIONS_KEY) {
+        if let Err(reason) = parse_actions(script) {
+            return Err(MapLoadError::ValidationError(format!(
+                "{:?} entity has invalid 'actions': {}",
+                entity.entity_type, reason
+            )));
+        }
+    }
+    if let Some(v) = entity.properties.get("repeat") {
+        if !matches!(v.as_str(), "true" | "false" | "1" | "0") {
+            return Err(MapLoadError::ValidationError(format!(
+                "{:?} entity has invalid 'repeat': expected true/false/1/0, got {:?}",
+                entity.entity_type, v
+            )));
+        }
+    }
 
     match entity.entity_type {
         EntityType::LightSource => {
@@ -284,12 +321,202 @@ fn validate_entity_properties(entity: &super::format::EntityData) -> MapResult<(
                 }
             }
         }
+        EntityType::Trigger => {
+            if let Err(reason) = WeatherData::from_properties(&entity.properties) {
+                return Err(MapLoadError::ValidationError(format!(
+                    "Trigger entity has invalid weather: {}",
+                    reason
+                )));
+            }
+            if let Some(v) = entity.properties.get("radius") {
+                match v.parse::<f32>() {
+                    Ok(f) if f > 0.0 => {}
+                    _ => {
+                        return Err(MapLoadError::ValidationError(format!(
+                            "Trigger entity has invalid 'radius': \
+                             expected positive f32, got {:?}",
+                            v
+                        )))
+                    }
+                }
+            }
+        }
+        EntityType::Door => {
+            if let Err(reason) = Door::from_properties(&entity.properties) {
+                return Err(MapLoadError::ValidationError(format!(
+                    "Door entity is invalid: {}",
+                    reason
+                )));
+            }
+        }
+        EntityType::Teleporter => {
+            if let Err(reason) = Teleporter::from_properties(&entity.properties) {
+                return Err(MapLoadError::ValidationError(format!(
+                    "Teleporter entity is invalid: {}",
+                    reason
+                )));
+            }
+        }
+        EntityType::Goal => {
+            if let Err(reason) = Goal::from_properties(&entity.properties) {
+                return Err(MapLoadError::ValidationError(format!(
+                    "Goal entity is invalid: {}",
+                    reason
+                )));
+            }
+        }
+        EntityType::Item
+            if entity
+                .properties
+                .get("key")
+                .is_some_and(|key| key.trim().is_empty()) =>
+        {
+            return Err(MapLoadError::ValidationError(
+                "Item entity has an empty 'key'".to_string(),
+            ));
+        }
         // Other entity types: no property validation (forward-compatible).
         _ => {}
     }
     Ok(())
 }
 
+/// Validates voxel group names, their starting states, and that scripted
+/// actions and doors only change groups that exist.
+fn validate_voxel_groups(map: &MapData) -> MapResult<()> {
+    let mut groups = HashSet::new();
+    for voxel in &map.world.voxels {
+        let Some(name) = &voxel.group else {
+            continue;
+        };
+        if name.trim().is_empty() || name.trim() != name || name.contains([';', '\n']) {
+            return Err(MapLoadError::ValidationError(format!(
+                "Voxel at {:?} has invalid group name {:?}: \
+                 expected a non-empty name without ';' or surrounding spaces",
+                voxel.pos, name
+            )));
+        }
+        groups.insert(name.as_str());
+    }
+
+    let mut configured = HashSet::new();
+    for group in &map.voxel_groups {
+        if !groups.contains(group.name.as_str()) {
+            // Left behind when the group's voxels are deleted; harmless
+            warn!("Voxel group {:?} has no voxels", group.name);
+        }
+        if !configured.insert(group.name.as_str()) {
+            return Err(MapLoadError::ValidationError(format!(
+                "Voxel group {:?} is listed more than once",
+                group.name
+            )));
+        }
+    }
+
+    for entity in &map.entities {
+        if entity.entity_type == EntityType::Door {
+            if let Ok(door) = Door::from_properties(&entity.properties) {
+                for name in std::iter::once(&door.group).chain(&door.open_group) {
+                    if !groups.contains(name.as_str()) {
+                        return Err(MapLoadError::ValidationError(format!(
+                            "Door entity uses unknown voxel group {:?}",
+                            name
+                        )));
+                    }
+                }
+            }
+        }
+        let Some(Ok(actions)) = entity.properties.get(ACTIONS_KEY).map(|s| parse_actions(s)) else {
+            continue;
+        };
+        for action in actions {
+            if let MapAction::Group { name, .. } = action {
+                if !groups.contains(name.as_str()) {
+                    return Err(MapLoadError::ValidationError(format!(
+                        "{:?} entity's actions change unknown voxel group {:?}",
+                        entity.entity_type, name
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validates that every teleporter's `target` names another teleporter.
+fn validate_teleporters(map: &MapData) -> MapResult<()> {
+    let teleporters: Vec<Teleporter> = map
+        .entities
+        .iter()
+        .filter(|entity| entity.entity_type == EntityType::Teleporter)
+        .filter_map(|entity| Teleporter::from_properties(&entity.properties).ok())
+        .collect();
+    for teleporter in &teleporters {
+        if teleporter.name.as_deref() == Some(teleporter.target.as_str()) {
+            return Err(MapLoadError::ValidationError(format!(
+                "Teleporter {:?} targets itself",
+                teleporter.target
+            )));
+        }
+        let found = teleporters
+            .iter()
+            .any(|other| other.name.as_deref() == Some(teleporter.target.as_str()));
+        if !found {
+            return Err(MapLoadError::ValidationError(format!(
+                "Teleporter targets unknown teleporter {:?}",
+                teleporter.target
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validates camera sequence names and keyframes, and that `cinematic`
+/// actions play sequences that exist.
+fn validate_camera_sequences(map: &MapData) -> MapResult<()> {
+    let mut names = HashSet::new();
+    for sequence in &map.camera_sequences {
+        if sequence.name.trim().is_empty() || !names.insert(sequence.name.as_str()) {
+            return Err(MapLoadError::ValidationError(format!(
+                "Camera sequence name {:?} is empty or used more than once",
+                sequence.name
+            )));
+        }
+        if sequence.keyframes.is_empty() {
+            return Err(MapLoadError::ValidationError(format!(
+                "Camera sequence {:?} has no keyframes",
+                sequence.name
+            )));
+        }
+        for keyframe in &sequence.keyframes {
+            if !keyframe.duration.is_finite() || keyframe.duration < 0.0 {
+                return Err(MapLoadError::ValidationError(format!(
+                    "Camera sequence {:?} has invalid keyframe duration {}: \
+                     expected a non-negative number of seconds",
+                    sequence.name, keyframe.duration
+                )));
+            }
+        }
+    }
+
+    for entity in &map.entities {
+        let Some(Ok(actions)) = entity.properties.get(ACTIONS_KEY).map(|s| parse_actions(s)) else {
+            continue;
+        };
+        for action in actions {
+            if let MapAction::Cinematic(name) = action {
+                if !names.contains(name.as_str()) {
+                    return Err(MapLoadError::ValidationError(format!(
+                        "{:?} entity's actions play unknown camera sequence {:?}",
+                        entity.entity_type, name
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Validates lighting data.
 fn validate_lighting(map: &MapData) -> MapResult<()> {
     let lighting = &map.lighting;
@@ -325,6 +552,68 @@ fn validate_lighting(map: &MapData) -> MapResult<()> {
     Ok(())
 }
 
+/// Validates weather data.
+fn validate_weather(map: &MapData) -> MapResult<()> {
+    let weather = &map.weather;
+
+    for (name, value) in [
+        ("intensity", weather.intensity),
+        ("fog density", weather.fog_density),
+        ("wetness", weather.wetness),
+    ] {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(MapLoadError::ValidationError(format!(
+                "Weather {} must be between 0.0 and 1.0, got {}",
+                name, value
+            )));
+        }
+    }
+
+    let (r, g, b) = weather.fog_color;
+    if !(0.0..=1.0).contains(&r) || !(0.0..=1.0).contains(&g) || !(0.0..=1.0).contains(&b) {
+        return Err(MapLoadError::ValidationError(format!(
+            "Fog color components must be between 0.0 and 1.0, got ({}, {}, {})",
+            r, g, b
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validates edge rendering data.
+fn validate_edges(map: &MapData) -> MapResult<()> {
+    let edges = &map.edges;
+
+    if !(0.0..=1.0).contains(&edges.strength) {
+        return Err(MapLoadError::ValidationError(format!(
+            "Edge strength must be between 0.0 and 1.0, got {}",
+            edges.strength
+        )));
+    }
+    if !(0.0..=0.5).contains(&edges.width) {
+        return Err(MapLoadError::ValidationError(format!(
+            "Edge width must be between 0.0 and 0.5, got {}",
+            edges.width
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validates chunk meshing data.
+fn validate_meshing(map: &MapData) -> MapResult<()> {
+    let meshing = &map.meshing;
+
+    if !(0.0..=1.0).contains(&meshing.color_tolerance) {
+        return Err(MapLoadError::ValidationError(format!(
+            "Meshing color tolerance must be between 0.0 and 1.0, got {}",
+            meshing.color_tolerance
+        )));
+    }
+
+    Ok(())
+}
+
 /// Warns on `adrakestory:`-prefixed keys that are not in the known engine key
 /// lists.
 ///
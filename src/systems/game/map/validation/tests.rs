@@ -23,6 +23,7 @@ fn test_invalid_voxel_position() {
         pattern: None,
         rotation: None,
         rotation_state: None,
+        group: None,
     });
     assert!(validate_map(&map).is_err());
 }
@@ -47,6 +48,7 @@ fn test_duplicate_voxel_position() {
 
 fn make_light_source(props: Vec<(&str, &str)>) -> EntityData {
     EntityData {
+        id: Default::default(),
         entity_type: EntityType::LightSource,
         position: (1.5, 0.5, 1.5),
         properties: props
@@ -114,6 +116,7 @@ fn light_source_incomplete_color_is_rejected() {
 fn npc_invalid_radius_is_rejected() {
     let mut map = MapData::default_map();
     map.entities.push(EntityData {
+        id: Default::default(),
         entity_type: EntityType::Npc,
         position: (1.0, 0.5, 1.0),
         properties: [("radius".to_string(), "big".to_string())].into(),
@@ -125,6 +128,7 @@ fn npc_invalid_radius_is_rejected() {
 fn npc_zero_radius_is_rejected() {
     let mut map = MapData::default_map();
     map.entities.push(EntityData {
+        id: Default::default(),
         entity_type: EntityType::Npc,
         position: (1.0, 0.5, 1.0),
         properties: [("radius".to_string(), "0.0".to_string())].into(),
@@ -311,3 +315,246 @@ fn light_source_all_flicker_properties_pass() {
     ]));
     assert!(validate_map(&map).is_ok());
 }
+
+fn make_trigger(props: Vec<(&str, &str)>) -> EntityData {
+    EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Trigger,
+        position: (1.0, 0.5, 1.0),
+        properties: props
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    }
+}
+
+#[test]
+fn weather_trigger_valid_properties_pass() {
+    let mut map = MapData::default_map();
+    map.entities.push(make_trigger(vec![
+        ("weather", "rain"),
+        ("intensity", "0.5"),
+        ("fog_density", "0.3"),
+        ("radius", "4"),
+    ]));
+    assert!(validate_map(&map).is_ok());
+}
+
+#[test]
+fn weather_trigger_unknown_kind_is_rejected() {
+    let mut map = MapData::default_map();
+    map.entities.push(make_trigger(vec![("weather", "hail")]));
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn weather_trigger_out_of_range_value_is_rejected() {
+    let mut map = MapData::default_map();
+    map.entities
+        .push(make_trigger(vec![("weather", "snow"), ("wetness", "2")]));
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn weather_out_of_range_fog_is_rejected() {
+    let mut map = MapData::default_map();
+    map.weather.fog_density = 1.5;
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn edges_out_of_range_are_rejected() {
+    let mut map = MapData::default_map();
+    map.edges.style = EdgeStyle::Lines;
+    assert!(validate_map(&map).is_ok());
+    map.edges.width = 0.75;
+    assert!(validate_map(&map).is_err());
+    map.edges.width = 0.1;
+    map.edges.strength = -0.5;
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn meshing_tolerance_out_of_range_is_rejected() {
+    let mut map = MapData::default_map();
+    map.meshing.color_tolerance = 0.2;
+    assert!(validate_map(&map).is_ok());
+    map.meshing.color_tolerance = 1.5;
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn valid_actions_pass() {
+    let mut map = MapData::default_map();
+    map.world.voxels[0].group = Some("bridge".to_string());
+    map.entities.push(make_trigger(vec![
+        ("actions", "say Hello; wait 1; hide bridge"),
+        ("repeat", "true"),
+    ]));
+    assert!(validate_map(&map).is_ok());
+}
+
+#[test]
+fn invalid_actions_are_rejected() {
+    let mut map = MapData::default_map();
+    map.entities
+        .push(make_trigger(vec![("actions", "say Hello; fly away")]));
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn actions_on_unknown_voxel_groups_are_rejected() {
+    let mut map = MapData::default_map();
+    map.entities
+        .push(make_trigger(vec![("actions", "toggle secret_door")]));
+    assert!(validate_map(&map).is_err());
+
+    map.world.voxels[0].group = Some("secret_door".to_string());
+    assert!(validate_map(&map).is_ok());
+}
+
+#[test]
+fn voxel_group_names_and_duplicate_states_are_rejected() {
+    let mut map = MapData::default_map();
+    map.world.voxels[0].group = Some("bridge; hide all".to_string());
+    assert!(validate_map(&map).is_err());
+
+    map.world.voxels[0].group = Some("bridge".to_string());
+    let starts_hidden = VoxelGroupData {
+        name: "bridge".to_string(),
+        visible: false,
+        solid: false,
+    };
+    map.voxel_groups = vec![starts_hidden.clone()];
+    assert!(validate_map(&map).is_ok());
+
+    map.voxel_groups.push(starts_hidden);
+    assert!(validate_map(&map).is_err(), "listed twice");
+
+    map.voxel_groups = vec![VoxelGroupData {
+        name: "drawbridge".to_string(),
+        visible: false,
+        solid: true,
+    }];
+    assert!(validate_map(&map).is_ok(), "unused entries only warn");
+}
+
+#[test]
+fn doors_need_an_existing_voxel_group() {
+    let mut map = MapData::default_map();
+    let door = |props: Vec<(&str, &str)>| EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Door,
+        ..make_trigger(props)
+    };
+    map.entities.push(door(vec![("key", "brass key")]));
+    assert!(validate_map(&map).is_err(), "no group");
+
+    map.entities[1] = door(vec![("group", "gate"), ("open_group", "gate_open")]);
+    map.world.voxels[0].group = Some("gate".to_string());
+    assert!(validate_map(&map).is_err(), "unknown open group");
+
+    map.world.voxels[1].group = Some("gate_open".to_string());
+    assert!(validate_map(&map).is_ok());
+
+    map.entities[1] = door(vec![("group", "gate"), ("open", "ajar")]);
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn item_keys_must_not_be_empty() {
+    let mut map = MapData::default_map();
+    map.entities.push(EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Item,
+        ..make_trigger(vec![("key", " ")])
+    });
+    assert!(validate_map(&map).is_err());
+
+    map.entities[1]
+        .properties
+        .insert("key".to_string(), "brass key".to_string());
+    assert!(validate_map(&map).is_ok());
+}
+
+#[test]
+fn teleporters_must_target_another_teleporter() {
+    let mut map = MapData::default_map();
+    let teleporter = |props: Vec<(&str, &str)>| EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Teleporter,
+        ..make_trigger(props)
+    };
+    map.entities
+        .push(teleporter(vec![("name", "cave"), ("target", "summit")]));
+    assert!(validate_map(&map).is_err(), "unknown target");
+
+    map.entities
+        .push(teleporter(vec![("name", "summit"), ("target", "cave")]));
+    assert!(validate_map(&map).is_ok());
+
+    map.entities[2] = teleporter(vec![("name", "summit"), ("target", "summit")]);
+    assert!(validate_map(&map).is_err(), "targets itself");
+
+    map.entities[2] = teleporter(vec![("name", "summit"), ("radius", "-1")]);
+    assert!(validate_map(&map).is_err());
+}
+
+#[test]
+fn camera_sequences_need_keyframes_and_unique_names() {
+    let mut map = MapData::default_map();
+    let keyframe = CameraKeyframeData {
+        position: (0.0, 5.0, 0.0),
+        look_at: (1.0, 0.0, 1.0),
+        duration: 2.0,
+        easing: CameraEasing::EaseInOut,
+    };
+    let intro = CameraSequenceData {
+        name: "intro".to_string(),
+        keyframes: vec![keyframe.clone()],
+    };
+    map.camera_sequences = vec![intro.clone()];
+    map.entities
+        .push(make_trigger(vec![("actions", "cinematic intro")]));
+    assert!(validate_map(&map).is_ok());
+
+    map.entities[1] = make_trigger(vec![("actions", "cinematic outro")]);
+    assert!(validate_map(&map).is_err(), "unknown sequence");
+    map.entities.pop();
+
+    map.camera_sequences.push(intro.clone());
+    assert!(validate_map(&map).is_err(), "listed twice");
+
+    map.camera_sequences = vec![CameraSequenceData {
+        keyframes: vec![],
+        ..intro.clone()
+    }];
+    assert!(validate_map(&map).is_err(), "no keyframes");
+
+    map.camera_sequences = vec![CameraSequenceData {
+        keyframes: vec![CameraKeyframeData {
+            duration: -1.0,
+            ..keyframe
+        }],
+        ..intro
+    }];
+    assert!(validate_map(&map).is_err(), "negative duration");
+}
+
+#[test]
+fn goal_radius_must_be_positive() {
+    let mut map = MapData::default_map();
+    let goal = |props: Vec<(&str, &str)>| EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Goal,
+        ..make_trigger(props)
+    };
+    map.entities.push(goal(vec![]));
+    assert!(validate_map(&map).is_ok());
+
+    map.entities[1] = goal(vec![("radius", "2.5")]);
+    assert!(validate_map(&map).is_ok());
+
+    map.entities[1] = goal(vec![("radius", "0")]);
+    assert!(validate_map(&map).is_err());
+}
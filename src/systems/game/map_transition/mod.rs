@@ -0,0 +1,278 @@
+//! Moving between maps without leaving the game.
+//!
+//! A [`LoadMapRequest`] fades the screen to black while the target map loads
+//! on the async compute pool. Once the screen is black and the map is ready,
+//! the current map's [`GameEntity`] entities are despawned and the new map is
+//! handed to the spawner, which spawns it on the next frame; the screen then
+//! fades back in. The game stays in `InGame` throughout, so there is no trip
+//! through the title or loading screens. A map that fails to load leaves the
+//! current one in place.
+//!
+//! The `load_map` [map action](super::actions) sends these requests, which
+//! makes doors and portals between maps a matter of a trigger's actions.
+
+use bevy::prelude::*;
+use bevy::tasks::{futures::check_ready, AsyncComputeTaskPool, Task};
+use std::path::PathBuf;
+
+use super::actions::cleanup_map_actions;
+use super::captions::clear_captions;
+use super::cleanup::despawn_game_world;
+use super::components::GameEntity;
+use super::hot_reload::{HotReloadState, MapPathForHotReload};
+use super::map::error::MapResult;
+use super::map::format::{EntityData, EntityType, MapData};
+use super::map::{LoadedMapData, MapLoadProgress, MapLoader};
+use super::speedrun::NextMapRequest;
+use crate::states::GameState;
+
+/// Duration of the fade out, and of the fade back in.
+pub const TRANSITION_FADE_SECONDS: f32 = 0.4;
+
+/// Ask to move the player to another map.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct LoadMapRequest {
+    pub path: PathBuf,
+    /// Where the player appears; the map's own player spawn when `None`
+    pub spawn_point: Option<Vec3>,
+}
+
+/// Progress of a map transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionPhase {
+    #[default]
+    Idle,
+    /// Fading to black while the map loads
+    FadingOut,
+    /// Black, waiting for the map to finish loading
+    Loading,
+    FadingIn,
+}
+
+type MapLoadTask = Task<(PathBuf, MapResult<MapData>)>;
+
+/// Map transition in progress, if any.
+#[derive(Resource, Default)]
+pub struct MapTransition {
+    pub phase: TransitionPhase,
+    /// Seconds spent in the current fade
+    elapsed: f32,
+    task: Option<MapLoadTask>,
+    spawn_point: Option<Vec3>,
+}
+
+impl MapTransition {
+    pub fn is_busy(&self) -> bool {
+        self.phase != TransitionPhase::Idle
+    }
+
+    /// Begin fading out towards the map `task` is loading.
+    fn start(&mut self, task: Option<MapLoadTask>, spawn_point: Option<Vec3>) {
+        self.phase = TransitionPhase::FadingOut;
+        self.elapsed = 0.0;
+        self.task = task;
+        self.spawn_point = spawn_point;
+    }
+
+    /// Advance the fades by `delta` seconds: fading out ends in `Loading`,
+    /// fading in ends in `Idle`.
+    pub fn advance_fade(&mut self, delta: f32) {
+        if !matches!(
+            self.phase,
+            TransitionPhase::FadingOut | TransitionPhase::FadingIn
+        ) {
+            return;
+        }
+        self.elapsed += delta;
+        if self.elapsed < TRANSITION_FADE_SECONDS {
+            return;
+        }
+        self.elapsed = 0.0;
+        self.phase = match self.phase {
+            TransitionPhase::FadingOut => TransitionPhase::Loading,
+            _ => TransitionPhase::Idle,
+        };
+    }
+
+    /// Start fading back in, with the new map or without one.
+    fn finish(&mut self) {
+        self.phase = TransitionPhase::FadingIn;
+        self.elapsed = 0.0;
+        self.task = None;
+        self.spawn_point = None;
+    }
+
+    /// Opacity of the black fade overlay.
+    pub fn fade_alpha(&self) -> f32 {
+        let progress = (self.elapsed / TRANSITION_FADE_SECONDS).clamp(0.0, 1.0);
+        match self.phase {
+            TransitionPhase::Idle => 0.0,
+            TransitionPhase::FadingOut => progress,
+            TransitionPhase::Loading => 1.0,
+            TransitionPhase::FadingIn => 1.0 - progress,
+        }
+    }
+}
+
+/// Move `map`'s player spawn to `point`, adding one if it has none. The
+/// map's camera moves with it, so the player starts in the same view.
+pub fn place_player_spawn(map: &mut MapData, point: Vec3) {
+    let spawn = map
+        .entities
+        .iter_mut()
+        .find(|entity| entity.entity_type == EntityType::PlayerSpawn);
+    let old = match spawn {
+        Some(spawn) => {
+            let (x, y, z) = spawn.position;
+            spawn.position = (point.x, point.y, point.z);
+            Vec3::new(x, y, z)
+        }
+        None => {
+            map.entities.insert(
+                0,
+                EntityData {
+                    id: map.next_entity_id(),
+                    entity_type: EntityType::PlayerSpawn,
+                    position: (point.x, point.y, point.z),
+                    properties: Default::default(),
+                },
+            );
+            return;
+        }
+    };
+    let offset = point - old;
+    let shift = |(x, y, z): (f32, f32, f32)| (x + offset.x, y + offset.y, z + offset.z);
+    map.camera.position = shift(map.camera.position);
+    map.camera.look_at = shift(map.camera.look_at);
+}
+
+/// Full-screen overlay the screen fades to between maps.
+#[derive(Component)]
+pub struct MapTransitionOverlay;
+
+fn setup_transition_fade(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        GlobalZIndex(70),
+        MapTransitionOverlay,
+    ));
+}
+
+/// Start loading the requested map and fading out. Requests made during a
+/// transition are dropped.
+pub fn start_map_transition(
+    mut requests: MessageReader<LoadMapRequest>,
+    mut transition: ResMut<MapTransition>,
+) {
+    for request in requests.read() {
+        if transition.is_busy() {
+            warn!(
+                "Ignoring map change to {:?}: a transition is under way",
+                request.path
+            );
+            continue;
+        }
+        info!("Changing map to {:?}", request.path);
+        let path = request.path.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let result = MapLoader::load_from_file(&path, &mut MapLoadProgress::default());
+            (path, result)
+        });
+        transition.start(Some(task), request.spawn_point);
+    }
+}
+
+/// Fade out, swap in the new map once it has loaded, and fade back in.
+#[allow(clippy::too_many_arguments)]
+pub fn run_map_transition(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut transition: ResMut<MapTransition>,
+    entities: Query<Entity, With<GameEntity>>,
+    mut map_path: ResMut<MapPathForHotReload>,
+    mut next_map: ResMut<NextMapRequest>,
+    mut hot_reload: ResMut<HotReloadState>,
+    overlay: Option<Single<&mut BackgroundColor, With<MapTransitionOverlay>>>,
+) {
+    transition.advance_fade(time.delta_secs());
+
+    if transition.phase == TransitionPhase::Loading {
+        let loaded = match transition.task.as_mut() {
+            Some(task) => check_ready(task),
+            None => None,
+        };
+        if let Some((path, result)) = loaded {
+            match result {
+                Ok(mut map) => {
+                    info!("Loaded map '{}', swapping it in", map.metadata.name);
+                    if let Some(point) = transition.spawn_point {
+                        place_player_spawn(&mut map, point);
+                    }
+                    despawn_game_world(&mut commands, &entities);
+                    commands.run_system_cached(cleanup_map_actions);
+                    commands.run_system_cached(clear_captions);
+                    // spawn_map_system spawns it next frame
+                    commands.insert_resource(LoadedMapData { map });
+                    if hot_reload.enabled {
+                        if let Err(e) = hot_reload.watch_file(path.clone()) {
+                            warn!("Failed to watch {:?} for hot reload: {}", path, e);
+                        }
+                    }
+                    // A later Retry reloads this map rather than the first one
+                    next_map.0 = Some(path.clone());
+                    map_path.0 = Some(path);
+                }
+                Err(e) => warn!("Failed to load map {:?}: {}; staying on this map", path, e),
+            }
+            transition.finish();
+        } else if transition.task.is_none() {
+            transition.finish();
+        }
+    }
+
+    if let Some(mut overlay) = overlay {
+        let color = Color::BLACK.with_alpha(transition.fade_alpha());
+        if overlay.0 != color {
+            overlay.0 = color;
+        }
+    }
+}
+
+/// Drop any transition when the game is left mid-way.
+pub fn cancel_map_transition(
+    mut transition: ResMut<MapTransition>,
+    overlay: Option<Single<&mut BackgroundColor, With<MapTransitionOverlay>>>,
+) {
+    *transition = MapTransition::default();
+    if let Some(mut overlay) = overlay {
+        overlay.0 = Color::NONE;
+    }
+}
+
+/// Plugin for moving between maps in game.
+pub struct MapTransitionPlugin;
+
+impl Plugin for MapTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapTransition>()
+            .add_message::<LoadMapRequest>()
+            .add_systems(Startup, setup_transition_fade)
+            .add_systems(
+                Update,
+                (start_map_transition, run_map_transition)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnEnter(GameState::LoadingMap), cancel_map_transition)
+            .add_systems(OnEnter(GameState::TitleScreen), cancel_map_transition);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn fades_out_then_waits_for_the_map() {
+    let mut transition = MapTransition::default();
+    transition.start(None, None);
+    assert_eq!(transition.fade_alpha(), 0.0);
+
+    transition.advance_fade(TRANSITION_FADE_SECONDS / 2.0);
+    assert_eq!(transition.phase, TransitionPhase::FadingOut);
+    assert!((transition.fade_alpha() - 0.5).abs() < 1e-5);
+
+    transition.advance_fade(TRANSITION_FADE_SECONDS);
+    assert_eq!(transition.phase, TransitionPhase::Loading);
+    assert_eq!(transition.fade_alpha(), 1.0);
+
+    // Stays black however long the map takes
+    transition.advance_fade(10.0);
+    assert_eq!(transition.phase, TransitionPhase::Loading);
+}
+
+#[test]
+fn fades_back_in_and_goes_idle() {
+    let mut transition = MapTransition::default();
+    transition.start(None, Some(Vec3::ONE));
+    transition.advance_fade(TRANSITION_FADE_SECONDS);
+    transition.finish();
+    assert_eq!(transition.phase, TransitionPhase::FadingIn);
+    assert_eq!(transition.fade_alpha(), 1.0);
+    assert!(transition.is_busy());
+
+    transition.advance_fade(TRANSITION_FADE_SECONDS);
+    assert_eq!(transition.phase, TransitionPhase::Idle);
+    assert_eq!(transition.fade_alpha(), 0.0);
+    assert!(!transition.is_busy());
+}
+
+#[test]
+fn spawn_point_moves_the_player_spawn_and_camera() {
+    let mut map = MapLoader::load_default();
+    let spawn = map
+        .entities
+        .iter()
+        .find(|entity| entity.entity_type == EntityType::PlayerSpawn)
+        .unwrap()
+        .position;
+    let camera = map.camera.position;
+    let offset = (1.0, 2.0, 3.0);
+    let point = Vec3::new(spawn.0 + offset.0, spawn.1 + offset.1, spawn.2 + offset.2);
+
+    place_player_spawn(&mut map, point);
+
+    let moved = map
+        .entities
+        .iter()
+        .find(|entity| entity.entity_type == EntityType::PlayerSpawn)
+        .unwrap();
+    assert_eq!(moved.position, (point.x, point.y, point.z));
+    let shifted = Vec3::new(camera.0, camera.1, camera.2) + Vec3::new(1.0, 2.0, 3.0);
+    let (x, y, z) = map.camera.position;
+    assert!((Vec3::new(x, y, z) - shifted).length() < 1e-4);
+}
+
+#[test]
+fn spawn_point_is_added_to_a_map_without_one() {
+    let mut map = MapLoader::load_default();
+    map.entities
+        .retain(|entity| entity.entity_type != EntityType::PlayerSpawn);
+
+    place_player_spawn(&mut map, Vec3::new(4.0, 1.0, 4.0));
+
+    let spawns: Vec<_> = map
+        .entities
+        .iter()
+        .filter(|entity| entity.entity_type == EntityType::PlayerSpawn)
+        .collect();
+    assert_eq!(spawns.len(), 1);
+    assert_eq!(spawns[0].position, (4.0, 1.0, 4.0));
+}
@@ -1,11 +1,40 @@
+pub mod actions;
+pub mod bloom;
+pub mod captions;
+pub mod cinematics;
+pub mod cleanup;
 pub mod components;
+pub mod debug_boxes;
+pub mod debug_console;
+pub mod doors;
+pub mod dynamic_quality;
+pub mod fixed_step;
 pub mod fps_counter;
 pub mod gamepad;
+pub mod go_to;
+pub mod hazards;
+pub mod health;
 pub mod hot_reload;
+pub mod hud_layout;
+pub mod interaction;
 pub mod interior_detection;
+pub mod map_transition;
+pub mod network;
 pub mod npc_labels;
+pub mod particles;
+pub mod photo_mode;
+pub mod playtest_log;
+pub mod render_scale;
+pub mod replay;
 pub mod resources;
+pub mod scripting;
+pub mod speedrun;
+pub mod split_screen;
+pub mod surface;
 pub mod systems;
+pub mod teleporters;
+pub mod voxel_groups;
+pub mod weather;
 
 // New focused modules
 mod camera;
@@ -0,0 +1,110 @@
+//! Co-op exploration for two to four players.
+//!
+//! One player hosts (`--host`, or Host Co-op on the title screen) and up to
+//! three others join with the host's address (`--connect <address>`, or
+//! Join Co-op). Joining players load the map the host is playing, by the
+//! same path, so everyone needs the same maps installed.
+//!
+//! The layer is hand-rolled over UDP. Every [`SEND_INTERVAL`] each client
+//! sends the host its pose, and the host sends each client the poses of the
+//! other players. Other players are drawn as [`RemotePlayer`]s moving
+//! smoothly towards their latest pose. Key pickups are shared: every message
+//! carries the full list of keys picked up on the map, so a lost packet
+//! only delays a pickup, and applying the list again changes nothing. A
+//! player nobody has heard from for [`TIMEOUT_SECONDS`] has left.
+//!
+//! Packets are the [`PROTOCOL_MAGIC`] header followed by a [`NetMessage`]
+//! encoded with bincode. Map changes and the rest of the game state are not
+//! shared.
+//!
+//! [`protocol`] holds the messages and their encoding, [`session`] the
+//! sockets and bookkeeping of a hosted or joined game, and [`systems`] the
+//! Bevy systems connecting a session to the game.
+//!
+//! [`RemotePlayer`]: systems::RemotePlayer
+//! [`NetMessage`]: protocol::NetMessage
+
+pub mod protocol;
+pub mod session;
+pub mod systems;
+
+pub use systems::{NetworkStatus, StartNetworkSession};
+
+use bevy::prelude::*;
+
+use super::doors::collect_keys;
+use crate::states::GameState;
+use systems::{
+    apply_shared_pickups, leave_on_exit, reset_shared_pickups, run_network_session, share_pickups,
+    start_network_sessions, sync_remote_players,
+};
+
+/// Port the host listens on.
+pub const DEFAULT_PORT: u16 = 7777;
+
+/// Most players in one game, the host included.
+pub const MAX_PLAYERS: usize = 4;
+
+/// Packet header; the last byte is the protocol version.
+pub const PROTOCOL_MAGIC: &[u8; 4] = b"ADS\x01";
+
+/// Seconds between pose updates.
+pub const SEND_INTERVAL: f32 = 0.05;
+
+/// Seconds of silence after which a player has left, or a host is gone.
+pub const TIMEOUT_SECONDS: f32 = 5.0;
+
+/// Seconds between join requests while waiting for the host.
+const JOIN_RETRY_SECONDS: f32 = 1.0;
+
+/// Largest packet read or decoded.
+const MAX_PACKET_BYTES: usize = 16 * 1024;
+
+/// Most packets read per frame.
+const MAX_PACKETS_PER_FRAME: usize = 256;
+
+/// How quickly remote players catch up with their latest pose.
+const REMOTE_SMOOTHING: f32 = 15.0;
+
+/// Distance at which a remote player jumps to its pose instead of moving.
+const REMOTE_SNAP_DISTANCE: f32 = 5.0;
+
+/// Distance within which a shared pickup matches a key item.
+const PICKUP_MATCH_DISTANCE: f32 = 0.05;
+
+/// Plugin that runs co-op sessions, optionally starting one right away.
+#[derive(Default)]
+pub struct NetworkPlugin {
+    pub start: Option<StartNetworkSession>,
+}
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<StartNetworkSession>()
+            .init_resource::<NetworkStatus>()
+            .add_systems(
+                Update,
+                (start_network_sessions, run_network_session, leave_on_exit).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    share_pickups.after(collect_keys),
+                    apply_shared_pickups.after(share_pickups),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                sync_remote_players
+                    .run_if(in_state(GameState::InGame).or(in_state(GameState::Paused))),
+            )
+            .add_systems(OnEnter(GameState::LoadingMap), reset_shared_pickups);
+        if let Some(start) = &self.start {
+            app.world_mut().write_message(start.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+//! Messages between host and clients and their encoding.
+
+use bevy::prelude::*;
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::path::PathBuf;
+
+use super::{DEFAULT_PORT, MAX_PACKET_BYTES, PROTOCOL_MAGIC};
+
+/// Player number within a game; the host is 0.
+pub type PlayerId = u8;
+
+/// Where a player is and which way they face.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PlayerPose {
+    pub position: (f32, f32, f32),
+    /// Facing around the Y axis, in radians
+    pub rotation: f32,
+}
+
+impl PlayerPose {
+    pub fn new(position: Vec3, rotation: f32) -> Self {
+        Self {
+            position: position.into(),
+            rotation,
+        }
+    }
+
+    pub fn translation(&self) -> Vec3 {
+        self.position.into()
+    }
+}
+
+/// A key item picked up by some player.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemPickup {
+    pub key: String,
+    pub position: (f32, f32, f32),
+}
+
+/// Everything sent between host and clients.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum NetMessage {
+    /// Client asks to join; repeated until welcomed
+    Join,
+    /// Host accepts a client and names the map to load
+    Welcome { id: PlayerId, map: PathBuf },
+    /// Host turns a client away because the game is full
+    Full,
+    /// Client's pose (`None` outside a map) and its pickups
+    ClientState {
+        pose: Option<PlayerPose>,
+        pickups: Vec<ItemPickup>,
+    },
+    /// Host's view of the other players and every pickup on the map
+    HostState {
+        players: Vec<(PlayerId, PlayerPose)>,
+        pickups: Vec<ItemPickup>,
+    },
+    /// Sender is leaving the game
+    Leave,
+}
+
+fn codec() -> impl Options {
+    bincode::DefaultOptions::new().with_limit(MAX_PACKET_BYTES as u64)
+}
+
+/// Encode a message as a packet.
+pub fn encode(message: &NetMessage) -> Vec<u8> {
+    let mut packet = PROTOCOL_MAGIC.to_vec();
+    // Writing to a Vec only fails past the size limit
+    if let Err(e) = codec().serialize_into(&mut packet, message) {
+        warn!("Could not encode {:?}: {}", message, e);
+    }
+    packet
+}
+
+/// Decode a packet, or `None` if it isn't one of ours.
+pub fn decode(packet: &[u8]) -> Option<NetMessage> {
+    let body = packet.strip_prefix(PROTOCOL_MAGIC.as_slice())?;
+    codec().deserialize(body).ok()
+}
+
+/// Socket address for `address`, with [`DEFAULT_PORT`] when it has none.
+/// IPv4 is preferred when a name resolves to both.
+pub fn resolve_address(address: &str) -> io::Result<SocketAddr> {
+    let address = address.trim();
+    if let Ok(addr) = address.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    // A bare IP address; IPv6 ones may be bracketed
+    let bare = address.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = bare.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, DEFAULT_PORT));
+    }
+    let with_port = if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, DEFAULT_PORT)
+    };
+    let resolved: Vec<SocketAddr> = with_port.to_socket_addrs()?.collect();
+    resolved
+        .iter()
+        .find(|addr| addr.is_ipv4())
+        .or(resolved.first())
+        .copied()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "address did not resolve"))
+}
@@ -0,0 +1,379 @@
+//! A hosted or joined game: its socket, players and shared pickups.
+
+use bevy::prelude::*;
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+
+use super::protocol::{
+    decode, encode, resolve_address, ItemPickup, NetMessage, PlayerId, PlayerPose,
+};
+use super::{
+    JOIN_RETRY_SECONDS, MAX_PACKETS_PER_FRAME, MAX_PACKET_BYTES, MAX_PLAYERS, SEND_INTERVAL,
+    TIMEOUT_SECONDS,
+};
+
+/// Something that happened in a session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetEvent {
+    /// A client joined the host's game
+    PlayerJoined(PlayerId),
+    /// A client left the host's game or timed out
+    PlayerLeft(PlayerId),
+    /// The host accepted this client
+    Welcomed { id: PlayerId, map: PathBuf },
+    /// The host's game is full
+    Rejected,
+    /// The host stopped answering
+    Disconnected { was_joined: bool },
+}
+
+/// A client as seen by the host.
+struct Peer {
+    id: PlayerId,
+    addr: SocketAddr,
+    pose: Option<PlayerPose>,
+    silence: f32,
+}
+
+enum Role {
+    Host {
+        peers: Vec<Peer>,
+    },
+    Client {
+        host: SocketAddr,
+        id: Option<PlayerId>,
+        players: Vec<(PlayerId, PlayerPose)>,
+        silence: f32,
+        join_timer: f32,
+    },
+}
+
+/// An open co-op game, hosted or joined.
+#[derive(Resource)]
+pub struct NetworkSession {
+    socket: UdpSocket,
+    role: Role,
+    /// Keys picked up on the current map, by anyone
+    pickups: Vec<ItemPickup>,
+    send_timer: f32,
+}
+
+impl NetworkSession {
+    /// Host a game on `port` (0 picks a free port).
+    pub fn host(port: u16) -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        socket.set_nonblocking(true)?;
+        info!("Hosting co-op on {}", socket.local_addr()?);
+        Ok(Self {
+            socket,
+            role: Role::Host { peers: Vec::new() },
+            pickups: Vec::new(),
+            send_timer: 0.0,
+        })
+    }
+
+    /// Join the game hosted at `address`. The session asks to join until
+    /// the host answers or [`TIMEOUT_SECONDS`] pass.
+    pub fn connect(address: &str) -> io::Result<Self> {
+        let host = resolve_address(address)?;
+        let local: SocketAddr = if host.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.set_nonblocking(true)?;
+        info!("Joining co-op at {}", host);
+        Ok(Self {
+            socket,
+            role: Role::Client {
+                host,
+                id: None,
+                players: Vec::new(),
+                silence: 0.0,
+                // Ask right away
+                join_timer: JOIN_RETRY_SECONDS,
+            },
+            pickups: Vec::new(),
+            send_timer: 0.0,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.role, Role::Host { .. })
+    }
+
+    /// This player's number, once a client has been welcomed.
+    #[allow(dead_code)]
+    pub fn local_id(&self) -> Option<PlayerId> {
+        match &self.role {
+            Role::Host { .. } => Some(0),
+            Role::Client { id, .. } => *id,
+        }
+    }
+
+    /// Latest poses of the other players in a map.
+    pub fn remote_poses(&self) -> Vec<(PlayerId, PlayerPose)> {
+        match &self.role {
+            Role::Host { peers } => peers
+                .iter()
+                .filter_map(|peer| peer.pose.map(|pose| (peer.id, pose)))
+                .collect(),
+            Role::Client { players, .. } => players.clone(),
+        }
+    }
+
+    /// Keys picked up on the current map.
+    pub fn pickups(&self) -> &[ItemPickup] {
+        &self.pickups
+    }
+
+    /// Share a pickup of this player's.
+    pub fn record_pickup(&mut self, pickup: ItemPickup) {
+        merge_pickups(&mut self.pickups, [pickup]);
+    }
+
+    /// Forget the pickups of the previous map.
+    pub fn reset_map(&mut self) {
+        self.pickups.clear();
+    }
+
+    fn send(&self, to: SocketAddr, message: &NetMessage) {
+        if let Err(e) = self.socket.send_to(&encode(message), to) {
+            debug!("Could not send to {}: {}", to, e);
+        }
+    }
+
+    /// Read the packets that arrived and answer them. A host welcomes new
+    /// players to `map`; without a map it waits before welcoming anyone.
+    pub fn receive(&mut self, map: Option<&Path>) -> Vec<NetEvent> {
+        let mut events = Vec::new();
+        let mut buffer = [0u8; MAX_PACKET_BYTES];
+        for _ in 0..MAX_PACKETS_PER_FRAME {
+            let (len, from) = match self.socket.recv_from(&mut buffer) {
+                Ok(received) => received,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                // e.g. an unreachable peer reported by a previous send
+                Err(e) => {
+                    debug!("Receive error: {}", e);
+                    continue;
+                }
+            };
+            if let Some(message) = decode(&buffer[..len]) {
+                self.handle(from, message, map, &mut events);
+            }
+        }
+        events
+    }
+
+    fn handle(
+        &mut self,
+        from: SocketAddr,
+        message: NetMessage,
+        map: Option<&Path>,
+        events: &mut Vec<NetEvent>,
+    ) {
+        let mut reply = None;
+        match &mut self.role {
+            Role::Host { peers } => {
+                let peer = peers.iter_mut().find(|peer| peer.addr == from);
+                match (message, peer) {
+                    (NetMessage::Join, Some(peer)) => {
+                        peer.silence = 0.0;
+                        reply = map.map(|map| NetMessage::Welcome {
+                            id: peer.id,
+                            map: map.to_path_buf(),
+                        });
+                    }
+                    (NetMessage::Join, None) => {
+                        let free_id = (1..MAX_PLAYERS as PlayerId)
+                            .find(|id| peers.iter().all(|p| p.id != *id));
+                        match (free_id, map) {
+                            (Some(id), Some(map)) => {
+                                peers.push(Peer {
+                                    id,
+                                    addr: from,
+                                    pose: None,
+                                    silence: 0.0,
+                                });
+                                info!("Player {} joined from {}", id + 1, from);
+                                events.push(NetEvent::PlayerJoined(id));
+                                reply = Some(NetMessage::Welcome {
+                                    id,
+                                    map: map.to_path_buf(),
+                                });
+                            }
+                            (None, _) => reply = Some(NetMessage::Full),
+                            // Not in a map yet; the client asks again
+                            (Some(_), None) => {}
+                        }
+                    }
+                    (NetMessage::ClientState { pose, pickups }, Some(peer)) => {
+                        peer.pose = pose;
+                        peer.silence = 0.0;
+                        merge_pickups(&mut self.pickups, pickups);
+                    }
+                    (NetMessage::Leave, Some(peer)) => {
+                        let id = peer.id;
+                        peers.retain(|peer| peer.addr != from);
+                        info!("Player {} left", id + 1);
+                        events.push(NetEvent::PlayerLeft(id));
+                    }
+                    _ => {}
+                }
+            }
+            Role::Client {
+                host,
+                id,
+                players,
+                silence,
+                ..
+            } => {
+                if from != *host {
+                    return;
+                }
+                *silence = 0.0;
+                match message {
+                    NetMessage::Welcome {
+                        id: welcome_id,
+                        map,
+                    } if id.is_none() => {
+                        *id = Some(welcome_id);
+                        info!("Joined as player {}", welcome_id + 1);
+                        events.push(NetEvent::Welcomed {
+                            id: welcome_id,
+                            map,
+                        });
+                    }
+                    NetMessage::Full if id.is_none() => events.push(NetEvent::Rejected),
+                    NetMessage::HostState {
+                        players: poses,
+                        pickups,
+                    } if id.is_some() => {
+                        *players = poses;
+                        merge_pickups(&mut self.pickups, pickups);
+                    }
+                    NetMessage::Leave => events.push(NetEvent::Disconnected {
+                        was_joined: id.is_some(),
+                    }),
+                    _ => {}
+                }
+            }
+        }
+        if let Some(reply) = reply {
+            self.send(from, &reply);
+        }
+    }
+
+    /// Advance timers by `delta` seconds, send this player's `pose` when
+    /// due and drop players who have gone quiet.
+    pub fn update(&mut self, delta: f32, pose: Option<PlayerPose>) -> Vec<NetEvent> {
+        let mut events = Vec::new();
+        self.send_timer += delta;
+        let send_due = self.send_timer >= SEND_INTERVAL;
+        if send_due {
+            self.send_timer = 0.0;
+        }
+
+        let mut outgoing = Vec::new();
+        match &mut self.role {
+            Role::Host { peers } => {
+                for peer in peers.iter_mut() {
+                    peer.silence += delta;
+                }
+                peers.retain(|peer| {
+                    let alive = peer.silence < TIMEOUT_SECONDS;
+                    if !alive {
+                        info!("Player {} timed out", peer.id + 1);
+                        events.push(NetEvent::PlayerLeft(peer.id));
+                    }
+                    alive
+                });
+                if send_due {
+                    for peer in peers.iter() {
+                        let players =
+                            pose.map(|pose| (0, pose))
+                                .into_iter()
+                                .chain(
+                                    peers.iter().filter(|other| other.id != peer.id).filter_map(
+                                        |other| other.pose.map(|pose| (other.id, pose)),
+                                    ),
+                                )
+                                .collect();
+                        outgoing.push((
+                            peer.addr,
+                            NetMessage::HostState {
+                                players,
+                                pickups: self.pickups.clone(),
+                            },
+                        ));
+                    }
+                }
+            }
+            Role::Client {
+                host,
+                id,
+                silence,
+                join_timer,
+                ..
+            } => {
+                *silence += delta;
+                if *silence >= TIMEOUT_SECONDS {
+                    warn!("No answer from the host at {}", host);
+                    events.push(NetEvent::Disconnected {
+                        was_joined: id.is_some(),
+                    });
+                    // Start over, so the event isn't repeated every frame
+                    *silence = 0.0;
+                    *id = None;
+                }
+                if id.is_none() {
+                    *join_timer += delta;
+                    if *join_timer >= JOIN_RETRY_SECONDS {
+                        *join_timer = 0.0;
+                        outgoing.push((*host, NetMessage::Join));
+                    }
+                } else if send_due {
+                    outgoing.push((
+                        *host,
+                        NetMessage::ClientState {
+                            pose,
+                            pickups: self.pickups.clone(),
+                        },
+                    ));
+                }
+            }
+        }
+        for (to, message) in outgoing {
+            self.send(to, &message);
+        }
+        events
+    }
+
+    /// Tell the other side this player is leaving.
+    pub fn leave(&self) {
+        match &self.role {
+            Role::Host { peers } => {
+                for peer in peers {
+                    self.send(peer.addr, &NetMessage::Leave);
+                }
+            }
+            Role::Client { host, .. } => self.send(*host, &NetMessage::Leave),
+        }
+    }
+}
+
+/// Add the pickups not already in `known`.
+fn merge_pickups(known: &mut Vec<ItemPickup>, pickups: impl IntoIterator<Item = ItemPickup>) {
+    for pickup in pickups {
+        if !known.contains(&pickup) {
+            known.push(pickup);
+        }
+    }
+}
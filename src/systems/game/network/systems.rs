@@ -0,0 +1,288 @@
+//! Systems connecting a session to the game: starting it, sending the
+//! player's pose, sharing pickups and drawing the other players.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::protocol::{ItemPickup, PlayerId, PlayerPose};
+use super::session::{NetEvent, NetworkSession};
+use super::{DEFAULT_PORT, PICKUP_MATCH_DISTANCE, REMOTE_SMOOTHING, REMOTE_SNAP_DISTANCE};
+use crate::states::GameState;
+use crate::systems::game::actions::{ActionRunner, MapAction};
+use crate::systems::game::components::{GameEntity, Player};
+use crate::systems::game::doors::{KeyCollected, KeyItem, PlayerKeys, KEY_COLOR};
+use crate::systems::game::hot_reload::MapPathForHotReload;
+use crate::systems::game::particles::ParticleBurst;
+use crate::systems::game::speedrun::NextMapRequest;
+use crate::systems::localization::Localization;
+
+/// Request to host or join a game.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub enum StartNetworkSession {
+    Host,
+    Join(String),
+}
+
+/// State of the co-op connection, shown on the title screen.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub enum NetworkStatus {
+    #[default]
+    Offline,
+    Hosting {
+        port: u16,
+    },
+    Connecting {
+        address: String,
+    },
+    Joined {
+        id: PlayerId,
+    },
+    Full,
+    Unreachable {
+        address: String,
+    },
+    Lost,
+    Error(String),
+}
+
+impl NetworkStatus {
+    /// Status line for the title screen; empty when offline.
+    pub fn describe(&self, localization: &Localization) -> String {
+        match self {
+            Self::Offline => String::new(),
+            Self::Hosting { port } => {
+                localization.format("net.hosting", &[("port", &port.to_string())])
+            }
+            Self::Connecting { address } => {
+                localization.format("net.connecting", &[("address", address)])
+            }
+            Self::Joined { id } => {
+                localization.format("net.joined", &[("player", &(id + 1).to_string())])
+            }
+            Self::Full => localization.get("net.full").to_string(),
+            Self::Unreachable { address } => {
+                localization.format("net.unreachable", &[("address", address)])
+            }
+            Self::Lost => localization.get("net.lost").to_string(),
+            Self::Error(reason) => localization.format("net.error", &[("reason", reason)]),
+        }
+    }
+}
+
+/// Another player's character.
+#[derive(Component, Debug)]
+pub struct RemotePlayer {
+    pub id: PlayerId,
+}
+
+/// Open the requested session, replacing any other.
+pub fn start_network_sessions(
+    mut commands: Commands,
+    mut requests: MessageReader<StartNetworkSession>,
+    session: Option<Res<NetworkSession>>,
+    mut status: ResMut<NetworkStatus>,
+) {
+    for request in requests.read() {
+        // Keep hosting rather than fight the open socket for the port
+        if *request == StartNetworkSession::Host && session.as_ref().is_some_and(|s| s.is_host()) {
+            continue;
+        }
+        if let Some(session) = &session {
+            session.leave();
+        }
+        let result = match request {
+            StartNetworkSession::Host => NetworkSession::host(DEFAULT_PORT).inspect(|_| {
+                *status = NetworkStatus::Hosting { port: DEFAULT_PORT };
+            }),
+            StartNetworkSession::Join(address) => NetworkSession::connect(address).inspect(|_| {
+                *status = NetworkStatus::Connecting {
+                    address: address.clone(),
+                };
+            }),
+        };
+        match result {
+            Ok(session) => commands.insert_resource(session),
+            Err(e) => {
+                error!("Could not start co-op: {}", e);
+                *status = NetworkStatus::Error(e.to_string());
+            }
+        }
+    }
+}
+
+/// Exchange packets with the other players and react to players joining
+/// and leaving.
+#[allow(clippy::too_many_arguments)]
+pub fn run_network_session(
+    mut commands: Commands,
+    time: Res<Time>,
+    session: Option<ResMut<NetworkSession>>,
+    player: Option<Single<(&Transform, &Player)>>,
+    map_path: Res<MapPathForHotReload>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_map: ResMut<NextMapRequest>,
+    mut status: ResMut<NetworkStatus>,
+    mut runner: ResMut<ActionRunner>,
+) {
+    let Some(mut session) = session else {
+        return;
+    };
+    let pose =
+        player.map(|player| PlayerPose::new(player.0.translation, player.1.current_rotation));
+    let mut events = session.receive(map_path.0.as_deref());
+    events.extend(session.update(time.delta_secs(), pose));
+
+    let in_game = *state.get() == GameState::InGame;
+    for event in events {
+        match event {
+            NetEvent::PlayerJoined(id) if in_game => {
+                runner.run(&[MapAction::Say(format!("Player {} joined.", id + 1))]);
+            }
+            NetEvent::PlayerLeft(id) if in_game => {
+                runner.run(&[MapAction::Say(format!("Player {} left.", id + 1))]);
+            }
+            NetEvent::PlayerJoined(_) | NetEvent::PlayerLeft(_) => {}
+            NetEvent::Welcomed { id, map } => {
+                *status = NetworkStatus::Joined { id };
+                next_map.0 = Some(map);
+                next_state.set(GameState::LoadingMap);
+            }
+            NetEvent::Rejected => {
+                *status = NetworkStatus::Full;
+                commands.remove_resource::<NetworkSession>();
+            }
+            NetEvent::Disconnected { was_joined } => {
+                *status = match (&*status, was_joined) {
+                    (NetworkStatus::Connecting { address }, false) => NetworkStatus::Unreachable {
+                        address: address.clone(),
+                    },
+                    _ => NetworkStatus::Lost,
+                };
+                if in_game && was_joined {
+                    runner.run(&[MapAction::Say("Lost connection to the host.".to_string())]);
+                }
+                commands.remove_resource::<NetworkSession>();
+            }
+        }
+    }
+}
+
+/// Share the keys this player picks up.
+pub fn share_pickups(
+    mut collected: MessageReader<KeyCollected>,
+    session: Option<ResMut<NetworkSession>>,
+) {
+    let Some(mut session) = session else {
+        collected.clear();
+        return;
+    };
+    for key in collected.read() {
+        session.record_pickup(ItemPickup {
+            key: key.key.clone(),
+            position: key.position.into(),
+        });
+    }
+}
+
+/// Give this player the keys others picked up and remove those items.
+pub fn apply_shared_pickups(
+    mut commands: Commands,
+    session: Option<Res<NetworkSession>>,
+    items: Query<(Entity, &KeyItem, &GlobalTransform)>,
+    mut keys: ResMut<PlayerKeys>,
+    mut runner: ResMut<ActionRunner>,
+    mut bursts: MessageWriter<ParticleBurst>,
+) {
+    let Some(session) = session else {
+        return;
+    };
+    for pickup in session.pickups() {
+        if !keys.has(&pickup.key) {
+            keys.insert(pickup.key.clone());
+        }
+        let position = Vec3::from(pickup.position);
+        for (entity, item, transform) in &items {
+            if item.key != pickup.key
+                || transform.translation().distance(position) > PICKUP_MATCH_DISTANCE
+            {
+                continue;
+            }
+            info!("Another player picked up key '{}'", item.key);
+            bursts.write(ParticleBurst::item_collected(position, KEY_COLOR));
+            runner.run(&[MapAction::Say(format!(
+                "Your partner picked up the {}.",
+                item.key
+            ))]);
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+/// Forget the pickups of the previous map.
+pub fn reset_shared_pickups(session: Option<ResMut<NetworkSession>>) {
+    if let Some(mut session) = session {
+        session.reset_map();
+    }
+}
+
+/// Spawn, move and despawn the characters of the other players.
+pub fn sync_remote_players(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    session: Option<Res<NetworkSession>>,
+    mut remotes: Query<(Entity, &RemotePlayer, &mut Transform)>,
+) {
+    let poses: HashMap<PlayerId, PlayerPose> = session
+        .map(|session| session.remote_poses().into_iter().collect())
+        .unwrap_or_default();
+    let blend = 1.0 - (-REMOTE_SMOOTHING * time.delta_secs()).exp();
+
+    for (entity, remote, mut transform) in &mut remotes {
+        let Some(pose) = poses.get(&remote.id) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let target = pose.translation();
+        let rotation = Quat::from_rotation_y(pose.rotation);
+        if transform.translation.distance(target) > REMOTE_SNAP_DISTANCE {
+            transform.translation = target;
+            transform.rotation = rotation;
+        } else {
+            transform.translation = transform.translation.lerp(target, blend);
+            transform.rotation = transform.rotation.slerp(rotation, blend);
+        }
+    }
+
+    for (id, pose) in poses {
+        if remotes.iter().any(|(_, remote, _)| remote.id == id) {
+            continue;
+        }
+        let character_scene: Handle<Scene> =
+            asset_server.load(GltfAssetLabel::Scene(0).from_asset("characters/base_basic_pbr.glb"));
+        // Offset and scale the model like the local player's
+        commands
+            .spawn((
+                Transform::from_translation(pose.translation())
+                    .with_rotation(Quat::from_rotation_y(pose.rotation)),
+                Visibility::default(),
+                RemotePlayer { id },
+                GameEntity,
+            ))
+            .with_child((
+                SceneRoot(character_scene),
+                Transform::from_translation(Vec3::new(0.0, -0.3, 0.0)).with_scale(Vec3::splat(0.5)),
+            ));
+        info!("Spawned player {}", id + 1);
+    }
+}
+
+/// Tell the other players when the game closes.
+pub fn leave_on_exit(mut exit: MessageReader<AppExit>, session: Option<Res<NetworkSession>>) {
+    if exit.read().count() > 0 {
+        if let Some(session) = session {
+            session.leave();
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::protocol::*;
+use super::session::*;
+use super::*;
+use std::net::{Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::thread::sleep;
+use std::time::Duration;
+
+fn pose(x: f32) -> PlayerPose {
+    PlayerPose::new(Vec3::new(x, 1.0, 2.0), 0.5)
+}
+
+/// Give loopback packets time to arrive.
+fn settle() {
+    sleep(Duration::from_millis(20));
+}
+
+fn host_and_client() -> (NetworkSession, NetworkSession) {
+    let host = NetworkSession::host(0).unwrap();
+    let port = host.local_addr().unwrap().port();
+    let client = NetworkSession::connect(&format!("127.0.0.1:{}", port)).unwrap();
+    (host, client)
+}
+
+/// Let `client` ask to join `host`, playing `map`.
+fn join(host: &mut NetworkSession, client: &mut NetworkSession, map: &Path) -> Vec<NetEvent> {
+    assert!(client.update(0.0, None).is_empty());
+    settle();
+    host.receive(Some(map));
+    settle();
+    client.receive(None)
+}
+
+#[test]
+fn messages_round_trip() {
+    let message = NetMessage::HostState {
+        players: vec![(0, pose(1.0)), (2, pose(-3.0))],
+        pickups: vec![ItemPickup {
+            key: "brass key".to_string(),
+            position: (1.0, 2.0, 3.0),
+        }],
+    };
+    assert_eq!(decode(&encode(&message)), Some(message));
+    assert_eq!(decode(&encode(&NetMessage::Join)), Some(NetMessage::Join));
+}
+
+#[test]
+fn foreign_packets_are_ignored() {
+    assert_eq!(decode(b""), None);
+    assert_eq!(decode(b"hello world"), None);
+    let mut packet = encode(&NetMessage::Join);
+    packet[3] = 0x7f;
+    assert_eq!(decode(&packet), None, "other protocol version");
+    assert_eq!(decode(PROTOCOL_MAGIC), None, "header only");
+}
+
+#[test]
+fn addresses_default_to_the_game_port() {
+    assert_eq!(
+        resolve_address("127.0.0.1").unwrap(),
+        SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT))
+    );
+    assert_eq!(
+        resolve_address(" 127.0.0.1:9000 ").unwrap(),
+        SocketAddr::from(([127, 0, 0, 1], 9000))
+    );
+    assert!(resolve_address("127.0.0.1:notaport").is_err());
+}
+
+#[test]
+fn bare_ipv6_addresses_get_the_default_port() {
+    let loopback = SocketAddr::from((Ipv6Addr::LOCALHOST, DEFAULT_PORT));
+    assert_eq!(resolve_address("::1").unwrap(), loopback);
+    assert_eq!(resolve_address("[::1]").unwrap(), loopback);
+    assert_eq!(
+        resolve_address("[::1]:9000").unwrap(),
+        SocketAddr::from((Ipv6Addr::LOCALHOST, 9000))
+    );
+}
+
+#[test]
+fn clients_join_the_hosts_map() {
+    let (mut host, mut client) = host_and_client();
+    let map = Path::new("assets/maps/village.ron");
+    assert_eq!(client.local_id(), None);
+
+    let events = join(&mut host, &mut client, map);
+    assert_eq!(
+        events,
+        vec![NetEvent::Welcomed {
+            id: 1,
+            map: map.to_path_buf()
+        }]
+    );
+    assert_eq!(client.local_id(), Some(1));
+    assert!(host.is_host() && !client.is_host());
+}
+
+#[test]
+fn hosts_outside_a_map_keep_clients_waiting() {
+    let (mut host, mut client) = host_and_client();
+    client.update(0.0, None);
+    settle();
+    assert!(host.receive(None).is_empty());
+    settle();
+    assert!(client.receive(None).is_empty());
+    assert_eq!(client.local_id(), None);
+}
+
+#[test]
+fn poses_and_pickups_reach_the_other_side() {
+    let (mut host, mut client) = host_and_client();
+    join(&mut host, &mut client, Path::new("map.ron"));
+
+    let pickup = ItemPickup {
+        key: "red key".to_string(),
+        position: (4.0, 1.0, 4.0),
+    };
+    client.record_pickup(pickup.clone());
+    client.update(SEND_INTERVAL, Some(pose(3.0)));
+    settle();
+    host.receive(None);
+    assert_eq!(host.remote_poses(), vec![(1, pose(3.0))]);
+    assert_eq!(host.pickups(), std::slice::from_ref(&pickup));
+
+    host.update(SEND_INTERVAL, Some(pose(7.0)));
+    settle();
+    client.receive(None);
+    assert_eq!(client.remote_poses(), vec![(0, pose(7.0))]);
+
+    // Sharing the same pickup again changes nothing
+    client.record_pickup(pickup.clone());
+    assert_eq!(client.pickups().len(), 1);
+    client.reset_map();
+    assert!(client.pickups().is_empty());
+}
+
+#[test]
+fn the_fifth_player_is_turned_away() {
+    let mut host = NetworkSession::host(0).unwrap();
+    let address = format!("127.0.0.1:{}", host.local_addr().unwrap().port());
+    let map = Path::new("map.ron");
+
+    for id in 1..MAX_PLAYERS as PlayerId {
+        let mut client = NetworkSession::connect(&address).unwrap();
+        let events = join(&mut host, &mut client, map);
+        assert_eq!(client.local_id(), Some(id), "{:?}", events);
+    }
+    let mut late = NetworkSession::connect(&address).unwrap();
+    assert_eq!(join(&mut host, &mut late, map), vec![NetEvent::Rejected]);
+}
+
+#[test]
+fn silent_players_time_out() {
+    let (mut host, mut client) = host_and_client();
+    join(&mut host, &mut client, Path::new("map.ron"));
+
+    assert_eq!(
+        host.update(TIMEOUT_SECONDS, None),
+        vec![NetEvent::PlayerLeft(1)]
+    );
+    assert_eq!(
+        client.update(TIMEOUT_SECONDS, None),
+        vec![NetEvent::Disconnected { was_joined: true }]
+    );
+}
+
+#[test]
+fn leaving_is_reported() {
+    let (mut host, mut client) = host_and_client();
+    join(&mut host, &mut client, Path::new("map.ron"));
+
+    client.leave();
+    settle();
+    assert_eq!(host.receive(None), vec![NetEvent::PlayerLeft(1)]);
+    assert!(host.remote_poses().is_empty());
+}
@@ -10,13 +10,17 @@
 //!
 //! Labels are standalone UI nodes (not children of the NPC entity). They are
 //! cleaned up in two ways:
-//! - [`cleanup_npc_labels`] — registered on `OnExit(GameState::InGame)`.
+//! - As [`GameEntity`]s, they are despawned with the rest of the world when
+//!   the game ends, so they survive pausing and come back on resume.
 //! - [`despawn_removed_npc_labels`] — despawns labels whose NPC was removed
 //!   (e.g. during hot-reload).
 
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
-use super::components::{Npc, NpcLabel, Player};
+use super::components::{GameEntity, Npc, NpcLabel, Player};
+use super::render_scale::RenderScaleTarget;
+use crate::systems::localization::Localization;
 
 /// Default name assigned to NPCs without a custom name in the map file.
 /// Labels are suppressed for this value (and the empty string).
@@ -61,12 +65,21 @@ impl NpcLabelFade {
 /// non-default, non-empty name. The label is a root-level absolutely-positioned
 /// [`Node`] entity — it is **not** a child of the NPC entity.
 ///
-/// Runs once per NPC via the `Added<Npc>` query filter.
-pub fn spawn_npc_label(mut commands: Commands, query: Query<(Entity, &Npc), Added<Npc>>) {
+/// Runs once per NPC via the `Added<Npc>` query filter. Names written as
+/// `loc:<key>` are translated through [`Localization`] when it is present.
+pub fn spawn_npc_label(
+    mut commands: Commands,
+    localization: Option<Res<Localization>>,
+    query: Query<(Entity, &Npc), Added<Npc>>,
+) {
     for (npc_entity, npc) in &query {
         if npc.name.is_empty() || npc.name == DEFAULT_NPC_NAME {
             continue;
         }
+        let display_name = match &localization {
+            Some(localization) => localization.resolve(&npc.name),
+            None => npc.name.clone(),
+        };
 
         commands.spawn((
             Node {
@@ -78,7 +91,7 @@ pub fn spawn_npc_label(mut commands: Commands, query: Query<(Entity, &Npc), Adde
             // Translate by (-50%, -50%) of the node's own size so the centre of
             // the text box lands on the projected screen point, not the top-left.
             UiTransform::from_translation(Val2::percent(-50.0, -50.0)),
-            Text::new(npc.name.clone()),
+            Text::new(display_name),
             TextFont {
                 font_size: LABEL_FONT_SIZE,
                 ..default()
@@ -87,6 +100,7 @@ pub fn spawn_npc_label(mut commands: Commands, query: Query<(Entity, &Npc), Adde
             Visibility::Hidden,
             NpcLabel { npc_entity },
             NpcLabelFade::new(),
+            GameEntity,
         ));
     }
 }
@@ -105,6 +119,8 @@ pub fn update_npc_label_visibility(
     player_query: Query<&Transform, With<Player>>,
     npc_query: Query<&GlobalTransform, With<Npc>>,
     mut label_query: Query<(&mut Node, &NpcLabel, &mut NpcLabelFade)>,
+    render_scale: Res<RenderScaleTarget>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
 ) {
     let Ok((camera, camera_global_transform)) = camera_query.single() else {
         return;
@@ -133,6 +149,10 @@ pub fn update_npc_label_visibility(
         let label_world_pos = npc_pos + Vec3::Y * LABEL_Y_OFFSET;
         match camera.world_to_viewport(camera_global_transform, label_world_pos) {
             Ok(screen_pos) => {
+                // The camera may render at a different resolution than the window
+                let screen_pos = window.as_ref().map_or(screen_pos, |window| {
+                    render_scale.to_window(screen_pos, window.size())
+                });
                 node.left = Val::Px(screen_pos.x);
                 node.top = Val::Px(screen_pos.y);
                 fade.target = 1.0;
@@ -181,7 +201,7 @@ pub fn tick_npc_label_fade(
 ///
 /// Runs every frame in `GameSystemSet::Visual` alongside the visibility system.
 /// Handles hot-reload and any other scenario where NPC entities are removed
-/// without an `OnExit(InGame)` transition.
+/// while the game keeps running.
 pub fn despawn_removed_npc_labels(
     mut commands: Commands,
     label_query: Query<(Entity, &NpcLabel)>,
@@ -194,16 +214,5 @@ pub fn despawn_removed_npc_labels(
     }
 }
 
-/// Despawns all NPC label UI nodes when leaving the `InGame` state.
-///
-/// Because labels are **not** children of NPC entities, they are not
-/// automatically removed when the map is unloaded. This system cleans them up
-/// on `OnExit(GameState::InGame)`.
-pub fn cleanup_npc_labels(mut commands: Commands, label_query: Query<Entity, With<NpcLabel>>) {
-    for entity in &label_query {
-        commands.entity(entity).despawn();
-    }
-}
-
 #[cfg(test)]
 mod tests;
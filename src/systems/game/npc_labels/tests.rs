@@ -1,4 +1,5 @@
 use super::*;
+use crate::systems::game::cleanup::despawn_game_entities;
 
 fn app_with_spawn_system() -> App {
     let mut app = App::new();
@@ -139,11 +140,11 @@ fn spawn_system_is_idempotent_across_frames() {
     assert_eq!(label_count, 1);
 }
 
-/// `cleanup_npc_labels` must despawn all NpcLabel entities.
+/// Labels are game entities, despawned with the rest of the world.
 #[test]
 fn cleanup_removes_all_labels() {
     let mut app = App::new();
-    app.add_systems(Update, (spawn_npc_label, cleanup_npc_labels).chain());
+    app.add_systems(Update, (spawn_npc_label, despawn_game_entities).chain());
 
     app.world_mut().spawn(Npc {
         name: "Wizard".to_string(),
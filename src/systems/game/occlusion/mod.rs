@@ -23,6 +23,14 @@
 //! - `falloff_softness`: Smoothness of the vertical transition
 //! - `technique`: Dithered (screen-door, default — no MSAA cost) or AlphaBlend (smooth, configurable via settings menu)
 //! - `mode`: ShaderBased, RegionBased, or Hybrid occlusion mode
+//!
+//! In RegionBased and Hybrid modes, walls between the camera and the player are
+//! also cut away while the player is inside a detected interior, unless the map
+//! turns this off with `camera.interior_cutaway: Some(false)`.
+//!
+//! The same shader draws the map's voxel edges (`edges` in the map file) as
+//! dark lines or bevels, from the [`EdgeUniforms`] set when the material is
+//! created.
 
 use bevy::{
     pbr::{ExtendedMaterial, MaterialExtension},
@@ -34,6 +42,7 @@ use serde::{Deserialize, Serialize};
 
 use super::components::{GameCamera, Player};
 use super::interior_detection::InteriorState;
+use super::map::format::{EdgeData, EdgeStyle};
 use crate::diagnostics::FrameProfiler;
 use crate::profile_scope;
 
@@ -51,6 +60,16 @@ pub enum TransparencyTechnique {
     AlphaBlend,
 }
 
+impl TransparencyTechnique {
+    /// The other technique; used by the F4 debug toggle.
+    pub fn toggled(self) -> Self {
+        match self {
+            TransparencyTechnique::Dithered => TransparencyTechnique::AlphaBlend,
+            TransparencyTechnique::AlphaBlend => TransparencyTechnique::Dithered,
+        }
+    }
+}
+
 /// Occlusion mode for handling overhead voxels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum OcclusionMode {
@@ -83,11 +102,13 @@ pub enum ShadowQuality {
     /// Only character/NPC meshes cast shadows. VoxelChunks get `NotShadowCaster`.
     /// Uses 2 cascades / 20-unit range to keep the shadow map alive for characters.
     CharactersOnly,
-    /// Short-range voxel shadows: 2 cascades, 20-unit maximum distance.
+    /// Short-range voxel shadows: 1024² map, 2 cascades, 20-unit maximum distance.
     #[default]
     Low,
-    /// Full-quality voxel shadows: 4 cascades, 100-unit maximum distance.
-    /// Matches the original hard-coded shadow configuration.
+    /// Mid-range voxel shadows: 2048² map, 3 cascades, 50-unit maximum distance.
+    Medium,
+    /// Full-quality voxel shadows: 4096² map, 4 cascades, 100-unit maximum distance.
+    /// Matches the original hard-coded cascades, at a sharper resolution.
     High,
 }
 
@@ -103,6 +124,22 @@ pub struct OcclusionExtension {
     /// Occlusion parameters passed to the shader
     #[uniform(100)]
     pub occlusion_uniforms: OcclusionUniforms,
+    /// Edge rendering parameters passed to the shader
+    #[uniform(101)]
+    pub edge_uniforms: EdgeUniforms,
+}
+
+impl OcclusionExtension {
+    /// An extension that draws `edges` but never makes voxels transparent.
+    pub fn edges_only(edges: &EdgeData) -> Self {
+        Self {
+            occlusion_uniforms: OcclusionUniforms {
+                mode: 0,
+                ..default()
+            },
+            edge_uniforms: EdgeUniforms::from_data(edges),
+        }
+    }
 }
 
 impl MaterialExtension for OcclusionExtension {
@@ -151,7 +188,7 @@ pub struct OcclusionUniforms {
     /// Padding for 16-byte alignment
     pub _padding3: u32,
     pub _padding4: u32,
-    /// Interior region minimum bounds (xyz), w = unused
+    /// Interior region minimum bounds (xyz), w = interior cutaway (1.0 = enabled)
     pub region_min: Vec4,
     /// Interior region maximum bounds (xyz), w = is_active (1.0 = active)
     pub region_max: Vec4,
@@ -178,6 +215,35 @@ impl Default for OcclusionUniforms {
     }
 }
 
+/// Uniform buffer for the map's edge rendering.
+#[derive(Clone, Copy, Debug, Default, ShaderType, PartialEq)]
+pub struct EdgeUniforms {
+    /// Edge style: 0 = None, 1 = Lines, 2 = Bevel
+    pub style: u32,
+    /// Line darkness or bevel strength (0.0 to 1.0)
+    pub strength: f32,
+    /// Width of the edge band as a fraction of a voxel
+    pub width: f32,
+    /// Padding for 16-byte alignment
+    pub _padding: f32,
+}
+
+impl EdgeUniforms {
+    pub fn from_data(edges: &EdgeData) -> Self {
+        let style = match edges.style {
+            EdgeStyle::Lines if edges.is_visible() => 1,
+            EdgeStyle::Bevel if edges.is_visible() => 2,
+            _ => 0,
+        };
+        Self {
+            style,
+            strength: edges.strength,
+            width: edges.width,
+            _padding: 0.0,
+        }
+    }
+}
+
 /// Resource to store the shared occlusion material handle.
 ///
 /// This handle is used by `update_occlusion_uniforms` to update the
@@ -315,6 +381,14 @@ impl DynamicOcclusionUniforms {
             region_max,
         }
     }
+
+    /// Flag the interior cutaway in `region_min.w`, only while a region is active.
+    fn with_interior_cutaway(mut self, enabled: bool) -> Self {
+        if enabled && self.region_max.w > 0.5 {
+            self.region_min.w = 1.0;
+        }
+        self
+    }
 }
 
 fn assemble_uniforms(
@@ -368,7 +442,7 @@ fn quantize_position(pos: Vec3, step: f32) -> Vec3 {
 #[allow(clippy::too_many_arguments)]
 pub fn update_occlusion_uniforms(
     config: Res<OcclusionConfig>,
-    camera_query: Option<Single<Ref<Transform>, With<GameCamera>>>,
+    camera_query: Option<Single<(Ref<Transform>, &GameCamera)>>,
     player_query: Option<Single<Ref<Transform>, With<Player>>>,
     material_handle: Option<Res<OcclusionMaterialHandle>>,
     mut materials: ResMut<Assets<OcclusionMaterial>>,
@@ -404,7 +478,10 @@ pub fn update_occlusion_uniforms(
     };
 
     // Recompute dynamic fields only when positions/interior changed or cache is empty.
-    let dynamic_input_changed = camera_ref.as_ref().map(|r| r.is_changed()).unwrap_or(false)
+    let dynamic_input_changed = camera_ref
+        .as_ref()
+        .map(|r| r.0.is_changed())
+        .unwrap_or(false)
         || player_ref.as_ref().map(|r| r.is_changed()).unwrap_or(false)
         || interior_state
             .as_ref()
@@ -415,8 +492,9 @@ pub fn update_occlusion_uniforms(
     let new_dynamic = if dynamic_input_changed {
         let camera_pos = camera_ref
             .as_ref()
-            .map(|t| t.translation)
+            .map(|c| c.0.translation)
             .unwrap_or(Vec3::new(0.0, 10.0, 10.0));
+        let interior_cutaway = camera_ref.as_ref().is_some_and(|c| c.1.interior_cutaway);
         let player_pos = player_ref
             .as_ref()
             .map(|t| t.translation)
@@ -427,11 +505,14 @@ pub fn update_occlusion_uniforms(
         let quant_step = config.uniform_quantization_step;
         let quantized_player = quantize_position(player_pos, quant_step);
         let quantized_camera = quantize_position(camera_pos, quant_step);
-        Some(DynamicOcclusionUniforms::new(
-            quantized_player,
-            quantized_camera,
-            interior_state.as_deref(),
-        ))
+        Some(
+            DynamicOcclusionUniforms::new(
+                quantized_player,
+                quantized_camera,
+                interior_state.as_deref(),
+            )
+            .with_interior_cutaway(interior_cutaway),
+        )
     } else {
         None
     };
@@ -513,6 +594,8 @@ pub fn update_occlusion_uniforms(
 /// - Red circle: Occlusion radius above player
 /// - Green line: Height threshold
 /// - Cyan box: Interior region (when detected)
+///
+/// While it is on, F4 switches between the Dithered and AlphaBlend techniques.
 pub fn debug_draw_occlusion_zone(
     mut config: ResMut<OcclusionConfig>,
     mut gizmos: Gizmos,
@@ -532,6 +615,11 @@ pub fn debug_draw_occlusion_zone(
         return;
     }
 
+    if keyboard.just_pressed(KeyCode::F4) {
+        config.technique = config.technique.toggled();
+        info!("[Occlusion] Technique: {:?}", config.technique);
+    }
+
     let Some(camera) = camera_query else {
         return;
     };
@@ -691,11 +779,12 @@ impl Plugin for OcclusionPlugin {
 /// Helper function to create an occlusion material with specified technique.
 ///
 /// This creates an ExtendedMaterial combining StandardMaterial (for PBR/shadows)
-/// with OcclusionExtension (for transparency).
+/// with OcclusionExtension (for transparency and the map's `edges`).
 ///
 /// Use this when spawning voxel chunks:
 /// ```rust,ignore
-/// let material_handle = create_occlusion_material(&mut materials, TransparencyTechnique::AlphaBlend);
+/// let material_handle =
+///     create_occlusion_material(&mut materials, TransparencyTechnique::AlphaBlend, &map.edges);
 /// commands.insert_resource(OcclusionMaterialHandle(material_handle.clone()));
 ///
 /// // Use material_handle for all chunks
@@ -708,6 +797,7 @@ impl Plugin for OcclusionPlugin {
 pub fn create_occlusion_material(
     materials: &mut Assets<OcclusionMaterial>,
     technique: TransparencyTechnique,
+    edges: &EdgeData,
 ) -> Handle<OcclusionMaterial> {
     let alpha_mode = match technique {
         // Mask(0.001): sets MAY_DISCARD so the depth prepass runs our custom fragment shader.
@@ -725,7 +815,10 @@ pub fn create_occlusion_material(
             alpha_mode,
             ..default()
         },
-        extension: OcclusionExtension::default(),
+        extension: OcclusionExtension {
+            edge_uniforms: EdgeUniforms::from_data(edges),
+            ..default()
+        },
     })
 }
 
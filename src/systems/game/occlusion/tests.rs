@@ -257,3 +257,79 @@ fn default_config_has_quantization_step() {
     let config = OcclusionConfig::default();
     assert_eq!(config.uniform_quantization_step, 0.25);
 }
+
+// ── Interior cutaway flag ────────────────────────────────────────────────
+
+#[test]
+fn interior_cutaway_only_flagged_while_region_active() {
+    use crate::systems::game::interior_detection::InteriorRegion;
+
+    let player = Vec3::new(1.0, 0.0, 1.0);
+    let cam = Vec3::new(0.0, 10.0, 0.0);
+
+    // Outdoors: nothing to cut even when the map enables it
+    let outside = DynamicOcclusionUniforms::new(player, cam, None).with_interior_cutaway(true);
+    assert_eq!(outside.region_min.w, 0.0);
+
+    let interior = InteriorState {
+        current_region: Some(InteriorRegion {
+            min: Vec3::new(-2.0, 1.5, -2.0),
+            max: Vec3::new(4.0, 100.0, 4.0),
+            ceiling_y: 3,
+            voxel_count: 9,
+        }),
+        ..default()
+    };
+    let enabled =
+        DynamicOcclusionUniforms::new(player, cam, Some(&interior)).with_interior_cutaway(true);
+    assert_eq!(enabled.region_min.w, 1.0);
+    assert_eq!(enabled.region_min.truncate(), Vec3::new(-2.0, 1.5, -2.0));
+
+    let disabled =
+        DynamicOcclusionUniforms::new(player, cam, Some(&interior)).with_interior_cutaway(false);
+    assert_eq!(disabled.region_min.w, 0.0);
+    assert_ne!(enabled, disabled);
+}
+
+#[test]
+fn technique_toggle_switches_between_dithered_and_alpha_blend() {
+    let dithered = TransparencyTechnique::Dithered;
+    assert_eq!(dithered.toggled(), TransparencyTechnique::AlphaBlend);
+    assert_eq!(dithered.toggled().toggled(), dithered);
+}
+
+// ── Edge uniforms ─────────────────────────────────────────────────────────
+
+#[test]
+fn edge_uniforms_encode_style() {
+    let mut edges = EdgeData::default();
+    assert_eq!(EdgeUniforms::from_data(&edges).style, 0);
+    edges.style = EdgeStyle::Lines;
+    assert_eq!(EdgeUniforms::from_data(&edges).style, 1);
+    edges.style = EdgeStyle::Bevel;
+    let uniforms = EdgeUniforms::from_data(&edges);
+    assert_eq!(uniforms.style, 2);
+    assert_eq!(uniforms.strength, edges.strength);
+    assert_eq!(uniforms.width, edges.width);
+}
+
+#[test]
+fn edge_uniforms_turn_off_invisible_edges() {
+    let edges = EdgeData {
+        style: EdgeStyle::Lines,
+        strength: 0.0,
+        width: 0.1,
+    };
+    assert_eq!(EdgeUniforms::from_data(&edges).style, 0);
+}
+
+#[test]
+fn edges_only_extension_has_no_occlusion() {
+    let edges = EdgeData {
+        style: EdgeStyle::Bevel,
+        ..Default::default()
+    };
+    let extension = OcclusionExtension::edges_only(&edges);
+    assert_eq!(extension.occlusion_uniforms.mode, 0);
+    assert_eq!(extension.edge_uniforms.style, 2);
+}
@@ -0,0 +1,256 @@
+//! Lightweight particle effects.
+//!
+//! Other systems request an effect by writing a [`ParticleBurst`] message, for
+//! example when a voxel is destroyed or an item is collected. Each particle is
+//! a small unlit cube that flies outward, falls and shrinks away.
+//!
+//! Particle entities are pooled: expired particles are hidden and handed to the
+//! next burst instead of being despawned, and the pool never grows past
+//! [`MAX_PARTICLES`], so bursts don't cause allocation spikes. Particles are
+//! [`GameEntity`]s, despawned with the rest of the world when the game ends;
+//! the pool notices and starts over on the next burst.
+
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::components::GameEntity;
+use super::map::spawner::get_sub_voxel_color;
+use crate::states::GameState;
+use crate::systems::accessibility::ColorPalette;
+
+/// Upper bound on particle entities alive at once (active + pooled).
+pub const MAX_PARTICLES: usize = 512;
+
+/// Edge length of a particle cube at full scale, in world units.
+const PARTICLE_SIZE: f32 = 0.08;
+
+/// Downward acceleration applied to particles.
+const PARTICLE_GRAVITY: f32 = 9.8;
+
+/// Request to emit a burst of particles.
+// Emitters arrive with destructible terrain and collectable items.
+#[allow(dead_code)]
+#[derive(Message, Clone, Debug)]
+pub struct ParticleBurst {
+    /// World position the particles fly out from
+    pub position: Vec3,
+    /// Particle color
+    pub color: Color,
+    /// Number of particles
+    pub count: u32,
+    /// Initial speed in world units per second
+    pub speed: f32,
+    /// Seconds before a particle disappears
+    pub lifetime: f32,
+}
+
+#[allow(dead_code)]
+impl ParticleBurst {
+    /// Debris for a voxel destroyed at `pos`, colored like the voxel's sub-voxels.
+    pub fn voxel_destroyed(pos: IVec3, palette: ColorPalette) -> Self {
+        Self {
+            position: pos.as_vec3(),
+            color: get_sub_voxel_color(palette, pos.x, pos.y, pos.z, 4, 4, 4),
+            count: 16,
+            speed: 2.5,
+            lifetime: 0.8,
+        }
+    }
+
+    /// Sparkle for an item collected at `position`.
+    pub fn item_collected(position: Vec3, color: Color) -> Self {
+        Self {
+            position,
+            color,
+            count: 10,
+            speed: 1.5,
+            lifetime: 0.6,
+        }
+    }
+}
+
+/// A live or pooled particle.
+#[derive(Component, Debug)]
+pub struct Particle {
+    velocity: Vec3,
+    age: f32,
+    lifetime: f32,
+}
+
+/// Small xorshift generator for effects that only need to look random.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Xorshift {
+    state: u32,
+}
+
+impl Xorshift {
+    /// Next pseudo-random value in `[0, 1)`.
+    pub(crate) fn next_unit(&mut self) -> f32 {
+        let mut x = if self.state == 0 {
+            0x9E37_79B9
+        } else {
+            self.state
+        };
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        (x >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// Pool of particle entities and the shared assets they render with.
+#[derive(Resource, Default)]
+pub struct ParticlePool {
+    /// Hidden particles ready for reuse
+    free: Vec<Entity>,
+    /// Particle entities spawned so far (active + free)
+    total: usize,
+    mesh: Option<Handle<Mesh>>,
+    /// One material per color, keyed by its sRGB bytes
+    materials: HashMap<[u8; 4], Handle<StandardMaterial>>,
+    /// Generator for particle directions
+    rng: Xorshift,
+}
+
+impl ParticlePool {
+    fn mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        self.mesh
+            .get_or_insert_with(|| meshes.add(Cuboid::from_length(PARTICLE_SIZE)))
+            .clone()
+    }
+
+    fn material(
+        &mut self,
+        color: Color,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        self.materials
+            .entry(color.to_srgba().to_u8_array())
+            .or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: color,
+                    unlit: true,
+                    ..default()
+                })
+            })
+            .clone()
+    }
+
+    /// Random outward velocity biased upward.
+    fn random_velocity(&mut self, speed: f32) -> Vec3 {
+        let direction = Vec3::new(
+            self.rng.next_unit() * 2.0 - 1.0,
+            0.5 + self.rng.next_unit(),
+            self.rng.next_unit() * 2.0 - 1.0,
+        )
+        .normalize();
+        direction * speed * (0.5 + self.rng.next_unit() * 0.5)
+    }
+}
+
+/// Turn [`ParticleBurst`] messages into particles, reusing pooled entities first.
+pub fn emit_particle_bursts(
+    mut commands: Commands,
+    mut bursts: MessageReader<ParticleBurst>,
+    mut pool: ResMut<ParticlePool>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut particles: Query<(
+        &mut Particle,
+        &mut Transform,
+        &mut Visibility,
+        &mut MeshMaterial3d<StandardMaterial>,
+    )>,
+) {
+    // Forget particles despawned with the game world
+    pool.free.retain(|&entity| particles.contains(entity));
+    pool.total = particles.iter().len();
+
+    for burst in bursts.read() {
+        let mesh = pool.mesh(&mut meshes);
+        let material = pool.material(burst.color, &mut materials);
+
+        for _ in 0..burst.count {
+            let particle = Particle {
+                velocity: pool.random_velocity(burst.speed),
+                age: 0.0,
+                lifetime: burst.lifetime,
+            };
+            let transform = Transform::from_translation(burst.position);
+
+            if let Some(entity) = pool.free.pop() {
+                let Ok((mut slot, mut slot_transform, mut visibility, mut slot_material)) =
+                    particles.get_mut(entity)
+                else {
+                    // Despawned behind the pool's back
+                    pool.total = pool.total.saturating_sub(1);
+                    continue;
+                };
+                *slot = particle;
+                *slot_transform = transform;
+                *visibility = Visibility::Visible;
+                slot_material.0 = material.clone();
+            } else if pool.total < MAX_PARTICLES {
+                commands.spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    transform,
+                    Visibility::Visible,
+                    NotShadowCaster,
+                    particle,
+                    GameEntity,
+                ));
+                pool.total += 1;
+            } else {
+                // Pool exhausted; drop the rest of this burst
+                break;
+            }
+        }
+    }
+}
+
+/// Move, shrink and expire active particles, returning expired ones to the pool.
+pub fn update_particles(
+    time: Res<Time>,
+    mut pool: ResMut<ParticlePool>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Visibility)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut particle, mut transform, mut visibility) in &mut particles {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            continue;
+        }
+
+        particle.velocity.y -= PARTICLE_GRAVITY * dt;
+        transform.translation += particle.velocity * dt;
+        transform.scale = Vec3::splat(1.0 - particle.age / particle.lifetime);
+    }
+}
+
+/// Plugin that sets up the particle message, pool and systems.
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ParticleBurst>()
+            .init_resource::<ParticlePool>()
+            .add_systems(
+                Update,
+                (emit_particle_bursts, update_particles)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
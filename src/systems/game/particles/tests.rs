@@ -0,0 +1,115 @@
+use super::*;
+use std::time::Duration;
+
+fn particle_app() -> App {
+    let mut app = App::new();
+    app.add_message::<ParticleBurst>()
+        .init_resource::<ParticlePool>()
+        .init_resource::<Assets<Mesh>>()
+        .init_resource::<Assets<StandardMaterial>>()
+        .init_resource::<Time>()
+        .add_systems(Update, (emit_particle_bursts, update_particles).chain());
+    app
+}
+
+fn emit(app: &mut App, count: u32) {
+    app.world_mut().write_message(ParticleBurst {
+        position: Vec3::ZERO,
+        color: Color::WHITE,
+        count,
+        speed: 1.0,
+        lifetime: 0.5,
+    });
+    // No time passes on the emitting frame
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::ZERO);
+    app.update();
+}
+
+fn advance(app: &mut App, secs: f32) {
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs_f32(secs));
+    app.update();
+}
+
+fn particle_count(app: &mut App) -> usize {
+    app.world_mut()
+        .query::<&Particle>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn burst_spawns_requested_particles() {
+    let mut app = particle_app();
+    emit(&mut app, 8);
+
+    assert_eq!(particle_count(&mut app), 8);
+    assert_eq!(app.world().resource::<ParticlePool>().total, 8);
+}
+
+#[test]
+fn expired_particles_are_reused_by_the_next_burst() {
+    let mut app = particle_app();
+    emit(&mut app, 8);
+    advance(&mut app, 1.0);
+    assert_eq!(app.world().resource::<ParticlePool>().free.len(), 8);
+
+    emit(&mut app, 5);
+    let pool = app.world().resource::<ParticlePool>();
+    assert_eq!(pool.free.len(), 3);
+    assert_eq!(pool.total, 8);
+    assert_eq!(particle_count(&mut app), 8);
+}
+
+#[test]
+fn pool_starts_over_after_the_game_world_is_despawned() {
+    let mut app = particle_app();
+    emit(&mut app, 8);
+    let particles: Vec<Entity> = app
+        .world_mut()
+        .query_filtered::<Entity, (With<Particle>, With<GameEntity>)>()
+        .iter(app.world())
+        .collect();
+    assert_eq!(particles.len(), 8);
+    for entity in particles {
+        app.world_mut().despawn(entity);
+    }
+
+    emit(&mut app, 5);
+    assert_eq!(particle_count(&mut app), 5);
+    assert_eq!(app.world().resource::<ParticlePool>().total, 5);
+}
+
+#[test]
+fn pool_never_exceeds_max_particles() {
+    let mut app = particle_app();
+    emit(&mut app, MAX_PARTICLES as u32 + 50);
+
+    assert_eq!(particle_count(&mut app), MAX_PARTICLES);
+}
+
+#[test]
+fn same_color_shares_one_material() {
+    let mut app = particle_app();
+    emit(&mut app, 4);
+    emit(&mut app, 4);
+
+    assert_eq!(
+        app.world()
+            .resource::<Assets<StandardMaterial>>()
+            .iter()
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn xorshift_values_stay_in_the_unit_range() {
+    let mut rng = Xorshift::default();
+    let values: Vec<f32> = (0..1000).map(|_| rng.next_unit()).collect();
+    assert!(values.iter().all(|v| (0.0..1.0).contains(v)));
+    assert!(values.windows(2).any(|pair| pair[0] != pair[1]));
+}
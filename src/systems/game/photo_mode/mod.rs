@@ -0,0 +1,591 @@
+//! Photo mode: a free-fly camera for screenshots, opened from the pause menu.
+//!
+//! Entering [`GameState::PhotoMode`] freezes the game (gameplay systems only
+//! run in `InGame`, and virtual time is paused so animations hold still),
+//! hides the HUD and lets the game camera fly within [`MAX_DISTANCE`] of the
+//! player with roll, field of view and exposure controls. The camera is swept
+//! through the spatial grid as a sphere of [`CAMERA_RADIUS`], so it slides
+//! along walls instead of flying into them. Photos are rendered
+//! offscreen at [`PHOTO_SCALE`] times the window size, without the UI, and
+//! saved as PNG. Leaving photo mode puts the camera back and returns to the
+//! pause menu.
+
+use bevy::camera::{Exposure, RenderTarget};
+use bevy::input::gamepad::{GamepadAxis, GamepadButton};
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured};
+use bevy::window::PrimaryWindow;
+use std::path::PathBuf;
+
+use super::components::{GameCamera, Player, SubVoxel};
+use super::gamepad::{apply_deadzone, ActiveGamepad, GamepadSettings};
+use super::resources::{GridHit, SpatialGrid};
+use crate::states::GameState;
+use crate::systems::localization::Localization;
+
+/// Flying speed in units per second.
+pub const MOVE_SPEED: f32 = 5.0;
+
+/// Speed multiplier while Shift (or a clicked-in left stick) is held.
+pub const FAST_MULTIPLIER: f32 = 3.0;
+
+/// How far from the player the camera may fly.
+pub const MAX_DISTANCE: f32 = 30.0;
+
+/// Radius of the sphere the camera is swept as, keeping it out of walls.
+pub const CAMERA_RADIUS: f32 = 0.2;
+
+/// Walls the camera slides along in one frame.
+const MAX_SLIDES: usize = 3;
+
+/// Gap left between the camera and a wall it stops at.
+const WALL_SKIN: f32 = 0.01;
+
+/// Turn rate of the arrow keys and right stick, in radians per second.
+const LOOK_SPEED: f32 = 1.8;
+
+/// Radians of turn per pixel of mouse movement.
+const MOUSE_SENSITIVITY: f32 = 0.003;
+
+/// Roll rate in radians per second.
+const ROLL_SPEED: f32 = 1.0;
+
+/// Field of view change in degrees per second.
+const FOV_SPEED: f32 = 30.0;
+
+/// Field of view range in degrees.
+pub const FOV_RANGE: (f32, f32) = (10.0, 120.0);
+
+/// Exposure change in EV per second.
+const EXPOSURE_SPEED: f32 = 2.0;
+
+/// Exposure range (EV100); lower is brighter.
+pub const EXPOSURE_RANGE: (f32, f32) = (3.0, 16.0);
+
+/// Photos are rendered at this multiple of the window resolution.
+pub const PHOTO_SCALE: u32 = 2;
+
+/// Largest photo side in pixels, to stay within GPU texture limits.
+pub const MAX_PHOTO_SIZE: u32 = 8192;
+
+/// Photo mode controls read this frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct PhotoInput {
+    /// Right, up and forward movement, each in [-1, 1]
+    pub movement: Vec3,
+    pub fast: bool,
+    /// Yaw and pitch to turn this frame, in radians
+    pub look: Vec2,
+    /// Roll direction in [-1, 1]; positive rolls clockwise
+    pub roll: f32,
+    /// Field of view direction in [-1, 1]; positive widens
+    pub fov: f32,
+    /// Exposure direction in [-1, 1]; positive brightens
+    pub exposure: f32,
+    /// Reset roll, field of view and exposure
+    pub reset: bool,
+    pub capture: bool,
+    pub exit: bool,
+}
+
+/// The free-fly camera's pose and lens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhotoCamera {
+    pub position: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+    /// Vertical field of view in radians
+    pub fov: f32,
+    pub ev100: f32,
+    /// Point the camera stays within [`MAX_DISTANCE`] of
+    pub anchor: Vec3,
+}
+
+impl PhotoCamera {
+    /// Start from the game camera's `transform` and lens.
+    pub fn from_transform(transform: &Transform, fov: f32, ev100: f32, anchor: Vec3) -> Self {
+        let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+        Self {
+            position: transform.translation,
+            yaw,
+            pitch,
+            roll,
+            fov,
+            ev100,
+            anchor,
+        }
+    }
+
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, self.roll)
+    }
+
+    pub fn transform(&self) -> Transform {
+        Transform::from_translation(self.position).with_rotation(self.rotation())
+    }
+
+    /// Apply a frame of `input` lasting `delta` seconds.
+    pub fn update(&mut self, input: &PhotoInput, delta: f32) {
+        self.yaw -= input.look.x;
+        self.pitch = (self.pitch + input.look.y).clamp(-1.5, 1.5);
+        self.roll = (self.roll - input.roll * ROLL_SPEED * delta)
+            .clamp(-std::f32::consts::FRAC_PI_2, std::f32::consts::FRAC_PI_2);
+
+        let (min_fov, max_fov) = FOV_RANGE;
+        self.fov = (self.fov + (input.fov * FOV_SPEED * delta).to_radians())
+            .clamp(min_fov.to_radians(), max_fov.to_radians());
+        let (min_ev, max_ev) = EXPOSURE_RANGE;
+        self.ev100 = (self.ev100 - input.exposure * EXPOSURE_SPEED * delta).clamp(min_ev, max_ev);
+
+        // Fly where the camera looks, ignoring roll
+        let heading = Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.0);
+        let direction = heading * Vec3::new(input.movement.x, 0.0, -input.movement.z)
+            + Vec3::Y * input.movement.y;
+        let speed = MOVE_SPEED * if input.fast { FAST_MULTIPLIER } else { 1.0 };
+        let position = self.position + direction.clamp_length_max(1.0) * speed * delta;
+        self.position = self.anchor + (position - self.anchor).clamp_length_max(MAX_DISTANCE);
+    }
+}
+
+/// Where a camera moving from `from` to `to` ends up in `grid`, sliding
+/// along the boxes of entities `is_solid` accepts.
+///
+/// Boxes holding the camera's center are passed through, so a camera that
+/// starts inside a wall can still fly out of it.
+pub fn sweep_camera(
+    grid: &SpatialGrid,
+    from: Vec3,
+    to: Vec3,
+    is_solid: impl Fn(Entity) -> bool,
+) -> Vec3 {
+    let mut position = from;
+    let mut motion = to - from;
+    for _ in 0..MAX_SLIDES {
+        let hit = grid.sweep_sphere(position, CAMERA_RADIUS, motion, |entity| {
+            is_solid(entity)
+                && grid
+                    .bounds(entity)
+                    .is_none_or(|(min, max)| position.cmplt(min).any() || position.cmpgt(max).any())
+        });
+        let Some(GridHit {
+            distance, normal, ..
+        }) = hit
+        else {
+            return position + motion;
+        };
+        let direction = motion.normalize_or_zero();
+        position += direction * (distance - WALL_SKIN).max(0.0);
+        let remaining = motion - direction * distance;
+        motion = remaining - normal * remaining.dot(normal);
+    }
+    position
+}
+
+/// Size of a photo of a `width` × `height` window.
+pub fn photo_size(width: u32, height: u32) -> UVec2 {
+    let size = UVec2::new(width.max(1), height.max(1)) * PHOTO_SCALE;
+    let largest = size.max_element();
+    if largest <= MAX_PHOTO_SIZE {
+        size
+    } else {
+        // Keep the aspect ratio
+        (size.as_vec2() * MAX_PHOTO_SIZE as f32 / largest as f32)
+            .round()
+            .as_uvec2()
+            .max(UVec2::ONE)
+    }
+}
+
+/// Folder photos are saved in: the user's Pictures folder, or
+/// `screenshots/` when there isn't one.
+fn photo_dir() -> PathBuf {
+    match dirs::picture_dir() {
+        Some(pictures) => pictures.join("adrakestory"),
+        None => PathBuf::from("screenshots"),
+    }
+}
+
+/// File name of a photo taken `millis` milliseconds after the Unix epoch.
+pub fn photo_file_name(millis: u128) -> String {
+    format!("photo_{}.png", millis)
+}
+
+/// Game camera settings to restore when photo mode ends.
+#[derive(Debug, Clone)]
+struct SavedCamera {
+    transform: Transform,
+    projection: Projection,
+    /// EV100 of the camera's `Exposure`, if it had one
+    ev100: Option<f32>,
+}
+
+/// State of an open photo mode; present only in [`GameState::PhotoMode`].
+#[derive(Resource, Debug)]
+pub struct PhotoMode {
+    pub camera: PhotoCamera,
+    /// Lens the camera started with, for resets
+    initial_fov: f32,
+    initial_ev100: f32,
+    saved: SavedCamera,
+    /// UI roots hidden while in photo mode, with their previous visibility
+    hidden_ui: Vec<(Entity, Visibility)>,
+    /// Where the last photo was saved
+    pub last_photo: Option<PathBuf>,
+}
+
+/// Root of the photo mode help overlay.
+#[derive(Component)]
+pub struct PhotoModeOverlay;
+
+/// Status line of the overlay: lens settings and the last saved photo.
+#[derive(Component)]
+pub struct PhotoModeStatus;
+
+/// Freeze the game, hide the HUD and hand the camera to photo mode.
+#[allow(clippy::type_complexity)]
+pub fn enter_photo_mode(
+    mut commands: Commands,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    camera: Option<Single<(Entity, &Transform, &Projection, Option<&Exposure>), With<GameCamera>>>,
+    player: Option<Single<&Transform, (With<Player>, Without<GameCamera>)>>,
+    ui_roots: Query<(Entity, &Visibility), (With<Node>, Without<ChildOf>)>,
+    localization: Res<Localization>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(camera) = camera else {
+        warn!("Photo mode needs the game camera");
+        next_state.set(GameState::Paused);
+        return;
+    };
+    let (camera, transform, projection, exposure) = *camera;
+    let fov = match projection {
+        Projection::Perspective(perspective) => perspective.fov,
+        _ => PerspectiveProjection::default().fov,
+    };
+    let ev100 = exposure.copied().unwrap_or_default().ev100;
+    let anchor = player.map_or(transform.translation, |player| player.translation);
+
+    virtual_time.pause();
+    commands.entity(camera).insert(Exposure { ev100 });
+
+    let hidden_ui: Vec<(Entity, Visibility)> = ui_roots
+        .iter()
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .map(|(entity, visibility)| (entity, *visibility))
+        .collect();
+    for &(entity, _) in &hidden_ui {
+        commands.entity(entity).insert(Visibility::Hidden);
+    }
+
+    commands.insert_resource(PhotoMode {
+        camera: PhotoCamera::from_transform(transform, fov, ev100, anchor),
+        initial_fov: fov,
+        initial_ev100: ev100,
+        saved: SavedCamera {
+            transform: *transform,
+            projection: projection.clone(),
+            ev100: exposure.map(|exposure| exposure.ev100),
+        },
+        hidden_ui,
+        last_photo: None,
+    });
+    commands.init_resource::<PhotoInput>();
+
+    let text = |text: String, size: f32| {
+        (
+            Text::new(text),
+            TextFont {
+                font_size: size,
+                ..default()
+            },
+            TextColor(Color::srgba(0.9, 0.9, 0.9, 0.9)),
+        )
+    };
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(16.0),
+                bottom: Val::Px(16.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            GlobalZIndex(70),
+            PhotoModeOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn(text(localization.get("photo.title").to_string(), 20.0));
+            parent.spawn(text(localization.get("photo.help").to_string(), 14.0));
+            parent.spawn((text(String::new(), 14.0), PhotoModeStatus));
+        });
+    info!("Entered photo mode");
+}
+
+/// Read keyboard, mouse and gamepad controls into [`PhotoInput`].
+#[allow(clippy::too_many_arguments)]
+pub fn gather_photo_input(
+    time: Res<Time<Real>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepads: Query<&Gamepad>,
+    settings: Res<GamepadSettings>,
+    mut input: ResMut<PhotoInput>,
+) {
+    let delta = time.delta_secs();
+    let axis = |negative: KeyCode, positive: KeyCode| {
+        keyboard.pressed(positive) as i32 as f32 - keyboard.pressed(negative) as i32 as f32
+    };
+    let mut next = PhotoInput {
+        movement: Vec3::new(
+            axis(KeyCode::KeyA, KeyCode::KeyD),
+            axis(KeyCode::KeyQ, KeyCode::KeyE),
+            axis(KeyCode::KeyS, KeyCode::KeyW),
+        ),
+        fast: keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        look: Vec2::new(
+            axis(KeyCode::ArrowLeft, KeyCode::ArrowRight),
+            axis(KeyCode::ArrowDown, KeyCode::ArrowUp),
+        ) * LOOK_SPEED
+            * delta,
+        roll: axis(KeyCode::KeyZ, KeyCode::KeyC),
+        fov: axis(KeyCode::Equal, KeyCode::Minus),
+        exposure: axis(KeyCode::BracketLeft, KeyCode::BracketRight),
+        reset: keyboard.just_pressed(KeyCode::KeyR),
+        capture: keyboard.any_just_pressed([KeyCode::Space, KeyCode::KeyP]),
+        exit: keyboard.any_just_pressed([KeyCode::Escape, KeyCode::Backspace]),
+    };
+
+    // Drag with either mouse button to look around
+    if mouse_buttons.any_pressed([MouseButton::Left, MouseButton::Right]) {
+        let motion = mouse_motion.delta * MOUSE_SENSITIVITY;
+        next.look += Vec2::new(motion.x, -motion.y);
+    }
+
+    if let Some(gamepad) = active_gamepad
+        .0
+        .and_then(|entity| gamepads.get(entity).ok())
+    {
+        let stick = |x, y| {
+            apply_deadzone(
+                Vec2::new(gamepad.get(x).unwrap_or(0.0), gamepad.get(y).unwrap_or(0.0)),
+                settings.stick_deadzone,
+            )
+        };
+        let trigger = |axis| {
+            let value = gamepad.get(axis).unwrap_or(0.0);
+            if value < settings.trigger_deadzone {
+                0.0
+            } else {
+                value
+            }
+        };
+        let button_axis = |negative: GamepadButton, positive: GamepadButton| {
+            gamepad.pressed(positive) as i32 as f32 - gamepad.pressed(negative) as i32 as f32
+        };
+
+        let left = stick(GamepadAxis::LeftStickX, GamepadAxis::LeftStickY);
+        let mut right = stick(GamepadAxis::RightStickX, GamepadAxis::RightStickY);
+        if settings.invert_camera_y {
+            right.y = -right.y;
+        }
+        next.movement += Vec3::new(
+            left.x,
+            trigger(GamepadAxis::RightZ) - trigger(GamepadAxis::LeftZ),
+            left.y,
+        );
+        next.fast |= gamepad.pressed(GamepadButton::LeftThumb);
+        next.look += right * LOOK_SPEED * delta;
+        next.roll += button_axis(GamepadButton::LeftTrigger, GamepadButton::RightTrigger);
+        next.fov += button_axis(GamepadButton::DPadUp, GamepadButton::DPadDown);
+        next.exposure += button_axis(GamepadButton::DPadLeft, GamepadButton::DPadRight);
+        next.reset |= gamepad.just_pressed(GamepadButton::RightThumb);
+        next.capture |= gamepad.just_pressed(GamepadButton::South);
+        next.exit |= gamepad.any_just_pressed([GamepadButton::East, GamepadButton::Start]);
+    }
+
+    next.movement = next.movement.clamp(Vec3::NEG_ONE, Vec3::ONE);
+    next.roll = next.roll.clamp(-1.0, 1.0);
+    next.fov = next.fov.clamp(-1.0, 1.0);
+    next.exposure = next.exposure.clamp(-1.0, 1.0);
+    *input = next;
+}
+
+/// Fly the camera and apply the lens settings.
+pub fn fly_photo_camera(
+    time: Res<Time<Real>>,
+    input: Res<PhotoInput>,
+    mut photo: ResMut<PhotoMode>,
+    spatial_grid: Option<Res<SpatialGrid>>,
+    sub_voxels: Query<(), With<SubVoxel>>,
+    camera: Single<(&mut Transform, &mut Projection, &mut Exposure), With<GameCamera>>,
+) {
+    if input.reset {
+        let (fov, ev100) = (photo.initial_fov, photo.initial_ev100);
+        photo.camera.roll = 0.0;
+        photo.camera.fov = fov;
+        photo.camera.ev100 = ev100;
+    }
+    let from = photo.camera.position;
+    photo.camera.update(&input, time.delta_secs());
+    if let Some(grid) = spatial_grid {
+        let to = photo.camera.position;
+        photo.camera.position = sweep_camera(&grid, from, to, |entity| sub_voxels.contains(entity));
+    }
+
+    let (mut transform, mut projection, mut exposure) = camera.into_inner();
+    *transform = photo.camera.transform();
+    if let Projection::Perspective(perspective) = projection.as_mut() {
+        perspective.fov = photo.camera.fov;
+    }
+    exposure.ev100 = photo.camera.ev100;
+}
+
+/// Render the current view offscreen at [`PHOTO_SCALE`] and save it.
+pub fn take_photo(
+    mut commands: Commands,
+    input: Res<PhotoInput>,
+    mut photo: ResMut<PhotoMode>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<Entity, With<GameCamera>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !input.capture {
+        return;
+    }
+    let dir = photo_dir();
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        error!("Cannot create photo folder {}: {}", dir.display(), e);
+        return;
+    }
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = dir.join(photo_file_name(millis));
+
+    let size = photo_size(window.physical_width(), window.physical_height());
+    let image = images.add(Image::new_target_texture(
+        size.x,
+        size.y,
+        TextureFormat::Rgba8UnormSrgb,
+        None,
+    ));
+
+    // A copy of the game camera renders the photo; the UI only draws on
+    // the window, so it stays out of the picture
+    let photo_camera = commands
+        .entity(*camera)
+        .clone_and_spawn_with_opt_out(|builder| {
+            builder.deny::<(GameCamera, RenderTarget)>();
+        })
+        .insert(RenderTarget::Image(image.clone().into()))
+        .id();
+    commands
+        .spawn(Screenshot::image(image))
+        .observe(save_to_disk(path.clone()))
+        .observe(move |_: On<ScreenshotCaptured>, mut commands: Commands| {
+            commands.entity(photo_camera).despawn();
+        });
+
+    info!("Taking photo {}×{} to {}", size.x, size.y, path.display());
+    photo.last_photo = Some(path);
+}
+
+/// Leave photo mode for the pause menu.
+pub fn exit_photo_mode_input(input: Res<PhotoInput>, mut next_state: ResMut<NextState<GameState>>) {
+    if input.exit {
+        next_state.set(GameState::Paused);
+    }
+}
+
+/// Show the lens settings and where the last photo went.
+pub fn update_photo_status(
+    photo: Res<PhotoMode>,
+    localization: Res<Localization>,
+    mut status: Query<&mut Text, With<PhotoModeStatus>>,
+) {
+    if !photo.is_changed() {
+        return;
+    }
+    let fov = format!("{:.0}", photo.camera.fov.to_degrees());
+    let ev = format!("{:.1}", photo.camera.ev100);
+    let mut line = localization.format("photo.status", &[("fov", &fov), ("ev", &ev)]);
+    if let Some(path) = &photo.last_photo {
+        let path = path.display().to_string();
+        line.push('\n');
+        line.push_str(&localization.format("photo.saved", &[("path", &path)]));
+    }
+    for mut text in &mut status {
+        text.0 = line.clone();
+    }
+}
+
+/// Put the camera and HUD back and unfreeze time.
+#[allow(clippy::type_complexity)]
+pub fn leave_photo_mode(
+    mut commands: Commands,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    photo: Option<Res<PhotoMode>>,
+    camera: Option<Single<(Entity, &mut Transform, &mut Projection), With<GameCamera>>>,
+    overlay: Query<Entity, With<PhotoModeOverlay>>,
+) {
+    virtual_time.unpause();
+    for entity in &overlay {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<PhotoInput>();
+    let Some(photo) = photo else {
+        return;
+    };
+    commands.remove_resource::<PhotoMode>();
+
+    for &(entity, visibility) in &photo.hidden_ui {
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.insert(visibility);
+        }
+    }
+    if let Some(camera) = camera {
+        let (entity, mut transform, mut projection) = camera.into_inner();
+        *transform = photo.saved.transform;
+        *projection = photo.saved.projection.clone();
+        match photo.saved.ev100 {
+            Some(ev100) => commands.entity(entity).insert(Exposure { ev100 }),
+            None => commands.entity(entity).remove::<Exposure>(),
+        };
+    }
+    info!("Left photo mode");
+}
+
+/// Plugin for photo mode.
+pub struct PhotoModePlugin;
+
+impl Plugin for PhotoModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::PhotoMode), enter_photo_mode)
+            .add_systems(
+                Update,
+                (
+                    gather_photo_input,
+                    fly_photo_camera,
+                    take_photo,
+                    update_photo_status,
+                    exit_photo_mode_input,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::PhotoMode))
+                    .run_if(resource_exists::<PhotoMode>),
+            )
+            .add_systems(OnExit(GameState::PhotoMode), leave_photo_mode);
+    }
+}
+
+#[cfg(test)]
+mod tests;
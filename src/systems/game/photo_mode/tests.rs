@@ -0,0 +1,117 @@
+use super::*;
+
+fn camera_at(position: Vec3) -> PhotoCamera {
+    PhotoCamera::from_transform(
+        &Transform::from_translation(position),
+        60f32.to_radians(),
+        9.7,
+        Vec3::ZERO,
+    )
+}
+
+#[test]
+fn camera_round_trips_its_transform() {
+    let transform =
+        Transform::from_xyz(3.0, 8.0, 5.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y);
+    let camera = PhotoCamera::from_transform(&transform, 1.0, 9.7, Vec3::ZERO);
+    let round_trip = camera.transform();
+    assert!(round_trip.translation.distance(transform.translation) < 1e-5);
+    assert!(round_trip.rotation.angle_between(transform.rotation) < 1e-4);
+}
+
+#[test]
+fn camera_flies_where_it_looks() {
+    let mut camera = camera_at(Vec3::ZERO);
+    camera.yaw = std::f32::consts::FRAC_PI_2; // facing -X
+    let input = PhotoInput {
+        movement: Vec3::Z,
+        ..default()
+    };
+    camera.update(&input, 1.0);
+    assert!(camera.position.distance(Vec3::new(-MOVE_SPEED, 0.0, 0.0)) < 1e-4);
+
+    // Roll doesn't change where "up" takes the camera
+    camera.roll = 1.0;
+    let up = PhotoInput {
+        movement: Vec3::Y,
+        ..default()
+    };
+    let before = camera.position;
+    camera.update(&up, 0.5);
+    assert!((camera.position - before).distance(Vec3::Y * MOVE_SPEED * 0.5) < 1e-4);
+}
+
+#[test]
+fn camera_stays_near_the_player() {
+    let mut camera = camera_at(Vec3::new(MAX_DISTANCE - 1.0, 0.0, 0.0));
+    camera.yaw = -std::f32::consts::FRAC_PI_2; // facing +X
+    let input = PhotoInput {
+        movement: Vec3::Z,
+        fast: true,
+        ..default()
+    };
+    camera.update(&input, 10.0);
+    assert!((camera.position.length() - MAX_DISTANCE).abs() < 1e-3);
+}
+
+#[test]
+fn lens_settings_are_clamped() {
+    let mut camera = camera_at(Vec3::ZERO);
+    let input = PhotoInput {
+        fov: 1.0,
+        exposure: 1.0,
+        roll: 1.0,
+        ..default()
+    };
+    camera.update(&input, 100.0);
+    assert!((camera.fov - FOV_RANGE.1.to_radians()).abs() < 1e-5);
+    assert_eq!(camera.ev100, EXPOSURE_RANGE.0);
+    assert_eq!(camera.roll, -std::f32::consts::FRAC_PI_2);
+}
+
+#[test]
+fn photo_size_scales_the_window_within_limits() {
+    assert_eq!(photo_size(1280, 720), UVec2::new(2560, 1440));
+    let huge = photo_size(7680, 4320);
+    assert_eq!(huge.x, MAX_PHOTO_SIZE);
+    assert_eq!(huge.y, 4608);
+    assert_eq!(photo_file_name(42), "photo_42.png");
+}
+
+fn wall_grid() -> SpatialGrid {
+    let mut grid = SpatialGrid::default();
+    // A wall across X = 2..3
+    grid.insert(
+        Entity::from_raw_u32(1).unwrap(),
+        Vec3::new(2.0, -5.0, -5.0),
+        Vec3::new(3.0, 5.0, 5.0),
+    );
+    grid
+}
+
+#[test]
+fn camera_stops_at_walls_and_slides_along_them() {
+    let grid = wall_grid();
+    let stopped = sweep_camera(&grid, Vec3::ZERO, Vec3::new(4.0, 0.0, 0.0), |_| true);
+    assert!((stopped.x - (2.0 - CAMERA_RADIUS)).abs() < 0.02);
+
+    let slid = sweep_camera(&grid, Vec3::ZERO, Vec3::new(4.0, 0.0, 2.0), |_| true);
+    assert!(slid.x < 2.0 - CAMERA_RADIUS + 0.02);
+    assert!((slid.z - 2.0).abs() < 0.02);
+
+    // Not solid: flown through
+    let through = sweep_camera(&grid, Vec3::ZERO, Vec3::new(4.0, 0.0, 0.0), |_| false);
+    assert_eq!(through, Vec3::new(4.0, 0.0, 0.0));
+}
+
+#[test]
+fn camera_inside_a_wall_can_fly_out() {
+    let grid = wall_grid();
+    let out = sweep_camera(
+        &grid,
+        Vec3::new(2.5, 0.0, 0.0),
+        Vec3::new(4.0, 0.0, 0.0),
+        |_| true,
+    );
+    assert_eq!(out, Vec3::new(4.0, 0.0, 0.0));
+}
@@ -5,31 +5,30 @@
 //! - Updating player position based on velocity
 //! - Ground collision detection
 //! - Setting grounded state
+//! - Keeping NPCs in the spatial grid and pushing the player out of them
 
-use super::collision::get_sub_voxel_bounds;
+use super::collision::{get_sub_voxel_bounds, move_vertically, CharacterBody};
 use super::components::{Npc, Player, SubVoxel};
-use super::resources::{PreFetchedCollisionEntities, SpatialGrid};
+use super::resources::{PhysicsConfig, PreFetchedCollisionEntities, SpatialGrid};
 use crate::diagnostics::FrameProfiler;
 use crate::profile_scope;
 use bevy::prelude::*;
 
-const GRAVITY: f32 = -32.0;
-const GROUND_DETECTION_EPSILON: f32 = 0.001;
-
 /// System that applies gravity to the player's velocity.
 ///
-/// Gravity is applied as a constant downward acceleration.
-/// Delta time is clamped to prevent physics issues when the window
-/// regains focus after being minimized.
+/// Gravity is applied as a constant downward acceleration
+/// ([`PhysicsConfig::gravity`]).
+/// Runs in `FixedUpdate`, so `Time` advances by the fixed physics timestep.
 pub fn apply_gravity(
     time: Res<Time>,
+    physics: Res<PhysicsConfig>,
     mut player: Single<&mut Player>,
     profiler: Option<Res<FrameProfiler>>,
 ) {
     profile_scope!(profiler, "apply_gravity");
-    // Clamp delta time to prevent physics issues
-    let delta = time.delta_secs().min(0.1);
-    player.velocity.y += GRAVITY * delta;
+    // Fixed timestep: the same at every frame rate
+    let delta = time.delta_secs();
+    player.velocity.y += physics.gravity * delta;
 }
 
 /// System that applies physics to the player, including velocity and ground/ceiling collision.
@@ -63,38 +62,40 @@ pub fn apply_physics(
     };
 
     let (mut player, mut transform) = player.into_inner();
-    // Clamp delta time to prevent physics issues
-    let delta = time.delta_secs().min(0.1);
-
-    // Apply velocity
-    let new_y = transform.translation.y + player.velocity.y * delta;
-    let player_bottom = new_y - player.half_height;
-    let player_top = new_y + player.half_height;
-    let current_bottom = transform.translation.y - player.half_height;
-    let current_top = transform.translation.y + player.half_height;
-
-    // Extract player position (loop-invariant values)
-    let player_x = transform.translation.x;
-    let player_z = transform.translation.z;
-    let player_radius = player.radius;
-    let player_half_height = player.half_height;
-
-    let mut hit_ground = false;
-    let mut hit_ceiling = false;
-    let mut highest_collision_y = f32::MIN;
-    let mut lowest_ceiling_y = f32::MAX;
-
-    // Physics AABB — based on new_y after gravity, not the horizontal movement AABB.
-    let physics_min = Vec3::new(
-        player_x - player_radius,
-        new_y - player_half_height,
-        player_z - player_radius,
-    );
-    let physics_max = Vec3::new(
-        player_x + player_radius,
-        new_y + player_half_height,
-        player_z + player_radius,
+    // Fixed timestep: the same at every frame rate
+    let delta = time.delta_secs();
+    apply_character_physics(
+        &mut player,
+        &mut transform,
+        &spatial_grid,
+        &pre_fetched,
+        &sub_voxel_query,
+        delta,
     );
+}
+
+/// Move one character by its vertical velocity for a step of `delta`
+/// seconds, landing it on the ground or stopping it at the ceiling.
+///
+/// The cylinder is swept over its whole path through the step (see
+/// [`move_vertically`]), so a fast fall can't carry it through a floor.
+pub fn apply_character_physics(
+    player: &mut Player,
+    transform: &mut Transform,
+    spatial_grid: &SpatialGrid,
+    pre_fetched: &PreFetchedCollisionEntities,
+    sub_voxel_query: &Query<&SubVoxel, Without<Player>>,
+    delta: f32,
+) {
+    let position = transform.translation;
+    let motion = player.velocity.y * delta;
+
+    // Physics AABB — everything the cylinder passes through this step, not
+    // the horizontal movement AABB.
+    let reach = Vec3::new(player.radius, player.half_height, player.radius);
+    let end = position + Vec3::Y * motion;
+    let physics_min = position.min(end) - reach;
+    let physics_max = position.max(end) + reach;
 
     // Reuse the pre-fetched slice from move_player when it covers the physics AABB.
     // This avoids a second get_entities_in_aabb call on frames where the player moves.
@@ -117,67 +118,47 @@ pub fn apply_physics(
         &owned
     };
 
-    // Check collision with nearby sub-voxels only
-    for &entity in relevant {
-        let Ok(sub_voxel) = sub_voxel_query.get(entity) else {
-            continue;
-        };
-
-        let (min, max) = get_sub_voxel_bounds(sub_voxel);
-
-        // For cylinder collision detection, we need to check if the player's circular
-        // cross-section actually overlaps with the sub-voxel's XZ bounds.
-        // Find the closest point on the sub-voxel's XZ rectangle to the player center
-        let closest_x = player_x.clamp(min.x, max.x);
-        let closest_z = player_z.clamp(min.z, max.z);
-
-        // Check if the closest point is within the cylinder's radius
-        let dx = player_x - closest_x;
-        let dz = player_z - closest_z;
-        let distance_squared = dx * dx + dz * dz;
-
-        // Skip if the cylinder doesn't actually overlap horizontally
-        if distance_squared >= player_radius * player_radius {
-            continue;
-        }
-
-        // Ground collision: Check when moving downward
-        if player.velocity.y <= 0.0 {
-            // Check if player's bottom would go through the top of this sub-voxel
-            // Player was above (or very close due to floating-point errors), and would now be at or below the top
-            if current_bottom >= max.y - GROUND_DETECTION_EPSILON
-                && player_bottom <= max.y + GROUND_DETECTION_EPSILON
-            {
-                highest_collision_y = highest_collision_y.max(max.y);
-                hit_ground = true;
-            }
-        }
+    // Collide with nearby sub-voxels only
+    let obstacles: Vec<(Vec3, Vec3)> = relevant
+        .iter()
+        .filter_map(|&entity| sub_voxel_query.get(entity).ok())
+        .map(get_sub_voxel_bounds)
+        .collect();
+    let body = CharacterBody {
+        radius: player.radius,
+        half_height: player.half_height,
+        step_height: 0.0,
+    };
+    let moved = move_vertically(position, motion, body, &obstacles);
 
-        // Ceiling collision: Check when moving upward
-        if player.velocity.y > 0.0 {
-            // Check if player's top would go through the bottom of this sub-voxel
-            // Player's top was below (or very close), and would now be at or above the bottom
-            if current_top <= min.y + GROUND_DETECTION_EPSILON
-                && player_top >= min.y - GROUND_DETECTION_EPSILON
-            {
-                lowest_ceiling_y = lowest_ceiling_y.min(min.y);
-                hit_ceiling = true;
-            }
-        }
+    transform.translation.y = moved.y;
+    if moved.blocked {
+        player.velocity.y = 0.0;
     }
+    player.is_grounded = moved.grounded;
+}
 
-    if hit_ground {
-        transform.translation.y = highest_collision_y + player_half_height;
-        player.velocity.y = 0.0;
-        player.is_grounded = true;
-    } else if hit_ceiling {
-        // Stop upward movement when hitting ceiling
-        transform.translation.y = lowest_ceiling_y - player_half_height;
-        player.velocity.y = 0.0;
-        player.is_grounded = false;
-    } else {
-        transform.translation.y = new_y;
-        player.is_grounded = false;
+/// Keep NPCs in the spatial grid, boxed by their collision radius, as they
+/// spawn, move and despawn. All of them are re-added when the grid is
+/// rebuilt for a new map.
+pub fn sync_npc_grid(
+    mut spatial_grid: ResMut<SpatialGrid>,
+    npcs: Query<(Entity, &Npc, Ref<Transform>)>,
+    mut despawned: RemovedComponents<Npc>,
+) {
+    for entity in despawned.read() {
+        spatial_grid.remove(entity);
+    }
+    let rebuilt = spatial_grid.is_added();
+    for (entity, npc, transform) in &npcs {
+        if rebuilt || transform.is_changed() {
+            let reach = Vec3::splat(npc.radius);
+            spatial_grid.update(
+                entity,
+                transform.translation - reach,
+                transform.translation + reach,
+            );
+        }
     }
 }
 
@@ -187,11 +168,14 @@ pub fn apply_physics(
 /// - Detects sphere-sphere collision between player and NPCs
 /// - Pushes the player away from NPCs when colliding
 /// - Prevents the player from walking through NPCs
+///
+/// Only NPCs the spatial grid lists near the player are checked.
 pub fn apply_npc_collision(
+    spatial_grid: Option<Res<SpatialGrid>>,
     npc_query: Query<(&Npc, &Transform), Without<Player>>,
     player: Option<Single<(&Player, &mut Transform)>>,
 ) {
-    let Some(player) = player else {
+    let (Some(spatial_grid), Some(player)) = (spatial_grid, player) else {
         return;
     };
     let (player, mut player_transform) = player.into_inner();
@@ -199,8 +183,12 @@ pub fn apply_npc_collision(
     let player_pos = player_transform.translation;
     let player_radius = player.radius;
 
-    // Check collision with all NPCs (sphere-sphere collision)
-    for (npc, npc_transform) in &npc_query {
+    // NPC boxes reaching the player's (sphere-sphere collision below)
+    let reach = Vec3::splat(player_radius);
+    for (entity, _) in spatial_grid.query_aabb(player_pos - reach, player_pos + reach) {
+        let Ok((npc, npc_transform)) = npc_query.get(entity) else {
+            continue;
+        };
         let npc_pos = npc_transform.translation;
 
         // Calculate horizontal distance (ignore Y for now to allow jumping over)
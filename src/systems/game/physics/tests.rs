@@ -1,4 +1,6 @@
-use super::super::components::SubVoxel;
+#![allow(clippy::type_complexity)]
+
+use super::super::components::{SubVoxel, VoxelType};
 use super::*;
 use bevy::ecs::system::SystemState;
 
@@ -13,6 +15,7 @@ fn prefetched_cache_used_when_bounds_cover_physics_aabb() {
     let ground_entity = world
         .spawn(SubVoxel {
             bounds: ground_bounds,
+            voxel_type: VoxelType::Stone,
         })
         .id();
 
@@ -73,14 +76,12 @@ fn empty_cache_falls_back_to_grid_query() {
     let ground_entity = world
         .spawn(SubVoxel {
             bounds: ground_bounds,
+            voxel_type: VoxelType::Stone,
         })
         .id();
 
     let mut grid = SpatialGrid::default();
-    grid.cells
-        .entry(bevy::prelude::IVec3::new(0, 0, 0))
-        .or_default()
-        .push(ground_entity);
+    grid.insert(ground_entity, ground_bounds.0, ground_bounds.1);
     world.insert_resource(grid);
 
     // Empty cache — bounds = None
@@ -102,3 +103,57 @@ fn empty_cache_falls_back_to_grid_query() {
     );
     assert_eq!(fallback_entities[0], ground_entity);
 }
+
+// A fall far faster than one floor's thickness per step still lands on it.
+#[test]
+fn high_velocity_falls_land_on_a_one_voxel_floor() {
+    let mut world = bevy::prelude::World::new();
+
+    // One voxel, top at y = 0.5
+    let floor_bounds = (Vec3::new(-0.5, -0.5, -0.5), Vec3::new(0.5, 0.5, 0.5));
+    let floor = world
+        .spawn(SubVoxel {
+            bounds: floor_bounds,
+            voxel_type: VoxelType::Stone,
+        })
+        .id();
+    let mut grid = SpatialGrid::default();
+    grid.insert(floor, floor_bounds.0, floor_bounds.1);
+    world.insert_resource(grid);
+    world.insert_resource(PreFetchedCollisionEntities::default());
+
+    let mut player = Player {
+        speed: 3.0,
+        velocity: Vec3::new(0.0, -300.0, 0.0),
+        is_grounded: false,
+        radius: 0.2,
+        half_height: 0.4,
+        target_rotation: 0.0,
+        current_rotation: 0.0,
+        start_rotation: 0.0,
+        rotation_elapsed: 0.0,
+        rotation_duration: 0.2,
+    };
+    let mut transform = Transform::from_xyz(0.0, 3.0, 0.0);
+
+    let mut state: SystemState<(
+        Res<SpatialGrid>,
+        Res<PreFetchedCollisionEntities>,
+        Query<&SubVoxel, Without<Player>>,
+    )> = SystemState::new(&mut world);
+    let (spatial_grid, pre_fetched, sub_voxel_query) = state.get(&world);
+
+    // 5 units in one 60 Hz step: the end of the step is well below the floor
+    apply_character_physics(
+        &mut player,
+        &mut transform,
+        &spatial_grid,
+        &pre_fetched,
+        &sub_voxel_query,
+        1.0 / 60.0,
+    );
+
+    assert!(player.is_grounded);
+    assert_eq!(player.velocity.y, 0.0);
+    assert!((transform.translation.y - 0.9).abs() < 1e-5);
+}
@@ -4,18 +4,17 @@
 //! - WASD keys / Gamepad left stick for movement
 //! - Arrow keys / Gamepad right stick for character facing direction
 //! - Space bar / A button for jumping
-//! - Collision detection during movement
+//! - Sweeping the player through the world, sliding along walls and
+//!   stepping up low ledges
 //!
 //! Character Facing Behavior:
 //! - If look direction input is active (arrow keys or right stick), character faces that direction
 //! - Otherwise, character faces the direction of movement
 
-use super::collision::{
-    check_sub_voxel_collision, CollisionParams, STEP_UP_TOLERANCE, SUB_VOXEL_SIZE,
-};
+use super::collision::{move_and_slide, CharacterBody};
 use super::components::{Player, SubVoxel};
 use super::gamepad::{InputSource, PlayerInput};
-use super::resources::{PreFetchedCollisionEntities, SpatialGrid};
+use super::resources::{PhysicsConfig, PreFetchedCollisionEntities, SpatialGrid};
 use crate::diagnostics::FrameProfiler;
 use crate::profile_scope;
 use bevy::prelude::*;
@@ -26,7 +25,7 @@ use std::f32::consts::FRAC_PI_2;
 /// This system:
 /// - Reads from PlayerInput resource for movement direction and jump
 /// - Handles both keyboard (WASD) and gamepad (left stick) input
-/// - Applies collision detection
+/// - Sweeps the player through sub-voxels with [`move_and_slide`]
 /// - Updates player position and grounded state
 ///
 /// Movement is adjusted for the camera rotation:
@@ -34,9 +33,11 @@ use std::f32::consts::FRAC_PI_2;
 /// - Back (-Y input) moves in -X direction
 /// - Left (-X input) moves in -Z direction
 /// - Right (+X input) moves in +Z direction
+#[allow(clippy::too_many_arguments)]
 pub fn move_player(
     time: Res<Time>,
     player_input: Res<PlayerInput>,
+    physics: Res<PhysicsConfig>,
     spatial_grid: Option<Res<SpatialGrid>>,
     mut pre_fetched: ResMut<PreFetchedCollisionEntities>,
     sub_voxel_query: Query<&SubVoxel, Without<Player>>,
@@ -52,9 +53,35 @@ pub fn move_player(
         return;
     };
     let (mut player, mut transform) = player.into_inner();
-    // Clamp delta time to prevent physics issues when window regains focus
-    let delta = time.delta_secs().min(0.1);
+    // Fixed timestep: the same at every frame rate
+    let delta = time.delta_secs();
+    move_character(
+        &mut player,
+        &mut transform,
+        &player_input,
+        &physics,
+        &spatial_grid,
+        &sub_voxel_query,
+        &mut pre_fetched,
+        delta,
+    );
+}
 
+/// Move one character by `player_input` for a step of `delta` seconds: start a
+/// jump, turn it towards its look or movement direction and sweep it
+/// through the sub-voxels around it. The sub-voxels fetched are left in
+/// `pre_fetched` for [`apply_physics`](super::physics::apply_physics).
+#[allow(clippy::too_many_arguments)]
+pub fn move_character(
+    player: &mut Player,
+    transform: &mut Transform,
+    player_input: &PlayerInput,
+    physics: &PhysicsConfig,
+    spatial_grid: &SpatialGrid,
+    sub_voxel_query: &Query<&SubVoxel, Without<Player>>,
+    pre_fetched: &mut PreFetchedCollisionEntities,
+    delta: f32,
+) {
     // Convert 2D input to 3D movement direction
     // PlayerInput.movement: x = left/right, y = forward/back
     // Game world: x = forward/back, z = left/right
@@ -62,7 +89,7 @@ pub fn move_player(
 
     // Jump - use just_pressed for responsive jumping
     if player_input.jump_just_pressed && player.is_grounded {
-        player.velocity.y = 8.0;
+        player.velocity.y = physics.jump_velocity;
         player.is_grounded = false;
     }
 
@@ -117,15 +144,9 @@ pub fn move_player(
         // Apply magnitude for analog movement (stick pushed halfway = half speed)
         let move_delta = normalized_dir * magnitude * player.speed * delta;
 
-        // Calculate current floor Y position (bottom of player cylinder)
-        let mut current_floor_y = current_pos.y - player.half_height;
-
-        let new_x = current_pos.x + move_delta.x;
-        let new_z = current_pos.z + move_delta.z;
-
         // Pre-fetch all nearby entities once with a widened AABB that covers:
         // - current position and destination in XZ (expanded by abs(move_delta))
-        // - step-up height upward in Y (so the same slice covers the step-up re-check)
+        // - step-up height upward in Y (so the same slice covers the headroom check)
         let prefetch_min = Vec3::new(
             current_pos.x - player.radius - move_delta.x.abs(),
             current_pos.y - player.half_height,
@@ -133,143 +154,37 @@ pub fn move_player(
         );
         let prefetch_max = Vec3::new(
             current_pos.x + player.radius + move_delta.x.abs(),
-            current_pos.y + player.half_height + SUB_VOXEL_SIZE + STEP_UP_TOLERANCE,
+            current_pos.y + player.half_height + physics.step_height,
             current_pos.z + player.radius + move_delta.z.abs(),
         );
-        let prefetched_entities = spatial_grid.get_entities_in_aabb(prefetch_min, prefetch_max);
+        // The grid holds NPCs too; only sub-voxels are obstacles here
+        let nearby: Vec<(Entity, (Vec3, Vec3))> = spatial_grid
+            .query_aabb(prefetch_min, prefetch_max)
+            .filter(|(entity, _)| sub_voxel_query.contains(*entity))
+            .collect();
+        let obstacles: Vec<(Vec3, Vec3)> = nearby.iter().map(|&(_, bounds)| bounds).collect();
 
         // Share the pre-fetched slice with apply_physics (runs later in the same frame).
         // apply_physics checks bounds containment before using it.
-        pre_fetched.entities.clone_from(&prefetched_entities);
+        pre_fetched.entities = nearby.into_iter().map(|(entity, _)| entity).collect();
         pre_fetched.bounds = Some((prefetch_min, prefetch_max));
 
-        // Optimization: Try diagonal movement first when moving in both axes
-        let moving_diagonally = move_delta.x != 0.0 && move_delta.z != 0.0;
-
-        if moving_diagonally {
-            let diagonal_collision = check_sub_voxel_collision(
-                &spatial_grid,
-                &sub_voxel_query,
-                CollisionParams {
-                    x: new_x,
-                    y: current_pos.y,
-                    z: new_z,
-                    radius: player.radius,
-                    half_height: player.half_height,
-                    current_floor_y,
-                },
-                Some(&prefetched_entities),
-            );
-
-            if !diagonal_collision.has_collision {
-                transform.translation.x = new_x;
-                transform.translation.z = new_z;
-            } else if diagonal_collision.can_step_up && player.is_grounded {
-                transform.translation.x = new_x;
-                transform.translation.z = new_z;
-                transform.translation.y = diagonal_collision.new_y;
-                player.velocity.y = 0.0;
+        // Only step up from the ground; in the air ledges are walls
+        let body = CharacterBody {
+            radius: player.radius,
+            half_height: player.half_height,
+            step_height: if player.is_grounded {
+                physics.step_height
             } else {
-                apply_axis_movement(
-                    &spatial_grid,
-                    &sub_voxel_query,
-                    &mut transform,
-                    &mut player,
-                    current_pos,
-                    new_x,
-                    new_z,
-                    &mut current_floor_y,
-                    &prefetched_entities,
-                );
-            }
-        } else {
-            apply_axis_movement(
-                &spatial_grid,
-                &sub_voxel_query,
-                &mut transform,
-                &mut player,
-                current_pos,
-                new_x,
-                new_z,
-                &mut current_floor_y,
-                &prefetched_entities,
-            );
-        }
-    }
-}
-
-/// Applies movement checking each axis individually for wall sliding behavior.
-#[inline]
-#[allow(clippy::too_many_arguments)]
-fn apply_axis_movement(
-    spatial_grid: &SpatialGrid,
-    sub_voxel_query: &Query<&SubVoxel, Without<Player>>,
-    transform: &mut Transform,
-    player: &mut Player,
-    current_pos: Vec3,
-    new_x: f32,
-    new_z: f32,
-    current_floor_y: &mut f32,
-    prefetched: &[Entity],
-) {
-    // Try moving on X axis
-    if new_x != current_pos.x {
-        let x_collision = check_sub_voxel_collision(
-            spatial_grid,
-            sub_voxel_query,
-            CollisionParams {
-                x: new_x,
-                y: current_pos.y,
-                z: current_pos.z,
-                radius: player.radius,
-                half_height: player.half_height,
-                current_floor_y: *current_floor_y,
-            },
-            Some(prefetched),
-        );
-
-        if !x_collision.has_collision {
-            // No collision, move freely
-            transform.translation.x = new_x;
-        } else if x_collision.can_step_up && player.is_grounded {
-            // Step-up collision - move horizontally and adjust height
-            transform.translation.x = new_x;
-            transform.translation.y = x_collision.new_y;
-            // Update current_floor_y for subsequent collision checks (critical for stairs)
-            *current_floor_y = transform.translation.y - player.half_height;
-            // Reset vertical velocity to prevent falling after step-up
-            player.velocity.y = 0.0;
-        }
-        // else: blocking collision, don't move
-    }
-
-    // Try moving on Z axis
-    if new_z != current_pos.z {
-        let z_collision = check_sub_voxel_collision(
-            spatial_grid,
-            sub_voxel_query,
-            CollisionParams {
-                x: transform.translation.x,
-                y: transform.translation.y,
-                z: new_z,
-                radius: player.radius,
-                half_height: player.half_height,
-                current_floor_y: *current_floor_y,
+                0.0
             },
-            Some(prefetched),
-        );
-
-        if !z_collision.has_collision {
-            // No collision, move freely
-            transform.translation.z = new_z;
-        } else if z_collision.can_step_up && player.is_grounded {
-            // Step-up collision - move horizontally and adjust height
-            transform.translation.z = new_z;
-            transform.translation.y = z_collision.new_y;
+        };
+        let moved = move_and_slide(current_pos, move_delta.xz(), body, &obstacles);
+        transform.translation = moved.position;
+        if moved.stepped_up {
             // Reset vertical velocity to prevent falling after step-up
             player.velocity.y = 0.0;
         }
-        // else: blocking collision, don't move
     }
 }
 
@@ -13,6 +13,7 @@ fn widened_aabb_covers_movement_destination() {
     let half_height = 0.4_f32;
     let move_delta_x = 0.1_f32;
     let move_delta_z = -0.05_f32;
+    let step_height = PhysicsConfig::default().step_height;
 
     let prefetch_min = Vec3::new(
         player_x - radius - move_delta_x.abs(),
@@ -21,7 +22,7 @@ fn widened_aabb_covers_movement_destination() {
     );
     let prefetch_max = Vec3::new(
         player_x + radius + move_delta_x.abs(),
-        player_y + half_height + SUB_VOXEL_SIZE + STEP_UP_TOLERANCE,
+        player_y + half_height + step_height,
         player_z + radius + move_delta_z.abs(),
     );
 
@@ -39,8 +40,8 @@ fn widened_aabb_covers_movement_destination() {
     assert!(new_z - radius >= prefetch_min.z);
     assert!(new_z + radius <= prefetch_max.z);
 
-    // Step-up height must be covered (top of cylinder at player_y + half_height + step_up)
-    let step_up_height = SUB_VOXEL_SIZE + STEP_UP_TOLERANCE;
-    let new_y_stepped = player_y + step_up_height;
+    // Headroom on the highest ledge must be covered (top of cylinder at
+    // player_y + half_height + step_height)
+    let new_y_stepped = player_y + step_height;
     assert!(new_y_stepped + half_height <= prefetch_max.y);
 }
@@ -0,0 +1,454 @@
+//! Local playtest logging, for level design.
+//!
+//! Launching the game with `--playtest-log` keeps a [`PlaytestLog`] for each
+//! map played: the player's position every [`SAMPLE_INTERVAL`] seconds,
+//! deaths and their cause, interactions, goals reached, and the time spent
+//! in each region of the map. Regions are [`REGION_SIZE`]-voxel squares on
+//! the ground plane. Retrying a map after a game over continues its log.
+//!
+//! Nothing is sent anywhere. Logs are written to
+//! `playtests/<map>-<unix time>.csv` whenever play stops (pause, game over,
+//! results, quitting). The map editor loads them with View → Load Playtest
+//! Log and draws the position trace as a heatmap over the map.
+//!
+//! The CSV has one row per event under an `event,time,x,y,z,detail` header,
+//! after a `# map: <path>` comment. Positions are the centre of the player's
+//! collider; `region` rows hold the seconds spent in the region and its
+//! lowest corner (y is left empty). Split-screen sessions log nothing, since
+//! the log follows a single player.
+
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+use super::components::{Npc, Player};
+use super::doors::Door;
+use super::health::{apply_damage, DamageEvent, DamageSource, Health};
+use super::hot_reload::MapPathForHotReload;
+use super::interaction::InteractionTarget;
+use super::speedrun::RunResult;
+use super::teleporters::Teleporter;
+use crate::states::GameState;
+
+/// Folder playtest logs are written to.
+pub const PLAYTEST_DIR: &str = "playtests";
+
+/// Seconds between position samples.
+pub const SAMPLE_INTERVAL: f32 = 0.25;
+
+/// Width of a map region, in voxels.
+pub const REGION_SIZE: i32 = 8;
+
+/// Column names of the CSV.
+const CSV_HEADER: &str = "event,time,x,y,z,detail";
+
+/// Errors reading or writing a playtest log.
+#[derive(Error, Debug)]
+pub enum PlaytestLogError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// What happened at a [`PlaytestEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaytestEventKind {
+    /// Periodic sample of the player's position
+    Position,
+    /// The player's health ran out; the detail is the cause
+    Death,
+    /// The player used an interactable; the detail says what it was
+    Interact,
+    /// The player reached a goal; the detail is the run time
+    Goal,
+}
+
+impl PlaytestEventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Position => "position",
+            Self::Death => "death",
+            Self::Interact => "interact",
+            Self::Goal => "goal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "position" => Some(Self::Position),
+            "death" => Some(Self::Death),
+            "interact" => Some(Self::Interact),
+            "goal" => Some(Self::Goal),
+            _ => None,
+        }
+    }
+}
+
+/// One logged event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaytestEvent {
+    /// Seconds of play since the log started
+    pub time: f32,
+    pub kind: PlaytestEventKind,
+    pub position: Vec3,
+    pub detail: String,
+}
+
+/// Everything logged on one map.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlaytestLog {
+    /// Map file played; `None` for the built-in default map
+    pub map: Option<PathBuf>,
+    /// Seconds of play logged
+    pub elapsed: f32,
+    pub events: Vec<PlaytestEvent>,
+    /// Seconds spent in each region, by its lowest (x, z) corner
+    pub regions: BTreeMap<(i32, i32), f32>,
+    /// Seconds since the last position sample
+    since_sample: f32,
+}
+
+/// Lowest (x, z) corner of the region containing `position`.
+pub fn region_of(position: Vec3) -> (i32, i32) {
+    let size = REGION_SIZE as f32;
+    (
+        (position.x / size).floor() as i32 * REGION_SIZE,
+        (position.z / size).floor() as i32 * REGION_SIZE,
+    )
+}
+
+/// Quote a CSV field if it needs it. Rows are single lines, so line breaks
+/// become spaces.
+fn csv_field(text: &str) -> String {
+    let text = text.replace(['\r', '\n'], " ");
+    if text.contains([',', '"']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+/// Undo [`csv_field`].
+fn parse_csv_field(text: &str) -> String {
+    match text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(quoted) => quoted.replace("\"\"", "\""),
+        None => text.to_string(),
+    }
+}
+
+impl PlaytestLog {
+    pub fn new(map: Option<PathBuf>) -> Self {
+        Self { map, ..default() }
+    }
+
+    /// Count `delta` seconds of play with the player at `position`, taking a
+    /// position sample when one is due. The first call always samples.
+    pub fn advance(&mut self, delta: f32, position: Vec3) {
+        let first = self.events.is_empty();
+        self.elapsed += delta;
+        self.since_sample += delta;
+        *self.regions.entry(region_of(position)).or_default() += delta;
+        if first || self.since_sample >= SAMPLE_INTERVAL {
+            self.since_sample = 0.0;
+            self.push(PlaytestEventKind::Position, position, String::new());
+        }
+    }
+
+    /// Log an event at the current time.
+    pub fn push(&mut self, kind: PlaytestEventKind, position: Vec3, detail: String) {
+        self.events.push(PlaytestEvent {
+            time: self.elapsed,
+            kind,
+            position,
+            detail,
+        });
+    }
+
+    pub fn count(&self, kind: PlaytestEventKind) -> usize {
+        self.events
+            .iter()
+            .filter(|event| event.kind == kind)
+            .count()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!(
+            "# map: {}\n{}\n",
+            self.map
+                .as_ref()
+                .map_or("default".to_string(), |map| map.display().to_string()),
+            CSV_HEADER
+        );
+        for event in &self.events {
+            let p = event.position;
+            let _ = writeln!(
+                csv,
+                "{},{:.3},{:.3},{:.3},{:.3},{}",
+                event.kind.name(),
+                event.time,
+                p.x,
+                p.y,
+                p.z,
+                csv_field(&event.detail)
+            );
+        }
+        for ((x, z), seconds) in &self.regions {
+            let _ = writeln!(csv, "region,{:.3},{},,{},", seconds, x, z);
+        }
+        csv
+    }
+
+    /// Parse a log written by [`PlaytestLog::to_csv`].
+    // Read by the map editor's heatmap, not the game.
+    #[allow(dead_code)]
+    pub fn from_csv(csv: &str) -> Result<Self, PlaytestLogError> {
+        let mut log = Self::default();
+        for (index, line) in csv.lines().enumerate() {
+            let error = |message: String| PlaytestLogError::Parse {
+                line: index + 1,
+                message,
+            };
+            if let Some(map) = line.strip_prefix("# map: ") {
+                log.map = (map != "default").then(|| PathBuf::from(map));
+                continue;
+            }
+            if line.trim().is_empty() || line.starts_with('#') || line == CSV_HEADER {
+                continue;
+            }
+
+            // The detail is last, so commas inside it stay in the sixth field
+            let fields: Vec<&str> = line.splitn(6, ',').collect();
+            if fields.len() != 6 {
+                return Err(error(format!("expected 6 fields, found {}", fields.len())));
+            }
+            let number = |i: usize| -> Result<f32, PlaytestLogError> {
+                let field = fields[i].trim();
+                if field.is_empty() {
+                    return Ok(0.0);
+                }
+                field
+                    .parse()
+                    .map_err(|_| error(format!("'{}' is not a number", field)))
+            };
+            let time = number(1)?;
+            if fields[0] == "region" {
+                let corner = (number(2)? as i32, number(4)? as i32);
+                log.regions.insert(corner, time);
+                continue;
+            }
+            let kind = PlaytestEventKind::from_name(fields[0])
+                .ok_or_else(|| error(format!("unknown event '{}'", fields[0])))?;
+            log.elapsed = log.elapsed.max(time);
+            log.events.push(PlaytestEvent {
+                time,
+                kind,
+                position: Vec3::new(number(2)?, number(3)?, number(4)?),
+                detail: parse_csv_field(fields[5]),
+            });
+        }
+        Ok(log)
+    }
+
+    // Read by the map editor's heatmap, not the game.
+    #[allow(dead_code)]
+    pub fn load(path: &Path) -> Result<Self, PlaytestLogError> {
+        Self::from_csv(&std::fs::read_to_string(path)?)
+    }
+
+    /// Write to `path`, creating its folder if needed.
+    pub fn save(&self, path: &Path) -> Result<(), PlaytestLogError> {
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
+        std::fs::write(path, self.to_csv())?;
+        Ok(())
+    }
+}
+
+/// File name of a map's playtest log started at unix time `started`: the
+/// map's file stem with anything but letters, digits, `-` and `_` replaced
+/// by `_`.
+pub fn playtest_file(map: Option<&Path>, started: u64) -> String {
+    let stem = map
+        .and_then(|map| map.file_stem())
+        .map_or("default".to_string(), |stem| {
+            stem.to_string_lossy().to_string()
+        });
+    let name: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-{}.csv", name, started)
+}
+
+/// The log being kept; only present when playtest logging is on.
+#[derive(Resource, Default)]
+pub struct PlaytestLogger {
+    pub log: Option<PlaytestLog>,
+    /// File the log is saved to
+    pub path: PathBuf,
+}
+
+impl PlaytestLogger {
+    fn save(&self) {
+        let Some(log) = &self.log else {
+            return;
+        };
+        match log.save(&self.path) {
+            Ok(()) => info!(
+                "[Playtest] Saved {:.0}s of play to {}",
+                log.elapsed,
+                self.path.display()
+            ),
+            Err(e) => warn!("[Playtest] Failed to save {}: {}", self.path.display(), e),
+        }
+    }
+}
+
+/// Start a new log when the player spawns on a different map; retries
+/// continue the current one.
+pub fn start_playtest_log(
+    spawned: Query<(), Added<Player>>,
+    map_path: Res<MapPathForHotReload>,
+    mut logger: ResMut<PlaytestLogger>,
+) {
+    if spawned.is_empty() {
+        return;
+    }
+    if logger.log.as_ref().is_some_and(|log| log.map == map_path.0) {
+        return;
+    }
+    logger.save();
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    logger.path = Path::new(PLAYTEST_DIR).join(playtest_file(map_path.0.as_deref(), started));
+    logger.log = Some(PlaytestLog::new(map_path.0.clone()));
+    info!("[Playtest] Logging to {}", logger.path.display());
+}
+
+/// Count play time and sample the player's position.
+pub fn track_playtest(
+    time: Res<Time>,
+    player: Option<Single<&Transform, With<Player>>>,
+    mut logger: ResMut<PlaytestLogger>,
+) {
+    if let (Some(log), Some(player)) = (logger.log.as_mut(), player) {
+        log.advance(time.delta_secs(), player.translation);
+    }
+}
+
+/// Log the player's death and what caused it.
+pub fn log_playtest_deaths(
+    mut damage: MessageReader<DamageEvent>,
+    player: Option<Single<(&Transform, &Health), With<Player>>>,
+    mut logger: ResMut<PlaytestLogger>,
+) {
+    let Some(source) = damage.read().last().map(|event| event.source) else {
+        return;
+    };
+    let (Some(log), Some(player)) = (logger.log.as_mut(), player) else {
+        return;
+    };
+    let (transform, health) = *player;
+    if health.is_dead() {
+        let cause = match source {
+            DamageSource::Hazard(voxel_type) => format!("hazard {:?}", voxel_type),
+            DamageSource::Fall => "fall".to_string(),
+            DamageSource::Enemy => "enemy".to_string(),
+        };
+        log.push(PlaytestEventKind::Death, transform.translation, cause);
+    }
+}
+
+/// Log what the player interacted with.
+pub fn log_playtest_interactions(
+    target: Res<InteractionTarget>,
+    player: Option<Single<&Transform, With<Player>>>,
+    targets: Query<(Option<&Npc>, Has<Door>, Has<Teleporter>)>,
+    mut logger: ResMut<PlaytestLogger>,
+) {
+    let (Some(entity), Some(log), Some(player)) = (target.0, logger.log.as_mut(), player) else {
+        return;
+    };
+    let detail = match targets.get(entity) {
+        Ok((Some(npc), _, _)) => format!("npc {}", npc.name),
+        Ok((None, true, _)) => "door".to_string(),
+        Ok((None, false, true)) => "teleporter".to_string(),
+        _ => "action".to_string(),
+    };
+    log.push(PlaytestEventKind::Interact, player.translation, detail);
+}
+
+/// Log a goal reached; runs on entering the results screen.
+pub fn log_playtest_goal(
+    result: Option<Res<RunResult>>,
+    player: Option<Single<&Transform, With<Player>>>,
+    mut logger: ResMut<PlaytestLogger>,
+) {
+    let (Some(result), Some(log), Some(player)) = (result, logger.log.as_mut(), player) else {
+        return;
+    };
+    log.push(
+        PlaytestEventKind::Goal,
+        player.translation,
+        format!("{:.2}s", result.time),
+    );
+}
+
+/// Save the log; runs whenever play stops.
+pub fn save_playtest_log(logger: Res<PlaytestLogger>) {
+    logger.save();
+}
+
+/// Save the log when the game closes.
+pub fn save_playtest_log_on_exit(mut exit: MessageReader<AppExit>, logger: Res<PlaytestLogger>) {
+    if exit.read().count() > 0 {
+        logger.save();
+    }
+}
+
+/// Plugin for opt-in playtest logging (`--playtest-log`).
+#[derive(Default)]
+pub struct PlaytestLogPlugin {
+    pub enabled: bool,
+}
+
+impl Plugin for PlaytestLogPlugin {
+    fn build(&self, app: &mut App) {
+        if !self.enabled {
+            return;
+        }
+        app.init_resource::<PlaytestLogger>()
+            .add_systems(OnExit(GameState::InGame), save_playtest_log)
+            .add_systems(
+                OnEnter(GameState::Results),
+                (log_playtest_goal, save_playtest_log).chain(),
+            )
+            .add_systems(Last, save_playtest_log_on_exit)
+            .add_systems(
+                Update,
+                (
+                    start_playtest_log,
+                    track_playtest,
+                    log_playtest_deaths.after(apply_damage),
+                    log_playtest_interactions,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,76 @@
+use super::*;
+
+#[test]
+fn regions_are_aligned_squares() {
+    assert_eq!(region_of(Vec3::new(0.0, 3.0, 7.9)), (0, 0));
+    assert_eq!(region_of(Vec3::new(8.0, 0.0, 15.5)), (8, 8));
+    assert_eq!(region_of(Vec3::new(-0.5, 0.0, -8.5)), (-8, -16));
+}
+
+#[test]
+fn advancing_samples_positions_and_counts_region_time() {
+    let mut log = PlaytestLog::new(None);
+    for i in 0..20 {
+        // 2 seconds in all, crossing from region (0, 0) into (8, 0) halfway
+        let x = if i < 10 { 4.0 } else { 12.0 };
+        log.advance(0.1, Vec3::new(x, 1.0, 2.0));
+    }
+
+    assert!((log.elapsed - 2.0).abs() < 1e-4);
+    assert!((log.regions[&(0, 0)] - 1.0).abs() < 1e-4);
+    assert!((log.regions[&(8, 0)] - 1.0).abs() < 1e-4);
+    // One sample straight away, then one every SAMPLE_INTERVAL
+    let samples = log.count(PlaytestEventKind::Position);
+    assert!((7..=9).contains(&samples), "{} samples", samples);
+}
+
+#[test]
+fn logs_round_trip_through_csv() {
+    let mut log = PlaytestLog::new(Some(PathBuf::from("assets/maps/cave.ron")));
+    log.advance(0.5, Vec3::new(1.0, 0.9, 2.0));
+    log.advance(0.5, Vec3::new(9.0, 0.9, -3.0));
+    log.push(
+        PlaytestEventKind::Death,
+        Vec3::new(9.0, -40.0, -3.0),
+        "fall".to_string(),
+    );
+    log.push(
+        PlaytestEventKind::Interact,
+        Vec3::new(9.5, 0.9, -3.0),
+        "npc Bram, \"the\" smith".to_string(),
+    );
+
+    let parsed = PlaytestLog::from_csv(&log.to_csv()).unwrap();
+
+    assert_eq!(parsed.map, log.map);
+    assert_eq!(parsed.events.len(), log.events.len());
+    for (parsed, event) in parsed.events.iter().zip(&log.events) {
+        assert_eq!(parsed.kind, event.kind);
+        assert_eq!(parsed.detail, event.detail);
+        assert!(parsed.position.distance(event.position) < 1e-3);
+        assert!((parsed.time - event.time).abs() < 1e-3);
+    }
+    assert_eq!(
+        parsed.regions.keys().collect::<Vec<_>>(),
+        log.regions.keys().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn malformed_rows_report_their_line() {
+    let csv = "# map: default\nevent,time,x,y,z,detail\nposition,0.0,1,2,3,\njump,1.0,1,2,3,\n";
+    match PlaytestLog::from_csv(csv) {
+        Err(PlaytestLogError::Parse { line, .. }) => assert_eq!(line, 4),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+    assert!(PlaytestLog::from_csv("position,0.0,1,2\n").is_err());
+}
+
+#[test]
+fn file_names_use_the_map_stem() {
+    assert_eq!(
+        playtest_file(Some(Path::new("assets/maps/cave 1.ron")), 1700000000),
+        "cave_1-1700000000.csv"
+    );
+    assert_eq!(playtest_file(None, 5), "default-5.csv");
+}
@@ -0,0 +1,224 @@
+//! Render scale: the 3D view drawn at a fraction or a multiple of the
+//! window's resolution.
+//!
+//! At any scale other than 100% the game camera renders into an off-screen
+//! image sized to the window times the scale, and a [`RenderScaleCamera`]
+//! stretches that image over the window behind the UI. Below 100% this saves
+//! GPU time on slow machines; above it the view is supersampled, which
+//! smooths edges on high-DPI displays. The UI is always drawn at the
+//! window's own resolution.
+//!
+//! The optional sharpen pass is Bevy's contrast adaptive sharpening, the
+//! filter used by FSR 1, run on whichever camera draws to the window. It
+//! brings back some of the detail lost when upscaling.
+//!
+//! Split-screen always renders at 100%, since each player's camera draws
+//! into its own part of the window.
+
+use bevy::anti_alias::contrast_adaptive_sharpening::ContrastAdaptiveSharpening;
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::window::{PrimaryWindow, WindowRef};
+
+use super::components::GameCamera;
+use super::split_screen::SplitScreenPlayer;
+
+/// Lowest render scale, in percent.
+pub const MIN_RENDER_SCALE: u32 = 50;
+
+/// Highest render scale, in percent.
+pub const MAX_RENDER_SCALE: u32 = 200;
+
+/// Render scale change per settings step, in percent.
+pub const RENDER_SCALE_STEP: u32 = 10;
+
+/// Render scale options, set from the settings screen.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderScaleSettings {
+    /// Resolution of the 3D view as a percentage of the window's
+    pub percent: u32,
+    /// Sharpen the final image
+    pub sharpen: bool,
+}
+
+impl Default for RenderScaleSettings {
+    fn default() -> Self {
+        Self {
+            percent: 100,
+            sharpen: false,
+        }
+    }
+}
+
+impl RenderScaleSettings {
+    /// Move the scale `delta` steps, staying in range.
+    pub fn step(&mut self, delta: i32) {
+        let percent = self.percent as i32 + delta * RENDER_SCALE_STEP as i32;
+        self.percent = (percent.max(0) as u32).clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE);
+    }
+}
+
+/// Size of the image the game camera renders into for a `window_size`
+/// window (both in physical pixels) at `percent`.
+pub fn scaled_size(window_size: UVec2, percent: u32) -> UVec2 {
+    (window_size.as_vec2() * percent as f32 / 100.0)
+        .round()
+        .as_uvec2()
+        .max(UVec2::ONE)
+}
+
+/// The image the game camera currently renders into, if it isn't drawing
+/// straight to the window.
+#[derive(Resource, Debug, Default)]
+pub struct RenderScaleTarget {
+    pub image: Option<Handle<Image>>,
+    /// Size of `image` in pixels
+    pub size: UVec2,
+    /// Game camera rendering into `image`
+    pub camera: Option<Entity>,
+}
+
+impl RenderScaleTarget {
+    /// Convert a position from the game camera's viewport to the window's
+    /// logical pixels, for UI placed over a projected world position.
+    pub fn to_window(&self, position: Vec2, window_size: Vec2) -> Vec2 {
+        if self.image.is_none() || self.size.min_element() == 0 {
+            return position;
+        }
+        position * window_size / self.size.as_vec2()
+    }
+}
+
+/// 2D camera that shows the scaled view in the window; also the UI camera
+/// while the render scale is in use.
+#[derive(Component)]
+pub struct RenderScaleCamera;
+
+/// Full-window image node showing the scaled view.
+#[derive(Component)]
+pub struct RenderScaleView;
+
+/// Point the game camera at an image of the scaled size, or back at the
+/// window at 100%, following window resizes and settings changes.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_render_scale(
+    mut commands: Commands,
+    settings: Res<RenderScaleSettings>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    split_players: Query<(), With<SplitScreenPlayer>>,
+    game_camera: Query<Entity, With<GameCamera>>,
+    display_cameras: Query<Entity, With<RenderScaleCamera>>,
+    mut views: Query<(Entity, &mut ImageNode), With<RenderScaleView>>,
+    mut target: ResMut<RenderScaleTarget>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let window_size = window.map_or(UVec2::ZERO, |window| window.physical_size());
+    let camera = game_camera.single().ok();
+    let scaled =
+        settings.percent != 100 && split_players.is_empty() && window_size.min_element() > 0;
+
+    let Some(camera) = camera.filter(|_| scaled) else {
+        if target.image.take().is_some() {
+            if let Some(camera) = camera {
+                commands
+                    .entity(camera)
+                    .insert(RenderTarget::Window(WindowRef::Primary));
+            }
+            for entity in display_cameras.iter().chain(views.iter().map(|(e, _)| e)) {
+                commands.entity(entity).despawn();
+            }
+            target.camera = None;
+        }
+        return;
+    };
+
+    let size = scaled_size(window_size, settings.percent);
+    if target.image.is_none() || target.size != size || target.camera != Some(camera) {
+        let image = images.add(Image::new_target_texture(
+            size.x,
+            size.y,
+            TextureFormat::bevy_default(),
+            None,
+        ));
+        commands
+            .entity(camera)
+            .insert(RenderTarget::Image(image.clone().into()));
+        for (_, mut view) in &mut views {
+            view.image = image.clone();
+        }
+        target.image = Some(image);
+        target.size = size;
+        target.camera = Some(camera);
+    }
+
+    if display_cameras.is_empty() {
+        commands.spawn((
+            Camera2d,
+            Camera {
+                order: 1,
+                ..default()
+            },
+            RenderScaleCamera,
+        ));
+    }
+    if views.is_empty() {
+        if let Some(image) = &target.image {
+            commands.spawn((
+                ImageNode::new(image.clone()),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                // Behind every other UI node
+                GlobalZIndex(i32::MIN),
+                RenderScaleView,
+            ));
+        }
+    }
+}
+
+/// Keep the sharpen pass on the camera that draws to the window.
+#[allow(clippy::type_complexity)]
+pub fn apply_sharpening(
+    mut commands: Commands,
+    settings: Res<RenderScaleSettings>,
+    cameras: Query<
+        (
+            Entity,
+            Has<RenderScaleCamera>,
+            Has<ContrastAdaptiveSharpening>,
+        ),
+        Or<(With<GameCamera>, With<RenderScaleCamera>)>,
+    >,
+) {
+    let upscaled = cameras.iter().any(|(_, display, _)| display);
+    for (entity, display, sharpened) in &cameras {
+        let wanted = settings.sharpen && display == upscaled;
+        if wanted && !sharpened {
+            commands
+                .entity(entity)
+                .insert(ContrastAdaptiveSharpening::default());
+        } else if !wanted && sharpened {
+            commands
+                .entity(entity)
+                .remove::<ContrastAdaptiveSharpening>();
+        }
+    }
+}
+
+/// Plugin that renders the game at the chosen render scale.
+pub struct RenderScalePlugin;
+
+impl Plugin for RenderScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RenderScaleSettings>()
+            .init_resource::<RenderScaleTarget>()
+            .add_systems(Update, (apply_render_scale, apply_sharpening).chain());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn render_scale_defaults_to_native() {
+    let settings = RenderScaleSettings::default();
+    assert_eq!(settings.percent, 100);
+    assert!(!settings.sharpen);
+}
+
+#[test]
+fn steps_stay_in_range() {
+    let mut settings = RenderScaleSettings::default();
+    settings.step(-1);
+    assert_eq!(settings.percent, 90);
+    settings.step(-10);
+    assert_eq!(settings.percent, MIN_RENDER_SCALE);
+    settings.step(20);
+    assert_eq!(settings.percent, MAX_RENDER_SCALE);
+}
+
+#[test]
+fn scaled_size_follows_the_percentage() {
+    let window = UVec2::new(1920, 1080);
+    assert_eq!(scaled_size(window, 50), UVec2::new(960, 540));
+    assert_eq!(scaled_size(window, 200), UVec2::new(3840, 2160));
+    assert_eq!(scaled_size(UVec2::new(1, 1), 50), UVec2::ONE);
+}
+
+#[test]
+fn viewport_positions_map_to_the_window() {
+    let mut target = RenderScaleTarget::default();
+    let window = Vec2::new(1280.0, 720.0);
+    let position = Vec2::new(320.0, 180.0);
+    assert_eq!(target.to_window(position, window), position);
+
+    target.image = Some(Handle::default());
+    target.size = UVec2::new(640, 360);
+    assert_eq!(target.to_window(position, window), Vec2::new(640.0, 360.0));
+}
@@ -0,0 +1,497 @@
+//! Replay recording and playback.
+//!
+//! Every run is recorded: each physics step, the step time, the player's
+//! input and a snapshot of the player's state are appended to a [`Replay`].
+//! A run is recorded from the moment the player spawns, and saved to
+//! `replays/last.replay` when it ends at a goal or a game over. A run that
+//! sets a new best time is also saved as `replays/<map>-best.replay`.
+//!
+//! Launching the game with `--replay <path>` loads the replay's map and
+//! plays the run back. Playback re-simulates the run by feeding the recorded
+//! input to the usual movement and physics, step by step; the fixed timestep
+//! makes this independent of the frame rate. After each step the player is
+//! compared with the snapshot. When physics has drifted further than
+//! [`DESYNC_TOLERANCE`] the first such step is logged, which points at the
+//! physics issue, and the player is put back on the recorded path. When the
+//! replay runs out the player takes over.
+//!
+//! Files are the [`REPLAY_MAGIC`] header followed by the replay encoded with
+//! bincode, like binary maps. Version 2 files, recorded before interact
+//! presses were, still load and play back without any.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::components::{GameEntity, Player};
+use super::gamepad::PlayerInput;
+use super::hot_reload::MapPathForHotReload;
+use super::speedrun::RunResult;
+use crate::states::GameState;
+use crate::systems::localization::Localization;
+
+/// File header identifying a replay; the last byte is the encoding version.
+/// Version 2 records physics steps rather than frames, and version 3 adds
+/// interact presses.
+pub const REPLAY_MAGIC: &[u8; 8] = b"ADSRPL\0\x03";
+
+/// Header of version 2 replays, which are migrated on load.
+const REPLAY_MAGIC_V2: &[u8; 8] = b"ADSRPL\0\x02";
+
+/// Folder replays are saved to.
+pub const REPLAY_DIR: &str = "replays";
+
+/// Replay of the most recent run, in [`REPLAY_DIR`].
+pub const LAST_REPLAY: &str = "last.replay";
+
+/// Distance between the re-simulated player and the recording at which
+/// playback reports a desync and snaps back to the recording.
+pub const DESYNC_TOLERANCE: f32 = 0.05;
+
+/// Errors reading or writing a replay file.
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Replay encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+    #[error("Not a replay file (missing header)")]
+    NotAReplay,
+}
+
+/// The [`PlayerInput`] fields that drive movement and physics.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ReplayInput {
+    pub movement: (f32, f32),
+    pub look_direction: (f32, f32),
+    pub jump_pressed: bool,
+    pub jump_just_pressed: bool,
+    pub interact_pressed: bool,
+}
+
+impl ReplayInput {
+    pub fn from_input(input: &PlayerInput) -> Self {
+        Self {
+            movement: input.movement.into(),
+            look_direction: input.look_direction.into(),
+            jump_pressed: input.jump_pressed,
+            jump_just_pressed: input.jump_just_pressed,
+            interact_pressed: input.interact_pressed,
+        }
+    }
+
+    /// Overwrite `input` with the recorded fields. The rest (camera, pause)
+    /// stays with the player, who can look around and pause while a replay
+    /// plays.
+    pub fn apply_to(&self, input: &mut PlayerInput) {
+        input.movement = self.movement.into();
+        input.look_direction = self.look_direction.into();
+        input.jump_pressed = self.jump_pressed;
+        input.jump_just_pressed = self.jump_just_pressed;
+        input.interact_pressed = self.interact_pressed;
+    }
+}
+
+/// The player's state at the end of a physics step.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct PlayerSnapshot {
+    pub position: (f32, f32, f32),
+    pub velocity: (f32, f32, f32),
+    pub is_grounded: bool,
+    /// Facing angle in radians
+    pub rotation: f32,
+}
+
+impl PlayerSnapshot {
+    pub fn capture(player: &Player, transform: &Transform) -> Self {
+        Self {
+            position: transform.translation.into(),
+            velocity: player.velocity.into(),
+            is_grounded: player.is_grounded,
+            rotation: player.current_rotation,
+        }
+    }
+
+    /// Put the player back in this state.
+    pub fn restore(&self, player: &mut Player, transform: &mut Transform) {
+        transform.translation = self.position.into();
+        player.velocity = self.velocity.into();
+        player.is_grounded = self.is_grounded;
+        player.current_rotation = self.rotation;
+    }
+
+    /// Distance from this snapshot's position to `position`.
+    pub fn drift(&self, position: Vec3) -> f32 {
+        Vec3::from(self.position).distance(position)
+    }
+}
+
+/// One recorded physics step.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ReplayFrame {
+    /// Step time in seconds
+    pub delta: f32,
+    pub input: ReplayInput,
+    pub player: PlayerSnapshot,
+}
+
+/// A recorded run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Replay {
+    /// Map file the run was played on; `None` for the built-in default map
+    pub map: Option<PathBuf>,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn new(map: Option<PathBuf>) -> Self {
+        Self {
+            map,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Length of the run in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.delta).sum()
+    }
+
+    /// Encode as a replay file.
+    pub fn encode(&self) -> Result<Vec<u8>, ReplayError> {
+        let mut bytes = REPLAY_MAGIC.to_vec();
+        bincode::serialize_into(&mut bytes, self)?;
+        Ok(bytes)
+    }
+
+    /// Decode a replay file produced by [`Replay::encode`], or by the
+    /// version 2 encoding.
+    pub fn decode(bytes: &[u8]) -> Result<Self, ReplayError> {
+        if let Some(body) = bytes.strip_prefix(REPLAY_MAGIC.as_slice()) {
+            return Ok(bincode::deserialize(body)?);
+        }
+        let body = bytes
+            .strip_prefix(REPLAY_MAGIC_V2.as_slice())
+            .ok_or(ReplayError::NotAReplay)?;
+        Ok(bincode::deserialize::<ReplayV2>(body)?.into())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    /// Write to `path`, creating its folder if needed.
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
+        std::fs::write(path, self.encode()?)?;
+        Ok(())
+    }
+}
+
+/// A version 2 replay, whose input has no interact presses.
+#[derive(Deserialize)]
+struct ReplayV2 {
+    map: Option<PathBuf>,
+    frames: Vec<ReplayFrameV2>,
+}
+
+#[derive(Deserialize)]
+struct ReplayFrameV2 {
+    delta: f32,
+    input: ReplayInputV2,
+    player: PlayerSnapshot,
+}
+
+#[derive(Deserialize)]
+struct ReplayInputV2 {
+    movement: (f32, f32),
+    look_direction: (f32, f32),
+    jump_pressed: bool,
+    jump_just_pressed: bool,
+}
+
+impl From<ReplayV2> for Replay {
+    fn from(replay: ReplayV2) -> Self {
+        let frames = replay
+            .frames
+            .into_iter()
+            .map(|frame| ReplayFrame {
+                delta: frame.delta,
+                input: ReplayInput {
+                    movement: frame.input.movement,
+                    look_direction: frame.input.look_direction,
+                    jump_pressed: frame.input.jump_pressed,
+                    jump_just_pressed: frame.input.jump_just_pressed,
+                    interact_pressed: false,
+                },
+                player: frame.player,
+            })
+            .collect();
+        Self {
+            map: replay.map,
+            frames,
+        }
+    }
+}
+
+/// File name of a map's best-run replay: the map name with anything but
+/// letters, digits, `-` and `_` replaced by `_`.
+pub fn best_replay_file(map: &str) -> String {
+    let name: String = map
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-best.replay", name)
+}
+
+/// The run being recorded; restarted whenever the player spawns.
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    pub replay: Option<Replay>,
+}
+
+/// A replay being played back, set up by `--replay`.
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    /// Index of the step being played
+    pub frame: usize,
+    /// Step of the first desync, once one is seen
+    pub first_desync: Option<usize>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            frame: 0,
+            first_desync: None,
+        }
+    }
+
+    /// The step being played, or `None` once the replay has run out.
+    pub fn current(&self) -> Option<&ReplayFrame> {
+        self.replay.frames.get(self.frame)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.frames.len()
+    }
+
+    /// Seconds of the replay played so far.
+    pub fn elapsed(&self) -> f32 {
+        self.replay.frames[..self.frame.min(self.replay.frames.len())]
+            .iter()
+            .map(|frame| frame.delta)
+            .sum()
+    }
+}
+
+/// Run condition: no replay is playing, so the run should be recorded.
+pub fn not_playing_replay(playback: Option<Res<ReplayPlayback>>) -> bool {
+    playback.is_none()
+}
+
+/// Start a new recording, or restart playback, when the player spawns.
+pub fn restart_replay_on_spawn(
+    spawned: Query<(), Added<Player>>,
+    map_path: Res<MapPathForHotReload>,
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<ResMut<ReplayPlayback>>,
+) {
+    if spawned.is_empty() {
+        return;
+    }
+    match playback {
+        Some(mut playback) => {
+            playback.frame = 0;
+            playback.first_desync = None;
+            info!(
+                "Playing replay: {} steps, {:.2}s",
+                playback.replay.frames.len(),
+                playback.replay.duration()
+            );
+        }
+        None => recorder.replay = Some(Replay::new(map_path.0.clone())),
+    }
+}
+
+/// Replace the step's input with the recorded input while a replay plays.
+pub fn apply_replay_input(
+    playback: Option<Res<ReplayPlayback>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    if let Some(frame) = playback.as_ref().and_then(|playback| playback.current()) {
+        frame.input.apply_to(&mut player_input);
+    }
+}
+
+/// Append this physics step to the recording.
+pub fn record_replay_frame(
+    time: Res<Time>,
+    player_input: Res<PlayerInput>,
+    mut recorder: ResMut<ReplayRecorder>,
+    player: Option<Single<(&Player, &Transform)>>,
+) {
+    let (Some(replay), Some(player)) = (recorder.replay.as_mut(), player) else {
+        return;
+    };
+    let (player, transform) = player.into_inner();
+    replay.frames.push(ReplayFrame {
+        delta: time.delta_secs(),
+        input: ReplayInput::from_input(&player_input),
+        player: PlayerSnapshot::capture(player, transform),
+    });
+}
+
+/// Compare the re-simulated player with the recording, snap back on a
+/// desync and move on to the next step.
+pub fn sync_replay_frame(
+    playback: Option<ResMut<ReplayPlayback>>,
+    player: Option<Single<(&mut Player, &mut Transform)>>,
+) {
+    let (Some(mut playback), Some(player)) = (playback, player) else {
+        return;
+    };
+    let Some(frame) = playback.current().copied() else {
+        return;
+    };
+    let (mut player, mut transform) = player.into_inner();
+    let drift = frame.player.drift(transform.translation);
+    if drift > DESYNC_TOLERANCE {
+        if playback.first_desync.is_none() {
+            warn!(
+                "Replay desync at step {} ({:.3}s): player is {:.3} units off the recording \
+                 (at {:?}, recorded {:?})",
+                playback.frame,
+                playback.elapsed(),
+                drift,
+                transform.translation,
+                Vec3::from(frame.player.position)
+            );
+            playback.first_desync = Some(playback.frame);
+        }
+        frame.player.restore(&mut player, &mut transform);
+    }
+
+    playback.frame += 1;
+    if playback.is_finished() {
+        info!(
+            "Replay finished after {:.2}s{}",
+            playback.replay.duration(),
+            match playback.first_desync {
+                Some(frame) => format!(" (first desync at step {})", frame),
+                None => " with no desync".to_string(),
+            }
+        );
+    }
+}
+
+/// Save the recorded run when it ends; runs on entering the results and
+/// game-over screens.
+pub fn save_recorded_replay(mut recorder: ResMut<ReplayRecorder>, result: Option<Res<RunResult>>) {
+    let Some(replay) = recorder.replay.take() else {
+        return;
+    };
+    if replay.frames.is_empty() {
+        return;
+    }
+    let folder = Path::new(REPLAY_DIR);
+    let mut files = vec![folder.join(LAST_REPLAY)];
+    if let Some(result) = result.filter(|result| result.is_new_best()) {
+        files.push(folder.join(best_replay_file(&result.map)));
+    }
+    for path in files {
+        match replay.save(&path) {
+            Ok(()) => info!(
+                "[Replay] Saved {} steps to {}",
+                replay.frames.len(),
+                path.display()
+            ),
+            Err(e) => warn!("[Replay] Failed to save {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Root node of the playback indicator.
+#[derive(Component)]
+pub struct ReplayHud;
+
+/// Show the playback indicator and its progress while a replay plays.
+pub fn update_replay_hud(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    localization: Res<Localization>,
+    mut hud: Query<(Entity, &mut Text), With<ReplayHud>>,
+) {
+    let Some(playback) = playback.filter(|playback| !playback.is_finished()) else {
+        for (entity, _) in &hud {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    let elapsed = format!("{:.1}", playback.elapsed());
+    let total = format!("{:.1}", playback.replay.duration());
+    let label = localization.format("replay.playing", &[("time", &elapsed), ("total", &total)]);
+    match hud.single_mut() {
+        Ok((_, mut text)) => {
+            if text.0 != label {
+                text.0 = label;
+            }
+        }
+        Err(_) => {
+            commands.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(1.0, 0.4, 0.4, 0.9)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(16.0),
+                    left: Val::Px(16.0),
+                    ..default()
+                },
+                ReplayHud,
+                GameEntity,
+            ));
+        }
+    }
+}
+
+/// Plugin for replay recording and playback, playing `playback` if given.
+///
+/// The per-step input, record and sync systems are ordered around movement
+/// and physics by the game's system sets, so the app adds them alongside
+/// those in `FixedUpdate`.
+#[derive(Default)]
+pub struct ReplayPlugin {
+    pub playback: Option<Replay>,
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(replay) = &self.playback {
+            app.insert_resource(ReplayPlayback::new(replay.clone()));
+        }
+        app.init_resource::<ReplayRecorder>()
+            .add_systems(OnEnter(GameState::Results), save_recorded_replay)
+            .add_systems(OnEnter(GameState::GameOver), save_recorded_replay)
+            .add_systems(
+                Update,
+                update_replay_hud.run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
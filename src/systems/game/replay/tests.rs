@@ -0,0 +1,135 @@
+use super::*;
+
+fn frame(delta: f32, x: f32) -> ReplayFrame {
+    ReplayFrame {
+        delta,
+        input: ReplayInput {
+            movement: (0.0, 1.0),
+            jump_just_pressed: x == 0.0,
+            ..default()
+        },
+        player: PlayerSnapshot {
+            position: (x, 0.5, 1.0),
+            velocity: (4.0, 0.0, 0.0),
+            is_grounded: true,
+            rotation: 0.0,
+        },
+    }
+}
+
+fn sample_replay() -> Replay {
+    Replay {
+        map: Some(PathBuf::from("assets/maps/default.ron")),
+        frames: vec![frame(0.016, 0.0), frame(0.017, 0.07), frame(0.016, 0.13)],
+    }
+}
+
+#[test]
+fn replays_round_trip_through_their_file_encoding() {
+    let replay = sample_replay();
+    let bytes = replay.encode().unwrap();
+    assert!(bytes.starts_with(REPLAY_MAGIC));
+    assert_eq!(Replay::decode(&bytes).unwrap(), replay);
+}
+
+#[test]
+fn version_2_replays_load_without_interact_presses() {
+    #[derive(Serialize)]
+    struct InputV2 {
+        movement: (f32, f32),
+        look_direction: (f32, f32),
+        jump_pressed: bool,
+        jump_just_pressed: bool,
+    }
+    let replay = sample_replay();
+    let frames: Vec<(f32, InputV2, PlayerSnapshot)> = replay
+        .frames
+        .iter()
+        .map(|frame| {
+            let input = InputV2 {
+                movement: frame.input.movement,
+                look_direction: frame.input.look_direction,
+                jump_pressed: frame.input.jump_pressed,
+                jump_just_pressed: frame.input.jump_just_pressed,
+            };
+            (frame.delta, input, frame.player)
+        })
+        .collect();
+    let mut bytes = b"ADSRPL\0\x02".to_vec();
+    bincode::serialize_into(&mut bytes, &(&replay.map, frames)).unwrap();
+
+    assert_eq!(Replay::decode(&bytes).unwrap(), replay);
+}
+
+#[test]
+fn decoding_rejects_files_without_the_header() {
+    assert!(matches!(
+        Replay::decode(b"ADSMAP\0\x01rest"),
+        Err(ReplayError::NotAReplay)
+    ));
+    let mut bytes = sample_replay().encode().unwrap();
+    bytes.truncate(REPLAY_MAGIC.len() + 4);
+    assert!(matches!(
+        Replay::decode(&bytes),
+        Err(ReplayError::Encoding(_))
+    ));
+}
+
+#[test]
+fn recorded_input_replaces_movement_jumps_and_interact_only() {
+    let recorded = ReplayInput {
+        movement: (0.5, -1.0),
+        jump_pressed: true,
+        interact_pressed: true,
+        ..default()
+    };
+    let mut input = PlayerInput {
+        movement: Vec2::new(1.0, 1.0),
+        look_direction: Vec2::X,
+        camera_delta: Vec2::new(2.0, 0.0),
+        pause_just_pressed: true,
+        ..default()
+    };
+    recorded.apply_to(&mut input);
+    assert_eq!(input.movement, Vec2::new(0.5, -1.0));
+    assert_eq!(input.look_direction, Vec2::ZERO);
+    assert!(input.jump_pressed && !input.jump_just_pressed);
+    assert!(input.interact_pressed);
+    assert_eq!(
+        input.camera_delta,
+        Vec2::new(2.0, 0.0),
+        "the camera stays with the player"
+    );
+    assert!(input.pause_just_pressed, "a replay can still be paused");
+    assert_eq!(ReplayInput::from_input(&input), recorded);
+}
+
+#[test]
+fn playback_tracks_progress_through_the_frames() {
+    let mut playback = ReplayPlayback::new(sample_replay());
+    assert_eq!(playback.elapsed(), 0.0);
+    assert_eq!(playback.current(), Some(&frame(0.016, 0.0)));
+    playback.frame = 2;
+    assert!((playback.elapsed() - 0.033).abs() < 1e-6);
+    assert!(!playback.is_finished());
+    playback.frame = 3;
+    assert!(playback.is_finished());
+    assert_eq!(playback.current(), None);
+    assert!((playback.elapsed() - playback.replay.duration()).abs() < 1e-6);
+}
+
+#[test]
+fn snapshots_measure_drift_from_the_recording() {
+    let snapshot = frame(0.016, 1.0).player;
+    assert_eq!(snapshot.drift(Vec3::new(1.0, 0.5, 1.0)), 0.0);
+    assert!(snapshot.drift(Vec3::new(1.0, 0.6, 1.0)) > DESYNC_TOLERANCE);
+}
+
+#[test]
+fn best_replay_files_are_named_after_the_map() {
+    assert_eq!(
+        best_replay_file("Crystal Caves"),
+        "Crystal_Caves-best.replay"
+    );
+    assert_eq!(best_replay_file("a/b:c"), "a_b_c-best.replay");
+}
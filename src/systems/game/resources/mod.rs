@@ -1,53 +1,42 @@
 use bevy::prelude::*;
-use std::collections::HashMap;
 
-pub const GRID_CELL_SIZE: f32 = 1.0;
-
-#[derive(Resource, Default)]
-pub struct SpatialGrid {
-    pub cells: HashMap<IVec3, Vec<Entity>>,
-}
+mod spatial_grid;
 
-impl SpatialGrid {
-    // Helper to convert world position to grid coordinates
-    pub fn world_to_grid_coords(pos: Vec3) -> IVec3 {
-        IVec3::new(
-            (pos.x / GRID_CELL_SIZE).floor() as i32,
-            (pos.y / GRID_CELL_SIZE).floor() as i32,
-            (pos.z / GRID_CELL_SIZE).floor() as i32,
-        )
-    }
+pub use spatial_grid::{GridHit, SpatialGrid};
 
-    // Helper to get entities in a specific cell
-    pub fn get_entities_in_cell(&self, grid_coords: IVec3) -> Option<&Vec<Entity>> {
-        self.cells.get(&grid_coords)
-    }
-
-    /// Get entities in a bounding box (e.g., player's collision area).
-    ///
-    /// Pre-allocates vector capacity based on expected entity count to reduce
-    /// heap allocations during collision checks.
-    pub fn get_entities_in_aabb(&self, min_world: Vec3, max_world: Vec3) -> Vec<Entity> {
-        let min_grid = Self::world_to_grid_coords(min_world);
-        let max_grid = Self::world_to_grid_coords(max_world);
+pub const GRID_CELL_SIZE: f32 = 1.0;
 
-        // Pre-allocate capacity based on expected entity count
-        // Estimate: number of cells × average entities per cell (~8 sub-voxels per voxel)
-        let num_cells = ((max_grid.x - min_grid.x + 1)
-            * (max_grid.y - min_grid.y + 1)
-            * (max_grid.z - min_grid.z + 1)) as usize;
-        let mut entities = Vec::with_capacity(num_cells * 8);
+/// Player movement and gravity tuning.
+///
+/// The map editor's jump preview reads the same values, so predicted jump
+/// arcs match what the player can actually do in game.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsConfig {
+    /// Vertical acceleration in units/s² (negative is down)
+    pub gravity: f32,
+    /// Upward velocity set when the player jumps
+    pub jump_velocity: f32,
+    /// Horizontal walking speed in units/s
+    pub move_speed: f32,
+    /// Radius of the player's collision cylinder
+    pub player_radius: f32,
+    /// Half height of the player's collision cylinder
+    pub player_half_height: f32,
+    /// Tallest ledge the player walks up without jumping. The default clears
+    /// a one sub-voxel (1/8) lip but not two.
+    pub step_height: f32,
+}
 
-        for x in min_grid.x..=max_grid.x {
-            for y in min_grid.y..=max_grid.y {
-                for z in min_grid.z..=max_grid.z {
-                    if let Some(cell_entities) = self.get_entities_in_cell(IVec3::new(x, y, z)) {
-                        entities.extend_from_slice(cell_entities);
-                    }
-                }
-            }
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            gravity: -32.0,
+            jump_velocity: 8.0,
+            move_speed: 3.0,
+            player_radius: 0.2,
+            player_half_height: 0.4,
+            step_height: 0.15,
         }
-        entities
     }
 }
 
@@ -56,7 +45,7 @@ pub struct GameInitialized(pub bool);
 
 /// Pre-fetched spatial grid query result shared from `move_player` to `apply_physics`.
 ///
-/// `move_player` populates this each frame when the player has movement input.
+/// `move_player` populates this each physics step when the player has movement input.
 /// `apply_physics` reads it when the stored AABB bounds contain the physics query AABB,
 /// avoiding a second `get_entities_in_aabb` call. Cleared at the start of every
 /// `move_player` run so a stale slice is never used.
@@ -68,6 +57,3 @@ pub struct PreFetchedCollisionEntities {
     /// `None` when the resource has been cleared (player not moving).
     pub bounds: Option<(Vec3, Vec3)>,
 }
-
-#[cfg(test)]
-mod tests;
@@ -0,0 +1,405 @@
+//! Broadphase for collision queries.
+//!
+//! [`SpatialGrid`] buckets entities' axis-aligned bounding boxes into cubic
+//! cells of [`GRID_CELL_SIZE`]. An entity is listed in every cell its box
+//! touches, and queries only look at the cells they cover:
+//!
+//! - [`SpatialGrid::query_aabb`] and [`SpatialGrid::query_sphere`] iterate
+//!   the entities in a neighborhood, each once.
+//! - [`SpatialGrid::raycast`] walks the cells along a ray and stops at the
+//!   first hit.
+//! - [`SpatialGrid::sweep_sphere`] finds where a moving sphere first touches
+//!   a box.
+//!
+//! Sub-voxels are inserted when the map spawns and NPCs are kept up to date
+//! as they move, so callers filter results with a query for the components
+//! they care about.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::GRID_CELL_SIZE;
+
+/// Steps used to home in on a sphere's contact with a box edge or corner.
+const SWEEP_REFINE_STEPS: usize = 32;
+
+/// An entity's box as listed in a cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GridEntry {
+    entity: Entity,
+    min: Vec3,
+    max: Vec3,
+}
+
+/// First box hit by [`SpatialGrid::raycast`] or [`SpatialGrid::sweep_sphere`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridHit {
+    pub entity: Entity,
+    /// Distance travelled along the ray or sweep before contact
+    pub distance: f32,
+    /// Where the ray meets the box; for sweeps, the sphere's center at contact
+    pub point: Vec3,
+    /// Surface normal at the contact, pointing away from the box
+    pub normal: Vec3,
+}
+
+#[derive(Resource, Default)]
+pub struct SpatialGrid {
+    cells: HashMap<IVec3, Vec<GridEntry>>,
+    bounds: HashMap<Entity, (Vec3, Vec3)>,
+}
+
+impl SpatialGrid {
+    // Helper to convert world position to grid coordinates
+    pub fn world_to_grid_coords(pos: Vec3) -> IVec3 {
+        IVec3::new(
+            (pos.x / GRID_CELL_SIZE).floor() as i32,
+            (pos.y / GRID_CELL_SIZE).floor() as i32,
+            (pos.z / GRID_CELL_SIZE).floor() as i32,
+        )
+    }
+
+    /// Number of entities in the grid.
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// The box `entity` was inserted with.
+    pub fn bounds(&self, entity: Entity) -> Option<(Vec3, Vec3)> {
+        self.bounds.get(&entity).copied()
+    }
+
+    /// Every entity in the grid with its box, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, (Vec3, Vec3))> + '_ {
+        self.bounds
+            .iter()
+            .map(|(&entity, &bounds)| (entity, bounds))
+    }
+
+    /// Add `entity` with the box `min`..`max`, replacing any box it had.
+    pub fn insert(&mut self, entity: Entity, min: Vec3, max: Vec3) {
+        self.remove(entity);
+        let entry = GridEntry { entity, min, max };
+        for cell in Self::cells_in(min, max) {
+            self.cells.entry(cell).or_default().push(entry);
+        }
+        self.bounds.insert(entity, (min, max));
+    }
+
+    /// Take `entity` out of the grid, returning the box it had.
+    pub fn remove(&mut self, entity: Entity) -> Option<(Vec3, Vec3)> {
+        let (min, max) = self.bounds.remove(&entity)?;
+        for cell in Self::cells_in(min, max) {
+            if let Some(entries) = self.cells.get_mut(&cell) {
+                entries.retain(|entry| entry.entity != entity);
+                if entries.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        Some((min, max))
+    }
+
+    /// Move `entity` to the box `min`..`max`, inserting it if it isn't in
+    /// the grid. Boxes that stay within the same cells are updated in place.
+    pub fn update(&mut self, entity: Entity, min: Vec3, max: Vec3) {
+        let Some(&(old_min, old_max)) = self.bounds.get(&entity) else {
+            self.insert(entity, min, max);
+            return;
+        };
+        let same_cells = Self::world_to_grid_coords(old_min) == Self::world_to_grid_coords(min)
+            && Self::world_to_grid_coords(old_max) == Self::world_to_grid_coords(max);
+        if !same_cells {
+            self.insert(entity, min, max);
+            return;
+        }
+        for cell in Self::cells_in(min, max) {
+            if let Some(entry) = self
+                .cells
+                .get_mut(&cell)
+                .and_then(|entries| entries.iter_mut().find(|entry| entry.entity == entity))
+            {
+                entry.min = min;
+                entry.max = max;
+            }
+        }
+        self.bounds.insert(entity, (min, max));
+    }
+
+    /// Coordinates of the cells holding at least one entity, in no
+    /// particular order.
+    pub fn occupied_cells(&self) -> impl ExactSizeIterator<Item = IVec3> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Entities whose box overlaps `min_world`..`max_world` (touching counts),
+    /// each once, with their boxes.
+    pub fn query_aabb(
+        &self,
+        min_world: Vec3,
+        max_world: Vec3,
+    ) -> impl Iterator<Item = (Entity, (Vec3, Vec3))> + '_ {
+        let min_grid = Self::world_to_grid_coords(min_world);
+        Self::cells_in(min_world, max_world)
+            .filter_map(|cell| self.cells.get(&cell).map(|entries| (cell, entries)))
+            .flat_map(move |(cell, entries)| {
+                entries.iter().filter(move |entry| {
+                    // Boxes spanning several cells are reported from the
+                    // first cell they share with the query
+                    let first = Self::world_to_grid_coords(entry.min).max(min_grid);
+                    cell == first
+                        && entry.min.cmple(max_world).all()
+                        && entry.max.cmpge(min_world).all()
+                })
+            })
+            .map(|entry| (entry.entity, (entry.min, entry.max)))
+    }
+
+    /// Get entities in a bounding box (e.g., player's collision area).
+    pub fn get_entities_in_aabb(&self, min_world: Vec3, max_world: Vec3) -> Vec<Entity> {
+        self.query_aabb(min_world, max_world)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// Entities whose box is within `radius` of `center`, each once.
+    pub fn query_sphere(
+        &self,
+        center: Vec3,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, (Vec3, Vec3))> + '_ {
+        self.query_aabb(center - Vec3::splat(radius), center + Vec3::splat(radius))
+            .filter(move |(_, (min, max))| {
+                center.distance_squared(center.clamp(*min, *max)) <= radius * radius
+            })
+    }
+
+    /// Cast a ray and return the first box it hits within `max_distance`,
+    /// skipping entities `filter` rejects. A ray starting inside a box hits
+    /// it at distance 0.
+    ///
+    /// Walks the cells the ray passes through in order and stops once a hit
+    /// is nearer than the next cell, so long rays through open space only
+    /// cost the cells they cross. `max_distance` must be finite.
+    pub fn raycast(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        mut filter: impl FnMut(Entity) -> bool,
+    ) -> Option<GridHit> {
+        debug_assert!(max_distance.is_finite(), "raycast needs a finite distance");
+        let direction = direction.try_normalize()?;
+        let mut cell = Self::world_to_grid_coords(origin);
+        let mut step = IVec3::ZERO;
+        let mut next_boundary = Vec3::INFINITY;
+        let mut boundary_spacing = Vec3::INFINITY;
+        for axis in 0..3 {
+            if direction[axis] > 0.0 {
+                step[axis] = 1;
+                next_boundary[axis] =
+                    ((cell[axis] + 1) as f32 * GRID_CELL_SIZE - origin[axis]) / direction[axis];
+            } else if direction[axis] < 0.0 {
+                step[axis] = -1;
+                next_boundary[axis] =
+                    (cell[axis] as f32 * GRID_CELL_SIZE - origin[axis]) / direction[axis];
+            } else {
+                continue;
+            }
+            boundary_spacing[axis] = GRID_CELL_SIZE / direction[axis].abs();
+        }
+
+        let mut best: Option<GridHit> = None;
+        loop {
+            for entry in self.cells.get(&cell).into_iter().flatten() {
+                if best.is_some_and(|hit| hit.entity == entry.entity) || !filter(entry.entity) {
+                    continue;
+                }
+                let Some(span) = ray_aabb(origin, direction, entry.min, entry.max) else {
+                    continue;
+                };
+                let distance = span.enter;
+                if distance <= max_distance && best.is_none_or(|hit| distance < hit.distance) {
+                    best = Some(GridHit {
+                        entity: entry.entity,
+                        distance,
+                        point: origin + direction * distance,
+                        normal: span.normal,
+                    });
+                }
+            }
+
+            let cell_exit = next_boundary.min_element();
+            if best.is_some_and(|hit| hit.distance <= cell_exit) || cell_exit > max_distance {
+                return best;
+            }
+            let axis = if next_boundary.x == cell_exit {
+                0
+            } else if next_boundary.y == cell_exit {
+                1
+            } else {
+                2
+            };
+            cell[axis] += step[axis];
+            next_boundary[axis] += boundary_spacing[axis];
+        }
+    }
+
+    /// Sweep a sphere from `center` along `motion` and return the first box
+    /// it touches, skipping entities `filter` rejects.
+    ///
+    /// A sphere already overlapping a box is stopped at once when moving
+    /// deeper into it and ignores it when moving out.
+    pub fn sweep_sphere(
+        &self,
+        center: Vec3,
+        radius: f32,
+        motion: Vec3,
+        mut filter: impl FnMut(Entity) -> bool,
+    ) -> Option<GridHit> {
+        let end = center + motion;
+        let reach = Vec3::splat(radius);
+        self.query_aabb(center.min(end) - reach, center.max(end) + reach)
+            .filter(|(entity, _)| filter(*entity))
+            .filter_map(|(entity, (min, max))| {
+                let (distance, normal) = sweep_sphere_aabb(center, radius, motion, min, max)?;
+                Some(GridHit {
+                    entity,
+                    distance,
+                    point: center + motion.normalize_or_zero() * distance,
+                    normal,
+                })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// Cells covered by the box `min`..`max`.
+    fn cells_in(min: Vec3, max: Vec3) -> impl Iterator<Item = IVec3> {
+        let min = Self::world_to_grid_coords(min);
+        let max = Self::world_to_grid_coords(max);
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+        })
+    }
+}
+
+/// Where a ray passes through a box.
+#[derive(Debug, Clone, Copy)]
+struct RaySpan {
+    /// Distance at which the ray enters; 0 when it starts inside
+    enter: f32,
+    /// Distance at which it leaves
+    exit: f32,
+    /// Normal of the face entered through; back along the ray when inside
+    normal: Vec3,
+}
+
+/// Slab test of a normalized ray against a box, ignoring what lies behind
+/// the origin.
+fn ray_aabb(origin: Vec3, direction: Vec3, min: Vec3, max: Vec3) -> Option<RaySpan> {
+    let mut span = RaySpan {
+        enter: 0.0,
+        exit: f32::INFINITY,
+        normal: -direction,
+    };
+    for axis in 0..3 {
+        let speed = direction[axis];
+        if speed == 0.0 {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let (near_face, far_face) = if speed > 0.0 {
+            (min[axis], max[axis])
+        } else {
+            (max[axis], min[axis])
+        };
+        let near = (near_face - origin[axis]) / speed;
+        if near > span.enter {
+            span.enter = near;
+            span.normal = Vec3::ZERO;
+            span.normal[axis] = -speed.signum();
+        }
+        span.exit = span.exit.min((far_face - origin[axis]) / speed);
+        if span.enter > span.exit {
+            return None;
+        }
+    }
+    Some(span)
+}
+
+/// Distance a sphere moves along `motion` before touching a box, and the
+/// contact normal.
+///
+/// The sphere hits where its center enters the box grown by `radius` with
+/// rounded edges. The grown box without rounding is cast against first;
+/// when that lands beside an edge or corner, the contact is refined along
+/// the ray, where the distance to the box is convex.
+fn sweep_sphere_aabb(
+    center: Vec3,
+    radius: f32,
+    motion: Vec3,
+    min: Vec3,
+    max: Vec3,
+) -> Option<(f32, Vec3)> {
+    let gap = |point: Vec3| point - point.clamp(min, max);
+
+    // Already overlapping: only block motion further in
+    let offset = gap(center);
+    if offset.length_squared() < radius * radius {
+        let normal = offset
+            .try_normalize()
+            .unwrap_or(-motion.normalize_or_zero());
+        return (motion.dot(normal) < 0.0).then_some((0.0, normal));
+    }
+
+    let length = motion.length();
+    let direction = motion.try_normalize()?;
+    let reach = Vec3::splat(radius);
+    let span = ray_aabb(center, direction, min - reach, max + reach)?;
+    let enter = span.enter;
+    if enter > length {
+        return None;
+    }
+
+    let distance_at = |t: f32| gap(center + direction * t).length();
+    let contact = if distance_at(enter) <= radius + 1e-5 {
+        enter
+    } else {
+        // Beside an edge or corner: find the closest approach, then the
+        // first point within the radius before it
+        let (mut low, mut high) = (enter, span.exit.min(length));
+        for _ in 0..SWEEP_REFINE_STEPS {
+            let third = (high - low) / 3.0;
+            if distance_at(low + third) < distance_at(high - third) {
+                high -= third;
+            } else {
+                low += third;
+            }
+        }
+        let closest = (low + high) / 2.0;
+        if distance_at(closest) > radius {
+            return None;
+        }
+        let (mut outside, mut inside) = (enter, closest);
+        for _ in 0..SWEEP_REFINE_STEPS {
+            let middle = (outside + inside) / 2.0;
+            if distance_at(middle) > radius {
+                outside = middle;
+            } else {
+                inside = middle;
+            }
+        }
+        outside
+    };
+    let normal = gap(center + direction * contact).normalize_or(-direction);
+    Some((contact, normal))
+}
+
+#[cfg(test)]
+mod tests;
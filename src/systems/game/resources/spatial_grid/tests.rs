@@ -0,0 +1,306 @@
+use super::*;
+
+#[test]
+fn test_world_to_grid_coords_positive() {
+    // Simple positive coordinates
+    assert_eq!(
+        SpatialGrid::world_to_grid_coords(Vec3::new(0.5, 0.5, 0.5)),
+        IVec3::new(0, 0, 0)
+    );
+    assert_eq!(
+        SpatialGrid::world_to_grid_coords(Vec3::new(1.5, 2.5, 3.5)),
+        IVec3::new(1, 2, 3)
+    );
+}
+
+#[test]
+fn test_world_to_grid_coords_negative() {
+    // Negative coordinates should floor correctly
+    assert_eq!(
+        SpatialGrid::world_to_grid_coords(Vec3::new(-0.5, -0.5, -0.5)),
+        IVec3::new(-1, -1, -1)
+    );
+    assert_eq!(
+        SpatialGrid::world_to_grid_coords(Vec3::new(-1.5, -2.5, -3.5)),
+        IVec3::new(-2, -3, -4)
+    );
+}
+
+#[test]
+fn test_world_to_grid_coords_on_boundary() {
+    // Exact boundaries
+    assert_eq!(
+        SpatialGrid::world_to_grid_coords(Vec3::new(1.0, 2.0, 3.0)),
+        IVec3::new(1, 2, 3)
+    );
+    assert_eq!(
+        SpatialGrid::world_to_grid_coords(Vec3::new(0.0, 0.0, 0.0)),
+        IVec3::new(0, 0, 0)
+    );
+}
+
+/// A box of `size` with its min corner at `min`.
+fn boxed(min: Vec3, size: f32) -> (Vec3, Vec3) {
+    (min, min + Vec3::splat(size))
+}
+
+/// Number of entities listed in one cell.
+fn in_cell(grid: &SpatialGrid, cell: IVec3) -> usize {
+    grid.cells.get(&cell).map_or(0, Vec::len)
+}
+
+fn entity(index: u32) -> Entity {
+    Entity::from_raw_u32(index).unwrap()
+}
+
+#[test]
+fn test_insert_and_query_single_entity() {
+    let mut grid = SpatialGrid::default();
+    let entity = entity(42);
+    let (min, max) = boxed(Vec3::new(1.25, 2.25, 3.25), 0.125);
+
+    grid.insert(entity, min, max);
+
+    let result: Vec<_> = grid.cells[&IVec3::new(1, 2, 3)]
+        .iter()
+        .map(|entry| entry.entity)
+        .collect();
+    assert_eq!(result, vec![entity]);
+    assert_eq!(grid.bounds(entity), Some((min, max)));
+    assert_eq!(grid.len(), 1);
+}
+
+#[test]
+fn test_get_entities_in_cell_empty() {
+    let grid = SpatialGrid::default();
+    assert_eq!(in_cell(&grid, IVec3::new(0, 0, 0)), 0);
+    assert!(grid.is_empty());
+}
+
+#[test]
+fn test_get_entities_in_aabb_single_cell() {
+    let mut grid = SpatialGrid::default();
+    let entity = entity(1);
+    let (min, max) = boxed(Vec3::splat(0.25), 0.125);
+    grid.insert(entity, min, max);
+
+    let entities = grid.get_entities_in_aabb(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.5, 0.5, 0.5));
+    assert_eq!(entities.len(), 1);
+    assert_eq!(entities[0], entity);
+}
+
+#[test]
+fn test_get_entities_in_aabb_multiple_cells() {
+    let mut grid = SpatialGrid::default();
+    let (e1, e2, e3) = (entity(1), entity(2), entity(3));
+
+    let (min, max) = boxed(Vec3::new(0.25, 0.25, 0.25), 0.125);
+    grid.insert(e1, min, max);
+    let (min, max) = boxed(Vec3::new(1.25, 0.25, 0.25), 0.125);
+    grid.insert(e2, min, max);
+    let (min, max) = boxed(Vec3::new(0.25, 1.25, 0.25), 0.125);
+    grid.insert(e3, min, max);
+
+    // Query spanning multiple cells
+    let entities = grid.get_entities_in_aabb(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.5, 1.5, 0.5));
+    assert_eq!(entities.len(), 3);
+}
+
+#[test]
+fn test_get_entities_in_aabb_empty_cells() {
+    let mut grid = SpatialGrid::default();
+    let (min, max) = boxed(Vec3::splat(5.25), 0.125);
+    grid.insert(entity(1), min, max);
+
+    // Query area that doesn't include the entity's cell
+    let entities = grid.get_entities_in_aabb(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0));
+    assert!(entities.is_empty());
+}
+
+#[test]
+fn test_cell_boundary_handling() {
+    let mut grid = SpatialGrid::default();
+    let entity = entity(1);
+    // Box starting at exact boundary (1.0, 1.0, 1.0) should be in cell (1, 1, 1)
+    let (min, max) = boxed(Vec3::splat(1.0), 0.125);
+    grid.insert(entity, min, max);
+    assert_eq!(in_cell(&grid, IVec3::new(1, 1, 1)), 1);
+
+    // Query that includes cell (1, 1, 1)
+    let entities = grid.get_entities_in_aabb(Vec3::new(0.5, 0.5, 0.5), Vec3::new(1.5, 1.5, 1.5));
+    assert_eq!(entities.len(), 1);
+}
+
+#[test]
+fn boxes_spanning_cells_are_listed_in_each_and_reported_once() {
+    let mut grid = SpatialGrid::default();
+    let wide = entity(1);
+    grid.insert(wide, Vec3::new(0.5, 0.0, 0.5), Vec3::new(2.5, 0.5, 0.75));
+    for x in 0..=2 {
+        assert_eq!(in_cell(&grid, IVec3::new(x, 0, 0)), 1);
+    }
+
+    let found = grid.get_entities_in_aabb(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(3.0, 1.0, 1.0));
+    assert_eq!(found, vec![wide]);
+    let found = grid.get_entities_in_aabb(Vec3::new(1.5, 0.0, 0.5), Vec3::new(2.0, 0.5, 0.6));
+    assert_eq!(found, vec![wide], "a query starting mid-box still sees it");
+}
+
+#[test]
+fn queries_skip_boxes_that_share_a_cell_but_do_not_overlap() {
+    let mut grid = SpatialGrid::default();
+    let (min, max) = boxed(Vec3::splat(0.75), 0.125);
+    grid.insert(entity(1), min, max);
+    assert!(grid
+        .get_entities_in_aabb(Vec3::ZERO, Vec3::splat(0.5))
+        .is_empty());
+    assert_eq!(grid.query_aabb(Vec3::ZERO, Vec3::splat(0.75)).count(), 1);
+}
+
+#[test]
+fn removing_and_updating_move_entities_between_cells() {
+    let mut grid = SpatialGrid::default();
+    let npc = entity(7);
+    let (min, max) = boxed(Vec3::splat(0.25), 0.5);
+    grid.insert(npc, min, max);
+
+    // Within the same cell: only the box changes
+    let (min, max) = boxed(Vec3::splat(0.3), 0.5);
+    grid.update(npc, min, max);
+    assert_eq!(grid.bounds(npc), Some((min, max)));
+    assert_eq!(grid.query_aabb(min, max).next(), Some((npc, (min, max))));
+
+    // Into another cell
+    let (min, max) = boxed(Vec3::new(4.25, 0.25, 0.25), 0.5);
+    grid.update(npc, min, max);
+    assert_eq!(in_cell(&grid, IVec3::ZERO), 0);
+    assert_eq!(grid.get_entities_in_aabb(min, max), vec![npc]);
+
+    assert_eq!(grid.remove(npc), Some((min, max)));
+    assert_eq!(grid.remove(npc), None);
+    assert!(grid.is_empty());
+    assert_eq!(in_cell(&grid, IVec3::new(4, 0, 0)), 0);
+}
+
+#[test]
+fn inserting_again_replaces_the_box() {
+    let mut grid = SpatialGrid::default();
+    let entity = entity(3);
+    grid.insert(entity, Vec3::ZERO, Vec3::splat(0.5));
+    grid.insert(entity, Vec3::splat(2.0), Vec3::splat(2.5));
+    assert_eq!(grid.len(), 1);
+    assert_eq!(grid.iter().count(), 1);
+    assert_eq!(in_cell(&grid, IVec3::ZERO), 0);
+}
+
+#[test]
+fn sphere_queries_skip_box_corners_outside_the_radius() {
+    let mut grid = SpatialGrid::default();
+    let (min, max) = boxed(Vec3::splat(1.0), 0.5);
+    grid.insert(entity(1), min, max);
+    // The box corner is sqrt(3) * 0.5 ≈ 0.87 from (0.5, 0.5, 0.5)
+    let center = Vec3::splat(0.5);
+    assert_eq!(grid.query_sphere(center, 0.8).count(), 0);
+    assert_eq!(grid.query_sphere(center, 0.9).count(), 1);
+}
+
+#[test]
+fn raycasts_return_the_nearest_hit_along_the_ray() {
+    let mut grid = SpatialGrid::default();
+    let (near, far) = (entity(1), entity(2));
+    grid.insert(near, Vec3::new(3.0, 0.0, 0.0), Vec3::new(3.5, 1.0, 1.0));
+    grid.insert(far, Vec3::new(6.0, 0.0, 0.0), Vec3::new(6.5, 1.0, 1.0));
+
+    let origin = Vec3::new(0.0, 0.5, 0.5);
+    let hit = grid.raycast(origin, Vec3::X, 10.0, |_| true).unwrap();
+    assert_eq!(hit.entity, near);
+    assert!((hit.distance - 3.0).abs() < 1e-5);
+    assert_eq!(hit.point, Vec3::new(3.0, 0.5, 0.5));
+    assert_eq!(hit.normal, Vec3::NEG_X);
+
+    // Filtered out, or out of range
+    let hit = grid.raycast(origin, Vec3::X, 10.0, |e| e != near).unwrap();
+    assert_eq!(hit.entity, far);
+    assert!(grid.raycast(origin, Vec3::X, 2.0, |_| true).is_none());
+    assert!(grid.raycast(origin, Vec3::NEG_X, 10.0, |_| true).is_none());
+}
+
+#[test]
+fn raycasts_cross_cells_diagonally_and_backwards() {
+    let mut grid = SpatialGrid::default();
+    let target = entity(1);
+    let (min, max) = boxed(Vec3::new(-3.0, -3.0, -3.0), 1.0);
+    grid.insert(target, min, max);
+
+    let origin = Vec3::new(1.5, 1.5, 1.5);
+    let hit = grid.raycast(origin, Vec3::NEG_ONE, 20.0, |_| true).unwrap();
+    assert_eq!(hit.entity, target);
+    assert!((hit.point - Vec3::splat(-2.0)).length() < 1e-4);
+
+    // Starting inside a box hits it at once
+    let inside = grid
+        .raycast(Vec3::splat(-2.5), Vec3::Y, 5.0, |_| true)
+        .unwrap();
+    assert_eq!(inside.distance, 0.0);
+}
+
+#[test]
+fn sphere_sweeps_stop_on_faces_and_edges() {
+    let mut grid = SpatialGrid::default();
+    let wall = entity(1);
+    grid.insert(wall, Vec3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 1.0, 1.0));
+
+    // Face on: stops one radius short
+    let hit = grid
+        .sweep_sphere(
+            Vec3::new(0.0, 0.5, 0.5),
+            0.25,
+            Vec3::new(4.0, 0.0, 0.0),
+            |_| true,
+        )
+        .unwrap();
+    assert_eq!(hit.entity, wall);
+    assert!((hit.distance - 1.75).abs() < 1e-5);
+    assert_eq!(hit.normal, Vec3::NEG_X);
+
+    // Passing above the top edge: touches only when within the radius
+    let motion = Vec3::new(4.0, 0.0, 0.0);
+    assert!(grid
+        .sweep_sphere(Vec3::new(0.0, 1.3, 0.5), 0.25, motion, |_| true)
+        .is_none());
+    let graze = grid
+        .sweep_sphere(Vec3::new(0.0, 1.2, 0.5), 0.25, motion, |_| true)
+        .unwrap();
+    // The center meets the edge's rounding: (x - 2)² + 0.2² = 0.25²
+    assert!((graze.point.x - (2.0 - 0.15)).abs() < 1e-3);
+    assert!(graze.normal.y > 0.0 && graze.normal.x < 0.0);
+}
+
+#[test]
+fn sphere_sweeps_let_overlapping_spheres_move_out() {
+    let mut grid = SpatialGrid::default();
+    grid.insert(entity(1), Vec3::ZERO, Vec3::ONE);
+    let center = Vec3::new(1.1, 0.5, 0.5);
+    assert!(grid
+        .sweep_sphere(center, 0.25, Vec3::new(1.0, 0.0, 0.0), |_| true)
+        .is_none());
+    let hit = grid
+        .sweep_sphere(center, 0.25, Vec3::new(-1.0, 0.0, 0.0), |_| true)
+        .unwrap();
+    assert_eq!(hit.distance, 0.0);
+}
+
+#[test]
+fn test_occupied_cells_follow_inserts_and_removals() {
+    let mut grid = SpatialGrid::default();
+    grid.insert(
+        entity(1),
+        Vec3::new(0.2, 0.2, 0.2),
+        Vec3::new(1.2, 0.8, 0.8),
+    );
+    let mut cells: Vec<_> = grid.occupied_cells().map(|cell| cell.to_array()).collect();
+    cells.sort();
+    assert_eq!(cells, vec![[0, 0, 0], [1, 0, 0]]);
+    grid.remove(entity(1));
+    assert_eq!(grid.occupied_cells().len(), 0);
+}
@@ -0,0 +1,205 @@
+//! Rhai scripts run by map entities.
+//!
+//! The `script <path>` action (or an entity's `script` property) runs a
+//! [Rhai](https://rhai.rs) script. Paths are relative to the map file. A
+//! script can't touch the game directly: it queues the same actions an
+//! action list would, through this API:
+//!
+//! - `say(text)` — show text on screen
+//! - `wait(seconds)` — delay the actions queued after it
+//! - `play_sound(path)` — play a sound asset
+//! - `player_position()` — the player's position as `#{x, y, z}`, or `()`
+//!   without a player
+//! - `set_player_position(x, y, z)` / `teleport_to(name)` — move the player
+//! - `show_group(name)`, `hide_group(name)`, `toggle_group(name)`,
+//!   `solidify_group(name)`, `dissolve_group(name)` — change a voxel group
+//!
+//! The script runs to its end at once; queries see the game as it was when
+//! it started. Its actions then run before anything queued after the
+//! `script` action. Scripts are compiled on first use and recompiled when
+//! the file changes, so edits apply the next time the script runs, with or
+//! without a map reload. Runaway scripts are stopped by operation limits.
+
+use bevy::prelude::*;
+use rhai::{Dynamic, Engine, EvalAltResult, AST};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use super::actions::MapAction;
+use super::go_to::GoToTarget;
+use super::voxel_groups::GroupChange;
+
+/// Entity property naming a script to run after the entity's actions.
+pub const SCRIPT_KEY: &str = "script";
+
+/// Most operations a script may run before it is stopped.
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// Deepest function call nesting a script may use.
+const MAX_CALL_LEVELS: usize = 32;
+
+/// Most actions a single run may queue.
+const MAX_QUEUED_ACTIONS: usize = 256;
+
+/// Game state a script can query.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScriptContext {
+    pub player_position: Option<Vec3>,
+}
+
+/// A compiled script and the modification time of its file.
+struct CachedScript {
+    ast: AST,
+    modified: Option<SystemTime>,
+}
+
+/// Compiled scripts by path.
+#[derive(Resource, Default)]
+pub struct ScriptLibrary {
+    scripts: HashMap<PathBuf, CachedScript>,
+}
+
+impl ScriptLibrary {
+    /// Compiled script at `path`, compiling it on first use and again when
+    /// the file has changed since.
+    pub fn load(&mut self, path: &Path) -> Result<&AST, String> {
+        let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+        let stale = self
+            .scripts
+            .get(path)
+            .is_none_or(|cached| cached.modified != modified || modified.is_none());
+        if stale {
+            let source = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read script {:?}: {}", path, e))?;
+            let ast = compile_script(&source)
+                .map_err(|e| format!("Failed to compile script {:?}: {}", path, e))?;
+            info!("Compiled script {:?}", path);
+            self.scripts
+                .insert(path.to_path_buf(), CachedScript { ast, modified });
+        }
+        Ok(&self.scripts[path].ast)
+    }
+}
+
+/// Path of `script` relative to the directory of the map file, or as given
+/// when there is no map file.
+pub fn resolve_script_path(map_path: Option<&Path>, script: &str) -> PathBuf {
+    let script = Path::new(script);
+    match map_path.and_then(Path::parent) {
+        Some(dir) if script.is_relative() => dir.join(script),
+        _ => script.to_path_buf(),
+    }
+}
+
+/// Engine with the script limits but without the game API, for compiling.
+fn limited_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.on_print(|text| info!("[script] {}", text));
+    engine.on_debug(|text, _, pos| debug!("[script {}] {}", pos, text));
+    engine
+}
+
+/// Compile script source.
+pub fn compile_script(source: &str) -> Result<AST, String> {
+    limited_engine().compile(source).map_err(|e| e.to_string())
+}
+
+/// A script argument as a number, accepting integers and floats.
+fn number(value: &Dynamic) -> Result<f32, Box<EvalAltResult>> {
+    value
+        .as_float()
+        .or_else(|_| value.as_int().map(|int| int as rhai::FLOAT))
+        .map(|float| float as f32)
+        .map_err(|type_name| format!("expected a number, got {}", type_name).into())
+}
+
+/// Add an action to a script run's queue.
+fn queue(queued: &Mutex<Vec<MapAction>>, action: MapAction) -> Result<(), Box<EvalAltResult>> {
+    let mut queued = queued.lock().map_err(|_| "action queue poisoned")?;
+    if queued.len() >= MAX_QUEUED_ACTIONS {
+        return Err(format!("a script may queue at most {} actions", MAX_QUEUED_ACTIONS).into());
+    }
+    queued.push(action);
+    Ok(())
+}
+
+/// Run a compiled script and return the actions it queued.
+pub fn run_script(ast: &AST, context: ScriptContext) -> Result<Vec<MapAction>, String> {
+    let queued = Arc::new(Mutex::new(Vec::new()));
+
+    let mut engine = limited_engine();
+
+    let q = queued.clone();
+    engine.register_fn("say", move |text: &str| {
+        queue(&q, MapAction::Say(text.to_string()))
+    });
+    let q = queued.clone();
+    engine.register_fn("wait", move |seconds: Dynamic| {
+        let seconds = number(&seconds)?;
+        if !seconds.is_finite() || seconds < 0.0 {
+            return Err("wait needs a non-negative number of seconds".into());
+        }
+        queue(&q, MapAction::Wait(seconds))
+    });
+    let q = queued.clone();
+    engine.register_fn("play_sound", move |path: &str| {
+        queue(&q, MapAction::Sound(path.to_string()))
+    });
+    let q = queued.clone();
+    engine.register_fn(
+        "set_player_position",
+        move |x: Dynamic, y: Dynamic, z: Dynamic| {
+            let position = Vec3::new(number(&x)?, number(&y)?, number(&z)?);
+            queue(&q, MapAction::Teleport(GoToTarget::Coordinates(position)))
+        },
+    );
+    let q = queued.clone();
+    engine.register_fn("teleport_to", move |name: &str| {
+        queue(
+            &q,
+            MapAction::Teleport(GoToTarget::Entity(name.to_string())),
+        )
+    });
+    for (function, change) in [
+        ("show_group", GroupChange::Show),
+        ("hide_group", GroupChange::Hide),
+        ("toggle_group", GroupChange::Toggle),
+        ("solidify_group", GroupChange::Solidify),
+        ("dissolve_group", GroupChange::Dissolve),
+    ] {
+        let q = queued.clone();
+        engine.register_fn(function, move |name: &str| {
+            queue(
+                &q,
+                MapAction::Group {
+                    name: name.to_string(),
+                    change,
+                },
+            )
+        });
+    }
+    let player_position = context.player_position;
+    engine.register_fn("player_position", move || match player_position {
+        Some(position) => {
+            let mut map = rhai::Map::new();
+            map.insert("x".into(), Dynamic::from_float(position.x as rhai::FLOAT));
+            map.insert("y".into(), Dynamic::from_float(position.y as rhai::FLOAT));
+            map.insert("z".into(), Dynamic::from_float(position.z as rhai::FLOAT));
+            Dynamic::from_map(map)
+        }
+        None => Dynamic::UNIT,
+    });
+
+    engine.run_ast(ast).map_err(|e| e.to_string())?;
+    drop(engine);
+    let actions = std::mem::take(&mut *queued.lock().map_err(|_| "action queue poisoned")?);
+    Ok(actions)
+}
+
+#[cfg(test)]
+mod tests;
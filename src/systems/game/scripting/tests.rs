@@ -0,0 +1,104 @@
+use super::*;
+
+fn run(source: &str, context: ScriptContext) -> Result<Vec<MapAction>, String> {
+    run_script(&compile_script(source)?, context)
+}
+
+#[test]
+fn scripts_queue_map_actions() {
+    let actions = run(
+        r#"
+        say("The bridge is down!");
+        wait(1);
+        hide_group("bridge");
+        play_sound("sounds/click.ogg");
+        set_player_position(4, 2.5, -7);
+        teleport_to("Shrine");
+        "#,
+        ScriptContext::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        actions,
+        vec![
+            MapAction::Say("The bridge is down!".to_string()),
+            MapAction::Wait(1.0),
+            MapAction::Group {
+                name: "bridge".to_string(),
+                change: GroupChange::Hide,
+            },
+            MapAction::Sound("sounds/click.ogg".to_string()),
+            MapAction::Teleport(GoToTarget::Coordinates(Vec3::new(4.0, 2.5, -7.0))),
+            MapAction::Teleport(GoToTarget::Entity("Shrine".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn scripts_read_the_player_position() {
+    let source = r#"
+        let pos = player_position();
+        if pos == () {
+            say("nobody");
+        } else if pos.y < 0.0 {
+            set_player_position(pos.x, 10, pos.z);
+        }
+    "#;
+
+    let context = ScriptContext {
+        player_position: Some(Vec3::new(1.0, -3.0, 2.0)),
+    };
+    assert_eq!(
+        run(source, context).unwrap(),
+        vec![MapAction::Teleport(GoToTarget::Coordinates(Vec3::new(
+            1.0, 10.0, 2.0
+        )))]
+    );
+    assert_eq!(
+        run(source, ScriptContext::default()).unwrap(),
+        vec![MapAction::Say("nobody".to_string())]
+    );
+}
+
+#[test]
+fn bad_and_runaway_scripts_fail() {
+    assert!(compile_script("say(").is_err());
+    assert!(run("wait(-1);", ScriptContext::default()).is_err());
+    assert!(run("wait(\"soon\");", ScriptContext::default()).is_err());
+    assert!(run("loop {}", ScriptContext::default()).is_err());
+    assert!(run("loop { say(\"spam\"); }", ScriptContext::default()).is_err());
+}
+
+#[test]
+fn script_paths_are_relative_to_the_map() {
+    let map = Path::new("assets/maps/village.ron");
+    assert_eq!(
+        resolve_script_path(Some(map), "scripts/door.rhai"),
+        PathBuf::from("assets/maps/scripts/door.rhai")
+    );
+    assert_eq!(
+        resolve_script_path(None, "scripts/door.rhai"),
+        PathBuf::from("scripts/door.rhai")
+    );
+}
+
+#[test]
+fn library_recompiles_changed_scripts() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("door.rhai");
+    let mut library = ScriptLibrary::default();
+
+    fs::write(&path, r#"say("one");"#).unwrap();
+    let first = run_script(library.load(&path).unwrap(), ScriptContext::default()).unwrap();
+    assert_eq!(first, vec![MapAction::Say("one".to_string())]);
+
+    fs::write(&path, r#"say("two");"#).unwrap();
+    let file = fs::File::options().write(true).open(&path).unwrap();
+    file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+        .unwrap();
+    let second = run_script(library.load(&path).unwrap(), ScriptContext::default()).unwrap();
+    assert_eq!(second, vec![MapAction::Say("two".to_string())]);
+
+    assert!(library.load(&dir.path().join("missing.rhai")).is_err());
+}
@@ -0,0 +1,74 @@
+//! Completion fanfare: a rising arpeggio of sine tones, or a goal's own
+//! `fanfare` sound.
+
+use bevy::audio::{Pitch, Volume};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Notes of the built-in fanfare: frequency in Hz, start and length in
+/// seconds.
+pub const FANFARE_NOTES: [(f32, f32, f32); 5] = [
+    (523.25, 0.0, 0.12),  // C5
+    (659.25, 0.12, 0.12), // E5
+    (783.99, 0.24, 0.12), // G5
+    (1046.5, 0.36, 0.5),  // C6
+    (783.99, 0.36, 0.5),  // G5 under the last note
+];
+
+const FANFARE_VOLUME: f32 = 0.2;
+
+/// A fanfare being played; notes start as their time comes.
+#[derive(Resource, Debug, Default)]
+pub struct Fanfare {
+    elapsed: f32,
+    /// Index of the next note to start
+    next: usize,
+}
+
+impl Fanfare {
+    /// Advance by `delta` seconds; returns the notes that start now.
+    pub fn advance(&mut self, delta: f32) -> &'static [(f32, f32, f32)] {
+        self.elapsed += delta;
+        let start = self.next;
+        while self.next < FANFARE_NOTES.len() && FANFARE_NOTES[self.next].1 <= self.elapsed {
+            self.next += 1;
+        }
+        &FANFARE_NOTES[start..self.next]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next == FANFARE_NOTES.len()
+    }
+}
+
+/// Start the completion fanfare: `sound` if the goal names one, else the
+/// built-in arpeggio.
+pub fn play_fanfare(commands: &mut Commands, asset_server: &AssetServer, sound: Option<&str>) {
+    match sound {
+        Some(path) => {
+            commands.spawn((
+                AudioPlayer::new(asset_server.load::<AudioSource>(path.to_string())),
+                PlaybackSettings::DESPAWN,
+            ));
+        }
+        None => commands.insert_resource(Fanfare::default()),
+    }
+}
+
+/// Play the built-in fanfare's notes as their time comes.
+pub fn run_fanfare(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut fanfare: ResMut<Fanfare>,
+    mut pitches: ResMut<Assets<Pitch>>,
+) {
+    for &(frequency, _, length) in fanfare.advance(time.delta_secs()) {
+        commands.spawn((
+            AudioPlayer(pitches.add(Pitch::new(frequency, Duration::from_secs_f32(length)))),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(FANFARE_VOLUME)),
+        ));
+    }
+    if fanfare.is_done() {
+        commands.remove_resource::<Fanfare>();
+    }
+}
@@ -0,0 +1,114 @@
+//! Speedrun timer HUD: the run's time and the map's best, top right.
+
+use bevy::prelude::*;
+
+use super::{format_time, SaveData, SpeedrunSettings, SpeedrunTimer, TimerPhase};
+use crate::systems::game::components::GameEntity;
+use crate::systems::game::hud_layout::HudLayout;
+use crate::systems::localization::Localization;
+
+const TIME_COLOR: Color = Color::srgba(0.95, 0.95, 0.95, 0.95);
+const WAITING_COLOR: Color = Color::srgba(0.95, 0.95, 0.95, 0.5);
+const BEST_COLOR: Color = Color::srgba(1.0, 0.8, 0.2, 0.9);
+
+/// Root node of the timer.
+#[derive(Component)]
+pub struct SpeedrunHud;
+
+/// Text showing the run's time.
+#[derive(Component)]
+pub struct SpeedrunTimeText;
+
+/// Text showing the map's best time.
+#[derive(Component)]
+pub struct SpeedrunBestText;
+
+fn spawn_hud(commands: &mut Commands, layout: &HudLayout) {
+    let anchor = layout.timer_anchor;
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                // Line the text up with the screen edge it sits against
+                align_items: if anchor.is_left() {
+                    AlignItems::FlexStart
+                } else {
+                    AlignItems::FlexEnd
+                },
+                ..layout.anchored_node(anchor)
+            },
+            SpeedrunHud,
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format_time(0.0)),
+                TextFont {
+                    font_size: 28.0 * layout.scale,
+                    ..default()
+                },
+                TextColor(WAITING_COLOR),
+                SpeedrunTimeText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0 * layout.scale,
+                    ..default()
+                },
+                TextColor(BEST_COLOR),
+                SpeedrunBestText,
+            ));
+        });
+}
+
+/// Show or hide the timer with the **Speedrun Timer** option, respawn it
+/// when the HUD layout changes and keep its text up to date.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub fn update_speedrun_hud(
+    mut commands: Commands,
+    settings: Res<SpeedrunSettings>,
+    timer: Option<Res<SpeedrunTimer>>,
+    save: Res<SaveData>,
+    localization: Res<Localization>,
+    layout: Res<HudLayout>,
+    hud: Query<Entity, With<SpeedrunHud>>,
+    mut time_text: Query<(&mut Text, &mut TextColor), With<SpeedrunTimeText>>,
+    mut best_text: Query<&mut Text, (With<SpeedrunBestText>, Without<SpeedrunTimeText>)>,
+) {
+    let Some(timer) = timer.filter(|_| settings.show_timer) else {
+        for entity in &hud {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    if hud.is_empty() || layout.is_changed() {
+        for entity in &hud {
+            commands.entity(entity).despawn();
+        }
+        spawn_hud(&mut commands, &layout);
+        return;
+    }
+
+    for (mut text, mut color) in &mut time_text {
+        text.0 = format_time(timer.elapsed);
+        color.0 = match timer.phase {
+            TimerPhase::Waiting => WAITING_COLOR,
+            TimerPhase::Running | TimerPhase::Finished => TIME_COLOR,
+        };
+    }
+    let best = save
+        .best_time(&timer.map)
+        .map(|best| {
+            let time = format_time(best);
+            localization.format("speedrun.best", &[("time", &time)])
+        })
+        .unwrap_or_default();
+    for mut text in &mut best_text {
+        // Only touch the text when it changes, to skip relayout
+        if text.0 != best {
+            text.0 = best.clone();
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Speedrun timer, per-map best times and level completion.
+//!
+//! Every map spawn resets the [`SpeedrunTimer`]. It starts the first time the
+//! player moves or jumps, only runs in [`GameState::InGame`] (so menus pause
+//! it), and stops when the player reaches a `Goal` entity. The finished run
+//! is compared with the map's best time in [`SaveData`], which is written to
+//! `save.ron` when the run beats it. Reaching the goal plays a fanfare and
+//! opens the results screen, which shows the time, the collectibles picked up
+//! and, when the goal names a `next_map`, offers to load it.
+//!
+//! The [`hud`] submodule draws the timer while the **Speedrun Timer** option
+//! is on; [`fanfare`] plays the completion fanfare.
+
+pub mod fanfare;
+pub mod hud;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::components::Player;
+use super::doors::KeyItem;
+use super::gamepad::PlayerInput;
+use super::hot_reload::MapPathForHotReload;
+use super::map::format::{EntityType, MapData};
+use super::map::LoadedMapData;
+use super::particles::ParticleBurst;
+use super::replay::ReplayPlayback;
+use crate::states::GameState;
+
+/// Radius used when a goal doesn't set `radius`.
+pub const DEFAULT_GOAL_RADIUS: f32 = 1.0;
+
+/// Color of goal beacons and their radius in the editor.
+pub const GOAL_COLOR: Color = Color::srgb(0.3, 0.9, 0.4);
+
+/// File best times are saved to.
+pub const SAVE_FILE: &str = "save.ron";
+
+/// A goal entity; reaching it finishes the run.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Goal {
+    pub radius: f32,
+    /// Map file to offer next, relative to the current map's folder
+    pub next_map: Option<String>,
+    /// Sound played instead of the built-in fanfare
+    pub fanfare: Option<String>,
+}
+
+impl Goal {
+    /// Read a goal from entity properties: `radius`, `next_map` and
+    /// `fanfare`, all optional.
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Self, String> {
+        let radius = match properties.get("radius") {
+            None => DEFAULT_GOAL_RADIUS,
+            Some(value) => match value.parse::<f32>() {
+                Ok(radius) if radius > 0.0 => radius,
+                _ => return Err(format!("expected a positive 'radius', got {:?}", value)),
+            },
+        };
+        let optional = |key: &str| match properties.get(key).map(|value| value.trim()) {
+            None => Ok(None),
+            Some("") => Err(format!("'{}' must not be empty", key)),
+            Some(value) => Ok(Some(value.to_string())),
+        };
+        Ok(Self {
+            radius,
+            next_map: optional("next_map")?,
+            fanfare: optional("fanfare")?,
+        })
+    }
+}
+
+/// Resolve a goal's `next_map` against the folder of the map in play.
+pub fn resolve_next_map(next_map: &str, current_map: Option<&Path>) -> PathBuf {
+    let next = Path::new(next_map);
+    match current_map.and_then(Path::parent) {
+        Some(folder) if next.is_relative() => folder.join(next),
+        _ => next.to_path_buf(),
+    }
+}
+
+/// Number of collectibles a map places: the key items the player can pick
+/// up.
+pub fn count_collectibles(map: &MapData) -> usize {
+    map.entities
+        .iter()
+        .filter(|entity| {
+            entity.entity_type == EntityType::Item && entity.properties.contains_key("key")
+        })
+        .count()
+}
+
+/// Map file the next `LoadingMap` should load instead of the usual one;
+/// set by the results screen's **Next Map** choice.
+#[derive(Resource, Debug, Default)]
+pub struct NextMapRequest(pub Option<PathBuf>);
+
+/// Options for the speedrun timer, set from the settings screen.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SpeedrunSettings {
+    /// Show the timer on the HUD
+    pub show_timer: bool,
+}
+
+/// Where a run is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerPhase {
+    /// Waiting for the player to move
+    Waiting,
+    Running,
+    /// Stopped at a goal
+    Finished,
+}
+
+/// Time of the run on the current map; replaced whenever a map spawns.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SpeedrunTimer {
+    /// Name of the map being run, the key of its best time
+    pub map: String,
+    pub phase: TimerPhase,
+    /// Seconds since the player first moved
+    pub elapsed: f32,
+}
+
+impl SpeedrunTimer {
+    /// A timer waiting to start on `map`.
+    pub fn new(map: &str) -> Self {
+        Self {
+            map: map.to_string(),
+            phase: TimerPhase::Waiting,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Start the run if it hasn't started yet.
+    pub fn start(&mut self) {
+        if self.phase == TimerPhase::Waiting {
+            self.phase = TimerPhase::Running;
+        }
+    }
+
+    /// Count `delta` seconds while running.
+    pub fn tick(&mut self, delta: f32) {
+        if self.phase == TimerPhase::Running {
+            self.elapsed += delta;
+        }
+    }
+
+    /// Stop the run; returns its time, or `None` if it wasn't running.
+    pub fn finish(&mut self) -> Option<f32> {
+        if self.phase != TimerPhase::Running {
+            return None;
+        }
+        self.phase = TimerPhase::Finished;
+        Some(self.elapsed)
+    }
+}
+
+/// Progress kept between sessions in `save.ron`.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SaveData {
+    /// Fastest finish in seconds, by map name
+    #[serde(default)]
+    pub best_times: BTreeMap<String, f32>,
+}
+
+impl SaveData {
+    pub fn best_time(&self, map: &str) -> Option<f32> {
+        self.best_times.get(map).copied()
+    }
+
+    /// Record a finish of `map` in `time` seconds; returns true if it is a
+    /// new best.
+    pub fn record(&mut self, map: &str, time: f32) -> bool {
+        if self.best_time(map).is_some_and(|best| best <= time) {
+            return false;
+        }
+        self.best_times.insert(map.to_string(), time);
+        true
+    }
+}
+
+/// A finished run, shown on the results screen.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct RunResult {
+    pub map: String,
+    pub time: f32,
+    /// Best time before this run, if the map had been finished before
+    pub previous_best: Option<f32>,
+    /// Collectibles picked up, out of `collectibles`
+    pub collected: usize,
+    pub collectibles: usize,
+    /// Map file the goal leads to
+    pub next_map: Option<PathBuf>,
+}
+
+impl RunResult {
+    /// Whether this run beat the previous best (or is the first finish).
+    pub fn is_new_best(&self) -> bool {
+        self.previous_best.is_none_or(|best| self.time < best)
+    }
+}
+
+/// Format `seconds` as `m:ss.mmm`.
+pub fn format_time(seconds: f32) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{}:{:02}.{:03}",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Format the gap between a run and a best time as `+m:ss.mmm` or
+/// `-m:ss.mmm`.
+pub fn format_difference(time: f32, best: f32) -> String {
+    let sign = if time < best { '-' } else { '+' };
+    format!("{}{}", sign, format_time((time - best).abs()))
+}
+
+/// Loads best times from `save.ron` on startup.
+pub fn load_save_data(mut save: ResMut<SaveData>) {
+    match std::fs::read_to_string(SAVE_FILE) {
+        Ok(contents) => match ron::from_str::<SaveData>(&contents) {
+            Ok(loaded) => {
+                *save = loaded;
+                info!(
+                    "[Save] Loaded {} best times from {}",
+                    save.best_times.len(),
+                    SAVE_FILE
+                );
+            }
+            Err(e) => warn!("[Save] Failed to parse {SAVE_FILE}: {e}; starting fresh"),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            // No save yet on first run
+        }
+        Err(e) => warn!("[Save] Could not read {SAVE_FILE}: {e}"),
+    }
+}
+
+/// Writes best times to `save.ron`.
+fn write_save_data(save: &SaveData) {
+    match ron::to_string(save) {
+        Ok(contents) => {
+            if let Err(e) = std::fs::write(SAVE_FILE, contents) {
+                warn!("[Save] Failed to write {SAVE_FILE}: {e}");
+            } else {
+                info!("[Save] Saved best times to {SAVE_FILE}");
+            }
+        }
+        Err(e) => warn!("[Save] Failed to serialize save data: {e}"),
+    }
+}
+
+/// Start the timer the first time the player moves or jumps.
+pub fn start_speedrun_timer(input: Res<PlayerInput>, timer: Option<ResMut<SpeedrunTimer>>) {
+    let Some(mut timer) = timer else {
+        return;
+    };
+    if timer.phase == TimerPhase::Waiting
+        && (input.movement != Vec2::ZERO || input.jump_just_pressed)
+    {
+        timer.start();
+        info!("Speedrun timer started on '{}'", timer.map);
+    }
+}
+
+/// Advance a running timer by one physics step, so run times don't depend
+/// on the frame rate.
+pub fn tick_speedrun_timer(time: Res<Time>, timer: Option<ResMut<SpeedrunTimer>>) {
+    if let Some(mut timer) = timer {
+        if timer.phase == TimerPhase::Running {
+            timer.tick(time.delta_secs());
+        }
+    }
+}
+
+/// Stop the timer when the player reaches a goal, record the time, play the
+/// fanfare and show the results screen.
+#[allow(clippy::too_many_arguments)]
+pub fn reach_goal(
+    mut commands: Commands,
+    timer: Option<ResMut<SpeedrunTimer>>,
+    mut save: ResMut<SaveData>,
+    player: Option<Single<&Transform, With<Player>>>,
+    goals: Query<(&Goal, &GlobalTransform)>,
+    key_items: Query<(), With<KeyItem>>,
+    map: Option<Res<LoadedMapData>>,
+    map_path: Res<MapPathForHotReload>,
+    asset_server: Res<AssetServer>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut bursts: MessageWriter<ParticleBurst>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (Some(mut timer), Some(player)) = (timer, player) else {
+        return;
+    };
+    if timer.phase != TimerPhase::Running {
+        return;
+    }
+    let player_pos = player.translation;
+    let Some((goal, position)) = goals
+        .iter()
+        .map(|(goal, transform)| (goal, transform.translation()))
+        .find(|(goal, position)| position.distance(player_pos) <= goal.radius)
+    else {
+        return;
+    };
+    let Some(time) = timer.finish() else {
+        return;
+    };
+    fanfare::play_fanfare(&mut commands, &asset_server, goal.fanfare.as_deref());
+    bursts.write(ParticleBurst::item_collected(position, GOAL_COLOR));
+
+    let previous_best = save.best_time(&timer.map);
+    // Replays show the recorded run's time but don't count as runs
+    if playback.is_none() && save.record(&timer.map, time) {
+        write_save_data(&save);
+    }
+    info!(
+        "Reached the goal of '{}' in {} (best before: {:?})",
+        timer.map,
+        format_time(time),
+        previous_best.map(format_time)
+    );
+    // Collectibles still lying around weren't picked up
+    let collectibles = map.as_ref().map_or(0, |map| count_collectibles(&map.map));
+    let collected = collectibles.saturating_sub(key_items.iter().count());
+    commands.insert_resource(RunResult {
+        map: timer.map.clone(),
+        time,
+        previous_best,
+        collected,
+        collectibles,
+        next_map: goal
+            .next_map
+            .as_deref()
+            .map(|next| resolve_next_map(next, map_path.0.as_deref())),
+    });
+    next_state.set(GameState::Results);
+}
+
+/// Plugin for the speedrun timer, goals and best times.
+pub struct SpeedrunPlugin;
+
+impl Plugin for SpeedrunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpeedrunSettings>()
+            .init_resource::<SaveData>()
+            .init_resource::<NextMapRequest>()
+            .add_systems(Startup, load_save_data)
+            .add_systems(
+                Update,
+                fanfare::run_fanfare.run_if(resource_exists::<fanfare::Fanfare>),
+            )
+            .add_systems(
+                FixedUpdate,
+                tick_speedrun_timer.run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (start_speedrun_timer, reach_goal, hud::update_speedrun_hud)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
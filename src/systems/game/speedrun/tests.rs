@@ -0,0 +1,152 @@
+use super::fanfare::{Fanfare, FANFARE_NOTES};
+use super::*;
+use crate::systems::game::map::format::EntityData;
+
+fn props(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn goals_read_their_radius() {
+    assert_eq!(
+        Goal::from_properties(&props(&[])).unwrap().radius,
+        DEFAULT_GOAL_RADIUS
+    );
+    assert_eq!(
+        Goal::from_properties(&props(&[("radius", "2.5")]))
+            .unwrap()
+            .radius,
+        2.5
+    );
+    assert!(Goal::from_properties(&props(&[("radius", "0")])).is_err());
+    assert!(Goal::from_properties(&props(&[("radius", "wide")])).is_err());
+}
+
+#[test]
+fn goals_read_their_next_map_and_fanfare() {
+    let goal = Goal::from_properties(&props(&[
+        ("next_map", "cave_2.ron"),
+        ("fanfare", "sounds/win.ogg"),
+    ]))
+    .unwrap();
+    assert_eq!(goal.next_map.as_deref(), Some("cave_2.ron"));
+    assert_eq!(goal.fanfare.as_deref(), Some("sounds/win.ogg"));
+
+    let goal = Goal::from_properties(&props(&[])).unwrap();
+    assert_eq!(goal.next_map, None);
+    assert_eq!(goal.fanfare, None);
+    assert!(Goal::from_properties(&props(&[("next_map", " ")])).is_err());
+    assert!(Goal::from_properties(&props(&[("fanfare", "")])).is_err());
+}
+
+#[test]
+fn next_maps_resolve_next_to_the_current_map() {
+    assert_eq!(
+        resolve_next_map("cave_2.ron", Some(Path::new("assets/maps/cave_1.ron"))),
+        PathBuf::from("assets/maps/cave_2.ron")
+    );
+    assert_eq!(
+        resolve_next_map("/tmp/other.ron", Some(Path::new("assets/maps/cave_1.ron"))),
+        PathBuf::from("/tmp/other.ron")
+    );
+    assert_eq!(
+        resolve_next_map("assets/maps/cave_2.ron", None),
+        PathBuf::from("assets/maps/cave_2.ron")
+    );
+}
+
+#[test]
+fn collectibles_are_items_with_a_key() {
+    let mut map = MapData::default_map();
+    let baseline = count_collectibles(&map);
+    let item = |properties: &[(&str, &str)]| EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Item,
+        position: (1.0, 0.5, 1.0),
+        properties: props(properties),
+    };
+    map.entities.push(item(&[("key", "gold")]));
+    map.entities.push(item(&[("key", "silver")]));
+    map.entities.push(item(&[]));
+    assert_eq!(count_collectibles(&map), baseline + 2);
+}
+
+#[test]
+fn fanfare_starts_each_note_once_in_order() {
+    let mut fanfare = Fanfare::default();
+    let mut started = fanfare.advance(0.0).to_vec();
+    assert_eq!(started.len(), 1, "the first note starts immediately");
+    while !fanfare.is_done() {
+        started.extend_from_slice(fanfare.advance(0.05));
+    }
+    assert_eq!(started, FANFARE_NOTES);
+    assert!(fanfare.advance(1.0).is_empty());
+}
+
+#[test]
+fn timer_runs_from_first_move_to_finish() {
+    let mut timer = SpeedrunTimer::new("Caves");
+    timer.tick(1.0);
+    assert_eq!(timer.elapsed, 0.0, "waiting timers don't count");
+    assert_eq!(timer.finish(), None);
+
+    timer.start();
+    timer.tick(1.5);
+    timer.tick(0.25);
+    assert_eq!(timer.finish(), Some(1.75));
+    assert_eq!(timer.phase, TimerPhase::Finished);
+
+    // A finished run stays finished
+    timer.start();
+    timer.tick(1.0);
+    assert_eq!(timer.elapsed, 1.75);
+    assert_eq!(timer.finish(), None);
+}
+
+#[test]
+fn save_keeps_the_fastest_time_per_map() {
+    let mut save = SaveData::default();
+    assert!(save.record("Caves", 30.0));
+    assert!(!save.record("Caves", 31.0));
+    assert!(!save.record("Caves", 30.0));
+    assert!(save.record("Caves", 29.5));
+    assert!(save.record("Summit", 90.0));
+    assert_eq!(save.best_time("Caves"), Some(29.5));
+    assert_eq!(save.best_time("Summit"), Some(90.0));
+    assert_eq!(save.best_time("Lake"), None);
+
+    let round_trip: SaveData = ron::from_str(&ron::to_string(&save).unwrap()).unwrap();
+    assert_eq!(round_trip, save);
+    assert_eq!(
+        ron::from_str::<SaveData>("()").unwrap(),
+        SaveData::default()
+    );
+}
+
+#[test]
+fn results_compare_against_the_previous_best() {
+    let result = |time, previous_best| RunResult {
+        map: "Caves".to_string(),
+        time,
+        previous_best,
+        collected: 0,
+        collectibles: 0,
+        next_map: None,
+    };
+    assert!(result(40.0, None).is_new_best());
+    assert!(result(29.0, Some(30.0)).is_new_best());
+    assert!(!result(30.0, Some(30.0)).is_new_best());
+    assert!(!result(31.0, Some(30.0)).is_new_best());
+}
+
+#[test]
+fn times_format_as_minutes_seconds_and_millis() {
+    assert_eq!(format_time(0.0), "0:00.000");
+    assert_eq!(format_time(65.25), "1:05.250");
+    assert_eq!(format_time(600.0), "10:00.000");
+    assert_eq!(format_difference(31.5, 30.0), "+0:01.500");
+    assert_eq!(format_difference(29.75, 30.0), "-0:00.250");
+}
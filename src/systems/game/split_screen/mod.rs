@@ -0,0 +1,444 @@
+//! Split-screen play for a second player on the same machine.
+//!
+//! Pressing Start on a gamepad other than player one's adds player two, who
+//! joins beside player one or at the map's second `PlayerSpawn`. On maps
+//! with a second spawn, player two joins as soon as the map starts if a
+//! second gamepad is connected. Select on player two's gamepad, or
+//! unplugging it, leaves again. Player two stays in from map to map until
+//! the game returns to the title screen.
+//!
+//! While player two is in, the window is split into a left and a right
+//! viewport: the game camera follows player one on the left, and a
+//! [`SplitScreenCamera`] follows player two on the right at the same angle.
+//! Each gamepad fills its own [`PlayerInput`]: player one's the resource,
+//! player two's the component on its [`SplitScreenPlayer`].
+//!
+//! Player two walks, jumps and collides like player one but is not a
+//! [`Player`] entity. Doors, keys, hazards, goals and map scripts react to
+//! player one only, and walls are cut away for player one's camera only.
+
+use bevy::camera::Viewport;
+use bevy::core_pipeline::prepass::DepthPrepass;
+use bevy::input::gamepad::GamepadButton;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use super::actions::{ActionRunner, MapAction};
+use super::camera::rotate_camera;
+use super::character_rotation::ease_character_rotation;
+use super::cinematics::Cinematic;
+use super::components::{GameCamera, GameEntity, Player, SubVoxel};
+use super::fixed_step::{end_physics_step, PhysicsInterpolation};
+use super::gamepad::{
+    gather_keyboard_input, read_gamepad, ActiveGamepad, GamepadSettings, InputSource, PlayerInput,
+};
+use super::input::handle_escape_key;
+use super::map::format::{EntityType, MapData};
+use super::map::loader::LoadedMapData;
+use super::map::spawner::{load_character_scene, player_body, spawn_character_model};
+use super::physics::{apply_character_physics, apply_physics};
+use super::player_movement::move_character;
+use super::resources::{PhysicsConfig, PreFetchedCollisionEntities, SpatialGrid};
+use crate::states::GameState;
+
+/// Where player two joins on maps with a single `PlayerSpawn`, relative to
+/// player one.
+pub const JOIN_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+
+/// Player two's gamepad, while player two is in the game.
+#[derive(Resource, Debug, Default)]
+pub struct SplitScreen {
+    pub gamepad: Option<Entity>,
+}
+
+/// Player two's character.
+#[derive(Component)]
+pub struct SplitScreenPlayer {
+    /// Gamepad steering this character
+    pub gamepad: Entity,
+    /// Movement state, as on player one's [`Player`]
+    pub body: Player,
+}
+
+/// Camera following player two in the right half of the window.
+#[derive(Component, Debug)]
+pub struct SplitScreenCamera;
+
+/// Camera drawing the UI over the whole window while it is split.
+#[derive(Component, Debug)]
+pub struct SplitScreenUiCamera;
+
+/// Player two's character and the cameras added for them.
+pub type SplitScreenEntity = Or<(
+    With<SplitScreenPlayer>,
+    With<SplitScreenCamera>,
+    With<SplitScreenUiCamera>,
+)>;
+
+/// Where player two starts: the map's second `PlayerSpawn`, or beside
+/// player one at `first`.
+pub fn second_player_spawn(map: Option<&MapData>, first: Vec3) -> Vec3 {
+    map.and_then(|map| {
+        map.entities
+            .iter()
+            .filter(|entity| entity.entity_type == EntityType::PlayerSpawn)
+            .nth(1)
+    })
+    .map_or(first + JOIN_OFFSET, |spawn| Vec3::from(spawn.position))
+}
+
+/// The left and right halves of a window `size` physical pixels big, as
+/// `(position, size)` pairs. An odd column goes to the right half.
+pub fn split_viewports(size: UVec2) -> [(UVec2, UVec2); 2] {
+    let left = size.x / 2;
+    [
+        (UVec2::ZERO, UVec2::new(left, size.y)),
+        (UVec2::new(left, 0), UVec2::new(size.x - left, size.y)),
+    ]
+}
+
+/// Let a second gamepad join with Start and leave with Select, and add
+/// player two on maps with a second spawn when a spare gamepad is there.
+pub fn join_split_screen(
+    mut split_screen: ResMut<SplitScreen>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    spawned: Query<(), Added<Player>>,
+    map: Option<Res<LoadedMapData>>,
+    mut runner: ResMut<ActionRunner>,
+) {
+    if let Some(gamepad) = split_screen.gamepad {
+        let left = match gamepads.get(gamepad) {
+            Ok((_, gamepad)) => gamepad.just_pressed(GamepadButton::Select),
+            Err(_) => true,
+        };
+        if left {
+            info!("Player two left split-screen");
+            split_screen.gamepad = None;
+            runner.run(&[MapAction::Say("Player 2 left.".to_string())]);
+        }
+        return;
+    }
+
+    let mut spare = gamepads
+        .iter()
+        .filter(|(entity, _)| active_gamepad.0 != Some(*entity));
+    let second_spawn = map.is_some_and(|map| {
+        map.map
+            .entities
+            .iter()
+            .filter(|entity| entity.entity_type == EntityType::PlayerSpawn)
+            .count()
+            > 1
+    });
+    let joining = if second_spawn && !spawned.is_empty() {
+        spare.next()
+    } else {
+        spare.find(|(_, gamepad)| gamepad.just_pressed(GamepadButton::Start))
+    };
+    if let Some((gamepad, _)) = joining {
+        info!("Player two joined split-screen with gamepad {:?}", gamepad);
+        split_screen.gamepad = Some(gamepad);
+        runner.run(&[MapAction::Say("Player 2 joined.".to_string())]);
+    }
+}
+
+/// Spawn player two and their camera once they join, and remove them when
+/// they leave.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_split_screen_player(
+    mut commands: Commands,
+    split_screen: Res<SplitScreen>,
+    asset_server: Res<AssetServer>,
+    physics: Res<PhysicsConfig>,
+    map: Option<Res<LoadedMapData>>,
+    player: Option<Single<&Transform, With<Player>>>,
+    camera: Option<Single<(&Transform, &Projection, &GameCamera)>>,
+    players: Query<Entity, With<SplitScreenPlayer>>,
+    cameras: Query<Entity, With<SplitScreenCamera>>,
+) {
+    let Some(gamepad) = split_screen.gamepad else {
+        for entity in players.iter().chain(&cameras) {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    if !players.is_empty() {
+        return;
+    }
+    // Wait for player one and the game camera
+    let (Some(player), Some(camera)) = (player, camera) else {
+        return;
+    };
+    let (camera_transform, projection, game_camera) = camera.into_inner();
+
+    let position = second_player_spawn(map.as_deref().map(|map| &map.map), player.translation);
+    let scene = load_character_scene(&asset_server);
+    let entity = commands
+        .spawn((
+            Transform::from_translation(position),
+            Visibility::default(),
+            SplitScreenPlayer {
+                gamepad,
+                body: player_body(&physics),
+            },
+            PlayerInput {
+                input_source: InputSource::Gamepad,
+                ..default()
+            },
+            PhysicsInterpolation::default(),
+            GameEntity,
+        ))
+        .id();
+    spawn_character_model(&mut commands, entity, scene);
+
+    commands.spawn((
+        Camera3d::default(),
+        Camera {
+            order: 1,
+            ..default()
+        },
+        projection.clone(),
+        Transform::from_translation(
+            position + camera_transform.rotation * game_camera.follow_offset,
+        )
+        .with_rotation(camera_transform.rotation),
+        DepthPrepass,
+        SplitScreenCamera,
+        GameEntity,
+    ));
+    info!("Spawned player two at {:?}", position);
+}
+
+/// Fill player two's [`PlayerInput`] from their gamepad. Runs after player
+/// one's input is gathered; Start on either gamepad pauses the game.
+pub fn gather_split_screen_input(
+    settings: Res<GamepadSettings>,
+    gamepads: Query<&Gamepad>,
+    cinematic: Option<Res<Cinematic>>,
+    mut players: Query<(&SplitScreenPlayer, &mut PlayerInput)>,
+    mut player_one_input: ResMut<PlayerInput>,
+) {
+    // Cinematics hold both players still
+    let cinematic_active = cinematic.is_some_and(|cinematic| cinematic.0.is_some());
+    for (player, mut input) in &mut players {
+        let reading = match gamepads.get(player.gamepad) {
+            Ok(gamepad) if !cinematic_active => read_gamepad(gamepad, &settings),
+            _ => PlayerInput {
+                input_source: InputSource::Gamepad,
+                ..default()
+            },
+        };
+        // Hold a jump until a physics step uses it
+        let jump_held = input.jump_just_pressed;
+        *input = reading;
+        input.jump_just_pressed |= jump_held;
+        player_one_input.pause_just_pressed |= input.pause_just_pressed;
+    }
+}
+
+/// Move player two like player one, within the same physics step.
+pub fn move_split_screen_players(
+    time: Res<Time>,
+    physics: Res<PhysicsConfig>,
+    spatial_grid: Option<Res<SpatialGrid>>,
+    sub_voxel_query: Query<&SubVoxel, Without<Player>>,
+    mut players: Query<(&mut SplitScreenPlayer, &mut Transform, &mut PlayerInput)>,
+) {
+    // SpatialGrid is removed during hot reload between despawn and respawn frames.
+    let Some(spatial_grid) = spatial_grid else {
+        return;
+    };
+    // Fixed timestep: the same at every frame rate
+    let delta = time.delta_secs();
+    for (mut player, mut transform, mut input) in &mut players {
+        let body = &mut player.body;
+        let mut pre_fetched = PreFetchedCollisionEntities::default();
+        move_character(
+            body,
+            &mut transform,
+            &input,
+            &physics,
+            &spatial_grid,
+            &sub_voxel_query,
+            &mut pre_fetched,
+            delta,
+        );
+        body.velocity.y += physics.gravity * delta;
+        apply_character_physics(
+            body,
+            &mut transform,
+            &spatial_grid,
+            &pre_fetched,
+            &sub_voxel_query,
+            delta,
+        );
+        // The step used up the held jump
+        input.jump_just_pressed = false;
+    }
+}
+
+/// Turn player two's model towards their facing direction.
+pub fn rotate_split_screen_models(
+    time: Res<Time>,
+    mut players: Query<(&mut SplitScreenPlayer, &Children)>,
+    mut transform_query: Query<&mut Transform, With<SceneRoot>>,
+) {
+    for (mut player, children) in &mut players {
+        ease_character_rotation(&mut player.body, time.delta_secs(), InputSource::Gamepad);
+        for child in children.iter() {
+            if let Ok(mut transform) = transform_query.get_mut(child) {
+                transform.rotation = Quat::from_rotation_y(player.body.current_rotation);
+            }
+        }
+    }
+}
+
+/// Make player two's camera follow them at the game camera's angle.
+#[allow(clippy::type_complexity)]
+pub fn follow_split_screen_cameras(
+    time: Res<Time>,
+    game_camera: Option<Single<(&GameCamera, &Transform)>>,
+    players: Query<&Transform, With<SplitScreenPlayer>>,
+    mut cameras: Query<
+        &mut Transform,
+        (
+            With<SplitScreenCamera>,
+            Without<SplitScreenPlayer>,
+            Without<GameCamera>,
+        ),
+    >,
+) {
+    let Some(game_camera) = game_camera else {
+        return;
+    };
+    let (game_camera, game_camera_transform) = game_camera.into_inner();
+    let (Ok(player), Ok(mut camera)) = (players.single(), cameras.single_mut()) else {
+        return;
+    };
+
+    camera.rotation = game_camera_transform.rotation;
+    let target_position = player.translation + camera.rotation * game_camera.follow_offset;
+    let alpha = 1.0 - (-game_camera.follow_speed * time.delta_secs()).exp();
+    camera.translation = camera.translation.lerp(target_position, alpha);
+}
+
+/// Split the window between the two cameras while player two is in, with
+/// the UI drawn over both halves.
+#[allow(clippy::type_complexity)]
+pub fn update_split_screen_viewports(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    players: Query<(), With<SplitScreenPlayer>>,
+    mut game_cameras: Query<&mut Camera, With<GameCamera>>,
+    mut cameras: Query<&mut Camera, (With<SplitScreenCamera>, Without<GameCamera>)>,
+    ui_cameras: Query<Entity, With<SplitScreenUiCamera>>,
+) {
+    // Photo mode takes the whole window for the game camera
+    let size = window.map_or(UVec2::ZERO, |window| window.physical_size());
+    let split =
+        !players.is_empty() && *state.get() != GameState::PhotoMode && size.x > 1 && size.y > 0;
+    let [left, right] = split_viewports(size);
+
+    for camera in &mut game_cameras {
+        set_viewport(camera, split.then_some(left));
+    }
+    for mut camera in &mut cameras {
+        if camera.is_active != split {
+            camera.is_active = split;
+        }
+        set_viewport(camera, split.then_some(right));
+    }
+
+    if split && ui_cameras.is_empty() {
+        commands.spawn((
+            Camera2d,
+            Camera {
+                order: 2,
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            IsDefaultUiCamera,
+            SplitScreenUiCamera,
+            GameEntity,
+        ));
+    } else if !split {
+        for entity in &ui_cameras {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Show `camera` in `area` (`(position, size)` in physical pixels), or in the
+/// whole window for `None`.
+fn set_viewport(mut camera: Mut<Camera>, area: Option<(UVec2, UVec2)>) {
+    let current = camera
+        .viewport
+        .as_ref()
+        .map(|viewport| (viewport.physical_position, viewport.physical_size));
+    if current != area {
+        camera.viewport = area.map(|(physical_position, physical_size)| Viewport {
+            physical_position,
+            physical_size,
+            ..default()
+        });
+    }
+}
+
+/// Remove player two's character and cameras when the map is left.
+pub fn despawn_split_screen(mut commands: Commands, entities: Query<Entity, SplitScreenEntity>) {
+    for entity in &entities {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Send player two out of the game on the way back to the title screen.
+pub fn end_split_screen(mut split_screen: ResMut<SplitScreen>) {
+    split_screen.gamepad = None;
+}
+
+/// Plugin for split-screen play.
+pub struct SplitScreenPlugin;
+
+impl Plugin for SplitScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SplitScreen>()
+            .add_systems(
+                Update,
+                (
+                    join_split_screen,
+                    spawn_split_screen_player,
+                    gather_split_screen_input
+                        .after(gather_keyboard_input)
+                        .before(handle_escape_key),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                FixedUpdate,
+                move_split_screen_players
+                    .after(apply_physics)
+                    .before(end_physics_step)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    rotate_split_screen_models,
+                    follow_split_screen_cameras.after(rotate_camera),
+                )
+                    .run_if(in_state(GameState::InGame).or(in_state(GameState::Paused))),
+            )
+            .add_systems(Update, update_split_screen_viewports)
+            .add_systems(OnEnter(GameState::LoadingMap), despawn_split_screen)
+            .add_systems(
+                OnEnter(GameState::TitleScreen),
+                (despawn_split_screen, end_split_screen),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::*;
+use crate::systems::game::map::format::EntityData;
+use std::collections::HashMap;
+
+fn spawn_at(position: (f32, f32, f32)) -> EntityData {
+    EntityData {
+        id: Default::default(),
+        entity_type: EntityType::PlayerSpawn,
+        position,
+        properties: HashMap::new(),
+    }
+}
+
+#[test]
+fn player_two_starts_at_the_second_spawn() {
+    let first = Vec3::new(1.0, 2.0, 3.0);
+    let mut map = MapData::empty_map();
+    map.entities = vec![spawn_at((1.0, 2.0, 3.0))];
+    assert_eq!(second_player_spawn(Some(&map), first), first + JOIN_OFFSET);
+    assert_eq!(second_player_spawn(None, first), first + JOIN_OFFSET);
+
+    map.entities.push(EntityData {
+        id: Default::default(),
+        entity_type: EntityType::Npc,
+        position: (9.0, 9.0, 9.0),
+        properties: HashMap::new(),
+    });
+    map.entities.push(spawn_at((5.0, 1.0, 6.0)));
+    map.entities.push(spawn_at((7.0, 1.0, 8.0)));
+    assert_eq!(
+        second_player_spawn(Some(&map), first),
+        Vec3::new(5.0, 1.0, 6.0)
+    );
+}
+
+#[test]
+fn viewports_cover_the_window_side_by_side() {
+    assert_eq!(
+        split_viewports(UVec2::new(1280, 720)),
+        [
+            (UVec2::ZERO, UVec2::new(640, 720)),
+            (UVec2::new(640, 0), UVec2::new(640, 720)),
+        ]
+    );
+    // The odd column goes right
+    let [(_, left), (right_position, right)] = split_viewports(UVec2::new(1281, 720));
+    assert_eq!(left.x + right.x, 1281);
+    assert_eq!(right_position.x, left.x);
+    assert_eq!(right.x, 641);
+}
@@ -0,0 +1,209 @@
+//! Surface-aware footstep effects.
+//!
+//! While the player walks on the ground, a footstep is emitted every
+//! [`STRIDE_LENGTH`] units travelled. Each footstep looks up the voxel type
+//! under the player's feet with [`surface_type_at`], writes a [`FootstepEvent`],
+//! and puffs dust colored for that surface. [`play_footsteps`] answers each
+//! event with a short tone pitched for the surface.
+
+use bevy::audio::{Pitch, Volume};
+use bevy::prelude::*;
+use std::time::Duration;
+
+use super::collision::surface_type_at;
+use super::components::{Player, SubVoxel, VoxelType};
+use super::particles::{emit_particle_bursts, ParticleBurst};
+use super::resources::SpatialGrid;
+use crate::states::GameState;
+
+/// Horizontal distance between footsteps, in world units.
+pub const STRIDE_LENGTH: f32 = 0.6;
+
+/// How far below the feet the ground may be and still count as a footstep.
+const FOOTSTEP_MAX_DEPTH: f32 = 0.15;
+
+/// Length of a footstep tone, in seconds.
+const FOOTSTEP_LENGTH: f32 = 0.05;
+
+const FOOTSTEP_VOLUME: f32 = 0.08;
+
+/// A footstep landed on `surface` under the player's feet.
+///
+/// Audio and other feedback systems read this to pick a per-surface sound.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct FootstepEvent {
+    pub surface: VoxelType,
+}
+
+/// How a surface responds to being walked on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceResponse {
+    /// Color of the dust puffed up by a footstep
+    pub dust_color: Color,
+    /// Number of dust particles per footstep (0 = none)
+    pub dust_count: u32,
+    /// Frequency in Hz of the footstep tone (0 = silent)
+    pub step_pitch: f32,
+}
+
+impl SurfaceResponse {
+    /// Response for walking on `voxel_type`.
+    pub fn for_voxel(voxel_type: VoxelType) -> Self {
+        match voxel_type {
+            VoxelType::Grass => Self {
+                dust_color: Color::srgb(0.35, 0.55, 0.25),
+                dust_count: 3,
+                step_pitch: 180.0,
+            },
+            VoxelType::Dirt => Self {
+                dust_color: Color::srgb(0.55, 0.42, 0.28),
+                dust_count: 5,
+                step_pitch: 140.0,
+            },
+            VoxelType::Stone => Self {
+                dust_color: Color::srgb(0.6, 0.6, 0.6),
+                dust_count: 2,
+                step_pitch: 320.0,
+            },
+            VoxelType::Water => Self {
+                dust_color: Color::srgb(0.6, 0.75, 0.95),
+                dust_count: 4,
+                step_pitch: 240.0,
+            },
+            VoxelType::Lava => Self {
+                dust_color: Color::srgb(1.0, 0.55, 0.1),
+                dust_count: 4,
+                step_pitch: 110.0,
+            },
+            VoxelType::Glowstone => Self {
+                dust_color: Color::srgb(1.0, 0.9, 0.55),
+                dust_count: 2,
+                step_pitch: 420.0,
+            },
+            VoxelType::Glass => Self {
+                dust_color: Color::NONE,
+                dust_count: 0,
+                step_pitch: 880.0,
+            },
+            VoxelType::Air => Self {
+                dust_color: Color::NONE,
+                dust_count: 0,
+                step_pitch: 0.0,
+            },
+        }
+    }
+
+    /// Dust burst for a footstep at `position`, if this surface makes any.
+    pub fn dust_burst(&self, position: Vec3) -> Option<ParticleBurst> {
+        (self.dust_count > 0).then_some(ParticleBurst {
+            position,
+            color: self.dust_color,
+            count: self.dust_count,
+            speed: 0.8,
+            lifetime: 0.4,
+        })
+    }
+
+    /// Tone for a footstep on this surface, if it makes any sound.
+    pub fn step_tone(&self) -> Option<Pitch> {
+        (self.step_pitch > 0.0)
+            .then(|| Pitch::new(self.step_pitch, Duration::from_secs_f32(FOOTSTEP_LENGTH)))
+    }
+}
+
+/// Distance walked since the last footstep.
+#[derive(Resource, Default)]
+pub struct FootstepTracker {
+    last_position: Option<Vec3>,
+    distance: f32,
+}
+
+impl FootstepTracker {
+    /// Record the player at `position`; returns true when a stride completes.
+    ///
+    /// Only horizontal movement counts, and nothing accumulates while airborne.
+    pub fn advance(&mut self, position: Vec3, grounded: bool) -> bool {
+        let moved = self
+            .last_position
+            .map_or(0.0, |last| last.xz().distance(position.xz()));
+        self.last_position = Some(position);
+
+        if !grounded {
+            return false;
+        }
+        self.distance += moved;
+        if self.distance >= STRIDE_LENGTH {
+            self.distance -= STRIDE_LENGTH * (self.distance / STRIDE_LENGTH).floor();
+            return true;
+        }
+        false
+    }
+}
+
+/// Emit footsteps and surface dust while the player walks.
+pub fn emit_footsteps(
+    player: Option<Single<(&Transform, &Player)>>,
+    spatial_grid: Option<Res<SpatialGrid>>,
+    sub_voxels: Query<&SubVoxel, Without<Player>>,
+    mut tracker: ResMut<FootstepTracker>,
+    mut footsteps: MessageWriter<FootstepEvent>,
+    mut bursts: MessageWriter<ParticleBurst>,
+) {
+    let (Some(player), Some(spatial_grid)) = (player, spatial_grid) else {
+        return;
+    };
+    let (transform, player) = *player;
+
+    if !tracker.advance(transform.translation, player.is_grounded) {
+        return;
+    }
+
+    let feet = transform.translation - Vec3::Y * player.half_height;
+    let Some(surface) = surface_type_at(&spatial_grid, &sub_voxels, feet, FOOTSTEP_MAX_DEPTH)
+    else {
+        return;
+    };
+
+    footsteps.write(FootstepEvent { surface });
+    if let Some(burst) = SurfaceResponse::for_voxel(surface).dust_burst(feet) {
+        bursts.write(burst);
+    }
+}
+
+/// Play a short tone for each footstep, pitched for the surface.
+pub fn play_footsteps(
+    mut commands: Commands,
+    mut footsteps: MessageReader<FootstepEvent>,
+    mut pitches: ResMut<Assets<Pitch>>,
+) {
+    for footstep in footsteps.read() {
+        let Some(tone) = SurfaceResponse::for_voxel(footstep.surface).step_tone() else {
+            continue;
+        };
+        commands.spawn((
+            AudioPlayer(pitches.add(tone)),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(FOOTSTEP_VOLUME)),
+        ));
+    }
+}
+
+/// Footsteps with per-surface sounds and dust while walking.
+pub struct SurfacePlugin;
+
+impl Plugin for SurfacePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<FootstepEvent>()
+            .init_resource::<FootstepTracker>()
+            .add_systems(
+                Update,
+                (
+                    emit_footsteps.before(emit_particle_bursts),
+                    play_footsteps.after(emit_footsteps),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn stride_completes_after_walking_stride_length() {
+    let mut tracker = FootstepTracker::default();
+    assert!(!tracker.advance(Vec3::ZERO, true));
+    assert!(!tracker.advance(Vec3::new(0.4, 0.0, 0.0), true));
+    assert!(tracker.advance(Vec3::new(0.7, 0.0, 0.0), true));
+    // Remainder carries into the next stride
+    assert!(!tracker.advance(Vec3::new(1.0, 0.0, 0.0), true));
+    assert!(tracker.advance(Vec3::new(1.3, 0.0, 0.0), true));
+}
+
+#[test]
+fn no_strides_while_airborne_or_moving_vertically() {
+    let mut tracker = FootstepTracker::default();
+    tracker.advance(Vec3::ZERO, true);
+    assert!(!tracker.advance(Vec3::new(2.0, 0.0, 0.0), false));
+    assert!(!tracker.advance(Vec3::new(2.0, 3.0, 0.0), true));
+}
+
+#[test]
+fn surfaces_have_distinct_dust() {
+    let grass = SurfaceResponse::for_voxel(VoxelType::Grass);
+    let dirt = SurfaceResponse::for_voxel(VoxelType::Dirt);
+    let stone = SurfaceResponse::for_voxel(VoxelType::Stone);
+    assert_ne!(grass.dust_color, dirt.dust_color);
+    assert_ne!(dirt.dust_color, stone.dust_color);
+
+    let burst = dirt.dust_burst(Vec3::ONE).unwrap();
+    assert_eq!(burst.count, dirt.dust_count);
+    assert_eq!(burst.position, Vec3::ONE);
+    assert!(SurfaceResponse::for_voxel(VoxelType::Air)
+        .dust_burst(Vec3::ZERO)
+        .is_none());
+}
+
+#[test]
+fn surfaces_have_distinct_footstep_tones() {
+    let grass = SurfaceResponse::for_voxel(VoxelType::Grass);
+    let stone = SurfaceResponse::for_voxel(VoxelType::Stone);
+    assert_ne!(grass.step_pitch, stone.step_pitch);
+    assert!(grass.step_tone().is_some());
+    assert!(SurfaceResponse::for_voxel(VoxelType::Air)
+        .step_tone()
+        .is_none());
+}
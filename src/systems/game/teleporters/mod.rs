@@ -0,0 +1,351 @@
+//! Teleporters that move the player to another teleporter.
+//!
+//! A `Teleporter` entity names its destination in `target` — the `name` of
+//! another teleporter. Standing inside its `radius` for [`HOLD_SECONDS`]
+//! (or pressing interact there, with `activation` set to `interact`) fades
+//! the screen out, moves the player to the destination and fades back in.
+//! The player keeps their velocity when `keep_velocity` is `true`.
+//!
+//! A player who arrives on a teleporter has to step off it before it can
+//! send them anywhere, so paired teleporters don't bounce them back.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::components::{GameCamera, GameEntity, Player};
+use super::interaction::InteractionTarget;
+use crate::states::GameState;
+
+/// Radius used when a teleporter doesn't set `radius`.
+pub const DEFAULT_TELEPORTER_RADIUS: f32 = 0.75;
+
+/// How long the player stands on a `hold` teleporter before it fires.
+pub const HOLD_SECONDS: f32 = 0.6;
+
+/// Duration of the fade out, and of the fade back in.
+pub const FADE_SECONDS: f32 = 0.25;
+
+/// Color of teleporter pads and their links in the editor.
+pub const TELEPORTER_COLOR: Color = Color::srgb(0.3, 0.8, 1.0);
+
+/// What sets a teleporter off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeleportActivation {
+    /// Standing inside the radius for [`HOLD_SECONDS`]
+    Hold,
+    /// Pressing interact inside the radius
+    Interact,
+}
+
+/// A teleporter entity.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Teleporter {
+    /// Name of this teleporter, for other teleporters to target
+    pub name: Option<String>,
+    /// Name of the teleporter the player is sent to
+    pub target: String,
+    pub radius: f32,
+    pub activation: TeleportActivation,
+    /// Whether the player keeps their velocity on arrival
+    pub keep_velocity: bool,
+}
+
+impl Teleporter {
+    /// Read a teleporter from entity properties: `target` (required), `name`,
+    /// `radius`, `activation` (`hold`/`interact`) and `keep_velocity`.
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Self, String> {
+        let non_empty = |key: &str| {
+            properties
+                .get(key)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let target = non_empty("target").ok_or("a teleporter needs a 'target' teleporter")?;
+        let radius = match properties.get("radius") {
+            None => DEFAULT_TELEPORTER_RADIUS,
+            Some(value) => match value.parse::<f32>() {
+                Ok(radius) if radius > 0.0 => radius,
+                _ => return Err(format!("expected a positive 'radius', got {:?}", value)),
+            },
+        };
+        let activation = match properties.get("activation").map(String::as_str) {
+            None | Some("hold") => TeleportActivation::Hold,
+            Some("interact") => TeleportActivation::Interact,
+            Some(other) => {
+                return Err(format!(
+                    "expected hold/interact for 'activation', got {:?}",
+                    other
+                ))
+            }
+        };
+        let keep_velocity = match properties.get("keep_velocity").map(String::as_str) {
+            None | Some("false") | Some("0") => false,
+            Some("true") | Some("1") => true,
+            Some(other) => {
+                return Err(format!(
+                    "expected true/false/1/0 for 'keep_velocity', got {:?}",
+                    other
+                ))
+            }
+        };
+        Ok(Self {
+            name: non_empty("name"),
+            target,
+            radius,
+            activation,
+            keep_velocity,
+        })
+    }
+}
+
+/// Progress of a teleport.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TeleportPhase {
+    Idle,
+    /// Fading to black before moving the player to `destination`
+    FadingOut {
+        destination: Vec3,
+        keep_velocity: bool,
+    },
+    FadingIn,
+}
+
+/// Teleport in progress and the hold timer; reset whenever a map spawns.
+#[derive(Resource, Debug)]
+pub struct TeleportState {
+    pub phase: TeleportPhase,
+    /// Seconds spent in the current fade
+    elapsed: f32,
+    /// The `hold` teleporter the player stands on, and for how long
+    holding: Option<(Entity, f32)>,
+    /// Teleporter the player arrived on and hasn't stepped off yet
+    pub arrived_at: Option<Entity>,
+}
+
+impl Default for TeleportState {
+    fn default() -> Self {
+        Self {
+            phase: TeleportPhase::Idle,
+            elapsed: 0.0,
+            holding: None,
+            arrived_at: None,
+        }
+    }
+}
+
+impl TeleportState {
+    /// Whether a teleport is fading out or back in.
+    pub fn is_busy(&self) -> bool {
+        self.phase != TeleportPhase::Idle
+    }
+
+    /// Count `delta` seconds standing on `teleporter`; returns true once the
+    /// player has held still on it for [`HOLD_SECONDS`].
+    pub fn hold(&mut self, teleporter: Entity, delta: f32) -> bool {
+        let held = match self.holding {
+            Some((entity, held)) if entity == teleporter => held + delta,
+            _ => delta,
+        };
+        self.holding = Some((teleporter, held));
+        held >= HOLD_SECONDS
+    }
+
+    /// Forget the hold timer when the player isn't on a `hold` teleporter.
+    pub fn release(&mut self) {
+        self.holding = None;
+    }
+
+    /// Start fading out towards `destination`, arriving on `arrival`.
+    pub fn start(&mut self, destination: Vec3, keep_velocity: bool, arrival: Entity) {
+        self.phase = TeleportPhase::FadingOut {
+            destination,
+            keep_velocity,
+        };
+        self.elapsed = 0.0;
+        self.holding = None;
+        self.arrived_at = Some(arrival);
+    }
+
+    /// Advance the fade by `delta` seconds. Returns the destination, and
+    /// whether to keep velocity, on the frame the screen is fully black.
+    pub fn advance(&mut self, delta: f32) -> Option<(Vec3, bool)> {
+        self.elapsed += delta;
+        if self.elapsed < FADE_SECONDS {
+            return None;
+        }
+        match self.phase {
+            TeleportPhase::FadingOut {
+                destination,
+                keep_velocity,
+            } => {
+                self.phase = TeleportPhase::FadingIn;
+                self.elapsed = 0.0;
+                Some((destination, keep_velocity))
+            }
+            TeleportPhase::FadingIn => {
+                self.phase = TeleportPhase::Idle;
+                None
+            }
+            TeleportPhase::Idle => None,
+        }
+    }
+
+    /// Opacity of the black fade overlay.
+    pub fn fade_alpha(&self) -> f32 {
+        let progress = (self.elapsed / FADE_SECONDS).clamp(0.0, 1.0);
+        match self.phase {
+            TeleportPhase::Idle => 0.0,
+            TeleportPhase::FadingOut { .. } => progress,
+            TeleportPhase::FadingIn => 1.0 - progress,
+        }
+    }
+}
+
+/// Full-screen overlay the screen fades to while teleporting.
+#[derive(Component)]
+pub struct TeleportFadeOverlay;
+
+fn spawn_teleport_fade(commands: &mut Commands, alpha: f32) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(alpha)),
+        GlobalZIndex(60),
+        TeleportFadeOverlay,
+        GameEntity,
+    ));
+}
+
+/// Start a teleport when the player holds still on, or interacts with, a
+/// teleporter.
+pub fn activate_teleporters(
+    time: Res<Time>,
+    target: Res<InteractionTarget>,
+    mut state: ResMut<TeleportState>,
+    player: Option<Single<(&Transform, &Player)>>,
+    teleporters: Query<(Entity, &Teleporter, &GlobalTransform)>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    if state.is_busy() {
+        return;
+    }
+    let (player_transform, player) = *player;
+    let player_pos = player_transform.translation;
+    let inside = |transform: &GlobalTransform, radius: f32| {
+        transform.translation().distance(player_pos) <= radius
+    };
+
+    // Arriving players step off before the teleporter works again
+    if let Some(arrived) = state.arrived_at {
+        let still_on = teleporters
+            .get(arrived)
+            .is_ok_and(|(_, teleporter, transform)| inside(transform, teleporter.radius));
+        if !still_on {
+            state.arrived_at = None;
+        }
+    }
+
+    let mut fired = None;
+    let mut holding = false;
+    for (entity, teleporter, transform) in &teleporters {
+        if state.arrived_at == Some(entity) || !inside(transform, teleporter.radius) {
+            continue;
+        }
+        let fires = match teleporter.activation {
+            TeleportActivation::Hold => {
+                holding = true;
+                state.hold(entity, time.delta_secs())
+            }
+            TeleportActivation::Interact => target.0 == Some(entity),
+        };
+        if fires {
+            fired = Some((entity, teleporter));
+            break;
+        }
+    }
+    if !holding {
+        state.release();
+    }
+
+    let Some((source, teleporter)) = fired else {
+        return;
+    };
+    let destination = teleporters
+        .iter()
+        .find(|(entity, other, _)| {
+            *entity != source && other.name.as_deref() == Some(teleporter.target.as_str())
+        })
+        .map(|(entity, _, transform)| (entity, transform.translation()));
+    match destination {
+        Some((arrival, position)) => {
+            info!("Teleporting to '{}'", teleporter.target);
+            state.start(
+                position + Vec3::Y * player.half_height,
+                teleporter.keep_velocity,
+                arrival,
+            );
+        }
+        None => {
+            warn!("Teleporter target '{}' not found", teleporter.target);
+            // Don't retry until the player steps off
+            state.arrived_at = Some(source);
+        }
+    }
+}
+
+/// Fade out, move the player (and the camera with them), and fade back in.
+/// The overlay is spawned with the game world the first time it's needed.
+#[allow(clippy::type_complexity)]
+pub fn run_teleport_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut state: ResMut<TeleportState>,
+    player: Option<Single<(&mut Transform, &mut Player)>>,
+    camera: Option<Single<&mut Transform, (With<GameCamera>, Without<Player>)>>,
+    overlay: Option<Single<&mut BackgroundColor, With<TeleportFadeOverlay>>>,
+) {
+    if state.is_busy() {
+        if let Some((destination, keep_velocity)) = state.advance(time.delta_secs()) {
+            if let Some(player) = player {
+                let (mut transform, mut player) = player.into_inner();
+                let offset = destination - transform.translation;
+                transform.translation = destination;
+                if !keep_velocity {
+                    player.velocity = Vec3::ZERO;
+                }
+                player.is_grounded = false;
+                if let Some(mut camera) = camera {
+                    camera.translation += offset;
+                }
+            }
+        }
+    }
+    match overlay {
+        Some(mut overlay) => overlay.0 = Color::BLACK.with_alpha(state.fade_alpha()),
+        None => spawn_teleport_fade(&mut commands, state.fade_alpha()),
+    }
+}
+
+/// Plugin that runs teleporters.
+pub struct TeleportersPlugin;
+
+impl Plugin for TeleportersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TeleportState>().add_systems(
+            Update,
+            (activate_teleporters, run_teleport_fade)
+                .chain()
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
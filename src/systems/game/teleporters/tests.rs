@@ -0,0 +1,86 @@
+use super::*;
+
+fn props(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn teleporters_read_their_properties() {
+    let teleporter = Teleporter::from_properties(&props(&[
+        ("name", "cave"),
+        ("target", "summit"),
+        ("radius", "1.5"),
+        ("activation", "interact"),
+        ("keep_velocity", "true"),
+    ]))
+    .unwrap();
+    assert_eq!(
+        teleporter,
+        Teleporter {
+            name: Some("cave".to_string()),
+            target: "summit".to_string(),
+            radius: 1.5,
+            activation: TeleportActivation::Interact,
+            keep_velocity: true,
+        }
+    );
+
+    let defaults = Teleporter::from_properties(&props(&[("target", "summit")])).unwrap();
+    assert_eq!(defaults.radius, DEFAULT_TELEPORTER_RADIUS);
+    assert_eq!(defaults.activation, TeleportActivation::Hold);
+    assert!(!defaults.keep_velocity);
+
+    for bad in [
+        props(&[]),
+        props(&[("target", "summit"), ("radius", "0")]),
+        props(&[("target", "summit"), ("activation", "touch")]),
+        props(&[("target", "summit"), ("keep_velocity", "yes")]),
+    ] {
+        assert!(Teleporter::from_properties(&bad).is_err(), "{:?}", bad);
+    }
+}
+
+#[test]
+fn holding_fires_after_hold_seconds_on_the_same_teleporter() {
+    let pad = Entity::from_raw_u32(1).unwrap();
+    let other = Entity::from_raw_u32(2).unwrap();
+    let mut state = TeleportState::default();
+
+    assert!(!state.hold(pad, HOLD_SECONDS * 0.6));
+    assert!(
+        !state.hold(other, HOLD_SECONDS * 0.6),
+        "restarts on another pad"
+    );
+    assert!(state.hold(other, HOLD_SECONDS * 0.6));
+
+    state.release();
+    assert!(
+        !state.hold(other, HOLD_SECONDS * 0.6),
+        "restarts after stepping off"
+    );
+}
+
+#[test]
+fn fade_moves_the_player_once_the_screen_is_black() {
+    let arrival = Entity::from_raw_u32(3).unwrap();
+    let destination = Vec3::new(4.0, 1.0, 2.0);
+    let mut state = TeleportState::default();
+    assert_eq!(state.fade_alpha(), 0.0);
+
+    state.start(destination, true, arrival);
+    assert!(state.is_busy());
+    assert_eq!(state.arrived_at, Some(arrival));
+    assert_eq!(state.advance(FADE_SECONDS * 0.5), None);
+    assert!((state.fade_alpha() - 0.5).abs() < 1e-5);
+
+    assert_eq!(state.advance(FADE_SECONDS * 0.5), Some((destination, true)));
+    assert_eq!(state.phase, TeleportPhase::FadingIn);
+    assert_eq!(state.fade_alpha(), 1.0);
+
+    assert_eq!(state.advance(FADE_SECONDS), None);
+    assert!(!state.is_busy());
+    assert_eq!(state.fade_alpha(), 0.0);
+}
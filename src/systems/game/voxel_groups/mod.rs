@@ -0,0 +1,246 @@
+//! Named voxel groups switched at runtime.
+//!
+//! A voxel joins a group through `VoxelData::group`. Each group is either
+//! visible (meshed into its chunks) or hidden, and either solid (in the
+//! collision grid) or passable. Scripted actions change groups by writing
+//! [`VoxelGroupRequest`]s:
+//!
+//! - `show` / `hide` — make the group appear (visible and solid) or vanish
+//! - `toggle` — show a hidden group, hide a shown one
+//! - `solidify` / `dissolve` — make it block the player or let them through
+//!
+//! Showing or hiding a group re-meshes only the chunks its voxels touch;
+//! solidifying or dissolving it only updates the spatial grid.
+
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use super::components::SubVoxel;
+use super::map::format::MapData;
+use super::map::spawner::{
+    rebuild_chunks, voxel_chunks, ChunkMaterials, ChunkSpawnContext, VoxelChunk,
+};
+use super::map::LoadedMapData;
+use super::occlusion::OcclusionConfig;
+use super::resources::SpatialGrid;
+use crate::states::GameState;
+
+/// How an action changes a voxel group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupChange {
+    Show,
+    Hide,
+    Toggle,
+    Solidify,
+    Dissolve,
+}
+
+/// Whether a group is drawn and whether it blocks movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupState {
+    pub visible: bool,
+    pub solid: bool,
+}
+
+impl Default for GroupState {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            solid: true,
+        }
+    }
+}
+
+impl GroupState {
+    /// The state after `change`.
+    pub fn apply(self, change: GroupChange) -> Self {
+        let shown = Self::default();
+        let hidden = Self {
+            visible: false,
+            solid: false,
+        };
+        match change {
+            GroupChange::Show => shown,
+            GroupChange::Hide => hidden,
+            GroupChange::Toggle if self.visible => hidden,
+            GroupChange::Toggle => shown,
+            GroupChange::Solidify => Self {
+                solid: true,
+                ..self
+            },
+            GroupChange::Dissolve => Self {
+                solid: false,
+                ..self
+            },
+        }
+    }
+}
+
+/// Current state of every voxel group in the loaded map.
+#[derive(Resource, Default, Debug)]
+pub struct VoxelGroups {
+    states: HashMap<String, GroupState>,
+}
+
+impl VoxelGroups {
+    /// Groups used by the map's voxels, in their starting state.
+    pub fn from_map(map: &MapData) -> Self {
+        let mut states: HashMap<String, GroupState> = map
+            .world
+            .voxels
+            .iter()
+            .filter_map(|voxel| voxel.group.clone())
+            .map(|name| (name, GroupState::default()))
+            .collect();
+        for group in &map.voxel_groups {
+            states.insert(
+                group.name.clone(),
+                GroupState {
+                    visible: group.visible,
+                    solid: group.solid,
+                },
+            );
+        }
+        Self { states }
+    }
+
+    /// State of `name`; unknown groups are visible and solid.
+    pub fn state(&self, name: &str) -> GroupState {
+        self.states.get(name).copied().unwrap_or_default()
+    }
+
+    /// Whether voxels in `group` (or in none) are drawn.
+    pub fn is_visible(&self, group: Option<&str>) -> bool {
+        group.is_none_or(|name| self.state(name).visible)
+    }
+
+    /// Whether voxels in `group` (or in none) block movement.
+    pub fn is_solid(&self, group: Option<&str>) -> bool {
+        group.is_none_or(|name| self.state(name).solid)
+    }
+
+    /// Apply `change` to `name`, returning its state before and after.
+    ///
+    /// Returns `None` for groups no voxel belongs to.
+    pub fn apply(&mut self, name: &str, change: GroupChange) -> Option<(GroupState, GroupState)> {
+        let state = self.states.get_mut(name)?;
+        let old = *state;
+        *state = old.apply(change);
+        Some((old, *state))
+    }
+}
+
+/// Marks a collision sub-voxel as part of a voxel group.
+#[derive(Component, Debug, Clone)]
+pub struct VoxelGroupMember(pub String);
+
+/// Request to change a voxel group, written by scripted actions.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct VoxelGroupRequest {
+    pub group: String,
+    pub change: GroupChange,
+}
+
+/// Chunks the voxels of `group` are meshed into.
+pub fn group_chunks(map: &MapData, group: &str) -> HashSet<IVec3> {
+    map.world
+        .voxels
+        .iter()
+        .filter(|voxel| voxel.group.as_deref() == Some(group))
+        .flat_map(|voxel| voxel_chunks(voxel.pos, 0.5))
+        .collect()
+}
+
+/// Add or remove a group's collision sub-voxels from the spatial grid.
+fn set_group_solid<'a>(
+    spatial_grid: &mut SpatialGrid,
+    members: impl Iterator<Item = (Entity, &'a SubVoxel)>,
+    solid: bool,
+) {
+    for (entity, sub_voxel) in members {
+        let (min, max) = sub_voxel.bounds;
+        if solid {
+            spatial_grid.insert(entity, min, max);
+        } else {
+            spatial_grid.remove(entity);
+        }
+    }
+}
+
+/// Apply voxel group requests, re-meshing chunks of groups shown or hidden.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_voxel_group_requests(
+    mut commands: Commands,
+    mut requests: MessageReader<VoxelGroupRequest>,
+    mut groups: ResMut<VoxelGroups>,
+    map: Res<LoadedMapData>,
+    mut spatial_grid: ResMut<SpatialGrid>,
+    materials: Res<ChunkMaterials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    occlusion_config: Res<OcclusionConfig>,
+    members: Query<(Entity, &SubVoxel, &VoxelGroupMember)>,
+    chunks: Query<(Entity, &VoxelChunk)>,
+) {
+    let mut dirty_chunks = HashSet::new();
+    for request in requests.read() {
+        let Some((old, new)) = groups.apply(&request.group, request.change) else {
+            warn!("Voxel group '{}' has no voxels", request.group);
+            continue;
+        };
+        info!(
+            "Voxel group '{}' is now {} and {}",
+            request.group,
+            if new.visible { "visible" } else { "hidden" },
+            if new.solid { "solid" } else { "passable" }
+        );
+        if old.solid != new.solid {
+            let group_members = members
+                .iter()
+                .filter(|(_, _, member)| member.0 == request.group)
+                .map(|(entity, sub_voxel, _)| (entity, sub_voxel));
+            set_group_solid(&mut spatial_grid, group_members, new.solid);
+        }
+        if old.visible != new.visible {
+            dirty_chunks.extend(group_chunks(&map.map, &request.group));
+        }
+    }
+    if dirty_chunks.is_empty() {
+        return;
+    }
+
+    for (entity, chunk) in &chunks {
+        if dirty_chunks.contains(&chunk.chunk_pos) {
+            commands.entity(entity).despawn();
+        }
+    }
+    let mut ctx = ChunkSpawnContext {
+        commands,
+        spatial_grid: &mut spatial_grid,
+        meshes: &mut meshes,
+        chunk_material: materials.chunk_material.clone(),
+        type_materials: materials.type_materials.clone(),
+        shadow_quality: occlusion_config.shadow_quality,
+        palette: materials.palette,
+    };
+    rebuild_chunks(&mut ctx, &map.map, &groups, &dirty_chunks);
+}
+
+/// Plugin that applies voxel group changes during gameplay.
+pub struct VoxelGroupsPlugin;
+
+impl Plugin for VoxelGroupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VoxelGroups>()
+            .add_message::<VoxelGroupRequest>()
+            .add_systems(
+                Update,
+                apply_voxel_group_requests
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(resource_exists::<SpatialGrid>)
+                    .run_if(resource_exists::<ChunkMaterials>),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,106 @@
+use super::*;
+use crate::systems::game::map::format::{VoxelData, VoxelGroupData, VoxelType};
+
+fn map_with_groups() -> MapData {
+    let mut map = MapData::default_map();
+    map.world.voxels = vec![
+        VoxelData::new((0, 0, 0), VoxelType::Stone, None),
+        VoxelData {
+            group: Some("bridge".to_string()),
+            ..VoxelData::new((1, 0, 0), VoxelType::Stone, None)
+        },
+        VoxelData {
+            group: Some("bridge".to_string()),
+            ..VoxelData::new((20, 0, 0), VoxelType::Stone, None)
+        },
+        VoxelData {
+            group: Some("platform".to_string()),
+            ..VoxelData::new((5, 0, 5), VoxelType::Stone, None)
+        },
+    ]
+    .into();
+    map.voxel_groups = vec![VoxelGroupData {
+        name: "platform".to_string(),
+        visible: false,
+        solid: false,
+    }];
+    map
+}
+
+#[test]
+fn groups_start_as_the_map_says() {
+    let groups = VoxelGroups::from_map(&map_with_groups());
+    assert_eq!(groups.state("bridge"), GroupState::default());
+    assert!(!groups.is_visible(Some("platform")));
+    assert!(!groups.is_solid(Some("platform")));
+    assert!(groups.is_visible(None) && groups.is_solid(None));
+}
+
+#[test]
+fn hiding_also_lets_the_player_through() {
+    let shown = GroupState::default();
+    let hidden = shown.apply(GroupChange::Hide);
+    assert_eq!(
+        hidden,
+        GroupState {
+            visible: false,
+            solid: false
+        }
+    );
+    assert_eq!(hidden.apply(GroupChange::Show), shown);
+    assert_eq!(shown.apply(GroupChange::Toggle), hidden);
+    assert_eq!(hidden.apply(GroupChange::Toggle), shown);
+}
+
+#[test]
+fn dissolving_keeps_the_group_visible() {
+    let dissolved = GroupState::default().apply(GroupChange::Dissolve);
+    assert!(dissolved.visible && !dissolved.solid);
+    assert!(dissolved.apply(GroupChange::Solidify).solid);
+}
+
+#[test]
+fn unknown_groups_are_not_changed() {
+    let mut groups = VoxelGroups::from_map(&map_with_groups());
+    assert_eq!(groups.apply("drawbridge", GroupChange::Hide), None);
+    assert_eq!(
+        groups
+            .apply("bridge", GroupChange::Hide)
+            .map(|(_, new)| new.visible),
+        Some(false)
+    );
+    assert!(!groups.is_visible(Some("bridge")));
+}
+
+#[test]
+fn group_chunks_cover_every_chunk_the_group_touches() {
+    let chunks = group_chunks(&map_with_groups(), "bridge");
+    // Voxels span half a unit either side of their position, so ones at
+    // y = 0 and z = 0 reach into the chunks below and behind too.
+    assert_eq!(chunks.len(), 8);
+    assert!(chunks.contains(&IVec3::ZERO));
+    assert!(chunks.contains(&IVec3::new(1, -1, -1)));
+    assert!(!chunks.iter().any(|c| c.x < 0 || c.x > 1));
+}
+
+#[test]
+fn solidity_moves_members_in_and_out_of_the_grid() {
+    let mut grid = SpatialGrid::default();
+    let entity = Entity::from_raw_u32(7).unwrap();
+    let sub_voxel = SubVoxel {
+        bounds: (Vec3::splat(0.25), Vec3::splat(0.375)),
+        voxel_type: VoxelType::Stone,
+    };
+    let (min, max) = sub_voxel.bounds;
+
+    set_group_solid(&mut grid, [(entity, &sub_voxel)].into_iter(), true);
+    set_group_solid(&mut grid, [(entity, &sub_voxel)].into_iter(), true);
+    assert_eq!(
+        grid.get_entities_in_aabb(min, max),
+        vec![entity],
+        "added once"
+    );
+
+    set_group_solid(&mut grid, [(entity, &sub_voxel)].into_iter(), false);
+    assert!(grid.get_entities_in_aabb(min, max).is_empty());
+}
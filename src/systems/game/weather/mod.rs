@@ -0,0 +1,457 @@
+//! Weather: rain and snow around the camera, fog and wet-looking terrain.
+//!
+//! The map's `weather` section sets the weather when the map spawns. Trigger
+//! entities with a `weather` property become [`WeatherZone`]s: while the player
+//! is inside one the weather eases towards the zone's, and back to the map's
+//! once they leave.
+//!
+//! Precipitation is a fixed pool of small unlit cubes that fall through a box
+//! around the camera and wrap back to the top, so nothing is spawned once the
+//! pool is full. Drops are [`GameEntity`]s, despawned with the rest of the
+//! world when the game ends, so they stay put while paused or in photo mode. Fog is a [`DistanceFog`] on the camera, and wetness darkens
+//! the shared chunk material. The editor reuses the precipitation and fog
+//! systems to preview a map's weather.
+
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use super::components::{GameEntity, Player};
+use super::map::format::{Precipitation, WeatherData};
+use super::map::spawner::VoxelChunk;
+use super::occlusion::{OcclusionMaterial, OcclusionMaterialHandle};
+use super::particles::Xorshift;
+use crate::states::GameState;
+
+/// Upper bound on precipitation particles, reached at full intensity.
+pub const MAX_WEATHER_DROPS: usize = 600;
+
+/// Half the width and depth of the box around the camera that drops fall through.
+const AREA_HALF_WIDTH: f32 = 12.0;
+
+/// Half the height of the box around the camera that drops fall through.
+const AREA_HALF_HEIGHT: f32 = 8.0;
+
+/// How fast intensity and fog change towards the target, per second.
+const BLEND_RATE: f32 = 0.25;
+
+/// How fast surfaces get wet or dry, per second.
+const WETNESS_RATE: f32 = 0.1;
+
+/// Exponential fog density at `fog_density` 1.0.
+const MAX_FOG_DENSITY: f32 = 0.15;
+
+/// How much fully wet terrain is darkened.
+const WET_DARKENING: f32 = 0.4;
+
+const RAIN_SPEED: f32 = 14.0;
+const SNOW_SPEED: f32 = 1.5;
+
+/// Current weather and the weather it is changing to.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Weather {
+    /// The map's own weather, restored when the player leaves every zone
+    pub base: WeatherData,
+    /// Weather the current conditions are easing towards
+    pub target: WeatherData,
+    /// Conditions right now
+    pub current: WeatherData,
+}
+
+impl Weather {
+    /// Weather that is already at `weather`, with no transition.
+    pub fn settled(weather: WeatherData) -> Self {
+        Self {
+            base: weather.clone(),
+            target: weather.clone(),
+            current: weather,
+        }
+    }
+
+    /// Ease the current conditions towards the target by `delta` seconds.
+    ///
+    /// A change of precipitation lets the old kind die down before the new
+    /// one starts.
+    pub fn step(&mut self, delta: f32) {
+        let target = &self.target;
+        let current = &mut self.current;
+        let blend = BLEND_RATE * delta;
+
+        if current.precipitation != target.precipitation {
+            if current.precipitation == Precipitation::None {
+                current.intensity = 0.0;
+            }
+            current.intensity = approach(current.intensity, 0.0, blend);
+            if current.intensity <= 0.0 {
+                current.precipitation = target.precipitation;
+            }
+        }
+        if current.precipitation == target.precipitation {
+            current.intensity = approach(current.intensity, target.intensity, blend);
+        }
+
+        current.fog_density = approach(current.fog_density, target.fog_density, blend);
+        current.fog_color = (
+            approach(current.fog_color.0, target.fog_color.0, blend),
+            approach(current.fog_color.1, target.fog_color.1, blend),
+            approach(current.fog_color.2, target.fog_color.2, blend),
+        );
+        current.wetness = approach(current.wetness, target.wetness, WETNESS_RATE * delta);
+    }
+}
+
+/// Move `value` towards `target` by at most `max_delta`.
+fn approach(value: f32, target: f32, max_delta: f32) -> f32 {
+    if (target - value).abs() <= max_delta {
+        target
+    } else {
+        value + max_delta.copysign(target - value)
+    }
+}
+
+/// Number of precipitation particles shown for `weather`.
+pub fn active_drops(weather: &WeatherData) -> usize {
+    if weather.precipitation == Precipitation::None {
+        return 0;
+    }
+    (weather.intensity.clamp(0.0, 1.0) * MAX_WEATHER_DROPS as f32).round() as usize
+}
+
+/// Camera fog for `weather`, or `None` when there is no fog.
+pub fn weather_fog(weather: &WeatherData) -> Option<DistanceFog> {
+    if weather.fog_density <= 0.001 {
+        return None;
+    }
+    let (r, g, b) = weather.fog_color;
+    Some(DistanceFog {
+        color: Color::srgb(r, g, b),
+        falloff: FogFalloff::Exponential {
+            density: weather.fog_density.min(1.0) * MAX_FOG_DENSITY,
+        },
+        ..default()
+    })
+}
+
+/// Base color that darkens terrain for `wetness` (white when dry).
+pub fn wet_tint(wetness: f32) -> Color {
+    let value = 1.0 - WET_DARKENING * wetness.clamp(0.0, 1.0);
+    Color::srgb(value, value, value)
+}
+
+/// Wrap `position` into the precipitation box around `center`.
+pub fn wrap_into_area(position: Vec3, center: Vec3) -> Vec3 {
+    let wrap = |value: f32, center: f32, half: f32| {
+        (value - center + half).rem_euclid(2.0 * half) + center - half
+    };
+    Vec3::new(
+        wrap(position.x, center.x, AREA_HALF_WIDTH),
+        wrap(position.y, center.y, AREA_HALF_HEIGHT),
+        wrap(position.z, center.z, AREA_HALF_WIDTH),
+    )
+}
+
+/// Camera that precipitation falls around and fog is applied to.
+#[derive(Component, Debug, Default)]
+pub struct WeatherCamera;
+
+/// Area that changes the weather while the player is inside it.
+///
+/// Spawned from trigger entities with a `weather` property; `radius` (default
+/// [`WeatherZone::DEFAULT_RADIUS`]) sets its size.
+#[derive(Component, Debug, Clone)]
+pub struct WeatherZone {
+    pub weather: WeatherData,
+    pub radius: f32,
+}
+
+impl WeatherZone {
+    /// Radius used when the trigger doesn't set one.
+    pub const DEFAULT_RADIUS: f32 = 3.0;
+}
+
+/// Weather of the zone `player` is in, preferring the nearest zone's center
+/// where zones overlap.
+pub fn zone_weather<'a>(
+    zones: impl IntoIterator<Item = (&'a WeatherZone, Vec3)>,
+    player: Vec3,
+) -> Option<&'a WeatherData> {
+    zones
+        .into_iter()
+        .map(|(zone, center)| (zone, center.distance(player)))
+        .filter(|(zone, distance)| *distance <= zone.radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(zone, _)| &zone.weather)
+}
+
+/// A precipitation particle.
+#[derive(Component, Debug)]
+pub struct WeatherDrop {
+    /// Position in the pool; drops past the active count are hidden
+    index: usize,
+    /// Offset that keeps snowflakes from swaying in step
+    phase: f32,
+}
+
+/// Precipitation particles and the assets they render with.
+#[derive(Resource, Default)]
+pub struct WeatherDrops {
+    /// Precipitation the drops currently look like
+    look: Precipitation,
+    mesh: Option<Handle<Mesh>>,
+    rain_material: Option<Handle<StandardMaterial>>,
+    snow_material: Option<Handle<StandardMaterial>>,
+    /// Generator for drop positions
+    rng: Xorshift,
+}
+
+impl WeatherDrops {
+    /// Random point in the box around `center`.
+    fn random_position(&mut self, center: Vec3) -> Vec3 {
+        center
+            + Vec3::new(
+                (self.rng.next_unit() * 2.0 - 1.0) * AREA_HALF_WIDTH,
+                (self.rng.next_unit() * 2.0 - 1.0) * AREA_HALF_HEIGHT,
+                (self.rng.next_unit() * 2.0 - 1.0) * AREA_HALF_WIDTH,
+            )
+    }
+
+    fn mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        self.mesh
+            .get_or_insert_with(|| meshes.add(Cuboid::from_length(1.0)))
+            .clone()
+    }
+
+    fn material(
+        &mut self,
+        precipitation: Precipitation,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        let (slot, color, alpha_mode) = match precipitation {
+            Precipitation::Snow => (&mut self.snow_material, Color::WHITE, AlphaMode::Opaque),
+            _ => (
+                &mut self.rain_material,
+                Color::srgba(0.75, 0.8, 0.9, 0.5),
+                AlphaMode::Blend,
+            ),
+        };
+        slot.get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: color,
+                alpha_mode,
+                unlit: true,
+                ..default()
+            })
+        })
+        .clone()
+    }
+}
+
+/// Size of a drop for `precipitation`.
+fn drop_scale(precipitation: Precipitation) -> Vec3 {
+    match precipitation {
+        Precipitation::Snow => Vec3::splat(0.08),
+        _ => Vec3::new(0.02, 0.45, 0.02),
+    }
+}
+
+/// Switch the weather to that of the zone the player is in, or back to the map's.
+pub fn apply_weather_zones(
+    player: Option<Single<&Transform, With<Player>>>,
+    zones: Query<(&WeatherZone, &GlobalTransform)>,
+    mut weather: ResMut<Weather>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    let desired = zone_weather(
+        zones
+            .iter()
+            .map(|(zone, transform)| (zone, transform.translation())),
+        player.translation,
+    )
+    .unwrap_or(&weather.base)
+    .clone();
+    if weather.target != desired {
+        weather.target = desired;
+    }
+}
+
+/// Ease the current weather towards its target.
+pub fn step_weather(time: Res<Time>, mut weather: ResMut<Weather>) {
+    if weather.current != weather.target {
+        weather.step(time.delta_secs());
+    }
+}
+
+/// Spawn, move and hide precipitation particles around the weather camera.
+#[allow(clippy::too_many_arguments)]
+pub fn update_precipitation(
+    mut commands: Commands,
+    time: Res<Time>,
+    weather: Res<Weather>,
+    camera: Option<Single<&GlobalTransform, With<WeatherCamera>>>,
+    mut pool: ResMut<WeatherDrops>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut drops: Query<(
+        &WeatherDrop,
+        &mut Transform,
+        &mut Visibility,
+        &mut MeshMaterial3d<StandardMaterial>,
+    )>,
+) {
+    let Some(camera) = camera else {
+        return;
+    };
+    let center = camera.translation();
+    let current = &weather.current;
+    let active = active_drops(current);
+
+    // Restyle the pool when the kind of precipitation changes
+    let restyle = active > 0 && pool.look != current.precipitation;
+    if restyle {
+        pool.look = current.precipitation;
+    }
+    let look = pool.look;
+    let material = pool.material(look, &mut materials);
+
+    for index in drops.iter().len()..active {
+        let position = pool.random_position(center);
+        let phase = pool.rng.next_unit() * std::f32::consts::TAU;
+        let mesh = pool.mesh(&mut meshes);
+        commands.spawn((
+            Mesh3d(mesh),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(position).with_scale(drop_scale(look)),
+            Visibility::Visible,
+            NotShadowCaster,
+            WeatherDrop { index, phase },
+            GameEntity,
+        ));
+    }
+
+    let delta = time.delta_secs();
+    let elapsed = time.elapsed_secs();
+    for (drop, mut transform, mut visibility, mut drop_material) in &mut drops {
+        if drop.index >= active {
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        }
+        if *visibility != Visibility::Visible {
+            *visibility = Visibility::Visible;
+        }
+        if restyle {
+            drop_material.0 = material.clone();
+            transform.scale = drop_scale(look);
+        }
+
+        let velocity = match look {
+            Precipitation::Snow => Vec3::new(
+                (elapsed * 1.3 + drop.phase).sin() * 0.5,
+                -SNOW_SPEED,
+                (elapsed * 0.9 + drop.phase).cos() * 0.5,
+            ),
+            _ => Vec3::new(0.0, -RAIN_SPEED, 0.0),
+        };
+        let moved = transform.translation + velocity * delta;
+        let mut wrapped = wrap_into_area(moved, center);
+        if wrapped.y > moved.y + AREA_HALF_HEIGHT {
+            // Fell out of the bottom: come back down somewhere else
+            let fresh = pool.random_position(center);
+            wrapped.x = fresh.x;
+            wrapped.z = fresh.z;
+        }
+        transform.translation = wrapped;
+    }
+}
+
+/// Add, update or remove the fog on weather cameras.
+pub fn update_weather_fog(
+    mut commands: Commands,
+    weather: Res<Weather>,
+    cameras: Query<(Entity, Ref<WeatherCamera>, Has<DistanceFog>)>,
+) {
+    for (entity, camera, has_fog) in &cameras {
+        if !weather.is_changed() && !camera.is_added() {
+            continue;
+        }
+        match weather_fog(&weather.current) {
+            Some(fog) => {
+                commands.entity(entity).insert(fog);
+            }
+            None if has_fog => {
+                commands.entity(entity).remove::<DistanceFog>();
+            }
+            None => {}
+        }
+    }
+}
+
+/// Darken the chunk materials as the ground gets wet.
+///
+/// Emissive (lava) chunk materials keep their color.
+pub fn update_chunk_wetness(
+    weather: Res<Weather>,
+    occlusion_material: Option<Res<OcclusionMaterialHandle>>,
+    mut occlusion_materials: ResMut<Assets<OcclusionMaterial>>,
+    chunks: Query<&MeshMaterial3d<StandardMaterial>, With<VoxelChunk>>,
+    new_chunks: Query<(), Added<VoxelChunk>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut applied: Local<Option<f32>>,
+) {
+    let wetness = weather.current.wetness;
+    if *applied == Some(wetness) && new_chunks.is_empty() {
+        return;
+    }
+    let tint = wet_tint(wetness);
+
+    if let Some(handle) = occlusion_material {
+        if let Some(material) = occlusion_materials.get_mut(&handle.0) {
+            material.base.base_color = tint;
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for handle in &chunks {
+        if !seen.insert(handle.id()) {
+            continue;
+        }
+        let is_plain = materials
+            .get(&handle.0)
+            .is_some_and(|material| material.emissive == LinearRgba::BLACK);
+        if is_plain {
+            if let Some(material) = materials.get_mut(&handle.0) {
+                // Glass keeps its transparency
+                material.base_color = tint.with_alpha(material.base_color.alpha());
+            }
+        }
+    }
+    *applied = Some(wetness);
+}
+
+/// Plugin that sets up the weather resources and systems.
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Weather>()
+            .init_resource::<WeatherDrops>()
+            .add_systems(
+                Update,
+                (
+                    apply_weather_zones,
+                    step_weather,
+                    update_precipitation,
+                    update_weather_fog,
+                    update_chunk_wetness,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,164 @@
+use super::*;
+
+fn rain(intensity: f32) -> WeatherData {
+    WeatherData {
+        precipitation: Precipitation::Rain,
+        intensity,
+        ..WeatherData::default()
+    }
+}
+
+#[test]
+fn clear_weather_has_no_drops_fog_or_tint() {
+    let clear = WeatherData::default();
+    assert_eq!(active_drops(&clear), 0);
+    assert!(weather_fog(&clear).is_none());
+    assert_eq!(wet_tint(clear.wetness).to_srgba(), Srgba::WHITE);
+}
+
+#[test]
+fn drops_scale_with_intensity() {
+    assert_eq!(active_drops(&rain(1.0)), MAX_WEATHER_DROPS);
+    assert_eq!(active_drops(&rain(0.5)), MAX_WEATHER_DROPS / 2);
+    assert_eq!(active_drops(&rain(0.0)), 0);
+}
+
+#[test]
+fn wet_ground_is_darker() {
+    let dry = wet_tint(0.0).to_srgba();
+    let soaked = wet_tint(1.0).to_srgba();
+    assert!(soaked.red < dry.red);
+    assert!(soaked.red > 0.0);
+}
+
+#[test]
+fn stepping_eases_towards_the_target() {
+    let mut weather = Weather::settled(WeatherData::default());
+    weather.target = WeatherData {
+        fog_density: 1.0,
+        wetness: 1.0,
+        ..rain(1.0)
+    };
+
+    weather.step(1.0);
+    assert_eq!(weather.current.precipitation, Precipitation::Rain);
+    assert!(weather.current.intensity > 0.0 && weather.current.intensity < 1.0);
+    assert!(weather.current.fog_density > 0.0 && weather.current.fog_density < 1.0);
+    assert!(
+        weather.current.wetness < weather.current.fog_density,
+        "ground gets wet slowly"
+    );
+
+    for _ in 0..20 {
+        weather.step(1.0);
+    }
+    assert_eq!(weather.current, weather.target);
+}
+
+#[test]
+fn switching_precipitation_fades_out_the_old_kind_first() {
+    let mut weather = Weather::settled(rain(1.0));
+    weather.target = WeatherData {
+        precipitation: Precipitation::Snow,
+        ..rain(1.0)
+    };
+
+    weather.step(1.0);
+    assert_eq!(weather.current.precipitation, Precipitation::Rain);
+    assert!(weather.current.intensity < 1.0);
+
+    for _ in 0..4 {
+        weather.step(1.0);
+    }
+    assert_eq!(weather.current.precipitation, Precipitation::Snow);
+}
+
+#[test]
+fn drops_wrap_around_the_camera() {
+    let center = Vec3::new(100.0, 20.0, -50.0);
+    let inside = center + Vec3::new(1.0, -2.0, 3.0);
+    assert_eq!(wrap_into_area(inside, center), inside);
+
+    let below = center + Vec3::new(0.0, -AREA_HALF_HEIGHT - 1.0, 0.0);
+    let wrapped = wrap_into_area(below, center);
+    assert!((wrapped.y - (center.y + AREA_HALF_HEIGHT - 1.0)).abs() < 1e-4);
+
+    let behind = center + Vec3::new(AREA_HALF_WIDTH + 2.0, 0.0, 0.0);
+    assert!((wrap_into_area(behind, center).x - (center.x - AREA_HALF_WIDTH + 2.0)).abs() < 1e-4);
+}
+
+#[test]
+fn the_nearest_zone_containing_the_player_wins() {
+    let storm = WeatherZone {
+        weather: rain(1.0),
+        radius: 5.0,
+    };
+    let blizzard = WeatherZone {
+        weather: WeatherData {
+            precipitation: Precipitation::Snow,
+            ..WeatherData::default()
+        },
+        radius: 2.0,
+    };
+    let zones = [(&storm, Vec3::ZERO), (&blizzard, Vec3::new(3.0, 0.0, 0.0))];
+
+    let at = |x: f32| zone_weather(zones, Vec3::new(x, 0.0, 0.0)).map(|w| w.precipitation);
+    assert_eq!(at(0.0), Some(Precipitation::Rain));
+    assert_eq!(at(3.0), Some(Precipitation::Snow));
+    assert_eq!(at(10.0), None);
+}
+
+#[test]
+fn triggers_read_weather_from_properties() {
+    let props = |pairs: &[(&str, &str)]| {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<std::collections::HashMap<_, _>>()
+    };
+
+    assert_eq!(WeatherData::from_properties(&props(&[])), Ok(None));
+    let snow = WeatherData::from_properties(&props(&[("weather", "Snow"), ("fog_density", "0.4")]))
+        .unwrap()
+        .unwrap();
+    assert_eq!(snow.precipitation, Precipitation::Snow);
+    assert_eq!(snow.fog_density, 0.4);
+    assert!(
+        WeatherData::from_properties(&props(&[("weather", "rain"), ("intensity", "x")])).is_err()
+    );
+}
+
+fn drop_count(app: &mut App) -> usize {
+    app.world_mut()
+        .query::<(&WeatherDrop, &GameEntity)>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn drops_despawned_with_the_game_world_are_spawned_again() {
+    let mut app = App::new();
+    app.insert_resource(Weather::settled(rain(0.1)))
+        .init_resource::<WeatherDrops>()
+        .init_resource::<Assets<Mesh>>()
+        .init_resource::<Assets<StandardMaterial>>()
+        .init_resource::<Time>()
+        .add_systems(Update, update_precipitation);
+    app.world_mut()
+        .spawn((WeatherCamera, GlobalTransform::default()));
+    let active = active_drops(&rain(0.1));
+
+    app.update();
+    assert_eq!(drop_count(&mut app), active);
+
+    let drops: Vec<Entity> = app
+        .world_mut()
+        .query_filtered::<Entity, With<WeatherDrop>>()
+        .iter(app.world())
+        .collect();
+    for entity in drops {
+        app.world_mut().despawn(entity);
+    }
+    app.update();
+    assert_eq!(drop_count(&mut app), active);
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+/// Marker for the root node of the game-over screen UI
+#[derive(Component)]
+pub struct GameOverRoot;
+
+/// Marker for the Retry button
+#[derive(Component)]
+pub struct RetryButton;
+
+/// Marker for the Quit to Title button
+#[derive(Component)]
+pub struct QuitToTitleButton;
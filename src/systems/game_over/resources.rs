@@ -0,0 +1,47 @@
+use crate::states::GameState;
+use bevy::prelude::*;
+
+/// Resource to track the selected menu index for keyboard navigation
+#[derive(Resource)]
+pub struct SelectedGameOverIndex {
+    pub index: usize,
+    pub total: usize,
+}
+
+impl Default for SelectedGameOverIndex {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            total: 2, // Retry and Quit to Title
+        }
+    }
+}
+
+/// Options on the game-over screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameOverChoice {
+    /// Reload the map and respawn at the checkpoint
+    Retry,
+    /// Return to the title screen
+    QuitToTitle,
+}
+
+impl GameOverChoice {
+    /// Choice for a menu index (0 = Retry, 1 = Quit to Title)
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Self::Retry),
+            1 => Some(Self::QuitToTitle),
+            _ => None,
+        }
+    }
+
+    /// State to enter for this choice. Retry goes back through the loading
+    /// screen, which respawns the player at the map's spawn point.
+    pub fn next_state(self) -> GameState {
+        match self {
+            Self::Retry => GameState::LoadingMap,
+            Self::QuitToTitle => GameState::TitleScreen,
+        }
+    }
+}
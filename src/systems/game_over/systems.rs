@@ -0,0 +1,198 @@
+use super::components::{GameOverRoot, QuitToTitleButton, RetryButton};
+use super::resources::{GameOverChoice, SelectedGameOverIndex};
+use crate::states::GameState;
+use crate::systems::game::gamepad::{get_menu_gamepad_input, ActiveGamepad, GamepadSettings};
+use crate::systems::localization::Localization;
+use crate::systems::pause_menu::components::ScalableText;
+use bevy::prelude::*;
+
+const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.15, 0.0);
+const HOVERED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.3);
+const PRESSED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.5);
+
+/// Spawns a menu button with a localized label
+fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Vw(20.0),
+                height: Val::Vh(8.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 30.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+                ScalableText::new(30.0, 1.0),
+            ));
+        });
+}
+
+/// Spawns the game-over screen over the frozen game world
+pub fn setup_game_over_screen(mut commands: Commands, localization: Res<Localization>) {
+    commands.insert_resource(SelectedGameOverIndex::default());
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.25, 0.0, 0.0, 0.7)),
+            GameOverRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new(localization.get("game_over.title")),
+                TextFont {
+                    font_size: 80.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.95, 0.3, 0.25, 1.0)),
+                Node {
+                    margin: UiRect::all(Val::Vw(5.0)),
+                    ..default()
+                },
+                ScalableText::new(80.0, 1.0),
+            ));
+
+            // Button container
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Vh(2.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                ))
+                .with_children(|parent| {
+                    spawn_button(parent, localization.get("game_over.retry"), RetryButton);
+                    spawn_button(
+                        parent,
+                        localization.get("game_over.quit_to_title"),
+                        QuitToTitleButton,
+                    );
+                });
+        });
+}
+
+/// Handles keyboard and gamepad navigation for the game-over screen
+pub fn game_over_navigation(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepad_query: Query<&Gamepad>,
+    settings: Res<GamepadSettings>,
+    mut selected: ResMut<SelectedGameOverIndex>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (gp_up, gp_down, gp_select, _gp_back) =
+        get_menu_gamepad_input(&active_gamepad, &gamepad_query, &settings);
+
+    if (keyboard_input.just_pressed(KeyCode::ArrowUp) || gp_up) && selected.index > 0 {
+        selected.index -= 1;
+    }
+    if (keyboard_input.just_pressed(KeyCode::ArrowDown) || gp_down)
+        && selected.index < selected.total - 1
+    {
+        selected.index += 1;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Enter) || gp_select {
+        if let Some(choice) = GameOverChoice::from_index(selected.index) {
+            next_state.set(choice.next_state());
+        }
+    }
+}
+
+type GameOverButtonQueryItem<'a> = (
+    &'a Interaction,
+    Mut<'a, BackgroundColor>,
+    Option<&'a RetryButton>,
+    Option<&'a QuitToTitleButton>,
+);
+
+/// Index of a game-over button from its marker components
+fn button_index(is_retry: bool, is_quit: bool) -> Option<usize> {
+    if is_retry {
+        Some(0)
+    } else if is_quit {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Updates the visual appearance of buttons based on keyboard selection
+pub fn update_selected_button_visual(
+    selected: Res<SelectedGameOverIndex>,
+    mut button_query: Query<GameOverButtonQueryItem, With<Button>>,
+) {
+    for (interaction, mut bg_color, is_retry, is_quit) in &mut button_query {
+        let Some(idx) = button_index(is_retry.is_some(), is_quit.is_some()) else {
+            continue;
+        };
+        // Only apply keyboard selection color if not being hovered/pressed by mouse
+        if *interaction == Interaction::None {
+            *bg_color = if idx == selected.index {
+                HOVERED_BUTTON.into()
+            } else {
+                NORMAL_BUTTON.into()
+            };
+        }
+    }
+}
+
+/// Handles mouse interaction with the Retry and Quit to Title buttons
+pub fn game_over_button_interaction(
+    mut interaction_query: Query<GameOverButtonQueryItem, (Changed<Interaction>, With<Button>)>,
+    mut selected: ResMut<SelectedGameOverIndex>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color, is_retry, is_quit) in &mut interaction_query {
+        let Some(idx) = button_index(is_retry.is_some(), is_quit.is_some()) else {
+            continue;
+        };
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                if let Some(choice) = GameOverChoice::from_index(idx) {
+                    next_state.set(choice.next_state());
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+                selected.index = idx;
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Cleans up the game-over screen. Both choices leave the game, and the
+/// world behind the screen is despawned on entering the next state.
+pub fn cleanup_game_over(mut commands: Commands, root_query: Query<Entity, With<GameOverRoot>>) {
+    for entity in &root_query {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<SelectedGameOverIndex>();
+}
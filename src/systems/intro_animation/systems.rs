@@ -36,10 +36,24 @@ pub fn setup_intro(mut commands: Commands) {
 
 pub fn animate_intro(
     time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
     mut timer: ResMut<IntroAnimationTimer>,
     mut text_color: Single<&mut TextColor, With<IntroText>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
+    // Any key, mouse button or gamepad button skips to the title screen
+    let skipped = keyboard.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some());
+    if skipped {
+        next_state.set(GameState::TitleScreen);
+        return;
+    }
+
     timer.timer.tick(time.delta());
 
     match timer.phase {
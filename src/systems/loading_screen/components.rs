@@ -13,3 +13,15 @@ pub struct ProgressBarFill;
 /// Component for the loading text.
 #[derive(Component)]
 pub struct LoadingText;
+
+/// Component for the spinner glyph next to the status text.
+#[derive(Component)]
+pub struct LoadingSpinner;
+
+/// Component for the list of problems a safe-mode load skipped.
+#[derive(Component)]
+pub struct LoadDiagnosticsText;
+
+/// Component for the rotating gameplay tip.
+#[derive(Component)]
+pub struct LoadingTipText;
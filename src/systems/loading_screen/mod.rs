@@ -1,6 +1,17 @@
 //! Loading screen system for displaying map loading progress.
+//!
+//! Shows the map's name, author and description (read from the file header
+//! before the full load), a progress bar, a spinner and rotating gameplay tips.
+//! When the map had to be loaded in safe mode, the problems it skipped are
+//! listed and the game waits for the player to continue.
 
 mod components;
+mod resources;
 mod systems;
 
-pub use systems::{cleanup_loading_screen, setup_loading_screen, update_loading_progress};
+pub use components::LoadingScreenUI;
+pub use resources::LoadingMapInfo;
+pub use systems::{
+    acknowledge_load_diagnostics, animate_loading_screen, cleanup_loading_screen,
+    setup_loading_screen, show_load_diagnostics, update_loading_progress,
+};
@@ -0,0 +1,40 @@
+//! Resources for the loading screen.
+
+use crate::systems::game::map::format::MapMetadata;
+use bevy::prelude::*;
+
+/// Seconds each gameplay tip stays on screen.
+const TIP_INTERVAL_SECS: f32 = 5.0;
+
+/// Seconds between spinner frames.
+const SPINNER_FRAME_SECS: f32 = 0.1;
+
+/// Header of the map being loaded, read before the full load starts.
+///
+/// `None` when the header could not be read (missing file, malformed RON);
+/// the loading screen then shows only the progress bar and tips.
+#[derive(Resource, Default)]
+pub struct LoadingMapInfo {
+    pub metadata: Option<MapMetadata>,
+}
+
+/// Timers driving the rotating tip and the spinner.
+#[derive(Resource)]
+pub struct LoadingScreenAnimation {
+    pub tip_index: usize,
+    pub tip_timer: Timer,
+    pub spinner_frame: usize,
+    pub spinner_timer: Timer,
+}
+
+impl LoadingScreenAnimation {
+    /// Start at `tip_index` so consecutive loads don't always open on the same tip.
+    pub fn new(tip_index: usize) -> Self {
+        Self {
+            tip_index,
+            tip_timer: Timer::from_seconds(TIP_INTERVAL_SECS, TimerMode::Repeating),
+            spinner_frame: 0,
+            spinner_timer: Timer::from_seconds(SPINNER_FRAME_SECS, TimerMode::Repeating),
+        }
+    }
+}
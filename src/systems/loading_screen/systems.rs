@@ -1,11 +1,48 @@
 //! Systems for the loading screen.
 
-use super::components::{LoadingScreenUI, LoadingText, ProgressBarFill};
-use crate::systems::game::map::MapLoadProgress;
+use super::components::{
+    LoadDiagnosticsText, LoadingScreenUI, LoadingSpinner, LoadingText, LoadingTipText,
+    ProgressBarFill,
+};
+use super::resources::{LoadingMapInfo, LoadingScreenAnimation};
+use crate::systems::game::map::{
+    LoadDiagnostic, LoadProgress, MapLoadDiagnostics, MapLoadProgress,
+};
+use crate::systems::localization::Localization;
 use bevy::prelude::*;
 
+/// Localization keys of the gameplay tips, shown in rotation.
+const TIP_KEYS: &[&str] = &[
+    "loading.tip.pause",
+    "loading.tip.jump",
+    "loading.tip.occlusion",
+    "loading.tip.settings",
+    "loading.tip.editor",
+];
+
+/// Frames of the text spinner shown next to the status line.
+const SPINNER_FRAMES: &[&str] = &["|", "/", "-", "\\"];
+
+/// Number of safe-mode problems listed; the rest are counted.
+const LISTED_DIAGNOSTICS: usize = 5;
+
 /// Setup the loading screen UI.
-pub fn setup_loading_screen(mut commands: Commands) {
+///
+/// Shows the map header from [`LoadingMapInfo`] when it is available.
+pub fn setup_loading_screen(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    map_info: Option<Res<LoadingMapInfo>>,
+    mut load_count: Local<usize>,
+) {
+    // Start each load on a different tip.
+    let animation = LoadingScreenAnimation::new(*load_count % TIP_KEYS.len());
+    *load_count += 1;
+    let first_tip = tip_text(animation.tip_index, &localization);
+    commands.insert_resource(animation);
+
+    let metadata = map_info.and_then(|info| info.metadata.clone());
+
     commands
         .spawn((
             Node {
@@ -22,7 +59,7 @@ pub fn setup_loading_screen(mut commands: Commands) {
         .with_children(|parent| {
             // Loading title
             parent.spawn((
-                Text::new("Loading Map..."),
+                Text::new(localization.get("loading.title")),
                 TextFont {
                     font_size: 48.0,
                     ..default()
@@ -34,6 +71,51 @@ pub fn setup_loading_screen(mut commands: Commands) {
                 },
             ));
 
+            // Map header
+            if let Some(metadata) = &metadata {
+                parent.spawn((
+                    Text::new(metadata.name.clone()),
+                    TextFont {
+                        font_size: 32.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.95, 0.85, 0.5)),
+                ));
+                if !metadata.author.is_empty() {
+                    parent.spawn((
+                        Text::new(
+                            localization
+                                .format("loading.by_author", &[("author", &metadata.author)]),
+                        ),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                    ));
+                }
+                if !metadata.description.is_empty() {
+                    parent.spawn((
+                        Text::new(metadata.description.clone()),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.75, 0.75, 0.75)),
+                        TextLayout::new_with_justify(Justify::Center),
+                        Node {
+                            max_width: Val::Px(600.0),
+                            margin: UiRect::top(Val::Px(10.0)),
+                            ..default()
+                        },
+                    ));
+                }
+                parent.spawn(Node {
+                    height: Val::Px(30.0),
+                    ..default()
+                });
+            }
+
             // Progress bar container
             parent
                 .spawn((
@@ -59,26 +141,111 @@ pub fn setup_loading_screen(mut commands: Commands) {
                     ));
                 });
 
-            // Loading status text
+            // Spinner + loading status text
+            parent
+                .spawn(Node {
+                    margin: UiRect::top(Val::Px(20.0)),
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(SPINNER_FRAMES[0]),
+                        TextFont {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.2, 0.8, 0.3)),
+                        LoadingSpinner,
+                    ));
+                    parent.spawn((
+                        Text::new(localization.get("loading.initializing")),
+                        TextFont {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        LoadingText,
+                    ));
+                });
+
+            // Problems skipped by a safe-mode load, filled in when known
             parent.spawn((
-                Text::new("Initializing..."),
+                Text::default(),
                 TextFont {
-                    font_size: 20.0,
+                    font_size: 16.0,
                     ..default()
                 },
-                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                TextColor(Color::srgb(0.95, 0.6, 0.3)),
                 Node {
+                    max_width: Val::Px(700.0),
                     margin: UiRect::top(Val::Px(20.0)),
                     ..default()
                 },
-                LoadingText,
+                LoadDiagnosticsText,
+            ));
+
+            // Rotating gameplay tip
+            parent.spawn((
+                Text::new(first_tip),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.7)),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    max_width: Val::Px(600.0),
+                    margin: UiRect::top(Val::Px(60.0)),
+                    ..default()
+                },
+                LoadingTipText,
             ));
         });
 }
 
+/// Advance the spinner and rotate the gameplay tip.
+pub fn animate_loading_screen(
+    time: Res<Time>,
+    localization: Res<Localization>,
+    animation: Option<ResMut<LoadingScreenAnimation>>,
+    mut spinner_query: Query<&mut Text, (With<LoadingSpinner>, Without<LoadingTipText>)>,
+    mut tip_query: Query<&mut Text, (With<LoadingTipText>, Without<LoadingSpinner>)>,
+) {
+    let Some(mut animation) = animation else {
+        return;
+    };
+
+    animation.spinner_timer.tick(time.delta());
+    if animation.spinner_timer.just_finished() {
+        animation.spinner_frame = (animation.spinner_frame + 1) % SPINNER_FRAMES.len();
+        for mut text in &mut spinner_query {
+            text.0 = SPINNER_FRAMES[animation.spinner_frame].to_string();
+        }
+    }
+
+    animation.tip_timer.tick(time.delta());
+    if animation.tip_timer.just_finished() {
+        animation.tip_index = (animation.tip_index + 1) % TIP_KEYS.len();
+        for mut text in &mut tip_query {
+            text.0 = tip_text(animation.tip_index, &localization);
+        }
+    }
+}
+
+/// Tip `index` prefixed with the localized "Tip:" label.
+fn tip_text(index: usize, localization: &Localization) -> String {
+    format!(
+        "{} {}",
+        localization.get("loading.tip_label"),
+        localization.get(TIP_KEYS[index % TIP_KEYS.len()])
+    )
+}
+
 /// Update the loading progress bar and text.
 pub fn update_loading_progress(
     progress: Res<MapLoadProgress>,
+    localization: Res<Localization>,
     mut fill_query: Query<&mut Node, With<ProgressBarFill>>,
     mut text_query: Query<&mut Text, With<LoadingText>>,
 ) {
@@ -90,8 +257,83 @@ pub fn update_loading_progress(
 
         // Update status text
         for mut text in &mut text_query {
-            text.0 = current_progress.description();
+            text.0 = localized_description(current_progress, &localization);
+        }
+    }
+}
+
+/// Translate a progress stage into the active language.
+fn localized_description(progress: &LoadProgress, localization: &Localization) -> String {
+    let (key, fraction) = match progress {
+        LoadProgress::Started => return localization.get("loading.started").to_string(),
+        LoadProgress::LoadingFile(p) => ("loading.file", p),
+        LoadProgress::ParsingData(p) => ("loading.parsing", p),
+        LoadProgress::ValidatingMap(p) => ("loading.validating", p),
+        LoadProgress::SpawningVoxels(p) => ("loading.voxels", p),
+        LoadProgress::SpawningEntities(p) => ("loading.entities", p),
+        LoadProgress::Finalizing(p) => ("loading.finalizing", p),
+        LoadProgress::Complete => return localization.get("loading.complete").to_string(),
+        LoadProgress::Error(msg) => {
+            return localization.format("loading.error", &[("message", msg)]);
         }
+    };
+    let percent = format!("{:.0}", fraction * 100.0);
+    localization.format(key, &[("percent", &percent)])
+}
+
+/// List the problems a safe-mode load skipped, once they are known.
+pub fn show_load_diagnostics(
+    diagnostics: Res<MapLoadDiagnostics>,
+    localization: Res<Localization>,
+    mut text_query: Query<&mut Text, With<LoadDiagnosticsText>>,
+) {
+    if !diagnostics.is_changed() {
+        return;
+    }
+    let text = diagnostics_text(&diagnostics.diagnostics, &localization);
+    for mut diagnostics_text in &mut text_query {
+        diagnostics_text.0 = text.clone();
+    }
+}
+
+/// The loading screen's summary of `diagnostics`, empty when there are none.
+fn diagnostics_text(diagnostics: &[LoadDiagnostic], localization: &Localization) -> String {
+    if diagnostics.is_empty() {
+        return String::new();
+    }
+    let count = diagnostics.len().to_string();
+    let mut lines = vec![localization.format("loading.safe_mode", &[("count", &count)])];
+    lines.extend(
+        diagnostics
+            .iter()
+            .take(LISTED_DIAGNOSTICS)
+            .map(|diagnostic| diagnostic.to_string()),
+    );
+    let hidden = diagnostics.len().saturating_sub(LISTED_DIAGNOSTICS);
+    if hidden > 0 {
+        let hidden = hidden.to_string();
+        lines.push(localization.format("loading.safe_mode_more", &[("count", &hidden)]));
+    }
+    lines.push(localization.get("loading.safe_mode_continue").to_string());
+    lines.join("\n")
+}
+
+/// Let the player go on into a map loaded in safe mode with Enter or the A
+/// button once they have seen what was skipped.
+pub fn acknowledge_load_diagnostics(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut diagnostics: ResMut<MapLoadDiagnostics>,
+) {
+    if diagnostics.allows_start() {
+        return;
+    }
+    let pressed = keyboard.just_pressed(KeyCode::Enter)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+    if pressed {
+        diagnostics.acknowledged = true;
     }
 }
 
@@ -100,4 +342,6 @@ pub fn cleanup_loading_screen(mut commands: Commands, query: Query<Entity, With<
     for entity in &query {
         commands.entity(entity).despawn();
     }
+    commands.remove_resource::<LoadingScreenAnimation>();
+    commands.remove_resource::<LoadingMapInfo>();
 }
@@ -0,0 +1,216 @@
+//! Localization (i18n) for player-facing UI text.
+//!
+//! Language tables are RON key-value maps stored in `assets/locales/<code>.ron`.
+//! The active table is held by the [`Localization`] resource; UI code looks up
+//! strings by key instead of hardcoding English.
+//!
+//! Lookups fall back to the English table and finally to the key itself, so a
+//! missing translation never produces an empty label. The English table is
+//! embedded in the binary, so the game still has readable text when the
+//! `assets/locales` directory is missing.
+//!
+//! Text nodes that must follow live language changes (e.g. the settings screen
+//! while the Language row is being cycled) carry a [`LocalizedText`] component
+//! and are refreshed by [`update_localized_text`].
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Directory containing the language tables.
+pub const LOCALES_DIR: &str = "assets/locales";
+
+/// Prefix marking map-authored text (e.g. an NPC `name` property) as a
+/// localization key rather than literal text.
+///
+/// `"loc:npc.blacksmith"` resolves through the active table; plain text is
+/// shown unchanged.
+pub const LOCALIZED_KEY_PREFIX: &str = "loc:";
+
+/// English table compiled into the binary as the last-resort fallback.
+const EMBEDDED_ENGLISH: &str = include_str!("../../../assets/locales/en.ron");
+
+/// Languages the game ships tables for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    /// All supported languages, in settings-menu cycling order.
+    pub const ALL: &'static [Language] = &[Language::English, Language::Spanish];
+
+    /// File stem of the language table in [`LOCALES_DIR`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// Name of the language in that language, as shown in the settings menu.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    /// Returns the language `delta` steps away in [`Language::ALL`], wrapping.
+    pub fn cycled(self, delta: i32) -> Language {
+        let all = Self::ALL;
+        let cur = all.iter().position(|l| *l == self).unwrap_or(0);
+        all[(cur as i32 + delta).rem_euclid(all.len() as i32) as usize]
+    }
+}
+
+/// Active language and its string tables.
+#[derive(Resource)]
+pub struct Localization {
+    language: Language,
+    /// Strings for the active language.
+    table: HashMap<String, String>,
+    /// English strings used when the active table lacks a key.
+    fallback: HashMap<String, String>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        let fallback = parse_table(EMBEDDED_ENGLISH).unwrap_or_default();
+        Self {
+            language: Language::English,
+            table: fallback.clone(),
+            fallback,
+        }
+    }
+}
+
+impl Localization {
+    /// Build a localization from in-memory tables. Used by tests and tools.
+    #[allow(dead_code)]
+    pub fn from_tables(
+        language: Language,
+        table: HashMap<String, String>,
+        fallback: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            language,
+            table,
+            fallback,
+        }
+    }
+
+    /// Currently active language.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Switch to `language`, loading its table from [`LOCALES_DIR`].
+    ///
+    /// If the file is missing or malformed the English fallback is used for
+    /// every key, and a warning is logged.
+    pub fn set_language(&mut self, language: Language) {
+        self.set_language_from_dir(language, Path::new(LOCALES_DIR));
+    }
+
+    /// Same as [`Localization::set_language`] but reads tables from `dir`.
+    pub fn set_language_from_dir(&mut self, language: Language, dir: &Path) {
+        if let Some(english) = load_table(Language::English, dir) {
+            self.fallback = english;
+        }
+        self.table = if language == Language::English {
+            self.fallback.clone()
+        } else {
+            load_table(language, dir).unwrap_or_default()
+        };
+        self.language = language;
+        info!("[Localization] Language set to {}", language.native_name());
+    }
+
+    /// Look up `key`, falling back to English and then to the key itself.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.table
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Look up `key` and substitute `{name}` placeholders from `args`.
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let mut text = self.get(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), value);
+        }
+        text
+    }
+
+    /// Resolve map-authored text: `loc:`-prefixed values are looked up as keys,
+    /// anything else is returned unchanged.
+    pub fn resolve(&self, text: &str) -> String {
+        match text.strip_prefix(LOCALIZED_KEY_PREFIX) {
+            Some(key) => self.get(key).to_string(),
+            None => text.to_string(),
+        }
+    }
+}
+
+/// Marks a UI text node whose content is the translation of `0`.
+///
+/// [`update_localized_text`] rewrites the node whenever the language changes.
+#[derive(Component)]
+pub struct LocalizedText(pub &'static str);
+
+/// Refreshes every [`LocalizedText`] node after the active language changes.
+pub fn update_localized_text(
+    localization: Res<Localization>,
+    mut query: Query<(&LocalizedText, &mut Text)>,
+) {
+    if !localization.is_changed() {
+        return;
+    }
+    for (localized, mut text) in &mut query {
+        let translated = localization.get(localized.0);
+        if text.0 != translated {
+            text.0 = translated.to_string();
+        }
+    }
+}
+
+/// Registers the [`Localization`] resource and live text refresh.
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Localization>()
+            .add_systems(Update, update_localized_text);
+    }
+}
+
+/// Parse a RON key-value table.
+pub fn parse_table(contents: &str) -> Result<HashMap<String, String>, ron::error::SpannedError> {
+    ron::from_str(contents)
+}
+
+fn load_table(language: Language, dir: &Path) -> Option<HashMap<String, String>> {
+    let path = dir.join(format!("{}.ron", language.code()));
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => match parse_table(&contents) {
+            Ok(table) => Some(table),
+            Err(e) => {
+                warn!("[Localization] Failed to parse {:?}: {e}", path);
+                None
+            }
+        },
+        Err(e) => {
+            warn!("[Localization] Could not read {:?}: {e}", path);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,113 @@
+use super::*;
+
+fn table(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn embedded_english_table_parses() {
+    assert!(parse_table(EMBEDDED_ENGLISH).is_ok());
+}
+
+#[test]
+fn default_localization_is_english_with_embedded_strings() {
+    let loc = Localization::default();
+    assert_eq!(loc.language(), Language::English);
+    assert_eq!(loc.get("title.new_game"), "New Game");
+}
+
+#[test]
+fn shipped_tables_define_every_english_key() {
+    let english = parse_table(EMBEDDED_ENGLISH).unwrap();
+    for language in Language::ALL {
+        let path = Path::new(LOCALES_DIR).join(format!("{}.ron", language.code()));
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let translated = parse_table(&contents).unwrap();
+        for key in english.keys() {
+            assert!(
+                translated.contains_key(key),
+                "{:?} is missing key {key}",
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn missing_key_falls_back_to_english() {
+    let loc = Localization::from_tables(
+        Language::Spanish,
+        table(&[("a", "uno")]),
+        table(&[("a", "one"), ("b", "two")]),
+    );
+    assert_eq!(loc.get("b"), "two");
+}
+
+#[test]
+fn unknown_key_returns_key_itself() {
+    let loc = Localization::from_tables(Language::English, table(&[]), table(&[]));
+    assert_eq!(loc.get("no.such.key"), "no.such.key");
+}
+
+#[test]
+fn active_table_takes_precedence_over_fallback() {
+    let loc = Localization::from_tables(
+        Language::Spanish,
+        table(&[("a", "uno")]),
+        table(&[("a", "one")]),
+    );
+    assert_eq!(loc.get("a"), "uno");
+}
+
+#[test]
+fn format_substitutes_named_placeholders() {
+    let loc = Localization::from_tables(
+        Language::English,
+        table(&[("loading.file", "Loading file... {percent}%")]),
+        table(&[]),
+    );
+    assert_eq!(
+        loc.format("loading.file", &[("percent", "50")]),
+        "Loading file... 50%"
+    );
+}
+
+#[test]
+fn resolve_looks_up_prefixed_text_and_passes_plain_text_through() {
+    let loc = Localization::from_tables(
+        Language::English,
+        table(&[("npc.smith", "Blacksmith")]),
+        table(&[]),
+    );
+    assert_eq!(loc.resolve("loc:npc.smith"), "Blacksmith");
+    assert_eq!(loc.resolve("Old Tom"), "Old Tom");
+}
+
+#[test]
+fn language_cycling_wraps_in_both_directions() {
+    assert_eq!(Language::English.cycled(1), Language::Spanish);
+    assert_eq!(Language::Spanish.cycled(1), Language::English);
+    assert_eq!(Language::English.cycled(-1), Language::Spanish);
+}
+
+#[test]
+fn set_language_from_missing_dir_keeps_english_fallback() {
+    let mut loc = Localization::default();
+    loc.set_language_from_dir(Language::Spanish, Path::new("does/not/exist"));
+    assert_eq!(loc.language(), Language::Spanish);
+    assert_eq!(loc.get("title.exit"), "Exit");
+}
+
+#[test]
+fn set_language_loads_table_from_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("en.ron"), r#"{"k": "english"}"#).unwrap();
+    std::fs::write(dir.path().join("es.ron"), r#"{"k": "español"}"#).unwrap();
+
+    let mut loc = Localization::default();
+    loc.set_language_from_dir(Language::Spanish, dir.path());
+    assert_eq!(loc.get("k"), "español");
+}
@@ -1,6 +1,11 @@
+pub mod accessibility;
 pub mod game;
+pub mod game_over;
 pub mod intro_animation;
 pub mod loading_screen;
+pub mod localization;
 pub mod pause_menu;
+pub mod preload;
+pub mod results;
 pub mod settings;
 pub mod title_screen;
@@ -1,20 +1,25 @@
+use super::resources::PauseMenuChoice;
 use bevy::prelude::*;
 
 /// Marker for the root node of the pause menu UI
 #[derive(Component)]
 pub struct PauseMenuRoot;
 
-/// Marker for the Resume button
+/// A pause menu button and the choice it makes
 #[derive(Component)]
-pub struct ResumeButton;
+pub struct PauseMenuButton(pub PauseMenuChoice);
 
-/// Marker for the Settings button
+/// Marker for the Hot Reload toggle label, updated when the setting changes
 #[derive(Component)]
-pub struct SettingsButton;
+pub struct HotReloadToggleText;
 
-/// Marker for the Quit button
+/// Marker for the root node of the confirmation dialog
 #[derive(Component)]
-pub struct QuitButton;
+pub struct ConfirmDialogRoot;
+
+/// A confirmation dialog button; `true` for the one that goes ahead
+#[derive(Component)]
+pub struct ConfirmButton(pub bool);
 
 /// Component for text that scales with window size
 #[derive(Component)]
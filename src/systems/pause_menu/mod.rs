@@ -1,3 +1,6 @@
 pub mod components;
 pub mod resources;
 pub mod systems;
+
+#[cfg(test)]
+mod tests;
@@ -1,3 +1,5 @@
+use crate::states::GameState;
+use crate::systems::game::speedrun::{SpeedrunTimer, TimerPhase};
 use bevy::prelude::*;
 
 /// Resource to track the selected menu index for keyboard navigation
@@ -11,7 +13,98 @@ impl Default for SelectedPauseMenuIndex {
     fn default() -> Self {
         Self {
             index: 0,
-            total: 3, // Resume, Settings, and Quit
+            total: PauseMenuChoice::ALL.len(),
+        }
+    }
+}
+
+/// Options on the pause menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseMenuChoice {
+    Resume,
+    Settings,
+    PhotoMode,
+    HotReload,
+    /// Reload the current map from the loading screen
+    RestartMap,
+    ReturnToTitle,
+    Quit,
+}
+
+impl PauseMenuChoice {
+    /// Choices top to bottom
+    pub const ALL: [PauseMenuChoice; 7] = [
+        PauseMenuChoice::Resume,
+        PauseMenuChoice::Settings,
+        PauseMenuChoice::PhotoMode,
+        PauseMenuChoice::HotReload,
+        PauseMenuChoice::RestartMap,
+        PauseMenuChoice::ReturnToTitle,
+        PauseMenuChoice::Quit,
+    ];
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+
+    /// Position on the menu
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|&c| c == self).unwrap_or(0)
+    }
+
+    /// Localization key of the button label. The Hot Reload label also
+    /// shows the setting, see `hot_reload_label`.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Self::Resume => "pause.resume",
+            Self::Settings => "pause.settings",
+            Self::PhotoMode => "pause.photo_mode",
+            Self::HotReload => "pause.hot_reload",
+            Self::RestartMap => "pause.restart_map",
+            Self::ReturnToTitle => "pause.return_to_title",
+            Self::Quit => "pause.quit",
+        }
+    }
+
+    /// State entered when this choice leaves the game, despawning the map
+    pub fn leaves_game_for(self) -> Option<GameState> {
+        match self {
+            Self::RestartMap => Some(GameState::LoadingMap),
+            Self::ReturnToTitle => Some(GameState::TitleScreen),
+            _ => None,
+        }
+    }
+
+    /// Localization key of the question asked before leaving the game
+    pub fn confirm_key(self) -> Option<&'static str> {
+        match self {
+            Self::RestartMap => Some("pause.confirm_restart_map"),
+            Self::ReturnToTitle => Some("pause.confirm_return_to_title"),
+            _ => None,
+        }
+    }
+}
+
+/// Whether leaving the map now would lose the run in progress: the player
+/// has moved since it spawned and has not reached a goal.
+pub fn progress_at_risk(timer: Option<&SpeedrunTimer>) -> bool {
+    timer.is_some_and(|timer| timer.phase == TimerPhase::Running)
+}
+
+/// Choice waiting on the confirmation dialog
+#[derive(Resource, Debug)]
+pub struct PendingConfirmation {
+    pub choice: PauseMenuChoice,
+    /// Whether the confirm button, rather than Cancel, is selected
+    pub confirm_selected: bool,
+}
+
+impl PendingConfirmation {
+    /// Starts on Cancel, so a stray Enter keeps the game
+    pub fn new(choice: PauseMenuChoice) -> Self {
+        Self {
+            choice,
+            confirm_selected: false,
         }
     }
 }
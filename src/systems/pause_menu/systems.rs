@@ -1,17 +1,89 @@
-use super::components::{PauseMenuRoot, QuitButton, ResumeButton, ScalableText, SettingsButton};
-use super::resources::SelectedPauseMenuIndex;
+use super::components::{
+    ConfirmButton, ConfirmDialogRoot, HotReloadToggleText, PauseMenuButton, PauseMenuRoot,
+    ScalableText,
+};
+use super::resources::{
+    progress_at_risk, PauseMenuChoice, PendingConfirmation, SelectedPauseMenuIndex,
+};
 use crate::states::GameState;
+use crate::systems::accessibility::{AccessibilitySettings, MenuTheme};
 use crate::systems::game::gamepad::{get_menu_gamepad_input, ActiveGamepad, GamepadSettings};
+use crate::systems::game::hot_reload::HotReloadState;
+use crate::systems::game::speedrun::SpeedrunTimer;
+use crate::systems::localization::Localization;
 use crate::systems::settings::resources::SettingsOrigin;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
 use bevy::window::WindowResized;
+/// Label for the Hot Reload toggle in the active language
+fn hot_reload_label(enabled: bool, localization: &Localization) -> String {
+    let state = localization.get(if enabled {
+        "settings.on"
+    } else {
+        "settings.off"
+    });
+    localization.format("pause.hot_reload", &[("state", state)])
+}
 
-const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.15, 0.0);
-const HOVERED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.3);
-const PRESSED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.5);
+/// Flips hot reload on or off. Takes effect when the game resumes and the
+/// watcher is restarted.
+fn toggle_hot_reload(hot_reload: &mut HotReloadState) {
+    hot_reload.enabled = !hot_reload.enabled;
+    info!(
+        "Hot reload {} from pause menu",
+        if hot_reload.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+}
+
+/// Spawns a menu button; `label_marker` goes on its text
+fn spawn_button(
+    parent: &mut ChildSpawnerCommands,
+    theme: &MenuTheme,
+    label: String,
+    marker: impl Component,
+    label_marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Vw(20.0),
+                height: Val::Vh(8.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(theme.button),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 30.0,
+                    ..default()
+                },
+                TextColor(theme.text),
+                ScalableText::new(30.0, 1.0),
+                label_marker,
+            ));
+        });
+}
 
 /// Spawns the pause menu UI
-pub fn setup_pause_menu(mut commands: Commands) {
+pub fn setup_pause_menu(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    hot_reload: Res<HotReloadState>,
+    accessibility: Res<AccessibilitySettings>,
+) {
+    let theme = MenuTheme::new(accessibility.high_contrast);
+
     // Insert selected menu index resource
     commands.insert_resource(SelectedPauseMenuIndex::default());
 
@@ -26,18 +98,18 @@ pub fn setup_pause_menu(mut commands: Commands) {
                 position_type: PositionType::Absolute,
                 ..default()
             },
-            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            BackgroundColor(theme.backdrop),
             PauseMenuRoot,
         ))
         .with_children(|parent| {
             // Title
             parent.spawn((
-                Text::new("Paused"),
+                Text::new(localization.get("pause.title")),
                 TextFont {
                     font_size: 80.0,
                     ..default()
                 },
-                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
+                TextColor(theme.text),
                 Node {
                     margin: UiRect::all(Val::Vw(5.0)),
                     ..default()
@@ -57,87 +129,66 @@ pub fn setup_pause_menu(mut commands: Commands) {
                     BackgroundColor(Color::NONE),
                 ))
                 .with_children(|parent| {
-                    // Resume button
-                    parent
-                        .spawn((
-                            Button,
-                            Node {
-                                width: Val::Vw(20.0),
-                                height: Val::Vh(8.0),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            BackgroundColor(NORMAL_BUTTON),
-                            ResumeButton,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Resume"),
-                                TextFont {
-                                    font_size: 30.0,
-                                    ..default()
-                                },
-                                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
-                                ScalableText::new(30.0, 1.0),
-                            ));
-                        });
-
-                    // Settings button
-                    parent
-                        .spawn((
-                            Button,
-                            Node {
-                                width: Val::Vw(20.0),
-                                height: Val::Vh(8.0),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            BackgroundColor(NORMAL_BUTTON),
-                            SettingsButton,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Settings"),
-                                TextFont {
-                                    font_size: 30.0,
-                                    ..default()
-                                },
-                                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
-                                ScalableText::new(30.0, 1.0),
-                            ));
-                        });
-
-                    // Quit button
-                    parent
-                        .spawn((
-                            Button,
-                            Node {
-                                width: Val::Vw(20.0),
-                                height: Val::Vh(8.0),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            BackgroundColor(NORMAL_BUTTON),
-                            QuitButton,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                Text::new("Quit"),
-                                TextFont {
-                                    font_size: 30.0,
-                                    ..default()
-                                },
-                                TextColor(Color::srgba(0.9, 0.9, 0.9, 1.0)),
-                                ScalableText::new(30.0, 1.0),
-                            ));
-                        });
+                    for choice in PauseMenuChoice::ALL {
+                        let button = PauseMenuButton(choice);
+                        if choice == PauseMenuChoice::HotReload {
+                            let label = hot_reload_label(hot_reload.enabled, &localization);
+                            spawn_button(parent, &theme, label, button, HotReloadToggleText);
+                        } else {
+                            let label = localization.get(choice.label_key()).to_string();
+                            spawn_button(parent, &theme, label, button, ());
+                        }
+                    }
                 });
         });
 }
 
+/// Everything a pause menu choice can change
+#[derive(SystemParam)]
+pub struct PauseMenuActions<'w, 's> {
+    commands: Commands<'w, 's>,
+    hot_reload: ResMut<'w, HotReloadState>,
+    timer: Option<Res<'w, SpeedrunTimer>>,
+    next_state: ResMut<'w, NextState<GameState>>,
+    exit: MessageWriter<'w, AppExit>,
+}
+
+impl PauseMenuActions<'_, '_> {
+    /// Carries out a pause menu choice. Leaving the map mid-run asks first.
+    fn choose(&mut self, choice: PauseMenuChoice) {
+        match choice {
+            PauseMenuChoice::Resume => self.next_state.set(GameState::InGame),
+            PauseMenuChoice::Settings => {
+                self.commands.insert_resource(SettingsOrigin::Paused);
+                self.next_state.set(GameState::Settings);
+            }
+            PauseMenuChoice::PhotoMode => self.next_state.set(GameState::PhotoMode),
+            PauseMenuChoice::HotReload => toggle_hot_reload(&mut self.hot_reload),
+            PauseMenuChoice::RestartMap | PauseMenuChoice::ReturnToTitle => {
+                if progress_at_risk(self.timer.as_deref()) {
+                    self.commands
+                        .insert_resource(PendingConfirmation::new(choice));
+                } else {
+                    self.leave_game(choice);
+                }
+            }
+            PauseMenuChoice::Quit => {
+                self.exit.write(AppExit::Success);
+            }
+        }
+    }
+
+    /// Closes the menu into the loading or title screen, which despawn the
+    /// game world as they open
+    fn leave_game(&mut self, choice: PauseMenuChoice) {
+        let Some(state) = choice.leaves_game_for() else {
+            return;
+        };
+        info!("Leaving the game from the pause menu: {:?}", choice);
+        self.next_state.set(state);
+    }
+}
+
 /// Handles ESC key or B button to resume game from pause menu
 pub fn pause_menu_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
@@ -167,16 +218,13 @@ pub fn pause_menu_input(
 }
 
 /// Handles keyboard and gamepad navigation for the pause menu
-#[allow(clippy::too_many_arguments)]
 pub fn keyboard_navigation(
-    mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     active_gamepad: Res<ActiveGamepad>,
     gamepad_query: Query<&Gamepad>,
     settings: Res<GamepadSettings>,
     mut selected: ResMut<SelectedPauseMenuIndex>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut exit: MessageWriter<AppExit>,
+    mut actions: PauseMenuActions,
 ) {
     // Get gamepad input
     let (gp_up, gp_down, gp_select, _gp_back) =
@@ -194,113 +242,242 @@ pub fn keyboard_navigation(
 
     // Select option with Enter or A button
     if keyboard_input.just_pressed(KeyCode::Enter) || gp_select {
-        match selected.index {
-            0 => {
-                // Resume
-                next_state.set(GameState::InGame);
-            }
-            1 => {
-                // Settings
-                commands.insert_resource(SettingsOrigin::Paused);
-                next_state.set(GameState::Settings);
-            }
-            2 => {
-                // Quit
-                exit.write(AppExit::Success);
-            }
-            _ => {}
+        if let Some(choice) = PauseMenuChoice::from_index(selected.index) {
+            actions.choose(choice);
         }
     }
 }
 
-/// Updates the visual appearance of buttons based on keyboard selection
 type PauseMenuButtonQueryItem<'a> = (
-    Option<&'a ResumeButton>,
-    Option<&'a SettingsButton>,
-    Option<&'a QuitButton>,
-    Mut<'a, BackgroundColor>,
     &'a Interaction,
+    Mut<'a, BackgroundColor>,
+    &'a PauseMenuButton,
 );
 
+/// Updates the visual appearance of buttons based on keyboard selection
 pub fn update_selected_button_visual(
     selected: Res<SelectedPauseMenuIndex>,
+    accessibility: Res<AccessibilitySettings>,
     mut button_query: Query<PauseMenuButtonQueryItem, With<Button>>,
 ) {
-    for (is_resume, is_settings, is_quit, mut bg_color, interaction) in &mut button_query {
-        // Determine button index
-        let button_index = if is_resume.is_some() {
-            Some(0)
-        } else if is_settings.is_some() {
-            Some(1)
-        } else if is_quit.is_some() {
-            Some(2)
-        } else {
-            None
-        };
-
-        if let Some(idx) = button_index {
-            // Only apply keyboard selection color if not being hovered/pressed by mouse
-            if *interaction == Interaction::None {
-                if idx == selected.index {
-                    *bg_color = HOVERED_BUTTON.into();
-                } else {
-                    *bg_color = NORMAL_BUTTON.into();
-                }
-            }
+    let theme = MenuTheme::new(accessibility.high_contrast);
+    for (interaction, mut bg_color, button) in &mut button_query {
+        // Only apply keyboard selection color if not being hovered/pressed by mouse
+        if *interaction == Interaction::None {
+            *bg_color = if button.0.index() == selected.index {
+                theme.button_hovered.into()
+            } else {
+                theme.button.into()
+            };
         }
     }
 }
 
-/// Handles button interaction for Resume, Settings, and Quit
-type PauseMenuButtonInteractionQueryItem<'a> = (
-    &'a Interaction,
-    Mut<'a, BackgroundColor>,
-    Option<&'a ResumeButton>,
-    Option<&'a SettingsButton>,
-    Option<&'a QuitButton>,
-);
-
+/// Handles mouse interaction with the pause menu buttons
 pub fn pause_menu_button_interaction(
-    mut commands: Commands,
-    mut interaction_query: Query<
-        PauseMenuButtonInteractionQueryItem,
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut interaction_query: Query<PauseMenuButtonQueryItem, (Changed<Interaction>, With<Button>)>,
     mut selected: ResMut<SelectedPauseMenuIndex>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut exit: MessageWriter<AppExit>,
+    mut actions: PauseMenuActions,
+    accessibility: Res<AccessibilitySettings>,
 ) {
-    for (interaction, mut color, is_resume, is_settings, is_quit) in &mut interaction_query {
+    let theme = MenuTheme::new(accessibility.high_contrast);
+    for (interaction, mut color, button) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
-                *color = PRESSED_BUTTON.into();
-                if is_resume.is_some() {
-                    next_state.set(GameState::InGame);
-                } else if is_settings.is_some() {
-                    commands.insert_resource(SettingsOrigin::Paused);
-                    next_state.set(GameState::Settings);
-                } else if is_quit.is_some() {
-                    exit.write(AppExit::Success);
-                }
+                *color = theme.button_pressed.into();
+                actions.choose(button.0);
             }
             Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
+                *color = theme.button_hovered.into();
                 // Update selected index when hovering
-                if is_resume.is_some() {
-                    selected.index = 0;
-                } else if is_settings.is_some() {
-                    selected.index = 1;
-                } else if is_quit.is_some() {
-                    selected.index = 2;
-                }
+                selected.index = button.0.index();
             }
             Interaction::None => {
-                *color = NORMAL_BUTTON.into();
+                *color = theme.button.into();
             }
         }
     }
 }
 
+/// Spawns the dialog asking to confirm a pending choice, over the menu
+pub fn spawn_confirm_dialog(
+    mut commands: Commands,
+    pending: Res<PendingConfirmation>,
+    localization: Res<Localization>,
+    accessibility: Res<AccessibilitySettings>,
+) {
+    let Some(question) = pending.choice.confirm_key() else {
+        return;
+    };
+    let theme = MenuTheme::new(accessibility.high_contrast);
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                position_type: PositionType::Absolute,
+                row_gap: Val::Vh(4.0),
+                ..default()
+            },
+            BackgroundColor(theme.backdrop),
+            // Keeps the mouse off the menu underneath
+            FocusPolicy::Block,
+            GlobalZIndex(10),
+            ConfirmDialogRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(localization.get(question)),
+                TextFont {
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(theme.text),
+                TextLayout::new_with_justify(Justify::Center),
+                ScalableText::new(40.0, 1.0),
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Vw(2.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    let confirm = localization.get(pending.choice.label_key()).to_string();
+                    spawn_button(parent, &theme, confirm, ConfirmButton(true), ());
+                    let cancel = localization.get("pause.cancel").to_string();
+                    spawn_button(parent, &theme, cancel, ConfirmButton(false), ());
+                });
+        });
+}
+
+/// Closes the confirmation dialog, back to the menu
+fn close_confirm_dialog(commands: &mut Commands, dialogs: &Query<Entity, With<ConfirmDialogRoot>>) {
+    for entity in dialogs {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<PendingConfirmation>();
+}
+
+/// Goes ahead with the pending choice, or cancels it
+fn answer_confirm_dialog(
+    confirmed: bool,
+    choice: PauseMenuChoice,
+    dialogs: &Query<Entity, With<ConfirmDialogRoot>>,
+    actions: &mut PauseMenuActions,
+) {
+    close_confirm_dialog(&mut actions.commands, dialogs);
+    if confirmed {
+        actions.leave_game(choice);
+    }
+}
+
+/// Handles keyboard and gamepad input on the confirmation dialog. ESC or
+/// B cancels.
+pub fn confirm_dialog_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepad_query: Query<&Gamepad>,
+    settings: Res<GamepadSettings>,
+    mut pending: ResMut<PendingConfirmation>,
+    dialogs: Query<Entity, With<ConfirmDialogRoot>>,
+    mut actions: PauseMenuActions,
+) {
+    // The key that opened the dialog is still down this frame
+    if pending.is_added() {
+        return;
+    }
+    let (gp_up, gp_down, gp_select, gp_back) =
+        get_menu_gamepad_input(&active_gamepad, &gamepad_query, &settings);
+
+    if keyboard_input.any_just_pressed([
+        KeyCode::ArrowLeft,
+        KeyCode::ArrowRight,
+        KeyCode::ArrowUp,
+        KeyCode::ArrowDown,
+    ]) || gp_up
+        || gp_down
+    {
+        pending.confirm_selected = !pending.confirm_selected;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) || gp_back {
+        close_confirm_dialog(&mut actions.commands, &dialogs);
+    } else if keyboard_input.just_pressed(KeyCode::Enter) || gp_select {
+        answer_confirm_dialog(
+            pending.confirm_selected,
+            pending.choice,
+            &dialogs,
+            &mut actions,
+        );
+    }
+}
+
+type ConfirmButtonQueryItem<'a> = (&'a Interaction, Mut<'a, BackgroundColor>, &'a ConfirmButton);
+
+/// Handles mouse interaction with the confirmation dialog buttons
+pub fn confirm_button_interaction(
+    mut interaction_query: Query<ConfirmButtonQueryItem, (Changed<Interaction>, With<Button>)>,
+    mut pending: ResMut<PendingConfirmation>,
+    dialogs: Query<Entity, With<ConfirmDialogRoot>>,
+    mut actions: PauseMenuActions,
+    accessibility: Res<AccessibilitySettings>,
+) {
+    let theme = MenuTheme::new(accessibility.high_contrast);
+    for (interaction, mut color, button) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = theme.button_pressed.into();
+                answer_confirm_dialog(button.0, pending.choice, &dialogs, &mut actions);
+            }
+            Interaction::Hovered => {
+                *color = theme.button_hovered.into();
+                pending.confirm_selected = button.0;
+            }
+            Interaction::None => {
+                *color = theme.button.into();
+            }
+        }
+    }
+}
+
+/// Highlights the selected confirmation dialog button
+pub fn update_confirm_button_visual(
+    pending: Res<PendingConfirmation>,
+    accessibility: Res<AccessibilitySettings>,
+    mut button_query: Query<ConfirmButtonQueryItem, With<Button>>,
+) {
+    let theme = MenuTheme::new(accessibility.high_contrast);
+    for (interaction, mut bg_color, button) in &mut button_query {
+        if *interaction == Interaction::None {
+            *bg_color = if button.0 == pending.confirm_selected {
+                theme.button_hovered.into()
+            } else {
+                theme.button.into()
+            };
+        }
+    }
+}
+
+/// Refreshes the Hot Reload toggle label after the setting changes
+pub fn update_hot_reload_button_text(
+    hot_reload: Res<HotReloadState>,
+    localization: Res<Localization>,
+    mut text_query: Query<&mut Text, With<HotReloadToggleText>>,
+) {
+    if !hot_reload.is_changed() && !localization.is_changed() {
+        return;
+    }
+    for mut text in &mut text_query {
+        text.0 = hot_reload_label(hot_reload.enabled, &localization);
+    }
+}
+
 /// Scales text elements based on window size to maintain proportions
 pub fn scale_text_on_resize(
     mut resize_events: MessageReader<WindowResized>,
@@ -315,10 +492,15 @@ pub fn scale_text_on_resize(
     }
 }
 
-/// Cleans up the pause menu UI
-pub fn cleanup_pause_menu(mut commands: Commands, root_query: Query<Entity, With<PauseMenuRoot>>) {
+/// Cleans up the pause menu UI and any open confirmation dialog
+#[allow(clippy::type_complexity)]
+pub fn cleanup_pause_menu(
+    mut commands: Commands,
+    root_query: Query<Entity, Or<(With<PauseMenuRoot>, With<ConfirmDialogRoot>)>>,
+) {
     for entity in &root_query {
         commands.entity(entity).despawn();
     }
     commands.remove_resource::<SelectedPauseMenuIndex>();
+    commands.remove_resource::<PendingConfirmation>();
 }
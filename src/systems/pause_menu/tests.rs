@@ -0,0 +1,40 @@
+use super::resources::{progress_at_risk, PauseMenuChoice, SelectedPauseMenuIndex};
+use crate::states::GameState;
+use crate::systems::game::speedrun::{SpeedrunTimer, TimerPhase};
+
+#[test]
+fn choices_round_trip_through_their_index() {
+    for (index, choice) in PauseMenuChoice::ALL.into_iter().enumerate() {
+        assert_eq!(choice.index(), index);
+        assert_eq!(PauseMenuChoice::from_index(index), Some(choice));
+    }
+    assert_eq!(
+        SelectedPauseMenuIndex::default().total,
+        PauseMenuChoice::ALL.len()
+    );
+}
+
+#[test]
+fn only_restart_and_return_leave_the_game() {
+    assert_eq!(
+        PauseMenuChoice::RestartMap.leaves_game_for(),
+        Some(GameState::LoadingMap)
+    );
+    assert_eq!(
+        PauseMenuChoice::ReturnToTitle.leaves_game_for(),
+        Some(GameState::TitleScreen)
+    );
+    assert_eq!(PauseMenuChoice::Resume.leaves_game_for(), None);
+    assert_eq!(PauseMenuChoice::Quit.confirm_key(), None);
+}
+
+#[test]
+fn only_a_running_timer_puts_progress_at_risk() {
+    let mut timer = SpeedrunTimer::new("test");
+    assert!(!progress_at_risk(None));
+    assert!(!progress_at_risk(Some(&timer)));
+    timer.phase = TimerPhase::Running;
+    assert!(progress_at_risk(Some(&timer)));
+    timer.phase = TimerPhase::Finished;
+    assert!(!progress_at_risk(Some(&timer)));
+}
@@ -0,0 +1,112 @@
+//! Assets loaded while the intro and title screen are up, so the first
+//! map load has less to do.
+//!
+//! At startup the character model starts loading, the chunk material is
+//! created and the default map's header is read on the async compute pool.
+//! [`PreloadedAssets`] holds strong handles for the rest of the session, so
+//! later loads of the same paths find the assets already in memory, and the
+//! loading screen takes the default map's header from here instead of
+//! reading the file again.
+
+use bevy::prelude::*;
+use bevy::tasks::{futures::check_ready, AsyncComputeTaskPool, Task};
+
+use crate::systems::game::map::format::MapMetadata;
+use crate::systems::game::map::spawner::load_character_scene;
+use crate::systems::game::map::MapLoader;
+
+/// Map played when none is given on the command line.
+pub const DEFAULT_MAP_PATH: &str = "assets/maps/default.ron";
+
+/// Assets loaded ahead of the first map.
+#[derive(Resource)]
+pub struct PreloadedAssets {
+    /// Player and NPC model; only held so the model stays loaded, since
+    /// loading the same path again returns this handle
+    #[allow(dead_code)]
+    pub character_scene: Handle<Scene>,
+    /// Vertex-colored material for voxel chunks drawn without occlusion
+    pub chunk_material: Handle<StandardMaterial>,
+    /// Header of [`DEFAULT_MAP_PATH`], once read
+    pub default_map_header: Option<MapMetadata>,
+    header_task: Option<Task<Option<MapMetadata>>>,
+}
+
+impl PreloadedAssets {
+    /// The preloaded header for the map at `path`, if that is the default
+    /// map and its header has been read.
+    pub fn header_for(&self, path: &str) -> Option<&MapMetadata> {
+        if path == DEFAULT_MAP_PATH {
+            self.default_map_header.as_ref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Material voxel chunks are drawn with when occlusion is off; vertex colors
+/// carry the voxel colors.
+pub fn chunk_standard_material() -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::WHITE,
+        perceptual_roughness: 0.9,
+        metallic: 0.0,
+        reflectance: 0.1,
+        ..default()
+    }
+}
+
+/// Start loading the character model and reading the default map header.
+pub fn start_preload(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let header_task = AsyncComputeTaskPool::get().spawn(async move {
+        match MapLoader::read_metadata(DEFAULT_MAP_PATH) {
+            Ok(metadata) => Some(metadata),
+            Err(e) => {
+                warn!(
+                    "Could not preload map header from '{}': {}",
+                    DEFAULT_MAP_PATH, e
+                );
+                None
+            }
+        }
+    });
+    commands.insert_resource(PreloadedAssets {
+        character_scene: load_character_scene(&asset_server),
+        chunk_material: materials.add(chunk_standard_material()),
+        default_map_header: None,
+        header_task: Some(header_task),
+    });
+}
+
+/// Collect the default map header once it has been read.
+pub fn poll_preload(mut preloaded: ResMut<PreloadedAssets>) {
+    let Some(task) = preloaded.header_task.as_mut() else {
+        return;
+    };
+    let Some(header) = check_ready(task) else {
+        return;
+    };
+    preloaded.header_task = None;
+    preloaded.default_map_header = header;
+}
+
+fn header_pending(preloaded: Option<Res<PreloadedAssets>>) -> bool {
+    preloaded.is_some_and(|preloaded| preloaded.header_task.is_some())
+}
+
+/// Plugin that preloads assets at startup.
+pub struct PreloadPlugin;
+
+impl Plugin for PreloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_preload)
+            .add_systems(Update, poll_preload.run_if(header_pending));
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn header_is_only_used_for_the_default_map() {
+    let preloaded = PreloadedAssets {
+        character_scene: Handle::default(),
+        chunk_material: Handle::default(),
+        default_map_header: Some(MapLoader::load_default().metadata),
+        header_task: None,
+    };
+    assert!(preloaded.header_for(DEFAULT_MAP_PATH).is_some());
+    assert!(preloaded
+        .header_for("assets/maps/village_64x64.ron")
+        .is_none());
+}
+
+#[test]
+fn default_map_header_reads() {
+    assert!(MapLoader::read_metadata(DEFAULT_MAP_PATH).is_ok());
+}
@@ -0,0 +1,14 @@
+use super::resources::ResultsChoice;
+use bevy::prelude::*;
+
+/// Marker for the root node of the results screen UI
+#[derive(Component)]
+pub struct ResultsRoot;
+
+/// A results screen button and the choice it makes
+#[derive(Component)]
+pub struct ResultsButton {
+    /// Position in `SelectedResultsIndex::choices`
+    pub index: usize,
+    pub choice: ResultsChoice,
+}
@@ -0,0 +1,63 @@
+use crate::states::GameState;
+use bevy::prelude::*;
+
+/// Resource to track the selected menu index for keyboard navigation
+#[derive(Resource)]
+pub struct SelectedResultsIndex {
+    pub index: usize,
+    /// Choices on the screen, top to bottom
+    pub choices: Vec<ResultsChoice>,
+}
+
+impl SelectedResultsIndex {
+    pub fn new(choices: Vec<ResultsChoice>) -> Self {
+        Self { index: 0, choices }
+    }
+
+    /// The highlighted choice
+    pub fn selected(&self) -> Option<ResultsChoice> {
+        self.choices.get(self.index).copied()
+    }
+}
+
+/// Options on the results screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResultsChoice {
+    /// Load the map the goal leads to
+    NextMap,
+    /// Reload the map for another run
+    Retry,
+    /// Return to the title screen
+    QuitToTitle,
+}
+
+impl ResultsChoice {
+    /// Choices offered after a run: Next Map first when the goal leads
+    /// somewhere, then Retry and Quit to Title
+    pub fn menu(has_next_map: bool) -> Vec<Self> {
+        let mut choices = Vec::with_capacity(3);
+        if has_next_map {
+            choices.push(Self::NextMap);
+        }
+        choices.extend([Self::Retry, Self::QuitToTitle]);
+        choices
+    }
+
+    /// Localization key of the button label
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Self::NextMap => "results.next_map",
+            Self::Retry => "results.retry",
+            Self::QuitToTitle => "results.quit_to_title",
+        }
+    }
+
+    /// State to enter for this choice. Next Map and Retry go back through
+    /// the loading screen, which resets the timer.
+    pub fn next_state(self) -> GameState {
+        match self {
+            Self::NextMap | Self::Retry => GameState::LoadingMap,
+            Self::QuitToTitle => GameState::TitleScreen,
+        }
+    }
+}
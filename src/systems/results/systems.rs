@@ -0,0 +1,283 @@
+use super::components::{ResultsButton, ResultsRoot};
+use super::resources::{ResultsChoice, SelectedResultsIndex};
+use crate::states::GameState;
+use crate::systems::game::gamepad::{get_menu_gamepad_input, ActiveGamepad, GamepadSettings};
+use crate::systems::game::speedrun::{format_difference, format_time, NextMapRequest, RunResult};
+use crate::systems::localization::Localization;
+use crate::systems::pause_menu::components::ScalableText;
+use bevy::prelude::*;
+
+const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.15, 0.0);
+const HOVERED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.3);
+const PRESSED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.5);
+const TEXT_COLOR: Color = Color::srgba(0.9, 0.9, 0.9, 1.0);
+const NEW_BEST_COLOR: Color = Color::srgba(1.0, 0.8, 0.2, 1.0);
+
+/// Spawns a menu button with a localized label
+fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, marker: ResultsButton) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Vw(20.0),
+                height: Val::Vh(8.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 30.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+                ScalableText::new(30.0, 1.0),
+            ));
+        });
+}
+
+/// Lines under the run's time: a new best, or the best it missed.
+fn comparison_lines(result: &RunResult, localization: &Localization) -> Vec<(String, Color)> {
+    match result.previous_best {
+        Some(best) if result.is_new_best() => {
+            let time = format_time(best);
+            vec![
+                (
+                    localization.get("results.new_best").to_string(),
+                    NEW_BEST_COLOR,
+                ),
+                (
+                    localization.format("results.previous_best", &[("time", &time)]),
+                    TEXT_COLOR,
+                ),
+            ]
+        }
+        Some(best) => {
+            let time = format_time(best);
+            let difference = format_difference(result.time, best);
+            vec![(
+                localization.format(
+                    "results.best",
+                    &[("time", &time), ("difference", &difference)],
+                ),
+                TEXT_COLOR,
+            )]
+        }
+        None => vec![(
+            localization.get("results.new_best").to_string(),
+            NEW_BEST_COLOR,
+        )],
+    }
+}
+
+/// Spawns the results screen over the frozen game world
+pub fn setup_results_screen(
+    mut commands: Commands,
+    result: Option<Res<RunResult>>,
+    localization: Res<Localization>,
+) {
+    let has_next_map = result.as_ref().is_some_and(|r| r.next_map.is_some());
+    let choices = ResultsChoice::menu(has_next_map);
+    commands.insert_resource(SelectedResultsIndex::new(choices.clone()));
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.1, 0.05, 0.7)),
+            ResultsRoot,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new(localization.get("results.title")),
+                TextFont {
+                    font_size: 80.0,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.4, 0.95, 0.5, 1.0)),
+                Node {
+                    margin: UiRect::all(Val::Vw(3.0)),
+                    ..default()
+                },
+                ScalableText::new(80.0, 1.0),
+            ));
+
+            if let Some(result) = &result {
+                let time = format_time(result.time);
+                parent.spawn((
+                    Text::new(localization.format("results.time", &[("time", &time)])),
+                    TextFont {
+                        font_size: 44.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_COLOR),
+                    ScalableText::new(44.0, 1.0),
+                ));
+                for (line, color) in comparison_lines(result, &localization) {
+                    parent.spawn((
+                        Text::new(line),
+                        TextFont {
+                            font_size: 26.0,
+                            ..default()
+                        },
+                        TextColor(color),
+                        ScalableText::new(26.0, 1.0),
+                    ));
+                }
+                if result.collectibles > 0 {
+                    let collected = result.collected.to_string();
+                    let total = result.collectibles.to_string();
+                    parent.spawn((
+                        Text::new(localization.format(
+                            "results.collectibles",
+                            &[("collected", &collected), ("total", &total)],
+                        )),
+                        TextFont {
+                            font_size: 26.0,
+                            ..default()
+                        },
+                        TextColor(TEXT_COLOR),
+                        ScalableText::new(26.0, 1.0),
+                    ));
+                }
+            }
+
+            // Button container
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Vh(2.0),
+                        margin: UiRect::top(Val::Vh(5.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                ))
+                .with_children(|parent| {
+                    for (index, choice) in choices.into_iter().enumerate() {
+                        spawn_button(
+                            parent,
+                            localization.get(choice.label_key()),
+                            ResultsButton { index, choice },
+                        );
+                    }
+                });
+        });
+}
+
+/// Carries out a results screen choice
+fn choose(
+    choice: ResultsChoice,
+    result: Option<&RunResult>,
+    next_map: &mut NextMapRequest,
+    next_state: &mut NextState<GameState>,
+) {
+    if choice == ResultsChoice::NextMap {
+        next_map.0 = result.and_then(|r| r.next_map.clone());
+    }
+    next_state.set(choice.next_state());
+}
+
+/// Handles keyboard and gamepad navigation for the results screen
+#[allow(clippy::too_many_arguments)]
+pub fn results_navigation(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepad_query: Query<&Gamepad>,
+    settings: Res<GamepadSettings>,
+    result: Option<Res<RunResult>>,
+    mut selected: ResMut<SelectedResultsIndex>,
+    mut next_map: ResMut<NextMapRequest>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (gp_up, gp_down, gp_select, _gp_back) =
+        get_menu_gamepad_input(&active_gamepad, &gamepad_query, &settings);
+
+    if (keyboard_input.just_pressed(KeyCode::ArrowUp) || gp_up) && selected.index > 0 {
+        selected.index -= 1;
+    }
+    if (keyboard_input.just_pressed(KeyCode::ArrowDown) || gp_down)
+        && selected.index + 1 < selected.choices.len()
+    {
+        selected.index += 1;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Enter) || gp_select {
+        if let Some(choice) = selected.selected() {
+            choose(choice, result.as_deref(), &mut next_map, &mut next_state);
+        }
+    }
+}
+
+type ResultsButtonQueryItem<'a> = (&'a Interaction, Mut<'a, BackgroundColor>, &'a ResultsButton);
+
+/// Updates the visual appearance of buttons based on keyboard selection
+pub fn update_selected_button_visual(
+    selected: Res<SelectedResultsIndex>,
+    mut button_query: Query<ResultsButtonQueryItem, With<Button>>,
+) {
+    for (interaction, mut bg_color, button) in &mut button_query {
+        // Only apply keyboard selection color if not being hovered/pressed by mouse
+        if *interaction == Interaction::None {
+            *bg_color = if button.index == selected.index {
+                HOVERED_BUTTON.into()
+            } else {
+                NORMAL_BUTTON.into()
+            };
+        }
+    }
+}
+
+/// Handles mouse interaction with the results buttons
+pub fn results_button_interaction(
+    mut interaction_query: Query<ResultsButtonQueryItem, (Changed<Interaction>, With<Button>)>,
+    result: Option<Res<RunResult>>,
+    mut selected: ResMut<SelectedResultsIndex>,
+    mut next_map: ResMut<NextMapRequest>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color, button) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                choose(
+                    button.choice,
+                    result.as_deref(),
+                    &mut next_map,
+                    &mut next_state,
+                );
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+                selected.index = button.index;
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Cleans up the results screen. Every choice leaves the game, and the
+/// world behind the screen is despawned on entering the next state.
+pub fn cleanup_results(mut commands: Commands, root_query: Query<Entity, With<ResultsRoot>>) {
+    for entity in &root_query {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<SelectedResultsIndex>();
+    commands.remove_resource::<RunResult>();
+}
@@ -14,6 +14,9 @@ pub struct SettingRow {
 /// Identifies which config field a settings row controls.
 #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum SettingId {
+    // General
+    Language,
+    SpeedrunTimer,
     // OcclusionConfig fields
     Enabled,
     Technique,
@@ -29,12 +32,33 @@ pub enum SettingId {
     // VsyncConfig fields
     VsyncEnabled,
     VsyncMultiplier,
+    // DynamicQualitySettings
+    DynamicQuality,
+    // RenderScaleSettings
+    RenderScale,
+    Sharpen,
+    // BloomSettings
+    Bloom,
+    // HudLayout
+    HudScale,
+    SafeArea,
+    HealthAnchor,
+    TimerAnchor,
+    // AccessibilitySettings
+    ColorPalette,
+    HighContrast,
+    TextScale,
+    Captions,
 }
 
 /// Marks the text node that displays the current value of a setting row.
 #[derive(Component)]
 pub struct SettingValueDisplay;
 
+/// Marker for the scrolling container holding the settings rows.
+#[derive(Component)]
+pub struct SettingsList;
+
 /// Marker for the Back button.
 #[derive(Component)]
 pub struct BackButton;
@@ -2,8 +2,9 @@
 //!
 //! Provides `SettingsPlugin` which registers the settings menu for `GameState::Settings`.
 //! Accessible from both the title screen and the pause menu.
-//! All `OcclusionConfig` and `VsyncConfig` fields are exposed with live-apply controls.
-//! Settings are saved to `settings.ron` on exit and loaded on startup.
+//! All `OcclusionConfig` and `VsyncConfig` fields, plus the UI language and the speedrun
+//! timer, are exposed with live-apply controls. Settings are saved to `settings.ron` on exit
+//! and loaded on startup.
 
 mod components;
 pub mod resources;
@@ -14,7 +15,9 @@ pub mod vsync;
 // future UI code that will build on the settings screen components/resources.
 // Nothing in the current codebase imports them yet, but they are stable identifiers.
 #[allow(unused_imports)]
-pub use components::{BackButton, SettingId, SettingRow, SettingValueDisplay, SettingsMenuRoot};
+pub use components::{
+    BackButton, SettingId, SettingRow, SettingValueDisplay, SettingsList, SettingsMenuRoot,
+};
 #[allow(unused_imports)]
 pub use resources::{SelectedSettingsIndex, SettingsOrigin};
 pub use vsync::{MonitorInfo, VsyncConfig};
@@ -22,8 +25,8 @@ pub use vsync::{MonitorInfo, VsyncConfig};
 use crate::states::GameState;
 use bevy::prelude::*;
 use systems::{
-    cleanup_settings_menu, load_settings, save_settings, settings_back_button, settings_input,
-    setup_settings_menu, update_settings_visual,
+    cleanup_settings_menu, load_settings, save_settings, scroll_to_selected_setting,
+    settings_back_button, settings_input, setup_settings_menu, update_settings_visual,
 };
 use vsync::{apply_vsync_system, detect_monitor_refresh_system};
 
@@ -44,7 +47,12 @@ impl Plugin for SettingsPlugin {
             )
             .add_systems(
                 Update,
-                (settings_input, update_settings_visual, settings_back_button)
+                (
+                    settings_input,
+                    update_settings_visual,
+                    scroll_to_selected_setting,
+                    settings_back_button,
+                )
                     .chain()
                     .run_if(in_state(GameState::Settings)),
             );
@@ -1,11 +1,22 @@
-use super::components::{BackButton, SettingId, SettingRow, SettingValueDisplay, SettingsMenuRoot};
+use super::components::{
+    BackButton, SettingId, SettingRow, SettingValueDisplay, SettingsList, SettingsMenuRoot,
+};
 use super::resources::{SelectedSettingsIndex, SettingsOrigin};
 use super::vsync::VsyncConfig;
 use crate::states::GameState;
+use crate::systems::accessibility::{
+    AccessibilitySettings, ColorPalette, MAX_TEXT_SCALE, MIN_TEXT_SCALE,
+};
+use crate::systems::game::bloom::{BloomSettings, MAX_BLOOM_INTENSITY};
+use crate::systems::game::dynamic_quality::{DynamicQualitySettings, QualityMode};
 use crate::systems::game::gamepad::{get_menu_gamepad_input, ActiveGamepad, GamepadSettings};
+use crate::systems::game::hud_layout::{HudAnchor, HudLayout};
 use crate::systems::game::occlusion::{
     OcclusionConfig, OcclusionMode, ShadowQuality, TransparencyTechnique,
 };
+use crate::systems::game::render_scale::{RenderScaleSettings, MAX_RENDER_SCALE, MIN_RENDER_SCALE};
+use crate::systems::game::speedrun::SpeedrunSettings;
+use crate::systems::localization::{Language, Localization, LocalizedText};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -16,25 +27,60 @@ const VALUE_COLOR: Color = Color::srgba(1.0, 0.8, 0.2, 1.0);
 const BACK_NORMAL: Color = Color::srgba(0.15, 0.15, 0.15, 0.0);
 const BACK_HOVERED: Color = Color::srgba(1.0, 0.8, 0.2, 0.3);
 
+/// Settings rows in display order, paired with the localization key of their label.
 const ALL_SETTINGS: &[(SettingId, &str)] = &[
-    (SettingId::Enabled, "Enable Occlusion"),
-    (SettingId::Technique, "Transparency"),
-    (SettingId::Mode, "Occlusion Mode"),
-    (SettingId::MinAlpha, "Min Alpha"),
-    (SettingId::ShadowQuality, "Shadow Quality"),
-    (SettingId::ShowDebug, "Debug Visualization"),
-    (SettingId::OcclusionRadius, "Occlusion Radius"),
-    (SettingId::HeightThreshold, "Height Threshold"),
-    (SettingId::FalloffSoftness, "Falloff Softness"),
-    (SettingId::InteriorHeight, "Interior Height"),
-    (SettingId::RegionUpdateInterval, "Region Update Rate"),
+    (SettingId::Language, "settings.language"),
+    (SettingId::SpeedrunTimer, "settings.speedrun_timer"),
+    (SettingId::Enabled, "settings.enabled"),
+    (SettingId::Technique, "settings.technique"),
+    (SettingId::Mode, "settings.mode"),
+    (SettingId::MinAlpha, "settings.min_alpha"),
+    (SettingId::ShadowQuality, "settings.shadow_quality"),
+    (SettingId::ShowDebug, "settings.show_debug"),
+    (SettingId::OcclusionRadius, "settings.occlusion_radius"),
+    (SettingId::HeightThreshold, "settings.height_threshold"),
+    (SettingId::FalloffSoftness, "settings.falloff_softness"),
+    (SettingId::InteriorHeight, "settings.interior_height"),
+    (
+        SettingId::RegionUpdateInterval,
+        "settings.region_update_interval",
+    ),
     // Display settings
-    (SettingId::VsyncEnabled, "VSync"),
-    (SettingId::VsyncMultiplier, "VSync Multiplier"),
+    (SettingId::VsyncEnabled, "settings.vsync"),
+    (SettingId::VsyncMultiplier, "settings.vsync_multiplier"),
+    (SettingId::DynamicQuality, "settings.dynamic_quality"),
+    (SettingId::RenderScale, "settings.render_scale"),
+    (SettingId::Sharpen, "settings.sharpen"),
+    (SettingId::Bloom, "settings.bloom"),
+    // HUD
+    (SettingId::HudScale, "settings.hud_scale"),
+    (SettingId::SafeArea, "settings.safe_area"),
+    (SettingId::HealthAnchor, "settings.health_anchor"),
+    (SettingId::TimerAnchor, "settings.timer_anchor"),
+    // Accessibility
+    (SettingId::ColorPalette, "settings.color_palette"),
+    (SettingId::HighContrast, "settings.high_contrast"),
+    (SettingId::TextScale, "settings.text_scale"),
+    (SettingId::Captions, "settings.captions"),
 ];
 
-fn format_value(id: SettingId, config: &OcclusionConfig, vsync: &VsyncConfig) -> String {
+#[allow(clippy::too_many_arguments)]
+fn format_value(
+    id: SettingId,
+    config: &OcclusionConfig,
+    vsync: &VsyncConfig,
+    speedrun: &SpeedrunSettings,
+    quality: &DynamicQualitySettings,
+    render_scale: &RenderScaleSettings,
+    bloom: &BloomSettings,
+    accessibility: &AccessibilitySettings,
+    hud_layout: &HudLayout,
+    localization: &Localization,
+) -> String {
+    let bool_label = |v: bool| bool_label(v, localization);
     match id {
+        SettingId::Language => localization.language().native_name().to_string(),
+        SettingId::SpeedrunTimer => bool_label(speedrun.show_timer),
         SettingId::Enabled => bool_label(config.enabled),
         SettingId::Technique => match config.technique {
             TransparencyTechnique::Dithered => "Dithered".to_string(),
@@ -51,6 +97,7 @@ fn format_value(id: SettingId, config: &OcclusionConfig, vsync: &VsyncConfig) ->
             ShadowQuality::None => "Off".to_string(),
             ShadowQuality::CharactersOnly => "Characters".to_string(),
             ShadowQuality::Low => "Low".to_string(),
+            ShadowQuality::Medium => "Medium".to_string(),
             ShadowQuality::High => "High".to_string(),
         },
         SettingId::ShowDebug => bool_label(config.show_debug),
@@ -68,20 +115,70 @@ fn format_value(id: SettingId, config: &OcclusionConfig, vsync: &VsyncConfig) ->
                 format!("{:.2}×", vsync.vsync_multiplier)
             }
         }
+        SettingId::DynamicQuality => match quality.mode {
+            QualityMode::Off => "Off".to_string(),
+            QualityMode::Quality => "Quality".to_string(),
+            QualityMode::Balanced => "Balanced".to_string(),
+            QualityMode::Performance => "Performance".to_string(),
+        },
+        SettingId::RenderScale => format!("{}%", render_scale.percent),
+        SettingId::Sharpen => bool_label(render_scale.sharpen),
+        SettingId::Bloom if !bloom.is_enabled() => bool_label(false),
+        SettingId::Bloom => format!("{:.2}", bloom.intensity),
+        SettingId::HudScale => format!("{:.0}%", hud_layout.scale * 100.0),
+        SettingId::SafeArea => format!("{}%", hud_layout.safe_area),
+        SettingId::HealthAnchor => anchor_label(hud_layout.health_anchor),
+        SettingId::TimerAnchor => anchor_label(hud_layout.timer_anchor),
+        SettingId::ColorPalette => match accessibility.palette {
+            ColorPalette::Standard => "Standard".to_string(),
+            ColorPalette::Deuteranopia => "Deuteranopia".to_string(),
+            ColorPalette::Protanopia => "Protanopia".to_string(),
+        },
+        SettingId::HighContrast => bool_label(accessibility.high_contrast),
+        SettingId::TextScale => format!("{:.0}%", accessibility.text_scale * 100.0),
+        SettingId::Captions => bool_label(accessibility.captions),
     }
 }
 
-fn bool_label(v: bool) -> String {
+fn anchor_label(anchor: HudAnchor) -> String {
+    match anchor {
+        HudAnchor::TopLeft => "Top Left",
+        HudAnchor::TopRight => "Top Right",
+        HudAnchor::BottomLeft => "Bottom Left",
+        HudAnchor::BottomRight => "Bottom Right",
+    }
+    .to_string()
+}
+
+fn bool_label(v: bool, localization: &Localization) -> String {
     if v {
-        "On".to_string()
+        localization.get("settings.on").to_string()
     } else {
-        "Off".to_string()
+        localization.get("settings.off").to_string()
     }
 }
 
-/// Adjust OcclusionConfig or VsyncConfig field by delta (-1 = previous/decrease, +1 = next/increase).
-fn adjust_value(id: SettingId, config: &mut OcclusionConfig, vsync: &mut VsyncConfig, delta: i32) {
+/// Adjust a setting by delta (-1 = previous/decrease, +1 = next/increase).
+#[allow(clippy::too_many_arguments)]
+fn adjust_value(
+    id: SettingId,
+    config: &mut OcclusionConfig,
+    vsync: &mut VsyncConfig,
+    speedrun: &mut SpeedrunSettings,
+    quality: &mut DynamicQualitySettings,
+    render_scale: &mut RenderScaleSettings,
+    bloom: &mut BloomSettings,
+    accessibility: &mut AccessibilitySettings,
+    hud_layout: &mut HudLayout,
+    localization: &mut Localization,
+    delta: i32,
+) {
     match id {
+        SettingId::Language => {
+            let next = localization.language().cycled(delta);
+            localization.set_language(next);
+        }
+        SettingId::SpeedrunTimer => speedrun.show_timer = !speedrun.show_timer,
         SettingId::Enabled => config.enabled = !config.enabled,
         SettingId::Technique => {
             let variants = [
@@ -113,6 +210,7 @@ fn adjust_value(id: SettingId, config: &mut OcclusionConfig, vsync: &mut VsyncCo
                 ShadowQuality::None,
                 ShadowQuality::CharactersOnly,
                 ShadowQuality::Low,
+                ShadowQuality::Medium,
                 ShadowQuality::High,
             ];
             let cur = variants
@@ -161,6 +259,20 @@ fn adjust_value(id: SettingId, config: &mut OcclusionConfig, vsync: &mut VsyncCo
                 STEPS[(cur as i32 + delta).rem_euclid(STEPS.len() as i32) as usize];
             vsync.dirty = true;
         }
+        SettingId::DynamicQuality => quality.mode = quality.mode.cycled(delta),
+        SettingId::RenderScale => render_scale.step(delta),
+        SettingId::Sharpen => render_scale.sharpen = !render_scale.sharpen,
+        SettingId::Bloom => bloom.step(delta),
+        SettingId::HudScale => hud_layout.step_scale(delta),
+        SettingId::SafeArea => hud_layout.step_safe_area(delta),
+        SettingId::HealthAnchor => {
+            hud_layout.health_anchor = hud_layout.health_anchor.cycled(delta)
+        }
+        SettingId::TimerAnchor => hud_layout.timer_anchor = hud_layout.timer_anchor.cycled(delta),
+        SettingId::ColorPalette => accessibility.palette = accessibility.palette.cycled(delta),
+        SettingId::HighContrast => accessibility.high_contrast = !accessibility.high_contrast,
+        SettingId::TextScale => accessibility.step_text_scale(delta),
+        SettingId::Captions => accessibility.captions = !accessibility.captions,
     }
 }
 
@@ -173,10 +285,18 @@ fn round1(v: f32) -> f32 {
 }
 
 /// Spawns the settings screen UI.
+#[allow(clippy::too_many_arguments)]
 pub fn setup_settings_menu(
     mut commands: Commands,
     config: Res<OcclusionConfig>,
     vsync: Res<VsyncConfig>,
+    speedrun: Res<SpeedrunSettings>,
+    quality: Res<DynamicQualitySettings>,
+    render_scale: Res<RenderScaleSettings>,
+    bloom: Res<BloomSettings>,
+    accessibility: Res<AccessibilitySettings>,
+    hud_layout: Res<HudLayout>,
+    localization: Res<Localization>,
 ) {
     commands.insert_resource(SelectedSettingsIndex::default());
 
@@ -197,7 +317,8 @@ pub fn setup_settings_menu(
         .with_children(|parent| {
             // Title
             parent.spawn((
-                Text::new("Settings"),
+                Text::new(localization.get("settings.title")),
+                LocalizedText("settings.title"),
                 TextFont {
                     font_size: 60.0,
                     ..default()
@@ -216,15 +337,30 @@ pub fn setup_settings_menu(
                         flex_direction: FlexDirection::Column,
                         align_items: AlignItems::Stretch,
                         width: Val::Vw(50.0),
+                        max_height: Val::Vh(80.0),
                         row_gap: Val::Vh(0.5),
+                        overflow: Overflow::scroll_y(),
                         ..default()
                     },
                     BackgroundColor(Color::NONE),
+                    ScrollPosition::default(),
+                    SettingsList,
                 ))
                 .with_children(|parent| {
-                    for (i, &(id, label)) in ALL_SETTINGS.iter().enumerate() {
-                        let value_text = format_value(id, &config, &vsync);
-                        spawn_setting_row(parent, i, id, label, &value_text);
+                    for (i, &(id, label_key)) in ALL_SETTINGS.iter().enumerate() {
+                        let value_text = format_value(
+                            id,
+                            &config,
+                            &vsync,
+                            &speedrun,
+                            &quality,
+                            &render_scale,
+                            &bloom,
+                            &accessibility,
+                            &hud_layout,
+                            &localization,
+                        );
+                        spawn_setting_row(parent, i, id, label_key, &localization, &value_text);
                     }
 
                     // Back button (index = ALL_SETTINGS.len())
@@ -243,7 +379,8 @@ pub fn setup_settings_menu(
                         ))
                         .with_children(|parent| {
                             parent.spawn((
-                                Text::new("Back"),
+                                Text::new(localization.get("settings.back")),
+                                LocalizedText("settings.back"),
                                 TextFont {
                                     font_size: 26.0,
                                     ..default()
@@ -259,7 +396,8 @@ fn spawn_setting_row(
     parent: &mut ChildSpawnerCommands<'_>,
     index: usize,
     id: SettingId,
-    label: &str,
+    label_key: &'static str,
+    localization: &Localization,
     value: &str,
 ) {
     parent
@@ -277,7 +415,8 @@ fn spawn_setting_row(
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new(label.to_string()),
+                Text::new(localization.get(label_key)),
+                LocalizedText(label_key),
                 TextFont {
                     font_size: 22.0,
                     ..default()
@@ -345,6 +484,13 @@ pub fn settings_input(
     mut selected: ResMut<SelectedSettingsIndex>,
     mut config: ResMut<OcclusionConfig>,
     mut vsync: ResMut<VsyncConfig>,
+    mut speedrun: ResMut<SpeedrunSettings>,
+    mut quality: ResMut<DynamicQualitySettings>,
+    mut render_scale: ResMut<RenderScaleSettings>,
+    mut bloom: ResMut<BloomSettings>,
+    mut accessibility: ResMut<AccessibilitySettings>,
+    mut hud_layout: ResMut<HudLayout>,
+    mut localization: ResMut<Localization>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     let (gp_up, gp_down, gp_select, gp_back) =
@@ -363,10 +509,34 @@ pub fn settings_input(
     if selected.index < ALL_SETTINGS.len() {
         let (id, _) = ALL_SETTINGS[selected.index];
         if keyboard.just_pressed(KeyCode::ArrowLeft) {
-            adjust_value(id, &mut config, &mut vsync, -1);
+            adjust_value(
+                id,
+                &mut config,
+                &mut vsync,
+                &mut speedrun,
+                &mut quality,
+                &mut render_scale,
+                &mut bloom,
+                &mut accessibility,
+                &mut hud_layout,
+                &mut localization,
+                -1,
+            );
         }
         if keyboard.just_pressed(KeyCode::ArrowRight) {
-            adjust_value(id, &mut config, &mut vsync, 1);
+            adjust_value(
+                id,
+                &mut config,
+                &mut vsync,
+                &mut speedrun,
+                &mut quality,
+                &mut render_scale,
+                &mut bloom,
+                &mut accessibility,
+                &mut hud_layout,
+                &mut localization,
+                1,
+            );
         }
     }
 
@@ -376,7 +546,19 @@ pub fn settings_input(
             go_back(&origin, &mut next_state);
         } else {
             let (id, _) = ALL_SETTINGS[selected.index];
-            adjust_value(id, &mut config, &mut vsync, 1);
+            adjust_value(
+                id,
+                &mut config,
+                &mut vsync,
+                &mut speedrun,
+                &mut quality,
+                &mut render_scale,
+                &mut bloom,
+                &mut accessibility,
+                &mut hud_layout,
+                &mut localization,
+                1,
+            );
         }
     }
 
@@ -386,6 +568,37 @@ pub fn settings_input(
     }
 }
 
+/// Smallest scroll from `current` that shows row `index` of a list of
+/// `pitch`-tall rows in a view `visible` tall.
+fn scroll_to_show(index: usize, pitch: f32, visible: f32, current: f32) -> f32 {
+    let top = index as f32 * pitch;
+    let bottom = top + pitch;
+    if top < current {
+        top
+    } else if bottom > current + visible {
+        bottom - visible
+    } else {
+        current
+    }
+}
+
+/// Scrolls the settings list so the selected row stays in view.
+pub fn scroll_to_selected_setting(
+    selected: Res<SelectedSettingsIndex>,
+    mut list: Query<(&mut ScrollPosition, &ComputedNode), With<SettingsList>>,
+) {
+    let Ok((mut scroll, node)) = list.single_mut() else {
+        return;
+    };
+    // Rows (and the Back button) are all about the same height
+    let scale = node.inverse_scale_factor();
+    let pitch = node.content_size().y * scale / selected.total as f32;
+    let target = scroll_to_show(selected.index, pitch, node.size().y * scale, scroll.y);
+    if target != scroll.y {
+        scroll.y = target;
+    }
+}
+
 fn go_back(origin: &SettingsOrigin, next_state: &mut NextState<GameState>) {
     match origin {
         SettingsOrigin::TitleScreen => next_state.set(GameState::TitleScreen),
@@ -394,11 +607,19 @@ fn go_back(origin: &SettingsOrigin, next_state: &mut NextState<GameState>) {
 }
 
 /// Updates the visual appearance of rows (highlight selected) and value display text.
+#[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub fn update_settings_visual(
     selected: Res<SelectedSettingsIndex>,
     config: Res<OcclusionConfig>,
     vsync: Res<VsyncConfig>,
+    speedrun: Res<SpeedrunSettings>,
+    quality: Res<DynamicQualitySettings>,
+    render_scale: Res<RenderScaleSettings>,
+    bloom: Res<BloomSettings>,
+    accessibility: Res<AccessibilitySettings>,
+    hud_layout: Res<HudLayout>,
+    localization: Res<Localization>,
     mut row_query: Query<(&SettingRow, &mut BackgroundColor), Without<SettingValueDisplay>>,
     mut value_query: Query<(&SettingRow, &mut Text), With<SettingValueDisplay>>,
     mut back_query: Query<
@@ -416,9 +637,30 @@ pub fn update_settings_visual(
     }
 
     // Update value display text if config changed
-    if config.is_changed() || vsync.is_changed() || selected.is_changed() {
+    if config.is_changed()
+        || vsync.is_changed()
+        || speedrun.is_changed()
+        || quality.is_changed()
+        || render_scale.is_changed()
+        || bloom.is_changed()
+        || accessibility.is_changed()
+        || hud_layout.is_changed()
+        || localization.is_changed()
+        || selected.is_changed()
+    {
         for (row, mut text) in &mut value_query {
-            **text = format_value(row.id, &config, &vsync);
+            **text = format_value(
+                row.id,
+                &config,
+                &vsync,
+                &speedrun,
+                &quality,
+                &render_scale,
+                &bloom,
+                &accessibility,
+                &hud_layout,
+                &localization,
+            );
         }
     }
 
@@ -463,14 +705,61 @@ struct AppSettings {
     vsync_enabled: bool,
     #[serde(default = "default_vsync_multiplier_for_settings")]
     vsync_multiplier: f32,
+    #[serde(default)]
+    language: Language,
+    #[serde(default)]
+    speedrun_timer: bool,
+    #[serde(default)]
+    dynamic_quality: QualityMode,
+    #[serde(default = "default_render_scale")]
+    render_scale: u32,
+    #[serde(default)]
+    sharpen: bool,
+    #[serde(default = "default_bloom")]
+    bloom: f32,
+    #[serde(default)]
+    color_palette: ColorPalette,
+    #[serde(default)]
+    high_contrast: bool,
+    #[serde(default = "default_text_scale")]
+    text_scale: f32,
+    #[serde(default)]
+    captions: bool,
+    #[serde(default)]
+    hud_layout: HudLayout,
 }
 
 fn default_vsync_multiplier_for_settings() -> f32 {
     1.0
 }
 
-/// Loads `OcclusionConfig` and `VsyncConfig` from `settings.ron` on startup.
-pub fn load_settings(mut config: ResMut<OcclusionConfig>, mut vsync: ResMut<VsyncConfig>) {
+fn default_render_scale() -> u32 {
+    100
+}
+
+fn default_bloom() -> f32 {
+    BloomSettings::default().intensity
+}
+
+fn default_text_scale() -> f32 {
+    1.0
+}
+
+/// Loads `OcclusionConfig`, `VsyncConfig`, the UI language, the speedrun
+/// timer option, the dynamic quality mode, the render scale, the bloom
+/// intensity, the HUD layout and the accessibility options from `settings.ron` on startup.
+#[allow(clippy::too_many_arguments)]
+pub fn load_settings(
+    mut config: ResMut<OcclusionConfig>,
+    mut vsync: ResMut<VsyncConfig>,
+    mut speedrun: ResMut<SpeedrunSettings>,
+    mut quality: ResMut<DynamicQualitySettings>,
+    mut render_scale: ResMut<RenderScaleSettings>,
+    mut bloom: ResMut<BloomSettings>,
+    mut accessibility: ResMut<AccessibilitySettings>,
+    mut hud_layout: ResMut<HudLayout>,
+    mut localization: ResMut<Localization>,
+) {
     match std::fs::read_to_string("settings.ron") {
         Ok(contents) => match ron::from_str::<AppSettings>(&contents) {
             Ok(loaded) => {
@@ -478,6 +767,19 @@ pub fn load_settings(mut config: ResMut<OcclusionConfig>, mut vsync: ResMut<Vsyn
                 vsync.vsync_enabled = loaded.vsync_enabled;
                 vsync.vsync_multiplier = loaded.vsync_multiplier;
                 vsync.dirty = true; // Apply loaded values on first frame.
+                localization.set_language(loaded.language);
+                speedrun.show_timer = loaded.speedrun_timer;
+                quality.mode = loaded.dynamic_quality;
+                render_scale.percent = loaded
+                    .render_scale
+                    .clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE);
+                render_scale.sharpen = loaded.sharpen;
+                bloom.intensity = loaded.bloom.clamp(0.0, MAX_BLOOM_INTENSITY);
+                accessibility.palette = loaded.color_palette;
+                accessibility.high_contrast = loaded.high_contrast;
+                accessibility.text_scale = loaded.text_scale.clamp(MIN_TEXT_SCALE, MAX_TEXT_SCALE);
+                accessibility.captions = loaded.captions;
+                *hud_layout = loaded.hud_layout.clamped();
                 info!("[Settings] Loaded settings from settings.ron");
             }
             Err(e) => warn!("[Settings] Failed to parse settings.ron: {e}; using defaults"),
@@ -489,12 +791,37 @@ pub fn load_settings(mut config: ResMut<OcclusionConfig>, mut vsync: ResMut<Vsyn
     }
 }
 
-/// Saves `OcclusionConfig` and `VsyncConfig` to `settings.ron` when leaving the settings screen.
-pub fn save_settings(config: Res<OcclusionConfig>, vsync: Res<VsyncConfig>) {
+/// Saves `OcclusionConfig`, `VsyncConfig`, the UI language, the speedrun
+/// timer option, the dynamic quality mode, the render scale, the bloom
+/// intensity, the HUD layout and the accessibility options to `settings.ron`
+/// when leaving the settings screen.
+#[allow(clippy::too_many_arguments)]
+pub fn save_settings(
+    config: Res<OcclusionConfig>,
+    vsync: Res<VsyncConfig>,
+    speedrun: Res<SpeedrunSettings>,
+    quality: Res<DynamicQualitySettings>,
+    render_scale: Res<RenderScaleSettings>,
+    bloom: Res<BloomSettings>,
+    accessibility: Res<AccessibilitySettings>,
+    hud_layout: Res<HudLayout>,
+    localization: Res<Localization>,
+) {
     let all = AppSettings {
         occlusion: config.clone(),
         vsync_enabled: vsync.vsync_enabled,
         vsync_multiplier: vsync.vsync_multiplier,
+        language: localization.language(),
+        speedrun_timer: speedrun.show_timer,
+        dynamic_quality: quality.mode,
+        render_scale: render_scale.percent,
+        sharpen: render_scale.sharpen,
+        bloom: bloom.intensity,
+        color_palette: accessibility.palette,
+        high_contrast: accessibility.high_contrast,
+        text_scale: accessibility.text_scale,
+        captions: accessibility.captions,
+        hud_layout: *hud_layout,
     };
     match ron::to_string(&all) {
         Ok(contents) => {
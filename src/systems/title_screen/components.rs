@@ -10,10 +10,16 @@ pub struct TitleScreenBackground;
 pub enum MenuButton {
     NewGame,
     Continue,
+    HostCoop,
+    JoinCoop,
     Settings,
     Exit,
 }
 
+/// Line showing the co-op connection or the join address being typed
+#[derive(Component)]
+pub struct NetworkStatusText;
+
 #[derive(Component)]
 pub struct ScalableText {
     pub base_size: f32,
@@ -0,0 +1,324 @@
+//! Title screen backdrop: a showcase map slowly orbited behind the menu.
+//!
+//! Entering the title screen loads the showcase map and meshes it at a coarse
+//! LOD on the async compute pool, then shows it through a 3D camera ordered
+//! before the UI camera. While the scene is up the UI camera stops clearing
+//! the window, so the menu draws over the scene instead of over black. The
+//! static title image stays up until the scene is ready and then
+//! cross-fades into it.
+//!
+//! Starting a game keeps the scene orbiting while the loading screen fades
+//! in over it, so the menu dissolves into the load rather than cutting to
+//! it. The scene is removed once the loading screen covers it, or when the
+//! load finishes first.
+
+use bevy::prelude::*;
+use bevy::tasks::{futures::check_ready, AsyncComputeTaskPool, Task};
+
+use super::components::{TitleScreenBackground, TitleScreenUI};
+use crate::states::GameState;
+use crate::systems::accessibility::AccessibilitySettings;
+use crate::systems::game::map::format::{LightingData, MapData};
+use crate::systems::game::map::spawner::build_scenery_meshes;
+use crate::systems::game::map::{MapLoadProgress, MapLoader};
+use crate::systems::game::render_scale::RenderScaleCamera;
+use crate::systems::loading_screen::LoadingScreenUI;
+use crate::systems::preload::{chunk_standard_material, PreloadedAssets};
+
+/// Map shown behind the title screen.
+pub const SHOWCASE_MAP: &str = "assets/maps/village_64x64.ron";
+
+/// LOD level the showcase map is meshed at; it is only seen from afar.
+const SCENERY_LOD: usize = 2;
+
+/// Orbit speed, in radians per second.
+const ORBIT_SPEED: f32 = 0.05;
+
+/// Seconds the title image takes to cross-fade into the scene.
+const REVEAL_SECONDS: f32 = 1.5;
+
+/// Seconds the loading screen takes to fade in over the scene.
+const COVER_SECONDS: f32 = 0.6;
+
+/// Darkening over the scene behind the menu, so the buttons stay readable.
+const MENU_DIM: f32 = 0.35;
+
+/// Camera path around the showcase map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orbit {
+    pub center: Vec3,
+    pub radius: f32,
+    pub height: f32,
+}
+
+impl Orbit {
+    /// An orbit that keeps the box from `min` to `max` in view.
+    pub fn around(min: Vec3, max: Vec3) -> Self {
+        let radius = ((max - min).xz().length() * 0.6).max(8.0);
+        Self {
+            center: (min + max) / 2.0,
+            radius,
+            height: radius * 0.5,
+        }
+    }
+
+    /// Camera transform `angle` radians around the orbit, looking at its center.
+    pub fn transform(&self, angle: f32) -> Transform {
+        let eye = self.center
+            + Vec3::new(
+                angle.cos() * self.radius,
+                self.height,
+                angle.sin() * self.radius,
+            );
+        Transform::from_translation(eye).looking_at(self.center, Vec3::Y)
+    }
+}
+
+/// Smallest box holding every voxel of `map`, or `None` if it has none.
+pub fn map_bounds(map: &MapData) -> Option<(Vec3, Vec3)> {
+    let mut positions = map
+        .world
+        .voxels
+        .iter()
+        .map(|voxel| Vec3::new(voxel.pos.0 as f32, voxel.pos.1 as f32, voxel.pos.2 as f32));
+    let first = positions.next()?;
+    Some(positions.fold((first, first), |(min, max), pos| {
+        (min.min(pos), max.max(pos))
+    }))
+}
+
+/// The showcase map, meshed and ready to spawn.
+struct Scenery {
+    meshes: Vec<Mesh>,
+    orbit: Orbit,
+    lighting: LightingData,
+}
+
+/// Load and mesh the showcase map, falling back to the built-in default map.
+fn build_scenery(accessibility: AccessibilitySettings) -> Scenery {
+    let map =
+        MapLoader::load_from_file(SHOWCASE_MAP, &mut MapLoadProgress::new()).unwrap_or_else(|e| {
+            warn!("Failed to load title screen map '{}': {}", SHOWCASE_MAP, e);
+            MapLoader::load_default()
+        });
+    let (min, max) = map_bounds(&map).unwrap_or((Vec3::ZERO, Vec3::ZERO));
+    Scenery {
+        meshes: build_scenery_meshes(&map, accessibility.palette, SCENERY_LOD),
+        orbit: Orbit::around(min, max),
+        lighting: map.lighting,
+    }
+}
+
+/// State of the title screen backdrop; present from the title screen until
+/// the scene is gone.
+#[derive(Resource)]
+pub struct TitleFlythrough {
+    task: Option<Task<Scenery>>,
+    /// Path of the camera once the scene is spawned
+    pub orbit: Option<Orbit>,
+    pub angle: f32,
+    /// Cross-fade from the title image, 0 to 1
+    pub reveal: f32,
+    /// Fade of the loading screen over the scene, 0 to 1
+    pub cover: f32,
+}
+
+/// Every entity of the backdrop scene.
+#[derive(Component)]
+pub struct TitleScenery;
+
+/// Camera orbiting the backdrop scene.
+#[derive(Component)]
+pub struct TitleSceneryCamera;
+
+/// Start building the scene, unless it is already up (such as when coming
+/// back from the settings screen).
+pub fn start_flythrough(
+    mut commands: Commands,
+    flythrough: Option<Res<TitleFlythrough>>,
+    accessibility: Res<AccessibilitySettings>,
+) {
+    if flythrough.is_some() {
+        return;
+    }
+    let accessibility = *accessibility;
+    let task = AsyncComputeTaskPool::get().spawn(async move { build_scenery(accessibility) });
+    commands.insert_resource(TitleFlythrough {
+        task: Some(task),
+        orbit: None,
+        angle: 0.0,
+        reveal: 0.0,
+        cover: 0.0,
+    });
+}
+
+/// Spawn the scene once it is built, and stop the UI camera clearing the
+/// window over it.
+pub fn spawn_scenery(
+    mut commands: Commands,
+    mut flythrough: ResMut<TitleFlythrough>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    preloaded: Option<Res<PreloadedAssets>>,
+    mut ui_cameras: Query<&mut Camera, (With<Camera2d>, Without<RenderScaleCamera>)>,
+) {
+    let Some(task) = flythrough.task.as_mut() else {
+        return;
+    };
+    let Some(scenery) = check_ready(task) else {
+        return;
+    };
+    flythrough.task = None;
+
+    // The game's own chunk material, so the first map finds it ready
+    let material = match &preloaded {
+        Some(preloaded) => preloaded.chunk_material.clone(),
+        None => materials.add(chunk_standard_material()),
+    };
+    for mesh in scenery.meshes {
+        commands.spawn((
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(material.clone()),
+            Transform::default(),
+            TitleScenery,
+        ));
+    }
+
+    if let Some(light) = &scenery.lighting.directional_light {
+        let (dx, dy, dz) = light.direction;
+        commands.spawn((
+            DirectionalLight {
+                illuminance: light.illuminance,
+                color: Color::srgb(light.color.0, light.color.1, light.color.2),
+                ..default()
+            },
+            Transform::from_rotation(Quat::from_rotation_arc(
+                Vec3::NEG_Z,
+                Vec3::new(dx, dy, dz).normalize(),
+            )),
+            TitleScenery,
+        ));
+    }
+    commands.insert_resource(GlobalAmbientLight {
+        color: Color::WHITE,
+        brightness: scenery.lighting.ambient_intensity * 1000.0,
+        affects_lightmapped_meshes: true,
+    });
+
+    commands.spawn((
+        Camera3d::default(),
+        // Before the UI camera, which draws the menu over it
+        Camera {
+            order: -1,
+            ..default()
+        },
+        scenery.orbit.transform(flythrough.angle),
+        TitleScenery,
+        TitleSceneryCamera,
+    ));
+    for mut camera in &mut ui_cameras {
+        camera.clear_color = ClearColorConfig::None;
+    }
+    flythrough.orbit = Some(scenery.orbit);
+}
+
+/// Move the camera along its orbit.
+pub fn orbit_camera(
+    time: Res<Time>,
+    mut flythrough: ResMut<TitleFlythrough>,
+    mut cameras: Query<&mut Transform, With<TitleSceneryCamera>>,
+) {
+    let Some(orbit) = flythrough.orbit else {
+        return;
+    };
+    flythrough.angle = (flythrough.angle + ORBIT_SPEED * time.delta_secs()) % std::f32::consts::TAU;
+    for mut transform in &mut cameras {
+        *transform = orbit.transform(flythrough.angle);
+    }
+}
+
+/// Cross-fade the title image into the scene and dim the scene behind the
+/// menu.
+pub fn reveal_scenery(
+    time: Res<Time>,
+    mut flythrough: ResMut<TitleFlythrough>,
+    mut images: Query<(&mut BackgroundColor, &mut ImageNode), With<TitleScreenBackground>>,
+    mut roots: Query<&mut BackgroundColor, (With<TitleScreenUI>, Without<TitleScreenBackground>)>,
+) {
+    if flythrough.orbit.is_none() {
+        return;
+    }
+    flythrough.reveal = (flythrough.reveal + time.delta_secs() / REVEAL_SECONDS).min(1.0);
+    let reveal = flythrough.reveal;
+    for (mut background, mut image) in &mut images {
+        // The title screen's own fade-in may still be raising the background
+        let alpha = background.0.alpha().min(1.0 - reveal);
+        background.0.set_alpha(alpha);
+        image.color.set_alpha(1.0 - reveal);
+    }
+    for mut root in &mut roots {
+        root.0 = Color::srgba(0.0, 0.0, 0.0, MENU_DIM * reveal);
+    }
+}
+
+/// Fade the loading screen in over the scene, removing the scene once it is
+/// covered.
+pub fn cover_scenery(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flythrough: ResMut<TitleFlythrough>,
+    mut loading_screens: Query<&mut BackgroundColor, With<LoadingScreenUI>>,
+) {
+    if flythrough.orbit.is_none() {
+        return;
+    }
+    flythrough.cover = (flythrough.cover + time.delta_secs() / COVER_SECONDS).min(1.0);
+    for mut background in &mut loading_screens {
+        background.0.set_alpha(flythrough.cover);
+    }
+    if flythrough.cover >= 1.0 {
+        commands.run_system_cached(remove_flythrough);
+    }
+}
+
+/// Despawn the scene and let the UI camera clear the window again.
+pub fn remove_flythrough(
+    mut commands: Commands,
+    scenery: Query<Entity, With<TitleScenery>>,
+    mut ui_cameras: Query<&mut Camera, (With<Camera2d>, Without<RenderScaleCamera>)>,
+) {
+    for entity in &scenery {
+        commands.entity(entity).despawn();
+    }
+    for mut camera in &mut ui_cameras {
+        camera.clear_color = ClearColorConfig::Default;
+    }
+    commands.remove_resource::<TitleFlythrough>();
+}
+
+/// Plugin that runs the title screen backdrop.
+pub struct TitleFlythroughPlugin;
+
+impl Plugin for TitleFlythroughPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::TitleScreen), start_flythrough)
+            .add_systems(
+                Update,
+                (
+                    // Not worth spawning once a game is already loading
+                    spawn_scenery.run_if(not(in_state(GameState::LoadingMap))),
+                    orbit_camera,
+                    reveal_scenery
+                        .after(super::systems::fade_in_title_screen)
+                        .run_if(in_state(GameState::TitleScreen)),
+                    cover_scenery.run_if(in_state(GameState::LoadingMap)),
+                )
+                    .chain()
+                    .run_if(resource_exists::<TitleFlythrough>),
+            )
+            .add_systems(OnExit(GameState::LoadingMap), remove_flythrough);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,48 @@
+use super::*;
+use crate::systems::game::map::format::VoxelData;
+
+#[test]
+fn orbit_looks_at_the_center_from_above() {
+    let orbit = Orbit::around(Vec3::ZERO, Vec3::new(40.0, 6.0, 30.0));
+    assert_eq!(orbit.center, Vec3::new(20.0, 3.0, 15.0));
+    assert_eq!(orbit.radius, 30.0);
+
+    for angle in [0.0, 1.0, 4.0] {
+        let transform = orbit.transform(angle);
+        let offset = transform.translation - orbit.center;
+        assert!((offset.xz().length() - orbit.radius).abs() < 1e-4);
+        assert!(offset.y > 0.0);
+        let toward_center = (orbit.center - transform.translation).normalize();
+        assert!(transform.forward().dot(toward_center) > 0.999);
+    }
+}
+
+#[test]
+fn small_maps_keep_a_minimum_orbit() {
+    let orbit = Orbit::around(Vec3::ZERO, Vec3::ONE);
+    assert_eq!(orbit.radius, 8.0);
+}
+
+#[test]
+fn bounds_cover_every_voxel() {
+    let mut map = MapData::default_map();
+    let template = map.world.voxels[0].clone();
+    map.world.voxels = vec![
+        VoxelData {
+            pos: (-3, 0, 2),
+            ..template.clone()
+        },
+        VoxelData {
+            pos: (5, 4, -1),
+            ..template
+        },
+    ]
+    .into();
+    assert_eq!(
+        map_bounds(&map),
+        Some((Vec3::new(-3.0, 0.0, -1.0), Vec3::new(5.0, 4.0, 2.0)))
+    );
+
+    map.world.voxels.clear();
+    assert_eq!(map_bounds(&map), None);
+}
@@ -1,3 +1,4 @@
 mod components;
+pub mod flythrough;
 mod resources;
 pub mod systems;
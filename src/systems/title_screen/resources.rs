@@ -1,3 +1,4 @@
+use crate::systems::game::network::DEFAULT_PORT;
 use bevy::prelude::*;
 
 #[derive(Resource)]
@@ -29,7 +30,21 @@ impl Default for SelectedMenuIndex {
     fn default() -> Self {
         Self {
             index: 0,
-            total: 4, // NewGame, Continue, Settings, Exit
+            total: 6, // NewGame, Continue, HostCoop, JoinCoop, Settings, Exit
+        }
+    }
+}
+
+/// Host address being typed after choosing Join Co-op
+#[derive(Resource)]
+pub struct JoinPrompt {
+    pub address: String,
+}
+
+impl Default for JoinPrompt {
+    fn default() -> Self {
+        Self {
+            address: format!("127.0.0.1:{}", DEFAULT_PORT),
         }
     }
 }
@@ -1,16 +1,25 @@
-use super::components::{MenuButton, ScalableText, TitleScreenBackground, TitleScreenUI};
-use super::resources::{SelectedMenuIndex, TitleScreenFadeTimer};
+use super::components::{
+    MenuButton, NetworkStatusText, ScalableText, TitleScreenBackground, TitleScreenUI,
+};
+use super::resources::{JoinPrompt, SelectedMenuIndex, TitleScreenFadeTimer};
 use crate::states::GameState;
+use crate::systems::accessibility::{AccessibilitySettings, MenuTheme};
 use crate::systems::game::gamepad::{get_menu_gamepad_input, ActiveGamepad, GamepadSettings};
+use crate::systems::game::network::{NetworkStatus, StartNetworkSession};
+use crate::systems::localization::Localization;
 use crate::systems::settings::resources::SettingsOrigin;
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::prelude::*;
 use bevy::window::WindowResized;
 
-const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.15, 0.0);
-const HOVERED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.3);
-const PRESSED_BUTTON: Color = Color::srgba(1.0, 0.8, 0.2, 0.5);
+pub fn setup_title_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    accessibility: Res<AccessibilitySettings>,
+) {
+    let theme = MenuTheme::new(accessibility.high_contrast);
 
-pub fn setup_title_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
     // Insert fade timer and menu selection
     commands.insert_resource(TitleScreenFadeTimer::new());
     commands.insert_resource(SelectedMenuIndex::default());
@@ -26,6 +35,8 @@ pub fn setup_title_screen(mut commands: Commands, asset_server: Res<AssetServer>
                 flex_direction: FlexDirection::Column,
                 ..default()
             },
+            // Dims the backdrop scene behind the menu once it is up
+            BackgroundColor(Color::NONE),
             TitleScreenUI,
         ))
         .with_children(|parent| {
@@ -49,7 +60,7 @@ pub fn setup_title_screen(mut commands: Commands, asset_server: Res<AssetServer>
                     font_size: 80.0,
                     ..default()
                 },
-                TextColor(Color::srgba(0.9, 0.9, 0.9, 0.0)),
+                TextColor(theme.text.with_alpha(0.0)),
                 Node {
                     margin: UiRect::all(Val::Vw(5.0)),
                     ..default()
@@ -69,15 +80,69 @@ pub fn setup_title_screen(mut commands: Commands, asset_server: Res<AssetServer>
                     BackgroundColor(Color::NONE),
                 ))
                 .with_children(|parent| {
-                    create_menu_button(parent, "New Game", MenuButton::NewGame);
-                    create_menu_button(parent, "Continue", MenuButton::Continue);
-                    create_menu_button(parent, "Settings", MenuButton::Settings);
-                    create_menu_button(parent, "Exit", MenuButton::Exit);
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        localization.get("title.new_game"),
+                        MenuButton::NewGame,
+                    );
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        localization.get("title.continue"),
+                        MenuButton::Continue,
+                    );
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        localization.get("title.host_coop"),
+                        MenuButton::HostCoop,
+                    );
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        localization.get("title.join_coop"),
+                        MenuButton::JoinCoop,
+                    );
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        localization.get("title.settings"),
+                        MenuButton::Settings,
+                    );
+                    create_menu_button(
+                        parent,
+                        &theme,
+                        localization.get("title.exit"),
+                        MenuButton::Exit,
+                    );
                 });
+
+            // Co-op status and join address
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(theme.text),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    margin: UiRect::top(Val::Vh(3.0)),
+                    ..default()
+                },
+                ScalableText::new(22.0, 1.0),
+                NetworkStatusText,
+            ));
         });
 }
 
-fn create_menu_button(parent: &mut ChildSpawnerCommands<'_>, text: &str, button_type: MenuButton) {
+fn create_menu_button(
+    parent: &mut ChildSpawnerCommands<'_>,
+    theme: &MenuTheme,
+    text: &str,
+    button_type: MenuButton,
+) {
     parent
         .spawn((
             Button,
@@ -88,7 +153,7 @@ fn create_menu_button(parent: &mut ChildSpawnerCommands<'_>, text: &str, button_
                 align_items: AlignItems::Center,
                 ..default()
             },
-            BackgroundColor(Color::NONE),
+            BackgroundColor(theme.button),
             button_type,
         ))
         .with_children(|parent| {
@@ -98,7 +163,7 @@ fn create_menu_button(parent: &mut ChildSpawnerCommands<'_>, text: &str, button_
                     font_size: 30.0,
                     ..default()
                 },
-                TextColor(Color::srgba(0.9, 0.9, 0.9, 0.0)),
+                TextColor(theme.text.with_alpha(0.0)),
                 ScalableText::new(30.0, 1.0),
             ));
         });
@@ -112,12 +177,15 @@ pub fn button_interaction(
     >,
     mut selected: ResMut<SelectedMenuIndex>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut network: MessageWriter<StartNetworkSession>,
     mut exit: MessageWriter<AppExit>,
+    accessibility: Res<AccessibilitySettings>,
 ) {
+    let theme = MenuTheme::new(accessibility.high_contrast);
     for (interaction, mut color, button) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
-                *color = PRESSED_BUTTON.into();
+                *color = theme.button_pressed.into();
                 match button {
                     MenuButton::NewGame => {
                         info!("Starting new game...");
@@ -126,6 +194,14 @@ pub fn button_interaction(
                     MenuButton::Continue => {
                         info!("Continue not implemented yet");
                     }
+                    MenuButton::HostCoop => {
+                        info!("Hosting co-op game...");
+                        network.write(StartNetworkSession::Host);
+                        next_state.set(GameState::LoadingMap);
+                    }
+                    MenuButton::JoinCoop => {
+                        commands.init_resource::<JoinPrompt>();
+                    }
                     MenuButton::Settings => {
                         info!("Opening settings...");
                         commands.insert_resource(SettingsOrigin::TitleScreen);
@@ -138,18 +214,20 @@ pub fn button_interaction(
                 }
             }
             Interaction::Hovered => {
-                *color = HOVERED_BUTTON.into();
+                *color = theme.button_hovered.into();
                 // Update selected index when hovering
                 let button_index = match button {
                     MenuButton::NewGame => 0,
                     MenuButton::Continue => 1,
-                    MenuButton::Settings => 2,
-                    MenuButton::Exit => 3,
+                    MenuButton::HostCoop => 2,
+                    MenuButton::JoinCoop => 3,
+                    MenuButton::Settings => 4,
+                    MenuButton::Exit => 5,
                 };
                 selected.index = button_index;
             }
             Interaction::None => {
-                *color = NORMAL_BUTTON.into();
+                *color = theme.button.into();
             }
         }
     }
@@ -208,6 +286,7 @@ pub fn keyboard_navigation(
     settings: Res<GamepadSettings>,
     mut selected: ResMut<SelectedMenuIndex>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut network: MessageWriter<StartNetworkSession>,
     mut exit: MessageWriter<AppExit>,
 ) {
     // Get gamepad input
@@ -235,11 +314,19 @@ pub fn keyboard_navigation(
                 info!("Continue not implemented yet");
             }
             2 => {
+                info!("Hosting co-op game...");
+                network.write(StartNetworkSession::Host);
+                next_state.set(GameState::LoadingMap);
+            }
+            3 => {
+                commands.init_resource::<JoinPrompt>();
+            }
+            4 => {
                 info!("Opening settings...");
                 commands.insert_resource(SettingsOrigin::TitleScreen);
                 next_state.set(GameState::Settings);
             }
-            3 => {
+            5 => {
                 info!("Exiting game...");
                 exit.write(AppExit::Success);
             }
@@ -250,13 +337,17 @@ pub fn keyboard_navigation(
 
 pub fn update_selected_button_visual(
     selected: Res<SelectedMenuIndex>,
+    accessibility: Res<AccessibilitySettings>,
     mut button_query: Query<(&MenuButton, &mut BackgroundColor, &Interaction)>,
 ) {
+    let theme = MenuTheme::new(accessibility.high_contrast);
     let buttons: Vec<(usize, &MenuButton)> = vec![
         (0, &MenuButton::NewGame),
         (1, &MenuButton::Continue),
-        (2, &MenuButton::Settings),
-        (3, &MenuButton::Exit),
+        (2, &MenuButton::HostCoop),
+        (3, &MenuButton::JoinCoop),
+        (4, &MenuButton::Settings),
+        (5, &MenuButton::Exit),
     ];
 
     for (button, mut bg_color, interaction) in &mut button_query {
@@ -268,6 +359,8 @@ pub fn update_selected_button_visual(
                     (button, *b),
                     (MenuButton::NewGame, MenuButton::NewGame)
                         | (MenuButton::Continue, MenuButton::Continue)
+                        | (MenuButton::HostCoop, MenuButton::HostCoop)
+                        | (MenuButton::JoinCoop, MenuButton::JoinCoop)
                         | (MenuButton::Settings, MenuButton::Settings)
                         | (MenuButton::Exit, MenuButton::Exit)
                 )
@@ -278,9 +371,9 @@ pub fn update_selected_button_visual(
             // Only apply keyboard selection color if not being hovered/pressed by mouse
             if *interaction == Interaction::None {
                 if idx == selected.index {
-                    *bg_color = HOVERED_BUTTON.into();
+                    *bg_color = theme.button_hovered.into();
                 } else {
-                    *bg_color = NORMAL_BUTTON.into();
+                    *bg_color = theme.button.into();
                 }
             }
         }
@@ -293,4 +386,65 @@ pub fn cleanup_title_screen(mut commands: Commands, query: Query<Entity, With<Ti
     }
     commands.remove_resource::<TitleScreenFadeTimer>();
     commands.remove_resource::<SelectedMenuIndex>();
+    commands.remove_resource::<JoinPrompt>();
+}
+
+/// Run condition: menu navigation is off while a join address is typed
+pub fn join_prompt_closed(prompt: Option<Res<JoinPrompt>>) -> bool {
+    prompt.is_none()
+}
+
+/// Type the host address after choosing Join Co-op; Enter joins, Escape
+/// goes back to the menu.
+pub fn edit_join_prompt(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut typed: MessageReader<KeyboardInput>,
+    prompt: Option<ResMut<JoinPrompt>>,
+    mut network: MessageWriter<StartNetworkSession>,
+) {
+    let Some(mut prompt) = prompt else {
+        typed.clear();
+        return;
+    };
+    for event in typed.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => {
+                prompt.address.extend(text.chars().filter(|c| {
+                    c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '-' | '[' | ']')
+                }))
+            }
+            Key::Backspace => {
+                prompt.address.pop();
+            }
+            _ => {}
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        commands.remove_resource::<JoinPrompt>();
+    } else if keyboard_input.just_pressed(KeyCode::Enter) && !prompt.address.is_empty() {
+        info!("Joining co-op game at {}...", prompt.address);
+        network.write(StartNetworkSession::Join(prompt.address.clone()));
+        commands.remove_resource::<JoinPrompt>();
+    }
+}
+
+/// Show the join address being typed, or else the co-op status.
+pub fn update_network_status_text(
+    status: Res<NetworkStatus>,
+    prompt: Option<Res<JoinPrompt>>,
+    localization: Res<Localization>,
+    mut text: Single<&mut Text, With<NetworkStatusText>>,
+) {
+    let line = match prompt {
+        Some(prompt) => localization.format("title.join_prompt", &[("address", &prompt.address)]),
+        None => status.describe(&localization),
+    };
+    if text.0 != line {
+        text.0 = line;
+    }
 }
@@ -0,0 +1,179 @@
+//! Scripted editor sessions played back through the input layer.
+//!
+//! A recording is built here the way `--record-input` would write one,
+//! saved, loaded and played back into a windowless app running the editor's
+//! keyboard, placement, shortcut, undo and save systems. The map the editor
+//! saves is then read back and checked, so the whole path from raw key and
+//! mouse events to the file on disk is covered.
+
+use adrakestory::editor::bookmarks::BookmarkEvent;
+use adrakestory::editor::clipboard::{CopySelectionEvent, PasteClipboardEvent};
+use adrakestory::editor::cursor::{
+    handle_keyboard_cursor_movement, toggle_keyboard_edit_mode, CursorState,
+};
+use adrakestory::editor::file_io::handle_save_map;
+use adrakestory::editor::input_recording::{InputRecording, InputRecordingPlugin, RecordedInput};
+use adrakestory::editor::shortcuts::{handle_global_shortcuts, handle_undo};
+use adrakestory::editor::state::EditorUIState;
+use adrakestory::editor::tools::{handle_voxel_placement, ActiveTransform, VoxelDragState};
+use adrakestory::editor::ui::dialogs::{AppExitEvent, MapDataChangedEvent};
+use adrakestory::editor::{
+    DocumentEvent, EditorHistory, EditorState, KeyboardEditMode, MapSaveTask, RedoEvent,
+    RenderMapEvent, SaveMapAsEvent, SaveMapEvent, UndoEvent,
+};
+use adrakestory::systems::game::map::format::MapData;
+use adrakestory::systems::game::map::loader::MapLoader;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{EguiUserTextures, PrimaryEguiContext};
+use std::path::Path;
+use std::time::Duration;
+
+/// Frame length of the headless app
+const FRAME: Duration = Duration::from_millis(20);
+
+/// Gap between scripted events, a few frames
+const STEP: f32 = 0.1;
+
+#[cfg(target_os = "macos")]
+const MODIFIER: KeyCode = KeyCode::SuperLeft;
+#[cfg(not(target_os = "macos"))]
+const MODIFIER: KeyCode = KeyCode::ControlLeft;
+
+/// Builds a recording one input at a time, `STEP` seconds apart.
+#[derive(Default)]
+struct Script {
+    recording: InputRecording,
+    time: f32,
+}
+
+impl Script {
+    fn input(&mut self, input: RecordedInput) -> &mut Self {
+        self.time += STEP;
+        self.recording.push(self.time, input);
+        self
+    }
+
+    fn key(&mut self, key: KeyCode, pressed: bool) -> &mut Self {
+        self.input(RecordedInput::Key {
+            key,
+            pressed,
+            logical_key: None,
+            text: None,
+        })
+    }
+
+    fn tap(&mut self, key: KeyCode) -> &mut Self {
+        self.key(key, true).key(key, false)
+    }
+
+    fn shortcut(&mut self, key: KeyCode) -> &mut Self {
+        self.key(MODIFIER, true).tap(key).key(MODIFIER, false)
+    }
+
+    fn click(&mut self) -> &mut Self {
+        for pressed in [true, false] {
+            self.input(RecordedInput::MouseButton {
+                button: MouseButton::Left,
+                pressed,
+            });
+        }
+        self
+    }
+}
+
+/// Windowless app running the editor systems the script drives, with
+/// `map` open as `path`.
+fn editor_app(map: MapData, path: &Path, recording: InputRecording) -> App {
+    let editor_state = EditorState {
+        current_map: map,
+        file_path: Some(path.to_path_buf()),
+        ..default()
+    };
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin))
+        .add_plugins(InputRecordingPlugin {
+            playback: Some(recording),
+            ..default()
+        })
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+        .insert_resource(editor_state)
+        .init_resource::<CursorState>()
+        .init_resource::<EditorHistory>()
+        .init_resource::<EditorUIState>()
+        .init_resource::<KeyboardEditMode>()
+        .init_resource::<ActiveTransform>()
+        .init_resource::<VoxelDragState>()
+        .init_resource::<MapSaveTask>()
+        .init_resource::<EguiUserTextures>()
+        .add_message::<SaveMapEvent>()
+        .add_message::<SaveMapAsEvent>()
+        .add_message::<UndoEvent>()
+        .add_message::<RedoEvent>()
+        .add_message::<CopySelectionEvent>()
+        .add_message::<PasteClipboardEvent>()
+        .add_message::<DocumentEvent>()
+        .add_message::<BookmarkEvent>()
+        .add_message::<RenderMapEvent>()
+        .add_message::<MapDataChangedEvent>()
+        .add_message::<AppExitEvent>()
+        .add_systems(
+            Update,
+            (
+                toggle_keyboard_edit_mode,
+                handle_keyboard_cursor_movement,
+                handle_voxel_placement,
+                handle_global_shortcuts,
+                handle_undo,
+                handle_save_map,
+            )
+                .chain(),
+        );
+    // Placement reads the cursor from the primary window, and the editor's
+    // systems ask egui whether it wants the input
+    app.world_mut().spawn((Window::default(), PrimaryWindow));
+    app.world_mut().spawn(PrimaryEguiContext);
+    app
+}
+
+#[test]
+fn scripted_session_places_undoes_and_saves() {
+    let mut script = Script::default();
+    // Keyboard edit mode, then ten voxels in a row along +X
+    script.tap(KeyCode::KeyI);
+    for _ in 0..10 {
+        script.tap(KeyCode::ArrowRight).click();
+    }
+    for _ in 0..5 {
+        script.shortcut(KeyCode::KeyZ);
+    }
+    script.shortcut(KeyCode::KeyS);
+
+    // Go through the file format like a recording made by the editor
+    let folder = tempfile::tempdir().unwrap();
+    let recording_path = folder.path().join("place_undo_save.ron");
+    script.recording.save(&recording_path).unwrap();
+    let recording = InputRecording::load(&recording_path).unwrap();
+
+    let map_path = folder.path().join("scripted.ron");
+    let mut app = editor_app(MapData::empty_map(), &map_path, recording);
+    let frames = (script.time / FRAME.as_secs_f32()).ceil() as usize + 5;
+    for _ in 0..frames {
+        app.update();
+    }
+    for result in app.world_mut().resource_mut::<MapSaveTask>().wait() {
+        result.expect("scripted save should succeed");
+    }
+
+    let saved = MapLoader::read_unvalidated(&map_path).expect("saved map should load");
+    let mut positions: Vec<(i32, i32, i32)> =
+        saved.world.voxels.iter().map(|voxel| voxel.pos).collect();
+    positions.sort();
+    // Each voxel goes on top of the cursor; the last five were undone
+    let expected: Vec<(i32, i32, i32)> = (1..=5).map(|x| (x, 1, 0)).collect();
+    assert_eq!(positions, expected);
+    assert_eq!(app.world().resource::<EditorHistory>().redo_count(), 5);
+}
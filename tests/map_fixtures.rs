@@ -0,0 +1,161 @@
+//! Golden-value tests for headless map loading and spawning.
+//!
+//! Each fixture in `tests/fixtures/maps/` is loaded through `MapLoader` and
+//! spawned with `spawn_map_system` in a windowless app. Chunk counts, quad
+//! counts and spawned entities are compared against golden values, so a
+//! meshing refactor that changes the output fails here. When a change to the
+//! output is intentional, update the golden values in the same commit.
+
+use adrakestory::systems::accessibility::AccessibilitySettings;
+use adrakestory::systems::game::components::{LightSource, Npc, Player, SubVoxel};
+use adrakestory::systems::game::map::{
+    spawn_map_system, ChunkLOD, LoadProgress, LoadedMapData, MapLoadProgress, MapLoader, VoxelChunk,
+};
+use adrakestory::systems::game::occlusion::{OcclusionConfig, OcclusionMaterial};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Expected spawn results for a fixture map.
+#[derive(Debug, PartialEq)]
+struct Golden {
+    voxels: usize,
+    chunks: usize,
+    /// Quads across all chunks at full detail (LOD 0)
+    quads: usize,
+    /// Collision sub-voxel entities
+    sub_voxels: usize,
+    players: usize,
+    npcs: usize,
+    lights: usize,
+}
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/maps")
+        .join(name)
+}
+
+/// Load and spawn a fixture headlessly; returns the app and the progress log.
+fn spawn_fixture(name: &str) -> (App, usize, Vec<LoadProgress>) {
+    let mut progress = MapLoadProgress::new();
+    let map = MapLoader::load_from_file(fixture_path(name), &mut progress)
+        .unwrap_or_else(|e| panic!("fixture {name} failed to load: {e}"));
+    let voxels = map.world.voxels.len();
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_asset::<OcclusionMaterial>()
+        .init_asset::<Scene>()
+        .insert_resource(OcclusionConfig::default())
+        .insert_resource(AccessibilitySettings::default())
+        .insert_resource(LoadedMapData { map })
+        .insert_resource(progress);
+    app.world_mut()
+        .run_system_once(spawn_map_system)
+        .expect("spawn_map_system should run");
+
+    let events = app.world().resource::<MapLoadProgress>().events.clone();
+    (app, voxels, events)
+}
+
+fn count<C: Component>(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), With<C>>()
+        .iter(app.world())
+        .count()
+}
+
+fn measure(name: &str) -> Golden {
+    let (mut app, voxels, _) = spawn_fixture(name);
+
+    let lod0_meshes: Vec<Handle<Mesh>> = app
+        .world_mut()
+        .query_filtered::<&ChunkLOD, With<VoxelChunk>>()
+        .iter(app.world())
+        .map(|lod| lod.lod_meshes[0].clone())
+        .collect();
+    let meshes = app.world().resource::<Assets<Mesh>>();
+    let quads = lod0_meshes
+        .iter()
+        .map(|handle| {
+            let mesh = meshes.get(handle).expect("chunk mesh should be stored");
+            mesh.indices().map_or(0, |indices| indices.len() / 6)
+        })
+        .sum();
+
+    Golden {
+        voxels,
+        chunks: lod0_meshes.len(),
+        quads,
+        sub_voxels: count::<SubVoxel>(&mut app),
+        players: count::<Player>(&mut app),
+        npcs: count::<Npc>(&mut app),
+        lights: count::<LightSource>(&mut app),
+    }
+}
+
+#[test]
+fn chunk_boundaries_matches_golden() {
+    assert_eq!(
+        measure("chunk_boundaries.ron"),
+        Golden {
+            voxels: 35,
+            // Voxels are centered on integer coordinates, so the cells at 0
+            // straddle the chunk boundary and spill into chunks at -1.
+            chunks: 16,
+            quads: 25856,
+            sub_voxels: 17920,
+            players: 1,
+            npcs: 0,
+            lights: 0,
+        }
+    );
+}
+
+#[test]
+fn patterns_matches_golden() {
+    assert_eq!(
+        measure("patterns.ron"),
+        Golden {
+            voxels: 24,
+            chunks: 8,
+            quads: 11784,
+            sub_voxels: 9016,
+            players: 1,
+            npcs: 1,
+            lights: 1,
+        }
+    );
+}
+
+#[test]
+fn spawning_is_deterministic() {
+    assert_eq!(measure("patterns.ron"), measure("patterns.ron"));
+}
+
+#[test]
+fn progress_reports_every_stage_in_order() {
+    let (_, _, events) = spawn_fixture("patterns.ron");
+
+    assert_eq!(events.first(), Some(&LoadProgress::Started));
+    assert_eq!(events.last(), Some(&LoadProgress::Complete));
+    for stage in [
+        LoadProgress::LoadingFile(1.0),
+        LoadProgress::ParsingData(1.0),
+        LoadProgress::ValidatingMap(1.0),
+        LoadProgress::SpawningVoxels(1.0),
+        LoadProgress::SpawningEntities(1.0),
+        LoadProgress::Finalizing(1.0),
+    ] {
+        assert!(events.contains(&stage), "missing {stage:?}");
+    }
+    assert!(
+        events
+            .windows(2)
+            .all(|pair| pair[0].percentage() <= pair[1].percentage() + f32::EPSILON),
+        "progress went backwards: {events:?}"
+    );
+}